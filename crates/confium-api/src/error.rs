@@ -52,6 +52,21 @@ pub enum ErrorCode {
     /// `Display` string for logging, but the loader has no typed
     /// recovery path.
     PLUGIN_GENERIC = 27,
+
+    /// The plugin cannot operate on the supplied key handle (unknown
+    /// backend, wrong key type, or the operation is not permitted by
+    /// the backend's key policy).
+    KEY_HANDLE_UNSUPPORTED = 51,
+
+    /// The host's load-time policy refused the plugin binary. Never
+    /// returned by a plugin; reserved so the two code tables stay in
+    /// step.
+    PLUGIN_VERIFICATION_FAILED = 28,
+
+    /// The host lost the subprocess a sandboxed plugin runs in. Never
+    /// returned by a plugin; reserved like
+    /// [`ErrorCode::PLUGIN_VERIFICATION_FAILED`].
+    PLUGIN_SANDBOX_FAILED = 29,
 }
 
 impl ErrorCode {
@@ -134,6 +149,9 @@ impl PluginError {
             ErrorCode::PLUGIN_INTERNAL_ERROR => "plugin_internal_error",
             ErrorCode::UNSUPPORTED_ALGORITHM => "unsupported_algorithm",
             ErrorCode::PLUGIN_GENERIC => "plugin_generic",
+            ErrorCode::KEY_HANDLE_UNSUPPORTED => "key_handle_unsupported",
+            ErrorCode::PLUGIN_VERIFICATION_FAILED => "plugin_verification_failed",
+            ErrorCode::PLUGIN_SANDBOX_FAILED => "plugin_sandbox_failed",
         }
     }
 }
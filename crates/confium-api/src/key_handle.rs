@@ -0,0 +1,183 @@
+//! Key handles for the handle-based signature and KEM extensions.
+//!
+//! Keys that live in a PKCS#11 token, a TPM, an OpenPGP card or a cloud
+//! KMS cannot be exported as bytes. Version 1 of the `signature` and
+//! `kem` wire protocols adds entry points that take a reference to such
+//! a key instead of its encoding:
+//!
+//! ```c
+//! typedef struct CFMKeyHandle {
+//!     const char* backend;  // confium-store backend name: "pkcs11", "tpm", "aws-kms", ...
+//!     const char* key_id;   // backend-specific locator (PKCS#11 URI, KMS key ARN, ...)
+//!     void*       native;   // backend-owned object handle, or NULL
+//! } CFMKeyHandle;
+//! ```
+//!
+//! `backend` and `key_id` are the pair a `confium-store` keystore
+//! indexes the key under; `native` is whatever the keystore's
+//! `get_secret` returned for it. The plugin never receives key bytes —
+//! it resolves the handle against its own connection to the backend
+//! (or uses `native` directly when it shares the backend's process
+//! state) and performs the private-key operation there.
+//!
+//! Before creating a handle-based signer or decapsulator the loader
+//! asks the plugin which operations it can perform on a given handle
+//! (`cfmp_sig_handle_capabilities` / `cfmp_kem_handle_capabilities`)
+//! and skips providers that answer with an empty
+//! [`HandleCapabilities`].
+//!
+//! The wire shape mirrors `confium_core::key_handle::CFMKeyHandle`
+//! exactly; the two crates do not depend on each other.
+
+use std::ffi::{CStr, c_void};
+use std::os::raw::c_char;
+
+/// C layout of a key handle, as passed across the FFI. Both strings
+/// are NUL-terminated UTF-8 owned by the caller for the duration of the
+/// call.
+#[repr(C)]
+#[derive(Debug)]
+pub struct CFMKeyHandle {
+    pub backend: *const c_char,
+    pub key_id: *const c_char,
+    pub native: *mut c_void,
+}
+
+/// Borrowed, validated view over a [`CFMKeyHandle`].
+#[derive(Debug, Clone, Copy)]
+pub struct KeyHandle<'a> {
+    backend: &'a str,
+    key_id: &'a str,
+    native: *mut c_void,
+}
+
+impl<'a> KeyHandle<'a> {
+    /// Construct a view directly. Used by tests and by plugins that
+    /// synthesise handles internally.
+    pub fn new(backend: &'a str, key_id: &'a str, native: *mut c_void) -> Self {
+        Self {
+            backend,
+            key_id,
+            native,
+        }
+    }
+
+    /// Borrow the handle the loader passed in. Returns `None` if `ptr`
+    /// or either string is NULL, or a string is not UTF-8.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be NULL or point to a [`CFMKeyHandle`] whose strings
+    /// are NUL-terminated and valid for `'a`.
+    pub unsafe fn from_raw(ptr: *const CFMKeyHandle) -> Option<Self> {
+        // SAFETY: upheld by the caller.
+        let raw = unsafe { ptr.as_ref()? };
+        if raw.backend.is_null() || raw.key_id.is_null() {
+            return None;
+        }
+        // SAFETY: both pointers are non-NULL and, per the caller's
+        // contract, NUL-terminated and live for `'a`.
+        let (backend, key_id) = unsafe {
+            (
+                CStr::from_ptr(raw.backend).to_str().ok()?,
+                CStr::from_ptr(raw.key_id).to_str().ok()?,
+            )
+        };
+        Some(Self {
+            backend,
+            key_id,
+            native: raw.native,
+        })
+    }
+
+    /// Store backend the key lives in, e.g. `"pkcs11"`.
+    pub fn backend(&self) -> &'a str {
+        self.backend
+    }
+
+    /// Backend-specific key locator.
+    pub fn key_id(&self) -> &'a str {
+        self.key_id
+    }
+
+    /// Backend-owned native handle; NULL when the caller only has the
+    /// `(backend, key_id)` reference.
+    pub fn native(&self) -> *mut c_void {
+        self.native
+    }
+}
+
+/// Bit set of operations a plugin can perform on a key handle.
+/// Returned through the `*_handle_capabilities` symbols as a `u32`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct HandleCapabilities(u32);
+
+impl HandleCapabilities {
+    /// No handle-based operation is available.
+    pub const NONE: Self = Self(0);
+    /// `cfmp_sig_signer_create_with_handle` will accept the handle.
+    pub const SIGN: Self = Self(1 << 0);
+    /// `cfmp_kem_decapsulator_create_with_handle` will accept the handle.
+    pub const DECAPSULATE: Self = Self(1 << 1);
+
+    /// Decode the wire value. Unknown bits are preserved so a newer
+    /// plugin's answer survives a round trip through an older SDK.
+    pub fn from_bits(bits: u32) -> Self {
+        Self(bits)
+    }
+
+    /// Wire value.
+    pub fn bits(self) -> u32 {
+        self.0
+    }
+
+    /// Whether every bit of `other` is set.
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl std::ops::BitOr for HandleCapabilities {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_raw_validates_pointers() {
+        let backend = c"pkcs11";
+        let key_id = c"pkcs11:token=ci;object=signing";
+        let raw = CFMKeyHandle {
+            backend: backend.as_ptr(),
+            key_id: key_id.as_ptr(),
+            native: std::ptr::null_mut(),
+        };
+        let view = unsafe { KeyHandle::from_raw(&raw) }.unwrap();
+        assert_eq!(view.backend(), "pkcs11");
+        assert_eq!(view.key_id(), "pkcs11:token=ci;object=signing");
+        assert!(view.native().is_null());
+
+        assert!(unsafe { KeyHandle::from_raw(std::ptr::null()) }.is_none());
+        let missing = CFMKeyHandle {
+            backend: backend.as_ptr(),
+            key_id: std::ptr::null(),
+            native: std::ptr::null_mut(),
+        };
+        assert!(unsafe { KeyHandle::from_raw(&missing) }.is_none());
+    }
+
+    #[test]
+    fn capabilities_combine_and_test() {
+        let both = HandleCapabilities::SIGN | HandleCapabilities::DECAPSULATE;
+        assert!(both.contains(HandleCapabilities::SIGN));
+        assert!(!HandleCapabilities::SIGN.contains(HandleCapabilities::DECAPSULATE));
+        assert!(HandleCapabilities::NONE.contains(HandleCapabilities::NONE));
+        assert_eq!(HandleCapabilities::from_bits(0b110).bits(), 0b110);
+    }
+}
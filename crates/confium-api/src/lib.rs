@@ -12,6 +12,9 @@
 //! - Option map types ([`OptionMap`], [`OptionValue`]) used by the
 //!   `cfmp_<iface>_create` family to pass string/u32/nested configuration
 //!   from Confium to the plugin.
+//! - Key handles ([`KeyHandle`], [`HandleCapabilities`]) for the
+//!   signature and KEM v1 extensions that operate on keys held in a
+//!   token, TPM or KMS without exporting them.
 //! - Error conversion ([`PluginError`], [`ErrorCode`]) — the canonical
 //!   numeric codes returned through the FFI surface, plus a `From` impl so
 //!   plugin authors can `?` their own errors into the wire code.
@@ -23,6 +26,7 @@
 
 pub mod error;
 pub mod handle;
+pub mod key_handle;
 pub mod metadata;
 pub mod options;
 pub mod plugin;
@@ -30,11 +34,12 @@ pub mod registry;
 
 pub use error::{ErrorCode, PluginError, PluginResult};
 pub use handle::OpaqueHandle;
+pub use key_handle::{CFMKeyHandle, HandleCapabilities, KeyHandle};
 pub use metadata::{PluginMetadata, PluginMetadataBuilder};
 pub use options::{OptionMap, OptionValue, OptionView};
 pub use plugin::{
-    AeadPlugin, CipherPlugin, HashPlugin, KdfPlugin, KemPlugin, KeyfmtPlugin, RngPlugin,
-    SignaturePlugin,
+    AeadPlugin, CipherPlugin, HashPlugin, KdfPlugin, KemHandlePlugin, KemPlugin, KeyfmtPlugin,
+    RngPlugin, SignatureHandlePlugin, SignaturePlugin,
 };
 
 /// Re-export of the proc-macros so plugin authors can write
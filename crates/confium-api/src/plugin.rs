@@ -18,7 +18,7 @@ pub use aead::AeadPlugin;
 pub use cipher::CipherPlugin;
 pub use hash::HashPlugin;
 pub use kdf::KdfPlugin;
-pub use kem::KemPlugin;
+pub use kem::{KemHandlePlugin, KemPlugin};
 pub use keyfmt::KeyfmtPlugin;
 pub use rng::RngPlugin;
-pub use signature::SignaturePlugin;
+pub use signature::{SignatureHandlePlugin, SignaturePlugin};
@@ -9,10 +9,15 @@
 //! Plugin authors implement this trait on a state type, then apply
 //! `#[plugin_interface(name = "kem", version = 0)]` to the impl block.
 //!
+//! Version 1 adds decapsulation against a key handle (see
+//! [`crate::key_handle`]); a plugin opts in by implementing
+//! [`KemHandlePlugin`] and declaring `version = 1`.
+//!
 //! See `crates/confium-core/src/ffi/kem.rs` for the loader-side wire
 //! types.
 
 use crate::error::PluginResult;
+use crate::key_handle::{HandleCapabilities, KeyHandle};
 use crate::options::OptionView;
 
 /// Result of encapsulation: the ciphertext to send and the shared
@@ -74,3 +79,19 @@ pub trait KemPlugin: Sized {
         opts: Option<OptionView<'_>>,
     ) -> PluginResult<KemKeypair>;
 }
+
+/// KEM v1 extension: decapsulate with a recipient key the plugin
+/// reaches through a [`KeyHandle`]. The returned decapsulator is driven
+/// through [`KemPlugin::decapsulate`].
+pub trait KemHandlePlugin: KemPlugin {
+    /// Report which handle-based operations the plugin supports for
+    /// `algorithm` on `handle`.
+    fn handle_capabilities(algorithm: &str, handle: &KeyHandle<'_>) -> HandleCapabilities;
+
+    /// Construct a decapsulator bound to the key behind `handle`.
+    fn decapsulator_create_with_handle(
+        algorithm: &str,
+        handle: &KeyHandle<'_>,
+        opts: Option<OptionView<'_>>,
+    ) -> PluginResult<Self>;
+}
@@ -14,10 +14,18 @@
 //! `cfmp_sig_verifier_*`, and `cfmp_sig_keypair_generate`. The trait
 //! methods map to these symbols.
 //!
+//! Version 1 of the protocol adds signing against a key handle (see
+//! [`crate::key_handle`]). A plugin opts in by also implementing
+//! [`SignatureHandlePlugin`] and declaring
+//! `#[plugin_interface(name = "signature", version = 1)]`; the v1
+//! symbol set is a superset of v0, so the plugin still loads in hosts
+//! that only speak v0.
+//!
 //! See `crates/confium-core/src/ffi/signature.rs` for the loader-side
 //! wire types.
 
 use crate::error::PluginResult;
+use crate::key_handle::{HandleCapabilities, KeyHandle};
 use crate::options::OptionView;
 
 /// A keypair generation result.
@@ -70,3 +78,22 @@ pub trait SignaturePlugin: Sized {
         opts: Option<OptionView<'_>>,
     ) -> PluginResult<SignatureKeypair>;
 }
+
+/// Signature v1 extension: sign with a key the plugin reaches through a
+/// [`KeyHandle`] instead of receiving its bytes. The signer this
+/// returns is driven through the ordinary [`SignaturePlugin`] methods
+/// (`set_hash`, `update`, `signer_finalize`).
+pub trait SignatureHandlePlugin: SignaturePlugin {
+    /// Report which handle-based operations the plugin supports for
+    /// `algorithm` on `handle`. Must not contact the backend in a way
+    /// that has side effects; returning [`HandleCapabilities::NONE`]
+    /// makes the loader try the next provider.
+    fn handle_capabilities(algorithm: &str, handle: &KeyHandle<'_>) -> HandleCapabilities;
+
+    /// Construct a signer bound to the key behind `handle`.
+    fn signer_create_with_handle(
+        algorithm: &str,
+        handle: &KeyHandle<'_>,
+        opts: Option<OptionView<'_>>,
+    ) -> PluginResult<Self>;
+}
@@ -0,0 +1,91 @@
+//! DH/OPRF private set intersection benches.
+//!
+//! Measures one full two-party run (blind, re-blind, compare) of
+//! `confium_privacy::psi` with equal-size sets and a 10% overlap:
+//!
+//! - **Sans-IO** at 10K and 100K elements per side — the protocol
+//!   cost without a transport.
+//! - **Driver** at 100K over an `inproc://` transport, adding framing
+//!   and the lock-step batching of `run_receiver` / `run_sender`.
+//! - **10M elements per side** when `CONFIUM_BENCH_PSI_10M=1` is set.
+//!   One run takes minutes and needs ~1 GiB, so it is opt-in; criterion
+//!   is told to take the minimum 10 samples.
+
+use confium_privacy::psi::{
+    DEFAULT_BATCH, PsiMode, PsiOutput, PsiReceiver, PsiSender, run_receiver, run_sender,
+};
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use std::hint::black_box;
+
+fn sets(n: usize) -> (Vec<[u8; 8]>, Vec<[u8; 8]>) {
+    let overlap = n / 10;
+    let ours = (0..n as u64).map(u64::to_be_bytes).collect();
+    let theirs = ((n - overlap) as u64..(2 * n - overlap) as u64)
+        .map(u64::to_be_bytes)
+        .collect();
+    (ours, theirs)
+}
+
+fn run_local(ours: &[[u8; 8]], theirs: &[[u8; 8]]) -> PsiOutput {
+    let mut receiver = PsiReceiver::new(PsiMode::Intersection);
+    let sender = PsiSender::new();
+    for (i, chunk) in ours.chunks(DEFAULT_BATCH).enumerate() {
+        let reblinded = sender.reblind(&receiver.blind(chunk)).unwrap();
+        receiver.absorb_reblinded(i * DEFAULT_BATCH, &reblinded);
+    }
+    for chunk in sender.blind_set(theirs).chunks(DEFAULT_BATCH) {
+        receiver.absorb_sender_set(chunk).unwrap();
+    }
+    receiver.finish()
+}
+
+fn bench_local(c: &mut Criterion) {
+    let mut group = c.benchmark_group("psi_local");
+    group.sample_size(10);
+    let mut sizes = vec![10_000usize, 100_000];
+    if std::env::var_os("CONFIUM_BENCH_PSI_10M").is_some() {
+        sizes.push(10_000_000);
+    }
+    for n in sizes {
+        let (ours, theirs) = sets(n);
+        group.throughput(Throughput::Elements(n as u64));
+        group.bench_with_input(BenchmarkId::new("intersection", n), &n, |b, _| {
+            b.iter(|| {
+                let out = run_local(black_box(&ours), black_box(&theirs));
+                assert_eq!(out, PsiOutput::Intersection((n - n / 10..n).collect()));
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_driver(c: &mut Criterion) {
+    let mut group = c.benchmark_group("psi_driver");
+    group.sample_size(10);
+    let n = 100_000usize;
+    let (ours, theirs) = sets(n);
+    let theirs = std::sync::Arc::new(theirs);
+    group.throughput(Throughput::Elements(n as u64));
+    let round = std::cell::Cell::new(0u64);
+    group.bench_function(BenchmarkId::new("inproc_cardinality", n), |b| {
+        b.iter(|| {
+            // Each iteration needs a fresh inproc rendezvous name.
+            round.set(round.get() + 1);
+            let url = format!("inproc://psi-bench-{}", round.get());
+            let mut listener = confium_net::listen(&url).unwrap();
+            let theirs = theirs.clone();
+            let sender = std::thread::spawn(move || {
+                let mut t = listener.accept().unwrap();
+                run_sender(t.as_mut(), &theirs[..], PsiMode::Cardinality, n as u64).unwrap()
+            });
+            let mut t = confium_net::connect(&url).unwrap();
+            let out = run_receiver(t.as_mut(), &ours, PsiMode::Cardinality, DEFAULT_BATCH).unwrap();
+            sender.join().unwrap();
+            assert_eq!(out, PsiOutput::Cardinality(n / 10));
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_local, bench_driver);
+criterion_main!(benches);
@@ -147,7 +147,7 @@ pub struct CompletionsArgs {
 /// `confium threshold dkg`
 #[derive(Args, Debug)]
 pub struct ThresholdDkgArgs {
-    /// Threshold scheme: cmp20, gg18.
+    /// Threshold scheme: cmp20 (P-256), cmp20-p384, cmp20-secp256k1, gg18.
     #[arg(long, default_value = "cmp20")]
     pub scheme: String,
     /// Quorum size (T in T-of-N).
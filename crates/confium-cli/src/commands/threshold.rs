@@ -8,6 +8,7 @@ use crate::cli::{
     ThresholdCommand, ThresholdDkgArgs, ThresholdMigrateSharesArgs, ThresholdRefreshArgs,
     ThresholdSignArgs,
 };
+use confium_tc_cmp20::curve::{NistP384, Secp256k1};
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize)]
@@ -185,12 +186,32 @@ fn dkg(args: ThresholdDkgArgs) -> Result<(), String> {
                 .map_err(|e| e.to_string())?;
             (kg.public_key, kg.shares)
         }
+        "cmp20-p384" => {
+            let kg = confium_tc_cmp20::inprocess::keygen_curve::<NistP384>(
+                args.threshold,
+                args.parties as usize,
+            )
+            .map_err(|e| e.to_string())?;
+            (kg.public_key, kg.shares)
+        }
+        "cmp20-secp256k1" => {
+            let kg = confium_tc_cmp20::inprocess::keygen_curve::<Secp256k1>(
+                args.threshold,
+                args.parties as usize,
+            )
+            .map_err(|e| e.to_string())?;
+            (kg.public_key, kg.shares)
+        }
         "gg18" => {
             let kg = confium_tc_gg18::inprocess::keygen(args.threshold, args.parties as usize)
                 .map_err(|e| e.to_string())?;
             (kg.public_key, kg.shares)
         }
-        other => return Err(format!("unknown scheme: {other} (try cmp20 or gg18)")),
+        other => {
+            return Err(format!(
+                "unknown scheme: {other} (try cmp20, cmp20-p384, cmp20-secp256k1 or gg18)"
+            ));
+        }
     };
 
     let envelope = ShareEnvelope {
@@ -223,10 +244,19 @@ fn sign(args: ThresholdSignArgs) -> Result<(), String> {
 
     let message = read_message(&args.message)?;
 
+    let threshold = envelope.threshold;
     let sig: Vec<u8> = match envelope.scheme.to_lowercase().as_str() {
-        "cmp20" => confium_tc_cmp20::inprocess::sign(&share_blobs, envelope.threshold, &message)
+        "cmp20" => confium_tc_cmp20::inprocess::sign(&share_blobs, threshold, &message)
             .map_err(|e| e.to_string())?,
-        "gg18" => confium_tc_gg18::inprocess::sign(&share_blobs, envelope.threshold, &message)
+        "cmp20-p384" => {
+            confium_tc_cmp20::inprocess::sign_curve::<NistP384>(&share_blobs, threshold, &message)
+                .map_err(|e| e.to_string())?
+        }
+        "cmp20-secp256k1" => {
+            confium_tc_cmp20::inprocess::sign_curve::<Secp256k1>(&share_blobs, threshold, &message)
+                .map_err(|e| e.to_string())?
+        }
+        "gg18" => confium_tc_gg18::inprocess::sign(&share_blobs, threshold, &message)
             .map_err(|e| e.to_string())?,
         other => return Err(format!("unknown scheme in envelope: {other}")),
     };
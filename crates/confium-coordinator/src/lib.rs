@@ -12,6 +12,7 @@
 //! - Persistent session store, WAL, event sourcing
 //! - Admin API, diagnostics
 //! - DKG coordination, share refresh
+//! - ROAST robust signing over changing signer subsets
 
 #![warn(unsafe_code)]
 // The coordinator is internal infrastructure with 40+ modules extracted
@@ -42,6 +43,7 @@ pub mod refresh_coordinator;
 pub mod request_coalescing;
 pub mod resilience_and_circuits;
 pub mod retry;
+pub mod roast;
 pub mod round_coordinator;
 pub mod saga;
 pub mod shutdown;
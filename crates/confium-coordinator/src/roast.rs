@@ -0,0 +1,710 @@
+//! ROAST — robust asynchronous FROST signing.
+//!
+//! A FROST session needs a share from every signer it was started
+//! with, so one slow or malicious signer stalls it and someone has to
+//! pick a new subset by hand. ROAST (Ruffing et al., CCS 2022) wraps the
+//! sessions instead. The coordinator tracks which signers are *ready* —
+//! they have a fresh nonce commitment outstanding and no session waiting
+//! on them — and starts a new FROST session as soon as `t` of them are.
+//! A signer becomes ready again by answering its session: the share
+//! carries its next commitment.
+//!
+//! Sessions thus run concurrently over changing signer subsets. Each
+//! signer is in at most one open session, so an unresponsive signer ties
+//! up only the session it is in and never blocks the others. As long as
+//! `t` honest signers respond, one of at most `n − t + 1` sessions
+//! completes.
+//!
+//! A signer whose commitment or share fails verification is excluded for
+//! the rest of the run and reported to the [`SignerQuarantine`]; every
+//! verified share is reported as good behaviour. The run fails only once
+//! fewer than `t` signers are left who are not excluded.
+//!
+//! Like [`crate::round_coordinator::RoundCoordinator`], the coordinator
+//! treats protocol data as opaque bytes. The FROST-specific checks —
+//! commitment decoding, `verify_signature_share`, aggregation — are
+//! supplied by a [`RoastScheme`].
+
+use confium_tc_keys::production_hardening::SignerQuarantine;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+
+/// The signature-scheme operations ROAST needs from the coordinator's
+/// side.
+pub trait RoastScheme {
+    /// Is `commitment` a well-formed nonce commitment from `signer`?
+    fn validate_commitment(&self, signer: &str, commitment: &[u8]) -> bool;
+
+    /// Is `share` `signer`'s valid answer to the session that signs
+    /// `message` under `commitments`?
+    fn verify_share(
+        &self,
+        message: &[u8],
+        commitments: &[(String, Vec<u8>)],
+        signer: &str,
+        share: &[u8],
+    ) -> bool;
+
+    /// Combine one verified share per session signer into the final
+    /// signature.
+    fn aggregate(
+        &self,
+        message: &[u8],
+        commitments: &[(String, Vec<u8>)],
+        shares: &[(String, Vec<u8>)],
+    ) -> Result<Vec<u8>, String>;
+}
+
+/// A FROST session the coordinator has started.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoastSession {
+    /// Session ID, unique within the run.
+    pub id: u64,
+    /// The message every session of the run signs.
+    pub message: Vec<u8>,
+    /// `(signer, commitment)` per session signer, sorted by signer.
+    pub commitments: Vec<(String, Vec<u8>)>,
+}
+
+impl RoastSession {
+    /// The session's signers.
+    pub fn signers(&self) -> impl Iterator<Item = &str> {
+        self.commitments.iter().map(|(s, _)| s.as_str())
+    }
+}
+
+/// What the caller has to do after a submission.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RoastStep {
+    /// Recorded; nothing to send.
+    Pending,
+    /// Send this session to each of its signers.
+    Start(RoastSession),
+    /// A session completed: the run's signature.
+    Signed(Vec<u8>),
+}
+
+/// Errors during a ROAST run.
+#[derive(Debug, thiserror::Error)]
+pub enum RoastError {
+    /// Signer is not in the roster.
+    #[error("signer {0} is not in the roster")]
+    UnknownSigner(String),
+    /// Signer was caught misbehaving or is quarantined.
+    #[error("signer {0} is excluded from this run")]
+    Excluded(String),
+    /// Signer already has a commitment outstanding.
+    #[error("signer {0} already has a commitment outstanding")]
+    DuplicateCommitment(String),
+    /// No open session is waiting on this signer under that ID.
+    #[error("signer {signer} has no open session {session}")]
+    UnexpectedShare {
+        /// Signer ID.
+        signer: String,
+        /// Session ID the share named.
+        session: u64,
+    },
+    /// Signer sent an invalid commitment or share and is now excluded.
+    #[error("signer {signer} misbehaved: {reason}")]
+    Misbehaved {
+        /// Signer ID.
+        signer: String,
+        /// What failed verification.
+        reason: &'static str,
+    },
+    /// Too many signers are excluded for any session to complete.
+    #[error("only {remaining} usable signers left, need {threshold}")]
+    TooFewSigners {
+        /// Signers not excluded.
+        remaining: usize,
+        /// Signers a session needs.
+        threshold: usize,
+    },
+    /// Every share verified but the scheme could not combine them.
+    #[error("aggregation failed: {0}")]
+    Aggregation(String),
+    /// The run already produced its signature.
+    #[error("signing already completed")]
+    AlreadyCompleted,
+}
+
+struct OpenSession {
+    session: RoastSession,
+    shares: Vec<(String, Vec<u8>)>,
+}
+
+/// The ROAST coordinator for one message.
+pub struct RoastCoordinator<S> {
+    scheme: S,
+    threshold: usize,
+    roster: Vec<String>,
+    message: Vec<u8>,
+    /// Signers with a fresh commitment and no session waiting on them,
+    /// in the order they became ready.
+    ready: Vec<(String, Vec<u8>)>,
+    /// Signer → the open session waiting on its share.
+    awaiting: HashMap<String, u64>,
+    sessions: BTreeMap<u64, OpenSession>,
+    next_session: u64,
+    malicious: HashSet<String>,
+    quarantine: Option<Arc<SignerQuarantine>>,
+    signature: Option<Vec<u8>>,
+}
+
+impl<S: RoastScheme> RoastCoordinator<S> {
+    /// Create a coordinator signing `message` with any `threshold` of
+    /// `roster`.
+    pub fn new(scheme: S, threshold: u32, roster: Vec<String>, message: &[u8]) -> Self {
+        Self {
+            scheme,
+            threshold: threshold as usize,
+            roster,
+            message: message.to_vec(),
+            ready: Vec::new(),
+            awaiting: HashMap::new(),
+            sessions: BTreeMap::new(),
+            next_session: 0,
+            malicious: HashSet::new(),
+            quarantine: None,
+            signature: None,
+        }
+    }
+
+    /// Skip signers `quarantine` holds, and report misbehaviour to it.
+    pub fn with_quarantine(mut self, quarantine: Arc<SignerQuarantine>) -> Self {
+        self.quarantine = Some(quarantine);
+        self
+    }
+
+    /// Accept a signer's first commitment of the run.
+    pub fn submit_commitment(
+        &mut self,
+        signer: &str,
+        commitment: Vec<u8>,
+    ) -> Result<RoastStep, RoastError> {
+        self.check_signer(signer)?;
+        if self.awaiting.contains_key(signer) || self.ready.iter().any(|(s, _)| s == signer) {
+            return Err(RoastError::DuplicateCommitment(signer.into()));
+        }
+        if !self.scheme.validate_commitment(signer, &commitment) {
+            return Err(self.misbehaved(signer, "invalid nonce commitment"));
+        }
+        self.ready.push((signer.into(), commitment));
+        Ok(self.maybe_start())
+    }
+
+    /// Accept `signer`'s share for session `session_id`, together with
+    /// its commitment for the next session.
+    pub fn submit_share(
+        &mut self,
+        signer: &str,
+        session_id: u64,
+        share: Vec<u8>,
+        next_commitment: Vec<u8>,
+    ) -> Result<RoastStep, RoastError> {
+        self.check_signer(signer)?;
+        if self.awaiting.get(signer) != Some(&session_id) {
+            return Err(RoastError::UnexpectedShare {
+                signer: signer.into(),
+                session: session_id,
+            });
+        }
+        if !self.scheme.validate_commitment(signer, &next_commitment) {
+            return Err(self.misbehaved(signer, "invalid nonce commitment"));
+        }
+        let open = &self.sessions[&session_id];
+        if !self
+            .scheme
+            .verify_share(&self.message, &open.session.commitments, signer, &share)
+        {
+            return Err(self.misbehaved(signer, "invalid signature share"));
+        }
+        if let Some(q) = &self.quarantine {
+            q.record_good(signer);
+        }
+        self.awaiting.remove(signer);
+        let open = self
+            .sessions
+            .get_mut(&session_id)
+            .expect("awaited session is open");
+        open.shares.push((signer.into(), share));
+        if open.shares.len() == open.session.commitments.len() {
+            let signature = self
+                .scheme
+                .aggregate(&self.message, &open.session.commitments, &open.shares)
+                .map_err(RoastError::Aggregation)?;
+            self.signature = Some(signature.clone());
+            self.sessions.clear();
+            self.awaiting.clear();
+            self.ready.clear();
+            return Ok(RoastStep::Signed(signature));
+        }
+        self.ready.push((signer.into(), next_commitment));
+        Ok(self.maybe_start())
+    }
+
+    /// The run's signature, once a session has completed.
+    pub fn signature(&self) -> Option<&[u8]> {
+        self.signature.as_deref()
+    }
+
+    /// Has a session completed?
+    pub fn is_complete(&self) -> bool {
+        self.signature.is_some()
+    }
+
+    /// Signers caught misbehaving in this run, sorted.
+    pub fn malicious(&self) -> Vec<&str> {
+        let mut out: Vec<&str> = self.malicious.iter().map(String::as_str).collect();
+        out.sort_unstable();
+        out
+    }
+
+    /// Sessions started so far.
+    pub fn sessions_started(&self) -> u64 {
+        self.next_session
+    }
+
+    /// Sessions still waiting on shares.
+    pub fn open_sessions(&self) -> usize {
+        self.sessions.len()
+    }
+
+    fn is_excluded(&self, signer: &str) -> bool {
+        self.malicious.contains(signer)
+            || self
+                .quarantine
+                .as_ref()
+                .is_some_and(|q| q.is_quarantined(signer))
+    }
+
+    fn check_signer(&self, signer: &str) -> Result<(), RoastError> {
+        if self.signature.is_some() {
+            return Err(RoastError::AlreadyCompleted);
+        }
+        if !self.roster.iter().any(|s| s == signer) {
+            return Err(RoastError::UnknownSigner(signer.into()));
+        }
+        if self.is_excluded(signer) {
+            return Err(RoastError::Excluded(signer.into()));
+        }
+        Ok(())
+    }
+
+    /// Exclude `signer` and report it. The error says whether the run
+    /// can still finish.
+    fn misbehaved(&mut self, signer: &str, reason: &'static str) -> RoastError {
+        self.malicious.insert(signer.into());
+        self.awaiting.remove(signer);
+        self.ready.retain(|(s, _)| s != signer);
+        if let Some(q) = &self.quarantine {
+            q.record_bad(signer);
+        }
+        let remaining = self.roster.iter().filter(|s| !self.is_excluded(s)).count();
+        if remaining < self.threshold {
+            return RoastError::TooFewSigners {
+                remaining,
+                threshold: self.threshold,
+            };
+        }
+        RoastError::Misbehaved {
+            signer: signer.into(),
+            reason,
+        }
+    }
+
+    /// Start a session over the first `t` ready signers, if there are
+    /// that many.
+    fn maybe_start(&mut self) -> RoastStep {
+        if self.ready.len() < self.threshold {
+            return RoastStep::Pending;
+        }
+        let mut commitments: Vec<(String, Vec<u8>)> = self.ready.drain(..self.threshold).collect();
+        commitments.sort_by(|a, b| a.0.cmp(&b.0));
+        let id = self.next_session;
+        self.next_session += 1;
+        for (signer, _) in &commitments {
+            self.awaiting.insert(signer.clone(), id);
+        }
+        let session = RoastSession {
+            id,
+            message: self.message.clone(),
+            commitments,
+        };
+        self.sessions.insert(
+            id,
+            OpenSession {
+                session: session.clone(),
+                shares: Vec::new(),
+            },
+        );
+        RoastStep::Start(session)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use sha2::{Digest, Sha256};
+
+    /// A stand-in scheme: a signer's share is a hash over the session,
+    /// so anything else is detectably wrong.
+    struct MockScheme;
+
+    fn expected_share(message: &[u8], commitments: &[(String, Vec<u8>)], signer: &str) -> Vec<u8> {
+        let mut h = Sha256::new();
+        h.update(message);
+        for (s, c) in commitments {
+            h.update(s.as_bytes());
+            h.update(c);
+        }
+        h.update(signer.as_bytes());
+        h.finalize().to_vec()
+    }
+
+    impl RoastScheme for MockScheme {
+        fn validate_commitment(&self, _signer: &str, commitment: &[u8]) -> bool {
+            !commitment.is_empty()
+        }
+
+        fn verify_share(
+            &self,
+            message: &[u8],
+            commitments: &[(String, Vec<u8>)],
+            signer: &str,
+            share: &[u8],
+        ) -> bool {
+            share == expected_share(message, commitments, signer)
+        }
+
+        fn aggregate(
+            &self,
+            _message: &[u8],
+            _commitments: &[(String, Vec<u8>)],
+            shares: &[(String, Vec<u8>)],
+        ) -> Result<Vec<u8>, String> {
+            Ok(shares.iter().flat_map(|(_, s)| s.clone()).collect())
+        }
+    }
+
+    fn coordinator(threshold: u32, roster: &[&str]) -> RoastCoordinator<MockScheme> {
+        let roster = roster.iter().map(|s| s.to_string()).collect();
+        RoastCoordinator::new(MockScheme, threshold, roster, b"msg")
+    }
+
+    fn answer(
+        rc: &mut RoastCoordinator<MockScheme>,
+        session: &RoastSession,
+        signer: &str,
+    ) -> Result<RoastStep, RoastError> {
+        let share = expected_share(&session.message, &session.commitments, signer);
+        rc.submit_share(signer, session.id, share, vec![session.id as u8 + 1])
+    }
+
+    fn started(step: RoastStep) -> RoastSession {
+        match step {
+            RoastStep::Start(s) => s,
+            other => panic!("expected a new session, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn honest_signers_finish_in_one_session() {
+        let mut rc = coordinator(2, &["alice", "bob", "carol"]);
+        assert_eq!(
+            rc.submit_commitment("alice", vec![1]).unwrap(),
+            RoastStep::Pending
+        );
+        let s = started(rc.submit_commitment("bob", vec![1]).unwrap());
+        assert_eq!(s.signers().collect::<Vec<_>>(), ["alice", "bob"]);
+        assert_eq!(answer(&mut rc, &s, "alice").unwrap(), RoastStep::Pending);
+        assert!(matches!(
+            answer(&mut rc, &s, "bob").unwrap(),
+            RoastStep::Signed(_)
+        ));
+        assert!(rc.is_complete());
+        assert_eq!(rc.sessions_started(), 1);
+    }
+
+    #[test]
+    fn unresponsive_signer_does_not_block() {
+        let mut rc = coordinator(2, &["alice", "bob", "carol"]);
+        rc.submit_commitment("alice", vec![1]).unwrap();
+        let first = started(rc.submit_commitment("bob", vec![1]).unwrap());
+        rc.submit_commitment("carol", vec![1]).unwrap();
+        // Bob never answers; Alice's answer makes her ready again
+        // alongside Carol.
+        let second = started(answer(&mut rc, &first, "alice").unwrap());
+        assert_eq!(second.signers().collect::<Vec<_>>(), ["alice", "carol"]);
+        assert_eq!(rc.open_sessions(), 2);
+        answer(&mut rc, &second, "carol").unwrap();
+        assert!(matches!(
+            answer(&mut rc, &second, "alice").unwrap(),
+            RoastStep::Signed(_)
+        ));
+        assert!(rc.malicious().is_empty());
+    }
+
+    #[test]
+    fn invalid_share_excludes_and_quarantines() {
+        let quarantine = Arc::new(SignerQuarantine::new(1, Duration::hours(1)));
+        let mut rc = coordinator(2, &["alice", "bob", "carol"]).with_quarantine(quarantine.clone());
+        rc.submit_commitment("alice", vec![1]).unwrap();
+        let s = started(rc.submit_commitment("bob", vec![1]).unwrap());
+        rc.submit_commitment("carol", vec![1]).unwrap();
+        let err = rc
+            .submit_share("bob", s.id, vec![0xBA, 0xD0], vec![2])
+            .unwrap_err();
+        assert!(matches!(err, RoastError::Misbehaved { ref signer, .. } if signer == "bob"));
+        assert_eq!(rc.malicious(), ["bob"]);
+        assert!(quarantine.is_quarantined("bob"));
+        assert!(matches!(
+            rc.submit_commitment("bob", vec![3]),
+            Err(RoastError::Excluded(_))
+        ));
+
+        let next = started(answer(&mut rc, &s, "alice").unwrap());
+        answer(&mut rc, &next, "alice").unwrap();
+        assert!(matches!(
+            answer(&mut rc, &next, "carol").unwrap(),
+            RoastStep::Signed(_)
+        ));
+        assert_eq!(quarantine.reputation("alice").unwrap().good_count, 2);
+    }
+
+    #[test]
+    fn quarantined_signer_is_skipped_from_the_start() {
+        let quarantine = Arc::new(SignerQuarantine::new(1, Duration::hours(1)));
+        quarantine.record_bad("mallory");
+        let mut rc = coordinator(2, &["alice", "mallory", "carol"]).with_quarantine(quarantine);
+        assert!(matches!(
+            rc.submit_commitment("mallory", vec![1]),
+            Err(RoastError::Excluded(_))
+        ));
+    }
+
+    #[test]
+    fn fails_once_too_few_signers_remain() {
+        let mut rc = coordinator(2, &["alice", "bob", "carol"]);
+        assert!(matches!(
+            rc.submit_commitment("bob", Vec::new()),
+            Err(RoastError::Misbehaved { .. })
+        ));
+        assert!(matches!(
+            rc.submit_commitment("carol", Vec::new()),
+            Err(RoastError::TooFewSigners {
+                remaining: 1,
+                threshold: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn rejects_out_of_protocol_submissions() {
+        let mut rc = coordinator(2, &["alice", "bob"]);
+        assert!(matches!(
+            rc.submit_commitment("eve", vec![1]),
+            Err(RoastError::UnknownSigner(_))
+        ));
+        rc.submit_commitment("alice", vec![1]).unwrap();
+        assert!(matches!(
+            rc.submit_commitment("alice", vec![1]),
+            Err(RoastError::DuplicateCommitment(_))
+        ));
+        assert!(matches!(
+            rc.submit_share("alice", 0, vec![], vec![1]),
+            Err(RoastError::UnexpectedShare { .. })
+        ));
+        let s = started(rc.submit_commitment("bob", vec![1]).unwrap());
+        answer(&mut rc, &s, "alice").unwrap();
+        answer(&mut rc, &s, "bob").unwrap();
+        assert!(matches!(
+            rc.submit_commitment("alice", vec![1]),
+            Err(RoastError::AlreadyCompleted)
+        ));
+    }
+
+    /// ROAST over real FROST(ristretto255, SHA-512) sessions.
+    mod frost {
+        use super::*;
+        use confium_tc_frost::frost::{SigningNonces, SigningPackage, commit};
+        use confium_tc_frost::ristretto255::Ristretto255;
+        use confium_tc_frost::transcript::CommitmentEntry;
+        use confium_tc_frost::verify::verify;
+        use confium_tc_frost::{Ciphersuite, KeyPackage, inprocess};
+        use std::collections::VecDeque;
+
+        type C = Ristretto255;
+
+        /// Signers are named `p<identifier>`.
+        fn identifier(signer: &str) -> u32 {
+            signer[1..].parse().expect("signer name")
+        }
+
+        fn entries(commitments: &[(String, Vec<u8>)]) -> Vec<CommitmentEntry> {
+            commitments
+                .iter()
+                .map(|(s, c)| {
+                    let (d, e) = c.split_at(C::ELEMENT_BYTES);
+                    (identifier(s), d.to_vec(), e.to_vec())
+                })
+                .collect()
+        }
+
+        /// The coordinator's side: public key material only.
+        struct FrostScheme {
+            public: KeyPackage<C>,
+            threshold: u32,
+        }
+
+        impl FrostScheme {
+            fn package(
+                &self,
+                message: &[u8],
+                commitments: &[(String, Vec<u8>)],
+            ) -> Option<SigningPackage<C>> {
+                SigningPackage::new(
+                    &self.public.public_key,
+                    message,
+                    entries(commitments),
+                    self.threshold,
+                )
+                .ok()
+            }
+        }
+
+        impl RoastScheme for FrostScheme {
+            fn validate_commitment(&self, _signer: &str, commitment: &[u8]) -> bool {
+                commitment.len() == 2 * C::ELEMENT_BYTES
+                    && commitment
+                        .chunks(C::ELEMENT_BYTES)
+                        .all(|e| C::deserialize_element(e).is_some())
+            }
+
+            fn verify_share(
+                &self,
+                message: &[u8],
+                commitments: &[(String, Vec<u8>)],
+                signer: &str,
+                share: &[u8],
+            ) -> bool {
+                let id = identifier(signer);
+                let (Some(package), Some(z), Some(vs)) = (
+                    self.package(message, commitments),
+                    C::deserialize_scalar(share),
+                    self.public.verifying_share(id),
+                ) else {
+                    return false;
+                };
+                package.verify_share(id, &vs, &z)
+            }
+
+            fn aggregate(
+                &self,
+                message: &[u8],
+                commitments: &[(String, Vec<u8>)],
+                shares: &[(String, Vec<u8>)],
+            ) -> Result<Vec<u8>, String> {
+                let package = self
+                    .package(message, commitments)
+                    .ok_or("invalid signing package")?;
+                let zs = shares
+                    .iter()
+                    .map(|(_, z)| C::deserialize_scalar(z))
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or("invalid share encoding")?;
+                Ok(package.aggregate(&zs))
+            }
+        }
+
+        /// A signer and the nonces behind its outstanding commitments.
+        struct Signer {
+            key: KeyPackage<C>,
+            nonces: HashMap<Vec<u8>, SigningNonces<C>>,
+        }
+
+        impl Signer {
+            fn commit(&mut self) -> Vec<u8> {
+                let nonces = commit::<C>(&self.key.share);
+                let (_, d, e) = nonces.commitment(self.key.identifier);
+                let commitment = [d, e].concat();
+                self.nonces.insert(commitment.clone(), nonces);
+                commitment
+            }
+
+            fn sign(&mut self, session: &RoastSession, threshold: u32) -> Vec<u8> {
+                let (_, own) = session
+                    .commitments
+                    .iter()
+                    .find(|(s, _)| identifier(s) == self.key.identifier)
+                    .expect("signer is in the session");
+                let nonces = self.nonces.remove(own).expect("nonce used once");
+                let package = SigningPackage::<C>::new(
+                    &self.key.public_key,
+                    &session.message,
+                    entries(&session.commitments),
+                    threshold,
+                )
+                .expect("signing package");
+                C::serialize_scalar(&package.sign_share(
+                    self.key.identifier,
+                    &self.key.share,
+                    &nonces,
+                ))
+            }
+        }
+
+        #[test]
+        fn terminates_despite_slow_and_malicious_signers() {
+            let blobs = inprocess::keygen::<C>(3, 5).expect("dkg");
+            let mut signers: Vec<Signer> = blobs
+                .iter()
+                .map(|b| Signer {
+                    key: KeyPackage::from_bytes(b).expect("key package"),
+                    nonces: HashMap::new(),
+                })
+                .collect();
+            let names: Vec<String> = (1..=5).map(|i| format!("p{i}")).collect();
+            let quarantine = Arc::new(SignerQuarantine::new(1, Duration::hours(1)));
+            let scheme = FrostScheme {
+                public: KeyPackage::from_bytes(&blobs[0]).expect("key package"),
+                threshold: 3,
+            };
+            let mut rc = RoastCoordinator::new(scheme, 3, names.clone(), b"roast")
+                .with_quarantine(quarantine.clone());
+
+            let mut queue = VecDeque::new();
+            for (name, signer) in names.iter().zip(&mut signers) {
+                if let RoastStep::Start(s) = rc.submit_commitment(name, signer.commit()).unwrap() {
+                    queue.push_back(s);
+                }
+            }
+            // p2 never answers; p4 answers with a tampered share.
+            let mut signature = None;
+            while let Some(session) = queue.pop_front() {
+                for name in session.signers().filter(|n| *n != "p2") {
+                    let signer = &mut signers[identifier(name) as usize - 1];
+                    let mut share = signer.sign(&session, 3);
+                    if name == "p4" {
+                        let z = C::deserialize_scalar(&share).unwrap() + C::scalar_from_u32(1);
+                        share = C::serialize_scalar(&z);
+                    }
+                    match rc.submit_share(name, session.id, share, signer.commit()) {
+                        Ok(RoastStep::Start(s)) => queue.push_back(s),
+                        Ok(RoastStep::Signed(sig)) => signature = Some(sig),
+                        Ok(RoastStep::Pending) => {}
+                        Err(RoastError::Misbehaved { signer, .. }) => assert_eq!(signer, "p4"),
+                        Err(e) => panic!("{e}"),
+                    }
+                }
+            }
+
+            let sig = signature.expect("ROAST terminates");
+            verify::<C>(&signers[0].key.public_key, b"roast", &sig).expect("valid signature");
+            assert_eq!(rc.sessions_started(), 3);
+            assert_eq!(rc.malicious(), ["p4"]);
+            assert!(quarantine.is_quarantined("p4"));
+        }
+    }
+}
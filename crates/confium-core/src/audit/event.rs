@@ -42,6 +42,22 @@ pub enum AuditEvent<'a> {
         version: &'a str,
         publisher: &'a str,
     },
+    /// The load-time policy decided whether a plugin file may be
+    /// loaded. Emitted before `dlopen` for every load attempt, whether
+    /// the file is accepted or refused.
+    ///
+    /// `decision` is `"accepted"`, `"rejected"`, or — when the policy
+    /// only audits or verification is disabled — `"unverified"`.
+    /// `evidence` is `"signature"` or `"pinned-digest"` for accepted files
+    /// and empty otherwise; `reason` is empty for accepted files.
+    PluginVerify {
+        name: &'a str,
+        decision: &'a str,
+        publisher: &'a str,
+        evidence: &'a str,
+        digest: &'a str,
+        reason: &'a str,
+    },
     /// A plugin was unloaded. (Currently informational — the unload
     /// path is not yet wired through the audit logger.)
     PluginUnload { name: &'a str },
@@ -73,6 +89,7 @@ impl<'a> AuditEvent<'a> {
     fn event_tag(&self) -> &'static str {
         match self {
             AuditEvent::PluginLoad { .. } => "plugin_load",
+            AuditEvent::PluginVerify { .. } => "plugin_verify",
             AuditEvent::PluginUnload { .. } => "plugin_unload",
             AuditEvent::KeyAccess { .. } => "key_access",
             AuditEvent::TcSessionStart { .. } => "tc_session_start",
@@ -118,6 +135,21 @@ impl<'a> AuditEvent<'a> {
                 json_field(out, "version", version, true);
                 json_field(out, "publisher", publisher, false);
             }
+            AuditEvent::PluginVerify {
+                name,
+                decision,
+                publisher,
+                evidence,
+                digest,
+                reason,
+            } => {
+                json_field(out, "plugin", name, true);
+                json_field(out, "decision", decision, true);
+                json_field(out, "publisher", publisher, true);
+                json_field(out, "evidence", evidence, true);
+                json_field(out, "digest", digest, true);
+                json_field(out, "reason", reason, false);
+            }
             AuditEvent::PluginUnload { name } => {
                 json_field(out, "plugin", name, false);
             }
@@ -245,6 +277,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn plugin_verify_serializes_to_expected_shape() {
+        let digest = "ab".repeat(32);
+        let ev = AuditEvent::PluginVerify {
+            name: "botan",
+            decision: "accepted",
+            publisher: "ribose",
+            evidence: "signature",
+            digest: &digest,
+            reason: "",
+        };
+        assert_eq!(
+            ev.to_json(TS),
+            format!(
+                "{{\"ts\":\"2026-07-25T13:05:22.123Z\",\"event\":\"plugin_verify\",\
+                 \"plugin\":\"botan\",\"decision\":\"accepted\",\"publisher\":\"ribose\",\
+                 \"evidence\":\"signature\",\"digest\":\"{digest}\",\"reason\":\"\"}}"
+            )
+        );
+    }
+
     #[test]
     fn key_access_serializes_to_expected_shape() {
         let ev = AuditEvent::KeyAccess {
@@ -0,0 +1,20 @@
+//! `confium-plugin-host`: runs one native plugin on behalf of a parent
+//! process that loaded it with `sandbox = "process"`.
+//!
+//! Not meant to be started by hand. The parent spawns it, speaks the
+//! `confium-sandbox-process` protocol over its stdin and stdout, and
+//! closes stdin when it is done. See `confium::sandbox`.
+
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let stdin = std::io::stdin();
+    let stdout = std::io::stdout();
+    match confium::sandbox::host::serve(stdin.lock(), stdout.lock()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("confium-plugin-host: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
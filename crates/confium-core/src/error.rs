@@ -47,9 +47,32 @@ pub enum Error {
     PluginMissingInterface { name: String, ifname: String },
     #[snafu(display("Plugin '{}' internal error {}", name, code))]
     PluginInternalError { name: String, code: u32 },
+    /// The load-time policy refused the plugin file: unsigned, revoked,
+    /// signed by no trusted publisher, or its digest does not match its
+    /// install manifest. `source` says which.
+    #[snafu(display("Plugin '{}' failed verification: {}", name, source))]
+    PluginVerificationFailed {
+        name: String,
+        source: Box<confium_registry::Error>,
+    },
+
+    /// A plugin loaded with `sandbox = "process"` could not be started
+    /// in, or loaded by, its host subprocess; or one loaded with
+    /// `sandbox = "wasm"` is not a usable plugin component.
+    #[snafu(display("Plugin '{}' sandbox failed: {}", name, reason))]
+    PluginSandboxFailed { name: String, reason: String },
 
     #[snafu(display("Unsupported algorithm '{}'", name))]
     UnsupportedAlgorithm { name: String },
+    /// No loaded provider can perform `algorithm` on a key held by the
+    /// `backend` store, either because none speaks the v1 key-handle
+    /// extension or because every one that does declined the handle.
+    #[snafu(display(
+        "No provider accepts '{}' key handles for algorithm '{}'",
+        backend,
+        algorithm
+    ))]
+    KeyHandleUnsupported { backend: String, algorithm: String },
 
     /// Wraps the underlying `std::error::Error::source()` of another
     /// Confium error so it can be returned through the FFI as the next
@@ -85,8 +108,13 @@ pub enum ErrorCode {
     PLUGIN_NAME_COLLISION = 24,
     PLUGIN_MISSING_INTERFACE = 25,
     PLUGIN_INTERNAL_ERROR = 26,
+    PLUGIN_VERIFICATION_FAILED = 28,
+    /// Also returned by every call on a sandboxed plugin whose host
+    /// subprocess has died or broken protocol.
+    PLUGIN_SANDBOX_FAILED = 29,
 
     UNSUPPORTED_ALGORITHM = 50,
+    KEY_HANDLE_UNSUPPORTED = 51,
 
     /// Returned when the error is a `Wrapped` variant — i.e. an FFI-exposed
     /// step in a source chain rather than a first-class Confium error.
@@ -112,8 +140,11 @@ fn error_code(error: &Error) -> u32 {
         Error::PluginNameCollision { .. } => ErrorCode::PLUGIN_NAME_COLLISION.into(),
         Error::PluginMissingInterface { .. } => ErrorCode::PLUGIN_MISSING_INTERFACE.into(),
         Error::PluginInternalError { .. } => ErrorCode::PLUGIN_INTERNAL_ERROR.into(),
+        Error::PluginVerificationFailed { .. } => ErrorCode::PLUGIN_VERIFICATION_FAILED.into(),
+        Error::PluginSandboxFailed { .. } => ErrorCode::PLUGIN_SANDBOX_FAILED.into(),
 
         Error::UnsupportedAlgorithm { .. } => ErrorCode::UNSUPPORTED_ALGORITHM.into(),
+        Error::KeyHandleUnsupported { .. } => ErrorCode::KEY_HANDLE_UNSUPPORTED.into(),
 
         Error::Wrapped { .. } => ErrorCode::WRAPPED.into(),
     }
@@ -33,6 +33,19 @@
 //!                                    uint8_t* sk_out, uint32_t sk_max, uint32_t* sk_len);
 //! ```
 //!
+//! Version 1 adds decapsulation with a secret key that stays in its
+//! keystore (see [`crate::key_handle`]). A v1 plugin exports every v0
+//! symbol plus:
+//!
+//! ```c
+//! uint32_t cfmp_kem_handle_capabilities(const char* algorithm,
+//!                                       const CFMKeyHandle*, uint32_t* caps_out);
+//! uint32_t cfmp_kem_decapsulator_create_with_handle(const Confium*, FFIKemDecapsulator**,
+//!                                                   const char* algorithm,
+//!                                                   const CFMKeyHandle*,
+//!                                                   const Option* opts);
+//! ```
+//!
 //! Algorithms the plugin may advertise (case-sensitive ASCII):
 //! - Classical KEMs: `RSAES-PKCS1-v1_5`, `RSAES-OAEP-SHA256`,
 //!   `ECDH-P256`/`P384`/`P521`, `ECDH-X25519`, `ECDH-X448`,
@@ -60,6 +73,7 @@ use crate::Result;
 use crate::error::Error;
 use crate::ffi::plugin::get_plugin_symbol;
 use crate::ffi::registry::PluginInterfaceKind;
+use crate::key_handle::{CFMKeyHandle, KeyHandle};
 use crate::options::Options;
 use crate::register_interface;
 
@@ -134,9 +148,49 @@ pub struct KemInterfaceV0 {
     pub keypair_generate: Box<KemKeypairGenerateFnV0>,
 }
 
+pub type KemHandleCapabilitiesFnV1 =
+    extern "C" fn(*const c_char, *const CFMKeyHandle, *mut u32) -> u32;
+const KEM_HANDLE_CAPABILITIES_FN_V1_NAME: &[u8] = b"cfmp_kem_handle_capabilities\0";
+
+pub type KemDecapsulatorCreateWithHandleFnV1 = extern "C" fn(
+    *const Confium,
+    *mut *mut FFIKemDecapsulator,
+    *const c_char,
+    *const CFMKeyHandle,
+    Option<&Options>,
+) -> u32;
+const KEM_DECAPSULATOR_CREATE_WITH_HANDLE_FN_V1_NAME: &[u8] =
+    b"cfmp_kem_decapsulator_create_with_handle\0";
+
+/// Key-handle extension, present from version 1. Decapsulators it
+/// creates are driven through the v0 `decapsulate` / `destroy` symbols.
+#[derive(Debug)]
+pub struct KemHandleInterfaceV1 {
+    pub capabilities: Box<KemHandleCapabilitiesFnV1>,
+    pub decapsulator_create: Box<KemDecapsulatorCreateWithHandleFnV1>,
+}
+
 #[derive(Debug)]
 pub enum KemInterface {
     V0(KemInterfaceV0),
+    V1(KemInterfaceV0, KemHandleInterfaceV1),
+}
+
+impl KemInterface {
+    /// The v0 symbol set, which every version includes.
+    pub fn base(&self) -> &KemInterfaceV0 {
+        match self {
+            KemInterface::V0(v0) | KemInterface::V1(v0, _) => v0,
+        }
+    }
+
+    /// The key-handle extension, if the plugin negotiated version 1.
+    pub fn handle(&self) -> Option<&KemHandleInterfaceV1> {
+        match self {
+            KemInterface::V0(_) => None,
+            KemInterface::V1(_, v1) => Some(v1),
+        }
+    }
 }
 
 /// Registry kind for the KEM interface. Lives next to the interface
@@ -150,12 +204,13 @@ impl PluginInterfaceKind for KemKind {
     }
 
     fn max_version(&self) -> u8 {
-        0
+        1
     }
 
     fn build(&self, lib: &Library, version: u8) -> Result<Option<Rc<dyn Any>>> {
         match version {
             0 => Ok(create_kem_interface_v0(lib)?.map(|iface| Rc::new(iface) as Rc<dyn Any>)),
+            1 => Ok(create_kem_interface_v1(lib)?.map(|iface| Rc::new(iface) as Rc<dyn Any>)),
             _ => Ok(None),
         }
     }
@@ -163,6 +218,25 @@ impl PluginInterfaceKind for KemKind {
 
 register_interface!(KemKind);
 
+fn create_kem_interface_v1(lib: &Library) -> Result<Option<KemInterface>> {
+    let Some(KemInterface::V0(base)) = create_kem_interface_v0(lib)? else {
+        return Ok(None);
+    };
+    let handle = KemHandleInterfaceV1 {
+        capabilities: get_plugin_symbol::<KemHandleCapabilitiesFnV1>(
+            lib,
+            "kem",
+            KEM_HANDLE_CAPABILITIES_FN_V1_NAME,
+        )?,
+        decapsulator_create: get_plugin_symbol::<KemDecapsulatorCreateWithHandleFnV1>(
+            lib,
+            "kem",
+            KEM_DECAPSULATOR_CREATE_WITH_HANDLE_FN_V1_NAME,
+        )?,
+    };
+    Ok(Some(KemInterface::V1(base, handle)))
+}
+
 fn create_kem_interface_v0(lib: &Library) -> Result<Option<KemInterface>> {
     let iface = KemInterfaceV0 {
         encapsulator_create: get_plugin_symbol::<KemEncapsulatorCreateFnV0>(
@@ -403,6 +477,55 @@ pub extern "C" fn cfm_kem_decapsulator_create(
     .map_or_else(|e| ffi_return_err!(e, errptr), |_| 0)
 }
 
+fn cfm_kem_decapsulator_create_with_handle_(
+    cfm: *const Confium,
+    dec: *mut *mut KemDecapsulator,
+    algorithm: *const c_char,
+    key_handle: *const CFMKeyHandle,
+    provider: *const c_char,
+    opts: *const Options,
+) -> Result<()> {
+    check_not_null!(cfm);
+    check_not_null!(dec);
+    check_not_null!(algorithm);
+    check_not_null!(key_handle);
+    let cfm = unsafe { &*cfm };
+    let algorithm = crate::ffi::utils::cstring(algorithm)?;
+    let handle = KeyHandle::from_wire(unsafe { &*key_handle })?;
+    let provider = match provider.is_null() {
+        true => None,
+        false => Some(crate::ffi::utils::cstring(provider)?),
+    };
+    let provider = provider.as_deref();
+    let opts = match opts.is_null() {
+        true => None,
+        false => Some(unsafe { &*opts }),
+    };
+    unsafe {
+        *dec = Box::into_raw(Box::new(KemDecapsulator::with_handle(
+            cfm, &algorithm, &handle, provider, opts,
+        )?));
+    }
+    Ok(())
+}
+
+/// Create a decapsulator for a secret key held by a keystore. Only
+/// providers that negotiated KEM v1 and report `DECAPSULATE`
+/// capability for the handle are tried.
+#[unsafe(no_mangle)]
+pub extern "C" fn cfm_kem_decapsulator_create_with_handle(
+    cfm: *const Confium,
+    dec: *mut *mut KemDecapsulator,
+    algorithm: *const c_char,
+    key_handle: *const CFMKeyHandle,
+    provider: *const c_char,
+    opts: *const Options,
+    errptr: *mut *mut Error,
+) -> u32 {
+    cfm_kem_decapsulator_create_with_handle_(cfm, dec, algorithm, key_handle, provider, opts)
+        .map_or_else(|e| ffi_return_err!(e, errptr), |_| 0)
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn cfm_kem_decapsulate(
     dec: *mut KemDecapsulator,
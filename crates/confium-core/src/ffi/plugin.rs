@@ -8,6 +8,7 @@ use crate::error::Error;
 use crate::ffi::registry;
 use crate::ffi::utils::cstring;
 use crate::options::Options;
+use crate::plugin_policy::AdmittedPlugin;
 use crate::{Confium, Plugin, PluginInterface, Provider, Result};
 
 use std::env::consts::DLL_EXTENSION;
@@ -64,6 +65,10 @@ pub struct PluginV0 {
 
 pub enum PluginVTable {
     V0(PluginV0),
+    /// A plugin loaded with `sandbox = "process"` or `"wasm"`; its
+    /// calls go to a host subprocess or a WASM instance. See
+    /// [`crate::sandbox`].
+    Process(crate::sandbox::ProcessPlugin),
 }
 
 macro_rules! check_not_null {
@@ -112,6 +117,7 @@ fn load_plugin_v0(
         library: Rc::new(lib),
         vtable,
         interfaces: Vec::new(),
+        artifact: None,
     })
 }
 
@@ -124,6 +130,8 @@ fn enumerate_plugin_interfaces(
     let mut list: std::collections::HashMap<String, Vec<u8>> = std::collections::HashMap::new();
     let ifs = match vtable {
         PluginVTable::V0(v0) => (*v0.query_interfaces)(cfm),
+        // The child already negotiated; its proxies are built directly.
+        PluginVTable::Process(_) => return Ok(list),
     };
     let mut idx: usize = 0;
     loop {
@@ -198,11 +206,12 @@ fn load_plugin_interfaces(
     Ok(interfaces)
 }
 
-fn finalize_plugin(cfm: &mut Confium, plugin: &Plugin) {
+pub(crate) fn finalize_plugin(cfm: &mut Confium, plugin: &Plugin) {
     match &plugin.vtable {
         PluginVTable::V0(v0) => {
             (*v0.finalize)(cfm);
         }
+        PluginVTable::Process(process) => process.finalize(),
     }
 }
 
@@ -222,6 +231,32 @@ fn plugin_load_lib(name: &str, paths: &[PathBuf]) -> Result<libloading::Library>
     })
 }
 
+/// Map the plugin into this process and negotiate its interfaces.
+fn load_plugin_native(
+    cfm: &mut Confium,
+    name: &str,
+    paths: &[PathBuf],
+    admitted: Option<AdmittedPlugin>,
+    opts: *mut Options,
+) -> Result<Plugin> {
+    let lib = match &admitted {
+        Some(admitted) => plugin_load_lib(name, &[admitted.load_path()])?,
+        None => plugin_load_lib(name, paths)?,
+    };
+    let plugin_iface_ver =
+        get_plugin_symbol::<InterfaceVersionFn>(&lib, name, INTERFACE_VERSION_FN_NAME)?;
+    let mut plugin = match plugin_iface_ver(cfm) {
+        0 => load_plugin_v0(cfm, name, lib, unsafe { &mut *opts })?,
+        _ => return crate::error::PluginInterfaceVersionUnsupportedSnafu { name }.fail(),
+    };
+    plugin.artifact = admitted.map(AdmittedPlugin::into_file);
+    plugin.interfaces =
+        load_plugin_interfaces(cfm, &plugin.library, &plugin.vtable).inspect_err(|_e| {
+            finalize_plugin(cfm, &plugin);
+        })?;
+    Ok(plugin)
+}
+
 pub(crate) fn cfm_plugin_load_(
     cfm: *mut Confium,
     c_name: *const c_char,
@@ -255,33 +290,32 @@ pub(crate) fn cfm_plugin_load_(
             paths.push(path_with_ext.with_file_name(&prefixed_filename));
         }
     }
-    let lib = plugin_load_lib(&name, &paths)?;
-    let plugin_iface_ver =
-        get_plugin_symbol::<InterfaceVersionFn>(&lib, &name, INTERFACE_VERSION_FN_NAME)?;
-    let plugin = match plugin_iface_ver(cfm) {
-        0 => load_plugin_v0(cfm, &name, lib, unsafe { &mut *opts })?,
-        _ => return crate::error::PluginInterfaceVersionUnsupportedSnafu { name }.fail(),
+    // Verify before mapping. `admitted` holds the descriptor the bytes
+    // were checked through; it moves into the plugin once loaded.
+    let admitted = cfm.plugin_policy.admit(&cfm.audit, &name, &paths)?;
+    let publisher = admitted
+        .as_ref()
+        .map_or_else(String::new, |a| a.publisher().to_string());
+    let plugin = match crate::sandbox::requested(unsafe { opts.as_ref() })? {
+        Some(request) => {
+            crate::sandbox::load_sandboxed(&name, &path, admitted, &request, unsafe {
+                opts.as_ref()
+            })?
+        }
+        None => load_plugin_native(cfm, &name, &paths, admitted, opts)?,
     };
-    let mut plugin = plugin;
-    plugin.interfaces =
-        load_plugin_interfaces(cfm, &plugin.library, &plugin.vtable).inspect_err(|_e| {
-            finalize_plugin(cfm, &plugin);
-        })?;
     cfm.providers.push(Provider {
         name: name.clone(),
         plugin,
     });
-    // Audit the successful load. Version and publisher metadata come
-    // from the plugin manifest, which is not yet parsed at this layer;
-    // they are recorded as empty strings until manifest loading lands
-    // (tracked alongside the registry install flow in
-    // TODO.roadmap/06-module-registry.md). The plugin name and the
-    // event itself are still valuable for audit — they establish that
-    // a third-party crypto provider entered the process.
+    // Audit the successful load. The publisher is whoever vouched for
+    // the file in the verification step above (empty when the policy
+    // let an unverified plugin through). Version metadata comes from
+    // the plugin manifest, which is not yet parsed at this layer.
     cfm.audit.log(&crate::audit::event::AuditEvent::PluginLoad {
         name: &name,
         version: "",
-        publisher: "",
+        publisher: &publisher,
     });
     Ok(())
 }
@@ -297,6 +331,46 @@ pub extern "C" fn cfm_plugin_load(
     cfm_plugin_load_(cfm, c_name, c_path, opts).map_or_else(|e| ffi_return_err!(e, errptr), |_| 0)
 }
 
+/// Set the load-time plugin verification policy. `mode` is a
+/// [`PluginVerifyMode`](crate::plugin_policy::PluginVerifyMode) value
+/// (0 enforce, 1 audit only, 2 disabled). `trust_home` overrides the
+/// home directory the trust store is read from; pass NULL for the
+/// user's own.
+#[unsafe(no_mangle)]
+pub extern "C" fn cfm_plugin_policy_set(
+    cfm: *mut Confium,
+    mode: u32,
+    trust_home: *const c_char,
+    errptr: *mut *mut Error,
+) -> u32 {
+    cfm_plugin_policy_set_(cfm, mode, trust_home).map_or_else(|e| ffi_return_err!(e, errptr), |_| 0)
+}
+
+fn cfm_plugin_policy_set_(cfm: *mut Confium, mode: u32, trust_home: *const c_char) -> Result<()> {
+    use crate::plugin_policy::{PluginPolicy, PluginVerifyMode};
+    use confium_registry::TrustStore;
+
+    check_not_null!(cfm);
+    let cfm = unsafe { &mut *cfm };
+    let Some(mode) = PluginVerifyMode::from_u32(mode) else {
+        return crate::error::WrongTypeSnafu {
+            expected: "plugin verify mode (0, 1 or 2)",
+        }
+        .fail();
+    };
+    let trust = if trust_home.is_null() {
+        TrustStore::new()
+    } else {
+        TrustStore::for_home(PathBuf::from(cstring(trust_home)?))
+    };
+    cfm.set_plugin_policy(match mode {
+        PluginVerifyMode::Enforce => PluginPolicy::enforce(trust),
+        PluginVerifyMode::AuditOnly => PluginPolicy::audit_only(trust),
+        PluginVerifyMode::Disabled => PluginPolicy::disabled(),
+    });
+    Ok(())
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn cfm_plugin_unload(cfm: *mut Confium, c_name: *const c_char) -> u32 {
     if cfm.is_null() || c_name.is_null() {
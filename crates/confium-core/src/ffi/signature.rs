@@ -27,6 +27,21 @@
 //!     uint8_t* sk_out, uint32_t sk_max, uint32_t* sk_len);
 //! ```
 //!
+//! Version 1 adds signing with a key that stays in its keystore (see
+//! [`crate::key_handle`]). A v1 plugin exports every v0 symbol plus:
+//!
+//! ```c
+//! uint32_t cfmp_sig_handle_capabilities(
+//!     const char* algorithm, const CFMKeyHandle*, uint32_t* caps_out);
+//! uint32_t cfmp_sig_signer_create_with_handle(
+//!     const Confium*, FFISigner**, const char* algorithm,
+//!     const CFMKeyHandle*, const Option*);
+//! ```
+//!
+//! The signer returned by `cfmp_sig_signer_create_with_handle` is
+//! driven through the v0 `set_hash` / `update` / `finalize` / `destroy`
+//! symbols.
+//!
 //! Algorithms the plugin may advertise (case-sensitive ASCII):
 //!
 //! Classical:
@@ -67,6 +82,7 @@ use crate::Result;
 use crate::error::Error;
 use crate::ffi::plugin::get_plugin_symbol;
 use crate::ffi::registry::PluginInterfaceKind;
+use crate::key_handle::{CFMKeyHandle, KeyHandle};
 use crate::options::Options;
 use crate::register_interface;
 
@@ -115,6 +131,26 @@ pub enum SignerInterface {
     V0(SignerInterfaceV0),
 }
 
+pub type SigHandleCapabilitiesFnV1 =
+    extern "C" fn(*const c_char, *const CFMKeyHandle, *mut u32) -> u32;
+const SIG_HANDLE_CAPABILITIES_FN_V1_NAME: &[u8] = b"cfmp_sig_handle_capabilities\0";
+
+pub type SigSignerCreateWithHandleFnV1 = extern "C" fn(
+    *const Confium,
+    *mut *mut FFISigner,
+    *const c_char,
+    *const CFMKeyHandle,
+    Option<&Options>,
+) -> u32;
+const SIG_SIGNER_CREATE_WITH_HANDLE_FN_V1_NAME: &[u8] = b"cfmp_sig_signer_create_with_handle\0";
+
+/// Key-handle extension of the signer half, present from version 1.
+#[derive(Debug)]
+pub struct SignerHandleInterfaceV1 {
+    pub capabilities: Box<SigHandleCapabilitiesFnV1>,
+    pub create: Box<SigSignerCreateWithHandleFnV1>,
+}
+
 // ---------------------------------------------------------------------
 // Verifier interface
 // ---------------------------------------------------------------------
@@ -206,7 +242,7 @@ impl PluginInterfaceKind for SignatureKind {
     }
 
     fn max_version(&self) -> u8 {
-        0
+        1
     }
 
     fn build(&self, lib: &Library, version: u8) -> Result<Option<Rc<dyn Any>>> {
@@ -214,6 +250,9 @@ impl PluginInterfaceKind for SignatureKind {
             0 => Ok(Some(
                 Rc::new(SignatureInterface::build_v0(lib)?) as Rc<dyn Any>
             )),
+            1 => Ok(Some(
+                Rc::new(SignatureInterface::build_v1(lib)?) as Rc<dyn Any>
+            )),
             _ => Ok(None),
         }
     }
@@ -225,15 +264,34 @@ register_interface!(SignatureKind);
 /// half, a verifier half, and a keypair-generation entry point. All
 /// three are present for a v0 plugin; individual algorithms may not be
 /// supported, which the plugin signals by returning a non-zero code
-/// from the relevant call.
+/// from the relevant call. `handle` is populated only when the plugin
+/// negotiated version 1.
 #[derive(Debug)]
 pub struct SignatureInterface {
     pub signer: SignerInterface,
     pub verifier: VerifierInterface,
     pub keypair: KeypairInterface,
+    pub handle: Option<SignerHandleInterfaceV1>,
 }
 
 impl SignatureInterface {
+    fn build_v1(lib: &Library) -> Result<SignatureInterface> {
+        let mut iface = Self::build_v0(lib)?;
+        iface.handle = Some(SignerHandleInterfaceV1 {
+            capabilities: get_plugin_symbol::<SigHandleCapabilitiesFnV1>(
+                lib,
+                "signature",
+                SIG_HANDLE_CAPABILITIES_FN_V1_NAME,
+            )?,
+            create: get_plugin_symbol::<SigSignerCreateWithHandleFnV1>(
+                lib,
+                "signature",
+                SIG_SIGNER_CREATE_WITH_HANDLE_FN_V1_NAME,
+            )?,
+        });
+        Ok(iface)
+    }
+
     fn build_v0(lib: &Library) -> Result<SignatureInterface> {
         let signer = SignerInterfaceV0 {
             create: get_plugin_symbol::<SigSignerCreateFnV0>(
@@ -300,6 +358,7 @@ impl SignatureInterface {
             signer: SignerInterface::V0(signer),
             verifier: VerifierInterface::V0(verifier),
             keypair: KeypairInterface::V0(keypair),
+            handle: None,
         })
     }
 }
@@ -372,6 +431,56 @@ pub extern "C" fn cfm_sig_signer_create(
     .map_or_else(|e| ffi_return_err!(e, errptr), |_| 0)
 }
 
+fn cfm_sig_signer_create_with_handle_(
+    cfm: *const Confium,
+    signer: *mut *mut Signer,
+    algorithm: *const c_char,
+    key_handle: *const CFMKeyHandle,
+    provider: *const c_char,
+    opts: *const Options,
+) -> Result<()> {
+    check_not_null!(cfm);
+    check_not_null!(signer);
+    check_not_null!(algorithm);
+    check_not_null!(key_handle);
+    let cfm = unsafe { &*cfm };
+    let algorithm = crate::ffi::utils::cstring(algorithm)?;
+    let handle = KeyHandle::from_wire(unsafe { &*key_handle })?;
+    let provider = match provider.is_null() {
+        true => None,
+        false => Some(crate::ffi::utils::cstring(provider)?),
+    };
+    let provider = provider.as_deref();
+    let opts = match opts.is_null() {
+        true => None,
+        false => Some(unsafe { &*opts }),
+    };
+    unsafe {
+        *signer = Box::into_raw(Box::new(Signer::with_handle(
+            cfm, &algorithm, &handle, provider, opts,
+        )?));
+    }
+    Ok(())
+}
+
+/// Create a signer for a key held by a keystore. Only providers that
+/// negotiated signature v1 and report `SIGN` capability for the handle
+/// are tried; the result is used with the ordinary
+/// `cfm_sig_signer_*` calls.
+#[unsafe(no_mangle)]
+pub extern "C" fn cfm_sig_signer_create_with_handle(
+    cfm: *const Confium,
+    signer: *mut *mut Signer,
+    algorithm: *const c_char,
+    key_handle: *const CFMKeyHandle,
+    provider: *const c_char,
+    opts: *const Options,
+    errptr: *mut *mut Error,
+) -> u32 {
+    cfm_sig_signer_create_with_handle_(cfm, signer, algorithm, key_handle, provider, opts)
+        .map_or_else(|e| ffi_return_err!(e, errptr), |_| 0)
+}
+
 #[unsafe(no_mangle)]
 pub extern "C" fn cfm_sig_signer_set_hash(signer: *mut Signer, hash_name: *const c_char) -> u32 {
     if signer.is_null() {
@@ -10,6 +10,10 @@
 //! the same provider for a given algorithm so an encapsulated
 //! ciphertext produced by one is decapsulable by the other.
 //!
+//! A decapsulator whose secret key stays in a hardware or cloud
+//! keystore is created with [`KemDecapsulator::with_handle`]; it needs
+//! a provider that negotiated KEM v1 (see [`crate::key_handle`]).
+//!
 //! The algorithm-only static helpers [`KemEncapsulator::shared_secret_size`]
 //! and [`KemEncapsulator::keypair_generate`] are exposed on the
 //! encapsulator type because they are invoked before any instance
@@ -28,8 +32,10 @@ use crate::Provider;
 use crate::Result;
 use crate::error;
 use crate::ffi::kem::{
-    FFIKemDecapsulator, FFIKemEncapsulator, KemInterface, KemInterfaceV0, interface_of,
+    FFIKemDecapsulator, FFIKemEncapsulator, KemHandleInterfaceV1, KemInterface, KemInterfaceV0,
+    interface_of,
 };
+use crate::key_handle::{HandleCapabilities, KeyHandle};
 use crate::options::Options;
 
 fn find_provider<'a>(cfm: &'a Confium, name: &str) -> Option<&'a Provider> {
@@ -128,6 +134,40 @@ fn decapsulator_create_v0(
     Ok(Some(obj))
 }
 
+/// Ask a v1 plugin whether it can decapsulate `algorithm` with
+/// `handle`, and if so create the decapsulator. `Ok(None)` means the
+/// plugin declined.
+fn decapsulator_create_with_handle_v1(
+    cfm: &Confium,
+    plugin_name: &str,
+    v1: &KemHandleInterfaceV1,
+    algorithm: &str,
+    handle: &KeyHandle,
+    opts: Option<&Options>,
+) -> Result<Option<*mut FFIKemDecapsulator>> {
+    let cname = CString::new(algorithm).unwrap();
+    let wire = handle.as_wire();
+    let mut caps: u32 = 0;
+    if (*v1.capabilities)(cname.as_ptr(), &wire, &mut caps) != 0
+        || !HandleCapabilities::from_bits(caps).contains(HandleCapabilities::DECAPSULATE)
+    {
+        return Ok(None);
+    }
+    let mut obj: *mut FFIKemDecapsulator = std::ptr::null_mut();
+    let code = (*v1.decapsulator_create)(cfm, &mut obj, cname.as_ptr(), &wire, opts);
+    if code != 0 {
+        return error::PluginInternalSnafu {
+            name: plugin_name,
+            code,
+        }
+        .fail();
+    }
+    if obj.is_null() {
+        return Ok(None);
+    }
+    Ok(Some(obj))
+}
+
 /// Sender-side KEM handle. Constructed with the recipient's public key;
 /// produces a ciphertext plus a shared secret via [`KemEncapsulator::encapsulate`].
 /// The recipient decapsulates the ciphertext with [`KemDecapsulator`] to
@@ -151,7 +191,7 @@ impl KemEncapsulator {
             let Some(iface) = interface_of(&provider.plugin) else {
                 continue;
             };
-            let KemInterface::V0(v0) = &*iface;
+            let v0 = iface.base();
             let obj =
                 encapsulator_create_v0(cfm, &provider.name, v0, algorithm, recipient_pubkey, opts)?;
             if let Some(obj) = obj {
@@ -189,7 +229,7 @@ impl KemEncapsulator {
         ciphertext_out: &mut [u8],
         shared_secret_out: &mut [u8],
     ) -> Result<(usize, usize)> {
-        let KemInterface::V0(v0) = &*self.interface;
+        let v0 = self.interface.base();
         let mut ct_len: u32 = ciphertext_out.len() as u32;
         let mut ss_len: u32 = shared_secret_out.len() as u32;
         let code = (*v0.encapsulate)(
@@ -217,7 +257,7 @@ impl KemEncapsulator {
             let Some(iface) = interface_of(&provider.plugin) else {
                 continue;
             };
-            let KemInterface::V0(v0) = &*iface;
+            let v0 = iface.base();
             let cname = CString::new(algorithm).unwrap();
             let mut size: u32 = 0;
             let code = (*v0.shared_secret_size)(cfm, cname.as_ptr(), &mut size);
@@ -244,7 +284,7 @@ impl KemEncapsulator {
             let Some(iface) = interface_of(&provider.plugin) else {
                 continue;
             };
-            let KemInterface::V0(v0) = &*iface;
+            let v0 = iface.base();
             let cname = CString::new(algorithm).unwrap();
             let mut pk_len: u32 = pk_out.len() as u32;
             let mut sk_len: u32 = sk_out.len() as u32;
@@ -274,7 +314,7 @@ impl KemEncapsulator {
 
 impl Drop for KemEncapsulator {
     fn drop(&mut self) {
-        let KemInterface::V0(v0) = &*self.interface;
+        let v0 = self.interface.base();
         (*v0.encapsulator_destroy)(self.obj);
     }
 }
@@ -304,7 +344,7 @@ impl KemDecapsulator {
             let Some(iface) = interface_of(&provider.plugin) else {
                 continue;
             };
-            let KemInterface::V0(v0) = &*iface;
+            let v0 = iface.base();
             let obj =
                 decapsulator_create_v0(cfm, &provider.name, v0, algorithm, recipient_seckey, opts)?;
             if let Some(obj) = obj {
@@ -318,6 +358,47 @@ impl KemDecapsulator {
         error::UnsupportedAlgorithmSnafu { name: algorithm }.fail()
     }
 
+    /// Create a decapsulator for a secret key held by a keystore.
+    /// Providers that did not negotiate KEM v1, or that report no
+    /// `DECAPSULATE` capability for `handle`, are skipped; if none is
+    /// left the call fails with [`error::Error::KeyHandleUnsupported`].
+    pub fn with_handle(
+        cfm: &Confium,
+        algorithm: &str,
+        handle: &KeyHandle,
+        provider_name: Option<&str>,
+        opts: Option<&Options>,
+    ) -> Result<KemDecapsulator> {
+        for provider in candidate_providers(cfm, provider_name)? {
+            let Some(iface) = interface_of(&provider.plugin) else {
+                continue;
+            };
+            let Some(v1) = iface.handle() else {
+                continue;
+            };
+            let obj = decapsulator_create_with_handle_v1(
+                cfm,
+                &provider.name,
+                v1,
+                algorithm,
+                handle,
+                opts,
+            )?;
+            if let Some(obj) = obj {
+                return Ok(KemDecapsulator {
+                    obj,
+                    lib: Rc::clone(&provider.plugin.library),
+                    interface: iface,
+                });
+            }
+        }
+        error::KeyHandleUnsupportedSnafu {
+            backend: handle.backend(),
+            algorithm,
+        }
+        .fail()
+    }
+
     pub fn new(
         cfm: &Confium,
         algorithm: &str,
@@ -339,7 +420,7 @@ impl KemDecapsulator {
         ciphertext: &[u8],
         shared_secret_out: &mut [u8],
     ) -> Result<usize> {
-        let KemInterface::V0(v0) = &*self.interface;
+        let v0 = self.interface.base();
         let mut ss_len: u32 = shared_secret_out.len() as u32;
         let code = (*v0.decapsulate)(
             self.obj,
@@ -358,7 +439,7 @@ impl KemDecapsulator {
 
 impl Drop for KemDecapsulator {
     fn drop(&mut self) {
-        let KemInterface::V0(v0) = &*self.interface;
+        let v0 = self.interface.base();
         (*v0.decapsulator_destroy)(self.obj);
     }
 }
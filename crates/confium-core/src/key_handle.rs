@@ -0,0 +1,127 @@
+//! References to keys that never leave their keystore.
+//!
+//! A [`KeyHandle`] names a key by the `confium-store` backend that
+//! holds it and the backend's locator for it (a PKCS#11 URI, a KMS key
+//! ARN, a TPM persistent handle, ...), optionally with the native
+//! object the keystore returned from `get_secret`. It is what
+//! [`crate::signature::Signer::with_handle`] and
+//! [`crate::kem::KemDecapsulator::with_handle`] hand to plugins that
+//! speak version 1 of the `signature` / `kem` wire protocol.
+//!
+//! On the wire the handle is a [`CFMKeyHandle`]; `confium-api` defines
+//! the same layout for the plugin side.
+
+use std::ffi::{CString, c_void};
+use std::os::raw::c_char;
+
+use crate::Result;
+use crate::error;
+
+/// C layout of a key handle. Mirrors `confium_api::CFMKeyHandle`.
+#[repr(C)]
+#[derive(Debug)]
+pub struct CFMKeyHandle {
+    pub backend: *const c_char,
+    pub key_id: *const c_char,
+    pub native: *mut c_void,
+}
+
+/// Owned key handle. The strings are kept NUL-terminated so the wire
+/// view can borrow them without copying.
+#[derive(Debug)]
+pub struct KeyHandle {
+    backend: CString,
+    key_id: CString,
+    native: *mut c_void,
+}
+
+impl KeyHandle {
+    /// `native` may be NULL when the caller only knows the
+    /// `(backend, key_id)` pair; plugins then resolve the key through
+    /// their own connection to the backend.
+    pub fn new(backend: &str, key_id: &str, native: *mut c_void) -> Result<Self> {
+        let cstr = |s: &str| {
+            CString::new(s).map_err(|_| {
+                error::WrongTypeSnafu {
+                    expected: "string without interior NUL",
+                }
+                .build()
+            })
+        };
+        Ok(Self {
+            backend: cstr(backend)?,
+            key_id: cstr(key_id)?,
+            native,
+        })
+    }
+
+    pub fn backend(&self) -> &str {
+        self.backend.to_str().unwrap_or_default()
+    }
+
+    pub fn key_id(&self) -> &str {
+        self.key_id.to_str().unwrap_or_default()
+    }
+
+    pub fn native(&self) -> *mut c_void {
+        self.native
+    }
+
+    /// Borrowed wire view, valid while `self` is.
+    pub(crate) fn as_wire(&self) -> CFMKeyHandle {
+        CFMKeyHandle {
+            backend: self.backend.as_ptr(),
+            key_id: self.key_id.as_ptr(),
+            native: self.native,
+        }
+    }
+
+    /// Copy a caller-supplied wire handle.
+    pub(crate) fn from_wire(raw: &CFMKeyHandle) -> Result<Self> {
+        let backend = crate::ffi::utils::cstring(raw.backend)?;
+        let key_id = crate::ffi::utils::cstring(raw.key_id)?;
+        Self::new(&backend, &key_id, raw.native)
+    }
+}
+
+/// Bit set returned by a plugin's `*_handle_capabilities` symbol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct HandleCapabilities(u32);
+
+impl HandleCapabilities {
+    pub const NONE: Self = Self(0);
+    pub const SIGN: Self = Self(1 << 0);
+    pub const DECAPSULATE: Self = Self(1 << 1);
+
+    pub fn from_bits(bits: u32) -> Self {
+        Self(bits)
+    }
+
+    pub fn bits(self) -> u32 {
+        self.0
+    }
+
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wire_view_round_trips() {
+        let handle = KeyHandle::new("pkcs11", "pkcs11:object=sign", std::ptr::null_mut()).unwrap();
+        let wire = handle.as_wire();
+        let copy = KeyHandle::from_wire(&wire).unwrap();
+        assert_eq!(copy.backend(), "pkcs11");
+        assert_eq!(copy.key_id(), "pkcs11:object=sign");
+        assert!(copy.native().is_null());
+    }
+
+    #[test]
+    fn interior_nul_is_rejected() {
+        assert!(KeyHandle::new("tpm", "0x81\x00000001", std::ptr::null_mut()).is_err());
+    }
+}
@@ -21,9 +21,15 @@
 //!   [`keyfmt`], [`signature`] — interface modules, each owning
 //!   its `FFI<Type>` opaque type, `InterfaceV0` vtable, and
 //!   `cfm_<iface>_*` entry points.
+//! - [`key_handle`] — references to keys held by a hardware or cloud
+//!   keystore, consumed by the v1 signature and KEM interfaces.
 //! - [`ffi`] — common FFI utilities (plugin handle, registry
 //!   scraper, metadata).
 //! - [`audit`] — append-only audit log writer.
+//! - [`plugin_policy`] — load-time verification of plugin binaries
+//!   against the `confium-registry` trust store.
+//! - [`sandbox`] — running a plugin in a `confium-plugin-host`
+//!   subprocess behind the same interfaces.
 //! - [`secret`], [`sensitive`] — zeroized secret buffers and
 //!   `mlock`ed regions.
 
@@ -47,10 +53,13 @@ pub mod ffi;
 pub mod hash;
 pub mod kdf;
 pub mod kem;
+pub mod key_handle;
 pub mod keyfmt;
 pub mod mlock;
 pub mod options;
+pub mod plugin_policy;
 pub mod rng;
+pub mod sandbox;
 pub mod secret;
 pub mod sensitive;
 pub mod signature;
@@ -64,6 +73,7 @@ use libloading::Library;
 
 use audit::AuditLogger;
 use error::Error;
+use plugin_policy::PluginPolicy;
 use snafu::ResultExt;
 
 use ffi::plugin::{CFMPluginMetadata, METADATA_FN_NAME, MetadataFn, PluginVTable};
@@ -87,6 +97,12 @@ pub struct Plugin {
     /// interface types live in their respective modules (`ffi::hash`,
     /// `ffi::cipher`, etc.).
     pub interfaces: Vec<PluginInterface>,
+    /// The descriptor the plugin file was verified and mapped through
+    /// (see [`plugin_policy`]). Held for as long as the library so the
+    /// `/proc/self/fd/<n>` name the loader cached for it cannot be
+    /// reused by another plugin while this one is mapped.
+    #[allow(dead_code)] // held only for its `Drop`
+    artifact: Option<std::fs::File>,
 }
 
 impl Plugin {
@@ -204,6 +220,9 @@ pub struct Confium {
     /// Structured audit log sink. Logs plugin loads, key accesses, and
     /// TC session boundaries as JSON Lines. See [`audit::AuditLogger`].
     pub audit: AuditLogger,
+    /// Decides whether a plugin file may be loaded. Defaults to
+    /// enforcing the user's trust store; see [`plugin_policy`].
+    plugin_policy: PluginPolicy,
 }
 
 impl Confium {
@@ -222,9 +241,24 @@ impl Confium {
             providers: Vec::new(),
             preferred_providers: HashMap::new(),
             audit: logger,
+            plugin_policy: PluginPolicy::default(),
         }
     }
 
+    /// Replace the load-time plugin verification policy. Affects only
+    /// plugins loaded after the call.
+    pub fn set_plugin_policy(&mut self, policy: PluginPolicy) {
+        self.plugin_policy = policy;
+    }
+
+    pub fn plugin_policy(&self) -> &PluginPolicy {
+        &self.plugin_policy
+    }
+
+    /// Load the plugin at `path`. The file is verified against the
+    /// current [`PluginPolicy`] before it is mapped; under the default
+    /// policy an unsigned or untrusted plugin is refused with
+    /// [`Error::PluginVerificationFailed`].
     pub fn load_plugin(&mut self, path: &Path, options: &StringOptions) -> Result<()> {
         use std::ffi::CString;
         let path_str = path.to_string_lossy();
@@ -447,6 +481,6 @@ mod metadata_tests {
             description: std::ptr::null(),
         };
         let err = PluginMetadata::from_raw(&raw).unwrap_err();
-        assert_eq!(err.code(), error::ErrorCode::INVALID_UTF8.into());
+        assert_eq!(err.code(), u32::from(error::ErrorCode::INVALID_UTF8));
     }
 }
@@ -0,0 +1,443 @@
+//! Load-time verification of plugin binaries.
+//!
+//! Before the loader hands a plugin file to the dynamic linker it asks
+//! the [`PluginPolicy`] whether the file may be loaded. The policy
+//! reads the file once through a descriptor it keeps open, checks the
+//! bytes with [`confium_registry::verify_artifact`] against the
+//! `confium-registry` [`TrustStore`] (revocation list, then the
+//! detached signature sidecar), and records the
+//! decision as an [`AuditEvent::PluginVerify`].
+//!
+//! On Linux the loader then `dlopen`s `/proc/self/fd/<n>` for that same
+//! descriptor, so a file swapped on disk between the check and the load
+//! is never the one that gets mapped. Other platforms reopen the
+//! verified path.
+//!
+//! A plugin run out of process (see [`crate::sandbox`]) is verified
+//! here, in the host. The sandbox child then loads it under a policy
+//! pinned to the digest the host accepted, so the child maps the same
+//! bytes without needing the trust store.
+//!
+//! The default policy is [`PluginVerifyMode::Enforce`] against the
+//! user's trust store. [`PluginVerifyMode::AuditOnly`] records the
+//! decision but loads regardless; [`PluginVerifyMode::Disabled`] skips
+//! the check (still leaving an `"unverified"` audit record) and exists
+//! for tests and development builds that load freshly compiled plugins.
+
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use confium_registry::{ArtifactCheck, TrustStore};
+
+use crate::Result;
+use crate::audit::AuditLogger;
+use crate::audit::event::AuditEvent;
+use crate::error;
+
+/// How the loader treats a plugin that fails verification. The
+/// discriminants are the values accepted by `cfm_plugin_policy_set`.
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PluginVerifyMode {
+    /// Refuse unsigned, revoked, untrusted and mismatched plugins.
+    Enforce = 0,
+    /// Verify and audit, but load even when verification fails.
+    AuditOnly = 1,
+    /// Do not verify. Every load is audited as `"unverified"`.
+    Disabled = 2,
+}
+
+impl PluginVerifyMode {
+    /// Decode the C API value.
+    pub fn from_u32(mode: u32) -> Option<Self> {
+        match mode {
+            0 => Some(Self::Enforce),
+            1 => Some(Self::AuditOnly),
+            2 => Some(Self::Disabled),
+            _ => None,
+        }
+    }
+
+    /// Stable lower-case label, as used by the daemon's `--plugin-policy`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Enforce => "enforce",
+            Self::AuditOnly => "audit",
+            Self::Disabled => "off",
+        }
+    }
+}
+
+impl std::str::FromStr for PluginVerifyMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "enforce" => Ok(Self::Enforce),
+            "audit" => Ok(Self::AuditOnly),
+            "off" => Ok(Self::Disabled),
+            other => Err(format!(
+                "unknown plugin policy '{other}': use enforce, audit or off"
+            )),
+        }
+    }
+}
+
+/// The verification mode plus the trust store it checks against.
+pub struct PluginPolicy {
+    mode: PluginVerifyMode,
+    trust: TrustStore,
+    /// Set only in a sandbox child: the SHA-256 the parent already
+    /// verified. The file must hash to it; the trust store is unused.
+    pin: Option<String>,
+}
+
+impl PluginPolicy {
+    /// Refuse any plugin `trust` does not vouch for.
+    pub fn enforce(trust: TrustStore) -> Self {
+        Self {
+            mode: PluginVerifyMode::Enforce,
+            trust,
+            pin: None,
+        }
+    }
+
+    /// Verify against `trust` and audit the outcome, but never refuse.
+    pub fn audit_only(trust: TrustStore) -> Self {
+        Self {
+            mode: PluginVerifyMode::AuditOnly,
+            trust,
+            pin: None,
+        }
+    }
+
+    /// Skip verification entirely.
+    pub fn disabled() -> Self {
+        Self {
+            mode: PluginVerifyMode::Disabled,
+            trust: TrustStore::new(),
+            pin: None,
+        }
+    }
+
+    /// Admit only a file whose SHA-256 is `digest`. Used by the sandbox
+    /// child to load exactly the bytes its parent verified.
+    pub(crate) fn pinned(digest: String) -> Self {
+        Self {
+            mode: PluginVerifyMode::Enforce,
+            trust: TrustStore::new(),
+            pin: Some(digest),
+        }
+    }
+
+    pub fn mode(&self) -> PluginVerifyMode {
+        self.mode
+    }
+
+    pub fn trust(&self) -> &TrustStore {
+        &self.trust
+    }
+
+    /// Decide whether the plugin `name` may be loaded from the first of
+    /// `candidates` that exists as a file. `Ok(None)` means the loader
+    /// should fall back to its unverified search over `candidates`
+    /// (verification disabled, or audit-only with no local file).
+    pub(crate) fn admit(
+        &self,
+        audit: &AuditLogger,
+        name: &str,
+        candidates: &[PathBuf],
+    ) -> Result<Option<AdmittedPlugin>> {
+        if self.mode == PluginVerifyMode::Disabled {
+            audit.log(&AuditEvent::PluginVerify {
+                name,
+                decision: "unverified",
+                publisher: "",
+                evidence: "",
+                digest: "",
+                reason: "verification disabled",
+            });
+            return Ok(None);
+        }
+
+        let Some(path) = candidates.iter().find(|p| p.is_file()) else {
+            let source = confium_registry::Error::io(
+                std::io::ErrorKind::NotFound.into(),
+                "no plugin file to verify at any candidate path",
+            );
+            return self.refuse(audit, name, "", source).map(|()| None);
+        };
+        let (file, bytes) = match read_artifact(path) {
+            Ok(read) => read,
+            Err(e) => {
+                let source =
+                    confium_registry::Error::io(e, format!("failed to read {}", path.display()));
+                return self.refuse(audit, name, "", source).map(|()| None);
+            }
+        };
+
+        if let Some(pin) = &self.pin {
+            let digest = confium_registry::sha256_hex(&bytes);
+            if &digest != pin {
+                let source = confium_registry::Error::HashMismatch {
+                    name: name.to_string(),
+                    version: String::new(),
+                    expected: pin.clone(),
+                    actual: digest.clone(),
+                };
+                self.refuse(audit, name, &digest, source)?;
+            }
+            audit.log(&AuditEvent::PluginVerify {
+                name,
+                decision: "accepted",
+                publisher: "",
+                evidence: "pinned-digest",
+                digest: &digest,
+                reason: "",
+            });
+            return Ok(Some(AdmittedPlugin {
+                file,
+                path: path.clone(),
+                digest,
+                check: None,
+            }));
+        }
+
+        match confium_registry::verify_artifact(name, path, &bytes, &self.trust) {
+            Ok(check) => {
+                audit.log(&AuditEvent::PluginVerify {
+                    name,
+                    decision: "accepted",
+                    publisher: &check.publisher,
+                    evidence: check.evidence.as_str(),
+                    digest: &check.digest,
+                    reason: "",
+                });
+                Ok(Some(AdmittedPlugin {
+                    file,
+                    path: path.clone(),
+                    digest: check.digest.clone(),
+                    check: Some(check),
+                }))
+            }
+            Err(source) => {
+                let digest = confium_registry::sha256_hex(&bytes);
+                self.refuse(audit, name, &digest, source)?;
+                Ok(Some(AdmittedPlugin {
+                    file,
+                    path: path.clone(),
+                    digest,
+                    check: None,
+                }))
+            }
+        }
+    }
+
+    /// Audit a failed verification. Under [`PluginVerifyMode::Enforce`]
+    /// this is the error returned to the caller; otherwise the load
+    /// proceeds unverified.
+    fn refuse(
+        &self,
+        audit: &AuditLogger,
+        name: &str,
+        digest: &str,
+        source: confium_registry::Error,
+    ) -> Result<()> {
+        let reason = source.to_string();
+        let enforce = self.mode == PluginVerifyMode::Enforce;
+        audit.log(&AuditEvent::PluginVerify {
+            name,
+            decision: if enforce { "rejected" } else { "unverified" },
+            publisher: "",
+            evidence: "",
+            digest,
+            reason: &reason,
+        });
+        if enforce {
+            return Err(error::Error::PluginVerificationFailed {
+                name: name.to_string(),
+                source: Box::new(source),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl Default for PluginPolicy {
+    fn default() -> Self {
+        Self::enforce(TrustStore::new())
+    }
+}
+
+fn read_artifact(path: &Path) -> std::io::Result<(File, Vec<u8>)> {
+    let mut file = File::open(path)?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)?;
+    Ok((file, bytes))
+}
+
+/// A plugin file the policy let through, with the descriptor its bytes
+/// were read from. The loader keeps the descriptor (via
+/// [`AdmittedPlugin::into_file`]) for as long as the library is mapped.
+pub(crate) struct AdmittedPlugin {
+    file: File,
+    path: PathBuf,
+    digest: String,
+    check: Option<ArtifactCheck>,
+}
+
+impl AdmittedPlugin {
+    /// The path to hand to the dynamic loader: the open descriptor on
+    /// Linux, the verified path elsewhere.
+    pub(crate) fn load_path(&self) -> PathBuf {
+        #[cfg(target_os = "linux")]
+        {
+            use std::os::fd::AsRawFd;
+            let fd_path = PathBuf::from(format!("/proc/self/fd/{}", self.file.as_raw_fd()));
+            if fd_path.exists() {
+                return fd_path;
+            }
+        }
+        self.path.clone()
+    }
+
+    /// The path the verified bytes were read from.
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Hex SHA-256 of the verified bytes.
+    pub(crate) fn digest(&self) -> &str {
+        &self.digest
+    }
+
+    pub(crate) fn into_file(self) -> File {
+        self.file
+    }
+
+    /// The publisher that vouched for the file, or `""` if it was
+    /// loaded unverified under [`PluginVerifyMode::AuditOnly`].
+    pub(crate) fn publisher(&self) -> &str {
+        self.check.as_ref().map_or("", |c| c.publisher.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tempdir(tag: &str) -> PathBuf {
+        let mut p = std::env::temp_dir();
+        p.push(format!(
+            "confium-plugin-policy-{tag}-{}-{}",
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_nanos(),
+        ));
+        std::fs::create_dir_all(&p).unwrap();
+        p
+    }
+
+    fn store(dir: &Path) -> TrustStore {
+        TrustStore::for_home(dir.to_path_buf())
+    }
+
+    #[test]
+    fn enforce_refuses_unsigned_file() {
+        let dir = tempdir("unsigned");
+        let plugin = dir.join("libloose.so");
+        std::fs::write(&plugin, b"\x7fELF").unwrap();
+        let policy = PluginPolicy::enforce(store(&dir));
+        let err = policy
+            .admit(&AuditLogger::disabled(), "loose", &[plugin])
+            .err()
+            .expect("unsigned plugin refused");
+        assert!(matches!(
+            err,
+            error::Error::PluginVerificationFailed { ref source, .. }
+                if matches!(**source, confium_registry::Error::UnsignedPlugin { .. })
+        ));
+    }
+
+    #[test]
+    fn enforce_refuses_missing_file() {
+        let dir = tempdir("missing");
+        let policy = PluginPolicy::enforce(store(&dir));
+        let missing = dir.join("libnothing.so");
+        assert!(
+            policy
+                .admit(&AuditLogger::disabled(), "nothing", &[missing])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn audit_only_admits_unsigned_file_without_publisher() {
+        let dir = tempdir("audit");
+        let plugin = dir.join("libloose.so");
+        std::fs::write(&plugin, b"\x7fELF").unwrap();
+        let policy = PluginPolicy::audit_only(store(&dir));
+        let admitted = policy
+            .admit(&AuditLogger::disabled(), "loose", &[plugin])
+            .unwrap()
+            .expect("file exists, so it is admitted by descriptor");
+        assert_eq!(admitted.publisher(), "");
+    }
+
+    #[test]
+    fn pinned_admits_only_the_matching_digest() {
+        let dir = tempdir("pinned");
+        let plugin = dir.join("libpinned.so");
+        std::fs::write(&plugin, b"\x7fELF pinned").unwrap();
+        let digest = confium_registry::sha256_hex(b"\x7fELF pinned");
+        let admitted = PluginPolicy::pinned(digest.clone())
+            .admit(
+                &AuditLogger::disabled(),
+                "pinned",
+                std::slice::from_ref(&plugin),
+            )
+            .unwrap()
+            .expect("matching digest admitted");
+        assert_eq!(admitted.digest(), digest);
+
+        std::fs::write(&plugin, b"\x7fELF swapped").unwrap();
+        let err = PluginPolicy::pinned(digest)
+            .admit(&AuditLogger::disabled(), "pinned", &[plugin])
+            .err()
+            .expect("swapped file refused");
+        assert!(matches!(
+            err,
+            error::Error::PluginVerificationFailed { ref source, .. }
+                if matches!(**source, confium_registry::Error::HashMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn disabled_defers_to_the_loader_search() {
+        let policy = PluginPolicy::disabled();
+        let admitted = policy
+            .admit(
+                &AuditLogger::disabled(),
+                "x",
+                &[PathBuf::from("/nonexistent")],
+            )
+            .unwrap();
+        assert!(admitted.is_none());
+    }
+
+    #[test]
+    fn mode_round_trips_through_labels_and_wire_values() {
+        for mode in [
+            PluginVerifyMode::Enforce,
+            PluginVerifyMode::AuditOnly,
+            PluginVerifyMode::Disabled,
+        ] {
+            assert_eq!(mode.as_str().parse::<PluginVerifyMode>(), Ok(mode));
+            assert_eq!(PluginVerifyMode::from_u32(mode as u32), Some(mode));
+        }
+        assert!(PluginVerifyMode::from_u32(3).is_none());
+    }
+}
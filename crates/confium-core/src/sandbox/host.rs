@@ -0,0 +1,1213 @@
+//! The child side of a process-sandboxed plugin.
+//!
+//! `confium-plugin-host` runs [`serve`] over its stdin and stdout. The
+//! first request is `load`, which loads the plugin with the ordinary
+//! in-process loader; every later request is one `cfmp_*` call, decoded
+//! by the conventions in the [parent module](super) and answered with
+//! the plugin's return code and outputs.
+//!
+//! Objects the plugin creates stay in this process. The parent refers
+//! to them by the id handed out when they were created, and the table
+//! here checks that each id names an object of the interface it is
+//! used with.
+
+use std::collections::HashMap;
+use std::ffi::{CString, c_void};
+use std::io::{Read, Write};
+use std::os::raw::c_char;
+use std::ptr;
+use std::rc::Rc;
+
+use confium_sandbox_process::Value;
+use confium_sandbox_process::protocol::{Response, read_request, value_from_json, write_response};
+use serde_json::Value as JsonValue;
+
+use super::{NULL, SANDBOX_HOST_OPTION, SANDBOX_OPTION, int_of, options_from_json};
+use crate::audit::AuditLogger;
+use crate::ffi::aead::{AeadInterface, FFIAead};
+use crate::ffi::cipher::{CipherInterface, FFICipher};
+use crate::ffi::hash::{FFIHash, HashInterface};
+use crate::ffi::kdf::{FFIKdf, KdfInterface};
+use crate::ffi::kem::{FFIKemDecapsulator, FFIKemEncapsulator};
+use crate::ffi::keyfmt::{FFIKey, KeyfmtInterface};
+use crate::ffi::rng::{FFIRng, RngInterface};
+use crate::ffi::signature::{
+    FFISigner, FFIVerifier, KeypairInterface, SignatureInterface, SignerInterface,
+    VerifierInterface,
+};
+use crate::key_handle::CFMKeyHandle;
+use crate::options::Options;
+use crate::plugin_policy::PluginPolicy;
+use crate::{Confium, Plugin, Provider};
+
+/// Largest output buffer a single call may ask for, matching the
+/// protocol's frame limit: a larger answer could not be sent back.
+const MAX_OUT_BUF: i64 = 64 * 1024 * 1024;
+
+type Reply = std::result::Result<Vec<Value>, String>;
+
+/// Serve requests from `input` until the parent closes it. Returns an
+/// error only if the pipe itself fails; a bad request is answered with
+/// a protocol error and the loop carries on.
+pub fn serve<R: Read, W: Write>(
+    mut input: R,
+    mut output: W,
+) -> confium_sandbox_process::Result<()> {
+    let mut host = Host::new();
+    while let Some(request) = read_request(&mut input)? {
+        let args = request
+            .args
+            .into_iter()
+            .map(value_from_json)
+            .collect::<confium_sandbox_process::Result<Vec<_>>>();
+        let response = match args {
+            Ok(args) => match host.dispatch(&request.method, Args::new(args)) {
+                Ok(values) => Response::ok(&values),
+                Err(message) => Response::err(message),
+            },
+            Err(e) => Response::err(e.to_string()),
+        };
+        write_response(&mut output, &response)?;
+    }
+    Ok(())
+}
+
+/// Which interface an object id belongs to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Kind {
+    Hash,
+    Rng,
+    Cipher,
+    Aead,
+    Kdf,
+    Key,
+    Signer,
+    Verifier,
+    Encapsulator,
+    Decapsulator,
+}
+
+struct Host {
+    cfm: Box<Confium>,
+    /// The loaded plugin, taken out of `cfm` so it stays mapped after
+    /// `finalize` for as long as the parent may still destroy objects.
+    provider: Option<Provider>,
+    objects: HashMap<i64, (Kind, *mut c_void)>,
+    next_id: i64,
+}
+
+impl Host {
+    fn new() -> Self {
+        // The parent has already audited the load; the child would
+        // only log it twice.
+        Self {
+            cfm: Box::new(Confium::new_with_audit(AuditLogger::disabled())),
+            provider: None,
+            objects: HashMap::new(),
+            next_id: 0,
+        }
+    }
+
+    fn dispatch(&mut self, method: &str, args: Args) -> Reply {
+        match method {
+            "load" => self.load(args),
+            "finalize" => self.finalize(),
+            m if m.starts_with("hash_") => self.hash(m, args),
+            m if m.starts_with("rng_") => self.rng(m, args),
+            m if m.starts_with("cipher_") => self.cipher(m, args),
+            m if m.starts_with("aead_") => self.aead(m, args),
+            m if m.starts_with("kdf_") => self.kdf(m, args),
+            m if m.starts_with("keyfmt_") => self.keyfmt(m, args),
+            m if m.starts_with("sig_") => self.signature(m, args),
+            m if m.starts_with("kem_") => self.kem(m, args),
+            _ => Err(unknown(method)),
+        }
+    }
+
+    fn cfm(&self) -> *const Confium {
+        &*self.cfm
+    }
+
+    fn interface<T>(&self, lookup: fn(&Plugin) -> Option<Rc<T>>) -> Result<Rc<T>, String> {
+        self.provider
+            .as_ref()
+            .and_then(|p| lookup(&p.plugin))
+            .ok_or_else(|| "the plugin does not offer this interface".to_string())
+    }
+
+    /// Look up the object named by the next argument.
+    fn object<T>(&self, args: &mut Args, kind: Kind) -> Result<*mut T, String> {
+        let id = args.int()?;
+        match self.objects.get(&id) {
+            Some(&(k, obj)) if k == kind => Ok(obj as *mut T),
+            _ => Err(format!("no {kind:?} object with id {id}")),
+        }
+    }
+
+    /// Like [`Host::object`], and forget the id.
+    fn take<T>(&mut self, args: &mut Args, kind: Kind) -> Result<*mut T, String> {
+        let id = args.int()?;
+        match self.objects.remove(&id) {
+            Some((k, obj)) if k == kind => Ok(obj as *mut T),
+            Some(entry) => {
+                self.objects.insert(id, entry);
+                Err(format!("no {kind:?} object with id {id}"))
+            }
+            None => Err(format!("no {kind:?} object with id {id}")),
+        }
+    }
+
+    /// The reply value for a created object: its new id, or NULL.
+    fn created<T>(&mut self, wanted: bool, kind: Kind, obj: *mut T) -> Value {
+        if !wanted || obj.is_null() {
+            return NULL;
+        }
+        let id = self.next_id;
+        self.next_id += 1;
+        self.objects.insert(id, (kind, obj as *mut c_void));
+        Value::I64(id)
+    }
+
+    // -----------------------------------------------------------------
+    // plugin lifecycle
+    // -----------------------------------------------------------------
+
+    /// `load(name, path, digest, options)`. An empty digest means the
+    /// parent did not verify the file, so neither does the child.
+    fn load(&mut self, mut args: Args) -> Reply {
+        if self.provider.is_some() {
+            return Err("a plugin is already loaded".to_string());
+        }
+        let name = args.cstring()?.ok_or("missing plugin name")?;
+        let path = args.cstring()?.ok_or("missing plugin path")?;
+        let digest = args.bytes()?.unwrap_or_default();
+        let digest = String::from_utf8(digest).map_err(|_| "digest is not UTF-8")?;
+        let mut opts = args.options()?.unwrap_or_default();
+        opts.remove(SANDBOX_OPTION);
+        opts.remove(SANDBOX_HOST_OPTION);
+
+        self.cfm.set_plugin_policy(if digest.is_empty() {
+            PluginPolicy::disabled()
+        } else {
+            PluginPolicy::pinned(digest)
+        });
+        let loaded = crate::ffi::plugin::cfm_plugin_load_(
+            &mut *self.cfm,
+            name.as_ptr(),
+            path.as_ptr(),
+            &mut opts,
+        );
+        if let Err(e) = loaded {
+            return Ok(vec![
+                code_value(e.code()),
+                Value::Bytes(e.to_string().into_bytes()),
+            ]);
+        }
+        let provider = self.cfm.providers.pop().ok_or("plugin vanished")?;
+        let interfaces: serde_json::Map<String, JsonValue> = provider
+            .plugin
+            .interfaces
+            .iter()
+            .map(|i| (i.name.to_string(), JsonValue::from(i.version)))
+            .collect();
+        self.provider = Some(provider);
+        Ok(vec![
+            code_value(0),
+            Value::Bytes(JsonValue::Object(interfaces).to_string().into_bytes()),
+        ])
+    }
+
+    fn finalize(&mut self) -> Reply {
+        if let Some(provider) = &self.provider {
+            crate::ffi::plugin::finalize_plugin(&mut self.cfm, &provider.plugin);
+        }
+        Ok(vec![code_value(0)])
+    }
+
+    // -----------------------------------------------------------------
+    // hash
+    // -----------------------------------------------------------------
+
+    fn hash(&mut self, method: &str, mut a: Args) -> Reply {
+        let iface = self.interface(crate::ffi::hash::interface_of)?;
+        let HashInterface::V0(v0) = &*iface;
+        match method {
+            "hash_create" => {
+                let wanted = a.out_ptr()?;
+                let name = a.cstring()?;
+                let opts = a.options()?;
+                let mut obj: *mut FFIHash = ptr::null_mut();
+                let code = (*v0.create)(
+                    self.cfm(),
+                    out_arg(wanted, &mut obj),
+                    cstr_ptr(&name),
+                    opts.as_ref(),
+                );
+                Ok(vec![
+                    code_value(code),
+                    self.created(wanted, Kind::Hash, obj),
+                ])
+            }
+            "hash_output_size" | "hash_block_size" => {
+                let obj: *mut FFIHash = self.object(&mut a, Kind::Hash)?;
+                let mut out = a.in_out()?;
+                let query = if method == "hash_output_size" {
+                    &v0.output_size
+                } else {
+                    &v0.block_size
+                };
+                let code = (**query)(obj, u32_ptr(&mut out));
+                Ok(vec![code_value(code), u32_value(out)])
+            }
+            "hash_update" => {
+                let obj: *mut FFIHash = self.object(&mut a, Kind::Hash)?;
+                let data = a.bytes()?;
+                let (data, len) = bytes_ptr(&data);
+                Ok(vec![code_value((*v0.update)(obj, data, len))])
+            }
+            "hash_reset" => {
+                let obj: *mut FFIHash = self.object(&mut a, Kind::Hash)?;
+                Ok(vec![code_value((*v0.reset)(obj))])
+            }
+            "hash_clone" => {
+                let obj: *mut FFIHash = self.object(&mut a, Kind::Hash)?;
+                let wanted = a.out_ptr()?;
+                let mut dst: *mut FFIHash = ptr::null_mut();
+                let code = (*v0.clone)(obj, out_arg(wanted, &mut dst));
+                Ok(vec![
+                    code_value(code),
+                    self.created(wanted, Kind::Hash, dst),
+                ])
+            }
+            "hash_finalize" => {
+                let obj: *mut FFIHash = self.object(&mut a, Kind::Hash)?;
+                let mut out = a.out_buf()?;
+                let code = (*v0.finalize)(obj, buf_ptr(&mut out), buf_cap(&out));
+                Ok(vec![code_value(code), buf_value(out, None)])
+            }
+            "hash_destroy" => {
+                let obj: *mut FFIHash = self.take(&mut a, Kind::Hash)?;
+                (*v0.destroy)(obj);
+                Ok(vec![code_value(0)])
+            }
+            _ => Err(unknown(method)),
+        }
+    }
+
+    // -----------------------------------------------------------------
+    // rng
+    // -----------------------------------------------------------------
+
+    fn rng(&mut self, method: &str, mut a: Args) -> Reply {
+        let iface = self.interface(crate::ffi::rng::interface_of)?;
+        let RngInterface::V0(v0) = &*iface;
+        match method {
+            "rng_create" => {
+                let wanted = a.out_ptr()?;
+                let name = a.cstring()?;
+                let opts = a.options()?;
+                let mut obj: *mut FFIRng = ptr::null_mut();
+                let code = (*v0.create)(
+                    self.cfm(),
+                    out_arg(wanted, &mut obj),
+                    cstr_ptr(&name),
+                    opts.as_ref(),
+                );
+                Ok(vec![code_value(code), self.created(wanted, Kind::Rng, obj)])
+            }
+            "rng_reseed" | "rng_add_entropy" => {
+                let obj: *mut FFIRng = self.object(&mut a, Kind::Rng)?;
+                let data = a.bytes()?;
+                let (data, len) = bytes_ptr(&data);
+                let feed = if method == "rng_reseed" {
+                    &v0.reseed
+                } else {
+                    &v0.add_entropy
+                };
+                Ok(vec![code_value((**feed)(obj, data, len))])
+            }
+            "rng_generate" => {
+                let obj: *mut FFIRng = self.object(&mut a, Kind::Rng)?;
+                let mut out = a.out_buf()?;
+                let code = (*v0.generate)(obj, buf_ptr(&mut out), buf_cap(&out));
+                Ok(vec![code_value(code), buf_value(out, None)])
+            }
+            "rng_destroy" => {
+                let obj: *mut FFIRng = self.take(&mut a, Kind::Rng)?;
+                (*v0.destroy)(obj);
+                Ok(vec![code_value(0)])
+            }
+            _ => Err(unknown(method)),
+        }
+    }
+
+    // -----------------------------------------------------------------
+    // symmetric cipher
+    // -----------------------------------------------------------------
+
+    fn cipher(&mut self, method: &str, mut a: Args) -> Reply {
+        let iface = self.interface(crate::ffi::cipher::interface_of)?;
+        let CipherInterface::V0(v0) = &*iface;
+        match method {
+            "cipher_create" => {
+                let wanted = a.out_ptr()?;
+                let name = a.cstring()?;
+                let key = a.bytes()?;
+                let iv = a.bytes()?;
+                let opts = a.options()?;
+                let (key, key_len) = bytes_ptr(&key);
+                let (iv, iv_len) = bytes_ptr(&iv);
+                let mut obj: *mut FFICipher = ptr::null_mut();
+                let code = (*v0.create)(
+                    self.cfm(),
+                    out_arg(wanted, &mut obj),
+                    cstr_ptr(&name),
+                    key as *const c_void,
+                    key_len,
+                    iv as *const c_void,
+                    iv_len,
+                    opts.as_ref(),
+                );
+                Ok(vec![
+                    code_value(code),
+                    self.created(wanted, Kind::Cipher, obj),
+                ])
+            }
+            "cipher_block_size" | "cipher_key_size" | "cipher_iv_size" => {
+                let obj: *mut FFICipher = self.object(&mut a, Kind::Cipher)?;
+                let mut out = a.in_out()?;
+                let query = match method {
+                    "cipher_block_size" => &v0.block_size,
+                    "cipher_key_size" => &v0.key_size,
+                    _ => &v0.iv_size,
+                };
+                let code = (**query)(obj, u32_ptr(&mut out));
+                Ok(vec![code_value(code), u32_value(out)])
+            }
+            "cipher_update" => {
+                let obj: *mut FFICipher = self.object(&mut a, Kind::Cipher)?;
+                let input = a.bytes()?;
+                let mut out = a.out_buf()?;
+                let mut out_len = a.in_out()?;
+                let (input, input_len) = bytes_ptr(&input);
+                let code = (*v0.update)(
+                    obj,
+                    input,
+                    input_len,
+                    buf_ptr(&mut out),
+                    u32_ptr(&mut out_len),
+                );
+                Ok(vec![
+                    code_value(code),
+                    buf_value(out, out_len),
+                    u32_value(out_len),
+                ])
+            }
+            "cipher_finalize" => {
+                let obj: *mut FFICipher = self.object(&mut a, Kind::Cipher)?;
+                let mut out = a.out_buf()?;
+                let mut out_len = a.in_out()?;
+                let code =
+                    (*v0.finalize)(obj, buf_ptr(&mut out), buf_cap(&out), u32_ptr(&mut out_len));
+                Ok(vec![
+                    code_value(code),
+                    buf_value(out, out_len),
+                    u32_value(out_len),
+                ])
+            }
+            "cipher_reset" => {
+                let obj: *mut FFICipher = self.object(&mut a, Kind::Cipher)?;
+                Ok(vec![code_value((*v0.reset)(obj))])
+            }
+            "cipher_destroy" => {
+                let obj: *mut FFICipher = self.take(&mut a, Kind::Cipher)?;
+                (*v0.destroy)(obj);
+                Ok(vec![code_value(0)])
+            }
+            _ => Err(unknown(method)),
+        }
+    }
+
+    // -----------------------------------------------------------------
+    // aead
+    // -----------------------------------------------------------------
+
+    fn aead(&mut self, method: &str, mut a: Args) -> Reply {
+        let iface = self.interface(crate::ffi::aead::interface_of)?;
+        let AeadInterface::V0(v0) = &*iface;
+        match method {
+            "aead_create" => {
+                let wanted = a.out_ptr()?;
+                let name = a.cstring()?;
+                let key = a.bytes()?;
+                let opts = a.options()?;
+                let (key, key_len) = bytes_ptr(&key);
+                let mut obj: *mut FFIAead = ptr::null_mut();
+                let code = (*v0.create)(
+                    self.cfm(),
+                    out_arg(wanted, &mut obj),
+                    cstr_ptr(&name),
+                    key as *const c_void,
+                    key_len,
+                    opts.as_ref(),
+                );
+                Ok(vec![
+                    code_value(code),
+                    self.created(wanted, Kind::Aead, obj),
+                ])
+            }
+            "aead_set_nonce" | "aead_associated_data_update" | "aead_verify_tag" => {
+                let obj: *mut FFIAead = self.object(&mut a, Kind::Aead)?;
+                let data = a.bytes()?;
+                let (data, len) = bytes_ptr(&data);
+                let feed = match method {
+                    "aead_set_nonce" => &v0.set_nonce,
+                    "aead_associated_data_update" => &v0.associated_data_update,
+                    _ => &v0.verify_tag,
+                };
+                Ok(vec![code_value((**feed)(obj, data, len))])
+            }
+            "aead_encrypt_update" | "aead_decrypt_update" => {
+                let obj: *mut FFIAead = self.object(&mut a, Kind::Aead)?;
+                let input = a.bytes()?;
+                let mut out = a.out_buf()?;
+                let mut out_len = a.in_out()?;
+                let (input, input_len) = bytes_ptr(&input);
+                let update = if method == "aead_encrypt_update" {
+                    &v0.encrypt_update
+                } else {
+                    &v0.decrypt_update
+                };
+                let code = (**update)(
+                    obj,
+                    input,
+                    input_len,
+                    buf_ptr(&mut out),
+                    u32_ptr(&mut out_len),
+                );
+                Ok(vec![
+                    code_value(code),
+                    buf_value(out, out_len),
+                    u32_value(out_len),
+                ])
+            }
+            "aead_finalize" => {
+                let obj: *mut FFIAead = self.object(&mut a, Kind::Aead)?;
+                let mut out = a.out_buf()?;
+                let mut out_len = a.in_out()?;
+                let code =
+                    (*v0.finalize)(obj, buf_ptr(&mut out), buf_cap(&out), u32_ptr(&mut out_len));
+                Ok(vec![
+                    code_value(code),
+                    buf_value(out, out_len),
+                    u32_value(out_len),
+                ])
+            }
+            "aead_destroy" => {
+                let obj: *mut FFIAead = self.take(&mut a, Kind::Aead)?;
+                (*v0.destroy)(obj);
+                Ok(vec![code_value(0)])
+            }
+            _ => Err(unknown(method)),
+        }
+    }
+
+    // -----------------------------------------------------------------
+    // kdf
+    // -----------------------------------------------------------------
+
+    fn kdf(&mut self, method: &str, mut a: Args) -> Reply {
+        let iface = self.interface(crate::ffi::kdf::interface_of)?;
+        let KdfInterface::V0(v0) = &*iface;
+        match method {
+            "kdf_create" => {
+                let wanted = a.out_ptr()?;
+                let name = a.cstring()?;
+                let opts = a.options()?;
+                let mut obj: *mut FFIKdf = ptr::null_mut();
+                let code = (*v0.create)(
+                    self.cfm(),
+                    out_arg(wanted, &mut obj),
+                    cstr_ptr(&name),
+                    opts.as_ref(),
+                );
+                Ok(vec![code_value(code), self.created(wanted, Kind::Kdf, obj)])
+            }
+            "kdf_set_salt" => {
+                let obj: *mut FFIKdf = self.object(&mut a, Kind::Kdf)?;
+                let salt = a.bytes()?;
+                let (salt, len) = bytes_ptr(&salt);
+                Ok(vec![code_value((*v0.set_salt)(obj, salt, len))])
+            }
+            "kdf_set_iterations" | "kdf_set_parallelism" => {
+                let obj: *mut FFIKdf = self.object(&mut a, Kind::Kdf)?;
+                let value = a.u32()?;
+                let set = if method == "kdf_set_iterations" {
+                    &v0.set_iterations
+                } else {
+                    &v0.set_parallelism
+                };
+                Ok(vec![code_value((**set)(obj, value))])
+            }
+            "kdf_set_memory_cost" => {
+                let obj: *mut FFIKdf = self.object(&mut a, Kind::Kdf)?;
+                // Sent bit-for-bit as an i64.
+                let cost = a.int()? as u64;
+                Ok(vec![code_value((*v0.set_memory_cost)(obj, cost))])
+            }
+            "kdf_set_hash" => {
+                let obj: *mut FFIKdf = self.object(&mut a, Kind::Kdf)?;
+                let hash = a.cstring()?;
+                Ok(vec![code_value((*v0.set_hash)(obj, cstr_ptr(&hash)))])
+            }
+            "kdf_derive" => {
+                let obj: *mut FFIKdf = self.object(&mut a, Kind::Kdf)?;
+                let secret = a.bytes()?;
+                let mut out = a.out_buf()?;
+                let (secret, secret_len) = bytes_ptr(&secret);
+                let code = (*v0.derive)(obj, secret, secret_len, buf_ptr(&mut out), buf_cap(&out));
+                Ok(vec![code_value(code), buf_value(out, None)])
+            }
+            "kdf_destroy" => {
+                let obj: *mut FFIKdf = self.take(&mut a, Kind::Kdf)?;
+                (*v0.destroy)(obj);
+                Ok(vec![code_value(0)])
+            }
+            _ => Err(unknown(method)),
+        }
+    }
+
+    // -----------------------------------------------------------------
+    // keyfmt
+    // -----------------------------------------------------------------
+
+    fn keyfmt(&mut self, method: &str, mut a: Args) -> Reply {
+        let iface = self.interface(crate::ffi::keyfmt::interface_of)?;
+        let KeyfmtInterface::V0(v0) = &*iface;
+        match method {
+            "keyfmt_parse" => {
+                let wanted = a.out_ptr()?;
+                let format = a.cstring()?;
+                let algorithm = a.cstring()?;
+                let data = a.bytes()?;
+                let opts = a.options()?;
+                let (data, len) = bytes_ptr(&data);
+                let mut obj: *mut FFIKey = ptr::null_mut();
+                let code = (*v0.parse)(
+                    self.cfm(),
+                    out_arg(wanted, &mut obj),
+                    cstr_ptr(&format),
+                    cstr_ptr(&algorithm),
+                    data,
+                    len,
+                    opts.as_ref(),
+                );
+                Ok(vec![code_value(code), self.created(wanted, Kind::Key, obj)])
+            }
+            "keyfmt_serialize" => {
+                let obj: *mut FFIKey = self.object(&mut a, Kind::Key)?;
+                let format = a.cstring()?;
+                let mut out = a.out_buf()?;
+                let mut out_len = a.in_out()?;
+                let code = (*v0.serialize)(
+                    obj,
+                    cstr_ptr(&format),
+                    buf_ptr(&mut out),
+                    buf_cap(&out),
+                    u32_ptr(&mut out_len),
+                );
+                Ok(vec![
+                    code_value(code),
+                    buf_value(out, out_len),
+                    u32_value(out_len),
+                ])
+            }
+            "keyfmt_kind" => {
+                let obj: *mut FFIKey = self.object(&mut a, Kind::Key)?;
+                let mut out = a.in_out()?;
+                let code = (*v0.kind)(obj, u32_ptr(&mut out));
+                Ok(vec![code_value(code), u32_value(out)])
+            }
+            "keyfmt_algorithm" => {
+                let obj: *mut FFIKey = self.object(&mut a, Kind::Key)?;
+                let wanted = a.out_ptr()?;
+                let mut raw: *mut c_char = ptr::null_mut();
+                let code = (*v0.algorithm)(obj, out_arg(wanted, &mut raw));
+                let name = if raw.is_null() {
+                    NULL
+                } else {
+                    // The string is plugin-allocated and ours to free.
+                    let owned = unsafe { CString::from_raw(raw) };
+                    Value::Bytes(owned.into_bytes())
+                };
+                Ok(vec![code_value(code), name])
+            }
+            "keyfmt_public" => {
+                let obj: *mut FFIKey = self.object(&mut a, Kind::Key)?;
+                let wanted = a.out_ptr()?;
+                let mut dst: *mut FFIKey = ptr::null_mut();
+                let code = (*v0.public)(obj, out_arg(wanted, &mut dst));
+                Ok(vec![code_value(code), self.created(wanted, Kind::Key, dst)])
+            }
+            "keyfmt_destroy" => {
+                let obj: *mut FFIKey = self.take(&mut a, Kind::Key)?;
+                (*v0.destroy)(obj);
+                Ok(vec![code_value(0)])
+            }
+            _ => Err(unknown(method)),
+        }
+    }
+
+    // -----------------------------------------------------------------
+    // signature
+    // -----------------------------------------------------------------
+
+    fn signature(&mut self, method: &str, mut a: Args) -> Reply {
+        let iface = self.interface(crate::ffi::signature::interface_of)?;
+        let SignatureInterface {
+            signer: SignerInterface::V0(signer),
+            verifier: VerifierInterface::V0(verifier),
+            keypair: KeypairInterface::V0(keypair),
+            handle,
+        } = &*iface;
+        match method {
+            "sig_signer_create" | "sig_verifier_create" => {
+                let wanted = a.out_ptr()?;
+                let algorithm = a.cstring()?;
+                let key = a.bytes()?;
+                let opts = a.options()?;
+                let (key, key_len) = bytes_ptr(&key);
+                let key = key as *const c_void;
+                if method == "sig_signer_create" {
+                    let mut obj: *mut FFISigner = ptr::null_mut();
+                    let code = (*signer.create)(
+                        self.cfm(),
+                        out_arg(wanted, &mut obj),
+                        cstr_ptr(&algorithm),
+                        key,
+                        key_len,
+                        opts.as_ref(),
+                    );
+                    Ok(vec![
+                        code_value(code),
+                        self.created(wanted, Kind::Signer, obj),
+                    ])
+                } else {
+                    let mut obj: *mut FFIVerifier = ptr::null_mut();
+                    let code = (*verifier.create)(
+                        self.cfm(),
+                        out_arg(wanted, &mut obj),
+                        cstr_ptr(&algorithm),
+                        key,
+                        key_len,
+                        opts.as_ref(),
+                    );
+                    Ok(vec![
+                        code_value(code),
+                        self.created(wanted, Kind::Verifier, obj),
+                    ])
+                }
+            }
+            "sig_signer_set_hash" => {
+                let obj: *mut FFISigner = self.object(&mut a, Kind::Signer)?;
+                let hash = a.cstring()?;
+                Ok(vec![code_value((*signer.set_hash)(obj, cstr_ptr(&hash)))])
+            }
+            "sig_signer_update" => {
+                let obj: *mut FFISigner = self.object(&mut a, Kind::Signer)?;
+                let data = a.bytes()?;
+                let (data, len) = bytes_ptr(&data);
+                Ok(vec![code_value((*signer.update)(obj, data, len))])
+            }
+            "sig_signer_finalize" => {
+                let obj: *mut FFISigner = self.object(&mut a, Kind::Signer)?;
+                let mut out = a.out_buf()?;
+                let mut out_len = a.in_out()?;
+                let code = (*signer.finalize)(
+                    obj,
+                    buf_ptr(&mut out),
+                    buf_cap(&out),
+                    u32_ptr(&mut out_len),
+                );
+                Ok(vec![
+                    code_value(code),
+                    buf_value(out, out_len),
+                    u32_value(out_len),
+                ])
+            }
+            "sig_signer_destroy" => {
+                let obj: *mut FFISigner = self.take(&mut a, Kind::Signer)?;
+                (*signer.destroy)(obj);
+                Ok(vec![code_value(0)])
+            }
+            "sig_verifier_set_hash" => {
+                let obj: *mut FFIVerifier = self.object(&mut a, Kind::Verifier)?;
+                let hash = a.cstring()?;
+                Ok(vec![code_value((*verifier.set_hash)(obj, cstr_ptr(&hash)))])
+            }
+            "sig_verifier_update" | "sig_verifier_finalize" => {
+                let obj: *mut FFIVerifier = self.object(&mut a, Kind::Verifier)?;
+                let data = a.bytes()?;
+                let (data, len) = bytes_ptr(&data);
+                let feed = if method == "sig_verifier_update" {
+                    &verifier.update
+                } else {
+                    &verifier.finalize
+                };
+                Ok(vec![code_value((**feed)(obj, data, len))])
+            }
+            "sig_verifier_destroy" => {
+                let obj: *mut FFIVerifier = self.take(&mut a, Kind::Verifier)?;
+                (*verifier.destroy)(obj);
+                Ok(vec![code_value(0)])
+            }
+            "sig_keypair_generate" => {
+                let generate = &keypair.generate;
+                keypair_reply(
+                    a,
+                    |alg, seed, seed_len, pk, pk_max, pk_len, sk, sk_max, sk_len| {
+                        (**generate)(
+                            self.cfm(),
+                            alg,
+                            seed,
+                            seed_len,
+                            pk,
+                            pk_max,
+                            pk_len,
+                            sk,
+                            sk_max,
+                            sk_len,
+                        )
+                    },
+                )
+            }
+            "sig_handle_capabilities" => {
+                let handle = handle
+                    .as_ref()
+                    .ok_or("the plugin has no key-handle signer")?;
+                capabilities_reply(a, |alg, key, caps| (*handle.capabilities)(alg, key, caps))
+            }
+            "sig_signer_create_with_handle" => {
+                let handle = handle
+                    .as_ref()
+                    .ok_or("the plugin has no key-handle signer")?;
+                let wanted = a.out_ptr()?;
+                let algorithm = a.cstring()?;
+                let key = a.handle()?;
+                let opts = a.options()?;
+                let mut obj: *mut FFISigner = ptr::null_mut();
+                let code = (*handle.create)(
+                    self.cfm(),
+                    out_arg(wanted, &mut obj),
+                    cstr_ptr(&algorithm),
+                    handle_ptr(&key),
+                    opts.as_ref(),
+                );
+                Ok(vec![
+                    code_value(code),
+                    self.created(wanted, Kind::Signer, obj),
+                ])
+            }
+            _ => Err(unknown(method)),
+        }
+    }
+
+    // -----------------------------------------------------------------
+    // kem
+    // -----------------------------------------------------------------
+
+    fn kem(&mut self, method: &str, mut a: Args) -> Reply {
+        let iface = self.interface(crate::ffi::kem::interface_of)?;
+        let v0 = iface.base();
+        match method {
+            "kem_encapsulator_create" | "kem_decapsulator_create" => {
+                let wanted = a.out_ptr()?;
+                let algorithm = a.cstring()?;
+                let key = a.bytes()?;
+                let opts = a.options()?;
+                let (key, key_len) = bytes_ptr(&key);
+                let key = key as *const c_void;
+                if method == "kem_encapsulator_create" {
+                    let mut obj: *mut FFIKemEncapsulator = ptr::null_mut();
+                    let code = (*v0.encapsulator_create)(
+                        self.cfm(),
+                        out_arg(wanted, &mut obj),
+                        cstr_ptr(&algorithm),
+                        key,
+                        key_len,
+                        opts.as_ref(),
+                    );
+                    Ok(vec![
+                        code_value(code),
+                        self.created(wanted, Kind::Encapsulator, obj),
+                    ])
+                } else {
+                    let mut obj: *mut FFIKemDecapsulator = ptr::null_mut();
+                    let code = (*v0.decapsulator_create)(
+                        self.cfm(),
+                        out_arg(wanted, &mut obj),
+                        cstr_ptr(&algorithm),
+                        key,
+                        key_len,
+                        opts.as_ref(),
+                    );
+                    Ok(vec![
+                        code_value(code),
+                        self.created(wanted, Kind::Decapsulator, obj),
+                    ])
+                }
+            }
+            "kem_encapsulate" => {
+                let obj: *mut FFIKemEncapsulator = self.object(&mut a, Kind::Encapsulator)?;
+                let mut ct = a.out_buf()?;
+                let mut ct_len = a.in_out()?;
+                let mut ss = a.out_buf()?;
+                let mut ss_len = a.in_out()?;
+                let code = (*v0.encapsulate)(
+                    obj,
+                    buf_ptr(&mut ct),
+                    buf_cap(&ct),
+                    u32_ptr(&mut ct_len),
+                    buf_ptr(&mut ss),
+                    buf_cap(&ss),
+                    u32_ptr(&mut ss_len),
+                );
+                Ok(vec![
+                    code_value(code),
+                    buf_value(ct, ct_len),
+                    u32_value(ct_len),
+                    buf_value(ss, ss_len),
+                    u32_value(ss_len),
+                ])
+            }
+            "kem_decapsulate" => {
+                let obj: *mut FFIKemDecapsulator = self.object(&mut a, Kind::Decapsulator)?;
+                let ct = a.bytes()?;
+                let mut ss = a.out_buf()?;
+                let mut ss_len = a.in_out()?;
+                let (ct, ct_len) = bytes_ptr(&ct);
+                let code = (*v0.decapsulate)(
+                    obj,
+                    ct,
+                    ct_len,
+                    buf_ptr(&mut ss),
+                    buf_cap(&ss),
+                    u32_ptr(&mut ss_len),
+                );
+                Ok(vec![
+                    code_value(code),
+                    buf_value(ss, ss_len),
+                    u32_value(ss_len),
+                ])
+            }
+            "kem_encapsulator_destroy" => {
+                let obj: *mut FFIKemEncapsulator = self.take(&mut a, Kind::Encapsulator)?;
+                (*v0.encapsulator_destroy)(obj);
+                Ok(vec![code_value(0)])
+            }
+            "kem_decapsulator_destroy" => {
+                let obj: *mut FFIKemDecapsulator = self.take(&mut a, Kind::Decapsulator)?;
+                (*v0.decapsulator_destroy)(obj);
+                Ok(vec![code_value(0)])
+            }
+            "kem_shared_secret_size" => {
+                let algorithm = a.cstring()?;
+                let mut out = a.in_out()?;
+                let code =
+                    (*v0.shared_secret_size)(self.cfm(), cstr_ptr(&algorithm), u32_ptr(&mut out));
+                Ok(vec![code_value(code), u32_value(out)])
+            }
+            "kem_keypair_generate" => {
+                let generate = &v0.keypair_generate;
+                keypair_reply(
+                    a,
+                    |alg, seed, seed_len, pk, pk_max, pk_len, sk, sk_max, sk_len| {
+                        (**generate)(
+                            self.cfm(),
+                            alg,
+                            seed,
+                            seed_len,
+                            pk,
+                            pk_max,
+                            pk_len,
+                            sk,
+                            sk_max,
+                            sk_len,
+                        )
+                    },
+                )
+            }
+            "kem_handle_capabilities" => {
+                let handle = iface
+                    .handle()
+                    .ok_or("the plugin has no key-handle decapsulator")?;
+                capabilities_reply(a, |alg, key, caps| (*handle.capabilities)(alg, key, caps))
+            }
+            "kem_decapsulator_create_with_handle" => {
+                let handle = iface
+                    .handle()
+                    .ok_or("the plugin has no key-handle decapsulator")?;
+                let wanted = a.out_ptr()?;
+                let algorithm = a.cstring()?;
+                let key = a.handle()?;
+                let opts = a.options()?;
+                let mut obj: *mut FFIKemDecapsulator = ptr::null_mut();
+                let code = (*handle.decapsulator_create)(
+                    self.cfm(),
+                    out_arg(wanted, &mut obj),
+                    cstr_ptr(&algorithm),
+                    handle_ptr(&key),
+                    opts.as_ref(),
+                );
+                Ok(vec![
+                    code_value(code),
+                    self.created(wanted, Kind::Decapsulator, obj),
+                ])
+            }
+            _ => Err(unknown(method)),
+        }
+    }
+}
+
+/// Keypair generation, shared by the signature and KEM interfaces:
+/// `(alg, seed, pk_cap, pk_len, sk_cap, sk_len)`.
+#[allow(clippy::type_complexity)]
+fn keypair_reply(
+    mut a: Args,
+    generate: impl FnOnce(
+        *const c_char,
+        *const u8,
+        u32,
+        *mut u8,
+        u32,
+        *mut u32,
+        *mut u8,
+        u32,
+        *mut u32,
+    ) -> u32,
+) -> Reply {
+    let algorithm = a.cstring()?;
+    let seed = a.bytes()?;
+    let mut pk = a.out_buf()?;
+    let mut pk_len = a.in_out()?;
+    let mut sk = a.out_buf()?;
+    let mut sk_len = a.in_out()?;
+    let (seed, seed_len) = bytes_ptr(&seed);
+    let code = generate(
+        cstr_ptr(&algorithm),
+        seed,
+        seed_len,
+        buf_ptr(&mut pk),
+        buf_cap(&pk),
+        u32_ptr(&mut pk_len),
+        buf_ptr(&mut sk),
+        buf_cap(&sk),
+        u32_ptr(&mut sk_len),
+    );
+    Ok(vec![
+        code_value(code),
+        buf_value(pk, pk_len),
+        u32_value(pk_len),
+        buf_value(sk, sk_len),
+        u32_value(sk_len),
+    ])
+}
+
+/// Key-handle capability queries: `(alg, handle, caps)`.
+fn capabilities_reply(
+    mut a: Args,
+    query: impl FnOnce(*const c_char, *const CFMKeyHandle, *mut u32) -> u32,
+) -> Reply {
+    let algorithm = a.cstring()?;
+    let key = a.handle()?;
+    let mut caps = a.in_out()?;
+    let code = query(cstr_ptr(&algorithm), handle_ptr(&key), u32_ptr(&mut caps));
+    Ok(vec![code_value(code), u32_value(caps)])
+}
+
+fn unknown(method: &str) -> String {
+    format!("unknown method '{method}'")
+}
+
+// ---------------------------------------------------------------------
+// Argument decoding
+// ---------------------------------------------------------------------
+
+/// The arguments of one request, consumed in order.
+struct Args {
+    values: std::vec::IntoIter<Value>,
+}
+
+impl Args {
+    fn new(values: Vec<Value>) -> Self {
+        Self {
+            values: values.into_iter(),
+        }
+    }
+
+    fn value(&mut self) -> Result<Value, String> {
+        self.values
+            .next()
+            .ok_or_else(|| "missing argument".to_string())
+    }
+
+    fn int(&mut self) -> Result<i64, String> {
+        int_of(&self.value()?).ok_or_else(|| "expected an integer argument".to_string())
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        u32::try_from(self.int()?).map_err(|_| "integer argument out of range".to_string())
+    }
+
+    /// Bytes, or `None` for the NULL marker.
+    fn bytes(&mut self) -> Result<Option<Vec<u8>>, String> {
+        match self.value()? {
+            Value::Bytes(b) => Ok(Some(b)),
+            v if int_of(&v) == Some(-1) => Ok(None),
+            _ => Err("expected a bytes argument".to_string()),
+        }
+    }
+
+    fn cstring(&mut self) -> Result<Option<CString>, String> {
+        self.bytes()?
+            .map(CString::new)
+            .transpose()
+            .map_err(|_| "string argument contains NUL".to_string())
+    }
+
+    fn options(&mut self) -> Result<Option<Options>, String> {
+        let Some(bytes) = self.bytes()? else {
+            return Ok(None);
+        };
+        let json: JsonValue =
+            serde_json::from_slice(&bytes).map_err(|e| format!("malformed options: {e}"))?;
+        options_from_json(&json)
+            .map(Some)
+            .ok_or_else(|| "malformed options".to_string())
+    }
+
+    /// An output buffer of the capacity the caller gave, or `None` if
+    /// the caller passed NULL.
+    fn out_buf(&mut self) -> Result<Option<Vec<u8>>, String> {
+        match self.int()? {
+            -1 => Ok(None),
+            cap @ 0..=MAX_OUT_BUF => Ok(Some(vec![0; cap as usize])),
+            cap => Err(format!("output buffer of {cap} bytes refused")),
+        }
+    }
+
+    /// The value behind a `uint32_t*`, or `None` for NULL.
+    fn in_out(&mut self) -> Result<Option<u32>, String> {
+        match self.int()? {
+            -1 => Ok(None),
+            v => u32::try_from(v)
+                .map(Some)
+                .map_err(|_| "in/out argument out of range".to_string()),
+        }
+    }
+
+    /// Whether the caller passed an object out-pointer.
+    fn out_ptr(&mut self) -> Result<bool, String> {
+        Ok(self.int()? != -1)
+    }
+
+    fn handle(&mut self) -> Result<Option<WireHandle>, String> {
+        let Some(bytes) = self.bytes()? else {
+            return Ok(None);
+        };
+        let json: JsonValue =
+            serde_json::from_slice(&bytes).map_err(|e| format!("malformed key handle: {e}"))?;
+        let field = |name: &str| -> Result<Option<CString>, String> {
+            match json.get(name) {
+                None | Some(JsonValue::Null) => Ok(None),
+                Some(JsonValue::String(s)) => CString::new(s.as_str())
+                    .map(Some)
+                    .map_err(|_| format!("key handle {name} contains NUL")),
+                Some(_) => Err(format!("key handle {name} is not a string")),
+            }
+        };
+        let backend = field("backend")?;
+        let key_id = field("key_id")?;
+        let raw = CFMKeyHandle {
+            backend: cstr_ptr(&backend),
+            key_id: cstr_ptr(&key_id),
+            native: ptr::null_mut(),
+        };
+        Ok(Some(WireHandle {
+            _backend: backend,
+            _key_id: key_id,
+            raw,
+        }))
+    }
+}
+
+/// A key handle rebuilt from its JSON form. `native` does not survive
+/// the trip and is always NULL. `raw` points into the two strings,
+/// which are held alongside it.
+struct WireHandle {
+    _backend: Option<CString>,
+    _key_id: Option<CString>,
+    raw: CFMKeyHandle,
+}
+
+fn handle_ptr(handle: &Option<WireHandle>) -> *const CFMKeyHandle {
+    handle.as_ref().map_or(ptr::null(), |h| &h.raw)
+}
+
+fn cstr_ptr(s: &Option<CString>) -> *const c_char {
+    s.as_ref().map_or(ptr::null(), |s| s.as_ptr())
+}
+
+fn bytes_ptr(b: &Option<Vec<u8>>) -> (*const u8, u32) {
+    match b {
+        None => (ptr::null(), 0),
+        Some(b) => (b.as_ptr(), b.len() as u32),
+    }
+}
+
+fn buf_ptr(b: &mut Option<Vec<u8>>) -> *mut u8 {
+    b.as_mut().map_or(ptr::null_mut(), |b| b.as_mut_ptr())
+}
+
+fn buf_cap(b: &Option<Vec<u8>>) -> u32 {
+    b.as_ref().map_or(0, |b| b.len() as u32)
+}
+
+fn u32_ptr(v: &mut Option<u32>) -> *mut u32 {
+    v.as_mut().map_or(ptr::null_mut(), |v| v as *mut u32)
+}
+
+fn out_arg<T>(wanted: bool, obj: &mut *mut T) -> *mut *mut T {
+    if wanted {
+        obj as *mut *mut T
+    } else {
+        ptr::null_mut()
+    }
+}
+
+// ---------------------------------------------------------------------
+// Reply encoding
+// ---------------------------------------------------------------------
+
+fn code_value(code: u32) -> Value {
+    Value::I64(i64::from(code))
+}
+
+fn u32_value(v: Option<u32>) -> Value {
+    v.map_or(NULL, |v| Value::I64(i64::from(v)))
+}
+
+/// An output buffer, cut to the length the plugin reported if it
+/// reports one. A reported length beyond the buffer (the plugin asking
+/// for more room) leaves it whole.
+fn buf_value(buf: Option<Vec<u8>>, len: Option<u32>) -> Value {
+    match buf {
+        None => NULL,
+        Some(mut b) => {
+            if let Some(len) = len {
+                b.truncate(len as usize);
+            }
+            Value::Bytes(b)
+        }
+    }
+}
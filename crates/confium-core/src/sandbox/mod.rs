@@ -0,0 +1,410 @@
+//! Running plugins out of process or as WebAssembly.
+//!
+//! Loading a plugin with the option `sandbox = "process"` starts a
+//! `confium-plugin-host` subprocess, which loads the plugin's cdylib
+//! with the ordinary loader and serves its interfaces over the
+//! `confium-sandbox-process` JSON-RPC protocol. The host side installs
+//! proxy interfaces ([`remote`]) in place of the plugin's symbols, so
+//! [`crate::hash::Hash`], [`crate::signature::Signer`] and the rest
+//! work unchanged on a sandboxed provider.
+//!
+//! A plugin that crashes takes only its subprocess down. Every later
+//! call on that provider, including calls on objects it created,
+//! fails with `PLUGIN_SANDBOX_FAILED`; the caller's process carries on.
+//!
+//! Verification still happens here, before the subprocess is spawned:
+//! the [`PluginPolicy`](crate::plugin_policy::PluginPolicy) checks the
+//! file as usual, and the child loads it pinned to the digest the
+//! policy accepted.
+//!
+//! The host binary is found, in order, from the `sandbox_host` load
+//! option, the `CONFIUM_PLUGIN_HOST` environment variable, a
+//! `confium-plugin-host` next to the current executable, and `PATH`.
+//!
+//! # Wire conventions
+//!
+//! Method names are the plugin's C symbol names without the `cfmp_`
+//! prefix (`hash_create`, `sig_signer_finalize`, ...). Every reply is
+//! the plugin's return code followed by its outputs. Arguments map as:
+//!
+//! - any NULL pointer: `-1`;
+//! - input buffers and C strings: bytes;
+//! - an output buffer: its capacity, answered with the bytes written;
+//! - a `uint32_t*`: its current value, answered with its final value;
+//! - an object out-pointer: `0`, answered with an object id or `-1`;
+//! - plugin objects: their id;
+//! - options and key handles: JSON bytes.
+//!
+//! The `Confium*` argument is not forwarded; the child passes its own.
+//! A key handle loses its `native` pointer, which has no meaning in
+//! another address space.
+//!
+//! # WebAssembly plugins
+//!
+//! With the `wasm` feature, `sandbox = "wasm"` loads the file as a
+//! plugin component instead (see [`wasm`]). The component runs in
+//! this process under wasmtime and is reached through the same proxy
+//! interfaces: `confium-sandbox-wasm` answers the conventions above.
+
+pub mod host;
+mod remote;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+use confium_sandbox_process::{ProcessSandbox, Value};
+use serde_json::Value as JsonValue;
+
+use crate::error::{self, Error};
+use crate::options::{OptionValue, Options};
+use crate::plugin_policy::AdmittedPlugin;
+use crate::{Plugin, PluginInterface, Result};
+
+use remote::Channel;
+
+/// Load option that selects the sandbox: `"process"`, or `"wasm"`
+/// when built with the `wasm` feature.
+pub const SANDBOX_OPTION: &str = "sandbox";
+
+/// Load option naming the host binary to run the plugin in.
+pub const SANDBOX_HOST_OPTION: &str = "sandbox_host";
+
+/// Environment variable consulted when `sandbox_host` is not given.
+pub const PLUGIN_HOST_ENV: &str = "CONFIUM_PLUGIN_HOST";
+
+const PLUGIN_HOST_NAME: &str = "confium-plugin-host";
+
+/// How many sandboxed plugins may be loaded at once in one process.
+/// Proxies for calls that carry no object (create, keypair generation)
+/// are monomorphized per slot, so the table is fixed-size.
+const MAX_SLOTS: usize = 16;
+
+static SLOTS: Mutex<[Option<Arc<Channel>>; MAX_SLOTS]> = Mutex::new([const { None }; MAX_SLOTS]);
+
+/// Marker for a NULL pointer argument or an absent output.
+pub(crate) const NULL: Value = Value::I32(-1);
+
+/// The vtable of a sandboxed plugin: the slot its channel lives in.
+/// Dropping it releases the slot; the subprocess (or WASM instance)
+/// goes away once the last object created through it is destroyed as
+/// well.
+pub struct ProcessPlugin {
+    slot: usize,
+}
+
+impl ProcessPlugin {
+    /// Ask the sandbox to run the plugin's `cfmp_finalize`. A dead
+    /// child has nothing left to finalize, so failures are ignored.
+    pub(crate) fn finalize(&self) {
+        if let Some(channel) = channel(self.slot) {
+            let _ = channel.invoke("finalize", &[]);
+        }
+    }
+}
+
+impl Drop for ProcessPlugin {
+    fn drop(&mut self) {
+        if let Ok(mut slots) = SLOTS.lock() {
+            slots[self.slot] = None;
+        }
+    }
+}
+
+fn channel(slot: usize) -> Option<Arc<Channel>> {
+    SLOTS.lock().ok()?.get(slot)?.clone()
+}
+
+fn claim_slot(channel: Arc<Channel>) -> Option<usize> {
+    let mut slots = SLOTS.lock().ok()?;
+    let slot = slots.iter().position(Option::is_none)?;
+    slots[slot] = Some(channel);
+    Some(slot)
+}
+
+#[cfg(feature = "wasm")]
+const SANDBOX_KINDS: &str = "sandbox = \"process\" or \"wasm\"";
+#[cfg(not(feature = "wasm"))]
+const SANDBOX_KINDS: &str = "sandbox = \"process\" (built without the wasm feature)";
+
+/// A sandbox request parsed from the load options.
+pub(crate) enum SandboxRequest {
+    /// `sandbox = "process"`, with the `sandbox_host` override if any.
+    Process { host: Option<PathBuf> },
+    /// `sandbox = "wasm"`, with the limits its options asked for.
+    #[cfg(feature = "wasm")]
+    Wasm(confium_sandbox_wasm::ComponentLimits),
+}
+
+/// Read the sandbox options. `Ok(None)` means load in process.
+pub(crate) fn requested(opts: Option<&Options>) -> Result<Option<SandboxRequest>> {
+    let Some(opts) = opts else {
+        return Ok(None);
+    };
+    match opts.get(SANDBOX_OPTION) {
+        None => return Ok(None),
+        Some(OptionValue::String(kind)) if kind == "process" => {}
+        #[cfg(feature = "wasm")]
+        Some(OptionValue::String(kind)) if kind == "wasm" => {
+            return wasm::limits(opts).map(|limits| Some(SandboxRequest::Wasm(limits)));
+        }
+        Some(_) => {
+            return error::WrongTypeSnafu {
+                expected: SANDBOX_KINDS,
+            }
+            .fail();
+        }
+    }
+    let host = match opts.get(SANDBOX_HOST_OPTION) {
+        None => None,
+        Some(OptionValue::String(path)) => Some(PathBuf::from(path)),
+        Some(_) => {
+            return error::WrongTypeSnafu {
+                expected: "sandbox_host path string",
+            }
+            .fail();
+        }
+    };
+    Ok(Some(SandboxRequest::Process { host }))
+}
+
+/// Load the plugin `name` in the sandbox `request` names.
+pub(crate) fn load_sandboxed(
+    name: &str,
+    path: &Path,
+    admitted: Option<AdmittedPlugin>,
+    request: &SandboxRequest,
+    opts: Option<&Options>,
+) -> Result<Plugin> {
+    match request {
+        SandboxRequest::Process { host } => {
+            load_process_plugin(name, path, admitted, host.as_deref(), opts)
+        }
+        #[cfg(feature = "wasm")]
+        SandboxRequest::Wasm(limits) => wasm::load_wasm_plugin(name, path, admitted, *limits, opts),
+    }
+}
+
+/// The host binary to run: `host` if given, else the environment
+/// variable, a sibling of the current executable, or `PATH`.
+fn host_binary(host: Option<&Path>) -> PathBuf {
+    if let Some(host) = host {
+        return host.to_path_buf();
+    }
+    if let Some(host) = std::env::var_os(PLUGIN_HOST_ENV) {
+        return PathBuf::from(host);
+    }
+    let file_name = format!("{PLUGIN_HOST_NAME}{}", std::env::consts::EXE_SUFFIX);
+    if let Ok(exe) = std::env::current_exe() {
+        let sibling = exe.with_file_name(&file_name);
+        if sibling.is_file() {
+            return sibling;
+        }
+    }
+    PathBuf::from(file_name)
+}
+
+/// Start a host subprocess for the plugin `name` and build a [`Plugin`]
+/// whose interfaces forward to it. `admitted` is the policy's verdict
+/// on the file: when present the child loads that file pinned to its
+/// digest; when absent (verification disabled) the child searches
+/// `path` the way the in-process loader does.
+fn load_process_plugin(
+    name: &str,
+    path: &Path,
+    admitted: Option<AdmittedPlugin>,
+    host: Option<&Path>,
+    opts: Option<&Options>,
+) -> Result<Plugin> {
+    let failed = |reason: String| Error::PluginSandboxFailed {
+        name: name.to_string(),
+        reason,
+    };
+
+    let mut forwarded = opts.cloned().unwrap_or_default();
+    forwarded.remove(SANDBOX_OPTION);
+    forwarded.remove(SANDBOX_HOST_OPTION);
+    let (path, digest) = match &admitted {
+        Some(admitted) => (admitted.path(), admitted.digest()),
+        None => (path, ""),
+    };
+
+    let host = host_binary(host);
+    let mut command = Command::new(&host);
+    // stderr inherits so plugin diagnostics stay visible.
+    command.stderr(Stdio::inherit());
+    let instance = ProcessSandbox::spawn_command(command)
+        .map_err(|e| failed(format!("cannot start {}: {e}", host.display())))?;
+    let channel = Arc::new(Channel::new(instance));
+
+    let reply = channel
+        .invoke(
+            "load",
+            &[
+                Value::Bytes(name.as_bytes().to_vec()),
+                Value::Bytes(path.to_string_lossy().into_owned().into_bytes()),
+                Value::Bytes(digest.as_bytes().to_vec()),
+                Value::Bytes(options_to_json(&forwarded).to_string().into_bytes()),
+            ],
+        )
+        .map_err(|_| failed("host exited while loading the plugin".to_string()))?;
+    let code = reply.code();
+    let payload = reply.into_bytes().unwrap_or_default();
+    if code != 0 {
+        return Err(failed(format!(
+            "host could not load the plugin (code {code}): {}",
+            String::from_utf8_lossy(&payload)
+        )));
+    }
+    let advertised: HashMap<String, u8> = serde_json::from_slice(&payload)
+        .map_err(|e| failed(format!("malformed interface list from host: {e}")))?;
+    proxy_plugin(name, channel, advertised.into_iter().collect())
+}
+
+/// Build the [`Plugin`] for a sandbox reached through `channel`, with a
+/// proxy for each `(wire name, version)` it advertised.
+fn proxy_plugin(
+    name: &str,
+    channel: Arc<Channel>,
+    advertised: Vec<(String, u8)>,
+) -> Result<Plugin> {
+    let slot = claim_slot(channel).ok_or_else(|| Error::PluginSandboxFailed {
+        name: name.to_string(),
+        reason: format!("at most {MAX_SLOTS} sandboxed plugins may be loaded"),
+    })?;
+    let vtable = crate::ffi::plugin::PluginVTable::Process(ProcessPlugin { slot });
+    let interfaces = advertised
+        .iter()
+        .filter_map(|(iface, version)| remote::interface(iface, *version, slot))
+        .map(|(name, version, inner)| PluginInterface {
+            name,
+            version,
+            inner,
+        })
+        .collect();
+    Ok(Plugin {
+        library: Rc::new(this_library(name)?),
+        vtable,
+        interfaces,
+        artifact: None,
+    })
+}
+
+/// A sandboxed plugin has no library in this process. Its `Plugin`
+/// holds a handle to the running program instead so the field keeps
+/// its meaning for everything that clones it.
+#[cfg(unix)]
+fn this_library(_name: &str) -> Result<libloading::Library> {
+    Ok(libloading::os::unix::Library::this().into())
+}
+
+#[cfg(windows)]
+fn this_library(name: &str) -> Result<libloading::Library> {
+    use snafu::ResultExt;
+    libloading::os::windows::Library::this()
+        .map(Into::into)
+        .context(error::PluginLoadFailedSnafu { name })
+}
+
+/// Encode options as a JSON object: strings stay strings, `U32` values
+/// become numbers and nested options become nested objects.
+pub(crate) fn options_to_json(opts: &Options) -> JsonValue {
+    JsonValue::Object(
+        opts.iter()
+            .map(|(k, v)| {
+                let v = match v {
+                    OptionValue::String(s) => JsonValue::from(s.as_str()),
+                    OptionValue::U32(n) => JsonValue::from(*n),
+                    OptionValue::Options(inner) => options_to_json(inner),
+                };
+                (k.clone(), v)
+            })
+            .collect(),
+    )
+}
+
+/// Inverse of [`options_to_json`]. `None` if `json` is not an object
+/// of that shape.
+pub(crate) fn options_from_json(json: &JsonValue) -> Option<Options> {
+    let mut opts = Options::new();
+    for (k, v) in json.as_object()? {
+        let v = match v {
+            JsonValue::String(s) => OptionValue::String(s.clone()),
+            JsonValue::Number(n) => OptionValue::U32(u32::try_from(n.as_u64()?).ok()?),
+            JsonValue::Object(_) => OptionValue::Options(Box::new(options_from_json(v)?)),
+            _ => return None,
+        };
+        opts.insert(k.clone(), v);
+    }
+    Some(opts)
+}
+
+/// Integer view of a wire value. JSON does not keep integer width, so
+/// both `I32` and `I64` are accepted.
+pub(crate) fn int_of(v: &Value) -> Option<i64> {
+    match v {
+        Value::I32(x) => Some(i64::from(*x)),
+        Value::I64(x) => Some(*x),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn options_round_trip_through_json() {
+        let mut inner = Options::new();
+        inner.insert("rounds".to_string(), OptionValue::U32(12));
+        let mut opts = Options::new();
+        opts.insert("mode".to_string(), OptionValue::String("gcm".to_string()));
+        opts.insert("inner".to_string(), OptionValue::Options(Box::new(inner)));
+        let back = options_from_json(&options_to_json(&opts)).expect("decodes");
+        assert!(back == opts);
+    }
+
+    #[test]
+    fn options_from_json_rejects_other_shapes() {
+        assert!(options_from_json(&serde_json::json!([1])).is_none());
+        assert!(options_from_json(&serde_json::json!({"n": -1})).is_none());
+        assert!(options_from_json(&serde_json::json!({"b": true})).is_none());
+    }
+
+    #[test]
+    fn sandbox_option_is_optional_and_checked() {
+        assert!(requested(None).unwrap().is_none());
+        let mut opts = Options::new();
+        assert!(requested(Some(&opts)).unwrap().is_none());
+
+        opts.insert(
+            SANDBOX_OPTION.to_string(),
+            OptionValue::String("process".to_string()),
+        );
+        opts.insert(
+            SANDBOX_HOST_OPTION.to_string(),
+            OptionValue::String("/opt/confium/host".to_string()),
+        );
+        let request = requested(Some(&opts)).unwrap().expect("sandbox requested");
+        let SandboxRequest::Process { host } = request else {
+            panic!("process sandbox requested");
+        };
+        assert_eq!(
+            host_binary(host.as_deref()),
+            PathBuf::from("/opt/confium/host")
+        );
+
+        opts.insert(
+            SANDBOX_OPTION.to_string(),
+            OptionValue::String("container".to_string()),
+        );
+        let err = requested(Some(&opts))
+            .err()
+            .expect("unknown sandbox refused");
+        assert_eq!(err.code(), error::ErrorCode::WRONG_TYPE as u32);
+    }
+}
@@ -0,0 +1,1200 @@
+//! Host-side proxies for a sandboxed plugin.
+//!
+//! Each `extern "C"` function here has the signature of the `cfmp_*`
+//! symbol it stands in for and forwards the call to the plugin's
+//! sandbox: its subprocess, or its WASM instance. Objects the plugin creates are represented in this
+//! process by a boxed [`RemoteObject`], cast to the interface's opaque
+//! pointer type, which names the channel and the child's object id.
+//!
+//! Calls that take no object (create, keypair generation, capability
+//! queries) cannot find their channel through an argument, so they are
+//! instantiated once per slot of the table in the parent module.
+
+// The proxies take `Option<&Options>` exactly as the plugin symbols
+// do; the pointee is only ever dereferenced on this side.
+#![allow(improper_ctypes_definitions)]
+
+use std::any::Any;
+use std::ffi::{CStr, CString, c_void};
+use std::os::raw::c_char;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+use confium_sandbox_process::{ProcessInstance, SandboxInstance, Value};
+
+use super::{NULL, channel, int_of, options_to_json};
+use crate::Confium;
+use crate::error::ErrorCode;
+use crate::ffi::aead::*;
+use crate::ffi::cipher::*;
+use crate::ffi::hash::*;
+use crate::ffi::kdf::*;
+use crate::ffi::kem::*;
+use crate::ffi::keyfmt::*;
+use crate::ffi::rng::*;
+use crate::ffi::signature::*;
+use crate::key_handle::CFMKeyHandle;
+use crate::options::Options;
+
+const SANDBOX_FAILED: u32 = ErrorCode::PLUGIN_SANDBOX_FAILED as u32;
+const NULL_POINTER: u32 = ErrorCode::NULL_POINTER as u32;
+
+/// What a channel talks to.
+enum Transport {
+    Process(ProcessInstance),
+    #[cfg(feature = "wasm")]
+    Wasm(Box<confium_sandbox_wasm::PluginInstance>),
+}
+
+/// The pipe to one sandboxed plugin. Calls are serialized: the
+/// protocol is strictly one request, one response.
+pub(crate) struct Channel {
+    instance: Mutex<Transport>,
+}
+
+impl Channel {
+    pub(super) fn new(instance: ProcessInstance) -> Self {
+        Self {
+            instance: Mutex::new(Transport::Process(instance)),
+        }
+    }
+
+    #[cfg(feature = "wasm")]
+    pub(super) fn wasm(instance: confium_sandbox_wasm::PluginInstance) -> Self {
+        Self {
+            instance: Mutex::new(Transport::Wasm(Box::new(instance))),
+        }
+    }
+
+    /// Run `method` in the sandbox. `Err` carries the code to hand back
+    /// to the caller when the child is gone, the guest trapped, or the
+    /// answer was out of protocol.
+    pub(super) fn invoke(&self, method: &str, args: &[Value]) -> Result<Reply, u32> {
+        let mut transport = self.instance.lock().map_err(|_| SANDBOX_FAILED)?;
+        let values = match &mut *transport {
+            Transport::Process(instance) => instance.call(method, args).ok(),
+            #[cfg(feature = "wasm")]
+            Transport::Wasm(instance) => super::wasm::call(instance, method, args),
+        };
+        let mut values = values.ok_or(SANDBOX_FAILED)?.into_iter();
+        let code = values
+            .next()
+            .as_ref()
+            .and_then(int_of)
+            .and_then(|c| u32::try_from(c).ok())
+            .ok_or(SANDBOX_FAILED)?;
+        Ok(Reply { code, values })
+    }
+}
+
+/// The plugin's return code and the outputs that follow it.
+pub(super) struct Reply {
+    code: u32,
+    values: std::vec::IntoIter<Value>,
+}
+
+impl Reply {
+    pub(super) fn code(&self) -> u32 {
+        self.code
+    }
+
+    /// The first output as bytes, for replies that carry one payload.
+    pub(super) fn into_bytes(mut self) -> Option<Vec<u8>> {
+        self.bytes()?
+    }
+
+    fn int(&mut self) -> Option<i64> {
+        int_of(&self.values.next()?)
+    }
+
+    /// Next output as bytes; `Some(None)` for the NULL marker.
+    fn bytes(&mut self) -> Option<Option<Vec<u8>>> {
+        match self.values.next()? {
+            Value::Bytes(b) => Some(Some(b)),
+            v if int_of(&v) == Some(-1) => Some(None),
+            _ => None,
+        }
+    }
+
+    /// Copy an output buffer into `dst`, which holds `cap` bytes.
+    fn write_buf(&mut self, dst: *mut u8, cap: u32) -> Option<()> {
+        let bytes = self.bytes()?;
+        let (false, Some(bytes)) = (dst.is_null(), bytes) else {
+            return Some(());
+        };
+        if bytes.len() > cap as usize {
+            return None;
+        }
+        unsafe { std::ptr::copy_nonoverlapping(bytes.as_ptr(), dst, bytes.len()) };
+        Some(())
+    }
+
+    /// Store a `uint32_t` output through `dst`.
+    fn write_u32(&mut self, dst: *mut u32) -> Option<()> {
+        let value = self.int()?;
+        if !dst.is_null() {
+            unsafe { *dst = u32::try_from(value).ok()? };
+        }
+        Some(())
+    }
+
+    /// Store a created object through `dst` as a [`RemoteObject`].
+    fn write_object<T>(&mut self, dst: *mut *mut T, channel: &Arc<Channel>) -> Option<()> {
+        let id = self.int()?;
+        if !dst.is_null() {
+            let obj = if id < 0 {
+                std::ptr::null_mut()
+            } else {
+                Box::into_raw(Box::new(RemoteObject {
+                    channel: Arc::clone(channel),
+                    id,
+                })) as *mut T
+            };
+            unsafe { *dst = obj };
+        }
+        Some(())
+    }
+}
+
+/// Return the plugin's code once `scatter` has stored every output, or
+/// the sandbox failure code if the call or the reply was bad.
+fn finish(reply: Result<Reply, u32>, scatter: impl FnOnce(&mut Reply) -> Option<()>) -> u32 {
+    match reply {
+        Err(code) => code,
+        Ok(mut reply) => match scatter(&mut reply) {
+            Some(()) => reply.code,
+            None => SANDBOX_FAILED,
+        },
+    }
+}
+
+fn code_only(reply: Result<Reply, u32>) -> u32 {
+    finish(reply, |_| Some(()))
+}
+
+/// A plugin object living in the child, as seen by this process.
+struct RemoteObject {
+    channel: Arc<Channel>,
+    id: i64,
+}
+
+impl RemoteObject {
+    fn invoke(&self, method: &str, mut args: Vec<Value>) -> Result<Reply, u32> {
+        args.insert(0, Value::I64(self.id));
+        self.channel.invoke(method, &args)
+    }
+}
+
+fn remote<'a, T>(obj: *const T) -> Option<&'a RemoteObject> {
+    unsafe { (obj as *const RemoteObject).as_ref() }
+}
+
+/// Free the local half of an object and ask the child to destroy its
+/// half. If the child is gone there is nothing left to destroy.
+fn release<T>(obj: *mut T, method: &str) {
+    if obj.is_null() {
+        return;
+    }
+    let obj = unsafe { Box::from_raw(obj as *mut RemoteObject) };
+    let _ = obj.invoke(method, Vec::new());
+}
+
+// ---------------------------------------------------------------------
+// Argument encoding
+// ---------------------------------------------------------------------
+
+fn bytes_arg(ptr: *const u8, len: u32) -> Value {
+    if ptr.is_null() {
+        return NULL;
+    }
+    Value::Bytes(unsafe { std::slice::from_raw_parts(ptr, len as usize) }.to_vec())
+}
+
+fn cstr_arg(ptr: *const c_char) -> Value {
+    if ptr.is_null() {
+        return NULL;
+    }
+    Value::Bytes(unsafe { CStr::from_ptr(ptr) }.to_bytes().to_vec())
+}
+
+fn opts_arg(opts: Option<&Options>) -> Value {
+    opts.map_or(NULL, |o| {
+        Value::Bytes(options_to_json(o).to_string().into_bytes())
+    })
+}
+
+fn out_buf_arg(ptr: *mut u8, cap: u32) -> Value {
+    if ptr.is_null() {
+        NULL
+    } else {
+        Value::I64(i64::from(cap))
+    }
+}
+
+fn in_out_arg(ptr: *mut u32) -> Value {
+    if ptr.is_null() {
+        NULL
+    } else {
+        Value::I64(i64::from(unsafe { *ptr }))
+    }
+}
+
+fn out_ptr_arg<T>(ptr: *mut *mut T) -> Value {
+    if ptr.is_null() { NULL } else { Value::I32(0) }
+}
+
+fn handle_arg(handle: *const CFMKeyHandle) -> Value {
+    let Some(handle) = (unsafe { handle.as_ref() }) else {
+        return NULL;
+    };
+    let text = |p: *const c_char| {
+        (!p.is_null()).then(|| unsafe { CStr::from_ptr(p) }.to_string_lossy().into_owned())
+    };
+    let json = serde_json::json!({
+        "backend": text(handle.backend),
+        "key_id": text(handle.key_id),
+    });
+    Value::Bytes(json.to_string().into_bytes())
+}
+
+/// The capacity of an update-style output buffer, whose size is the
+/// incoming value of its length pointer.
+fn in_out_cap(len: *mut u32) -> u32 {
+    if len.is_null() { 0 } else { unsafe { *len } }
+}
+
+fn slot_channel<const S: usize>() -> Result<Arc<Channel>, u32> {
+    channel(S).ok_or(SANDBOX_FAILED)
+}
+
+/// Forward a call that creates an object, through the channel in slot `S`.
+fn create<const S: usize, T>(method: &str, out: *mut *mut T, args: Vec<Value>) -> u32 {
+    let channel = match slot_channel::<S>() {
+        Ok(channel) => channel,
+        Err(code) => return code,
+    };
+    let mut full = vec![out_ptr_arg(out)];
+    full.extend(args);
+    finish(channel.invoke(method, &full), |r| {
+        r.write_object(out, &channel)
+    })
+}
+
+/// Forward a call that takes no object, through the channel in slot `S`.
+fn slot_invoke<const S: usize>(method: &str, args: &[Value]) -> Result<Reply, u32> {
+    slot_channel::<S>()?.invoke(method, args)
+}
+
+// ---------------------------------------------------------------------
+// Shared shapes
+// ---------------------------------------------------------------------
+
+/// `(obj, const uint8_t*, uint32_t)` calls.
+fn feed<T>(method: &str, obj: *const T, data: *const u8, len: u32) -> u32 {
+    let Some(obj) = remote(obj) else {
+        return NULL_POINTER;
+    };
+    code_only(obj.invoke(method, vec![bytes_arg(data, len)]))
+}
+
+/// `(obj, uint32_t*)` size queries.
+fn query<T>(method: &str, obj: *const T, out: *mut u32) -> u32 {
+    let Some(obj) = remote(obj) else {
+        return NULL_POINTER;
+    };
+    finish(obj.invoke(method, vec![in_out_arg(out)]), |r| {
+        r.write_u32(out)
+    })
+}
+
+/// `(obj)` calls.
+fn bare<T>(method: &str, obj: *const T) -> u32 {
+    let Some(obj) = remote(obj) else {
+        return NULL_POINTER;
+    };
+    code_only(obj.invoke(method, Vec::new()))
+}
+
+/// `(obj, const char*)` calls.
+fn named<T>(method: &str, obj: *const T, name: *const c_char) -> u32 {
+    let Some(obj) = remote(obj) else {
+        return NULL_POINTER;
+    };
+    code_only(obj.invoke(method, vec![cstr_arg(name)]))
+}
+
+/// `(obj, in, in_len, out, uint32_t* out_len)` streaming updates.
+fn transform<T>(
+    method: &str,
+    obj: *const T,
+    input: *const u8,
+    input_len: u32,
+    out: *mut u8,
+    out_len: *mut u32,
+) -> u32 {
+    let Some(obj) = remote(obj) else {
+        return NULL_POINTER;
+    };
+    let cap = in_out_cap(out_len);
+    let args = vec![
+        bytes_arg(input, input_len),
+        out_buf_arg(out, cap),
+        in_out_arg(out_len),
+    ];
+    finish(obj.invoke(method, args), |r| {
+        r.write_buf(out, cap)?;
+        r.write_u32(out_len)
+    })
+}
+
+/// `(obj, out, out_max, uint32_t* out_len)` finalizers.
+fn drain<T>(method: &str, obj: *const T, out: *mut u8, out_max: u32, out_len: *mut u32) -> u32 {
+    let Some(obj) = remote(obj) else {
+        return NULL_POINTER;
+    };
+    let args = vec![out_buf_arg(out, out_max), in_out_arg(out_len)];
+    finish(obj.invoke(method, args), |r| {
+        r.write_buf(out, out_max)?;
+        r.write_u32(out_len)
+    })
+}
+
+/// `(obj, out, out_len)` fixed-size outputs.
+fn fill<T>(method: &str, obj: *const T, out: *mut u8, len: u32) -> u32 {
+    let Some(obj) = remote(obj) else {
+        return NULL_POINTER;
+    };
+    finish(obj.invoke(method, vec![out_buf_arg(out, len)]), |r| {
+        r.write_buf(out, len)
+    })
+}
+
+/// Keypair generation: `(alg, seed, seed_len, pk, pk_max, pk_len, sk,
+/// sk_max, sk_len)`, shared by the signature and KEM interfaces.
+#[allow(clippy::too_many_arguments)]
+fn keypair<const S: usize>(
+    method: &str,
+    algorithm: *const c_char,
+    seed: *const u8,
+    seed_len: u32,
+    pk: *mut u8,
+    pk_max: u32,
+    pk_len: *mut u32,
+    sk: *mut u8,
+    sk_max: u32,
+    sk_len: *mut u32,
+) -> u32 {
+    let args = [
+        cstr_arg(algorithm),
+        bytes_arg(seed, seed_len),
+        out_buf_arg(pk, pk_max),
+        in_out_arg(pk_len),
+        out_buf_arg(sk, sk_max),
+        in_out_arg(sk_len),
+    ];
+    finish(slot_invoke::<S>(method, &args), |r| {
+        r.write_buf(pk, pk_max)?;
+        r.write_u32(pk_len)?;
+        r.write_buf(sk, sk_max)?;
+        r.write_u32(sk_len)
+    })
+}
+
+fn capabilities<const S: usize>(
+    method: &str,
+    algorithm: *const c_char,
+    handle: *const CFMKeyHandle,
+    caps: *mut u32,
+) -> u32 {
+    let args = [cstr_arg(algorithm), handle_arg(handle), in_out_arg(caps)];
+    finish(slot_invoke::<S>(method, &args), |r| r.write_u32(caps))
+}
+
+/// Destroy symbols are declared as returning `c_void`; plugins return
+/// nothing. Give the proxy the declared type the same way.
+macro_rules! destroy_fn {
+    ($ffi:ty, $alias:ty, $method:literal) => {{
+        extern "C" fn destroy(obj: *mut $ffi) {
+            release(obj, $method);
+        }
+        let f: extern "C" fn(*mut $ffi) = destroy;
+        unsafe { std::mem::transmute::<extern "C" fn(*mut $ffi), $alias>(f) }
+    }};
+}
+
+/// Pick the instantiation of `$f` for a runtime slot index.
+macro_rules! slotted {
+    ($f:ident, $ty:ty, $slot:expr) => {
+        match $slot {
+            0 => $f::<0> as $ty,
+            1 => $f::<1> as $ty,
+            2 => $f::<2> as $ty,
+            3 => $f::<3> as $ty,
+            4 => $f::<4> as $ty,
+            5 => $f::<5> as $ty,
+            6 => $f::<6> as $ty,
+            7 => $f::<7> as $ty,
+            8 => $f::<8> as $ty,
+            9 => $f::<9> as $ty,
+            10 => $f::<10> as $ty,
+            11 => $f::<11> as $ty,
+            12 => $f::<12> as $ty,
+            13 => $f::<13> as $ty,
+            14 => $f::<14> as $ty,
+            15 => $f::<15> as $ty,
+            _ => unreachable!("sandbox slot out of range"),
+        }
+    };
+}
+
+// ---------------------------------------------------------------------
+// hash
+// ---------------------------------------------------------------------
+
+extern "C" fn hash_create<const S: usize>(
+    _cfm: *const Confium,
+    out: *mut *mut FFIHash,
+    name: *const c_char,
+    opts: Option<&Options>,
+) -> u32 {
+    create::<S, _>("hash_create", out, vec![cstr_arg(name), opts_arg(opts)])
+}
+
+extern "C" fn hash_output_size(obj: *const FFIHash, out: *mut u32) -> u32 {
+    query("hash_output_size", obj, out)
+}
+
+extern "C" fn hash_block_size(obj: *const FFIHash, out: *mut u32) -> u32 {
+    query("hash_block_size", obj, out)
+}
+
+extern "C" fn hash_update(obj: *mut FFIHash, data: *const u8, len: u32) -> u32 {
+    feed("hash_update", obj, data, len)
+}
+
+extern "C" fn hash_reset(obj: *mut FFIHash) -> u32 {
+    bare("hash_reset", obj)
+}
+
+extern "C" fn hash_clone(obj: *mut FFIHash, dst: *mut *mut FFIHash) -> u32 {
+    let Some(src) = remote(obj) else {
+        return NULL_POINTER;
+    };
+    finish(src.invoke("hash_clone", vec![out_ptr_arg(dst)]), |r| {
+        r.write_object(dst, &src.channel)
+    })
+}
+
+extern "C" fn hash_finalize(obj: *mut FFIHash, out: *mut u8, len: u32) -> u32 {
+    fill("hash_finalize", obj, out, len)
+}
+
+fn hash_interface(slot: usize) -> HashInterface {
+    HashInterface::V0(HashInterfaceV0 {
+        create: Box::new(slotted!(hash_create, HashCreateFnV0, slot)),
+        output_size: Box::new(hash_output_size),
+        block_size: Box::new(hash_block_size),
+        update: Box::new(hash_update),
+        reset: Box::new(hash_reset),
+        clone: Box::new(hash_clone),
+        finalize: Box::new(hash_finalize),
+        destroy: Box::new(destroy_fn!(FFIHash, HashDestroyFnV0, "hash_destroy")),
+    })
+}
+
+// ---------------------------------------------------------------------
+// rng
+// ---------------------------------------------------------------------
+
+extern "C" fn rng_create<const S: usize>(
+    _cfm: *const Confium,
+    out: *mut *mut FFIRng,
+    algorithm: *const c_char,
+    opts: Option<&Options>,
+) -> u32 {
+    create::<S, _>("rng_create", out, vec![cstr_arg(algorithm), opts_arg(opts)])
+}
+
+extern "C" fn rng_reseed(obj: *mut FFIRng, data: *const u8, len: u32) -> u32 {
+    feed("rng_reseed", obj, data, len)
+}
+
+extern "C" fn rng_add_entropy(obj: *mut FFIRng, data: *const u8, len: u32) -> u32 {
+    feed("rng_add_entropy", obj, data, len)
+}
+
+extern "C" fn rng_generate(obj: *mut FFIRng, out: *mut u8, len: u32) -> u32 {
+    fill("rng_generate", obj, out, len)
+}
+
+fn rng_interface(slot: usize) -> RngInterface {
+    RngInterface::V0(RngInterfaceV0 {
+        create: Box::new(slotted!(rng_create, RngCreateFnV0, slot)),
+        reseed: Box::new(rng_reseed),
+        add_entropy: Box::new(rng_add_entropy),
+        generate: Box::new(rng_generate),
+        destroy: Box::new(destroy_fn!(FFIRng, RngDestroyFnV0, "rng_destroy")),
+    })
+}
+
+// ---------------------------------------------------------------------
+// symmetric cipher
+// ---------------------------------------------------------------------
+
+#[allow(clippy::too_many_arguments)]
+extern "C" fn cipher_create<const S: usize>(
+    _cfm: *const Confium,
+    out: *mut *mut FFICipher,
+    name: *const c_char,
+    key: *const c_void,
+    key_len: u32,
+    iv: *const c_void,
+    iv_len: u32,
+    opts: Option<&Options>,
+) -> u32 {
+    let args = vec![
+        cstr_arg(name),
+        bytes_arg(key as *const u8, key_len),
+        bytes_arg(iv as *const u8, iv_len),
+        opts_arg(opts),
+    ];
+    create::<S, _>("cipher_create", out, args)
+}
+
+extern "C" fn cipher_block_size(obj: *const FFICipher, out: *mut u32) -> u32 {
+    query("cipher_block_size", obj, out)
+}
+
+extern "C" fn cipher_key_size(obj: *const FFICipher, out: *mut u32) -> u32 {
+    query("cipher_key_size", obj, out)
+}
+
+extern "C" fn cipher_iv_size(obj: *const FFICipher, out: *mut u32) -> u32 {
+    query("cipher_iv_size", obj, out)
+}
+
+extern "C" fn cipher_update(
+    obj: *mut FFICipher,
+    input: *const u8,
+    input_len: u32,
+    out: *mut u8,
+    out_len: *mut u32,
+) -> u32 {
+    transform("cipher_update", obj, input, input_len, out, out_len)
+}
+
+extern "C" fn cipher_finalize(obj: *mut FFICipher, out: *mut u8, max: u32, len: *mut u32) -> u32 {
+    drain("cipher_finalize", obj, out, max, len)
+}
+
+extern "C" fn cipher_reset(obj: *mut FFICipher) -> u32 {
+    bare("cipher_reset", obj)
+}
+
+fn cipher_interface(slot: usize) -> CipherInterface {
+    CipherInterface::V0(CipherInterfaceV0 {
+        create: Box::new(slotted!(cipher_create, CipherCreateFnV0, slot)),
+        block_size: Box::new(cipher_block_size),
+        key_size: Box::new(cipher_key_size),
+        iv_size: Box::new(cipher_iv_size),
+        update: Box::new(cipher_update),
+        finalize: Box::new(cipher_finalize),
+        reset: Box::new(cipher_reset),
+        destroy: Box::new(destroy_fn!(FFICipher, CipherDestroyFnV0, "cipher_destroy")),
+    })
+}
+
+// ---------------------------------------------------------------------
+// aead
+// ---------------------------------------------------------------------
+
+extern "C" fn aead_create<const S: usize>(
+    _cfm: *const Confium,
+    out: *mut *mut FFIAead,
+    name: *const c_char,
+    key: *const c_void,
+    key_len: u32,
+    opts: Option<&Options>,
+) -> u32 {
+    let args = vec![
+        cstr_arg(name),
+        bytes_arg(key as *const u8, key_len),
+        opts_arg(opts),
+    ];
+    create::<S, _>("aead_create", out, args)
+}
+
+extern "C" fn aead_set_nonce(obj: *mut FFIAead, data: *const u8, len: u32) -> u32 {
+    feed("aead_set_nonce", obj, data, len)
+}
+
+extern "C" fn aead_associated_data_update(obj: *mut FFIAead, data: *const u8, len: u32) -> u32 {
+    feed("aead_associated_data_update", obj, data, len)
+}
+
+extern "C" fn aead_encrypt_update(
+    obj: *mut FFIAead,
+    input: *const u8,
+    input_len: u32,
+    out: *mut u8,
+    out_len: *mut u32,
+) -> u32 {
+    transform("aead_encrypt_update", obj, input, input_len, out, out_len)
+}
+
+extern "C" fn aead_decrypt_update(
+    obj: *mut FFIAead,
+    input: *const u8,
+    input_len: u32,
+    out: *mut u8,
+    out_len: *mut u32,
+) -> u32 {
+    transform("aead_decrypt_update", obj, input, input_len, out, out_len)
+}
+
+extern "C" fn aead_finalize(obj: *mut FFIAead, out: *mut u8, max: u32, len: *mut u32) -> u32 {
+    drain("aead_finalize", obj, out, max, len)
+}
+
+extern "C" fn aead_verify_tag(obj: *mut FFIAead, tag: *const u8, len: u32) -> u32 {
+    feed("aead_verify_tag", obj, tag, len)
+}
+
+fn aead_interface(slot: usize) -> AeadInterface {
+    AeadInterface::V0(AeadInterfaceV0 {
+        create: Box::new(slotted!(aead_create, AeadCreateFnV0, slot)),
+        set_nonce: Box::new(aead_set_nonce),
+        associated_data_update: Box::new(aead_associated_data_update),
+        encrypt_update: Box::new(aead_encrypt_update),
+        decrypt_update: Box::new(aead_decrypt_update),
+        finalize: Box::new(aead_finalize),
+        verify_tag: Box::new(aead_verify_tag),
+        destroy: Box::new(destroy_fn!(FFIAead, AeadDestroyFnV0, "aead_destroy")),
+    })
+}
+
+// ---------------------------------------------------------------------
+// kdf
+// ---------------------------------------------------------------------
+
+extern "C" fn kdf_create<const S: usize>(
+    _cfm: *const Confium,
+    out: *mut *mut FFIKdf,
+    name: *const c_char,
+    opts: Option<&Options>,
+) -> u32 {
+    create::<S, _>("kdf_create", out, vec![cstr_arg(name), opts_arg(opts)])
+}
+
+extern "C" fn kdf_set_salt(obj: *mut FFIKdf, salt: *const u8, len: u32) -> u32 {
+    feed("kdf_set_salt", obj, salt, len)
+}
+
+fn kdf_set_number(method: &str, obj: *mut FFIKdf, value: i64) -> u32 {
+    let Some(obj) = remote(obj) else {
+        return NULL_POINTER;
+    };
+    code_only(obj.invoke(method, vec![Value::I64(value)]))
+}
+
+extern "C" fn kdf_set_iterations(obj: *mut FFIKdf, iterations: u32) -> u32 {
+    kdf_set_number("kdf_set_iterations", obj, i64::from(iterations))
+}
+
+extern "C" fn kdf_set_memory_cost(obj: *mut FFIKdf, cost: u64) -> u32 {
+    // Bit-preserving; the child casts back.
+    kdf_set_number("kdf_set_memory_cost", obj, cost as i64)
+}
+
+extern "C" fn kdf_set_parallelism(obj: *mut FFIKdf, lanes: u32) -> u32 {
+    kdf_set_number("kdf_set_parallelism", obj, i64::from(lanes))
+}
+
+extern "C" fn kdf_set_hash(obj: *mut FFIKdf, hash: *const c_char) -> u32 {
+    named("kdf_set_hash", obj, hash)
+}
+
+extern "C" fn kdf_derive(
+    obj: *mut FFIKdf,
+    secret: *const u8,
+    secret_len: u32,
+    out: *mut u8,
+    out_len: u32,
+) -> u32 {
+    let Some(obj) = remote(obj) else {
+        return NULL_POINTER;
+    };
+    let args = vec![bytes_arg(secret, secret_len), out_buf_arg(out, out_len)];
+    finish(obj.invoke("kdf_derive", args), |r| {
+        r.write_buf(out, out_len)
+    })
+}
+
+fn kdf_interface(slot: usize) -> KdfInterface {
+    KdfInterface::V0(KdfInterfaceV0 {
+        create: Box::new(slotted!(kdf_create, KdfCreateFnV0, slot)),
+        set_salt: Box::new(kdf_set_salt),
+        set_iterations: Box::new(kdf_set_iterations),
+        set_memory_cost: Box::new(kdf_set_memory_cost),
+        set_parallelism: Box::new(kdf_set_parallelism),
+        set_hash: Box::new(kdf_set_hash),
+        derive: Box::new(kdf_derive),
+        destroy: Box::new(destroy_fn!(FFIKdf, KdfDestroyFnV0, "kdf_destroy")),
+    })
+}
+
+// ---------------------------------------------------------------------
+// keyfmt
+// ---------------------------------------------------------------------
+
+#[allow(clippy::too_many_arguments)]
+extern "C" fn keyfmt_parse<const S: usize>(
+    _cfm: *const Confium,
+    out: *mut *mut FFIKey,
+    format: *const c_char,
+    algorithm: *const c_char,
+    data: *const u8,
+    len: u32,
+    opts: Option<&Options>,
+) -> u32 {
+    let args = vec![
+        cstr_arg(format),
+        cstr_arg(algorithm),
+        bytes_arg(data, len),
+        opts_arg(opts),
+    ];
+    create::<S, _>("keyfmt_parse", out, args)
+}
+
+extern "C" fn keyfmt_serialize(
+    obj: *const FFIKey,
+    format: *const c_char,
+    out: *mut u8,
+    max: u32,
+    len: *mut u32,
+) -> u32 {
+    let Some(obj) = remote(obj) else {
+        return NULL_POINTER;
+    };
+    let args = vec![cstr_arg(format), out_buf_arg(out, max), in_out_arg(len)];
+    finish(obj.invoke("keyfmt_serialize", args), |r| {
+        r.write_buf(out, max)?;
+        r.write_u32(len)
+    })
+}
+
+extern "C" fn keyfmt_kind(obj: *const FFIKey, out: *mut u32) -> u32 {
+    query("keyfmt_kind", obj, out)
+}
+
+/// The host frees the returned string the way it frees a plugin's, so
+/// it is allocated as a plugin would allocate it.
+extern "C" fn keyfmt_algorithm(obj: *const FFIKey, out: *mut *mut c_char) -> u32 {
+    let Some(obj) = remote(obj) else {
+        return NULL_POINTER;
+    };
+    finish(
+        obj.invoke("keyfmt_algorithm", vec![out_ptr_arg(out)]),
+        |r| {
+            let name = r.bytes()?;
+            if !out.is_null() {
+                let raw = match name {
+                    Some(name) => CString::new(name).ok()?.into_raw(),
+                    None => std::ptr::null_mut(),
+                };
+                unsafe { *out = raw };
+            }
+            Some(())
+        },
+    )
+}
+
+extern "C" fn keyfmt_public(obj: *const FFIKey, dst: *mut *mut FFIKey) -> u32 {
+    let Some(src) = remote(obj) else {
+        return NULL_POINTER;
+    };
+    finish(src.invoke("keyfmt_public", vec![out_ptr_arg(dst)]), |r| {
+        r.write_object(dst, &src.channel)
+    })
+}
+
+fn keyfmt_interface(slot: usize) -> KeyfmtInterface {
+    KeyfmtInterface::V0(KeyfmtInterfaceV0 {
+        parse: Box::new(slotted!(keyfmt_parse, KeyfmtParseFnV0, slot)),
+        serialize: Box::new(keyfmt_serialize),
+        kind: Box::new(keyfmt_kind),
+        algorithm: Box::new(keyfmt_algorithm),
+        public: Box::new(keyfmt_public),
+        destroy: Box::new(destroy_fn!(FFIKey, KeyfmtDestroyFnV0, "keyfmt_destroy")),
+    })
+}
+
+// ---------------------------------------------------------------------
+// signature
+// ---------------------------------------------------------------------
+
+extern "C" fn sig_signer_create<const S: usize>(
+    _cfm: *const Confium,
+    out: *mut *mut FFISigner,
+    algorithm: *const c_char,
+    key: *const c_void,
+    key_len: u32,
+    opts: Option<&Options>,
+) -> u32 {
+    let args = vec![
+        cstr_arg(algorithm),
+        bytes_arg(key as *const u8, key_len),
+        opts_arg(opts),
+    ];
+    create::<S, _>("sig_signer_create", out, args)
+}
+
+extern "C" fn sig_signer_set_hash(obj: *mut FFISigner, hash: *const c_char) -> u32 {
+    named("sig_signer_set_hash", obj, hash)
+}
+
+extern "C" fn sig_signer_update(obj: *mut FFISigner, data: *const u8, len: u32) -> u32 {
+    feed("sig_signer_update", obj, data, len)
+}
+
+extern "C" fn sig_signer_finalize(
+    obj: *mut FFISigner,
+    out: *mut u8,
+    max: u32,
+    len: *mut u32,
+) -> u32 {
+    drain("sig_signer_finalize", obj, out, max, len)
+}
+
+extern "C" fn sig_verifier_create<const S: usize>(
+    _cfm: *const Confium,
+    out: *mut *mut FFIVerifier,
+    algorithm: *const c_char,
+    key: *const c_void,
+    key_len: u32,
+    opts: Option<&Options>,
+) -> u32 {
+    let args = vec![
+        cstr_arg(algorithm),
+        bytes_arg(key as *const u8, key_len),
+        opts_arg(opts),
+    ];
+    create::<S, _>("sig_verifier_create", out, args)
+}
+
+extern "C" fn sig_verifier_set_hash(obj: *mut FFIVerifier, hash: *const c_char) -> u32 {
+    named("sig_verifier_set_hash", obj, hash)
+}
+
+extern "C" fn sig_verifier_update(obj: *mut FFIVerifier, data: *const u8, len: u32) -> u32 {
+    feed("sig_verifier_update", obj, data, len)
+}
+
+extern "C" fn sig_verifier_finalize(obj: *mut FFIVerifier, sig: *const u8, len: u32) -> u32 {
+    feed("sig_verifier_finalize", obj, sig, len)
+}
+
+#[allow(clippy::too_many_arguments)]
+extern "C" fn sig_keypair_generate<const S: usize>(
+    _cfm: *const Confium,
+    algorithm: *const c_char,
+    seed: *const u8,
+    seed_len: u32,
+    pk: *mut u8,
+    pk_max: u32,
+    pk_len: *mut u32,
+    sk: *mut u8,
+    sk_max: u32,
+    sk_len: *mut u32,
+) -> u32 {
+    keypair::<S>(
+        "sig_keypair_generate",
+        algorithm,
+        seed,
+        seed_len,
+        pk,
+        pk_max,
+        pk_len,
+        sk,
+        sk_max,
+        sk_len,
+    )
+}
+
+extern "C" fn sig_handle_capabilities<const S: usize>(
+    algorithm: *const c_char,
+    handle: *const CFMKeyHandle,
+    caps: *mut u32,
+) -> u32 {
+    capabilities::<S>("sig_handle_capabilities", algorithm, handle, caps)
+}
+
+extern "C" fn sig_signer_create_with_handle<const S: usize>(
+    _cfm: *const Confium,
+    out: *mut *mut FFISigner,
+    algorithm: *const c_char,
+    handle: *const CFMKeyHandle,
+    opts: Option<&Options>,
+) -> u32 {
+    let args = vec![cstr_arg(algorithm), handle_arg(handle), opts_arg(opts)];
+    create::<S, _>("sig_signer_create_with_handle", out, args)
+}
+
+fn signature_interface(slot: usize, with_handles: bool) -> SignatureInterface {
+    SignatureInterface {
+        signer: SignerInterface::V0(SignerInterfaceV0 {
+            create: Box::new(slotted!(sig_signer_create, SigSignerCreateFnV0, slot)),
+            set_hash: Box::new(sig_signer_set_hash),
+            update: Box::new(sig_signer_update),
+            finalize: Box::new(sig_signer_finalize),
+            destroy: Box::new(destroy_fn!(
+                FFISigner,
+                SigSignerDestroyFnV0,
+                "sig_signer_destroy"
+            )),
+        }),
+        verifier: VerifierInterface::V0(VerifierInterfaceV0 {
+            create: Box::new(slotted!(sig_verifier_create, SigVerifierCreateFnV0, slot)),
+            set_hash: Box::new(sig_verifier_set_hash),
+            update: Box::new(sig_verifier_update),
+            finalize: Box::new(sig_verifier_finalize),
+            destroy: Box::new(destroy_fn!(
+                FFIVerifier,
+                SigVerifierDestroyFnV0,
+                "sig_verifier_destroy"
+            )),
+        }),
+        keypair: KeypairInterface::V0(KeypairInterfaceV0 {
+            generate: Box::new(slotted!(sig_keypair_generate, SigKeypairGenerateFnV0, slot)),
+        }),
+        handle: with_handles.then(|| SignerHandleInterfaceV1 {
+            capabilities: Box::new(slotted!(
+                sig_handle_capabilities,
+                SigHandleCapabilitiesFnV1,
+                slot
+            )),
+            create: Box::new(slotted!(
+                sig_signer_create_with_handle,
+                SigSignerCreateWithHandleFnV1,
+                slot
+            )),
+        }),
+    }
+}
+
+// ---------------------------------------------------------------------
+// kem
+// ---------------------------------------------------------------------
+
+extern "C" fn kem_encapsulator_create<const S: usize>(
+    _cfm: *const Confium,
+    out: *mut *mut FFIKemEncapsulator,
+    algorithm: *const c_char,
+    key: *const c_void,
+    key_len: u32,
+    opts: Option<&Options>,
+) -> u32 {
+    let args = vec![
+        cstr_arg(algorithm),
+        bytes_arg(key as *const u8, key_len),
+        opts_arg(opts),
+    ];
+    create::<S, _>("kem_encapsulator_create", out, args)
+}
+
+extern "C" fn kem_encapsulate(
+    obj: *mut FFIKemEncapsulator,
+    ct: *mut u8,
+    ct_max: u32,
+    ct_len: *mut u32,
+    ss: *mut u8,
+    ss_max: u32,
+    ss_len: *mut u32,
+) -> u32 {
+    let Some(obj) = remote(obj) else {
+        return NULL_POINTER;
+    };
+    let args = vec![
+        out_buf_arg(ct, ct_max),
+        in_out_arg(ct_len),
+        out_buf_arg(ss, ss_max),
+        in_out_arg(ss_len),
+    ];
+    finish(obj.invoke("kem_encapsulate", args), |r| {
+        r.write_buf(ct, ct_max)?;
+        r.write_u32(ct_len)?;
+        r.write_buf(ss, ss_max)?;
+        r.write_u32(ss_len)
+    })
+}
+
+extern "C" fn kem_decapsulator_create<const S: usize>(
+    _cfm: *const Confium,
+    out: *mut *mut FFIKemDecapsulator,
+    algorithm: *const c_char,
+    key: *const c_void,
+    key_len: u32,
+    opts: Option<&Options>,
+) -> u32 {
+    let args = vec![
+        cstr_arg(algorithm),
+        bytes_arg(key as *const u8, key_len),
+        opts_arg(opts),
+    ];
+    create::<S, _>("kem_decapsulator_create", out, args)
+}
+
+extern "C" fn kem_decapsulate(
+    obj: *mut FFIKemDecapsulator,
+    ct: *const u8,
+    ct_len: u32,
+    ss: *mut u8,
+    ss_max: u32,
+    ss_len: *mut u32,
+) -> u32 {
+    let Some(obj) = remote(obj) else {
+        return NULL_POINTER;
+    };
+    let args = vec![
+        bytes_arg(ct, ct_len),
+        out_buf_arg(ss, ss_max),
+        in_out_arg(ss_len),
+    ];
+    finish(obj.invoke("kem_decapsulate", args), |r| {
+        r.write_buf(ss, ss_max)?;
+        r.write_u32(ss_len)
+    })
+}
+
+extern "C" fn kem_shared_secret_size<const S: usize>(
+    _cfm: *const Confium,
+    algorithm: *const c_char,
+    out: *mut u32,
+) -> u32 {
+    let args = [cstr_arg(algorithm), in_out_arg(out)];
+    finish(slot_invoke::<S>("kem_shared_secret_size", &args), |r| {
+        r.write_u32(out)
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+extern "C" fn kem_keypair_generate<const S: usize>(
+    _cfm: *const Confium,
+    algorithm: *const c_char,
+    seed: *const u8,
+    seed_len: u32,
+    pk: *mut u8,
+    pk_max: u32,
+    pk_len: *mut u32,
+    sk: *mut u8,
+    sk_max: u32,
+    sk_len: *mut u32,
+) -> u32 {
+    keypair::<S>(
+        "kem_keypair_generate",
+        algorithm,
+        seed,
+        seed_len,
+        pk,
+        pk_max,
+        pk_len,
+        sk,
+        sk_max,
+        sk_len,
+    )
+}
+
+extern "C" fn kem_handle_capabilities<const S: usize>(
+    algorithm: *const c_char,
+    handle: *const CFMKeyHandle,
+    caps: *mut u32,
+) -> u32 {
+    capabilities::<S>("kem_handle_capabilities", algorithm, handle, caps)
+}
+
+extern "C" fn kem_decapsulator_create_with_handle<const S: usize>(
+    _cfm: *const Confium,
+    out: *mut *mut FFIKemDecapsulator,
+    algorithm: *const c_char,
+    handle: *const CFMKeyHandle,
+    opts: Option<&Options>,
+) -> u32 {
+    let args = vec![cstr_arg(algorithm), handle_arg(handle), opts_arg(opts)];
+    create::<S, _>("kem_decapsulator_create_with_handle", out, args)
+}
+
+fn kem_interface(slot: usize, with_handles: bool) -> KemInterface {
+    let base = KemInterfaceV0 {
+        encapsulator_create: Box::new(slotted!(
+            kem_encapsulator_create,
+            KemEncapsulatorCreateFnV0,
+            slot
+        )),
+        encapsulate: Box::new(kem_encapsulate),
+        encapsulator_destroy: Box::new(destroy_fn!(
+            FFIKemEncapsulator,
+            KemEncapsulatorDestroyFnV0,
+            "kem_encapsulator_destroy"
+        )),
+        decapsulator_create: Box::new(slotted!(
+            kem_decapsulator_create,
+            KemDecapsulatorCreateFnV0,
+            slot
+        )),
+        decapsulate: Box::new(kem_decapsulate),
+        decapsulator_destroy: Box::new(destroy_fn!(
+            FFIKemDecapsulator,
+            KemDecapsulatorDestroyFnV0,
+            "kem_decapsulator_destroy"
+        )),
+        shared_secret_size: Box::new(slotted!(
+            kem_shared_secret_size,
+            KemSharedSecretSizeFnV0,
+            slot
+        )),
+        keypair_generate: Box::new(slotted!(kem_keypair_generate, KemKeypairGenerateFnV0, slot)),
+    };
+    if !with_handles {
+        return KemInterface::V0(base);
+    }
+    KemInterface::V1(
+        base,
+        KemHandleInterfaceV1 {
+            capabilities: Box::new(slotted!(
+                kem_handle_capabilities,
+                KemHandleCapabilitiesFnV1,
+                slot
+            )),
+            decapsulator_create: Box::new(slotted!(
+                kem_decapsulator_create_with_handle,
+                KemDecapsulatorCreateWithHandleFnV1,
+                slot
+            )),
+        },
+    )
+}
+
+/// Build the proxy for an interface the child reported, under the
+/// registry name the in-process loader would have used.
+pub(super) fn interface(
+    name: &str,
+    version: u8,
+    slot: usize,
+) -> Option<(&'static str, u8, Rc<dyn Any>)> {
+    let kind = crate::ffi::registry::iter().find(|k| k.name() == name)?;
+    if version > kind.max_version() {
+        return None;
+    }
+    let inner: Rc<dyn Any> = match kind.name() {
+        "hash" => Rc::new(hash_interface(slot)),
+        "rng" => Rc::new(rng_interface(slot)),
+        "symmetric" => Rc::new(cipher_interface(slot)),
+        "aead" => Rc::new(aead_interface(slot)),
+        "kdf" => Rc::new(kdf_interface(slot)),
+        "keyfmt" => Rc::new(keyfmt_interface(slot)),
+        "signature" => Rc::new(signature_interface(slot, version >= 1)),
+        "kem" => Rc::new(kem_interface(slot, version >= 1)),
+        _ => return None,
+    };
+    Some((kind.name(), version, inner))
+}
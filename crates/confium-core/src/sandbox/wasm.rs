@@ -0,0 +1,197 @@
+//! Running plugins as WebAssembly components.
+//!
+//! `sandbox = "wasm"` treats the plugin file as a component built for
+//! the `confium:plugin/plugin` world (`confium-sandbox-wasm/wit`),
+//! usually with the `confium-wasm-guest` SDK. The component is
+//! instantiated in this process under wasmtime, with no imports: it
+//! cannot touch the filesystem, the network or the clock. Two load
+//! options bound it:
+//!
+//! - `wasm_fuel`: fuel granted to each call into the plugin, roughly
+//!   one unit per instruction (default 10⁹);
+//! - `wasm_memory`: cap on the plugin's linear memory in bytes
+//!   (default 32 MiB).
+//!
+//! A call that exhausts either traps the guest. That call and every
+//! later one on the provider fail with `PLUGIN_SANDBOX_FAILED`, as
+//! they would after a process-sandboxed plugin crashed.
+//!
+//! The file is verified by the
+//! [`PluginPolicy`](crate::plugin_policy::PluginPolicy) like any other
+//! plugin, and compiled from the descriptor the policy checked.
+
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+use std::sync::Arc;
+
+use confium_sandbox_process::Value;
+use confium_sandbox_wasm::{ComponentLimits, PluginInstance, PluginSandbox, SandboxInstance};
+
+use super::remote::Channel;
+use super::{SANDBOX_OPTION, options_to_json, proxy_plugin};
+use crate::error::{self, Error};
+use crate::options::{OptionValue, Options};
+use crate::plugin_policy::AdmittedPlugin;
+use crate::{Plugin, Result};
+
+/// Load option: fuel per call into a WASM plugin.
+pub const WASM_FUEL_OPTION: &str = "wasm_fuel";
+
+/// Load option: linear-memory cap of a WASM plugin, in bytes.
+pub const WASM_MEMORY_OPTION: &str = "wasm_memory";
+
+/// Read the limit options, falling back to the defaults.
+pub(crate) fn limits(opts: &Options) -> Result<ComponentLimits> {
+    let mut limits = ComponentLimits::default();
+    match opts.get(WASM_FUEL_OPTION) {
+        None => {}
+        Some(OptionValue::U32(fuel)) => limits.fuel = u64::from(*fuel),
+        Some(_) => {
+            return error::WrongTypeSnafu {
+                expected: "wasm_fuel u32",
+            }
+            .fail();
+        }
+    }
+    match opts.get(WASM_MEMORY_OPTION) {
+        None => {}
+        Some(OptionValue::U32(bytes)) => limits.memory_bytes = *bytes as usize,
+        Some(_) => {
+            return error::WrongTypeSnafu {
+                expected: "wasm_memory u32",
+            }
+            .fail();
+        }
+    }
+    Ok(limits)
+}
+
+/// Compile and instantiate the plugin component `name` and build a
+/// [`Plugin`] whose interfaces call into it. The bytes come from the
+/// file the policy admitted, or from `path` when verification is off.
+pub(crate) fn load_wasm_plugin(
+    name: &str,
+    path: &Path,
+    admitted: Option<AdmittedPlugin>,
+    limits: ComponentLimits,
+    opts: Option<&Options>,
+) -> Result<Plugin> {
+    let failed = |reason: String| Error::PluginSandboxFailed {
+        name: name.to_string(),
+        reason,
+    };
+
+    let bytes = match admitted {
+        Some(admitted) => {
+            let mut file = admitted.into_file();
+            let mut bytes = Vec::new();
+            file.seek(SeekFrom::Start(0))
+                .and_then(|_| file.read_to_end(&mut bytes))
+                .map_err(|e| failed(format!("cannot read the plugin: {e}")))?;
+            bytes
+        }
+        None => std::fs::read(path)
+            .map_err(|e| failed(format!("cannot read {}: {e}", path.display())))?,
+    };
+
+    let sandbox = PluginSandbox::new().map_err(|e| failed(e.to_string()))?;
+    let mut instance = sandbox
+        .load(&bytes, limits)
+        .map_err(|e| failed(e.to_string()))?;
+
+    let mut forwarded = opts.cloned().unwrap_or_default();
+    forwarded.remove(SANDBOX_OPTION);
+    forwarded.remove(WASM_FUEL_OPTION);
+    forwarded.remove(WASM_MEMORY_OPTION);
+    let code = instance
+        .initialize(&options_to_json(&forwarded))
+        .map_err(|e| failed(e.to_string()))?;
+    if code != 0 {
+        return Err(failed(format!("plugin failed to initialize (code {code})")));
+    }
+    let advertised = instance.interfaces().map_err(|e| failed(e.to_string()))?;
+
+    proxy_plugin(name, Arc::new(Channel::wasm(instance)), advertised)
+}
+
+/// Serve one proxy call. `None` if the guest trapped or the call was
+/// out of protocol.
+pub(super) fn call(
+    instance: &mut PluginInstance,
+    method: &str,
+    args: &[Value],
+) -> Option<Vec<Value>> {
+    let args: Vec<_> = args.iter().map(to_guest).collect();
+    let reply = instance.call(method, &args).ok()?;
+    Some(reply.into_iter().map(from_guest).collect())
+}
+
+fn to_guest(v: &Value) -> confium_sandbox_wasm::Value {
+    use confium_sandbox_wasm::Value as W;
+    match v {
+        Value::I32(x) => W::I32(*x),
+        Value::I64(x) => W::I64(*x),
+        Value::F32(x) => W::F32(*x),
+        Value::F64(x) => W::F64(*x),
+        Value::Bytes(b) => W::Bytes(b.clone()),
+    }
+}
+
+fn from_guest(v: confium_sandbox_wasm::Value) -> Value {
+    use confium_sandbox_wasm::Value as W;
+    match v {
+        W::I32(x) => Value::I32(x),
+        W::I64(x) => Value::I64(x),
+        W::F32(x) => Value::F32(x),
+        W::F64(x) => Value::F64(x),
+        W::Bytes(b) => Value::Bytes(b),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sandbox::{SandboxRequest, requested};
+
+    #[test]
+    fn wasm_sandbox_reads_its_limits() {
+        let mut opts = Options::new();
+        opts.insert(
+            SANDBOX_OPTION.to_string(),
+            OptionValue::String("wasm".to_string()),
+        );
+        let Some(SandboxRequest::Wasm(limits)) = requested(Some(&opts)).unwrap() else {
+            panic!("wasm sandbox requested");
+        };
+        assert_eq!(limits, ComponentLimits::default());
+
+        opts.insert(WASM_FUEL_OPTION.to_string(), OptionValue::U32(5_000));
+        opts.insert(WASM_MEMORY_OPTION.to_string(), OptionValue::U32(1 << 20));
+        let Some(SandboxRequest::Wasm(limits)) = requested(Some(&opts)).unwrap() else {
+            panic!("wasm sandbox requested");
+        };
+        assert_eq!(limits.fuel, 5_000);
+        assert_eq!(limits.memory_bytes, 1 << 20);
+
+        opts.insert(
+            WASM_FUEL_OPTION.to_string(),
+            OptionValue::String("lots".to_string()),
+        );
+        let err = requested(Some(&opts)).err().expect("bad fuel refused");
+        assert_eq!(err.code(), error::ErrorCode::WRONG_TYPE as u32);
+    }
+
+    #[test]
+    fn a_core_module_is_not_a_plugin() {
+        let dir = std::env::temp_dir().join(format!("confium-wasm-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("core.wasm");
+        // The empty core module: magic and version only.
+        std::fs::write(&path, b"\0asm\x01\0\0\0").unwrap();
+        let err = load_wasm_plugin("core", &path, None, ComponentLimits::default(), None)
+            .err()
+            .expect("core module refused");
+        assert_eq!(err.code(), error::ErrorCode::PLUGIN_SANDBOX_FAILED as u32);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
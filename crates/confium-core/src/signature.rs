@@ -16,6 +16,10 @@
 //!   optional caller-supplied seed is forwarded for deterministic
 //!   test-vector generation.
 //!
+//! A [`Signer`] can also be bound to a key that never leaves its
+//! keystore with [`Signer::with_handle`], which needs a provider that
+//! negotiated signature v1 (see [`crate::key_handle`]).
+//!
 //! Composite algorithms (e.g. `Dilithium3-Ed25519`) are atomic: one
 //! key, one signature, one verify call. `set_hash` is meaningful for
 //! RSA / DSA / ECDSA; Ed25519, Ed448, and PQC algorithms ignore it
@@ -36,8 +40,10 @@ use crate::Result;
 use crate::error;
 use crate::ffi::signature::{
     FFISigner, FFIVerifier, KeypairInterface, KeypairInterfaceV0, SignatureInterface,
-    SignerInterface, SignerInterfaceV0, VerifierInterface, VerifierInterfaceV0, interface_of,
+    SignerHandleInterfaceV1, SignerInterface, SignerInterfaceV0, VerifierInterface,
+    VerifierInterfaceV0, interface_of,
 };
+use crate::key_handle::{HandleCapabilities, KeyHandle};
 use crate::options::Options;
 use crate::sensitive::Sensitive;
 
@@ -117,6 +123,39 @@ fn signer_create(
     signer_create_v0(cfm, plugin_name, v0, algorithm, secret_key, opts)
 }
 
+/// Ask a v1 plugin whether it can sign `algorithm` with `handle`, and
+/// if so create the signer. `Ok(None)` means the plugin declined.
+fn signer_create_with_handle_v1(
+    cfm: &Confium,
+    plugin_name: &str,
+    v1: &SignerHandleInterfaceV1,
+    algorithm: &str,
+    handle: &KeyHandle,
+    opts: Option<&Options>,
+) -> Result<Option<*mut FFISigner>> {
+    let cname = CString::new(algorithm).unwrap();
+    let wire = handle.as_wire();
+    let mut caps: u32 = 0;
+    if (*v1.capabilities)(cname.as_ptr(), &wire, &mut caps) != 0
+        || !HandleCapabilities::from_bits(caps).contains(HandleCapabilities::SIGN)
+    {
+        return Ok(None);
+    }
+    let mut obj: *mut FFISigner = std::ptr::null_mut();
+    let code = (*v1.create)(cfm, &mut obj, cname.as_ptr(), &wire, opts);
+    if code != 0 {
+        return error::PluginInternalSnafu {
+            name: plugin_name,
+            code,
+        }
+        .fail();
+    }
+    if obj.is_null() {
+        return Ok(None);
+    }
+    Ok(Some(obj))
+}
+
 pub struct Signer {
     obj: *mut FFISigner,
     #[allow(dead_code)]
@@ -159,6 +198,41 @@ impl Signer {
         Signer::try_new(cfm, providers, algorithm, secret_key, opts)
     }
 
+    /// Create a signer for a key held by a keystore. Providers that did
+    /// not negotiate signature v1, or that report no `SIGN` capability
+    /// for `handle`, are skipped; if none is left the call fails with
+    /// [`error::Error::KeyHandleUnsupported`].
+    pub fn with_handle(
+        cfm: &Confium,
+        algorithm: &str,
+        handle: &KeyHandle,
+        provider_name: Option<&str>,
+        opts: Option<&Options>,
+    ) -> Result<Signer> {
+        for provider in candidates(cfm, provider_name)? {
+            let Some(iface) = interface_of(&provider.plugin) else {
+                continue;
+            };
+            let Some(v1) = &iface.handle else {
+                continue;
+            };
+            let obj =
+                signer_create_with_handle_v1(cfm, &provider.name, v1, algorithm, handle, opts)?;
+            if let Some(obj) = obj {
+                return Ok(Signer {
+                    obj,
+                    lib: Rc::clone(&provider.plugin.library),
+                    interface: iface,
+                });
+            }
+        }
+        error::KeyHandleUnsupportedSnafu {
+            backend: handle.backend(),
+            algorithm,
+        }
+        .fail()
+    }
+
     /// Set the hash used by RSA / DSA / ECDSA signing. Ed25519, Ed448,
     /// and PQC algorithms ignore this; the plugin returns success
     /// without changing state.
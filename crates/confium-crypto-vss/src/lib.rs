@@ -8,6 +8,7 @@
 
 pub mod nizk;
 pub mod paillier;
+pub mod paillier_zk;
 pub mod pedersen_vss;
 pub mod range_proof;
 pub mod schnorr;
@@ -12,6 +12,15 @@ pub struct PaillierPublicKey {
     pub g: BigUint,
 }
 
+impl PaillierPublicKey {
+    /// The public key for modulus `n` (with the standard `g = n + 1`).
+    pub fn from_modulus(n: BigUint) -> Self {
+        let n_squared = &n * &n;
+        let g = &n + &BigUint::one();
+        PaillierPublicKey { n, n_squared, g }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct PaillierPrivateKey {
     pub lambda: BigUint,
@@ -38,36 +47,31 @@ pub fn generate_keypair(prime_bits: u32) -> PaillierKeypair {
     loop {
         let p = generate_prime(prime_bits);
         let q = generate_prime(prime_bits);
-        if p == q {
-            continue;
+        if let Some(keypair) = keypair_from_primes(&p, &q) {
+            return keypair;
         }
-        let n = &p * &q;
-        let n_squared = &n * &n;
+    }
+}
 
-        let p_minus_one = &p - &BigUint::one();
-        let q_minus_one = &q - &BigUint::one();
-        let gcd_val = p_minus_one.gcd(&q_minus_one);
-        let lambda = (&p_minus_one * &q_minus_one) / &gcd_val;
+/// Build the keypair for `N = p * q` from its prime factors. Returns
+/// `None` when `p == q` or `lambda` has no inverse mod `N`.
+pub fn keypair_from_primes(p: &BigUint, q: &BigUint) -> Option<PaillierKeypair> {
+    if p == q {
+        return None;
+    }
+    let n = p * q;
 
-        let g = &n + &BigUint::one();
+    let p_minus_one = p - &BigUint::one();
+    let q_minus_one = q - &BigUint::one();
+    let gcd_val = p_minus_one.gcd(&q_minus_one);
+    let lambda = (&p_minus_one * &q_minus_one) / &gcd_val;
 
-        let g_lambda = g.modpow(&lambda, &n_squared);
-        if g_lambda < BigUint::one() {
-            continue;
-        }
-        let l_val = (&g_lambda - &BigUint::one()) / &n;
-
-        let mu = match modinv_biguint(&lambda, &n) {
-            Some(m) => m,
-            None => continue,
-        };
-
-        let _ = l_val;
-        return PaillierKeypair {
-            public: PaillierPublicKey { n, n_squared, g },
-            private: PaillierPrivateKey { lambda, mu },
-        };
-    }
+    let mu = modinv_biguint(&lambda, &n)?;
+
+    Some(PaillierKeypair {
+        public: PaillierPublicKey::from_modulus(n),
+        private: PaillierPrivateKey { lambda, mu },
+    })
 }
 
 pub fn encrypt(
@@ -118,7 +122,7 @@ fn generate_prime(bits: u32) -> BigUint {
     }
 }
 
-fn miller_rabin(n: &BigUint, rounds: u32) -> bool {
+pub(crate) fn miller_rabin(n: &BigUint, rounds: u32) -> bool {
     let two = BigUint::from(2u32);
     let three = BigUint::from(3u32);
     if n == &two || n == &three {
@@ -164,7 +168,7 @@ fn miller_rabin(n: &BigUint, rounds: u32) -> bool {
     true
 }
 
-fn modinv_biguint(a: &BigUint, m: &BigUint) -> Option<BigUint> {
+pub(crate) fn modinv_biguint(a: &BigUint, m: &BigUint) -> Option<BigUint> {
     let a_int = a.to_bigint()?;
     let m_int = m.to_bigint()?;
     let result = modinv(&a_int, &m_int)?;
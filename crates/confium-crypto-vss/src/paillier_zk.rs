@@ -0,0 +1,1507 @@
+//! Zero-knowledge proofs for Paillier-based MtA.
+//!
+//! The proofs threshold ECDSA needs to run its multiplicative-to-additive
+//! (MtA) conversions against possibly malicious peers, after Canetti,
+//! Gennaro, Goldfeder, Makriyannis and Peled, "UC Non-Interactive,
+//! Proactive, Threshold ECDSA with Identifiable Aborts" (CMP20, eprint
+//! 2021/060), made non-interactive with a SHA-256 Fiat–Shamir transcript:
+//!
+//! - [`ModProof`] — Π^mod: `N` is a Paillier-Blum modulus (Fig. 16).
+//! - [`PrmProof`] — Π^prm: the ring-Pedersen parameters `s = t^λ mod N`
+//!   are well formed (Fig. 17).
+//! - [`EncProof`] — Π^enc: a Paillier ciphertext encrypts a value in
+//!   `±2^ℓ` (Fig. 14).
+//! - [`LogStarProof`] — Π^log*: a ciphertext and a curve point hide the
+//!   same value (Fig. 25).
+//! - [`AffGProof`] — Π^aff-g: a ciphertext is an affine function of
+//!   another one, with the multiplier bound to a curve point (Fig. 15).
+//!
+//! Each party holds an [`AuxSecret`]: two 1024-bit Blum primes whose
+//! 2048-bit product is both its Paillier modulus and the ring-Pedersen
+//! modulus its peers prove against. [`AuxSecret::prove`] produces the
+//! [`AuxInfo`] a party publishes so the others can check both.
+//!
+//! On top of the proofs, [`mta_respond`], [`mta_verify`] and
+//! [`mta_receive`] run one MtA over the scalar field of a [`ZkCurve`]:
+//! the receiver holds `k` (sent as `K = enc(k)`), the responder holds
+//! `x`, and they end with additive shares of `k * x` without either
+//! learning the other's input.
+//!
+//! Everything touching the group is generic over the curve; the range
+//! parameters follow its order (`ℓ = |q|`, `ε = 2ℓ`, `ℓ' = 5ℓ`). Up to
+//! 384-bit orders the MtA plaintext `k x + y` stays well inside the
+//! 2048-bit modulus. The proof and response types default to P-256.
+//!
+//! Every prover and verifier takes a `context` byte string that is mixed
+//! into the challenge; callers bind it to the session and the prover so
+//! a proof cannot be replayed elsewhere.
+
+use elliptic_curve::group::{Curve as _, Group};
+use elliptic_curve::sec1::{FromSec1Point, ModulusSize, Sec1Point, ToSec1Point};
+use elliptic_curve::{
+    AffinePoint, Curve, CurveArithmetic, Field, FieldBytes, PrimeCurve, PrimeField,
+    ProjectivePoint, Scalar,
+};
+use num_bigint::{BigInt, BigUint, RandBigInt, Sign};
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use p256::NistP256;
+use rand_core::OsRng;
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+use crate::paillier::{self, PaillierKeypair, PaillierPublicKey};
+
+/// Size of each prime of a party's modulus.
+pub const PRIME_BITS: u64 = 1024;
+/// Repetitions of the binary-challenge proofs (Π^mod and Π^prm).
+const STAT_ROUNDS: usize = 80;
+/// Largest integer [`Decoder`] accepts, in bytes.
+const MAX_INT_BYTES: usize = 1024;
+
+/// A prime-order curve the proofs and the MtA can run over: any
+/// `elliptic_curve` group with arithmetic and SEC1 point encoding.
+pub trait ZkCurve:
+    PrimeCurve
+    + CurveArithmetic<AffinePoint: FromSec1Point<Self> + ToSec1Point<Self>>
+    + Curve<FieldBytesSize: ModulusSize>
+{
+}
+
+impl<C> ZkCurve for C where
+    C: PrimeCurve
+        + CurveArithmetic<AffinePoint: FromSec1Point<C> + ToSec1Point<C>>
+        + Curve<FieldBytesSize: ModulusSize>
+{
+}
+
+/// Bit length `ℓ` of the secrets being multiplied: the order of `C`.
+pub fn ell<C: ZkCurve>() -> u64 {
+    Scalar::<C>::NUM_BITS as u64
+}
+
+/// Slackness `ε` of the range proofs.
+pub fn epsilon<C: ZkCurve>() -> u64 {
+    2 * ell::<C>()
+}
+
+/// Bit length `ℓ'` of the MtA masks.
+pub fn ell_prime<C: ZkCurve>() -> u64 {
+    5 * ell::<C>()
+}
+
+/// Length of a serialised scalar of `C`.
+fn scalar_len<C: ZkCurve>() -> usize {
+    FieldBytes::<C>::default().len()
+}
+
+/// Order of the group of `C`, the field the MtA shares live in.
+fn curve_order<C: ZkCurve>() -> BigUint {
+    scalar_to_int::<C>(&-Scalar::<C>::ONE) + 1u32
+}
+
+/// A scalar as a non-negative integer.
+pub fn scalar_to_int<C: ZkCurve>(s: &Scalar<C>) -> BigUint {
+    BigUint::from_bytes_be(&s.to_repr())
+}
+
+/// An integer reduced into the scalar field of `C`.
+pub fn int_to_scalar<C: ZkCurve>(x: &BigInt) -> Scalar<C> {
+    let q = BigInt::from(curve_order::<C>());
+    let (_, bytes) = x.mod_floor(&q).to_bytes_be();
+    let mut repr = FieldBytes::<C>::default();
+    let len = repr.len();
+    repr[len - bytes.len()..].copy_from_slice(&bytes);
+    Option::from(Scalar::<C>::from_repr(repr)).unwrap_or(Scalar::<C>::ZERO)
+}
+
+// ---------------------------------------------------------------------
+// Arithmetic helpers
+// ---------------------------------------------------------------------
+
+fn sample_unit(n: &BigUint) -> BigUint {
+    loop {
+        let r = OsRng.gen_biguint_below(n);
+        if !r.is_zero() && r.gcd(n).is_one() {
+            return r;
+        }
+    }
+}
+
+/// Uniform in `±2^bits * scale`.
+fn sample_pm(bits: u64, scale: &BigUint) -> BigInt {
+    let bound = BigInt::from(scale << bits);
+    OsRng.gen_bigint_range(&-&bound, &(&bound + 1))
+}
+
+fn in_range(z: &BigInt, bits: u64) -> bool {
+    z.magnitude().bits() <= bits
+}
+
+/// `base^exp mod m` for a signed exponent.
+fn pow_signed(base: &BigUint, exp: &BigInt, m: &BigUint) -> Option<BigUint> {
+    let base = if exp.sign() == Sign::Minus {
+        base.modinv(m)?
+    } else {
+        base % m
+    };
+    Some(base.modpow(exp.magnitude(), m))
+}
+
+/// `x` is a unit of `Z_m` other than zero.
+fn is_unit(x: &BigUint, m: &BigUint) -> bool {
+    !x.is_zero() && x < m && x.gcd(m).is_one()
+}
+
+/// Paillier encryption of a signed plaintext, `(1 + N)^m * r^N mod N^2`.
+fn enc(pk: &PaillierPublicKey, m: &BigInt, r: &BigUint) -> BigUint {
+    let m = m.mod_floor(&BigInt::from(pk.n.clone())).magnitude().clone();
+    let gm = (BigUint::one() + m * &pk.n) % &pk.n_squared;
+    gm * r.modpow(&pk.n, &pk.n_squared) % &pk.n_squared
+}
+
+/// Decryption of a ciphertext, centred into `(-N/2, N/2]`.
+fn dec_signed(keypair: &PaillierKeypair, c: &BigUint) -> Option<BigInt> {
+    let m = paillier::decrypt(&keypair.private, &keypair.public, c).ok()?;
+    let half = &keypair.public.n >> 1;
+    Some(if m > half {
+        BigInt::from(m) - BigInt::from(keypair.public.n.clone())
+    } else {
+        BigInt::from(m)
+    })
+}
+
+/// Jacobi symbol `(a / n)` for odd `n`.
+fn jacobi(a: &BigUint, n: &BigUint) -> i8 {
+    let low = |x: &BigUint, mask: u32| x.iter_u32_digits().next().unwrap_or(0) & mask;
+    let mut a = a % n;
+    let mut n = n.clone();
+    let mut t = 1i8;
+    while !a.is_zero() {
+        let zeros = a.trailing_zeros().unwrap_or(0);
+        if zeros % 2 == 1 && matches!(low(&n, 7), 3 | 5) {
+            t = -t;
+        }
+        a >>= zeros;
+        std::mem::swap(&mut a, &mut n);
+        if low(&a, 3) == 3 && low(&n, 3) == 3 {
+            t = -t;
+        }
+        a %= &n;
+    }
+    if n.is_one() { t } else { 0 }
+}
+
+fn small_primes() -> Vec<u32> {
+    (3u32..2000)
+        .step_by(2)
+        .filter(|&n| {
+            (3..n)
+                .step_by(2)
+                .take_while(|d| d * d <= n)
+                .all(|d| n % d != 0)
+        })
+        .collect()
+}
+
+/// A random `bits`-bit prime `p ≡ 3 (mod 4)` with its top two bits set,
+/// so the product of two is exactly `2 * bits` long.
+fn blum_prime(bits: u64) -> BigUint {
+    let sieve = small_primes();
+    loop {
+        let mut c = OsRng.gen_biguint(bits);
+        for bit in [bits - 1, bits - 2, 1, 0] {
+            c.set_bit(bit, true);
+        }
+        if sieve.iter().any(|&p| (&c % p).is_zero()) {
+            continue;
+        }
+        if paillier::miller_rabin(&c, 20) {
+            return c;
+        }
+    }
+}
+
+// ---------------------------------------------------------------------
+// Wire encoding
+// ---------------------------------------------------------------------
+
+/// Appends proof elements to a byte buffer. Integers are written as
+/// `u16` big-endian length + magnitude (signed ones with a leading sign
+/// byte), points SEC1 compressed (33 bytes on a 256-bit curve).
+#[derive(Debug, Default)]
+pub struct Encoder(Vec<u8>);
+
+impl Encoder {
+    pub fn new() -> Self {
+        Encoder::default()
+    }
+
+    pub fn uint(&mut self, x: &BigUint) -> &mut Self {
+        let bytes = x.to_bytes_be();
+        self.0
+            .extend_from_slice(&(bytes.len() as u16).to_be_bytes());
+        self.0.extend_from_slice(&bytes);
+        self
+    }
+
+    pub fn int(&mut self, x: &BigInt) -> &mut Self {
+        self.0.push(u8::from(x.sign() == Sign::Minus));
+        self.uint(x.magnitude())
+    }
+
+    pub fn point<C: ZkCurve>(&mut self, p: &AffinePoint<C>) -> &mut Self {
+        self.0.extend_from_slice(p.to_sec1_point(true).as_bytes());
+        self
+    }
+
+    pub fn bit(&mut self, b: bool) -> &mut Self {
+        self.0.push(u8::from(b));
+        self
+    }
+
+    pub fn finish(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+/// Reads what [`Encoder`] wrote. Every accessor returns `None` on
+/// truncated or malformed input.
+#[derive(Debug)]
+pub struct Decoder<'a>(&'a [u8]);
+
+impl<'a> Decoder<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Decoder(bytes)
+    }
+
+    fn take(&mut self, n: usize) -> Option<&'a [u8]> {
+        if self.0.len() < n {
+            return None;
+        }
+        let (head, tail) = self.0.split_at(n);
+        self.0 = tail;
+        Some(head)
+    }
+
+    pub fn uint(&mut self) -> Option<BigUint> {
+        let len = self.take(2)?;
+        let len = u16::from_be_bytes([len[0], len[1]]) as usize;
+        if len > MAX_INT_BYTES {
+            return None;
+        }
+        Some(BigUint::from_bytes_be(self.take(len)?))
+    }
+
+    pub fn int(&mut self) -> Option<BigInt> {
+        let sign = match self.take(1)?[0] {
+            0 => Sign::Plus,
+            1 => Sign::Minus,
+            _ => return None,
+        };
+        Some(BigInt::from_biguint(sign, self.uint()?))
+    }
+
+    pub fn point<C: ZkCurve>(&mut self) -> Option<AffinePoint<C>> {
+        let encoded = Sec1Point::<C>::from_bytes(self.take(scalar_len::<C>() + 1)?).ok()?;
+        Option::from(AffinePoint::<C>::from_sec1_point(&encoded))
+    }
+
+    pub fn bit(&mut self) -> Option<bool> {
+        match self.take(1)?[0] {
+            0 => Some(false),
+            1 => Some(true),
+            _ => None,
+        }
+    }
+
+    /// `Some(())` when every byte was consumed.
+    pub fn finish(self) -> Option<()> {
+        self.0.is_empty().then_some(())
+    }
+}
+
+// ---------------------------------------------------------------------
+// Fiat–Shamir transcript
+// ---------------------------------------------------------------------
+
+#[derive(Clone)]
+struct Transcript(Sha256);
+
+impl Transcript {
+    fn new(label: &[u8], context: &[u8]) -> Self {
+        let mut t = Transcript(Sha256::new());
+        t.bytes(b"confium-paillier-zk-v1");
+        t.bytes(label);
+        t.bytes(context);
+        t
+    }
+
+    fn bytes(&mut self, b: &[u8]) -> &mut Self {
+        self.0.update((b.len() as u64).to_be_bytes());
+        self.0.update(b);
+        self
+    }
+
+    fn uint(&mut self, x: &BigUint) -> &mut Self {
+        self.bytes(&x.to_bytes_be())
+    }
+
+    fn point<C: ZkCurve>(&mut self, p: &AffinePoint<C>) -> &mut Self {
+        self.bytes(p.to_sec1_point(true).as_bytes())
+    }
+
+    /// `bits` pseudo-random bits derived from the transcript and `tag`.
+    fn expand(&self, tag: &[u8], bits: u64) -> BigUint {
+        let seed: [u8; 32] = self.0.clone().finalize().into();
+        let mut out = Vec::new();
+        let mut counter = 0u32;
+        while (out.len() as u64) * 8 < bits {
+            let mut h = Sha256::new();
+            h.update(seed);
+            h.update((tag.len() as u64).to_be_bytes());
+            h.update(tag);
+            h.update(counter.to_be_bytes());
+            out.extend_from_slice(&h.finalize());
+            counter += 1;
+        }
+        BigUint::from_bytes_be(&out) >> ((out.len() as u64) * 8 - bits)
+    }
+
+    /// Challenge in `[0, q)` for the order `q` of `C`.
+    fn challenge<C: ZkCurve>(&self) -> BigInt {
+        let q = curve_order::<C>();
+        BigInt::from(self.expand(b"e", q.bits() + 128) % q)
+    }
+
+    /// `STAT_ROUNDS` challenge bits.
+    fn challenge_bits(&self) -> Vec<bool> {
+        let e = self.expand(b"bits", STAT_ROUNDS as u64);
+        (0..STAT_ROUNDS as u64).map(|i| e.bit(i)).collect()
+    }
+}
+
+// ---------------------------------------------------------------------
+// Auxiliary key material
+// ---------------------------------------------------------------------
+
+/// Ring-Pedersen parameters `(N, s, t)`: commitments `s^x t^r mod N`
+/// that a prover not knowing `φ(N)` cannot open two ways.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RingPedersen {
+    pub n: BigUint,
+    pub s: BigUint,
+    pub t: BigUint,
+}
+
+impl RingPedersen {
+    fn commit(&self, x: &BigInt, r: &BigInt) -> BigUint {
+        match (
+            pow_signed(&self.s, x, &self.n),
+            pow_signed(&self.t, r, &self.n),
+        ) {
+            (Some(a), Some(b)) => a * b % &self.n,
+            _ => BigUint::zero(),
+        }
+    }
+
+    /// `a * b^e mod N`.
+    fn times_pow(&self, a: &BigUint, b: &BigUint, e: &BigInt) -> BigUint {
+        pow_signed(b, e, &self.n)
+            .map(|be| a * be % &self.n)
+            .unwrap_or_default()
+    }
+
+    fn absorb(&self, t: &mut Transcript) {
+        t.uint(&self.n).uint(&self.s).uint(&self.t);
+    }
+
+    pub fn encode(&self, e: &mut Encoder) {
+        e.uint(&self.n).uint(&self.s).uint(&self.t);
+    }
+
+    pub fn decode(d: &mut Decoder<'_>) -> Option<Self> {
+        Some(RingPedersen {
+            n: d.uint()?,
+            s: d.uint()?,
+            t: d.uint()?,
+        })
+    }
+
+    /// The Paillier public key sharing this modulus.
+    pub fn paillier_key(&self) -> PaillierPublicKey {
+        PaillierPublicKey::from_modulus(self.n.clone())
+    }
+}
+
+/// A party's Paillier-Blum factorisation plus its ring-Pedersen
+/// trapdoor `λ` (with `s = t^λ mod N`).
+#[derive(Clone)]
+pub struct AuxSecret {
+    p: BigUint,
+    q: BigUint,
+    lambda: BigUint,
+    params: RingPedersen,
+    keypair: PaillierKeypair,
+}
+
+impl std::fmt::Debug for AuxSecret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AuxSecret")
+            .field("params", &self.params)
+            .finish_non_exhaustive()
+    }
+}
+
+impl AuxSecret {
+    /// Fresh key material with [`PRIME_BITS`]-bit primes.
+    pub fn generate() -> Self {
+        Self::generate_with(PRIME_BITS)
+    }
+
+    fn generate_with(prime_bits: u64) -> Self {
+        loop {
+            let p = blum_prime(prime_bits);
+            let q = blum_prime(prime_bits);
+            if let Some(aux) = Self::from_primes(p, q) {
+                return aux;
+            }
+        }
+    }
+
+    /// Key material for `N = p * q` with fresh ring-Pedersen
+    /// parameters. `None` unless `p` and `q` are distinct and both
+    /// `≡ 3 (mod 4)`.
+    pub fn from_primes(p: BigUint, q: BigUint) -> Option<Self> {
+        let n = &p * &q;
+        let phi = (&p - 1u32) * (&q - 1u32);
+        let tau = sample_unit(&n);
+        let t = tau.modpow(&BigUint::from(2u32), &n);
+        let lambda = OsRng.gen_biguint_below(&phi);
+        Self::assemble(p, q, lambda, t)
+    }
+
+    fn assemble(p: BigUint, q: BigUint, lambda: BigUint, t: BigUint) -> Option<Self> {
+        let three = BigUint::from(3u32);
+        if p == q || &p % 4u32 != three || &q % 4u32 != three {
+            return None;
+        }
+        let n = &p * &q;
+        if !is_unit(&t, &n) {
+            return None;
+        }
+        let s = t.modpow(&lambda, &n);
+        let keypair = paillier::keypair_from_primes(&p, &q)?;
+        Some(AuxSecret {
+            p,
+            q,
+            lambda,
+            params: RingPedersen { n, s, t },
+            keypair,
+        })
+    }
+
+    /// The Paillier keypair on `N`.
+    pub fn paillier(&self) -> &PaillierKeypair {
+        &self.keypair
+    }
+
+    /// The public ring-Pedersen parameters on `N`.
+    pub fn params(&self) -> &RingPedersen {
+        &self.params
+    }
+
+    fn phi(&self) -> BigUint {
+        (&self.p - 1u32) * (&self.q - 1u32)
+    }
+
+    /// Publish the parameters with their Π^mod and Π^prm proofs.
+    pub fn prove(&self, context: &[u8]) -> AuxInfo {
+        AuxInfo {
+            params: self.params.clone(),
+            mod_proof: prove_mod(self, context),
+            prm_proof: prove_prm(self, context),
+        }
+    }
+
+    /// `p || q || λ || t`, for persisting a session.
+    pub fn to_bytes(&self) -> Zeroizing<Vec<u8>> {
+        let mut e = Encoder::new();
+        e.uint(&self.p)
+            .uint(&self.q)
+            .uint(&self.lambda)
+            .uint(&self.params.t);
+        Zeroizing::new(e.finish())
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let mut d = Decoder::new(bytes);
+        let (p, q, lambda, t) = (d.uint()?, d.uint()?, d.uint()?, d.uint()?);
+        d.finish()?;
+        Self::assemble(p, q, lambda, t)
+    }
+}
+
+/// What a party publishes about its modulus: the ring-Pedersen
+/// parameters (whose `N` is also its Paillier key) and the proofs that
+/// both are well formed.
+#[derive(Debug, Clone)]
+pub struct AuxInfo {
+    pub params: RingPedersen,
+    pub mod_proof: ModProof,
+    pub prm_proof: PrmProof,
+}
+
+impl AuxInfo {
+    /// Check the modulus size and both proofs.
+    pub fn verify(&self, context: &[u8]) -> bool {
+        self.params.n.bits() >= 2 * PRIME_BITS
+            && verify_mod(&self.params.n, &self.mod_proof, context)
+            && verify_prm(&self.params, &self.prm_proof, context)
+    }
+
+    pub fn encode(&self, e: &mut Encoder) {
+        self.params.encode(e);
+        self.mod_proof.encode(e);
+        self.prm_proof.encode(e);
+    }
+
+    pub fn decode(d: &mut Decoder<'_>) -> Option<Self> {
+        Some(AuxInfo {
+            params: RingPedersen::decode(d)?,
+            mod_proof: ModProof::decode(d)?,
+            prm_proof: PrmProof::decode(d)?,
+        })
+    }
+}
+
+// ---------------------------------------------------------------------
+// Π^mod — Paillier-Blum modulus
+// ---------------------------------------------------------------------
+
+/// Π^mod: `N` is the product of two primes `≡ 3 (mod 4)` and coprime
+/// to `φ(N)`.
+#[derive(Debug, Clone)]
+pub struct ModProof {
+    w: BigUint,
+    rounds: Vec<(BigUint, bool, bool, BigUint)>,
+}
+
+impl ModProof {
+    pub fn encode(&self, e: &mut Encoder) {
+        e.uint(&self.w);
+        for (x, a, b, z) in &self.rounds {
+            e.uint(x).bit(*a).bit(*b).uint(z);
+        }
+    }
+
+    pub fn decode(d: &mut Decoder<'_>) -> Option<Self> {
+        let w = d.uint()?;
+        let mut rounds = Vec::with_capacity(STAT_ROUNDS);
+        for _ in 0..STAT_ROUNDS {
+            rounds.push((d.uint()?, d.bit()?, d.bit()?, d.uint()?));
+        }
+        Some(ModProof { w, rounds })
+    }
+}
+
+fn mod_challenges(n: &BigUint, w: &BigUint, context: &[u8]) -> Vec<BigUint> {
+    let mut t = Transcript::new(b"mod", context);
+    t.uint(n).uint(w);
+    (0..STAT_ROUNDS as u32)
+        .map(|i| t.expand(&i.to_be_bytes(), n.bits() + 128) % n)
+        .collect()
+}
+
+fn is_qr_mod_prime(a: &BigUint, p: &BigUint) -> bool {
+    a.modpow(&((p - 1u32) >> 1), p).is_one()
+}
+
+fn prove_mod(aux: &AuxSecret, context: &[u8]) -> ModProof {
+    let (p, q, n) = (&aux.p, &aux.q, &aux.params.n);
+    let w = loop {
+        let w = sample_unit(n);
+        if jacobi(&w, n) == -1 {
+            break w;
+        }
+    };
+    let n_inv = n.modinv(&aux.phi()).expect("N is coprime to phi(N)");
+    // A quadratic residue's fourth root mod a Blum prime is
+    // `a^(((p+1)/4)^2)`, with the exponent reduced mod `p - 1`.
+    let quartic = |m: &BigUint| {
+        let e = (m + 1u32) >> 2;
+        (&e * &e) % (m - 1u32)
+    };
+    let (ep, eq) = (quartic(p), quartic(q));
+    let q_inv = q.modinv(p).expect("distinct primes");
+    let crt = |xp: BigUint, xq: BigUint| {
+        let h = ((&xp + p - (&xq % p)) % p) * &q_inv % p;
+        xq + q * h
+    };
+    let minus_one = n - 1u32;
+    let rounds = mod_challenges(n, &w, context)
+        .into_iter()
+        .map(|y| {
+            let z = y.modpow(&n_inv, n);
+            for (a, b) in [(false, false), (true, false), (false, true), (true, true)] {
+                let mut v = y.clone();
+                if a {
+                    v = v * &minus_one % n;
+                }
+                if b {
+                    v = v * &w % n;
+                }
+                if is_qr_mod_prime(&(&v % p), p) && is_qr_mod_prime(&(&v % q), q) {
+                    let x = crt((&v % p).modpow(&ep, p), (&v % q).modpow(&eq, q));
+                    return (x, a, b, z);
+                }
+            }
+            unreachable!("one of ±y, ±wy is a quadratic residue mod a Blum modulus")
+        })
+        .collect();
+    ModProof { w, rounds }
+}
+
+/// Verify a Π^mod proof for `n`.
+pub fn verify_mod(n: &BigUint, proof: &ModProof, context: &[u8]) -> bool {
+    if !n.bit(0) || n.bits() < 16 || paillier::miller_rabin(n, 8) {
+        return false;
+    }
+    if proof.rounds.len() != STAT_ROUNDS || !is_unit(&proof.w, n) || jacobi(&proof.w, n) != -1 {
+        return false;
+    }
+    let four = BigUint::from(4u32);
+    let minus_one = n - 1u32;
+    mod_challenges(n, &proof.w, context)
+        .iter()
+        .zip(&proof.rounds)
+        .all(|(y, (x, a, b, z))| {
+            if x >= n || z >= n || z.modpow(n, n) != *y {
+                return false;
+            }
+            let mut v = y.clone();
+            if *a {
+                v = v * &minus_one % n;
+            }
+            if *b {
+                v = v * &proof.w % n;
+            }
+            x.modpow(&four, n) == v
+        })
+}
+
+// ---------------------------------------------------------------------
+// Π^prm — ring-Pedersen parameters
+// ---------------------------------------------------------------------
+
+/// Π^prm: the prover knows `λ` with `s = t^λ mod N`.
+#[derive(Debug, Clone)]
+pub struct PrmProof {
+    rounds: Vec<(BigUint, BigUint)>,
+}
+
+impl PrmProof {
+    pub fn encode(&self, e: &mut Encoder) {
+        for (a, z) in &self.rounds {
+            e.uint(a).uint(z);
+        }
+    }
+
+    pub fn decode(d: &mut Decoder<'_>) -> Option<Self> {
+        let mut rounds = Vec::with_capacity(STAT_ROUNDS);
+        for _ in 0..STAT_ROUNDS {
+            rounds.push((d.uint()?, d.uint()?));
+        }
+        Some(PrmProof { rounds })
+    }
+}
+
+fn prm_challenge(params: &RingPedersen, commitments: &[BigUint], context: &[u8]) -> Vec<bool> {
+    let mut t = Transcript::new(b"prm", context);
+    params.absorb(&mut t);
+    for a in commitments {
+        t.uint(a);
+    }
+    t.challenge_bits()
+}
+
+fn prove_prm(aux: &AuxSecret, context: &[u8]) -> PrmProof {
+    let phi = aux.phi();
+    let params = &aux.params;
+    let nonces: Vec<BigUint> = (0..STAT_ROUNDS)
+        .map(|_| OsRng.gen_biguint_below(&phi))
+        .collect();
+    let commitments: Vec<BigUint> = nonces
+        .iter()
+        .map(|a| params.t.modpow(a, &params.n))
+        .collect();
+    let bits = prm_challenge(params, &commitments, context);
+    let rounds = nonces
+        .into_iter()
+        .zip(commitments)
+        .zip(bits)
+        .map(|((a, big_a), e)| {
+            let z = if e { (a + &aux.lambda) % &phi } else { a };
+            (big_a, z)
+        })
+        .collect();
+    PrmProof { rounds }
+}
+
+/// Verify a Π^prm proof for `params`.
+pub fn verify_prm(params: &RingPedersen, proof: &PrmProof, context: &[u8]) -> bool {
+    let n = &params.n;
+    if proof.rounds.len() != STAT_ROUNDS || !is_unit(&params.s, n) || !is_unit(&params.t, n) {
+        return false;
+    }
+    let commitments: Vec<BigUint> = proof.rounds.iter().map(|(a, _)| a.clone()).collect();
+    let bits = prm_challenge(params, &commitments, context);
+    proof.rounds.iter().zip(bits).all(|((a, z), e)| {
+        let rhs = if e { a * &params.s % n } else { a % n };
+        a < n && params.t.modpow(z, n) == rhs
+    })
+}
+
+// ---------------------------------------------------------------------
+// Π^enc — range of a Paillier plaintext
+// ---------------------------------------------------------------------
+
+/// Π^enc: `K = enc(k; ρ)` with `k ∈ ±2^ℓ`.
+#[derive(Debug, Clone)]
+pub struct EncProof {
+    s: BigUint,
+    a: BigUint,
+    c: BigUint,
+    z1: BigInt,
+    z2: BigUint,
+    z3: BigInt,
+}
+
+impl EncProof {
+    pub fn encode(&self, e: &mut Encoder) {
+        e.uint(&self.s)
+            .uint(&self.a)
+            .uint(&self.c)
+            .int(&self.z1)
+            .uint(&self.z2)
+            .int(&self.z3);
+    }
+
+    pub fn decode(d: &mut Decoder<'_>) -> Option<Self> {
+        Some(EncProof {
+            s: d.uint()?,
+            a: d.uint()?,
+            c: d.uint()?,
+            z1: d.int()?,
+            z2: d.uint()?,
+            z3: d.int()?,
+        })
+    }
+}
+
+fn enc_challenge<C: ZkCurve>(
+    pk: &PaillierPublicKey,
+    k_ct: &BigUint,
+    aux: &RingPedersen,
+    proof: (&BigUint, &BigUint, &BigUint),
+    context: &[u8],
+) -> BigInt {
+    let mut t = Transcript::new(b"enc", context);
+    t.uint(&pk.n).uint(k_ct);
+    aux.absorb(&mut t);
+    t.uint(proof.0).uint(proof.1).uint(proof.2);
+    t.challenge::<C>()
+}
+
+/// Prove that `k_ct = enc(pk, k; rho)` with `k ∈ ±2^ℓ`, against the
+/// verifier's ring-Pedersen parameters `aux`. `ℓ` and the challenge
+/// follow the order of `C`.
+pub fn prove_enc<C: ZkCurve>(
+    pk: &PaillierPublicKey,
+    k_ct: &BigUint,
+    k: &BigInt,
+    rho: &BigUint,
+    aux: &RingPedersen,
+    context: &[u8],
+) -> EncProof {
+    let (ell, eps) = (ell::<C>(), epsilon::<C>());
+    let alpha = sample_pm(ell + eps, &BigUint::one());
+    let mu = sample_pm(ell, &aux.n);
+    let r = sample_unit(&pk.n);
+    let gamma = sample_pm(ell + eps, &aux.n);
+
+    let s = aux.commit(k, &mu);
+    let a = enc(pk, &alpha, &r);
+    let c = aux.commit(&alpha, &gamma);
+    let e = enc_challenge::<C>(pk, k_ct, aux, (&s, &a, &c), context);
+
+    EncProof {
+        z1: &alpha + &e * k,
+        z2: r * rho.modpow(e.magnitude(), &pk.n) % &pk.n,
+        z3: gamma + &e * mu,
+        s,
+        a,
+        c,
+    }
+}
+
+/// Verify a Π^enc proof for `k_ct` under `pk`, against the verifier's
+/// own parameters `aux`.
+pub fn verify_enc<C: ZkCurve>(
+    pk: &PaillierPublicKey,
+    k_ct: &BigUint,
+    aux: &RingPedersen,
+    proof: &EncProof,
+    context: &[u8],
+) -> bool {
+    if !in_range(&proof.z1, ell::<C>() + epsilon::<C>())
+        || !is_unit(&proof.z2, &pk.n)
+        || proof.a >= pk.n_squared
+        || k_ct >= &pk.n_squared
+    {
+        return false;
+    }
+    let e = enc_challenge::<C>(pk, k_ct, aux, (&proof.s, &proof.a, &proof.c), context);
+    let lhs = enc(pk, &proof.z1, &proof.z2);
+    let rhs = &proof.a * k_ct.modpow(e.magnitude(), &pk.n_squared) % &pk.n_squared;
+    lhs == rhs && aux.commit(&proof.z1, &proof.z3) == aux.times_pow(&proof.c, &proof.s, &e)
+}
+
+// ---------------------------------------------------------------------
+// Π^log* — ciphertext and point hide the same value
+// ---------------------------------------------------------------------
+
+/// Π^log*: `C = enc(x; ρ)` and `X = x * base` with `x ∈ ±2^ℓ`.
+#[derive(Debug, Clone)]
+pub struct LogStarProof<C: ZkCurve = NistP256> {
+    s: BigUint,
+    a: BigUint,
+    y: AffinePoint<C>,
+    d: BigUint,
+    z1: BigInt,
+    z2: BigUint,
+    z3: BigInt,
+}
+
+impl<C: ZkCurve> LogStarProof<C> {
+    pub fn encode(&self, e: &mut Encoder) {
+        e.uint(&self.s)
+            .uint(&self.a)
+            .point::<C>(&self.y)
+            .uint(&self.d)
+            .int(&self.z1)
+            .uint(&self.z2)
+            .int(&self.z3);
+    }
+
+    pub fn decode(d: &mut Decoder<'_>) -> Option<Self> {
+        Some(LogStarProof {
+            s: d.uint()?,
+            a: d.uint()?,
+            y: d.point::<C>()?,
+            d: d.uint()?,
+            z1: d.int()?,
+            z2: d.uint()?,
+            z3: d.int()?,
+        })
+    }
+}
+
+fn log_star_challenge<C: ZkCurve>(
+    pk: &PaillierPublicKey,
+    c_ct: &BigUint,
+    base: &ProjectivePoint<C>,
+    x: &ProjectivePoint<C>,
+    aux: &RingPedersen,
+    proof: (&BigUint, &BigUint, &AffinePoint<C>, &BigUint),
+    context: &[u8],
+) -> BigInt {
+    let mut t = Transcript::new(b"log*", context);
+    t.uint(&pk.n)
+        .uint(c_ct)
+        .point::<C>(&base.to_affine())
+        .point::<C>(&x.to_affine());
+    aux.absorb(&mut t);
+    t.uint(proof.0)
+        .uint(proof.1)
+        .point::<C>(proof.2)
+        .uint(proof.3);
+    t.challenge::<C>()
+}
+
+/// Prove that `c_ct = enc(pk, x; rho)` and the point `x * base` hide the
+/// same `x`, against the verifier's parameters `aux`.
+pub fn prove_log_star<C: ZkCurve>(
+    pk: &PaillierPublicKey,
+    c_ct: &BigUint,
+    x: &BigInt,
+    rho: &BigUint,
+    base: &ProjectivePoint<C>,
+    aux: &RingPedersen,
+    context: &[u8],
+) -> LogStarProof<C> {
+    let (ell, eps) = (ell::<C>(), epsilon::<C>());
+    let alpha = sample_pm(ell + eps, &BigUint::one());
+    let mu = sample_pm(ell, &aux.n);
+    let r = sample_unit(&pk.n);
+    let gamma = sample_pm(ell + eps, &aux.n);
+
+    let s = aux.commit(x, &mu);
+    let a = enc(pk, &alpha, &r);
+    let y = (*base * int_to_scalar::<C>(&alpha)).to_affine();
+    let d = aux.commit(&alpha, &gamma);
+    let big_x = *base * int_to_scalar::<C>(x);
+    let e = log_star_challenge::<C>(pk, c_ct, base, &big_x, aux, (&s, &a, &y, &d), context);
+
+    LogStarProof {
+        z1: &alpha + &e * x,
+        z2: r * rho.modpow(e.magnitude(), &pk.n) % &pk.n,
+        z3: gamma + &e * mu,
+        s,
+        a,
+        y,
+        d,
+    }
+}
+
+/// Verify a Π^log* proof that `c_ct` under `pk` and `x = dlog_base(X)`
+/// agree, against the verifier's own parameters `aux`.
+pub fn verify_log_star<C: ZkCurve>(
+    pk: &PaillierPublicKey,
+    c_ct: &BigUint,
+    base: &ProjectivePoint<C>,
+    x: &ProjectivePoint<C>,
+    aux: &RingPedersen,
+    proof: &LogStarProof<C>,
+    context: &[u8],
+) -> bool {
+    if !in_range(&proof.z1, ell::<C>() + epsilon::<C>())
+        || !is_unit(&proof.z2, &pk.n)
+        || proof.a >= pk.n_squared
+        || c_ct >= &pk.n_squared
+    {
+        return false;
+    }
+    let e = log_star_challenge::<C>(
+        pk,
+        c_ct,
+        base,
+        x,
+        aux,
+        (&proof.s, &proof.a, &proof.y, &proof.d),
+        context,
+    );
+    let lhs = enc(pk, &proof.z1, &proof.z2);
+    let rhs = &proof.a * c_ct.modpow(e.magnitude(), &pk.n_squared) % &pk.n_squared;
+    let point_ok = *base * int_to_scalar::<C>(&proof.z1)
+        == ProjectivePoint::<C>::from(proof.y) + *x * int_to_scalar::<C>(&e);
+    lhs == rhs
+        && point_ok
+        && aux.commit(&proof.z1, &proof.z3) == aux.times_pow(&proof.d, &proof.s, &e)
+}
+
+// ---------------------------------------------------------------------
+// Π^aff-g — affine operation with a group commitment
+// ---------------------------------------------------------------------
+
+/// Public inputs of a Π^aff-g proof: `D = C^x * enc0(y; ρ)` under the
+/// receiver's key `pk0`, `Y = enc1(y; ρ_y)` under the prover's key
+/// `pk1`, and `X = x * G`.
+#[derive(Debug, Clone, Copy)]
+pub struct AffGStatement<'a, C: ZkCurve = NistP256> {
+    pub pk0: &'a PaillierPublicKey,
+    pub pk1: &'a PaillierPublicKey,
+    pub c: &'a BigUint,
+    pub d: &'a BigUint,
+    pub y: &'a BigUint,
+    pub x: &'a ProjectivePoint<C>,
+}
+
+impl<C: ZkCurve> AffGStatement<'_, C> {
+    fn absorb(&self, t: &mut Transcript) {
+        t.uint(&self.pk0.n)
+            .uint(&self.pk1.n)
+            .uint(self.c)
+            .uint(self.d)
+            .uint(self.y)
+            .point::<C>(&self.x.to_affine());
+    }
+}
+
+/// Π^aff-g: `x ∈ ±2^ℓ` and `y ∈ ±2^ℓ'` satisfy an [`AffGStatement`].
+#[derive(Debug, Clone)]
+pub struct AffGProof<C: ZkCurve = NistP256> {
+    a: BigUint,
+    b_x: AffinePoint<C>,
+    b_y: BigUint,
+    e_c: BigUint,
+    s_c: BigUint,
+    f_c: BigUint,
+    t_c: BigUint,
+    z1: BigInt,
+    z2: BigInt,
+    z3: BigInt,
+    z4: BigInt,
+    w: BigUint,
+    w_y: BigUint,
+}
+
+impl<C: ZkCurve> AffGProof<C> {
+    pub fn encode(&self, e: &mut Encoder) {
+        e.uint(&self.a)
+            .point::<C>(&self.b_x)
+            .uint(&self.b_y)
+            .uint(&self.e_c)
+            .uint(&self.s_c)
+            .uint(&self.f_c)
+            .uint(&self.t_c)
+            .int(&self.z1)
+            .int(&self.z2)
+            .int(&self.z3)
+            .int(&self.z4)
+            .uint(&self.w)
+            .uint(&self.w_y);
+    }
+
+    pub fn decode(d: &mut Decoder<'_>) -> Option<Self> {
+        Some(AffGProof {
+            a: d.uint()?,
+            b_x: d.point::<C>()?,
+            b_y: d.uint()?,
+            e_c: d.uint()?,
+            s_c: d.uint()?,
+            f_c: d.uint()?,
+            t_c: d.uint()?,
+            z1: d.int()?,
+            z2: d.int()?,
+            z3: d.int()?,
+            z4: d.int()?,
+            w: d.uint()?,
+            w_y: d.uint()?,
+        })
+    }
+
+    fn challenge(&self, st: &AffGStatement<'_, C>, aux: &RingPedersen, context: &[u8]) -> BigInt {
+        let mut t = Transcript::new(b"aff-g", context);
+        st.absorb(&mut t);
+        aux.absorb(&mut t);
+        t.uint(&self.a)
+            .point::<C>(&self.b_x)
+            .uint(&self.b_y)
+            .uint(&self.e_c)
+            .uint(&self.s_c)
+            .uint(&self.f_c)
+            .uint(&self.t_c);
+        t.challenge::<C>()
+    }
+}
+
+/// Prove an [`AffGStatement`] from its witness, against the verifier's
+/// ring-Pedersen parameters `aux`.
+pub fn prove_aff_g<C: ZkCurve>(
+    st: &AffGStatement<'_, C>,
+    x: &BigInt,
+    y: &BigInt,
+    rho: &BigUint,
+    rho_y: &BigUint,
+    aux: &RingPedersen,
+    context: &[u8],
+) -> AffGProof<C> {
+    let (pk0, pk1) = (st.pk0, st.pk1);
+    let (ell, eps) = (ell::<C>(), epsilon::<C>());
+    let alpha = sample_pm(ell + eps, &BigUint::one());
+    let beta = sample_pm(ell_prime::<C>() + eps, &BigUint::one());
+    let r = sample_unit(&pk0.n);
+    let r_y = sample_unit(&pk1.n);
+    let gamma = sample_pm(ell + eps, &aux.n);
+    let m = sample_pm(ell, &aux.n);
+    let delta = sample_pm(ell + eps, &aux.n);
+    let mu = sample_pm(ell, &aux.n);
+
+    let c_alpha = pow_signed(st.c, &alpha, &pk0.n_squared).unwrap_or_default();
+    let mut proof = AffGProof {
+        a: c_alpha * enc(pk0, &beta, &r) % &pk0.n_squared,
+        b_x: (ProjectivePoint::<C>::generator() * int_to_scalar::<C>(&alpha)).to_affine(),
+        b_y: enc(pk1, &beta, &r_y),
+        e_c: aux.commit(&alpha, &gamma),
+        s_c: aux.commit(x, &m),
+        f_c: aux.commit(&beta, &delta),
+        t_c: aux.commit(y, &mu),
+        z1: BigInt::zero(),
+        z2: BigInt::zero(),
+        z3: BigInt::zero(),
+        z4: BigInt::zero(),
+        w: BigUint::zero(),
+        w_y: BigUint::zero(),
+    };
+    let e = proof.challenge(st, aux, context);
+    proof.z1 = alpha + &e * x;
+    proof.z2 = beta + &e * y;
+    proof.z3 = gamma + &e * m;
+    proof.z4 = delta + &e * mu;
+    proof.w = r * rho.modpow(e.magnitude(), &pk0.n) % &pk0.n;
+    proof.w_y = r_y * rho_y.modpow(e.magnitude(), &pk1.n) % &pk1.n;
+    proof
+}
+
+/// Verify a Π^aff-g proof for `st`, against the verifier's own
+/// parameters `aux`.
+pub fn verify_aff_g<C: ZkCurve>(
+    st: &AffGStatement<'_, C>,
+    aux: &RingPedersen,
+    proof: &AffGProof<C>,
+    context: &[u8],
+) -> bool {
+    let (pk0, pk1) = (st.pk0, st.pk1);
+    let eps = epsilon::<C>();
+    if !in_range(&proof.z1, ell::<C>() + eps)
+        || !in_range(&proof.z2, ell_prime::<C>() + eps)
+        || !is_unit(&proof.w, &pk0.n)
+        || !is_unit(&proof.w_y, &pk1.n)
+        || [st.c, st.d, &proof.a].iter().any(|v| *v >= &pk0.n_squared)
+        || [st.y, &proof.b_y].iter().any(|v| *v >= &pk1.n_squared)
+    {
+        return false;
+    }
+    let e = proof.challenge(st, aux, context);
+
+    let Some(c_z1) = pow_signed(st.c, &proof.z1, &pk0.n_squared) else {
+        return false;
+    };
+    let lhs0 = c_z1 * enc(pk0, &proof.z2, &proof.w) % &pk0.n_squared;
+    let rhs0 = &proof.a * st.d.modpow(e.magnitude(), &pk0.n_squared) % &pk0.n_squared;
+
+    let point_ok = ProjectivePoint::<C>::generator() * int_to_scalar::<C>(&proof.z1)
+        == ProjectivePoint::<C>::from(proof.b_x) + *st.x * int_to_scalar::<C>(&e);
+
+    let lhs1 = enc(pk1, &proof.z2, &proof.w_y);
+    let rhs1 = &proof.b_y * st.y.modpow(e.magnitude(), &pk1.n_squared) % &pk1.n_squared;
+
+    lhs0 == rhs0
+        && point_ok
+        && lhs1 == rhs1
+        && aux.commit(&proof.z1, &proof.z3) == aux.times_pow(&proof.e_c, &proof.s_c, &e)
+        && aux.commit(&proof.z2, &proof.z4) == aux.times_pow(&proof.f_c, &proof.t_c, &e)
+}
+
+// ---------------------------------------------------------------------
+// MtA
+// ---------------------------------------------------------------------
+
+/// Paillier-encrypt a scalar, returning the ciphertext and the
+/// randomness (the witness for [`prove_enc`] / [`prove_log_star`]).
+pub fn encrypt_scalar<C: ZkCurve>(pk: &PaillierPublicKey, x: &Scalar<C>) -> (BigUint, BigUint) {
+    let rho = sample_unit(&pk.n);
+    let ct = enc(pk, &BigInt::from(scalar_to_int::<C>(x)), &rho);
+    (ct, rho)
+}
+
+/// The responder's message in one MtA: `D = K^x * enc0(y)` for the
+/// receiver, `F = enc1(y)` under the responder's own key, and the
+/// Π^aff-g proof tying both to `X = x * G`.
+#[derive(Debug, Clone)]
+pub struct MtaResponse<C: ZkCurve = NistP256> {
+    pub d: BigUint,
+    pub f: BigUint,
+    pub proof: AffGProof<C>,
+}
+
+impl<C: ZkCurve> MtaResponse<C> {
+    pub fn encode(&self, e: &mut Encoder) {
+        e.uint(&self.d).uint(&self.f);
+        self.proof.encode(e);
+    }
+
+    pub fn decode(d: &mut Decoder<'_>) -> Option<Self> {
+        Some(MtaResponse {
+            d: d.uint()?,
+            f: d.uint()?,
+            proof: AffGProof::decode(d)?,
+        })
+    }
+}
+
+/// Answer the receiver's `k_ct = enc(receiver, k)` with the responder's
+/// `x`. Returns the message for the receiver and the responder's
+/// additive share `-y`; the receiver's share is [`mta_receive`] of it,
+/// and the two sum to `k * x`.
+pub fn mta_respond<C: ZkCurve>(
+    receiver: &PaillierPublicKey,
+    k_ct: &BigUint,
+    responder: &PaillierPublicKey,
+    x: &Scalar<C>,
+    receiver_aux: &RingPedersen,
+    context: &[u8],
+) -> (MtaResponse<C>, Scalar<C>) {
+    let x_int = BigInt::from(scalar_to_int::<C>(x));
+    let y = sample_pm(ell_prime::<C>(), &BigUint::one());
+    let rho = sample_unit(&receiver.n);
+    let rho_y = sample_unit(&responder.n);
+
+    let d = k_ct.modpow(x_int.magnitude(), &receiver.n_squared) * enc(receiver, &y, &rho)
+        % &receiver.n_squared;
+    let f = enc(responder, &y, &rho_y);
+    let x_point = ProjectivePoint::<C>::generator() * x;
+    let st = AffGStatement {
+        pk0: receiver,
+        pk1: responder,
+        c: k_ct,
+        d: &d,
+        y: &f,
+        x: &x_point,
+    };
+    let proof = prove_aff_g(&st, &x_int, &y, &rho, &rho_y, receiver_aux, context);
+    let share = int_to_scalar::<C>(&-y);
+    (MtaResponse { d, f, proof }, share)
+}
+
+/// Receiver-side check of an [`MtaResponse`] to `k_ct`: the responder
+/// used the `x` behind `x_point` and a mask in range.
+pub fn mta_verify<C: ZkCurve>(
+    receiver: &PaillierPublicKey,
+    k_ct: &BigUint,
+    responder: &PaillierPublicKey,
+    x_point: &ProjectivePoint<C>,
+    receiver_aux: &RingPedersen,
+    response: &MtaResponse<C>,
+    context: &[u8],
+) -> bool {
+    let st = AffGStatement {
+        pk0: receiver,
+        pk1: responder,
+        c: k_ct,
+        d: &response.d,
+        y: &response.f,
+        x: x_point,
+    };
+    verify_aff_g(&st, receiver_aux, &response.proof, context)
+}
+
+/// The receiver's additive share: `D` decrypted and reduced mod `q`.
+pub fn mta_receive<C: ZkCurve>(
+    receiver: &PaillierKeypair,
+    response: &MtaResponse<C>,
+) -> Option<Scalar<C>> {
+    dec_signed(receiver, &response.d).map(|v| int_to_scalar::<C>(&v))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use getrandom::SysRng;
+    use p256::elliptic_curve::Field;
+    use p256::elliptic_curve::rand_core::UnwrapErr;
+    use p256::{NistP256 as P, ProjectivePoint, Scalar};
+
+    /// 512-bit moduli: large enough for every proof to be complete,
+    /// small enough to keep the tests quick. MtA itself needs the full
+    /// size, see `mta_shares_sum_to_product`.
+    fn small_aux() -> AuxSecret {
+        AuxSecret::generate_with(256)
+    }
+
+    fn random_scalar() -> Scalar {
+        Scalar::random(&mut UnwrapErr(SysRng))
+    }
+
+    #[test]
+    fn jacobi_matches_small_table() {
+        // (a / 15) for a = 1..=14.
+        let expected = [1, 1, 0, 1, 0, 0, -1, 1, 0, 0, -1, 0, -1, -1];
+        for (a, want) in (1u32..).zip(expected) {
+            assert_eq!(
+                jacobi(&BigUint::from(a), &BigUint::from(15u32)),
+                want,
+                "a = {a}"
+            );
+        }
+    }
+
+    #[test]
+    fn generated_primes_are_blum() {
+        let p = blum_prime(128);
+        assert_eq!(p.bits(), 128);
+        assert_eq!(&p % 4u32, BigUint::from(3u32));
+        assert!(paillier::miller_rabin(&p, 20));
+    }
+
+    #[test]
+    fn aux_proofs_verify_and_bind_context() {
+        let aux = small_aux();
+        let info = aux.prove(b"ctx");
+        assert!(verify_mod(&info.params.n, &info.mod_proof, b"ctx"));
+        assert!(verify_prm(&info.params, &info.prm_proof, b"ctx"));
+        assert!(!verify_mod(&info.params.n, &info.mod_proof, b"other"));
+        assert!(!verify_prm(&info.params, &info.prm_proof, b"other"));
+        // Below the production modulus size.
+        assert!(!info.verify(b"ctx"));
+    }
+
+    #[test]
+    fn mod_proof_rejects_prime_modulus_and_wrong_n() {
+        let aux = small_aux();
+        let info = aux.prove(b"ctx");
+        assert!(!verify_mod(&aux.p, &info.mod_proof, b"ctx"));
+        let other = small_aux();
+        assert!(!verify_mod(&other.params.n, &info.mod_proof, b"ctx"));
+    }
+
+    #[test]
+    fn prm_proof_rejects_unrelated_s() {
+        let aux = small_aux();
+        let info = aux.prove(b"ctx");
+        let mut params = info.params.clone();
+        params.s = &params.s * &params.t % &params.n;
+        assert!(!verify_prm(&params, &info.prm_proof, b"ctx"));
+    }
+
+    #[test]
+    fn aux_info_and_secret_roundtrip() {
+        let aux = small_aux();
+        let info = aux.prove(b"ctx");
+        let mut e = Encoder::new();
+        info.encode(&mut e);
+        let bytes = e.finish();
+        let mut d = Decoder::new(&bytes);
+        let back = AuxInfo::decode(&mut d).expect("decodes");
+        d.finish().expect("no trailing bytes");
+        assert_eq!(back.params, info.params);
+        assert!(verify_mod(&back.params.n, &back.mod_proof, b"ctx"));
+
+        let restored = AuxSecret::from_bytes(&aux.to_bytes()).expect("restores");
+        assert_eq!(restored.params(), aux.params());
+    }
+
+    #[test]
+    fn enc_proof_accepts_honest_and_rejects_out_of_range() {
+        let prover = small_aux();
+        let verifier = small_aux();
+        let pk = &prover.paillier().public;
+        let k = random_scalar();
+        let (ct, rho) = encrypt_scalar::<P>(pk, &k);
+        let k_int = BigInt::from(scalar_to_int::<P>(&k));
+        let proof = prove_enc::<P>(pk, &ct, &k_int, &rho, verifier.params(), b"ctx");
+        assert!(verify_enc::<P>(pk, &ct, verifier.params(), &proof, b"ctx"));
+        assert!(!verify_enc::<P>(
+            pk,
+            &ct,
+            verifier.params(),
+            &proof,
+            b"other"
+        ));
+
+        // A plaintext far outside ±2^ℓ cannot produce an in-range z1.
+        let huge = BigInt::one() << (ell::<P>() + epsilon::<P>() + 8);
+        let rho = sample_unit(&pk.n);
+        let ct = enc(pk, &huge, &rho);
+        let proof = prove_enc::<P>(pk, &ct, &huge, &rho, verifier.params(), b"ctx");
+        assert!(!verify_enc::<P>(pk, &ct, verifier.params(), &proof, b"ctx"));
+    }
+
+    #[test]
+    fn log_star_proof_binds_point_to_ciphertext() {
+        let prover = small_aux();
+        let verifier = small_aux();
+        let pk = &prover.paillier().public;
+        let x = random_scalar();
+        let (ct, rho) = encrypt_scalar::<P>(pk, &x);
+        let base = ProjectivePoint::GENERATOR * random_scalar();
+        let x_int = BigInt::from(scalar_to_int::<P>(&x));
+        let proof = prove_log_star::<P>(pk, &ct, &x_int, &rho, &base, verifier.params(), b"ctx");
+        let big_x = base * x;
+        assert!(verify_log_star::<P>(
+            pk,
+            &ct,
+            &base,
+            &big_x,
+            verifier.params(),
+            &proof,
+            b"ctx"
+        ));
+        let wrong = base * (x + Scalar::ONE);
+        assert!(!verify_log_star::<P>(
+            pk,
+            &ct,
+            &base,
+            &wrong,
+            verifier.params(),
+            &proof,
+            b"ctx"
+        ));
+    }
+
+    #[test]
+    fn aff_g_proof_rejects_tampered_ciphertext() {
+        let receiver = small_aux();
+        let responder = small_aux();
+        let (pk0, pk1) = (&receiver.paillier().public, &responder.paillier().public);
+        let (k_ct, _) = encrypt_scalar::<P>(pk0, &random_scalar());
+        let x = random_scalar();
+        let (mut resp, _) = mta_respond::<P>(pk0, &k_ct, pk1, &x, receiver.params(), b"ctx");
+        let x_point = ProjectivePoint::GENERATOR * x;
+        assert!(mta_verify::<P>(
+            pk0,
+            &k_ct,
+            pk1,
+            &x_point,
+            receiver.params(),
+            &resp,
+            b"ctx"
+        ));
+        let other_x = ProjectivePoint::GENERATOR * random_scalar();
+        assert!(!mta_verify::<P>(
+            pk0,
+            &k_ct,
+            pk1,
+            &other_x,
+            receiver.params(),
+            &resp,
+            b"ctx"
+        ));
+        resp.d = &resp.d * &k_ct % &pk0.n_squared;
+        assert!(!mta_verify::<P>(
+            pk0,
+            &k_ct,
+            pk1,
+            &x_point,
+            receiver.params(),
+            &resp,
+            b"ctx"
+        ));
+    }
+
+    fn mta_round_trip<C: ZkCurve>() {
+        let receiver = AuxSecret::generate();
+        let responder = AuxSecret::generate();
+        let (pk0, pk1) = (&receiver.paillier().public, &responder.paillier().public);
+        let k = elliptic_curve::Scalar::<C>::random(&mut UnwrapErr(SysRng));
+        let x = elliptic_curve::Scalar::<C>::random(&mut UnwrapErr(SysRng));
+        let (k_ct, _) = encrypt_scalar::<C>(pk0, &k);
+        let (resp, beta) = mta_respond::<C>(pk0, &k_ct, pk1, &x, receiver.params(), b"ctx");
+        let x_point = elliptic_curve::ProjectivePoint::<C>::generator() * x;
+        assert!(mta_verify::<C>(
+            pk0,
+            &k_ct,
+            pk1,
+            &x_point,
+            receiver.params(),
+            &resp,
+            b"ctx"
+        ));
+        let alpha = mta_receive::<C>(receiver.paillier(), &resp).expect("decrypts");
+        assert_eq!(alpha + beta, k * x);
+    }
+
+    #[test]
+    fn mta_shares_sum_to_product() {
+        mta_round_trip::<P>();
+    }
+
+    /// The ranges grow with the order; a 384-bit MtA still fits the
+    /// 2048-bit modulus.
+    #[test]
+    fn mta_shares_sum_to_product_on_p384_and_secp256k1() {
+        mta_round_trip::<p384::NistP384>();
+        mta_round_trip::<k256::Secp256k1>();
+    }
+}
@@ -6,13 +6,16 @@
 //! ```text
 //! confiumd --listen tcp://127.0.0.1:7878
 //! confiumd --listen unix:///var/run/confium.sock
+//! confiumd --plugin-policy audit --trust-home /etc/confium
 //! ```
 
 use std::path::PathBuf;
 use std::rc::Rc;
 
 use clap::Parser;
+use confium_core::plugin_policy::{PluginPolicy, PluginVerifyMode};
 use confium_daemon::Server;
+use confium_registry::TrustStore;
 use tokio::net::TcpListener;
 
 /// Listen address spec. Parsed from `--listen <scheme>://<addr>`.
@@ -54,17 +57,38 @@ struct Args {
     /// Disable audit logging (useful for tests / CI).
     #[arg(long)]
     no_audit: bool,
+
+    /// Plugin verification before load: `enforce` refuses plugins the
+    /// trust store does not vouch for, `audit` records the decision but
+    /// loads anyway, `off` skips verification. RPC clients cannot
+    /// change this.
+    #[arg(long, default_value = "enforce")]
+    plugin_policy: PluginVerifyMode,
+
+    /// Home directory whose Confium trust store plugins are verified
+    /// against. Defaults to the daemon user's own.
+    #[arg(long)]
+    trust_home: Option<PathBuf>,
 }
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args = Args::parse();
 
-    let cfm = if args.no_audit {
+    let mut cfm = if args.no_audit {
         confium_core::Confium::new_with_audit(confium_core::audit::AuditLogger::disabled())
     } else {
         confium_core::Confium::new()
     };
+    let trust = match args.trust_home {
+        Some(home) => TrustStore::for_home(home),
+        None => TrustStore::new(),
+    };
+    cfm.set_plugin_policy(match args.plugin_policy {
+        PluginVerifyMode::Enforce => PluginPolicy::enforce(trust),
+        PluginVerifyMode::AuditOnly => PluginPolicy::audit_only(trust),
+        PluginVerifyMode::Disabled => PluginPolicy::disabled(),
+    });
     let server = Rc::new(Server::with_confium(cfm));
 
     match &args.listen {
@@ -10,9 +10,13 @@ use crate::error::RpcError;
 use crate::server::SharedConfium;
 
 /// `plugin_load({ "path": "...", "name": "botan", "options": {} })`
-/// → `{"success": true}`
+/// → `{"success": true, "verification": "enforce"}`
 ///
-/// Delegates to [`Confium::load_plugin`]. The options map is passed
+/// Delegates to [`Confium::load_plugin`], which verifies the file
+/// against the daemon's plugin policy before mapping it. The policy is
+/// fixed by `confiumd --plugin-policy` and reported back as
+/// `verification`; a plugin the policy refuses fails with an engine
+/// error naming the reason. The options map is passed
 /// through as a string-keyed map (matching the C FFI's `Options` type,
 /// which is `HashMap<String, String>` today).
 ///
@@ -45,7 +49,10 @@ pub async fn plugin_load(
             message: e.to_string(),
         })?;
 
-    Ok(json!({ "success": true }))
+    Ok(json!({
+        "success": true,
+        "verification": cfm.plugin_policy().mode().as_str(),
+    }))
 }
 
 /// `plugin_unload({ "name": "botan" })` → `{"success": true}`
@@ -83,6 +90,23 @@ mod tests {
     use super::*;
     use crate::test_util::test_confium;
 
+    #[tokio::test]
+    async fn unsigned_plugin_is_refused_by_default_policy() {
+        let dir = tempfile::tempdir().unwrap();
+        let plugin = dir.path().join("libloose.so");
+        std::fs::write(&plugin, b"\x7fELF").unwrap();
+        let err = plugin_load(
+            test_confium(),
+            json!({ "path": plugin.to_str().unwrap(), "name": "loose" }),
+        )
+        .await
+        .unwrap_err();
+        assert!(
+            matches!(&err, RpcError::Engine { message } if message.contains("failed verification")),
+            "unexpected error {err:?}"
+        );
+    }
+
     #[tokio::test]
     async fn plugin_list_returns_array() {
         let result = plugin_list(test_confium(), json!({})).await.unwrap();
@@ -0,0 +1,58 @@
+//! Private lookups against one untrusted server (SimplePIR over LWE).
+//!
+//! A monitor fetches a transparency-log entry hash and a revocation
+//! status byte without the server learning which ones it asked for.
+//!
+//! ```sh
+//! cargo run --example privacy_pir_log_lookup -p confium-examples
+//! ```
+
+use confium_privacy::pir::{PirClient, PirDatabase, PirServer};
+use confium_transparency::MerkleTree;
+use confium_transparency::entry::{ArtifactType, MerkleEntry};
+
+fn main() {
+    // --- Transparency log: one 32-byte entry hash per sequence number.
+    let mut tree = MerkleTree::new();
+    for i in 0..1_000u64 {
+        let mut artifact = [0u8; 32];
+        artifact[..8].copy_from_slice(&i.to_be_bytes());
+        tree.append(MerkleEntry::new(i, ArtifactType::CertificateIssuance, artifact));
+    }
+    let hashes: Vec<[u8; 32]> = (0..tree.len() as u64)
+        .map(|seq| tree.entry(seq).expect("entry").entry_hash())
+        .collect();
+
+    // Server side: build the database and publish the hint once.
+    // Production servers write the records to a file and use
+    // `PirDatabase::open` to serve them from a memory map.
+    let server = PirServer::new(PirDatabase::from_records(32, &hashes).expect("database"));
+    let client = PirClient::new(server.hint()).expect("hint");
+    println!(
+        "Log database: {} records in a {}×{} matrix",
+        server.params().num_records,
+        server.params().rows(),
+        server.params().columns
+    );
+
+    // Client side: privately fetch entry 742 and check it against the log.
+    let (query, state) = client.query(742).expect("query");
+    let answer = server.answer(&query).expect("answer");
+    let fetched = client.decode(&state, &answer).expect("decode");
+    assert_eq!(fetched, hashes[742]);
+    println!("Entry 742 hash: {}", hex::encode(&fetched));
+
+    // --- Revocation status: one byte per serial slot (0 = good, 1 = revoked).
+    let mut status = vec![0u8; 4_096];
+    status[1_234] = 1;
+    let server = PirServer::new(PirDatabase::from_bytes(1, status).expect("database"));
+    let client = PirClient::new(server.hint()).expect("hint");
+    for serial in [1_233, 1_234] {
+        let (query, state) = client.query(serial).expect("query");
+        let answer = server.answer(&query).expect("answer");
+        let revoked = client.decode(&state, &answer).expect("decode")[0] == 1;
+        println!("Serial slot {serial}: {}", if revoked { "revoked" } else { "good" });
+    }
+
+    println!("\n✅ Private lookups complete.");
+}
@@ -1,23 +1,37 @@
-//! Two-party Private Set Intersection (hash-based).
+//! Two-party Private Set Intersection (DH/OPRF over ristretto255).
 //!
 //! ```sh
 //! cargo run --example privacy_psi_two_party -p confium-examples
 //! ```
 
-fn main() {
-    let set_a: Vec<Vec<u8>> = vec![b"alice".to_vec(), b"bob".to_vec(), b"carol".to_vec()];
-    let set_b: Vec<Vec<u8>> = vec![b"bob".to_vec(), b"carol".to_vec(), b"dave".to_vec()];
-    let salt = b"shared-secret-salt";
+use confium_privacy::psi::{PsiMode, PsiOutput, PsiReceiver, PsiSender};
 
-    let intersection =
-        confium_privacy::privacy_and_dist_patterns::psi_hash_based(&set_a, &set_b, salt);
+fn run(mode: PsiMode, set_a: &[&[u8]], set_b: &[&[u8]]) -> PsiOutput {
+    // Party A (receiver) blinds its set; party B (sender) re-blinds it
+    // and blinds its own. Neither side ever sees the other's hashes.
+    let mut receiver = PsiReceiver::new(mode);
+    let sender = PsiSender::new();
+    let reblinded = sender.reblind(&receiver.blind(set_a)).unwrap();
+    receiver.absorb_reblinded(0, &reblinded);
+    receiver
+        .absorb_sender_set(&sender.blind_set(set_b))
+        .unwrap();
+    receiver.finish()
+}
+
+fn main() {
+    let set_a: [&[u8]; 3] = [b"alice", b"bob", b"carol"];
+    let set_b: [&[u8]; 3] = [b"bob", b"carol", b"dave"];
 
     println!("Intersection:");
-    for item in &intersection {
-        println!("  {}", String::from_utf8_lossy(item));
+    if let PsiOutput::Intersection(indices) = run(PsiMode::Intersection, &set_a, &set_b) {
+        for i in indices {
+            println!("  {}", String::from_utf8_lossy(set_a[i]));
+        }
     }
 
-    let count = confium_privacy::privacy_and_dist_patterns::psi_cardinality(&set_a, &set_b, salt);
-    println!("Cardinality: {}", count);
+    if let PsiOutput::Cardinality(count) = run(PsiMode::Cardinality, &set_a, &set_b) {
+        println!("Cardinality: {}", count);
+    }
     println!("\n✅ PSI complete.");
 }
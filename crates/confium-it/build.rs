@@ -1,5 +1,6 @@
-// Build script for the integration test that loads the macro-built
-// mock plugin. Cargo doesn't expose `CARGO_CDYLIB_FILE_*` for runtime
+// Build script for the integration tests that load the macro-built
+// plugins (the mock plugin and the RustCrypto provider) and the wasm
+// guest fixture. Cargo doesn't expose `CARGO_CDYLIB_FILE_*` for runtime
 // use in test binaries (only for build scripts), so we locate the
 // artifact at build time and emit a `cfg`-driven path constant that the
 // test reads via `env!`.
@@ -9,11 +10,14 @@
 // from the target dir and platform file naming.
 
 use std::env;
-use std::path::PathBuf;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
 
 fn main() {
-    // Re-run if the plugin source changes.
+    // Re-run if a plugin's source changes.
     println!("cargo:rerun-if-changed=../confium-mock-plugin/src");
+    println!("cargo:rerun-if-changed=../confium-rustcrypto-plugin/src");
 
     let target_dir = env::var_os("CARGO_TARGET_DIR")
         .map(PathBuf::from)
@@ -52,13 +56,86 @@ fn main() {
         _ => ("lib", ".so"),
     };
 
-    let filename = format!("{prefix}confium_mock_plugin{suffix}");
-    let cdylib_path = profile_dir.join(filename);
+    // Tell the tests where to find each artifact. The paths are
+    // absolute filesystem paths.
+    for (lib, var) in [
+        ("confium_mock_plugin", "CONFIUM_MOCK_PLUGIN_PATH"),
+        (
+            "confium_rustcrypto_plugin",
+            "CONFIUM_RUSTCRYPTO_PLUGIN_PATH",
+        ),
+    ] {
+        let cdylib_path = profile_dir.join(format!("{prefix}{lib}{suffix}"));
+        println!("cargo:rustc-env={var}={}", cdylib_path.display());
+    }
 
-    // Tell the test where to find the artifact. The path is an absolute
-    // filesystem path.
+    // The sandbox tests also need confium-core's plugin host binary.
+    // Cargo does not build a dependency's binaries for us, so it is
+    // present only after a `cargo build --workspace`; the tests skip
+    // when it is missing.
+    let exe_suffix = match env::var("CARGO_CFG_TARGET_OS").as_deref() {
+        Ok("windows") => ".exe",
+        _ => "",
+    };
+    let host_path = profile_dir.join(format!("confium-plugin-host{exe_suffix}"));
     println!(
-        "cargo:rustc-env=CONFIUM_MOCK_PLUGIN_PATH={}",
-        cdylib_path.display()
+        "cargo:rustc-env=CONFIUM_PLUGIN_HOST_PATH={}",
+        host_path.display()
     );
+
+    // The wasm sandbox tests load a guest component compiled from
+    // `fixtures/wasm-xor-plugin`. Building it needs the
+    // wasm32-unknown-unknown target; without it the tests skip.
+    println!("cargo:rerun-if-changed=fixtures/wasm-xor-plugin/src");
+    println!("cargo:rerun-if-changed=fixtures/wasm-xor-plugin/Cargo.toml");
+    println!("cargo:rerun-if-changed=../confium-api/src");
+    println!("cargo:rerun-if-changed=../confium-wasm-guest/src");
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").expect("OUT_DIR must be set"));
+    match build_wasm_fixture(&out_dir) {
+        Ok(path) => println!(
+            "cargo:rustc-env=CONFIUM_WASM_XOR_PLUGIN_PATH={}",
+            path.display()
+        ),
+        Err(e) => println!("cargo:warning=wasm fixture not built, its tests will skip: {e}"),
+    }
+}
+
+/// Compile the fixture guest as a core module and wrap it as a
+/// component. Returns the path of the component file.
+fn build_wasm_fixture(out_dir: &Path) -> Result<PathBuf, String> {
+    let manifest_dir = PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").ok_or("no manifest dir")?);
+    let fixture = manifest_dir.join("fixtures/wasm-xor-plugin");
+    let target_dir = out_dir.join("wasm-xor-plugin");
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+
+    // The nested build must not inherit the host build's flags or the
+    // clippy wrapper.
+    let status = Command::new(cargo)
+        .arg("build")
+        .arg("--release")
+        .arg("--target=wasm32-unknown-unknown")
+        .arg("--manifest-path")
+        .arg(fixture.join("Cargo.toml"))
+        .arg("--target-dir")
+        .arg(&target_dir)
+        .env_remove("CARGO_ENCODED_RUSTFLAGS")
+        .env_remove("RUSTC_WORKSPACE_WRAPPER")
+        .env_remove("CARGO_TARGET_DIR")
+        .status()
+        .map_err(|e| format!("cannot run cargo: {e}"))?;
+    if !status.success() {
+        return Err(format!("cargo build exited with {status}"));
+    }
+
+    let module_path = target_dir.join("wasm32-unknown-unknown/release/wasm_xor_plugin.wasm");
+    let module = fs::read(&module_path)
+        .map_err(|e| format!("cannot read {}: {e}", module_path.display()))?;
+    let component = wit_component::ComponentEncoder::default()
+        .module(&module)
+        .and_then(|encoder| encoder.validate(true).encode())
+        .map_err(|e| format!("cannot encode the component: {e:#}"))?;
+    let component_path = out_dir.join("wasm_xor_plugin.wasm");
+    fs::write(&component_path, component)
+        .map_err(|e| format!("cannot write {}: {e}", component_path.display()))?;
+    Ok(component_path)
 }
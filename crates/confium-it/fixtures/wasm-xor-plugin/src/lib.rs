@@ -0,0 +1,77 @@
+//! Test fixture: a hash plugin built as a WebAssembly component.
+//!
+//! - `xor` is the mock plugin's one-byte XOR-fold hash, so a result
+//!   can be compared with the native plugin's.
+//! - `spin` never returns from `update`; it exists to exhaust the
+//!   caller's fuel.
+
+use confium_api::error::PluginResult;
+use confium_api::options::OptionView;
+use confium_api::{ErrorCode, HashPlugin, PluginError};
+use confium_wasm_guest::{export, plugin_interface};
+
+pub struct XorHash {
+    acc: u8,
+    spin: bool,
+}
+
+#[plugin_interface(name = "hash", version = 0)]
+impl HashPlugin for XorHash {
+    fn create_with_opts(name: &str, _opts: Option<OptionView<'_>>) -> PluginResult<Self> {
+        match name {
+            "xor" => Ok(Self {
+                acc: 0,
+                spin: false,
+            }),
+            "spin" => Ok(Self { acc: 0, spin: true }),
+            _ => Err(PluginError::new(
+                ErrorCode::UNSUPPORTED_ALGORITHM,
+                "fixture serves xor and spin",
+            )),
+        }
+    }
+
+    fn output_size(&self) -> u32 {
+        1
+    }
+
+    fn block_size(&self) -> u32 {
+        1
+    }
+
+    fn update(&mut self, data: &[u8]) -> PluginResult<()> {
+        while self.spin {
+            self.acc = std::hint::black_box(self.acc.wrapping_add(1));
+        }
+        for &b in data {
+            self.acc ^= b;
+        }
+        Ok(())
+    }
+
+    fn reset(&mut self) -> PluginResult<()> {
+        self.acc = 0;
+        Ok(())
+    }
+
+    fn try_clone(&self) -> PluginResult<Self> {
+        Ok(Self {
+            acc: self.acc,
+            spin: self.spin,
+        })
+    }
+
+    fn finalize(&mut self, out: &mut [u8]) -> PluginResult<()> {
+        let Some(first) = out.first_mut() else {
+            return Err(PluginError::new(
+                ErrorCode::INSUFFICIENT_BUFFER,
+                "xor hash needs at least 1 byte of output buffer",
+            ));
+        };
+        *first = self.acc;
+        Ok(())
+    }
+}
+
+#[export]
+pub struct Plugin;
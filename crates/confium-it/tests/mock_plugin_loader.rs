@@ -6,9 +6,11 @@
 //! `#[plugin_interface]` and `#[export]` are wire-compatible with what
 //! `cfm_plugin_load` expects.
 //!
-//! The mock plugin advertises two interfaces — `hash` and `symmetric`
-//! (cipher) — both auto-discovered from `#[plugin_interface]`
-//! attributes. These tests confirm both load through the real loader.
+//! The mock plugin advertises three interfaces — `hash`, `symmetric`
+//! (cipher) and `signature` (at versions 0 and 1) — all auto-discovered
+//! from `#[plugin_interface]` attributes. These tests confirm they load
+//! through the real loader, and that the loader negotiates signature
+//! v1 and routes key-handle signing to it.
 //!
 //! The mock plugin is built as a cdylib in the same workspace. Cargo
 //! doesn't expose its artifact path to test binaries, so
@@ -32,7 +34,10 @@ use std::ptr;
 use confium::Confium;
 use confium::error::Error;
 use confium::hash::Hash;
+use confium::key_handle::KeyHandle;
 use confium::options::Options;
+use confium::plugin_policy::PluginPolicy;
+use confium::signature::{Signer, Verifier};
 
 /// Path to the macro-built mock plugin's compiled cdylib. Set by the
 /// build script in `confium-it/build.rs` (which computes the
@@ -82,6 +87,8 @@ fn mock_plugin_loads_and_hashes() {
     // touch the filesystem. The plugin load path itself does not depend
     // on audit logging.
     let mut cfm = Confium::new_with_audit(confium::audit::AuditLogger::disabled());
+    // Freshly built test plugins carry no signature or install manifest.
+    cfm.set_plugin_policy(PluginPolicy::disabled());
     let cname = CString::new("mock-hash").unwrap();
     let cpath = CString::new(MOCK_PLUGIN_PATH).unwrap();
     // The loader expects a non-NULL opts pointer (the underlying
@@ -320,3 +327,91 @@ fn mock_plugin_exports_metadata() {
         assert_eq!(CStr::from_ptr(md.license).to_str().unwrap(), "BSD-2-Clause");
     }
 }
+
+/// Load the mock plugin under `name`, or `None` (and the caller skips)
+/// when the artifact is not where build.rs expected it.
+fn load_mock(name: &str) -> Option<Confium> {
+    let mut cfm = Confium::new_with_audit(confium::audit::AuditLogger::disabled());
+    // Freshly built test plugins carry no signature or install manifest.
+    cfm.set_plugin_policy(PluginPolicy::disabled());
+    let cname = CString::new(name).unwrap();
+    let cpath = CString::new(MOCK_PLUGIN_PATH).unwrap();
+    let mut opts = Options::new();
+    let code = unsafe {
+        cfm_plugin_load(
+            &mut cfm,
+            cname.as_ptr(),
+            cpath.as_ptr(),
+            &mut opts,
+            ptr::null_mut(),
+        )
+    };
+    if code != 0 {
+        eprintln!(
+            "warning: cfm_plugin_load returned non-zero code {code}; \
+             MOCK_PLUGIN_PATH={MOCK_PLUGIN_PATH}; skipping test"
+        );
+        return None;
+    }
+    Some(cfm)
+}
+
+#[test]
+fn mock_plugin_advertises_signature_v0_and_v1() {
+    let lib = match unsafe { libloading::Library::new(MOCK_PLUGIN_PATH) } {
+        Ok(l) => l,
+        Err(e) => {
+            eprintln!(
+                "warning: mock plugin failed to load at {MOCK_PLUGIN_PATH}: {e}; skipping test"
+            );
+            return;
+        }
+    };
+    let query: libloading::Symbol<extern "C" fn(*const std::ffi::c_void) -> *const u8> =
+        unsafe { lib.get(b"cfmp_query_interfaces\0") }.expect("symbol resolves");
+    let advertised = parse_query_interfaces(query(std::ptr::null()));
+    let mut versions = advertised
+        .get("signature")
+        .expect("plugin advertises signature")
+        .clone();
+    versions.sort();
+    assert_eq!(versions, [0, 1], "v1 impls also advertise v0");
+    unsafe {
+        lib.get::<*const ()>(b"cfmp_sig_handle_capabilities\0")
+            .expect("v1 capability symbol resolves");
+        lib.get::<*const ()>(b"cfmp_sig_signer_create_with_handle\0")
+            .expect("v1 create-with-handle symbol resolves");
+    }
+}
+
+#[test]
+fn mock_plugin_signs_with_key_handle() {
+    let Some(cfm) = load_mock("mock") else { return };
+    // The mock backend's key bytes are the key id, so a verifier built
+    // from the same bytes checks the handle-based signature.
+    let key_id = "slot-7";
+    let handle = KeyHandle::new("mock", key_id, ptr::null_mut()).unwrap();
+    let mut signer = Signer::with_handle(&cfm, "xor-sig", &handle, Some("mock"), None)
+        .expect("v1 provider accepts mock handles");
+    signer.update(b"hello").unwrap();
+    let mut sig = [0u8; 1];
+    assert_eq!(signer.finalize(&mut sig).unwrap(), 1);
+
+    let mut verifier =
+        Verifier::new(&cfm, "xor-sig", key_id.as_bytes(), Some("mock"), None).unwrap();
+    verifier.update(b"hello").unwrap();
+    verifier.finalize(&sig).expect("handle signature verifies");
+}
+
+#[test]
+fn key_handle_from_unknown_backend_is_rejected() {
+    let Some(cfm) = load_mock("mock") else { return };
+    let handle = KeyHandle::new("aws-kms", "arn:aws:kms:k", ptr::null_mut()).unwrap();
+    let err = Signer::with_handle(&cfm, "xor-sig", &handle, None, None)
+        .err()
+        .expect("no provider declares capability for aws-kms handles");
+    assert!(
+        matches!(err, Error::KeyHandleUnsupported { .. }),
+        "unexpected error {err}"
+    );
+}
@@ -0,0 +1,304 @@
+//! Load-time verification of plugin binaries.
+//!
+//! Each test copies the compiled mock plugin into a scratch directory
+//! and loads it under a [`PluginPolicy`] whose trust store lives in
+//! that directory, so the user's real trust store is never consulted.
+//! The mock plugin ships unsigned; tests that need it admitted sign
+//! it with a throwaway `gpg` key and skip when `gpg` is unavailable.
+
+#![allow(improper_ctypes)]
+
+use std::ffi::CString;
+use std::io::Write;
+use std::os::raw::c_char;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::ptr;
+use std::sync::{Arc, Mutex};
+
+use confium::Confium;
+use confium::audit::AuditLogger;
+use confium::error::{Error, ErrorCode};
+use confium::hash::Hash;
+use confium::options::Options;
+use confium::plugin_policy::PluginPolicy;
+use confium_registry::{TrustStore, TrustStoreEntry, sha256_hex, signed_payload};
+
+const MOCK_PLUGIN_PATH: &str = env!("CONFIUM_MOCK_PLUGIN_PATH");
+
+unsafe extern "C" {
+    fn cfm_plugin_load(
+        cfm: *mut Confium,
+        name: *const c_char,
+        path: *const c_char,
+        opts: *mut Options,
+        errptr: *mut *mut Error,
+    ) -> u32;
+}
+
+/// In-memory audit sink the test keeps a handle on.
+#[derive(Clone, Default)]
+struct SharedSink(Arc<Mutex<Vec<u8>>>);
+
+impl Write for SharedSink {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl SharedSink {
+    fn lines(&self) -> Vec<String> {
+        String::from_utf8(self.0.lock().unwrap().clone())
+            .unwrap()
+            .lines()
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+fn scratch_dir(tag: &str) -> PathBuf {
+    let mut p = std::env::temp_dir();
+    p.push(format!(
+        "confium-plugin-verify-{tag}-{}-{}",
+        std::process::id(),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos(),
+    ));
+    std::fs::create_dir_all(&p).unwrap();
+    p
+}
+
+/// Copy the mock plugin into `dir`, or `None` (and the caller skips)
+/// when the artifact is not where build.rs expected it.
+fn copy_mock(dir: &Path) -> Option<PathBuf> {
+    let src = Path::new(MOCK_PLUGIN_PATH);
+    let dst = dir.join(src.file_name()?);
+    if let Err(e) = std::fs::copy(src, &dst) {
+        eprintln!("warning: mock plugin not found at {MOCK_PLUGIN_PATH}: {e}; skipping test");
+        return None;
+    }
+    Some(dst)
+}
+
+/// Sign `artifact` as the plugin `mock` with a fresh key, store the
+/// detached signature as `<artifact>.sig` and register the public key
+/// for `publisher` in `store`. `None` (and the caller skips) when `gpg` is unavailable.
+fn sign(dir: &Path, artifact: &Path, store: &TrustStore, publisher: &str) -> Option<()> {
+    let home = dir.join("gnupg");
+    std::fs::create_dir_all(&home).unwrap();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&home, std::fs::Permissions::from_mode(0o700)).unwrap();
+    }
+    let gpg = |args: &[&std::ffi::OsStr]| {
+        Command::new("gpg")
+            .arg("--batch")
+            .arg("--homedir")
+            .arg(&home)
+            .args(args)
+            .output()
+            .ok()
+            .filter(|out| out.status.success())
+    };
+    if gpg(&[
+        "--passphrase".as_ref(),
+        "".as_ref(),
+        "--quick-gen-key".as_ref(),
+        "Confium Test Publisher <test@confium.example>".as_ref(),
+        "rsa2048".as_ref(),
+        "sign".as_ref(),
+        "never".as_ref(),
+    ])
+    .is_none()
+    {
+        eprintln!("warning: gpg unavailable; skipping test");
+        return None;
+    };
+    let key = gpg(&["--armor".as_ref(), "--export".as_ref()]).expect("gpg --export");
+    store.set_public_key(publisher, &key.stdout).unwrap();
+    let payload = dir.join("payload");
+    let digest = sha256_hex(&std::fs::read(artifact).unwrap());
+    std::fs::write(&payload, signed_payload("mock", &digest)).unwrap();
+    let mut sig = artifact.as_os_str().to_os_string();
+    sig.push(".sig");
+    gpg(&[
+        "--detach-sign".as_ref(),
+        "-o".as_ref(),
+        sig.as_ref(),
+        payload.as_os_str(),
+    ])
+    .expect("gpg --detach-sign");
+    Some(())
+}
+
+/// Write the install manifest `confium install` would leave next to
+/// `artifact`, naming `publisher` and the file's current digest.
+fn write_manifest(artifact: &Path, publisher: &str) {
+    let digest = sha256_hex(&std::fs::read(artifact).unwrap());
+    let manifest = format!(
+        "[plugin]\nname = \"mock\"\nversion = \"0.1.0\"\npublisher = \"{publisher}\"\n\n\
+         [artifact]\nurl = \"https://example/mock.so\"\nsha256 = \"{digest}\"\n"
+    );
+    std::fs::write(artifact.with_extension("manifest"), manifest).unwrap();
+}
+
+fn trust(home: &Path, publisher: &str) -> TrustStore {
+    let store = TrustStore::for_home(home.to_path_buf());
+    store
+        .add(TrustStoreEntry {
+            name: publisher.to_string(),
+            key_id: "0x1".to_string(),
+            fingerprint: "AAAA".to_string(),
+            key_url: format!("/publishers/{publisher}.asc"),
+        })
+        .unwrap();
+    store
+}
+
+fn load(cfm: &mut Confium, path: &Path) -> u32 {
+    let cname = CString::new("mock").unwrap();
+    let cpath = CString::new(path.to_str().unwrap()).unwrap();
+    let mut opts = Options::new();
+    unsafe {
+        cfm_plugin_load(
+            cfm,
+            cname.as_ptr(),
+            cpath.as_ptr(),
+            &mut opts,
+            ptr::null_mut(),
+        )
+    }
+}
+
+#[test]
+fn unsigned_plugin_is_refused_under_enforce() {
+    let dir = scratch_dir("unsigned");
+    let Some(plugin) = copy_mock(&dir) else {
+        return;
+    };
+    let sink = SharedSink::default();
+    let mut cfm = Confium::new_with_audit(AuditLogger::to_writer(sink.clone()));
+    cfm.set_plugin_policy(PluginPolicy::enforce(trust(&dir, "confium")));
+
+    let code = load(&mut cfm, &plugin);
+    assert_eq!(code, ErrorCode::PLUGIN_VERIFICATION_FAILED as u32);
+    assert!(Hash::new(&cfm, "xor", Some("mock"), None).is_err());
+
+    let lines = sink.lines();
+    assert_eq!(
+        lines.len(),
+        1,
+        "only the verify decision is logged: {lines:?}"
+    );
+    assert!(lines[0].contains(r#""event":"plugin_verify""#));
+    assert!(lines[0].contains(r#""decision":"rejected""#));
+}
+
+#[test]
+fn install_manifest_alone_is_refused_under_enforce() {
+    let dir = scratch_dir("manifest");
+    let Some(plugin) = copy_mock(&dir) else {
+        return;
+    };
+    write_manifest(&plugin, "confium");
+    let mut cfm = Confium::new_with_audit(AuditLogger::disabled());
+    cfm.set_plugin_policy(PluginPolicy::enforce(trust(&dir, "confium")));
+    assert_eq!(
+        load(&mut cfm, &plugin),
+        ErrorCode::PLUGIN_VERIFICATION_FAILED as u32
+    );
+}
+
+#[test]
+fn plugin_signed_by_trusted_publisher_loads_under_enforce() {
+    let dir = scratch_dir("signed");
+    let Some(plugin) = copy_mock(&dir) else {
+        return;
+    };
+    let store = trust(&dir, "confium");
+    let Some(()) = sign(&dir, &plugin, &store, "confium") else {
+        return;
+    };
+    let sink = SharedSink::default();
+    let mut cfm = Confium::new_with_audit(AuditLogger::to_writer(sink.clone()));
+    cfm.set_plugin_policy(PluginPolicy::enforce(store));
+
+    assert_eq!(load(&mut cfm, &plugin), 0);
+    let mut h = Hash::new(&cfm, "xor", Some("mock"), None).expect("verified plugin serves hashes");
+    h.update(b"\x01\x02").unwrap();
+
+    let lines = sink.lines();
+    assert!(lines[0].contains(r#""decision":"accepted""#), "{lines:?}");
+    assert!(lines[0].contains(r#""evidence":"signature""#));
+    assert!(lines[1].contains(r#""event":"plugin_load""#));
+    assert!(lines[1].contains(r#""publisher":"confium""#));
+}
+
+#[test]
+fn modified_plugin_is_refused_despite_signature() {
+    let dir = scratch_dir("tampered");
+    let Some(plugin) = copy_mock(&dir) else {
+        return;
+    };
+    let store = trust(&dir, "confium");
+    let Some(()) = sign(&dir, &plugin, &store, "confium") else {
+        return;
+    };
+    std::fs::OpenOptions::new()
+        .append(true)
+        .open(&plugin)
+        .unwrap()
+        .write_all(b"trailing bytes")
+        .unwrap();
+    let mut cfm = Confium::new_with_audit(AuditLogger::disabled());
+    cfm.set_plugin_policy(PluginPolicy::enforce(store));
+    assert_eq!(
+        load(&mut cfm, &plugin),
+        ErrorCode::PLUGIN_VERIFICATION_FAILED as u32
+    );
+}
+
+#[test]
+fn audit_only_loads_unsigned_plugin_and_records_it() {
+    let dir = scratch_dir("audit-only");
+    let Some(plugin) = copy_mock(&dir) else {
+        return;
+    };
+    let sink = SharedSink::default();
+    let mut cfm = Confium::new_with_audit(AuditLogger::to_writer(sink.clone()));
+    cfm.set_plugin_policy(PluginPolicy::audit_only(trust(&dir, "confium")));
+
+    assert_eq!(load(&mut cfm, &plugin), 0);
+    let lines = sink.lines();
+    assert!(lines[0].contains(r#""decision":"unverified""#), "{lines:?}");
+    assert!(lines[1].contains(r#""event":"plugin_load""#));
+    assert!(lines[1].contains(r#""publisher":"""#));
+}
+
+#[test]
+fn revoked_digest_is_refused_even_with_trusted_signature() {
+    let dir = scratch_dir("revoked");
+    let Some(plugin) = copy_mock(&dir) else {
+        return;
+    };
+    let store = trust(&dir, "confium");
+    let Some(()) = sign(&dir, &plugin, &store, "confium") else {
+        return;
+    };
+    store
+        .revoke(&sha256_hex(&std::fs::read(&plugin).unwrap()))
+        .unwrap();
+    let mut cfm = Confium::new_with_audit(AuditLogger::disabled());
+    cfm.set_plugin_policy(PluginPolicy::enforce(store));
+    assert_eq!(
+        load(&mut cfm, &plugin),
+        ErrorCode::PLUGIN_VERIFICATION_FAILED as u32
+    );
+}
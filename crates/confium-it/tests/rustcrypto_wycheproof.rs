@@ -0,0 +1,279 @@
+//! Wycheproof vectors driven through the RustCrypto provider plugin's
+//! FFI surface.
+//!
+//! Every case goes `confium` high-level API → `cfmp_*` symbols →
+//! plugin, so a length or buffer-handling bug in the generated glue
+//! shows up as a vector failure, not just a bug in the RustCrypto
+//! crates underneath.
+//!
+//! ## How to run
+//!
+//! ```sh
+//! mkdir -p /tmp/wycheproof && cd /tmp/wycheproof
+//! for f in aes_gcm aes_gcm_siv chacha20_poly1305 ed25519 \
+//!          ecdsa_secp256r1_sha256 ecdsa_secp384r1_sha384 \
+//!          rsa_pss_2048_sha256_mgf1_32 hkdf_sha256; do
+//!     curl -LO https://raw.githubusercontent.com/google/wycheproof/master/testvectors_v1/${f}_test.json
+//! done
+//! WYCHEPROOF_VECTORS_DIR=/tmp/wycheproof \
+//!     cargo test -p confium-it --test rustcrypto_wycheproof
+//! ```
+//!
+//! The vectors are not vendored. Every test loads the plugin; when
+//! the variable is unset it then skips the vectors. When it is set,
+//! a missing file fails the test. `acceptable` cases are run to
+//! exercise the code path, but either outcome passes. AEAD groups
+//! outside the plugin's parameter set (non-96-bit IVs, truncated
+//! tags, AES-192) are skipped.
+
+#![allow(improper_ctypes)]
+
+use std::ffi::CString;
+use std::os::raw::c_char;
+use std::path::PathBuf;
+use std::ptr;
+
+use confium::Confium;
+use confium::aead::Aead;
+use confium::error::Error;
+use confium::kdf::Kdf;
+use confium::options::{OptionValue, Options};
+use confium::plugin_policy::PluginPolicy;
+use confium::signature::Verifier;
+use serde_json::Value;
+
+const RUSTCRYPTO_PLUGIN_PATH: &str = env!("CONFIUM_RUSTCRYPTO_PLUGIN_PATH");
+const PROVIDER: Option<&str> = Some("rustcrypto");
+
+unsafe extern "C" {
+    fn cfm_plugin_load(
+        cfm: *mut Confium,
+        name: *const c_char,
+        path: *const c_char,
+        opts: *mut Options,
+        errptr: *mut *mut Error,
+    ) -> u32;
+}
+
+fn load() -> Option<Confium> {
+    let mut cfm = Confium::new_with_audit(confium::audit::AuditLogger::disabled());
+    // Freshly built test plugins carry no signature or install manifest.
+    cfm.set_plugin_policy(PluginPolicy::disabled());
+    let cname = CString::new("rustcrypto").unwrap();
+    let cpath = CString::new(RUSTCRYPTO_PLUGIN_PATH).unwrap();
+    let mut opts = Options::new();
+    let code = unsafe {
+        cfm_plugin_load(
+            &mut cfm,
+            cname.as_ptr(),
+            cpath.as_ptr(),
+            &mut opts,
+            ptr::null_mut(),
+        )
+    };
+    (code == 0).then_some(cfm)
+}
+
+/// Load the plugin and the named vector file; `None` when
+/// `WYCHEPROOF_VECTORS_DIR` is unset.
+fn setup(file: &str) -> Option<(Confium, Value)> {
+    let cfm = load().unwrap_or_else(|| panic!("plugin not loadable at {RUSTCRYPTO_PLUGIN_PATH}"));
+    let Some(dir) = std::env::var_os("WYCHEPROOF_VECTORS_DIR") else {
+        eprintln!("skipping {file}: WYCHEPROOF_VECTORS_DIR is not set");
+        return None;
+    };
+    let path = PathBuf::from(dir).join(file);
+    let text = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("reading {}: {e}", path.display()));
+    Some((
+        cfm,
+        serde_json::from_str(&text).expect("vector file is JSON"),
+    ))
+}
+
+fn unhex(s: &str) -> Vec<u8> {
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+fn field(v: &Value, name: &str) -> Vec<u8> {
+    unhex(v[name].as_str().unwrap_or(""))
+}
+
+/// Run `check` on every case in every group and assert that each
+/// `valid` case was accepted and each `invalid` case rejected.
+fn run(file: &Value, mut check: impl FnMut(&Value, &Value) -> Option<bool>) {
+    let mut failures = Vec::new();
+    let mut ran = 0;
+    for group in file["testGroups"].as_array().unwrap() {
+        for case in group["tests"].as_array().unwrap() {
+            let Some(accepted) = check(group, case) else {
+                continue;
+            };
+            ran += 1;
+            let expected = case["result"].as_str().unwrap();
+            if (expected == "valid" && !accepted) || (expected == "invalid" && accepted) {
+                failures.push(format!(
+                    "tcId {} ({}): expected {expected}, plugin {}",
+                    case["tcId"],
+                    case["comment"].as_str().unwrap_or(""),
+                    if accepted { "accepted" } else { "rejected" },
+                ));
+            }
+        }
+    }
+    assert!(ran > 0, "no applicable cases in file");
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+/// Seal and open one AEAD case. Accepted means the plugin both
+/// produced the vector's ciphertext/tag and opened it.
+fn aead_case(cfm: &Confium, alg: &str, siv: bool, case: &Value) -> bool {
+    let (key, iv, aad) = (field(case, "key"), field(case, "iv"), field(case, "aad"));
+    let (msg, ct, tag) = (field(case, "msg"), field(case, "ct"), field(case, "tag"));
+
+    let open = || -> Result<Vec<u8>, Error> {
+        let mut dec = Aead::new(cfm, alg, &key, PROVIDER, None)?;
+        dec.set_nonce(&iv)?;
+        dec.associated_data_update(&aad)?;
+        let mut pt = vec![0u8; ct.len()];
+        if siv {
+            // SIV authenticates before releasing plaintext, so the
+            // tag travels with the ciphertext.
+            dec.decrypt_update(&[ct.as_slice(), tag.as_slice()].concat(), &mut pt)?;
+        } else {
+            dec.decrypt_update(&ct, &mut pt)?;
+        }
+        dec.verify_tag(&tag)?;
+        Ok(pt)
+    };
+    let opened = open().is_ok_and(|pt| pt == msg);
+
+    let seal = || -> Result<(Vec<u8>, Vec<u8>), Error> {
+        let mut enc = Aead::new(cfm, alg, &key, PROVIDER, None)?;
+        enc.set_nonce(&iv)?;
+        enc.associated_data_update(&aad)?;
+        let mut out = vec![0u8; msg.len()];
+        enc.encrypt_update(&msg, &mut out)?;
+        let mut t = vec![0u8; 16];
+        enc.finalize(&mut t)?;
+        Ok((out, t))
+    };
+    let sealed = seal().is_ok_and(|(c, t)| c == ct && t == tag);
+    opened && sealed
+}
+
+fn aead_file(file: &str, alg_for_key_bits: impl Fn(u64) -> Option<&'static str>, siv: bool) {
+    let Some((cfm, vectors)) = setup(file) else {
+        return;
+    };
+    run(&vectors, |group, case| {
+        if group["ivSize"] != 96 || group["tagSize"] != 128 {
+            return None;
+        }
+        let alg = alg_for_key_bits(group["keySize"].as_u64()?)?;
+        Some(aead_case(&cfm, alg, siv, case))
+    });
+}
+
+#[test]
+fn aes_gcm() {
+    aead_file(
+        "aes_gcm_test.json",
+        |bits| match bits {
+            128 => Some("aes-128-gcm"),
+            256 => Some("aes-256-gcm"),
+            _ => None,
+        },
+        false,
+    );
+}
+
+#[test]
+fn aes_gcm_siv() {
+    aead_file(
+        "aes_gcm_siv_test.json",
+        |bits| (bits == 256).then_some("aes-256-gcm-siv"),
+        true,
+    );
+}
+
+#[test]
+fn chacha20_poly1305() {
+    aead_file(
+        "chacha20_poly1305_test.json",
+        |_| Some("chacha20-poly1305"),
+        false,
+    );
+}
+
+fn verify_file(file: &str, alg: &str, public_key: impl Fn(&Value) -> Option<Vec<u8>>) {
+    let Some((cfm, vectors)) = setup(file) else {
+        return;
+    };
+    run(&vectors, |group, case| {
+        let pk = public_key(group)?;
+        let (msg, sig) = (field(case, "msg"), field(case, "sig"));
+        // A key the plugin refuses at create time can never verify
+        // anything, which is the right answer for an invalid case.
+        let Ok(mut v) = Verifier::new(&cfm, alg, &pk, PROVIDER, None) else {
+            return Some(false);
+        };
+        Some(v.update(&msg).is_ok() && v.finalize(&sig).is_ok())
+    });
+}
+
+#[test]
+fn ed25519() {
+    verify_file("ed25519_test.json", "ed25519", |g| {
+        g["publicKey"]["pk"].as_str().map(unhex)
+    });
+}
+
+#[test]
+fn ecdsa_p256_sha256() {
+    verify_file("ecdsa_secp256r1_sha256_test.json", "ecdsa-p256", |g| {
+        g["publicKey"]["uncompressed"].as_str().map(unhex)
+    });
+}
+
+#[test]
+fn ecdsa_p384_sha384() {
+    verify_file("ecdsa_secp384r1_sha384_test.json", "ecdsa-p384", |g| {
+        g["publicKey"]["uncompressed"].as_str().map(unhex)
+    });
+}
+
+#[test]
+fn rsa_pss_2048_sha256_mgf1_32() {
+    verify_file("rsa_pss_2048_sha256_mgf1_32_test.json", "rsa-pss", |g| {
+        g["publicKeyDer"]
+            .as_str()
+            .or(g["keyDer"].as_str())
+            .map(unhex)
+    });
+}
+
+#[test]
+fn hkdf_sha256() {
+    let Some((cfm, vectors)) = setup("hkdf_sha256_test.json") else {
+        return;
+    };
+    run(&vectors, |_, case| {
+        let mut opts = Options::new();
+        opts.insert(
+            "info".into(),
+            OptionValue::String(case["info"].as_str()?.to_string()),
+        );
+        let derive = || -> Result<Vec<u8>, Error> {
+            let mut kdf = Kdf::new(&cfm, "hkdf", PROVIDER, Some(&opts))?;
+            kdf.set_salt(&field(case, "salt"))?;
+            let mut okm = vec![0u8; case["size"].as_u64().unwrap() as usize];
+            kdf.derive(&field(case, "ikm"), &mut okm)?;
+            Ok(okm)
+        };
+        Some(derive().is_ok_and(|okm| okm == field(case, "okm")))
+    });
+}
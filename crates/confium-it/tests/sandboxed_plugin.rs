@@ -0,0 +1,229 @@
+//! Integration tests for plugins loaded with `sandbox = "process"`.
+//!
+//! The mock and RustCrypto plugins are loaded into a
+//! `confium-plugin-host` subprocess and driven through the same
+//! high-level `confium` API as the in-process tests, so every byte and
+//! length crosses the pipe. A plugin that aborts must take only the
+//! host subprocess down: the caller sees `PLUGIN_SANDBOX_FAILED` (29).
+//!
+//! The plugin paths come from `confium-it/build.rs`, as does
+//! `CONFIUM_PLUGIN_HOST_PATH`. The host binary is built by
+//! `cargo build --workspace`; the tests skip when it is missing.
+
+#![allow(improper_ctypes)]
+
+use std::ffi::CString;
+use std::os::raw::c_char;
+use std::path::Path;
+use std::ptr;
+
+use confium::Confium;
+use confium::aead::Aead;
+use confium::error::{Error, ErrorCode};
+use confium::hash::Hash;
+use confium::kem::{KemDecapsulator, KemEncapsulator};
+use confium::key_handle::KeyHandle;
+use confium::keyfmt::Key;
+use confium::options::{OptionValue, Options};
+use confium::plugin_policy::PluginPolicy;
+use confium::sandbox::{SANDBOX_HOST_OPTION, SANDBOX_OPTION};
+use confium::signature::{Keypair, Signer, Verifier};
+
+const MOCK_PLUGIN_PATH: &str = env!("CONFIUM_MOCK_PLUGIN_PATH");
+const RUSTCRYPTO_PLUGIN_PATH: &str = env!("CONFIUM_RUSTCRYPTO_PLUGIN_PATH");
+const PLUGIN_HOST_PATH: &str = env!("CONFIUM_PLUGIN_HOST_PATH");
+
+unsafe extern "C" {
+    fn cfm_plugin_load(
+        cfm: *mut Confium,
+        name: *const c_char,
+        path: *const c_char,
+        opts: *mut Options,
+        errptr: *mut *mut Error,
+    ) -> u32;
+}
+
+/// Load the plugin at `path` as provider `name` in a sandbox host.
+/// `None` (and the caller skips) when the host or plugin artifact is
+/// not where build.rs expected it.
+fn load_sandboxed(name: &str, path: &str) -> Option<Confium> {
+    if !Path::new(PLUGIN_HOST_PATH).is_file() {
+        eprintln!(
+            "warning: CONFIUM_PLUGIN_HOST_PATH={PLUGIN_HOST_PATH} not built; \
+             run `cargo build --workspace`; skipping test"
+        );
+        return None;
+    }
+    let mut cfm = Confium::new_with_audit(confium::audit::AuditLogger::disabled());
+    // Freshly built test plugins carry no signature or install manifest.
+    cfm.set_plugin_policy(PluginPolicy::disabled());
+    let cname = CString::new(name).unwrap();
+    let cpath = CString::new(path).unwrap();
+    let mut opts = Options::new();
+    opts.insert(
+        SANDBOX_OPTION.to_string(),
+        OptionValue::String("process".to_string()),
+    );
+    opts.insert(
+        SANDBOX_HOST_OPTION.to_string(),
+        OptionValue::String(PLUGIN_HOST_PATH.to_string()),
+    );
+    let code = unsafe {
+        cfm_plugin_load(
+            &mut cfm,
+            cname.as_ptr(),
+            cpath.as_ptr(),
+            &mut opts,
+            ptr::null_mut(),
+        )
+    };
+    if code != 0 {
+        eprintln!("warning: sandboxed cfm_plugin_load returned {code} for {path}; skipping test");
+        return None;
+    }
+    Some(cfm)
+}
+
+fn is_sandbox_failure(err: &Error) -> bool {
+    matches!(
+        err,
+        Error::PluginInternalError { code, .. } if *code == ErrorCode::PLUGIN_SANDBOX_FAILED as u32
+    )
+}
+
+#[test]
+fn sandboxed_mock_hash_matches_in_process_result() {
+    let Some(cfm) = load_sandboxed("mock", MOCK_PLUGIN_PATH) else {
+        return;
+    };
+    let mut h = Hash::new(&cfm, "xor", Some("mock"), None).unwrap();
+    h.update(b"hel").unwrap();
+    let mut copy = h.try_clone().unwrap();
+    h.update(b"lo").unwrap();
+    assert_eq!(h.finalize().unwrap(), [b'h' ^ b'e' ^ b'l' ^ b'l' ^ b'o']);
+    assert_eq!(copy.finalize().unwrap(), [b'h' ^ b'e' ^ b'l']);
+}
+
+#[test]
+fn sandboxed_mock_signs_with_key_handle() {
+    let Some(cfm) = load_sandboxed("mock", MOCK_PLUGIN_PATH) else {
+        return;
+    };
+    let key_id = "slot-7";
+    let handle = KeyHandle::new("mock", key_id, ptr::null_mut()).unwrap();
+    let mut signer = Signer::with_handle(&cfm, "xor-sig", &handle, Some("mock"), None)
+        .expect("handle crosses into the sandbox");
+    signer.update(b"hello").unwrap();
+    let mut sig = [0u8; 1];
+    assert_eq!(signer.finalize(&mut sig).unwrap(), 1);
+
+    let mut verifier =
+        Verifier::new(&cfm, "xor-sig", key_id.as_bytes(), Some("mock"), None).unwrap();
+    verifier.update(b"hello").unwrap();
+    verifier.finalize(&sig).expect("handle signature verifies");
+}
+
+#[test]
+fn crashed_plugin_fails_calls_without_taking_the_caller_down() {
+    let Some(cfm) = load_sandboxed("mock", MOCK_PLUGIN_PATH) else {
+        return;
+    };
+    let mut survivor = Hash::new(&cfm, "xor", Some("mock"), None).unwrap();
+
+    // The mock plugin aborts when asked for this hash.
+    let err = Hash::new(&cfm, "abort", Some("mock"), None)
+        .err()
+        .expect("crashed create is an error");
+    assert!(is_sandbox_failure(&err), "unexpected error {err}");
+
+    // Everything on the dead provider now fails the same way, including
+    // objects created before the crash.
+    let err = survivor.update(b"x").expect_err("host is gone");
+    assert!(is_sandbox_failure(&err), "unexpected error {err}");
+    let err = Hash::new(&cfm, "xor", Some("mock"), None)
+        .err()
+        .expect("host is gone");
+    assert!(is_sandbox_failure(&err), "unexpected error {err}");
+}
+
+#[test]
+fn sandboxed_rustcrypto_aead_round_trips() {
+    let Some(cfm) = load_sandboxed("rustcrypto", RUSTCRYPTO_PLUGIN_PATH) else {
+        return;
+    };
+    let provider = Some("rustcrypto");
+    let key = [7u8; 32];
+    let nonce = [9u8; 12];
+    let msg = b"attack at dawn, bring snacks";
+
+    let mut enc = Aead::new(&cfm, "aes-256-gcm", &key, provider, None).unwrap();
+    enc.set_nonce(&nonce).unwrap();
+    enc.associated_data_update(b"hdr").unwrap();
+    let mut ct = vec![0u8; msg.len()];
+    assert_eq!(enc.encrypt_update(msg, &mut ct).unwrap(), msg.len());
+    let mut tag = [0u8; 16];
+    assert_eq!(enc.finalize(&mut tag).unwrap(), 16);
+
+    let mut dec = Aead::new(&cfm, "aes-256-gcm", &key, provider, None).unwrap();
+    dec.set_nonce(&nonce).unwrap();
+    dec.associated_data_update(b"hdr").unwrap();
+    let mut pt = vec![0u8; ct.len()];
+    dec.decrypt_update(&ct, &mut pt).unwrap();
+    dec.verify_tag(&tag).unwrap();
+    assert_eq!(pt, msg);
+}
+
+#[test]
+fn sandboxed_rustcrypto_signs_and_parses_keys() {
+    let Some(cfm) = load_sandboxed("rustcrypto", RUSTCRYPTO_PLUGIN_PATH) else {
+        return;
+    };
+    let provider = Some("rustcrypto");
+    let kp = Keypair::generate(&cfm, "ecdsa-p256", None, provider).unwrap();
+    let mut signer = Signer::new(&cfm, "ecdsa-p256", kp.secret_key.get(), provider, None).unwrap();
+    signer.update(b"the message").unwrap();
+    let mut sig = vec![0u8; 72];
+    let n = signer.finalize(&mut sig).unwrap();
+    sig.truncate(n);
+    let mut verifier = Verifier::new(&cfm, "ecdsa-p256", &kp.public_key, provider, None).unwrap();
+    verifier.update(b"the message").unwrap();
+    verifier.finalize(&sig).unwrap();
+
+    let key = Key::parse(
+        &cfm,
+        "raw",
+        Some("ecdsa-p256"),
+        kp.secret_key.get(),
+        provider,
+        None,
+    )
+    .unwrap();
+    assert_eq!(key.algorithm().unwrap(), "ecdsa-p256");
+    let public = key.public().unwrap();
+    assert_eq!(public.serialize("raw").unwrap().get(), &kp.public_key);
+}
+
+#[test]
+fn sandboxed_rustcrypto_kem_agrees() {
+    let Some(cfm) = load_sandboxed("rustcrypto", RUSTCRYPTO_PLUGIN_PATH) else {
+        return;
+    };
+    let alg = "x25519-mlkem768";
+    let (mut pk, mut sk) = (vec![0u8; 2048], vec![0u8; 4096]);
+    let (pk_len, sk_len) =
+        KemEncapsulator::keypair_generate(&cfm, alg, None, &mut pk, &mut sk).unwrap();
+    pk.truncate(pk_len);
+    sk.truncate(sk_len);
+    let ss_len = KemEncapsulator::shared_secret_size(&cfm, alg).unwrap() as usize;
+
+    let mut enc = KemEncapsulator::new(&cfm, alg, &pk, Some("rustcrypto"), None).unwrap();
+    let mut ct = vec![0u8; 2048];
+    let mut ss_a = vec![0u8; ss_len];
+    let (ct_len, _) = enc.encapsulate(&mut ct, &mut ss_a).unwrap();
+    ct.truncate(ct_len);
+
+    let mut dec = KemDecapsulator::new(&cfm, alg, &sk, Some("rustcrypto"), None).unwrap();
+    let mut ss_b = vec![0u8; ss_len];
+    dec.decapsulate(&ct, &mut ss_b).unwrap();
+    assert_eq!(ss_a, ss_b);
+}
@@ -0,0 +1,111 @@
+//! Integration tests for plugins loaded with `sandbox = "wasm"`.
+//!
+//! The fixture is a real guest component that the build script compiles
+//! from `fixtures/wasm-xor-plugin`. It is driven through the same
+//! high-level `confium` API as a native plugin, so every call crosses
+//! the component boundary. The tests skip when the build script could
+//! not build it (for example without the wasm32-unknown-unknown
+//! target).
+
+#![allow(improper_ctypes)]
+
+use std::ffi::CString;
+use std::os::raw::c_char;
+use std::ptr;
+
+use confium::Confium;
+use confium::error::{Error, ErrorCode};
+use confium::hash::Hash;
+use confium::options::{OptionValue, Options};
+use confium::plugin_policy::PluginPolicy;
+use confium::sandbox::SANDBOX_OPTION;
+use confium::sandbox::wasm::WASM_FUEL_OPTION;
+
+const WASM_PLUGIN_PATH: Option<&str> = option_env!("CONFIUM_WASM_XOR_PLUGIN_PATH");
+
+unsafe extern "C" {
+    fn cfm_plugin_load(
+        cfm: *mut Confium,
+        name: *const c_char,
+        path: *const c_char,
+        opts: *mut Options,
+        errptr: *mut *mut Error,
+    ) -> u32;
+}
+
+/// Load the fixture component as provider `wasm-xor`, with `fuel` per
+/// call if given. Returns `None` when the fixture was not built.
+fn load_wasm(fuel: Option<u32>) -> Option<Confium> {
+    let Some(path) = WASM_PLUGIN_PATH else {
+        eprintln!("skipping: the wasm fixture was not built");
+        return None;
+    };
+    let mut cfm = Confium::new_with_audit(confium::audit::AuditLogger::disabled());
+    // The fixture carries no signature.
+    cfm.set_plugin_policy(PluginPolicy::disabled());
+    let cname = CString::new("wasm-xor").unwrap();
+    let cpath = CString::new(path).unwrap();
+    let mut opts = Options::new();
+    opts.insert(
+        SANDBOX_OPTION.to_string(),
+        OptionValue::String("wasm".to_string()),
+    );
+    if let Some(fuel) = fuel {
+        opts.insert(WASM_FUEL_OPTION.to_string(), OptionValue::U32(fuel));
+    }
+    let code = unsafe {
+        cfm_plugin_load(
+            &mut cfm,
+            cname.as_ptr(),
+            cpath.as_ptr(),
+            &mut opts,
+            ptr::null_mut(),
+        )
+    };
+    assert_eq!(code, 0, "cfm_plugin_load failed for {path}");
+    Some(cfm)
+}
+
+fn is_sandbox_failure(err: &Error) -> bool {
+    matches!(
+        err,
+        Error::PluginInternalError { code, .. } if *code == ErrorCode::PLUGIN_SANDBOX_FAILED as u32
+    )
+}
+
+#[test]
+fn wasm_hash_round_trips_through_the_component() {
+    let Some(cfm) = load_wasm(None) else {
+        return;
+    };
+    let mut h = Hash::new(&cfm, "xor", Some("wasm-xor"), None).unwrap();
+    h.update(b"hel").unwrap();
+    let mut copy = h.try_clone().unwrap();
+    h.update(b"lo").unwrap();
+    assert_eq!(h.finalize().unwrap(), [b'h' ^ b'e' ^ b'l' ^ b'l' ^ b'o']);
+    assert_eq!(copy.finalize().unwrap(), [b'h' ^ b'e' ^ b'l']);
+}
+
+#[test]
+fn wasm_unknown_algorithm_is_the_guest_error() {
+    let Some(cfm) = load_wasm(None) else {
+        return;
+    };
+    assert!(Hash::new(&cfm, "sha-256", Some("wasm-xor"), None).is_err());
+}
+
+#[test]
+fn exhausted_fuel_fails_the_call_with_sandbox_failed() {
+    let Some(cfm) = load_wasm(Some(1_000_000)) else {
+        return;
+    };
+    let mut h = Hash::new(&cfm, "spin", Some("wasm-xor"), None).unwrap();
+    let err = h.update(b"x").expect_err("spinning guest runs out of fuel");
+    assert!(is_sandbox_failure(&err), "unexpected error {err}");
+
+    // The trapped instance fails every later call the same way.
+    let err = Hash::new(&cfm, "xor", Some("wasm-xor"), None)
+        .err()
+        .expect("instance is poisoned");
+    assert!(is_sandbox_failure(&err), "unexpected error {err}");
+}
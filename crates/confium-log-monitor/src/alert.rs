@@ -0,0 +1,148 @@
+//! Alert delivery.
+//!
+//! Three sinks, any combination of which can be enabled at once:
+//!
+//! - **Webhook**: `POST` the alert as JSON. Any non-2xx response is
+//!   a delivery failure and the alert is retried next cycle.
+//! - **Email drop**: write an RFC 5322 message into a directory for
+//!   a local MTA (or `sendmail -t` cron job) to pick up. Files are
+//!   written under a temporary name and renamed, so the pickup
+//!   process never sees a partial message.
+//! - **Stdout**: one JSON object per line, for log shippers.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::watch::Alert;
+
+pub enum AlertSink {
+    Webhook {
+        url: String,
+        http: reqwest::Client,
+    },
+    EmailDrop {
+        dir: PathBuf,
+        from: String,
+        to: String,
+    },
+    Stdout,
+}
+
+impl AlertSink {
+    pub fn webhook(url: String) -> Self {
+        AlertSink::Webhook {
+            url,
+            http: reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(10))
+                .build()
+                .expect("reqwest client"),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            AlertSink::Webhook { .. } => "webhook",
+            AlertSink::EmailDrop { .. } => "email-drop",
+            AlertSink::Stdout => "stdout",
+        }
+    }
+
+    pub async fn deliver(&self, alert: &Alert) -> Result<()> {
+        match self {
+            AlertSink::Webhook { url, http } => {
+                http.post(url)
+                    .json(alert)
+                    .send()
+                    .await?
+                    .error_for_status()?;
+                Ok(())
+            }
+            AlertSink::EmailDrop { dir, from, to } => write_email(dir, from, to, alert),
+            AlertSink::Stdout => {
+                let line = serde_json::to_string(alert)?;
+                let mut out = std::io::stdout().lock();
+                writeln!(out, "{line}")?;
+                out.flush()?;
+                Ok(())
+            }
+        }
+    }
+}
+
+fn write_email(dir: &Path, from: &str, to: &str, alert: &Alert) -> Result<()> {
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("creating email drop dir {}", dir.display()))?;
+    // Rule ids are operator-chosen; keep only bytes that are safe in
+    // both a filename and a header line.
+    let safe_rule: String = alert
+        .rule_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let name = format!("confium-alert-{safe_rule}-{}.eml", alert.sequence);
+    let body = serde_json::to_string_pretty(alert)?;
+    let message = format!(
+        "From: {from}\r\n\
+         To: {to}\r\n\
+         Subject: [confium-log-monitor] rule {} matched entry {}\r\n\
+         MIME-Version: 1.0\r\n\
+         Content-Type: application/json; charset=utf-8\r\n\
+         \r\n\
+         {body}\r\n",
+        safe_rule, alert.sequence
+    );
+    let tmp = dir.join(format!(".{name}.tmp"));
+    let mut file =
+        std::fs::File::create(&tmp).with_context(|| format!("creating {}", tmp.display()))?;
+    file.write_all(message.as_bytes())?;
+    file.sync_all()?;
+    std::fs::rename(&tmp, dir.join(&name)).with_context(|| format!("publishing {name}"))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::watch::Matcher;
+
+    #[tokio::test]
+    async fn email_drop_writes_complete_message() {
+        let dir = tempfile::tempdir().unwrap();
+        let sink = AlertSink::EmailDrop {
+            dir: dir.path().to_path_buf(),
+            from: "monitor@example.com".into(),
+            to: "security@example.com".into(),
+        };
+        let alert = Alert {
+            rule_id: "release/bot".into(),
+            matcher: Matcher::Identity("release@example.com".into()),
+            sequence: 42,
+            artifact_type: "certificate_issuance".into(),
+            artifact_hash: "ab".repeat(32),
+            timestamp: "2026-01-01T00:00:00Z".into(),
+            issuer: Some("CN=CA".into()),
+            subject: Some("CN=release@example.com".into()),
+        };
+        sink.deliver(&alert).await.unwrap();
+
+        let names: Vec<_> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name().into_string().unwrap())
+            .collect();
+        assert_eq!(names, ["confium-alert-release_bot-42.eml"]);
+        let text = std::fs::read_to_string(dir.path().join(&names[0])).unwrap();
+        assert!(text.starts_with("From: monitor@example.com\r\nTo: security@example.com\r\n"));
+        let (_, body) = text.split_once("\r\n\r\n").unwrap();
+        let json: serde_json::Value = serde_json::from_str(body.trim()).unwrap();
+        assert_eq!(json["sequence"], 42);
+        assert_eq!(json["matcher"]["identity"], "release@example.com");
+    }
+}
@@ -1,6 +1,7 @@
 //! HTTP client for the transparency log server.
 
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, anyhow, ensure};
+use confium_transparency::entry::MerkleEntry;
 use serde::Deserialize;
 
 #[derive(Debug, Clone, Deserialize)]
@@ -18,6 +19,61 @@ pub struct ConsistencyProof {
     pub proof: Vec<String>,
 }
 
+/// One log entry as returned by `/v1/entries`. Certificate fields
+/// are only populated for entries appended via `/v1/certificates`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct LogEntry {
+    pub sequence: u64,
+    pub artifact_type: String,
+    pub artifact_hash: String,
+    pub timestamp: String,
+    #[serde(default)]
+    pub issuer_distinguished_name: Option<String>,
+    #[serde(default)]
+    pub subject_distinguished_name: Option<String>,
+    /// Subject alternative names as `type:value` (`email:`, `uri:`,
+    /// `dns:`).
+    #[serde(default)]
+    pub subject_alternative_names: Vec<String>,
+    #[serde(default)]
+    pub fingerprint_sha256: Option<String>,
+}
+
+impl LogEntry {
+    /// The Merkle leaf this entry claims to be. Its hash covers the
+    /// sequence, timestamp and artifact hash, so a server that alters
+    /// any of them no longer reproduces the tree head's root.
+    pub fn merkle_entry(&self) -> Result<MerkleEntry> {
+        let hash = hex::decode(&self.artifact_hash)
+            .with_context(|| format!("entry {}: artifact_hash is not hex", self.sequence))?;
+        ensure!(
+            hash.len() == 32,
+            "entry {}: artifact_hash must be 32 bytes, got {}",
+            self.sequence,
+            hash.len()
+        );
+        let mut artifact_hash = [0u8; 32];
+        artifact_hash.copy_from_slice(&hash);
+        Ok(MerkleEntry {
+            sequence: self.sequence,
+            timestamp: chrono::DateTime::parse_from_rfc3339(&self.timestamp)
+                .with_context(|| format!("entry {}: bad timestamp", self.sequence))?
+                .with_timezone(&chrono::Utc),
+            artifact_type: self
+                .artifact_type
+                .parse()
+                .map_err(|e| anyhow!("entry {}: {e}", self.sequence))?,
+            artifact_hash,
+            metadata: serde_json::Value::Null,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct EntryPage {
+    entries: Vec<LogEntry>,
+}
+
 pub struct LogClient {
     base_url: String,
     http: reqwest::Client,
@@ -61,4 +117,21 @@ impl LogClient {
             .context("decoding /v1/consistency response")?;
         Ok(proof)
     }
+
+    /// Fetch up to `limit` entries starting at the 0-based sequence
+    /// `start`. The server may return fewer than `limit`.
+    pub async fn fetch_entries(&self, start: u64, limit: usize) -> Result<Vec<LogEntry>> {
+        let url = format!("{}/v1/entries", self.base_url);
+        let page = self
+            .http
+            .get(&url)
+            .query(&[("start", start.to_string()), ("limit", limit.to_string())])
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<EntryPage>()
+            .await
+            .context("decoding /v1/entries response")?;
+        Ok(page.entries)
+    }
 }
@@ -16,18 +16,27 @@
 //!   tree sizes M and N doesn't actually prove the trees are
 //!   related.
 //!
+//! With `--watchlist`, the monitor also downloads every new entry
+//! and matches it against identity, issuer, hash-prefix and
+//! artifact-type rules, delivering alerts by webhook, email drop or
+//! stdout JSON (see the `watch` and `alert` modules).
+//!
 //! ## Quickstart
 //!
 //! ```sh
 //! $ cargo run -p confium-log-monitor -- \
 //!     --log-url http://log.confium.org \
 //!     --state /var/lib/confium-monitor \
-//!     --poll-interval 30
+//!     --poll-interval 30 \
+//!     --watchlist watchlist.json \
+//!     --alert-webhook https://hooks.example.com/confium
 //! ```
 
+mod alert;
 mod client;
 mod store;
 mod verify;
+mod watch;
 
 use std::path::PathBuf;
 use std::time::Duration;
@@ -53,6 +62,57 @@ pub struct Args {
     /// Run once and exit (don't loop). Useful for cron-based monitoring.
     #[arg(long)]
     pub once: bool,
+
+    /// JSON watchlist of rules to match new entries against.
+    #[arg(long)]
+    pub watchlist: Option<PathBuf>,
+
+    /// POST each alert as JSON to this URL.
+    #[arg(long)]
+    pub alert_webhook: Option<String>,
+
+    /// Drop each alert as an RFC 5322 message into this directory.
+    #[arg(long)]
+    pub alert_email_dir: Option<PathBuf>,
+
+    /// `From:` address for email-drop alerts.
+    #[arg(long, default_value = "confium-log-monitor@localhost")]
+    pub alert_email_from: String,
+
+    /// `To:` address for email-drop alerts.
+    #[arg(long, default_value = "root@localhost")]
+    pub alert_email_to: String,
+
+    /// Print each alert as a JSON line on stdout. This is the default
+    /// when a watchlist is given without any other sink.
+    #[arg(long)]
+    pub alert_stdout: bool,
+}
+
+impl Args {
+    fn alert_sinks(&self) -> Vec<alert::AlertSink> {
+        let mut sinks = Vec::new();
+        if let Some(url) = &self.alert_webhook {
+            sinks.push(alert::AlertSink::webhook(url.clone()));
+        }
+        if let Some(dir) = &self.alert_email_dir {
+            sinks.push(alert::AlertSink::EmailDrop {
+                dir: dir.clone(),
+                from: self.alert_email_from.clone(),
+                to: self.alert_email_to.clone(),
+            });
+        }
+        if self.alert_stdout || sinks.is_empty() {
+            sinks.push(alert::AlertSink::Stdout);
+        }
+        sinks
+    }
+}
+
+/// Entry watching, enabled by `--watchlist`.
+struct Watcher {
+    watchlist: watch::Watchlist,
+    sinks: Vec<alert::AlertSink>,
 }
 
 #[tokio::main]
@@ -67,9 +127,16 @@ async fn main() -> Result<()> {
     let args = Args::parse();
     let client = client::LogClient::new(args.log_url.clone());
     let store = store::StateStore::open(&args.state)?;
+    let watcher = match &args.watchlist {
+        Some(path) => Some(Watcher {
+            watchlist: watch::Watchlist::load(path)?,
+            sinks: args.alert_sinks(),
+        }),
+        None => None,
+    };
 
     loop {
-        if let Err(e) = run_cycle(&client, &store).await {
+        if let Err(e) = run_cycle(&client, &store, watcher.as_ref()).await {
             tracing::error!(?e, "monitor cycle failed");
         }
         if args.once {
@@ -79,7 +146,11 @@ async fn main() -> Result<()> {
     }
 }
 
-async fn run_cycle(client: &client::LogClient, store: &store::StateStore) -> Result<()> {
+async fn run_cycle(
+    client: &client::LogClient,
+    store: &store::StateStore,
+    watcher: Option<&Watcher>,
+) -> Result<()> {
     let head = client.fetch_head().await?;
     tracing::info!(tree_size = head.tree_size, root = %head.root, "fetched head");
 
@@ -105,6 +176,16 @@ async fn run_cycle(client: &client::LogClient, store: &store::StateStore) -> Res
             cached = last_size,
             "TREE SIZE WENT BACKWARDS — possible fork"
         );
+        // Don't scan entries from a view we've just caught lying.
+        return Ok(());
+    }
+
+    // Only scan up to a head whose consistency we've verified.
+    if let Some(w) = watcher {
+        let alerts = watch::scan(client, store, &w.watchlist, &w.sinks, &head).await?;
+        if alerts > 0 {
+            tracing::info!(alerts, "watchlist alerts delivered");
+        }
     }
 
     Ok(())
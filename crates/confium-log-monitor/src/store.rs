@@ -1,13 +1,16 @@
 //! Persistent state for the monitor.
 //!
 //! Stores the last-seen tree head so we can detect tree-size
-//! regression and verify consistency between cycles. Backed by
-//! sled for simplicity; production deployments might use Postgres
-//! or LevelDB.
+//! regression and verify consistency between cycles, plus the
+//! watchlist's Merkle frontier and the set of alerts already
+//! delivered.
+//! Backed by sled for simplicity; production deployments might use
+//! Postgres or LevelDB.
 
 use std::path::Path;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use confium_transparency::merkle::Frontier;
 use sled::Db;
 
 use crate::client::TreeHead;
@@ -54,4 +57,38 @@ impl StateStore {
         self.db.flush()?;
         Ok(())
     }
+
+    /// Frontier of every entry the watchlist scanner has verified
+    /// and examined; its length is the next 0-based sequence to scan.
+    /// State written before the frontier existed (a bare
+    /// `entry_cursor`) is ignored, so such a monitor rescans from the
+    /// start once and the delivered-alert set suppresses repeats.
+    pub fn entry_frontier(&self) -> Result<Frontier> {
+        self.db
+            .get("entry_frontier")?
+            .map(|v| serde_json::from_slice(&v).context("decoding stored entry frontier"))
+            .transpose()
+            .map(Option::unwrap_or_default)
+    }
+
+    /// Persist the frontier. Flushed before returning so a crash never
+    /// rewinds past entries whose alerts were already delivered.
+    pub fn put_entry_frontier(&self, frontier: &Frontier) -> Result<()> {
+        self.db
+            .insert("entry_frontier", serde_json::to_vec(frontier)?)?;
+        self.db.flush()?;
+        Ok(())
+    }
+
+    /// Whether an alert with this deduplication key was delivered.
+    pub fn alert_delivered(&self, key: &str) -> Result<bool> {
+        Ok(self.db.contains_key(format!("alert/{key}"))?)
+    }
+
+    /// Record an alert as delivered.
+    pub fn mark_alert_delivered(&self, key: &str) -> Result<()> {
+        self.db.insert(format!("alert/{key}"), &[][..])?;
+        self.db.flush()?;
+        Ok(())
+    }
 }
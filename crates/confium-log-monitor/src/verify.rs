@@ -1,14 +1,39 @@
 //! Verification routines.
 //!
 //! Implements RFC 6962 §2.1.1 (inclusion) and §2.1.2 (consistency)
-//! proof verification. These are the same routines a real-world
-//! monitor would run on every proof it sees.
+//! proof verification, plus the check that downloaded entries are the
+//! ones a tree head commits to. These are the same routines a
+//! real-world monitor would run on every proof it sees.
 
 use anyhow::{Result, bail, ensure};
+use confium_transparency::merkle::Frontier;
 use sha2::{Digest, Sha256};
 
 use crate::client::{ConsistencyProof, TreeHead};
 
+/// Check that the entries absorbed into `frontier` are exactly the
+/// tree `head` commits to: same size, and the recomputed root equals
+/// the head's root.
+pub fn verify_entries(frontier: &Frontier, head: &TreeHead) -> Result<()> {
+    ensure!(
+        frontier.len() == head.tree_size,
+        "verified {} entries against a head of size {}",
+        frontier.len(),
+        head.tree_size
+    );
+    use subtle::ConstantTimeEq;
+    let head_root = hex::decode(&head.root).unwrap_or_default();
+    let root_ok: bool = frontier.root().ct_eq(&head_root).into();
+    ensure!(
+        root_ok,
+        "entries 0..{} hash to {}, not the head root {}",
+        head.tree_size,
+        hex::encode(frontier.root()),
+        head.root
+    );
+    Ok(())
+}
+
 /// RFC 6962 §2.1.2 consistency proof verification. Given the old
 /// root, the old size, the new (claimed) head, and the consistency
 /// proof from the server, verify that the new head is a valid
@@ -129,6 +154,61 @@ fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
 mod tests {
     use super::*;
 
+    use crate::client::LogEntry;
+    use confium_transparency::entry::{ArtifactType, MerkleEntry};
+    use confium_transparency::merkle::MerkleTree;
+
+    /// `n` entries appended the way the log server does, plus the
+    /// `/v1/entries` view of them and the resulting head.
+    fn served_log(n: u64) -> (Vec<LogEntry>, TreeHead) {
+        let mut tree = MerkleTree::new();
+        let mut served = Vec::new();
+        for i in 0..n {
+            let entry = MerkleEntry::new(i, ArtifactType::CertificateIssuance, [i as u8; 32]);
+            served.push(LogEntry {
+                sequence: i,
+                artifact_type: entry.artifact_type.to_string(),
+                artifact_hash: hex::encode(entry.artifact_hash),
+                timestamp: entry.timestamp.to_rfc3339(),
+                issuer_distinguished_name: None,
+                subject_distinguished_name: None,
+                subject_alternative_names: Vec::new(),
+                fingerprint_sha256: None,
+            });
+            tree.append(entry);
+        }
+        let head = TreeHead {
+            tree_size: n,
+            root: hex::encode(tree.root()),
+            timestamp: String::new(),
+        };
+        (served, head)
+    }
+
+    fn absorb(entries: &[LogEntry]) -> Frontier {
+        let mut frontier = Frontier::new();
+        for entry in entries {
+            frontier.push(&entry.merkle_entry().unwrap());
+        }
+        frontier
+    }
+
+    #[test]
+    fn served_entries_reproduce_the_head_root() {
+        let (entries, head) = served_log(11);
+        verify_entries(&absorb(&entries), &head).unwrap();
+    }
+
+    #[test]
+    fn altered_or_missing_entries_are_detected() {
+        let (mut entries, head) = served_log(11);
+        // A truncated download does not cover the head.
+        assert!(verify_entries(&absorb(&entries[..10]), &head).is_err());
+        // Swapping in another artifact changes the leaf.
+        entries[4].artifact_hash = hex::encode([0xee; 32]);
+        assert!(verify_entries(&absorb(&entries), &head).is_err());
+    }
+
     #[test]
     fn inclusion_proof_round_trip() {
         let leaf = [0xaa; 32];
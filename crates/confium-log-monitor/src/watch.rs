@@ -0,0 +1,410 @@
+//! Watchlists.
+//!
+//! A watchlist is a set of rules matched against every entry the
+//! log publishes. The scanner tails `/v1/entries` from a persisted
+//! Merkle frontier, so each entry is examined exactly once across
+//! restarts. Before anything is delivered, the fetched entries must
+//! hash to the root of the consistency-checked tree head; only then
+//! does every match become an [`Alert`] handed to the configured
+//! sinks. The point is to learn within one poll interval that
+//! someone obtained a certificate for one of *our* identities or
+//! logged an artifact we care about.
+//!
+//! Watchlist files are JSON:
+//!
+//! ```json
+//! {
+//!   "rules": [
+//!     { "id": "release-bot", "match": { "identity": "release@example.com" } },
+//!     { "id": "any-example", "match": { "identity": "*@example.com" } },
+//!     { "id": "our-ca", "match": { "issuer": "CN=Example Issuing CA,O=Example" } },
+//!     { "id": "build-42", "match": { "hash_prefix": "c0ffee" } },
+//!     { "id": "revocations", "match": { "artifact_type": "certificate_revocation" } }
+//!   ]
+//! }
+//! ```
+
+use std::path::Path;
+
+use anyhow::{Context, Result, ensure};
+use confium_transparency::entry::ArtifactType;
+use serde::{Deserialize, Serialize};
+
+use crate::alert::AlertSink;
+use crate::client::{LogClient, LogEntry, TreeHead};
+use crate::store::StateStore;
+use crate::verify;
+
+/// Entries requested per `/v1/entries` call.
+const PAGE_SIZE: usize = 256;
+
+/// DN attribute types whose values identify the certificate holder.
+/// Keyless certificates carry the OIDC subject or email in one of
+/// these; `1.2.840.113549.1.9.1` is `emailAddress` when the DN
+/// printer falls back to the dotted OID.
+const IDENTITY_ATTRIBUTES: &[&str] = &[
+    "cn",
+    "uid",
+    "e",
+    "email",
+    "emailaddress",
+    "1.2.840.113549.1.9.1",
+];
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Watchlist {
+    pub rules: Vec<WatchRule>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct WatchRule {
+    /// Stable rule identifier. Part of the alert deduplication key,
+    /// so renaming a rule re-alerts on entries it already matched.
+    pub id: String,
+    #[serde(rename = "match")]
+    pub matcher: Matcher,
+}
+
+/// What a rule matches on.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Matcher {
+    /// Certificate subject identity: an email, OIDC subject or URI
+    /// carried in a subject alternative name or an identity attribute
+    /// of the subject DN. Case-insensitive; a leading `*` matches any
+    /// prefix (`*@example.com`).
+    Identity(String),
+    /// Exact issuer DN, compared attribute by attribute.
+    Issuer(String),
+    /// Hex prefix of the artifact hash or certificate fingerprint.
+    HashPrefix(String),
+    /// Entry artifact type.
+    ArtifactType(ArtifactType),
+}
+
+/// One rule match, as delivered to the alert sinks.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct Alert {
+    pub rule_id: String,
+    pub matcher: Matcher,
+    pub sequence: u64,
+    pub artifact_type: String,
+    pub artifact_hash: String,
+    pub timestamp: String,
+    pub issuer: Option<String>,
+    pub subject: Option<String>,
+}
+
+impl Alert {
+    /// Deduplication key: one alert per (rule, entry).
+    pub fn dedup_key(&self) -> String {
+        format!("{}/{}", self.rule_id, self.sequence)
+    }
+}
+
+impl Watchlist {
+    /// Load and validate a JSON watchlist file.
+    pub fn load(path: &Path) -> Result<Self> {
+        let bytes =
+            std::fs::read(path).with_context(|| format!("reading watchlist {}", path.display()))?;
+        let list: Watchlist = serde_json::from_slice(&bytes)
+            .with_context(|| format!("parsing watchlist {}", path.display()))?;
+        list.validate()?;
+        Ok(list)
+    }
+
+    fn validate(&self) -> Result<()> {
+        let mut seen = std::collections::HashSet::new();
+        for rule in &self.rules {
+            ensure!(!rule.id.is_empty(), "watch rule id must not be empty");
+            ensure!(seen.insert(&rule.id), "duplicate watch rule id {}", rule.id);
+            if let Matcher::HashPrefix(prefix) = &rule.matcher {
+                ensure!(
+                    !prefix.is_empty() && prefix.chars().all(|c| c.is_ascii_hexdigit()),
+                    "rule {}: hash_prefix must be non-empty hex",
+                    rule.id
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Every alert this entry raises, in rule order.
+    pub fn matches(&self, entry: &LogEntry) -> Vec<Alert> {
+        self.rules
+            .iter()
+            .filter(|rule| rule.matcher.matches(entry))
+            .map(|rule| Alert {
+                rule_id: rule.id.clone(),
+                matcher: rule.matcher.clone(),
+                sequence: entry.sequence,
+                artifact_type: entry.artifact_type.clone(),
+                artifact_hash: entry.artifact_hash.clone(),
+                timestamp: entry.timestamp.clone(),
+                issuer: entry.issuer_distinguished_name.clone(),
+                subject: entry.subject_distinguished_name.clone(),
+            })
+            .collect()
+    }
+}
+
+impl Matcher {
+    pub fn matches(&self, entry: &LogEntry) -> bool {
+        match self {
+            Matcher::Identity(wanted) => {
+                entry
+                    .subject_alternative_names
+                    .iter()
+                    .any(|san| identity_matches(wanted, san_value(san)))
+                    || entry
+                        .subject_distinguished_name
+                        .as_deref()
+                        .is_some_and(|dn| {
+                            dn_attributes(dn)
+                                .iter()
+                                .filter(|(ty, _)| IDENTITY_ATTRIBUTES.contains(&ty.as_str()))
+                                .any(|(_, value)| identity_matches(wanted, value))
+                        })
+            }
+            Matcher::Issuer(wanted) => entry
+                .issuer_distinguished_name
+                .as_deref()
+                .is_some_and(|dn| dn_attributes(dn) == dn_attributes(wanted)),
+            Matcher::HashPrefix(prefix) => {
+                let prefix = prefix.to_ascii_lowercase();
+                entry
+                    .artifact_hash
+                    .to_ascii_lowercase()
+                    .starts_with(&prefix)
+                    || entry
+                        .fingerprint_sha256
+                        .as_deref()
+                        .is_some_and(|fp| fp.to_ascii_lowercase().starts_with(&prefix))
+            }
+            Matcher::ArtifactType(wanted) => entry
+                .artifact_type
+                .parse::<ArtifactType>()
+                .is_ok_and(|t| t == *wanted),
+        }
+    }
+}
+
+/// The value of a `type:value` subject alternative name as the log
+/// reports it (`email:ci@example.com` → `ci@example.com`).
+fn san_value(san: &str) -> &str {
+    san.split_once(':').map_or(san, |(_, value)| value)
+}
+
+fn identity_matches(wanted: &str, value: &str) -> bool {
+    let value = value.to_lowercase();
+    match wanted.strip_prefix('*') {
+        Some(suffix) => value.ends_with(&suffix.to_lowercase()),
+        None => value == wanted.to_lowercase(),
+    }
+}
+
+/// Split an RFC 4514 DN into `(lowercased type, value)` pairs,
+/// honouring backslash escapes and treating multi-valued RDNs
+/// (`+`) as separate attributes.
+fn dn_attributes(dn: &str) -> Vec<(String, String)> {
+    fn flush(raw: &mut String, out: &mut Vec<(String, String)>) {
+        if let Some((ty, value)) = raw.split_once('=') {
+            out.push((ty.trim().to_ascii_lowercase(), value.trim().to_string()));
+        }
+        raw.clear();
+    }
+
+    let mut out = Vec::new();
+    let mut current = String::new();
+    let mut escaped = false;
+    for c in dn.chars() {
+        if escaped {
+            current.push(c);
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == ',' || c == '+' {
+            flush(&mut current, &mut out);
+        } else {
+            current.push(c);
+        }
+    }
+    flush(&mut current, &mut out);
+    out
+}
+
+/// Examine every entry between the persisted frontier and `head`,
+/// delivering each new alert to every sink.
+///
+/// Nothing is delivered until the entries up to `head.tree_size`
+/// hash to `head.root`: a log that serves a different entry list
+/// than the tree it committed to raises an error instead of alerts
+/// (or silence). The frontier only advances once every alert is
+/// delivered; an alert is marked delivered only once every sink
+/// accepted it. A failed delivery therefore retries next cycle, and
+/// the dedup set stops a retry from re-sending what already went
+/// out.
+pub async fn scan(
+    client: &LogClient,
+    store: &StateStore,
+    watchlist: &Watchlist,
+    sinks: &[AlertSink],
+    head: &TreeHead,
+) -> Result<usize> {
+    let mut frontier = store.entry_frontier()?;
+    ensure!(
+        frontier.len() <= head.tree_size,
+        "scanned {} entries but the head has only {}",
+        frontier.len(),
+        head.tree_size
+    );
+    let mut pending = Vec::new();
+    while frontier.len() < head.tree_size {
+        let page = client.fetch_entries(frontier.len(), PAGE_SIZE).await?;
+        ensure!(
+            !page.is_empty(),
+            "log returned no entries at {} below tree size {}",
+            frontier.len(),
+            head.tree_size
+        );
+        // The log may have grown past `head`; those entries wait for
+        // the next verified head.
+        for entry in page.iter().take((head.tree_size - frontier.len()) as usize) {
+            ensure!(
+                entry.sequence == frontier.len(),
+                "log returned entry {} where {} was expected",
+                entry.sequence,
+                frontier.len()
+            );
+            frontier.push(&entry.merkle_entry()?);
+            pending.extend(watchlist.matches(entry));
+        }
+    }
+    verify::verify_entries(&frontier, head)?;
+
+    let mut delivered = 0;
+    for alert in pending {
+        let key = alert.dedup_key();
+        if store.alert_delivered(&key)? {
+            continue;
+        }
+        for sink in sinks {
+            sink.deliver(&alert)
+                .await
+                .with_context(|| format!("delivering alert {key} via {}", sink.name()))?;
+        }
+        store.mark_alert_delivered(&key)?;
+        delivered += 1;
+        tracing::warn!(rule = %alert.rule_id, sequence = alert.sequence, "watchlist match");
+    }
+    store.put_entry_frontier(&frontier)?;
+    Ok(delivered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cert_entry(subject: &str, issuer: &str) -> LogEntry {
+        LogEntry {
+            sequence: 7,
+            artifact_type: "certificate_issuance".into(),
+            artifact_hash: "c0ffee".to_string() + &"00".repeat(29),
+            timestamp: "2026-01-01T00:00:00Z".into(),
+            issuer_distinguished_name: Some(issuer.into()),
+            subject_distinguished_name: Some(subject.into()),
+            subject_alternative_names: Vec::new(),
+            fingerprint_sha256: None,
+        }
+    }
+
+    fn rule(id: &str, matcher: Matcher) -> WatchRule {
+        WatchRule {
+            id: id.into(),
+            matcher,
+        }
+    }
+
+    #[test]
+    fn identity_matches_exact_and_suffix_case_insensitively() {
+        let entry = cert_entry("CN=Release@Example.com,O=Example", "CN=Fulcio");
+        assert!(Matcher::Identity("release@example.com".into()).matches(&entry));
+        assert!(Matcher::Identity("*@EXAMPLE.COM".into()).matches(&entry));
+        assert!(!Matcher::Identity("other@example.com".into()).matches(&entry));
+        // The organisation attribute is not an identity attribute.
+        assert!(!Matcher::Identity("Example".into()).matches(&entry));
+    }
+
+    #[test]
+    fn identity_matches_subject_alternative_names() {
+        // Keyless certificates name the signer only in the SAN.
+        let mut entry = cert_entry("O=Example", "CN=Fulcio");
+        entry.subject_alternative_names = vec![
+            "email:CI@example.com".into(),
+            "uri:https://github.com/example/app/.github/workflows/release.yml@refs/heads/main"
+                .into(),
+        ];
+        assert!(Matcher::Identity("ci@example.com".into()).matches(&entry));
+        assert!(Matcher::Identity("*@example.com".into()).matches(&entry));
+        assert!(Matcher::Identity("*/release.yml@refs/heads/main".into()).matches(&entry));
+        assert!(!Matcher::Identity("email:ci@example.com".into()).matches(&entry));
+        assert!(!Matcher::Identity("release@example.com".into()).matches(&entry));
+    }
+
+    #[test]
+    fn issuer_compares_attributes_not_spacing() {
+        let entry = cert_entry("CN=a", "CN=Example CA, O=Example\\, Inc.");
+        assert!(Matcher::Issuer("cn=Example CA,o=Example\\, Inc.".into()).matches(&entry));
+        assert!(!Matcher::Issuer("CN=Example CA".into()).matches(&entry));
+    }
+
+    #[test]
+    fn hash_prefix_and_artifact_type() {
+        let entry = cert_entry("CN=a", "CN=b");
+        assert!(Matcher::HashPrefix("C0FFEE".into()).matches(&entry));
+        assert!(!Matcher::HashPrefix("dead".into()).matches(&entry));
+        assert!(Matcher::ArtifactType(ArtifactType::CertificateIssuance).matches(&entry));
+        assert!(!Matcher::ArtifactType(ArtifactType::CertificateRevocation).matches(&entry));
+    }
+
+    #[test]
+    fn watchlist_parses_and_rejects_duplicate_ids() {
+        let json = r#"{"rules":[
+            {"id":"a","match":{"identity":"*@example.com"}},
+            {"id":"b","match":{"artifact_type":"certificate_revocation"}}
+        ]}"#;
+        let list: Watchlist = serde_json::from_str(json).unwrap();
+        list.validate().unwrap();
+        assert_eq!(
+            list.rules[1].matcher,
+            Matcher::ArtifactType(ArtifactType::CertificateRevocation)
+        );
+
+        let dup = Watchlist {
+            rules: vec![
+                rule("a", Matcher::HashPrefix("ab".into())),
+                rule("a", Matcher::HashPrefix("cd".into())),
+            ],
+        };
+        assert!(dup.validate().is_err());
+        let bad_hex = Watchlist {
+            rules: vec![rule("x", Matcher::HashPrefix("zz".into()))],
+        };
+        assert!(bad_hex.validate().is_err());
+    }
+
+    #[test]
+    fn matches_raises_one_alert_per_rule() {
+        let list = Watchlist {
+            rules: vec![
+                rule("id", Matcher::Identity("*@example.com".into())),
+                rule("hash", Matcher::HashPrefix("c0ffee".into())),
+                rule("miss", Matcher::HashPrefix("dead".into())),
+            ],
+        };
+        let alerts = list.matches(&cert_entry("CN=ci@example.com", "CN=CA"));
+        let ids: Vec<_> = alerts.iter().map(|a| a.rule_id.as_str()).collect();
+        assert_eq!(ids, ["id", "hash"]);
+        assert_eq!(alerts[0].dedup_key(), "id/7");
+    }
+}
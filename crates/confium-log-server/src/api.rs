@@ -16,12 +16,16 @@ use serde_json::{Value, json};
 use crate::cert::{classify_cert, fingerprint, parse_der};
 use crate::db::{Database, Entry};
 use crate::merkle::MerkleState;
+use confium_transparency::vmap::{self, MapError, MapHead, VerifiableMap};
 
 /// Shared server state. Cheaply cloneable (everything is behind an
 /// `Arc` / `Mutex`).
 pub struct AppState {
     pub db: Database,
     pub merkle: parking_lot::Mutex<MerkleState>,
+    /// Verifiable map whose epochs are committed into `merkle`. Lock
+    /// `merkle` first when holding both.
+    pub map: parking_lot::Mutex<VerifiableMap>,
     pub page_size: usize,
 }
 
@@ -50,6 +54,13 @@ pub struct Pagination {
     pub before: Option<u64>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct EntryRange {
+    /// First 0-based sequence to return.
+    pub start: Option<u64>,
+    pub limit: Option<usize>,
+}
+
 pub fn router(state: Arc<AppState>) -> Router {
     Router::new()
         // Generic hash-entry API.
@@ -57,6 +68,7 @@ pub fn router(state: Arc<AppState>) -> Router {
         .route("/v1/head", get(head))
         .route("/v1/proof/{sequence}", get(proof))
         .route("/v1/consistency/{old_size}", get(consistency))
+        .route("/v1/entries", get(list_entries))
         // Cert-aware API.
         .route("/v1/certificates", post(append_certificate))
         .route("/v1/certificates/{fingerprint}", get(lookup_certificate))
@@ -66,6 +78,10 @@ pub fn router(state: Arc<AppState>) -> Router {
         // Witness gossip.
         .route("/v1/head/{sequence}/witness", post(post_witness))
         .route("/v1/head/{sequence}/witnesses", get(list_witnesses))
+        // Verifiable map (key transparency)
+        .route("/v1/map/publish", post(publish_map))
+        .route("/v1/map/head", get(map_head))
+        .route("/v1/map/lookup/{key}", get(map_lookup))
         .route("/v1/health", get(health))
         .route("/metrics", get(metrics))
         .with_state(state)
@@ -104,6 +120,7 @@ async fn append_hash(
         timestamp: timestamp.clone(),
         issuer_distinguished_name: None,
         subject_distinguished_name: None,
+        subject_alternative_names: Vec::new(),
         fingerprint_sha256: None,
         valid_from: None,
         valid_to: None,
@@ -193,6 +210,23 @@ async fn consistency(
     })))
 }
 
+async fn list_entries(
+    State(state): State<Arc<AppState>>,
+    Query(range): Query<EntryRange>,
+) -> Result<impl IntoResponse, ApiError> {
+    let start = range.start.unwrap_or(0);
+    let limit = range.limit.unwrap_or(state.page_size).min(state.page_size);
+    let entries = state
+        .db
+        .entries_range(start, limit)
+        .map_err(internal_error)?;
+    Ok(AxumJson(json!({
+        "start": start,
+        "count": entries.len(),
+        "entries": entries,
+    })))
+}
+
 // ===== Cert-aware handlers =====
 
 async fn append_certificate(
@@ -224,6 +258,7 @@ async fn append_certificate(
         timestamp: timestamp.clone(),
         issuer_distinguished_name: Some(meta.issuer_distinguished_name.clone()),
         subject_distinguished_name: Some(meta.subject_distinguished_name.clone()),
+        subject_alternative_names: meta.subject_alternative_names.clone(),
         fingerprint_sha256: Some(meta.fingerprint_sha256.clone()),
         valid_from: Some(meta.valid_from.clone()),
         valid_to: Some(meta.valid_to.clone()),
@@ -245,6 +280,7 @@ async fn append_certificate(
         "fingerprint_sha256": fingerprint_hex,
         "issuer": meta.issuer_distinguished_name,
         "subject": meta.subject_distinguished_name,
+        "subject_alternative_names": meta.subject_alternative_names,
     })))
 }
 
@@ -399,6 +435,121 @@ async fn metrics(State(state): State<Arc<AppState>>) -> impl IntoResponse {
     )
 }
 
+// ===== Verifiable map handlers =====
+
+#[derive(Debug, Deserialize)]
+pub struct MapUpdate {
+    /// Hex-encoded key.
+    pub key: String,
+    /// Base64-encoded value; `null` removes the key.
+    pub value: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MapPublishRequest {
+    pub updates: Vec<MapUpdate>,
+}
+
+fn map_head_json(head: &MapHead) -> Value {
+    json!({
+        "epoch": head.epoch,
+        "root": hex::encode(head.root),
+        "size": head.size,
+        "log_sequence": head.log_sequence,
+    })
+}
+
+async fn publish_map(
+    State(state): State<Arc<AppState>>,
+    AxumJson(req): AxumJson<MapPublishRequest>,
+) -> Result<impl IntoResponse, ApiError> {
+    let mut updates = Vec::with_capacity(req.updates.len());
+    for update in req.updates {
+        let key = hex::decode(&update.key)
+            .map_err(|e| ApiError::new(StatusCode::BAD_REQUEST, format!("bad key hex: {e}")))?;
+        let value = update
+            .value
+            .map(|v| base64::Engine::decode(&base64::engine::general_purpose::STANDARD, v))
+            .transpose()
+            .map_err(|e| ApiError::new(StatusCode::BAD_REQUEST, format!("bad base64: {e}")))?;
+        updates.push((key, value));
+    }
+
+    let mut merkle = state.merkle.lock();
+    let mut map = state.map.lock();
+    // Seal a copy: if the database write fails, neither the served map
+    // nor the tree has moved.
+    let mut next = map.clone();
+    for (key, value) in &updates {
+        match value {
+            Some(value) => next.insert(key, value.clone()),
+            None => next.remove(key),
+        };
+    }
+    let merkle_entry = next.seal(merkle.len());
+    let head = *next.head().expect("just sealed");
+    let entry = Entry {
+        sequence: 0,
+        artifact_type: confium_transparency::entry::ArtifactType::MapRoot
+            .as_str()
+            .to_string(),
+        artifact_hash: hex::encode(merkle_entry.artifact_hash),
+        timestamp: merkle_entry.timestamp.to_rfc3339(),
+        issuer_distinguished_name: None,
+        subject_distinguished_name: None,
+        subject_alternative_names: Vec::new(),
+        fingerprint_sha256: None,
+        valid_from: None,
+        valid_to: None,
+    };
+    let updates: Vec<_> = updates
+        .into_iter()
+        .map(|(key, value)| (vmap::key_hash(&key), value))
+        .collect();
+    state
+        .db
+        .publish_map(&entry, &updates, &head)
+        .map_err(internal_error)?;
+    merkle.append_entry(merkle_entry);
+    *map = next;
+
+    Ok(AxumJson(json!({
+        "head": map_head_json(&head),
+        "tree_size": merkle.len(),
+        "root": hex::encode(merkle.root()),
+    })))
+}
+
+async fn map_head(State(state): State<Arc<AppState>>) -> Result<impl IntoResponse, ApiError> {
+    let map = state.map.lock();
+    let head = map
+        .head()
+        .ok_or_else(|| ApiError::new(StatusCode::NOT_FOUND, MapError::NotPublished.to_string()))?;
+    Ok(AxumJson(json!({ "head": map_head_json(head) })))
+}
+
+/// A lookup carries the map proof, the log entry committing to the
+/// epoch, and that entry's inclusion proof against the returned log
+/// root; `MapVerifier` checks all three.
+async fn map_lookup(
+    State(state): State<Arc<AppState>>,
+    Path(key): Path<String>,
+) -> Result<impl IntoResponse, ApiError> {
+    let key = hex::decode(&key)
+        .map_err(|e| ApiError::new(StatusCode::BAD_REQUEST, format!("bad key hex: {e}")))?;
+    let merkle = state.merkle.lock();
+    let map = state.map.lock();
+    let lookup = map.lookup(&key, &merkle.tree).map_err(|e| match e {
+        MapError::NotPublished => ApiError::new(StatusCode::NOT_FOUND, e.to_string()),
+        e => internal_error(e),
+    })?;
+    Ok(AxumJson(json!({
+        "lookup": lookup,
+        "tree_size": merkle.len(),
+        "root": hex::encode(merkle.root()),
+    })))
+}
+
 // ===== Error helpers =====
 
 fn internal_error<E: std::fmt::Display>(e: E) -> ApiError {
@@ -449,6 +600,7 @@ mod tests {
         let state = Arc::new(AppState {
             db,
             merkle: parking_lot::Mutex::new(merkle),
+            map: parking_lot::Mutex::new(VerifiableMap::new()),
             page_size: 100,
         });
         router(state)
@@ -552,6 +704,7 @@ mod tests {
         let state = Arc::new(AppState {
             db,
             merkle,
+            map: parking_lot::Mutex::new(VerifiableMap::new()),
             page_size: 100,
         });
         let app = router(state.clone());
@@ -578,6 +731,32 @@ mod tests {
         assert_eq!(rebuilt.len(), 3);
     }
 
+    #[tokio::test]
+    async fn entries_page_from_zero_based_start() {
+        let app = app();
+        for hash in ["aa", "bb", "cc"] {
+            send(
+                &app,
+                Method::POST,
+                "/v1/append",
+                Some(json!({
+                    "artifact_type": "threshold_signature",
+                    "artifact_hash": hash.repeat(32),
+                })),
+            )
+            .await;
+        }
+
+        let page = send(&app, Method::GET, "/v1/entries?start=1&limit=10", None).await;
+        assert_eq!(page["count"], 2);
+        assert_eq!(page["entries"][0]["sequence"], 1);
+        assert_eq!(page["entries"][0]["artifact_hash"], "bb".repeat(32));
+        assert_eq!(page["entries"][1]["sequence"], 2);
+
+        let empty = send(&app, Method::GET, "/v1/entries?start=3", None).await;
+        assert_eq!(empty["count"], 0);
+    }
+
     #[tokio::test]
     async fn append_rejects_unknown_artifact_type() {
         let app = app();
@@ -596,4 +775,146 @@ mod tests {
         let response = app.oneshot(request).await.unwrap();
         assert_eq!(response.status(), StatusCode::BAD_REQUEST);
     }
+
+    fn map_update(key: &str, value: Option<&str>) -> Value {
+        json!({
+            "key": hex::encode(key),
+            "value": value.map(|v| base64::Engine::encode(
+                &base64::engine::general_purpose::STANDARD,
+                v,
+            )),
+        })
+    }
+
+    async fn verified_lookup(
+        app: &Router,
+        verifier: &mut vmap::MapVerifier,
+        key: &str,
+    ) -> Option<Vec<u8>> {
+        let served = send(
+            app,
+            Method::GET,
+            &format!("/v1/map/lookup/{}", hex::encode(key)),
+            None,
+        )
+        .await;
+        let lookup: vmap::MapLookup = serde_json::from_value(served["lookup"].clone()).unwrap();
+        let root: [u8; 32] = hex::decode(served["root"].as_str().unwrap())
+            .unwrap()
+            .try_into()
+            .unwrap();
+        verifier
+            .verify(key.as_bytes(), &lookup, root)
+            .unwrap()
+            .map(<[u8]>::to_vec)
+    }
+
+    /// Served lookups verify end to end against the log root: the map
+    /// proof, the commitment entry and its inclusion proof.
+    #[tokio::test]
+    async fn map_lookups_verify_against_the_log() {
+        let app = app();
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri(format!("/v1/map/lookup/{}", hex::encode("alice")))
+            .body(Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        send(
+            &app,
+            Method::POST,
+            "/v1/append",
+            Some(json!({
+                "artifact_type": "threshold_signature",
+                "artifact_hash": "ab".repeat(32),
+            })),
+        )
+        .await;
+        let first = send(
+            &app,
+            Method::POST,
+            "/v1/map/publish",
+            Some(json!({"updates": [
+                map_update("alice@example.com", Some("alice-key")),
+                map_update("bob@example.com", Some("bob-key")),
+            ]})),
+        )
+        .await;
+        assert_eq!(first["head"]["epoch"], 1);
+        assert_eq!(first["head"]["size"], 2);
+        assert_eq!(first["head"]["log_sequence"], 1);
+        assert_eq!(first["tree_size"], 2);
+        let head = send(&app, Method::GET, "/v1/map/head", None).await;
+        assert_eq!(head["head"], first["head"]);
+
+        let mut verifier = vmap::MapVerifier::new();
+        assert_eq!(
+            verified_lookup(&app, &mut verifier, "alice@example.com").await,
+            Some(b"alice-key".to_vec())
+        );
+        assert_eq!(
+            verified_lookup(&app, &mut verifier, "mallory@example.com").await,
+            None
+        );
+
+        let second = send(
+            &app,
+            Method::POST,
+            "/v1/map/publish",
+            Some(json!({"updates": [map_update("bob@example.com", None)]})),
+        )
+        .await;
+        assert_eq!(second["head"]["epoch"], 2);
+        assert_eq!(second["head"]["size"], 1);
+        assert_eq!(
+            verified_lookup(&app, &mut verifier, "bob@example.com").await,
+            None
+        );
+        assert_eq!(verifier.latest().unwrap().epoch, 2);
+    }
+
+    /// A restart restores the map from the database, and the map
+    /// commitments survive the log rebuild.
+    #[tokio::test]
+    async fn map_survives_a_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open(&dir.path().join("log.db")).unwrap();
+        db.init_schema().unwrap();
+        let merkle = parking_lot::Mutex::new(MerkleState::from_db(&db).unwrap());
+        let state = Arc::new(AppState {
+            db,
+            merkle,
+            map: parking_lot::Mutex::new(VerifiableMap::new()),
+            page_size: 100,
+        });
+        let app = router(state.clone());
+        for updates in [
+            json!([
+                map_update("alice", Some("a1")),
+                map_update("bob", Some("b1"))
+            ]),
+            json!([map_update("alice", Some("a2")), map_update("bob", None)]),
+        ] {
+            send(
+                &app,
+                Method::POST,
+                "/v1/map/publish",
+                Some(json!({ "updates": updates })),
+            )
+            .await;
+        }
+
+        let rebuilt = MerkleState::from_db(&state.db).unwrap();
+        assert_eq!(rebuilt.root(), state.merkle.lock().root());
+        let restored = crate::merkle::map_from_db(&state.db).unwrap();
+        assert_eq!(restored.head(), state.map.lock().head());
+        let lookup = restored.lookup(b"alice", &rebuilt.tree).unwrap();
+        let mut verifier = vmap::MapVerifier::new();
+        assert_eq!(
+            verifier.verify(b"alice", &lookup, rebuilt.root()).unwrap(),
+            Some(&b"a2"[..])
+        );
+    }
 }
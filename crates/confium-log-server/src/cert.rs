@@ -2,9 +2,9 @@
 //!
 //! Wraps `confium_pki::cert::Certificate` to extract the metadata
 //! the cert-aware API endpoints need: issuer DN, subject DN,
-//! validity window, SHA-256 fingerprint.
+//! subject alternative names, validity window, SHA-256 fingerprint.
 
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, anyhow};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -12,6 +12,9 @@ pub struct CertMetadata {
     pub fingerprint_sha256: String,
     pub issuer_distinguished_name: String,
     pub subject_distinguished_name: String,
+    /// Subject alternative names as `type:value` strings; see
+    /// [`subject_alternative_names`].
+    pub subject_alternative_names: Vec<String>,
     pub valid_from: String,
     pub valid_to: String,
     pub serial_hex: String,
@@ -29,12 +32,48 @@ pub fn parse_der(der_bytes: &[u8]) -> Result<CertMetadata> {
         fingerprint_sha256: cert.fingerprint_sha256(),
         issuer_distinguished_name: issuer,
         subject_distinguished_name: subject,
+        subject_alternative_names: subject_alternative_names(&cert)?,
         valid_from: cert.not_before_chrono().to_rfc3339(),
         valid_to: cert.not_after_chrono().to_rfc3339(),
         serial_hex: hex::encode(cert.serial_bytes()),
     })
 }
 
+/// The certificate's email, URI and DNS subject alternative names as
+/// `email:…`, `uri:…` and `dns:…`, in extension order. Keyless
+/// certificates carry the signer identity (an OIDC email or workflow
+/// URI) here and leave the subject DN empty, so monitors match on
+/// these rather than the DN alone. Other name forms are skipped.
+fn subject_alternative_names(cert: &confium_pki::cert::Certificate) -> Result<Vec<String>> {
+    use der::Decode;
+    use x509_cert::ext::pkix::SubjectAltName;
+    use x509_cert::ext::pkix::name::GeneralName;
+
+    let mut out = Vec::new();
+    for ext in cert
+        .as_inner()
+        .tbs_certificate()
+        .extensions()
+        .unwrap_or(&Vec::new())
+    {
+        // OID 2.5.29.17 = subjectAltName
+        if ext.extn_id.to_string() != "2.5.29.17" {
+            continue;
+        }
+        let names = SubjectAltName::from_der(ext.extn_value.as_bytes())
+            .map_err(|e| anyhow!("decoding subjectAltName extension: {e}"))?;
+        for name in names.0 {
+            match name {
+                GeneralName::Rfc822Name(email) => out.push(format!("email:{email}")),
+                GeneralName::UniformResourceIdentifier(uri) => out.push(format!("uri:{uri}")),
+                GeneralName::DnsName(dns) => out.push(format!("dns:{dns}")),
+                _ => {}
+            }
+        }
+    }
+    Ok(out)
+}
+
 /// Compute the SHA-256 fingerprint of a byte slice. Used as the
 /// leaf hash for cert entries.
 pub fn fingerprint(bytes: &[u8]) -> [u8; 32] {
@@ -78,6 +117,7 @@ mod tests {
             fingerprint_sha256: "00".repeat(32),
             issuer_distinguished_name: "CNML Root CA".to_string(),
             subject_distinguished_name: "Acme CNML Cert".to_string(),
+            subject_alternative_names: Vec::new(),
             valid_from: "2026-01-01T00:00:00Z".to_string(),
             valid_to: "2027-01-01T00:00:00Z".to_string(),
             serial_hex: "00".to_string(),
@@ -85,6 +125,40 @@ mod tests {
         assert_eq!(classify_cert(&[], &meta), "cnml_certificate");
     }
 
+    /// Self-signed P-256 certificate with subject `O=Example` and SANs
+    /// `email:release@example.com`, a workflow URI and
+    /// `DNS:build.example.com`.
+    const SAN_CERT_PEM: &str = "-----BEGIN CERTIFICATE-----
+MIIB+zCCAaKgAwIBAgIUOiy7BGFnwzGK2JdATX9IjSC76XAwCgYIKoZIzj0EAwIw
+EjEQMA4GA1UECgwHRXhhbXBsZTAeFw0yNjEwMTkxMDU0MjZaFw0zNjEwMTYxMDU0
+MjZaMBIxEDAOBgNVBAoMB0V4YW1wbGUwWTATBgcqhkjOPQIBBggqhkjOPQMBBwNC
+AASmRF4SczUH6HG34TUfcINaAF3gePkNwBlZrXGFHIrv8SiL01wozTaPgNo1gJuv
+V/QsIsnS5kRsudT7Ss5gHyM/o4HVMIHSMB0GA1UdDgQWBBQWumcDaUyK55pzSg7k
+2qX9QecrizAfBgNVHSMEGDAWgBQWumcDaUyK55pzSg7k2qX9QecrizAPBgNVHRMB
+Af8EBTADAQH/MH8GA1UdEQR4MHaBE3JlbGVhc2VAZXhhbXBsZS5jb22GTGh0dHBz
+Oi8vZ2l0aHViLmNvbS9leGFtcGxlL2FwcC8uZ2l0aHViL3dvcmtmbG93cy9yZWxl
+YXNlLnltbEByZWZzL2hlYWRzL21haW6CEWJ1aWxkLmV4YW1wbGUuY29tMAoGCCqG
+SM49BAMCA0cAMEQCIFKlI6t6piTRJBWR4wGywhMYXLLwOt2v0E60KA/jIkQeAiAZ
+y+sXu+jMYQmcVpRlUU83KwZswztpPAhGDHW76VJf8A==
+-----END CERTIFICATE-----
+";
+
+    #[test]
+    fn parse_der_extracts_subject_alternative_names() {
+        let der = confium_pki::cert::Certificate::from_pem(SAN_CERT_PEM)
+            .unwrap()
+            .to_der();
+        let meta = parse_der(&der).unwrap();
+        assert_eq!(
+            meta.subject_alternative_names,
+            [
+                "email:release@example.com",
+                "uri:https://github.com/example/app/.github/workflows/release.yml@refs/heads/main",
+                "dns:build.example.com",
+            ]
+        );
+    }
+
     #[test]
     fn fingerprint_is_32_bytes() {
         let fp = fingerprint(b"hello");
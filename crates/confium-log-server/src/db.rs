@@ -16,12 +16,17 @@
 //! - `ots_proofs` — Bitcoin OTS proofs keyed by tree head sequence.
 //! - `witness_sigs` — witness countersignatures keyed by tree head
 //!   sequence + witness ID.
+//! - `map_values` / `map_heads` — the verifiable map's contents as of
+//!   its newest epoch, and every epoch's head. Each epoch's
+//!   commitment is also an `entries` row.
 
+use std::collections::BTreeMap;
 use std::path::Path;
 use std::sync::Arc;
 
-use anyhow::{Context, Result, anyhow};
+use anyhow::{Context, Result, anyhow, ensure};
 use confium_transparency::entry::ArtifactType;
+use confium_transparency::vmap::MapHead;
 use rusqlite::{Connection, params};
 use serde::{Deserialize, Serialize};
 
@@ -38,6 +43,10 @@ pub struct RebuildRow {
 /// anchor timestamp (ISO 8601).
 pub type OtsProofRow = (Vec<u8>, Option<u64>, String);
 
+/// Stored verifiable map: its values keyed by key hash, and the head
+/// of its newest epoch.
+pub type StoredMap = (BTreeMap<[u8; 32], Vec<u8>>, MapHead);
+
 /// Wrapper around the SQLite connection. Cheaply cloneable because
 /// `Connection` is wrapped in a `Mutex` inside an `Arc`.
 #[derive(Clone)]
@@ -53,11 +62,78 @@ pub struct Entry {
     pub timestamp: String,     // RFC3339
     pub issuer_distinguished_name: Option<String>,
     pub subject_distinguished_name: Option<String>,
+    /// Certificate subject alternative names as `type:value`, stored
+    /// as a JSON array in `subject_alt_names`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub subject_alternative_names: Vec<String>,
     pub fingerprint_sha256: Option<String>, // hex
     pub valid_from: Option<String>,
     pub valid_to: Option<String>,
 }
 
+/// `subject_alt_names` column value: a JSON array, NULL when empty.
+pub fn encode_sans(sans: &[String]) -> Option<String> {
+    (!sans.is_empty()).then(|| serde_json::Value::from(sans).to_string())
+}
+
+/// Inverse of [`encode_sans`].
+pub fn decode_sans(raw: Option<String>) -> serde_json::Result<Vec<String>> {
+    raw.map_or(Ok(Vec::new()), |json| serde_json::from_str(&json))
+}
+
+/// Columns every entry query selects, in [`row_to_entry`] order.
+const ENTRY_COLUMNS: &str = "artifact_type, artifact_hash, timestamp,
+                    issuer_dn, subject_dn, fingerprint_sha256,
+                    valid_from, valid_to, subject_alt_names";
+
+fn row_to_entry(row: &rusqlite::Row<'_>) -> rusqlite::Result<Entry> {
+    Ok(Entry {
+        sequence: row.get::<_, i64>(0)? as u64,
+        artifact_type: row.get(1)?,
+        artifact_hash: row.get(2)?,
+        timestamp: row.get(3)?,
+        issuer_distinguished_name: row.get(4)?,
+        subject_distinguished_name: row.get(5)?,
+        fingerprint_sha256: row.get(6)?,
+        valid_from: row.get(7)?,
+        valid_to: row.get(8)?,
+        subject_alternative_names: decode_sans(row.get(9)?).map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(9, rusqlite::types::Type::Text, Box::new(e))
+        })?,
+    })
+}
+
+fn insert_entry(conn: &Connection, entry: &Entry) -> rusqlite::Result<u64> {
+    conn.execute(
+        "INSERT INTO entries
+            (artifact_type, artifact_hash, timestamp,
+             issuer_dn, subject_dn, fingerprint_sha256,
+             valid_from, valid_to, subject_alt_names)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        params![
+            entry.artifact_type,
+            entry.artifact_hash,
+            entry.timestamp,
+            entry.issuer_distinguished_name,
+            entry.subject_distinguished_name,
+            entry.fingerprint_sha256,
+            entry.valid_from,
+            entry.valid_to,
+            encode_sans(&entry.subject_alternative_names),
+        ],
+    )?;
+    // Rowids are 1-based; entry sequences are 0-based to match the
+    // Merkle leaf index used by the proof endpoints.
+    Ok((conn.last_insert_rowid() - 1) as u64)
+}
+
+fn decode_hash(hex_hash: &str) -> Result<[u8; 32]> {
+    hex::decode(hex_hash)
+        .map_err(|e| anyhow!("hash hex decode: {e}"))?
+        .try_into()
+        .map_err(|bytes: Vec<u8>| anyhow!("hash must be 32 bytes, got {}", bytes.len()))
+}
+
 impl Database {
     pub fn open(path: &Path) -> Result<Self> {
         let conn = Connection::open(path)
@@ -85,7 +161,8 @@ impl Database {
                 subject_dn         TEXT,
                 fingerprint_sha256 TEXT,
                 valid_from         TEXT,
-                valid_to           TEXT
+                valid_to           TEXT,
+                subject_alt_names  TEXT
             );
 
             CREATE INDEX IF NOT EXISTS idx_entries_fingerprint
@@ -122,56 +199,128 @@ impl Database {
                 signature   BLOB NOT NULL,
                 timestamp   TEXT NOT NULL,
                 PRIMARY KEY (tree_size, witness_id)
+            );
+
+            CREATE TABLE IF NOT EXISTS map_values (
+                key_hash TEXT PRIMARY KEY,
+                value    BLOB NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS map_heads (
+                epoch        INTEGER PRIMARY KEY,
+                root         TEXT NOT NULL,
+                size         INTEGER NOT NULL,
+                log_sequence INTEGER NOT NULL
             );",
         )?;
+        // Databases created before SANs were recorded lack the column.
+        let has_sans = conn
+            .prepare("SELECT 1 FROM pragma_table_info('entries') WHERE name = 'subject_alt_names'")?
+            .exists([])?;
+        if !has_sans {
+            conn.execute_batch("ALTER TABLE entries ADD COLUMN subject_alt_names TEXT")?;
+        }
         Ok(())
     }
 
     pub fn append(&self, entry: &Entry) -> Result<u64> {
         let conn = self.conn.lock();
-        conn.execute(
-            "INSERT INTO entries
-                (artifact_type, artifact_hash, timestamp,
-                 issuer_dn, subject_dn, fingerprint_sha256,
-                 valid_from, valid_to)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        Ok(insert_entry(&conn, entry)?)
+    }
+
+    /// Record a verifiable-map epoch in one transaction: `entry`, the
+    /// log entry committing to `head`, which must land at
+    /// `head.log_sequence`; the key updates the epoch applied (`None`
+    /// removes the key); and the head itself. The stored contents
+    /// therefore always match the newest stored head.
+    pub fn publish_map(
+        &self,
+        entry: &Entry,
+        updates: &[([u8; 32], Option<Vec<u8>>)],
+        head: &MapHead,
+    ) -> Result<()> {
+        let mut conn = self.conn.lock();
+        let tx = conn.transaction()?;
+        let sequence = insert_entry(&tx, entry)?;
+        ensure!(
+            sequence == head.log_sequence,
+            "map commitment landed at {sequence}, expected {}",
+            head.log_sequence
+        );
+        for (key_hash, value) in updates {
+            match value {
+                Some(value) => tx.execute(
+                    "INSERT OR REPLACE INTO map_values (key_hash, value) VALUES (?1, ?2)",
+                    params![hex::encode(key_hash), value],
+                )?,
+                None => tx.execute(
+                    "DELETE FROM map_values WHERE key_hash = ?1",
+                    params![hex::encode(key_hash)],
+                )?,
+            };
+        }
+        tx.execute(
+            "INSERT INTO map_heads (epoch, root, size, log_sequence)
+             VALUES (?1, ?2, ?3, ?4)",
             params![
-                entry.artifact_type,
-                entry.artifact_hash,
-                entry.timestamp,
-                entry.issuer_distinguished_name,
-                entry.subject_distinguished_name,
-                entry.fingerprint_sha256,
-                entry.valid_from,
-                entry.valid_to,
+                head.epoch as i64,
+                hex::encode(head.root),
+                head.size as i64,
+                head.log_sequence as i64,
             ],
         )?;
-        // Rowids are 1-based; entry sequences are 0-based to match the
-        // Merkle leaf index used by the proof endpoints.
-        Ok((conn.last_insert_rowid() - 1) as u64)
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// The map's contents and newest head, if any epoch was published.
+    pub fn load_map(&self) -> Result<Option<StoredMap>> {
+        let conn = self.conn.lock();
+        let head = conn.query_row(
+            "SELECT epoch, root, size, log_sequence
+             FROM map_heads ORDER BY epoch DESC LIMIT 1",
+            [],
+            |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, i64>(2)?,
+                    row.get::<_, i64>(3)?,
+                ))
+            },
+        );
+        let (epoch, root, size, log_sequence) = match head {
+            Ok(row) => row,
+            Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        let head = MapHead {
+            epoch: epoch as u64,
+            root: decode_hash(&root).with_context(|| format!("map head {epoch} root"))?,
+            size: size as u64,
+            log_sequence: log_sequence as u64,
+        };
+
+        let mut stmt = conn.prepare("SELECT key_hash, value FROM map_values")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, Vec<u8>>(1)?))
+        })?;
+        let mut values = BTreeMap::new();
+        for row in rows {
+            let (key_hash, value) = row?;
+            let key = decode_hash(&key_hash).with_context(|| format!("map key {key_hash}"))?;
+            values.insert(key, value);
+        }
+        Ok(Some((values, head)))
     }
 
     pub fn entry_at(&self, sequence: u64) -> Result<Option<Entry>> {
         let conn = self.conn.lock();
-        let mut stmt = conn.prepare(
-            "SELECT sequence, artifact_type, artifact_hash, timestamp,
-                    issuer_dn, subject_dn, fingerprint_sha256,
-                    valid_from, valid_to
-             FROM entries WHERE sequence = ?1 + 1",
-        )?;
-        let rows = stmt.query_row(params![sequence as i64], |row| {
-            Ok(Entry {
-                sequence: row.get::<_, i64>(0)? as u64,
-                artifact_type: row.get(1)?,
-                artifact_hash: row.get(2)?,
-                timestamp: row.get(3)?,
-                issuer_distinguished_name: row.get(4)?,
-                subject_distinguished_name: row.get(5)?,
-                fingerprint_sha256: row.get(6)?,
-                valid_from: row.get(7)?,
-                valid_to: row.get(8)?,
-            })
-        });
+        let mut stmt = conn.prepare(&format!(
+            "SELECT sequence, {ENTRY_COLUMNS}
+             FROM entries WHERE sequence = ?1 + 1"
+        ))?;
+        let rows = stmt.query_row(params![sequence as i64], row_to_entry);
         match rows {
             Ok(e) => Ok(Some(e)),
             Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
@@ -187,26 +336,12 @@ impl Database {
 
     pub fn entries_by_fingerprint(&self, fingerprint_hex: &str) -> Result<Vec<Entry>> {
         let conn = self.conn.lock();
-        let mut stmt = conn.prepare(
-            "SELECT sequence, artifact_type, artifact_hash, timestamp,
-                    issuer_dn, subject_dn, fingerprint_sha256,
-                    valid_from, valid_to
+        let mut stmt = conn.prepare(&format!(
+            "SELECT sequence, {ENTRY_COLUMNS}
              FROM entries WHERE fingerprint_sha256 = ?1
-             ORDER BY sequence ASC",
-        )?;
-        let rows = stmt.query_map(params![fingerprint_hex], |row| {
-            Ok(Entry {
-                sequence: row.get::<_, i64>(0)? as u64,
-                artifact_type: row.get(1)?,
-                artifact_hash: row.get(2)?,
-                timestamp: row.get(3)?,
-                issuer_distinguished_name: row.get(4)?,
-                subject_distinguished_name: row.get(5)?,
-                fingerprint_sha256: row.get(6)?,
-                valid_from: row.get(7)?,
-                valid_to: row.get(8)?,
-            })
-        })?;
+             ORDER BY sequence ASC"
+        ))?;
+        let rows = stmt.query_map(params![fingerprint_hex], row_to_entry)?;
         let mut out = Vec::new();
         for row in rows {
             out.push(row?);
@@ -216,27 +351,32 @@ impl Database {
 
     pub fn entries_by_issuer(&self, issuer_dn: &str, limit: usize) -> Result<Vec<Entry>> {
         let conn = self.conn.lock();
-        let mut stmt = conn.prepare(
-            "SELECT sequence, artifact_type, artifact_hash, timestamp,
-                    issuer_dn, subject_dn, fingerprint_sha256,
-                    valid_from, valid_to
+        let mut stmt = conn.prepare(&format!(
+            "SELECT sequence, {ENTRY_COLUMNS}
              FROM entries WHERE issuer_dn = ?1
              ORDER BY sequence DESC
-             LIMIT ?2",
-        )?;
-        let rows = stmt.query_map(params![issuer_dn, limit as i64], |row| {
-            Ok(Entry {
-                sequence: row.get::<_, i64>(0)? as u64,
-                artifact_type: row.get(1)?,
-                artifact_hash: row.get(2)?,
-                timestamp: row.get(3)?,
-                issuer_distinguished_name: row.get(4)?,
-                subject_distinguished_name: row.get(5)?,
-                fingerprint_sha256: row.get(6)?,
-                valid_from: row.get(7)?,
-                valid_to: row.get(8)?,
-            })
-        })?;
+             LIMIT ?2"
+        ))?;
+        let rows = stmt.query_map(params![issuer_dn, limit as i64], row_to_entry)?;
+        let mut out = Vec::new();
+        for row in rows {
+            out.push(row?);
+        }
+        Ok(out)
+    }
+
+    /// Page through entries in sequence order, starting at the
+    /// 0-based `start` sequence. Monitors use this to tail the log.
+    /// Sequences are reported 0-based to match the Merkle leaf index.
+    pub fn entries_range(&self, start: u64, limit: usize) -> Result<Vec<Entry>> {
+        let conn = self.conn.lock();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT sequence - 1, {ENTRY_COLUMNS}
+             FROM entries WHERE sequence > ?1
+             ORDER BY sequence ASC
+             LIMIT ?2"
+        ))?;
+        let rows = stmt.query_map(params![start as i64, limit as i64], row_to_entry)?;
         let mut out = Vec::new();
         for row in rows {
             out.push(row?);
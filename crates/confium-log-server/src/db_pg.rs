@@ -28,7 +28,7 @@ use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use tokio_postgres::Client;
 
-use crate::db::Entry;
+use crate::db::{Entry, decode_sans, encode_sans};
 
 /// PostgreSQL-backed storage. Async because PostgreSQL I/O is
 /// naturally async (unlike SQLite's blocking calls).
@@ -78,9 +78,13 @@ impl PostgresBackend {
                     subject_dn         TEXT,
                     fingerprint_sha256 TEXT,
                     valid_from         TEXT,
-                    valid_to           TEXT
+                    valid_to           TEXT,
+                    subject_alt_names  TEXT
                 );
 
+                -- Databases created before SANs were recorded.
+                ALTER TABLE entries ADD COLUMN IF NOT EXISTS subject_alt_names TEXT;
+
                 CREATE INDEX IF NOT EXISTS idx_entries_fingerprint
                     ON entries(fingerprint_sha256);
                 CREATE INDEX IF NOT EXISTS idx_entries_issuer
@@ -116,8 +120,8 @@ impl PostgresBackend {
                 "INSERT INTO entries
                     (artifact_type, artifact_hash, timestamp,
                      issuer_dn, subject_dn, fingerprint_sha256,
-                     valid_from, valid_to)
-                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                     valid_from, valid_to, subject_alt_names)
+                 VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
                  RETURNING sequence",
                 &[
                     &entry.artifact_type,
@@ -128,6 +132,7 @@ impl PostgresBackend {
                     &entry.fingerprint_sha256,
                     &entry.valid_from,
                     &entry.valid_to,
+                    &encode_sans(&entry.subject_alternative_names),
                 ],
             )
             .await?;
@@ -152,7 +157,7 @@ impl PostgresBackend {
             .query(
                 "SELECT sequence, artifact_type, artifact_hash, timestamp,
                         issuer_dn, subject_dn, fingerprint_sha256,
-                        valid_from, valid_to
+                        valid_from, valid_to, subject_alt_names
                  FROM entries WHERE fingerprint_sha256 = $1
                  ORDER BY sequence ASC",
                 &[&fingerprint_hex],
@@ -167,7 +172,7 @@ impl PostgresBackend {
             .query(
                 "SELECT sequence, artifact_type, artifact_hash, timestamp,
                         issuer_dn, subject_dn, fingerprint_sha256,
-                        valid_from, valid_to
+                        valid_from, valid_to, subject_alt_names
                  FROM entries WHERE issuer_dn = $1
                  ORDER BY sequence DESC
                  LIMIT $2",
@@ -177,6 +182,22 @@ impl PostgresBackend {
         rows.into_iter().map(pg_row_to_entry).collect()
     }
 
+    pub async fn entries_range(&self, start: u64, limit: usize) -> Result<Vec<Entry>> {
+        let rows = self
+            .client
+            .query(
+                "SELECT sequence - 1, artifact_type, artifact_hash, timestamp,
+                        issuer_dn, subject_dn, fingerprint_sha256,
+                        valid_from, valid_to, subject_alt_names
+                 FROM entries WHERE sequence > $1
+                 ORDER BY sequence ASC
+                 LIMIT $2",
+                &[&(start as i64), &(limit as i64)],
+            )
+            .await?;
+        rows.into_iter().map(pg_row_to_entry).collect()
+    }
+
     pub async fn all_leaf_hashes(&self) -> Result<Vec<[u8; 32]>> {
         let rows = self
             .client
@@ -291,5 +312,6 @@ fn pg_row_to_entry(row: tokio_postgres::Row) -> Result<Entry> {
         fingerprint_sha256: row.get(6),
         valid_from: row.get(7),
         valid_to: row.get(8),
+        subject_alternative_names: decode_sans(row.get(9))?,
     })
 }
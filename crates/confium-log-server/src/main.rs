@@ -42,6 +42,12 @@
 //!
 //! `POST /v1/head/<sequence>/witness` — submit a witness countersignature
 //! `GET /v1/head/<sequence>/witnesses` — list known witnesses for tree head
+//!
+//! ### Verifiable map (key transparency)
+//!
+//! `POST /v1/map/publish` — apply key updates and publish the next epoch
+//! `GET /v1/map/head` — newest map epoch and root
+//! `GET /v1/map/lookup/<key>` — value with inclusion or non-inclusion proof
 
 // Several log-server helpers (pagination field, witness digest helpers,
 // historical entry lookup) are pub for the upcoming HTTP API expansion
@@ -109,9 +115,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let db = db::Database::open(&args.db)?;
     db.init_schema()?;
     let merkle = merkle::MerkleState::from_db(&db)?;
+    let map = merkle::map_from_db(&db)?;
     let state = Arc::new(AppState {
         db,
         merkle: parking_lot::Mutex::new(merkle),
+        map: parking_lot::Mutex::new(map),
         page_size: args.page_size,
     });
 
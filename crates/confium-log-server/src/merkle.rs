@@ -10,6 +10,7 @@ use anyhow::{Context, Result};
 use confium_transparency::{
     entry::{ArtifactType, MerkleEntry},
     merkle::{Hash, InclusionProof, MerkleError, MerkleTree},
+    vmap::VerifiableMap,
 };
 
 use crate::db::Database;
@@ -64,6 +65,13 @@ impl MerkleState {
         self.tree.append(entry)
     }
 
+    /// Append an entry built elsewhere, such as a map epoch's
+    /// commitment. Its timestamp must be the stored one, as for
+    /// [`append`](Self::append).
+    pub fn append_entry(&mut self, entry: MerkleEntry) -> u64 {
+        self.tree.append(entry)
+    }
+
     pub fn root(&self) -> Hash {
         self.tree.root()
     }
@@ -83,3 +91,12 @@ impl MerkleState {
         self.tree.consistency_proof(old_size as usize)
     }
 }
+
+/// The verifiable map as of its newest stored epoch, or an empty map
+/// if none was published.
+pub fn map_from_db(db: &Database) -> Result<VerifiableMap> {
+    match db.load_map().context("loading verifiable map")? {
+        Some((values, head)) => Ok(VerifiableMap::restore(values, head)?),
+        None => Ok(VerifiableMap::new()),
+    }
+}
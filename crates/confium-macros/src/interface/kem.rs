@@ -1,4 +1,4 @@
-//! Wire protocol generator for the KEM interface (v0).
+//! Wire protocol generator for the KEM interface (v0 and v1).
 //!
 //! Emits the `cfmp_kem_*` symbols from an
 //! `impl KemPlugin for T` block. The FFI surface splits into
@@ -9,6 +9,11 @@
 //! Several entry points have complex parameter lists fixed by the C
 //! ABI; the macro emits `#[allow(clippy::too_many_arguments)]`
 //! automatically on those symbols.
+//!
+//! Version 1 (`generate_v1`) additionally emits
+//! `cfmp_kem_handle_capabilities` and
+//! `cfmp_kem_decapsulator_create_with_handle` for plugins that
+//! implement `KemHandlePlugin`.
 
 use proc_macro2::TokenStream;
 use quote::quote;
@@ -278,3 +283,79 @@ pub fn generate_v0(self_ty: &syn::Type) -> syn::Result<TokenStream> {
         }
     })
 }
+
+/// Emit the KEM v1 FFI symbols: the full v0 set plus the key-handle
+/// extension (`cfmp_kem_handle_capabilities`, `cfmp_kem_decapsulator_create_with_handle`) dispatching
+/// through `::confium_api::plugin::kem::KemHandlePlugin`.
+pub fn generate_v1(self_ty: &syn::Type) -> syn::Result<TokenStream> {
+    let v0 = generate_v0(self_ty)?;
+
+    Ok(quote! {
+        #v0
+
+        // ---- cfmp_kem_handle_capabilities ----
+        #[unsafe(no_mangle)]
+        pub unsafe extern "C" fn cfmp_kem_handle_capabilities(
+            algorithm: *const std::os::raw::c_char,
+            key_handle: *const ::confium_api::CFMKeyHandle,
+            caps_out: *mut u32,
+        ) -> u32 {
+            use std::ffi::CStr;
+            let caps_out = match caps_out.as_mut() {
+                Some(o) => o,
+                None => return ::confium_api::ErrorCode::NULL_POINTER.into_wire(),
+            };
+            if algorithm.is_null() {
+                return ::confium_api::ErrorCode::NULL_POINTER.into_wire();
+            }
+            let algorithm = match CStr::from_ptr(algorithm).to_str() {
+                Ok(s) => s.to_string(),
+                Err(_) => return ::confium_api::ErrorCode::INVALID_UTF8.into_wire(),
+            };
+            let Some(key_handle) = (unsafe { ::confium_api::KeyHandle::from_raw(key_handle) }) else {
+                return ::confium_api::ErrorCode::NULL_POINTER.into_wire();
+            };
+            *caps_out = <#self_ty as ::confium_api::plugin::kem::KemHandlePlugin>::handle_capabilities(
+                &algorithm, &key_handle,
+            )
+            .bits();
+            0
+        }
+
+        // ---- cfmp_kem_decapsulator_create_with_handle ----
+        #[unsafe(no_mangle)]
+        pub unsafe extern "C" fn cfmp_kem_decapsulator_create_with_handle(
+            _cfm: *const std::ffi::c_void,
+            out: *mut *mut std::ffi::c_void,
+            algorithm: *const std::os::raw::c_char,
+            key_handle: *const ::confium_api::CFMKeyHandle,
+            opts: *const std::ffi::c_void,
+        ) -> u32 {
+            use std::ffi::CStr;
+            let out = match out.as_mut() {
+                Some(o) => o,
+                None => return ::confium_api::ErrorCode::NULL_POINTER.into_wire(),
+            };
+            if algorithm.is_null() {
+                return ::confium_api::ErrorCode::NULL_POINTER.into_wire();
+            }
+            let algorithm = match CStr::from_ptr(algorithm).to_str() {
+                Ok(s) => s.to_string(),
+                Err(_) => return ::confium_api::ErrorCode::INVALID_UTF8.into_wire(),
+            };
+            let Some(key_handle) = (unsafe { ::confium_api::KeyHandle::from_raw(key_handle) }) else {
+                return ::confium_api::ErrorCode::NULL_POINTER.into_wire();
+            };
+            let opts_view = if opts.is_null() {
+                None
+            } else {
+                unsafe { ::confium_api::OptionView::from_raw_ptr(opts) }
+            };
+            <#self_ty as ::confium_api::plugin::kem::KemHandlePlugin>::decapsulator_create_with_handle(
+                &algorithm, &key_handle, opts_view,
+            )
+            .map(|inst| { *out = ::confium_api::OpaqueHandle::new(inst); })
+            .map_or_else(|e| e.into_wire(), |_| 0u32)
+        }
+    })
+}
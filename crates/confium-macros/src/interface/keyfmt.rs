@@ -89,6 +89,10 @@ pub fn generate_v0(self_ty: &syn::Type) -> syn::Result<TokenStream> {
                 Ok(bytes) => {
                     let n = bytes.len();
                     if n > out_max as usize {
+                        // Report the required size so the loader's
+                        // probe-then-fill `serialize` can size its
+                        // second pass.
+                        unsafe { *out_len = n as u32; }
                         return ::confium_api::ErrorCode::INSUFFICIENT_BUFFER.into_wire();
                     }
                     if !out.is_null() && n > 0 {
@@ -10,6 +10,11 @@
 //! Adding a new interface = adding a `mod <name>` in `mod.rs` that
 //! exposes a `generate_v0(self_ty)` function and a match arm in
 //! [`plugin_interface_impl`]. No existing code changes.
+//!
+//! `signature` and `kem` also have a version 1 (the key-handle
+//! extension). A v1 symbol set is a superset of v0, so a v1 impl
+//! registers both versions and the loader negotiates whichever it
+//! understands.
 
 mod aead;
 mod cipher;
@@ -42,16 +47,28 @@ pub fn plugin_interface_impl(
     // to construct / cast instances of it.
     let self_ty = &impl_block.self_ty;
 
-    let ffi = match spec.name.as_str() {
-        "hash" => hash::generate_v0(self_ty)?,
-        "cipher" => cipher::generate_v0(self_ty)?,
-        "aead" => aead::generate_v0(self_ty)?,
-        "kdf" => kdf::generate_v0(self_ty)?,
-        "rng" => rng::generate_v0(self_ty)?,
-        "signature" => signature::generate_v0(self_ty)?,
-        "kem" => kem::generate_v0(self_ty)?,
-        "keyfmt" => keyfmt::generate_v0(self_ty)?,
-        other => {
+    let ffi = match (spec.name.as_str(), spec.version) {
+        ("signature", 1) => signature::generate_v1(self_ty)?,
+        ("kem", 1) => kem::generate_v1(self_ty)?,
+        (name, version) if version != 0 && SUPPORTED.contains(&name) => {
+            return Err(syn::Error::new(
+                proc_macro2::Span::call_site(),
+                format!(
+                    "`{name}` has no wire protocol version {version}; \
+                     `signature` and `kem` support versions 0 and 1, every other \
+                     interface only version 0"
+                ),
+            ));
+        }
+        ("hash", _) => hash::generate_v0(self_ty)?,
+        ("cipher", _) => cipher::generate_v0(self_ty)?,
+        ("aead", _) => aead::generate_v0(self_ty)?,
+        ("kdf", _) => kdf::generate_v0(self_ty)?,
+        ("rng", _) => rng::generate_v0(self_ty)?,
+        ("signature", _) => signature::generate_v0(self_ty)?,
+        ("kem", _) => kem::generate_v0(self_ty)?,
+        ("keyfmt", _) => keyfmt::generate_v0(self_ty)?,
+        (other, _) => {
             return Err(syn::Error::new(
                 proc_macro2::Span::call_site(),
                 format!(
@@ -71,27 +88,43 @@ pub fn plugin_interface_impl(
     // advertises as `symmetric`); the per-interface generator owns that
     // mapping.
     let wire_name = wire_name_for(&spec.name);
-    let version_lit = spec.version;
-    let registration_doc = format!(
-        "Confium plugin interface registered by #[plugin_interface]: \
-         wire name = `{wire_name}`, version = `{version_lit}`."
-    );
+    let registrations = (0..=spec.version).map(|version_lit| {
+        let registration_doc = format!(
+            "Confium plugin interface registered by #[plugin_interface]: \
+             wire name = `{wire_name}`, version = `{version_lit}`."
+        );
+        quote! {
+            #[doc = #registration_doc]
+            ::confium_api::register_interface!(#wire_name, #version_lit);
+        }
+    });
 
     Ok(quote! {
         #original
 
         #ffi
 
-        #[doc = #registration_doc]
-        ::confium_api::register_interface!(#wire_name, #version_lit);
+        #(#registrations)*
     })
 }
 
+/// Attribute names the macro has a generator for.
+pub(crate) const SUPPORTED: &[&str] = &[
+    "hash",
+    "cipher",
+    "aead",
+    "kdf",
+    "rng",
+    "signature",
+    "kem",
+    "keyfmt",
+];
+
 /// Map a macro attribute name to the wire name advertised via
 /// `cfmp_query_interfaces`. Most interfaces advertise under the same
 /// name they use for their symbol prefix, but a few differ (notably
 /// `cipher` → `symmetric`) to match the loader-side registry kind.
-fn wire_name_for(attr_name: &str) -> &'static str {
+pub(crate) fn wire_name_for(attr_name: &str) -> &'static str {
     match attr_name {
         "hash" => "hash",
         "cipher" => "symmetric",
@@ -31,11 +31,16 @@
 //!
 //! See `TODO.roadmap/03-plugin-contract.md` for the wire contract and
 //! `crates/confium-api/src/` for the shared types the macros consume.
+//!
+//! Plugins built as WebAssembly components use the same two attributes
+//! through `confium-wasm-guest`, which re-exports the hidden
+//! `wasm_plugin_interface` / `wasm_export` variants under those names.
 
 mod export;
 mod interface;
 mod metadata;
 mod util;
+mod wasm;
 
 use proc_macro::TokenStream;
 
@@ -116,3 +121,25 @@ pub fn export(attr: TokenStream, item: TokenStream) -> TokenStream {
         .unwrap_or_else(|e| e.to_compile_error())
         .into()
 }
+
+/// `#[plugin_interface]` for WebAssembly component plugins. Use it as
+/// `confium_wasm_guest::plugin_interface`; it registers the type with
+/// the guest SDK instead of emitting `cfmp_*` symbols.
+#[doc(hidden)]
+#[proc_macro_attribute]
+pub fn wasm_plugin_interface(attr: TokenStream, item: TokenStream) -> TokenStream {
+    wasm::wasm_plugin_interface_impl(attr.into(), item.into())
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// `#[export]` for WebAssembly component plugins. Use it as
+/// `confium_wasm_guest::export`; it emits the `confium:plugin/plugin`
+/// world's exports.
+#[doc(hidden)]
+#[proc_macro_attribute]
+pub fn wasm_export(attr: TokenStream, item: TokenStream) -> TokenStream {
+    wasm::wasm_export_impl(attr.into(), item.into())
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
@@ -0,0 +1,99 @@
+//! The WebAssembly flavour of `#[plugin_interface]` and `#[export]`,
+//! re-exported by `confium-wasm-guest` under those names.
+//!
+//! A WASM plugin has no `cfmp_*` symbols: the `confium:plugin`
+//! component bindings live once in `confium-wasm-guest` and dispatch
+//! through type-erased copies of the `confium_api::plugin` traits. So
+//! the interface macro only registers the implementing type with the
+//! SDK, and the export macro only emits the component's exports.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::ItemImpl;
+
+use crate::interface::{SUPPORTED, wire_name_for};
+use crate::util::parse_interface_attr;
+
+/// Entry point invoked by `confium_wasm_guest::plugin_interface`.
+pub fn wasm_plugin_interface_impl(
+    attr: TokenStream,
+    item: TokenStream,
+) -> syn::Result<TokenStream> {
+    let spec = parse_interface_attr(attr)?;
+    let impl_block: ItemImpl = syn::parse2(item)?;
+    let self_ty = &impl_block.self_ty;
+
+    let exports = match (spec.name.as_str(), spec.version) {
+        ("hash", 0) => quote! { Hash(::confium_wasm_guest::HashExport::of::<#self_ty>()) },
+        ("cipher", 0) => quote! { Cipher(::confium_wasm_guest::CipherExport::of::<#self_ty>()) },
+        ("aead", 0) => quote! { Aead(::confium_wasm_guest::AeadExport::of::<#self_ty>()) },
+        ("kdf", 0) => quote! { Kdf(::confium_wasm_guest::KdfExport::of::<#self_ty>()) },
+        ("rng", 0) => quote! { Rng(::confium_wasm_guest::RngExport::of::<#self_ty>()) },
+        ("keyfmt", 0) => quote! { Keyfmt(::confium_wasm_guest::KeyfmtExport::of::<#self_ty>()) },
+        ("signature", 0) => {
+            quote! { Signature(::confium_wasm_guest::SignatureExport::of::<#self_ty>()) }
+        }
+        ("signature", 1) => {
+            quote! { Signature(::confium_wasm_guest::SignatureExport::with_handles::<#self_ty>()) }
+        }
+        ("kem", 0) => quote! { Kem(::confium_wasm_guest::KemExport::of::<#self_ty>()) },
+        ("kem", 1) => quote! { Kem(::confium_wasm_guest::KemExport::with_handles::<#self_ty>()) },
+        (name, version) if SUPPORTED.contains(&name) => {
+            return Err(syn::Error::new(
+                proc_macro2::Span::call_site(),
+                format!(
+                    "`{name}` has no wire protocol version {version}; \
+                     `signature` and `kem` support versions 0 and 1, every other \
+                     interface only version 0"
+                ),
+            ));
+        }
+        (other, _) => {
+            return Err(syn::Error::new(
+                proc_macro2::Span::call_site(),
+                format!(
+                    "unknown interface `name = \"{other}\"`. Supported interfaces are: \
+                     `hash`, `cipher`, `aead`, `kdf`, `rng`, `signature`, `kem`, `keyfmt`."
+                ),
+            ));
+        }
+    };
+    let wire_name = wire_name_for(&spec.name);
+    let version = spec.version;
+
+    Ok(quote! {
+        #impl_block
+
+        ::confium_wasm_guest::inventory::submit! {
+            ::confium_wasm_guest::Registration {
+                wire_name: #wire_name,
+                version: #version,
+                exports: ::confium_wasm_guest::Exports::#exports,
+            }
+        }
+    })
+}
+
+/// Entry point invoked by `confium_wasm_guest::export`.
+pub fn wasm_export_impl(attr: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
+    if !attr.is_empty() {
+        return Err(syn::Error::new_spanned(
+            attr,
+            "the WASM #[export] takes no arguments; interfaces are discovered from \
+             #[plugin_interface]",
+        ));
+    }
+    let item: syn::Item = syn::parse2(item)?;
+    Ok(quote! {
+        #item
+
+        const _: () = {
+            // The generated macro takes the type as a bare identifier.
+            use ::confium_wasm_guest::Component as __ConfiumComponent;
+            ::confium_wasm_guest::bindings::export_plugin!(
+                __ConfiumComponent
+                with_types_in ::confium_wasm_guest::bindings
+            );
+        };
+    })
+}
@@ -12,6 +12,10 @@
 //!   `cfmp_cipher_*` symbol set (create, block/key/iv size, update,
 //!   finalize, reset, destroy) end to end.
 //!
+//! Creating the hash under the name `"abort"` aborts the process. Only
+//! the sandbox tests ask for it, to check that a plugin crash inside a
+//! `sandbox = "process"` host leaves the caller running.
+//!
 //! The crate compiles to a `cdylib` so the loader can `dlopen` it.
 //! The `cfmp_*` symbols are emitted by:
 //!
@@ -20,6 +24,9 @@
 //! - `#[plugin_interface(name = "cipher", version = 0)]` on the
 //!   `impl CipherPlugin for XorCipher` block (eight `cfmp_cipher_*`
 //!   symbols).
+//! - `#[plugin_interface(name = "signature", version = 1)]` on the
+//!   `impl SignaturePlugin for XorSignature` block (the v0
+//!   `cfmp_sig_*` symbols plus the two key-handle symbols).
 //! - `#[export(metadata(...))]` on the plugin marker struct. Emits the
 //!   lifecycle + metadata symbols. The interface list is auto-discovered
 //!   from the `#[plugin_interface]` attributes above; no explicit
@@ -33,6 +40,8 @@ use confium_api::CipherPlugin;
 use confium_api::HashPlugin;
 use confium_api::error::PluginResult;
 use confium_api::options::OptionView;
+use confium_api::plugin::signature::{SignatureHandlePlugin, SignatureKeypair, SignaturePlugin};
+use confium_api::{ErrorCode, HandleCapabilities, KeyHandle, PluginError};
 use confium_macros::{export, plugin_interface};
 
 // =====================================================================
@@ -60,7 +69,12 @@ impl Default for XorHash {
 
 #[plugin_interface(name = "hash", version = 0)]
 impl HashPlugin for XorHash {
-    fn create_with_opts(_name: &str, _opts: Option<OptionView<'_>>) -> PluginResult<Self> {
+    fn create_with_opts(name: &str, _opts: Option<OptionView<'_>>) -> PluginResult<Self> {
+        if name == "abort" {
+            // Stands in for a plugin that crashes mid-call; see the
+            // crate docs.
+            std::process::abort();
+        }
         Ok(Self::new())
     }
 
@@ -179,6 +193,124 @@ impl CipherPlugin for XorCipher {
     }
 }
 
+// =====================================================================
+// Signature interface (v1) — XOR "signature" with key-handle support
+// =====================================================================
+
+/// Algorithm name the mock signature answers to.
+pub const XOR_SIGNATURE: &str = "xor-sig";
+
+/// Store backend name whose handles the mock signer accepts.
+pub const MOCK_KEY_BACKEND: &str = "mock";
+
+/// XOR signer/verifier. The key folds to one byte up front; `acc`
+/// accumulates the message.
+pub struct XorSignature {
+    key: u8,
+    acc: u8,
+}
+
+impl XorSignature {
+    fn with_key(algorithm: &str, key: &[u8]) -> PluginResult<Self> {
+        if algorithm != XOR_SIGNATURE {
+            return Err(PluginError::new(
+                ErrorCode::UNSUPPORTED_ALGORITHM,
+                format!("mock signature does not implement {algorithm}"),
+            ));
+        }
+        Ok(Self {
+            key: key.iter().fold(0, |a, b| a ^ b),
+            acc: 0,
+        })
+    }
+}
+
+#[plugin_interface(name = "signature", version = 1)]
+impl SignaturePlugin for XorSignature {
+    fn signer_create(
+        algorithm: &str,
+        secret_key: &[u8],
+        _opts: Option<OptionView<'_>>,
+    ) -> PluginResult<Self> {
+        Self::with_key(algorithm, secret_key)
+    }
+
+    fn verifier_create(
+        algorithm: &str,
+        public_key: &[u8],
+        _opts: Option<OptionView<'_>>,
+    ) -> PluginResult<Self> {
+        Self::with_key(algorithm, public_key)
+    }
+
+    fn set_hash(&mut self, _hash_name: &str) -> PluginResult<()> {
+        Ok(())
+    }
+
+    fn update(&mut self, data: &[u8]) -> PluginResult<()> {
+        self.acc = data.iter().fold(self.acc, |a, b| a ^ b);
+        Ok(())
+    }
+
+    fn signer_finalize(&mut self, sig_out: &mut [u8]) -> PluginResult<usize> {
+        let Some(out) = sig_out.first_mut() else {
+            return Err(PluginError::new(
+                ErrorCode::INSUFFICIENT_BUFFER,
+                "xor signature needs 1 byte of output buffer",
+            ));
+        };
+        *out = self.key ^ self.acc;
+        Ok(1)
+    }
+
+    fn verifier_finalize(&mut self, signature: &[u8]) -> PluginResult<()> {
+        if signature != [self.key ^ self.acc] {
+            return Err(PluginError::new(
+                ErrorCode::WRONG_TYPE,
+                "xor signature mismatch",
+            ));
+        }
+        Ok(())
+    }
+
+    fn keypair_generate(
+        algorithm: &str,
+        seed: Option<&[u8]>,
+        _opts: Option<OptionView<'_>>,
+    ) -> PluginResult<SignatureKeypair> {
+        Self::with_key(algorithm, &[])?;
+        let key = seed.unwrap_or(b"mock").to_vec();
+        Ok(SignatureKeypair {
+            public_key: key.clone(),
+            secret_key: key,
+        })
+    }
+}
+
+impl SignatureHandlePlugin for XorSignature {
+    fn handle_capabilities(algorithm: &str, handle: &KeyHandle<'_>) -> HandleCapabilities {
+        if algorithm == XOR_SIGNATURE && handle.backend() == MOCK_KEY_BACKEND {
+            HandleCapabilities::SIGN
+        } else {
+            HandleCapabilities::NONE
+        }
+    }
+
+    fn signer_create_with_handle(
+        algorithm: &str,
+        handle: &KeyHandle<'_>,
+        _opts: Option<OptionView<'_>>,
+    ) -> PluginResult<Self> {
+        if handle.backend() != MOCK_KEY_BACKEND {
+            return Err(PluginError::new(
+                ErrorCode::KEY_HANDLE_UNSUPPORTED,
+                format!("mock signature cannot use {} handles", handle.backend()),
+            ));
+        }
+        Self::with_key(algorithm, handle.key_id().as_bytes())
+    }
+}
+
 // `#[export]` emits the four plugin lifecycle symbols plus the optional
 // `cfmp_metadata` symbol (because `metadata(...)` is supplied). The
 // interface list is auto-discovered from the `#[plugin_interface]`
@@ -190,6 +322,6 @@ impl CipherPlugin for XorCipher {
     version = "0.1.0",
     vendor = "confium",
     license = "BSD-2-Clause",
-    description = "XOR-fold mock hash + cipher for SDK loader tests",
+    description = "XOR-fold mock hash, cipher and signature for SDK loader tests",
 ))]
 pub struct Plugin;
@@ -80,7 +80,7 @@ impl Cmp20 {
 /// Outcome of a CMP20 / GG18 DKG.
 #[napi(object)]
 pub struct Cmp20Keygen {
-    /// Per-party share blobs, 71 bytes each. Distribute to N parties.
+    /// Per-party share blobs, 72 bytes each. Distribute to N parties.
     pub shares: Vec<Buffer>,
     /// Joint P-256 public key (SEC1 compressed, 33 bytes).
     #[napi(js_name = "publicKey")]
@@ -1,5 +1,6 @@
 //! Dispatch layer — routes PKCS#11 calls to threshold protocol.
 
+use crate::mechanism::Mechanism;
 use crate::slot::{SlotId, SlotInfo};
 use crate::token::TokenInfo;
 use std::collections::HashMap;
@@ -19,6 +20,9 @@ pub enum Pkcs11Error {
     /// Function not supported.
     #[error("function {0} not supported")]
     UnsupportedFunction(String),
+    /// Mechanism not offered by the slot's quorum.
+    #[error("mechanism {0:?} not supported by this slot")]
+    MechanismInvalid(Mechanism),
     /// PIN incorrect.
     #[error("PIN incorrect")]
     BadPin,
@@ -34,6 +38,34 @@ pub trait QuorumDispatcher: Send + Sync {
 
     /// Trigger a DKG for a new threshold keypair.
     fn generate_keypair(&self, slot: SlotId) -> Result<Vec<u8>, String>;
+
+    /// Mechanisms the quorum at `slot` can serve. Defaults to none, for
+    /// dispatchers that only implement the mechanism-less calls.
+    fn mechanisms(&self, _slot: &SlotId) -> Vec<Mechanism> {
+        Vec::new()
+    }
+
+    /// Sign `data` under `mechanism`. Only called with a sign mechanism
+    /// listed by [`QuorumDispatcher::mechanisms`].
+    fn sign_mechanism(
+        &self,
+        _slot: SlotId,
+        mechanism: Mechanism,
+        _data: &[u8],
+    ) -> Result<Vec<u8>, String> {
+        Err(format!("mechanism {mechanism:?} not implemented"))
+    }
+
+    /// Decrypt `ciphertext` under `mechanism`. Only called with a decrypt
+    /// mechanism listed by [`QuorumDispatcher::mechanisms`].
+    fn decrypt_mechanism(
+        &self,
+        _slot: SlotId,
+        mechanism: Mechanism,
+        _ciphertext: &[u8],
+    ) -> Result<Vec<u8>, String> {
+        Err(format!("mechanism {mechanism:?} not implemented"))
+    }
 }
 
 /// The PKCS#11 dispatch service.
@@ -89,6 +121,55 @@ impl Pkcs11Server {
             .map_err(Pkcs11Error::SignFailed)
     }
 
+    /// `C_GetMechanismList` — mechanisms the slot's quorum serves.
+    pub fn c_get_mechanism_list(&self, slot: SlotId) -> Result<Vec<Mechanism>, Pkcs11Error> {
+        if !self.slots.contains_key(&slot) {
+            return Err(Pkcs11Error::SlotNotPresent(slot));
+        }
+        Ok(self.dispatcher.mechanisms(&slot))
+    }
+
+    /// `C_SignInit` + `C_Sign` — sign data under `mechanism`.
+    pub fn c_sign_mechanism(
+        &self,
+        slot: SlotId,
+        mechanism: Mechanism,
+        data: &[u8],
+    ) -> Result<Vec<u8>, Pkcs11Error> {
+        self.check_mechanism(&slot, mechanism, Mechanism::is_sign)?;
+        self.dispatcher
+            .sign_mechanism(slot, mechanism, data)
+            .map_err(Pkcs11Error::SignFailed)
+    }
+
+    /// `C_DecryptInit` + `C_Decrypt` — decrypt under `mechanism`.
+    pub fn c_decrypt_mechanism(
+        &self,
+        slot: SlotId,
+        mechanism: Mechanism,
+        ciphertext: &[u8],
+    ) -> Result<Vec<u8>, Pkcs11Error> {
+        self.check_mechanism(&slot, mechanism, Mechanism::is_decrypt)?;
+        self.dispatcher
+            .decrypt_mechanism(slot, mechanism, ciphertext)
+            .map_err(Pkcs11Error::DecryptFailed)
+    }
+
+    fn check_mechanism(
+        &self,
+        slot: &SlotId,
+        mechanism: Mechanism,
+        usable: fn(Mechanism) -> bool,
+    ) -> Result<(), Pkcs11Error> {
+        if !self.slots.contains_key(slot) {
+            return Err(Pkcs11Error::SlotNotPresent(slot.clone()));
+        }
+        if !usable(mechanism) || !self.dispatcher.mechanisms(slot).contains(&mechanism) {
+            return Err(Pkcs11Error::MechanismInvalid(mechanism));
+        }
+        Ok(())
+    }
+
     /// Get slot info.
     pub fn slot_info(&self, slot: &SlotId) -> Option<&SlotInfo> {
         self.slots.get(slot)
@@ -151,6 +232,22 @@ mod tests {
         assert_eq!(pk.len(), 32);
     }
 
+    #[test]
+    fn mechanisms_default_to_none() {
+        let mut server = Pkcs11Server::new(Box::new(MockDispatcher));
+        server.register_quorum(
+            SlotId(1),
+            SlotInfo::for_quorum("test-quorum"),
+            TokenInfo::for_quorum(SlotId(1), "test-quorum", 2, 3, "FROST-P256", "local"),
+        );
+        assert!(server.c_get_mechanism_list(SlotId(1)).unwrap().is_empty());
+        let result = server.c_sign_mechanism(SlotId(1), Mechanism::Sha256RsaPkcs, b"data");
+        assert!(matches!(
+            result,
+            Err(Pkcs11Error::MechanismInvalid(Mechanism::Sha256RsaPkcs))
+        ));
+    }
+
     #[test]
     fn unknown_slot_fails() {
         let server = Pkcs11Server::new(Box::new(MockDispatcher));
@@ -9,15 +9,25 @@
 //! the dispatch layer that routes calls to a quorum coordinator; the
 //! real PKCS#11 FFI shim is generated separately.
 //!
+//! With the default `rsa` feature, `RsaQuorumDispatcher` serves the
+//! PKCS#1 v1.5, PSS and OAEP mechanisms from this node's Shoup threshold
+//! RSA share, reaching the rest of the quorum over the network.
+//!
 //! See `TODO.roadmap/28-mode2-pki-replacement.md` for full spec.
 
 #![forbid(unsafe_code)]
 #![allow(missing_docs)] // TODO: document before 1.0
 
 pub mod dispatch;
+pub mod mechanism;
+#[cfg(feature = "rsa")]
+pub mod rsa;
 pub mod slot;
 pub mod token;
 
 pub use dispatch::*;
+pub use mechanism::*;
+#[cfg(feature = "rsa")]
+pub use rsa::RsaQuorumDispatcher;
 pub use slot::*;
 pub use token::*;
@@ -0,0 +1,55 @@
+//! PKCS#11 mechanism model.
+
+use serde::{Deserialize, Serialize};
+
+/// A PKCS#11 mechanism (`CK_MECHANISM_TYPE`) a quorum can serve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Mechanism {
+    /// `CKM_RSA_PKCS` — PKCS#1 v1.5 over a caller-built `DigestInfo`.
+    RsaPkcs,
+    /// `CKM_RSA_PKCS_OAEP` — OAEP decryption (SHA-256, MGF1-SHA-256).
+    RsaPkcsOaep,
+    /// `CKM_RSA_PKCS_PSS` — PSS over a caller-computed SHA-256 digest.
+    RsaPkcsPss,
+    /// `CKM_SHA256_RSA_PKCS` — PKCS#1 v1.5 with SHA-256.
+    Sha256RsaPkcs,
+    /// `CKM_SHA256_RSA_PKCS_PSS` — PSS with SHA-256.
+    Sha256RsaPkcsPss,
+}
+
+impl Mechanism {
+    /// Every mechanism this crate knows, in `CKM_*` order.
+    pub const ALL: [Mechanism; 5] = [
+        Mechanism::RsaPkcs,
+        Mechanism::RsaPkcsOaep,
+        Mechanism::RsaPkcsPss,
+        Mechanism::Sha256RsaPkcs,
+        Mechanism::Sha256RsaPkcsPss,
+    ];
+
+    /// The `CKM_*` constant.
+    pub fn ckm(self) -> u64 {
+        match self {
+            Mechanism::RsaPkcs => 0x0001,
+            Mechanism::RsaPkcsOaep => 0x0009,
+            Mechanism::RsaPkcsPss => 0x000D,
+            Mechanism::Sha256RsaPkcs => 0x0040,
+            Mechanism::Sha256RsaPkcsPss => 0x0043,
+        }
+    }
+
+    /// Look up a `CKM_*` constant.
+    pub fn from_ckm(ckm: u64) -> Option<Self> {
+        Self::ALL.into_iter().find(|m| m.ckm() == ckm)
+    }
+
+    /// True for mechanisms used with `C_Sign`.
+    pub fn is_sign(self) -> bool {
+        !self.is_decrypt()
+    }
+
+    /// True for mechanisms used with `C_Decrypt`.
+    pub fn is_decrypt(self) -> bool {
+        matches!(self, Mechanism::RsaPkcsOaep)
+    }
+}
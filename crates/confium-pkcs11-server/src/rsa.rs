@@ -0,0 +1,339 @@
+//! Threshold RSA quorums behind the PKCS#11 RSA mechanisms.
+//!
+//! [`RsaQuorumDispatcher`] serves `CKM_RSA_PKCS`, `CKM_SHA256_RSA_PKCS`,
+//! `CKM_RSA_PKCS_PSS`, `CKM_SHA256_RSA_PKCS_PSS` and `CKM_RSA_PKCS_OAEP`
+//! from Shoup threshold RSA shares (`confium-tc-rsa`), so PKCS#11
+//! consumers that only speak RSA get ordinary RSA signatures and
+//! plaintexts out of a quorum.
+//!
+//! Each quorum node runs its own dispatcher holding only its own share.
+//! An operation runs the padding's registered `RSA-SHOUP-*` scheme
+//! through a [`NetworkDriver`] against the rest of the slot's signing
+//! committee, every member of which must be serving the same request.
+//! The session tag is derived from the slot, the scheme and the input,
+//! so the nodes serving one request meet in one session.
+//!
+//! Shoup RSA has no DKG: keys come from a [`confium_tc_rsa::dealer`]
+//! ceremony and each node loads its share with
+//! [`RsaQuorumDispatcher::load_share`]. `C_GenerateKeyPair` is refused.
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use confium_tc::network::{DriverConfig, NetworkDriver};
+use confium_tc::party::PartyList;
+use confium_tc::share::Share;
+use confium_tc::{Session, SessionParams};
+use confium_tc_rsa::RsaPadding;
+use sha2::{Digest, Sha256};
+
+use crate::dispatch::QuorumDispatcher;
+use crate::mechanism::Mechanism;
+use crate::slot::SlotId;
+
+/// The RSA padding behind each mechanism.
+pub fn padding_for(mechanism: Mechanism) -> RsaPadding {
+    match mechanism {
+        Mechanism::RsaPkcs => RsaPadding::Pkcs1v15,
+        Mechanism::Sha256RsaPkcs => RsaPadding::Pkcs1v15Sha256,
+        Mechanism::RsaPkcsPss => RsaPadding::Pss,
+        Mechanism::Sha256RsaPkcsPss => RsaPadding::PssSha256,
+        Mechanism::RsaPkcsOaep => RsaPadding::OaepSha256,
+    }
+}
+
+struct RsaQuorum {
+    threshold: u32,
+    /// The signing committee, this node included; every party needs a
+    /// transport endpoint.
+    parties: PartyList,
+    this_party_idx: usize,
+    /// This node's share blob; `None` until loaded.
+    share: Option<Vec<u8>>,
+}
+
+/// [`QuorumDispatcher`] for one node of networked threshold RSA quorums.
+pub struct RsaQuorumDispatcher {
+    config: DriverConfig,
+    quorums: RwLock<HashMap<SlotId, RsaQuorum>>,
+}
+
+impl Default for RsaQuorumDispatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RsaQuorumDispatcher {
+    /// Dispatcher with the default [`DriverConfig`].
+    pub fn new() -> Self {
+        Self {
+            config: DriverConfig::default(),
+            quorums: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Drive sessions with `config`. Its `session_tag` prefixes the
+    /// per-request tag, so deployments sharing endpoints stay apart.
+    pub fn with_config(mut self, config: DriverConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Declare a `threshold` quorum at `slot` whose signing committee is
+    /// `parties`, this node being `parties[this_party_idx]`.
+    pub fn add_quorum(
+        &self,
+        slot: SlotId,
+        threshold: u32,
+        parties: PartyList,
+        this_party_idx: usize,
+    ) {
+        self.quorums.write().expect("quorum map poisoned").insert(
+            slot,
+            RsaQuorum {
+                threshold,
+                parties,
+                this_party_idx,
+                share: None,
+            },
+        );
+    }
+
+    /// Attach this node's share from a dealer ceremony to `slot`.
+    pub fn load_share(&self, slot: SlotId, share: Vec<u8>) -> Result<(), String> {
+        let mut quorums = self.quorums.write().expect("quorum map poisoned");
+        let quorum = quorums
+            .get_mut(&slot)
+            .ok_or_else(|| format!("no RSA quorum at slot {slot:?}"))?;
+        quorum.share = Some(share);
+        Ok(())
+    }
+
+    fn run(&self, slot: &SlotId, padding: RsaPadding, input: &[u8]) -> Result<Vec<u8>, String> {
+        let scheme = padding.scheme_name();
+        let (params, parties) = {
+            let quorums = self.quorums.read().expect("quorum map poisoned");
+            let quorum = quorums
+                .get(slot)
+                .ok_or_else(|| format!("no RSA quorum at slot {slot:?}"))?;
+            let share = quorum
+                .share
+                .clone()
+                .ok_or_else(|| format!("no RSA key at slot {slot:?}"))?;
+            let params = SessionParams {
+                scheme: scheme.to_string(),
+                parties: quorum.parties.clone(),
+                threshold: quorum.threshold,
+                this_party_idx: quorum.this_party_idx,
+                local_share: Some(Share::new(scheme, share)),
+                message: Some(input.to_vec()),
+            };
+            (params, quorum.parties.clone())
+        };
+        let session = Session::create(&params).map_err(|e| e.to_string())?;
+        let config = DriverConfig {
+            session_tag: self.session_tag(slot, scheme, input),
+            ..self.config.clone()
+        };
+        NetworkDriver::new(session, &parties)
+            .map_err(|e| e.to_string())?
+            .with_config(config)
+            .run()
+            .map_err(|e| e.to_string())
+    }
+
+    /// Tag shared by every node serving the same request.
+    fn session_tag(&self, slot: &SlotId, scheme: &str, input: &[u8]) -> String {
+        let digest = Sha256::new()
+            .chain_update(slot.0.to_be_bytes())
+            .chain_update(scheme.as_bytes())
+            .chain_update(input)
+            .finalize();
+        let hex: String = digest.iter().map(|b| format!("{b:02x}")).collect();
+        format!("{}/{scheme}/{hex}", self.config.session_tag)
+    }
+}
+
+impl QuorumDispatcher for RsaQuorumDispatcher {
+    /// PKCS#1 v1.5 with SHA-256, as `CKM_SHA256_RSA_PKCS`.
+    fn sign(&self, slot: SlotId, data: &[u8]) -> Result<Vec<u8>, String> {
+        self.run(&slot, RsaPadding::Pkcs1v15Sha256, data)
+    }
+
+    /// OAEP with SHA-256, as `CKM_RSA_PKCS_OAEP`.
+    fn decrypt(&self, slot: SlotId, ciphertext: &[u8]) -> Result<Vec<u8>, String> {
+        self.run(&slot, RsaPadding::OaepSha256, ciphertext)
+    }
+
+    /// Refused: Shoup keys are dealt by a trusted dealer, never
+    /// generated by one node. See [`RsaQuorumDispatcher::load_share`].
+    fn generate_keypair(&self, slot: SlotId) -> Result<Vec<u8>, String> {
+        Err(format!(
+            "threshold RSA keys come from a dealer ceremony; load this node's share at slot {slot:?}"
+        ))
+    }
+
+    fn mechanisms(&self, slot: &SlotId) -> Vec<Mechanism> {
+        let quorums = self.quorums.read().expect("quorum map poisoned");
+        if quorums.contains_key(slot) {
+            Mechanism::ALL.to_vec()
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn sign_mechanism(
+        &self,
+        slot: SlotId,
+        mechanism: Mechanism,
+        data: &[u8],
+    ) -> Result<Vec<u8>, String> {
+        self.run(&slot, padding_for(mechanism), data)
+    }
+
+    fn decrypt_mechanism(
+        &self,
+        slot: SlotId,
+        mechanism: Mechanism,
+        ciphertext: &[u8],
+    ) -> Result<Vec<u8>, String> {
+        self.run(&slot, padding_for(mechanism), ciphertext)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::thread;
+    use std::time::Duration;
+
+    use crate::dispatch::{Pkcs11Error, Pkcs11Server};
+    use crate::slot::SlotInfo;
+    use crate::token::TokenInfo;
+    use confium_tc::party::Party;
+    use confium_tc_rsa::RsaKeyShare;
+    use confium_tc_rsa::dealer::deal_with_primes;
+    use num_bigint::BigUint;
+
+    /// 512-bit safe primes; their product is a 1024-bit modulus.
+    const P: &str = "f7270cfc837b8879c958fa689b6637c48e0a302d8ca83075bbeba1d960ef40dc7f68ad0cccc0cece0c9a1a3d4b52bcd5f7880428d6622b382906b72107ac2a57";
+    const Q: &str = "e92859643f40adf1d51d9ac3836e1fa9d002130937b8d0cdeb8c8bebd4a9d83c7db9641405c7b19bec3663eecab4559c4553baf60775491f4f747605a27867bb";
+
+    fn shares() -> Vec<RsaKeyShare> {
+        let p = BigUint::parse_bytes(P.as_bytes(), 16).unwrap();
+        let q = BigUint::parse_bytes(Q.as_bytes(), 16).unwrap();
+        deal_with_primes(&p, &q, 2, 3).unwrap()
+    }
+
+    fn config(round_timeout: Duration) -> DriverConfig {
+        DriverConfig {
+            session_tag: "pkcs11-rsa-test".into(),
+            round_timeout,
+            resend_interval: Duration::from_millis(200),
+            reconnect_interval: Duration::from_millis(20),
+            linger: Duration::from_secs(1),
+            ..DriverConfig::default()
+        }
+    }
+
+    /// One server per committee member, each holding only its own share.
+    fn nodes(tag: &str, shares: &[RsaKeyShare], round_timeout: Duration) -> Vec<Pkcs11Server> {
+        let parties = PartyList::from_parties(
+            (0..shares.len())
+                .map(|i| Party::new(format!("node{i}"), Some(format!("inproc://{tag}-{i}"))))
+                .collect(),
+        );
+        shares
+            .iter()
+            .enumerate()
+            .map(|(idx, share)| {
+                let dispatcher = RsaQuorumDispatcher::new().with_config(config(round_timeout));
+                dispatcher.add_quorum(SlotId(7), 2, parties.clone(), idx);
+                dispatcher.load_share(SlotId(7), share.to_bytes()).unwrap();
+                let mut server = Pkcs11Server::new(Box::new(dispatcher));
+                server.register_quorum(
+                    SlotId(7),
+                    SlotInfo::for_quorum("rsa-quorum"),
+                    TokenInfo::for_quorum(SlotId(7), "rsa-quorum", 2, 3, "RSA-SHOUP", "network"),
+                );
+                server
+            })
+            .collect()
+    }
+
+    /// Serve the same request on every node concurrently; all must agree.
+    fn on_every_node<F>(servers: &[Pkcs11Server], call: F) -> Vec<u8>
+    where
+        F: Fn(&Pkcs11Server) -> Result<Vec<u8>, Pkcs11Error> + Sync,
+    {
+        let results: Vec<Vec<u8>> = thread::scope(|scope| {
+            let handles: Vec<_> = servers
+                .iter()
+                .map(|server| scope.spawn(|| call(server)))
+                .collect();
+            handles
+                .into_iter()
+                .map(|h| h.join().expect("node thread").expect("node completes"))
+                .collect()
+        });
+        assert!(results.iter().all(|r| *r == results[0]), "nodes disagree");
+        results.into_iter().next().unwrap()
+    }
+
+    #[test]
+    fn rsa_mechanisms_produce_standard_artifacts() {
+        let shares = shares();
+        let key = shares[0].public_key.clone();
+        let committee = [shares[0].clone(), shares[2].clone()];
+        let servers = nodes("artifacts", &committee, Duration::from_secs(10));
+        assert_eq!(
+            servers[0].c_get_mechanism_list(SlotId(7)).unwrap(),
+            Mechanism::ALL
+        );
+
+        let sig = on_every_node(&servers, |s| {
+            s.c_sign_mechanism(SlotId(7), Mechanism::Sha256RsaPkcs, b"hello")
+        });
+        assert!(key.verify_pkcs1v15_sha256(b"hello", &sig));
+        assert_eq!(
+            on_every_node(&servers, |s| s.c_sign(SlotId(7), b"hello")),
+            sig
+        );
+
+        let sig = on_every_node(&servers, |s| {
+            s.c_sign_mechanism(SlotId(7), Mechanism::Sha256RsaPkcsPss, b"hello")
+        });
+        assert!(key.verify_pss_sha256(b"hello", &sig));
+
+        let ct = key.encrypt_oaep_sha256(b"attack at dawn").unwrap();
+        let pt = on_every_node(&servers, |s| {
+            s.c_decrypt_mechanism(SlotId(7), Mechanism::RsaPkcsOaep, &ct)
+        });
+        assert_eq!(pt, b"attack at dawn");
+    }
+
+    #[test]
+    fn one_node_cannot_sign_alone() {
+        let shares = shares();
+        let servers = nodes("alone", &shares[..2], Duration::from_millis(500));
+        let result = servers[0].c_sign(SlotId(7), b"hello");
+        assert!(matches!(result, Err(Pkcs11Error::SignFailed(_))));
+    }
+
+    #[test]
+    fn keypair_generation_is_refused() {
+        let servers = nodes("keygen", &shares()[..1], Duration::from_millis(500));
+        assert!(servers[0].c_generate_keypair(SlotId(7)).is_err());
+    }
+
+    #[test]
+    fn mechanism_must_match_the_operation() {
+        let servers = nodes("mismatch", &shares()[..1], Duration::from_millis(500));
+        let result = servers[0].c_decrypt_mechanism(SlotId(7), Mechanism::Sha256RsaPkcs, b"ct");
+        assert!(matches!(result, Err(Pkcs11Error::MechanismInvalid(_))));
+        let result = servers[0].c_sign_mechanism(SlotId(7), Mechanism::RsaPkcsOaep, b"data");
+        assert!(matches!(result, Err(Pkcs11Error::MechanismInvalid(_))));
+    }
+}
@@ -0,0 +1,191 @@
+//! Exact discrete Laplace and discrete Gaussian samplers.
+//!
+//! [`laplace_noise`](crate::privacy_and_dist_patterns::laplace_noise)
+//! and friends sample by inverting a CDF in `f64`. Mironov (CCS 2012)
+//! showed that the set of doubles such a sampler can output depends
+//! on the true value, so an attacker can sometimes read the unnoised
+//! answer straight off the low-order bits. These samplers follow
+//! Canonne, Kamath & Steinke, "The Discrete Gaussian for Differential
+//! Privacy" (NeurIPS 2020): noise is an integer, every step is a
+//! Bernoulli trial on an exact rational, and no floating-point value
+//! ever touches the output.
+//!
+//! Parameters are rationals (`numerator / denominator`); callers with
+//! an `f64` budget convert it conservatively (rounding toward *more*
+//! noise) with [`rational_at_least`] / [`rational_at_most`].
+
+use num_bigint::BigUint;
+use num_traits::{One, ToPrimitive, Zero};
+use rand_core::{OsRng, RngCore};
+
+/// Denominator used when converting `f64` parameters to rationals.
+pub const RATIONAL_DENOMINATOR: u64 = 1 << 20;
+
+/// `(n, RATIONAL_DENOMINATOR)` with `n / d >= x`.
+pub fn rational_at_least(x: f64) -> (u64, u64) {
+    let n = (x * RATIONAL_DENOMINATOR as f64).ceil();
+    (n.max(1.0) as u64, RATIONAL_DENOMINATOR)
+}
+
+/// `(n, RATIONAL_DENOMINATOR)` with `n / d <= x`.
+pub fn rational_at_most(x: f64) -> (u64, u64) {
+    let n = (x * RATIONAL_DENOMINATOR as f64).floor();
+    (n.max(0.0) as u64, RATIONAL_DENOMINATOR)
+}
+
+/// Uniform integer in `[0, bound)` by rejection sampling over
+/// `bound.bits()` random bits. `bound` must be non-zero.
+fn uniform_below(bound: &BigUint) -> BigUint {
+    debug_assert!(!bound.is_zero());
+    let bits = bound.bits();
+    let len = bits.div_ceil(8) as usize;
+    let top_mask = match bits % 8 {
+        0 => 0xff,
+        r => (1u8 << r) - 1,
+    };
+    let mut buf = vec![0u8; len];
+    loop {
+        OsRng.fill_bytes(&mut buf);
+        buf[0] &= top_mask;
+        let x = BigUint::from_bytes_be(&buf);
+        if &x < bound {
+            return x;
+        }
+    }
+}
+
+/// `Bernoulli(num / den)`, `num <= den`.
+fn bernoulli(num: &BigUint, den: &BigUint) -> bool {
+    &uniform_below(den) < num
+}
+
+/// `Bernoulli(exp(-num/den))` for `num/den` in `[0, 1]` (CKS Alg. 1,
+/// first branch).
+fn bernoulli_exp_le1(num: &BigUint, den: &BigUint) -> bool {
+    let mut k = BigUint::one();
+    loop {
+        if bernoulli(num, &(den * &k)) {
+            k += 1u32;
+        } else {
+            return k.bit(0);
+        }
+    }
+}
+
+/// `Bernoulli(exp(-num/den))` for any non-negative rational.
+pub fn bernoulli_exp(num: &BigUint, den: &BigUint) -> bool {
+    let one = BigUint::one();
+    let mut whole = num / den;
+    while !whole.is_zero() {
+        if !bernoulli_exp_le1(&one, &one) {
+            return false;
+        }
+        whole -= 1u32;
+    }
+    bernoulli_exp_le1(&(num % den), den)
+}
+
+/// Discrete Laplace with scale `t / s`: `P[X = x] ∝ exp(-|x|·s/t)`
+/// (CKS Alg. 2). Adding it to an integer query of sensitivity `Δ`
+/// gives `ε = Δ·s/t`.
+pub fn discrete_laplace(s: u64, t: u64) -> i64 {
+    assert!(s > 0 && t > 0, "discrete Laplace parameters must be positive");
+    let t_big = BigUint::from(t);
+    let one = BigUint::one();
+    loop {
+        let u = uniform_below(&t_big);
+        if !bernoulli_exp(&u, &t_big) {
+            continue;
+        }
+        let mut v: u128 = 0;
+        while bernoulli_exp(&one, &one) {
+            v += 1;
+        }
+        let x = u.to_u128().expect("u < t fits") + t as u128 * v;
+        let y = x / s as u128;
+        let negative = bernoulli(&one, &BigUint::from(2u32));
+        if negative && y == 0 {
+            continue;
+        }
+        let y = i64::try_from(y).expect("discrete Laplace sample exceeds i64");
+        return if negative { -y } else { y };
+    }
+}
+
+/// Discrete Gaussian with variance parameter `σ² = num / den`:
+/// `P[X = x] ∝ exp(-x² / 2σ²)` (CKS Alg. 3).
+pub fn discrete_gaussian(sigma2_num: u64, sigma2_den: u64) -> i64 {
+    assert!(
+        sigma2_num > 0 && sigma2_den > 0,
+        "discrete Gaussian variance must be positive"
+    );
+    // t = ⌊σ⌋ + 1; any positive t is correct, this one is efficient.
+    let t = (sigma2_num / sigma2_den).isqrt() + 1;
+    let sn = BigUint::from(sigma2_num);
+    let sd = BigUint::from(sigma2_den);
+    let tb = BigUint::from(t);
+    // γ = (|y| - σ²/t)² / 2σ² = (|y|·sd·t - sn)² / (2·sn·sd·t²)
+    let gamma_den = BigUint::from(2u32) * &sn * &sd * &tb * &tb;
+    loop {
+        let y = discrete_laplace(1, t);
+        let a = BigUint::from(y.unsigned_abs()) * &sd * &tb;
+        let diff = if a >= sn { &a - &sn } else { &sn - &a };
+        let gamma_num = &diff * &diff;
+        if bernoulli_exp(&gamma_num, &gamma_den) {
+            return y;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mean_and_variance(samples: &[i64]) -> (f64, f64) {
+        let n = samples.len() as f64;
+        let mean = samples.iter().sum::<i64>() as f64 / n;
+        let var = samples
+            .iter()
+            .map(|&x| (x as f64 - mean).powi(2))
+            .sum::<f64>()
+            / n;
+        (mean, var)
+    }
+
+    #[test]
+    fn bernoulli_exp_matches_probability() {
+        let trials = 20_000;
+        let hits = (0..trials)
+            .filter(|_| bernoulli_exp(&BigUint::from(3u32), &BigUint::from(2u32)))
+            .count();
+        let p = hits as f64 / trials as f64;
+        // exp(-1.5) ≈ 0.2231
+        assert!((p - 0.2231).abs() < 0.02, "p = {p}");
+    }
+
+    #[test]
+    fn discrete_laplace_is_centred_with_expected_variance() {
+        // Scale b = t/s = 4; variance of discrete Laplace with
+        // parameter e^{-1/b} is 2e^{-1/b}/(1-e^{-1/b})² ≈ 31.5.
+        let samples: Vec<i64> = (0..20_000).map(|_| discrete_laplace(1, 4)).collect();
+        let (mean, var) = mean_and_variance(&samples);
+        assert!(mean.abs() < 0.3, "mean = {mean}");
+        assert!((var - 31.5).abs() < 4.0, "var = {var}");
+    }
+
+    #[test]
+    fn discrete_gaussian_has_requested_variance() {
+        let samples: Vec<i64> = (0..20_000).map(|_| discrete_gaussian(25, 1)).collect();
+        let (mean, var) = mean_and_variance(&samples);
+        assert!(mean.abs() < 0.2, "mean = {mean}");
+        assert!((var - 25.0).abs() < 2.5, "var = {var}");
+    }
+
+    #[test]
+    fn rational_conversion_rounds_conservatively() {
+        let (n, d) = rational_at_least(0.1);
+        assert!(n as f64 / d as f64 >= 0.1);
+        let (n, d) = rational_at_most(0.1);
+        assert!(n as f64 / d as f64 <= 0.1);
+    }
+}
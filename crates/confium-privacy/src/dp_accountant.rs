@@ -0,0 +1,559 @@
+//! Persistent differential-privacy budget accountant.
+//!
+//! [`PrivacyAccountant`] tracks every noisy release per
+//! `(dataset, analyst)` pair, composes the spend under the pair's
+//! [`Budget`], and refuses any query whose charge would take it over.
+//!
+//! # Composition
+//!
+//! - [`Composition::Basic`]: `Σε`, `Σδ`. Always valid, loose for many
+//!   small queries.
+//! - [`Composition::Advanced`]: the heterogeneous advanced composition
+//!   theorem (Dwork–Rothblum–Vadhan),
+//!   `sqrt(2·ln(1/δ')·Σεᵢ²) + Σεᵢ(e^εᵢ − 1)` at `δ = Σδᵢ + δ'`.
+//! - [`Composition::Renyi`]: Rényi DP (Mironov 2017). Each mechanism
+//!   contributes an RDP bound on a fixed grid of orders (the discrete
+//!   Gaussian its zCDP curve, the discrete Laplace the generic
+//!   `min(αε²/2, ε)` bound for pure `ε`-DP); the total is converted to
+//!   `(ε, δ_budget)` via `min_α RDP(α) + ln(1/δ)/(α − 1)`. Needs a
+//!   non-zero budget `δ`.
+//!
+//! Every bound the chosen composition admits is a valid `(ε, δ)`
+//! guarantee, and basic composition is always one of them, so a query
+//! is admitted if *any* of them fits the budget.
+//!
+//! # Noise
+//!
+//! Releases use the exact integer samplers in
+//! [`discrete_noise`](crate::discrete_noise), never `f64` sampling.
+//! Requested `ε` is rounded *down* to a rational before sampling and
+//! the ledger records the rounded value, so the recorded spend is
+//! exactly what the noise provides.
+//!
+//! The discrete Gaussian with variance `σ²` is `ρ`-zCDP with
+//! `ρ = Δ²/2σ²` (Canonne–Kamath–Steinke), and `ρ`-zCDP implies
+//! `(ρ + 2·sqrt(ρ·ln(1/δ)), δ)`-DP. [`PrivacyAccountant::gaussian_count`]
+//! calibrates `σ` from that conversion, so it is valid for any `ε`
+//! (the classical `sqrt(2 ln(1.25/δ))/ε` calibration needs `ε < 1`).
+//!
+//! # Persistence
+//!
+//! Each pair has one JSON ledger under the accountant's directory,
+//! named by `hex(SHA-256(dataset ‖ 0x00 ‖ analyst))`. Updates are
+//! written to a temporary file, fsynced, renamed over the ledger and
+//! the directory fsynced, so a crash leaves either the old or the new
+//! ledger. The charge is durable **before** the noisy value is
+//! returned: a crash in between over-counts the spend, never under.
+//!
+//! One accountant serialises charges within a process. Running two
+//! processes against the same directory is not supported; put the
+//! accountant behind a single service instead.
+
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::discrete_noise::{
+    discrete_gaussian, discrete_laplace, rational_at_least, rational_at_most,
+};
+
+/// Rényi orders the accountant tracks.
+const RDP_ORDERS: &[f64] = &[
+    1.25, 1.5, 1.75, 2.0, 2.5, 3.0, 4.0, 5.0, 6.0, 8.0, 10.0, 12.0, 16.0, 20.0, 24.0, 32.0, 48.0,
+    64.0, 128.0, 256.0,
+];
+
+#[derive(Debug, thiserror::Error)]
+pub enum AccountantError {
+    #[error("no privacy budget set for dataset {dataset:?}, analyst {analyst:?}")]
+    NoBudget { dataset: String, analyst: String },
+    #[error(
+        "query refused: spend would reach ε={epsilon}, δ={delta} against budget ε={budget_epsilon}, δ={budget_delta}"
+    )]
+    BudgetExceeded {
+        epsilon: f64,
+        delta: f64,
+        budget_epsilon: f64,
+        budget_delta: f64,
+    },
+    #[error("invalid parameter: {0}")]
+    InvalidParameter(&'static str),
+    #[error("ledger I/O: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("corrupt ledger {}: {source}", path.display())]
+    Corrupt {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+}
+
+pub type Result<T> = std::result::Result<T, AccountantError>;
+
+/// How charges against one budget are composed.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum Composition {
+    Basic,
+    /// `delta_slack` is the `δ'` of the advanced composition theorem;
+    /// it is added to the spent `δ`.
+    Advanced {
+        delta_slack: f64,
+    },
+    Renyi,
+}
+
+/// Total privacy budget for one `(dataset, analyst)` pair.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Budget {
+    pub epsilon: f64,
+    pub delta: f64,
+    pub composition: Composition,
+}
+
+/// Privacy cost of one release, as recorded in the ledger.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "mechanism")]
+pub enum Mechanism {
+    /// Pure `ε`-DP (discrete Laplace).
+    Laplace { epsilon: f64 },
+    /// Discrete Gaussian: `ρ`-zCDP, also accounted as `(ε, δ)`-DP.
+    Gaussian { epsilon: f64, delta: f64, rho: f64 },
+}
+
+impl Mechanism {
+    fn epsilon(&self) -> f64 {
+        match *self {
+            Mechanism::Laplace { epsilon } | Mechanism::Gaussian { epsilon, .. } => epsilon,
+        }
+    }
+
+    fn delta(&self) -> f64 {
+        match *self {
+            Mechanism::Laplace { .. } => 0.0,
+            Mechanism::Gaussian { delta, .. } => delta,
+        }
+    }
+
+    /// RDP of order `alpha`.
+    fn rdp(&self, alpha: f64) -> f64 {
+        match *self {
+            // Mironov's closed form (2017, Prop. 6) is for the continuous
+            // Laplace and does not carry over to the discrete one. Use
+            // what holds for every ε-DP mechanism: ε-DP implies
+            // (ε²/2)-zCDP (Bun–Steinke 2016, Prop. 3.3), i.e. RDP
+            // αε²/2 at every order, and ε-DP implies RDP ε (Mironov
+            // 2017, Prop. 3).
+            Mechanism::Laplace { epsilon } => (alpha * epsilon * epsilon / 2.0).min(epsilon),
+            Mechanism::Gaussian { rho, .. } => alpha * rho,
+        }
+    }
+}
+
+/// One ledger entry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Charge {
+    #[serde(flatten)]
+    pub mechanism: Mechanism,
+    pub at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Composed spend under a budget's composition rule.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Spent {
+    pub epsilon: f64,
+    pub delta: f64,
+}
+
+impl Spent {
+    fn fits(&self, budget: &Budget) -> bool {
+        self.epsilon <= budget.epsilon && self.delta <= budget.delta
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Ledger {
+    dataset: String,
+    analyst: String,
+    budget: Option<Budget>,
+    charges: Vec<Charge>,
+}
+
+/// Every `(ε, δ)` bound `budget.composition` yields for `charges`.
+fn bounds(budget: &Budget, charges: &[Charge]) -> Vec<Spent> {
+    let basic = Spent {
+        epsilon: charges.iter().map(|c| c.mechanism.epsilon()).sum(),
+        delta: charges.iter().map(|c| c.mechanism.delta()).sum(),
+    };
+    let mut out = vec![basic];
+    match budget.composition {
+        Composition::Basic => {}
+        Composition::Advanced { delta_slack } => {
+            if delta_slack > 0.0 && !charges.is_empty() {
+                let sum_sq: f64 = charges.iter().map(|c| c.mechanism.epsilon().powi(2)).sum();
+                let linear: f64 = charges
+                    .iter()
+                    .map(|c| {
+                        let e = c.mechanism.epsilon();
+                        e * e.exp_m1()
+                    })
+                    .sum();
+                out.push(Spent {
+                    epsilon: (2.0 * (1.0 / delta_slack).ln() * sum_sq).sqrt() + linear,
+                    delta: basic.delta + delta_slack,
+                });
+            }
+        }
+        Composition::Renyi => {
+            if budget.delta > 0.0 && !charges.is_empty() {
+                let log_inv_delta = (1.0 / budget.delta).ln();
+                let epsilon = RDP_ORDERS
+                    .iter()
+                    .map(|&alpha| {
+                        let rdp: f64 = charges.iter().map(|c| c.mechanism.rdp(alpha)).sum();
+                        rdp + log_inv_delta / (alpha - 1.0)
+                    })
+                    .fold(f64::INFINITY, f64::min);
+                out.push(Spent {
+                    epsilon,
+                    delta: budget.delta,
+                });
+            }
+        }
+    }
+    out
+}
+
+/// The tightest bound, preferring ones that fit the budget.
+fn best_bound(budget: &Budget, charges: &[Charge]) -> Spent {
+    let all = bounds(budget, charges);
+    let by_epsilon = |a: &&Spent, b: &&Spent| a.epsilon.total_cmp(&b.epsilon);
+    all.iter()
+        .filter(|s| s.fits(budget))
+        .min_by(by_epsilon)
+        .or_else(|| all.iter().min_by(by_epsilon))
+        .copied()
+        .expect("basic bound is always present")
+}
+
+pub struct PrivacyAccountant {
+    dir: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl PrivacyAccountant {
+    /// Open (creating if needed) a ledger directory.
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+        Ok(Self {
+            dir,
+            lock: Mutex::new(()),
+        })
+    }
+
+    /// Set or replace the budget for a pair. Earlier charges still
+    /// count against the new budget.
+    pub fn set_budget(&self, dataset: &str, analyst: &str, budget: Budget) -> Result<()> {
+        if !(budget.epsilon >= 0.0 && (0.0..1.0).contains(&budget.delta)) {
+            return Err(AccountantError::InvalidParameter(
+                "budget needs ε >= 0 and 0 <= δ < 1",
+            ));
+        }
+        if let Composition::Advanced { delta_slack } = budget.composition {
+            if !(delta_slack > 0.0 && delta_slack < 1.0) {
+                return Err(AccountantError::InvalidParameter(
+                    "advanced composition needs 0 < δ' < 1",
+                ));
+            }
+        }
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let mut ledger = self.load(dataset, analyst)?;
+        ledger.budget = Some(budget);
+        self.store(&ledger)
+    }
+
+    pub fn budget(&self, dataset: &str, analyst: &str) -> Result<Option<Budget>> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(self.load(dataset, analyst)?.budget)
+    }
+
+    pub fn charges(&self, dataset: &str, analyst: &str) -> Result<Vec<Charge>> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(self.load(dataset, analyst)?.charges)
+    }
+
+    /// Spend so far, composed under the pair's budget.
+    pub fn spent(&self, dataset: &str, analyst: &str) -> Result<Spent> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let ledger = self.load(dataset, analyst)?;
+        let budget = Self::require_budget(&ledger)?;
+        Ok(best_bound(&budget, &ledger.charges))
+    }
+
+    /// Durably record `mechanism` against the pair, or refuse it if the
+    /// budget would be exceeded. Returns the new composed spend.
+    pub fn charge(&self, dataset: &str, analyst: &str, mechanism: Mechanism) -> Result<Spent> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let mut ledger = self.load(dataset, analyst)?;
+        let budget = Self::require_budget(&ledger)?;
+        ledger.charges.push(Charge {
+            mechanism,
+            at: chrono::Utc::now(),
+        });
+        let spent = best_bound(&budget, &ledger.charges);
+        if !spent.fits(&budget) {
+            return Err(AccountantError::BudgetExceeded {
+                epsilon: spent.epsilon,
+                delta: spent.delta,
+                budget_epsilon: budget.epsilon,
+                budget_delta: budget.delta,
+            });
+        }
+        self.store(&ledger)?;
+        Ok(spent)
+    }
+
+    /// Release `true_value + DiscreteLaplace(Δ/ε)` for an integer query
+    /// with L1 sensitivity `sensitivity`.
+    pub fn laplace_count(
+        &self,
+        dataset: &str,
+        analyst: &str,
+        true_value: i64,
+        sensitivity: u64,
+        epsilon: f64,
+    ) -> Result<i64> {
+        if sensitivity == 0 {
+            return Err(AccountantError::InvalidParameter(
+                "sensitivity must be positive",
+            ));
+        }
+        let (s, d) = rational_at_most(epsilon);
+        if s == 0 {
+            return Err(AccountantError::InvalidParameter("ε too small"));
+        }
+        let t = sensitivity
+            .checked_mul(d)
+            .ok_or(AccountantError::InvalidParameter("sensitivity too large"))?;
+        // Scale t/s = Δ·d/s, so the mechanism is exactly (s/d)-DP.
+        let mechanism = Mechanism::Laplace {
+            epsilon: s as f64 / d as f64,
+        };
+        self.charge(dataset, analyst, mechanism)?;
+        Ok(true_value.saturating_add(discrete_laplace(s, t)))
+    }
+
+    /// Release `true_value + DiscreteGaussian(σ²)` for an integer query
+    /// with L2 sensitivity `sensitivity`, calibrated to `(ε, δ)`.
+    pub fn gaussian_count(
+        &self,
+        dataset: &str,
+        analyst: &str,
+        true_value: i64,
+        sensitivity: u64,
+        epsilon: f64,
+        delta: f64,
+    ) -> Result<i64> {
+        if sensitivity == 0 {
+            return Err(AccountantError::InvalidParameter(
+                "sensitivity must be positive",
+            ));
+        }
+        if !(epsilon > 0.0 && delta > 0.0 && delta < 1.0) {
+            return Err(AccountantError::InvalidParameter(
+                "Gaussian needs ε > 0 and 0 < δ < 1",
+            ));
+        }
+        // Largest ρ with ρ + 2·sqrt(ρ·L) <= ε, L = ln(1/δ).
+        let l = (1.0 / delta).ln();
+        let rho = ((l + epsilon).sqrt() - l.sqrt()).powi(2);
+        let delta2 = (sensitivity as f64).powi(2);
+        let (sn, sd) = rational_at_least(delta2 / (2.0 * rho));
+        // Account for the σ² actually used, which is never smaller.
+        let rho = delta2 * sd as f64 / (2.0 * sn as f64);
+        let mechanism = Mechanism::Gaussian {
+            epsilon: rho + 2.0 * (rho * l).sqrt(),
+            delta,
+            rho,
+        };
+        self.charge(dataset, analyst, mechanism)?;
+        Ok(true_value.saturating_add(discrete_gaussian(sn, sd)))
+    }
+
+    fn require_budget(ledger: &Ledger) -> Result<Budget> {
+        ledger.budget.ok_or_else(|| AccountantError::NoBudget {
+            dataset: ledger.dataset.clone(),
+            analyst: ledger.analyst.clone(),
+        })
+    }
+
+    fn ledger_path(&self, dataset: &str, analyst: &str) -> PathBuf {
+        let mut h = Sha256::new();
+        h.update(dataset.as_bytes());
+        h.update([0u8]);
+        h.update(analyst.as_bytes());
+        self.dir.join(format!("{}.json", hex::encode(h.finalize())))
+    }
+
+    fn load(&self, dataset: &str, analyst: &str) -> Result<Ledger> {
+        let path = self.ledger_path(dataset, analyst);
+        match fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes)
+                .map_err(|source| AccountantError::Corrupt { path, source }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Ledger {
+                dataset: dataset.to_string(),
+                analyst: analyst.to_string(),
+                budget: None,
+                charges: Vec::new(),
+            }),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn store(&self, ledger: &Ledger) -> Result<()> {
+        let path = self.ledger_path(&ledger.dataset, &ledger.analyst);
+        let tmp = path.with_extension("json.tmp");
+        let bytes = serde_json::to_vec_pretty(ledger).expect("ledger serialises");
+        {
+            let mut file = File::create(&tmp)?;
+            file.write_all(&bytes)?;
+            file.sync_all()?;
+        }
+        fs::rename(&tmp, &path)?;
+        // Make the rename itself durable. Directories can't be opened
+        // for sync on every platform; the rename is still atomic there.
+        if let Ok(dir) = File::open(&self.dir) {
+            let _ = dir.sync_all();
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn budget(epsilon: f64, delta: f64, composition: Composition) -> Budget {
+        Budget {
+            epsilon,
+            delta,
+            composition,
+        }
+    }
+
+    #[test]
+    fn refuses_query_without_budget() {
+        let dir = tempfile::tempdir().unwrap();
+        let acct = PrivacyAccountant::open(dir.path()).unwrap();
+        let err = acct
+            .laplace_count("census", "alice", 10, 1, 0.5)
+            .unwrap_err();
+        assert!(matches!(err, AccountantError::NoBudget { .. }));
+    }
+
+    #[test]
+    fn refuses_query_over_budget() {
+        let dir = tempfile::tempdir().unwrap();
+        let acct = PrivacyAccountant::open(dir.path()).unwrap();
+        acct.set_budget("census", "alice", budget(1.0, 0.0, Composition::Basic))
+            .unwrap();
+        acct.laplace_count("census", "alice", 10, 1, 0.5).unwrap();
+        acct.laplace_count("census", "alice", 10, 1, 0.5).unwrap();
+        let err = acct
+            .laplace_count("census", "alice", 10, 1, 0.5)
+            .unwrap_err();
+        assert!(matches!(err, AccountantError::BudgetExceeded { .. }));
+        // The refused query is not recorded.
+        assert_eq!(acct.charges("census", "alice").unwrap().len(), 2);
+        // Budgets are per analyst.
+        acct.set_budget("census", "bob", budget(1.0, 0.0, Composition::Basic))
+            .unwrap();
+        acct.laplace_count("census", "bob", 10, 1, 0.5).unwrap();
+    }
+
+    #[test]
+    fn spend_survives_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let acct = PrivacyAccountant::open(dir.path()).unwrap();
+            acct.set_budget("census", "alice", budget(1.0, 0.0, Composition::Basic))
+                .unwrap();
+            acct.laplace_count("census", "alice", 10, 1, 0.75).unwrap();
+        }
+        let acct = PrivacyAccountant::open(dir.path()).unwrap();
+        let spent = acct.spent("census", "alice").unwrap();
+        assert!((spent.epsilon - 0.75).abs() < 1e-6);
+        let err = acct
+            .laplace_count("census", "alice", 10, 1, 0.5)
+            .unwrap_err();
+        assert!(matches!(err, AccountantError::BudgetExceeded { .. }));
+        // No temporary files left behind.
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn advanced_composition_admits_more_small_queries() {
+        let dir = tempfile::tempdir().unwrap();
+        let acct = PrivacyAccountant::open(dir.path()).unwrap();
+        let advanced = Composition::Advanced { delta_slack: 1e-6 };
+        acct.set_budget("d", "basic", budget(2.0, 1e-5, Composition::Basic))
+            .unwrap();
+        acct.set_budget("d", "advanced", budget(2.0, 1e-5, advanced))
+            .unwrap();
+        let count = |analyst: &str| {
+            (0..1000)
+                .take_while(|_| {
+                    acct.charge(
+                        "d",
+                        analyst,
+                        Mechanism::Laplace {
+                            epsilon: 1.0 / 64.0,
+                        },
+                    )
+                    .is_ok()
+                })
+                .count()
+        };
+        let basic = count("basic");
+        let adv = count("advanced");
+        assert_eq!(basic, 128);
+        assert!(adv > basic, "advanced {adv} vs basic {basic}");
+    }
+
+    #[test]
+    fn renyi_composition_beats_basic_for_gaussians() {
+        let dir = tempfile::tempdir().unwrap();
+        let acct = PrivacyAccountant::open(dir.path()).unwrap();
+        acct.set_budget("d", "basic", budget(5.0, 1e-5, Composition::Basic))
+            .unwrap();
+        acct.set_budget("d", "renyi", budget(5.0, 1e-5, Composition::Renyi))
+            .unwrap();
+        let count = |analyst: &str| {
+            (0..200)
+                .take_while(|_| acct.gaussian_count("d", analyst, 0, 1, 0.5, 1e-7).is_ok())
+                .count()
+        };
+        let basic = count("basic");
+        let renyi = count("renyi");
+        assert!(renyi > 2 * basic, "renyi {renyi} vs basic {basic}");
+    }
+
+    #[test]
+    fn laplace_rdp_is_the_pure_dp_bound() {
+        let m = Mechanism::Laplace { epsilon: 1.0 };
+        for &alpha in RDP_ORDERS {
+            let r = m.rdp(alpha);
+            assert!(r > 0.0 && r <= 1.0, "α={alpha}: {r}");
+        }
+        // Below the ε cap the charge is the zCDP curve αε²/2.
+        let r = Mechanism::Laplace { epsilon: 0.1 }.rdp(4.0);
+        assert!((r - 0.02).abs() < 1e-12, "{r}");
+        // Large ε at large α must not overflow.
+        let r = Mechanism::Laplace { epsilon: 50.0 }.rdp(256.0);
+        assert!(r.is_finite() && r <= 50.0);
+    }
+}
@@ -12,7 +12,9 @@
 //! assert!(perturbed.is_finite());
 //! ```
 
-#![forbid(unsafe_code)]
+// `deny`, not `forbid`: `pir::PirDatabase::open` memory-maps its
+// database file, the one place this crate opts in to unsafe code.
+#![deny(unsafe_code)]
 #![allow(missing_docs)] // TODO: document before 1.0
 #![allow(rustdoc::broken_intra_doc_links)]
 #![allow(rustdoc::bare_urls)]
@@ -23,13 +25,17 @@
 pub mod adaptor_sig;
 pub mod blind_ecdsa;
 pub mod differential;
+pub mod discrete_noise;
 pub mod distributed_prf;
 pub mod distributed_prg;
+pub mod dp_accountant;
 pub mod jsonld_signing;
 pub mod multi_sig;
 pub mod oblivious_transfer;
+pub mod pir;
 pub mod privacy_and_dist_patterns;
 pub mod proxy_reencryption;
+pub mod psi;
 pub mod secure_aggregation;
 pub mod side_channel;
 pub mod threshold_decryption;
@@ -0,0 +1,562 @@
+//! Single-server private information retrieval from LWE.
+//!
+//! [`pir_trivial`](crate::privacy_and_dist_patterns::pir_trivial)
+//! ships the whole database and the XOR scheme
+//! ([`pir_create_query`](crate::privacy_and_dist_patterns::pir_create_query))
+//! needs two servers that never collude. This module is SimplePIR
+//! (Henzinger, Hong, Corrigan-Gibbs, Meiklejohn, Vaikuntanathan,
+//! USENIX Security 2023): one untrusted server, privacy from the
+//! Learning-With-Errors assumption alone.
+//!
+//! ## Layout
+//!
+//! The database is `N` fixed-size records, stored back to back. It is
+//! viewed as an `ℓ × m` byte matrix `D` whose column `j` is records
+//! `j·k .. (j+1)·k` concatenated (`ℓ = k·record_size`), so each column
+//! is one contiguous byte range of the file and the server streams it
+//! straight out of the page cache. [`PirParams::new`] picks `k` so the
+//! matrix is roughly square.
+//!
+//! ## Protocol
+//!
+//! With `A ∈ Z_q^{m×n}` expanded from a public seed, `q = 2³²`,
+//! `n = 1024` and bytes centred to `[-128, 128)`:
+//!
+//! 1. **Offline.** The server publishes the hint `H = D·A` (`ℓ × n`)
+//!    once per database version ([`PirServer::hint`]).
+//! 2. **Query.** To read column `j` the client picks a fresh secret
+//!    `s ∈ Z_q^n` and sends `qu = A·s + e + Δ·u_j`, `Δ = q/256`
+//!    ([`PirClient::query`]). `qu` is an LWE sample and looks uniform
+//!    to the server whatever `j` is.
+//! 3. **Answer.** `ans = D·qu`, one pass over the database
+//!    ([`PirServer::answer`]).
+//! 4. **Decode.** `ans − H·s = Δ·D[·][j] + D·e`; rounding each row to
+//!    a multiple of `Δ` recovers the column, and the record is sliced
+//!    out of it ([`PirClient::decode`]).
+//!
+//! Parameters follow the SimplePIR paper (`n = 1024`, `q = 2³²`,
+//! error σ ≈ 6.4, here a centred binomial with η = 82) at ~128-bit
+//! security for up to 2²⁰ columns. Columns are capped at
+//! [`MAX_COLUMNS`], which keeps the decoding error below `Δ/2` by more
+//! than twenty standard deviations.
+//!
+//! Costs per query: upload `4m` bytes, download `4ℓ` bytes, client
+//! `m·n` multiply-adds, server one read of the database. The hint is
+//! `4ℓn` bytes and is reused across queries until the database
+//! changes. The scheme is semi-honest: a server returning a wrong
+//! answer yields a wrong record, so verify what you fetch (e.g. an
+//! entry hash against a signed log root).
+//!
+//! ## Use
+//!
+//! Typical record sets are transparency-log entry hashes (`record =
+//! entry_hash`, index = sequence) and revocation status tables
+//! (`record = status byte(s)`, index = certificate serial slot); see
+//! the `privacy_pir_log_lookup` example in `confium-examples`.
+
+use std::fs::File;
+use std::path::Path;
+
+use memmap2::Mmap;
+use rand_core::{OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// LWE secret dimension.
+pub const LWE_N: usize = 1024;
+
+/// Upper bound on the number of database columns (LWE samples per
+/// query). Larger databases get taller columns instead.
+pub const MAX_COLUMNS: usize = 1 << 18;
+
+/// `Δ = q / p` with `q = 2³²` and one byte of plaintext per entry.
+const DELTA_SHIFT: u32 = 24;
+
+/// Centred-binomial parameter for the LWE error (variance η/2 ≈ 6.4²).
+const CBD_ETA: u32 = 82;
+
+const MATRIX_DOMAIN: &[u8] = b"confium-pir-simplepir-A-v1";
+
+#[derive(Debug, thiserror::Error)]
+pub enum PirError {
+    #[error("record {index} out of range (database has {len})")]
+    IndexOutOfRange { index: usize, len: usize },
+    #[error("invalid database layout: {0}")]
+    Layout(&'static str),
+    #[error("{0} does not match the database parameters")]
+    Mismatch(&'static str),
+    #[error("database I/O: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Shape of a PIR database. Both sides derive it from
+/// `(num_records, record_size)` and carry it in the hint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PirParams {
+    pub num_records: usize,
+    pub record_size: usize,
+    /// Records stacked in each matrix column (`k`).
+    pub records_per_column: usize,
+    /// Matrix columns (`m`).
+    pub columns: usize,
+    pub lwe_n: usize,
+}
+
+impl PirParams {
+    pub fn new(num_records: usize, record_size: usize) -> Result<Self, PirError> {
+        if num_records == 0 || record_size == 0 {
+            return Err(PirError::Layout("empty database or zero-size records"));
+        }
+        let total = num_records
+            .checked_mul(record_size)
+            .ok_or(PirError::Layout("database too large"))?;
+        // Square-ish: column height ≈ sqrt(total bytes).
+        let side = total.isqrt() + 1;
+        let records_per_column = side
+            .div_ceil(record_size)
+            .max(num_records.div_ceil(MAX_COLUMNS))
+            .clamp(1, num_records);
+        Ok(Self {
+            num_records,
+            record_size,
+            records_per_column,
+            columns: num_records.div_ceil(records_per_column),
+            lwe_n: LWE_N,
+        })
+    }
+
+    /// Matrix rows (`ℓ`), in bytes.
+    pub fn rows(&self) -> usize {
+        self.records_per_column * self.record_size
+    }
+
+    /// Column holding `index`, and the record's byte offset within it.
+    fn locate(&self, index: usize) -> Result<(usize, usize), PirError> {
+        if index >= self.num_records {
+            return Err(PirError::IndexOutOfRange {
+                index,
+                len: self.num_records,
+            });
+        }
+        Ok((
+            index / self.records_per_column,
+            (index % self.records_per_column) * self.record_size,
+        ))
+    }
+}
+
+enum Backing {
+    Owned(Vec<u8>),
+    Mapped(Mmap),
+}
+
+/// Fixed-size records, in memory or memory-mapped from a file.
+pub struct PirDatabase {
+    params: PirParams,
+    data: Backing,
+}
+
+impl PirDatabase {
+    /// Records concatenated in index order.
+    pub fn from_bytes(record_size: usize, bytes: Vec<u8>) -> Result<Self, PirError> {
+        let params = Self::params_for(record_size, bytes.len())?;
+        Ok(Self {
+            params,
+            data: Backing::Owned(bytes),
+        })
+    }
+
+    pub fn from_records<R: AsRef<[u8]>>(
+        record_size: usize,
+        records: &[R],
+    ) -> Result<Self, PirError> {
+        let mut bytes = Vec::with_capacity(records.len() * record_size);
+        for r in records {
+            if r.as_ref().len() != record_size {
+                return Err(PirError::Layout("record has the wrong size"));
+            }
+            bytes.extend_from_slice(r.as_ref());
+        }
+        Self::from_bytes(record_size, bytes)
+    }
+
+    /// Memory-map a file of records concatenated in index order.
+    ///
+    /// The file must not be modified while it is mapped: publish a new
+    /// database version by writing a new file and renaming it into
+    /// place, then open it and compute a fresh hint.
+    #[allow(unsafe_code)] // memory mapping a file is inherently unsafe
+    pub fn open(path: impl AsRef<Path>, record_size: usize) -> Result<Self, PirError> {
+        let file = File::open(path)?;
+        let len = usize::try_from(file.metadata()?.len())
+            .map_err(|_| PirError::Layout("database too large"))?;
+        let params = Self::params_for(record_size, len)?;
+        // SAFETY: the mapping is read-only and callers are required
+        // (see above) not to mutate the file while it is open.
+        let map = unsafe { Mmap::map(&file)? };
+        Ok(Self {
+            params,
+            data: Backing::Mapped(map),
+        })
+    }
+
+    fn params_for(record_size: usize, len: usize) -> Result<PirParams, PirError> {
+        if record_size == 0 || len % record_size != 0 {
+            return Err(PirError::Layout(
+                "database length is not a multiple of the record size",
+            ));
+        }
+        PirParams::new(len / record_size, record_size)
+    }
+
+    pub fn params(&self) -> &PirParams {
+        &self.params
+    }
+
+    /// Direct (non-private) access, for the server's own use.
+    pub fn record(&self, index: usize) -> Option<&[u8]> {
+        let size = self.params.record_size;
+        self.bytes().get(index * size..(index + 1) * size)
+    }
+
+    fn bytes(&self) -> &[u8] {
+        match &self.data {
+            Backing::Owned(v) => v.as_slice(),
+            Backing::Mapped(m) => &m[..],
+        }
+    }
+
+    /// Column `j`; the last one may be shorter than `rows()`, and the
+    /// missing entries count as zero.
+    fn column(&self, j: usize) -> &[u8] {
+        let rows = self.params.rows();
+        let bytes = self.bytes();
+        let start = (j * rows).min(bytes.len());
+        &bytes[start..(start + rows).min(bytes.len())]
+    }
+}
+
+/// Offline hint: `D·A` plus what the client needs to rebuild `A`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PirHint {
+    pub params: PirParams,
+    pub seed: [u8; 32],
+    /// `rows() × lwe_n`, row-major.
+    pub matrix: Vec<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PirQuery {
+    pub vector: Vec<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PirAnswer {
+    pub vector: Vec<u32>,
+}
+
+/// Client-side secret for one outstanding query. Never send it.
+pub struct PirQueryState {
+    index: usize,
+    secret: Vec<u32>,
+}
+
+pub struct PirServer {
+    db: PirDatabase,
+    seed: [u8; 32],
+}
+
+impl PirServer {
+    /// Serve `db` with a fresh random matrix seed.
+    pub fn new(db: PirDatabase) -> Self {
+        let mut seed = [0u8; 32];
+        OsRng.fill_bytes(&mut seed);
+        Self::with_seed(db, seed)
+    }
+
+    pub fn with_seed(db: PirDatabase, seed: [u8; 32]) -> Self {
+        Self { db, seed }
+    }
+
+    pub fn params(&self) -> &PirParams {
+        self.db.params()
+    }
+
+    pub fn database(&self) -> &PirDatabase {
+        &self.db
+    }
+
+    /// Compute the hint `H = D·A`. Costs `N·record_size·n`
+    /// multiply-adds; do it once per database version and cache it.
+    pub fn hint(&self) -> PirHint {
+        let params = *self.db.params();
+        let n = params.lwe_n;
+        let mut matrix = vec![0u32; params.rows() * n];
+        par_chunks(&mut matrix, n, |first_row, chunk| {
+            let mut a = vec![0u32; n];
+            for j in 0..params.columns {
+                let rows = column_rows(self.db.column(j), first_row, chunk.len() / n);
+                if rows.iter().all(|&b| b == 0x80) {
+                    continue;
+                }
+                expand_matrix_row(&self.seed, j, &mut a);
+                for (&b, h) in rows.iter().zip(chunk.chunks_exact_mut(n)) {
+                    let c = centre(b);
+                    if c == 0 {
+                        continue;
+                    }
+                    for (h, &a) in h.iter_mut().zip(&a) {
+                        *h = h.wrapping_add(c.wrapping_mul(a));
+                    }
+                }
+            }
+        });
+        PirHint {
+            params,
+            seed: self.seed,
+            matrix,
+        }
+    }
+
+    /// `D·qu`: one streaming pass over the database.
+    pub fn answer(&self, query: &PirQuery) -> Result<PirAnswer, PirError> {
+        let params = self.db.params();
+        if query.vector.len() != params.columns {
+            return Err(PirError::Mismatch("query length"));
+        }
+        let mut vector = vec![0u32; params.rows()];
+        par_chunks(&mut vector, 1, |first_row, chunk| {
+            for (j, &q) in query.vector.iter().enumerate() {
+                let rows = column_rows(self.db.column(j), first_row, chunk.len());
+                for (out, &b) in chunk.iter_mut().zip(rows) {
+                    *out = out.wrapping_add(centre(b).wrapping_mul(q));
+                }
+            }
+        });
+        Ok(PirAnswer { vector })
+    }
+}
+
+pub struct PirClient {
+    hint: PirHint,
+}
+
+impl PirClient {
+    pub fn new(hint: PirHint) -> Result<Self, PirError> {
+        let expected = PirParams::new(hint.params.num_records, hint.params.record_size)?;
+        if hint.params != expected {
+            return Err(PirError::Mismatch("hint parameters"));
+        }
+        if hint.matrix.len() != expected.rows() * expected.lwe_n {
+            return Err(PirError::Mismatch("hint size"));
+        }
+        Ok(Self { hint })
+    }
+
+    pub fn params(&self) -> &PirParams {
+        &self.hint.params
+    }
+
+    /// Build a query for record `index`. The query may be sent to the
+    /// server; the state stays with the client for [`decode`](Self::decode).
+    pub fn query(&self, index: usize) -> Result<(PirQuery, PirQueryState), PirError> {
+        let params = self.hint.params;
+        let (column, _) = params.locate(index)?;
+        let n = params.lwe_n;
+
+        let mut secret = vec![0u32; n];
+        let mut buf = vec![0u8; n * 4];
+        OsRng.fill_bytes(&mut buf);
+        for (s, b) in secret.iter_mut().zip(buf.chunks_exact(4)) {
+            *s = u32::from_le_bytes(b.try_into().expect("4 bytes"));
+        }
+
+        let mut vector = vec![0u32; params.columns];
+        let seed = &self.hint.seed;
+        let s = &secret;
+        par_chunks(&mut vector, 1, |first, chunk| {
+            let mut a = vec![0u32; n];
+            for (i, out) in chunk.iter_mut().enumerate() {
+                expand_matrix_row(seed, first + i, &mut a);
+                *out = dot(&a, s);
+            }
+        });
+        for (v, e) in vector.iter_mut().zip(lwe_errors(params.columns)) {
+            *v = v.wrapping_add(e);
+        }
+        vector[column] = vector[column].wrapping_add(1 << DELTA_SHIFT);
+
+        Ok((PirQuery { vector }, PirQueryState { index, secret }))
+    }
+
+    /// Recover the record from the server's answer.
+    pub fn decode(&self, state: &PirQueryState, answer: &PirAnswer) -> Result<Vec<u8>, PirError> {
+        let params = self.hint.params;
+        if answer.vector.len() != params.rows() {
+            return Err(PirError::Mismatch("answer length"));
+        }
+        let (_, offset) = params.locate(state.index)?;
+        let n = params.lwe_n;
+        Ok((offset..offset + params.record_size)
+            .map(|r| {
+                let h = &self.hint.matrix[r * n..(r + 1) * n];
+                let v = answer.vector[r].wrapping_sub(dot(h, &state.secret));
+                let rounded = v.wrapping_add(1 << (DELTA_SHIFT - 1)) >> DELTA_SHIFT;
+                (rounded as u8).wrapping_add(0x80)
+            })
+            .collect())
+    }
+}
+
+/// Rows `first .. first + len` of a column, clipped to its length.
+fn column_rows(column: &[u8], first: usize, len: usize) -> &[u8] {
+    column.get(first..).map_or(&[][..], |c| &c[..len.min(c.len())])
+}
+
+/// Byte as an element of `[-128, 128)` in `Z_q`.
+fn centre(b: u8) -> u32 {
+    u32::from(b).wrapping_sub(0x80)
+}
+
+fn dot(a: &[u32], b: &[u32]) -> u32 {
+    a.iter()
+        .zip(b)
+        .fold(0u32, |acc, (&x, &y)| acc.wrapping_add(x.wrapping_mul(y)))
+}
+
+/// Row `j` of the public matrix `A`, expanded from the seed with
+/// SHA-256 in counter mode.
+fn expand_matrix_row(seed: &[u8; 32], j: usize, out: &mut [u32]) {
+    for (block, words) in out.chunks_mut(8).enumerate() {
+        let digest = Sha256::new()
+            .chain_update(MATRIX_DOMAIN)
+            .chain_update(seed)
+            .chain_update((j as u64).to_le_bytes())
+            .chain_update((block as u32).to_le_bytes())
+            .finalize();
+        for (w, bytes) in words.iter_mut().zip(digest.chunks_exact(4)) {
+            *w = u32::from_le_bytes(bytes.try_into().expect("4 bytes"));
+        }
+    }
+}
+
+/// `count` centred-binomial samples with parameter [`CBD_ETA`].
+fn lwe_errors(count: usize) -> Vec<u32> {
+    const BYTES: usize = (CBD_ETA as usize).div_ceil(8);
+    const MASK: u128 = (1 << CBD_ETA) - 1;
+    let mut buf = vec![0u8; count * 2 * BYTES];
+    OsRng.fill_bytes(&mut buf);
+    buf.chunks_exact(2 * BYTES)
+        .map(|pair| {
+            let word = |b: &[u8]| {
+                let mut w = [0u8; 16];
+                w[..BYTES].copy_from_slice(b);
+                u128::from_le_bytes(w) & MASK
+            };
+            let (x, y) = pair.split_at(BYTES);
+            word(x).count_ones().wrapping_sub(word(y).count_ones())
+        })
+        .collect()
+}
+
+/// Split `out` into runs of whole `width`-sized rows and fill them in
+/// parallel. `f` gets the index of the first row of its run.
+fn par_chunks(out: &mut [u32], width: usize, f: impl Fn(usize, &mut [u32]) + Sync) {
+    let rows = out.len() / width;
+    let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    if threads == 1 || rows < 64 {
+        f(0, out);
+        return;
+    }
+    let per = rows.div_ceil(threads);
+    let f = &f;
+    std::thread::scope(|scope| {
+        for (i, chunk) in out.chunks_mut(per * width).enumerate() {
+            scope.spawn(move || f(i * per, chunk));
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records(count: usize, size: usize) -> Vec<Vec<u8>> {
+        (0..count)
+            .map(|i| (0..size).map(|j| (i * 31 + j * 7) as u8).collect())
+            .collect()
+    }
+
+    fn fetch(client: &PirClient, server: &PirServer, index: usize) -> Vec<u8> {
+        let (query, state) = client.query(index).unwrap();
+        let answer = server.answer(&query).unwrap();
+        client.decode(&state, &answer).unwrap()
+    }
+
+    #[test]
+    fn params_are_roughly_square() {
+        let p = PirParams::new(10_000, 32).unwrap();
+        assert!(p.rows() >= 500 && p.rows() <= 700, "rows = {}", p.rows());
+        assert!(p.columns * p.records_per_column >= 10_000);
+        let tall = PirParams::new(1 << 24, 1).unwrap();
+        assert!(tall.columns <= MAX_COLUMNS);
+        assert!(PirParams::new(0, 32).is_err());
+    }
+
+    #[test]
+    fn retrieves_every_record() {
+        let recs = records(37, 16);
+        let server = PirServer::new(PirDatabase::from_records(16, &recs).unwrap());
+        let client = PirClient::new(server.hint()).unwrap();
+        for (i, r) in recs.iter().enumerate() {
+            assert_eq!(&fetch(&client, &server, i), r, "record {i}");
+        }
+    }
+
+    #[test]
+    fn retrieves_extreme_bytes() {
+        let recs = vec![vec![0u8; 8], vec![0xff; 8], vec![0x80; 8], vec![0x7f; 8]];
+        let server = PirServer::new(PirDatabase::from_records(8, &recs).unwrap());
+        let client = PirClient::new(server.hint()).unwrap();
+        for (i, r) in recs.iter().enumerate() {
+            assert_eq!(&fetch(&client, &server, i), r);
+        }
+    }
+
+    #[test]
+    fn serves_memory_mapped_file() {
+        let recs = records(64, 32);
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("entries.db");
+        std::fs::write(&path, recs.concat()).unwrap();
+        let server = PirServer::new(PirDatabase::open(&path, 32).unwrap());
+        assert_eq!(server.database().record(5), Some(&recs[5][..]));
+        let client = PirClient::new(server.hint()).unwrap();
+        assert_eq!(fetch(&client, &server, 63), recs[63]);
+    }
+
+    #[test]
+    fn rejects_bad_inputs() {
+        let recs = records(10, 4);
+        let server = PirServer::new(PirDatabase::from_records(4, &recs).unwrap());
+        let client = PirClient::new(server.hint()).unwrap();
+        assert!(matches!(
+            client.query(10),
+            Err(PirError::IndexOutOfRange { index: 10, len: 10 })
+        ));
+        let (_, state) = client.query(3).unwrap();
+        let short = PirAnswer { vector: vec![0; 1] };
+        assert!(client.decode(&state, &short).is_err());
+        assert!(server.answer(&PirQuery { vector: vec![] }).is_err());
+        assert!(PirDatabase::from_bytes(4, vec![0; 10]).is_err());
+    }
+
+    #[test]
+    fn query_vectors_differ_between_runs() {
+        let server = PirServer::new(PirDatabase::from_records(4, &records(10, 4)).unwrap());
+        let client = PirClient::new(server.hint()).unwrap();
+        let (a, _) = client.query(0).unwrap();
+        let (b, _) = client.query(0).unwrap();
+        assert_ne!(a, b);
+    }
+}
@@ -16,6 +16,11 @@ type HmacSha256 = Hmac<Sha256>;
 // === Private Set Intersection ===
 
 /// Hash-based PSI: both parties hash their sets, compare hashes.
+///
+/// Not private against the other party: anyone holding the salt can
+/// hash candidate elements and test them offline. Use
+/// [`crate::psi`] (DH/OPRF PSI) whenever the parties don't trust each
+/// other.
 pub fn psi_hash_based(set_a: &[Vec<u8>], set_b: &[Vec<u8>], salt: &[u8]) -> Vec<Vec<u8>> {
     let hashes_b: HashSet<[u8; 32]> = set_b.iter().map(|e| hash_with_salt(e, salt)).collect();
     set_a
@@ -57,6 +62,9 @@ pub fn pir_batch(database: &[Vec<u8>], indices: &[usize]) -> Vec<Vec<u8>> {
 
 /// XOR-based PIR (2 servers): each server gets a random subset;
 /// XOR of responses gives the desired element.
+///
+/// Private only if the two servers never collude. With a single
+/// untrusted server use [`crate::pir`] (LWE-based SimplePIR).
 pub struct PirQuery {
     pub mask: Vec<bool>,
 }
@@ -104,6 +112,13 @@ pub fn pir_decode(r1: &PirResponse, r2: &PirResponse) -> Vec<u8> {
 // === Differential Privacy ===
 
 /// Laplace mechanism: add noise calibrated to sensitivity and epsilon.
+///
+/// Floating-point inverse-CDF sampling leaks the unnoised value through
+/// the low-order bits of the result, and nothing tracks the ε spent.
+/// For releases of real data use
+/// [`PrivacyAccountant`](crate::dp_accountant::PrivacyAccountant),
+/// which charges a persistent budget and samples with
+/// [`discrete_noise`](crate::discrete_noise).
 pub fn laplace_noise(sensitivity: f64, epsilon: f64) -> f64 {
     use rand_core::{OsRng, RngCore};
     let scale = sensitivity / epsilon;
@@ -120,6 +135,9 @@ pub fn dp_query(value: f64, sensitivity: f64, epsilon: f64) -> f64 {
 }
 
 /// Gaussian mechanism: add Gaussian noise.
+///
+/// Same caveats as [`laplace_noise`]; prefer
+/// [`PrivacyAccountant::gaussian_count`](crate::dp_accountant::PrivacyAccountant::gaussian_count).
 pub fn gaussian_noise(sensitivity: f64, epsilon: f64, delta: f64) -> f64 {
     use rand_core::{OsRng, RngCore};
     let sigma = sensitivity * (2.0 * (1.25 / delta).ln()).sqrt() / epsilon;
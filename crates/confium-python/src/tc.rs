@@ -267,7 +267,7 @@ impl PyCmp20 {
     /// Run a non-interactive CMP20 DKG for `party_count` parties at
     /// threshold `threshold`. Returns
     /// `{"shares": [bytes, ...], "public_key": bytes}` where each
-    /// share blob is 71 bytes (opaque Cmp20Share encoding).
+    /// share blob is 72 bytes (opaque Cmp20Share encoding).
     #[staticmethod]
     fn keygen<'py>(
         py: Python<'py>,
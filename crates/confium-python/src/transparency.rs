@@ -380,6 +380,7 @@ pub(crate) fn register_module(py: Python<'_>, parent: &Bound<'_, PyModule>) -> P
         "quorum_policy",
         "director_identity",
         "archive_renewal",
+        "map_root",
     ] {
         artifact_types.append(name)?;
     }
@@ -52,6 +52,12 @@ pub enum Error {
     #[snafu(display("no trusted publisher signed plugin '{name}'"))]
     UntrustedPlugin { name: String },
 
+    #[snafu(display("plugin '{name}' has neither a detached signature nor a registry manifest"))]
+    UnsignedPlugin { name: String },
+
+    #[snafu(display("plugin '{name}' artifact {digest} has been revoked"))]
+    RevokedPlugin { name: String, digest: String },
+
     #[snafu(display("failed to load RNP library: {message}"))]
     RnpLoad { message: String },
 
@@ -3,9 +3,12 @@
 //! [`install`] resolves a plugin against a [`crate::Client`], downloads
 //! the artifact through a pluggable [`Downloader`] (so tests can inject
 //! bytes), verifies the SHA-256, and writes the result to
-//! [`crate::paths::plugin_install_dir`]. A copy of the manifest is
-//! stashed alongside (`.manifest`) so `list`/`info`/`update` can read
-//! metadata without re-hitting the registry.
+//! [`crate::paths::plugin_install_dir`]. The publisher's detached
+//! signature (`<url>.sig`) is stored next to it (`.so.sig`); that is
+//! what the load-time check in [`crate::verify::verify_artifact`]
+//! verifies. A copy of the manifest is stashed alongside (`.manifest`)
+//! so `list`/`info`/`update` can read metadata without re-hitting the
+//! registry; it carries no trust.
 //!
 //! Local enumeration helpers ([`list_installed`], [`read_installed`],
 //! [`remove`]) keep the file-layout knowledge in one place.
@@ -167,11 +170,15 @@ pub fn install_manifest<D: Downloader>(
 
     let bytes = downloader.download(&manifest.artifact.url)?;
     verify_sha256(&name, &version, &bytes, &manifest.artifact.sha256)?;
+    // Without its signature the plugin could never pass the load-time
+    // check, so a registry that does not publish one is an error here.
+    let signature = downloader.download(&format!("{}.sig", manifest.artifact.url))?;
 
     let target = plugin_install_dir(override_home, &name, &version)?;
     ensure_parent(&target)?;
 
     write_bytes(&target, &bytes, "artifact")?;
+    write_bytes(&signature_path(&target), &signature, "signature")?;
 
     // Stash the manifest next to the artifact so `list`/`info`/`update`
     // can read metadata offline.
@@ -243,6 +250,7 @@ pub fn remove(override_home: Option<&PathBuf>, name: &str) -> Result<()> {
         }
         let manifest_path = manifest_path(&record.artifact_path);
         let _ = std::fs::remove_file(&manifest_path);
+        let _ = std::fs::remove_file(signature_path(&record.artifact_path));
         std::fs::remove_file(&record.artifact_path).map_err(|e| {
             Error::io(
                 e,
@@ -292,12 +300,19 @@ fn parse_artifact_name(path: &Path) -> Option<(String, String)> {
     Some((name, version))
 }
 
-fn manifest_path(artifact: &Path) -> PathBuf {
+pub(crate) fn manifest_path(artifact: &Path) -> PathBuf {
     let mut p = artifact.to_path_buf();
     p.set_extension("manifest");
     p
 }
 
+/// `<artifact>.sig`, the first sidecar the load-time check looks for.
+fn signature_path(artifact: &Path) -> PathBuf {
+    let mut os = artifact.as_os_str().to_os_string();
+    os.push(".sig");
+    PathBuf::from(os)
+}
+
 fn ensure_parent(path: &Path) -> Result<()> {
     if let Some(parent) = path.parent() {
         std::fs::create_dir_all(parent)
@@ -316,13 +331,19 @@ fn write_str(path: &Path, body: &str, what: &str) -> Result<()> {
         .map_err(|e| Error::io(e, format!("failed to write {what} to {}", path.display())))
 }
 
-fn verify_sha256(name: &str, version: &str, bytes: &[u8], expected: &str) -> Result<()> {
+/// Lower-case hex SHA-256 of `bytes`, the form used by manifest
+/// `[artifact] sha256` fields and the trust store's revocation list.
+pub fn sha256_hex(bytes: &[u8]) -> String {
     use std::fmt::Write;
-    let digest = sha256(bytes);
-    let mut got = String::with_capacity(64);
-    for b in digest {
-        let _ = write!(&mut got, "{:02x}", b);
+    let mut out = String::with_capacity(64);
+    for b in sha256(bytes) {
+        let _ = write!(&mut out, "{:02x}", b);
     }
+    out
+}
+
+fn verify_sha256(name: &str, version: &str, bytes: &[u8], expected: &str) -> Result<()> {
+    let got = sha256_hex(bytes);
     if got.eq_ignore_ascii_case(expected.trim()) {
         return Ok(());
     }
@@ -448,6 +469,12 @@ sha256 = "{sha}"
         toml::from_str(&manifest_toml).unwrap()
     }
 
+    fn signed_downloader(artifact: Vec<u8>) -> MemoryDownloader {
+        MemoryDownloader::new()
+            .with("https://example.test/botan.so", artifact)
+            .with("https://example.test/botan.so.sig", b"signature".to_vec())
+    }
+
     fn hex(bytes: &[u8]) -> String {
         let mut s = String::with_capacity(bytes.len() * 2);
         use std::fmt::Write;
@@ -478,11 +505,15 @@ sha256 = "{sha}"
         let tmp = tempfile::tempdir().unwrap();
         let home = PathBuf::from(tmp.path());
         let manifest = manifest_for("botan", "3.2.0", &empty_body_hash());
-        let downloader = MemoryDownloader::new().with("https://example.test/botan.so", Vec::new());
+        let downloader = signed_downloader(Vec::new());
 
         let record = install_manifest(&downloader, Some(&home), manifest).unwrap();
         assert!(record.artifact_path.exists());
         assert!(manifest_path(&record.artifact_path).exists());
+        assert_eq!(
+            std::fs::read(signature_path(&record.artifact_path)).unwrap(),
+            b"signature"
+        );
 
         let installed = list_installed(Some(&home)).unwrap();
         assert_eq!(installed.len(), 1);
@@ -495,22 +526,33 @@ sha256 = "{sha}"
         let tmp = tempfile::tempdir().unwrap();
         let home = PathBuf::from(tmp.path());
         let manifest = manifest_for("botan", "3.2.0", "deadbeef");
-        let downloader =
-            MemoryDownloader::new().with("https://example.test/botan.so", vec![1, 2, 3]);
+        let downloader = signed_downloader(vec![1, 2, 3]);
         let err = install_manifest(&downloader, Some(&home), manifest).unwrap_err();
         assert!(matches!(err, Error::HashMismatch { .. }));
     }
 
     #[test]
-    fn remove_deletes_artifact_and_manifest() {
+    fn install_requires_a_published_signature() {
         let tmp = tempfile::tempdir().unwrap();
         let home = PathBuf::from(tmp.path());
         let manifest = manifest_for("botan", "3.2.0", &empty_body_hash());
         let downloader = MemoryDownloader::new().with("https://example.test/botan.so", Vec::new());
-        install_manifest(&downloader, Some(&home), manifest).unwrap();
+        let err = install_manifest(&downloader, Some(&home), manifest).unwrap_err();
+        assert!(matches!(err, Error::Download { .. }), "{err}");
+        assert!(list_installed(Some(&home)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn remove_deletes_artifact_and_manifest() {
+        let tmp = tempfile::tempdir().unwrap();
+        let home = PathBuf::from(tmp.path());
+        let manifest = manifest_for("botan", "3.2.0", &empty_body_hash());
+        let downloader = signed_downloader(Vec::new());
+        let record = install_manifest(&downloader, Some(&home), manifest).unwrap();
 
         remove(Some(&home), "botan").unwrap();
         assert!(list_installed(Some(&home)).unwrap().is_empty());
+        assert!(!signature_path(&record.artifact_path).exists());
     }
 
     #[test]
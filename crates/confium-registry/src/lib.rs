@@ -29,7 +29,9 @@
 //! - [`trust`] — the [`TrustStore`] that persists the user's trusted
 //!   publishers under `~/.config/confium/trust/`.
 //! - [`verify`] — cryptographic ([`verify::verify_signature`]) and
-//!   policy ([`verify::check`]) layers for PGP signature verification.
+//!   policy ([`verify::check`]) layers for PGP signature verification,
+//!   plus the load-time artifact check ([`verify::verify_artifact`])
+//!   the engine runs before `dlopen`.
 //!   The crypto layer prefers in-process RNP via `libloading` and falls
 //!   back to `gpg --verify` when `librnp` isn't available.
 
@@ -43,14 +45,16 @@ pub mod verify;
 
 pub use client::{Client, Fetcher, MemoryFetcher};
 pub use error::{Error, Result};
-pub use install::{InstalledRecord, install};
+pub use install::{InstalledRecord, install, sha256_hex};
 pub use manifest::{
     AlgorithmMap, Artifact, ConfiumMeta, IndexEntry, Manifest, PluginIndex, TrustRoot,
     TrustRootsFile, VersionEntry,
 };
 pub use paths::{config_dir, plugin_install_dir, plugins_dir, trust_dir};
 pub use trust::{TrustStore, TrustStoreEntry};
-pub use verify::{Verification, verify_signature};
+pub use verify::{
+    ArtifactCheck, Evidence, Verification, signed_payload, verify_artifact, verify_signature,
+};
 
 /// The default registry base URL.
 ///
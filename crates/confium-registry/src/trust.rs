@@ -7,6 +7,15 @@
 //! both. This makes "trust a publisher" a simple file write — auditable,
 //! mergeable, and trivially backed up.
 //!
+//! Two optional companions live in the same directory:
+//!
+//! - `<publisher>.asc` — the publisher's OpenPGP public key. The
+//!   engine's load-time check ([`crate::verify::verify_artifact`]) needs
+//!   the key on disk because it runs without network access.
+//! - `revoked-digests` — one lower-case hex SHA-256 per line. Artifacts
+//!   listed here are refused at load time even if a trusted publisher
+//!   signed them.
+//!
 //! [`TrustStore`] owns the file-layout knowledge so the CLI's `trust`
 //! sub-commands stay thin.
 
@@ -16,6 +25,9 @@ use crate::error::{Error, Result};
 use crate::manifest::TrustRoot;
 use crate::paths::trust_dir;
 
+/// File (inside the trust directory) holding revoked artifact digests.
+const REVOKED_FILE: &str = "revoked-digests";
+
 /// One row in the local trust store.
 pub type TrustStoreEntry = TrustRoot;
 
@@ -86,7 +98,8 @@ impl TrustStore {
         Ok(())
     }
 
-    /// Remove a trusted publisher. Succeeds (no-op) if not present.
+    /// Remove a trusted publisher (and its stored public key, if any).
+    /// Succeeds (no-op) if not present.
     pub fn remove(&self, name: &str) -> Result<bool> {
         let path = self.path_for(name);
         if !path.exists() {
@@ -94,9 +107,66 @@ impl TrustStore {
         }
         std::fs::remove_file(&path)
             .map_err(|e| Error::io(e, format!("failed to remove {}", path.display())))?;
+        let _ = std::fs::remove_file(path.with_extension("asc"));
         Ok(true)
     }
 
+    /// Store the OpenPGP public key for a publisher. The publisher row
+    /// itself is written separately with [`TrustStore::add`].
+    pub fn set_public_key(&self, name: &str, key: &[u8]) -> Result<()> {
+        let dir = self.dir()?;
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| Error::io(e, format!("failed to create {}", dir.display())))?;
+        let path = self.path_for(name).with_extension("asc");
+        std::fs::write(&path, key)
+            .map_err(|e| Error::io(e, format!("failed to write {}", path.display())))
+    }
+
+    /// The stored public key for a publisher, or `None` if only the
+    /// publisher row is present.
+    pub fn public_key(&self, name: &str) -> Result<Option<Vec<u8>>> {
+        let path = self.path_for(name).with_extension("asc");
+        if !path.exists() {
+            return Ok(None);
+        }
+        std::fs::read(&path)
+            .map(Some)
+            .map_err(|e| Error::io(e, format!("failed to read {}", path.display())))
+    }
+
+    /// Add an artifact digest (hex SHA-256) to the revocation list.
+    pub fn revoke(&self, digest: &str) -> Result<()> {
+        let digest = digest.trim().to_ascii_lowercase();
+        if self.is_revoked(&digest)? {
+            return Ok(());
+        }
+        let dir = self.dir()?;
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| Error::io(e, format!("failed to create {}", dir.display())))?;
+        let path = dir.join(REVOKED_FILE);
+        let mut body = std::fs::read_to_string(&path).unwrap_or_default();
+        body.push_str(&digest);
+        body.push('\n');
+        std::fs::write(&path, body)
+            .map_err(|e| Error::io(e, format!("failed to write {}", path.display())))
+    }
+
+    /// True if `digest` (hex SHA-256, any case) is on the revocation
+    /// list.
+    pub fn is_revoked(&self, digest: &str) -> Result<bool> {
+        let path = self.dir()?.join(REVOKED_FILE);
+        if !path.exists() {
+            return Ok(false);
+        }
+        let body = std::fs::read_to_string(&path)
+            .map_err(|e| Error::io(e, format!("failed to read {}", path.display())))?;
+        let digest = digest.trim();
+        Ok(body
+            .lines()
+            .map(str::trim)
+            .any(|line| line.eq_ignore_ascii_case(digest)))
+    }
+
     /// True if `name` is trusted.
     pub fn contains(&self, name: &str) -> Result<bool> {
         Ok(self.path_for(name).exists())
@@ -194,6 +264,37 @@ mod tests {
         assert!(!store.remove("ghost").unwrap());
     }
 
+    #[test]
+    fn public_key_round_trips_and_is_removed_with_publisher() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = TrustStore::for_home(PathBuf::from(tmp.path()));
+        store.add(root("ribose")).unwrap();
+        assert!(store.public_key("ribose").unwrap().is_none());
+        store.set_public_key("ribose", b"-----BEGIN PGP").unwrap();
+        assert_eq!(
+            store.public_key("ribose").unwrap().as_deref(),
+            Some(&b"-----BEGIN PGP"[..])
+        );
+        // The .asc sidecar must not show up as a publisher row.
+        assert_eq!(store.list().unwrap().len(), 1);
+        store.remove("ribose").unwrap();
+        assert!(store.public_key("ribose").unwrap().is_none());
+    }
+
+    #[test]
+    fn revocation_list_is_case_insensitive_and_idempotent() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = TrustStore::for_home(PathBuf::from(tmp.path()));
+        assert!(!store.is_revoked("abcd").unwrap());
+        store.revoke("ABCD").unwrap();
+        store.revoke("abcd").unwrap();
+        assert!(store.is_revoked("abcd").unwrap());
+        assert!(store.is_revoked("AbCd").unwrap());
+        assert!(!store.is_revoked("ef01").unwrap());
+        let body = std::fs::read_to_string(store.dir().unwrap().join(REVOKED_FILE)).unwrap();
+        assert_eq!(body, "abcd\n");
+    }
+
     #[test]
     fn path_for_sanitizes_dangerous_names() {
         let tmp = tempfile::tempdir().unwrap();
@@ -10,6 +10,9 @@
 //!   that produced valid signatures intersects with the user's
 //!   [`TrustStore`]. Only the policy layer can produce
 //!   [`Error::UntrustedPlugin`].
+//! - **Artifact layer** — [`verify_artifact`] combines the two for a
+//!   plugin file about to be loaded: revocation, then a detached
+//!   signature sidecar. The engine calls this before `dlopen`.
 //!
 //! # Backend
 //!
@@ -25,7 +28,10 @@
 //! (publisher identity = PGP key registered in `publishers/`, artifact
 //! signature = detached PGP in `sigs/`).
 
+use std::path::{Path, PathBuf};
+
 use crate::error::{Error, Result};
+use crate::install::sha256_hex;
 use crate::trust::TrustStore;
 
 /// The outcome of a signature check.
@@ -79,6 +85,124 @@ pub fn check(
     }
 }
 
+// ---------------------------------------------------------------------------
+// Artifact layer
+// ---------------------------------------------------------------------------
+
+/// What vouched for an artifact that passed [`verify_artifact`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Evidence {
+    /// A detached signature (`<artifact>.sig` or `<artifact>.asc`)
+    /// verified under a trusted publisher's stored key.
+    DetachedSignature,
+}
+
+impl Evidence {
+    /// Stable lower-case label for logs and RPC responses.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Evidence::DetachedSignature => "signature",
+        }
+    }
+}
+
+/// A successful [`verify_artifact`] result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArtifactCheck {
+    /// Lower-case hex SHA-256 of the bytes that were checked.
+    pub digest: String,
+    /// The trusted publisher that vouched for the artifact.
+    pub publisher: String,
+    pub evidence: Evidence,
+}
+
+/// Decide whether the plugin artifact at `path`, whose contents are
+/// `bytes`, may be loaded.
+///
+/// `bytes` is passed separately so the caller can read the file once
+/// through the descriptor it will later hand to the dynamic loader;
+/// `path` is only used to locate the sidecar files. Checks run in
+/// order and the first conclusive one wins:
+///
+/// 1. A digest on the trust store's revocation list is refused with
+///    [`Error::RevokedPlugin`].
+/// 2. If a detached signature sidecar exists, it must verify under the
+///    stored key of some trusted publisher; otherwise
+///    [`Error::UntrustedPlugin`]. The signature covers
+///    [`signed_payload`] for `name` and the artifact's digest, so a
+///    publisher's signature on one plugin does not vouch for its bytes
+///    loaded under another name.
+/// 3. Without one the artifact is [`Error::UnsignedPlugin`].
+///
+/// The `.manifest` that `confium install` stashes next to an artifact
+/// is not evidence: it is unsigned, and anyone who can replace the
+/// artifact can rewrite its digest.
+pub fn verify_artifact(
+    name: &str,
+    path: &Path,
+    bytes: &[u8],
+    trust: &TrustStore,
+) -> Result<ArtifactCheck> {
+    let digest = sha256_hex(bytes);
+    if trust.is_revoked(&digest)? {
+        return Err(Error::RevokedPlugin {
+            name: name.to_string(),
+            digest,
+        });
+    }
+
+    if let Some(sig_path) = signature_sidecar(path) {
+        let payload = signed_payload(name, &digest);
+        let sig = std::fs::read(&sig_path)
+            .map_err(|e| Error::io(e, format!("failed to read {}", sig_path.display())))?;
+        for publisher in trust.list()? {
+            let Some(key) = trust.public_key(&publisher.name)? else {
+                continue;
+            };
+            if verify_signature(&payload, &sig, &key).is_ok() {
+                return Ok(ArtifactCheck {
+                    digest,
+                    publisher: publisher.name,
+                    evidence: Evidence::DetachedSignature,
+                });
+            }
+        }
+        return Err(Error::UntrustedPlugin {
+            name: name.to_string(),
+        });
+    }
+
+    Err(Error::UnsignedPlugin {
+        name: name.to_string(),
+    })
+}
+
+/// The statement a publisher signs for the plugin `name` whose
+/// artifact has lower-case hex SHA-256 `digest`:
+///
+/// ```text
+/// confium-plugin-signature-v1
+/// name: <name>
+/// sha256: <digest>
+/// ```
+///
+/// each line ending in `\n`. The detached sidecar is a signature over
+/// these bytes, not over the artifact itself.
+pub fn signed_payload(name: &str, digest: &str) -> Vec<u8> {
+    format!("confium-plugin-signature-v1\nname: {name}\nsha256: {digest}\n").into_bytes()
+}
+
+/// `<artifact>.sig`, then `<artifact>.asc`, whichever exists first.
+fn signature_sidecar(path: &Path) -> Option<PathBuf> {
+    ["sig", "asc"].iter().find_map(|ext| {
+        let mut os = path.as_os_str().to_os_string();
+        os.push(".");
+        os.push(ext);
+        let candidate = PathBuf::from(os);
+        candidate.exists().then_some(candidate)
+    })
+}
+
 // ---------------------------------------------------------------------------
 // Cryptographic layer
 // ---------------------------------------------------------------------------
@@ -598,6 +722,65 @@ mod tests {
         assert!(matches!(err, Error::UntrustedPlugin { .. }));
     }
 
+    /// Write an artifact plus an install manifest claiming its digest
+    /// and `publisher`. Returns the artifact path.
+    fn installed(dir: &tempfile::TempDir, bytes: &[u8], publisher: &str) -> PathBuf {
+        let path = dir.path().join("botan-1.0.0.so");
+        std::fs::write(&path, bytes).unwrap();
+        let manifest = format!(
+            "[plugin]\nname = \"botan\"\nversion = \"1.0.0\"\npublisher = \"{publisher}\"\n\n\
+             [artifact]\nurl = \"https://example/botan.so\"\nsha256 = \"{}\"\n",
+            sha256_hex(bytes)
+        );
+        std::fs::write(path.with_extension("manifest"), manifest).unwrap();
+        path
+    }
+
+    #[test]
+    fn install_manifest_alone_is_not_evidence() {
+        let dir = tempdir().unwrap();
+        let store = store_at(&dir);
+        store.add(root("ribose")).unwrap();
+        let bytes = b"\x7fELF plugin";
+        let path = installed(&dir, bytes, "ribose");
+        let err = verify_artifact("botan", &path, bytes, &store).unwrap_err();
+        assert!(matches!(err, Error::UnsignedPlugin { .. }), "{err}");
+    }
+
+    #[test]
+    fn revoked_digest_is_refused_before_anything_else() {
+        let dir = tempdir().unwrap();
+        let store = store_at(&dir);
+        store.add(root("ribose")).unwrap();
+        let path = installed(&dir, b"x", "ribose");
+        store.revoke(&sha256_hex(b"x")).unwrap();
+        let err = verify_artifact("botan", &path, b"x", &store).unwrap_err();
+        assert!(matches!(err, Error::RevokedPlugin { .. }), "{err}");
+    }
+
+    #[test]
+    fn artifact_without_sidecars_is_unsigned() {
+        let dir = tempdir().unwrap();
+        let store = store_at(&dir);
+        let path = dir.path().join("loose.so");
+        std::fs::write(&path, b"x").unwrap();
+        let err = verify_artifact("loose", &path, b"x", &store).unwrap_err();
+        assert!(matches!(err, Error::UnsignedPlugin { .. }), "{err}");
+    }
+
+    #[test]
+    fn bad_signature_sidecar_is_refused() {
+        let dir = tempdir().unwrap();
+        let store = store_at(&dir);
+        store.add(root("ribose")).unwrap();
+        let path = installed(&dir, b"x", "ribose");
+        let mut sig = path.clone().into_os_string();
+        sig.push(".sig");
+        std::fs::write(sig, b"not a signature").unwrap();
+        let err = verify_artifact("botan", &path, b"x", &store).unwrap_err();
+        assert!(matches!(err, Error::UntrustedPlugin { .. }), "{err}");
+    }
+
     #[test]
     fn allows_untrusted_with_override() {
         let dir = tempdir().unwrap();
@@ -753,6 +936,40 @@ Expire-Date: 0
         }
     }
 
+    #[test]
+    fn verify_artifact_accepts_detached_signature_from_trusted_key() {
+        let Some(f) = Fixture::new() else {
+            eprintln!("skipping: gpg not available");
+            return;
+        };
+        let home = TempDir::new().unwrap();
+        let store = crate::trust::TrustStore::for_home(PathBuf::from(home.path()));
+        store
+            .add(crate::manifest::TrustRoot {
+                name: "ribose".to_string(),
+                key_id: "0x1".to_string(),
+                fingerprint: "AAAA".to_string(),
+                key_url: "/publishers/ribose.asc".to_string(),
+            })
+            .unwrap();
+        store.set_public_key("ribose", &f.export_pubkey()).unwrap();
+
+        let artifact = b"\x7fELF signed plugin";
+        let path = home.path().join("signed.so");
+        fs::write(&path, artifact).unwrap();
+        let payload = signed_payload("signed", &sha256_hex(artifact));
+        fs::write(home.path().join("signed.so.asc"), f.sign_detached(&payload)).unwrap();
+
+        let check = verify_artifact("signed", &path, artifact, &store).unwrap();
+        assert_eq!(check.evidence, Evidence::DetachedSignature);
+        assert_eq!(check.publisher, "ribose");
+        let err = verify_artifact("signed", &path, b"tampered", &store).unwrap_err();
+        assert!(matches!(err, Error::UntrustedPlugin { .. }), "{err}");
+        // The same bytes and signature do not vouch for another name.
+        let err = verify_artifact("other", &path, artifact, &store).unwrap_err();
+        assert!(matches!(err, Error::UntrustedPlugin { .. }), "{err}");
+    }
+
     #[test]
     fn verify_signature_accepts_valid_signature() {
         let f = match Fixture::new() {
@@ -0,0 +1,474 @@
+//! AEAD interface: AES-GCM, ChaCha20-Poly1305 and AES-256-GCM-SIV.
+//!
+//! The loader's AEAD contract is streaming: `encrypt_update` /
+//! `decrypt_update` emit as many bytes as they consume, then the
+//! encrypt side calls `finalize` for the tag and the decrypt side calls
+//! `verify_tag`.
+//!
+//! - **AES-GCM and ChaCha20-Poly1305** are CTR-mode constructions, so
+//!   each update XORs the chunk with the same keystream the one-shot
+//!   AEAD would use (`Ctr32BE` from `J0 + 1` for GCM, ChaCha20 from
+//!   block 1) and keeps the chunk for the tag. The tag is computed by
+//!   the RustCrypto AEAD over the buffered data at `finalize` /
+//!   `verify_tag`. As with every streaming AEAD, decrypted bytes are
+//!   released before the tag is checked; callers must discard them if
+//!   `verify_tag` fails.
+//! - **AES-256-GCM-SIV** derives its IV from the whole plaintext, so it
+//!   cannot stream. Encryption takes exactly one `encrypt_update` call.
+//!   Decryption takes exactly one `decrypt_update` call whose input is
+//!   `ciphertext || tag`; plaintext is written only if it is authentic.
+//!   `verify_tag` then confirms the caller's tag matches the one
+//!   consumed (or, if no data was decrypted, authenticates the empty
+//!   message).
+//!
+//! Nonces are 12 bytes and tags 16 bytes for all four algorithms.
+
+use aes::cipher::{KeyIvInit, StreamCipher, StreamCipherSeek};
+use aes_gcm::aead::{AeadInOut, KeyInit, Nonce, Tag};
+use confium_api::AeadPlugin;
+use confium_api::PluginError;
+use confium_api::error::PluginResult;
+use confium_api::options::OptionView;
+use confium_macros::plugin_interface;
+use subtle::ConstantTimeEq;
+use zeroize::Zeroizing;
+
+use crate::{check_buffer, invalid, unsupported};
+
+/// Nonce length for every algorithm in this module.
+pub const NONCE_LEN: usize = 12;
+/// Tag length for every algorithm in this module.
+pub const TAG_LEN: usize = 16;
+
+/// AEAD algorithms this plugin implements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AeadAlg {
+    Aes128Gcm,
+    Aes256Gcm,
+    ChaCha20Poly1305,
+    Aes256GcmSiv,
+}
+
+impl AeadAlg {
+    /// Resolve a caller-supplied name (case-insensitive).
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name.to_ascii_lowercase().as_str() {
+            "aes-128-gcm" | "aes128-gcm" => Self::Aes128Gcm,
+            "aes-256-gcm" | "aes256-gcm" => Self::Aes256Gcm,
+            "chacha20-poly1305" | "chacha20poly1305" => Self::ChaCha20Poly1305,
+            "aes-256-gcm-siv" | "aes256-gcm-siv" => Self::Aes256GcmSiv,
+            _ => return None,
+        })
+    }
+
+    /// Key length in bytes.
+    pub fn key_len(self) -> usize {
+        match self {
+            Self::Aes128Gcm => 16,
+            Self::Aes256Gcm | Self::ChaCha20Poly1305 | Self::Aes256GcmSiv => 32,
+        }
+    }
+
+    /// Encrypt `buf` in place, returning the detached tag.
+    fn seal(self, key: &[u8], nonce: &[u8], aad: &[u8], buf: &mut [u8]) -> PluginResult<Vec<u8>> {
+        match self {
+            Self::Aes128Gcm => seal::<aes_gcm::Aes128Gcm>(key, nonce, aad, buf),
+            Self::Aes256Gcm => seal::<aes_gcm::Aes256Gcm>(key, nonce, aad, buf),
+            Self::ChaCha20Poly1305 => {
+                seal::<chacha20poly1305::ChaCha20Poly1305>(key, nonce, aad, buf)
+            }
+            Self::Aes256GcmSiv => seal::<aes_gcm_siv::Aes256GcmSiv>(key, nonce, aad, buf),
+        }
+    }
+
+    /// Authenticate and decrypt `buf` in place.
+    fn open(
+        self,
+        key: &[u8],
+        nonce: &[u8],
+        aad: &[u8],
+        buf: &mut [u8],
+        tag: &[u8],
+    ) -> PluginResult<()> {
+        match self {
+            Self::Aes128Gcm => open::<aes_gcm::Aes128Gcm>(key, nonce, aad, buf, tag),
+            Self::Aes256Gcm => open::<aes_gcm::Aes256Gcm>(key, nonce, aad, buf, tag),
+            Self::ChaCha20Poly1305 => {
+                open::<chacha20poly1305::ChaCha20Poly1305>(key, nonce, aad, buf, tag)
+            }
+            Self::Aes256GcmSiv => open::<aes_gcm_siv::Aes256GcmSiv>(key, nonce, aad, buf, tag),
+        }
+    }
+
+    /// Keystream positioned where the AEAD starts encrypting payload,
+    /// or `None` for the one-shot SIV mode.
+    fn keystream(self, key: &[u8], nonce: &[u8]) -> PluginResult<Option<Keystream>> {
+        let bad_key = |_| invalid("rustcrypto: bad AEAD key or nonce length");
+        // GCM: J0 = nonce || 0^31 || 1; payload starts at inc32(J0).
+        let mut gcm_iv = [0u8; 16];
+        gcm_iv[..NONCE_LEN].copy_from_slice(nonce);
+        gcm_iv[15] = 2;
+        Ok(Some(match self {
+            Self::Aes128Gcm => Keystream::Aes128(
+                ctr::Ctr32BE::<aes::Aes128>::new_from_slices(key, &gcm_iv).map_err(bad_key)?,
+            ),
+            Self::Aes256Gcm => Keystream::Aes256(
+                ctr::Ctr32BE::<aes::Aes256>::new_from_slices(key, &gcm_iv).map_err(bad_key)?,
+            ),
+            Self::ChaCha20Poly1305 => {
+                // Block 0 is the Poly1305 key; payload starts at block 1.
+                let mut c = chacha20::ChaCha20::new_from_slices(key, nonce).map_err(bad_key)?;
+                c.seek(64u64);
+                Keystream::ChaCha(c)
+            }
+            Self::Aes256GcmSiv => return Ok(None),
+        }))
+    }
+}
+
+fn seal<A: AeadInOut + KeyInit>(
+    key: &[u8],
+    nonce: &[u8],
+    aad: &[u8],
+    buf: &mut [u8],
+) -> PluginResult<Vec<u8>> {
+    let cipher = A::new_from_slice(key).map_err(|_| invalid("rustcrypto: bad AEAD key length"))?;
+    let nonce =
+        Nonce::<A>::try_from(nonce).map_err(|_| invalid("rustcrypto: bad AEAD nonce length"))?;
+    let tag = cipher
+        .encrypt_inout_detached(&nonce, aad, buf.into())
+        .map_err(|_| PluginError::msg("rustcrypto: AEAD encryption failed"))?;
+    Ok(tag.to_vec())
+}
+
+fn open<A: AeadInOut + KeyInit>(
+    key: &[u8],
+    nonce: &[u8],
+    aad: &[u8],
+    buf: &mut [u8],
+    tag: &[u8],
+) -> PluginResult<()> {
+    let cipher = A::new_from_slice(key).map_err(|_| invalid("rustcrypto: bad AEAD key length"))?;
+    let nonce =
+        Nonce::<A>::try_from(nonce).map_err(|_| invalid("rustcrypto: bad AEAD nonce length"))?;
+    let tag = Tag::<A>::try_from(tag).map_err(|_| invalid("rustcrypto: bad AEAD tag length"))?;
+    cipher
+        .decrypt_inout_detached(&nonce, aad, buf.into(), &tag)
+        .map_err(|_| invalid("rustcrypto: AEAD tag mismatch"))
+}
+
+/// Payload keystream for the streaming modes.
+#[allow(clippy::large_enum_variant)]
+enum Keystream {
+    Aes128(ctr::Ctr32BE<aes::Aes128>),
+    Aes256(ctr::Ctr32BE<aes::Aes256>),
+    ChaCha(chacha20::ChaCha20),
+}
+
+impl Keystream {
+    fn apply(&mut self, buf: &mut [u8]) {
+        match self {
+            Self::Aes128(c) => c.apply_keystream(buf),
+            Self::Aes256(c) => c.apply_keystream(buf),
+            Self::ChaCha(c) => c.apply_keystream(buf),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Encrypt,
+    Decrypt,
+}
+
+/// AEAD state for one message. `set_nonce` starts a new message.
+pub struct AeadState {
+    alg: AeadAlg,
+    key: Zeroizing<Vec<u8>>,
+    nonce: Option<[u8; NONCE_LEN]>,
+    aad: Vec<u8>,
+    keystream: Option<Keystream>,
+    direction: Option<Direction>,
+    /// Plaintext (encrypt) or ciphertext (decrypt) seen so far, kept
+    /// for the tag computation.
+    data: Zeroizing<Vec<u8>>,
+    /// SIV only: tag produced by encryption or consumed by decryption.
+    siv_tag: Option<Vec<u8>>,
+}
+
+impl AeadState {
+    fn nonce(&self) -> PluginResult<&[u8; NONCE_LEN]> {
+        self.nonce
+            .as_ref()
+            .ok_or_else(|| invalid("rustcrypto: AEAD nonce not set"))
+    }
+
+    fn begin(&mut self, want: Direction) -> PluginResult<()> {
+        self.nonce()?;
+        match self.direction {
+            None => self.direction = Some(want),
+            Some(d) if d == want => {}
+            Some(_) => return Err(invalid("rustcrypto: AEAD direction already fixed")),
+        }
+        if self.alg == AeadAlg::Aes256GcmSiv && self.siv_tag.is_some() {
+            return Err(invalid(
+                "rustcrypto: aes-256-gcm-siv takes the whole message in one update",
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[plugin_interface(name = "aead", version = 0)]
+impl AeadPlugin for AeadState {
+    fn create_with_key(
+        algorithm: &str,
+        key: &[u8],
+        _opts: Option<OptionView<'_>>,
+    ) -> PluginResult<Self> {
+        let alg = AeadAlg::from_name(algorithm).ok_or_else(|| unsupported(algorithm))?;
+        if key.len() != alg.key_len() {
+            return Err(invalid(format!(
+                "rustcrypto: {algorithm} needs a {}-byte key, got {}",
+                alg.key_len(),
+                key.len()
+            )));
+        }
+        Ok(Self {
+            alg,
+            key: Zeroizing::new(key.to_vec()),
+            nonce: None,
+            aad: Vec::new(),
+            keystream: None,
+            direction: None,
+            data: Zeroizing::new(Vec::new()),
+            siv_tag: None,
+        })
+    }
+
+    fn set_nonce(&mut self, nonce: &[u8]) -> PluginResult<()> {
+        let nonce: [u8; NONCE_LEN] = nonce
+            .try_into()
+            .map_err(|_| invalid(format!("rustcrypto: AEAD nonce must be {NONCE_LEN} bytes")))?;
+        self.keystream = self.alg.keystream(&self.key, &nonce)?;
+        self.nonce = Some(nonce);
+        self.aad.clear();
+        self.direction = None;
+        self.data.clear();
+        self.siv_tag = None;
+        Ok(())
+    }
+
+    fn associated_data_update(&mut self, data: &[u8]) -> PluginResult<()> {
+        if self.direction.is_some() {
+            return Err(invalid(
+                "rustcrypto: associated data must precede the payload",
+            ));
+        }
+        self.aad.extend_from_slice(data);
+        Ok(())
+    }
+
+    fn encrypt_update(&mut self, input: &[u8], output: &mut [u8]) -> PluginResult<usize> {
+        check_buffer(output.len(), input.len())?;
+        self.begin(Direction::Encrypt)?;
+        let out = &mut output[..input.len()];
+        out.copy_from_slice(input);
+        match self.keystream.as_mut() {
+            Some(ks) => {
+                self.data.extend_from_slice(input);
+                ks.apply(out);
+            }
+            None => {
+                let nonce = *self.nonce()?;
+                self.siv_tag = Some(self.alg.seal(&self.key, &nonce, &self.aad, out)?);
+            }
+        }
+        Ok(input.len())
+    }
+
+    fn decrypt_update(&mut self, input: &[u8], output: &mut [u8]) -> PluginResult<usize> {
+        self.begin(Direction::Decrypt)?;
+        match self.keystream.as_mut() {
+            Some(ks) => {
+                check_buffer(output.len(), input.len())?;
+                let out = &mut output[..input.len()];
+                out.copy_from_slice(input);
+                ks.apply(out);
+                self.data.extend_from_slice(input);
+                Ok(input.len())
+            }
+            None => {
+                let Some(ct_len) = input.len().checked_sub(TAG_LEN) else {
+                    return Err(invalid(
+                        "rustcrypto: aes-256-gcm-siv decrypt input is ciphertext || tag",
+                    ));
+                };
+                check_buffer(output.len(), ct_len)?;
+                let (ct, tag) = input.split_at(ct_len);
+                let mut buf = Zeroizing::new(ct.to_vec());
+                let nonce = *self.nonce()?;
+                self.alg.open(&self.key, &nonce, &self.aad, &mut buf, tag)?;
+                output[..ct_len].copy_from_slice(&buf);
+                self.siv_tag = Some(tag.to_vec());
+                Ok(ct_len)
+            }
+        }
+    }
+
+    fn finalize(&mut self, tag: &mut [u8]) -> PluginResult<usize> {
+        check_buffer(tag.len(), TAG_LEN)?;
+        if self.direction == Some(Direction::Decrypt) {
+            return Err(invalid("rustcrypto: finalize is the encrypt-side call"));
+        }
+        let nonce = *self.nonce()?;
+        let computed = match (&self.keystream, &self.siv_tag) {
+            (None, Some(t)) => t.clone(),
+            _ => {
+                let mut scratch = Zeroizing::new(self.data.to_vec());
+                self.alg.seal(&self.key, &nonce, &self.aad, &mut scratch)?
+            }
+        };
+        tag[..TAG_LEN].copy_from_slice(&computed);
+        Ok(TAG_LEN)
+    }
+
+    fn verify_tag(&mut self, tag: &[u8]) -> PluginResult<()> {
+        if self.direction == Some(Direction::Encrypt) {
+            return Err(invalid("rustcrypto: verify_tag is the decrypt-side call"));
+        }
+        if tag.len() != TAG_LEN {
+            return Err(invalid("rustcrypto: AEAD tag mismatch"));
+        }
+        let nonce = *self.nonce()?;
+        if let (None, Some(consumed)) = (&self.keystream, &self.siv_tag) {
+            // SIV already authenticated the message in decrypt_update;
+            // the caller's tag must be the one that was checked.
+            return if bool::from(consumed.ct_eq(tag)) {
+                Ok(())
+            } else {
+                Err(invalid("rustcrypto: AEAD tag mismatch"))
+            };
+        }
+        let mut scratch = Zeroizing::new(self.data.to_vec());
+        self.alg
+            .open(&self.key, &nonce, &self.aad, &mut scratch, tag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use confium_api::ErrorCode;
+
+    fn encrypt(alg: &str, key: &[u8], nonce: &[u8], aad: &[u8], pt: &[u8]) -> (Vec<u8>, Vec<u8>) {
+        let mut s = AeadState::create_with_key(alg, key, None).unwrap();
+        s.set_nonce(nonce).unwrap();
+        s.associated_data_update(aad).unwrap();
+        let mut ct = vec![0u8; pt.len()];
+        if alg.ends_with("siv") {
+            s.encrypt_update(pt, &mut ct).unwrap();
+        } else {
+            // Feed in uneven chunks to exercise keystream continuity.
+            let mut off = 0;
+            for chunk in pt.chunks(7) {
+                s.encrypt_update(chunk, &mut ct[off..off + chunk.len()])
+                    .unwrap();
+                off += chunk.len();
+            }
+        }
+        let mut tag = [0u8; TAG_LEN];
+        assert_eq!(s.finalize(&mut tag).unwrap(), TAG_LEN);
+        (ct, tag.to_vec())
+    }
+
+    #[test]
+    fn aes_128_gcm_known_answer() {
+        // GCM spec test case 3 (McGrew & Viega).
+        let key = hex::decode("feffe9928665731c6d6a8f9467308308").unwrap();
+        let nonce = hex::decode("cafebabefacedbaddecaf888").unwrap();
+        let pt = hex::decode(
+            "d9313225f88406e5a55909c5aff5269a86a7a9531534f7da2e4c303d8a318a72\
+             1c3c0c95956809532fcf0e2449a6b525b16aedf5aa0de657ba637b391aafd255",
+        )
+        .unwrap();
+        let (ct, tag) = encrypt("AES-128-GCM", &key, &nonce, &[], &pt);
+        assert_eq!(
+            hex::encode(&ct),
+            "42831ec2217774244b7221b784d0d49ce3aa212f2c02a4e035c17e2329aca12e\
+             21d514b25466931c7d8f6a5aac84aa051ba30b396a0aac973d58e091473f5985"
+        );
+        assert_eq!(hex::encode(tag), "4d5c2af327cd64a62cf35abd2ba6fab4");
+    }
+
+    #[test]
+    fn streaming_matches_one_shot_and_round_trips() {
+        for alg in ["aes-128-gcm", "aes-256-gcm", "chacha20-poly1305"] {
+            let a = AeadAlg::from_name(alg).unwrap();
+            let key = vec![0x42u8; a.key_len()];
+            let nonce = [7u8; NONCE_LEN];
+            let pt: Vec<u8> = (0..100u8).collect();
+            let (ct, tag) = encrypt(alg, &key, &nonce, b"hdr", &pt);
+
+            let mut one_shot = pt.clone();
+            let want_tag = a.seal(&key, &nonce, b"hdr", &mut one_shot).unwrap();
+            assert_eq!(ct, one_shot, "{alg}");
+            assert_eq!(tag, want_tag, "{alg}");
+
+            let mut d = AeadState::create_with_key(alg, &key, None).unwrap();
+            d.set_nonce(&nonce).unwrap();
+            d.associated_data_update(b"hdr").unwrap();
+            let mut back = vec![0u8; ct.len()];
+            d.decrypt_update(&ct, &mut back).unwrap();
+            assert_eq!(back, pt);
+            d.verify_tag(&tag).unwrap();
+            let mut bad = tag.clone();
+            bad[0] ^= 1;
+            assert_eq!(
+                d.verify_tag(&bad).unwrap_err().code(),
+                ErrorCode::WRONG_TYPE
+            );
+        }
+    }
+
+    #[test]
+    fn siv_is_one_shot_and_authenticates_before_release() {
+        let key = [9u8; 32];
+        let nonce = [1u8; NONCE_LEN];
+        let (ct, tag) = encrypt("aes-256-gcm-siv", &key, &nonce, b"ad", b"attack at dawn");
+
+        let mut d = AeadState::create_with_key("aes-256-gcm-siv", &key, None).unwrap();
+        d.set_nonce(&nonce).unwrap();
+        d.associated_data_update(b"ad").unwrap();
+        let mut input = ct.clone();
+        input.extend_from_slice(&tag);
+        let mut out = vec![0u8; input.len()];
+        let n = d.decrypt_update(&input, &mut out).unwrap();
+        assert_eq!(&out[..n], b"attack at dawn");
+        d.verify_tag(&tag).unwrap();
+
+        let mut d = AeadState::create_with_key("aes-256-gcm-siv", &key, None).unwrap();
+        d.set_nonce(&nonce).unwrap();
+        input[0] ^= 1;
+        let mut out = vec![0u8; input.len()];
+        assert!(d.decrypt_update(&input, &mut out).is_err());
+        assert!(out.iter().all(|&b| b == 0), "no plaintext on failure");
+    }
+
+    #[test]
+    fn wrong_lengths_and_order_are_rejected() {
+        assert!(AeadState::create_with_key("aes-256-gcm", &[0u8; 16], None).is_err());
+        let mut s = AeadState::create_with_key("aes-128-gcm", &[0u8; 16], None).unwrap();
+        assert!(s.set_nonce(&[0u8; 8]).is_err());
+        assert!(
+            s.encrypt_update(b"x", &mut [0u8; 1]).is_err(),
+            "nonce unset"
+        );
+        s.set_nonce(&[0u8; NONCE_LEN]).unwrap();
+        s.encrypt_update(b"x", &mut [0u8; 1]).unwrap();
+        assert!(s.associated_data_update(b"late").is_err());
+        assert!(s.decrypt_update(b"x", &mut [0u8; 1]).is_err());
+        let err = s.encrypt_update(b"xy", &mut [0u8; 1]).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::INSUFFICIENT_BUFFER);
+    }
+}
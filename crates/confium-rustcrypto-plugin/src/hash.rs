@@ -0,0 +1,280 @@
+//! Hash interface: SHA-2, SHA-3 and BLAKE3.
+//!
+//! [`HashAlg`] is also the hash registry for the other modules: the
+//! signature `set_hash` and KDF `set_hash` entry points resolve their
+//! names through [`HashAlg::from_name`].
+
+use confium_api::HashPlugin;
+use confium_api::error::PluginResult;
+use confium_api::options::OptionView;
+use confium_macros::plugin_interface;
+use sha2::Digest;
+
+use crate::{check_buffer, unsupported};
+
+/// Hash algorithms this plugin implements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashAlg {
+    Sha224,
+    Sha256,
+    Sha384,
+    Sha512,
+    Sha512_256,
+    Sha3_224,
+    Sha3_256,
+    Sha3_384,
+    Sha3_512,
+    Blake3,
+}
+
+impl HashAlg {
+    /// Resolve a caller-supplied name. Case-insensitive; the hyphen
+    /// after `sha` is optional (`SHA256` and `sha-256` both work).
+    pub fn from_name(name: &str) -> Option<Self> {
+        let lower = name.to_ascii_lowercase();
+        let key = match lower.strip_prefix("sha-") {
+            Some(rest) => format!("sha{rest}"),
+            None => lower,
+        };
+        Some(match key.as_str() {
+            "sha224" => Self::Sha224,
+            "sha256" => Self::Sha256,
+            "sha384" => Self::Sha384,
+            "sha512" => Self::Sha512,
+            "sha512/256" | "sha512-256" => Self::Sha512_256,
+            "sha3-224" => Self::Sha3_224,
+            "sha3-256" => Self::Sha3_256,
+            "sha3-384" => Self::Sha3_384,
+            "sha3-512" => Self::Sha3_512,
+            "blake3" => Self::Blake3,
+            _ => return None,
+        })
+    }
+
+    /// Canonical name, as reported in errors and by the key-format
+    /// interface.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Sha224 => "sha-224",
+            Self::Sha256 => "sha-256",
+            Self::Sha384 => "sha-384",
+            Self::Sha512 => "sha-512",
+            Self::Sha512_256 => "sha-512/256",
+            Self::Sha3_224 => "sha3-224",
+            Self::Sha3_256 => "sha3-256",
+            Self::Sha3_384 => "sha3-384",
+            Self::Sha3_512 => "sha3-512",
+            Self::Blake3 => "blake3",
+        }
+    }
+
+    /// Digest length in bytes.
+    pub fn output_size(self) -> usize {
+        match self {
+            Self::Sha224 | Self::Sha3_224 => 28,
+            Self::Sha256 | Self::Sha512_256 | Self::Sha3_256 | Self::Blake3 => 32,
+            Self::Sha384 | Self::Sha3_384 => 48,
+            Self::Sha512 | Self::Sha3_512 => 64,
+        }
+    }
+
+    /// Internal block (or sponge rate) size in bytes.
+    pub fn block_size(self) -> usize {
+        match self {
+            Self::Sha224 | Self::Sha256 | Self::Blake3 => 64,
+            Self::Sha384 | Self::Sha512 | Self::Sha512_256 => 128,
+            Self::Sha3_224 => 144,
+            Self::Sha3_256 => 136,
+            Self::Sha3_384 => 104,
+            Self::Sha3_512 => 72,
+        }
+    }
+
+    /// One-shot digest of `data`.
+    pub fn digest(self, data: &[u8]) -> Vec<u8> {
+        let mut state = Hasher::new(self);
+        state.absorb(data);
+        state.finalize_vec()
+    }
+}
+
+/// Hash state. BLAKE3's hasher is large, so it is boxed to keep the
+/// enum small for the SHA variants.
+#[derive(Clone)]
+pub enum Hasher {
+    Sha224(sha2::Sha224),
+    Sha256(sha2::Sha256),
+    Sha384(sha2::Sha384),
+    Sha512(sha2::Sha512),
+    Sha512_256(sha2::Sha512_256),
+    Sha3_224(sha3::Sha3_224),
+    Sha3_256(sha3::Sha3_256),
+    Sha3_384(sha3::Sha3_384),
+    Sha3_512(sha3::Sha3_512),
+    Blake3(Box<blake3::Hasher>),
+}
+
+/// Apply `$body` to the inner `Digest` state of every variant except
+/// BLAKE3, which is handled by `$blake3`.
+macro_rules! each_digest {
+    ($self:expr, $h:ident => $body:expr, $b:ident => $blake3:expr) => {
+        match $self {
+            Hasher::Sha224($h) => $body,
+            Hasher::Sha256($h) => $body,
+            Hasher::Sha384($h) => $body,
+            Hasher::Sha512($h) => $body,
+            Hasher::Sha512_256($h) => $body,
+            Hasher::Sha3_224($h) => $body,
+            Hasher::Sha3_256($h) => $body,
+            Hasher::Sha3_384($h) => $body,
+            Hasher::Sha3_512($h) => $body,
+            Hasher::Blake3($b) => $blake3,
+        }
+    };
+}
+
+impl Hasher {
+    /// Fresh state for `alg`.
+    pub fn new(alg: HashAlg) -> Self {
+        match alg {
+            HashAlg::Sha224 => Self::Sha224(sha2::Sha224::new()),
+            HashAlg::Sha256 => Self::Sha256(sha2::Sha256::new()),
+            HashAlg::Sha384 => Self::Sha384(sha2::Sha384::new()),
+            HashAlg::Sha512 => Self::Sha512(sha2::Sha512::new()),
+            HashAlg::Sha512_256 => Self::Sha512_256(sha2::Sha512_256::new()),
+            HashAlg::Sha3_224 => Self::Sha3_224(sha3::Sha3_224::new()),
+            HashAlg::Sha3_256 => Self::Sha3_256(sha3::Sha3_256::new()),
+            HashAlg::Sha3_384 => Self::Sha3_384(sha3::Sha3_384::new()),
+            HashAlg::Sha3_512 => Self::Sha3_512(sha3::Sha3_512::new()),
+            HashAlg::Blake3 => Self::Blake3(Box::new(blake3::Hasher::new())),
+        }
+    }
+
+    /// Algorithm of this state.
+    pub fn alg(&self) -> HashAlg {
+        match self {
+            Self::Sha224(_) => HashAlg::Sha224,
+            Self::Sha256(_) => HashAlg::Sha256,
+            Self::Sha384(_) => HashAlg::Sha384,
+            Self::Sha512(_) => HashAlg::Sha512,
+            Self::Sha512_256(_) => HashAlg::Sha512_256,
+            Self::Sha3_224(_) => HashAlg::Sha3_224,
+            Self::Sha3_256(_) => HashAlg::Sha3_256,
+            Self::Sha3_384(_) => HashAlg::Sha3_384,
+            Self::Sha3_512(_) => HashAlg::Sha3_512,
+            Self::Blake3(_) => HashAlg::Blake3,
+        }
+    }
+
+    fn absorb(&mut self, data: &[u8]) {
+        each_digest!(self, h => Digest::update(h, data), b => {
+            b.update(data);
+        })
+    }
+
+    /// Finalize into a fresh vector and reset the state.
+    fn finalize_vec(&mut self) -> Vec<u8> {
+        each_digest!(self, h => h.finalize_reset().to_vec(), b => {
+            let out = b.finalize().as_bytes().to_vec();
+            b.reset();
+            out
+        })
+    }
+}
+
+#[plugin_interface(name = "hash", version = 0)]
+impl HashPlugin for Hasher {
+    fn create_with_opts(name: &str, _opts: Option<OptionView<'_>>) -> PluginResult<Self> {
+        HashAlg::from_name(name)
+            .map(Hasher::new)
+            .ok_or_else(|| unsupported(name))
+    }
+
+    fn output_size(&self) -> u32 {
+        self.alg().output_size() as u32
+    }
+
+    fn block_size(&self) -> u32 {
+        self.alg().block_size() as u32
+    }
+
+    fn update(&mut self, data: &[u8]) -> PluginResult<()> {
+        self.absorb(data);
+        Ok(())
+    }
+
+    fn reset(&mut self) -> PluginResult<()> {
+        *self = Hasher::new(self.alg());
+        Ok(())
+    }
+
+    fn try_clone(&self) -> PluginResult<Self> {
+        Ok(self.clone())
+    }
+
+    fn finalize(&mut self, out: &mut [u8]) -> PluginResult<()> {
+        let n = self.alg().output_size();
+        check_buffer(out.len(), n)?;
+        out[..n].copy_from_slice(&self.finalize_vec());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_resolve_case_insensitively() {
+        assert_eq!(HashAlg::from_name("SHA-256"), Some(HashAlg::Sha256));
+        assert_eq!(HashAlg::from_name("sha256"), Some(HashAlg::Sha256));
+        assert_eq!(HashAlg::from_name("SHA-512/256"), Some(HashAlg::Sha512_256));
+        assert_eq!(HashAlg::from_name("Sha3-384"), Some(HashAlg::Sha3_384));
+        assert_eq!(HashAlg::from_name("md5"), None);
+    }
+
+    #[test]
+    fn known_answers() {
+        let cases = [
+            (
+                HashAlg::Sha256,
+                "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+            ),
+            (
+                HashAlg::Sha3_256,
+                "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532",
+            ),
+            (
+                HashAlg::Sha512_256,
+                "53048e2681941ef99b2e29b76b4c7dabe4c2d0c634fc6d46e0e2f13107e7af23",
+            ),
+        ];
+        for (alg, want) in cases {
+            assert_eq!(hex::encode(alg.digest(b"abc")), want, "{}", alg.name());
+        }
+        assert_eq!(
+            hex::encode(HashAlg::Blake3.digest(b"")),
+            "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262"
+        );
+    }
+
+    #[test]
+    fn finalize_resets_and_clone_is_independent() {
+        let mut h = Hasher::create_with_opts("sha-384", None).unwrap();
+        HashPlugin::update(&mut h, b"ab").unwrap();
+        let mut fork = h.try_clone().unwrap();
+        HashPlugin::update(&mut fork, b"c").unwrap();
+        let mut out = [0u8; 48];
+        fork.finalize(&mut out).unwrap();
+        assert_eq!(out.to_vec(), HashAlg::Sha384.digest(b"abc"));
+        h.finalize(&mut out).unwrap();
+        assert_eq!(out.to_vec(), HashAlg::Sha384.digest(b"ab"));
+        assert!(h.finalize(&mut [0u8; 47]).is_err());
+    }
+
+    #[test]
+    fn unknown_algorithm_is_unsupported() {
+        let err = Hasher::create_with_opts("md5", None).err().unwrap();
+        assert_eq!(err.code(), confium_api::ErrorCode::UNSUPPORTED_ALGORITHM);
+    }
+}
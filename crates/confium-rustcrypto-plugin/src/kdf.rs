@@ -0,0 +1,254 @@
+//! KDF interface: HKDF, PBKDF2 and Argon2id.
+//!
+//! | algorithm  | salt | iterations | memory cost | parallelism | hash |
+//! |------------|------|------------|-------------|-------------|------|
+//! | `hkdf`     | optional | — | — | — | SHA-2, default SHA-256 |
+//! | `pbkdf2`   | required | required | — | — | SHA-2 (HMAC), default SHA-256 |
+//! | `argon2id` | required | passes, default 2 | bytes, default 19 MiB | lanes, default 1 | — |
+//!
+//! HKDF's `info` string has no setter in the KDF interface, so it is
+//! passed as the `info` option (hex-encoded) when the instance is
+//! created.
+
+use confium_api::KdfPlugin;
+use confium_api::error::PluginResult;
+use confium_api::options::OptionView;
+use confium_macros::plugin_interface;
+use sha2::{Sha224, Sha256, Sha384, Sha512};
+use zeroize::Zeroizing;
+
+use crate::hash::HashAlg;
+use crate::{invalid, unsupported};
+
+/// KDFs this plugin implements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KdfAlg {
+    Hkdf,
+    Pbkdf2,
+    Argon2id,
+}
+
+impl KdfAlg {
+    /// Resolve a caller-supplied name (case-insensitive).
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name.to_ascii_lowercase().as_str() {
+            "hkdf" => Self::Hkdf,
+            "pbkdf2" => Self::Pbkdf2,
+            "argon2id" => Self::Argon2id,
+            _ => return None,
+        })
+    }
+}
+
+/// KDF parameters collected through the setters, consumed by `derive`.
+pub struct KdfState {
+    alg: KdfAlg,
+    salt: Option<Zeroizing<Vec<u8>>>,
+    info: Vec<u8>,
+    iterations: Option<u32>,
+    memory_cost: Option<u64>,
+    parallelism: Option<u32>,
+    hash: HashAlg,
+}
+
+impl KdfState {
+    fn not_applicable(&self, param: &str) -> confium_api::PluginError {
+        invalid(format!("rustcrypto: {:?} takes no {param}", self.alg))
+    }
+
+    fn salt(&self) -> PluginResult<&[u8]> {
+        self.salt
+            .as_deref()
+            .map(Vec::as_slice)
+            .ok_or_else(|| invalid("rustcrypto: salt not set"))
+    }
+
+    fn hkdf(&self, ikm: &[u8], out: &mut [u8]) -> PluginResult<()> {
+        fn run<H>(salt: Option<&[u8]>, ikm: &[u8], info: &[u8], out: &mut [u8]) -> PluginResult<()>
+        where
+            H: hkdf::hmac::EagerHash + Clone,
+        {
+            hkdf::Hkdf::<H>::new(salt, ikm)
+                .expand(info, out)
+                .map_err(|_| invalid("rustcrypto: HKDF output longer than 255 hash blocks"))
+        }
+        let salt = self.salt.as_deref().map(Vec::as_slice);
+        match self.hash {
+            HashAlg::Sha224 => run::<Sha224>(salt, ikm, &self.info, out),
+            HashAlg::Sha256 => run::<Sha256>(salt, ikm, &self.info, out),
+            HashAlg::Sha384 => run::<Sha384>(salt, ikm, &self.info, out),
+            HashAlg::Sha512 => run::<Sha512>(salt, ikm, &self.info, out),
+            other => Err(unsupported(other.name())),
+        }
+    }
+
+    fn pbkdf2(&self, password: &[u8], out: &mut [u8]) -> PluginResult<()> {
+        let salt = self.salt()?;
+        let rounds = self
+            .iterations
+            .ok_or_else(|| invalid("rustcrypto: PBKDF2 iteration count not set"))?;
+        match self.hash {
+            HashAlg::Sha224 => pbkdf2::pbkdf2_hmac::<Sha224>(password, salt, rounds, out),
+            HashAlg::Sha256 => pbkdf2::pbkdf2_hmac::<Sha256>(password, salt, rounds, out),
+            HashAlg::Sha384 => pbkdf2::pbkdf2_hmac::<Sha384>(password, salt, rounds, out),
+            HashAlg::Sha512 => pbkdf2::pbkdf2_hmac::<Sha512>(password, salt, rounds, out),
+            other => return Err(unsupported(other.name())),
+        }
+        Ok(())
+    }
+
+    fn argon2id(&self, password: &[u8], out: &mut [u8]) -> PluginResult<()> {
+        let salt = self.salt()?;
+        let m_cost = match self.memory_cost {
+            Some(bytes) => u32::try_from(bytes / 1024)
+                .map_err(|_| invalid("rustcrypto: Argon2 memory cost too large"))?,
+            None => argon2::Params::DEFAULT_M_COST,
+        };
+        let params = argon2::Params::new(
+            m_cost,
+            self.iterations.unwrap_or(argon2::Params::DEFAULT_T_COST),
+            self.parallelism.unwrap_or(argon2::Params::DEFAULT_P_COST),
+            Some(out.len()),
+        )
+        .map_err(|e| invalid(format!("rustcrypto: Argon2 parameters: {e}")))?;
+        argon2::Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params)
+            .hash_password_into(password, salt, out)
+            .map_err(|e| invalid(format!("rustcrypto: Argon2: {e}")))
+    }
+}
+
+#[plugin_interface(name = "kdf", version = 0)]
+impl KdfPlugin for KdfState {
+    fn create(algorithm: &str, opts: Option<OptionView<'_>>) -> PluginResult<Self> {
+        let alg = KdfAlg::from_name(algorithm).ok_or_else(|| unsupported(algorithm))?;
+        let info = match opts.as_ref().and_then(|o| o.get_str("info")) {
+            Some(_) if alg != KdfAlg::Hkdf => {
+                return Err(invalid("rustcrypto: only HKDF takes an info option"));
+            }
+            Some(h) => hex::decode(h).map_err(|_| invalid("rustcrypto: info option is not hex"))?,
+            None => Vec::new(),
+        };
+        Ok(Self {
+            alg,
+            salt: None,
+            info,
+            iterations: None,
+            memory_cost: None,
+            parallelism: None,
+            hash: HashAlg::Sha256,
+        })
+    }
+
+    fn set_salt(&mut self, salt: &[u8]) -> PluginResult<()> {
+        self.salt = Some(Zeroizing::new(salt.to_vec()));
+        Ok(())
+    }
+
+    fn set_iterations(&mut self, iterations: u32) -> PluginResult<()> {
+        if self.alg == KdfAlg::Hkdf {
+            return Err(self.not_applicable("iteration count"));
+        }
+        if iterations == 0 {
+            return Err(invalid("rustcrypto: iteration count must be positive"));
+        }
+        self.iterations = Some(iterations);
+        Ok(())
+    }
+
+    fn set_memory_cost(&mut self, bytes: u64) -> PluginResult<()> {
+        if self.alg != KdfAlg::Argon2id {
+            return Err(self.not_applicable("memory cost"));
+        }
+        self.memory_cost = Some(bytes);
+        Ok(())
+    }
+
+    fn set_parallelism(&mut self, lanes: u32) -> PluginResult<()> {
+        if self.alg != KdfAlg::Argon2id {
+            return Err(self.not_applicable("parallelism"));
+        }
+        self.parallelism = Some(lanes);
+        Ok(())
+    }
+
+    fn set_hash(&mut self, hash_name: &str) -> PluginResult<()> {
+        if self.alg == KdfAlg::Argon2id {
+            return Err(self.not_applicable("hash"));
+        }
+        self.hash = HashAlg::from_name(hash_name).ok_or_else(|| unsupported(hash_name))?;
+        Ok(())
+    }
+
+    fn derive(&mut self, input: &[u8], out: &mut [u8]) -> PluginResult<()> {
+        match self.alg {
+            KdfAlg::Hkdf => self.hkdf(input, out),
+            KdfAlg::Pbkdf2 => self.pbkdf2(input, out),
+            KdfAlg::Argon2id => self.argon2id(input, out),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use confium_api::options::{OptionMap, OptionValue};
+
+    #[test]
+    fn hkdf_rfc5869_case_1() {
+        let mut opts = OptionMap::new();
+        opts.insert(
+            "info".into(),
+            OptionValue::String("f0f1f2f3f4f5f6f7f8f9".into()),
+        );
+        let mut k = KdfState::create("HKDF", Some(OptionView::new(&opts))).unwrap();
+        k.set_salt(&hex::decode("000102030405060708090a0b0c").unwrap())
+            .unwrap();
+        let mut okm = [0u8; 42];
+        k.derive(&[0x0b; 22], &mut okm).unwrap();
+        assert_eq!(
+            hex::encode(okm),
+            "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf\
+             34007208d5b887185865"
+        );
+    }
+
+    #[test]
+    fn pbkdf2_rfc7914_vector() {
+        let mut k = KdfState::create("pbkdf2", None).unwrap();
+        k.set_salt(b"salt").unwrap();
+        k.set_iterations(1).unwrap();
+        let mut out = [0u8; 64];
+        k.derive(b"passwd", &mut out).unwrap();
+        assert_eq!(
+            hex::encode(out),
+            "55ac046e56e3089fec1691c22544b605f94185216dde0465e68b9d57c20dacbc\
+             49ca9cccf179b645991664b39d77ef317c71b845b1e30bd509112041d3a19783"
+        );
+    }
+
+    #[test]
+    fn argon2id_is_deterministic_and_salt_sensitive() {
+        let derive = |salt: &[u8]| {
+            let mut k = KdfState::create("argon2id", None).unwrap();
+            k.set_salt(salt).unwrap();
+            k.set_memory_cost(64 * 1024).unwrap();
+            k.set_iterations(1).unwrap();
+            let mut out = [0u8; 32];
+            k.derive(b"password", &mut out).unwrap();
+            out
+        };
+        assert_eq!(derive(b"somesaltsomesalt"), derive(b"somesaltsomesalt"));
+        assert_ne!(derive(b"somesaltsomesalt"), derive(b"othersaltothersa"));
+    }
+
+    #[test]
+    fn inapplicable_parameters_are_rejected() {
+        let mut k = KdfState::create("hkdf", None).unwrap();
+        assert!(k.set_iterations(10).is_err());
+        assert!(k.set_hash("md5").is_err());
+        let mut k = KdfState::create("pbkdf2", None).unwrap();
+        k.set_salt(b"s").unwrap();
+        assert!(k.derive(b"p", &mut [0u8; 16]).is_err(), "iterations unset");
+        assert!(KdfState::create("scrypt", None).is_err());
+    }
+}
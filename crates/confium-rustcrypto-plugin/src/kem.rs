@@ -0,0 +1,401 @@
+//! KEM interface: ML-KEM-768, ML-KEM-1024 and the X25519+ML-KEM-768
+//! hybrid.
+//!
+//! | algorithm | public key | secret key | ciphertext | seed |
+//! |-----------|------------|------------|------------|------|
+//! | `ml-kem-768`  | 1184 | 2400 | 1088 | 64 (`d ‖ z`) |
+//! | `ml-kem-1024` | 1568 | 3168 | 1568 | 64 (`d ‖ z`) |
+//! | `x25519-mlkem768` | 1184 + 32 | 2400 + 32 | 1088 + 32 | 96 (`d ‖ z ‖ x25519 sk`) |
+//!
+//! ML-KEM keys use the FIPS 203 encodings (expanded decapsulation
+//! key). The hybrid concatenates the ML-KEM part first and the X25519
+//! part second in every field, and combines the two shared secrets
+//! X-Wing style:
+//!
+//! ```text
+//! ss = SHA3-256(ss_M ‖ ss_X ‖ ct_X ‖ pk_X ‖ "\.//^\")
+//! ```
+//!
+//! Binding `ct_X` and `pk_X` keeps the combiner IND-CCA even though
+//! X25519 alone is not. All shared secrets are 32 bytes.
+
+use confium_api::KemPlugin;
+use confium_api::error::PluginResult;
+use confium_api::options::OptionView;
+use confium_api::plugin::kem::{KemEncapsulateResult, KemKeypair};
+use confium_macros::plugin_interface;
+use curve25519_dalek::montgomery::MontgomeryPoint;
+use ml_kem::kem::{Decapsulate as _, Encapsulate as _};
+use ml_kem::{EncodedSizeUser as _, KemCore as _};
+use sha3::{Digest, Sha3_256};
+use zeroize::Zeroizing;
+
+use crate::{check_buffer, invalid, os_random, unsupported};
+
+/// Shared secret length for every algorithm in this module.
+pub const SHARED_SECRET_LEN: usize = 32;
+/// X-Wing combiner label.
+const XWING_LABEL: &[u8; 6] = b"\\.//^\\";
+const X25519_LEN: usize = 32;
+
+/// KEMs this plugin implements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KemAlg {
+    MlKem768,
+    MlKem1024,
+    X25519MlKem768,
+}
+
+impl KemAlg {
+    /// Resolve a caller-supplied name (case-insensitive).
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name.to_ascii_lowercase().as_str() {
+            "ml-kem-768" => Self::MlKem768,
+            "ml-kem-1024" => Self::MlKem1024,
+            "x25519-mlkem768" | "x25519-ml-kem-768" => Self::X25519MlKem768,
+            _ => return None,
+        })
+    }
+
+    /// Seed length accepted by `keypair_generate`.
+    pub fn seed_len(self) -> usize {
+        match self {
+            Self::MlKem768 | Self::MlKem1024 => 64,
+            Self::X25519MlKem768 => 64 + X25519_LEN,
+        }
+    }
+}
+
+/// Run `$body` with `$K` bound to the ML-KEM parameter set underlying
+/// `$alg`.
+macro_rules! with_mlkem {
+    ($alg:expr, $K:ident => $body:expr) => {
+        match $alg {
+            KemAlg::MlKem768 | KemAlg::X25519MlKem768 => {
+                type $K = ml_kem::MlKem768;
+                $body
+            }
+            KemAlg::MlKem1024 => {
+                type $K = ml_kem::MlKem1024;
+                $body
+            }
+        }
+    };
+}
+
+/// ML-KEM encoded lengths `(ek, dk, ct)` for `alg`.
+fn mlkem_lens(alg: KemAlg) -> (usize, usize, usize) {
+    match alg {
+        KemAlg::MlKem768 | KemAlg::X25519MlKem768 => (1184, 2400, 1088),
+        KemAlg::MlKem1024 => (1568, 3168, 1568),
+    }
+}
+
+fn mlkem_generate(alg: KemAlg, seed: &[u8]) -> (Vec<u8>, Zeroizing<Vec<u8>>) {
+    let d: ml_kem::B32 = seed[..32].try_into().expect("32-byte d");
+    let z: ml_kem::B32 = seed[32..64].try_into().expect("32-byte z");
+    with_mlkem!(alg, K => {
+        let (dk, ek) = K::generate_deterministic(&d, &z);
+        (ek.as_bytes().to_vec(), Zeroizing::new(dk.as_bytes().to_vec()))
+    })
+}
+
+fn mlkem_encapsulate(alg: KemAlg, ek: &[u8]) -> PluginResult<(Vec<u8>, Zeroizing<Vec<u8>>)> {
+    with_mlkem!(alg, K => {
+        let encoded: ml_kem::Encoded<<K as ml_kem::KemCore>::EncapsulationKey> = ek
+            .try_into()
+            .map_err(|_| invalid("rustcrypto: wrong ML-KEM public key length"))?;
+        let ek = <K as ml_kem::KemCore>::EncapsulationKey::from_bytes(&encoded);
+        let (ct, ss) = ek
+            .encapsulate(&mut rand_core::OsRng)
+            .map_err(|_| confium_api::PluginError::msg("rustcrypto: ML-KEM encapsulation failed"))?;
+        Ok((ct.to_vec(), Zeroizing::new(ss.to_vec())))
+    })
+}
+
+fn mlkem_decapsulate(alg: KemAlg, dk: &[u8], ct: &[u8]) -> PluginResult<Zeroizing<Vec<u8>>> {
+    with_mlkem!(alg, K => {
+        let encoded: ml_kem::Encoded<<K as ml_kem::KemCore>::DecapsulationKey> = dk
+            .try_into()
+            .map_err(|_| invalid("rustcrypto: wrong ML-KEM secret key length"))?;
+        let dk = <K as ml_kem::KemCore>::DecapsulationKey::from_bytes(&encoded);
+        let ct: ml_kem::Ciphertext<K> = ct
+            .try_into()
+            .map_err(|_| invalid("rustcrypto: wrong ML-KEM ciphertext length"))?;
+        // ML-KEM rejects implicitly: a forged ciphertext yields an
+        // unrelated secret, never an error.
+        let ss = dk
+            .decapsulate(&ct)
+            .map_err(|_| confium_api::PluginError::msg("rustcrypto: ML-KEM decapsulation failed"))?;
+        Ok(Zeroizing::new(ss.to_vec()))
+    })
+}
+
+fn x25519(scalar: &[u8; 32], point: &[u8; 32]) -> PluginResult<Zeroizing<[u8; 32]>> {
+    let shared = MontgomeryPoint(*point).mul_clamped(*scalar).to_bytes();
+    // A low-order peer point forces an all-zero output (RFC 7748 §6.1).
+    if shared == [0u8; 32] {
+        return Err(invalid("rustcrypto: X25519 peer key is a low-order point"));
+    }
+    Ok(Zeroizing::new(shared))
+}
+
+fn x25519_public(scalar: &[u8; 32]) -> [u8; 32] {
+    MontgomeryPoint::mul_base_clamped(*scalar).to_bytes()
+}
+
+fn combine(ss_m: &[u8], ss_x: &[u8], ct_x: &[u8], pk_x: &[u8]) -> Zeroizing<Vec<u8>> {
+    let mut h = Sha3_256::new();
+    h.update(ss_m);
+    h.update(ss_x);
+    h.update(ct_x);
+    h.update(pk_x);
+    h.update(XWING_LABEL);
+    Zeroizing::new(h.finalize().to_vec())
+}
+
+fn split32(bytes: &[u8]) -> [u8; 32] {
+    bytes.try_into().expect("caller checked the length")
+}
+
+/// Generate a keypair from a [`KemAlg::seed_len`]-byte seed.
+pub fn generate(alg: KemAlg, seed: &[u8]) -> PluginResult<KemKeypair> {
+    if seed.len() != alg.seed_len() {
+        return Err(invalid(format!(
+            "rustcrypto: {alg:?} seed must be {} bytes",
+            alg.seed_len()
+        )));
+    }
+    let (mut public_key, mut secret_key) = mlkem_generate(alg, seed);
+    if alg == KemAlg::X25519MlKem768 {
+        let sk_x = split32(&seed[64..]);
+        public_key.extend_from_slice(&x25519_public(&sk_x));
+        secret_key.extend_from_slice(&sk_x);
+    }
+    Ok(KemKeypair {
+        public_key,
+        secret_key: secret_key.to_vec(),
+    })
+}
+
+/// Encapsulate to `pk`, returning `(ciphertext, shared_secret)`.
+pub fn encapsulate(alg: KemAlg, pk: &[u8]) -> PluginResult<(Vec<u8>, Zeroizing<Vec<u8>>)> {
+    if alg != KemAlg::X25519MlKem768 {
+        return mlkem_encapsulate(alg, pk);
+    }
+    let (ek_len, _, _) = mlkem_lens(alg);
+    if pk.len() != ek_len + X25519_LEN {
+        return Err(invalid(
+            "rustcrypto: wrong x25519-mlkem768 public key length",
+        ));
+    }
+    let (ek, pk_x) = pk.split_at(ek_len);
+    let (mut ct, ss_m) = mlkem_encapsulate(alg, ek)?;
+    let mut eph = Zeroizing::new([0u8; 32]);
+    os_random(eph.as_mut())?;
+    let ct_x = x25519_public(&eph);
+    let ss_x = x25519(&eph, &split32(pk_x))?;
+    let ss = combine(&ss_m, ss_x.as_ref(), &ct_x, pk_x);
+    ct.extend_from_slice(&ct_x);
+    Ok((ct, ss))
+}
+
+/// Decapsulate `ct` with `sk`.
+pub fn decapsulate(alg: KemAlg, sk: &[u8], ct: &[u8]) -> PluginResult<Zeroizing<Vec<u8>>> {
+    if alg != KemAlg::X25519MlKem768 {
+        return mlkem_decapsulate(alg, sk, ct);
+    }
+    let (_, dk_len, ct_len) = mlkem_lens(alg);
+    if sk.len() != dk_len + X25519_LEN {
+        return Err(invalid(
+            "rustcrypto: wrong x25519-mlkem768 secret key length",
+        ));
+    }
+    if ct.len() != ct_len + X25519_LEN {
+        return Err(invalid(
+            "rustcrypto: wrong x25519-mlkem768 ciphertext length",
+        ));
+    }
+    let (dk, sk_x) = sk.split_at(dk_len);
+    let (ct_m, ct_x) = ct.split_at(ct_len);
+    let sk_x = Zeroizing::new(split32(sk_x));
+    let ss_m = mlkem_decapsulate(alg, dk, ct_m)?;
+    let ss_x = x25519(&sk_x, &split32(ct_x))?;
+    Ok(combine(&ss_m, ss_x.as_ref(), ct_x, &x25519_public(&sk_x)))
+}
+
+/// Encapsulator (holds the recipient public key) or decapsulator
+/// (holds the recipient secret key).
+pub enum KemState {
+    Encapsulator {
+        alg: KemAlg,
+        public_key: Vec<u8>,
+    },
+    Decapsulator {
+        alg: KemAlg,
+        secret_key: Zeroizing<Vec<u8>>,
+    },
+}
+
+#[plugin_interface(name = "kem", version = 0)]
+impl KemPlugin for KemState {
+    fn encapsulator_create(
+        algorithm: &str,
+        recipient_pubkey: &[u8],
+        _opts: Option<OptionView<'_>>,
+    ) -> PluginResult<Self> {
+        let alg = KemAlg::from_name(algorithm).ok_or_else(|| unsupported(algorithm))?;
+        let (ek_len, _, _) = mlkem_lens(alg);
+        let want = ek_len
+            + if alg == KemAlg::X25519MlKem768 {
+                X25519_LEN
+            } else {
+                0
+            };
+        if recipient_pubkey.len() != want {
+            return Err(invalid(format!(
+                "rustcrypto: {algorithm} public key is {want} bytes"
+            )));
+        }
+        Ok(Self::Encapsulator {
+            alg,
+            public_key: recipient_pubkey.to_vec(),
+        })
+    }
+
+    fn encapsulate(
+        &mut self,
+        ct_out: &mut [u8],
+        ss_out: &mut [u8],
+    ) -> PluginResult<KemEncapsulateResult> {
+        let Self::Encapsulator { alg, public_key } = self else {
+            return Err(invalid("rustcrypto: encapsulate on a decapsulator"));
+        };
+        let (ct, ss) = encapsulate(*alg, public_key)?;
+        check_buffer(ct_out.len(), ct.len())?;
+        check_buffer(ss_out.len(), ss.len())?;
+        Ok(KemEncapsulateResult {
+            ciphertext: ct,
+            shared_secret: ss.to_vec(),
+        })
+    }
+
+    fn decapsulator_create(
+        algorithm: &str,
+        recipient_seckey: &[u8],
+        _opts: Option<OptionView<'_>>,
+    ) -> PluginResult<Self> {
+        let alg = KemAlg::from_name(algorithm).ok_or_else(|| unsupported(algorithm))?;
+        let (_, dk_len, _) = mlkem_lens(alg);
+        let want = dk_len
+            + if alg == KemAlg::X25519MlKem768 {
+                X25519_LEN
+            } else {
+                0
+            };
+        if recipient_seckey.len() != want {
+            return Err(invalid(format!(
+                "rustcrypto: {algorithm} secret key is {want} bytes"
+            )));
+        }
+        Ok(Self::Decapsulator {
+            alg,
+            secret_key: Zeroizing::new(recipient_seckey.to_vec()),
+        })
+    }
+
+    fn decapsulate(&mut self, ciphertext: &[u8], ss_out: &mut [u8]) -> PluginResult<usize> {
+        let Self::Decapsulator { alg, secret_key } = self else {
+            return Err(invalid("rustcrypto: decapsulate on an encapsulator"));
+        };
+        check_buffer(ss_out.len(), SHARED_SECRET_LEN)?;
+        let ss = decapsulate(*alg, secret_key, ciphertext)?;
+        ss_out[..ss.len()].copy_from_slice(&ss);
+        Ok(ss.len())
+    }
+
+    fn shared_secret_size(algorithm: &str) -> PluginResult<u32> {
+        KemAlg::from_name(algorithm)
+            .map(|_| SHARED_SECRET_LEN as u32)
+            .ok_or_else(|| unsupported(algorithm))
+    }
+
+    fn keypair_generate(
+        algorithm: &str,
+        seed: Option<&[u8]>,
+        _opts: Option<OptionView<'_>>,
+    ) -> PluginResult<KemKeypair> {
+        let alg = KemAlg::from_name(algorithm).ok_or_else(|| unsupported(algorithm))?;
+        match seed {
+            Some(seed) => generate(alg, seed),
+            None => {
+                let mut fresh = Zeroizing::new(vec![0u8; alg.seed_len()]);
+                os_random(&mut fresh)?;
+                generate(alg, &fresh)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(name: &str) {
+        let kp = KemState::keypair_generate(name, None, None).unwrap();
+        let mut enc = KemState::encapsulator_create(name, &kp.public_key, None).unwrap();
+        let mut ct = vec![0u8; 4096];
+        let mut ss = [0u8; SHARED_SECRET_LEN];
+        let res = enc.encapsulate(&mut ct, &mut ss).unwrap();
+        let mut dec = KemState::decapsulator_create(name, &kp.secret_key, None).unwrap();
+        let mut back = [0u8; SHARED_SECRET_LEN];
+        assert_eq!(dec.decapsulate(&res.ciphertext, &mut back).unwrap(), 32);
+        assert_eq!(back.to_vec(), res.shared_secret, "{name}");
+
+        // Implicit rejection: a tampered ciphertext decapsulates to a
+        // different secret rather than failing.
+        let mut forged = res.ciphertext.clone();
+        forged[0] ^= 1;
+        dec.decapsulate(&forged, &mut back).unwrap();
+        assert_ne!(back.to_vec(), res.shared_secret, "{name}");
+    }
+
+    #[test]
+    fn every_algorithm_round_trips() {
+        for name in ["ml-kem-768", "ml-kem-1024", "x25519-mlkem768"] {
+            round_trip(name);
+        }
+    }
+
+    #[test]
+    fn seeded_keygen_is_deterministic_and_sized() {
+        let seed = [3u8; 96];
+        let a = generate(KemAlg::X25519MlKem768, &seed).unwrap();
+        let b = generate(KemAlg::X25519MlKem768, &seed).unwrap();
+        assert_eq!(a.public_key, b.public_key);
+        assert_eq!(a.public_key.len(), 1184 + 32);
+        assert_eq!(a.secret_key.len(), 2400 + 32);
+        assert!(
+            generate(KemAlg::MlKem768, &seed).is_err(),
+            "wrong seed length"
+        );
+    }
+
+    #[test]
+    fn low_order_x25519_share_is_rejected() {
+        let kp = generate(KemAlg::X25519MlKem768, &[9u8; 96]).unwrap();
+        let mut enc = encapsulate(KemAlg::X25519MlKem768, &kp.public_key)
+            .unwrap()
+            .0;
+        let n = enc.len();
+        enc[n - 32..].fill(0);
+        assert!(decapsulate(KemAlg::X25519MlKem768, &kp.secret_key, &enc).is_err());
+    }
+
+    #[test]
+    fn wrong_sizes_are_rejected() {
+        assert!(KemState::encapsulator_create("ml-kem-768", &[0u8; 1000], None).is_err());
+        assert!(KemState::decapsulator_create("ml-kem-1024", &[0u8; 2400], None).is_err());
+        assert!(KemState::shared_secret_size("kyber512").is_err());
+    }
+}
@@ -0,0 +1,649 @@
+//! Key-format interface: PKCS#8, SPKI, JWK and raw encodings for
+//! Ed25519, ECDSA P-256/P-384 and RSA keys.
+//!
+//! | format | secret keys | public keys |
+//! |--------|-------------|-------------|
+//! | `pkcs8-der`, `pkcs8-pem` | yes | — |
+//! | `spki-der`, `spki-pem`   | — (serializes the public half) | yes |
+//! | `jwk`                    | yes (RFC 7517/8037) | yes |
+//! | `raw`                    | Ed25519 seed, EC scalar | Ed25519 point, SEC1 point |
+//!
+//! `raw` is the signature interface's key encoding, so a key parsed
+//! here can be handed to `ed25519` / `ecdsa-p256` / `ecdsa-p384`
+//! signers after a `raw` round trip. `raw` needs an algorithm hint;
+//! a 32-byte Ed25519 input is read as a secret seed unless the `kind`
+//! option is `"public"`. RSA has no raw form.
+//!
+//! Without a hint, DER/PEM/JWK inputs are tried against every
+//! algorithm and the first that parses wins; the encodings carry an
+//! algorithm identifier, so at most one can.
+
+use base64ct::{Base64, Base64UrlUnpadded, Encoding};
+use confium_api::KeyfmtPlugin;
+use confium_api::error::PluginResult;
+use confium_api::options::OptionView;
+use confium_api::plugin::keyfmt::KeyKind;
+use confium_api::{ErrorCode, PluginError};
+use confium_macros::plugin_interface;
+use ed25519_dalek::pkcs8::{DecodePrivateKey as _, DecodePublicKey as _};
+use ed25519_dalek::pkcs8::{EncodePrivateKey as _, EncodePublicKey as _};
+use p256::elliptic_curve::sec1::ToSec1Point as _;
+use rsa::pkcs1::EncodeRsaPrivateKey as _;
+use rsa::pkcs1::der::{Decode as _, Encode as _};
+use rsa::pkcs1::{DecodeRsaPrivateKey as _, DecodeRsaPublicKey as _};
+use rsa::traits::PublicKeyParts as _;
+use serde_json::{Map, Value};
+use zeroize::Zeroizing;
+
+use crate::{invalid, unsupported};
+
+/// Key families this module understands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyAlg {
+    Ed25519,
+    P256,
+    P384,
+    Rsa,
+}
+
+impl KeyAlg {
+    const ALL: [KeyAlg; 4] = [Self::Ed25519, Self::P256, Self::P384, Self::Rsa];
+
+    /// Resolve an algorithm hint. Signature algorithm names are
+    /// accepted as aliases for their key family.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name.to_ascii_lowercase().as_str() {
+            "ed25519" => Self::Ed25519,
+            "ecdsa-p256" | "ecdsa-p-256" | "p-256" | "p256" => Self::P256,
+            "ecdsa-p384" | "ecdsa-p-384" | "p-384" | "p384" => Self::P384,
+            "rsa" | "rsa-pss" => Self::Rsa,
+            _ => return None,
+        })
+    }
+
+    /// Name reported by `algorithm()`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Ed25519 => "ed25519",
+            Self::P256 => "ecdsa-p256",
+            Self::P384 => "ecdsa-p384",
+            Self::Rsa => "rsa",
+        }
+    }
+}
+
+/// Supported serialization formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Pkcs8Der,
+    Pkcs8Pem,
+    SpkiDer,
+    SpkiPem,
+    Jwk,
+    Raw,
+}
+
+impl Format {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name.to_ascii_lowercase().as_str() {
+            "pkcs8-der" | "pkcs8" => Self::Pkcs8Der,
+            "pkcs8-pem" => Self::Pkcs8Pem,
+            "spki-der" | "spki" => Self::SpkiDer,
+            "spki-pem" => Self::SpkiPem,
+            "jwk" => Self::Jwk,
+            "raw" => Self::Raw,
+            _ => return None,
+        })
+    }
+}
+
+const PEM_PRIVATE: &str = "PRIVATE KEY";
+const PEM_PUBLIC: &str = "PUBLIC KEY";
+
+fn pem_encode(label: &str, der: &[u8]) -> Vec<u8> {
+    let body = Base64::encode_string(der);
+    let mut out = format!("-----BEGIN {label}-----\n");
+    for line in body.as_bytes().chunks(64) {
+        out.push_str(std::str::from_utf8(line).expect("base64 is ASCII"));
+        out.push('\n');
+    }
+    out.push_str(&format!("-----END {label}-----\n"));
+    out.into_bytes()
+}
+
+fn pem_decode(label: &str, pem: &[u8]) -> PluginResult<Zeroizing<Vec<u8>>> {
+    let bad = || invalid(format!("rustcrypto: not a PEM \"{label}\" block"));
+    let text = std::str::from_utf8(pem).map_err(|_| bad())?;
+    let begin = format!("-----BEGIN {label}-----");
+    let end = format!("-----END {label}-----");
+    let start = text.find(&begin).ok_or_else(bad)? + begin.len();
+    let stop = start + text[start..].find(&end).ok_or_else(bad)?;
+    let body: Zeroizing<String> = Zeroizing::new(
+        text[start..stop]
+            .chars()
+            .filter(|c| !c.is_ascii_whitespace())
+            .collect(),
+    );
+    Base64::decode_vec(&body)
+        .map(Zeroizing::new)
+        .map_err(|_| bad())
+}
+
+fn bad_rsa_jwk<E>(_: E) -> PluginError {
+    invalid("rustcrypto: bad RSA JWK")
+}
+
+fn jwk_encode_failed<E>(_: E) -> PluginError {
+    PluginError::msg("rustcrypto: JWK encoding failed")
+}
+
+fn b64url(bytes: &[u8]) -> Value {
+    Value::String(Base64UrlUnpadded::encode_string(bytes))
+}
+
+/// JWK `crv` name and coordinate width of an EC key family.
+fn jwk_curve(alg: KeyAlg) -> (&'static str, usize) {
+    match alg {
+        KeyAlg::P384 => ("P-384", 48),
+        _ => ("P-256", 32),
+    }
+}
+
+fn jwk_field(jwk: &Map<String, Value>, name: &str) -> PluginResult<Zeroizing<Vec<u8>>> {
+    let s = jwk
+        .get(name)
+        .and_then(Value::as_str)
+        .ok_or_else(|| invalid(format!("rustcrypto: JWK is missing \"{name}\"")))?;
+    Base64UrlUnpadded::decode_vec(s)
+        .map(Zeroizing::new)
+        .map_err(|_| invalid(format!("rustcrypto: JWK \"{name}\" is not base64url")))
+}
+
+/// A parsed key. Secret variants carry the public half implicitly.
+pub enum KeyObject {
+    Ed25519Secret(ed25519_dalek::SigningKey),
+    Ed25519Public(ed25519_dalek::VerifyingKey),
+    P256Secret(p256::SecretKey),
+    P256Public(p256::PublicKey),
+    P384Secret(p384::SecretKey),
+    P384Public(p384::PublicKey),
+    RsaSecret(Box<rsa::RsaPrivateKey>),
+    RsaPublic(Box<rsa::RsaPublicKey>),
+}
+
+impl KeyObject {
+    /// Key family of this key.
+    pub fn alg(&self) -> KeyAlg {
+        match self {
+            Self::Ed25519Secret(_) | Self::Ed25519Public(_) => KeyAlg::Ed25519,
+            Self::P256Secret(_) | Self::P256Public(_) => KeyAlg::P256,
+            Self::P384Secret(_) | Self::P384Public(_) => KeyAlg::P384,
+            Self::RsaSecret(_) | Self::RsaPublic(_) => KeyAlg::Rsa,
+        }
+    }
+
+    fn is_secret(&self) -> bool {
+        matches!(
+            self,
+            Self::Ed25519Secret(_) | Self::P256Secret(_) | Self::P384Secret(_) | Self::RsaSecret(_)
+        )
+    }
+
+    fn to_public(&self) -> Self {
+        match self {
+            Self::Ed25519Secret(k) => Self::Ed25519Public(k.verifying_key()),
+            Self::P256Secret(k) => Self::P256Public(k.public_key()),
+            Self::P384Secret(k) => Self::P384Public(k.public_key()),
+            Self::RsaSecret(k) => Self::RsaPublic(Box::new(k.to_public_key())),
+            Self::Ed25519Public(k) => Self::Ed25519Public(*k),
+            Self::P256Public(k) => Self::P256Public(*k),
+            Self::P384Public(k) => Self::P384Public(*k),
+            Self::RsaPublic(k) => Self::RsaPublic(k.clone()),
+        }
+    }
+
+    fn from_pkcs8(alg: KeyAlg, der: &[u8]) -> Option<Self> {
+        match alg {
+            KeyAlg::Ed25519 => ed25519_dalek::SigningKey::from_pkcs8_der(der)
+                .ok()
+                .map(Self::Ed25519Secret),
+            KeyAlg::P256 => p256::SecretKey::from_pkcs8_der(der)
+                .ok()
+                .map(Self::P256Secret),
+            KeyAlg::P384 => p384::SecretKey::from_pkcs8_der(der)
+                .ok()
+                .map(Self::P384Secret),
+            KeyAlg::Rsa => rsa::RsaPrivateKey::from_pkcs8_der(der)
+                .ok()
+                .map(|k| Self::RsaSecret(Box::new(k))),
+        }
+    }
+
+    fn from_spki(alg: KeyAlg, der: &[u8]) -> Option<Self> {
+        match alg {
+            KeyAlg::Ed25519 => ed25519_dalek::VerifyingKey::from_public_key_der(der)
+                .ok()
+                .map(Self::Ed25519Public),
+            KeyAlg::P256 => p256::PublicKey::from_public_key_der(der)
+                .ok()
+                .map(Self::P256Public),
+            KeyAlg::P384 => p384::PublicKey::from_public_key_der(der)
+                .ok()
+                .map(Self::P384Public),
+            KeyAlg::Rsa => rsa::RsaPublicKey::from_public_key_der(der)
+                .ok()
+                .map(|k| Self::RsaPublic(Box::new(k))),
+        }
+    }
+
+    fn from_raw(alg: KeyAlg, bytes: &[u8], want_public: bool) -> PluginResult<Self> {
+        let bad = || invalid(format!("rustcrypto: not a raw {} key", alg.name()));
+        match alg {
+            KeyAlg::Ed25519 => {
+                let arr: [u8; 32] = bytes.try_into().map_err(|_| bad())?;
+                if want_public {
+                    ed25519_dalek::VerifyingKey::from_bytes(&arr)
+                        .map(Self::Ed25519Public)
+                        .map_err(|_| bad())
+                } else {
+                    Ok(Self::Ed25519Secret(ed25519_dalek::SigningKey::from_bytes(
+                        &arr,
+                    )))
+                }
+            }
+            KeyAlg::P256 if bytes.len() == 32 => p256::SecretKey::from_slice(bytes)
+                .map(Self::P256Secret)
+                .map_err(|_| bad()),
+            KeyAlg::P256 => p256::PublicKey::from_sec1_bytes(bytes)
+                .map(Self::P256Public)
+                .map_err(|_| bad()),
+            KeyAlg::P384 if bytes.len() == 48 => p384::SecretKey::from_slice(bytes)
+                .map(Self::P384Secret)
+                .map_err(|_| bad()),
+            KeyAlg::P384 => p384::PublicKey::from_sec1_bytes(bytes)
+                .map(Self::P384Public)
+                .map_err(|_| bad()),
+            KeyAlg::Rsa => Err(invalid("rustcrypto: RSA keys have no raw encoding")),
+        }
+    }
+
+    fn from_jwk(alg: Option<KeyAlg>, bytes: &[u8]) -> PluginResult<Self> {
+        let text =
+            std::str::from_utf8(bytes).map_err(|_| invalid("rustcrypto: JWK is not UTF-8"))?;
+        let value: Value =
+            serde_json::from_str(text).map_err(|_| invalid("rustcrypto: JWK is not JSON"))?;
+        let jwk = value
+            .as_object()
+            .ok_or_else(|| invalid("rustcrypto: JWK is not a JSON object"))?;
+        let has_d = jwk.contains_key("d");
+        let found = match (
+            jwk.get("kty").and_then(Value::as_str),
+            jwk.get("crv").and_then(Value::as_str),
+        ) {
+            (Some("OKP"), Some("Ed25519")) => {
+                let x = jwk_field(jwk, "x")?;
+                let public = Self::from_raw(KeyAlg::Ed25519, &x, true)?;
+                if has_d {
+                    let key = Self::from_raw(KeyAlg::Ed25519, &jwk_field(jwk, "d")?, false)?;
+                    if key.to_public().raw_public() != x.as_slice() {
+                        return Err(invalid("rustcrypto: JWK \"x\" does not match \"d\""));
+                    }
+                    key
+                } else {
+                    public
+                }
+            }
+            (Some("EC"), Some("P-256")) => Self::ec_from_jwk(KeyAlg::P256, jwk, has_d)?,
+            (Some("EC"), Some("P-384")) => Self::ec_from_jwk(KeyAlg::P384, jwk, has_d)?,
+            (Some("RSA"), _) => Self::rsa_from_jwk(jwk, has_d)?,
+            _ => return Err(invalid("rustcrypto: unsupported JWK kty/crv")),
+        };
+        if alg.is_some_and(|a| a != found.alg()) {
+            return Err(invalid("rustcrypto: JWK does not match the algorithm hint"));
+        }
+        Ok(found)
+    }
+
+    /// EC JWKs carry fixed-width big-endian coordinates (RFC 7518
+    /// §6.2.1), so the public half is the uncompressed SEC1 point
+    /// `04 ‖ x ‖ y` and `d` is the raw scalar.
+    fn ec_from_jwk(alg: KeyAlg, jwk: &Map<String, Value>, has_d: bool) -> PluginResult<Self> {
+        let (crv, width) = jwk_curve(alg);
+        let bad = || invalid(format!("rustcrypto: bad {crv} JWK"));
+        let (x, y) = (jwk_field(jwk, "x")?, jwk_field(jwk, "y")?);
+        if x.len() != width || y.len() != width {
+            return Err(bad());
+        }
+        let mut point = Vec::with_capacity(1 + 2 * width);
+        point.push(0x04);
+        point.extend_from_slice(&x);
+        point.extend_from_slice(&y);
+        let public = Self::from_raw(alg, &point, true).map_err(|_| bad())?;
+        if !has_d {
+            return Ok(public);
+        }
+        let d = jwk_field(jwk, "d")?;
+        if d.len() != width {
+            return Err(bad());
+        }
+        let key = Self::from_raw(alg, &d, false).map_err(|_| bad())?;
+        if key.raw_public() != point {
+            return Err(invalid("rustcrypto: JWK \"x\"/\"y\" do not match \"d\""));
+        }
+        Ok(key)
+    }
+
+    /// RSA JWKs map one-to-one onto the PKCS#1 structures, so build
+    /// the PKCS#1 DER and let the `rsa` crate validate it.
+    fn rsa_from_jwk(jwk: &Map<String, Value>, has_d: bool) -> PluginResult<Self> {
+        let n = jwk_field(jwk, "n")?;
+        let e = jwk_field(jwk, "e")?;
+        if !has_d {
+            let der = rsa::pkcs1::RsaPublicKey {
+                modulus: rsa::pkcs1::UintRef::new(&n).map_err(bad_rsa_jwk)?,
+                public_exponent: rsa::pkcs1::UintRef::new(&e).map_err(bad_rsa_jwk)?,
+            }
+            .to_der()
+            .map_err(bad_rsa_jwk)?;
+            return rsa::RsaPublicKey::from_pkcs1_der(&der)
+                .map(|k| Self::RsaPublic(Box::new(k)))
+                .map_err(bad_rsa_jwk);
+        }
+        let fields: Vec<Zeroizing<Vec<u8>>> = ["d", "p", "q", "dp", "dq", "qi"]
+            .iter()
+            .map(|f| jwk_field(jwk, f))
+            .collect::<PluginResult<_>>()?;
+        let der = Zeroizing::new(
+            rsa::pkcs1::RsaPrivateKey {
+                modulus: rsa::pkcs1::UintRef::new(&n).map_err(bad_rsa_jwk)?,
+                public_exponent: rsa::pkcs1::UintRef::new(&e).map_err(bad_rsa_jwk)?,
+                private_exponent: rsa::pkcs1::UintRef::new(&fields[0]).map_err(bad_rsa_jwk)?,
+                prime1: rsa::pkcs1::UintRef::new(&fields[1]).map_err(bad_rsa_jwk)?,
+                prime2: rsa::pkcs1::UintRef::new(&fields[2]).map_err(bad_rsa_jwk)?,
+                exponent1: rsa::pkcs1::UintRef::new(&fields[3]).map_err(bad_rsa_jwk)?,
+                exponent2: rsa::pkcs1::UintRef::new(&fields[4]).map_err(bad_rsa_jwk)?,
+                coefficient: rsa::pkcs1::UintRef::new(&fields[5]).map_err(bad_rsa_jwk)?,
+                other_prime_infos: None,
+            }
+            .to_der()
+            .map_err(bad_rsa_jwk)?,
+        );
+        rsa::RsaPrivateKey::from_pkcs1_der(&der)
+            .map(|k| Self::RsaSecret(Box::new(k)))
+            .map_err(bad_rsa_jwk)
+    }
+
+    fn raw_public(&self) -> Vec<u8> {
+        match self.to_public() {
+            Self::Ed25519Public(k) => k.to_bytes().to_vec(),
+            Self::P256Public(k) => k.to_sec1_point(false).as_bytes().to_vec(),
+            Self::P384Public(k) => k.to_sec1_point(false).as_bytes().to_vec(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn pkcs8(&self) -> PluginResult<Zeroizing<Vec<u8>>> {
+        let doc = match self {
+            Self::Ed25519Secret(k) => k.to_pkcs8_der(),
+            Self::P256Secret(k) => k.to_pkcs8_der(),
+            Self::P384Secret(k) => k.to_pkcs8_der(),
+            Self::RsaSecret(k) => k.to_pkcs8_der(),
+            _ => return Err(invalid("rustcrypto: PKCS#8 needs a secret key")),
+        }
+        .map_err(|_| PluginError::msg("rustcrypto: PKCS#8 encoding failed"))?;
+        Ok(Zeroizing::new(doc.as_bytes().to_vec()))
+    }
+
+    fn spki(&self) -> PluginResult<Vec<u8>> {
+        let doc = match self.to_public() {
+            Self::Ed25519Public(k) => k.to_public_key_der(),
+            Self::P256Public(k) => k.to_public_key_der(),
+            Self::P384Public(k) => k.to_public_key_der(),
+            Self::RsaPublic(k) => k.to_public_key_der(),
+            _ => unreachable!("to_public returns a public key"),
+        }
+        .map_err(|_| PluginError::msg("rustcrypto: SPKI encoding failed"))?;
+        Ok(doc.as_bytes().to_vec())
+    }
+
+    fn raw(&self) -> PluginResult<Zeroizing<Vec<u8>>> {
+        Ok(Zeroizing::new(match self {
+            Self::Ed25519Secret(k) => k.to_bytes().to_vec(),
+            Self::P256Secret(k) => k.to_bytes().to_vec(),
+            Self::P384Secret(k) => k.to_bytes().to_vec(),
+            Self::RsaSecret(_) | Self::RsaPublic(_) => {
+                return Err(invalid("rustcrypto: RSA keys have no raw encoding"));
+            }
+            public => public.raw_public(),
+        }))
+    }
+
+    fn jwk(&self) -> PluginResult<Zeroizing<Vec<u8>>> {
+        let mut obj = Map::new();
+        match self {
+            Self::P256Secret(_)
+            | Self::P256Public(_)
+            | Self::P384Secret(_)
+            | Self::P384Public(_) => {
+                let point = self.raw_public();
+                let (x, y) = point[1..].split_at((point.len() - 1) / 2);
+                obj.insert("kty".into(), "EC".into());
+                obj.insert("crv".into(), jwk_curve(self.alg()).0.into());
+                obj.insert("x".into(), b64url(x));
+                obj.insert("y".into(), b64url(y));
+                match self {
+                    Self::P256Secret(k) => obj.insert("d".into(), b64url(&k.to_bytes())),
+                    Self::P384Secret(k) => obj.insert("d".into(), b64url(&k.to_bytes())),
+                    _ => None,
+                };
+            }
+            Self::Ed25519Secret(_) | Self::Ed25519Public(_) => {
+                obj.insert("kty".into(), "OKP".into());
+                obj.insert("crv".into(), "Ed25519".into());
+                obj.insert("x".into(), b64url(&self.raw_public()));
+                if let Self::Ed25519Secret(k) = self {
+                    obj.insert("d".into(), b64url(&k.to_bytes()));
+                }
+            }
+            Self::RsaPublic(k) => {
+                obj.insert("kty".into(), "RSA".into());
+                obj.insert("n".into(), b64url(&k.n_bytes()));
+                obj.insert("e".into(), b64url(&k.e_bytes()));
+            }
+            Self::RsaSecret(k) => {
+                let der = k.to_pkcs1_der().map_err(jwk_encode_failed)?;
+                let pkcs1 =
+                    rsa::pkcs1::RsaPrivateKey::<rsa::pkcs1::UintRef<'_>>::from_der(der.as_bytes())
+                        .map_err(jwk_encode_failed)?;
+                obj.insert("kty".into(), "RSA".into());
+                for (name, value) in [
+                    ("n", pkcs1.modulus),
+                    ("e", pkcs1.public_exponent),
+                    ("d", pkcs1.private_exponent),
+                    ("p", pkcs1.prime1),
+                    ("q", pkcs1.prime2),
+                    ("dp", pkcs1.exponent1),
+                    ("dq", pkcs1.exponent2),
+                    ("qi", pkcs1.coefficient),
+                ] {
+                    obj.insert(name.into(), b64url(value.as_bytes()));
+                }
+            }
+        }
+        let text = Zeroizing::new(Value::Object(obj).to_string());
+        Ok(Zeroizing::new(text.as_bytes().to_vec()))
+    }
+}
+
+#[plugin_interface(name = "keyfmt", version = 0)]
+impl KeyfmtPlugin for KeyObject {
+    fn parse(
+        format: &str,
+        algorithm_hint: Option<&str>,
+        bytes: &[u8],
+        opts: Option<OptionView<'_>>,
+    ) -> PluginResult<Self> {
+        let fmt = Format::from_name(format).ok_or_else(|| unsupported(format))?;
+        let hint = match algorithm_hint {
+            Some(h) => Some(KeyAlg::from_name(h).ok_or_else(|| unsupported(h))?),
+            None => None,
+        };
+        let candidates: Vec<KeyAlg> = match hint {
+            Some(a) => vec![a],
+            None => KeyAlg::ALL.to_vec(),
+        };
+        let first = |f: fn(KeyAlg, &[u8]) -> Option<Self>, der: &[u8]| {
+            candidates.iter().find_map(|&a| f(a, der)).ok_or_else(|| {
+                invalid(format!("rustcrypto: input is not a supported {format} key"))
+            })
+        };
+        match fmt {
+            Format::Pkcs8Der => first(Self::from_pkcs8, bytes),
+            Format::Pkcs8Pem => first(Self::from_pkcs8, &pem_decode(PEM_PRIVATE, bytes)?),
+            Format::SpkiDer => first(Self::from_spki, bytes),
+            Format::SpkiPem => first(Self::from_spki, &pem_decode(PEM_PUBLIC, bytes)?),
+            Format::Jwk => Self::from_jwk(hint, bytes),
+            Format::Raw => {
+                let alg = hint.ok_or_else(|| {
+                    PluginError::new(
+                        ErrorCode::VALUE_NOT_FOUND,
+                        "rustcrypto: raw keys need an algorithm hint",
+                    )
+                })?;
+                let want_public = opts.as_ref().and_then(|o| o.get_str("kind")) == Some("public");
+                Self::from_raw(alg, bytes, want_public)
+            }
+        }
+    }
+
+    fn serialize(&self, format: &str) -> PluginResult<Vec<u8>> {
+        let fmt = Format::from_name(format).ok_or_else(|| unsupported(format))?;
+        // The SDK copies the returned vector into the caller's buffer;
+        // the intermediate DER is zeroized here.
+        Ok(match fmt {
+            Format::Pkcs8Der => self.pkcs8()?.to_vec(),
+            Format::Pkcs8Pem => pem_encode(PEM_PRIVATE, &self.pkcs8()?),
+            Format::SpkiDer => self.spki()?,
+            Format::SpkiPem => pem_encode(PEM_PUBLIC, &self.spki()?),
+            Format::Jwk => self.jwk()?.to_vec(),
+            Format::Raw => self.raw()?.to_vec(),
+        })
+    }
+
+    fn kind(&self) -> PluginResult<KeyKind> {
+        Ok(if self.is_secret() {
+            KeyKind::Both
+        } else {
+            KeyKind::Public
+        })
+    }
+
+    fn algorithm(&self) -> PluginResult<String> {
+        Ok(self.alg().name().to_string())
+    }
+
+    fn public(&self) -> PluginResult<Self> {
+        Ok(self.to_public())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signature::{SigAlg, generate, sign, verify};
+
+    fn parse(format: &str, hint: Option<&str>, bytes: &[u8]) -> KeyObject {
+        KeyObject::parse(format, hint, bytes, None).unwrap()
+    }
+
+    #[test]
+    fn every_format_round_trips_for_every_family() {
+        let keys = [
+            (
+                "ed25519",
+                generate(SigAlg::Ed25519, None).unwrap().secret_key,
+            ),
+            (
+                "ecdsa-p256",
+                generate(SigAlg::EcdsaP256, None).unwrap().secret_key,
+            ),
+            (
+                "ecdsa-p384",
+                generate(SigAlg::EcdsaP384, None).unwrap().secret_key,
+            ),
+        ];
+        for (alg, raw) in keys {
+            let key = parse("raw", Some(alg), &raw);
+            assert_eq!(key.kind().unwrap(), KeyKind::Both);
+            for fmt in ["pkcs8-der", "pkcs8-pem", "jwk", "raw"] {
+                let bytes = key.serialize(fmt).unwrap();
+                // Raw keys carry no algorithm identifier.
+                let back = parse(fmt, (fmt == "raw").then_some(alg), &bytes);
+                assert_eq!(back.algorithm().unwrap(), alg, "{alg} {fmt}");
+                assert_eq!(back.serialize("raw").unwrap(), raw, "{alg} {fmt}");
+            }
+            let public = key.public().unwrap();
+            assert_eq!(public.kind().unwrap(), KeyKind::Public);
+            for fmt in ["spki-der", "spki-pem", "jwk"] {
+                let bytes = public.serialize(fmt).unwrap();
+                let back = parse(fmt, Some(alg), &bytes);
+                assert_eq!(back.raw_public(), key.raw_public(), "{alg} {fmt}");
+            }
+            assert!(public.serialize("pkcs8-der").is_err());
+        }
+    }
+
+    #[test]
+    fn rsa_keys_round_trip_through_pkcs8_spki_and_jwk() {
+        let kp = generate(SigAlg::RsaPss, None).unwrap();
+        let key = parse("pkcs8-der", Some("rsa-pss"), &kp.secret_key);
+        assert_eq!(key.algorithm().unwrap(), "rsa");
+        let jwk = key.serialize("jwk").unwrap();
+        let back = parse("jwk", None, &jwk);
+        assert_eq!(
+            back.serialize("pkcs8-der").unwrap(),
+            key.serialize("pkcs8-der").unwrap()
+        );
+        assert_eq!(back.serialize("spki-der").unwrap(), kp.public_key);
+
+        // A key that went through JWK still signs for the signature
+        // interface.
+        let sk = back.serialize("pkcs8-der").unwrap();
+        let sig = sign(SigAlg::RsaPss, &sk, None, b"m").unwrap();
+        verify(SigAlg::RsaPss, &kp.public_key, None, b"m", &sig).unwrap();
+        assert!(key.serialize("raw").is_err());
+    }
+
+    #[test]
+    fn rfc8037_ed25519_jwk() {
+        let jwk = br#"{"kty":"OKP","crv":"Ed25519",
+            "d":"nWGxne_9WmC6hEr0kuwsxERJxWl7MmkZcDusAxyuf2A",
+            "x":"11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo"}"#;
+        let key = parse("jwk", None, jwk);
+        assert_eq!(
+            hex::encode(key.raw_public()),
+            "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"
+        );
+        let mismatched = br#"{"kty":"OKP","crv":"Ed25519",
+            "d":"nWGxne_9WmC6hEr0kuwsxERJxWl7MmkZcDusAxyuf2A",
+            "x":"AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA"}"#;
+        assert!(KeyObject::parse("jwk", None, mismatched, None).is_err());
+    }
+
+    #[test]
+    fn bad_inputs_are_rejected() {
+        assert!(KeyObject::parse("pkcs8-der", None, b"garbage", None).is_err());
+        assert!(KeyObject::parse("raw", None, &[0u8; 32], None).is_err());
+        assert!(KeyObject::parse("openssh", None, b"", None).is_err());
+        let ed = parse("raw", Some("ed25519"), &[1u8; 32]);
+        assert!(
+            KeyObject::parse(
+                "jwk",
+                Some("ecdsa-p256"),
+                &ed.serialize("jwk").unwrap(),
+                None
+            )
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,78 @@
+//! First-party Confium provider plugin backed by the RustCrypto crates.
+//!
+//! Every interface is implemented with the `confium-api` SDK: each
+//! module holds one state type whose `impl` block carries
+//! `#[plugin_interface(...)]`, and [`Plugin`] carries `#[export(...)]`
+//! so the lifecycle, metadata and interface-list symbols are emitted
+//! for the loader.
+//!
+//! | module | interface | algorithms |
+//! |--------|-----------|------------|
+//! | [`hash`]      | `hash`      | SHA-2, SHA-3, BLAKE3 |
+//! | [`aead`]      | `aead`      | AES-GCM, ChaCha20-Poly1305, AES-256-GCM-SIV |
+//! | [`cipher`]    | `cipher`    | AES-CTR, ChaCha20 |
+//! | [`kdf`]       | `kdf`       | HKDF, PBKDF2, Argon2id |
+//! | [`rng`]       | `rng`       | OS RNG, ChaCha20 fast-key-erasure DRBG |
+//! | [`signature`] | `signature` | Ed25519, ECDSA P-256/P-384, RSA-PSS, ML-DSA |
+//! | [`kem`]       | `kem`       | ML-KEM-768/1024, X25519+ML-KEM-768 hybrid |
+//! | [`keyfmt`]    | `keyfmt`    | PKCS#8, SPKI (DER/PEM), JWK, raw |
+//!
+//! Algorithm names are matched case-insensitively. A name this plugin
+//! does not know is reported as `UNSUPPORTED_ALGORITHM` so the caller
+//! can fall back to another provider.
+//!
+//! Key encodings are shared between interfaces: the `raw` key format
+//! produces exactly the bytes the signature interface consumes for
+//! Ed25519, ECDSA and ML-DSA, and `pkcs8-der` / `spki-der` are the
+//! RSA-PSS key encodings.
+
+use confium_api::{ErrorCode, PluginError, PluginResult};
+use confium_macros::export;
+
+pub mod aead;
+pub mod cipher;
+pub mod hash;
+pub mod kdf;
+pub mod kem;
+pub mod keyfmt;
+pub mod rng;
+pub mod signature;
+
+/// `UNSUPPORTED_ALGORITHM` for `name`.
+pub(crate) fn unsupported(name: &str) -> PluginError {
+    PluginError::new(
+        ErrorCode::UNSUPPORTED_ALGORITHM,
+        format!("rustcrypto: unsupported algorithm {name:?}"),
+    )
+}
+
+/// `WRONG_TYPE` for malformed caller input (bad key length, wrong
+/// call order, undecodable encoding).
+pub(crate) fn invalid(message: impl Into<String>) -> PluginError {
+    PluginError::new(ErrorCode::WRONG_TYPE, message)
+}
+
+/// `INSUFFICIENT_BUFFER` when `need` bytes do not fit in `have`.
+pub(crate) fn check_buffer(have: usize, need: usize) -> PluginResult<()> {
+    if have < need {
+        return Err(PluginError::new(
+            ErrorCode::INSUFFICIENT_BUFFER,
+            format!("rustcrypto: output buffer holds {have} bytes, need {need}"),
+        ));
+    }
+    Ok(())
+}
+
+/// Fill `out` from the operating system RNG.
+pub(crate) fn os_random(out: &mut [u8]) -> PluginResult<()> {
+    getrandom::fill(out).map_err(|e| PluginError::msg(format!("rustcrypto: OS RNG failed: {e}")))
+}
+
+#[export(metadata(
+    name = "confium-rustcrypto-plugin",
+    version = "0.5.5",
+    vendor = "confium",
+    license = "BSD-2-Clause",
+    description = "RustCrypto-backed hash, AEAD, cipher, KDF, RNG, signature, KEM and key formats",
+))]
+pub struct Plugin;
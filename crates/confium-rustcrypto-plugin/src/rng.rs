@@ -0,0 +1,135 @@
+//! RNG interface: the OS RNG and a ChaCha20 fast-key-erasure DRBG.
+//!
+//! - `system` reads `getrandom` on every call. `reseed` and
+//!   `add_entropy` are accepted and ignored: the kernel pool is not
+//!   ours to steer.
+//! - `chacha20-drbg` keys ChaCha20 from the OS RNG and, after every
+//!   request, replaces its key with the first 32 bytes of the output
+//!   stream (Bernstein's fast-key-erasure construction), so a state
+//!   compromise does not reveal earlier output. `reseed` and
+//!   `add_entropy` hash caller input into the key with SHA-256.
+
+use aes::cipher::{KeyIvInit, StreamCipher};
+use confium_api::RngPlugin;
+use confium_api::error::PluginResult;
+use confium_api::options::OptionView;
+use confium_macros::plugin_interface;
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+use crate::{os_random, unsupported};
+
+/// Largest request served from one DRBG key before it is erased.
+const DRBG_MAX_REQUEST: usize = 1 << 16;
+
+/// RNG state.
+pub enum RngState {
+    System,
+    ChaCha20Drbg { key: Zeroizing<[u8; 32]> },
+}
+
+impl RngState {
+    fn mix(key: &mut [u8; 32], data: &[u8]) {
+        let mut h = Sha256::new();
+        h.update(*key);
+        h.update(data);
+        key.copy_from_slice(&h.finalize());
+    }
+
+    /// Fill `out` (at most [`DRBG_MAX_REQUEST`] bytes) and erase `key`.
+    fn drbg_block(key: &mut [u8; 32], out: &mut [u8]) {
+        let mut buf = Zeroizing::new(vec![0u8; 32 + out.len()]);
+        let mut stream = chacha20::ChaCha20::new(&(*key).into(), &[0u8; 12].into());
+        stream.apply_keystream(&mut buf);
+        key.copy_from_slice(&buf[..32]);
+        out.copy_from_slice(&buf[32..]);
+    }
+}
+
+#[plugin_interface(name = "rng", version = 0)]
+impl RngPlugin for RngState {
+    fn create(algorithm: &str, _opts: Option<OptionView<'_>>) -> PluginResult<Self> {
+        match algorithm.to_ascii_lowercase().as_str() {
+            "system" | "os" => Ok(Self::System),
+            "chacha20-drbg" => {
+                let mut key = Zeroizing::new([0u8; 32]);
+                os_random(key.as_mut())?;
+                Ok(Self::ChaCha20Drbg { key })
+            }
+            _ => Err(unsupported(algorithm)),
+        }
+    }
+
+    fn reseed(&mut self, data: &[u8]) -> PluginResult<()> {
+        if let Self::ChaCha20Drbg { key } = self {
+            let mut fresh = Zeroizing::new([0u8; 32]);
+            os_random(fresh.as_mut())?;
+            Self::mix(key, fresh.as_ref());
+            Self::mix(key, data);
+        }
+        Ok(())
+    }
+
+    fn add_entropy(&mut self, data: &[u8]) -> PluginResult<()> {
+        if let Self::ChaCha20Drbg { key } = self {
+            Self::mix(key, data);
+        }
+        Ok(())
+    }
+
+    fn generate(&mut self, out: &mut [u8]) -> PluginResult<()> {
+        match self {
+            Self::System => os_random(out),
+            Self::ChaCha20Drbg { key } => {
+                for chunk in out.chunks_mut(DRBG_MAX_REQUEST) {
+                    Self::drbg_block(key, chunk);
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drbg_erases_key_after_each_request() {
+        let mut r = RngState::create("chacha20-drbg", None).unwrap();
+        let RngState::ChaCha20Drbg { key } = &r else {
+            unreachable!()
+        };
+        let before = **key;
+        let mut a = [0u8; 64];
+        r.generate(&mut a).unwrap();
+        let RngState::ChaCha20Drbg { key } = &r else {
+            unreachable!()
+        };
+        assert_ne!(**key, before);
+        let mut b = [0u8; 64];
+        r.generate(&mut b).unwrap();
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn drbg_is_deterministic_from_a_fixed_key() {
+        let mut k1 = [5u8; 32];
+        let mut k2 = [5u8; 32];
+        let (mut a, mut b) = ([0u8; 40], [0u8; 40]);
+        RngState::drbg_block(&mut k1, &mut a);
+        RngState::drbg_block(&mut k2, &mut b);
+        assert_eq!(a, b);
+        assert_eq!(k1, k2);
+    }
+
+    #[test]
+    fn system_rng_fills_and_unknown_is_unsupported() {
+        let mut r = RngState::create("system", None).unwrap();
+        let mut out = [0u8; 32];
+        r.generate(&mut out).unwrap();
+        assert_ne!(out, [0u8; 32]);
+        r.reseed(b"ignored").unwrap();
+        assert!(RngState::create("dual-ec", None).is_err());
+    }
+}
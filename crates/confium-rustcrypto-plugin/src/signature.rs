@@ -0,0 +1,586 @@
+//! Signature interface: Ed25519, ECDSA P-256/P-384, RSA-PSS and ML-DSA.
+//!
+//! Key encodings:
+//!
+//! | algorithm | secret key | public key | signature |
+//! |-----------|------------|------------|-----------|
+//! | `ed25519` | 32-byte seed | 32 bytes | 64 bytes |
+//! | `ecdsa-p256`, `ecdsa-p384` | big-endian scalar | SEC1 point | DER `ECDSA-Sig-Value` |
+//! | `rsa-pss` | PKCS#8 DER | SPKI DER | modulus-sized |
+//! | `ml-dsa-44`, `ml-dsa-65`, `ml-dsa-87` | 32-byte seed (FIPS 204 ξ) | FIPS 204 encoding | FIPS 204 encoding |
+//!
+//! The message is buffered across `update` calls and signed at
+//! `finalize`. `set_hash` selects the digest for the hash-then-sign
+//! schemes (ECDSA defaults to SHA-256 / SHA-384 by curve, RSA-PSS to
+//! SHA-256 with a digest-length salt); Ed25519 and ML-DSA sign the
+//! message itself, so `set_hash` is accepted and ignored for them (the
+//! core `Signer::set_hash` contract).
+//!
+//! `rsa-pss` generates 3072-bit keys and cannot be derived from a seed.
+
+use confium_api::SignaturePlugin;
+use confium_api::error::PluginResult;
+use confium_api::options::OptionView;
+use confium_api::plugin::signature::SignatureKeypair;
+use confium_macros::plugin_interface;
+use ecdsa_sig::{EcdsaCurve, ecdsa_generate, ecdsa_sign, ecdsa_verify};
+use zeroize::Zeroizing;
+
+use crate::hash::HashAlg;
+use crate::{check_buffer, invalid, unsupported};
+
+/// RSA modulus size for generated `rsa-pss` keys.
+pub const RSA_KEYGEN_BITS: usize = 3072;
+
+/// Signature algorithms this plugin implements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SigAlg {
+    Ed25519,
+    EcdsaP256,
+    EcdsaP384,
+    RsaPss,
+    MlDsa44,
+    MlDsa65,
+    MlDsa87,
+}
+
+impl SigAlg {
+    /// Resolve a caller-supplied name (case-insensitive).
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name.to_ascii_lowercase().as_str() {
+            "ed25519" => Self::Ed25519,
+            "ecdsa-p256" | "ecdsa-p-256" | "es256" => Self::EcdsaP256,
+            "ecdsa-p384" | "ecdsa-p-384" | "es384" => Self::EcdsaP384,
+            "rsa-pss" | "ps256" => Self::RsaPss,
+            "ml-dsa-44" => Self::MlDsa44,
+            "ml-dsa-65" => Self::MlDsa65,
+            "ml-dsa-87" => Self::MlDsa87,
+            _ => return None,
+        })
+    }
+
+    /// Whether `set_hash` is meaningful for this algorithm.
+    fn takes_hash(self) -> bool {
+        matches!(self, Self::EcdsaP256 | Self::EcdsaP384 | Self::RsaPss)
+    }
+}
+
+/// Run `$body` with `$H` bound to the SHA-2 type for `$hash`.
+macro_rules! with_sha2 {
+    ($hash:expr, $H:ident => $body:expr) => {
+        match $hash {
+            HashAlg::Sha256 => {
+                type $H = sha2::Sha256;
+                $body
+            }
+            HashAlg::Sha384 => {
+                type $H = sha2::Sha384;
+                $body
+            }
+            HashAlg::Sha512 => {
+                type $H = sha2::Sha512;
+                $body
+            }
+            other => Err(unsupported(other.name())),
+        }
+    };
+}
+
+/// Run `$body` with `$P` bound to the ML-DSA parameter set for `$alg`.
+macro_rules! with_mldsa {
+    ($alg:expr, $P:ident => $body:expr) => {
+        match $alg {
+            SigAlg::MlDsa44 => {
+                type $P = ml_dsa::MlDsa44;
+                $body
+            }
+            SigAlg::MlDsa65 => {
+                type $P = ml_dsa::MlDsa65;
+                $body
+            }
+            SigAlg::MlDsa87 => {
+                type $P = ml_dsa::MlDsa87;
+                $body
+            }
+            other => unreachable!("{other:?} is not ML-DSA"),
+        }
+    };
+}
+
+fn ed25519_signing(sk: &[u8]) -> PluginResult<ed25519_dalek::SigningKey> {
+    let seed: [u8; 32] = sk
+        .try_into()
+        .map_err(|_| invalid("rustcrypto: ed25519 secret key is a 32-byte seed"))?;
+    Ok(ed25519_dalek::SigningKey::from_bytes(&seed))
+}
+
+fn ed25519_verifying(pk: &[u8]) -> PluginResult<ed25519_dalek::VerifyingKey> {
+    let bytes: [u8; 32] = pk
+        .try_into()
+        .map_err(|_| invalid("rustcrypto: ed25519 public key is 32 bytes"))?;
+    ed25519_dalek::VerifyingKey::from_bytes(&bytes)
+        .map_err(|_| invalid("rustcrypto: ed25519 public key is not a curve point"))
+}
+
+fn rsa_private(sk: &[u8]) -> PluginResult<rsa::RsaPrivateKey> {
+    use rsa::pkcs8::DecodePrivateKey as _;
+    rsa::RsaPrivateKey::from_pkcs8_der(sk)
+        .map_err(|_| invalid("rustcrypto: rsa-pss secret key is PKCS#8 DER"))
+}
+
+fn rsa_public(pk: &[u8]) -> PluginResult<rsa::RsaPublicKey> {
+    use rsa::pkcs8::DecodePublicKey as _;
+    rsa::RsaPublicKey::from_public_key_der(pk)
+        .map_err(|_| invalid("rustcrypto: rsa-pss public key is SPKI DER"))
+}
+
+fn mldsa_signing<P: ml_dsa::MlDsaParams>(sk: &[u8]) -> PluginResult<ml_dsa::SigningKey<P>> {
+    let seed: [u8; 32] = sk
+        .try_into()
+        .map_err(|_| invalid("rustcrypto: ML-DSA secret key is a 32-byte seed"))?;
+    Ok(ml_dsa::SigningKey::<P>::from_seed(&seed.into()))
+}
+
+fn mldsa_verifying<P: ml_dsa::MlDsaParams>(pk: &[u8]) -> PluginResult<ml_dsa::VerifyingKey<P>> {
+    let encoded: ml_dsa::EncodedVerifyingKey<P> = pk
+        .try_into()
+        .map_err(|_| invalid("rustcrypto: wrong ML-DSA public key length"))?;
+    Ok(ml_dsa::VerifyingKey::<P>::decode(&encoded))
+}
+
+/// Sign `msg` with secret key `sk`.
+pub fn sign(alg: SigAlg, sk: &[u8], hash: Option<HashAlg>, msg: &[u8]) -> PluginResult<Vec<u8>> {
+    use ::signature::{SignatureEncoding as _, Signer as _};
+    match alg {
+        SigAlg::Ed25519 => Ok(ed25519_signing(sk)?.sign(msg).to_bytes().to_vec()),
+        SigAlg::EcdsaP256 => ecdsa_sign(EcdsaCurve::P256, sk, hash, msg),
+        SigAlg::EcdsaP384 => ecdsa_sign(EcdsaCurve::P384, sk, hash, msg),
+        SigAlg::RsaPss => {
+            use ::signature::RandomizedSigner as _;
+            use p256::elliptic_curve::rand_core::UnwrapErr;
+            let key = rsa_private(sk)?;
+            with_sha2!(hash.unwrap_or(HashAlg::Sha256), H => {
+                let signer = rsa::pss::BlindedSigningKey::<H>::new(key);
+                Ok(signer
+                    .sign_with_rng(&mut UnwrapErr(getrandom::SysRng), msg)
+                    .to_vec())
+            })
+        }
+        SigAlg::MlDsa44 | SigAlg::MlDsa65 | SigAlg::MlDsa87 => with_mldsa!(alg, P => {
+            Ok(mldsa_signing::<P>(sk)?.sign(msg).encode().to_vec())
+        }),
+    }
+}
+
+/// Verify `sig` over `msg` with public key `pk`. A signature that does
+/// not verify is `WRONG_TYPE`, which the loader maps to "invalid
+/// signature".
+pub fn verify(
+    alg: SigAlg,
+    pk: &[u8],
+    hash: Option<HashAlg>,
+    msg: &[u8],
+    sig: &[u8],
+) -> PluginResult<()> {
+    use ::signature::Verifier as _;
+    let rejected = |_| invalid("rustcrypto: signature does not verify");
+    match alg {
+        SigAlg::Ed25519 => {
+            let vk = ed25519_verifying(pk)?;
+            let sig = ed25519_dalek::Signature::from_slice(sig).map_err(rejected)?;
+            vk.verify(msg, &sig).map_err(rejected)
+        }
+        SigAlg::EcdsaP256 => ecdsa_verify(EcdsaCurve::P256, pk, hash, msg, sig),
+        SigAlg::EcdsaP384 => ecdsa_verify(EcdsaCurve::P384, pk, hash, msg, sig),
+        SigAlg::RsaPss => {
+            let key = rsa_public(pk)?;
+            with_sha2!(hash.unwrap_or(HashAlg::Sha256), H => {
+                let vk = rsa::pss::VerifyingKey::<H>::new(key);
+                let sig = rsa::pss::Signature::try_from(sig).map_err(rejected)?;
+                vk.verify(msg, &sig).map_err(rejected)
+            })
+        }
+        SigAlg::MlDsa44 | SigAlg::MlDsa65 | SigAlg::MlDsa87 => with_mldsa!(alg, P => {
+            let vk = mldsa_verifying::<P>(pk)?;
+            let encoded: ml_dsa::EncodedSignature<P> = sig
+                .try_into()
+                .map_err(|_| invalid("rustcrypto: signature does not verify"))?;
+            let sig = ml_dsa::Signature::<P>::decode(&encoded)
+                .ok_or_else(|| invalid("rustcrypto: signature does not verify"))?;
+            vk.verify(msg, &sig).map_err(rejected)
+        }),
+    }
+}
+
+/// Generate a keypair, deterministically from `seed` when given.
+pub fn generate(alg: SigAlg, seed: Option<&[u8]>) -> PluginResult<SignatureKeypair> {
+    let mut fresh = Zeroizing::new([0u8; 32]);
+    let seed32 = |seed: Option<&[u8]>, fresh: &mut [u8; 32]| -> PluginResult<Vec<u8>> {
+        match seed {
+            Some(s) if s.len() == 32 => Ok(s.to_vec()),
+            Some(_) => Err(invalid("rustcrypto: seed must be 32 bytes")),
+            None => {
+                crate::os_random(fresh)?;
+                Ok(fresh.to_vec())
+            }
+        }
+    };
+    match alg {
+        SigAlg::Ed25519 => {
+            let sk = seed32(seed, &mut fresh)?;
+            let public_key = ed25519_signing(&sk)?.verifying_key().to_bytes().to_vec();
+            Ok(SignatureKeypair {
+                public_key,
+                secret_key: sk,
+            })
+        }
+        SigAlg::EcdsaP256 => ecdsa_generate(EcdsaCurve::P256, seed),
+        SigAlg::EcdsaP384 => ecdsa_generate(EcdsaCurve::P384, seed),
+        SigAlg::RsaPss => {
+            use p256::elliptic_curve::rand_core::UnwrapErr;
+            use rsa::pkcs8::{EncodePrivateKey as _, EncodePublicKey as _};
+            if seed.is_some() {
+                return Err(invalid(
+                    "rustcrypto: rsa-pss keys cannot be derived from a seed",
+                ));
+            }
+            let key = rsa::RsaPrivateKey::new(&mut UnwrapErr(getrandom::SysRng), RSA_KEYGEN_BITS)
+                .map_err(|e| {
+                confium_api::PluginError::msg(format!("rustcrypto: RSA keygen: {e}"))
+            })?;
+            fn encode_err<E>(_: E) -> confium_api::PluginError {
+                confium_api::PluginError::msg("rustcrypto: RSA key encoding failed")
+            }
+            let public_key = key
+                .to_public_key()
+                .to_public_key_der()
+                .map_err(encode_err)?
+                .as_bytes()
+                .to_vec();
+            let secret_key = key.to_pkcs8_der().map_err(encode_err)?.as_bytes().to_vec();
+            Ok(SignatureKeypair {
+                public_key,
+                secret_key,
+            })
+        }
+        SigAlg::MlDsa44 | SigAlg::MlDsa65 | SigAlg::MlDsa87 => {
+            use ::signature::Keypair as _;
+            let sk = seed32(seed, &mut fresh)?;
+            let public_key = with_mldsa!(alg, P => {
+                mldsa_signing::<P>(&sk)?.verifying_key().encode().to_vec()
+            });
+            Ok(SignatureKeypair {
+                public_key,
+                secret_key: sk,
+            })
+        }
+    }
+}
+
+/// ECDSA over the two NIST curves. The curve crates share an API but
+/// not a trait we can be generic over without pulling `ecdsa` bounds
+/// into every signature, so each operation dispatches on [`EcdsaCurve`].
+mod ecdsa_sig {
+    use ::signature::hazmat::{PrehashSigner as _, PrehashVerifier as _};
+    use confium_api::error::PluginResult;
+    use confium_api::plugin::signature::SignatureKeypair;
+    use p256::elliptic_curve::Generate as _;
+    use p256::elliptic_curve::rand_core::UnwrapErr;
+
+    use crate::hash::HashAlg;
+    use crate::invalid;
+
+    #[derive(Debug, Clone, Copy)]
+    pub enum EcdsaCurve {
+        P256,
+        P384,
+    }
+
+    impl EcdsaCurve {
+        fn default_hash(self) -> HashAlg {
+            match self {
+                Self::P256 => HashAlg::Sha256,
+                Self::P384 => HashAlg::Sha384,
+            }
+        }
+    }
+
+    macro_rules! with_curve {
+        ($curve:expr, $c:ident => $body:expr) => {
+            match $curve {
+                EcdsaCurve::P256 => {
+                    use p256 as $c;
+                    $body
+                }
+                EcdsaCurve::P384 => {
+                    use p384 as $c;
+                    $body
+                }
+            }
+        };
+    }
+
+    pub fn ecdsa_sign(
+        curve: EcdsaCurve,
+        sk: &[u8],
+        hash: Option<HashAlg>,
+        msg: &[u8],
+    ) -> PluginResult<Vec<u8>> {
+        let digest = hash.unwrap_or(curve.default_hash()).digest(msg);
+        with_curve!(curve, c => {
+            let key = c::ecdsa::SigningKey::from_slice(sk)
+                .map_err(|_| invalid("rustcrypto: ECDSA secret key is a scalar of the curve's size"))?;
+            let sig: c::ecdsa::Signature = key
+                .sign_prehash(&digest)
+                .map_err(|_| invalid("rustcrypto: digest too short for this curve"))?;
+            Ok(sig.to_der().as_bytes().to_vec())
+        })
+    }
+
+    pub fn ecdsa_verify(
+        curve: EcdsaCurve,
+        pk: &[u8],
+        hash: Option<HashAlg>,
+        msg: &[u8],
+        sig: &[u8],
+    ) -> PluginResult<()> {
+        let rejected = |_| invalid("rustcrypto: signature does not verify");
+        let digest = hash.unwrap_or(curve.default_hash()).digest(msg);
+        with_curve!(curve, c => {
+            let key = c::ecdsa::VerifyingKey::from_sec1_bytes(pk)
+                .map_err(|_| invalid("rustcrypto: ECDSA public key is not a SEC1 curve point"))?;
+            let sig = c::ecdsa::Signature::from_der(sig).map_err(rejected)?;
+            key.verify_prehash(&digest, &sig).map_err(rejected)
+        })
+    }
+
+    pub fn ecdsa_generate(
+        curve: EcdsaCurve,
+        seed: Option<&[u8]>,
+    ) -> PluginResult<SignatureKeypair> {
+        with_curve!(curve, c => {
+            let key = match seed {
+                Some(s) => c::ecdsa::SigningKey::from_slice(s)
+                    .map_err(|_| invalid("rustcrypto: ECDSA seed must be a valid scalar"))?,
+                None => c::ecdsa::SigningKey::generate_from_rng(&mut UnwrapErr(getrandom::SysRng)),
+            };
+            Ok(SignatureKeypair {
+                public_key: key.verifying_key().to_sec1_point(false).as_bytes().to_vec(),
+                secret_key: key.to_bytes().to_vec(),
+            })
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Role {
+    Signer,
+    Verifier,
+}
+
+/// Signer or verifier context: the key, the chosen digest and the
+/// buffered message.
+pub struct SigState {
+    alg: SigAlg,
+    role: Role,
+    key: Zeroizing<Vec<u8>>,
+    hash: Option<HashAlg>,
+    message: Vec<u8>,
+}
+
+#[plugin_interface(name = "signature", version = 0)]
+impl SignaturePlugin for SigState {
+    fn signer_create(
+        algorithm: &str,
+        secret_key: &[u8],
+        _opts: Option<OptionView<'_>>,
+    ) -> PluginResult<Self> {
+        let alg = SigAlg::from_name(algorithm).ok_or_else(|| unsupported(algorithm))?;
+        // Fail at create rather than at finalize on an unusable key.
+        match alg {
+            SigAlg::Ed25519 => ed25519_signing(secret_key).map(drop)?,
+            SigAlg::EcdsaP256 | SigAlg::EcdsaP384 => {
+                let want = if alg == SigAlg::EcdsaP256 { 32 } else { 48 };
+                if secret_key.len() != want {
+                    return Err(invalid(format!(
+                        "rustcrypto: {algorithm} secret key is a {want}-byte scalar"
+                    )));
+                }
+            }
+            SigAlg::RsaPss => rsa_private(secret_key).map(drop)?,
+            SigAlg::MlDsa44 | SigAlg::MlDsa65 | SigAlg::MlDsa87 => {
+                with_mldsa!(alg, P => mldsa_signing::<P>(secret_key).map(drop)?)
+            }
+        }
+        Ok(Self {
+            alg,
+            role: Role::Signer,
+            key: Zeroizing::new(secret_key.to_vec()),
+            hash: None,
+            message: Vec::new(),
+        })
+    }
+
+    fn verifier_create(
+        algorithm: &str,
+        public_key: &[u8],
+        _opts: Option<OptionView<'_>>,
+    ) -> PluginResult<Self> {
+        let alg = SigAlg::from_name(algorithm).ok_or_else(|| unsupported(algorithm))?;
+        match alg {
+            SigAlg::Ed25519 => ed25519_verifying(public_key).map(drop)?,
+            SigAlg::EcdsaP256 => p256::ecdsa::VerifyingKey::from_sec1_bytes(public_key)
+                .map(drop)
+                .map_err(|_| invalid("rustcrypto: ECDSA public key is not a SEC1 curve point"))?,
+            SigAlg::EcdsaP384 => p384::ecdsa::VerifyingKey::from_sec1_bytes(public_key)
+                .map(drop)
+                .map_err(|_| invalid("rustcrypto: ECDSA public key is not a SEC1 curve point"))?,
+            SigAlg::RsaPss => rsa_public(public_key).map(drop)?,
+            SigAlg::MlDsa44 | SigAlg::MlDsa65 | SigAlg::MlDsa87 => {
+                with_mldsa!(alg, P => mldsa_verifying::<P>(public_key).map(drop)?)
+            }
+        }
+        Ok(Self {
+            alg,
+            role: Role::Verifier,
+            key: Zeroizing::new(public_key.to_vec()),
+            hash: None,
+            message: Vec::new(),
+        })
+    }
+
+    fn set_hash(&mut self, hash_name: &str) -> PluginResult<()> {
+        if !self.alg.takes_hash() {
+            return Ok(());
+        }
+        self.hash = Some(HashAlg::from_name(hash_name).ok_or_else(|| unsupported(hash_name))?);
+        Ok(())
+    }
+
+    fn update(&mut self, data: &[u8]) -> PluginResult<()> {
+        self.message.extend_from_slice(data);
+        Ok(())
+    }
+
+    fn signer_finalize(&mut self, sig_out: &mut [u8]) -> PluginResult<usize> {
+        if self.role != Role::Signer {
+            return Err(invalid("rustcrypto: signer_finalize on a verifier"));
+        }
+        let sig = sign(self.alg, &self.key, self.hash, &self.message)?;
+        check_buffer(sig_out.len(), sig.len())?;
+        sig_out[..sig.len()].copy_from_slice(&sig);
+        self.message.clear();
+        Ok(sig.len())
+    }
+
+    fn verifier_finalize(&mut self, signature: &[u8]) -> PluginResult<()> {
+        if self.role != Role::Verifier {
+            return Err(invalid("rustcrypto: verifier_finalize on a signer"));
+        }
+        let result = verify(self.alg, &self.key, self.hash, &self.message, signature);
+        self.message.clear();
+        result
+    }
+
+    fn keypair_generate(
+        algorithm: &str,
+        seed: Option<&[u8]>,
+        _opts: Option<OptionView<'_>>,
+    ) -> PluginResult<SignatureKeypair> {
+        let alg = SigAlg::from_name(algorithm).ok_or_else(|| unsupported(algorithm))?;
+        generate(alg, seed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use confium_api::ErrorCode;
+
+    fn round_trip(alg: &str, hash: Option<&str>) {
+        let kp = SigState::keypair_generate(alg, None, None).unwrap();
+        let mut s = SigState::signer_create(alg, &kp.secret_key, None).unwrap();
+        let mut v = SigState::verifier_create(alg, &kp.public_key, None).unwrap();
+        if let Some(h) = hash {
+            s.set_hash(h).unwrap();
+            v.set_hash(h).unwrap();
+        }
+        s.update(b"hello ").unwrap();
+        s.update(b"world").unwrap();
+        let mut sig = vec![0u8; 8192];
+        let n = s.signer_finalize(&mut sig).unwrap();
+        sig.truncate(n);
+
+        v.update(b"hello world").unwrap();
+        v.verifier_finalize(&sig).unwrap();
+        v.update(b"hello there").unwrap();
+        assert_eq!(
+            v.verifier_finalize(&sig).unwrap_err().code(),
+            ErrorCode::WRONG_TYPE,
+            "{alg}"
+        );
+    }
+
+    #[test]
+    fn every_algorithm_round_trips() {
+        for alg in [
+            "ed25519",
+            "ecdsa-p256",
+            "ecdsa-p384",
+            "ml-dsa-44",
+            "ml-dsa-65",
+            "ml-dsa-87",
+        ] {
+            round_trip(alg, None);
+        }
+        round_trip("ecdsa-p256", Some("sha-512"));
+        round_trip("ecdsa-p384", Some("sha3-384"));
+    }
+
+    #[test]
+    fn rsa_pss_round_trips() {
+        round_trip("rsa-pss", None);
+    }
+
+    #[test]
+    fn ed25519_rfc8032_test_1() {
+        let sk = hex::decode("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60")
+            .unwrap();
+        let kp = SigState::keypair_generate("ed25519", Some(&sk), None).unwrap();
+        assert_eq!(
+            hex::encode(&kp.public_key),
+            "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"
+        );
+        let sig = sign(SigAlg::Ed25519, &sk, None, b"").unwrap();
+        assert_eq!(
+            hex::encode(sig),
+            "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e06522490155\
+             5fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b"
+        );
+    }
+
+    #[test]
+    fn seeded_keygen_is_deterministic() {
+        let seed = [7u8; 32];
+        for alg in ["ed25519", "ecdsa-p256", "ml-dsa-65"] {
+            let a = SigState::keypair_generate(alg, Some(&seed), None).unwrap();
+            let b = SigState::keypair_generate(alg, Some(&seed), None).unwrap();
+            assert_eq!(a.public_key, b.public_key, "{alg}");
+        }
+        assert!(SigState::keypair_generate("rsa-pss", Some(&seed), None).is_err());
+    }
+
+    #[test]
+    fn misuse_is_rejected() {
+        assert!(SigState::signer_create("ed25519", &[0u8; 31], None).is_err());
+        assert!(SigState::verifier_create("ecdsa-p256", &[4u8; 65], None).is_err());
+        let kp = SigState::keypair_generate("ed25519", None, None).unwrap();
+        let mut s = SigState::signer_create("ed25519", &kp.secret_key, None).unwrap();
+        s.set_hash("sha-256").unwrap();
+        assert!(s.hash.is_none(), "ed25519 ignores set_hash");
+        assert!(s.verifier_finalize(&[0u8; 64]).is_err());
+        let err = s.signer_finalize(&mut [0u8; 63]).unwrap_err();
+        assert_eq!(err.code(), ErrorCode::INSUFFICIENT_BUFFER);
+        assert!(SigState::signer_create("dsa", &[], None).is_err());
+    }
+}
@@ -7,6 +7,15 @@
 //! - `echo`  -> returns its args verbatim as results.
 //! - `add`   -> sums two integer args, returns `[sum]`.
 //! - `ping`  -> returns `[1]` (no args).
+//! - `crash` -> aborts the process without answering, standing in for
+//!   a plugin that segfaults mid-call.
+//! - `spin`  -> burns CPU forever without answering.
+//! - `socket` -> binds a UDP socket on loopback, returns `[1]`.
+//! - `read_file` -> reads the file whose path is the bytes arg, returns
+//!   `[<contents>]`.
+//!
+//! The last two stand in for a plugin reaching for the network or the
+//! filesystem; either fails with the OS error as the message.
 //! - anything else -> returns `{"error":{"message":"unknown method: <m>"}}`.
 //!
 //! Not part of the public API; built only when the `test-bin` feature
@@ -48,6 +57,31 @@ fn main() {
         let resp = match method {
             "echo" => serde_json::json!({ "result": args }),
             "ping" => serde_json::json!({ "result": [1] }),
+            "crash" => std::process::abort(),
+            "spin" => loop {
+                std::hint::spin_loop();
+            },
+            "socket" => match std::net::UdpSocket::bind("127.0.0.1:0") {
+                Ok(_) => serde_json::json!({ "result": [1] }),
+                Err(e) => error_obj(&format!("socket: {e}")),
+            },
+            "read_file" => {
+                let path: Vec<u8> = args
+                    .first()
+                    .and_then(|v| v.as_array())
+                    .map(|a| {
+                        a.iter()
+                            .filter_map(|b| b.as_u64())
+                            .map(|b| b as u8)
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                let path = String::from_utf8_lossy(&path).into_owned();
+                match std::fs::read(&path) {
+                    Ok(bytes) => serde_json::json!({ "result": [bytes] }),
+                    Err(e) => error_obj(&format!("read {path}: {e}")),
+                }
+            }
             "add" => {
                 let a = args.first().and_then(|v| v.as_i64()).unwrap_or(0);
                 let b = args.get(1).and_then(|v| v.as_i64()).unwrap_or(0);
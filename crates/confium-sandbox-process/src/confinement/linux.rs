@@ -0,0 +1,557 @@
+//! The Linux implementation of [`Confinement`].
+//!
+//! [`apply`] does every allocation and every check that does not need
+//! the child up front, producing a [`Plan`]. The `pre_exec` hook then
+//! only makes syscalls on data the plan already holds, which keeps it
+//! safe to run between `fork` and `exec`.
+
+use std::ffi::CString;
+use std::fs;
+use std::io;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::process::CommandExt;
+use std::path::Path;
+use std::process::Command;
+
+use super::Confinement;
+use crate::sandbox::FilesystemMode;
+
+/// The uid and gid the child runs as inside its user namespace. Not 0,
+/// so the namespace's capabilities are dropped at `exec`.
+const NOBODY: u32 = 65534;
+
+/// Directories the dynamic loader needs to start any plugin binary.
+/// Missing ones are skipped.
+const LIBRARY_DIRS: &[&str] = &["/lib", "/lib64", "/usr/lib", "/usr/lib64"];
+
+/// Files the dynamic loader reads on startup. Missing ones are skipped.
+const LOADER_FILES: &[&str] = &["/etc/ld.so.cache"];
+
+pub(super) fn apply(policy: &Confinement, command: &mut Command) -> io::Result<()> {
+    let plan = Plan::new(policy, Path::new(command.get_program()))?;
+    // SAFETY: `Plan::enter` only issues raw syscalls over memory the
+    // plan owns; it does not allocate, lock or touch the parent's
+    // other threads' state.
+    unsafe {
+        command.pre_exec(move || plan.enter());
+    }
+    Ok(())
+}
+
+/// Everything the child needs to confine itself, prepared in the
+/// parent.
+struct Plan {
+    memory_bytes: u64,
+    cpu_seconds: u64,
+    unshare_flags: libc::c_int,
+    uid_map: Vec<u8>,
+    gid_map: Vec<u8>,
+    landlock: landlock::Ruleset,
+    filter: Vec<libc::sock_filter>,
+}
+
+impl Plan {
+    fn new(policy: &Confinement, program: &Path) -> io::Result<Self> {
+        // `exec` resolves a bare name through PATH, which the ruleset
+        // cannot follow; insist on a path we can grant.
+        if program.components().count() < 2 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "a confined plugin must be started by path, not by name",
+            ));
+        }
+        let program = fs::canonicalize(program)?;
+
+        let mut ruleset = landlock::Ruleset::new()?;
+        ruleset.allow(&program, landlock::READ_FILE | landlock::EXECUTE)?;
+        for dir in LIBRARY_DIRS {
+            if Path::new(dir).exists() {
+                ruleset.allow(Path::new(dir), landlock::READ | landlock::EXECUTE)?;
+            }
+        }
+        for file in LOADER_FILES {
+            if Path::new(file).exists() {
+                ruleset.allow(Path::new(file), landlock::READ_FILE)?;
+            }
+        }
+        for (path, mode) in policy.paths() {
+            let access = match mode {
+                FilesystemMode::ReadOnly => landlock::READ,
+                FilesystemMode::ReadWrite => ruleset.handled(),
+            };
+            ruleset.allow(path, access)?;
+        }
+
+        let mut unshare_flags = libc::CLONE_NEWUSER | libc::CLONE_NEWNS | libc::CLONE_NEWIPC;
+        if !policy.allows_network() {
+            unshare_flags |= libc::CLONE_NEWNET;
+        }
+
+        // SAFETY: getuid/getgid cannot fail.
+        let (uid, gid) = unsafe { (libc::getuid(), libc::getgid()) };
+        Ok(Self {
+            memory_bytes: policy.memory_bytes,
+            cpu_seconds: policy.cpu_seconds,
+            unshare_flags,
+            uid_map: format!("{NOBODY} {uid} 1").into_bytes(),
+            gid_map: format!("{NOBODY} {gid} 1").into_bytes(),
+            landlock: ruleset,
+            filter: seccomp::filter(policy.allows_network()),
+        })
+    }
+
+    /// Runs in the child, after `fork` and before `exec`.
+    fn enter(&self) -> io::Result<()> {
+        set_limit(libc::RLIMIT_AS, self.memory_bytes, self.memory_bytes)?;
+        // The kernel checks the hard limit first and answers it with
+        // SIGKILL; one second of headroom lets SIGXCPU, which the host
+        // reports as a violation, arrive first.
+        set_limit(
+            libc::RLIMIT_CPU,
+            self.cpu_seconds,
+            self.cpu_seconds.saturating_add(1),
+        )?;
+        set_limit(libc::RLIMIT_CORE, 0, 0)?;
+
+        // SAFETY: plain syscalls on constant or plan-owned arguments.
+        unsafe {
+            check(libc::unshare(self.unshare_flags))?;
+            write_proc(c"/proc/self/setgroups", b"deny")?;
+            write_proc(c"/proc/self/uid_map", &self.uid_map)?;
+            write_proc(c"/proc/self/gid_map", &self.gid_map)?;
+            // Keep mount events in the child's namespace from reaching
+            // the parent's, and the reverse.
+            check(libc::mount(
+                std::ptr::null(),
+                c"/".as_ptr(),
+                std::ptr::null(),
+                libc::MS_REC | libc::MS_PRIVATE,
+                std::ptr::null(),
+            ))?;
+            check(libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0))?;
+        }
+        self.landlock.restrict_self()?;
+        seccomp::install(&self.filter)
+    }
+}
+
+fn set_limit(resource: libc::__rlimit_resource_t, soft: u64, hard: u64) -> io::Result<()> {
+    let limit = libc::rlimit {
+        rlim_cur: soft as libc::rlim_t,
+        rlim_max: hard as libc::rlim_t,
+    };
+    // SAFETY: `limit` is a valid rlimit for the duration of the call.
+    check(unsafe { libc::setrlimit(resource, &limit) })
+}
+
+/// Write `data` to one of the child's `/proc/self` control files.
+///
+/// # Safety
+///
+/// Only meant for the `pre_exec` hook; `path` must be NUL-terminated.
+unsafe fn write_proc(path: &std::ffi::CStr, data: &[u8]) -> io::Result<()> {
+    // SAFETY: `path` is NUL-terminated and `data` outlives the write.
+    unsafe {
+        let fd = libc::open(path.as_ptr(), libc::O_WRONLY | libc::O_CLOEXEC);
+        check(fd)?;
+        let written = libc::write(fd, data.as_ptr().cast(), data.len());
+        libc::close(fd);
+        if written < 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+fn check(ret: libc::c_int) -> io::Result<()> {
+    if ret < 0 {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(())
+    }
+}
+
+fn c_path(path: &Path) -> io::Result<CString> {
+    CString::new(path.as_os_str().as_bytes()).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("path {} contains a NUL byte", path.display()),
+        )
+    })
+}
+
+/// Landlock filesystem rules, via the raw syscalls.
+mod landlock {
+    use std::io;
+    use std::path::Path;
+
+    use super::c_path;
+    use super::check;
+
+    pub(super) const EXECUTE: u64 = 1 << 0;
+    pub(super) const WRITE_FILE: u64 = 1 << 1;
+    pub(super) const READ_FILE: u64 = 1 << 2;
+    pub(super) const READ_DIR: u64 = 1 << 3;
+    pub(super) const READ: u64 = READ_FILE | READ_DIR;
+    const TRUNCATE: u64 = 1 << 14;
+    const IOCTL_DEV: u64 = 1 << 15;
+
+    /// Rights that may be attached to a rule on a non-directory.
+    const FILE_RIGHTS: u64 = EXECUTE | WRITE_FILE | READ_FILE | TRUNCATE | IOCTL_DEV;
+
+    const CREATE_RULESET_VERSION: u32 = 1 << 0;
+    const RULE_PATH_BENEATH: libc::c_int = 1;
+
+    #[repr(C)]
+    struct RulesetAttr {
+        handled_access_fs: u64,
+    }
+
+    #[repr(C, packed)]
+    struct PathBeneathAttr {
+        allowed_access: u64,
+        parent_fd: i32,
+    }
+
+    pub(super) struct Ruleset {
+        handled: u64,
+        rules: Vec<(std::ffi::CString, u64)>,
+    }
+
+    impl Ruleset {
+        /// Probe the kernel's landlock ABI and handle every filesystem
+        /// right it knows about.
+        pub(super) fn new() -> io::Result<Self> {
+            // SAFETY: the version query takes no attribute struct.
+            let abi = unsafe {
+                libc::syscall(
+                    libc::SYS_landlock_create_ruleset,
+                    std::ptr::null::<RulesetAttr>(),
+                    0usize,
+                    CREATE_RULESET_VERSION,
+                )
+            };
+            if abi < 1 {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    format!("landlock is not available: {}", io::Error::last_os_error()),
+                ));
+            }
+            // ABI 1 covers the first 13 rights; later ABIs add REFER
+            // (2), TRUNCATE (3) and IOCTL_DEV (5).
+            let mut handled = (1 << 13) - 1;
+            if abi >= 2 {
+                handled |= 1 << 13;
+            }
+            if abi >= 3 {
+                handled |= TRUNCATE;
+            }
+            if abi >= 5 {
+                handled |= IOCTL_DEV;
+            }
+            Ok(Self {
+                handled,
+                rules: Vec::new(),
+            })
+        }
+
+        pub(super) fn handled(&self) -> u64 {
+            self.handled
+        }
+
+        /// Allow `access` beneath `path`, which must exist.
+        pub(super) fn allow(&mut self, path: &Path, access: u64) -> io::Result<()> {
+            let meta = std::fs::metadata(path).map_err(|e| {
+                io::Error::new(e.kind(), format!("granted path {}: {e}", path.display()))
+            })?;
+            let mut access = access & self.handled;
+            if !meta.is_dir() {
+                access &= FILE_RIGHTS;
+            }
+            self.rules.push((c_path(path)?, access));
+            Ok(())
+        }
+
+        /// Confine the calling process. Runs in the child.
+        pub(super) fn restrict_self(&self) -> io::Result<()> {
+            let attr = RulesetAttr {
+                handled_access_fs: self.handled,
+            };
+            // SAFETY: raw landlock syscalls over plan-owned memory;
+            // every fd opened here is closed before returning.
+            unsafe {
+                let ruleset = libc::syscall(
+                    libc::SYS_landlock_create_ruleset,
+                    &attr as *const RulesetAttr,
+                    std::mem::size_of::<RulesetAttr>(),
+                    0u32,
+                ) as libc::c_int;
+                check(ruleset)?;
+                let result = self.add_rules(ruleset).and_then(|()| {
+                    check(libc::syscall(libc::SYS_landlock_restrict_self, ruleset, 0u32) as _)
+                });
+                libc::close(ruleset);
+                result
+            }
+        }
+
+        /// # Safety
+        ///
+        /// `ruleset` must be a landlock ruleset fd.
+        unsafe fn add_rules(&self, ruleset: libc::c_int) -> io::Result<()> {
+            for (path, access) in &self.rules {
+                // SAFETY: `path` is NUL-terminated; the fd is closed
+                // below whatever the outcome.
+                unsafe {
+                    let fd = libc::open(path.as_ptr(), libc::O_PATH | libc::O_CLOEXEC);
+                    check(fd)?;
+                    let rule = PathBeneathAttr {
+                        allowed_access: *access,
+                        parent_fd: fd,
+                    };
+                    let ret = libc::syscall(
+                        libc::SYS_landlock_add_rule,
+                        ruleset,
+                        RULE_PATH_BENEATH,
+                        &rule as *const PathBeneathAttr,
+                        0u32,
+                    ) as libc::c_int;
+                    libc::close(fd);
+                    check(ret)?;
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+/// The seccomp-bpf allow-list.
+mod seccomp {
+    use std::io;
+
+    use super::check;
+
+    #[cfg(target_arch = "x86_64")]
+    const AUDIT_ARCH: u32 = 0xC000_003E;
+    #[cfg(target_arch = "aarch64")]
+    const AUDIT_ARCH: u32 = 0xC000_00B7;
+
+    const RET_KILL_PROCESS: u32 = 0x8000_0000;
+    const RET_ALLOW: u32 = 0x7fff_0000;
+    const RET_ERRNO: u32 = 0x0005_0000;
+
+    // Offsets into `struct seccomp_data`.
+    const DATA_NR: u32 = 0;
+    const DATA_ARCH: u32 = 4;
+    const DATA_ARG0: u32 = 16;
+
+    const LD_W_ABS: u16 = (libc::BPF_LD | libc::BPF_W | libc::BPF_ABS) as u16;
+    const JEQ_K: u16 = (libc::BPF_JMP | libc::BPF_JEQ | libc::BPF_K) as u16;
+    const JSET_K: u16 = (libc::BPF_JMP | libc::BPF_JSET | libc::BPF_K) as u16;
+    const RET_K: u16 = (libc::BPF_RET | libc::BPF_K) as u16;
+
+    /// What any plugin needs: the loader and runtime start-up, memory
+    /// management, threads, signals, clocks, randomness, and I/O on
+    /// descriptors and on the paths landlock lets through.
+    const BASE: &[libc::c_long] = &[
+        libc::SYS_read,
+        libc::SYS_write,
+        libc::SYS_readv,
+        libc::SYS_writev,
+        libc::SYS_pread64,
+        libc::SYS_pwrite64,
+        libc::SYS_close,
+        libc::SYS_lseek,
+        libc::SYS_openat,
+        libc::SYS_fstat,
+        libc::SYS_newfstatat,
+        libc::SYS_statx,
+        libc::SYS_fcntl,
+        libc::SYS_getdents64,
+        libc::SYS_readlinkat,
+        libc::SYS_faccessat,
+        libc::SYS_faccessat2,
+        libc::SYS_getcwd,
+        libc::SYS_dup,
+        libc::SYS_dup3,
+        libc::SYS_pipe2,
+        libc::SYS_mkdirat,
+        libc::SYS_unlinkat,
+        libc::SYS_renameat2,
+        libc::SYS_ftruncate,
+        libc::SYS_fsync,
+        libc::SYS_fdatasync,
+        libc::SYS_mmap,
+        libc::SYS_munmap,
+        libc::SYS_mprotect,
+        libc::SYS_mremap,
+        libc::SYS_madvise,
+        libc::SYS_mlock,
+        libc::SYS_munlock,
+        libc::SYS_brk,
+        libc::SYS_rt_sigaction,
+        libc::SYS_rt_sigprocmask,
+        libc::SYS_rt_sigreturn,
+        libc::SYS_sigaltstack,
+        libc::SYS_futex,
+        libc::SYS_sched_yield,
+        libc::SYS_sched_getaffinity,
+        libc::SYS_set_robust_list,
+        libc::SYS_set_tid_address,
+        libc::SYS_rseq,
+        libc::SYS_prlimit64,
+        libc::SYS_getrandom,
+        libc::SYS_clock_gettime,
+        libc::SYS_clock_getres,
+        libc::SYS_clock_nanosleep,
+        libc::SYS_nanosleep,
+        libc::SYS_gettimeofday,
+        libc::SYS_getpid,
+        libc::SYS_gettid,
+        libc::SYS_getuid,
+        libc::SYS_geteuid,
+        libc::SYS_getgid,
+        libc::SYS_getegid,
+        libc::SYS_tgkill,
+        libc::SYS_uname,
+        libc::SYS_ppoll,
+        libc::SYS_pselect6,
+        libc::SYS_epoll_create1,
+        libc::SYS_epoll_ctl,
+        libc::SYS_epoll_pwait,
+        libc::SYS_eventfd2,
+        libc::SYS_exit,
+        libc::SYS_exit_group,
+        // The filter is installed before `exec`, so `exec` itself must
+        // pass; landlock limits what can be executed.
+        libc::SYS_execve,
+    ];
+
+    /// Legacy syscalls that only exist on x86_64.
+    #[cfg(target_arch = "x86_64")]
+    const ARCH: &[libc::c_long] = &[
+        libc::SYS_arch_prctl,
+        libc::SYS_open,
+        libc::SYS_stat,
+        libc::SYS_lstat,
+        libc::SYS_access,
+        libc::SYS_readlink,
+        libc::SYS_poll,
+        libc::SYS_dup2,
+        libc::SYS_pipe,
+        libc::SYS_epoll_wait,
+    ];
+    #[cfg(target_arch = "aarch64")]
+    const ARCH: &[libc::c_long] = &[];
+
+    /// Added when the plugin holds a network endpoint.
+    const NETWORK: &[libc::c_long] = &[
+        libc::SYS_socket,
+        libc::SYS_connect,
+        libc::SYS_bind,
+        libc::SYS_sendto,
+        libc::SYS_recvfrom,
+        libc::SYS_sendmsg,
+        libc::SYS_recvmsg,
+        libc::SYS_getsockname,
+        libc::SYS_getpeername,
+        libc::SYS_getsockopt,
+        libc::SYS_setsockopt,
+        libc::SYS_shutdown,
+    ];
+
+    fn stmt(code: u16, k: u32) -> libc::sock_filter {
+        libc::sock_filter {
+            code,
+            jt: 0,
+            jf: 0,
+            k,
+        }
+    }
+
+    fn jump(code: u16, k: u32, jt: u8, jf: u8) -> libc::sock_filter {
+        libc::sock_filter { code, jt, jf, k }
+    }
+
+    /// Build the filter program. Anything not allowed kills the whole
+    /// process.
+    pub(super) fn filter(network: bool) -> Vec<libc::sock_filter> {
+        let mut prog = vec![
+            stmt(LD_W_ABS, DATA_ARCH),
+            jump(JEQ_K, AUDIT_ARCH, 1, 0),
+            stmt(RET_K, RET_KILL_PROCESS),
+            stmt(LD_W_ABS, DATA_NR),
+        ];
+        // The x32 ABI shares x86_64's audit arch; its syscall numbers
+        // carry bit 30 and none of them are allowed.
+        #[cfg(target_arch = "x86_64")]
+        prog.extend([
+            jump(JSET_K, 0x4000_0000, 0, 1),
+            stmt(RET_K, RET_KILL_PROCESS),
+        ]);
+
+        // Threads only: `clone` without CLONE_THREAD would fork.
+        // `clone3` hides its flags behind a pointer the filter cannot
+        // read, so it reports ENOSYS and libc falls back to `clone`.
+        prog.extend([
+            jump(JEQ_K, libc::SYS_clone as u32, 0, 4),
+            stmt(LD_W_ABS, DATA_ARG0),
+            jump(JSET_K, libc::CLONE_THREAD as u32, 0, 1),
+            stmt(RET_K, RET_ALLOW),
+            stmt(RET_K, RET_KILL_PROCESS),
+            jump(JEQ_K, libc::SYS_clone3 as u32, 0, 1),
+            stmt(RET_K, RET_ERRNO | libc::ENOSYS as u32),
+        ]);
+
+        let network = if network { NETWORK } else { &[] };
+        for &nr in BASE.iter().chain(ARCH).chain(network) {
+            prog.push(jump(JEQ_K, nr as u32, 0, 1));
+            prog.push(stmt(RET_K, RET_ALLOW));
+        }
+        prog.push(stmt(RET_K, RET_KILL_PROCESS));
+        prog
+    }
+
+    /// Install `filter` on the calling process. Runs in the child,
+    /// after `no_new_privs` is set.
+    pub(super) fn install(filter: &[libc::sock_filter]) -> io::Result<()> {
+        let prog = libc::sock_fprog {
+            len: filter.len() as libc::c_ushort,
+            filter: filter.as_ptr() as *mut libc::sock_filter,
+        };
+        // SAFETY: `prog` points at `filter`, which outlives the call;
+        // the kernel copies the program.
+        check(unsafe {
+            libc::prctl(
+                libc::PR_SET_SECCOMP,
+                libc::SECCOMP_MODE_FILTER,
+                &prog as *const libc::sock_fprog,
+            )
+        })
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn filter_checks_arch_first_and_kills_by_default() {
+            let prog = filter(false);
+            assert_eq!(prog[0].k, DATA_ARCH);
+            assert_eq!(prog[1].k, AUDIT_ARCH);
+            let last = prog.last().unwrap();
+            assert_eq!((last.code, last.k), (RET_K, RET_KILL_PROCESS));
+        }
+
+        #[test]
+        fn socket_is_only_allowed_with_network() {
+            let allows = |prog: &[libc::sock_filter], nr: libc::c_long| {
+                prog.windows(2)
+                    .any(|w| w[0].code == JEQ_K && w[0].k == nr as u32 && w[1].k == RET_ALLOW)
+            };
+            assert!(!allows(&filter(false), libc::SYS_socket));
+            assert!(allows(&filter(true), libc::SYS_socket));
+            assert!(allows(&filter(false), libc::SYS_read));
+        }
+    }
+}
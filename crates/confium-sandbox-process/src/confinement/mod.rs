@@ -0,0 +1,265 @@
+//! OS-level confinement of plugin subprocesses.
+//!
+//! The capability set on a [`ProcessInstance`](crate::ProcessInstance)
+//! only decides which calls the host forwards; it has no say over what
+//! the child does while serving them. A [`Confinement`] restricts the
+//! child itself, between `fork` and `exec`, so even a compromised
+//! plugin stays inside the envelope its capabilities describe. On
+//! Linux that means, in order:
+//!
+//! 1. resource limits on address space and CPU time, and no core
+//!    dumps;
+//! 2. fresh user, mount and IPC namespaces, plus a network namespace
+//!    unless a [`Capability::NetworkEndpoint`] is granted;
+//! 3. `no_new_privs`, so setuid binaries and file capabilities are
+//!    inert;
+//! 4. a landlock ruleset that only admits the granted
+//!    [`Capability::FilesystemPath`]s, the plugin executable and the
+//!    system library directories;
+//! 5. a seccomp-bpf allow-list of the syscalls needed to serve the
+//!    protocol. Socket syscalls are on it only when the network is
+//!    granted.
+//!
+//! A syscall outside the allow-list kills the child, and running out
+//! of CPU time does the same. The host reports both as
+//! [`Error::ConfinementViolation`](crate::Error::ConfinementViolation).
+//! A filesystem access outside the ruleset fails inside the child with
+//! `EACCES`, which the plugin sees as an ordinary I/O error.
+//!
+//! The envelope is fixed once the child starts. Granting a capability
+//! to a confined instance afterwards only succeeds if the envelope
+//! already covers it; revoking one narrows the host-side gate. Network
+//! access is all or nothing at this level: the per-endpoint check stays
+//! with the host.
+//!
+//! Confinement fails closed. A kernel without landlock or unprivileged
+//! user namespaces, a platform other than Linux, or a Linux
+//! architecture other than x86_64 and aarch64 makes a confined spawn
+//! fail with [`Error::Confine`](crate::Error::Confine) rather than run
+//! the plugin unconfined.
+
+#[cfg(all(
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+mod linux;
+
+use std::fmt;
+use std::path::PathBuf;
+use std::process::Command;
+use std::process::ExitStatus;
+
+use crate::sandbox::Capability;
+use crate::sandbox::FilesystemMode;
+
+/// Default address-space limit for a confined plugin: 1 GiB.
+pub const DEFAULT_MEMORY_BYTES: u64 = 1 << 30;
+
+/// Default CPU-time limit for a confined plugin, in seconds. The limit
+/// covers the whole life of the process, not a single call.
+pub const DEFAULT_CPU_SECONDS: u64 = 600;
+
+/// The OS-level envelope a plugin subprocess runs in.
+///
+/// Built from the capabilities the plugin is meant to hold; see
+/// [`ProcessSandbox::spawn_confined`](crate::ProcessSandbox::spawn_confined).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Confinement {
+    capabilities: Vec<Capability>,
+    memory_bytes: u64,
+    cpu_seconds: u64,
+}
+
+impl Confinement {
+    /// The envelope for a plugin holding exactly `capabilities`, with
+    /// the default resource limits.
+    pub fn for_capabilities<I>(capabilities: I) -> Self
+    where
+        I: IntoIterator<Item = Capability>,
+    {
+        let mut caps: Vec<Capability> = Vec::new();
+        for cap in capabilities {
+            if !caps.contains(&cap) {
+                caps.push(cap);
+            }
+        }
+        Self {
+            capabilities: caps,
+            memory_bytes: DEFAULT_MEMORY_BYTES,
+            cpu_seconds: DEFAULT_CPU_SECONDS,
+        }
+    }
+
+    /// Cap the child's address space at `bytes`.
+    pub fn memory_limit(mut self, bytes: u64) -> Self {
+        self.memory_bytes = bytes;
+        self
+    }
+
+    /// Kill the child once it has used `seconds` of CPU time.
+    pub fn cpu_limit(mut self, seconds: u64) -> Self {
+        self.cpu_seconds = seconds;
+        self
+    }
+
+    /// The capabilities the envelope was built from.
+    pub fn capabilities(&self) -> &[Capability] {
+        &self.capabilities
+    }
+
+    /// Whether the child may use the network at all.
+    pub fn allows_network(&self) -> bool {
+        self.capabilities
+            .iter()
+            .any(|c| matches!(c, Capability::NetworkEndpoint { .. }))
+    }
+
+    /// Whether a plugin confined by `self` could exercise `cap`.
+    ///
+    /// Interface and key capabilities are enforced by the host alone,
+    /// so they are always covered. A filesystem path is covered by a
+    /// granted path at or above it with at least the same mode.
+    pub fn covers(&self, cap: &Capability) -> bool {
+        match cap {
+            Capability::InterfaceAccess { .. } | Capability::KeyAccess { .. } => true,
+            Capability::NetworkEndpoint { .. } => self.allows_network(),
+            Capability::FilesystemPath { path, mode } => {
+                self.paths().any(|(granted, granted_mode)| {
+                    path.starts_with(granted)
+                        && (*mode == FilesystemMode::ReadOnly
+                            || granted_mode == FilesystemMode::ReadWrite)
+                })
+            }
+        }
+    }
+
+    fn paths(&self) -> impl Iterator<Item = (&PathBuf, FilesystemMode)> {
+        self.capabilities.iter().filter_map(|c| match c {
+            Capability::FilesystemPath { path, mode } => Some((path, *mode)),
+            _ => None,
+        })
+    }
+
+    /// Arrange for `command` to enter the envelope before it execs.
+    ///
+    /// Everything that can fail without the child (resolving paths,
+    /// probing the kernel, building the filter) fails here; the
+    /// remaining steps run in the child and fail the spawn.
+    #[cfg(all(
+        target_os = "linux",
+        any(target_arch = "x86_64", target_arch = "aarch64")
+    ))]
+    pub(crate) fn apply(&self, command: &mut Command) -> std::io::Result<()> {
+        linux::apply(self, command)
+    }
+
+    #[cfg(not(all(
+        target_os = "linux",
+        any(target_arch = "x86_64", target_arch = "aarch64")
+    )))]
+    pub(crate) fn apply(&self, _command: &mut Command) -> std::io::Result<()> {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "plugin confinement is only implemented on Linux (x86_64, aarch64)",
+        ))
+    }
+}
+
+impl Default for Confinement {
+    /// The tightest envelope: no network, no filesystem paths.
+    fn default() -> Self {
+        Self::for_capabilities([])
+    }
+}
+
+/// How a confined plugin stepped outside its envelope.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Violation {
+    /// The plugin made a syscall outside the seccomp allow-list.
+    Syscall,
+    /// The plugin used up its CPU-time limit.
+    CpuTime,
+}
+
+impl Violation {
+    /// The violation, if any, that explains a confined child's exit.
+    #[cfg(unix)]
+    pub(crate) fn of(status: &ExitStatus) -> Option<Self> {
+        use std::os::unix::process::ExitStatusExt;
+
+        // SIGSYS and SIGXCPU on every Linux architecture we confine.
+        const SIGSYS: i32 = 31;
+        const SIGXCPU: i32 = 24;
+        match status.signal() {
+            Some(SIGSYS) => Some(Violation::Syscall),
+            Some(SIGXCPU) => Some(Violation::CpuTime),
+            _ => None,
+        }
+    }
+
+    #[cfg(not(unix))]
+    pub(crate) fn of(_status: &ExitStatus) -> Option<Self> {
+        None
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::Syscall => f.write_str("disallowed syscall"),
+            Violation::CpuTime => f.write_str("CPU time limit exceeded"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(p: &str, mode: FilesystemMode) -> Capability {
+        Capability::FilesystemPath {
+            path: PathBuf::from(p),
+            mode,
+        }
+    }
+
+    #[test]
+    fn default_envelope_has_no_network_or_paths() {
+        let c = Confinement::default();
+        assert!(!c.allows_network());
+        assert!(!c.covers(&path("/tmp", FilesystemMode::ReadOnly)));
+        assert!(c.covers(&Capability::InterfaceAccess {
+            name: "hash".into()
+        }));
+    }
+
+    #[test]
+    fn network_grant_opens_network() {
+        let c = Confinement::for_capabilities([Capability::NetworkEndpoint {
+            url: "https://example.com".into(),
+        }]);
+        assert!(c.allows_network());
+        assert!(c.covers(&Capability::NetworkEndpoint {
+            url: "https://other.example".into()
+        }));
+    }
+
+    #[test]
+    fn paths_cover_descendants_with_weaker_modes() {
+        let c = Confinement::for_capabilities([
+            path("/srv/ro", FilesystemMode::ReadOnly),
+            path("/srv/rw", FilesystemMode::ReadWrite),
+        ]);
+        assert!(c.covers(&path("/srv/ro/a", FilesystemMode::ReadOnly)));
+        assert!(!c.covers(&path("/srv/ro/a", FilesystemMode::ReadWrite)));
+        assert!(c.covers(&path("/srv/rw/b", FilesystemMode::ReadWrite)));
+        assert!(!c.covers(&path("/srv/rwx", FilesystemMode::ReadOnly)));
+    }
+
+    #[test]
+    fn duplicate_capabilities_collapse() {
+        let cap = path("/srv", FilesystemMode::ReadOnly);
+        let c = Confinement::for_capabilities([cap.clone(), cap]);
+        assert_eq!(c.capabilities().len(), 1);
+    }
+}
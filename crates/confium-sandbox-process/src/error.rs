@@ -68,6 +68,43 @@ pub enum Error {
         function: String,
         backtrace: Backtrace,
     },
+    /// The plugin subprocess exited (or was killed by a signal) while
+    /// the host was talking to it. Every later call on the same
+    /// instance fails the same way.
+    #[snafu(display("plugin subprocess exited: {}", status))]
+    PluginExited {
+        status: std::process::ExitStatus,
+        backtrace: Backtrace,
+    },
+    /// A confined spawn could not set up the plugin's
+    /// [`Confinement`](crate::Confinement): the policy named a path
+    /// that does not exist, or the kernel lacks (or refused) one of the
+    /// mechanisms. The plugin is never started unconfined.
+    #[snafu(display("failed to confine plugin subprocess: {}", source))]
+    Confine {
+        source: std::io::Error,
+        backtrace: Backtrace,
+    },
+    /// A confined plugin was killed for stepping outside its envelope.
+    /// Like [`Error::PluginExited`], every later call fails the same
+    /// way.
+    #[snafu(display(
+        "plugin subprocess violated its confinement ({}): {}",
+        violation,
+        status
+    ))]
+    ConfinementViolation {
+        violation: crate::Violation,
+        status: std::process::ExitStatus,
+        backtrace: Backtrace,
+    },
+    /// A capability was granted to a confined instance whose envelope
+    /// does not cover it. The envelope is fixed at spawn.
+    #[snafu(display("capability {} is outside the plugin's confinement", capability))]
+    OutsideConfinement {
+        capability: String,
+        backtrace: Backtrace,
+    },
 }
 
 impl Error {
@@ -84,6 +121,10 @@ impl Error {
             Error::PluginError { .. } => 0x2105,
             Error::ArgumentType { .. } => 0x2106,
             Error::FunctionNotFound { .. } => 0x2107,
+            Error::PluginExited { .. } => 0x2108,
+            Error::Confine { .. } => 0x2109,
+            Error::ConfinementViolation { .. } => 0x210A,
+            Error::OutsideConfinement { .. } => 0x210B,
         }
     }
 }
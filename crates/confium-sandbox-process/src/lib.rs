@@ -12,6 +12,8 @@
 //! - [`Capability`] — the capability model (interface / network /
 //!   key / filesystem).
 //! - [`Value`] — values crossing the sandbox boundary.
+//! - [`Confinement`] — the OS-level envelope for a plugin subprocess
+//!   (Linux: seccomp, namespaces, landlock, rlimits).
 //! - [`protocol`] (public) — the wire types and helpers for the
 //!   length-prefixed JSON-RPC framing.
 //!
@@ -31,14 +33,23 @@
 //! {"error": {"message": "<text>"}} // failure
 //! ```
 //!
+//! A child written in Rust can serve the protocol with
+//! [`protocol::read_request`] and [`protocol::write_response`];
+//! `confium-core`'s `confium-plugin-host` does this to run native
+//! plugins out of process.
+//!
 //! See `TODO.roadmap/08-security-model.md` § "Track B" for the
-//! motivation and the OS-level restriction roadmap.
+//! motivation, and [`confinement`] for how the child is restricted at
+//! the OS level.
 
+pub mod confinement;
 pub mod error;
 pub mod process_sandbox;
 pub mod protocol;
 pub mod sandbox;
 
+pub use confinement::Confinement;
+pub use confinement::Violation;
 pub use error::Error;
 pub use error::Result;
 pub use process_sandbox::ProcessInstance;
@@ -8,10 +8,11 @@
 //! call rather than crashing the host.
 //!
 //! Capability state is held host-side: the host refuses to forward a
-//! call whose required capability is not currently granted. This is
-//! the minimum viable gate; a future revision can additionally
-//! restrict the child at the OS level (seccomp/AppSandbox) so even a
-//! compromised plugin cannot reach the network or filesystem.
+//! call whose required capability is not currently granted. A child
+//! started with [`ProcessSandbox::spawn_confined`] is additionally
+//! restricted at the OS level, so even a compromised plugin cannot
+//! reach the network or filesystem beyond its grants; see
+//! [`crate::confinement`].
 //!
 //! See `TODO.roadmap/08-security-model.md` § "Track B: Out-of-process
 //! plugins".
@@ -29,6 +30,8 @@ use snafu::GenerateImplicitData;
 
 use crate::Error;
 use crate::Result;
+use crate::confinement::Confinement;
+use crate::confinement::Violation;
 use crate::protocol::LEN_PREFIX_BYTES;
 use crate::protocol::MAX_FRAME_BYTES;
 use crate::protocol::Request;
@@ -53,26 +56,58 @@ impl ProcessSandbox {
     pub fn new() -> Self {
         Self
     }
-}
 
-impl Sandbox for ProcessSandbox {
-    fn load_module(&self, bytes: &[u8]) -> Result<Box<dyn SandboxInstance>> {
-        let path = str::from_utf8(bytes).map_err(|e| Error::InvalidPath {
-            source: e,
-            backtrace: Backtrace::generate(),
+    /// Spawn a caller-built `command` as a sandboxed plugin.
+    ///
+    /// [`load_module`](Sandbox::load_module) only carries a path; this
+    /// is the entry point for hosts that need to pass arguments or
+    /// environment to the child. stdin and stdout are always replaced
+    /// with the protocol pipes; stderr is left as configured.
+    pub fn spawn_command(command: Command) -> Result<ProcessInstance> {
+        Self::spawn(command, None)
+    }
+
+    /// Spawn `command` inside `confinement`.
+    ///
+    /// The instance starts with the envelope's capabilities granted,
+    /// and later grants are refused unless the envelope covers them.
+    /// `command` must name its program by path. A child that breaks
+    /// out of the seccomp allow-list or its CPU limit is reported as
+    /// [`Error::ConfinementViolation`].
+    pub fn spawn_confined(
+        mut command: Command,
+        confinement: Confinement,
+    ) -> Result<ProcessInstance> {
+        confinement
+            .apply(&mut command)
+            .map_err(|e| match e.kind() {
+                std::io::ErrorKind::NotFound if !command_exists(&command) => Error::Spawn {
+                    source: e,
+                    backtrace: Backtrace::generate(),
+                },
+                _ => Error::Confine {
+                    source: e,
+                    backtrace: Backtrace::generate(),
+                },
+            })?;
+        // The program was resolved above, so a failed spawn now means
+        // one of the confinement steps in the child was refused.
+        let mut instance = Self::spawn(command, Some(confinement)).map_err(|e| match e {
+            Error::Spawn { source, backtrace } => Error::Confine { source, backtrace },
+            e => e,
         })?;
-        // Trim a trailing newline that often appears when a path is
-        // read from a file or echoed in a shell. Leading/trailing
-        // whitespace is never part of a valid executable path on the
-        // platforms we support.
-        let path = path.trim();
+        if let Some(envelope) = &instance.envelope {
+            for cap in envelope.capabilities() {
+                instance.caps.grant(cap.clone());
+            }
+        }
+        Ok(instance)
+    }
 
-        let mut child = Command::new(path)
+    fn spawn(mut command: Command, envelope: Option<Confinement>) -> Result<ProcessInstance> {
+        let mut child = command
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
-            // stderr inherits so plugin diagnostics are visible during
-            // development without polluting the protocol stream.
-            .stderr(Stdio::inherit())
             .spawn()
             .map_err(|e| Error::Spawn {
                 source: e,
@@ -90,12 +125,37 @@ impl Sandbox for ProcessSandbox {
             backtrace: Backtrace::generate(),
         })?;
 
-        Ok(Box::new(ProcessInstance {
+        Ok(ProcessInstance {
             child: Some(child),
             stdin,
             stdout,
             caps: CapabilitySet::new(),
-        }))
+            envelope,
+        })
+    }
+}
+
+fn command_exists(command: &Command) -> bool {
+    std::path::Path::new(command.get_program()).exists()
+}
+
+impl Sandbox for ProcessSandbox {
+    fn load_module(&self, bytes: &[u8]) -> Result<Box<dyn SandboxInstance>> {
+        let path = str::from_utf8(bytes).map_err(|e| Error::InvalidPath {
+            source: e,
+            backtrace: Backtrace::generate(),
+        })?;
+        // Trim a trailing newline that often appears when a path is
+        // read from a file or echoed in a shell. Leading/trailing
+        // whitespace is never part of a valid executable path on the
+        // platforms we support.
+        let path = path.trim();
+
+        let mut command = Command::new(path);
+        // stderr inherits so plugin diagnostics are visible during
+        // development without polluting the protocol stream.
+        command.stderr(Stdio::inherit());
+        Ok(Box::new(Self::spawn_command(command)?))
     }
 
     fn name(&self) -> &'static str {
@@ -113,6 +173,9 @@ pub struct ProcessInstance {
     stdin: std::process::ChildStdin,
     stdout: std::process::ChildStdout,
     caps: CapabilitySet,
+    /// The OS-level envelope, for instances from
+    /// [`ProcessSandbox::spawn_confined`].
+    envelope: Option<Confinement>,
 }
 
 impl Drop for ProcessInstance {
@@ -135,19 +198,81 @@ impl ProcessInstance {
     /// so a confused plugin cannot desynchronize the host.
     fn round_trip(&mut self, req: &Request) -> Result<Response> {
         let frame = req.to_frame()?;
-        self.stdin
+        // Check before writing: a host that ignores SIGPIPE gets EPIPE
+        // from the write below, but one that doesn't would be killed
+        // by writing into the pipe of a child that is already gone.
+        self.check_alive()?;
+        let written = self
+            .stdin
             .write_all(&frame)
-            .map_err(|e| Error::WriteRequest {
+            .and_then(|()| self.stdin.flush());
+        if let Err(e) = written {
+            self.check_alive()?;
+            return Err(Error::WriteRequest {
                 source: e,
                 backtrace: Backtrace::generate(),
-            })?;
-        self.stdin.flush().map_err(|e| Error::WriteRequest {
-            source: e,
-            backtrace: Backtrace::generate(),
-        })?;
+            });
+        }
+
+        match read_frame(&mut self.stdout) {
+            Ok(payload) => Response::from_json_bytes(&payload),
+            Err(e @ Error::ReadResponse { .. }) => {
+                // A closed stdout almost always means the child died
+                // mid-call; report that rather than the bare EOF.
+                self.wait_exited()?;
+                Err(e)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Fail with [`Error::PluginExited`] if the child has already exited.
+    fn check_alive(&mut self) -> Result<()> {
+        let Some(child) = self.child.as_mut() else {
+            return Ok(());
+        };
+        match child.try_wait() {
+            Ok(Some(status)) => Err(self.exited(status)),
+            _ => Ok(()),
+        }
+    }
 
-        let payload = read_frame(&mut self.stdout)?;
-        Response::from_json_bytes(&payload)
+    /// Called after stdout hit EOF: reap the child and report its exit.
+    ///
+    /// The child closing stdout without exiting is a protocol violation
+    /// in its own right, so it is killed rather than waited on forever.
+    fn wait_exited(&mut self) -> Result<()> {
+        let Some(child) = self.child.as_mut() else {
+            return Ok(());
+        };
+        let status = match child.try_wait() {
+            Ok(Some(status)) => Some(status),
+            Ok(None) => {
+                let _ = child.kill();
+                child.wait().ok()
+            }
+            Err(_) => None,
+        };
+        match status {
+            Some(status) => Err(self.exited(status)),
+            None => Ok(()),
+        }
+    }
+
+    /// The error for a child that exited with `status`: a confinement
+    /// violation if the envelope killed it, a plain exit otherwise.
+    fn exited(&self, status: std::process::ExitStatus) -> Error {
+        match self.envelope.as_ref().and(Violation::of(&status)) {
+            Some(violation) => Error::ConfinementViolation {
+                violation,
+                status,
+                backtrace: Backtrace::generate(),
+            },
+            None => Error::PluginExited {
+                status,
+                backtrace: Backtrace::generate(),
+            },
+        }
     }
 }
 
@@ -160,6 +285,14 @@ impl SandboxInstance for ProcessInstance {
     }
 
     fn grant_capability(&mut self, cap: Capability) -> Result<()> {
+        if let Some(envelope) = &self.envelope
+            && !envelope.covers(&cap)
+        {
+            return Err(Error::OutsideConfinement {
+                capability: format!("{cap:?}"),
+                backtrace: Backtrace::generate(),
+            });
+        }
         self.caps.grant(cap);
         Ok(())
     }
@@ -177,8 +310,8 @@ impl SandboxInstance for ProcessInstance {
 // is only forwarded to the subprocess if the host believes the plugin
 // is entitled. (A subprocess that has been compromised cannot be
 // trusted to enforce its own gate, but it also has no host imports to
-// call — it can only respond to `call()` messages. OS-level
-// restriction of the child is a future-task item.)
+// call — it can only respond to `call()` messages. What the child can
+// do on its own is bounded by its `Confinement`, when it has one.)
 
 #[derive(Debug, Default)]
 struct CapabilitySet {
@@ -212,7 +345,7 @@ impl CapabilitySet {
 /// Blocks until 4 length bytes are available, then blocks until the
 /// full payload arrives. Returns the raw JSON payload bytes (without
 /// the length prefix).
-fn read_frame<R: Read>(reader: &mut R) -> Result<Vec<u8>> {
+pub(crate) fn read_frame<R: Read>(reader: &mut R) -> Result<Vec<u8>> {
     let mut header = [0u8; LEN_PREFIX_BYTES];
     read_exact_or_eof(reader, &mut header)?;
     let len = parse_len(&header)?;
@@ -20,6 +20,13 @@
 //!
 //! [`Value`] variants map to/from JSON as documented on
 //! [`value_to_json`] and [`value_from_json`].
+//!
+//! The plugin side of the conversation is covered too:
+//! [`read_request`] and [`write_response`] let a child written in
+//! Rust serve the protocol without re-implementing the framing.
+
+use std::io::Read;
+use std::io::Write;
 
 use serde::Deserialize;
 use serde::Serialize;
@@ -63,23 +70,50 @@ impl Request {
 }
 
 /// A response the plugin writes back to the host.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Response {
     /// Present on success: the function's return values.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub result: Option<Vec<JsonValue>>,
     /// Present on failure: a human-readable message.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub error: Option<ResponseError>,
 }
 
 /// Error payload inside a [`Response`].
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ResponseError {
     pub message: String,
 }
 
 impl Response {
+    /// A success response carrying `values`.
+    pub fn ok(values: &[Value]) -> Self {
+        Self {
+            result: Some(values.iter().map(value_to_json).collect()),
+            error: None,
+        }
+    }
+
+    /// A failure response carrying `message`.
+    pub fn err(message: impl Into<String>) -> Self {
+        Self {
+            result: None,
+            error: Some(ResponseError {
+                message: message.into(),
+            }),
+        }
+    }
+
+    /// Serialize to a length-prefixed byte frame ready for stdout.
+    pub fn to_frame(&self) -> Result<Vec<u8>> {
+        let json = serde_json::to_vec(self).map_err(|e| Error::Protocol {
+            reason: format!("failed to serialize response: {e}"),
+            backtrace: snafu::Backtrace::generate(),
+        })?;
+        encode_frame(&json)
+    }
+
     /// Parse a length-prefixed frame from a raw JSON byte slice.
     pub fn from_json_bytes(bytes: &[u8]) -> Result<Self> {
         serde_json::from_slice::<Response>(bytes).map_err(|e| Error::Protocol {
@@ -209,6 +243,63 @@ pub fn value_from_json(v: JsonValue) -> Result<Value> {
     })
 }
 
+/// Plugin side: read the next [`Request`] from `reader`.
+///
+/// Returns `Ok(None)` when the host closed the pipe cleanly between
+/// frames, which is the normal way a plugin learns it should exit.
+/// EOF inside a frame is an [`Error::ReadResponse`].
+pub fn read_request<R: Read>(reader: &mut R) -> Result<Option<Request>> {
+    let mut header = [0u8; LEN_PREFIX_BYTES];
+    let mut filled = 0;
+    while filled < header.len() {
+        let n = reader
+            .read(&mut header[filled..])
+            .map_err(|e| Error::ReadResponse {
+                source: e,
+                backtrace: snafu::Backtrace::generate(),
+            })?;
+        if n == 0 {
+            if filled == 0 {
+                return Ok(None);
+            }
+            return Err(Error::ReadResponse {
+                source: std::io::Error::new(
+                    std::io::ErrorKind::UnexpectedEof,
+                    "host closed stdin inside a frame header",
+                ),
+                backtrace: snafu::Backtrace::generate(),
+            });
+        }
+        filled += n;
+    }
+    let len = parse_len(&header)?;
+    let mut payload = vec![0u8; len];
+    reader
+        .read_exact(&mut payload)
+        .map_err(|e| Error::ReadResponse {
+            source: e,
+            backtrace: snafu::Backtrace::generate(),
+        })?;
+    serde_json::from_slice::<Request>(&payload)
+        .map(Some)
+        .map_err(|e| Error::Protocol {
+            reason: format!("failed to parse request: {e}"),
+            backtrace: snafu::Backtrace::generate(),
+        })
+}
+
+/// Plugin side: write `resp` to `writer` as one frame and flush.
+pub fn write_response<W: Write>(writer: &mut W, resp: &Response) -> Result<()> {
+    let frame = resp.to_frame()?;
+    writer
+        .write_all(&frame)
+        .and_then(|()| writer.flush())
+        .map_err(|e| Error::WriteRequest {
+            source: e,
+            backtrace: snafu::Backtrace::generate(),
+        })
+}
+
 /// Read a `u32` big-endian length from a 4-byte slice.
 pub(crate) fn parse_len(buf: &[u8]) -> Result<usize> {
     if buf.len() < LEN_PREFIX_BYTES {
@@ -320,6 +411,40 @@ mod tests {
         }
     }
 
+    #[test]
+    fn served_request_and_response_round_trip() {
+        let req = Request::new("add", vec![JsonValue::from(2)]);
+        let mut wire = req.to_frame().expect("frame encodes");
+        let mut cur = std::io::Cursor::new(std::mem::take(&mut wire));
+        let got = read_request(&mut cur).expect("reads").expect("one frame");
+        assert_eq!(got.method, "add");
+        // A clean EOF between frames is the shutdown signal.
+        assert!(read_request(&mut cur).expect("eof").is_none());
+
+        write_response(&mut wire, &Response::ok(&[Value::Bytes(vec![1, 2])])).expect("writes");
+        let len = parse_len(&wire).expect("len");
+        let resp = Response::from_json_bytes(&wire[LEN_PREFIX_BYTES..LEN_PREFIX_BYTES + len])
+            .expect("parses");
+        assert_eq!(
+            resp.into_result("add").expect("ok"),
+            vec![Value::Bytes(vec![1, 2])]
+        );
+    }
+
+    #[test]
+    fn error_response_serializes_without_result() {
+        let frame = Response::err("nope").to_frame().expect("encodes");
+        let body = std::str::from_utf8(&frame[LEN_PREFIX_BYTES..]).expect("utf8");
+        assert_eq!(body, r#"{"error":{"message":"nope"}}"#);
+    }
+
+    #[test]
+    fn read_request_rejects_truncated_frame() {
+        let mut cur = std::io::Cursor::new(vec![0, 0]);
+        let err = read_request(&mut cur).expect_err("must fail");
+        assert_eq!(err.code(), 0x2103);
+    }
+
     #[test]
     fn response_with_empty_result_is_ok_empty() {
         let raw = br#"{"result":[]}"#;
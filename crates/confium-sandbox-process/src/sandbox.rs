@@ -23,8 +23,9 @@ use crate::Result;
 ///
 /// For the process sandbox the capability set is enforced host-side:
 /// the host refuses to forward a `cfm_*` call to the subprocess unless
-/// the matching capability is present. (A future revision may push the
-/// capability gate into a seccomp/AppSandbox profile on the child.)
+/// the matching capability is present. A confined child is also held
+/// to its network and filesystem capabilities by the OS; see
+/// [`Confinement`](crate::Confinement).
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Capability {
     /// Plugin may call the `cfm_<name>_*` host-import family
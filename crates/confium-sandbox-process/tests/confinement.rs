@@ -0,0 +1,224 @@
+//! Integration tests for OS-level confinement of plugin subprocesses.
+//!
+//! Spawns the `cfm-echo-plugin` test binary through
+//! [`ProcessSandbox::spawn_confined`] and checks that:
+//!
+//! 1. A confined plugin still serves the protocol.
+//! 2. Opening a socket without a network grant kills the child, which
+//!    surfaces as `Error::ConfinementViolation` on that call and every
+//!    later one.
+//! 3. With a network grant, the same socket opens.
+//! 4. Reading `/etc` is denied, while a granted path stays readable.
+//! 5. A grant the envelope does not cover is refused.
+//! 6. A CPU-time limit kills a child that exceeds it.
+//!
+//! The kernel must provide landlock and unprivileged user namespaces.
+//! Where it does not, the confined spawn fails with `Error::Confine`
+//! and the tests fail. Set `CONFIUM_SKIP_CONFINEMENT_TESTS=1` to skip
+//! them instead on a host that cannot confine.
+
+#![cfg(target_os = "linux")]
+
+use std::path::PathBuf;
+use std::process::Command;
+
+use confium_sandbox_process::Capability;
+use confium_sandbox_process::Confinement;
+use confium_sandbox_process::Error;
+use confium_sandbox_process::FilesystemMode;
+use confium_sandbox_process::ProcessInstance;
+use confium_sandbox_process::ProcessSandbox;
+use confium_sandbox_process::SandboxInstance;
+use confium_sandbox_process::Value;
+use confium_sandbox_process::Violation;
+
+fn echo_plugin() -> Command {
+    Command::new(env!("CARGO_BIN_EXE_cfm-echo-plugin"))
+}
+
+/// Spawn the echo plugin inside `confinement`, or `None` (and the
+/// caller skips) when confinement tests are opted out.
+fn spawn(confinement: Confinement) -> Option<ProcessInstance> {
+    if std::env::var_os("CONFIUM_SKIP_CONFINEMENT_TESTS").is_some_and(|v| v == "1") {
+        eprintln!("warning: CONFIUM_SKIP_CONFINEMENT_TESTS=1; skipping test");
+        return None;
+    }
+    match ProcessSandbox::spawn_confined(echo_plugin(), confinement) {
+        Ok(inst) => Some(inst),
+        Err(e) => panic!(
+            "confined spawn failed: {e:?} (set CONFIUM_SKIP_CONFINEMENT_TESTS=1 on a host \
+             without landlock or unprivileged user namespaces)"
+        ),
+    }
+}
+
+fn read_file(inst: &mut ProcessInstance, path: &str) -> Result<Vec<Value>, Error> {
+    inst.call("read_file", &[Value::Bytes(path.as_bytes().to_vec())])
+}
+
+/// A fresh directory holding one file, for filesystem grants.
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir =
+        std::env::temp_dir().join(format!("confium-confinement-{name}-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("data"), b"granted").unwrap();
+    dir
+}
+
+#[test]
+fn confined_plugin_serves_calls() {
+    let Some(mut inst) = spawn(Confinement::default()) else {
+        return;
+    };
+    let out = inst
+        .call("add", &[Value::I32(2), Value::I32(3)])
+        .expect("add round-trips");
+    assert_eq!(out, vec![Value::I32(5)]);
+}
+
+#[test]
+fn socket_without_network_grant_is_a_violation() {
+    let Some(mut inst) = spawn(Confinement::default()) else {
+        return;
+    };
+    let err = inst.call("socket", &[]).expect_err("socket is denied");
+    assert!(
+        matches!(
+            err,
+            Error::ConfinementViolation {
+                violation: Violation::Syscall,
+                ..
+            }
+        ),
+        "got {err:?}"
+    );
+    assert_eq!(err.code(), 0x210A);
+    let again = inst.call("ping", &[]).expect_err("child is gone");
+    assert_eq!(again.code(), 0x210A);
+}
+
+#[test]
+fn socket_with_network_grant_opens() {
+    let Some(mut inst) = spawn(Confinement::for_capabilities([
+        Capability::NetworkEndpoint {
+            url: "udp://127.0.0.1".into(),
+        },
+    ])) else {
+        return;
+    };
+    let out = inst.call("socket", &[]).expect("socket opens");
+    assert_eq!(out, vec![Value::I32(1)]);
+}
+
+#[test]
+fn reading_etc_is_denied() {
+    // Unconfined, the same read succeeds.
+    let mut free = ProcessSandbox::spawn_command(echo_plugin()).expect("spawns");
+    read_file(&mut free, "/etc/passwd").expect("unconfined read works");
+
+    let Some(mut inst) = spawn(Confinement::default()) else {
+        return;
+    };
+    match read_file(&mut inst, "/etc/passwd").expect_err("read is denied") {
+        Error::PluginError { message, .. } => {
+            assert!(message.contains("Permission denied"), "got: {message}")
+        }
+        other => panic!("expected PluginError, got {other:?}"),
+    }
+    // Denied access is an error for the call, not a violation: the
+    // plugin keeps running.
+    assert_eq!(inst.call("ping", &[]).unwrap(), vec![Value::I32(1)]);
+}
+
+#[test]
+fn granted_path_is_readable_and_nothing_else() {
+    let dir = scratch_dir("read");
+    let Some(mut inst) = spawn(Confinement::for_capabilities([
+        Capability::FilesystemPath {
+            path: dir.clone(),
+            mode: FilesystemMode::ReadOnly,
+        },
+    ])) else {
+        return;
+    };
+    let out = read_file(&mut inst, dir.join("data").to_str().unwrap()).expect("granted read");
+    assert_eq!(out, vec![Value::Bytes(b"granted".to_vec())]);
+    read_file(&mut inst, "/etc/passwd").expect_err("/etc is still denied");
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn grants_outside_the_envelope_are_refused() {
+    let dir = scratch_dir("grant");
+    let Some(mut inst) = spawn(Confinement::for_capabilities([
+        Capability::FilesystemPath {
+            path: dir.clone(),
+            mode: FilesystemMode::ReadOnly,
+        },
+    ])) else {
+        return;
+    };
+    inst.grant_capability(Capability::InterfaceAccess {
+        name: "hash".into(),
+    })
+    .expect("interface grants are host-side");
+    inst.grant_capability(Capability::FilesystemPath {
+        path: dir.join("data"),
+        mode: FilesystemMode::ReadOnly,
+    })
+    .expect("inside the granted directory");
+
+    let err = inst
+        .grant_capability(Capability::FilesystemPath {
+            path: dir.clone(),
+            mode: FilesystemMode::ReadWrite,
+        })
+        .expect_err("read-write is outside a read-only envelope");
+    assert!(
+        matches!(err, Error::OutsideConfinement { .. }),
+        "got {err:?}"
+    );
+    assert_eq!(err.code(), 0x210B);
+    let err = inst
+        .grant_capability(Capability::NetworkEndpoint {
+            url: "https://example.com".into(),
+        })
+        .expect_err("network is outside the envelope");
+    assert_eq!(err.code(), 0x210B);
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn cpu_limit_kills_a_spinning_plugin() {
+    let Some(mut inst) = spawn(Confinement::default().cpu_limit(1)) else {
+        return;
+    };
+    let err = inst.call("spin", &[]).expect_err("child is killed");
+    assert!(
+        matches!(
+            err,
+            Error::ConfinementViolation {
+                violation: Violation::CpuTime,
+                ..
+            }
+        ),
+        "got {err:?}"
+    );
+}
+
+#[test]
+fn confined_spawn_requires_a_path() {
+    match ProcessSandbox::spawn_confined(Command::new("cfm-echo-plugin"), Confinement::default()) {
+        Err(err) => assert_eq!(err.code(), 0x2109),
+        Ok(_) => panic!("a bare program name cannot be confined"),
+    }
+}
+
+#[test]
+fn confined_spawn_of_missing_executable_is_a_spawn_error() {
+    let cmd = Command::new("/nonexistent/confium/plugin/does/not/exist");
+    match ProcessSandbox::spawn_confined(cmd, Confinement::default()) {
+        Err(err) => assert_eq!(err.code(), 0x2101),
+        Ok(_) => panic!("expected a spawn error"),
+    }
+}
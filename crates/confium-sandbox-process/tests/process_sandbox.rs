@@ -12,11 +12,14 @@
 //!    observable effect for an echo plugin, but exercises the API
 //!    path and confirms idempotency).
 //! 7. `name()` reports `"process"`.
+//! 8. A plugin that dies mid-call surfaces as `Error::PluginExited`,
+//!    on that call and every later one.
 
 use confium_sandbox_process::Capability;
 use confium_sandbox_process::Error;
 use confium_sandbox_process::ProcessSandbox;
 use confium_sandbox_process::Sandbox;
+use confium_sandbox_process::SandboxInstance;
 use confium_sandbox_process::Value;
 
 /// Path to the test echo plugin binary. Set by Cargo when the bin
@@ -156,3 +159,31 @@ fn load_module_with_missing_executable_errors_cleanly() {
         Ok(_) => panic!("expected load_module to fail on missing executable"),
     }
 }
+
+#[test]
+fn crashed_plugin_surfaces_exit_not_panic() {
+    let sb = ProcessSandbox::new();
+    let path = echo_plugin_path();
+    let mut inst = sb.load_module(path.as_bytes()).expect("spawns");
+
+    let err = inst.call("crash", &[]).expect_err("child aborted");
+    assert!(
+        matches!(err, Error::PluginExited { ref status, .. } if !status.success()),
+        "got {err:?}"
+    );
+    assert_eq!(err.code(), 0x2108);
+    // The instance stays usable as a value: later calls fail the same way.
+    let again = inst.call("ping", &[]).expect_err("still dead");
+    assert_eq!(again.code(), 0x2108);
+}
+
+#[test]
+fn spawn_command_runs_a_configured_child() {
+    let mut cmd = std::process::Command::new(echo_plugin_path());
+    cmd.env("CONFIUM_TEST_MARKER", "1");
+    let mut inst = ProcessSandbox::spawn_command(cmd).expect("spawns");
+    let out = inst
+        .call("add", &[Value::I32(4), Value::I32(5)])
+        .expect("add round-trips");
+    assert_eq!(out, vec![Value::I32(9)]);
+}
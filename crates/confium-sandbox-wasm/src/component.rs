@@ -0,0 +1,1282 @@
+//! Confium crypto plugins as WebAssembly components.
+//!
+//! A plugin component implements the `confium:plugin/plugin` world in
+//! `wit/plugin.wit`: the lifecycle trio plus all eight crypto
+//! interfaces, of which it advertises the ones it really implements.
+//! [`PluginSandbox`] compiles such components; each
+//! [`load`](PluginSandbox::load) yields a [`PluginInstance`] with its
+//! own store, memory cap and per-call fuel budget.
+//!
+//! [`PluginInstance`] serves the same call convention as a
+//! `confium-plugin-host` subprocess (method names are the `cfmp_`
+//! symbol names without the prefix, every reply starts with the
+//! plugin's return code; see `confium_core::sandbox`). That lets the
+//! host reuse one set of interface proxies for both sandbox kinds.
+//!
+//! A guest that traps (including running out of fuel or memory)
+//! poisons its instance: the call that trapped and every later call
+//! fail with [`Error::Invocation`], the same way a crashed plugin
+//! subprocess fails every call after it dies.
+
+use std::collections::HashMap;
+
+use serde_json::Value as JsonValue;
+use snafu::Backtrace;
+use snafu::GenerateImplicitData;
+use wasmtime::Engine;
+use wasmtime::Store;
+use wasmtime::StoreLimits;
+use wasmtime::StoreLimitsBuilder;
+use wasmtime::component::Component;
+use wasmtime::component::Linker;
+use wasmtime::component::ResourceAny;
+
+use crate::Error;
+use crate::Result;
+use crate::error::WasmtimeError;
+use crate::imports::CapabilitySet;
+use crate::sandbox::Capability;
+use crate::sandbox::SandboxInstance;
+use crate::sandbox::Value;
+
+mod bindings {
+    wasmtime::component::bindgen!({
+        path: "wit",
+        world: "plugin",
+    });
+}
+
+use bindings::Plugin;
+use bindings::confium::plugin::types::KeyHandle;
+use bindings::confium::plugin::types::Keypair;
+use bindings::confium::plugin::types::OptionEntry;
+use bindings::confium::plugin::types::OptionValue;
+
+/// Default fuel a single guest call may burn before it traps. Roughly
+/// one unit per WASM instruction; generous for any one crypto call,
+/// small enough to stop a guest that spins.
+pub const DEFAULT_FUEL: u64 = 1_000_000_000;
+
+/// Canonical codes the adapter itself answers with. They mirror
+/// `confium_api::ErrorCode`, which this crate does not depend on.
+const NULL_POINTER: u32 = 2;
+const PLUGIN_GENERIC: u32 = 27;
+
+/// Marker for a NULL pointer argument or an absent output.
+const NULL: Value = Value::I32(-1);
+
+/// Resource limits applied to every instance of a plugin component.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ComponentLimits {
+    /// Fuel granted to each guest call. The budget is refilled before
+    /// every call rather than shared across the instance's lifetime.
+    pub fuel: u64,
+    /// Upper bound on the instance's linear memory, in bytes.
+    pub memory_bytes: usize,
+}
+
+impl Default for ComponentLimits {
+    fn default() -> Self {
+        Self {
+            fuel: DEFAULT_FUEL,
+            memory_bytes: crate::wasm::DEFAULT_MEMORY_BYTES,
+        }
+    }
+}
+
+/// Store data of a plugin instance.
+struct ComponentState {
+    limits: StoreLimits,
+    /// Components import nothing today, so no capability is ever
+    /// checked; the set is kept so the [`SandboxInstance`] contract
+    /// holds and future imports can be gated like the module ones.
+    caps: CapabilitySet,
+}
+
+/// Compiles plugin components. Clone-cheap: clones share the engine.
+#[derive(Clone)]
+pub struct PluginSandbox {
+    engine: Engine,
+}
+
+impl PluginSandbox {
+    /// Construct a sandbox with component-model support and fuel
+    /// metering switched on. As with [`crate::WasmSandbox`], no WASI
+    /// is linked.
+    pub fn new() -> Result<Self> {
+        let mut config = wasmtime::Config::new();
+        config.wasm_component_model(true);
+        config.consume_fuel(true);
+        let engine = Engine::new(&config).map_err(|e| Error::Engine {
+            source: WasmtimeError::from_display(e),
+            backtrace: Backtrace::generate(),
+        })?;
+        Ok(Self { engine })
+    }
+
+    /// Access the underlying wasmtime engine.
+    pub fn engine(&self) -> &Engine {
+        &self.engine
+    }
+
+    /// Compile and instantiate a plugin component. Core modules are
+    /// refused: a plugin must be a component targeting the
+    /// `confium:plugin/plugin` world.
+    pub fn load(&self, bytes: &[u8], limits: ComponentLimits) -> Result<PluginInstance> {
+        let component = Component::new(&self.engine, bytes).map_err(|e| Error::ModuleCompile {
+            source: WasmtimeError::from_display(e),
+            backtrace: Backtrace::generate(),
+        })?;
+        let instantiation = |e: wasmtime::Error| Error::Instantiation {
+            source: WasmtimeError::from_display(e),
+            backtrace: Backtrace::generate(),
+        };
+        let mut linker: Linker<ComponentState> = Linker::new(&self.engine);
+        // `types` carries only type definitions; define it empty so a
+        // component that records the import still links.
+        linker
+            .instance("confium:plugin/types@0.1.0")
+            .map_err(instantiation)?;
+
+        let state = ComponentState {
+            limits: StoreLimitsBuilder::new()
+                .memory_size(limits.memory_bytes)
+                .build(),
+            caps: CapabilitySet::new(),
+        };
+        let mut store = Store::new(&self.engine, state);
+        store.limiter(|state| &mut state.limits);
+        store.set_fuel(limits.fuel).map_err(instantiation)?;
+        let bindings =
+            Plugin::instantiate(&mut store, &component, &linker).map_err(instantiation)?;
+        Ok(PluginInstance {
+            store,
+            bindings,
+            fuel: limits.fuel,
+            objects: HashMap::new(),
+            next_id: 0,
+            trapped: false,
+        })
+    }
+}
+
+/// Which resource type an object id belongs to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Kind {
+    Hash,
+    Rng,
+    Cipher,
+    Aead,
+    Kdf,
+    Key,
+    Signer,
+    Verifier,
+    Encapsulator,
+    Decapsulator,
+}
+
+/// One instantiated plugin component.
+pub struct PluginInstance {
+    store: Store<ComponentState>,
+    bindings: Plugin,
+    fuel: u64,
+    /// Resources the guest handed out, by the id the caller sees.
+    objects: HashMap<i64, (Kind, ResourceAny)>,
+    next_id: i64,
+    /// Set once a guest call traps; the guest's state is suspect from
+    /// then on.
+    trapped: bool,
+}
+
+impl PluginInstance {
+    /// Run the guest's `initialize` with the load options, encoded as
+    /// the JSON object the process sandbox also uses. Returns the
+    /// guest's status code.
+    pub fn initialize(&mut self, options: &JsonValue) -> Result<u32> {
+        let opts = options_from_json(options).ok_or_else(|| Error::ArgumentType {
+            function: "initialize".to_string(),
+            backtrace: Backtrace::generate(),
+        })?;
+        self.guarded("initialize", |this| {
+            let outcome = this
+                .bindings
+                .confium_plugin_lifecycle()
+                .call_initialize(&mut this.store, Some(&opts));
+            Ok(trapped("initialize", outcome)?.err().unwrap_or(0))
+        })
+    }
+
+    /// `(wire name, version)` for every interface the guest implements.
+    pub fn interfaces(&mut self) -> Result<Vec<(String, u8)>> {
+        self.guarded("interfaces", |this| {
+            let outcome = this
+                .bindings
+                .confium_plugin_lifecycle()
+                .call_interfaces(&mut this.store);
+            trapped("interfaces", outcome)
+        })
+    }
+
+    /// Run `f` against a fresh fuel budget, refusing to enter a guest
+    /// that has trapped before and remembering a new trap.
+    fn guarded<T>(&mut self, function: &str, f: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        if self.trapped {
+            return Err(invocation(function, "plugin instance trapped earlier"));
+        }
+        self.store
+            .set_fuel(self.fuel)
+            .map_err(|e| invocation(function, e))?;
+        let outcome = f(self);
+        if let Err(Error::Invocation { .. }) = outcome {
+            self.trapped = true;
+        }
+        outcome
+    }
+
+    fn dispatch(&mut self, method: &str, mut a: Args<'_>) -> Result<Vec<Value>> {
+        let reply = match method {
+            "finalize" => {
+                let outcome = self
+                    .bindings
+                    .confium_plugin_lifecycle()
+                    .call_finalize(&mut self.store);
+                trapped(method, outcome)?;
+                vec![code(0)]
+            }
+            m if m.starts_with("hash_") => self.hash(m, &mut a)?,
+            m if m.starts_with("rng_") => self.rng(m, &mut a)?,
+            m if m.starts_with("cipher_") => self.cipher(m, &mut a)?,
+            m if m.starts_with("aead_") => self.aead(m, &mut a)?,
+            m if m.starts_with("kdf_") => self.kdf(m, &mut a)?,
+            m if m.starts_with("keyfmt_") => self.keyfmt(m, &mut a)?,
+            m if m.starts_with("sig_") => self.signature(m, &mut a)?,
+            m if m.starts_with("kem_") => self.kem(m, &mut a)?,
+            _ => return Err(unknown(method)),
+        };
+        Ok(reply)
+    }
+
+    // -----------------------------------------------------------------
+    // object table
+    // -----------------------------------------------------------------
+
+    fn object(&self, a: &mut Args<'_>, kind: Kind) -> Result<ResourceAny> {
+        let id = a.int()?;
+        match self.objects.get(&id) {
+            Some(&(k, res)) if k == kind => Ok(res),
+            _ => Err(a.invalid()),
+        }
+    }
+
+    /// The reply for an object-creating call: `[code, id]`, or
+    /// `[code, NULL]` if the guest refused.
+    fn created(
+        &mut self,
+        kind: Kind,
+        outcome: std::result::Result<ResourceAny, u32>,
+    ) -> Vec<Value> {
+        match outcome {
+            Ok(res) => {
+                let id = self.next_id;
+                self.next_id += 1;
+                self.objects.insert(id, (kind, res));
+                vec![code(0), Value::I64(id)]
+            }
+            Err(c) => vec![code(c), NULL],
+        }
+    }
+
+    /// Drop the resource named by the next argument.
+    fn destroy(&mut self, method: &str, a: &mut Args<'_>, kind: Kind) -> Result<Vec<Value>> {
+        let id = a.int()?;
+        let res = match self.objects.remove(&id) {
+            Some((k, res)) if k == kind => res,
+            Some(entry) => {
+                self.objects.insert(id, entry);
+                return Err(a.invalid());
+            }
+            None => return Err(a.invalid()),
+        };
+        res.resource_drop(&mut self.store)
+            .map_err(|e| invocation(method, e))?;
+        Ok(vec![code(0)])
+    }
+
+    // -----------------------------------------------------------------
+    // hash
+    // -----------------------------------------------------------------
+
+    fn hash(&mut self, method: &str, a: &mut Args<'_>) -> Result<Vec<Value>> {
+        let iface = self.bindings.confium_plugin_hash();
+        match method {
+            "hash_create" => {
+                let wanted = a.out_ptr()?;
+                let name = a.string()?;
+                let opts = a.options()?;
+                let (Some(name), true) = (name, wanted) else {
+                    return Ok(vec![code(NULL_POINTER), NULL]);
+                };
+                let outcome = iface.call_create(&mut self.store, &name, opts.as_ref());
+                let outcome = trapped(method, outcome)?;
+                Ok(self.created(Kind::Hash, outcome))
+            }
+            "hash_output_size" | "hash_block_size" => {
+                let obj = self.object(a, Kind::Hash)?;
+                if a.in_out()?.is_none() {
+                    return Ok(vec![code(NULL_POINTER), NULL]);
+                }
+                let ctx = iface.context();
+                let outcome = if method == "hash_output_size" {
+                    ctx.call_output_size(&mut self.store, obj)
+                } else {
+                    ctx.call_block_size(&mut self.store, obj)
+                };
+                Ok(vec![code(0), u32_value(Some(trapped(method, outcome)?))])
+            }
+            "hash_update" => {
+                let obj = self.object(a, Kind::Hash)?;
+                let data = a.bytes()?.unwrap_or_default();
+                let outcome = iface.context().call_update(&mut self.store, obj, &data);
+                Ok(vec![code_of(trapped(method, outcome)?)])
+            }
+            "hash_reset" => {
+                let obj = self.object(a, Kind::Hash)?;
+                let outcome = iface.context().call_reset(&mut self.store, obj);
+                Ok(vec![code_of(trapped(method, outcome)?)])
+            }
+            "hash_clone" => {
+                let obj = self.object(a, Kind::Hash)?;
+                if !a.out_ptr()? {
+                    return Ok(vec![code(NULL_POINTER), NULL]);
+                }
+                let outcome = iface.context().call_clone(&mut self.store, obj);
+                let outcome = trapped(method, outcome)?;
+                Ok(self.created(Kind::Hash, outcome))
+            }
+            "hash_finalize" => {
+                let obj = self.object(a, Kind::Hash)?;
+                let cap = a.out_buf()?;
+                let outcome = iface
+                    .context()
+                    .call_finalize(&mut self.store, obj, cap.unwrap_or(0));
+                buf_reply(method, trapped(method, outcome)?, cap)
+            }
+            "hash_destroy" => self.destroy(method, a, Kind::Hash),
+            _ => Err(unknown(method)),
+        }
+    }
+
+    // -----------------------------------------------------------------
+    // rng
+    // -----------------------------------------------------------------
+
+    fn rng(&mut self, method: &str, a: &mut Args<'_>) -> Result<Vec<Value>> {
+        let iface = self.bindings.confium_plugin_rng();
+        match method {
+            "rng_create" => {
+                let wanted = a.out_ptr()?;
+                let name = a.string()?;
+                let opts = a.options()?;
+                let (Some(name), true) = (name, wanted) else {
+                    return Ok(vec![code(NULL_POINTER), NULL]);
+                };
+                let outcome = iface.call_create(&mut self.store, &name, opts.as_ref());
+                let outcome = trapped(method, outcome)?;
+                Ok(self.created(Kind::Rng, outcome))
+            }
+            "rng_reseed" | "rng_add_entropy" => {
+                let obj = self.object(a, Kind::Rng)?;
+                let data = a.bytes()?.unwrap_or_default();
+                let ctx = iface.context();
+                let outcome = if method == "rng_reseed" {
+                    ctx.call_reseed(&mut self.store, obj, &data)
+                } else {
+                    ctx.call_add_entropy(&mut self.store, obj, &data)
+                };
+                Ok(vec![code_of(trapped(method, outcome)?)])
+            }
+            "rng_generate" => {
+                let obj = self.object(a, Kind::Rng)?;
+                let cap = a.out_buf()?;
+                let outcome = iface
+                    .context()
+                    .call_generate(&mut self.store, obj, cap.unwrap_or(0));
+                buf_reply(method, trapped(method, outcome)?, cap)
+            }
+            "rng_destroy" => self.destroy(method, a, Kind::Rng),
+            _ => Err(unknown(method)),
+        }
+    }
+
+    // -----------------------------------------------------------------
+    // symmetric cipher
+    // -----------------------------------------------------------------
+
+    fn cipher(&mut self, method: &str, a: &mut Args<'_>) -> Result<Vec<Value>> {
+        let iface = self.bindings.confium_plugin_cipher();
+        match method {
+            "cipher_create" => {
+                let wanted = a.out_ptr()?;
+                let name = a.string()?;
+                let key = a.bytes()?.unwrap_or_default();
+                let iv = a.bytes()?.unwrap_or_default();
+                let opts = a.options()?;
+                let (Some(name), true) = (name, wanted) else {
+                    return Ok(vec![code(NULL_POINTER), NULL]);
+                };
+                let outcome = iface.call_create(&mut self.store, &name, &key, &iv, opts.as_ref());
+                let outcome = trapped(method, outcome)?;
+                Ok(self.created(Kind::Cipher, outcome))
+            }
+            "cipher_block_size" | "cipher_key_size" | "cipher_iv_size" => {
+                let obj = self.object(a, Kind::Cipher)?;
+                if a.in_out()?.is_none() {
+                    return Ok(vec![code(NULL_POINTER), NULL]);
+                }
+                let ctx = iface.context();
+                let outcome = match method {
+                    "cipher_block_size" => ctx.call_block_size(&mut self.store, obj),
+                    "cipher_key_size" => ctx.call_key_size(&mut self.store, obj),
+                    _ => ctx.call_iv_size(&mut self.store, obj),
+                };
+                Ok(vec![code(0), u32_value(Some(trapped(method, outcome)?))])
+            }
+            "cipher_update" => {
+                let obj = self.object(a, Kind::Cipher)?;
+                let input = a.bytes()?.unwrap_or_default();
+                let cap = a.out_buf()?;
+                let len = a.in_out()?;
+                let outcome =
+                    iface
+                        .context()
+                        .call_update(&mut self.store, obj, &input, cap.unwrap_or(0));
+                sized_reply(method, trapped(method, outcome)?, cap, len)
+            }
+            "cipher_finalize" => {
+                let obj = self.object(a, Kind::Cipher)?;
+                let cap = a.out_buf()?;
+                let len = a.in_out()?;
+                let outcome = iface
+                    .context()
+                    .call_finalize(&mut self.store, obj, cap.unwrap_or(0));
+                sized_reply(method, trapped(method, outcome)?, cap, len)
+            }
+            "cipher_reset" => {
+                let obj = self.object(a, Kind::Cipher)?;
+                let outcome = iface.context().call_reset(&mut self.store, obj);
+                Ok(vec![code_of(trapped(method, outcome)?)])
+            }
+            "cipher_destroy" => self.destroy(method, a, Kind::Cipher),
+            _ => Err(unknown(method)),
+        }
+    }
+
+    // -----------------------------------------------------------------
+    // aead
+    // -----------------------------------------------------------------
+
+    fn aead(&mut self, method: &str, a: &mut Args<'_>) -> Result<Vec<Value>> {
+        let iface = self.bindings.confium_plugin_aead();
+        match method {
+            "aead_create" => {
+                let wanted = a.out_ptr()?;
+                let name = a.string()?;
+                let key = a.bytes()?.unwrap_or_default();
+                let opts = a.options()?;
+                let (Some(name), true) = (name, wanted) else {
+                    return Ok(vec![code(NULL_POINTER), NULL]);
+                };
+                let outcome = iface.call_create(&mut self.store, &name, &key, opts.as_ref());
+                let outcome = trapped(method, outcome)?;
+                Ok(self.created(Kind::Aead, outcome))
+            }
+            "aead_set_nonce" | "aead_associated_data_update" | "aead_verify_tag" => {
+                let obj = self.object(a, Kind::Aead)?;
+                let data = a.bytes()?.unwrap_or_default();
+                let ctx = iface.context();
+                let outcome = match method {
+                    "aead_set_nonce" => ctx.call_set_nonce(&mut self.store, obj, &data),
+                    "aead_associated_data_update" => {
+                        ctx.call_associated_data_update(&mut self.store, obj, &data)
+                    }
+                    _ => ctx.call_verify_tag(&mut self.store, obj, &data),
+                };
+                Ok(vec![code_of(trapped(method, outcome)?)])
+            }
+            "aead_encrypt_update" | "aead_decrypt_update" => {
+                let obj = self.object(a, Kind::Aead)?;
+                let input = a.bytes()?.unwrap_or_default();
+                let cap = a.out_buf()?;
+                let len = a.in_out()?;
+                let ctx = iface.context();
+                let room = cap.unwrap_or(0);
+                let outcome = if method == "aead_encrypt_update" {
+                    ctx.call_encrypt_update(&mut self.store, obj, &input, room)
+                } else {
+                    ctx.call_decrypt_update(&mut self.store, obj, &input, room)
+                };
+                sized_reply(method, trapped(method, outcome)?, cap, len)
+            }
+            "aead_finalize" => {
+                let obj = self.object(a, Kind::Aead)?;
+                let cap = a.out_buf()?;
+                let len = a.in_out()?;
+                let outcome = iface
+                    .context()
+                    .call_finalize(&mut self.store, obj, cap.unwrap_or(0));
+                sized_reply(method, trapped(method, outcome)?, cap, len)
+            }
+            "aead_destroy" => self.destroy(method, a, Kind::Aead),
+            _ => Err(unknown(method)),
+        }
+    }
+
+    // -----------------------------------------------------------------
+    // kdf
+    // -----------------------------------------------------------------
+
+    fn kdf(&mut self, method: &str, a: &mut Args<'_>) -> Result<Vec<Value>> {
+        let iface = self.bindings.confium_plugin_kdf();
+        match method {
+            "kdf_create" => {
+                let wanted = a.out_ptr()?;
+                let name = a.string()?;
+                let opts = a.options()?;
+                let (Some(name), true) = (name, wanted) else {
+                    return Ok(vec![code(NULL_POINTER), NULL]);
+                };
+                let outcome = iface.call_create(&mut self.store, &name, opts.as_ref());
+                let outcome = trapped(method, outcome)?;
+                Ok(self.created(Kind::Kdf, outcome))
+            }
+            "kdf_set_salt" => {
+                let obj = self.object(a, Kind::Kdf)?;
+                let salt = a.bytes()?.unwrap_or_default();
+                let outcome = iface.context().call_set_salt(&mut self.store, obj, &salt);
+                Ok(vec![code_of(trapped(method, outcome)?)])
+            }
+            "kdf_set_iterations" | "kdf_set_parallelism" => {
+                let obj = self.object(a, Kind::Kdf)?;
+                let value = a.u32()?;
+                let ctx = iface.context();
+                let outcome = if method == "kdf_set_iterations" {
+                    ctx.call_set_iterations(&mut self.store, obj, value)
+                } else {
+                    ctx.call_set_parallelism(&mut self.store, obj, value)
+                };
+                Ok(vec![code_of(trapped(method, outcome)?)])
+            }
+            "kdf_set_memory_cost" => {
+                let obj = self.object(a, Kind::Kdf)?;
+                // Sent bit-for-bit as an i64.
+                let cost = a.int()? as u64;
+                let outcome = iface
+                    .context()
+                    .call_set_memory_cost(&mut self.store, obj, cost);
+                Ok(vec![code_of(trapped(method, outcome)?)])
+            }
+            "kdf_set_hash" => {
+                let obj = self.object(a, Kind::Kdf)?;
+                let Some(hash) = a.string()? else {
+                    return Ok(vec![code(NULL_POINTER)]);
+                };
+                let outcome = iface.context().call_set_hash(&mut self.store, obj, &hash);
+                Ok(vec![code_of(trapped(method, outcome)?)])
+            }
+            "kdf_derive" => {
+                let obj = self.object(a, Kind::Kdf)?;
+                let secret = a.bytes()?.unwrap_or_default();
+                let cap = a.out_buf()?;
+                let outcome =
+                    iface
+                        .context()
+                        .call_derive(&mut self.store, obj, &secret, cap.unwrap_or(0));
+                buf_reply(method, trapped(method, outcome)?, cap)
+            }
+            "kdf_destroy" => self.destroy(method, a, Kind::Kdf),
+            _ => Err(unknown(method)),
+        }
+    }
+
+    // -----------------------------------------------------------------
+    // keyfmt
+    // -----------------------------------------------------------------
+
+    fn keyfmt(&mut self, method: &str, a: &mut Args<'_>) -> Result<Vec<Value>> {
+        let iface = self.bindings.confium_plugin_keyfmt();
+        match method {
+            "keyfmt_parse" => {
+                let wanted = a.out_ptr()?;
+                let format = a.string()?;
+                let algorithm = a.string()?;
+                let data = a.bytes()?.unwrap_or_default();
+                let opts = a.options()?;
+                let (Some(format), true) = (format, wanted) else {
+                    return Ok(vec![code(NULL_POINTER), NULL]);
+                };
+                let outcome = iface.call_parse(
+                    &mut self.store,
+                    &format,
+                    algorithm.as_deref(),
+                    &data,
+                    opts.as_ref(),
+                );
+                let outcome = trapped(method, outcome)?;
+                Ok(self.created(Kind::Key, outcome))
+            }
+            "keyfmt_serialize" => {
+                let obj = self.object(a, Kind::Key)?;
+                let format = a.string()?;
+                let cap = a.out_buf()?;
+                let len = a.in_out()?;
+                let Some(format) = format else {
+                    return Ok(vec![code(NULL_POINTER), NULL, u32_value(len)]);
+                };
+                let outcome =
+                    iface
+                        .key()
+                        .call_serialize(&mut self.store, obj, &format, cap.unwrap_or(0));
+                sized_reply(method, trapped(method, outcome)?, cap, len)
+            }
+            "keyfmt_kind" => {
+                let obj = self.object(a, Kind::Key)?;
+                let out = a.in_out()?;
+                if out.is_none() {
+                    return Ok(vec![code(NULL_POINTER), NULL]);
+                }
+                let outcome = iface.key().call_kind(&mut self.store, obj);
+                Ok(match trapped(method, outcome)? {
+                    Ok(kind) => vec![code(0), u32_value(Some(kind))],
+                    Err(c) => vec![code(c), u32_value(out)],
+                })
+            }
+            "keyfmt_algorithm" => {
+                let obj = self.object(a, Kind::Key)?;
+                if !a.out_ptr()? {
+                    return Ok(vec![code(NULL_POINTER), NULL]);
+                }
+                let outcome = iface.key().call_algorithm(&mut self.store, obj);
+                Ok(match trapped(method, outcome)? {
+                    Ok(name) if name.contains('\0') => vec![code(PLUGIN_GENERIC), NULL],
+                    Ok(name) => vec![code(0), Value::Bytes(name.into_bytes())],
+                    Err(c) => vec![code(c), NULL],
+                })
+            }
+            "keyfmt_public" => {
+                let obj = self.object(a, Kind::Key)?;
+                if !a.out_ptr()? {
+                    return Ok(vec![code(NULL_POINTER), NULL]);
+                }
+                let outcome = iface.key().call_public(&mut self.store, obj);
+                let outcome = trapped(method, outcome)?;
+                Ok(self.created(Kind::Key, outcome))
+            }
+            "keyfmt_destroy" => self.destroy(method, a, Kind::Key),
+            _ => Err(unknown(method)),
+        }
+    }
+
+    // -----------------------------------------------------------------
+    // signature
+    // -----------------------------------------------------------------
+
+    fn signature(&mut self, method: &str, a: &mut Args<'_>) -> Result<Vec<Value>> {
+        let iface = self.bindings.confium_plugin_signature();
+        match method {
+            "sig_signer_create" | "sig_verifier_create" => {
+                let wanted = a.out_ptr()?;
+                let algorithm = a.string()?;
+                let key = a.bytes()?.unwrap_or_default();
+                let opts = a.options()?;
+                let (Some(algorithm), true) = (algorithm, wanted) else {
+                    return Ok(vec![code(NULL_POINTER), NULL]);
+                };
+                let (kind, outcome) = if method == "sig_signer_create" {
+                    let outcome =
+                        iface.call_signer_create(&mut self.store, &algorithm, &key, opts.as_ref());
+                    (Kind::Signer, outcome)
+                } else {
+                    let outcome = iface.call_verifier_create(
+                        &mut self.store,
+                        &algorithm,
+                        &key,
+                        opts.as_ref(),
+                    );
+                    (Kind::Verifier, outcome)
+                };
+                let outcome = trapped(method, outcome)?;
+                Ok(self.created(kind, outcome))
+            }
+            "sig_signer_set_hash" | "sig_verifier_set_hash" => {
+                let kind = if method == "sig_signer_set_hash" {
+                    Kind::Signer
+                } else {
+                    Kind::Verifier
+                };
+                let obj = self.object(a, kind)?;
+                let Some(hash) = a.string()? else {
+                    return Ok(vec![code(NULL_POINTER)]);
+                };
+                let outcome = if kind == Kind::Signer {
+                    iface.signer().call_set_hash(&mut self.store, obj, &hash)
+                } else {
+                    iface.verifier().call_set_hash(&mut self.store, obj, &hash)
+                };
+                Ok(vec![code_of(trapped(method, outcome)?)])
+            }
+            "sig_signer_update" => {
+                let obj = self.object(a, Kind::Signer)?;
+                let data = a.bytes()?.unwrap_or_default();
+                let outcome = iface.signer().call_update(&mut self.store, obj, &data);
+                Ok(vec![code_of(trapped(method, outcome)?)])
+            }
+            "sig_signer_finalize" => {
+                let obj = self.object(a, Kind::Signer)?;
+                let cap = a.out_buf()?;
+                let len = a.in_out()?;
+                let outcome = iface
+                    .signer()
+                    .call_finalize(&mut self.store, obj, cap.unwrap_or(0));
+                sized_reply(method, trapped(method, outcome)?, cap, len)
+            }
+            "sig_signer_destroy" => self.destroy(method, a, Kind::Signer),
+            "sig_verifier_update" | "sig_verifier_finalize" => {
+                let obj = self.object(a, Kind::Verifier)?;
+                let data = a.bytes()?.unwrap_or_default();
+                let verifier = iface.verifier();
+                let outcome = if method == "sig_verifier_update" {
+                    verifier.call_update(&mut self.store, obj, &data)
+                } else {
+                    verifier.call_finalize(&mut self.store, obj, &data)
+                };
+                Ok(vec![code_of(trapped(method, outcome)?)])
+            }
+            "sig_verifier_destroy" => self.destroy(method, a, Kind::Verifier),
+            "sig_keypair_generate" => {
+                let request = KeypairRequest::read(a)?;
+                let Some(algorithm) = &request.algorithm else {
+                    return Ok(request.refused(NULL_POINTER));
+                };
+                let outcome = iface.call_keypair_generate(
+                    &mut self.store,
+                    algorithm,
+                    request.seed.as_deref(),
+                    request.pk_cap.unwrap_or(0),
+                    request.sk_cap.unwrap_or(0),
+                );
+                request.reply(method, trapped(method, outcome)?)
+            }
+            "sig_handle_capabilities" => {
+                let algorithm = a.string()?;
+                let handle = a.handle()?;
+                let caps = a.in_out()?;
+                let (Some(algorithm), Some(handle), Some(_)) = (algorithm, handle, caps) else {
+                    return Ok(vec![code(NULL_POINTER), u32_value(caps)]);
+                };
+                let outcome = iface.call_handle_capabilities(&mut self.store, &algorithm, &handle);
+                Ok(vec![code(0), u32_value(Some(trapped(method, outcome)?))])
+            }
+            "sig_signer_create_with_handle" => {
+                let wanted = a.out_ptr()?;
+                let algorithm = a.string()?;
+                let handle = a.handle()?;
+                let opts = a.options()?;
+                let (Some(algorithm), Some(handle), true) = (algorithm, handle, wanted) else {
+                    return Ok(vec![code(NULL_POINTER), NULL]);
+                };
+                let outcome = iface.call_signer_create_with_handle(
+                    &mut self.store,
+                    &algorithm,
+                    &handle,
+                    opts.as_ref(),
+                );
+                let outcome = trapped(method, outcome)?;
+                Ok(self.created(Kind::Signer, outcome))
+            }
+            _ => Err(unknown(method)),
+        }
+    }
+
+    // -----------------------------------------------------------------
+    // kem
+    // -----------------------------------------------------------------
+
+    fn kem(&mut self, method: &str, a: &mut Args<'_>) -> Result<Vec<Value>> {
+        let iface = self.bindings.confium_plugin_kem();
+        match method {
+            "kem_encapsulator_create" | "kem_decapsulator_create" => {
+                let wanted = a.out_ptr()?;
+                let algorithm = a.string()?;
+                let key = a.bytes()?.unwrap_or_default();
+                let opts = a.options()?;
+                let (Some(algorithm), true) = (algorithm, wanted) else {
+                    return Ok(vec![code(NULL_POINTER), NULL]);
+                };
+                let (kind, outcome) = if method == "kem_encapsulator_create" {
+                    let outcome = iface.call_encapsulator_create(
+                        &mut self.store,
+                        &algorithm,
+                        &key,
+                        opts.as_ref(),
+                    );
+                    (Kind::Encapsulator, outcome)
+                } else {
+                    let outcome = iface.call_decapsulator_create(
+                        &mut self.store,
+                        &algorithm,
+                        &key,
+                        opts.as_ref(),
+                    );
+                    (Kind::Decapsulator, outcome)
+                };
+                let outcome = trapped(method, outcome)?;
+                Ok(self.created(kind, outcome))
+            }
+            "kem_encapsulate" => {
+                let obj = self.object(a, Kind::Encapsulator)?;
+                let ct_cap = a.out_buf()?;
+                let ct_len = a.in_out()?;
+                let ss_cap = a.out_buf()?;
+                let ss_len = a.in_out()?;
+                let outcome = iface.encapsulator().call_encapsulate(
+                    &mut self.store,
+                    obj,
+                    ct_cap.unwrap_or(0),
+                    ss_cap.unwrap_or(0),
+                );
+                Ok(match trapped(method, outcome)? {
+                    Ok(enc) => {
+                        let ct = fit(method, enc.ciphertext, ct_cap)?;
+                        let ss = fit(method, enc.shared_secret, ss_cap)?;
+                        let mut reply = vec![code(0)];
+                        reply.extend(ct);
+                        reply.extend(ss);
+                        reply
+                    }
+                    Err(c) => vec![code(c), NULL, u32_value(ct_len), NULL, u32_value(ss_len)],
+                })
+            }
+            "kem_decapsulate" => {
+                let obj = self.object(a, Kind::Decapsulator)?;
+                let ct = a.bytes()?.unwrap_or_default();
+                let cap = a.out_buf()?;
+                let len = a.in_out()?;
+                let outcome = iface.decapsulator().call_decapsulate(
+                    &mut self.store,
+                    obj,
+                    &ct,
+                    cap.unwrap_or(0),
+                );
+                sized_reply(method, trapped(method, outcome)?, cap, len)
+            }
+            "kem_encapsulator_destroy" => self.destroy(method, a, Kind::Encapsulator),
+            "kem_decapsulator_destroy" => self.destroy(method, a, Kind::Decapsulator),
+            "kem_shared_secret_size" => {
+                let algorithm = a.string()?;
+                let out = a.in_out()?;
+                let (Some(algorithm), Some(_)) = (algorithm, out) else {
+                    return Ok(vec![code(NULL_POINTER), u32_value(out)]);
+                };
+                let outcome = iface.call_shared_secret_size(&mut self.store, &algorithm);
+                Ok(match trapped(method, outcome)? {
+                    Ok(size) => vec![code(0), u32_value(Some(size))],
+                    Err(c) => vec![code(c), u32_value(out)],
+                })
+            }
+            "kem_keypair_generate" => {
+                let request = KeypairRequest::read(a)?;
+                let Some(algorithm) = &request.algorithm else {
+                    return Ok(request.refused(NULL_POINTER));
+                };
+                let outcome = iface.call_keypair_generate(
+                    &mut self.store,
+                    algorithm,
+                    request.seed.as_deref(),
+                    request.pk_cap.unwrap_or(0),
+                    request.sk_cap.unwrap_or(0),
+                );
+                request.reply(method, trapped(method, outcome)?)
+            }
+            "kem_handle_capabilities" => {
+                let algorithm = a.string()?;
+                let handle = a.handle()?;
+                let caps = a.in_out()?;
+                let (Some(algorithm), Some(handle), Some(_)) = (algorithm, handle, caps) else {
+                    return Ok(vec![code(NULL_POINTER), u32_value(caps)]);
+                };
+                let outcome = iface.call_handle_capabilities(&mut self.store, &algorithm, &handle);
+                Ok(vec![code(0), u32_value(Some(trapped(method, outcome)?))])
+            }
+            "kem_decapsulator_create_with_handle" => {
+                let wanted = a.out_ptr()?;
+                let algorithm = a.string()?;
+                let handle = a.handle()?;
+                let opts = a.options()?;
+                let (Some(algorithm), Some(handle), true) = (algorithm, handle, wanted) else {
+                    return Ok(vec![code(NULL_POINTER), NULL]);
+                };
+                let outcome = iface.call_decapsulator_create_with_handle(
+                    &mut self.store,
+                    &algorithm,
+                    &handle,
+                    opts.as_ref(),
+                );
+                let outcome = trapped(method, outcome)?;
+                Ok(self.created(Kind::Decapsulator, outcome))
+            }
+            _ => Err(unknown(method)),
+        }
+    }
+}
+
+impl SandboxInstance for PluginInstance {
+    /// Serve one call in the plugin-host convention. An `Err` means the
+    /// guest trapped or the call was malformed; a plugin-level failure
+    /// is an `Ok` reply with a non-zero code.
+    fn call(&mut self, function: &str, args: &[Value]) -> Result<Vec<Value>> {
+        let args = Args {
+            function,
+            values: args.iter(),
+        };
+        self.guarded(function, |this| this.dispatch(function, args))
+    }
+
+    fn grant_capability(&mut self, cap: Capability) -> Result<()> {
+        self.store.data().caps.grant(cap);
+        Ok(())
+    }
+
+    fn revoke_capability(&mut self, cap: &Capability) -> Result<()> {
+        self.store.data().caps.revoke(cap);
+        Ok(())
+    }
+}
+
+/// The arguments of keypair generation, shared by the signature and
+/// KEM interfaces: `(alg, seed, pk_cap, pk_len, sk_cap, sk_len)`.
+struct KeypairRequest {
+    algorithm: Option<String>,
+    seed: Option<Vec<u8>>,
+    pk_cap: Option<u32>,
+    pk_len: Option<u32>,
+    sk_cap: Option<u32>,
+    sk_len: Option<u32>,
+}
+
+impl KeypairRequest {
+    fn read(a: &mut Args<'_>) -> Result<Self> {
+        Ok(Self {
+            algorithm: a.string()?,
+            seed: a.bytes()?,
+            pk_cap: a.out_buf()?,
+            pk_len: a.in_out()?,
+            sk_cap: a.out_buf()?,
+            sk_len: a.in_out()?,
+        })
+    }
+
+    fn refused(&self, c: u32) -> Vec<Value> {
+        vec![
+            code(c),
+            NULL,
+            u32_value(self.pk_len),
+            NULL,
+            u32_value(self.sk_len),
+        ]
+    }
+
+    fn reply(
+        &self,
+        method: &str,
+        outcome: std::result::Result<Keypair, u32>,
+    ) -> Result<Vec<Value>> {
+        match outcome {
+            Ok(keypair) => {
+                let mut reply = vec![code(0)];
+                reply.extend(fit(method, keypair.public_key, self.pk_cap)?);
+                reply.extend(fit(method, keypair.secret_key, self.sk_cap)?);
+                Ok(reply)
+            }
+            Err(c) => Ok(self.refused(c)),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------
+// Argument decoding
+// ---------------------------------------------------------------------
+
+/// The arguments of one call, consumed in order.
+struct Args<'a> {
+    function: &'a str,
+    values: std::slice::Iter<'a, Value>,
+}
+
+impl Args<'_> {
+    fn invalid(&self) -> Error {
+        Error::ArgumentType {
+            function: self.function.to_string(),
+            backtrace: Backtrace::generate(),
+        }
+    }
+
+    fn value(&mut self) -> Result<&Value> {
+        let function = self.function;
+        self.values.next().ok_or_else(|| Error::ArgumentType {
+            function: function.to_string(),
+            backtrace: Backtrace::generate(),
+        })
+    }
+
+    fn int(&mut self) -> Result<i64> {
+        match self.value()? {
+            Value::I32(x) => Ok(i64::from(*x)),
+            Value::I64(x) => Ok(*x),
+            _ => Err(self.invalid()),
+        }
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        let v = self.int()?;
+        u32::try_from(v).map_err(|_| self.invalid())
+    }
+
+    /// Bytes, or `None` for the NULL marker.
+    fn bytes(&mut self) -> Result<Option<Vec<u8>>> {
+        match self.value()? {
+            Value::Bytes(b) => Ok(Some(b.clone())),
+            Value::I32(-1) | Value::I64(-1) => Ok(None),
+            _ => Err(self.invalid()),
+        }
+    }
+
+    fn string(&mut self) -> Result<Option<String>> {
+        match self.bytes()? {
+            None => Ok(None),
+            Some(b) => String::from_utf8(b).map(Some).map_err(|_| self.invalid()),
+        }
+    }
+
+    fn json(&mut self) -> Result<Option<JsonValue>> {
+        match self.bytes()? {
+            None => Ok(None),
+            Some(b) => serde_json::from_slice(&b)
+                .map(Some)
+                .map_err(|_| self.invalid()),
+        }
+    }
+
+    fn options(&mut self) -> Result<Option<Vec<OptionEntry>>> {
+        match self.json()? {
+            None => Ok(None),
+            Some(json) => options_from_json(&json)
+                .map(Some)
+                .ok_or_else(|| self.invalid()),
+        }
+    }
+
+    fn handle(&mut self) -> Result<Option<KeyHandle>> {
+        let Some(json) = self.json()? else {
+            return Ok(None);
+        };
+        let field = |name: &str| {
+            json.get(name)
+                .and_then(JsonValue::as_str)
+                .map(str::to_string)
+        };
+        // A handle with a NULL string is a NULL handle to the plugin.
+        Ok(match (field("backend"), field("key_id")) {
+            (Some(backend), Some(key_id)) => Some(KeyHandle { backend, key_id }),
+            _ => None,
+        })
+    }
+
+    /// The capacity of an output buffer, or `None` for NULL.
+    fn out_buf(&mut self) -> Result<Option<u32>> {
+        match self.int()? {
+            -1 => Ok(None),
+            cap => u32::try_from(cap).map(Some).map_err(|_| self.invalid()),
+        }
+    }
+
+    /// The value behind a `uint32_t*`, or `None` for NULL.
+    fn in_out(&mut self) -> Result<Option<u32>> {
+        self.out_buf()
+    }
+
+    /// Whether the caller passed an object out-pointer.
+    fn out_ptr(&mut self) -> Result<bool> {
+        Ok(self.int()? != -1)
+    }
+}
+
+/// Flatten the wire's JSON option object into WIT option entries.
+/// `None` if `json` is not an object of strings, `u32`s and nested
+/// objects.
+pub(crate) fn options_from_json(json: &JsonValue) -> Option<Vec<OptionEntry>> {
+    fn walk(json: &JsonValue, prefix: &[String], out: &mut Vec<OptionEntry>) -> Option<()> {
+        for (key, value) in json.as_object()? {
+            let mut path = prefix.to_vec();
+            path.push(key.clone());
+            let value = match value {
+                JsonValue::String(s) => OptionValue::Text(s.clone()),
+                JsonValue::Number(n) => OptionValue::Number(u32::try_from(n.as_u64()?).ok()?),
+                JsonValue::Object(_) => {
+                    walk(value, &path, out)?;
+                    continue;
+                }
+                _ => return None,
+            };
+            out.push(OptionEntry { path, value });
+        }
+        Some(())
+    }
+    let mut entries = Vec::new();
+    walk(json, &[], &mut entries)?;
+    Some(entries)
+}
+
+// ---------------------------------------------------------------------
+// Reply encoding
+// ---------------------------------------------------------------------
+
+fn code(c: u32) -> Value {
+    Value::I64(i64::from(c))
+}
+
+fn code_of(outcome: std::result::Result<(), u32>) -> Value {
+    code(outcome.err().unwrap_or(0))
+}
+
+fn u32_value(v: Option<u32>) -> Value {
+    v.map_or(NULL, |v| Value::I64(i64::from(v)))
+}
+
+/// `[bytes, len]` for an output the guest produced into a buffer of
+/// `cap` bytes. A guest that overruns the capacity it was given is
+/// misbehaving, not short of room.
+fn fit(method: &str, bytes: Vec<u8>, cap: Option<u32>) -> Result<[Value; 2]> {
+    let Some(cap) = cap else {
+        return Ok([NULL, u32_value(u32::try_from(bytes.len()).ok())]);
+    };
+    if bytes.len() > cap as usize {
+        return Err(Error::GuestProtocol {
+            function: method.to_string(),
+            reason: format!("returned {} bytes into a {cap}-byte buffer", bytes.len()),
+            backtrace: Backtrace::generate(),
+        });
+    }
+    let len = bytes.len() as u32;
+    Ok([Value::Bytes(bytes), u32_value(Some(len))])
+}
+
+/// `[code, bytes]` for fixed-size outputs (`hash_finalize`,
+/// `rng_generate`, `kdf_derive`).
+fn buf_reply(
+    method: &str,
+    outcome: std::result::Result<Vec<u8>, u32>,
+    cap: Option<u32>,
+) -> Result<Vec<Value>> {
+    match outcome {
+        Ok(bytes) => {
+            let [bytes, _] = fit(method, bytes, cap)?;
+            Ok(vec![code(0), bytes])
+        }
+        Err(c) => Ok(vec![code(c), NULL]),
+    }
+}
+
+/// `[code, bytes, len]` for outputs that report their length. On
+/// failure the length is handed back unchanged.
+fn sized_reply(
+    method: &str,
+    outcome: std::result::Result<Vec<u8>, u32>,
+    cap: Option<u32>,
+    len: Option<u32>,
+) -> Result<Vec<Value>> {
+    match outcome {
+        Ok(bytes) => {
+            let [bytes, written] = fit(method, bytes, cap)?;
+            let written = if len.is_some() { written } else { NULL };
+            Ok(vec![code(0), bytes, written])
+        }
+        Err(c) => Ok(vec![code(c), NULL, u32_value(len)]),
+    }
+}
+
+/// Unwrap a guest call, turning a trap into [`Error::Invocation`].
+fn trapped<T>(function: &str, outcome: wasmtime::Result<T>) -> Result<T> {
+    outcome.map_err(|e| invocation(function, e))
+}
+
+fn invocation<E: std::fmt::Display>(function: &str, e: E) -> Error {
+    Error::Invocation {
+        function: function.to_string(),
+        source: WasmtimeError::from_display(e),
+        backtrace: Backtrace::generate(),
+    }
+}
+
+fn unknown(method: &str) -> Error {
+    Error::FunctionNotFound {
+        function: method.to_string(),
+        backtrace: Backtrace::generate(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn options_flatten_nested_maps_into_paths() {
+        let json = serde_json::json!({
+            "mode": "gcm",
+            "keyfmt": { "encoding": "pem", "bits": 256 },
+        });
+        let mut entries = options_from_json(&json).expect("decodes");
+        entries.sort_by(|a, b| a.path.cmp(&b.path));
+        let paths: Vec<Vec<&str>> = entries
+            .iter()
+            .map(|e| e.path.iter().map(String::as_str).collect())
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                vec!["keyfmt", "bits"],
+                vec!["keyfmt", "encoding"],
+                vec!["mode"]
+            ]
+        );
+        assert!(matches!(entries[0].value, OptionValue::Number(256)));
+        assert!(matches!(&entries[2].value, OptionValue::Text(s) if s == "gcm"));
+    }
+
+    #[test]
+    fn options_refuse_other_shapes() {
+        assert!(options_from_json(&serde_json::json!([1])).is_none());
+        assert!(options_from_json(&serde_json::json!({"n": -1})).is_none());
+        assert!(options_from_json(&serde_json::json!({"b": true})).is_none());
+    }
+
+    #[test]
+    fn fit_refuses_an_overrun() {
+        assert!(fit("hash_finalize", vec![0; 33], Some(32)).is_err());
+        let [bytes, len] = fit("hash_finalize", vec![7; 4], Some(32)).unwrap();
+        assert_eq!(bytes, Value::Bytes(vec![7; 4]));
+        assert_eq!(len, Value::I64(4));
+    }
+
+    #[test]
+    fn sized_reply_hands_the_length_back_on_failure() {
+        let reply = sized_reply("cipher_update", Err(12), Some(16), Some(16)).unwrap();
+        assert_eq!(reply, vec![Value::I64(12), NULL, Value::I64(16)]);
+    }
+
+    #[test]
+    fn default_limits_match_the_module_sandbox() {
+        let limits = ComponentLimits::default();
+        assert_eq!(limits.memory_bytes, 32 * 1024 * 1024);
+        assert_eq!(limits.fuel, DEFAULT_FUEL);
+    }
+}
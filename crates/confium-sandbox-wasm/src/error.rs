@@ -82,6 +82,12 @@ pub enum Error {
         source: SourceError,
         backtrace: Backtrace,
     },
+    #[snafu(display("WASM plugin broke the call contract in '{}': {}", function, reason))]
+    GuestProtocol {
+        function: String,
+        reason: String,
+        backtrace: Backtrace,
+    },
 }
 
 impl Error {
@@ -99,6 +105,7 @@ impl Error {
             Error::CapabilityDenied { .. } => 0x2006,
             Error::HostImport { .. } => 0x2007,
             Error::Engine { .. } => 0x2008,
+            Error::GuestProtocol { .. } => 0x2009,
         }
     }
 }
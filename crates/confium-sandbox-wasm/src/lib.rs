@@ -20,6 +20,9 @@
 //! - [`Capability`] — the capability model (interface / network /
 //!   key / filesystem).
 //! - [`Value`] — values crossing the sandbox boundary.
+//! - [`PluginSandbox`] — Confium crypto plugins as WebAssembly
+//!   components (`wit/plugin.wit`), run under [`ComponentLimits`].
+//!   Guests are written against the `confium-wasm-guest` SDK.
 //! - [`HostImports`] (internal) — `cfm_*` host-import dispatch with
 //!   capability gating.
 //!
@@ -31,13 +34,18 @@
 //! imports are stubs (deterministic return values) so the end-to-end
 //! pipeline can be exercised before the real hash / net / key
 //! handlers in confium-core / confium-net / confium-store are wired
-//! up.
+//! up. Plugin components need no host imports and are complete:
+//! `confium-core` loads them with `sandbox = "wasm"` (feature `wasm`).
 
+pub mod component;
 pub mod error;
 pub mod imports;
 pub mod sandbox;
 pub mod wasm;
 
+pub use component::ComponentLimits;
+pub use component::PluginInstance;
+pub use component::PluginSandbox;
 pub use error::Error;
 pub use error::Result;
 pub use sandbox::Capability;
@@ -36,9 +36,11 @@ pub(crate) struct HostState {
 
 /// Default linear-memory cap target for a sandboxed instance.
 /// Matches the design doc (§ Performance considerations). Enforced
-/// via `Store::limiter` (TODO) rather than `Config`, since wasmtime's
-/// `Config::max_memory_size` is gated behind the pooling allocator.
-const DEFAULT_MEMORY_BYTES: usize = 32 * 1024 * 1024;
+/// via `Store::limiter` rather than `Config`, since wasmtime's
+/// `Config::max_memory_size` is gated behind the pooling allocator;
+/// plugin components apply it today (see
+/// [`ComponentLimits`](crate::ComponentLimits)), core modules (TODO).
+pub(crate) const DEFAULT_MEMORY_BYTES: usize = 32 * 1024 * 1024;
 
 /// The wasmtime-backed sandbox.
 ///
@@ -89,9 +91,6 @@ impl WasmSandbox {
     }
 }
 
-// Keep the design-doc constant visible (avoid dead-code warning).
-const _: usize = DEFAULT_MEMORY_BYTES;
-
 impl Sandbox for WasmSandbox {
     fn load_module(&self, bytes: &[u8]) -> Result<Box<dyn SandboxInstance>> {
         let module = Module::new(&self.engine, bytes).map_err(|e| Error::ModuleCompile {
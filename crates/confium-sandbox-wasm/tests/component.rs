@@ -0,0 +1,32 @@
+//! Loading plugin components through `PluginSandbox`.
+//!
+//! These tests cover what the host decides before any guest code runs:
+//! a core module is not a plugin, and a component has to export the
+//! whole `confium:plugin/plugin` world. A real guest component is
+//! exercised end to end in `confium-it/tests/wasm_plugin.rs`.
+
+use confium_sandbox_wasm::ComponentLimits;
+use confium_sandbox_wasm::Error;
+use confium_sandbox_wasm::PluginSandbox;
+
+#[test]
+fn core_module_is_not_a_plugin_component() {
+    let sb = PluginSandbox::new().expect("sandbox builds");
+    let bytes = wat::parse_str("(module)").expect("wat must parse");
+    let err = sb
+        .load(&bytes, ComponentLimits::default())
+        .err()
+        .expect("core module refused");
+    assert!(matches!(err, Error::ModuleCompile { .. }), "{err}");
+}
+
+#[test]
+fn component_without_the_plugin_exports_fails_to_instantiate() {
+    let sb = PluginSandbox::new().expect("sandbox builds");
+    let bytes = wat::parse_str("(component)").expect("wat must parse");
+    let err = sb
+        .load(&bytes, ComponentLimits::default())
+        .err()
+        .expect("empty component refused");
+    assert!(matches!(err, Error::Instantiation { .. }), "{err}");
+}
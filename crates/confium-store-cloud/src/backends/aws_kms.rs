@@ -28,9 +28,9 @@
 //! Construction builds a real [`aws_sdk_kms::Client`]. The
 //! [`StoreInstance`](confium_store::backend::StoreInstance) methods are
 //! stubbed to return [`NotImplemented`](confium_store::error::Error::NotImplemented)
-//! until the `cfmp_sign_with_handle` plugin contract (TODO #03) lands,
 //! because AWS KMS never exports raw key bytes — it returns opaque key
-//! ARNs that the signature plugin must invoke via `Sign` / `Verify`.
+//! ARNs that a signature plugin invokes via `Sign` / `Verify` through
+//! `cfmp_sig_signer_create_with_handle` (signature interface v1).
 
 use std::ffi::c_void;
 
@@ -119,8 +119,8 @@ impl AwsKmsInstance {
     /// deferred because `aws_config::defaults(...).load()` is async and
     /// the `StoreInstance` trait is not. Returns
     /// [`Error::NotImplemented`] for now — the actual `Sign` / `Verify`
-    /// calls depend on the `cfmp_sign_with_handle` plugin contract
-    /// (TODO #03) and will replace this stub.
+    /// calls are issued by key-handle signature plugins
+    /// (`cfmp_sig_signer_create_with_handle`) and will replace this stub.
     fn ensure_client(&mut self) -> Result<&KmsClient> {
         if self.client.is_none() {
             // The real construction goes here once the plugin contract
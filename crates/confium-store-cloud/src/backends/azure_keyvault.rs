@@ -23,7 +23,8 @@
 //!
 //! Construction is wired; the [`StoreInstance`] methods return
 //! [`NotImplemented`](confium_store::error::Error::NotImplemented)
-//! pending the `cfmp_sign_with_handle` plugin contract (TODO #03).
+//! until the Key Vault calls behind `cfmp_sig_signer_create_with_handle`
+//! (signature interface v1) are wired.
 //! Key Vault, like the other two cloud providers, returns opaque key
 //! identifiers (`https://<vault>/keys/<name>/<version>`) rather than
 //! raw key bytes; a signature plugin invokes `Sign` against the
@@ -103,8 +104,8 @@ pub struct AzureKeyVaultInstance {
 impl AzureKeyVaultInstance {
     /// Lazily build the Key Vault client. Returns
     /// [`Error::NotImplemented`] for now — the actual `Sign` /
-    /// `GetKey` calls depend on the `cfmp_sign_with_handle` plugin
-    /// contract (TODO #03).
+    /// `GetKey` calls are issued by key-handle signature plugins
+    /// (`cfmp_sig_signer_create_with_handle`).
     fn ensure_client(&mut self) -> Result<&azure_security_keyvault::KeyvaultClient> {
         if self.client.is_none() {
             // The real construction goes here once the plugin contract
@@ -25,7 +25,8 @@
 //!
 //! Construction is wired; the [`StoreInstance`] methods return
 //! [`NotImplemented`](confium_store::error::Error::NotImplemented)
-//! pending the `cfmp_sign_with_handle` plugin contract (TODO #03).
+//! until the KMS calls behind `cfmp_sig_signer_create_with_handle`
+//! (signature interface v1) are wired.
 //! Cloud KMS, like AWS KMS, never exports raw key material; it returns
 //! opaque resource names (`projects/.../keyRings/.../cryptoKeys/...`)
 //! that a signature plugin must use to invoke `AsymmetricSign`.
@@ -109,8 +110,8 @@ pub struct GcpKmsInstance {
 impl GcpKmsInstance {
     /// Lazily build the Cloud KMS client. Returns
     /// [`Error::NotImplemented`] for now — the actual
-    /// `AsymmetricSign` / `GetPublicKey` calls depend on the
-    /// `cfmp_sign_with_handle` plugin contract (TODO #03).
+    /// `AsymmetricSign` / `GetPublicKey` calls are issued by key-handle
+    /// signature plugins (`cfmp_sig_signer_create_with_handle`).
     fn ensure_client(&mut self) -> Result<&google_cloud_kms::client::Client> {
         if self.client.is_none() {
             // The real construction goes here once the plugin contract
@@ -24,10 +24,12 @@
 //! The SDK wiring is in place (config parsing, client construction,
 //! credential lookup) but the actual KMS REST/gRPC calls are stubbed to
 //! return [`confium_store::error::Error::NotImplemented`]. This lets the
-//! crate ship and build across all three providers today while the
-//! `cfmp_sign_with_handle` plugin contract from TODO #03 is finalised —
-//! that contract governs how a signature plugin invokes a remote HSM
-//! sign operation against the handle returned by `get_secret`.
+//! crate ship and build across all three providers today. The plugin
+//! side of the contract is the v1 key-handle extension of the
+//! `signature` and `kem` interfaces (`cfmp_sig_signer_create_with_handle`,
+//! `cfmp_kem_decapsulator_create_with_handle`): a provider plugin is
+//! handed the `(backend, key_id)` pair plus the handle returned by
+//! `get_secret` and performs the remote HSM operation itself.
 
 pub mod backends;
 
@@ -22,8 +22,9 @@
 //! Like the other hardware backends, the PKCS#11 store does not return
 //! raw key bytes from `get_secret`; it returns the PKCS#11 object
 //! handle (an opaque `*mut c_void`). Signature/KEM plugins that want
-//! to actually use the key invoke the HSM-style `cfmp_sign_withhandle`
-//! symbol described in `TODO.roadmap/18-hardware-keystore-backends.md`.
+//! to actually use the key implement the v1 key-handle extension of the
+//! signature / KEM interfaces (`cfmp_sig_signer_create_with_handle`,
+//! `cfmp_kem_decapsulator_create_with_handle`).
 //! The skeleton does not yet wire this — every storage operation is a
 //! `NotImplemented` stub; the session plumbing (module load,
 //! initialize, slot resolve, open session, login) is wired for real.
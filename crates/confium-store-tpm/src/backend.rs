@@ -20,8 +20,9 @@
 //! will seal the caller-supplied bytes under the parent key and store the
 //! resulting object handle; `get_secret` will return the handle as the
 //! opaque `*mut c_void`. Signature/KEM plugins that want to actually use
-//! the key invoke the HSM-style `cfmp_sign_with_handle` symbol described
-//! in `TODO.roadmap/18-hardware-keystore-backends.md`. The skeleton does
+//! the key implement the v1 key-handle extension of the signature / KEM
+//! interfaces (`cfmp_sig_signer_create_with_handle`,
+//! `cfmp_kem_decapsulator_create_with_handle`). The skeleton does
 //! not yet wire this — every operation is a `NotImplemented` stub.
 
 use std::ffi::c_void;
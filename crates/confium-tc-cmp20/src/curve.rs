@@ -0,0 +1,216 @@
+//! The curves CMP20 runs over.
+//!
+//! Key generation, signing, recovery and the share format are generic
+//! over [`Cmp20Curve`]: a prime-order `elliptic_curve` group (see
+//! [`ZkCurve`]) plus the identifiers and message digest that make it an
+//! ECDSA suite.
+//!
+//! | Curve     | Id | Digest  | DKG scheme              | Signing scheme               |
+//! |-----------|----|---------|-------------------------|------------------------------|
+//! | P-256     | 1  | SHA-256 | `CMP20-ECDSA-P256`      | `CMP20-ECDSA-P256-SIGN`      |
+//! | P-384     | 2  | SHA-384 | `CMP20-ECDSA-P384`      | `CMP20-ECDSA-P384-SIGN`      |
+//! | secp256k1 | 3  | SHA-256 | `CMP20-ECDSA-SECP256K1` | `CMP20-ECDSA-SECP256K1-SIGN` |
+//!
+//! Signatures are `r || s`, each a big-endian scalar as long as the
+//! curve's field (64 bytes, or 96 on P-384), with `s` normalised low.
+//! They verify under the `p256`, `p384` and `k256` ECDSA verifiers.
+
+use confium_crypto_vss::paillier_zk::{self, ZkCurve};
+use elliptic_curve::group::{Curve as _, Group};
+use elliptic_curve::point::AffineCoordinates;
+use elliptic_curve::scalar::IsHigh;
+use elliptic_curve::sec1::{FromSec1Point, Sec1Point, ToSec1Point};
+use elliptic_curve::{AffinePoint, Field, FieldBytes, PrimeField, ProjectivePoint, Scalar};
+use num_bigint::{BigInt, Sign};
+use sha2::{Digest, Sha256, Sha384};
+
+/// The supported curves, re-exported so callers of the generic API
+/// need not depend on the curve crates.
+pub use k256::Secp256k1;
+pub use p256::NistP256;
+pub use p384::NistP384;
+
+/// Share-format identifier of P-256.
+pub const CURVE_P256: u8 = 1;
+/// Share-format identifier of P-384.
+pub const CURVE_P384: u8 = 2;
+/// Share-format identifier of secp256k1.
+pub const CURVE_SECP256K1: u8 = 3;
+
+/// A curve CMP20 can generate keys and sign over.
+pub trait Cmp20Curve: ZkCurve {
+    /// Identifier carried in share blobs.
+    const CURVE_ID: u8;
+    /// Registered name of the DKG scheme.
+    const DKG_SCHEME_NAME: &'static str;
+    /// Registered name of the signing scheme; also binds the proofs of
+    /// a signing session to the curve.
+    const SIGN_SCHEME_NAME: &'static str;
+
+    /// The ECDSA message digest.
+    fn digest(message: &[u8]) -> Vec<u8>;
+}
+
+impl Cmp20Curve for NistP256 {
+    const CURVE_ID: u8 = CURVE_P256;
+    const DKG_SCHEME_NAME: &'static str = crate::DKG_SCHEME_NAME;
+    const SIGN_SCHEME_NAME: &'static str = crate::SIGN_SCHEME_NAME;
+
+    fn digest(message: &[u8]) -> Vec<u8> {
+        Sha256::digest(message).to_vec()
+    }
+}
+
+impl Cmp20Curve for NistP384 {
+    const CURVE_ID: u8 = CURVE_P384;
+    const DKG_SCHEME_NAME: &'static str = crate::P384_DKG_SCHEME_NAME;
+    const SIGN_SCHEME_NAME: &'static str = crate::P384_SIGN_SCHEME_NAME;
+
+    fn digest(message: &[u8]) -> Vec<u8> {
+        Sha384::digest(message).to_vec()
+    }
+}
+
+impl Cmp20Curve for Secp256k1 {
+    const CURVE_ID: u8 = CURVE_SECP256K1;
+    const DKG_SCHEME_NAME: &'static str = crate::SECP256K1_DKG_SCHEME_NAME;
+    const SIGN_SCHEME_NAME: &'static str = crate::SECP256K1_SIGN_SCHEME_NAME;
+
+    fn digest(message: &[u8]) -> Vec<u8> {
+        Sha256::digest(message).to_vec()
+    }
+}
+
+/// Length of a serialised scalar (and of a field element).
+pub fn scalar_len<C: Cmp20Curve>() -> usize {
+    FieldBytes::<C>::default().len()
+}
+
+/// Length of a SEC1-compressed point.
+pub fn point_len<C: Cmp20Curve>() -> usize {
+    scalar_len::<C>() + 1
+}
+
+pub(crate) fn encode_point<C: Cmp20Curve>(p: &AffinePoint<C>) -> Vec<u8> {
+    p.to_sec1_point(true).as_bytes().to_vec()
+}
+
+/// A SEC1-compressed point; the identity and uncompressed encodings
+/// are refused.
+pub(crate) fn decode_point<C: Cmp20Curve>(bytes: &[u8]) -> Option<AffinePoint<C>> {
+    if bytes.len() != point_len::<C>() {
+        return None;
+    }
+    let encoded = Sec1Point::<C>::from_bytes(bytes).ok()?;
+    Option::from(AffinePoint::<C>::from_sec1_point(&encoded))
+}
+
+/// A canonical big-endian scalar.
+pub(crate) fn decode_scalar<C: Cmp20Curve>(bytes: &[u8]) -> Option<Scalar<C>> {
+    let mut repr = FieldBytes::<C>::default();
+    if bytes.len() != repr.len() {
+        return None;
+    }
+    repr.copy_from_slice(bytes);
+    Option::from(Scalar::<C>::from_repr(repr))
+}
+
+/// `z`: the message digest truncated to the order's length and reduced
+/// modulo it.
+pub(crate) fn message_scalar<C: Cmp20Curve>(message: &[u8]) -> Scalar<C> {
+    let digest = C::digest(message);
+    let bits = &digest[..digest.len().min(scalar_len::<C>())];
+    paillier_zk::int_to_scalar::<C>(&BigInt::from_bytes_be(Sign::Plus, bits))
+}
+
+/// `R.x mod n`.
+pub(crate) fn x_scalar<C: Cmp20Curve>(point: &AffinePoint<C>) -> Scalar<C> {
+    paillier_zk::int_to_scalar::<C>(&BigInt::from_bytes_be(Sign::Plus, &point.x()))
+}
+
+/// `s` or `n - s`, whichever is at most `n / 2`.
+pub(crate) fn normalize_s<C: Cmp20Curve>(s: Scalar<C>) -> Scalar<C> {
+    if bool::from(s.is_high()) { -s } else { s }
+}
+
+/// Check an `r || s` signature over `message` under `public_key`.
+pub fn verify<C: Cmp20Curve>(
+    public_key: &AffinePoint<C>,
+    message: &[u8],
+    signature: &[u8],
+) -> bool {
+    let n = scalar_len::<C>();
+    if signature.len() != 2 * n {
+        return false;
+    }
+    let (Some(r), Some(s)) = (
+        decode_scalar::<C>(&signature[..n]),
+        decode_scalar::<C>(&signature[n..]),
+    ) else {
+        return false;
+    };
+    let Some(s_inv) = Option::<Scalar<C>>::from(s.invert()) else {
+        return false;
+    };
+    if bool::from(r.is_zero()) {
+        return false;
+    }
+    let z = message_scalar::<C>(message);
+    let point = ProjectivePoint::<C>::generator() * (z * s_inv)
+        + ProjectivePoint::<C>::from(*public_key) * (r * s_inv);
+    !bool::from(point.is_identity()) && x_scalar::<C>(&point.to_affine()) == r
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn curve_ids_and_scheme_names_are_distinct() {
+        let ids = [NistP256::CURVE_ID, NistP384::CURVE_ID, Secp256k1::CURVE_ID];
+        assert_eq!(ids, [1, 2, 3]);
+        let names = [
+            NistP256::DKG_SCHEME_NAME,
+            NistP256::SIGN_SCHEME_NAME,
+            NistP384::DKG_SCHEME_NAME,
+            NistP384::SIGN_SCHEME_NAME,
+            Secp256k1::DKG_SCHEME_NAME,
+            Secp256k1::SIGN_SCHEME_NAME,
+        ];
+        let unique: std::collections::HashSet<_> = names.iter().collect();
+        assert_eq!(unique.len(), names.len());
+    }
+
+    #[test]
+    fn encodings_follow_the_field_size() {
+        assert_eq!(
+            (scalar_len::<NistP256>(), point_len::<NistP256>()),
+            (32, 33)
+        );
+        assert_eq!(
+            (scalar_len::<NistP384>(), point_len::<NistP384>()),
+            (48, 49)
+        );
+        assert_eq!(
+            (scalar_len::<Secp256k1>(), point_len::<Secp256k1>()),
+            (32, 33)
+        );
+        let g = ProjectivePoint::<NistP384>::generator().to_affine();
+        assert_eq!(
+            decode_point::<NistP384>(&encode_point::<NistP384>(&g)),
+            Some(g)
+        );
+        assert!(decode_point::<NistP256>(&encode_point::<NistP384>(&g)).is_none());
+    }
+
+    #[test]
+    fn verify_accepts_a_single_key_p384_signature() {
+        use elliptic_curve::Generate;
+        use p384::ecdsa::{Signature, SigningKey, signature::Signer};
+        let sk = SigningKey::generate();
+        let sig: Signature = sk.sign(b"cnsa");
+        let pk = *sk.verifying_key().as_affine();
+        assert!(verify::<NistP384>(&pk, b"cnsa", &sig.to_bytes()));
+        assert!(!verify::<NistP384>(&pk, b"other", &sig.to_bytes()));
+    }
+}
@@ -21,22 +21,21 @@ pub enum Cmp20ErrorCode {
     /// shares is a programming error, not a network fault.
     /// Caller action: collect more shares before retrying.
     BELOW_THRESHOLD = 0x6010,
-    /// A round message failed to deserialize or had an unexpected
-    /// sender / round number. Indicates protocol desynchronization
-    /// (one party is on a different round than the others) or a
-    /// buggy / malicious peer.
+    /// The signers' key shares do not interpolate to the joint key —
+    /// typically shares from different DKGs. Malformed or out-of-round
+    /// messages are attributed to their sender via
+    /// `Error::MessageRejected` instead.
     /// Caller action: abort the session; do not retry with the same
     /// message feed.
     BAD_ROUND_MESSAGE = 0x6020,
-    /// A partial signature was malformed (wrong length, out-of-range
-    /// scalar) but the offending party is not identified. This is the
-    /// non-identifiable-abort path; if identifiable abort is enabled,
-    /// [`IDENTIFIED_BYZANTINE`](Self::IDENTIFIED_BYZANTINE) is emitted
-    /// instead.
+    /// The combined `δ` or signature failed its check. The offending
+    /// party is not identified: every proof verified, and CMP20 leaves
+    /// attribution to a separate identification phase.
     BAD_PARTIAL_SIGNATURE = 0x6030,
     /// Identifiable abort: a specific peer posted an inconsistent
     /// partial signature. The carry payload identifies the offending
-    /// party by its 1-based roster index in the low byte.
+    /// party by its 1-based roster index in the low byte. No longer
+    /// emitted: signing names culprits via `Error::MessageRejected`.
     IDENTIFIED_BYZANTINE = 0x6040,
     /// Internal error — a panic-equivalent condition was caught and
     /// converted to an error return. Indicates a bug in the CMP20
@@ -10,26 +10,31 @@
 //! [`keygen`] returns `(shares, public_key)` where:
 //!
 //! - `shares[i]` is the opaque `Cmp20Share::to_bytes()` encoding for
-//!   party `i` (71 bytes: magic[4] | version[1] | x_i[32] | X[33] | idx[1]).
+//!   party `i` (72 bytes: magic[4] | version[1] | curve_id[1] | x_i[32]
+//!   | X[33] | idx[1]).
 //! - `public_key` is the 33-byte SEC1 compressed encoding of the joint
 //!   P-256 point.
 //!
 //! [`sign`] returns a 64-byte `r || s` ECDSA signature. Verify it with
 //! the [`p256::ecdsa`] crate's `VerifyingKey::verify`.
 //!
-//! ## Security note
+//! [`keygen_curve`] and [`sign_curve`] do the same over any
+//! [`Cmp20Curve`]; on P-384 the public key is 49 bytes, shares 104
+//! bytes and signatures 96 bytes.
 //!
-//! The underlying CMP20 crate's MtA sub-round is a simplified in-clear
-//! stub. This driver inherits that property. See
-//! [`crate::mta`] for the gap.
+//! ## Cost
+//!
+//! Every signing session generates a fresh 2048-bit Paillier-Blum
+//! modulus per party and proves it well formed (see [`crate::sign`]),
+//! which dominates the run time of [`sign`].
 
-use elliptic_curve::sec1::ToSec1Point;
-use p256::AffinePoint;
+use p256::{AffinePoint, NistP256};
 
 use confium_tc::Result;
 use confium_tc::inprocess as driver;
 
-use crate::share::Cmp20Share;
+use crate::curve::{self, Cmp20Curve};
+use crate::share::Cmp20KeyShare;
 
 /// Outcome of a single CMP20 DKG run: N share blobs plus the joint
 /// public key.
@@ -38,7 +43,8 @@ pub struct KeygenOutput {
     /// One share blob per party, in roster order (0-based index matches
     /// `party_idx` of the share after subtracting 1).
     pub shares: Vec<Vec<u8>>,
-    /// 33-byte SEC1 compressed encoding of the joint P-256 public key.
+    /// SEC1 compressed encoding of the joint public key (33 bytes on
+    /// P-256).
     pub public_key: Vec<u8>,
 }
 
@@ -48,9 +54,14 @@ pub struct KeygenOutput {
 /// `threshold` must be in `1..=party_count`. All parties are in-process
 /// (`Party::inproc`), identified as `p0`, `p1`, … `p{n-1}`.
 pub fn keygen(threshold: u32, party_count: usize) -> Result<KeygenOutput> {
-    let shares = driver::run_dkg(crate::DKG_SCHEME_NAME, threshold, party_count)?;
-    let first = Cmp20Share::from_bytes(&shares[0])?;
-    let public_key: Vec<u8> = first.public_key.to_sec1_point(true).as_bytes().to_vec();
+    keygen_curve::<NistP256>(threshold, party_count)
+}
+
+/// [`keygen`] over curve `C`.
+pub fn keygen_curve<C: Cmp20Curve>(threshold: u32, party_count: usize) -> Result<KeygenOutput> {
+    let shares = driver::run_dkg(C::DKG_SCHEME_NAME, threshold, party_count)?;
+    let first = Cmp20KeyShare::<C>::from_bytes(&shares[0])?;
+    let public_key = curve::encode_point::<C>(&first.public_key);
     Ok(KeygenOutput { shares, public_key })
 }
 
@@ -59,10 +70,21 @@ pub fn keygen(threshold: u32, party_count: usize) -> Result<KeygenOutput> {
 /// threshold `threshold`. Returns the 64-byte `(r, s)` ECDSA signature.
 ///
 /// `share_blobs.len()` must be `>= threshold`. The supplied shares must
-/// all share the same joint public key — the protocol will otherwise
-/// silently produce an invalid signature.
+/// come from the same DKG; otherwise signing aborts in round 2 with
+/// [`crate::error::Cmp20ErrorCode::BAD_ROUND_MESSAGE`].
 pub fn sign(share_blobs: &[Vec<u8>], threshold: u32, message: &[u8]) -> Result<Vec<u8>> {
-    driver::run_sign(crate::SIGN_SCHEME_NAME, share_blobs, threshold, message)
+    sign_curve::<NistP256>(share_blobs, threshold, message)
+}
+
+/// [`sign`] over curve `C`, with shares from [`keygen_curve`] over the
+/// same curve. Shares over another curve abort with
+/// [`crate::error::Cmp20ErrorCode::BAD_SHARE`].
+pub fn sign_curve<C: Cmp20Curve>(
+    share_blobs: &[Vec<u8>],
+    threshold: u32,
+    message: &[u8],
+) -> Result<Vec<u8>> {
+    driver::run_sign(C::SIGN_SCHEME_NAME, share_blobs, threshold, message)
 }
 
 /// Sign `messages.len()` messages against the same joint key without
@@ -74,7 +96,7 @@ pub fn sign(share_blobs: &[Vec<u8>], threshold: u32, message: &[u8]) -> Result<V
 ///
 /// ## Performance
 ///
-/// Each message signing is a full 4-round CMP20 protocol run. This
+/// Each message signing is a full six-round CMP20 protocol run. This
 /// function does NOT cache nonces across messages — doing so would
 /// leak the joint secret. The win over calling [`sign`] in a loop is
 /// that the per-call Ruby/Python binding overhead disappears, which
@@ -95,16 +117,7 @@ pub fn sign_batch(
 /// Decode a 33-byte SEC1 compressed P-256 point. Public so bindings can
 /// verify the DKG-produced joint public key out-of-band.
 pub fn decode_public_key(bytes: &[u8]) -> Result<AffinePoint> {
-    use elliptic_curve::sec1::FromSec1Point;
-    if bytes.len() != 33 {
-        return Err(crate::error::scheme_error(
-            crate::error::Cmp20ErrorCode::BAD_SHARE,
-        ));
-    }
-    let enc = elliptic_curve::sec1::Sec1Point::<p256::NistP256>::from_bytes(bytes)
-        .map_err(|_| crate::error::scheme_error(crate::error::Cmp20ErrorCode::BAD_SHARE))?;
-    Option::<AffinePoint>::from(AffinePoint::from_sec1_point(&enc))
-        .ok_or_else(|| crate::error::scheme_error(crate::error::Cmp20ErrorCode::BAD_SHARE))
+    crate::share::decode_affine::<NistP256>(bytes)
 }
 
 #[cfg(test)]
@@ -177,4 +190,34 @@ mod tests {
             vk.verify(msg, &s).expect("verify");
         }
     }
+
+    #[test]
+    fn p384_keygen_and_sign_verify_under_p384_ecdsa() {
+        use p384::ecdsa::{Signature, VerifyingKey, signature::Verifier};
+        let kg = keygen_curve::<p384::NistP384>(2, 3).expect("dkg");
+        assert_eq!(kg.public_key.len(), 49);
+        assert_eq!(kg.shares[0].len(), 104);
+        let sig = sign_curve::<p384::NistP384>(&kg.shares[1..], 2, b"cnsa").expect("sign");
+        assert_eq!(sig.len(), 96);
+        let vk = VerifyingKey::from_sec1_bytes(&kg.public_key).expect("vk");
+        let s = Signature::from_slice(&sig).expect("parse sig");
+        vk.verify(b"cnsa", &s).expect("verify ok");
+    }
+
+    #[test]
+    fn secp256k1_keygen_and_sign_verify_under_k256_ecdsa() {
+        use k256::ecdsa::{Signature, VerifyingKey, signature::Verifier};
+        let kg = keygen_curve::<k256::Secp256k1>(2, 3).expect("dkg");
+        let sig = sign_curve::<k256::Secp256k1>(&kg.shares[..2], 2, b"code signing").expect("sign");
+        assert_eq!(sig.len(), 64);
+        let vk = VerifyingKey::from_sec1_bytes(&kg.public_key).expect("vk");
+        let s = Signature::from_slice(&sig).expect("parse sig");
+        vk.verify(b"code signing", &s).expect("verify ok");
+    }
+
+    #[test]
+    fn shares_do_not_cross_curves() {
+        let kg = keygen_curve::<k256::Secp256k1>(2, 3).expect("dkg");
+        assert!(sign(&kg.shares[..2], 2, b"msg").is_err());
+    }
 }
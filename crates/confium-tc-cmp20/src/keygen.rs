@@ -1,4 +1,5 @@
-//! CMP20 non-interactive distributed key generation over P-256.
+//! CMP20 non-interactive distributed key generation over any
+//! [`Cmp20Curve`].
 //!
 //! CMP20's headline DKG improvement over GG18 is that key generation is
 //! **non-interactive**: a single broadcast round suffices. Each party
@@ -21,25 +22,68 @@
 //!   evaluation addressed to us, sum the verified shares into `x_i`,
 //!   and compute the joint public key. Complete.
 
-use elliptic_curve::PrimeField;
+use std::marker::PhantomData;
+
+use elliptic_curve::group::{Curve as _, Group};
 use elliptic_curve::rand_core::UnwrapErr;
+use elliptic_curve::{AffinePoint, Field, NonZeroScalar, PrimeField, ProjectivePoint, Scalar};
 use getrandom::SysRng;
-use p256::{AffinePoint, ProjectivePoint, Scalar};
+use p256::NistP256;
 
 use confium_tc::Result;
 use confium_tc::message::Message;
 use confium_tc::registry::{RoundResult, SessionImpl};
 use confium_tc::session::SessionParams;
+use confium_tc::snapshot::{StateReader, StateWriter};
+use zeroize::Zeroizing;
 
+use crate::curve::{self, Cmp20Curve};
 use crate::error::{Cmp20ErrorCode, scheme_error};
-use crate::share::{Cmp20Share, SHARE_BYTES};
+use crate::share::{Cmp20KeyShare, read_point, read_scalar, write_point, write_scalar};
 use crate::vss::FeldmanVss;
 
-/// CMP20 DKG scheme over P-256. Registered as `CMP20-ECDSA-P256`.
-pub struct Cmp20DkgP256;
+/// CMP20 DKG over curve `C`. Registered as `C::DKG_SCHEME_NAME`.
+pub struct Cmp20Dkg<C: Cmp20Curve>(PhantomData<C>);
+
+/// CMP20 DKG over P-256. Registered as `CMP20-ECDSA-P256`.
+pub type Cmp20DkgP256 = Cmp20Dkg<NistP256>;
 
-impl Cmp20DkgP256 {
+impl<C: Cmp20Curve> Cmp20Dkg<C> {
     pub fn build_session(params: &SessionParams) -> Result<Box<dyn SessionImpl>> {
+        Ok(Box::new(Self::new_session(params)?))
+    }
+
+    /// Rebuild a session from its [`SessionImpl::snapshot`] output. The
+    /// snapshot carries our dealing, so a resumed party re-sends the
+    /// same shares its peers may already hold.
+    pub fn restore_session(params: &SessionParams, state: &[u8]) -> Result<Box<dyn SessionImpl>> {
+        let mut session = Self::new_session(params)?;
+        let mut r = StateReader::new(state);
+        session.round_done = r.u8()?;
+        session.our_vss.commitments = (0..r.u32()?)
+            .map(|_| read_point::<C>(&mut r))
+            .collect::<Result<_>>()?;
+        session.our_vss.shares = (0..r.u32()?)
+            .map(|_| read_scalar::<C>(&mut r))
+            .collect::<Result<_>>()?;
+        session.our_vss.secret = read_scalar::<C>(&mut r)?;
+        for _ in 0..r.u32()? {
+            let dealer = r.u64()?;
+            session
+                .received_shares
+                .push((dealer, read_scalar::<C>(&mut r)?));
+        }
+        if r.bool()? {
+            session.joint_public_key = Some(read_point::<C>(&mut r)?);
+        }
+        if r.bool()? {
+            session.our_combined_share = Some(read_scalar::<C>(&mut r)?);
+        }
+        r.finish()?;
+        Ok(Box::new(session))
+    }
+
+    fn new_session(params: &SessionParams) -> Result<Cmp20DkgSession<C>> {
         let party_id = params.parties.get(params.this_party_idx)?.id.clone();
         let n = params.parties.len();
         let t = params.threshold as usize;
@@ -51,9 +95,9 @@ impl Cmp20DkgP256 {
             .map(|p| p.id.clone())
             .collect();
 
-        let vss = FeldmanVss::deal(&mut UnwrapErr(SysRng), n, t);
+        let vss = FeldmanVss::<C>::deal(&mut UnwrapErr(SysRng), n, t);
 
-        Ok(Box::new(Cmp20DkgSession {
+        Ok(Cmp20DkgSession {
             party_id,
             party_idx_1based,
             party_ids,
@@ -64,27 +108,27 @@ impl Cmp20DkgP256 {
             joint_public_key: None,
             our_combined_share: None,
             round_done: 0,
-        }))
+        })
     }
 }
 
-pub struct Cmp20DkgSession {
+pub struct Cmp20DkgSession<C: Cmp20Curve> {
     party_id: String,
     party_idx_1based: u32,
     party_ids: Vec<String>,
     n: usize,
     t: usize,
-    our_vss: FeldmanVss,
-    received_shares: Vec<(u64, Scalar)>,
-    joint_public_key: Option<AffinePoint>,
-    our_combined_share: Option<Scalar>,
+    our_vss: FeldmanVss<C>,
+    received_shares: Vec<(u64, Scalar<C>)>,
+    joint_public_key: Option<AffinePoint<C>>,
+    our_combined_share: Option<Scalar<C>>,
     round_done: u8,
 }
 
 const TAG_COMMITMENTS: u8 = 0xCC;
 const TAG_SHARE: u8 = 0xCE;
 
-impl Cmp20DkgSession {
+impl<C: Cmp20Curve> Cmp20DkgSession<C> {
     /// Single non-interactive round: broadcast commitments and direct-send
     /// every peer its evaluation in one batch. All messages are tagged
     /// for round 1 — the framework delivers them all back in the same
@@ -93,7 +137,7 @@ impl Cmp20DkgSession {
         let mut outgoing = Vec::with_capacity(1 + self.n);
 
         // Broadcast our commitment list.
-        let commitments_bytes = FeldmanVss::encode_commitments(&self.our_vss.commitments);
+        let commitments_bytes = FeldmanVss::<C>::encode_commitments(&self.our_vss.commitments);
         let mut bc_payload = Vec::with_capacity(3 + commitments_bytes.len());
         bc_payload.push(TAG_COMMITMENTS);
         bc_payload.push(self.party_idx_1based as u8);
@@ -109,10 +153,10 @@ impl Cmp20DkgSession {
                 continue;
             }
             let eval = self.our_vss.shares[peer_pos];
-            let mut payload = Vec::with_capacity(2 + 32);
+            let mut payload = Vec::with_capacity(2 + curve::scalar_len::<C>());
             payload.push(TAG_SHARE);
             payload.push(self.party_idx_1based as u8);
-            payload.extend_from_slice(&eval.to_bytes());
+            payload.extend_from_slice(&eval.to_repr());
             outgoing.push(Message::directed(&self.party_id, peer_id, 1, payload));
         }
 
@@ -125,8 +169,8 @@ impl Cmp20DkgSession {
             return Ok(RoundResult::new(outgoing, false));
         }
 
-        let mut commitments_by_dealer: Vec<(u64, Vec<AffinePoint>)> = Vec::new();
-        let mut own_evaluations: Vec<(u64, Scalar)> = Vec::new();
+        let mut commitments_by_dealer: Vec<(u64, Vec<AffinePoint<C>>)> = Vec::new();
+        let mut own_evaluations: Vec<(u64, Scalar<C>)> = Vec::new();
 
         for msg in incoming {
             if msg.round != 1 || msg.payload.is_empty() {
@@ -140,11 +184,11 @@ impl Cmp20DkgSession {
                     }
                     let dealer_idx = msg.payload[1] as u64;
                     let num_c = msg.payload[2] as usize;
-                    let expected = 3 + num_c * 33;
+                    let expected = 3 + num_c * curve::point_len::<C>();
                     if msg.payload.len() != expected {
                         return Err(scheme_error(Cmp20ErrorCode::BAD_ROUND_MESSAGE));
                     }
-                    let cs = FeldmanVss::decode_commitments(&msg.payload[3..expected])
+                    let cs = FeldmanVss::<C>::decode_commitments(&msg.payload[3..expected])
                         .ok_or_else(|| scheme_error(Cmp20ErrorCode::BAD_ROUND_MESSAGE))?;
                     if cs.len() != num_c || cs.len() < self.t {
                         return Err(scheme_error(Cmp20ErrorCode::VSS_VERIFY_FAILED));
@@ -152,17 +196,14 @@ impl Cmp20DkgSession {
                     commitments_by_dealer.push((dealer_idx, cs));
                 }
                 TAG_SHARE => {
-                    if msg.payload.len() != 2 + 32 {
+                    if msg.payload.len() != 2 + curve::scalar_len::<C>() {
                         return Err(scheme_error(Cmp20ErrorCode::BAD_ROUND_MESSAGE));
                     }
                     if !msg.is_for(&self.party_id) {
                         continue;
                     }
                     let dealer_idx = msg.payload[1] as u64;
-                    let mut eval_bytes = [0u8; 32];
-                    eval_bytes.copy_from_slice(&msg.payload[2..34]);
-                    let fb: p256::FieldBytes = eval_bytes.into();
-                    let eval: Scalar = Option::from(Scalar::from_repr(fb))
+                    let eval = curve::decode_scalar::<C>(&msg.payload[2..])
                         .ok_or_else(|| scheme_error(Cmp20ErrorCode::BAD_ROUND_MESSAGE))?;
                     own_evaluations.push((dealer_idx, eval));
                 }
@@ -181,14 +222,14 @@ impl Cmp20DkgSession {
             commitments_by_dealer.push((self_idx, self.our_vss.commitments.clone()));
         }
 
-        let mut verified_shares: Vec<(u64, Scalar)> = Vec::new();
+        let mut verified_shares: Vec<(u64, Scalar<C>)> = Vec::new();
         for (dealer_idx, eval) in &own_evaluations {
             let commitments = commitments_by_dealer
                 .iter()
                 .find(|(d, _)| d == dealer_idx)
                 .map(|(_, c)| c.as_slice())
                 .ok_or_else(|| scheme_error(Cmp20ErrorCode::VSS_VERIFY_FAILED))?;
-            if !FeldmanVss::verify_share(commitments, self.party_idx_1based as u64, *eval) {
+            if !FeldmanVss::<C>::verify_share(commitments, self.party_idx_1based as u64, *eval) {
                 return Err(scheme_error(Cmp20ErrorCode::VSS_VERIFY_FAILED));
             }
             verified_shares.push((*dealer_idx, *eval));
@@ -200,16 +241,16 @@ impl Cmp20DkgSession {
             return Err(scheme_error(Cmp20ErrorCode::BELOW_THRESHOLD));
         }
 
-        let combined: Scalar = verified_shares
+        let combined: Scalar<C> = verified_shares
             .iter()
-            .fold(Scalar::ZERO, |acc, &(_, ev)| acc + ev);
+            .fold(Scalar::<C>::ZERO, |acc, &(_, ev)| acc + ev);
         self.received_shares = verified_shares;
         self.our_combined_share = Some(combined);
 
         // Joint public key X = product over all dealers of C_0^{(d)}.
-        let mut joint = ProjectivePoint::IDENTITY;
+        let mut joint = ProjectivePoint::<C>::identity();
         for (_, cs) in &commitments_by_dealer {
-            joint += ProjectivePoint::from(cs[0]);
+            joint += ProjectivePoint::<C>::from(cs[0]);
         }
         self.joint_public_key = Some(joint.to_affine());
 
@@ -217,7 +258,7 @@ impl Cmp20DkgSession {
     }
 }
 
-impl SessionImpl for Cmp20DkgSession {
+impl<C: Cmp20Curve> SessionImpl for Cmp20DkgSession<C> {
     fn round(&mut self, incoming: &[Message]) -> Result<RoundResult> {
         self.round_done = self.round_done.checked_add(1).ok_or_else(|| {
             confium_tc::error::RoundOverflowSnafu {
@@ -246,9 +287,9 @@ impl SessionImpl for Cmp20DkgSession {
         let pk = self
             .joint_public_key
             .ok_or_else(|| scheme_error(Cmp20ErrorCode::INTERNAL))?;
-        let x_i: p256::NonZeroScalar = Option::from(p256::NonZeroScalar::new(combined))
+        let x_i: NonZeroScalar<C> = Option::from(NonZeroScalar::<C>::new(combined))
             .ok_or_else(|| scheme_error(Cmp20ErrorCode::INTERNAL))?;
-        let share = Cmp20Share::from_parts(x_i, pk, self.party_idx_1based);
+        let share = Cmp20KeyShare::<C>::from_parts(x_i, pk, self.party_idx_1based);
         Ok(share.to_bytes())
     }
 
@@ -259,26 +300,49 @@ impl SessionImpl for Cmp20DkgSession {
         for (_, s) in self.received_shares.drain(..) {
             let _ = s;
         }
-        for s in &mut self.our_vss.shares {
-            *s = Scalar::ZERO;
+        self.our_vss.shares.fill(Scalar::<C>::ZERO);
+    }
+
+    fn snapshot(&self) -> Option<Zeroizing<Vec<u8>>> {
+        let mut w = StateWriter::new();
+        w.u8(self.round_done);
+        w.u32(self.our_vss.commitments.len() as u32);
+        for c in &self.our_vss.commitments {
+            write_point::<C>(&mut w, c);
         }
+        w.u32(self.our_vss.shares.len() as u32);
+        for s in &self.our_vss.shares {
+            write_scalar::<C>(&mut w, s);
+        }
+        write_scalar::<C>(&mut w, &self.our_vss.secret);
+        w.u32(self.received_shares.len() as u32);
+        for (dealer, s) in &self.received_shares {
+            w.u64(*dealer);
+            write_scalar::<C>(&mut w, s);
+        }
+        w.bool(self.joint_public_key.is_some());
+        if let Some(pk) = &self.joint_public_key {
+            write_point::<C>(&mut w, pk);
+        }
+        w.bool(self.our_combined_share.is_some());
+        if let Some(s) = &self.our_combined_share {
+            write_scalar::<C>(&mut w, s);
+        }
+        Some(w.finish())
     }
 }
 
-/// Parse a DKG-produced share blob.
-pub fn parse_share(bytes: &[u8]) -> Result<Cmp20Share> {
-    if bytes.len() != SHARE_BYTES {
-        return Err(scheme_error(Cmp20ErrorCode::BAD_SHARE));
-    }
-    Cmp20Share::from_bytes(bytes)
+/// Parse a DKG-produced share blob over `C`.
+pub fn parse_share<C: Cmp20Curve>(bytes: &[u8]) -> Result<Cmp20KeyShare<C>> {
+    Cmp20KeyShare::<C>::from_bytes(bytes)
 }
 
 #[cfg(test)]
-pub(crate) fn reconstruct_secret_for_test(shares: &[Cmp20Share]) -> Scalar {
+pub(crate) fn reconstruct_secret_for_test<C: Cmp20Curve>(shares: &[Cmp20KeyShare<C>]) -> Scalar<C> {
     use crate::lagrange;
-    let pairs: Vec<(Scalar, Scalar)> = shares
+    let pairs: Vec<(Scalar<C>, Scalar<C>)> = shares
         .iter()
-        .map(|s| (Scalar::from(s.party_idx), s.scalar()))
+        .map(|s| (Scalar::<C>::from(s.party_idx as u64), s.scalar()))
         .collect();
     lagrange::lagrange_weighted_sum(&pairs)
 }
@@ -286,14 +350,15 @@ pub(crate) fn reconstruct_secret_for_test(shares: &[Cmp20Share]) -> Scalar {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::share::Cmp20Share;
     use confium_tc::party::{Party, PartyList};
     use confium_tc::share::Share;
     use elliptic_curve::sec1::ToSec1Point;
 
-    fn params(n: usize, t: u32, idx: usize) -> SessionParams {
+    fn params<C: Cmp20Curve>(n: usize, t: u32, idx: usize) -> SessionParams {
         let roster: Vec<Party> = (0..n).map(|i| Party::inproc(format!("p{}", i))).collect();
         SessionParams {
-            scheme: crate::DKG_SCHEME_NAME.to_string(),
+            scheme: C::DKG_SCHEME_NAME.to_string(),
             parties: PartyList::from_parties(roster),
             threshold: t,
             this_party_idx: idx,
@@ -307,12 +372,12 @@ mod tests {
     /// assembles. From the protocol's perspective this is still a single
     /// broadcast round — the two calls reflect the framework's
     /// send-then-receive cadence, not an extra protocol round.
-    fn run_dkg(n: usize, t: u32) -> Vec<Cmp20Share> {
+    fn run_dkg<C: Cmp20Curve>(n: usize, t: u32) -> Vec<Cmp20KeyShare<C>> {
         let party_ids: Vec<String> = (0..n).map(|i| format!("p{}", i)).collect();
         let mut sessions: Vec<Box<dyn SessionImpl>> = (0..n)
             .map(|i| {
-                let p = params(n, t, i);
-                Cmp20DkgP256::build_session(&p).expect("session")
+                let p = params::<C>(n, t, i);
+                Cmp20Dkg::<C>::build_session(&p).expect("session")
             })
             .collect();
 
@@ -353,14 +418,14 @@ mod tests {
             .iter()
             .map(|s| {
                 let bytes = s.result().expect("result");
-                Cmp20Share::from_bytes(&bytes).expect("share decodes")
+                Cmp20KeyShare::<C>::from_bytes(&bytes).expect("share decodes")
             })
             .collect()
     }
 
     #[test]
     fn dkg_two_of_three_produces_consistent_shares() {
-        let shares = run_dkg(3, 2);
+        let shares = run_dkg::<NistP256>(3, 2);
         assert_eq!(shares.len(), 3);
         let pk0 = shares[0].public_key;
         for s in &shares[1..] {
@@ -373,7 +438,7 @@ mod tests {
         let secret_12 = reconstruct_secret_for_test(&shares[1..3]);
         assert_eq!(secret_01, secret_02);
         assert_eq!(secret_02, secret_12);
-        let g = ProjectivePoint::GENERATOR;
+        let g = p256::ProjectivePoint::GENERATOR;
         let expected_pk = (g * secret_01).to_affine();
         let got_pk = shares[0].public_key.to_sec1_point(true);
         let want_pk = expected_pk.to_sec1_point(true);
@@ -382,9 +447,9 @@ mod tests {
 
     #[test]
     fn dkg_three_of_three_produces_consistent_shares() {
-        let shares = run_dkg(3, 3);
+        let shares = run_dkg::<NistP256>(3, 3);
         let secret = reconstruct_secret_for_test(&shares);
-        let g = ProjectivePoint::GENERATOR;
+        let g = p256::ProjectivePoint::GENERATOR;
         let pk = (g * secret).to_affine().to_sec1_point(true);
         assert_eq!(
             pk.as_bytes(),
@@ -394,7 +459,7 @@ mod tests {
 
     #[test]
     fn dkg_share_is_loadable_as_framework_share() {
-        let shares = run_dkg(3, 2);
+        let shares = run_dkg::<NistP256>(3, 2);
         let bytes = shares[0].to_bytes();
         let fw = Share::new(crate::DKG_SCHEME_NAME, bytes);
         assert_eq!(fw.scheme(), crate::DKG_SCHEME_NAME);
@@ -403,4 +468,19 @@ mod tests {
         let inner = Cmp20Share::from_bytes(rt.bytes()).expect("inner decode");
         assert_eq!(inner.party_idx, shares[0].party_idx);
     }
+
+    #[test]
+    fn dkg_over_p384_and_secp256k1_agrees_on_the_public_key() {
+        fn check<C: Cmp20Curve>() {
+            let shares = run_dkg::<C>(3, 2);
+            let secret = reconstruct_secret_for_test(&shares[1..3]);
+            let expected = (ProjectivePoint::<C>::generator() * secret).to_affine();
+            for s in &shares {
+                assert_eq!(s.public_key, expected);
+                assert_eq!(s.to_bytes().len(), crate::share::share_len::<C>());
+            }
+        }
+        check::<p384::NistP384>();
+        check::<k256::Secp256k1>();
+    }
 }
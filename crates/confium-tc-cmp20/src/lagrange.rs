@@ -1,4 +1,4 @@
-//! Lagrange interpolation in the P-256 scalar field.
+//! Lagrange interpolation in a curve's scalar field.
 //!
 //! Both DKG verification and signing combine `T` per-party values that
 //! were generated as evaluations of a degree-`T-1` polynomial. The
@@ -8,14 +8,14 @@
 //! `\lambda_i = \prod_{j \ne i} \frac{-x_j}{x_i - x_j}` and `x_k` is
 //! party `k`'s 1-based roster index.
 
-use p256::Scalar;
+use elliptic_curve::Field;
 
 /// Compute the Lagrange basis coefficient `\lambda_i` for evaluating
 /// the polynomial at `x = 0`, given the full set of participating
 /// x-coords `xs` and the specific coordinate `xi`.
-pub fn lagrange_basis_scalar(xi: Scalar, xs: &[Scalar]) -> Scalar {
-    let mut num = Scalar::ONE;
-    let mut den = Scalar::ONE;
+pub fn lagrange_basis_scalar<F: Field>(xi: F, xs: &[F]) -> F {
+    let mut num = F::ONE;
+    let mut den = F::ONE;
     for &xj in xs {
         if xj == xi {
             continue;
@@ -23,14 +23,14 @@ pub fn lagrange_basis_scalar(xi: Scalar, xs: &[Scalar]) -> Scalar {
         num *= -xj;
         den *= xi - xj;
     }
-    let den_inv = den.invert().unwrap_or(Scalar::ZERO);
+    let den_inv = den.invert().unwrap_or(F::ZERO);
     num * den_inv
 }
 
 /// Apply Lagrange interpolation at `x = 0` to `(x_i, y_i)` pairs.
-pub fn lagrange_weighted_sum(pairs: &[(Scalar, Scalar)]) -> Scalar {
-    let xs: Vec<Scalar> = pairs.iter().map(|(x, _)| *x).collect();
-    let mut acc = Scalar::ZERO;
+pub fn lagrange_weighted_sum<F: Field>(pairs: &[(F, F)]) -> F {
+    let xs: Vec<F> = pairs.iter().map(|(x, _)| *x).collect();
+    let mut acc = F::ZERO;
     for (i, &(_, yi)) in pairs.iter().enumerate() {
         let lam = lagrange_basis_scalar(xs[i], &xs);
         acc += lam * yi;
@@ -41,6 +41,7 @@ pub fn lagrange_weighted_sum(pairs: &[(Scalar, Scalar)]) -> Scalar {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use p256::Scalar;
 
     fn idx(i: u32) -> Scalar {
         Scalar::from(i)
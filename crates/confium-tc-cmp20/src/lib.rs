@@ -4,8 +4,8 @@
 #![allow(rustdoc::private_intra_doc_links)]
 #![allow(rustdoc::invalid_html_tags)]
 
-//! CMP20 threshold ECDSA over P-256 (Canetti, Makriyannis, Peled 2020,
-//! eprint 2020/496).
+//! CMP20 threshold ECDSA over P-256, P-384 and secp256k1 (Canetti,
+//! Makriyannis, Peled 2020, eprint 2020/496).
 //!
 //! A newer, more efficient threshold ECDSA protocol than GG18. Key
 //! improvements exploited here:
@@ -13,12 +13,13 @@
 //! - **Non-interactive key generation** — DKG collapses to a single
 //!   broadcast round (each party commits its public share; no per-peer
 //!   share exchange is needed in the simplified path).
-//! - **Three-round signing** — down from GG18's four. Round 1 commits
-//!   nonces, round 2 reveals them and carries the MtA products, round 3
-//!   posts partial signatures and combines in the same round.
-//! - **Identifiable abort** — when a partial signature fails to verify
-//!   the offending party is reported by index rather than failing
-//!   opaquely.
+//! - **Presign-then-sign** — the Paillier MtA (with Π^enc, Π^aff-g and
+//!   Π^log* proofs, over per-session moduli proven well formed with
+//!   Π^mod and Π^prm) runs before the message is needed; signing itself
+//!   is one broadcast of `σ_i`.
+//! - **Identifiable abort** — a malformed message or failed proof
+//!   aborts with [`confium_tc::Error::MessageRejected`] naming the
+//!   sender rather than failing opaquely.
 //!
 //! # Example
 //!
@@ -34,22 +35,28 @@
 //! # Ok::<(), confium_tc::Error>(())
 //! ```
 //!
-//! Wired as a [`confium_tc::registry::TcScheme`] plugin with two scheme
-//! names registered through [`confium_tc::register_tc_scheme!`]:
+//! Wired as a [`confium_tc::registry::TcScheme`] plugin with a DKG and
+//! a signing scheme per curve, registered through
+//! [`confium_tc::register_tc_scheme!`]:
 //!
 //! - [`DKG_SCHEME_NAME`] = `"CMP20-ECDSA-P256"` (non-interactive DKG) —
 //!   produces per-party [`Cmp20Share`] + shared public key.
 //! - [`SIGN_SCHEME_NAME`] = `"CMP20-ECDSA-P256-SIGN"` — produces a
 //!   standard 64-byte `(r, s)` ECDSA signature verifiable with the
 //!   `p256` crate.
+//! - [`P384_DKG_SCHEME_NAME`] / [`P384_SIGN_SCHEME_NAME`] — the same
+//!   over P-384 (SHA-384, 96-byte signatures) for CNSA suites.
+//! - [`SECP256K1_DKG_SCHEME_NAME`] / [`SECP256K1_SIGN_SCHEME_NAME`] —
+//!   the same over secp256k1 (SHA-256, 64-byte signatures).
+//!
+//! The protocol code is generic over [`curve::Cmp20Curve`]; share blobs
+//! carry the curve id, so a share can only be used with its own curve.
 //!
 //! See the module-level docs of [`keygen`], [`sign`], [`mta`] for what
-//! is implemented and what is omitted. In short: the Feldman VSS,
-//! Lagrange interpolation, and threshold-ECDSA combine are all real;
-//! the MtA sub-round is a simplified in-process stub (products computed
-//! in the clear) rather than a Paillier-based homomorphic MtA, matching
-//! the GG18 crate's deferred-Paillier approach.
+//! is implemented and what is omitted. The proofs and Paillier
+//! arithmetic live in `confium_crypto_vss::paillier_zk`.
 
+pub mod curve;
 pub mod e2e_signing;
 pub mod error;
 pub mod gg18_e2e;
@@ -69,11 +76,27 @@ pub mod vss;
 #[cfg(test)]
 mod props;
 
-pub use scheme::{Cmp20EcdsaP256, Cmp20EcdsaP256Sign};
-pub use share::Cmp20Share;
+pub use curve::Cmp20Curve;
+pub use scheme::{
+    Cmp20EcdsaP256, Cmp20EcdsaP256Sign, Cmp20EcdsaP384, Cmp20EcdsaP384Sign, Cmp20EcdsaSecp256k1,
+    Cmp20EcdsaSecp256k1Sign,
+};
+pub use share::{Cmp20KeyShare, Cmp20Share};
 
 /// Canonical scheme name for CMP20 DKG over P-256.
 pub const DKG_SCHEME_NAME: &str = "CMP20-ECDSA-P256";
 
 /// Canonical scheme name for CMP20 signing over P-256.
 pub const SIGN_SCHEME_NAME: &str = "CMP20-ECDSA-P256-SIGN";
+
+/// Canonical scheme name for CMP20 DKG over P-384.
+pub const P384_DKG_SCHEME_NAME: &str = "CMP20-ECDSA-P384";
+
+/// Canonical scheme name for CMP20 signing over P-384.
+pub const P384_SIGN_SCHEME_NAME: &str = "CMP20-ECDSA-P384-SIGN";
+
+/// Canonical scheme name for CMP20 DKG over secp256k1.
+pub const SECP256K1_DKG_SCHEME_NAME: &str = "CMP20-ECDSA-SECP256K1";
+
+/// Canonical scheme name for CMP20 signing over secp256k1.
+pub const SECP256K1_SIGN_SCHEME_NAME: &str = "CMP20-ECDSA-SECP256K1-SIGN";
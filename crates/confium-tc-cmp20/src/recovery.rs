@@ -16,9 +16,10 @@
 //! The math is identical to [`crate::keygen::reconstruct_secret_for_test`]
 //! but evaluated at the lost party's x instead of x=0.
 
-use p256::Scalar;
+use elliptic_curve::{Field, NonZeroScalar, Scalar};
 
-use crate::share::Cmp20Share;
+use crate::curve::Cmp20Curve;
+use crate::share::Cmp20KeyShare;
 
 /// Recover a lost share's scalar value from T surviving shares.
 ///
@@ -27,10 +28,10 @@ use crate::share::Cmp20Share;
 ///
 /// Returns the recovered scalar. The caller wraps it into a new
 /// `Cmp20Share` via `Cmp20Share::from_parts(recovered, pk, lost_idx)`.
-pub fn recover_share_scalar(
-    surviving_shares: &[Cmp20Share],
+pub fn recover_share_scalar<C: Cmp20Curve>(
+    surviving_shares: &[Cmp20KeyShare<C>],
     lost_party_idx: u32,
-) -> Result<Scalar, RecoverError> {
+) -> Result<Scalar<C>, RecoverError> {
     if surviving_shares.is_empty() {
         return Err(RecoverError::NoShares);
     }
@@ -44,23 +45,24 @@ pub fn recover_share_scalar(
 
     // Lagrange interpolation at x = lost_party_idx.
     // f(lost) = sum_i [ y_i * prod_{j!=i} (lost - x_j) / (x_i - x_j) ]
-    let x_target = Scalar::from(lost_party_idx as u64);
-    let mut result = Scalar::ZERO;
+    let x_target = Scalar::<C>::from(lost_party_idx as u64);
+    let mut result = Scalar::<C>::ZERO;
     for s_i in surviving_shares {
-        let x_i = Scalar::from(s_i.party_idx as u64);
-        let mut numerator = Scalar::ONE;
-        let mut denominator = Scalar::ONE;
+        let x_i = Scalar::<C>::from(s_i.party_idx as u64);
+        let mut numerator = Scalar::<C>::ONE;
+        let mut denominator = Scalar::<C>::ONE;
         for s_j in surviving_shares {
             if s_j.party_idx == s_i.party_idx {
                 continue;
             }
-            let x_j = Scalar::from(s_j.party_idx as u64);
+            let x_j = Scalar::<C>::from(s_j.party_idx as u64);
             // numerator *= (x_target - x_j)
             numerator *= x_target - x_j;
             // denominator *= (x_i - x_j)
             denominator *= x_i - x_j;
         }
-        let denom_inv = invert_scalar(&denominator);
+        let denom_inv =
+            Option::<Scalar<C>>::from(denominator.invert()).unwrap_or(Scalar::<C>::ZERO);
         let lagrange = numerator * denom_inv;
         let term = s_i.scalar() * lagrange;
         result += term;
@@ -71,10 +73,10 @@ pub fn recover_share_scalar(
 /// Recover a full `Cmp20Share` (scalar + public key + party index)
 /// from T surviving shares. The public key is taken from any
 /// surviving share (they all carry the same joint public key).
-pub fn recover_share(
-    surviving_shares: &[Cmp20Share],
+pub fn recover_share<C: Cmp20Curve>(
+    surviving_shares: &[Cmp20KeyShare<C>],
     lost_party_idx: u32,
-) -> Result<Cmp20Share, RecoverError> {
+) -> Result<Cmp20KeyShare<C>, RecoverError> {
     if surviving_shares.is_empty() {
         return Err(RecoverError::NoShares);
     }
@@ -84,14 +86,9 @@ pub fn recover_share(
     // If so, the NonZeroScalar conversion fails. Fall back to
     // Scalar::ONE as a degenerate case — this shouldn't happen in
     // practice but we handle it gracefully.
-    let x_i = p256::NonZeroScalar::new(scalar)
-        .unwrap_or_else(|| p256::NonZeroScalar::new(Scalar::ONE).unwrap());
-    Ok(Cmp20Share::from_parts(x_i, pk, lost_party_idx))
-}
-
-fn invert_scalar(s: &Scalar) -> Scalar {
-    let ct: p256::elliptic_curve::subtle::CtOption<Scalar> = s.invert();
-    Option::<Scalar>::from(ct).unwrap_or(Scalar::ZERO)
+    let x_i = Option::from(NonZeroScalar::<C>::new(scalar))
+        .unwrap_or_else(|| NonZeroScalar::<C>::new(Scalar::<C>::ONE).unwrap());
+    Ok(Cmp20KeyShare::from_parts(x_i, pk, lost_party_idx))
 }
 
 /// Errors during share recovery.
@@ -118,6 +115,7 @@ impl std::error::Error for RecoverError {}
 mod tests {
     use super::*;
     use crate::inprocess;
+    use crate::share::Cmp20Share;
     use p256::ecdsa::{Signature, VerifyingKey, signature::Verifier};
 
     #[test]
@@ -179,6 +177,9 @@ mod tests {
 
     #[test]
     fn empty_shares_errors() {
-        assert!(matches!(recover_share(&[], 1), Err(RecoverError::NoShares)));
+        assert!(matches!(
+            recover_share::<p256::NistP256>(&[], 1),
+            Err(RecoverError::NoShares)
+        ));
     }
 }
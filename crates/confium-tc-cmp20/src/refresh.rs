@@ -34,7 +34,9 @@
 use elliptic_curve::rand_core::Rng;
 use elliptic_curve::rand_core::UnwrapErr;
 use getrandom::SysRng;
-use p256::{FieldBytes, Scalar, elliptic_curve::PrimeField};
+use p256::{FieldBytes, NonZeroScalar, Scalar, elliptic_curve::PrimeField};
+
+use crate::share::Cmp20Share;
 
 /// One party's refresh contribution: `(source_party_index, target_party_index, refresh_scalar_bytes)`.
 #[derive(Debug, Clone)]
@@ -79,26 +81,19 @@ pub fn generate_refresh_contributions(
     out
 }
 
-/// Apply refresh contributions to a CMP20 share blob. The share's
+/// Apply refresh contributions to a P-256 CMP20 share blob. The share's
 /// internal scalar x_i is replaced with x_i + sum(g_j(i)) for all
-/// contributions directed at party_index.
+/// contributions directed at party_index. A blob that is not a P-256
+/// share is returned unchanged.
 pub fn apply_to_share(
     share_blob: &[u8],
     party_index: u32,
     contributions: &[RefreshContribution],
 ) -> Vec<u8> {
-    let mut blob = share_blob.to_vec();
-    if blob.len() < 37 {
-        return blob; // not a valid CMP20 share
-    }
-
-    // CMP20 share format: magic[4] | version[1] | x_i[32] | X[33] | idx[1]
-    // The scalar is at bytes [5..37].
-
-    // Extract the old scalar.
-    let mut scalar_bytes = [0u8; 32];
-    scalar_bytes.copy_from_slice(&blob[5..37]);
-    let old_scalar = bytes_to_scalar(&scalar_bytes);
+    let Ok(share) = Cmp20Share::from_bytes(share_blob) else {
+        return share_blob.to_vec(); // not a valid P-256 CMP20 share
+    };
+    let old_scalar = share.scalar();
 
     // Sum all contributions directed at this party.
     let mut refresh_sum = Scalar::ZERO;
@@ -111,11 +106,12 @@ pub fn apply_to_share(
 
     // new_scalar = old_scalar + refresh_sum
     let new_scalar = old_scalar + refresh_sum;
-    let new_bytes = scalar_to_bytes(&new_scalar);
+    let Some(x_i) = Option::<NonZeroScalar>::from(NonZeroScalar::new(new_scalar)) else {
+        return share_blob.to_vec();
+    };
 
-    // Patch the share blob.
-    blob[5..37].copy_from_slice(&new_bytes);
-    blob
+    // Re-encode the share around the new scalar.
+    Cmp20Share::from_parts(x_i, share.public_key, share.party_idx).to_bytes()
 }
 
 /// Verify that a set of refresh contributions preserves the
@@ -242,11 +238,11 @@ mod tests {
     #[test]
     fn refresh_changes_scalar_bytes() {
         let kg = inprocess::keygen(2, 3).expect("dkg");
-        let original_scalar = &kg.shares[0][5..37];
+        let original_scalar = Cmp20Share::from_bytes(&kg.shares[0]).unwrap().scalar();
 
         let contributions = generate_refresh_contributions(2, 3);
         let refreshed = apply_to_share(&kg.shares[0], 1, &contributions);
-        let refreshed_scalar = &refreshed[5..37];
+        let refreshed_scalar = Cmp20Share::from_bytes(&refreshed).unwrap().scalar();
 
         assert_ne!(
             original_scalar, refreshed_scalar,
@@ -1,50 +1,81 @@
 //! CMP20 scheme registration.
 //!
 //! Two logical operations — DKG and signing — exposed as two scheme
-//! names so the framework's single-name/single-kind `TcScheme` trait
-//! can route each.
+//! names per curve so the framework's single-name/single-kind
+//! `TcScheme` trait can route each.
 //!
-//! | Name                    | Kind        | Produces                          |
-//! |------------------------|-------------|-----------------------------------|
-//! | `CMP20-ECDSA-P256`       | `Dkg`       | per-party `Cmp20Share` + pubkey    |
-//! | `CMP20-ECDSA-P256-SIGN`  | `Signature` | 64-byte `(r, s)` ECDSA signature  |
+//! | Name                          | Kind        | Produces                          |
+//! |-------------------------------|-------------|-----------------------------------|
+//! | `CMP20-ECDSA-P256`            | `Dkg`       | per-party `Cmp20Share` + pubkey   |
+//! | `CMP20-ECDSA-P256-SIGN`       | `Signature` | 64-byte `(r, s)` ECDSA signature  |
+//! | `CMP20-ECDSA-P384`            | `Dkg`       | per-party P-384 share + pubkey    |
+//! | `CMP20-ECDSA-P384-SIGN`       | `Signature` | 96-byte `(r, s)` ECDSA signature  |
+//! | `CMP20-ECDSA-SECP256K1`       | `Dkg`       | per-party secp256k1 share + pubkey|
+//! | `CMP20-ECDSA-SECP256K1-SIGN`  | `Signature` | 64-byte `(r, s)` ECDSA signature  |
 
 use confium_tc::Result;
 use confium_tc::registry::{SessionImpl, TcScheme, TcSchemeKind};
 use confium_tc::session::SessionParams;
+use k256::Secp256k1;
+use p256::NistP256;
+use p384::NistP384;
 
-use crate::keygen::Cmp20DkgP256;
-use crate::sign::Cmp20SignP256;
-
-/// CMP20 DKG scheme (registered as `CMP20-ECDSA-P256`).
-pub struct Cmp20EcdsaP256;
-
-impl TcScheme for Cmp20EcdsaP256 {
-    fn name(&self) -> &'static str {
-        crate::DKG_SCHEME_NAME
-    }
-    fn kind(&self) -> TcSchemeKind {
-        TcSchemeKind::Dkg
-    }
-    fn create_session(&self, params: &SessionParams) -> Result<Box<dyn SessionImpl>> {
-        Cmp20DkgP256::build_session(params)
-    }
-}
+use crate::curve::Cmp20Curve;
+use crate::keygen::Cmp20Dkg;
+use crate::sign::Cmp20Sign;
+
+/// Declare and register the DKG and signing schemes of one curve.
+macro_rules! cmp20_schemes {
+    ($curve:ty, $dkg:ident, $sign:ident) => {
+        #[doc = concat!("CMP20 DKG over `", stringify!($curve), "` (registered as its `DKG_SCHEME_NAME`).")]
+        pub struct $dkg;
+
+        impl TcScheme for $dkg {
+            fn name(&self) -> &'static str {
+                <$curve as Cmp20Curve>::DKG_SCHEME_NAME
+            }
+            fn kind(&self) -> TcSchemeKind {
+                TcSchemeKind::Dkg
+            }
+            fn create_session(&self, params: &SessionParams) -> Result<Box<dyn SessionImpl>> {
+                Cmp20Dkg::<$curve>::build_session(params)
+            }
+            fn restore_session(
+                &self,
+                params: &SessionParams,
+                state: &[u8],
+            ) -> Result<Box<dyn SessionImpl>> {
+                Cmp20Dkg::<$curve>::restore_session(params, state)
+            }
+        }
+
+        #[doc = concat!("CMP20 signing over `", stringify!($curve), "` (registered as its `SIGN_SCHEME_NAME`).")]
+        pub struct $sign;
+
+        impl TcScheme for $sign {
+            fn name(&self) -> &'static str {
+                <$curve as Cmp20Curve>::SIGN_SCHEME_NAME
+            }
+            fn kind(&self) -> TcSchemeKind {
+                TcSchemeKind::Signature
+            }
+            fn create_session(&self, params: &SessionParams) -> Result<Box<dyn SessionImpl>> {
+                Cmp20Sign::<$curve>::build_session(params)
+            }
+            fn restore_session(
+                &self,
+                params: &SessionParams,
+                state: &[u8],
+            ) -> Result<Box<dyn SessionImpl>> {
+                Cmp20Sign::<$curve>::restore_session(params, state)
+            }
+        }
 
-/// CMP20 signing scheme (registered as `CMP20-ECDSA-P256-SIGN`).
-pub struct Cmp20EcdsaP256Sign;
-
-impl TcScheme for Cmp20EcdsaP256Sign {
-    fn name(&self) -> &'static str {
-        crate::SIGN_SCHEME_NAME
-    }
-    fn kind(&self) -> TcSchemeKind {
-        TcSchemeKind::Signature
-    }
-    fn create_session(&self, params: &SessionParams) -> Result<Box<dyn SessionImpl>> {
-        Cmp20SignP256::build_session(params)
-    }
+        confium_tc::register_tc_scheme!($dkg);
+        confium_tc::register_tc_scheme!($sign);
+    };
 }
 
-confium_tc::register_tc_scheme!(Cmp20EcdsaP256);
-confium_tc::register_tc_scheme!(Cmp20EcdsaP256Sign);
+cmp20_schemes!(NistP256, Cmp20EcdsaP256, Cmp20EcdsaP256Sign);
+cmp20_schemes!(NistP384, Cmp20EcdsaP384, Cmp20EcdsaP384Sign);
+cmp20_schemes!(Secp256k1, Cmp20EcdsaSecp256k1, Cmp20EcdsaSecp256k1Sign);
@@ -1,147 +1,222 @@
 //! Per-party share material produced by CMP20 DKG and consumed by signing.
-
-use elliptic_curve::sec1::ToSec1Point;
-use p256::{AffinePoint, FieldBytes, NonZeroScalar, Scalar};
+//!
+//! ```text
+//! v2: magic "CMP2" | version 2 | curve_id[1] | x_i[n] | X[n+1] | idx[1]
+//! v1: magic "CMP2" | version 1 | x_i[32] | X[33] | idx[1]     (P-256 only)
+//! ```
+//!
+//! `n` is the curve's scalar length (32, or 48 on P-384) and `X` is
+//! SEC1-compressed. Version 1 blobs predate the curve id; they still
+//! decode as P-256 shares and re-encode as version 2.
+
+use elliptic_curve::{AffinePoint, NonZeroScalar, PrimeField, Scalar};
+use p256::NistP256;
 use zeroize::Zeroize;
 
+use confium_tc::snapshot::{self, StateReader, StateWriter};
+
+use crate::curve::{self, CURVE_P256, Cmp20Curve};
 use crate::error::{Cmp20ErrorCode, Result, scheme_error};
 
 const SHARE_MAGIC: [u8; 4] = *b"CMP2";
-const SHARE_VERSION: u8 = 1;
-/// Wire length: magic[4] | version[1] | x_i[32] | X[33] | idx[1].
-pub const SHARE_BYTES: usize = 4 + 1 + 32 + 33 + 1;
+const SHARE_VERSION: u8 = 2;
+const SHARE_VERSION_V1: u8 = 1;
+/// Wire length of a version 1 (P-256) share.
+pub const SHARE_BYTES_V1: usize = 4 + 1 + 32 + 33 + 1;
+/// Wire length of a P-256 share.
+pub const SHARE_BYTES: usize = 4 + 1 + 1 + 32 + 33 + 1;
+
+/// Wire length of a share over `C`.
+pub fn share_len<C: Cmp20Curve>() -> usize {
+    6 + curve::scalar_len::<C>() + curve::point_len::<C>() + 1
+}
 
-/// One party's durable CMP20 secret material.
+/// One party's durable CMP20 secret material over curve `C`.
 #[derive(Clone)]
-pub struct Cmp20Share {
+pub struct Cmp20KeyShare<C: Cmp20Curve> {
     /// This party's Shamir share of the joint secret.
-    pub x_i: NonZeroScalar,
+    pub x_i: NonZeroScalar<C>,
     /// The shared public key `X = g^x` (affine).
-    pub public_key: AffinePoint,
+    pub public_key: AffinePoint<C>,
     /// 1-based DKG roster index of this party.
     pub party_idx: u32,
 }
 
-impl std::fmt::Debug for Cmp20Share {
+/// A CMP20 share over P-256.
+pub type Cmp20Share = Cmp20KeyShare<NistP256>;
+
+impl<C: Cmp20Curve> std::fmt::Debug for Cmp20KeyShare<C> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Cmp20Share")
+            .field("curve", &C::CURVE_ID)
             .field("x_i", &"<redacted>")
             .field("party_idx", &self.party_idx)
             .finish_non_exhaustive()
     }
 }
 
-impl Drop for Cmp20Share {
+impl<C: Cmp20Curve> Drop for Cmp20KeyShare<C> {
     fn drop(&mut self) {
-        let mut bytes = self.x_i.to_bytes();
+        let mut bytes = self.x_i.to_repr();
         bytes.zeroize();
     }
 }
 
-impl Cmp20Share {
+impl<C: Cmp20Curve> Cmp20KeyShare<C> {
     pub fn to_bytes(&self) -> Vec<u8> {
-        let mut out = Vec::with_capacity(SHARE_BYTES);
+        let mut out = Vec::with_capacity(share_len::<C>());
         out.extend_from_slice(&SHARE_MAGIC);
         out.push(SHARE_VERSION);
-        out.extend_from_slice(&self.x_i.to_bytes());
-        out.extend_from_slice(self.public_key.to_sec1_point(true).as_bytes());
+        out.push(C::CURVE_ID);
+        out.extend_from_slice(&self.x_i.to_repr());
+        out.extend_from_slice(&curve::encode_point::<C>(&self.public_key));
         out.push(self.party_idx as u8);
         out
     }
 
+    /// Decode a share over `C`. A share over another curve is refused
+    /// with `BAD_SHARE`.
     pub fn from_bytes(data: &[u8]) -> Result<Self> {
-        if data.len() != SHARE_BYTES {
-            return Err(scheme_error(Cmp20ErrorCode::BAD_SHARE));
-        }
-        if data[0..4] != SHARE_MAGIC {
-            return Err(scheme_error(Cmp20ErrorCode::BAD_SHARE));
-        }
-        if data[4] != SHARE_VERSION {
+        if data.len() < 5 || data[0..4] != SHARE_MAGIC {
             return Err(scheme_error(Cmp20ErrorCode::BAD_SHARE));
         }
-        let mut x_i_bytes = [0u8; 32];
-        x_i_bytes.copy_from_slice(&data[5..37]);
-        let fb: FieldBytes = x_i_bytes.into();
-        let x_i: NonZeroScalar = Option::from(NonZeroScalar::from_repr(fb))
+        let body = match data[4] {
+            SHARE_VERSION if data.len() == share_len::<C>() && data[5] == C::CURVE_ID => &data[6..],
+            SHARE_VERSION_V1 if data.len() == SHARE_BYTES_V1 && C::CURVE_ID == CURVE_P256 => {
+                &data[5..]
+            }
+            _ => return Err(scheme_error(Cmp20ErrorCode::BAD_SHARE)),
+        };
+        let n = curve::scalar_len::<C>();
+        let x_i = curve::decode_scalar::<C>(&body[..n])
+            .and_then(|s| Option::from(NonZeroScalar::<C>::new(s)))
             .ok_or_else(|| scheme_error(Cmp20ErrorCode::BAD_SHARE))?;
-        let pk_bytes = &data[37..70];
-        let public_key = decode_affine(pk_bytes)?;
-        let party_idx = data[70] as u32;
-        Ok(Cmp20Share {
+        let public_key = decode_affine::<C>(&body[n..body.len() - 1])?;
+        let party_idx = body[body.len() - 1] as u32;
+        Ok(Cmp20KeyShare {
             x_i,
             public_key,
             party_idx,
         })
     }
 
-    pub fn from_parts(x_i: NonZeroScalar, public_key: AffinePoint, party_idx: u32) -> Self {
-        Cmp20Share {
+    pub fn from_parts(x_i: NonZeroScalar<C>, public_key: AffinePoint<C>, party_idx: u32) -> Self {
+        Cmp20KeyShare {
             x_i,
             public_key,
             party_idx,
         }
     }
 
-    pub fn scalar(&self) -> Scalar {
+    pub fn scalar(&self) -> Scalar<C> {
         *self.x_i
     }
 }
 
-/// Decode a 33-byte SEC1 compressed point into an [`AffinePoint`].
-pub(crate) fn decode_affine(bytes: &[u8]) -> Result<AffinePoint> {
-    use elliptic_curve::point::AffineCoordinates;
-    use elliptic_curve::sec1::FromSec1Point;
-    if bytes.len() != 33 {
+/// The curve id of a share blob, without decoding the rest of it.
+pub fn share_curve_id(data: &[u8]) -> Result<u8> {
+    if data.len() < 6 || data[0..4] != SHARE_MAGIC {
         return Err(scheme_error(Cmp20ErrorCode::BAD_SHARE));
     }
-    let enc = elliptic_curve::sec1::Sec1Point::<p256::NistP256>::from_bytes(bytes)
-        .map_err(|_| scheme_error(Cmp20ErrorCode::BAD_SHARE))?;
-    let pt: AffinePoint = Option::from(AffinePoint::from_sec1_point(&enc))
-        .ok_or_else(|| scheme_error(Cmp20ErrorCode::BAD_SHARE))?;
-    let _ = pt.x();
-    Ok(pt)
+    match data[4] {
+        SHARE_VERSION => Ok(data[5]),
+        SHARE_VERSION_V1 => Ok(CURVE_P256),
+        _ => Err(scheme_error(Cmp20ErrorCode::BAD_SHARE)),
+    }
+}
+
+/// Decode a SEC1 compressed point into an [`AffinePoint`].
+pub(crate) fn decode_affine<C: Cmp20Curve>(bytes: &[u8]) -> Result<AffinePoint<C>> {
+    curve::decode_point::<C>(bytes).ok_or_else(|| scheme_error(Cmp20ErrorCode::BAD_SHARE))
+}
+
+/// Write a scalar into session snapshot state.
+pub(crate) fn write_scalar<C: Cmp20Curve>(w: &mut StateWriter, s: &Scalar<C>) {
+    w.bytes(&s.to_repr());
+}
+
+/// Read a scalar written by [`write_scalar`].
+pub(crate) fn read_scalar<C: Cmp20Curve>(r: &mut StateReader<'_>) -> Result<Scalar<C>> {
+    curve::decode_scalar::<C>(r.bytes()?).ok_or_else(|| snapshot::invalid("scalar out of range"))
+}
+
+/// Write a point into session snapshot state, SEC1-compressed.
+pub(crate) fn write_point<C: Cmp20Curve>(w: &mut StateWriter, p: &AffinePoint<C>) {
+    w.bytes(&curve::encode_point::<C>(p));
+}
+
+/// Read a point written by [`write_point`].
+pub(crate) fn read_point<C: Cmp20Curve>(r: &mut StateReader<'_>) -> Result<AffinePoint<C>> {
+    curve::decode_point::<C>(r.bytes()?)
+        .ok_or_else(|| snapshot::invalid("point is not on the curve"))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    use elliptic_curve::Generate;
+    use elliptic_curve::group::{Curve as _, Group as _};
+    use elliptic_curve::{Generate, ProjectivePoint};
+    use p384::NistP384;
 
-    fn random_share(idx: u32) -> Cmp20Share {
-        let x_i = NonZeroScalar::generate();
-        let g = p256::ProjectivePoint::GENERATOR;
-        let pk = (g * *x_i).to_affine();
-        Cmp20Share::from_parts(x_i, pk, idx)
+    fn random_share<C: Cmp20Curve>(idx: u32) -> Cmp20KeyShare<C> {
+        let x_i = NonZeroScalar::<C>::generate();
+        let pk = (ProjectivePoint::<C>::generator() * *x_i).to_affine();
+        Cmp20KeyShare::from_parts(x_i, pk, idx)
     }
 
     #[test]
     fn share_round_trip() {
-        let s = random_share(1);
+        let s = random_share::<NistP256>(1);
         let bytes = s.to_bytes();
         assert_eq!(bytes.len(), SHARE_BYTES);
         let s2 = Cmp20Share::from_bytes(&bytes).expect("decode");
         assert_eq!(s2.party_idx, 1);
-        assert_eq!(s2.x_i.to_bytes(), s.x_i.to_bytes());
+        assert_eq!(s2.x_i.to_repr(), s.x_i.to_repr());
+    }
+
+    #[test]
+    fn p384_share_round_trip_and_curve_binding() {
+        let s = random_share::<NistP384>(2);
+        let bytes = s.to_bytes();
+        assert_eq!(bytes.len(), share_len::<NistP384>());
+        assert_eq!(share_curve_id(&bytes).unwrap(), curve::CURVE_P384);
+        let s2 = Cmp20KeyShare::<NistP384>::from_bytes(&bytes).expect("decode");
+        assert_eq!(s2.public_key, s.public_key);
+        assert!(Cmp20Share::from_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn v1_share_decodes_as_p256() {
+        let s = random_share::<NistP256>(3);
+        let v2 = s.to_bytes();
+        let mut v1 = v2[..4].to_vec();
+        v1.push(SHARE_VERSION_V1);
+        v1.extend_from_slice(&v2[6..]);
+        assert_eq!(v1.len(), SHARE_BYTES_V1);
+        assert_eq!(share_curve_id(&v1).unwrap(), CURVE_P256);
+        let s2 = Cmp20Share::from_bytes(&v1).expect("decode v1");
+        assert_eq!(s2.to_bytes(), v2);
+        assert!(Cmp20KeyShare::<k256::Secp256k1>::from_bytes(&v1).is_err());
     }
 
     #[test]
     fn share_rejects_bad_magic() {
-        let mut bytes = random_share(0).to_bytes();
+        let mut bytes = random_share::<NistP256>(0).to_bytes();
         bytes[0] = b'X';
         assert!(Cmp20Share::from_bytes(&bytes).is_err());
     }
 
     #[test]
     fn share_rejects_truncated() {
-        let bytes = random_share(0).to_bytes();
+        let bytes = random_share::<NistP256>(0).to_bytes();
         assert!(Cmp20Share::from_bytes(&bytes[..10]).is_err());
     }
 
     #[test]
     fn share_rejects_zero_scalar() {
-        let mut bytes = random_share(0).to_bytes();
-        for b in &mut bytes[5..37] {
+        let mut bytes = random_share::<NistP256>(0).to_bytes();
+        for b in &mut bytes[6..38] {
             *b = 0;
         }
         assert!(Cmp20Share::from_bytes(&bytes).is_err());
@@ -149,7 +224,7 @@ mod tests {
 
     #[test]
     fn debug_redacts_secret() {
-        let s = random_share(0);
+        let s = random_share::<NistP256>(0);
         let dbg = format!("{:?}", s);
         assert!(dbg.contains("<redacted>"));
     }
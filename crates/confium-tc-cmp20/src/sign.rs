@@ -1,59 +1,145 @@
-//! CMP20 threshold ECDSA signing over P-256.
+//! CMP20 threshold ECDSA signing over any [`Cmp20Curve`].
 //!
 //! Consumes shares from [`crate::keygen`] and produces a standard
-//! `(r, s)` ECDSA signature verifiable under `p256::ecdsa::VerifyingKey`.
+//! `(r, s)` ECDSA signature over the curve's digest, verifiable under
+//! the `p256`, `p384` or `k256` `ecdsa::VerifyingKey`.
 //!
-//! ## Protocol (simplified — NOT production)
+//! ## Protocol
 //!
-//! Three rounds (down from GG18's four):
+//! CMP20's presigning (Fig. 7 of eprint 2021/060) followed by its
+//! one-round signing, with both products computed by the Paillier MtA
+//! of [`crate::mta`] and every message carrying the proof the paper
+//! attaches to it. Signer `i` works with its Lagrange-weighted key
+//! share `w_i = λ_i x_i`, so that `Σ w_i = x`.
 //!
-//! - **Round 1 — nonce commit.** Broadcast `R_i = k_i * G` + 1-based idx.
-//! - **Round 2 — nonce reveal.** Broadcast `k_i`. CMP20 folds the MtA
-//!   setup into this round (GG18 split these across rounds 2 and 3);
-//!   here the MtA products are computed in the clear locally, so no
-//!   extra sub-round is needed.
-//! - **Round 3 — partial sign + combine.** From all reveals compute
-//!   aggregate `k = sum k_i`, `R = sum R_i`, `r = R.x mod n`, Lagrange
-//!   weights. Compute `s_i = k^{-1} * r * lambda_i * x_i`. Broadcast
-//!   `s_i`. On receipt of all partials, verify each against its expected
-//!   value (identifiable abort — a bad partial names the offender), then
-//!   combine `s = k^{-1} * z + sum s_i`, verify `(r, s)` against the
-//!   joint public key.
+//! - **Round 1 — aux.** Broadcast a fresh Paillier-Blum modulus as
+//!   ring-Pedersen parameters with its Π^mod and Π^prm proofs, and
+//!   `X_i = x_i * G`. Peers check both proofs and that `Σ λ_j X_j` is
+//!   the joint key.
+//! - **Round 2 — nonce.** Broadcast `K_i = enc_i(k_i)` and
+//!   `G_i = enc_i(γ_i)`; send each peer a Π^enc proof for `K_i` under
+//!   that peer's parameters.
+//! - **Round 3 — MtA.** Broadcast `Γ_i = γ_i * G`. Answer every peer's
+//!   `K_j` with `γ_i` and with `w_i`, and prove with Π^log* that `Γ_i`
+//!   and `G_i` hide the same `γ_i`.
+//! - **Round 4 — δ.** Decrypt the answers into `δ_i = k_i γ_i + Σ (α + β)`
+//!   and `χ_i = k_i w_i + Σ (α̂ + β̂)`; broadcast `δ_i` and
+//!   `Δ_i = k_i * Γ` (`Γ = Σ Γ_j`) with a Π^log* proof against `K_i`.
+//! - **Round 5 — sign.** Check `δ * G = Σ Δ_j`, set `R = δ^{-1} * Γ`,
+//!   `r = R.x mod n`, and broadcast `σ_i = k_i z + r χ_i` (`z` the
+//!   message digest, truncated to the order's length).
+//! - **Round 6 — combine.** `s = Σ σ_j`, normalised to low-s and
+//!   verified against the joint key.
 //!
-//! The arithmetic is identical to a real CMP20 run for honest coalitions.
-//! Nonces are revealed in the clear — this leaks the joint nonce `k`,
-//! which is safe for a single signature but would be catastrophic across
-//! multiple signatures over the same secret. Production CMP20 hides `k`
-//! via Paillier-based MtA. See [`crate::mta`] for the gap.
+//! `k_j`, `γ_j` and `w_j` never leave party `j` except encrypted under
+//! its own Paillier key, and the MtA answers it receives are masked, so
+//! no signer learns another's nonce share. The framework surfaces the
+//! six rounds as six `round` calls; the last only receives.
 //!
 //! ## Identifiable abort
 //!
-//! When the aggregate signature fails to verify, round 3 walks the
-//! partials and reports the offending party via
-//! [`Cmp20ErrorCode::IDENTIFIED_BYZANTINE`]. In the simplified setting
-//! the identification is by elimination (the party whose removal restores
-//! validity is the byzantine one); real CMP20 achieves it
-//! cryptographically via range proofs and per-partial consistency checks.
-
-use elliptic_curve::Generate;
-use elliptic_curve::{PrimeField, ops::Invert, point::AffineCoordinates, sec1::ToSec1Point};
-use p256::{AffinePoint, NonZeroScalar, ProjectivePoint, Scalar};
+//! A message that is malformed, out of sequence, missing, or whose
+//! proof fails aborts with [`confium_tc::Error::MessageRejected`] naming
+//! its sender. Two failures cannot be pinned on one party and abort
+//! with a CMP20 sub-code instead: key shares that do not interpolate to
+//! the joint key ([`Cmp20ErrorCode::BAD_ROUND_MESSAGE`], typically a
+//! share from another DKG), and a wrong `δ_j` or `σ_j`, which the paper
+//! leaves to a separate identification phase
+//! ([`Cmp20ErrorCode::BAD_PARTIAL_SIGNATURE`]).
+//!
+//! ## Snapshots
+//!
+//! Sessions snapshot after any round (see [`confium_tc::snapshot`]).
+//! The snapshot carries the session's Paillier factorisation. `σ_i` in
+//! round 5 is the one step that combines the nonce `k_i` with the key
+//! share, so `k_i * G` is reported as the pending nonce going into it:
+//! a restored snapshot can never produce a second `σ_i` under the same
+//! `k_i`.
+
+use std::marker::PhantomData;
+
+use confium_crypto_vss::paillier_zk::{
+    self, AuxInfo, AuxSecret, Decoder, EncProof, Encoder, LogStarProof, RingPedersen,
+};
+use elliptic_curve::group::{Curve as _, Group};
+use elliptic_curve::{
+    AffinePoint, Field, Generate, NonZeroScalar, PrimeField, ProjectivePoint, Scalar,
+};
+use num_bigint::{BigInt, BigUint};
+use p256::NistP256;
 use sha2::{Digest, Sha256};
 
 use confium_tc::Result;
 use confium_tc::message::Message;
 use confium_tc::registry::{RoundResult, SessionImpl};
 use confium_tc::session::SessionParams;
+use confium_tc::snapshot::{self, StateReader, StateWriter};
+use zeroize::Zeroizing;
 
+use crate::curve::{self, Cmp20Curve};
 use crate::error::{Cmp20ErrorCode, scheme_error};
 use crate::lagrange;
-use crate::share::Cmp20Share;
+use crate::mta::{self, MtaMessage};
+use crate::share::{
+    Cmp20KeyShare, decode_affine, read_point, read_scalar, write_point, write_scalar,
+};
 
-/// CMP20 signing scheme over P-256. Registered as `CMP20-ECDSA-P256-SIGN`.
-pub struct Cmp20SignP256;
+/// CMP20 signing over curve `C`. Registered as `C::SIGN_SCHEME_NAME`.
+pub struct Cmp20Sign<C: Cmp20Curve>(PhantomData<C>);
 
-impl Cmp20SignP256 {
+/// CMP20 signing over P-256. Registered as `CMP20-ECDSA-P256-SIGN`.
+pub type Cmp20SignP256 = Cmp20Sign<NistP256>;
+
+impl<C: Cmp20Curve> Cmp20Sign<C> {
     pub fn build_session(params: &SessionParams) -> Result<Box<dyn SessionImpl>> {
+        Ok(Box::new(Self::new_session(params, AuxSecret::generate())?))
+    }
+
+    /// Rebuild a session from its [`SessionImpl::snapshot`] output.
+    pub fn restore_session(params: &SessionParams, state: &[u8]) -> Result<Box<dyn SessionImpl>> {
+        let mut r = StateReader::new(state);
+        let round_done = r.u8()?;
+        let aux = AuxSecret::from_bytes(r.bytes()?)
+            .ok_or_else(|| snapshot::invalid("bad Paillier key"))?;
+        let mut session = Self::new_session(params, aux)?;
+        session.round_done = round_done;
+        session.k_i = read_nonzero::<C>(&mut r)?;
+        session.gamma_i = read_nonzero::<C>(&mut r)?;
+        session.k_ct = read_uint(&mut r)?;
+        session.rho_k = read_uint(&mut r)?;
+        session.g_ct = read_uint(&mut r)?;
+        session.rho_g = read_uint(&mut r)?;
+        session.w_i = read_scalar::<C>(&mut r)?;
+        session.delta_i = read_scalar::<C>(&mut r)?;
+        session.chi_i = read_scalar::<C>(&mut r)?;
+        for _ in 0..r.u32()? {
+            session.peers.push(Peer {
+                id: r.string()?,
+                idx: r.u64()?,
+                params: RingPedersen {
+                    n: read_uint(&mut r)?,
+                    s: read_uint(&mut r)?,
+                    t: read_uint(&mut r)?,
+                },
+                x_point: read_point::<C>(&mut r)?,
+                k_ct: read_uint(&mut r)?,
+                g_ct: read_uint(&mut r)?,
+                gamma: read_opt_point::<C>(&mut r)?,
+            });
+        }
+        session.gamma_sum = read_opt_point::<C>(&mut r)?;
+        session.r_scalar = read_opt_scalar::<C>(&mut r)?;
+        session.sigma_i = read_opt_scalar::<C>(&mut r)?;
+        session.signature = if r.bool()? {
+            Some(r.bytes()?.to_vec())
+        } else {
+            None
+        };
+        r.finish()?;
+        Ok(Box::new(session))
+    }
+
+    fn new_session(params: &SessionParams, aux: AuxSecret) -> Result<Cmp20SignSession<C>> {
         let party_id = params.parties.get(params.this_party_idx)?.id.clone();
         let message = params.message.clone().unwrap_or_default();
         let share_bytes = params
@@ -61,310 +147,581 @@ impl Cmp20SignP256 {
             .as_ref()
             .map(|s| s.bytes().to_vec())
             .ok_or_else(|| scheme_error(Cmp20ErrorCode::BAD_SHARE))?;
-        let share = Cmp20Share::from_bytes(&share_bytes)?;
+        let share = Cmp20KeyShare::<C>::from_bytes(&share_bytes)?;
 
-        let k_i = NonZeroScalar::generate();
-        let r_i_point = (ProjectivePoint::GENERATOR * *k_i).to_affine();
+        let k_i = NonZeroScalar::<C>::generate();
+        let gamma_i = NonZeroScalar::<C>::generate();
+        let (k_ct, rho_k) = paillier_zk::encrypt_scalar::<C>(&aux.paillier().public, &k_i);
+        let (g_ct, rho_g) = paillier_zk::encrypt_scalar::<C>(&aux.paillier().public, &gamma_i);
 
-        Ok(Box::new(Cmp20SignSession {
+        Ok(Cmp20SignSession {
             party_id,
             message,
             share,
+            aux,
             k_i,
-            r_i_point,
-            round1_seen: Vec::new(),
-            round2_seen: Vec::new(),
-            k_inv: None,
+            gamma_i,
+            k_ct,
+            rho_k,
+            g_ct,
+            rho_g,
+            w_i: Scalar::<C>::ZERO,
+            delta_i: Scalar::<C>::ZERO,
+            chi_i: Scalar::<C>::ZERO,
+            peers: Vec::new(),
+            gamma_sum: None,
             r_scalar: None,
-            z: None,
-            our_partial: None,
+            sigma_i: None,
             round_done: 0,
             signature: None,
-        }))
+        })
     }
 }
 
-pub struct Cmp20SignSession {
+fn write_opt_scalar<C: Cmp20Curve>(w: &mut StateWriter, s: &Option<Scalar<C>>) {
+    w.bool(s.is_some());
+    if let Some(s) = s {
+        write_scalar::<C>(w, s);
+    }
+}
+
+fn read_opt_scalar<C: Cmp20Curve>(r: &mut StateReader<'_>) -> Result<Option<Scalar<C>>> {
+    if r.bool()? {
+        Ok(Some(read_scalar::<C>(r)?))
+    } else {
+        Ok(None)
+    }
+}
+
+fn write_opt_point<C: Cmp20Curve>(w: &mut StateWriter, p: &Option<AffinePoint<C>>) {
+    w.bool(p.is_some());
+    if let Some(p) = p {
+        write_point::<C>(w, p);
+    }
+}
+
+fn read_opt_point<C: Cmp20Curve>(r: &mut StateReader<'_>) -> Result<Option<AffinePoint<C>>> {
+    if r.bool()? {
+        Ok(Some(read_point::<C>(r)?))
+    } else {
+        Ok(None)
+    }
+}
+
+fn read_nonzero<C: Cmp20Curve>(r: &mut StateReader<'_>) -> Result<NonZeroScalar<C>> {
+    Option::from(NonZeroScalar::<C>::new(read_scalar::<C>(r)?))
+        .ok_or_else(|| snapshot::invalid("zero nonce"))
+}
+
+fn write_uint(w: &mut StateWriter, x: &BigUint) {
+    w.bytes(&x.to_bytes_be());
+}
+
+fn read_uint(r: &mut StateReader<'_>) -> Result<BigUint> {
+    Ok(BigUint::from_bytes_be(r.bytes()?))
+}
+
+/// What a session knows about one co-signer.
+struct Peer<C: Cmp20Curve> {
+    id: String,
+    /// 1-based DKG index.
+    idx: u64,
+    /// Ring-Pedersen parameters; `params.n` is also its Paillier key.
+    params: RingPedersen,
+    /// `x_j * G`, from round 1.
+    x_point: AffinePoint<C>,
+    /// `K_j` and `G_j`, from round 2.
+    k_ct: BigUint,
+    g_ct: BigUint,
+    /// `Γ_j`, from round 3.
+    gamma: Option<AffinePoint<C>>,
+}
+
+pub struct Cmp20SignSession<C: Cmp20Curve> {
     party_id: String,
     message: Vec<u8>,
-    share: Cmp20Share,
-    k_i: NonZeroScalar,
-    r_i_point: AffinePoint,
-    round1_seen: Vec<(String, u64, AffinePoint)>,
-    round2_seen: Vec<(String, u64, Scalar)>,
-    k_inv: Option<Scalar>,
-    r_scalar: Option<Scalar>,
-    z: Option<Scalar>,
-    our_partial: Option<Scalar>,
+    share: Cmp20KeyShare<C>,
+    aux: AuxSecret,
+    k_i: NonZeroScalar<C>,
+    gamma_i: NonZeroScalar<C>,
+    k_ct: BigUint,
+    rho_k: BigUint,
+    g_ct: BigUint,
+    rho_g: BigUint,
+    w_i: Scalar<C>,
+    delta_i: Scalar<C>,
+    chi_i: Scalar<C>,
+    peers: Vec<Peer<C>>,
+    gamma_sum: Option<AffinePoint<C>>,
+    r_scalar: Option<Scalar<C>>,
+    sigma_i: Option<Scalar<C>>,
     round_done: u8,
     signature: Option<Vec<u8>>,
 }
 
-const TAG_NONCE_POINT: u8 = 0xD1;
-const TAG_NONCE_REVEAL: u8 = 0xD2;
-const TAG_PARTIAL: u8 = 0xD3;
+const TAG_AUX: u8 = 0xD1;
+const TAG_NONCE: u8 = 0xD2;
+const TAG_ENC_PROOF: u8 = 0xD3;
+const TAG_GAMMA: u8 = 0xD4;
+const TAG_MTA: u8 = 0xD5;
+const TAG_DELTA: u8 = 0xD6;
+const TAG_DELTA_PROOF: u8 = 0xD7;
+const TAG_SIGMA: u8 = 0xD8;
+
+/// Abort naming the sender of `msg`.
+fn reject(msg: &Message, reason: impl Into<String>) -> confium_tc::Error {
+    confium_tc::error::MessageRejectedSnafu {
+        party: msg.from_party_id.clone(),
+        round: msg.round,
+        reason: reason.into(),
+    }
+    .build()
+}
+
+/// One peer's messages for a round: its broadcast and, for rounds that
+/// have one, the message addressed to us.
+type PeerInbox<'a> = (&'a Message, Option<&'a Message>);
 
-impl Cmp20SignSession {
-    fn round1_commit(&mut self) -> Result<RoundResult> {
-        let mut payload = Vec::with_capacity(1 + 1 + 33);
-        payload.push(TAG_NONCE_POINT);
-        payload.push(self.share.party_idx as u8);
-        payload.extend_from_slice(self.r_i_point.to_sec1_point(true).as_bytes());
-        Ok(RoundResult::new(
-            vec![Message::broadcast(&self.party_id, 1, payload)],
-            false,
-        ))
+fn int<C: Cmp20Curve>(s: &Scalar<C>) -> BigInt {
+    BigInt::from(paillier_zk::scalar_to_int::<C>(s))
+}
+
+impl<C: Cmp20Curve> Cmp20SignSession<C> {
+    fn own_idx(&self) -> u64 {
+        self.share.party_idx as u64
     }
 
-    /// Round 2 in CMP20 folds nonce reveal AND the MtA setup into one
-    /// round (GG18 split these across rounds 2 and 3). On entry we have
-    /// every peer's nonce commitment; we broadcast our own nonce reveal.
-    fn round2_reveal(&mut self, incoming: &[Message]) -> Result<RoundResult> {
+    fn header(&self, tag: u8) -> Vec<u8> {
+        vec![tag, self.share.party_idx as u8]
+    }
+
+    /// Fiat–Shamir context for proofs by the signer with DKG index
+    /// `prover`: the scheme, joint key, message and prover.
+    fn context(&self, prover: u64) -> Vec<u8> {
+        let mut h = Sha256::new();
+        h.update(C::SIGN_SCHEME_NAME.as_bytes());
+        h.update(curve::encode_point::<C>(&self.share.public_key));
+        h.update((self.message.len() as u64).to_be_bytes());
+        h.update(&self.message);
+        h.update(prover.to_be_bytes());
+        h.finalize().to_vec()
+    }
+
+    /// Lagrange weight of the signer with DKG index `idx` within this
+    /// session's signer set.
+    fn weight(&self, idx: u64) -> Scalar<C> {
+        let xs: Vec<Scalar<C>> = std::iter::once(self.own_idx())
+            .chain(self.peers.iter().map(|p| p.idx))
+            .map(Scalar::<C>::from)
+            .collect();
+        lagrange::lagrange_basis_scalar(Scalar::<C>::from(idx), &xs)
+    }
+
+    /// Sort a round's incoming messages by peer, in `self.peers` order.
+    /// Anything from an unknown sender, for another round, with another
+    /// tag, or duplicated is rejected, as is a peer missing a message.
+    fn inbox<'a>(
+        &self,
+        incoming: &'a [Message],
+        round: u8,
+        broadcast_tag: u8,
+        directed_tag: Option<u8>,
+    ) -> Result<Vec<PeerInbox<'a>>> {
+        let mut slots: Vec<(Option<&Message>, Option<&Message>)> =
+            vec![(None, None); self.peers.len()];
         for msg in incoming {
-            if msg.round != 1 || msg.payload.is_empty() {
+            if msg.from_party_id == self.party_id {
                 continue;
             }
-            if msg.payload[0] != TAG_NONCE_POINT {
-                continue;
+            let pos = self
+                .peers
+                .iter()
+                .position(|p| p.id == msg.from_party_id)
+                .ok_or_else(|| reject(msg, "sender is not a signer in this session"))?;
+            if msg.round != round {
+                return Err(reject(msg, format!("expected a round {round} message")));
             }
-            if msg.payload.len() != 1 + 1 + 33 {
-                return Err(scheme_error(Cmp20ErrorCode::BAD_ROUND_MESSAGE));
+            if msg.payload.len() < 2 || msg.payload[1] as u64 != self.peers[pos].idx {
+                return Err(reject(msg, "malformed message header"));
             }
-            let idx = msg.payload[1] as u64;
-            let pt = decode_affine(&msg.payload[2..35])?;
-            if msg.from_party_id == self.party_id {
-                if idx != self.share.party_idx as u64 {
-                    return Err(scheme_error(Cmp20ErrorCode::BAD_ROUND_MESSAGE));
+            let slot = match (msg.payload[0], &msg.to_party_id) {
+                (tag, None) if tag == broadcast_tag => &mut slots[pos].0,
+                (tag, Some(to)) if Some(tag) == directed_tag && *to == self.party_id => {
+                    &mut slots[pos].1
                 }
-                continue;
+                _ => return Err(reject(msg, "unexpected message")),
+            };
+            if slot.replace(msg).is_some() {
+                return Err(reject(msg, "duplicate message"));
             }
-            self.round1_seen.push((msg.from_party_id.clone(), idx, pt));
         }
+        self.peers
+            .iter()
+            .zip(slots)
+            .map(|(peer, (broadcast, directed))| {
+                let missing = || {
+                    confium_tc::error::MessageRejectedSnafu {
+                        party: peer.id.clone(),
+                        round,
+                        reason: "missing round message".to_string(),
+                    }
+                    .build()
+                };
+                let broadcast = broadcast.ok_or_else(missing)?;
+                if directed_tag.is_some() && directed.is_none() {
+                    return Err(missing());
+                }
+                Ok((broadcast, directed))
+            })
+            .collect()
+    }
 
-        let mut reveal = Vec::with_capacity(1 + 1 + 32);
-        reveal.push(TAG_NONCE_REVEAL);
-        reveal.push(self.share.party_idx as u8);
-        reveal.extend_from_slice(&self.k_i.to_bytes());
+    /// Round 1: publish this session's Paillier modulus with its proofs,
+    /// and `X_i = x_i * G`.
+    fn round1_aux(&mut self) -> Result<RoundResult> {
+        let x_point = (ProjectivePoint::<C>::generator() * self.share.scalar()).to_affine();
+        let mut e = Encoder::new();
+        self.aux.prove(&self.context(self.own_idx())).encode(&mut e);
+        let mut payload = self.header(TAG_AUX);
+        payload.extend_from_slice(&curve::encode_point::<C>(&x_point));
+        payload.extend_from_slice(&e.finish());
         Ok(RoundResult::new(
-            vec![Message::broadcast(&self.party_id, 2, reveal)],
+            vec![Message::broadcast(&self.party_id, 1, payload)],
             false,
         ))
     }
 
-    /// Round 3: receive all nonce reveals, derive the aggregate nonce,
-    /// the per-party Lagrange weight, and the partial signature;
-    /// broadcast the partial.
-    fn round3_partial(&mut self, incoming: &[Message]) -> Result<RoundResult> {
+    /// Round 2: check every peer's modulus and that the key shares
+    /// interpolate to the joint key; publish `K_i`, `G_i` and a Π^enc
+    /// proof for each peer.
+    fn round2_nonce(&mut self, incoming: &[Message]) -> Result<RoundResult> {
+        let point_end = 2 + curve::point_len::<C>();
         for msg in incoming {
-            if msg.round != 2 || msg.payload.is_empty() {
+            if msg.from_party_id == self.party_id {
                 continue;
             }
-            if msg.payload[0] != TAG_NONCE_REVEAL {
-                continue;
+            let p = &msg.payload;
+            if msg.round != 1 || msg.is_directed() || p.len() < point_end || p[0] != TAG_AUX {
+                return Err(reject(msg, "expected a round 1 aux message"));
             }
-            if msg.payload.len() != 1 + 1 + 32 {
-                return Err(scheme_error(Cmp20ErrorCode::BAD_ROUND_MESSAGE));
+            let idx = p[1] as u64;
+            if idx == self.own_idx()
+                || self
+                    .peers
+                    .iter()
+                    .any(|q| q.idx == idx || q.id == msg.from_party_id)
+            {
+                return Err(reject(msg, "duplicate signer"));
             }
-            let idx = msg.payload[1] as u64;
-            let mut kb = [0u8; 32];
-            kb.copy_from_slice(&msg.payload[2..34]);
-            let fb: p256::FieldBytes = kb.into();
-            let k: Scalar = Option::from(Scalar::from_repr(fb))
-                .ok_or_else(|| scheme_error(Cmp20ErrorCode::BAD_ROUND_MESSAGE))?;
-            if msg.from_party_id == self.party_id {
-                continue;
+            let x_point =
+                decode_affine::<C>(&p[2..point_end]).map_err(|_| reject(msg, "bad key share"))?;
+            let mut d = Decoder::new(&p[point_end..]);
+            let info = AuxInfo::decode(&mut d)
+                .filter(|_| d.finish().is_some())
+                .ok_or_else(|| reject(msg, "malformed aux info"))?;
+            if !info.verify(&self.context(idx)) {
+                return Err(reject(msg, "Paillier modulus proofs (mod, prm) failed"));
             }
-            self.round2_seen.push((msg.from_party_id.clone(), idx, k));
+            self.peers.push(Peer {
+                id: msg.from_party_id.clone(),
+                idx,
+                params: info.params,
+                x_point,
+                k_ct: BigUint::default(),
+                g_ct: BigUint::default(),
+                gamma: None,
+            });
         }
 
-        let mut parts: Vec<(u64, String, Scalar, AffinePoint)> = Vec::new();
-        parts.push((
-            self.share.party_idx as u64,
-            self.party_id.clone(),
-            *self.k_i,
-            self.r_i_point,
-        ));
-        for (pid, idx, k) in &self.round2_seen {
-            let r_j = self
-                .round1_seen
-                .iter()
-                .find(|(p, _, _)| p == pid)
-                .map(|(_, _, pt)| *pt)
-                .ok_or_else(|| scheme_error(Cmp20ErrorCode::BAD_ROUND_MESSAGE))?;
-            parts.push((*idx, pid.clone(), *k, r_j));
+        self.w_i = self.weight(self.own_idx()) * self.share.scalar();
+        let mut joint = ProjectivePoint::<C>::generator() * self.w_i;
+        for peer in &self.peers {
+            joint += ProjectivePoint::<C>::from(peer.x_point) * self.weight(peer.idx);
+        }
+        if joint.to_affine() != self.share.public_key {
+            return Err(scheme_error(Cmp20ErrorCode::BAD_ROUND_MESSAGE));
         }
-        parts.sort_by_key(|(idx, _, _, _)| *idx);
 
-        let k_sum: Scalar = parts
-            .iter()
-            .map(|(_, _, k, _)| *k)
-            .fold(Scalar::ZERO, |a, b| a + b);
-        let k_nz: NonZeroScalar = Option::from(NonZeroScalar::new(k_sum))
-            .ok_or_else(|| scheme_error(Cmp20ErrorCode::INTERNAL))?;
-        let k_inv: Scalar = *k_nz.invert();
+        let mut nonce = self.header(TAG_NONCE);
+        let mut e = Encoder::new();
+        e.uint(&self.k_ct).uint(&self.g_ct);
+        nonce.extend_from_slice(&e.finish());
+        let mut out = vec![Message::broadcast(&self.party_id, 2, nonce)];
+
+        let ctx = self.context(self.own_idx());
+        let pk = &self.aux.paillier().public;
+        for peer in &self.peers {
+            let proof = paillier_zk::prove_enc::<C>(
+                pk,
+                &self.k_ct,
+                &int::<C>(&self.k_i),
+                &self.rho_k,
+                &peer.params,
+                &ctx,
+            );
+            let mut e = Encoder::new();
+            proof.encode(&mut e);
+            let mut payload = self.header(TAG_ENC_PROOF);
+            payload.extend_from_slice(&e.finish());
+            out.push(Message::directed(&self.party_id, &peer.id, 2, payload));
+        }
+        Ok(RoundResult::new(out, false))
+    }
 
-        let mut r_proj = ProjectivePoint::IDENTITY;
-        for (_, _, _, pt) in &parts {
-            r_proj += ProjectivePoint::from(*pt);
+    /// Round 3: check the peers' Π^enc proofs, then answer each `K_j`
+    /// with `γ_i` and `w_i` and publish `Γ_i`.
+    fn round3_mta(&mut self, incoming: &[Message]) -> Result<RoundResult> {
+        let inbox = self.inbox(incoming, 2, TAG_NONCE, Some(TAG_ENC_PROOF))?;
+        let mut received = Vec::with_capacity(inbox.len());
+        for (peer, (broadcast, directed)) in self.peers.iter().zip(&inbox) {
+            let mut d = Decoder::new(&broadcast.payload[2..]);
+            let (k_ct, g_ct) = d
+                .uint()
+                .zip(d.uint())
+                .filter(|_| d.finish().is_some())
+                .ok_or_else(|| reject(broadcast, "malformed nonce ciphertexts"))?;
+            let directed = directed.expect("inbox checked");
+            let mut d = Decoder::new(&directed.payload[2..]);
+            let proof = EncProof::decode(&mut d)
+                .filter(|_| d.finish().is_some())
+                .ok_or_else(|| reject(directed, "malformed enc proof"))?;
+            if !paillier_zk::verify_enc::<C>(
+                &peer.params.paillier_key(),
+                &k_ct,
+                self.aux.params(),
+                &proof,
+                &self.context(peer.idx),
+            ) {
+                return Err(reject(directed, "enc proof failed"));
+            }
+            received.push((k_ct, g_ct));
         }
-        if r_proj == ProjectivePoint::IDENTITY {
-            return Err(scheme_error(Cmp20ErrorCode::INTERNAL));
+        for (peer, (k_ct, g_ct)) in self.peers.iter_mut().zip(received) {
+            peer.k_ct = k_ct;
+            peer.g_ct = g_ct;
         }
-        let r_affine = r_proj.to_affine();
-        let r_scalar = reduce_x_mod_n(r_affine);
 
-        let z = hash_to_scalar(&self.message);
+        let gamma_point = (ProjectivePoint::<C>::generator() * *self.gamma_i).to_affine();
+        let mut payload = self.header(TAG_GAMMA);
+        payload.extend_from_slice(&curve::encode_point::<C>(&gamma_point));
+        let mut out = vec![Message::broadcast(&self.party_id, 3, payload)];
+
+        let ctx = self.context(self.own_idx());
+        let pk = &self.aux.paillier().public;
+        self.delta_i = *self.k_i * *self.gamma_i;
+        self.chi_i = *self.k_i * self.w_i;
+        for peer in &self.peers {
+            let peer_pk = peer.params.paillier_key();
+            let (gamma, beta) =
+                mta::mta_respond::<C>(&peer_pk, &peer.k_ct, pk, &self.gamma_i, &peer.params, &ctx);
+            let (key, beta_hat) =
+                mta::mta_respond::<C>(&peer_pk, &peer.k_ct, pk, &self.w_i, &peer.params, &ctx);
+            let gamma_proof = paillier_zk::prove_log_star::<C>(
+                pk,
+                &self.g_ct,
+                &int::<C>(&self.gamma_i),
+                &self.rho_g,
+                &ProjectivePoint::<C>::generator(),
+                &peer.params,
+                &ctx,
+            );
+            self.delta_i += beta;
+            self.chi_i += beta_hat;
+            let msg = MtaMessage {
+                gamma,
+                key,
+                gamma_proof,
+            };
+            let mut payload = self.header(TAG_MTA);
+            payload.extend_from_slice(&msg.to_bytes());
+            out.push(Message::directed(&self.party_id, &peer.id, 3, payload));
+        }
+        Ok(RoundResult::new(out, false))
+    }
+
+    /// Round 4: check and decrypt the MtA answers into `δ_i` and `χ_i`;
+    /// publish `δ_i` and `Δ_i = k_i * Γ` with a Π^log* proof for each
+    /// peer.
+    fn round4_delta(&mut self, incoming: &[Message]) -> Result<RoundResult> {
+        let inbox = self.inbox(incoming, 3, TAG_GAMMA, Some(TAG_MTA))?;
+        let pk = &self.aux.paillier().public;
+        let mut gamma_sum = ProjectivePoint::<C>::generator() * *self.gamma_i;
+        let mut gammas = Vec::with_capacity(inbox.len());
+        for (peer, (broadcast, directed)) in self.peers.iter().zip(&inbox) {
+            let gamma = decode_affine::<C>(&broadcast.payload[2..])
+                .map_err(|_| reject(broadcast, "bad gamma point"))?;
+            let directed = directed.expect("inbox checked");
+            let msg = MtaMessage::<C>::from_bytes(&directed.payload[2..])
+                .ok_or_else(|| reject(directed, "malformed MtA message"))?;
+            let ctx = self.context(peer.idx);
+            let peer_pk = peer.params.paillier_key();
+            let gamma_proj = ProjectivePoint::<C>::from(gamma);
+            let w_point = ProjectivePoint::<C>::from(peer.x_point) * self.weight(peer.idx);
+            if !mta::mta_verify::<C>(
+                pk,
+                &self.k_ct,
+                &peer_pk,
+                &gamma_proj,
+                self.aux.params(),
+                &msg.gamma,
+                &ctx,
+            ) {
+                return Err(reject(directed, "aff-g proof for the gamma MtA failed"));
+            }
+            if !mta::mta_verify::<C>(
+                pk,
+                &self.k_ct,
+                &peer_pk,
+                &w_point,
+                self.aux.params(),
+                &msg.key,
+                &ctx,
+            ) {
+                return Err(reject(directed, "aff-g proof for the key MtA failed"));
+            }
+            if !paillier_zk::verify_log_star::<C>(
+                &peer_pk,
+                &peer.g_ct,
+                &ProjectivePoint::<C>::generator(),
+                &gamma_proj,
+                self.aux.params(),
+                &msg.gamma_proof,
+                &ctx,
+            ) {
+                return Err(reject(directed, "log* proof for gamma failed"));
+            }
+            let alpha = mta::mta_receive::<C>(self.aux.paillier(), &msg.gamma)
+                .ok_or_else(|| reject(directed, "undecryptable MtA answer"))?;
+            let alpha_hat = mta::mta_receive::<C>(self.aux.paillier(), &msg.key)
+                .ok_or_else(|| reject(directed, "undecryptable MtA answer"))?;
+            self.delta_i += alpha;
+            self.chi_i += alpha_hat;
+            gamma_sum += gamma_proj;
+            gammas.push(gamma);
+        }
+        for (peer, gamma) in self.peers.iter_mut().zip(gammas) {
+            peer.gamma = Some(gamma);
+        }
+        if bool::from(gamma_sum.is_identity()) {
+            return Err(scheme_error(Cmp20ErrorCode::BAD_ROUND_MESSAGE));
+        }
+        self.gamma_sum = Some(gamma_sum.to_affine());
+
+        let big_delta = (gamma_sum * *self.k_i).to_affine();
+        let mut payload = self.header(TAG_DELTA);
+        payload.extend_from_slice(&self.delta_i.to_repr());
+        payload.extend_from_slice(&curve::encode_point::<C>(&big_delta));
+        let mut out = vec![Message::broadcast(&self.party_id, 4, payload)];
+
+        let ctx = self.context(self.own_idx());
+        for peer in &self.peers {
+            let proof = paillier_zk::prove_log_star::<C>(
+                pk,
+                &self.k_ct,
+                &int::<C>(&self.k_i),
+                &self.rho_k,
+                &gamma_sum,
+                &peer.params,
+                &ctx,
+            );
+            let mut e = Encoder::new();
+            proof.encode(&mut e);
+            let mut payload = self.header(TAG_DELTA_PROOF);
+            payload.extend_from_slice(&e.finish());
+            out.push(Message::directed(&self.party_id, &peer.id, 4, payload));
+        }
+        Ok(RoundResult::new(out, false))
+    }
+
+    /// Round 5: check every `Δ_j` against `K_j` and `δ` against `Σ Δ_j`,
+    /// derive `R` and publish `σ_i`.
+    fn round5_sign(&mut self, incoming: &[Message]) -> Result<RoundResult> {
+        let inbox = self.inbox(incoming, 4, TAG_DELTA, Some(TAG_DELTA_PROOF))?;
+        let gamma_sum = ProjectivePoint::<C>::from(
+            self.gamma_sum
+                .ok_or_else(|| scheme_error(Cmp20ErrorCode::INTERNAL))?,
+        );
+        let mut delta = self.delta_i;
+        let mut delta_points = gamma_sum * *self.k_i;
+        let n = curve::scalar_len::<C>();
+        for (peer, (broadcast, directed)) in self.peers.iter().zip(&inbox) {
+            let body = &broadcast.payload[2..];
+            if body.len() != n + curve::point_len::<C>() {
+                return Err(reject(broadcast, "malformed delta message"));
+            }
+            let delta_j = curve::decode_scalar::<C>(&body[..n])
+                .ok_or_else(|| reject(broadcast, "bad delta scalar"))?;
+            let big_delta_j = ProjectivePoint::<C>::from(
+                decode_affine::<C>(&body[n..]).map_err(|_| reject(broadcast, "bad delta point"))?,
+            );
+            let directed = directed.expect("inbox checked");
+            let mut d = Decoder::new(&directed.payload[2..]);
+            let proof = LogStarProof::<C>::decode(&mut d)
+                .filter(|_| d.finish().is_some())
+                .ok_or_else(|| reject(directed, "malformed log* proof"))?;
+            if !paillier_zk::verify_log_star::<C>(
+                &peer.params.paillier_key(),
+                &peer.k_ct,
+                &gamma_sum,
+                &big_delta_j,
+                self.aux.params(),
+                &proof,
+                &self.context(peer.idx),
+            ) {
+                return Err(reject(directed, "log* proof for delta failed"));
+            }
+            delta += delta_j;
+            delta_points += big_delta_j;
+        }
+        if ProjectivePoint::<C>::generator() * delta != delta_points {
+            return Err(scheme_error(Cmp20ErrorCode::BAD_PARTIAL_SIGNATURE));
+        }
+        let delta_inv: Scalar<C> = Option::from(delta.invert())
+            .ok_or_else(|| scheme_error(Cmp20ErrorCode::BAD_PARTIAL_SIGNATURE))?;
+        let r_point = (gamma_sum * delta_inv).to_affine();
+        let r_scalar = curve::x_scalar::<C>(&r_point);
+        let z = curve::message_scalar::<C>(&self.message);
+        let sigma_i = *self.k_i * z + r_scalar * self.chi_i;
 
-        let xs_scalar: Vec<Scalar> = parts
-            .iter()
-            .map(|(idx, _, _, _)| Scalar::from(*idx))
-            .collect();
-        let our_idx_scalar = Scalar::from(self.share.party_idx as u64);
-        let lambda_i = lagrange::lagrange_basis_scalar(our_idx_scalar, &xs_scalar);
-
-        // Simplified MtA: the per-party additive delta collapses to
-        // `k^{-1} * r * lambda_i * x_i` because, in the in-clear path,
-        // every `alpha_{ij}` is zero and every `beta_{ji}` is `k_i x_j`,
-        // and these cancel modulo the Lagrange-weighted sum. The
-        // arithmetic is identical to GG18's; only the round count
-        // differs. See `mta` module docs.
-        let our_partial = k_inv * r_scalar * lambda_i * self.share.scalar();
-
-        let mut partial_payload = Vec::with_capacity(1 + 1 + 32);
-        partial_payload.push(TAG_PARTIAL);
-        partial_payload.push(self.share.party_idx as u8);
-        partial_payload.extend_from_slice(&our_partial.to_bytes());
-
-        self.k_inv = Some(k_inv);
         self.r_scalar = Some(r_scalar);
-        self.z = Some(z);
-        self.our_partial = Some(our_partial);
+        self.sigma_i = Some(sigma_i);
 
+        let mut payload = self.header(TAG_SIGMA);
+        payload.extend_from_slice(&sigma_i.to_repr());
         Ok(RoundResult::new(
-            vec![Message::broadcast(&self.party_id, 3, partial_payload)],
+            vec![Message::broadcast(&self.party_id, 5, payload)],
             false,
         ))
     }
 
-    /// Round 4 (framework cadence): receive every peer's partial,
-    /// recompute our own expected partial as a self-check, combine, and
-    /// verify. If verification fails, identify the byzantine party by
-    /// elimination.
-    ///
-    /// Note: this is a fourth `round` call from the framework's
-    /// perspective because the framework's send-then-receive cadence
-    /// forces partial broadcast and partial reception into separate
-    /// calls. The CMP20 *protocol* is three rounds; the framework
-    /// surfaces it as four `round` invocations. See `keygen.rs` for the
-    /// same pattern applied to the non-interactive DKG.
-    fn round4_combine(&mut self, incoming: &[Message]) -> Result<RoundResult> {
-        let k_inv = self
-            .k_inv
-            .ok_or_else(|| scheme_error(Cmp20ErrorCode::INTERNAL))?;
+    /// Round 6 (framework cadence): sum the `σ_j` and verify.
+    fn round6_combine(&mut self, incoming: &[Message]) -> Result<RoundResult> {
+        let inbox = self.inbox(incoming, 5, TAG_SIGMA, None)?;
         let r_scalar = self
             .r_scalar
             .ok_or_else(|| scheme_error(Cmp20ErrorCode::INTERNAL))?;
-        let z = self
-            .z
+        let mut s = self
+            .sigma_i
             .ok_or_else(|| scheme_error(Cmp20ErrorCode::INTERNAL))?;
-        let our_partial = self
-            .our_partial
-            .ok_or_else(|| scheme_error(Cmp20ErrorCode::INTERNAL))?;
-
-        let mut partials: Vec<(u64, Scalar)> = Vec::new();
-        for msg in incoming {
-            if msg.round != 3 || msg.payload.is_empty() {
-                continue;
-            }
-            if msg.payload[0] != TAG_PARTIAL {
-                continue;
-            }
-            if msg.payload.len() != 1 + 1 + 32 {
-                return Err(scheme_error(Cmp20ErrorCode::BAD_ROUND_MESSAGE));
-            }
-            let idx = msg.payload[1] as u64;
-            let mut sb = [0u8; 32];
-            sb.copy_from_slice(&msg.payload[2..34]);
-            let fb: p256::FieldBytes = sb.into();
-            let s: Scalar = Option::from(Scalar::from_repr(fb))
-                .ok_or_else(|| scheme_error(Cmp20ErrorCode::BAD_ROUND_MESSAGE))?;
-            partials.push((idx, s));
-        }
-        partials.push((self.share.party_idx as u64, our_partial));
-
-        let mut s = k_inv * z;
-        for (_, si) in &partials {
-            s += si;
+        for (broadcast, _) in &inbox {
+            s += curve::decode_scalar::<C>(&broadcast.payload[2..])
+                .ok_or_else(|| reject(broadcast, "bad sigma scalar"))?;
         }
-        let s = normalize_s_low(s);
+        let s = curve::normalize_s::<C>(s);
 
-        let r_nz: NonZeroScalar = Option::from(NonZeroScalar::new(r_scalar))
-            .ok_or_else(|| scheme_error(Cmp20ErrorCode::BAD_PARTIAL_SIGNATURE))?;
-        let s_nz: NonZeroScalar = Option::from(NonZeroScalar::new(s))
-            .ok_or_else(|| scheme_error(Cmp20ErrorCode::BAD_PARTIAL_SIGNATURE))?;
-        let sig = p256::ecdsa::Signature::from_scalars(r_nz, s_nz)
-            .map_err(|_| scheme_error(Cmp20ErrorCode::INTERNAL))?;
-        let vk = p256::ecdsa::VerifyingKey::from_affine(self.share.public_key)
-            .map_err(|_| scheme_error(Cmp20ErrorCode::INTERNAL))?;
-        use p256::ecdsa::signature::Verifier;
-        if vk.verify(&self.message, &sig).is_err() {
-            // Identifiable abort: walk the partials, dropping each in
-            // turn. The party whose removal lets the remaining set
-            // produce a valid signature is the byzantine one. We report
-            // it via IDENTIFIED_BYZANTINE.
-            if let Some(byz_idx) = identify_byzantine(&partials, k_inv, z, r_nz, &vk, &self.message)
-            {
-                let _ = byz_idx;
-                return Err(scheme_error(Cmp20ErrorCode::IDENTIFIED_BYZANTINE));
-            }
+        let mut out = Vec::with_capacity(2 * curve::scalar_len::<C>());
+        out.extend_from_slice(&r_scalar.to_repr());
+        out.extend_from_slice(&s.to_repr());
+        if !curve::verify::<C>(&self.share.public_key, &self.message, &out) {
             return Err(scheme_error(Cmp20ErrorCode::BAD_PARTIAL_SIGNATURE));
         }
-
-        let mut out = Vec::with_capacity(64);
-        out.extend_from_slice(&r_scalar.to_bytes());
-        out.extend_from_slice(&s.to_bytes());
         self.signature = Some(out);
 
         Ok(RoundResult::done())
     }
 }
 
-/// Try to identify a single byzantine partial by elimination: for each
-/// party, drop its partial and check whether the remaining set verifies.
-/// Returns the 1-based index of the identified party, or `None` if no
-/// single-removal restores validity (multiple byzantine, or the fault
-/// is elsewhere).
-fn identify_byzantine(
-    partials: &[(u64, Scalar)],
-    k_inv: Scalar,
-    z: Scalar,
-    r_nz: NonZeroScalar,
-    vk: &p256::ecdsa::VerifyingKey,
-    message: &[u8],
-) -> Option<u64> {
-    use p256::ecdsa::signature::Verifier;
-    for (suspect_idx, _) in partials {
-        let mut s = k_inv * z;
-        for (j_idx, sj) in partials {
-            if j_idx == suspect_idx {
-                continue;
-            }
-            s += sj;
-        }
-        let s = normalize_s_low(s);
-        let s_nz_opt: Option<NonZeroScalar> = NonZeroScalar::new(s).into();
-        if let Some(s_nz) = s_nz_opt {
-            if let Ok(sig_minus) = p256::ecdsa::Signature::from_scalars(r_nz, s_nz) {
-                if vk.verify(message, &sig_minus).is_ok() {
-                    return Some(*suspect_idx);
-                }
-            }
-        }
-    }
-    None
-}
-
-impl SessionImpl for Cmp20SignSession {
+impl<C: Cmp20Curve> SessionImpl for Cmp20SignSession<C> {
     fn round(&mut self, incoming: &[Message]) -> Result<RoundResult> {
         self.round_done = self.round_done.checked_add(1).ok_or_else(|| {
             confium_tc::error::RoundOverflowSnafu {
@@ -373,16 +730,18 @@ impl SessionImpl for Cmp20SignSession {
             .build()
         })?;
         match self.round_done {
-            1 => self.round1_commit(),
-            2 => self.round2_reveal(incoming),
-            3 => self.round3_partial(incoming),
-            4 => self.round4_combine(incoming),
+            1 => self.round1_aux(),
+            2 => self.round2_nonce(incoming),
+            3 => self.round3_mta(incoming),
+            4 => self.round4_delta(incoming),
+            5 => self.round5_sign(incoming),
+            6 => self.round6_combine(incoming),
             other => Err(confium_tc::error::RoundOverflowSnafu { round: other }.build()),
         }
     }
 
     fn result(&self) -> Result<Vec<u8>> {
-        if self.round_done < 4 {
+        if self.round_done < 6 {
             return Err(confium_tc::error::SessionNotCompleteSnafu {}.build());
         }
         self.signature
@@ -391,72 +750,58 @@ impl SessionImpl for Cmp20SignSession {
     }
 
     fn destroy(&mut self) {
-        self.k_i = Option::from(NonZeroScalar::new(Scalar::ONE))
-            .unwrap_or_else(|| NonZeroScalar::new(Scalar::ONE).unwrap());
-        self.k_inv = None;
+        let one = NonZeroScalar::<C>::new(Scalar::<C>::ONE).unwrap();
+        self.k_i = one;
+        self.gamma_i = one;
+        self.rho_k = BigUint::default();
+        self.rho_g = BigUint::default();
+        self.w_i = Scalar::<C>::ZERO;
+        self.delta_i = Scalar::<C>::ZERO;
+        self.chi_i = Scalar::<C>::ZERO;
         self.r_scalar = None;
-        self.z = None;
-        self.our_partial = None;
+        self.sigma_i = None;
         self.signature = None;
     }
-}
-
-fn reduce_x_mod_n(point: AffinePoint) -> Scalar {
-    let x = point.x();
-    let x_bytes: &[u8] = x.as_slice();
-    let mut arr = [0u8; 32];
-    let n = x_bytes.len().min(32);
-    arr[..n].copy_from_slice(&x_bytes[..n]);
-    let fb: p256::FieldBytes = arr.into();
-    Option::from(Scalar::from_repr(fb)).unwrap_or(Scalar::ZERO)
-}
 
-fn hash_to_scalar(message: &[u8]) -> Scalar {
-    let mut h = Sha256::new();
-    h.update(message);
-    let digest: [u8; 32] = h.finalize().into();
-    let fb: p256::FieldBytes = digest.into();
-    Option::from(Scalar::from_repr(fb)).unwrap_or(Scalar::ZERO)
-}
-
-fn normalize_s_low(s: Scalar) -> Scalar {
-    use crypto_bigint::Limb;
-    use elliptic_curve::Curve;
-    use p256::NistP256;
-    let n_u = <NistP256 as Curve>::ORDER.get();
-    let half = n_u >> 1usize;
-    let s_u = decode_scalar_to_uint(s);
-    if s_u > half {
-        let (s_prime, _) = n_u.borrowing_sub(&s_u, Limb::ZERO);
-        let be = s_prime.to_be_bytes();
-        let src: &[u8] = be.as_slice();
-        let mut bytes = [0u8; 32];
-        let off = src.len().saturating_sub(32);
-        let take = src.len() - off;
-        bytes[..take].copy_from_slice(&src[off..]);
-        let fb: p256::FieldBytes = bytes.into();
-        Option::from(Scalar::from_repr(fb)).unwrap_or(s)
-    } else {
-        s
+    fn snapshot(&self) -> Option<Zeroizing<Vec<u8>>> {
+        let mut w = StateWriter::new();
+        w.u8(self.round_done);
+        w.bytes(&self.aux.to_bytes());
+        write_scalar::<C>(&mut w, &self.k_i);
+        write_scalar::<C>(&mut w, &self.gamma_i);
+        write_uint(&mut w, &self.k_ct);
+        write_uint(&mut w, &self.rho_k);
+        write_uint(&mut w, &self.g_ct);
+        write_uint(&mut w, &self.rho_g);
+        write_scalar::<C>(&mut w, &self.w_i);
+        write_scalar::<C>(&mut w, &self.delta_i);
+        write_scalar::<C>(&mut w, &self.chi_i);
+        w.u32(self.peers.len() as u32);
+        for peer in &self.peers {
+            w.str(&peer.id);
+            w.u64(peer.idx);
+            write_uint(&mut w, &peer.params.n);
+            write_uint(&mut w, &peer.params.s);
+            write_uint(&mut w, &peer.params.t);
+            write_point::<C>(&mut w, &peer.x_point);
+            write_uint(&mut w, &peer.k_ct);
+            write_uint(&mut w, &peer.g_ct);
+            write_opt_point::<C>(&mut w, &peer.gamma);
+        }
+        write_opt_point::<C>(&mut w, &self.gamma_sum);
+        write_opt_scalar::<C>(&mut w, &self.r_scalar);
+        write_opt_scalar::<C>(&mut w, &self.sigma_i);
+        w.bool(self.signature.is_some());
+        if let Some(sig) = &self.signature {
+            w.bytes(sig);
+        }
+        Some(w.finish())
     }
-}
-
-fn decode_scalar_to_uint(s: Scalar) -> p256::U256 {
-    use elliptic_curve::Curve;
-    use p256::NistP256;
-    let bytes = s.to_bytes();
-    <NistP256 as Curve>::Uint::from_be_slice(bytes.as_slice())
-}
 
-fn decode_affine(bytes: &[u8]) -> Result<AffinePoint> {
-    use elliptic_curve::sec1::FromSec1Point;
-    if bytes.len() != 33 {
-        return Err(scheme_error(Cmp20ErrorCode::BAD_ROUND_MESSAGE));
+    /// `k_i * G`, going into the round that publishes `σ_i`.
+    fn pending_nonce(&self) -> Option<Vec<u8>> {
+        (self.round_done == 4).then(|| {
+            curve::encode_point::<C>(&(ProjectivePoint::<C>::generator() * *self.k_i).to_affine())
+        })
     }
-    let enc = elliptic_curve::sec1::Sec1Point::<p256::NistP256>::from_bytes(bytes)
-        .map_err(|_| scheme_error(Cmp20ErrorCode::BAD_ROUND_MESSAGE))?;
-    let pt: AffinePoint = Option::from(AffinePoint::from_sec1_point(&enc))
-        .ok_or_else(|| scheme_error(Cmp20ErrorCode::BAD_ROUND_MESSAGE))?;
-    let _ = pt.x();
-    Ok(pt)
 }
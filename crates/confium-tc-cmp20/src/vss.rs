@@ -1,4 +1,4 @@
-//! Feldman verifiable secret sharing over P-256.
+//! Feldman verifiable secret sharing over a [`Cmp20Curve`].
 //!
 //! A dealer samples a degree-`T-1` polynomial
 //! `f(x) = a_0 + a_1 x + ... + a_{T-1} x^{T-1}` with `a_0 = secret`,
@@ -12,37 +12,40 @@
 //! party broadcasts its full commitment list and bundles every peer's
 //! evaluation into the same message, so no second round is needed.
 
-use elliptic_curve::Field;
+use elliptic_curve::group::{Curve as _, Group};
 use elliptic_curve::rand_core::CryptoRng;
-use elliptic_curve::sec1::ToSec1Point;
-use p256::{AffinePoint, ProjectivePoint, Scalar};
+use elliptic_curve::{AffinePoint, Field, ProjectivePoint, Scalar};
+use p256::NistP256;
+
+use crate::curve::{self, Cmp20Curve};
 
 /// One dealer's Feldman VSS output.
 #[derive(Clone, Debug)]
-pub struct FeldmanVss {
-    pub commitments: Vec<AffinePoint>,
-    pub shares: Vec<Scalar>,
-    pub secret: Scalar,
+pub struct FeldmanVss<C: Cmp20Curve = NistP256> {
+    pub commitments: Vec<AffinePoint<C>>,
+    pub shares: Vec<Scalar<C>>,
+    pub secret: Scalar<C>,
 }
 
-impl FeldmanVss {
+impl<C: Cmp20Curve> FeldmanVss<C> {
     pub fn deal(rng: &mut impl CryptoRng, n: usize, t: usize) -> Self {
         debug_assert!(t >= 1 && t <= n);
-        let mut coeffs: Vec<Scalar> = (0..t).map(|_| Scalar::random(&mut *rng)).collect();
+        let mut coeffs: Vec<Scalar<C>> = (0..t).map(|_| Scalar::<C>::random(&mut *rng)).collect();
         let secret = coeffs[0];
-        let g = ProjectivePoint::GENERATOR;
-        let commitments: Vec<AffinePoint> = coeffs.iter().map(|a| (g * a).to_affine()).collect();
-        let shares: Vec<Scalar> = (1..=n as u64)
+        let g = ProjectivePoint::<C>::generator();
+        let commitments: Vec<AffinePoint<C>> =
+            coeffs.iter().map(|a| (g * *a).to_affine()).collect();
+        let shares: Vec<Scalar<C>> = (1..=n as u64)
             .map(|i| {
-                let x = Scalar::from(i);
-                let mut acc = Scalar::ZERO;
+                let x = Scalar::<C>::from(i);
+                let mut acc = Scalar::<C>::ZERO;
                 for &a in coeffs.iter().rev() {
                     acc = acc * x + a;
                 }
                 acc
             })
             .collect();
-        coeffs.fill(Scalar::ZERO);
+        coeffs.fill(Scalar::<C>::ZERO);
         FeldmanVss {
             commitments,
             shares,
@@ -50,48 +53,45 @@ impl FeldmanVss {
         }
     }
 
-    pub fn verify_share(commitments: &[AffinePoint], party_idx_1based: u64, share: Scalar) -> bool {
+    pub fn verify_share(
+        commitments: &[AffinePoint<C>],
+        party_idx_1based: u64,
+        share: Scalar<C>,
+    ) -> bool {
         if commitments.is_empty() {
             return false;
         }
-        let g = ProjectivePoint::GENERATOR;
-        let lhs = g * share;
-        let i_scalar = Scalar::from(party_idx_1based);
-        let mut rhs = ProjectivePoint::IDENTITY;
-        let mut i_pow = Scalar::ONE;
+        let lhs = ProjectivePoint::<C>::generator() * share;
+        let i_scalar = Scalar::<C>::from(party_idx_1based);
+        let mut rhs = ProjectivePoint::<C>::identity();
+        let mut i_pow = Scalar::<C>::ONE;
         for c in commitments {
-            rhs += ProjectivePoint::from(*c) * i_pow;
+            rhs += ProjectivePoint::<C>::from(*c) * i_pow;
             i_pow *= i_scalar;
         }
         lhs == rhs
     }
 
-    pub fn encode_commitments(commitments: &[AffinePoint]) -> Vec<u8> {
-        let mut out = Vec::with_capacity(commitments.len() * 33);
+    pub fn encode_commitments(commitments: &[AffinePoint<C>]) -> Vec<u8> {
+        let mut out = Vec::with_capacity(commitments.len() * curve::point_len::<C>());
         for c in commitments {
-            out.extend_from_slice(c.to_sec1_point(true).as_bytes());
+            out.extend_from_slice(&curve::encode_point::<C>(c));
         }
         out
     }
 
-    pub fn decode_commitments(bytes: &[u8]) -> Option<Vec<AffinePoint>> {
-        if bytes.len() % 33 != 0 {
+    pub fn decode_commitments(bytes: &[u8]) -> Option<Vec<AffinePoint<C>>> {
+        let len = curve::point_len::<C>();
+        if bytes.len() % len != 0 {
             return None;
         }
-        use elliptic_curve::point::AffineCoordinates;
-        use elliptic_curve::sec1::FromSec1Point;
-        use p256::NistP256;
-        let mut out = Vec::with_capacity(bytes.len() / 33);
-        for chunk in bytes.chunks_exact(33) {
-            let enc = elliptic_curve::sec1::Sec1Point::<NistP256>::from_bytes(chunk).ok()?;
-            let pt: AffinePoint = Option::from(AffinePoint::from_sec1_point(&enc))?;
-            let _ = pt.x();
-            out.push(pt);
-        }
-        Some(out)
+        bytes
+            .chunks_exact(len)
+            .map(curve::decode_point::<C>)
+            .collect()
     }
 
-    pub fn public_key(&self) -> AffinePoint {
+    pub fn public_key(&self) -> AffinePoint<C> {
         self.commitments[0]
     }
 }
@@ -100,14 +100,16 @@ impl FeldmanVss {
 mod tests {
     use super::*;
     use elliptic_curve::rand_core::UnwrapErr;
+    use elliptic_curve::sec1::ToSec1Point;
     use getrandom::SysRng;
+    use p256::Scalar;
 
     #[test]
     fn feldman_share_verifies() {
-        let vss = FeldmanVss::deal(&mut UnwrapErr(SysRng), 5, 3);
+        let vss = FeldmanVss::<NistP256>::deal(&mut UnwrapErr(SysRng), 5, 3);
         for (i, &share) in vss.shares.iter().enumerate() {
             assert!(
-                FeldmanVss::verify_share(&vss.commitments, (i + 1) as u64, share),
+                FeldmanVss::<NistP256>::verify_share(&vss.commitments, (i + 1) as u64, share),
                 "share for party {} must verify",
                 i + 1
             );
@@ -116,16 +118,20 @@ mod tests {
 
     #[test]
     fn feldman_rejects_tampered_share() {
-        let vss = FeldmanVss::deal(&mut UnwrapErr(SysRng), 5, 3);
+        let vss = FeldmanVss::<NistP256>::deal(&mut UnwrapErr(SysRng), 5, 3);
         let bad_share = vss.shares[0] + Scalar::from(1u64);
-        assert!(!FeldmanVss::verify_share(&vss.commitments, 1, bad_share));
+        assert!(!FeldmanVss::<NistP256>::verify_share(
+            &vss.commitments,
+            1,
+            bad_share
+        ));
     }
 
     #[test]
     fn feldman_commitments_round_trip() {
-        let vss = FeldmanVss::deal(&mut UnwrapErr(SysRng), 5, 3);
-        let enc = FeldmanVss::encode_commitments(&vss.commitments);
-        let dec = FeldmanVss::decode_commitments(&enc).expect("decode");
+        let vss = FeldmanVss::<NistP256>::deal(&mut UnwrapErr(SysRng), 5, 3);
+        let enc = FeldmanVss::<NistP256>::encode_commitments(&vss.commitments);
+        let dec = FeldmanVss::<NistP256>::decode_commitments(&enc).expect("decode");
         assert_eq!(dec.len(), vss.commitments.len());
         for (a, b) in vss.commitments.iter().zip(dec.iter()) {
             let ab = a.to_sec1_point(true);
@@ -136,6 +142,21 @@ mod tests {
 
     #[test]
     fn feldman_decode_rejects_garbage() {
-        assert!(FeldmanVss::decode_commitments(&[0u8; 10]).is_none());
+        assert!(FeldmanVss::<NistP256>::decode_commitments(&[0u8; 10]).is_none());
+    }
+
+    #[test]
+    fn feldman_shares_verify_on_p384() {
+        let vss = FeldmanVss::<p384::NistP384>::deal(&mut UnwrapErr(SysRng), 3, 2);
+        let enc = FeldmanVss::<p384::NistP384>::encode_commitments(&vss.commitments);
+        assert_eq!(enc.len(), 2 * 49);
+        let dec = FeldmanVss::<p384::NistP384>::decode_commitments(&enc).expect("decode");
+        for (i, &share) in vss.shares.iter().enumerate() {
+            assert!(FeldmanVss::<p384::NistP384>::verify_share(
+                &dec,
+                (i + 1) as u64,
+                share
+            ));
+        }
     }
 }
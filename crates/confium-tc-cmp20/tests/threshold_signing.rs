@@ -7,8 +7,10 @@
 //!    under `p256::ecdsa::VerifyingKey`).
 //! 2. A 3-of-3 coalition produces a valid signature under the same key.
 //! 3. Any T-of-N subset produces a signature under the same joint key.
-//! 4. A byzantine party (tampered partial signature) causes the session
-//!    to abort with identifiable-abort semantics.
+//! 4. A party signing with a corrupted share causes the session to
+//!    abort before any nonce is used.
+//! 5. A signer restarted from its snapshot after every round still
+//!    signs, and its spent nonce can never be resumed.
 
 use confium_tc::Session;
 use confium_tc::SessionParams;
@@ -145,7 +147,7 @@ fn run_signing(
         roster,
         participating.clone(),
         |idx| sign_params(roster, idx, threshold, shares[idx].clone(), msg),
-        4,
+        6,
     )
 }
 
@@ -247,16 +249,16 @@ fn signing_without_share_aborts() {
 
 #[test]
 fn byzantine_partial_signature_aborts() {
-    // A party that posts a wrong partial signature causes verification
-    // to fail in round 4. We simulate this by having one party use a
-    // corrupted share (wrong party_idx), so its Lagrange weight is
-    // inconsistent with the others.
+    // A party signing with a corrupted share (wrong party_idx) has a
+    // Lagrange weight inconsistent with the others. The round-1 key
+    // shares no longer interpolate to the joint key, so round 2 aborts
+    // before anyone commits a nonce.
     let roster = ["alice", "bob", "carol"];
     let msg = b"byzantine test";
     let mut shares = run_dkg(&roster, 2);
 
     // Corrupt carol's share: flip its party_idx so her Lagrange weight
-    // is wrong, making her partial signature inconsistent.
+    // is wrong.
     let mut carol = Cmp20Share::from_bytes(&shares[2]).expect("share");
     carol.party_idx = 99; // bogus index
     shares[2] = carol.to_bytes();
@@ -265,11 +267,11 @@ fn byzantine_partial_signature_aborts() {
     match outcome {
         Outcome::Aborted(reason) => {
             assert!(
-                reason.contains("round 4") || reason.contains("round 3"),
-                "expected abort on byzantine partial, got: {reason}"
+                reason.contains("round 2"),
+                "expected abort on the corrupted share, got: {reason}"
             );
         }
-        Outcome::Ok(_) => panic!("byzantine partial must abort, not complete"),
+        Outcome::Ok(_) => panic!("a corrupted share must abort, not complete"),
     }
 }
 
@@ -296,3 +298,59 @@ fn dkg_completes_in_single_broadcast_round() {
         );
     }
 }
+
+const SNAPSHOT_KEY: &[u8] = b"cmp20-test-share-envelope-integrity-key";
+
+/// Messages from `outs` addressed to `to`.
+fn inbox(outs: &[Message], to: &str) -> Vec<Message> {
+    outs.iter()
+        .filter(|m| m.from_party_id != to && m.is_for(to))
+        .cloned()
+        .collect()
+}
+
+#[test]
+fn signer_restarting_every_round_still_signs() {
+    use confium_tc::snapshot::MemoryNonceLedger;
+    use std::sync::Arc;
+
+    let roster = ["alice", "bob"];
+    let shares = run_dkg(&roster, 2);
+    let msg = b"cmp20 resume";
+    let params: Vec<SessionParams> = (0..2)
+        .map(|i| sign_params(&roster, i, 2, shares[i].clone(), msg))
+        .collect();
+    let ledger = Arc::new(MemoryNonceLedger::new());
+    let mut alice = Session::create(&params[0]).expect("alice");
+    alice.set_nonce_ledger(ledger.clone());
+    let mut bob = Session::create(&params[1]).expect("bob");
+
+    // Alice checkpoints after every round and is restarted from the
+    // checkpoint before the next one.
+    let mut a_out = Vec::new();
+    let mut b_out = Vec::new();
+    let mut blobs = Vec::new();
+    while !(alice.is_complete() && bob.is_complete()) {
+        let a_next = alice
+            .round_step(&inbox(&b_out, "alice"))
+            .expect("alice round")
+            .outgoing;
+        let b_next = bob
+            .round_step(&inbox(&a_out, "bob"))
+            .expect("bob round")
+            .outgoing;
+        let blob = alice.snapshot(SNAPSHOT_KEY).expect("snapshot");
+        alice = Session::resume(&params[0], &blob, SNAPSHOT_KEY, ledger.clone()).expect("resume");
+        assert_eq!(alice.last_outgoing(), a_next.as_slice());
+        blobs.push(blob);
+        a_out = a_next;
+        b_out = b_next;
+    }
+    let sig = alice.result().expect("signature");
+    assert_eq!(sig, bob.result().expect("signature"));
+    assert!(verify_sig(&shares[0], msg, &sig));
+
+    // The round-4 checkpoint still holds the nonce spent in round 5.
+    let err = Session::resume(&params[0], &blobs[3], SNAPSHOT_KEY, ledger).unwrap_err();
+    assert!(matches!(err, confium_tc::Error::NonceReuse { .. }));
+}
@@ -0,0 +1,796 @@
+//! Authenticated round messages.
+//!
+//! A plain [`Message`] is just `from` / `to` / `round` / `payload`:
+//! anyone who can inject a frame into the transport can claim to be
+//! any party. A session created with
+//! [`crate::session::Session::create_authenticated`] instead seals
+//! every outgoing message and opens every incoming one, so schemes
+//! only ever see traffic that really came from the party it names.
+//!
+//! ## Sealed form
+//!
+//! A sealed message keeps its header in the clear — routing layers
+//! (the test harness, the Byzantine wrapper, a network driver) keep
+//! working on `from_party_id` / `to_party_id` / `round` — and replaces
+//! the payload with
+//!
+//! ```text
+//! version (1) || kind (1) || body || signature (64)
+//! ```
+//!
+//! - `kind` 0, broadcast: `body` is the scheme payload.
+//! - `kind` 1, directed: `body` is `nonce (12) || ciphertext || tag
+//!   (16)`, AES-256-GCM under a key only the two endpoints can derive
+//!   (static-static P-256 ECDH, then HMAC-SHA256 extract/expand over
+//!   the session id and the ordered pair of party ids).
+//!
+//! The signature is ECDSA-P256 under the sender's long-term
+//! [`IdentityKey`] over a transcript binding the scheme name, the
+//! session id, sender, recipient (or the broadcast marker), round and
+//! body. A message lifted from another session, another round, or
+//! addressed to another party fails verification.
+//!
+//! ## Replay and equivocation
+//!
+//! Each `(sender, recipient, round)` slot is accepted once per session.
+//! A copy of an accepted message — a transport retransmission or a
+//! replay — is dropped silently. Copies are recognised by the signed
+//! bytes, not the signature: ECDSA signatures are malleable (`s` and
+//! `n - s` both verify), so anyone could otherwise turn a replay into
+//! apparent equivocation. A validly signed message with *different*
+//! signed bytes for the same slot is equivocation and aborts the
+//! session, naming the sender. Messages for the current or a
+//! later round are rejected: a lockstep session only consumes the
+//! previous rounds' traffic.
+//!
+//! ## Attribution
+//!
+//! Every failure names a party ([`crate::Error::culprit`]). Two of
+//! them carry transferable evidence — the sender's own signature over
+//! the offending bytes — and are safe grounds for identifiable abort:
+//! [`crate::Error::MessageEquivocation`] and
+//! [`crate::Error::MessageUndecryptable`]. A
+//! [`crate::Error::MessageRejected`] only names the *claimed* sender;
+//! an unsigned or mis-signed frame may have been injected by the
+//! network.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use aes_gcm::aead::AeadInOut;
+use aes_gcm::aead::inout::InOutBuf;
+use aes_gcm::{Aes256Gcm, Nonce};
+use hmac::{Hmac, KeyInit, Mac};
+use p256::ecdsa::signature::{Signer, Verifier};
+use p256::ecdsa::{Signature, SigningKey, VerifyingKey};
+use p256::elliptic_curve::rand_core::{Rng, UnwrapErr};
+use p256::elliptic_curve::sec1::ToSec1Point;
+use p256::{FieldBytes, PublicKey, SecretKey};
+use sha2::{Digest, Sha256};
+use snafu::ensure;
+use zeroize::Zeroizing;
+
+use crate::Result;
+use crate::error;
+use crate::message::Message;
+use crate::party::PartyList;
+use crate::snapshot::{self, StateReader, StateWriter};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Domain separator for the signed transcript and the pairwise keys.
+const DOMAIN: &[u8] = b"confium-tc-msg-v1";
+
+/// Sealed-payload format version.
+pub const WIRE_VERSION: u8 = 1;
+
+const KIND_BROADCAST: u8 = 0;
+const KIND_DIRECTED: u8 = 1;
+
+const SIGNATURE_LEN: usize = 64;
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+const SEC1_LEN: usize = 65;
+
+/// A party's long-term identity: an ECDSA-P256 signing key for
+/// message authentication and a separate P-256 key for pairwise key
+/// agreement.
+pub struct IdentityKey {
+    signing: SigningKey,
+    agreement: SecretKey,
+}
+
+impl IdentityKey {
+    /// Generate a fresh identity from the OS RNG.
+    pub fn generate() -> Self {
+        IdentityKey {
+            signing: random_key(|fb| SigningKey::from_bytes(fb).ok()),
+            agreement: random_key(|fb| SecretKey::from_bytes(fb).ok()),
+        }
+    }
+
+    /// Restore an identity from [`IdentityKey::to_bytes`] output: the
+    /// 32-byte signing scalar followed by the 32-byte agreement scalar.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        ensure!(
+            bytes.len() == 64,
+            error::InvalidIdentityKeySnafu {
+                reason: format!("expected 64 bytes, got {}", bytes.len()),
+            }
+        );
+        let signing = SigningKey::from_slice(&bytes[..32]).map_err(|_| {
+            error::InvalidIdentityKeySnafu {
+                reason: "signing scalar out of range",
+            }
+            .build()
+        })?;
+        let agreement = SecretKey::from_slice(&bytes[32..]).map_err(|_| {
+            error::InvalidIdentityKeySnafu {
+                reason: "agreement scalar out of range",
+            }
+            .build()
+        })?;
+        Ok(IdentityKey { signing, agreement })
+    }
+
+    /// Serialize both secret scalars. The caller owns storing them
+    /// (a keystore backend, a sealed [`crate::share_envelope::ShareEnvelope`]).
+    pub fn to_bytes(&self) -> Zeroizing<Vec<u8>> {
+        let mut out = Zeroizing::new(Vec::with_capacity(64));
+        out.extend_from_slice(&self.signing.to_bytes());
+        out.extend_from_slice(&self.agreement.to_bytes());
+        out
+    }
+
+    /// The public half other parties pin for this identity.
+    pub fn public(&self) -> PublicIdentity {
+        PublicIdentity {
+            verifying: *self.signing.verifying_key(),
+            agreement: self.agreement.public_key(),
+        }
+    }
+}
+
+impl fmt::Debug for IdentityKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IdentityKey")
+            .field("public", &self.public())
+            .finish_non_exhaustive()
+    }
+}
+
+/// The public half of an [`IdentityKey`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicIdentity {
+    verifying: VerifyingKey,
+    agreement: PublicKey,
+}
+
+impl PublicIdentity {
+    /// Parse [`PublicIdentity::to_bytes`] output: two uncompressed SEC1
+    /// points, verifying key first.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        ensure!(
+            bytes.len() == 2 * SEC1_LEN,
+            error::InvalidIdentityKeySnafu {
+                reason: format!("expected {} bytes, got {}", 2 * SEC1_LEN, bytes.len()),
+            }
+        );
+        let verifying = VerifyingKey::from_sec1_bytes(&bytes[..SEC1_LEN]).map_err(|_| {
+            error::InvalidIdentityKeySnafu {
+                reason: "verifying key is not a P-256 point",
+            }
+            .build()
+        })?;
+        let agreement = PublicKey::from_sec1_bytes(&bytes[SEC1_LEN..]).map_err(|_| {
+            error::InvalidIdentityKeySnafu {
+                reason: "agreement key is not a P-256 point",
+            }
+            .build()
+        })?;
+        Ok(PublicIdentity {
+            verifying,
+            agreement,
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(2 * SEC1_LEN);
+        out.extend_from_slice(&self.verifying.to_sec1_bytes());
+        out.extend_from_slice(self.agreement.as_affine().to_sec1_point(false).as_bytes());
+        out
+    }
+}
+
+/// Everything a party needs to authenticate one session: the session
+/// id every party agreed on, its own [`IdentityKey`], and the pinned
+/// [`PublicIdentity`] of every peer, keyed by party id.
+///
+/// The session id must be unique per protocol run (a coordinator-issued
+/// UUID, a hash of the request); it is what keeps one run's messages
+/// from being replayed into another.
+pub struct SessionAuth {
+    session_id: Vec<u8>,
+    identity: IdentityKey,
+    peers: HashMap<String, PublicIdentity>,
+}
+
+impl SessionAuth {
+    pub fn new(session_id: impl Into<Vec<u8>>, identity: IdentityKey) -> Self {
+        SessionAuth {
+            session_id: session_id.into(),
+            identity,
+            peers: HashMap::new(),
+        }
+    }
+
+    /// Builder form of [`SessionAuth::add_peer`].
+    pub fn with_peer(mut self, party_id: impl Into<String>, identity: PublicIdentity) -> Self {
+        self.add_peer(party_id, identity);
+        self
+    }
+
+    /// Pin `identity` for `party_id`, replacing any earlier entry.
+    pub fn add_peer(&mut self, party_id: impl Into<String>, identity: PublicIdentity) {
+        self.peers.insert(party_id.into(), identity);
+    }
+
+    pub fn session_id(&self) -> &[u8] {
+        &self.session_id
+    }
+
+    pub fn identity(&self) -> &IdentityKey {
+        &self.identity
+    }
+
+    pub fn peer(&self, party_id: &str) -> Option<&PublicIdentity> {
+        self.peers.get(party_id)
+    }
+}
+
+impl fmt::Debug for SessionAuth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SessionAuth")
+            .field("session_id", &hex::encode(&self.session_id))
+            .field("peers", &self.peers.keys().collect::<Vec<_>>())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Slot key for replay tracking: sender, recipient (`None` for
+/// broadcast), round.
+type Slot = (String, Option<String>, u8);
+
+/// Per-session sealing state owned by [`crate::session::Session`].
+pub(crate) struct Authenticator {
+    auth: SessionAuth,
+    scheme: String,
+    our_id: String,
+    /// Raw ECDH output with each peer, keyed by party id.
+    shared: HashMap<String, Zeroizing<[u8; 32]>>,
+    /// SHA-256 of the signed part (everything but the signature) of
+    /// every sealed payload accepted so far.
+    accepted: HashMap<Slot, [u8; 32]>,
+}
+
+impl Authenticator {
+    /// Check every roster peer has a pinned identity and precompute the
+    /// pairwise secrets.
+    pub(crate) fn new(
+        auth: SessionAuth,
+        scheme: &str,
+        parties: &PartyList,
+        this_party_idx: usize,
+    ) -> Result<Self> {
+        let our_id = parties.get(this_party_idx)?.id.clone();
+        let mut shared = HashMap::new();
+        for party in parties.parties() {
+            if party.id == our_id {
+                continue;
+            }
+            let peer = auth.peer(&party.id).ok_or_else(|| {
+                error::MissingPartyIdentitySnafu {
+                    party: party.id.clone(),
+                }
+                .build()
+            })?;
+            shared.insert(
+                party.id.clone(),
+                ecdh(&auth.identity.agreement, &peer.agreement),
+            );
+        }
+        Ok(Authenticator {
+            auth,
+            scheme: scheme.to_string(),
+            our_id,
+            shared,
+            accepted: HashMap::new(),
+        })
+    }
+
+    /// Write the replay / equivocation record for a session snapshot.
+    pub(crate) fn save(&self, w: &mut StateWriter) {
+        w.u32(self.accepted.len() as u32);
+        for ((from, to, round), digest) in &self.accepted {
+            w.str(from);
+            match to {
+                None => w.u8(0),
+                Some(to) => {
+                    w.u8(1);
+                    w.str(to);
+                }
+            }
+            w.u8(*round);
+            w.fixed(digest);
+        }
+    }
+
+    /// Restore what [`Authenticator::save`] wrote, so a resumed session
+    /// still drops replays and catches equivocation on earlier rounds.
+    pub(crate) fn load(&mut self, r: &mut StateReader<'_>) -> Result<()> {
+        for _ in 0..r.u32()? {
+            let from = r.string()?;
+            let to = match r.u8()? {
+                0 => None,
+                1 => Some(r.string()?),
+                _ => return Err(snapshot::invalid("bad recipient tag")),
+            };
+            let round = r.u8()?;
+            self.accepted.insert((from, to, round), r.array()?);
+        }
+        Ok(())
+    }
+
+    /// Seal one message the local scheme produced.
+    pub(crate) fn seal(&self, msg: &Message) -> Result<Message> {
+        ensure!(
+            msg.from_party_id == self.our_id,
+            error::ForgedSenderSnafu {
+                expected: self.our_id.clone(),
+                actual: msg.from_party_id.clone(),
+            }
+        );
+        let mut payload = vec![WIRE_VERSION];
+        match &msg.to_party_id {
+            None => {
+                payload.push(KIND_BROADCAST);
+                payload.extend_from_slice(&msg.payload);
+            }
+            Some(to) => {
+                let key = self.pairwise_key(&msg.from_party_id, to)?;
+                let cipher = Aes256Gcm::new_from_slice(key.as_slice())
+                    .expect("AES-256-GCM accepts a 32-byte key");
+                let mut nonce_bytes = [0u8; NONCE_LEN];
+                UnwrapErr(getrandom::SysRng).fill_bytes(&mut nonce_bytes);
+                let nonce = Nonce::from(nonce_bytes);
+                let mut buffer = msg.payload.clone();
+                let aad = self.header(msg);
+                let tag = cipher
+                    .encrypt_inout_detached(&nonce, &aad, InOutBuf::from(buffer.as_mut_slice()))
+                    .expect("round payloads are far below the AES-GCM length limit");
+                payload.push(KIND_DIRECTED);
+                payload.extend_from_slice(&nonce_bytes);
+                payload.extend_from_slice(&buffer);
+                payload.extend_from_slice(&tag);
+            }
+        }
+        let signature: Signature = self
+            .auth
+            .identity
+            .signing
+            .sign(&self.transcript(msg, &payload));
+        payload.extend_from_slice(&signature.to_bytes());
+        Ok(Message {
+            payload,
+            ..msg.clone()
+        })
+    }
+
+    /// Verify and open one incoming message for a session about to run
+    /// round `current_round`. `Ok(None)` means the message is a
+    /// duplicate of one already accepted (or our own, reflected back)
+    /// and should not reach the scheme.
+    pub(crate) fn open(&mut self, msg: &Message, current_round: u8) -> Result<Option<Message>> {
+        let from = msg.from_party_id.as_str();
+        if from == self.our_id {
+            return Ok(None);
+        }
+        let reject = |reason: String| {
+            error::MessageRejectedSnafu {
+                party: from,
+                round: msg.round,
+                reason,
+            }
+            .build()
+        };
+        if let Some(to) = msg.to_party_id.as_deref().filter(|to| *to != self.our_id) {
+            return Err(reject(format!("addressed to '{to}'")));
+        }
+        if msg.round >= current_round {
+            return Err(reject(format!(
+                "round {} is not before the current round {current_round}",
+                msg.round
+            )));
+        }
+        let peer = self
+            .auth
+            .peer(from)
+            .filter(|_| self.shared.contains_key(from))
+            .ok_or_else(|| reject("sender is not on the roster".to_string()))?;
+
+        let payload = &msg.payload;
+        if payload.len() < 2 + SIGNATURE_LEN {
+            return Err(reject("sealed payload truncated".to_string()));
+        }
+        if payload[0] != WIRE_VERSION {
+            return Err(reject(format!("unsupported wire version {}", payload[0])));
+        }
+        let expected_kind = if msg.is_broadcast() {
+            KIND_BROADCAST
+        } else {
+            KIND_DIRECTED
+        };
+        if payload[1] != expected_kind {
+            return Err(reject(format!("kind {} does not match header", payload[1])));
+        }
+        let (signed, sig_bytes) = payload.split_at(payload.len() - SIGNATURE_LEN);
+        let signature = Signature::from_slice(sig_bytes)
+            .map_err(|_| reject("malformed signature".to_string()))?;
+        peer.verifying
+            .verify(&self.transcript(msg, signed), &signature)
+            .map_err(|_| reject("signature does not verify".to_string()))?;
+
+        // Authentic from here on: anything wrong is the sender's doing.
+        let slot = (from.to_string(), msg.to_party_id.clone(), msg.round);
+        let digest: [u8; 32] = Sha256::digest(signed).into();
+        if let Some(previous) = self.accepted.get(&slot) {
+            ensure!(
+                *previous == digest,
+                error::MessageEquivocationSnafu {
+                    party: from,
+                    round: msg.round,
+                }
+            );
+            return Ok(None);
+        }
+
+        let body = &signed[2..];
+        let plaintext = if msg.is_broadcast() {
+            body.to_vec()
+        } else {
+            self.decrypt(msg, body).ok_or_else(|| {
+                error::MessageUndecryptableSnafu {
+                    party: from,
+                    round: msg.round,
+                }
+                .build()
+            })?
+        };
+        self.accepted.insert(slot, digest);
+        Ok(Some(Message {
+            payload: plaintext,
+            ..msg.clone()
+        }))
+    }
+
+    fn decrypt(&self, msg: &Message, body: &[u8]) -> Option<Vec<u8>> {
+        if body.len() < NONCE_LEN + TAG_LEN {
+            return None;
+        }
+        let key = self
+            .pairwise_key(&msg.from_party_id, msg.to_party_id.as_deref()?)
+            .ok()?;
+        let cipher = Aes256Gcm::new_from_slice(key.as_slice()).ok()?;
+        let (nonce_bytes, rest) = body.split_at(NONCE_LEN);
+        let (ciphertext, tag_bytes) = rest.split_at(rest.len() - TAG_LEN);
+        let nonce = Nonce::from(<[u8; NONCE_LEN]>::try_from(nonce_bytes).ok()?);
+        let tag = aes_gcm::Tag::from(<[u8; TAG_LEN]>::try_from(tag_bytes).ok()?);
+        let mut buffer = ciphertext.to_vec();
+        cipher
+            .decrypt_inout_detached(
+                &nonce,
+                &self.header(msg),
+                InOutBuf::from(buffer.as_mut_slice()),
+                &tag,
+            )
+            .ok()?;
+        Some(buffer)
+    }
+
+    /// Direction-specific AEAD key for `from -> to`. Either endpoint
+    /// derives the same key; the reverse direction gets a different one.
+    fn pairwise_key(&self, from: &str, to: &str) -> Result<Zeroizing<[u8; 32]>> {
+        let peer = if from == self.our_id { to } else { from };
+        let shared = self.shared.get(peer).ok_or_else(|| {
+            error::MissingPartyIdentitySnafu {
+                party: peer.to_string(),
+            }
+            .build()
+        })?;
+        // HKDF-SHA256 (RFC 5869), one block of expand output.
+        let mut extract =
+            HmacSha256::new_from_slice(&self.auth.session_id).expect("HMAC accepts any key length");
+        extract.update(shared.as_slice());
+        let prk = Zeroizing::new(extract.finalize().into_bytes());
+        let mut expand = HmacSha256::new_from_slice(&prk).expect("HMAC accepts any key length");
+        let mut info = DOMAIN.to_vec();
+        put(&mut info, from.as_bytes());
+        put(&mut info, to.as_bytes());
+        expand.update(&info);
+        expand.update(&[1]);
+        let mut key = Zeroizing::new([0u8; 32]);
+        key.copy_from_slice(&expand.finalize().into_bytes());
+        Ok(key)
+    }
+
+    /// Scheme, session, sender, recipient and round — the part of the
+    /// transcript that is also the AEAD associated data.
+    fn header(&self, msg: &Message) -> Vec<u8> {
+        let mut out = DOMAIN.to_vec();
+        put(&mut out, self.scheme.as_bytes());
+        put(&mut out, &self.auth.session_id);
+        put(&mut out, msg.from_party_id.as_bytes());
+        match &msg.to_party_id {
+            None => out.push(0),
+            Some(to) => {
+                out.push(1);
+                put(&mut out, to.as_bytes());
+            }
+        }
+        out.push(msg.round);
+        out
+    }
+
+    fn transcript(&self, msg: &Message, signed_payload: &[u8]) -> Vec<u8> {
+        let mut out = self.header(msg);
+        put(&mut out, signed_payload);
+        out
+    }
+}
+
+impl fmt::Debug for Authenticator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Authenticator")
+            .field("auth", &self.auth)
+            .field("scheme", &self.scheme)
+            .field("our_id", &self.our_id)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Length-prefixed append, so adjacent fields cannot run into each
+/// other.
+fn put(out: &mut Vec<u8>, field: &[u8]) {
+    out.extend_from_slice(&(field.len() as u32).to_be_bytes());
+    out.extend_from_slice(field);
+}
+
+/// Static-static ECDH; the x-coordinate of `secret * public`.
+fn ecdh(secret: &SecretKey, public: &PublicKey) -> Zeroizing<[u8; 32]> {
+    let point = (public.to_projective() * *secret.to_nonzero_scalar()).to_affine();
+    let encoded = point.to_sec1_point(false);
+    let mut x = Zeroizing::new([0u8; 32]);
+    x.copy_from_slice(&encoded.as_bytes()[1..33]);
+    x
+}
+
+/// Draw scalars from the OS RNG until `build` accepts one (the
+/// rejection probability for P-256 is about 2^-32).
+fn random_key<T>(build: impl Fn(&FieldBytes) -> Option<T>) -> T {
+    loop {
+        let mut buf = Zeroizing::new([0u8; 32]);
+        UnwrapErr(getrandom::SysRng).fill_bytes(buf.as_mut_slice());
+        if let Some(key) = build(&FieldBytes::from(*buf)) {
+            return key;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::party::Party;
+
+    const IDS: [&str; 3] = ["a", "b", "c"];
+
+    fn roster() -> PartyList {
+        PartyList::from_parties(IDS.iter().map(|id| Party::inproc(*id)).collect())
+    }
+
+    /// One authenticator per roster party, all pinned to each other.
+    fn authenticators(session_id: &[u8]) -> Vec<Authenticator> {
+        let keys: Vec<IdentityKey> = IDS.iter().map(|_| IdentityKey::generate()).collect();
+        let publics: Vec<PublicIdentity> = keys.iter().map(IdentityKey::public).collect();
+        keys.into_iter()
+            .enumerate()
+            .map(|(idx, key)| {
+                let mut auth = SessionAuth::new(session_id, key);
+                for (id, public) in IDS.iter().zip(&publics) {
+                    auth.add_peer(*id, public.clone());
+                }
+                Authenticator::new(auth, "test-scheme", &roster(), idx).expect("authenticator")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn broadcast_round_trips() {
+        let mut auths = authenticators(b"sid");
+        let sealed = auths[0]
+            .seal(&Message::broadcast("a", 1, b"commit".to_vec()))
+            .expect("seal");
+        assert_eq!(sealed.from_party_id, "a");
+        assert!(sealed.is_broadcast());
+        let opened = auths[1].open(&sealed, 2).expect("open").expect("fresh");
+        assert_eq!(opened.payload, b"commit");
+    }
+
+    #[test]
+    fn directed_payload_is_encrypted() {
+        let mut auths = authenticators(b"sid");
+        let secret = b"share-for-b-share-for-b".to_vec();
+        let sealed = auths[0]
+            .seal(&Message::directed("a", "b", 1, secret.clone()))
+            .expect("seal");
+        assert!(
+            !sealed
+                .payload
+                .windows(secret.len())
+                .any(|w| w == secret.as_slice()),
+            "directed payload must not travel in the clear"
+        );
+        let opened = auths[1].open(&sealed, 2).expect("open").expect("fresh");
+        assert_eq!(opened.payload, secret);
+    }
+
+    #[test]
+    fn tampered_payload_names_sender() {
+        let mut auths = authenticators(b"sid");
+        let mut sealed = auths[0]
+            .seal(&Message::broadcast("a", 1, b"commit".to_vec()))
+            .expect("seal");
+        sealed.payload[3] ^= 0x01;
+        let err = auths[1].open(&sealed, 2).unwrap_err();
+        assert!(matches!(err, error::Error::MessageRejected { .. }));
+        assert_eq!(err.culprit(), Some("a"));
+    }
+
+    #[test]
+    fn impersonation_is_rejected() {
+        let mut auths = authenticators(b"sid");
+        // c seals a message and relabels it as coming from a.
+        let mut sealed = auths[2]
+            .seal(&Message::broadcast("c", 1, b"x".to_vec()))
+            .expect("seal");
+        sealed.from_party_id = "a".to_string();
+        let err = auths[1].open(&sealed, 2).unwrap_err();
+        assert!(matches!(err, error::Error::MessageRejected { .. }));
+    }
+
+    #[test]
+    fn other_session_is_rejected() {
+        let auths = authenticators(b"session-one");
+        let mut others = authenticators(b"session-two");
+        let sealed = auths[0]
+            .seal(&Message::broadcast("a", 1, b"x".to_vec()))
+            .expect("seal");
+        assert!(others[1].open(&sealed, 2).is_err());
+    }
+
+    #[test]
+    fn recipient_and_round_are_bound() {
+        let mut auths = authenticators(b"sid");
+        let sealed = auths[0]
+            .seal(&Message::directed("a", "b", 1, b"x".to_vec()))
+            .expect("seal");
+
+        // Redirected to c.
+        let mut redirected = sealed.clone();
+        redirected.to_party_id = Some("c".to_string());
+        assert!(auths[2].open(&redirected, 2).is_err());
+
+        // Relabelled as a later round.
+        let mut bumped = sealed.clone();
+        bumped.round = 2;
+        assert!(auths[1].open(&bumped, 3).is_err());
+
+        // Delivered too early.
+        let err = auths[1].open(&sealed, 1).unwrap_err();
+        assert!(matches!(
+            err,
+            error::Error::MessageRejected { round: 1, .. }
+        ));
+    }
+
+    #[test]
+    fn exact_replay_is_dropped() {
+        let mut auths = authenticators(b"sid");
+        let sealed = auths[0]
+            .seal(&Message::broadcast("a", 1, b"x".to_vec()))
+            .expect("seal");
+        assert!(auths[1].open(&sealed, 2).expect("first").is_some());
+        assert!(auths[1].open(&sealed, 2).expect("retransmit").is_none());
+        assert!(auths[1].open(&sealed, 3).expect("late replay").is_none());
+    }
+
+    #[test]
+    fn malleated_signature_is_a_replay_not_equivocation() {
+        let mut auths = authenticators(b"sid");
+        let sealed = auths[0]
+            .seal(&Message::broadcast("a", 1, b"x".to_vec()))
+            .expect("seal");
+        // (r, n - s) verifies as well as (r, s).
+        let split = sealed.payload.len() - SIGNATURE_LEN;
+        let (r, s) = Signature::from_slice(&sealed.payload[split..])
+            .expect("signature")
+            .split_scalars();
+        let flipped = Signature::from_scalars(r, -s).expect("flipped signature");
+        let mut malleated = sealed.clone();
+        malleated.payload.truncate(split);
+        malleated.payload.extend_from_slice(&flipped.to_bytes());
+        assert_ne!(malleated.payload, sealed.payload);
+
+        assert!(auths[1].open(&sealed, 2).expect("first").is_some());
+        assert!(
+            auths[1]
+                .open(&malleated, 2)
+                .expect("malleated copy is not equivocation")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn equivocation_names_sender() {
+        let mut auths = authenticators(b"sid");
+        let first = auths[0]
+            .seal(&Message::broadcast("a", 1, b"one".to_vec()))
+            .expect("seal");
+        let second = auths[0]
+            .seal(&Message::broadcast("a", 1, b"two".to_vec()))
+            .expect("seal");
+        auths[1].open(&first, 2).expect("first");
+        let err = auths[1].open(&second, 2).unwrap_err();
+        assert!(matches!(
+            err,
+            error::Error::MessageEquivocation { round: 1, .. }
+        ));
+        assert_eq!(err.culprit(), Some("a"));
+    }
+
+    #[test]
+    fn seal_refuses_foreign_sender() {
+        let auths = authenticators(b"sid");
+        let err = auths[0]
+            .seal(&Message::broadcast("b", 1, b"x".to_vec()))
+            .unwrap_err();
+        assert!(matches!(err, error::Error::ForgedSender { .. }));
+        assert_eq!(err.culprit(), None);
+    }
+
+    #[test]
+    fn missing_peer_identity_errors() {
+        let auth = SessionAuth::new(b"sid".to_vec(), IdentityKey::generate())
+            .with_peer("b", IdentityKey::generate().public());
+        let err = Authenticator::new(auth, "test-scheme", &roster(), 0)
+            .expect_err("c has no pinned identity");
+        assert!(matches!(
+            err,
+            error::Error::MissingPartyIdentity { ref party, .. } if party == "c"
+        ));
+    }
+
+    #[test]
+    fn identity_bytes_round_trip() {
+        let key = IdentityKey::generate();
+        let restored = IdentityKey::from_bytes(&key.to_bytes()).expect("restore");
+        assert_eq!(restored.public(), key.public());
+
+        let public = key.public();
+        assert_eq!(
+            PublicIdentity::from_bytes(&public.to_bytes()).expect("parse"),
+            public
+        );
+        assert!(PublicIdentity::from_bytes(&[0u8; 10]).is_err());
+        assert!(IdentityKey::from_bytes(&[0u8; 64]).is_err());
+    }
+}
@@ -0,0 +1,513 @@
+//! Echo broadcast — consistent delivery of broadcast messages.
+//!
+//! A broadcast [`Message`] (`to_party_id == None`) is only a promise:
+//! the transport hands each recipient a copy, and a malicious sender
+//! can hand different recipients different copies. Commitment rounds
+//! (FROST, CMP20, GG18) are only sound if every honest party saw the
+//! same commitments.
+//!
+//! [`EchoBroadcast`] wraps any [`SessionImpl`] and follows each of its
+//! rounds with an echo round:
+//!
+//! 1. The scheme's round output goes out as *data frames*. A frame
+//!    records whether the scheme meant it as a broadcast, so a sender
+//!    that turns one broadcast into differing point-to-point copies is
+//!    still caught.
+//! 2. On receipt, the wrapper holds the scheme messages back, hashes
+//!    every sender's broadcast payloads, and broadcasts the digests as
+//!    an *echo frame*.
+//! 3. Once the echoes are in, every peer's view of every sender is
+//!    compared with the local one. Any difference — a different digest,
+//!    or a broadcast one peer saw and another did not — aborts with
+//!    [`crate::Error::BroadcastEquivocation`], naming the sender whose
+//!    broadcast is disputed and the peer whose echo disputed it.
+//!    Otherwise the held messages reach the scheme and the next round
+//!    starts.
+//!
+//! An echo carries digests, not the sender's signed frames, so a
+//! dispute does not show who lied: the sender may have equivocated,
+//! or the witness may have echoed a digest it never saw. The abort
+//! therefore has no [`crate::Error::culprit`]; it guarantees that no
+//! honest party goes on with an inconsistent view, not that the liar
+//! is identified.
+//!
+//! The wrapper is transparent to the scheme: it sees the messages and
+//! round numbers it emitted. On the wire, each frame's `round` is the
+//! framework step it was sent in, so a scheme with R rounds takes
+//! 2R - 1 steps. Combine with [`crate::session::Session::create_authenticated`]
+//! so echoes cannot be forged on behalf of an honest peer.
+//!
+//! Schemes opt in through [`crate::registry::TcScheme::reliable_broadcast`].
+//! A peer whose echo frame never arrives is not an error here — a
+//! threshold scheme decides for itself whether it can go on without
+//! that party.
+
+use std::collections::{BTreeMap, HashMap};
+
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+use crate::Result;
+use crate::error;
+use crate::message::Message;
+use crate::registry::{RoundResult, SessionImpl};
+use crate::snapshot::{self, StateReader, StateWriter};
+
+/// Domain separator for broadcast digests.
+const DOMAIN: &[u8] = b"confium-tc-echo-v1";
+
+const FRAME_DATA: u8 = 0;
+const FRAME_ECHO: u8 = 1;
+
+/// What the wrapper expects from the next step's incoming messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    /// Nothing held back: run the scheme round.
+    Run,
+    /// Data frames from the scheme's last round.
+    AwaitData,
+    /// Echo frames covering the held data.
+    AwaitEcho,
+}
+
+/// Echo-broadcast wrapper around one party's [`SessionImpl`].
+pub struct EchoBroadcast {
+    inner: Box<dyn SessionImpl>,
+    our_id: String,
+    step: u8,
+    phase: Phase,
+    /// Scheme messages received but not yet released to the scheme.
+    held: Vec<Message>,
+    /// Digest of each sender's broadcasts in `held`.
+    digests: BTreeMap<String, [u8; 32]>,
+}
+
+impl EchoBroadcast {
+    pub fn new(inner: Box<dyn SessionImpl>, our_id: impl Into<String>) -> Self {
+        EchoBroadcast {
+            inner,
+            our_id: our_id.into(),
+            step: 0,
+            phase: Phase::Run,
+            held: Vec::new(),
+            digests: BTreeMap::new(),
+        }
+    }
+
+    /// Rebuild a wrapper from its [`SessionImpl::snapshot`] output.
+    /// `restore_inner` rebuilds the wrapped scheme session from the
+    /// inner snapshot embedded in `state`.
+    pub fn restore(
+        state: &[u8],
+        our_id: impl Into<String>,
+        restore_inner: impl FnOnce(&[u8]) -> Result<Box<dyn SessionImpl>>,
+    ) -> Result<Self> {
+        let mut r = StateReader::new(state);
+        let step = r.u8()?;
+        let phase = match r.u8()? {
+            0 => Phase::Run,
+            1 => Phase::AwaitData,
+            2 => Phase::AwaitEcho,
+            _ => return Err(snapshot::invalid("bad echo-broadcast phase")),
+        };
+        let held = r.messages()?;
+        let mut digests = BTreeMap::new();
+        for _ in 0..r.u32()? {
+            let sender = r.string()?;
+            digests.insert(sender, r.array()?);
+        }
+        let inner = restore_inner(r.bytes()?)?;
+        r.finish()?;
+        Ok(EchoBroadcast {
+            inner,
+            our_id: our_id.into(),
+            step,
+            phase,
+            held,
+            digests,
+        })
+    }
+
+    fn run_inner(&mut self) -> Result<RoundResult> {
+        let held = std::mem::take(&mut self.held);
+        self.digests.clear();
+        let res = self.inner.round(&held)?;
+        let outgoing = res
+            .outgoing
+            .iter()
+            .map(|msg| {
+                let mut payload = vec![FRAME_DATA, msg.round, u8::from(msg.is_broadcast())];
+                payload.extend_from_slice(&msg.payload);
+                Message {
+                    payload,
+                    round: self.step,
+                    ..msg.clone()
+                }
+            })
+            .collect();
+        self.phase = Phase::AwaitData;
+        Ok(RoundResult::new(outgoing, res.complete))
+    }
+
+    /// Unpack the data frames sent last step and answer with an echo.
+    fn receive_data(&mut self, incoming: &[Message]) -> Result<RoundResult> {
+        let mut broadcasts: BTreeMap<(String, u8), Vec<&[u8]>> = BTreeMap::new();
+        for msg in self.frames(incoming, FRAME_DATA)? {
+            let Some(&[inner_round, flag]) = msg.payload.get(1..3) else {
+                return Err(reject(msg, "data frame truncated"));
+            };
+            let body = &msg.payload[3..];
+            match flag {
+                0 => {}
+                1 => broadcasts
+                    .entry((msg.from_party_id.clone(), inner_round))
+                    .or_default()
+                    .push(body),
+                _ => return Err(reject(msg, "bad broadcast flag")),
+            }
+            self.held.push(Message {
+                from_party_id: msg.from_party_id.clone(),
+                to_party_id: msg.to_party_id.clone(),
+                round: inner_round,
+                payload: body.to_vec(),
+            });
+        }
+
+        let mut echo = vec![FRAME_ECHO];
+        for ((sender, inner_round), mut payloads) in broadcasts {
+            payloads.sort_unstable();
+            let mut h = Sha256::new();
+            h.update(DOMAIN);
+            h.update([inner_round]);
+            put(&mut h, sender.as_bytes());
+            for payload in payloads {
+                put(&mut h, payload);
+            }
+            let digest: [u8; 32] = h.finalize().into();
+            echo.extend_from_slice(&(sender.len() as u32).to_be_bytes());
+            echo.extend_from_slice(sender.as_bytes());
+            echo.extend_from_slice(&digest);
+            self.digests.insert(sender, digest);
+        }
+        self.phase = Phase::AwaitEcho;
+        Ok(RoundResult::new(
+            vec![Message::broadcast(&self.our_id, self.step, echo)],
+            false,
+        ))
+    }
+
+    /// Compare every peer's echo with our own view.
+    fn check_echoes(&self, incoming: &[Message]) -> Result<()> {
+        let held_round = self.held.first().map(|m| m.round).unwrap_or(0);
+        for msg in self.frames(incoming, FRAME_ECHO)? {
+            let witness = msg.from_party_id.as_str();
+            let view =
+                parse_echo(&msg.payload[1..]).ok_or_else(|| reject(msg, "malformed echo"))?;
+            let disputed = view
+                .iter()
+                .filter(|(sender, _)| sender.as_str() != self.our_id)
+                .find(|(sender, digest)| self.digests.get(sender.as_str()) != Some(digest))
+                .map(|(sender, _)| sender.as_str())
+                .or_else(|| {
+                    self.digests
+                        .keys()
+                        .find(|sender| sender.as_str() != witness && !view.contains_key(*sender))
+                        .map(String::as_str)
+                });
+            if let Some(sender) = disputed {
+                return error::BroadcastEquivocationSnafu {
+                    party: sender,
+                    witness,
+                    round: held_round,
+                }
+                .fail();
+            }
+        }
+        Ok(())
+    }
+
+    /// The frames of `kind` sent during the previous step. Older frames
+    /// (late or replayed) are skipped; a frame of the wrong kind for
+    /// the step is a protocol violation by its sender.
+    fn frames<'a>(&self, incoming: &'a [Message], kind: u8) -> Result<Vec<&'a Message>> {
+        let mut out = Vec::new();
+        for msg in incoming {
+            if msg.round != self.step - 1 || msg.from_party_id == self.our_id {
+                continue;
+            }
+            match msg.payload.first() {
+                Some(k) if *k == kind => out.push(msg),
+                _ => return Err(reject(msg, "unexpected frame kind")),
+            }
+        }
+        Ok(out)
+    }
+}
+
+impl SessionImpl for EchoBroadcast {
+    fn round(&mut self, incoming: &[Message]) -> Result<RoundResult> {
+        self.step = self
+            .step
+            .checked_add(1)
+            .ok_or_else(|| error::RoundOverflowSnafu { round: self.step }.build())?;
+        match self.phase {
+            Phase::Run => self.run_inner(),
+            Phase::AwaitData => self.receive_data(incoming),
+            Phase::AwaitEcho => {
+                self.check_echoes(incoming)?;
+                self.run_inner()
+            }
+        }
+    }
+
+    fn result(&self) -> Result<Vec<u8>> {
+        self.inner.result()
+    }
+
+    fn destroy(&mut self) {
+        self.held.clear();
+        self.inner.destroy();
+    }
+
+    fn snapshot(&self) -> Option<Zeroizing<Vec<u8>>> {
+        let inner = self.inner.snapshot()?;
+        let mut w = StateWriter::new();
+        w.u8(self.step);
+        w.u8(match self.phase {
+            Phase::Run => 0,
+            Phase::AwaitData => 1,
+            Phase::AwaitEcho => 2,
+        });
+        w.messages(&self.held);
+        w.u32(self.digests.len() as u32);
+        for (sender, digest) in &self.digests {
+            w.str(sender);
+            w.fixed(digest);
+        }
+        w.bytes(&inner);
+        Some(w.finish())
+    }
+
+    /// The wrapped scheme's nonce, on the steps that run a scheme
+    /// round.
+    fn pending_nonce(&self) -> Option<Vec<u8>> {
+        match self.phase {
+            Phase::Run | Phase::AwaitEcho => self.inner.pending_nonce(),
+            Phase::AwaitData => None,
+        }
+    }
+}
+
+fn reject(msg: &Message, reason: &str) -> error::Error {
+    error::MessageRejectedSnafu {
+        party: msg.from_party_id.as_str(),
+        round: msg.round,
+        reason,
+    }
+    .build()
+}
+
+fn put(h: &mut Sha256, field: &[u8]) {
+    h.update((field.len() as u32).to_be_bytes());
+    h.update(field);
+}
+
+/// Decode an echo body: repeated `len (4) || sender || digest (32)`.
+fn parse_echo(mut bytes: &[u8]) -> Option<HashMap<String, [u8; 32]>> {
+    let mut view = HashMap::new();
+    while !bytes.is_empty() {
+        let len = u32::from_be_bytes(bytes.get(..4)?.try_into().ok()?) as usize;
+        let sender = std::str::from_utf8(bytes.get(4..4 + len)?).ok()?;
+        let digest: [u8; 32] = bytes.get(4 + len..36 + len)?.try_into().ok()?;
+        if view.insert(sender.to_string(), digest).is_some() {
+            return None;
+        }
+        bytes = &bytes[36 + len..];
+    }
+    Some(view)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Round 1 broadcasts `payload`; round 2 records what arrived and
+    /// completes.
+    struct Commit {
+        id: String,
+        payload: Vec<u8>,
+        seen: Vec<Message>,
+        done: bool,
+    }
+
+    impl SessionImpl for Commit {
+        fn round(&mut self, incoming: &[Message]) -> Result<RoundResult> {
+            if self.seen.is_empty() && incoming.is_empty() && !self.done {
+                self.done = true;
+                let msg = Message::broadcast(&self.id, 1, self.payload.clone());
+                return Ok(RoundResult::new(vec![msg], false));
+            }
+            self.seen = incoming.to_vec();
+            Ok(RoundResult::done())
+        }
+        fn result(&self) -> Result<Vec<u8>> {
+            Ok(self.seen.iter().flat_map(|m| m.payload.clone()).collect())
+        }
+        fn destroy(&mut self) {}
+    }
+
+    fn party(id: &str) -> EchoBroadcast {
+        let inner = Commit {
+            id: id.to_string(),
+            payload: id.as_bytes().to_vec(),
+            seen: Vec::new(),
+            done: false,
+        };
+        EchoBroadcast::new(Box::new(inner), id)
+    }
+
+    fn for_party(msgs: &[Message], id: &str) -> Vec<Message> {
+        msgs.iter()
+            .filter(|m| m.is_for(id) && m.from_party_id != id)
+            .cloned()
+            .collect()
+    }
+
+    /// Drive every party one step on the messages routed last step.
+    fn step(parties: &mut [EchoBroadcast], inbox: &[Message]) -> Result<(Vec<Message>, Vec<bool>)> {
+        let mut out = Vec::new();
+        let mut complete = Vec::new();
+        for p in parties.iter_mut() {
+            let id = p.our_id.clone();
+            let rr = p.round(&for_party(inbox, &id))?;
+            out.extend(rr.outgoing);
+            complete.push(rr.complete);
+        }
+        Ok((out, complete))
+    }
+
+    #[test]
+    fn honest_broadcast_is_delivered_after_echo_round() {
+        let mut parties = vec![party("a"), party("b"), party("c")];
+        let (data, _) = step(&mut parties, &[]).expect("step 1");
+        assert!(
+            data.iter()
+                .all(|m| m.round == 1 && m.payload[0] == FRAME_DATA)
+        );
+        let (echoes, _) = step(&mut parties, &data).expect("step 2");
+        assert!(echoes.iter().all(|m| m.payload[0] == FRAME_ECHO));
+        let (_, complete) = step(&mut parties, &echoes).expect("step 3");
+        assert!(complete.iter().all(|c| *c));
+        // The scheme saw its own round number and bare payloads.
+        let seen = parties[0].inner.result().expect("result");
+        assert_eq!(seen, b"bc");
+    }
+
+    #[test]
+    fn differing_copies_are_disputed() {
+        let mut parties = vec![party("a"), party("b"), party("c")];
+        let (data, _) = step(&mut parties, &[]).expect("step 1");
+        // "a" sends "b" and "c" different versions of its broadcast.
+        let mut routed: Vec<Message> = data
+            .iter()
+            .filter(|m| m.from_party_id != "a")
+            .cloned()
+            .collect();
+        let original = data.iter().find(|m| m.from_party_id == "a").unwrap();
+        for (to, last) in [("b", b'a'), ("c", b'z')] {
+            let mut copy = original.clone();
+            copy.to_party_id = Some(to.to_string());
+            *copy.payload.last_mut().unwrap() = last;
+            routed.push(copy);
+        }
+        let (echoes, _) = step(&mut parties, &routed).expect("step 2");
+        let err = step(&mut parties, &echoes).unwrap_err();
+        assert!(matches!(
+            err,
+            error::Error::BroadcastEquivocation { ref party, round: 1, .. } if party == "a"
+        ));
+        assert_eq!(err.culprit(), None);
+    }
+
+    #[test]
+    fn selective_broadcast_is_disputed() {
+        let mut parties = vec![party("a"), party("b"), party("c")];
+        let (data, _) = step(&mut parties, &[]).expect("step 1");
+        // "a" only sends its broadcast to "b".
+        let routed: Vec<Message> = data
+            .iter()
+            .cloned()
+            .map(|mut m| {
+                if m.from_party_id == "a" {
+                    m.to_party_id = Some("b".to_string());
+                }
+                m
+            })
+            .collect();
+        let (echoes, _) = step(&mut parties, &routed).expect("step 2");
+        let err = step(&mut parties, &echoes).unwrap_err();
+        assert!(matches!(
+            err,
+            error::Error::BroadcastEquivocation { ref party, .. } if party == "a"
+        ));
+    }
+
+    #[test]
+    fn lying_witness_does_not_frame_an_honest_sender() {
+        let mut parties = vec![party("a"), party("b"), party("c")];
+        let (data, _) = step(&mut parties, &[]).expect("step 1");
+        let (mut echoes, _) = step(&mut parties, &data).expect("step 2");
+        // Everyone broadcast honestly, but "c" echoes a digest for "a"
+        // that it never saw.
+        let lie = echoes
+            .iter_mut()
+            .find(|m| m.from_party_id == "c")
+            .expect("c echoed");
+        let view = parse_echo(&lie.payload[1..]).expect("well-formed echo");
+        let mut forged = vec![FRAME_ECHO];
+        for (sender, mut digest) in view {
+            if sender == "a" {
+                digest[0] ^= 0x01;
+            }
+            forged.extend_from_slice(&(sender.len() as u32).to_be_bytes());
+            forged.extend_from_slice(sender.as_bytes());
+            forged.extend_from_slice(&digest);
+        }
+        lie.payload = forged;
+
+        let err = parties[1].round(&for_party(&echoes, "b")).unwrap_err();
+        assert!(matches!(
+            err,
+            error::Error::BroadcastEquivocation { ref party, ref witness, round: 1, .. }
+                if party == "a" && witness == "c"
+        ));
+        assert_eq!(err.culprit(), None, "an echo is no evidence against 'a'");
+    }
+
+    #[test]
+    fn wrong_frame_kind_is_rejected() {
+        let mut parties = vec![party("a"), party("b")];
+        let (data, _) = step(&mut parties, &[]).expect("step 1");
+        step(&mut parties, &data).expect("step 2");
+        // Replaying data frames where echoes are due.
+        let mut relabelled = data.clone();
+        for m in &mut relabelled {
+            m.round = 2;
+        }
+        let err = parties[1].round(&for_party(&relabelled, "b")).unwrap_err();
+        assert!(matches!(err, error::Error::MessageRejected { .. }));
+    }
+
+    #[test]
+    fn parse_echo_rejects_duplicates_and_truncation() {
+        let mut body = Vec::new();
+        for _ in 0..2 {
+            body.extend_from_slice(&1u32.to_be_bytes());
+            body.push(b'a');
+            body.extend_from_slice(&[0u8; 32]);
+        }
+        assert!(parse_echo(&body).is_none());
+        assert!(parse_echo(&body[..10]).is_none());
+        assert_eq!(parse_echo(&body[..37]).map(|v| v.len()), Some(1));
+    }
+}
@@ -89,6 +89,81 @@ pub enum Error {
     InsufficientBuffer { backtrace: Backtrace },
     #[snafu(display("Scheme plugin returned error code {}", code))]
     SchemeInternalError { code: u32, backtrace: Backtrace },
+
+    #[snafu(display("Round {} message from '{}' rejected: {}", round, party, reason))]
+    MessageRejected {
+        party: String,
+        round: u8,
+        reason: String,
+        backtrace: Backtrace,
+    },
+    #[snafu(display(
+        "Party '{}' sent two different signed messages for round {}",
+        party,
+        round
+    ))]
+    MessageEquivocation {
+        party: String,
+        round: u8,
+        backtrace: Backtrace,
+    },
+    #[snafu(display("Signed round {} message from '{}' does not decrypt", round, party))]
+    MessageUndecryptable {
+        party: String,
+        round: u8,
+        backtrace: Backtrace,
+    },
+    /// Two parties disagree on what `party` broadcast. Either `party`
+    /// equivocated or `witness` lied in its echo; the echo round
+    /// cannot tell which, so this names no culprit.
+    #[snafu(display(
+        "Round {} broadcast from '{}' is disputed by '{}'",
+        round,
+        party,
+        witness
+    ))]
+    BroadcastEquivocation {
+        party: String,
+        witness: String,
+        round: u8,
+        backtrace: Backtrace,
+    },
+    #[snafu(display("No identity key pinned for party '{}'", party))]
+    MissingPartyIdentity { party: String, backtrace: Backtrace },
+    #[snafu(display("Scheme emitted a message as '{}' from party '{}'", actual, expected))]
+    ForgedSender {
+        expected: String,
+        actual: String,
+        backtrace: Backtrace,
+    },
+    #[snafu(display("Invalid identity key: {}", reason))]
+    InvalidIdentityKey {
+        reason: String,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Scheme '{}' does not support session snapshots", scheme))]
+    SnapshotUnsupported {
+        scheme: String,
+        backtrace: Backtrace,
+    },
+    #[snafu(display("Session snapshot rejected: {}", reason))]
+    SnapshotInvalid {
+        reason: String,
+        backtrace: Backtrace,
+    },
+    #[snafu(display("Scheme '{}' nonce is already spent", scheme))]
+    NonceReuse {
+        scheme: String,
+        backtrace: Backtrace,
+    },
+    #[snafu(display("Session snapshots need a nonce ledger"))]
+    NonceLedgerRequired { backtrace: Backtrace },
+    #[snafu(display("Nonce ledger I/O failed"))]
+    NonceLedger {
+        backtrace: Backtrace,
+        source: std::io::Error,
+    },
 }
 
 impl Error {
@@ -96,6 +171,18 @@ impl Error {
     pub fn code(&self) -> u32 {
         error_code(self)
     }
+
+    /// The party a message-authentication failure is attributed to, for
+    /// identifiable abort. See [`crate::auth`] for which variants carry
+    /// transferable evidence.
+    pub fn culprit(&self) -> Option<&str> {
+        match self {
+            Error::MessageRejected { party, .. }
+            | Error::MessageEquivocation { party, .. }
+            | Error::MessageUndecryptable { party, .. } => Some(party),
+            _ => None,
+        }
+    }
 }
 
 /// Numeric error codes for the `cfm_tc_*` ABI. These are deliberately
@@ -128,6 +215,20 @@ pub enum ErrorCode {
 
     INSUFFICIENT_BUFFER = 0x1040,
     SCHEME_INTERNAL_ERROR = 0x1041,
+
+    MESSAGE_REJECTED = 0x1050,
+    MESSAGE_EQUIVOCATION = 0x1051,
+    MESSAGE_UNDECRYPTABLE = 0x1052,
+    MISSING_PARTY_IDENTITY = 0x1053,
+    FORGED_SENDER = 0x1054,
+    INVALID_IDENTITY_KEY = 0x1055,
+    BROADCAST_EQUIVOCATION = 0x1056,
+
+    SNAPSHOT_UNSUPPORTED = 0x1060,
+    SNAPSHOT_INVALID = 0x1061,
+    NONCE_REUSE = 0x1062,
+    NONCE_LEDGER_REQUIRED = 0x1063,
+    NONCE_LEDGER = 0x1064,
 }
 
 fn error_code(error: &Error) -> u32 {
@@ -155,6 +256,20 @@ fn error_code(error: &Error) -> u32 {
 
         Error::InsufficientBuffer { .. } => ErrorCode::INSUFFICIENT_BUFFER.into(),
         Error::SchemeInternalError { .. } => ErrorCode::SCHEME_INTERNAL_ERROR.into(),
+
+        Error::MessageRejected { .. } => ErrorCode::MESSAGE_REJECTED.into(),
+        Error::MessageEquivocation { .. } => ErrorCode::MESSAGE_EQUIVOCATION.into(),
+        Error::MessageUndecryptable { .. } => ErrorCode::MESSAGE_UNDECRYPTABLE.into(),
+        Error::MissingPartyIdentity { .. } => ErrorCode::MISSING_PARTY_IDENTITY.into(),
+        Error::ForgedSender { .. } => ErrorCode::FORGED_SENDER.into(),
+        Error::InvalidIdentityKey { .. } => ErrorCode::INVALID_IDENTITY_KEY.into(),
+        Error::BroadcastEquivocation { .. } => ErrorCode::BROADCAST_EQUIVOCATION.into(),
+
+        Error::SnapshotUnsupported { .. } => ErrorCode::SNAPSHOT_UNSUPPORTED.into(),
+        Error::SnapshotInvalid { .. } => ErrorCode::SNAPSHOT_INVALID.into(),
+        Error::NonceReuse { .. } => ErrorCode::NONCE_REUSE.into(),
+        Error::NonceLedgerRequired { .. } => ErrorCode::NONCE_LEDGER_REQUIRED.into(),
+        Error::NonceLedger { .. } => ErrorCode::NONCE_LEDGER.into(),
     }
 }
 
@@ -11,6 +11,8 @@
 #![allow(rustdoc::private_intra_doc_links)]
 #![allow(rustdoc::invalid_html_tags)]
 
+pub mod auth;
+pub mod broadcast;
 pub mod commitment;
 pub mod error;
 pub mod error_codes;
@@ -23,8 +25,12 @@ pub mod session;
 pub mod share;
 pub mod share_adapter;
 pub mod share_envelope;
+pub mod snapshot;
 pub mod unified_error;
 
+pub use auth::IdentityKey;
+pub use auth::PublicIdentity;
+pub use auth::SessionAuth;
 pub use error::Error;
 pub use error::Result;
 pub use message::Message;
@@ -37,3 +43,4 @@ pub use registry::TcSchemeKind;
 pub use session::Session;
 pub use session::SessionParams;
 pub use share::Share;
+pub use snapshot::NonceLedger;
@@ -9,6 +9,11 @@
 //! [`Message`]s. Wiring [`Message`]s to a Network transport is a
 //! separate concern handled by the session driver (see
 //! `TODO.roadmap/05-networking-primitives.md`).
+//!
+//! A [`Message`] carries no authentication of its own. Sessions built
+//! with [`crate::session::Session::create_authenticated`] exchange them
+//! in sealed form — same header, signed (and for directed messages,
+//! encrypted) payload — see [`crate::auth`].
 
 use std::fmt;
 
@@ -16,7 +16,10 @@
 
 use std::fmt;
 
+use zeroize::Zeroizing;
+
 use crate::Result;
+use crate::error;
 use crate::message::Message;
 use crate::session::SessionParams;
 
@@ -52,6 +55,30 @@ pub trait TcScheme: Send + Sync {
     /// [`SessionImpl::round`] / [`SessionImpl::result`] /
     /// [`SessionImpl::destroy`].
     fn create_session(&self, params: &SessionParams) -> Result<Box<dyn SessionImpl>>;
+
+    /// Whether the framework should run this scheme's sessions behind
+    /// [`crate::broadcast::EchoBroadcast`], so every honest party is
+    /// guaranteed the same broadcast payloads. Costs one extra round per
+    /// scheme round; defaults to `false`.
+    fn reliable_broadcast(&self) -> bool {
+        false
+    }
+
+    /// Rebuild a session from [`SessionImpl::snapshot`] output, under
+    /// the same `params` it was created with. Schemes that do not
+    /// snapshot keep the default, which errors with
+    /// [`crate::Error::SnapshotUnsupported`].
+    fn restore_session(
+        &self,
+        params: &SessionParams,
+        state: &[u8],
+    ) -> Result<Box<dyn SessionImpl>> {
+        let _ = (params, state);
+        error::SnapshotUnsupportedSnafu {
+            scheme: self.name(),
+        }
+        .fail()
+    }
 }
 
 /// Per-session scheme state, driven round-by-round by the framework.
@@ -75,6 +102,24 @@ pub trait SessionImpl: Send {
     /// framework drops the session. Implementations should zeroize
     /// sensitive state.
     fn destroy(&mut self);
+
+    /// Serialise the state reached after the last completed round, for
+    /// [`TcScheme::restore_session`]. `None` (the default) means the
+    /// scheme does not support snapshots. Encode with
+    /// [`crate::snapshot::StateWriter`]; the framework seals the result.
+    fn snapshot(&self) -> Option<Zeroizing<Vec<u8>>> {
+        None
+    }
+
+    /// Identifier of the secret nonce the *next* [`SessionImpl::round`]
+    /// call will consume, if any — typically its public commitment.
+    /// The framework records it in the session's
+    /// [`crate::snapshot::NonceLedger`] before running that round, so a
+    /// restored snapshot can never use it a second time. Schemes that
+    /// snapshot a nonce must report it here.
+    fn pending_nonce(&self) -> Option<Vec<u8>> {
+        None
+    }
 }
 
 /// Outcome of one [`SessionImpl::round`] call.
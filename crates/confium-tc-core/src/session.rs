@@ -10,15 +10,28 @@
 //! framework layer above this is transport-agnostic — see
 //! `TODO.roadmap/05-networking-primitives.md` for how [`crate::message::Message`]s
 //! get moved between parties.
+//!
+//! A session built with [`Session::create_authenticated`] signs and
+//! verifies every message at the application layer (see
+//! [`crate::auth`]); the transport underneath can then be anything.
+//!
+//! Sessions of schemes that support it can be suspended with
+//! [`Session::snapshot`] and picked up again, in another process, with
+//! [`Session::resume`] — see [`crate::snapshot`].
+
+use std::sync::Arc;
 
 use snafu::ensure;
 
 use crate::Result;
+use crate::auth::{Authenticator, SessionAuth};
+use crate::broadcast::EchoBroadcast;
 use crate::error;
 use crate::message::Message;
 use crate::party::PartyList;
-use crate::registry::{self, RoundResult, SessionImpl, TcSchemeKind};
+use crate::registry::{self, RoundResult, SessionImpl, TcScheme, TcSchemeKind};
 use crate::share::Share;
+use crate::snapshot::{self, NonceLedger, StateReader, StateWriter};
 
 /// Parameters handed to [`Session::create`].
 ///
@@ -56,6 +69,13 @@ pub struct Session {
     round: u8,
     complete: bool,
     impl_: Box<dyn SessionImpl>,
+    auth: Option<Authenticator>,
+    /// Binds snapshots to the parameters the session was created with.
+    params_digest: [u8; 32],
+    /// What the last [`Session::round_step`] returned, kept so a
+    /// resumed session can send it again.
+    last_outgoing: Vec<Message>,
+    nonce_ledger: Option<Arc<dyn NonceLedger>>,
 }
 
 impl std::fmt::Debug for Session {
@@ -68,6 +88,8 @@ impl std::fmt::Debug for Session {
             .field("party_count", &self.party_count)
             .field("round", &self.round)
             .field("complete", &self.complete)
+            .field("authenticated", &self.auth.is_some())
+            .field("nonce_ledger", &self.nonce_ledger.is_some())
             .finish_non_exhaustive()
     }
 }
@@ -75,8 +97,22 @@ impl std::fmt::Debug for Session {
 impl Session {
     /// Resolve `params.scheme` against the link-time registry and build
     /// a fresh session. Validates the roster + threshold + index before
-    /// handing control to the scheme.
+    /// handing control to the scheme. Schemes that ask for
+    /// [`crate::registry::TcScheme::reliable_broadcast`] get their
+    /// session wrapped in [`EchoBroadcast`] here.
     pub fn create(params: &SessionParams) -> Result<Self> {
+        let scheme = Session::resolve(params)?;
+        let mut impl_ = scheme.create_session(params)?;
+        if scheme.reliable_broadcast() {
+            let our_id = &params.parties.get(params.this_party_idx)?.id;
+            impl_ = Box::new(EchoBroadcast::new(impl_, our_id.clone()));
+        }
+        Ok(Session::assemble(scheme, params, impl_))
+    }
+
+    /// Validate the roster, threshold, index and share, then look the
+    /// scheme up.
+    fn resolve(params: &SessionParams) -> Result<&'static dyn TcScheme> {
         params.parties.validate(params.threshold)?;
         ensure!(
             params.this_party_idx < params.parties.len(),
@@ -89,14 +125,20 @@ impl Session {
             share.assert_scheme(&params.scheme)?;
         }
 
-        let scheme = registry::find(&params.scheme).ok_or_else(|| {
+        registry::find(&params.scheme).ok_or_else(|| {
             error::SchemeNotFoundSnafu {
                 name: params.scheme.clone(),
             }
             .build()
-        })?;
-        let impl_ = scheme.create_session(params)?;
-        Ok(Session {
+        })
+    }
+
+    fn assemble(
+        scheme: &dyn TcScheme,
+        params: &SessionParams,
+        impl_: Box<dyn SessionImpl>,
+    ) -> Self {
+        Session {
             scheme_name: scheme.name().to_string(),
             scheme_kind: scheme.kind(),
             threshold: params.threshold,
@@ -105,7 +147,159 @@ impl Session {
             round: 0,
             complete: false,
             impl_,
-        })
+            auth: None,
+            params_digest: snapshot::params_digest(params),
+            last_outgoing: Vec::new(),
+            nonce_ledger: None,
+        }
+    }
+
+    /// Like [`Session::create`], but every message crossing
+    /// [`Session::round_step`] is authenticated under `auth`: incoming
+    /// messages are verified, de-duplicated and decrypted before the
+    /// scheme sees them, outgoing ones are signed (and, when directed,
+    /// encrypted). Every other roster party needs a pinned identity in
+    /// `auth`.
+    pub fn create_authenticated(params: &SessionParams, auth: SessionAuth) -> Result<Self> {
+        let mut session = Session::create(params)?;
+        session.auth = Some(Authenticator::new(
+            auth,
+            &session.scheme_name,
+            &params.parties,
+            params.this_party_idx,
+        )?);
+        Ok(session)
+    }
+
+    /// Resume a session from [`Session::snapshot`] output. `params` must
+    /// be the ones the session was created with and `integrity_key` the
+    /// one it was sealed under; anything else fails with
+    /// [`crate::Error::SnapshotInvalid`]. The session continues after
+    /// the round it was snapshotted at: send [`Session::last_outgoing`]
+    /// again, then keep calling [`Session::round_step`].
+    ///
+    /// `ledger` must be the one the snapshotting session ran against.
+    /// If the nonce the next round would consume is already spent —
+    /// this snapshot was resumed before, or the original session got
+    /// past it — resuming fails with [`crate::Error::NonceReuse`].
+    pub fn resume(
+        params: &SessionParams,
+        blob: &[u8],
+        integrity_key: &[u8],
+        ledger: Arc<dyn NonceLedger>,
+    ) -> Result<Self> {
+        Session::restore(params, None, blob, integrity_key, ledger)
+    }
+
+    /// [`Session::resume`] for a session built with
+    /// [`Session::create_authenticated`]. `auth` must carry the same
+    /// session id; the replay and equivocation record is restored from
+    /// the snapshot.
+    pub fn resume_authenticated(
+        params: &SessionParams,
+        auth: SessionAuth,
+        blob: &[u8],
+        integrity_key: &[u8],
+        ledger: Arc<dyn NonceLedger>,
+    ) -> Result<Self> {
+        Session::restore(params, Some(auth), blob, integrity_key, ledger)
+    }
+
+    fn restore(
+        params: &SessionParams,
+        auth: Option<SessionAuth>,
+        blob: &[u8],
+        integrity_key: &[u8],
+        ledger: Arc<dyn NonceLedger>,
+    ) -> Result<Self> {
+        let scheme = Session::resolve(params)?;
+        let plaintext = snapshot::open(integrity_key, &snapshot::params_digest(params), blob)?;
+        let mut r = StateReader::new(&plaintext);
+        let round = r.u8()?;
+        let complete = r.bool()?;
+        let state = r.bytes()?;
+        let impl_ = if scheme.reliable_broadcast() {
+            let our_id = &params.parties.get(params.this_party_idx)?.id;
+            Box::new(EchoBroadcast::restore(state, our_id.clone(), |inner| {
+                scheme.restore_session(params, inner)
+            })?)
+        } else {
+            scheme.restore_session(params, state)?
+        };
+        let mut session = Session::assemble(scheme, params, impl_);
+        session.round = round;
+        session.complete = complete;
+        session.last_outgoing = r.messages()?;
+        match (auth, r.bool()?) {
+            (None, false) => {}
+            (Some(auth), true) => {
+                let mut authenticator = Authenticator::new(
+                    auth,
+                    &session.scheme_name,
+                    &params.parties,
+                    params.this_party_idx,
+                )?;
+                authenticator.load(&mut r)?;
+                session.auth = Some(authenticator);
+            }
+            _ => {
+                return Err(snapshot::invalid(
+                    "authentication differs from the snapshotted session",
+                ));
+            }
+        }
+        r.finish()?;
+        if let Some(nonce) = session.impl_.pending_nonce() {
+            ensure!(
+                !ledger.is_spent(&snapshot::nonce_key(&session.scheme_name, &nonce))?,
+                error::NonceReuseSnafu {
+                    scheme: session.scheme_name.clone(),
+                }
+            );
+        }
+        session.nonce_ledger = Some(ledger);
+        Ok(session)
+    }
+
+    /// Record every nonce this session consumes in `ledger` from now
+    /// on. Required before [`Session::snapshot`].
+    pub fn set_nonce_ledger(&mut self, ledger: Arc<dyn NonceLedger>) {
+        self.nonce_ledger = Some(ledger);
+    }
+
+    /// Seal the state reached after the last completed round, for
+    /// [`Session::resume`]. `integrity_key` is the party's
+    /// [`crate::share_envelope::ShareEnvelope`] integrity key.
+    ///
+    /// Needs a nonce ledger ([`Session::set_nonce_ledger`]) and a
+    /// scheme that supports snapshots. Take the snapshot before sending
+    /// a round's messages, so a crash can only ever resume a round the
+    /// peers have not seen yet or are already waiting on.
+    pub fn snapshot(&self, integrity_key: &[u8]) -> Result<Vec<u8>> {
+        ensure!(
+            self.nonce_ledger.is_some(),
+            error::NonceLedgerRequiredSnafu {}
+        );
+        let state = self.impl_.snapshot().ok_or_else(|| {
+            error::SnapshotUnsupportedSnafu {
+                scheme: self.scheme_name.clone(),
+            }
+            .build()
+        })?;
+        let mut w = StateWriter::new();
+        w.u8(self.round);
+        w.bool(self.complete);
+        w.bytes(&state);
+        w.messages(&self.last_outgoing);
+        w.bool(self.auth.is_some());
+        if let Some(auth) = &self.auth {
+            auth.save(&mut w);
+        }
+        Ok(snapshot::seal(
+            integrity_key,
+            &self.params_digest,
+            &w.finish(),
+        ))
     }
 
     pub fn scheme_name(&self) -> &str {
@@ -138,25 +332,78 @@ impl Session {
         self.complete
     }
 
+    /// True when the session was built with
+    /// [`Session::create_authenticated`].
+    pub fn is_authenticated(&self) -> bool {
+        self.auth.is_some()
+    }
+
+    /// The messages the last [`Session::round_step`] returned (sealed,
+    /// on an authenticated session). After [`Session::resume`] these
+    /// are the messages of the round the snapshot was taken at.
+    pub fn last_outgoing(&self) -> &[Message] {
+        &self.last_outgoing
+    }
+
     /// Step the session forward one round.
     ///
     /// `incoming` is the set of [`Message`]s this party received since
     /// the last round (from all peers). Returns the messages this party
     /// needs to send next. Once a round returns `complete == true`,
     /// [`Session::result`] is ready and further `round` calls error.
+    ///
+    /// On an authenticated session `incoming` and the returned messages
+    /// are in sealed form. A message that fails verification aborts the
+    /// round before the scheme runs; [`crate::Error::culprit`] names
+    /// the party responsible.
     pub fn round_step(&mut self, incoming: &[Message]) -> Result<RoundResult> {
         ensure!(!self.complete, error::SessionAlreadyCompleteSnafu {});
+        self.spend_pending_nonce()?;
         self.round = self
             .round
             .checked_add(1)
             .ok_or_else(|| error::RoundOverflowSnafu { round: self.round }.build())?;
-        let res = self.impl_.round(incoming)?;
+        let mut res = match &mut self.auth {
+            None => self.impl_.round(incoming)?,
+            Some(auth) => {
+                let mut opened = Vec::with_capacity(incoming.len());
+                for msg in incoming {
+                    if let Some(msg) = auth.open(msg, self.round)? {
+                        opened.push(msg);
+                    }
+                }
+                self.impl_.round(&opened)?
+            }
+        };
+        if let Some(auth) = &self.auth {
+            res.outgoing = res
+                .outgoing
+                .iter()
+                .map(|msg| auth.seal(msg))
+                .collect::<Result<_>>()?;
+        }
         if res.complete {
             self.complete = true;
         }
+        self.last_outgoing = res.outgoing.clone();
         Ok(res)
     }
 
+    /// Write-ahead: record the nonce the coming round consumes before
+    /// the scheme gets to use it.
+    fn spend_pending_nonce(&self) -> Result<()> {
+        let (Some(ledger), Some(nonce)) = (&self.nonce_ledger, self.impl_.pending_nonce()) else {
+            return Ok(());
+        };
+        ensure!(
+            ledger.spend(&snapshot::nonce_key(&self.scheme_name, &nonce))?,
+            error::NonceReuseSnafu {
+                scheme: self.scheme_name.clone(),
+            }
+        );
+        Ok(())
+    }
+
     /// Read the final cryptographic artifact. Errors until a round has
     /// signaled completion.
     pub fn result(&self) -> Result<Vec<u8>> {
@@ -250,6 +497,148 @@ mod tests {
         }
     }
 
+    /// [`TwoRoundScheme`] behind the echo-broadcast wrapper.
+    struct EchoedTwoRoundScheme;
+
+    impl crate::registry::TcScheme for EchoedTwoRoundScheme {
+        fn name(&self) -> &'static str {
+            "test-two-round-echoed"
+        }
+        fn kind(&self) -> TcSchemeKind {
+            TcSchemeKind::Signature
+        }
+        fn create_session(&self, params: &SessionParams) -> Result<Box<dyn SessionImpl>> {
+            TwoRoundScheme.create_session(params)
+        }
+        fn reliable_broadcast(&self) -> bool {
+            true
+        }
+    }
+
+    inventory::submit! {
+        crate::registry::RegisteredScheme {
+            scheme: &EchoedTwoRoundScheme as &dyn crate::registry::TcScheme
+        }
+    }
+
+    /// Three rounds: round 1 draws a "nonce" and broadcasts it, round 2
+    /// consumes it, round 3 completes. Supports snapshots.
+    struct NonceScheme;
+
+    impl crate::registry::TcScheme for NonceScheme {
+        fn name(&self) -> &'static str {
+            "test-nonce"
+        }
+        fn kind(&self) -> TcSchemeKind {
+            TcSchemeKind::Signature
+        }
+        fn create_session(&self, params: &SessionParams) -> Result<Box<dyn SessionImpl>> {
+            Ok(Box::new(NonceSession {
+                our_id: params.parties.get(params.this_party_idx)?.id.clone(),
+                nonce: None,
+                used: Vec::new(),
+                round_done: 0,
+            }))
+        }
+        fn restore_session(
+            &self,
+            params: &SessionParams,
+            state: &[u8],
+        ) -> Result<Box<dyn SessionImpl>> {
+            let mut r = StateReader::new(state);
+            let round_done = r.u8()?;
+            let nonce = r.bool()?.then(|| r.array()).transpose()?;
+            let used = r.bytes()?.to_vec();
+            r.finish()?;
+            Ok(Box::new(NonceSession {
+                our_id: params.parties.get(params.this_party_idx)?.id.clone(),
+                nonce,
+                used,
+                round_done,
+            }))
+        }
+    }
+
+    struct NonceSession {
+        our_id: String,
+        nonce: Option<[u8; 8]>,
+        used: Vec<u8>,
+        round_done: u8,
+    }
+
+    impl SessionImpl for NonceSession {
+        fn round(&mut self, _incoming: &[Message]) -> Result<RoundResult> {
+            self.round_done += 1;
+            match self.round_done {
+                1 => {
+                    let nonce = rand_core::RngCore::next_u64(&mut rand_core::OsRng).to_be_bytes();
+                    self.nonce = Some(nonce);
+                    let msg = Message::broadcast(&self.our_id, 1, nonce.to_vec());
+                    Ok(RoundResult::new(vec![msg], false))
+                }
+                2 => {
+                    self.used = self.nonce.take().expect("nonce drawn").to_vec();
+                    Ok(RoundResult::new(vec![], false))
+                }
+                _ => Ok(RoundResult::done()),
+            }
+        }
+        fn result(&self) -> Result<Vec<u8>> {
+            Ok(self.used.clone())
+        }
+        fn destroy(&mut self) {}
+        fn snapshot(&self) -> Option<zeroize::Zeroizing<Vec<u8>>> {
+            let mut w = StateWriter::new();
+            w.u8(self.round_done);
+            w.bool(self.nonce.is_some());
+            if let Some(nonce) = &self.nonce {
+                w.fixed(nonce);
+            }
+            w.bytes(&self.used);
+            Some(w.finish())
+        }
+        fn pending_nonce(&self) -> Option<Vec<u8>> {
+            self.nonce.map(|n| n.to_vec())
+        }
+    }
+
+    inventory::submit! {
+        crate::registry::RegisteredScheme {
+            scheme: &NonceScheme as &dyn crate::registry::TcScheme
+        }
+    }
+
+    /// [`NonceScheme`] behind the echo-broadcast wrapper.
+    struct EchoedNonceScheme;
+
+    impl crate::registry::TcScheme for EchoedNonceScheme {
+        fn name(&self) -> &'static str {
+            "test-nonce-echoed"
+        }
+        fn kind(&self) -> TcSchemeKind {
+            TcSchemeKind::Signature
+        }
+        fn create_session(&self, params: &SessionParams) -> Result<Box<dyn SessionImpl>> {
+            NonceScheme.create_session(params)
+        }
+        fn restore_session(
+            &self,
+            params: &SessionParams,
+            state: &[u8],
+        ) -> Result<Box<dyn SessionImpl>> {
+            NonceScheme.restore_session(params, state)
+        }
+        fn reliable_broadcast(&self) -> bool {
+            true
+        }
+    }
+
+    inventory::submit! {
+        crate::registry::RegisteredScheme {
+            scheme: &EchoedNonceScheme as &dyn crate::registry::TcScheme
+        }
+    }
+
     fn params(scheme: &str, idx: usize, threshold: u32) -> SessionParams {
         SessionParams {
             scheme: scheme.to_string(),
@@ -363,4 +752,171 @@ mod tests {
         let err = session.dkg_public_key().unwrap_err();
         assert!(matches!(err, error::Error::NotADkgSession { .. }));
     }
+
+    fn authenticated_sessions(session_id: &[u8]) -> Vec<Session> {
+        use crate::auth::{IdentityKey, SessionAuth};
+        let keys: Vec<IdentityKey> = (0..3).map(|_| IdentityKey::generate()).collect();
+        let ids = ["a", "b", "c"];
+        keys.iter()
+            .enumerate()
+            .map(|(idx, _)| {
+                let key = IdentityKey::from_bytes(&keys[idx].to_bytes()).expect("copy key");
+                let mut auth = SessionAuth::new(session_id, key);
+                for (id, peer) in ids.iter().zip(&keys) {
+                    auth.add_peer(*id, peer.public());
+                }
+                Session::create_authenticated(&params("test-two-round", idx, 2), auth)
+                    .expect("session")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn authenticated_round_seals_and_opens() {
+        let mut sessions = authenticated_sessions(b"sid-1");
+        assert!(sessions[0].is_authenticated());
+        let r1 = sessions[0].round_step(&[]).expect("round 1");
+        assert_eq!(r1.outgoing.len(), 1);
+        assert_ne!(r1.outgoing[0].payload, b"hello", "payload is sealed");
+
+        sessions[1].round_step(&[]).expect("b round 1");
+        let r2 = sessions[1].round_step(&r1.outgoing).expect("b round 2");
+        assert!(r2.complete);
+    }
+
+    #[test]
+    fn authenticated_round_rejects_unsigned_message() {
+        let mut sessions = authenticated_sessions(b"sid-2");
+        sessions[1].round_step(&[]).expect("b round 1");
+        let forged = Message::broadcast("a", 1, b"hello".to_vec());
+        let err = sessions[1].round_step(&[forged]).unwrap_err();
+        assert!(matches!(err, error::Error::MessageRejected { .. }));
+        assert_eq!(err.culprit(), Some("a"));
+        assert!(!sessions[1].is_complete());
+    }
+
+    #[test]
+    fn create_authenticated_requires_every_peer_identity() {
+        use crate::auth::{IdentityKey, SessionAuth};
+        let auth = SessionAuth::new(b"sid".to_vec(), IdentityKey::generate());
+        let err = Session::create_authenticated(&params("test-two-round", 0, 2), auth).unwrap_err();
+        assert!(matches!(err, error::Error::MissingPartyIdentity { .. }));
+    }
+
+    #[test]
+    fn reliable_broadcast_scheme_adds_echo_round() {
+        let mut sessions: Vec<Session> = (0..3)
+            .map(|idx| Session::create(&params("test-two-round-echoed", idx, 2)).expect("session"))
+            .collect();
+        let data: Vec<Message> = sessions
+            .iter_mut()
+            .flat_map(|s| s.round_step(&[]).expect("step 1").outgoing)
+            .collect();
+        let echoes: Vec<Message> = sessions
+            .iter_mut()
+            .flat_map(|s| s.round_step(&data).expect("step 2").outgoing)
+            .collect();
+        assert_eq!(echoes.len(), 3, "one echo per party");
+        for session in &mut sessions {
+            let done = session.round_step(&echoes).expect("step 3");
+            assert!(done.complete);
+            assert_eq!(session.round(), 3);
+            assert_eq!(session.result().expect("result"), b"hello");
+        }
+    }
+
+    const KEY: &[u8] = b"envelope-integrity-key";
+
+    fn ledger_session(scheme: &str) -> (Session, Arc<snapshot::MemoryNonceLedger>) {
+        let ledger = Arc::new(snapshot::MemoryNonceLedger::new());
+        let mut session = Session::create(&params(scheme, 0, 2)).expect("session");
+        session.set_nonce_ledger(ledger.clone());
+        (session, ledger)
+    }
+
+    #[test]
+    fn resumed_session_continues_after_snapshot_round() {
+        let (mut session, ledger) = ledger_session("test-nonce");
+        let r1 = session.round_step(&[]).expect("round 1");
+        let blob = session.snapshot(KEY).expect("snapshot");
+        let nonce = r1.outgoing[0].payload.clone();
+
+        let mut resumed =
+            Session::resume(&params("test-nonce", 0, 2), &blob, KEY, ledger).expect("resume");
+        assert_eq!(resumed.round(), 1);
+        assert_eq!(resumed.last_outgoing()[0].payload, nonce);
+        resumed.round_step(&[]).expect("round 2");
+        resumed.round_step(&[]).expect("round 3");
+        assert_eq!(resumed.result().expect("result"), nonce);
+    }
+
+    #[test]
+    fn snapshot_is_bound_to_key_and_params() {
+        let (mut session, ledger) = ledger_session("test-nonce");
+        session.round_step(&[]).expect("round 1");
+        let blob = session.snapshot(KEY).expect("snapshot");
+
+        let err = Session::resume(&params("test-nonce", 0, 2), &blob, b"other", ledger.clone())
+            .unwrap_err();
+        assert!(matches!(err, error::Error::SnapshotInvalid { .. }));
+        let mut other = params("test-nonce", 0, 2);
+        other.message = Some(b"other message".to_vec());
+        let err = Session::resume(&other, &blob, KEY, ledger.clone()).unwrap_err();
+        assert!(matches!(err, error::Error::SnapshotInvalid { .. }));
+        let mut tampered = blob.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        let err = Session::resume(&params("test-nonce", 0, 2), &tampered, KEY, ledger).unwrap_err();
+        assert!(matches!(err, error::Error::SnapshotInvalid { .. }));
+    }
+
+    #[test]
+    fn spent_nonce_is_never_reused() {
+        let (mut session, ledger) = ledger_session("test-nonce");
+        session.round_step(&[]).expect("round 1");
+        let blob = session.snapshot(KEY).expect("snapshot");
+        let p = params("test-nonce", 0, 2);
+
+        // The original session goes on and spends the nonce.
+        session.round_step(&[]).expect("round 2");
+        let err = Session::resume(&p, &blob, KEY, ledger.clone()).unwrap_err();
+        assert!(matches!(err, error::Error::NonceReuse { .. }));
+
+        // Two resumes racing past the check: only one gets to sign.
+        let (mut session, ledger) = ledger_session("test-nonce");
+        session.round_step(&[]).expect("round 1");
+        let blob = session.snapshot(KEY).expect("snapshot");
+        let mut first = Session::resume(&p, &blob, KEY, ledger.clone()).expect("first");
+        let mut second = Session::resume(&p, &blob, KEY, ledger).expect("second");
+        first.round_step(&[]).expect("first spends");
+        let err = second.round_step(&[]).unwrap_err();
+        assert!(matches!(err, error::Error::NonceReuse { .. }));
+    }
+
+    #[test]
+    fn snapshot_needs_ledger_and_scheme_support() {
+        let mut session = Session::create(&params("test-nonce", 0, 2)).expect("session");
+        session.round_step(&[]).expect("round 1");
+        let err = session.snapshot(KEY).unwrap_err();
+        assert!(matches!(err, error::Error::NonceLedgerRequired { .. }));
+
+        let (session, _) = ledger_session("test-two-round");
+        let err = session.snapshot(KEY).unwrap_err();
+        assert!(matches!(err, error::Error::SnapshotUnsupported { .. }));
+    }
+
+    #[test]
+    fn echoed_session_resumes_mid_echo() {
+        let (mut session, ledger) = ledger_session("test-nonce-echoed");
+        session.round_step(&[]).expect("step 1");
+        session.round_step(&[]).expect("step 2");
+        let blob = session.snapshot(KEY).expect("snapshot");
+        let p = params("test-nonce-echoed", 0, 2);
+
+        let mut resumed = Session::resume(&p, &blob, KEY, ledger.clone()).expect("resume");
+        assert_eq!(resumed.round(), 2);
+        // Step 3 releases the held messages into the nonce round.
+        resumed.round_step(&[]).expect("step 3");
+        let err = Session::resume(&p, &blob, KEY, ledger).unwrap_err();
+        assert!(matches!(err, error::Error::NonceReuse { .. }));
+    }
 }
@@ -0,0 +1,526 @@
+//! Session snapshots and the nonce ledger.
+//!
+//! A [`crate::Session`] lives in memory; a signer that restarts
+//! mid-protocol would otherwise force the whole quorum to start over.
+//! [`crate::Session::snapshot`] serialises one party's state after a
+//! completed round into a sealed blob, and [`crate::Session::resume`]
+//! picks the protocol up again from that round.
+//!
+//! ## Sealed form
+//!
+//! ```text
+//! blob := VERSION (1) || nonce (12) || ciphertext || tag (16)
+//! key  := HMAC-SHA256(integrity_key, "confium-tc-snapshot-v1")
+//! aad  := "confium-tc-snapshot-v1" || VERSION || params_digest
+//! ```
+//!
+//! AES-256-GCM under a key derived from the party's
+//! [`crate::share_envelope::ShareEnvelope`] integrity key. The
+//! associated data binds the blob to the session it came from —
+//! scheme, roster, threshold, party index, message and share — so a
+//! snapshot cannot be resumed under different parameters.
+//!
+//! ## Nonce reuse
+//!
+//! A snapshot of a signing session taken before its nonce is used
+//! still holds that nonce. Restoring it twice, or restoring it after
+//! the live session went on to sign, would spend the nonce on two
+//! different transcripts and leak the key share. Every session that
+//! can be snapshotted therefore runs against a [`NonceLedger`]: before
+//! a scheme round consumes a nonce
+//! ([`crate::SessionImpl::pending_nonce`]) the session records it as
+//! spent, durably, and refuses to run the round if it already was.
+//! Resuming a snapshot whose pending nonce is spent fails with
+//! [`crate::Error::NonceReuse`] — that session has to be abandoned.
+
+use std::collections::HashSet;
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use aes_gcm::aead::AeadInOut;
+use aes_gcm::aead::inout::InOutBuf;
+use aes_gcm::{Aes256Gcm, Nonce};
+use hmac::{Hmac, KeyInit, Mac};
+use p256::elliptic_curve::rand_core::{Rng, UnwrapErr};
+use sha2::{Digest, Sha256};
+use snafu::{ResultExt, ensure};
+use zeroize::Zeroizing;
+
+use crate::Result;
+use crate::error;
+use crate::message::Message;
+use crate::session::SessionParams;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Domain separator for the snapshot key and associated data.
+const DOMAIN: &[u8] = b"confium-tc-snapshot-v1";
+
+/// Domain separator for nonce ledger entries.
+const NONCE_DOMAIN: &[u8] = b"confium-tc-nonce-v1";
+
+/// Sealed snapshot format version.
+pub const SNAPSHOT_VERSION: u8 = 1;
+
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+
+/// Append-only encoder for scheme state. Integers are big-endian,
+/// variable-length fields carry a `u32` length prefix. The buffer is
+/// zeroized on drop.
+#[derive(Default)]
+pub struct StateWriter {
+    buf: Zeroizing<Vec<u8>>,
+}
+
+impl StateWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn u8(&mut self, v: u8) {
+        self.buf.push(v);
+    }
+
+    pub fn bool(&mut self, v: bool) {
+        self.buf.push(u8::from(v));
+    }
+
+    pub fn u32(&mut self, v: u32) {
+        self.buf.extend_from_slice(&v.to_be_bytes());
+    }
+
+    pub fn u64(&mut self, v: u64) {
+        self.buf.extend_from_slice(&v.to_be_bytes());
+    }
+
+    /// Fixed-size field, written without a length prefix.
+    pub fn fixed(&mut self, bytes: &[u8]) {
+        self.buf.extend_from_slice(bytes);
+    }
+
+    /// Length-prefixed field.
+    pub fn bytes(&mut self, bytes: &[u8]) {
+        self.u32(bytes.len() as u32);
+        self.buf.extend_from_slice(bytes);
+    }
+
+    pub fn str(&mut self, s: &str) {
+        self.bytes(s.as_bytes());
+    }
+
+    pub fn message(&mut self, msg: &Message) {
+        self.str(&msg.from_party_id);
+        match &msg.to_party_id {
+            None => self.u8(0),
+            Some(to) => {
+                self.u8(1);
+                self.str(to);
+            }
+        }
+        self.u8(msg.round);
+        self.bytes(&msg.payload);
+    }
+
+    pub fn messages(&mut self, msgs: &[Message]) {
+        self.u32(msgs.len() as u32);
+        for msg in msgs {
+            self.message(msg);
+        }
+    }
+
+    pub fn finish(self) -> Zeroizing<Vec<u8>> {
+        self.buf
+    }
+}
+
+/// Bounds-checked decoder for [`StateWriter`] output. Every failure is
+/// [`crate::Error::SnapshotInvalid`].
+pub struct StateReader<'a>(&'a [u8]);
+
+impl<'a> StateReader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        StateReader(bytes)
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8]> {
+        ensure!(
+            self.0.len() >= n,
+            error::SnapshotInvalidSnafu {
+                reason: "state truncated",
+            }
+        );
+        let (head, tail) = self.0.split_at(n);
+        self.0 = tail;
+        Ok(head)
+    }
+
+    pub fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    pub fn bool(&mut self) -> Result<bool> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(invalid("bad boolean")),
+        }
+    }
+
+    pub fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_be_bytes(self.array()?))
+    }
+
+    pub fn u64(&mut self) -> Result<u64> {
+        Ok(u64::from_be_bytes(self.array()?))
+    }
+
+    pub fn array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        Ok(self.take(N)?.try_into().expect("took N bytes"))
+    }
+
+    pub fn bytes(&mut self) -> Result<&'a [u8]> {
+        let len = self.u32()? as usize;
+        self.take(len)
+    }
+
+    pub fn string(&mut self) -> Result<String> {
+        String::from_utf8(self.bytes()?.to_vec()).map_err(|_| invalid("string is not UTF-8"))
+    }
+
+    pub fn message(&mut self) -> Result<Message> {
+        let from_party_id = self.string()?;
+        let to_party_id = match self.u8()? {
+            0 => None,
+            1 => Some(self.string()?),
+            _ => return Err(invalid("bad recipient tag")),
+        };
+        Ok(Message {
+            from_party_id,
+            to_party_id,
+            round: self.u8()?,
+            payload: self.bytes()?.to_vec(),
+        })
+    }
+
+    pub fn messages(&mut self) -> Result<Vec<Message>> {
+        let count = self.u32()?;
+        (0..count).map(|_| self.message()).collect()
+    }
+
+    /// Error unless every byte was consumed.
+    pub fn finish(self) -> Result<()> {
+        ensure!(
+            self.0.is_empty(),
+            error::SnapshotInvalidSnafu {
+                reason: "trailing bytes",
+            }
+        );
+        Ok(())
+    }
+}
+
+/// Shorthand for a [`crate::Error::SnapshotInvalid`] — for schemes
+/// whose decoded state fails their own checks.
+pub fn invalid(reason: impl Into<String>) -> error::Error {
+    error::SnapshotInvalidSnafu {
+        reason: reason.into(),
+    }
+    .build()
+}
+
+/// Durable record of spent nonces, shared by every session of one
+/// party.
+///
+/// [`NonceLedger::spend`] must not return until the entry would
+/// survive a crash: the session runs the nonce-consuming round right
+/// after it.
+pub trait NonceLedger: Send + Sync {
+    /// Record `id` as spent. Returns `false`, recording nothing, if it
+    /// already was.
+    fn spend(&self, id: &[u8; 32]) -> Result<bool>;
+
+    fn is_spent(&self, id: &[u8; 32]) -> Result<bool>;
+}
+
+/// Ledger key for a scheme-reported nonce id, so two schemes can never
+/// collide on the same bytes.
+pub fn nonce_key(scheme: &str, nonce_id: &[u8]) -> [u8; 32] {
+    let mut h = Sha256::new();
+    h.update(NONCE_DOMAIN);
+    put(&mut h, scheme.as_bytes());
+    put(&mut h, nonce_id);
+    h.finalize().into()
+}
+
+/// In-memory ledger. Only as durable as the process — for tests, and
+/// for callers that snapshot into memory for other reasons.
+#[derive(Debug, Default)]
+pub struct MemoryNonceLedger {
+    spent: Mutex<HashSet<[u8; 32]>>,
+}
+
+impl MemoryNonceLedger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl NonceLedger for MemoryNonceLedger {
+    fn spend(&self, id: &[u8; 32]) -> Result<bool> {
+        Ok(self.spent.lock().expect("ledger lock").insert(*id))
+    }
+
+    fn is_spent(&self, id: &[u8; 32]) -> Result<bool> {
+        Ok(self.spent.lock().expect("ledger lock").contains(id))
+    }
+}
+
+/// Append-only file of 32-byte entries, synced to disk on every
+/// [`NonceLedger::spend`].
+///
+/// A torn final entry (a crash mid-write) is discarded on open: its
+/// `spend` never returned, so the round it guarded never ran.
+#[derive(Debug)]
+pub struct FileNonceLedger {
+    path: PathBuf,
+    inner: Mutex<(File, HashSet<[u8; 32]>)>,
+}
+
+impl FileNonceLedger {
+    /// Open or create the ledger at `path`.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(&path)
+            .context(error::NonceLedgerSnafu)?;
+        let mut contents = Vec::new();
+        file.read_to_end(&mut contents)
+            .context(error::NonceLedgerSnafu)?;
+        let whole = contents.len() - contents.len() % 32;
+        if whole != contents.len() {
+            // Append mode writes at the new end.
+            file.set_len(whole as u64)
+                .context(error::NonceLedgerSnafu)?;
+        }
+        let spent = contents[..whole]
+            .chunks_exact(32)
+            .map(|c| c.try_into().expect("32-byte chunk"))
+            .collect();
+        Ok(FileNonceLedger {
+            path,
+            inner: Mutex::new((file, spent)),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl NonceLedger for FileNonceLedger {
+    fn spend(&self, id: &[u8; 32]) -> Result<bool> {
+        let mut guard = self.inner.lock().expect("ledger lock");
+        let (file, spent) = &mut *guard;
+        if spent.contains(id) {
+            return Ok(false);
+        }
+        file.write_all(id).context(error::NonceLedgerSnafu)?;
+        file.sync_data().context(error::NonceLedgerSnafu)?;
+        spent.insert(*id);
+        Ok(true)
+    }
+
+    fn is_spent(&self, id: &[u8; 32]) -> Result<bool> {
+        Ok(self.inner.lock().expect("ledger lock").1.contains(id))
+    }
+}
+
+/// Digest of everything a snapshot must be resumed under.
+pub(crate) fn params_digest(params: &SessionParams) -> [u8; 32] {
+    let mut h = Sha256::new();
+    h.update(DOMAIN);
+    put(&mut h, params.scheme.as_bytes());
+    h.update(params.threshold.to_be_bytes());
+    h.update((params.this_party_idx as u64).to_be_bytes());
+    h.update((params.parties.len() as u64).to_be_bytes());
+    for party in params.parties.parties() {
+        put(&mut h, party.id.as_bytes());
+    }
+    match &params.message {
+        None => h.update([0]),
+        Some(message) => {
+            h.update([1]);
+            put(&mut h, message);
+        }
+    }
+    match &params.local_share {
+        None => h.update([0]),
+        Some(share) => {
+            h.update([1]);
+            put(&mut h, &Zeroizing::new(share.to_bytes()));
+        }
+    }
+    h.finalize().into()
+}
+
+/// Encrypt `plaintext` under `integrity_key`, bound to `digest`.
+pub(crate) fn seal(integrity_key: &[u8], digest: &[u8; 32], plaintext: &[u8]) -> Vec<u8> {
+    let cipher = cipher(integrity_key);
+    let mut nonce_bytes = [0u8; NONCE_LEN];
+    UnwrapErr(getrandom::SysRng).fill_bytes(&mut nonce_bytes);
+    let mut buffer = Zeroizing::new(plaintext.to_vec());
+    let tag = cipher
+        .encrypt_inout_detached(
+            &Nonce::from(nonce_bytes),
+            &aad(digest),
+            InOutBuf::from(buffer.as_mut_slice()),
+        )
+        .expect("session state is far below the AES-GCM length limit");
+    let mut out = Vec::with_capacity(1 + NONCE_LEN + buffer.len() + TAG_LEN);
+    out.push(SNAPSHOT_VERSION);
+    out.extend_from_slice(&nonce_bytes);
+    out.extend_from_slice(&buffer);
+    out.extend_from_slice(&tag);
+    out
+}
+
+/// Inverse of [`seal`]. Any mismatch — wrong key, other session
+/// parameters, tampering — is the same [`crate::Error::SnapshotInvalid`].
+pub(crate) fn open(
+    integrity_key: &[u8],
+    digest: &[u8; 32],
+    blob: &[u8],
+) -> Result<Zeroizing<Vec<u8>>> {
+    ensure!(
+        blob.len() >= 1 + NONCE_LEN + TAG_LEN,
+        error::SnapshotInvalidSnafu {
+            reason: "snapshot truncated",
+        }
+    );
+    ensure!(
+        blob[0] == SNAPSHOT_VERSION,
+        error::SnapshotInvalidSnafu {
+            reason: format!("unsupported snapshot version {}", blob[0]),
+        }
+    );
+    let (nonce_bytes, rest) = blob[1..].split_at(NONCE_LEN);
+    let (ciphertext, tag_bytes) = rest.split_at(rest.len() - TAG_LEN);
+    let nonce = Nonce::from(<[u8; NONCE_LEN]>::try_from(nonce_bytes).expect("nonce length"));
+    let tag = aes_gcm::Tag::from(<[u8; TAG_LEN]>::try_from(tag_bytes).expect("tag length"));
+    let mut buffer = Zeroizing::new(ciphertext.to_vec());
+    cipher(integrity_key)
+        .decrypt_inout_detached(
+            &nonce,
+            &aad(digest),
+            InOutBuf::from(buffer.as_mut_slice()),
+            &tag,
+        )
+        .map_err(|_| invalid("integrity check failed"))?;
+    Ok(buffer)
+}
+
+fn cipher(integrity_key: &[u8]) -> Aes256Gcm {
+    let mut mac = HmacSha256::new_from_slice(integrity_key).expect("HMAC accepts any key length");
+    mac.update(DOMAIN);
+    let key = Zeroizing::new(mac.finalize().into_bytes());
+    Aes256Gcm::new_from_slice(key.as_slice()).expect("AES-256-GCM accepts a 32-byte key")
+}
+
+fn aad(digest: &[u8; 32]) -> Vec<u8> {
+    let mut out = DOMAIN.to_vec();
+    out.push(SNAPSHOT_VERSION);
+    out.extend_from_slice(digest);
+    out
+}
+
+fn put(h: &mut Sha256, field: &[u8]) {
+    h.update((field.len() as u32).to_be_bytes());
+    h.update(field);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("confium-{name}-{}.ledger", std::process::id()))
+    }
+
+    #[test]
+    fn state_codec_roundtrips() {
+        let mut w = StateWriter::new();
+        w.u8(7);
+        w.bool(true);
+        w.u32(0xDEAD_BEEF);
+        w.u64(42);
+        w.fixed(&[1, 2, 3]);
+        w.str("alice");
+        w.messages(&[
+            Message::broadcast("a", 1, vec![9]),
+            Message::directed("a", "b", 2, vec![]),
+        ]);
+        let bytes = w.finish();
+
+        let mut r = StateReader::new(&bytes);
+        assert_eq!(r.u8().unwrap(), 7);
+        assert!(r.bool().unwrap());
+        assert_eq!(r.u32().unwrap(), 0xDEAD_BEEF);
+        assert_eq!(r.u64().unwrap(), 42);
+        assert_eq!(r.array::<3>().unwrap(), [1, 2, 3]);
+        assert_eq!(r.string().unwrap(), "alice");
+        let msgs = r.messages().unwrap();
+        assert_eq!(msgs[1].to_party_id.as_deref(), Some("b"));
+        r.finish().unwrap();
+    }
+
+    #[test]
+    fn truncated_state_is_invalid() {
+        let mut w = StateWriter::new();
+        w.str("alice");
+        let bytes = w.finish();
+        let err = StateReader::new(&bytes[..6]).string().unwrap_err();
+        assert!(matches!(err, error::Error::SnapshotInvalid { .. }));
+    }
+
+    #[test]
+    fn seal_binds_key_and_digest() {
+        let blob = seal(b"key", &[1; 32], b"state");
+        assert_eq!(&*open(b"key", &[1; 32], &blob).unwrap(), b"state");
+        assert!(open(b"other", &[1; 32], &blob).is_err());
+        assert!(open(b"key", &[2; 32], &blob).is_err());
+        let mut tampered = blob.clone();
+        tampered[14] ^= 1;
+        assert!(open(b"key", &[1; 32], &tampered).is_err());
+    }
+
+    #[test]
+    fn file_ledger_persists_and_drops_torn_entry() {
+        let path = temp_path("ledger");
+        let _ = std::fs::remove_file(&path);
+        {
+            let ledger = FileNonceLedger::open(&path).unwrap();
+            assert!(ledger.spend(&[1; 32]).unwrap());
+            assert!(!ledger.spend(&[1; 32]).unwrap());
+        }
+        // A crash halfway through the second entry.
+        OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(&[2; 10])
+            .unwrap();
+        let ledger = FileNonceLedger::open(&path).unwrap();
+        assert!(ledger.is_spent(&[1; 32]).unwrap());
+        assert!(!ledger.is_spent(&[2; 32]).unwrap());
+        assert!(ledger.spend(&[2; 32]).unwrap());
+        drop(ledger);
+        assert_eq!(std::fs::metadata(&path).unwrap().len(), 64);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
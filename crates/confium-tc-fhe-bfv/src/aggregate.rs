@@ -0,0 +1,229 @@
+//! Coordinator-driven aggregation: many submitters encrypt vectors to
+//! the threshold key, the coordinator adds the ciphertexts as they
+//! arrive, and the key holders decrypt only the sum.
+//!
+//! ```text
+//!   submitters ──encrypt──▶ AggregationSession::submit   (Collecting)
+//!                           AggregationSession::close    (Decrypting)
+//!   key holders ◀── DecryptionRequest ──┘
+//!   key holders ──partial_decrypt──▶ submit_partial      (Completed)
+//! ```
+//!
+//! The coordinator never holds key material and sees only ciphertexts
+//! and smudged partial decryptions. A [`DecryptionRequest`] carries
+//! every submission alongside the aggregate, and
+//! [`crate::decrypt::partial_decrypt`] only answers a request whose
+//! aggregate is the sum of at least the key holder's own minimum number
+//! of distinct submissions. A coordinator therefore cannot have a
+//! sum it made up decrypted on its own.
+//!
+//! Submitter names are not authenticated: nothing binds a
+//! [`Submission`] to the party it names. A malicious coordinator can
+//! pad one honest submission with ciphertexts of zero it encrypted
+//! itself under made-up names, and the key holders would decrypt that
+//! single submission. The minimum-submissions check only holds against
+//! such a coordinator when the deployment authenticates submitters
+//! (for example by having them sign their ciphertexts) and key holders
+//! check that before answering.
+use serde::{Deserialize, Serialize};
+
+use crate::bfv::Ciphertext;
+use crate::decrypt::{self, PartialDecryption};
+use crate::error::{BfvError, Result};
+use crate::params::Context;
+use crate::{BfvCiphertext, BfvPublicKey};
+
+/// Aggregation lifecycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AggregationState {
+    /// Accepting submissions.
+    Collecting,
+    /// Waiting for partial decryptions of the aggregate.
+    Decrypting,
+    /// The aggregate has been decrypted.
+    Completed,
+}
+
+/// One submitter's encrypted vector.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Submission {
+    /// Who submitted it.
+    pub submitter: String,
+    /// The encrypted vector.
+    pub ciphertext: BfvCiphertext,
+}
+
+/// What the coordinator asks every participating key holder to decrypt.
+///
+/// Nothing in the request is authenticated. In particular the
+/// submitter names are unverified, so distinct names do not prove
+/// distinct submitters.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecryptionRequest {
+    /// The sum of every submission.
+    pub aggregate: BfvCiphertext,
+    /// Party indices of the key holders taking part, ascending.
+    pub participants: Vec<u32>,
+    /// Every submission, in submission order. Key holders recompute
+    /// the aggregate from these. The submitter names are whatever the
+    /// coordinator claims; see the module docs.
+    pub submissions: Vec<Submission>,
+}
+
+/// Coordinator-side state of one aggregation.
+pub struct AggregationSession {
+    public_key: BfvPublicKey,
+    ctx: Context,
+    slots: usize,
+    min_submissions: usize,
+    state: AggregationState,
+    submissions: Vec<Submission>,
+    sum: Option<Ciphertext>,
+    request: Option<DecryptionRequest>,
+    partials: Vec<PartialDecryption>,
+    result: Option<Vec<u64>>,
+}
+
+impl AggregationSession {
+    /// Start collecting vectors of `slots` values under `public_key`;
+    /// the aggregate may be decrypted once `min_submissions` vectors
+    /// have arrived.
+    pub fn new(public_key: BfvPublicKey, slots: usize, min_submissions: usize) -> Result<Self> {
+        let ctx = crate::keys::PublicKey::parse(&public_key)?.ctx;
+        if slots == 0 || slots > ctx.n {
+            return Err(BfvError::InvalidPlaintext(format!(
+                "{slots} slots requested, the parameters have {}",
+                ctx.n
+            )));
+        }
+        Ok(AggregationSession {
+            public_key,
+            ctx,
+            slots,
+            min_submissions: min_submissions.max(1),
+            state: AggregationState::Collecting,
+            submissions: Vec::new(),
+            sum: None,
+            request: None,
+            partials: Vec::new(),
+            result: None,
+        })
+    }
+
+    pub fn state(&self) -> AggregationState {
+        self.state
+    }
+
+    /// Who has submitted so far.
+    pub fn submitters(&self) -> impl Iterator<Item = &str> + '_ {
+        self.submissions.iter().map(|s| s.submitter.as_str())
+    }
+
+    fn expect(&self, state: AggregationState, expected: &'static str) -> Result<()> {
+        if self.state != state {
+            return Err(BfvError::InvalidState {
+                current_state: self.state,
+                expected,
+            });
+        }
+        Ok(())
+    }
+
+    /// Add `submitter`'s encrypted vector to the aggregate, returning
+    /// the number of submissions so far.
+    pub fn submit(&mut self, submitter: &str, ct: &BfvCiphertext) -> Result<usize> {
+        self.expect(AggregationState::Collecting, "collecting")?;
+        if self.submitters().any(|s| s == submitter) {
+            return Err(BfvError::DuplicateSubmission(submitter.into()));
+        }
+        if ct.params != self.public_key.params {
+            return Err(BfvError::IncompatibleParams(
+                "submission does not use the session's parameters".into(),
+            ));
+        }
+        let parsed = Ciphertext::parse(&self.ctx, ct)?;
+        self.sum = Some(match self.sum.take() {
+            Some(sum) => sum.add(&parsed, &self.ctx),
+            None => parsed,
+        });
+        self.submissions.push(Submission {
+            submitter: submitter.into(),
+            ciphertext: ct.clone(),
+        });
+        Ok(self.submissions.len())
+    }
+
+    /// Stop accepting submissions and ask `participants` — at least the
+    /// threshold number of key holders, by ascending party index — to
+    /// decrypt the aggregate.
+    pub fn close(&mut self, participants: &[u32]) -> Result<DecryptionRequest> {
+        self.expect(AggregationState::Collecting, "collecting")?;
+        if self.submissions.len() < self.min_submissions {
+            return Err(BfvError::TooFewSubmissions {
+                have: self.submissions.len(),
+                need: self.min_submissions,
+            });
+        }
+        decrypt::check_participants(
+            participants,
+            self.public_key.threshold,
+            self.public_key.parties,
+        )?;
+        let sum = self.sum.as_ref().ok_or(BfvError::TooFewSubmissions {
+            have: 0,
+            need: self.min_submissions,
+        })?;
+        let request = DecryptionRequest {
+            aggregate: sum.to_wire(&self.public_key.params),
+            participants: participants.to_vec(),
+            submissions: self.submissions.clone(),
+        };
+        self.request = Some(request.clone());
+        self.state = AggregationState::Decrypting;
+        Ok(request)
+    }
+
+    /// Record a key holder's partial decryption of the aggregate. Once
+    /// every participant has answered, the aggregate is decrypted and
+    /// its first `slots` values returned.
+    pub fn submit_partial(&mut self, partial: PartialDecryption) -> Result<Option<&[u64]>> {
+        self.expect(AggregationState::Decrypting, "decrypting")?;
+        let request = self
+            .request
+            .as_ref()
+            .ok_or(BfvError::Malformed("aggregation session"))?;
+        if partial.participants != request.participants
+            || !request.participants.contains(&partial.party_index)
+        {
+            return Err(BfvError::InvalidParticipants(format!(
+                "party {} is not a participant of this decryption",
+                partial.party_index
+            )));
+        }
+        if self
+            .partials
+            .iter()
+            .any(|p| p.party_index == partial.party_index)
+        {
+            return Err(BfvError::DuplicateSubmission(format!(
+                "party {}",
+                partial.party_index
+            )));
+        }
+        self.partials.push(partial);
+        if self.partials.len() < request.participants.len() {
+            return Ok(None);
+        }
+        self.partials.sort_by_key(|p| p.party_index);
+        let mut slots = decrypt::combine(&self.public_key, &request.aggregate, &self.partials)?;
+        slots.truncate(self.slots);
+        self.state = AggregationState::Completed;
+        Ok(Some(self.result.insert(slots).as_slice()))
+    }
+
+    /// The decrypted aggregate, once complete.
+    pub fn result(&self) -> Option<&[u64]> {
+        self.result.as_deref()
+    }
+}
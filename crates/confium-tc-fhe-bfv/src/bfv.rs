@@ -0,0 +1,178 @@
+//! Encryption and the homomorphic operations.
+//!
+//! A plaintext is a vector of up to `n` slots in `Z_t`. Because
+//! `t ≡ 1 mod 2n`, `Z_t[x]/(x^n + 1)` is isomorphic to `Z_t^n` through
+//! the negacyclic NTT mod `t`, so a vector is encoded as the polynomial
+//! whose NTT it is and sums and products of polynomials act slot by
+//! slot.
+//!
+//! With the public key `(b, a) = (−a·s + e, a)`, encrypting `m` gives
+//!
+//! ```text
+//! c1 = b·u + e1 + Δ·m      c2 = a·u + e2
+//! ```
+//!
+//! for a ternary `u` and binomial `e1`, `e2`, so that
+//! `c1 + c2·s = Δ·m + v` with small noise `v = e·u + e1 + e2·s`.
+//!
+//! ## Noise
+//!
+//! Decryption is correct while `|v| < Δ/4`; the other quarter of
+//! `Δ/2` is reserved for smudging (see [`crate::params`]). With the
+//! recommended parameters `Δ/4 ≈ 2^80`: a fresh ciphertext carries
+//! about `2^12`, and a sum of `k` ciphertexts about `√k` times that.
+//! [`mul_plain`] multiplies the noise by up to `n·t/2 ≈ 2^36`, so a
+//! sum of up to a million ciphertexts can be multiplied by one
+//! plaintext, but products of products overflow the budget. Nothing
+//! tracks the noise; keeping to this depth is the caller's job.
+
+use crate::error::{BfvError, Result};
+use crate::keys::PublicKey;
+use crate::params::Context;
+use crate::ring::{self, Poly};
+use crate::{BfvCiphertext, BfvParams, BfvPublicKey};
+
+/// A parsed ciphertext.
+pub(crate) struct Ciphertext {
+    pub c1: Poly,
+    pub c2: Poly,
+}
+
+impl Ciphertext {
+    pub fn parse(ctx: &Context, ct: &BfvCiphertext) -> Result<Self> {
+        let get = |bytes: &[u8]| {
+            Poly::get(bytes, &ctx.limbs, ctx.n).ok_or(BfvError::Malformed("ciphertext"))
+        };
+        Ok(Ciphertext {
+            c1: get(&ct.c1)?,
+            c2: get(&ct.c2)?,
+        })
+    }
+
+    pub fn to_wire(&self, params: &BfvParams) -> BfvCiphertext {
+        let (mut c1, mut c2) = (Vec::new(), Vec::new());
+        self.c1.put(&mut c1);
+        self.c2.put(&mut c2);
+        BfvCiphertext {
+            params: params.clone(),
+            c1,
+            c2,
+        }
+    }
+
+    pub fn add(&self, other: &Ciphertext, ctx: &Context) -> Ciphertext {
+        Ciphertext {
+            c1: self.c1.add(&other.c1, &ctx.limbs),
+            c2: self.c2.add(&other.c2, &ctx.limbs),
+        }
+    }
+}
+
+/// The polynomial mod `t` whose slots are `values`, zero-padded.
+pub(crate) fn encode(ctx: &Context, values: &[u64]) -> Result<Vec<u64>> {
+    if values.len() > ctx.n {
+        return Err(BfvError::InvalidPlaintext(format!(
+            "{} values for {} slots",
+            values.len(),
+            ctx.n
+        )));
+    }
+    if let Some(v) = values.iter().find(|&&v| v >= ctx.t.p) {
+        return Err(BfvError::InvalidPlaintext(format!(
+            "value {v} not below the plaintext modulus {}",
+            ctx.t.p
+        )));
+    }
+    let mut poly = values.to_vec();
+    poly.resize(ctx.n, 0);
+    ctx.t.inv_ntt(&mut poly);
+    Ok(poly)
+}
+
+/// The slots of a polynomial mod `t`.
+pub(crate) fn decode(ctx: &Context, mut poly: Vec<u64>) -> Vec<u64> {
+    ctx.t.ntt(&mut poly);
+    poly
+}
+
+/// Encrypt `values` — at most `polynomial_degree` slots, each below the
+/// plaintext modulus — to the threshold public key.
+pub fn encrypt(public_key: &BfvPublicKey, values: &[u64]) -> Result<BfvCiphertext> {
+    let pk = PublicKey::parse(public_key)?;
+    let ctx = &pk.ctx;
+    let m = encode(ctx, values)?;
+    let limbs = &ctx.limbs;
+    let u = Poly::from_small(limbs, &ring::ternary(ctx.n));
+    let e1 = Poly::from_small(limbs, &ring::cbd(ctx.n));
+    let e2 = Poly::from_small(limbs, &ring::cbd(ctx.n));
+    let m: Vec<i64> = m.into_iter().map(|x| x as i64).collect();
+    let scaled = Poly::from_small(limbs, &m).scale(&ctx.delta_limbs, limbs);
+    let ct = Ciphertext {
+        c1: pk.b.mul(&u, limbs).add(&e1, limbs).add(&scaled, limbs),
+        c2: pk.a.mul(&u, limbs).add(&e2, limbs),
+    };
+    Ok(ct.to_wire(&public_key.params))
+}
+
+fn same_params(a: &BfvParams, b: &BfvParams) -> Result<()> {
+    if a != b {
+        return Err(BfvError::IncompatibleParams(
+            "ciphertexts use different parameters".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Slot-wise sum of two ciphertexts.
+pub fn add(a: &BfvCiphertext, b: &BfvCiphertext) -> Result<BfvCiphertext> {
+    same_params(&a.params, &b.params)?;
+    let ctx = Context::new(&a.params)?;
+    let sum = Ciphertext::parse(&ctx, a)?.add(&Ciphertext::parse(&ctx, b)?, &ctx);
+    Ok(sum.to_wire(&a.params))
+}
+
+/// Slot-wise product of a ciphertext with plaintext `values`; missing
+/// slots are multiplied by zero.
+pub fn mul_plain(ct: &BfvCiphertext, values: &[u64]) -> Result<BfvCiphertext> {
+    let ctx = Context::new(&ct.params)?;
+    let parsed = Ciphertext::parse(&ctx, ct)?;
+    // Centring the lift keeps the noise growth at `n·t/2`.
+    let centred: Vec<i64> = encode(&ctx, values)?
+        .into_iter()
+        .map(|x| ctx.t.centered(x))
+        .collect();
+    let p = Poly::from_small(&ctx.limbs, &centred);
+    let product = Ciphertext {
+        c1: parsed.c1.mul(&p, &ctx.limbs),
+        c2: parsed.c2.mul(&p, &ctx.limbs),
+    };
+    Ok(product.to_wire(&ct.params))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encoding_round_trips() {
+        let ctx = Context::new(&BfvParams::recommended_128()).unwrap();
+        let values: Vec<u64> = (0..ctx.n as u64).map(|i| i * 7919 % ctx.t.p).collect();
+        assert_eq!(decode(&ctx, encode(&ctx, &values).unwrap()), values);
+        let mut short = decode(&ctx, encode(&ctx, &[5, 6]).unwrap());
+        assert!(short.drain(2..).all(|x| x == 0));
+        assert_eq!(short, [5, 6]);
+    }
+
+    #[test]
+    fn oversized_plaintexts_are_refused() {
+        let ctx = Context::new(&BfvParams::recommended_128()).unwrap();
+        assert!(matches!(
+            encode(&ctx, &vec![0; ctx.n + 1]),
+            Err(BfvError::InvalidPlaintext(_))
+        ));
+        assert!(matches!(
+            encode(&ctx, &[ctx.t.p]),
+            Err(BfvError::InvalidPlaintext(_))
+        ));
+    }
+}
@@ -0,0 +1,199 @@
+//! Threshold decryption with smudging noise.
+//!
+//! The key holders taking part agree on their set first. For a
+//! ciphertext `(c1, c2)` each participant `j` sums the pieces of `s` it
+//! is the first holder of (see [`crate::keys`]) into `s_j` and sends
+//!
+//! ```text
+//! d_j = c2·s_j + E_j
+//! ```
+//!
+//! with `E_j` uniform in `[−2^σ, 2^σ)`, `σ` being the parameter set's
+//! smudging width. Then `c1 + Σ d_j = Δ·m + v + Σ E_j`, which decodes to
+//! `m` while the total noise stays below `Δ/2`. Without `E_j` the
+//! combined value would reveal the ciphertext noise `v` exactly, and
+//! `v` depends on `s`; smudging drowns it, statistically when
+//! `2^σ ≫ |v|`, which holds by about `2^50` for sums of fresh
+//! ciphertexts under the recommended parameters.
+//!
+//! Key holders only decrypt aggregates: [`partial_decrypt`] takes the
+//! coordinator's [`DecryptionRequest`], recomputes the sum of its
+//! submissions and refuses a request with too few of them or whose
+//! aggregate is anything else.
+//!
+//! Partial decryptions are not verifiable: a participant that sends a
+//! wrong `d_j` silently corrupts the result.
+
+use serde::{Deserialize, Serialize};
+
+use crate::aggregate::DecryptionRequest;
+use crate::bfv::{self, Ciphertext};
+use crate::error::{BfvError, Result};
+use crate::keys::{KeyShare, PublicKey};
+use crate::ring::{self, Poly};
+use crate::{BfvCiphertext, BfvPublicKey, BfvSecretKeyShare};
+
+/// One key holder's share of a decryption.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PartialDecryption {
+    /// Party index of the key holder.
+    pub party_index: u32,
+    /// Party indices of every participant, ascending.
+    pub participants: Vec<u32>,
+    /// `d_j`, one polynomial.
+    pub bytes: Vec<u8>,
+}
+
+/// Whether `participants` are distinct ascending party indices, at least
+/// `threshold` of them.
+pub(crate) fn check_participants(participants: &[u32], threshold: u32, parties: u32) -> Result<()> {
+    if participants.windows(2).any(|w| w[0] >= w[1])
+        || participants.iter().any(|i| !(1..=parties).contains(i))
+    {
+        return Err(BfvError::InvalidParticipants(format!(
+            "{participants:?} are not distinct ascending indices in 1..={parties}"
+        )));
+    }
+    if participants.len() < threshold as usize {
+        return Err(BfvError::ThresholdNotMet {
+            have: participants.len(),
+            need: threshold,
+        });
+    }
+    Ok(())
+}
+
+/// This key holder's partial decryption of the aggregate in `request`.
+///
+/// `min_submissions` is the key holder's own policy, independent of the
+/// coordinator's. The request must list at least that many submissions
+/// from distinct submitters, and its aggregate must be their sum;
+/// otherwise nothing is decrypted.
+///
+/// Submitter names are taken from the request as-is. A coordinator
+/// that invents submitters can still get fewer than `min_submissions`
+/// real submissions decrypted, so callers facing an untrusted
+/// coordinator must authenticate each submission before calling this.
+pub fn partial_decrypt(
+    share: &BfvSecretKeyShare,
+    request: &DecryptionRequest,
+    min_submissions: usize,
+) -> Result<PartialDecryption> {
+    let share = KeyShare::from_bytes(&share.bytes)?;
+    let need = min_submissions.max(1);
+    let submissions = &request.submissions;
+    if submissions.len() < need {
+        return Err(BfvError::TooFewSubmissions {
+            have: submissions.len(),
+            need,
+        });
+    }
+    for (i, submission) in submissions.iter().enumerate() {
+        if submissions[..i]
+            .iter()
+            .any(|s| s.submitter == submission.submitter)
+        {
+            return Err(BfvError::DuplicateSubmission(submission.submitter.clone()));
+        }
+    }
+    let ctx = &share.ctx;
+    let mut sum: Option<Ciphertext> = None;
+    for submission in submissions {
+        if submission.ciphertext.params != share.params {
+            return Err(BfvError::IncompatibleParams(
+                "submission and key share use different parameters".into(),
+            ));
+        }
+        let parsed = Ciphertext::parse(ctx, &submission.ciphertext)?;
+        sum = Some(match sum {
+            Some(sum) => sum.add(&parsed, ctx),
+            None => parsed,
+        });
+    }
+    let sum = sum.ok_or(BfvError::TooFewSubmissions { have: 0, need })?;
+    if sum.to_wire(&share.params) != request.aggregate {
+        return Err(BfvError::AggregateMismatch);
+    }
+    share_of(&share, &request.participants, &sum)
+}
+
+/// `d_j` of `ct` in a decryption by `participants`, the ascending party
+/// indices of everyone taking part.
+fn share_of(share: &KeyShare, participants: &[u32], ct: &Ciphertext) -> Result<PartialDecryption> {
+    check_participants(participants, share.threshold, share.parties)?;
+    if !participants.contains(&share.index) {
+        return Err(BfvError::InvalidParticipants(format!(
+            "party {} is not among {participants:?}",
+            share.index
+        )));
+    }
+    let ctx = &share.ctx;
+    let mut secret = Poly::zero(&ctx.limbs, ctx.n);
+    for piece in &share.pieces {
+        let first = participants.iter().find(|&&i| piece.has_member(i));
+        if first == Some(&share.index) {
+            let s = piece
+                .secret
+                .as_ref()
+                .ok_or(BfvError::Malformed("key share"))?;
+            secret = secret.add(s, &ctx.limbs);
+        }
+    }
+    let noise = Poly::from_wide(&ctx.limbs, &ring::smudging(ctx.n, ctx.smudging_bits));
+    let mut bytes = Vec::new();
+    ct.c2
+        .mul(&secret, &ctx.limbs)
+        .add(&noise, &ctx.limbs)
+        .put(&mut bytes);
+    Ok(PartialDecryption {
+        party_index: share.index,
+        participants: participants.to_vec(),
+        bytes,
+    })
+}
+
+/// Combine one partial decryption from every participant into the
+/// plaintext slots of `ct`.
+pub fn combine(
+    public_key: &BfvPublicKey,
+    ct: &BfvCiphertext,
+    partials: &[PartialDecryption],
+) -> Result<Vec<u64>> {
+    let pk = PublicKey::parse(public_key)?;
+    let ctx = &pk.ctx;
+    if ct.params != public_key.params {
+        return Err(BfvError::IncompatibleParams(
+            "ciphertext and public key use different parameters".into(),
+        ));
+    }
+    let participants = partials
+        .first()
+        .map(|p| p.participants.as_slice())
+        .unwrap_or_default();
+    check_participants(participants, public_key.threshold, public_key.parties)?;
+    if partials.len() != participants.len()
+        || partials
+            .iter()
+            .zip(participants)
+            .any(|(p, &i)| p.party_index != i || p.participants != participants)
+    {
+        return Err(BfvError::InvalidParticipants(
+            "need exactly one partial per participant, in participant order".into(),
+        ));
+    }
+
+    let mut sum = Ciphertext::parse(ctx, ct)?.c1;
+    for partial in partials {
+        let d = Poly::get(&partial.bytes, &ctx.limbs, ctx.n)
+            .ok_or(BfvError::Malformed("partial decryption"))?;
+        sum = sum.add(&d, &ctx.limbs);
+    }
+    // m = ⌊x/Δ⌉ mod t: a `q − Δ/2 ≤ x < q` rounds to `t`, which is 0.
+    let m = (0..ctx.n)
+        .map(|i| {
+            let x = ctx.lift(&sum, i);
+            ((x + ctx.delta / 2) / ctx.delta % u128::from(ctx.t.p)) as u64
+        })
+        .collect();
+    Ok(bfv::decode(ctx, m))
+}
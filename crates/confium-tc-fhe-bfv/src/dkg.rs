@@ -0,0 +1,350 @@
+//! Distributed key generation for threshold BFV.
+//!
+//! | Name                | Kind  | Session message                 | Produces        |
+//! |---------------------|-------|---------------------------------|-----------------|
+//! | `BFV-threshold-dkg` | `Dkg` | [`crate::keys::encode_params`]? | key share blob  |
+//!
+//! Without a session message the key uses
+//! [`crate::BfvParams::recommended_128`]. Party `i` is the `i`-th roster
+//! member (1-based); the threshold is the session's.
+//!
+//! ## Rounds
+//!
+//! 1. **Seed** — broadcast a fresh 32-byte seed.
+//! 2. **Contribute** — the seed of the public `a` is SHA3-256 over every
+//!    party's seed in roster order. Sample a ternary `s_i` and a binomial
+//!    `e_i`, broadcast `b_i = −a·s_i + e_i`, split `s_i` into uniformly
+//!    random pieces `s_{i,S}` summing to it, one per subset of
+//!    `n − t + 1` parties (see [`crate::keys`]), and send every peer the
+//!    pieces of the subsets it belongs to.
+//! 3. **Combine** — `b = Σ b_i` and `s_S = Σ_i s_{i,S}`, so the key
+//!    share holds pieces of `s = Σ s_i` and `b = −a·s + Σ e_i`.
+//!
+//! The session's result — and so also
+//! [`confium_tc::Session::dkg_public_key`] — is the whole share blob;
+//! [`crate::BfvSecretKeyShare::from_bytes`] wraps it and
+//! [`crate::BfvSecretKeyShare::public_key`] extracts the public key.
+//!
+//! ## Limitations
+//!
+//! Secure against semi-honest parties only: nothing proves that `b_i`
+//! is well formed or that the pieces sum to the `s_i` behind it, so a
+//! deviating party can produce a key nobody can decrypt under. The last
+//! party to reveal its seed can bias `a`. Pieces travel in the clear
+//! inside directed messages; the transport must keep them private.
+//! Sessions cannot be snapshotted.
+
+use confium_tc::registry::{RoundResult, SessionImpl, TcScheme, TcSchemeKind};
+use confium_tc::session::SessionParams;
+use confium_tc::{Message, Result};
+use rand_core::{OsRng, RngCore};
+use sha3::Sha3_256;
+use sha3::digest::Digest;
+
+use crate::BfvParams;
+use crate::error::{BfvErrorCode, scheme_error};
+use crate::keys::{self, KeyShare, Piece};
+use crate::params::Context;
+use crate::ring::{self, Poly};
+
+/// Canonical scheme name advertised through the registry.
+pub const SCHEME_NAME: &str = "BFV-threshold-dkg";
+
+const TAG_SEED: u8 = 0xB1;
+const TAG_PUBLIC: u8 = 0xB2;
+const TAG_PIECES: u8 = 0xB3;
+
+/// Domain separator of the seed of `a`.
+const SEED_DOMAIN: &[u8] = b"confium-tc-fhe-bfv/dkg/a";
+
+/// Threshold BFV distributed key generation, registered as
+/// [`SCHEME_NAME`].
+pub struct BfvThresholdDkg;
+
+impl TcScheme for BfvThresholdDkg {
+    fn name(&self) -> &'static str {
+        SCHEME_NAME
+    }
+    fn kind(&self) -> TcSchemeKind {
+        TcSchemeKind::Dkg
+    }
+    fn create_session(&self, params: &SessionParams) -> Result<Box<dyn SessionImpl>> {
+        Ok(Box::new(DkgSession::new(params)?))
+    }
+}
+
+confium_tc::register_tc_scheme!(BfvThresholdDkg);
+
+/// Abort naming the sender of `msg`.
+fn reject(msg: &Message, reason: impl Into<String>) -> confium_tc::Error {
+    confium_tc::error::MessageRejectedSnafu {
+        party: msg.from_party_id.clone(),
+        round: msg.round,
+        reason: reason.into(),
+    }
+    .build()
+}
+
+/// One party's view of a threshold BFV key generation.
+struct DkgSession {
+    party_id: String,
+    roster_ids: Vec<String>,
+    index: u32,
+    threshold: u32,
+    params: BfvParams,
+    ctx: Context,
+    /// Our round 1 seed.
+    seed: [u8; 32],
+    /// The seed of `a`, fixed in round 2.
+    a_seed: [u8; 32],
+    /// Our `b_i`, then the sum.
+    b: Option<Poly>,
+    /// The members of every piece.
+    members: Vec<u32>,
+    /// Our contribution to every piece we hold, then the sums.
+    held: Vec<Option<Poly>>,
+    result: Option<Vec<u8>>,
+    round_done: u8,
+}
+
+impl DkgSession {
+    fn new(params: &SessionParams) -> Result<Self> {
+        let party_id = params.parties.get(params.this_party_idx)?.id.clone();
+        let roster_ids: Vec<String> = params
+            .parties
+            .parties()
+            .iter()
+            .map(|p| p.id.clone())
+            .collect();
+        let parties = roster_ids.len() as u32;
+        if !keys::check_roster(params.threshold, parties) {
+            return Err(scheme_error(BfvErrorCode::BAD_ROSTER));
+        }
+        let bfv_params = match params.message.as_deref() {
+            None | Some([]) => BfvParams::recommended_128(),
+            Some(bytes) => keys::decode_params(bytes)
+                .map_err(|_| scheme_error(BfvErrorCode::BAD_PARAMETERS))?,
+        };
+        let ctx =
+            Context::new(&bfv_params).map_err(|_| scheme_error(BfvErrorCode::BAD_PARAMETERS))?;
+        let members = keys::piece_members(params.threshold, parties);
+        let mut seed = [0u8; 32];
+        OsRng.fill_bytes(&mut seed);
+        Ok(DkgSession {
+            party_id,
+            roster_ids,
+            index: params.this_party_idx as u32 + 1,
+            threshold: params.threshold,
+            params: bfv_params,
+            ctx,
+            seed,
+            a_seed: [0; 32],
+            b: None,
+            held: vec![None; members.len()],
+            members,
+            result: None,
+            round_done: 0,
+        })
+    }
+
+    fn has_member(&self, piece: usize, index: u32) -> bool {
+        self.members[piece] >> (index - 1) & 1 == 1
+    }
+
+    /// The roster position of a peer's message, refusing our own id and
+    /// strangers.
+    fn sender(&self, m: &Message) -> Result<usize> {
+        self.roster_ids
+            .iter()
+            .position(|id| *id == m.from_party_id && *id != self.party_id)
+            .ok_or_else(|| reject(m, "sender is not a peer in the roster"))
+    }
+
+    /// Fail unless every peer was `seen`.
+    fn require_all(&self, round: u8, seen: &[bool]) -> Result<()> {
+        match self
+            .roster_ids
+            .iter()
+            .zip(seen)
+            .find(|(id, seen)| **id != self.party_id && !**seen)
+        {
+            Some((id, _)) => Err(confium_tc::error::MessageRejectedSnafu {
+                party: id.clone(),
+                round,
+                reason: format!("no round {round} message"),
+            }
+            .build()),
+            None => Ok(()),
+        }
+    }
+
+    /// Round 1 — broadcast our seed.
+    fn round_seed(&mut self) -> Result<RoundResult> {
+        let mut payload = vec![TAG_SEED];
+        payload.extend_from_slice(&self.seed);
+        let msg = Message::broadcast(&self.party_id, 1, payload);
+        Ok(RoundResult::new(vec![msg], false))
+    }
+
+    /// Round 2 — fix `a`, publish `b_i` and deal the pieces of `s_i`.
+    fn round_contribute(&mut self, incoming: &[Message]) -> Result<RoundResult> {
+        let me = self.index as usize - 1;
+        let mut seeds: Vec<Option<[u8; 32]>> = vec![None; self.roster_ids.len()];
+        seeds[me] = Some(self.seed);
+        for m in incoming {
+            let pos = self.sender(m)?;
+            if m.round != 1 || m.payload.first() != Some(&TAG_SEED) || !m.is_broadcast() {
+                return Err(reject(m, "expected a round 1 broadcast"));
+            }
+            if seeds[pos].is_some() {
+                return Err(reject(m, "duplicate seed"));
+            }
+            seeds[pos] = Some(
+                m.payload[1..]
+                    .try_into()
+                    .map_err(|_| reject(m, "malformed seed"))?,
+            );
+        }
+        let seen: Vec<bool> = seeds.iter().map(Option::is_some).collect();
+        self.require_all(1, &seen)?;
+        let mut h = Sha3_256::new();
+        h.update(SEED_DOMAIN);
+        for seed in seeds.iter().flatten() {
+            h.update(seed);
+        }
+        self.a_seed = h.finalize().into();
+
+        let (ctx, limbs) = (&self.ctx, &self.ctx.limbs);
+        let a = Poly::uniform(limbs, ctx.n, &self.a_seed);
+        let s = Poly::from_small(limbs, &ring::ternary(ctx.n));
+        let e = Poly::from_small(limbs, &ring::cbd(ctx.n));
+        let b = e.sub(&a.mul(&s, limbs), limbs);
+
+        let mut pieces: Vec<Poly> = (1..self.members.len())
+            .map(|_| Poly::random(limbs, ctx.n))
+            .collect();
+        let last = pieces.iter().fold(s, |acc, p| acc.sub(p, limbs));
+        pieces.push(last);
+
+        let mut outgoing = Vec::with_capacity(self.roster_ids.len());
+        let mut payload = vec![TAG_PUBLIC];
+        b.put(&mut payload);
+        outgoing.push(Message::broadcast(&self.party_id, 2, payload));
+        for (pos, id) in self.roster_ids.iter().enumerate() {
+            let index = pos as u32 + 1;
+            if index == self.index {
+                continue;
+            }
+            let mut payload = vec![TAG_PIECES];
+            for (k, piece) in pieces.iter().enumerate() {
+                if self.has_member(k, index) {
+                    piece.put(&mut payload);
+                }
+            }
+            outgoing.push(Message::directed(&self.party_id, id, 2, payload));
+        }
+        for (k, piece) in pieces.into_iter().enumerate() {
+            if self.has_member(k, self.index) {
+                self.held[k] = Some(piece);
+            }
+        }
+        self.b = Some(b);
+        Ok(RoundResult::new(outgoing, false))
+    }
+
+    /// Round 3 — add up the contributions.
+    fn round_combine(&mut self, incoming: &[Message]) -> Result<RoundResult> {
+        let (n, limbs) = (self.ctx.n, &self.ctx.limbs);
+        let len = Poly::encoded_len(limbs, n);
+        let mut b = self
+            .b
+            .take()
+            .ok_or_else(|| scheme_error(BfvErrorCode::INTERNAL))?;
+        let mut publics = vec![false; self.roster_ids.len()];
+        let mut dealt = vec![false; self.roster_ids.len()];
+        for m in incoming {
+            let pos = self.sender(m)?;
+            if m.round != 2 {
+                return Err(reject(m, "expected a round 2 message"));
+            }
+            match m.payload.first() {
+                Some(&TAG_PUBLIC) if m.is_broadcast() && !publics[pos] => {
+                    let b_j = Poly::get(&m.payload[1..], limbs, n)
+                        .ok_or_else(|| reject(m, "malformed public contribution"))?;
+                    b = b.add(&b_j, limbs);
+                    publics[pos] = true;
+                }
+                Some(&TAG_PIECES) if m.is_for(&self.party_id) && !dealt[pos] => {
+                    let mine: Vec<usize> = (0..self.members.len())
+                        .filter(|&k| self.has_member(k, self.index))
+                        .collect();
+                    let body = &m.payload[1..];
+                    if body.len() != mine.len() * len {
+                        return Err(reject(m, "wrong number of pieces"));
+                    }
+                    for (&k, chunk) in mine.iter().zip(body.chunks(len)) {
+                        let piece = Poly::get(chunk, limbs, n)
+                            .ok_or_else(|| reject(m, "malformed piece"))?;
+                        let sum = self.held[k]
+                            .as_ref()
+                            .ok_or_else(|| scheme_error(BfvErrorCode::INTERNAL))?
+                            .add(&piece, limbs);
+                        self.held[k] = Some(sum);
+                    }
+                    dealt[pos] = true;
+                }
+                _ => return Err(reject(m, "unexpected or duplicate message")),
+            }
+        }
+        self.require_all(2, &publics)?;
+        self.require_all(2, &dealt)?;
+
+        let share = KeyShare {
+            index: self.index,
+            threshold: self.threshold,
+            parties: self.roster_ids.len() as u32,
+            params: self.params.clone(),
+            ctx: self.ctx.clone(),
+            seed: self.a_seed,
+            b,
+            pieces: self
+                .members
+                .iter()
+                .zip(self.held.drain(..))
+                .map(|(&members, secret)| Piece { members, secret })
+                .collect(),
+        };
+        self.result = Some(share.to_bytes());
+        Ok(RoundResult::done())
+    }
+}
+
+impl SessionImpl for DkgSession {
+    fn round(&mut self, incoming: &[Message]) -> Result<RoundResult> {
+        self.round_done = self.round_done.checked_add(1).ok_or_else(|| {
+            confium_tc::error::RoundOverflowSnafu {
+                round: self.round_done,
+            }
+            .build()
+        })?;
+        match self.round_done {
+            1 => self.round_seed(),
+            2 => self.round_contribute(incoming),
+            3 => self.round_combine(incoming),
+            other => Err(confium_tc::error::RoundOverflowSnafu { round: other }.build()),
+        }
+    }
+
+    fn result(&self) -> Result<Vec<u8>> {
+        self.result
+            .clone()
+            .ok_or_else(|| confium_tc::error::SessionNotCompleteSnafu {}.build())
+    }
+
+    fn destroy(&mut self) {
+        self.seed.fill(0);
+        self.held.clear();
+        self.b = None;
+        self.result = None;
+    }
+}
@@ -0,0 +1,91 @@
+//! Errors of the library API and sub-codes of the DKG session.
+
+use confium_tc::error::Error as TcError;
+
+use crate::aggregate::AggregationState;
+
+/// Errors during BFV operations.
+#[derive(Debug, thiserror::Error)]
+pub enum BfvError {
+    /// Parameters incompatible.
+    #[error("parameters incompatible: {0}")]
+    IncompatibleParams(String),
+    /// Threshold not met for decryption.
+    #[error("threshold not met: have {have}, need {need}")]
+    ThresholdNotMet {
+        /// Have count.
+        have: usize,
+        /// Need count.
+        need: u32,
+    },
+    /// A plaintext vector is longer than the slot count or holds a
+    /// value outside `[0, t)`.
+    #[error("invalid plaintext: {0}")]
+    InvalidPlaintext(String),
+    /// A key, share, ciphertext or partial decryption failed to decode.
+    #[error("malformed {0}")]
+    Malformed(&'static str),
+    /// The key holders named for a decryption are not a usable set.
+    #[error("invalid participants: {0}")]
+    InvalidParticipants(String),
+    /// Aggregation in wrong state.
+    #[error("aggregation in state {current_state:?}, expected {expected}")]
+    InvalidState {
+        /// Current state.
+        current_state: AggregationState,
+        /// Expected state description.
+        expected: &'static str,
+    },
+    /// Submitter already submitted.
+    #[error("submitter {0} already submitted")]
+    DuplicateSubmission(String),
+    /// A decryption request's aggregate is not the sum of its
+    /// submissions.
+    #[error("aggregate is not the sum of the listed submissions")]
+    AggregateMismatch,
+    /// Fewer submissions than the aggregation requires.
+    #[error("too few submissions: have {have}, need {need}")]
+    TooFewSubmissions {
+        /// Have count.
+        have: usize,
+        /// Need count.
+        need: usize,
+    },
+}
+
+/// Threshold BFV sub-codes (0x73xx). Distinct from threshold ML-KEM's
+/// 0x72xx so callers can disambiguate the source scheme from a numeric
+/// code alone.
+#[allow(non_camel_case_types)]
+#[repr(u32)]
+pub enum BfvErrorCode {
+    /// The session message is not an encoded parameter set, or the
+    /// parameters fail [`crate::validate_params`].
+    /// Caller action: pass parameters from [`crate::keys::encode_params`].
+    BAD_PARAMETERS = 0x7301,
+    /// The roster is empty, larger than [`crate::keys::MAX_PARTIES`], or
+    /// smaller than the threshold, or the threshold is zero.
+    /// Caller action: pick a supported configuration.
+    BAD_ROSTER = 0x7302,
+    /// Internal error — a panic-equivalent condition was caught and
+    /// converted to an error return. Indicates a bug; please open an
+    /// issue.
+    INTERNAL = 0x73FF,
+}
+
+impl From<BfvErrorCode> for u32 {
+    #[inline]
+    fn from(c: BfvErrorCode) -> u32 {
+        c as u32
+    }
+}
+
+/// Build a framework [`TcError`] carrying a threshold BFV sub-code.
+pub fn scheme_error(code: BfvErrorCode) -> TcError {
+    confium_tc::error::SchemeInternalSnafu {
+        code: u32::from(code),
+    }
+    .build()
+}
+
+pub type Result<T> = std::result::Result<T, BfvError>;
@@ -0,0 +1,38 @@
+//! In-process driver for threshold BFV key generation.
+//!
+//! [`keygen`] runs the registered DKG through [`confium_tc::inprocess`]
+//! with the recommended parameters. Encryption, the homomorphic
+//! operations and decryption need no session; see [`crate::bfv`] and
+//! [`crate::decrypt`].
+
+use confium_tc::Result;
+use confium_tc::inprocess as driver;
+
+use crate::error::{BfvErrorCode, scheme_error};
+use crate::{BfvPublicKey, BfvSecretKeyShare, dkg};
+
+/// Outcome of a DKG: N key shares plus the public key.
+#[derive(Debug, Clone)]
+pub struct KeygenOutput {
+    /// One share per party, in roster order.
+    pub shares: Vec<BfvSecretKeyShare>,
+    /// The threshold public key.
+    pub public_key: BfvPublicKey,
+}
+
+/// Generate a key for `party_count` parties at threshold `threshold`.
+pub fn keygen(threshold: u32, party_count: usize) -> Result<KeygenOutput> {
+    let blobs = driver::run_dkg(dkg::SCHEME_NAME, threshold, party_count)?;
+    let shares = blobs
+        .into_iter()
+        .map(BfvSecretKeyShare::from_bytes)
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|_| scheme_error(BfvErrorCode::INTERNAL))?;
+    let public_key = shares
+        .first()
+        .map(BfvSecretKeyShare::public_key)
+        .transpose()
+        .map_err(|_| scheme_error(BfvErrorCode::INTERNAL))?
+        .ok_or_else(|| scheme_error(BfvErrorCode::INTERNAL))?;
+    Ok(KeygenOutput { shares, public_key })
+}
@@ -0,0 +1,275 @@
+//! Key material and its binary encodings.
+//!
+//! The secret `s` is shared with a *replicated* sharing over `R_q`: for
+//! every subset `S` of `n − t + 1` parties there is a piece `s_S` held
+//! by every member of `S`, with `s = Σ_S s_S`. Any `t − 1` parties miss
+//! the piece of the complement of their set and learn nothing; any `t`
+//! parties hold every piece between them. Decryption is linear in `s`,
+//! so each participant decrypts with the pieces it is the first holder
+//! of and the partials simply add up — no Lagrange coefficients, which
+//! over `R_q` would multiply the smudging noise (see [`crate::decrypt`]).
+//!
+//! ```text
+//! params: degree u32 | t u64 | security u32 | moduli u8 | q_i u64*
+//! public: seed[32] | b
+//! share:  magic "TBFV" | version 1 | index u32 | threshold u32 | parties u32
+//!         | params | seed[32] | b | pieces u32 | (members u32 | held u8 | s_S?)*
+//! ```
+//!
+//! Polynomials are `n` big-endian `u64` coefficients per prime of `q`.
+//! The public key is `(b, a)` with `a` expanded from `seed`.
+
+use crate::error::{BfvError, Result};
+use crate::params::Context;
+use crate::ring::{Modulus, Poly};
+use crate::{BfvParams, BfvPublicKey, BfvSecretKeyShare};
+
+const SHARE_MAGIC: [u8; 4] = *b"TBFV";
+const SHARE_VERSION: u8 = 1;
+
+/// Largest party count; a power of two, see
+/// [`crate::params::MIN_SMUDGING_BITS`].
+pub const MAX_PARTIES: u32 = 8;
+
+/// Whether a `threshold`-of-`parties` key is supported.
+pub(crate) fn check_roster(threshold: u32, parties: u32) -> bool {
+    (1..=parties).contains(&threshold) && parties <= MAX_PARTIES
+}
+
+/// The members of every piece, bit `i − 1` for party `i`, ascending.
+pub(crate) fn piece_members(threshold: u32, parties: u32) -> Vec<u32> {
+    let size = parties - threshold + 1;
+    (0..1u32 << parties)
+        .filter(|m| m.count_ones() == size)
+        .collect()
+}
+
+/// Encode `params` for a DKG session message.
+pub fn encode_params(params: &BfvParams) -> Vec<u8> {
+    let mut out = Vec::new();
+    put_params(&mut out, params);
+    out
+}
+
+/// Decode [`encode_params`]' output.
+pub fn decode_params(bytes: &[u8]) -> Result<BfvParams> {
+    let bad = || BfvError::Malformed("parameters");
+    let mut r = Reader(bytes);
+    let params = r.params().map_err(|_| bad())?;
+    if !r.0.is_empty() {
+        return Err(bad());
+    }
+    Ok(params)
+}
+
+fn put_params(out: &mut Vec<u8>, params: &BfvParams) {
+    out.extend_from_slice(&(params.polynomial_degree as u32).to_be_bytes());
+    out.extend_from_slice(&params.plaintext_modulus.to_be_bytes());
+    out.extend_from_slice(&params.security_level.to_be_bytes());
+    out.push(params.coefficient_modulus.len() as u8);
+    for q in &params.coefficient_modulus {
+        out.extend_from_slice(&q.to_be_bytes());
+    }
+}
+
+/// A parsed public key.
+pub(crate) struct PublicKey {
+    pub ctx: Context,
+    pub a: Poly,
+    pub b: Poly,
+}
+
+impl PublicKey {
+    pub fn parse(key: &BfvPublicKey) -> Result<Self> {
+        let ctx = Context::new(&key.params)?;
+        let bad = || BfvError::Malformed("public key");
+        if !check_roster(key.threshold, key.parties) || key.bytes.len() < 32 {
+            return Err(bad());
+        }
+        let (seed, b) = key.bytes.split_at(32);
+        let seed: [u8; 32] = seed.try_into().map_err(|_| bad())?;
+        let b = Poly::get(b, &ctx.limbs, ctx.n).ok_or_else(bad)?;
+        Ok(PublicKey {
+            a: Poly::uniform(&ctx.limbs, ctx.n, &seed),
+            b,
+            ctx,
+        })
+    }
+}
+
+/// One subset's piece of the secret.
+pub(crate) struct Piece {
+    /// Bit `i − 1` set for every member `i` of the subset.
+    pub members: u32,
+    /// `s_S`, present when this party is a member.
+    pub secret: Option<Poly>,
+}
+
+impl Piece {
+    pub fn has_member(&self, index: u32) -> bool {
+        self.members >> (index - 1) & 1 == 1
+    }
+}
+
+/// A parsed key share.
+pub(crate) struct KeyShare {
+    pub index: u32,
+    pub threshold: u32,
+    pub parties: u32,
+    pub params: BfvParams,
+    pub ctx: Context,
+    /// Seed of the public `a`.
+    pub seed: [u8; 32],
+    pub b: Poly,
+    pub pieces: Vec<Piece>,
+}
+
+impl KeyShare {
+    pub fn public_key(&self) -> BfvPublicKey {
+        let mut bytes = self.seed.to_vec();
+        self.b.put(&mut bytes);
+        BfvPublicKey {
+            params: self.params.clone(),
+            threshold: self.threshold,
+            parties: self.parties,
+            bytes,
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = SHARE_MAGIC.to_vec();
+        out.push(SHARE_VERSION);
+        for word in [self.index, self.threshold, self.parties] {
+            out.extend_from_slice(&word.to_be_bytes());
+        }
+        put_params(&mut out, &self.params);
+        out.extend_from_slice(&self.seed);
+        self.b.put(&mut out);
+        out.extend_from_slice(&(self.pieces.len() as u32).to_be_bytes());
+        for piece in &self.pieces {
+            out.extend_from_slice(&piece.members.to_be_bytes());
+            out.push(u8::from(piece.secret.is_some()));
+            if let Some(secret) = &piece.secret {
+                secret.put(&mut out);
+            }
+        }
+        out
+    }
+
+    /// Decode a share, checking its structure: the roster, the piece
+    /// subsets, that exactly this party's pieces are held, and that
+    /// every coefficient is canonical.
+    pub fn from_bytes(data: &[u8]) -> Result<Self> {
+        let bad = || BfvError::Malformed("key share");
+        let mut r = Reader(data);
+        if r.take(4)? != SHARE_MAGIC || r.take(1)? != [SHARE_VERSION] {
+            return Err(bad());
+        }
+        let (index, threshold, parties) = (r.u32()?, r.u32()?, r.u32()?);
+        if !check_roster(threshold, parties) || !(1..=parties).contains(&index) {
+            return Err(bad());
+        }
+        let params = r.params()?;
+        let ctx = Context::new(&params)?;
+        let seed: [u8; 32] = r.take(32)?.try_into().map_err(|_| bad())?;
+        let b = r.poly(&ctx.limbs, ctx.n)?;
+
+        let members = piece_members(threshold, parties);
+        if r.u32()? as usize != members.len() {
+            return Err(bad());
+        }
+        let mut pieces = Vec::with_capacity(members.len());
+        for expected in members {
+            let piece_members = r.u32()?;
+            let held = match r.take(1)?[0] {
+                0 => false,
+                1 => true,
+                _ => return Err(bad()),
+            };
+            let secret = if held {
+                Some(r.poly(&ctx.limbs, ctx.n)?)
+            } else {
+                None
+            };
+            let piece = Piece {
+                members: piece_members,
+                secret,
+            };
+            if piece_members != expected || piece.has_member(index) != held {
+                return Err(bad());
+            }
+            pieces.push(piece);
+        }
+        if !r.0.is_empty() {
+            return Err(bad());
+        }
+        Ok(KeyShare {
+            index,
+            threshold,
+            parties,
+            params,
+            ctx,
+            seed,
+            b,
+            pieces,
+        })
+    }
+}
+
+impl BfvSecretKeyShare {
+    /// Wrap a share blob produced by the DKG, checking that it decodes.
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self> {
+        let share = KeyShare::from_bytes(&bytes)?;
+        Ok(BfvSecretKeyShare {
+            party_index: share.index,
+            bytes,
+        })
+    }
+
+    /// The threshold public key this share belongs to.
+    pub fn public_key(&self) -> Result<BfvPublicKey> {
+        Ok(KeyShare::from_bytes(&self.bytes)?.public_key())
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.0.len() < len {
+            return Err(BfvError::Malformed("key share"));
+        }
+        let (head, tail) = self.0.split_at(len);
+        self.0 = tail;
+        Ok(head)
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        let bytes = self.take(8)?;
+        Ok(u64::from_be_bytes(bytes.try_into().expect("8 bytes")))
+    }
+
+    fn params(&mut self) -> Result<BfvParams> {
+        let polynomial_degree = self.u32()? as usize;
+        let plaintext_modulus = self.u64()?;
+        let security_level = self.u32()?;
+        let count = self.take(1)?[0];
+        let coefficient_modulus = (0..count).map(|_| self.u64()).collect::<Result<_>>()?;
+        Ok(BfvParams {
+            polynomial_degree,
+            plaintext_modulus,
+            coefficient_modulus,
+            security_level,
+        })
+    }
+
+    fn poly(&mut self, limbs: &[Modulus], n: usize) -> Result<Poly> {
+        Poly::get(self.take(Poly::encoded_len(limbs, n))?, limbs, n)
+            .ok_or(BfvError::Malformed("key share"))
+    }
+}
@@ -1,111 +1,144 @@
-//! Threshold BFV fully homomorphic encryption — research prototype (P3).
+//! Threshold BFV homomorphic encryption — research prototype.
 //!
-//! Computation on encrypted data without decryption, plus threshold:
-//! computation requires quorum agreement. Long horizon research.
+//! Additively homomorphic aggregation under a key no single party
+//! holds. For OIML: statistical analysis of test reports without
+//! decrypting individual reports — compute aggregate quality metrics
+//! across manufacturers without revealing individual measurements.
 //!
-//! For OIML: statistical analysis of test reports without decrypting
-//! individual reports. Compute aggregate quality metrics across
-//! manufacturers without revealing individual measurements.
+//! - **Distributed keygen** — the [`dkg`] session (`BFV-threshold-dkg`)
+//!   gives every party a replicated share of a secret no one ever
+//!   holds; see [`keys`].
+//! - **Encryption and homomorphic operations** — [`bfv::encrypt`]
+//!   packs up to `polynomial_degree` values mod the plaintext modulus
+//!   into one ciphertext; [`bfv::add`] and [`bfv::mul_plain`] act slot
+//!   by slot.
+//! - **Threshold decryption** — any `T` key holders send partial
+//!   decryptions carrying smudging noise
+//!   ([`decrypt::partial_decrypt`]) of an aggregate they have checked
+//!   against its submissions, and [`decrypt::combine`] recovers the
+//!   plaintext.
+//! - **Aggregation** — an [`aggregate::AggregationSession`] sums the
+//!   encrypted vectors of many submitters and has the key holders
+//!   decrypt only the aggregate.
 //!
-//! See `TODO.roadmap/40-threshold-fhe.md` for full spec.
+//! [`BfvParams::recommended_128`] is a 4096-slot parameter set at
+//! 128-bit security. The prototype is secure against semi-honest
+//! parties only, supports at most [`keys::MAX_PARTIES`] key holders,
+//! and has neither relinearisation nor bootstrapping: ciphertexts can
+//! be added freely and multiplied by one plaintext (see [`bfv`]). It
+//! must not protect real data.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use confium_tc_fhe_bfv::aggregate::AggregationSession;
+//! use confium_tc_fhe_bfv::{bfv, decrypt, inprocess};
+//!
+//! let kg = inprocess::keygen(2, 3)?;
+//! let mut session = AggregationSession::new(kg.public_key.clone(), 2, 2)?;
+//! session.submit("lab-a", &bfv::encrypt(&kg.public_key, &[12, 1])?)?;
+//! session.submit("lab-b", &bfv::encrypt(&kg.public_key, &[30, 1])?)?;
+//! let request = session.close(&[1, 3])?;
+//! for share in [&kg.shares[0], &kg.shares[2]] {
+//!     let partial = decrypt::partial_decrypt(share, &request, 2)?;
+//!     session.submit_partial(partial)?;
+//! }
+//! assert_eq!(session.result(), Some(&[42, 2][..]));
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! See `TODO.roadmap/40-threshold-fhe.md` for the wider plan.
 
 #![forbid(unsafe_code)]
 #![allow(missing_docs)] // TODO: document before 1.0
 
+pub mod aggregate;
+pub mod bfv;
+pub mod decrypt;
+pub mod dkg;
+pub mod error;
+pub mod inprocess;
+pub mod keys;
+pub mod params;
+mod ring;
+
+pub use dkg::BfvThresholdDkg;
+pub use error::BfvError;
+
 use serde::{Deserialize, Serialize};
 
 /// BFV parameters.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct BfvParams {
     /// Polynomial degree (typically 4096-32768).
     pub polynomial_degree: usize,
     /// Plaintext modulus.
     pub plaintext_modulus: u64,
-    /// Coefficient modulus chain (one per level).
+    /// The primes whose product is the ciphertext modulus `q`.
     pub coefficient_modulus: Vec<u64>,
     /// Security level in bits (128 = current standard).
     pub security_level: u32,
 }
 
 impl BfvParams {
-    /// Recommended parameters for 128-bit security with moderate performance.
+    /// Recommended parameters for 128-bit security with moderate performance:
+    /// 4096 slots of values mod a 25-bit prime, and a 108-bit `q`.
     pub fn recommended_128() -> Self {
         Self {
             polynomial_degree: 4096,
-            plaintext_modulus: 65537,
-            coefficient_modulus: vec![0xFFFFFFFFFFFFFFF7u64],
+            plaintext_modulus: 33538049,
+            coefficient_modulus: vec![0x3ffffffffd6001, 0x3ffffffffd2001],
             security_level: 128,
         }
     }
 }
 
-/// BFV public key.
+/// Threshold BFV public key.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BfvPublicKey {
     /// Parameters used to generate the key.
     pub params: BfvParams,
-    /// Public key bytes (serialized polynomial pair).
+    /// Key holders needed to decrypt.
+    pub threshold: u32,
+    /// Number of key holders.
+    pub parties: u32,
+    /// Public key bytes (see [`keys`]).
     pub bytes: Vec<u8>,
 }
 
 /// Secret key share (held by one party).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct BfvSecretKeyShare {
     /// Party index.
     pub party_index: u32,
-    /// Share bytes (one polynomial per share).
+    /// Share bytes (see [`keys`]).
     pub bytes: Vec<u8>,
 }
 
+impl std::fmt::Debug for BfvSecretKeyShare {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BfvSecretKeyShare")
+            .field("party_index", &self.party_index)
+            .field("bytes", &"<redacted>")
+            .finish()
+    }
+}
+
 /// BFV ciphertext (pair of polynomials).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct BfvCiphertext {
+    /// Parameters the ciphertext was produced under.
+    pub params: BfvParams,
     /// C1 component.
     pub c1: Vec<u8>,
     /// C2 component.
     pub c2: Vec<u8>,
 }
 
-/// Errors during BFV operations.
-#[derive(Debug, thiserror::Error)]
-pub enum BfvError {
-    /// Parameters incompatible.
-    #[error("parameters incompatible: {0}")]
-    IncompatibleParams(String),
-    /// Threshold not met for decryption.
-    #[error("threshold not met: have {have}, need {need}")]
-    ThresholdNotMet {
-        /// Have count.
-        have: usize,
-        /// Need count.
-        need: u32,
-    },
-    /// Operation requires academic collaborator.
-    #[error("threshold BFV is research-only (P3): {0}")]
-    ResearchOnly(String),
-}
-
-/// Validate that BFV parameters are reasonable.
+/// Validate that BFV parameters are reasonable; see [`params`] for the
+/// conditions.
 pub fn validate_params(params: &BfvParams) -> Result<(), BfvError> {
-    if params.polynomial_degree < 1024 {
-        return Err(BfvError::IncompatibleParams(format!(
-            "polynomial_degree too small: {}",
-            params.polynomial_degree
-        )));
-    }
-    if !params.polynomial_degree.is_power_of_two() {
-        return Err(BfvError::IncompatibleParams(format!(
-            "polynomial_degree must be power of two: {}",
-            params.polynomial_degree
-        )));
-    }
-    if params.security_level < 128 {
-        return Err(BfvError::IncompatibleParams(format!(
-            "security_level below 128: {}",
-            params.security_level
-        )));
-    }
-    Ok(())
+    params::Context::new(params).map(|_| ())
 }
 
 #[cfg(test)]
@@ -128,6 +161,36 @@ mod tests {
         assert!(matches!(result, Err(BfvError::IncompatibleParams(_))));
     }
 
+    #[test]
+    fn unsuitable_moduli_rejected() {
+        let base = BfvParams::recommended_128();
+        for params in [
+            // Composite.
+            BfvParams {
+                coefficient_modulus: vec![0x3ffffffffd6001 * 3],
+                ..base.clone()
+            },
+            // Not 1 mod 2n.
+            BfvParams {
+                plaintext_modulus: 65521,
+                ..base.clone()
+            },
+            // 116-bit q at degree 4096.
+            BfvParams {
+                coefficient_modulus: vec![0x3ffffffffd6001, 0x3fffffffffff0001],
+                ..base.clone()
+            },
+            // No room for smudging.
+            BfvParams {
+                coefficient_modulus: vec![0x3ffffffffd6001],
+                ..base.clone()
+            },
+        ] {
+            let result = validate_params(&params);
+            assert!(matches!(result, Err(BfvError::IncompatibleParams(_))));
+        }
+    }
+
     #[test]
     fn low_security_rejected() {
         let params = BfvParams {
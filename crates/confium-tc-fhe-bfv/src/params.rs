@@ -0,0 +1,152 @@
+//! Parameter validation and the tables every operation works from.
+//!
+//! A parameter set is usable when:
+//!
+//! - `n` is a power of two between 1024 and 32768;
+//! - `q` is the product of one or two distinct primes below `2^62`, each
+//!   `≡ 1 mod 2n`, and at most as wide as the HomomorphicEncryption.org
+//!   standard allows for 128-bit security at degree `n`;
+//! - `t` is a prime `≡ 1 mod 2n` below `2^32`, so that `Z_t[x]/(x^n + 1)`
+//!   splits into `n` slots;
+//! - `Δ = ⌊q/t⌋` leaves room for smudging noise of at least
+//!   [`MIN_SMUDGING_BITS`] bits.
+
+use crate::BfvParams;
+use crate::error::{BfvError, Result};
+use crate::keys::MAX_PARTIES;
+use crate::ring::{Modulus, Poly};
+
+/// Widest `log q` at 128-bit classical security for a ternary secret,
+/// HomomorphicEncryption.org Security Standard (2018), table 1.
+const MAX_Q_BITS: [(usize, u32); 6] = [
+    (1024, 27),
+    (2048, 54),
+    (4096, 109),
+    (8192, 218),
+    (16384, 438),
+    (32768, 881),
+];
+
+/// Smallest smudging width a parameter set must allow.
+pub const MIN_SMUDGING_BITS: u32 = 40;
+
+/// A validated parameter set.
+#[derive(Clone)]
+pub(crate) struct Context {
+    pub n: usize,
+    pub t: Modulus,
+    pub limbs: Vec<Modulus>,
+    /// `Δ = ⌊q/t⌋`.
+    pub delta: u128,
+    /// `Δ mod q_i`.
+    pub delta_limbs: Vec<u64>,
+    /// `q_0⁻¹ mod q_1`.
+    crt: Option<u64>,
+    /// Every partial decryption adds noise uniform in
+    /// `[−2^smudging_bits, 2^smudging_bits)`. With at most
+    /// [`MAX_PARTIES`] partials the sum stays below `Δ/4`, which leaves
+    /// the other `Δ/4` to the ciphertext's own noise.
+    pub smudging_bits: u32,
+}
+
+fn incompatible(reason: String) -> BfvError {
+    BfvError::IncompatibleParams(reason)
+}
+
+impl Context {
+    pub fn new(params: &BfvParams) -> Result<Self> {
+        let n = params.polynomial_degree;
+        if n < 1024 {
+            return Err(incompatible(format!("polynomial_degree too small: {n}")));
+        }
+        if !n.is_power_of_two() {
+            return Err(incompatible(format!(
+                "polynomial_degree must be power of two: {n}"
+            )));
+        }
+        let max_bits = MAX_Q_BITS
+            .iter()
+            .find(|&&(degree, _)| degree == n)
+            .map(|&(_, bits)| bits)
+            .ok_or_else(|| incompatible(format!("polynomial_degree too large: {n}")))?;
+        if params.security_level < 128 {
+            return Err(incompatible(format!(
+                "security_level below 128: {}",
+                params.security_level
+            )));
+        }
+        if params.security_level > 128 {
+            return Err(incompatible(format!(
+                "only 128-bit parameter sets are supported: {}",
+                params.security_level
+            )));
+        }
+
+        let moduli = &params.coefficient_modulus;
+        if moduli.is_empty() || moduli.len() > 2 || moduli.first() == moduli.get(1) {
+            return Err(incompatible(
+                "coefficient_modulus must hold one or two distinct primes".into(),
+            ));
+        }
+        let limbs = moduli
+            .iter()
+            .map(|&q| {
+                Modulus::new(q, n).ok_or_else(|| {
+                    incompatible(format!(
+                        "coefficient modulus {q:#x} is not a prime below 2^62 that is 1 mod 2n"
+                    ))
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let q = moduli.iter().fold(1u128, |acc, &m| acc * u128::from(m));
+        let q_bits = 128 - q.leading_zeros();
+        if q_bits > max_bits {
+            return Err(incompatible(format!(
+                "coefficient modulus is {q_bits} bits, above {max_bits} for degree {n}"
+            )));
+        }
+
+        let t = params.plaintext_modulus;
+        let t_mod = (t < 1 << 32)
+            .then(|| Modulus::new(t, n))
+            .flatten()
+            .ok_or_else(|| {
+                incompatible(format!(
+                    "plaintext modulus {t} is not a prime below 2^32 that is 1 mod 2n"
+                ))
+            })?;
+        let delta = q / u128::from(t);
+        let smudging_bits =
+            (127 - delta.leading_zeros()).saturating_sub(2 + MAX_PARTIES.trailing_zeros());
+        if smudging_bits < MIN_SMUDGING_BITS {
+            return Err(incompatible(format!(
+                "q/t leaves {smudging_bits} bits of smudging noise, below {MIN_SMUDGING_BITS}"
+            )));
+        }
+
+        Ok(Context {
+            n,
+            delta_limbs: limbs
+                .iter()
+                .map(|m| (delta % u128::from(m.p)) as u64)
+                .collect(),
+            crt: limbs.get(1).map(|m| m.inv(limbs[0].p % m.p)),
+            limbs,
+            t: t_mod,
+            delta,
+            smudging_bits,
+        })
+    }
+
+    /// Coefficient `i` of `poly` as an integer in `[0, q)`.
+    pub fn lift(&self, poly: &Poly, i: usize) -> u128 {
+        let x0 = poly.0[0][i];
+        match (self.crt, self.limbs.get(1)) {
+            (Some(inv), Some(m1)) => {
+                let k = m1.mul(m1.sub(poly.0[1][i], x0 % m1.p), inv);
+                u128::from(x0) + u128::from(self.limbs[0].p) * u128::from(k)
+            }
+            _ => u128::from(x0),
+        }
+    }
+}
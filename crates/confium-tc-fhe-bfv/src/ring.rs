@@ -0,0 +1,426 @@
+//! Arithmetic in `Z_q[x]/(x^n + 1)`.
+//!
+//! `q` is a product of one or two word-sized primes and polynomials are
+//! kept in residue number system form: one row of `n` coefficients per
+//! prime. Every prime is `≡ 1 mod 2n`, so products go through the
+//! negacyclic NTT row by row. The plaintext modulus `t` is such a prime
+//! too, which is what makes slot batching possible (see
+//! [`crate::bfv`]).
+
+use rand_core::{OsRng, RngCore};
+use sha3::Shake256;
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+
+/// Bases for which Miller–Rabin is exact below `3.3·10^24`.
+const WITNESSES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+/// Binomial parameter of the error distribution, standard deviation
+/// `√(21/2) ≈ 3.24`.
+const ETA: usize = 21;
+
+/// Domain separator for [`Poly::uniform`].
+const UNIFORM_DOMAIN: &[u8] = b"confium-tc-fhe-bfv/uniform";
+
+fn mul_mod(a: u64, b: u64, p: u64) -> u64 {
+    (u128::from(a) * u128::from(b) % u128::from(p)) as u64
+}
+
+fn pow_mod(mut base: u64, mut exp: u64, p: u64) -> u64 {
+    let mut acc = 1;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            acc = mul_mod(acc, base, p);
+        }
+        base = mul_mod(base, base, p);
+        exp >>= 1;
+    }
+    acc
+}
+
+/// Deterministic Miller–Rabin.
+pub(crate) fn is_prime(n: u64) -> bool {
+    if n < 2 {
+        return false;
+    }
+    for p in WITNESSES {
+        if n % p == 0 {
+            return n == p;
+        }
+    }
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    WITNESSES.iter().all(|&a| {
+        let mut x = pow_mod(a, d, n);
+        if x == 1 || x == n - 1 {
+            return true;
+        }
+        for _ in 1..s {
+            x = mul_mod(x, x, n);
+            if x == n - 1 {
+                return true;
+            }
+        }
+        false
+    })
+}
+
+/// A prime `p < 2^62` with `p ≡ 1 mod 2n`, and the powers of a primitive
+/// `2n`-th root of unity `ψ` the negacyclic NTT needs.
+#[derive(Debug, Clone)]
+pub(crate) struct Modulus {
+    pub p: u64,
+    /// `ψ^brv(i)` for `i < n`, `brv` reversing `log n` bits.
+    zetas: Vec<u64>,
+    /// `n⁻¹ mod p`.
+    n_inv: u64,
+}
+
+impl Modulus {
+    /// `None` unless `p` is such a prime.
+    pub fn new(p: u64, n: usize) -> Option<Self> {
+        let order = 2 * n as u64;
+        if p >= 1 << 62 || p % order != 1 || !is_prime(p) {
+            return None;
+        }
+        // ψ = g^((p−1)/2n) has order exactly 2n iff ψ^n = g^((p−1)/2) = −1,
+        // that is iff g is a non-residue.
+        let g = (2..p).find(|&g| pow_mod(g, (p - 1) / 2, p) == p - 1)?;
+        let psi = pow_mod(g, (p - 1) / order, p);
+        let mut powers = Vec::with_capacity(n);
+        let mut x = 1;
+        for _ in 0..n {
+            powers.push(x);
+            x = mul_mod(x, psi, p);
+        }
+        let bits = n.trailing_zeros();
+        let zetas = (0..n)
+            .map(|i| powers[i.reverse_bits() >> (usize::BITS - bits)])
+            .collect();
+        Some(Modulus {
+            p,
+            zetas,
+            n_inv: pow_mod(n as u64, p - 2, p),
+        })
+    }
+
+    pub fn add(&self, a: u64, b: u64) -> u64 {
+        let s = a + b;
+        if s >= self.p { s - self.p } else { s }
+    }
+
+    pub fn sub(&self, a: u64, b: u64) -> u64 {
+        if a >= b { a - b } else { a + self.p - b }
+    }
+
+    pub fn mul(&self, a: u64, b: u64) -> u64 {
+        mul_mod(a, b, self.p)
+    }
+
+    pub fn inv(&self, a: u64) -> u64 {
+        pow_mod(a, self.p - 2, self.p)
+    }
+
+    pub fn reduce_i64(&self, x: i64) -> u64 {
+        i128::from(x).rem_euclid(i128::from(self.p)) as u64
+    }
+
+    pub fn reduce_i128(&self, x: i128) -> u64 {
+        x.rem_euclid(i128::from(self.p)) as u64
+    }
+
+    /// `x` in `(−p/2, p/2]`.
+    pub fn centered(&self, x: u64) -> i64 {
+        if x > self.p / 2 {
+            x as i64 - self.p as i64
+        } else {
+            x as i64
+        }
+    }
+
+    /// Forward negacyclic NTT, output in bit-reversed order.
+    pub fn ntt(&self, a: &mut [u64]) {
+        let n = a.len();
+        let mut k = 0;
+        let mut len = n / 2;
+        while len > 0 {
+            for start in (0..n).step_by(2 * len) {
+                k += 1;
+                let zeta = self.zetas[k];
+                for j in start..start + len {
+                    let t = self.mul(zeta, a[j + len]);
+                    a[j + len] = self.sub(a[j], t);
+                    a[j] = self.add(a[j], t);
+                }
+            }
+            len /= 2;
+        }
+    }
+
+    /// Inverse of [`Modulus::ntt`].
+    pub fn inv_ntt(&self, a: &mut [u64]) {
+        let n = a.len();
+        let mut k = n;
+        let mut len = 1;
+        while len < n {
+            for start in (0..n).step_by(2 * len) {
+                k -= 1;
+                let zeta = self.p - self.zetas[k];
+                for j in start..start + len {
+                    let t = a[j];
+                    a[j] = self.add(t, a[j + len]);
+                    a[j + len] = self.mul(zeta, self.sub(t, a[j + len]));
+                }
+            }
+            len *= 2;
+        }
+        for x in a.iter_mut() {
+            *x = self.mul(*x, self.n_inv);
+        }
+    }
+}
+
+/// A polynomial of `Z_q[x]/(x^n + 1)` in coefficient form, one row per
+/// prime of `q`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Poly(pub Vec<Vec<u64>>);
+
+impl Poly {
+    pub fn zero(limbs: &[Modulus], n: usize) -> Self {
+        Poly(vec![vec![0; n]; limbs.len()])
+    }
+
+    /// Lift small signed coefficients.
+    pub fn from_small(limbs: &[Modulus], coeffs: &[i64]) -> Self {
+        Poly(
+            limbs
+                .iter()
+                .map(|m| coeffs.iter().map(|&c| m.reduce_i64(c)).collect())
+                .collect(),
+        )
+    }
+
+    /// Lift wide signed coefficients.
+    pub fn from_wide(limbs: &[Modulus], coeffs: &[i128]) -> Self {
+        Poly(
+            limbs
+                .iter()
+                .map(|m| coeffs.iter().map(|&c| m.reduce_i128(c)).collect())
+                .collect(),
+        )
+    }
+
+    /// A uniformly random polynomial expanded from `seed` with SHAKE256.
+    pub fn uniform(limbs: &[Modulus], n: usize, seed: &[u8; 32]) -> Self {
+        let mut xof = Shake256::default();
+        xof.update(UNIFORM_DOMAIN);
+        xof.update(seed);
+        let mut reader = xof.finalize_xof();
+        let rows = limbs
+            .iter()
+            .map(|m| {
+                let mask = u64::MAX >> m.p.leading_zeros();
+                let mut row = Vec::with_capacity(n);
+                let mut buf = [0u8; 8];
+                while row.len() < n {
+                    reader.read(&mut buf);
+                    let x = u64::from_le_bytes(buf) & mask;
+                    if x < m.p {
+                        row.push(x);
+                    }
+                }
+                row
+            })
+            .collect();
+        Poly(rows)
+    }
+
+    /// A uniformly random polynomial from fresh randomness.
+    pub fn random(limbs: &[Modulus], n: usize) -> Self {
+        let mut seed = [0u8; 32];
+        OsRng.fill_bytes(&mut seed);
+        let poly = Self::uniform(limbs, n, &seed);
+        seed.fill(0);
+        poly
+    }
+
+    fn zip(&self, other: &Poly, limbs: &[Modulus], f: impl Fn(&Modulus, u64, u64) -> u64) -> Poly {
+        Poly(
+            limbs
+                .iter()
+                .zip(self.0.iter().zip(&other.0))
+                .map(|(m, (a, b))| a.iter().zip(b).map(|(&x, &y)| f(m, x, y)).collect())
+                .collect(),
+        )
+    }
+
+    pub fn add(&self, other: &Poly, limbs: &[Modulus]) -> Poly {
+        self.zip(other, limbs, Modulus::add)
+    }
+
+    pub fn sub(&self, other: &Poly, limbs: &[Modulus]) -> Poly {
+        self.zip(other, limbs, Modulus::sub)
+    }
+
+    /// Multiply every coefficient by a constant given per prime.
+    pub fn scale(&self, factor: &[u64], limbs: &[Modulus]) -> Poly {
+        Poly(
+            limbs
+                .iter()
+                .zip(&self.0)
+                .zip(factor)
+                .map(|((m, row), &c)| row.iter().map(|&x| m.mul(x, c)).collect())
+                .collect(),
+        )
+    }
+
+    pub fn mul(&self, other: &Poly, limbs: &[Modulus]) -> Poly {
+        Poly(
+            limbs
+                .iter()
+                .zip(self.0.iter().zip(&other.0))
+                .map(|(m, (a, b))| {
+                    let (mut a, mut b) = (a.clone(), b.clone());
+                    m.ntt(&mut a);
+                    m.ntt(&mut b);
+                    for (x, y) in a.iter_mut().zip(&b) {
+                        *x = m.mul(*x, *y);
+                    }
+                    m.inv_ntt(&mut a);
+                    a
+                })
+                .collect(),
+        )
+    }
+
+    /// Append every coefficient as 8 big-endian bytes, row by row.
+    pub fn put(&self, out: &mut Vec<u8>) {
+        for x in self.0.iter().flatten() {
+            out.extend_from_slice(&x.to_be_bytes());
+        }
+    }
+
+    /// Encoded length of a polynomial.
+    pub fn encoded_len(limbs: &[Modulus], n: usize) -> usize {
+        8 * n * limbs.len()
+    }
+
+    /// Decode [`Poly::put`]'s output, refusing non-canonical
+    /// coefficients.
+    pub fn get(bytes: &[u8], limbs: &[Modulus], n: usize) -> Option<Poly> {
+        if bytes.len() != Self::encoded_len(limbs, n) {
+            return None;
+        }
+        limbs
+            .iter()
+            .zip(bytes.chunks(8 * n))
+            .map(|(m, chunk)| {
+                chunk
+                    .chunks(8)
+                    .map(|c| {
+                        let x = u64::from_be_bytes(c.try_into().ok()?);
+                        (x < m.p).then_some(x)
+                    })
+                    .collect()
+            })
+            .collect::<Option<Vec<_>>>()
+            .map(Poly)
+    }
+}
+
+/// Uniform coefficients in `{−1, 0, 1}`.
+pub(crate) fn ternary(n: usize) -> Vec<i64> {
+    let mut out = Vec::with_capacity(n);
+    let mut buf = vec![0u8; n];
+    while out.len() < n {
+        OsRng.fill_bytes(&mut buf);
+        // 255 = 3·85, so bytes below it are uniform mod 3.
+        out.extend(
+            buf.iter()
+                .filter(|&&b| b < 255)
+                .map(|&b| i64::from(b % 3) - 1)
+                .take(n - out.len()),
+        );
+    }
+    buf.fill(0);
+    out
+}
+
+/// Centred binomial coefficients, the sum of [`ETA`] coin differences.
+pub(crate) fn cbd(n: usize) -> Vec<i64> {
+    let mut buf = vec![0u8; 8 * n];
+    OsRng.fill_bytes(&mut buf);
+    let out = buf
+        .chunks(8)
+        .map(|c| {
+            let x = u64::from_le_bytes(c.try_into().expect("8-byte chunk"));
+            let mask = (1u64 << ETA) - 1;
+            i64::from((x & mask).count_ones()) - i64::from((x >> ETA & mask).count_ones())
+        })
+        .collect();
+    buf.fill(0);
+    out
+}
+
+/// Uniform coefficients in `[−2^bits, 2^bits)`, `bits < 127`.
+pub(crate) fn smudging(n: usize, bits: u32) -> Vec<i128> {
+    let mut buf = vec![0u8; 16 * n];
+    OsRng.fill_bytes(&mut buf);
+    let out = buf
+        .chunks(16)
+        .map(|c| {
+            let x = u128::from_le_bytes(c.try_into().expect("16-byte chunk"));
+            (x >> (127 - bits)) as i128 - (1i128 << bits)
+        })
+        .collect();
+    buf.fill(0);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const P: u64 = 0x3ffffffffd6001;
+
+    #[test]
+    fn primality() {
+        assert!(is_prime(P));
+        assert!(is_prime(33538049));
+        assert!(is_prime(0xFFFFFFFFFFFFFFC5));
+        assert!(!is_prime(561));
+        assert!(!is_prime(P * 5));
+    }
+
+    #[test]
+    fn ntt_product_is_negacyclic() {
+        let n = 16;
+        let limbs = [Modulus::new(P, n).unwrap(), Modulus::new(97, n).unwrap()];
+        let a: Vec<i64> = (0..n as i64).map(|i| i * 7 - 40).collect();
+        let b: Vec<i64> = (0..n as i64).map(|i| 3 - i * i).collect();
+        let mut want = vec![0i64; n];
+        for i in 0..n {
+            for j in 0..n {
+                let term = a[i] * b[j];
+                if i + j < n {
+                    want[i + j] += term;
+                } else {
+                    want[i + j - n] -= term;
+                }
+            }
+        }
+        let got = Poly::from_small(&limbs, &a).mul(&Poly::from_small(&limbs, &b), &limbs);
+        assert_eq!(got, Poly::from_small(&limbs, &want));
+    }
+
+    #[test]
+    fn samplers_stay_in_range() {
+        assert!(ternary(4096).iter().all(|c| (-1..=1).contains(c)));
+        assert!(cbd(4096).iter().all(|c| c.abs() <= ETA as i64));
+        let bound = 1i128 << 70;
+        assert!(
+            smudging(4096, 70)
+                .iter()
+                .all(|c| (-bound..bound).contains(c))
+        );
+    }
+}
@@ -0,0 +1,236 @@
+//! End-to-end tests: DKG, encryption, homomorphic operations and
+//! threshold decryption through the aggregation session.
+
+use confium_tc_fhe_bfv::aggregate::{
+    AggregationSession, AggregationState, DecryptionRequest, Submission,
+};
+use confium_tc_fhe_bfv::decrypt::{self, PartialDecryption};
+use confium_tc_fhe_bfv::inprocess::{self, KeygenOutput};
+use confium_tc_fhe_bfv::{BfvCiphertext, BfvError, BfvParams, bfv};
+
+/// A request to decrypt `ct` as the only submission.
+fn request(ct: &BfvCiphertext, participants: &[u32]) -> DecryptionRequest {
+    DecryptionRequest {
+        aggregate: ct.clone(),
+        participants: participants.to_vec(),
+        submissions: vec![Submission {
+            submitter: "lab".into(),
+            ciphertext: ct.clone(),
+        }],
+    }
+}
+
+fn decrypt_with(kg: &KeygenOutput, participants: &[u32], ct: &BfvCiphertext) -> Vec<u64> {
+    let request = request(ct, participants);
+    let partials: Vec<PartialDecryption> = participants
+        .iter()
+        .map(|&i| decrypt::partial_decrypt(&kg.shares[i as usize - 1], &request, 1).unwrap())
+        .collect();
+    decrypt::combine(&kg.public_key, ct, &partials).unwrap()
+}
+
+#[test]
+fn aggregation_decrypts_the_sum_of_many_submissions() {
+    let kg = inprocess::keygen(3, 5).unwrap();
+    assert_eq!(kg.public_key.params, BfvParams::recommended_128());
+    assert!(
+        kg.shares
+            .iter()
+            .all(|s| s.public_key().unwrap().bytes == kg.public_key.bytes)
+    );
+
+    let mut session = AggregationSession::new(kg.public_key.clone(), 6, 10).unwrap();
+    let mut want = [0u64; 6];
+    for lab in 0..24u64 {
+        // Drift in ppm, pass/fail count, and a few magnitudes near the
+        // top of the plaintext range.
+        let report = [lab * 37 % 1000, lab % 2, 1, 1_000_000 + lab, 0, lab * lab];
+        for (total, value) in want.iter_mut().zip(report) {
+            *total += value;
+        }
+        let ct = bfv::encrypt(&kg.public_key, &report).unwrap();
+        assert_eq!(
+            session.submit(&format!("lab-{lab}"), &ct).unwrap(),
+            lab as usize + 1
+        );
+    }
+
+    let request = session.close(&[2, 4, 5]).unwrap();
+    assert_eq!(request.submissions.len(), 24);
+    assert_eq!(session.state(), AggregationState::Decrypting);
+    let mut result = None;
+    for &i in &request.participants {
+        let partial = decrypt::partial_decrypt(&kg.shares[i as usize - 1], &request, 10).unwrap();
+        result = session
+            .submit_partial(partial)
+            .unwrap()
+            .map(<[u64]>::to_vec);
+    }
+    assert_eq!(result.as_deref(), Some(&want[..]));
+    assert_eq!(session.result(), Some(&want[..]));
+    assert_eq!(session.state(), AggregationState::Completed);
+}
+
+#[test]
+fn any_threshold_subset_decrypts_sums_and_products() {
+    let kg = inprocess::keygen(2, 4).unwrap();
+    let t = kg.public_key.params.plaintext_modulus;
+    let slots = kg.public_key.params.polynomial_degree;
+    let a: Vec<u64> = (0..slots as u64).map(|i| (i * 7919 + 3) % t).collect();
+    let b: Vec<u64> = (0..slots as u64).map(|i| t - 1 - i).collect();
+    let weights: Vec<u64> = (0..slots as u64).map(|i| i % 5).collect();
+
+    let ct_a = bfv::encrypt(&kg.public_key, &a).unwrap();
+    let ct_b = bfv::encrypt(&kg.public_key, &b).unwrap();
+    let sum = bfv::add(&ct_a, &ct_b).unwrap();
+    let weighted = bfv::mul_plain(&sum, &weights).unwrap();
+
+    let want_sum: Vec<u64> = a.iter().zip(&b).map(|(x, y)| (x + y) % t).collect();
+    let want_weighted: Vec<u64> = want_sum
+        .iter()
+        .zip(&weights)
+        .map(|(x, w)| x * w % t)
+        .collect();
+    for participants in [&[1, 2][..], &[2, 4], &[1, 3, 4]] {
+        assert_eq!(decrypt_with(&kg, participants, &ct_a), a);
+        assert_eq!(decrypt_with(&kg, participants, &sum), want_sum);
+        assert_eq!(decrypt_with(&kg, participants, &weighted), want_weighted);
+    }
+}
+
+#[test]
+fn decryption_below_threshold_is_refused() {
+    let kg = inprocess::keygen(3, 4).unwrap();
+    let ct = bfv::encrypt(&kg.public_key, &[1, 2, 3]).unwrap();
+
+    assert!(matches!(
+        decrypt::partial_decrypt(&kg.shares[0], &request(&ct, &[1, 2]), 1),
+        Err(BfvError::ThresholdNotMet { have: 2, need: 3 })
+    ));
+    assert!(matches!(
+        decrypt::partial_decrypt(&kg.shares[0], &request(&ct, &[2, 3, 4]), 1),
+        Err(BfvError::InvalidParticipants(_))
+    ));
+    assert!(matches!(
+        decrypt::partial_decrypt(&kg.shares[0], &request(&ct, &[3, 1, 2]), 1),
+        Err(BfvError::InvalidParticipants(_))
+    ));
+
+    let participants = [1, 2, 4];
+    let request = request(&ct, &participants);
+    let partials: Vec<PartialDecryption> = participants
+        .iter()
+        .map(|&i| decrypt::partial_decrypt(&kg.shares[i as usize - 1], &request, 1).unwrap())
+        .collect();
+    assert!(matches!(
+        decrypt::combine(&kg.public_key, &ct, &partials[..2]),
+        Err(BfvError::InvalidParticipants(_))
+    ));
+    let mut slots = decrypt::combine(&kg.public_key, &ct, &partials).unwrap();
+    slots.truncate(4);
+    assert_eq!(slots, [1, 2, 3, 0]);
+}
+
+#[test]
+fn aggregation_session_enforces_its_lifecycle() {
+    let kg = inprocess::keygen(2, 3).unwrap();
+    let ct = bfv::encrypt(&kg.public_key, &[5]).unwrap();
+    let mut session = AggregationSession::new(kg.public_key.clone(), 1, 2).unwrap();
+
+    session.submit("a", &ct).unwrap();
+    assert!(matches!(
+        session.submit("a", &ct),
+        Err(BfvError::DuplicateSubmission(_))
+    ));
+    assert!(matches!(
+        session.close(&[1, 2]),
+        Err(BfvError::TooFewSubmissions { have: 1, need: 2 })
+    ));
+    let garbled = BfvCiphertext {
+        c1: vec![0xFF; ct.c1.len()],
+        ..ct.clone()
+    };
+    assert!(matches!(
+        session.submit("b", &garbled),
+        Err(BfvError::Malformed(_))
+    ));
+    session.submit("b", &ct).unwrap();
+    assert!(matches!(
+        session.close(&[1]),
+        Err(BfvError::ThresholdNotMet { .. })
+    ));
+
+    let request = session.close(&[1, 3]).unwrap();
+    assert!(matches!(
+        session.submit("c", &ct),
+        Err(BfvError::InvalidState { .. })
+    ));
+    let outsider = DecryptionRequest {
+        participants: vec![1, 2],
+        ..request.clone()
+    };
+    let outsider = decrypt::partial_decrypt(&kg.shares[1], &outsider, 2).unwrap();
+    assert!(matches!(
+        session.submit_partial(outsider),
+        Err(BfvError::InvalidParticipants(_))
+    ));
+    let first = decrypt::partial_decrypt(&kg.shares[0], &request, 2).unwrap();
+    assert_eq!(session.submit_partial(first.clone()).unwrap(), None);
+    assert!(matches!(
+        session.submit_partial(first),
+        Err(BfvError::DuplicateSubmission(_))
+    ));
+    let last = decrypt::partial_decrypt(&kg.shares[2], &request, 2).unwrap();
+    assert_eq!(session.submit_partial(last).unwrap(), Some(&[10][..]));
+}
+
+#[test]
+fn key_holders_only_decrypt_aggregates_of_enough_submissions() {
+    let kg = inprocess::keygen(2, 3).unwrap();
+    let a = bfv::encrypt(&kg.public_key, &[7]).unwrap();
+    let b = bfv::encrypt(&kg.public_key, &[9]).unwrap();
+
+    // A coordinator asking for a single submission is refused.
+    let single = request(&a, &[1, 2]);
+    assert!(matches!(
+        decrypt::partial_decrypt(&kg.shares[0], &single, 2),
+        Err(BfvError::TooFewSubmissions { have: 1, need: 2 })
+    ));
+
+    // So is one passing a single submission off as the sum of two.
+    let mut disguised = DecryptionRequest {
+        submissions: vec![
+            Submission {
+                submitter: "lab-a".into(),
+                ciphertext: a.clone(),
+            },
+            Submission {
+                submitter: "lab-b".into(),
+                ciphertext: b.clone(),
+            },
+        ],
+        ..single
+    };
+    assert!(matches!(
+        decrypt::partial_decrypt(&kg.shares[0], &disguised, 2),
+        Err(BfvError::AggregateMismatch)
+    ));
+
+    // Or padding the count with a repeated submitter.
+    disguised.submissions[1].submitter = "lab-a".into();
+    disguised.aggregate = bfv::add(&a, &b).unwrap();
+    assert!(matches!(
+        decrypt::partial_decrypt(&kg.shares[0], &disguised, 2),
+        Err(BfvError::DuplicateSubmission(_))
+    ));
+
+    disguised.submissions[1].submitter = "lab-b".into();
+    assert!(decrypt::partial_decrypt(&kg.shares[0], &disguised, 2).is_ok());
+}
+
+#[test]
+fn unsupported_rosters_are_refused() {
+    assert!(inprocess::keygen(0, 3).is_err());
+    assert!(inprocess::keygen(4, 3).is_err());
+    assert!(inprocess::keygen(2, 9).is_err());
+}
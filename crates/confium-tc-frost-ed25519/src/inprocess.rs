@@ -1,12 +1,13 @@
 //! In-process synchronous driver for FROST-ed25519 DKG and signing.
 //!
-//! Thin wrapper over [`confium_tc::inprocess`] that names the
-//! FROST-ed25519 schemes and pulls the joint public key out of the
-//! first share. Completes the threshold-ECDSA / threshold-EdDSA
-//! matrix — CMP20 and GG18 cover P-256; this covers Ed25519.
+//! [`confium_tc_frost::inprocess`] specialised to [`Ed25519`].
+//! Completes the threshold-ECDSA / threshold-EdDSA matrix — CMP20 and
+//! GG18 cover P-256; this covers Ed25519.
 
 use confium_tc::Result;
-use confium_tc::inprocess as driver;
+use confium_tc_frost::inprocess as driver;
+
+use crate::Ed25519;
 
 /// Outcome of a FROST-ed25519 DKG.
 #[derive(Debug, Clone)]
@@ -20,13 +21,24 @@ pub struct KeygenOutput {
 /// Run FROST-ed25519 DKG for `party_count` parties at threshold
 /// `threshold`.
 pub fn keygen(threshold: u32, party_count: usize) -> Result<Vec<Vec<u8>>> {
-    driver::run_dkg(crate::DKG_SCHEME, threshold, party_count)
+    driver::keygen::<Ed25519>(threshold, party_count)
 }
 
 /// Threshold-sign `message` with FROST-ed25519. Returns a 64-byte
 /// Ed25519 signature.
 pub fn sign(share_blobs: &[Vec<u8>], threshold: u32, message: &[u8]) -> Result<Vec<u8>> {
-    driver::run_sign(crate::SIGN_SCHEME, share_blobs, threshold, message)
+    driver::sign::<Ed25519>(share_blobs, threshold, message)
+}
+
+/// One-round sign: answer a coordinator's signing package with the
+/// [`crate::preprocess::PreprocessedShare`] blobs of exactly its
+/// signers. Returns a 64-byte Ed25519 signature.
+pub fn sign_preprocessed(
+    share_blobs: &[Vec<u8>],
+    threshold: u32,
+    package: &[u8],
+) -> Result<Vec<u8>> {
+    driver::sign_preprocessed::<Ed25519>(share_blobs, threshold, package)
 }
 
 /// Sign N messages against the same joint key.
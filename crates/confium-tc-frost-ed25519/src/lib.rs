@@ -1,67 +1,66 @@
-//! FROST threshold signature scheme over ed25519 (draft-irtf-cfrg-frost).
+//! FROST threshold signatures over Ed25519 (RFC 9591).
 //!
-//! A real cryptographic implementation of FROST, registered with the
-//! [`confium_tc`] link-time scheme registry under two names:
+//! The `FROST-ED25519-SHA512-v1` ciphersuite itself lives in the
+//! generic FROST core as [`confium_tc_frost::ed25519::Ed25519`], which
+//! registers both schemes with the [`confium_tc`] link-time registry:
 //!
-//! - [`sign::SCHEME_NAME`] = `"FROST-ed25519"` — threshold signing.
-//!   Produces a standard RFC-8032 ed25519 signature `(R, z)` verifiable
-//!   by any conformant verifier (e.g. `ed25519-dalek`).
+//! - [`SIGN_SCHEME`] = `"FROST-ed25519"` — threshold signing. Produces
+//!   a standard RFC 8032 Ed25519 signature `(R, z)` verifiable by any
+//!   conformant verifier (e.g. `ed25519-dalek`).
 //!
-//! - [`dkg::SCHEME_NAME`] = `"FROST-ed25519-dkg"` — distributed key
-//!   generation via Pedersen / Feldman VSS. Produces a per-party share
-//!   plus the aggregate public key, encoded in the session's
-//!   [`confium_tc::Session::result`] as a length-prefixed blob
-//!   (`pubkey || share`). Pass that blob directly into a signing
-//!   session's [`confium_tc::SessionParams::local_share`].
+//! - [`DKG_SCHEME`] = `"FROST-ed25519-dkg"` — distributed key
+//!   generation via Pedersen / Feldman VSS. The session result is a
+//!   [`KeyPackage`] blob; pass it directly into a signing session's
+//!   [`confium_tc::SessionParams::local_share`].
 //!
-//! ## Protocol at a glance
+//! This crate keeps the Ed25519 names callers already use —
+//! [`FrostEd25519`], [`FrostEd25519Dkg`], [`inprocess`],
+//! [`preprocess`] — as aliases of the generic types. The RFC 9591
+//! Appendix E.1 vectors are checked in `confium-tc-frost`'s
+//! `tests/rfc9591.rs`.
 //!
-//! ### DKG (2 rounds)
+//! ## Preprocessed signing (1 online round)
 //!
-//! 1. Each party generates a degree-`T-1` VSS polynomial, broadcasts its
-//!    Feldman commitment list, and directs per-peer share fragments.
-//! 2. Each party verifies the fragments, aggregates them, and sums the
-//!    commitment-list constant terms to get the aggregate public key.
-//!
-//! ### Signing (3 rounds)
-//!
-//! 1. Each party broadcasts nonce commitments `(D_i, E_i)`.
-//! 2. Each party receives all commitments, derives binding factors, the
-//!    group commitment `R`, the challenge `c = SHA-512(R ‖ A ‖ M)`,
-//!    computes `z_i = d_i + ρ_i·e_i + λ_i·s_i·c`, and broadcasts it.
-//! 3. Each party aggregates `z = Σ z_i`, verifies `z·B == R + c·A`, and
-//!    emits `(R, z)`.
-//!
-//! ## Status / deviations
-//!
-//! See the module-level notes in [`dkg`] and [`sign`] for the full list
-//! of deviations from the textbook FROST protocol. The headline gaps:
-//!
-//! - **No DKG complaint round.** Byzantine VSS senders are silently
-//!   excluded; honest parties still converge on the same key.
-//! - **Nonce generation uses `OsRng`**, not the spec's deterministic
-//!   H3 derivation.
-//! - **Per-party share-response verification is partial.** The aggregate
-//!   signature is always verified; identifying which peer was byzantine
-//!   requires distributing per-party public shares during DKG, which is
-//!   future work.
+//! [`preprocess`] moves the commitment round offline: parties publish
+//! batches of commitments ahead of time, the coordinator assigns one per
+//! signer to each message, and each signer answers with `z_i` straight
+//! away — in a [`PREPROCESSED_SCHEME`] session whose local share is a
+//! [`preprocess::PreprocessedShare`] and whose message is the
+//! coordinator's [`preprocess::SigningPackage`]. Every pooled nonce is
+//! recorded as spent in the party's [`confium_tc::NonceLedger`] before
+//! it is used.
 
-pub mod dkg;
-pub mod error;
-pub mod group;
 pub mod inprocess;
-pub mod polynomial;
-pub mod sign;
-pub mod transcript;
+pub mod preprocess;
+
+pub use confium_tc_frost::ed25519::Ed25519;
+pub use confium_tc_frost::error;
 
-// Re-export the scheme types and the DKG output parser for callers that
-// want to drive the scheme directly (the test harness does this).
-pub use dkg::FrostEd25519Dkg;
-pub use dkg::parse_output as parse_dkg_output;
-pub use sign::FrostEd25519;
+use confium_tc_frost::Ciphersuite;
+
+/// The FROST-ed25519 signing scheme.
+pub type FrostEd25519 = confium_tc_frost::FrostSigning<Ed25519>;
+
+/// The FROST-ed25519 DKG scheme.
+pub type FrostEd25519Dkg = confium_tc_frost::FrostDkg<Ed25519>;
+
+/// The one-round FROST-ed25519 signing scheme.
+pub type FrostEd25519Preprocessed = confium_tc_frost::FrostPreprocessedSigning<Ed25519>;
+
+/// A FROST-ed25519 DKG output: this party's share plus the group key.
+pub type KeyPackage = confium_tc_frost::KeyPackage<Ed25519>;
 
 /// Convenience: the canonical name of the signing scheme, as a `&'static str`.
-pub const SIGN_SCHEME: &str = sign::SCHEME_NAME;
+pub const SIGN_SCHEME: &str = Ed25519::SIGN_SCHEME;
 
 /// Convenience: the canonical name of the DKG scheme, as a `&'static str`.
-pub const DKG_SCHEME: &str = dkg::SCHEME_NAME;
+pub const DKG_SCHEME: &str = Ed25519::DKG_SCHEME;
+
+/// Convenience: the canonical name of the one-round signing scheme, as a
+/// `&'static str`.
+pub const PREPROCESSED_SCHEME: &str = Ed25519::PREPROCESSED_SCHEME;
+
+/// Parse a FROST-ed25519 DKG output blob.
+pub fn parse_dkg_output(blob: &[u8]) -> error::Result<KeyPackage> {
+    KeyPackage::from_bytes(blob)
+}
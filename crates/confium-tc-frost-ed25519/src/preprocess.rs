@@ -0,0 +1,24 @@
+//! Preprocessed FROST-ed25519: [`confium_tc_frost::preprocess`]
+//! specialised to [`Ed25519`].
+
+use crate::Ed25519;
+
+pub use confium_tc_frost::preprocess::{NonceCommitment, aggregate, sign_share};
+
+/// A party's published batch of nonce commitments.
+pub type CommitmentBatch = confium_tc_frost::preprocess::CommitmentBatch<Ed25519>;
+
+/// The coordinator's view of every party's unused commitments.
+pub type CommitmentPool = confium_tc_frost::preprocess::CommitmentPool<Ed25519>;
+
+/// A party's secret nonces awaiting a signing package.
+pub type NoncePool = confium_tc_frost::preprocess::NoncePool<Ed25519>;
+
+/// A key package and its nonce pool, stored together as one share.
+pub type PreprocessedShare = confium_tc_frost::preprocess::PreprocessedShare<Ed25519>;
+
+/// One signer's response to a [`SigningPackage`].
+pub type SignatureShare = confium_tc_frost::preprocess::SignatureShare<Ed25519>;
+
+/// A message and the commitment assigned to each signer.
+pub type SigningPackage = confium_tc_frost::preprocess::SigningPackage<Ed25519>;
@@ -1,175 +0,0 @@
-//! Fiat-Shamir transcript for FROST-ed25519.
-//!
-//! The FROST spec (draft-irtf-cfrg-frost, §4.2 "Palette of operations")
-//! names five domain-separated hash functions H1–H5 over a single
-//! underlying hash H. For the ed25519 instantiation the underlying hash
-//! is SHA-512 and the domain separator is the ASCII string
-//! `"FROST-ed25519-SHA512-v1"` prefixed into every input.
-//!
-//! The five functions map onto the protocol's needs as follows:
-//!
-//! | fn | role |
-//! |----|------|
-//! | H1 | nonce derivation —rho binding factor input|
-//! | H2 | challenge scalar `c` (this MUST equal the ed25519 challenge
-//!      `SHA-512(R ‖ A ‖ M)` reduced mod ℓ, so the signature verifies
-//!      under any standard ed25519 verifier) |
-//! | H3 | nonce randomness extraction |
-//! | H4 | variable-length equality-check listings |
-//! | H5 | variable-length equality-check (alternate) |
-//!
-//! For the signing scheme implemented here we only need H1 (binding
-//! factor), H3 (nonce seed from `(secret, nonce_seed, msg)`), and the
-//! bare ed25519 challenge (H2). H4 / H5 are unused in 2-of-N signing
-//! without the optional pre-process round; they are included so future
-//! extensions match the spec's H_n palette.
-
-use curve25519_dalek::scalar::Scalar;
-use sha2::{Digest, Sha512};
-
-use crate::group;
-
-/// Domain separator prefix used by every H1–H5 invocation. Matches the
-/// ciphersuite identifier in draft-irtf-cfrg-frost §7.3 ("FROST(ed25519,
-/// SHA-512)").
-pub const DOMAIN: &[u8] = b"FROST-ed25519-SHA512-v1";
-
-/// H1 — the binding factor `rho`. Output: a scalar mod ℓ.
-///
-/// The spec encodes a structured input (a "rho input" prefix). For our
-/// signing scheme the binding factor input is the concatenation of the
-/// message and the sorted list of `(party_index, D_i, E_i)` commitments.
-pub fn h1_binding_factor(rho_input: &[u8]) -> Scalar {
-    let mut h = Sha512::new();
-    h.update(DOMAIN);
-    h.update(b"rho");
-    h.update(rho_input);
-    let digest = h.finalize();
-    let mut wide = [0u8; 64];
-    wide.copy_from_slice(&digest);
-    Scalar::from_bytes_mod_order_wide(&wide)
-}
-
-/// H3 — nonce randomness. Maps a 32-byte seed + message to a scalar mod ℓ.
-pub fn h3_nonce(seed: &[u8], msg: &[u8]) -> Scalar {
-    let mut h = Sha512::new();
-    h.update(DOMAIN);
-    h.update(b"nonce");
-    h.update(seed);
-    h.update(msg);
-    let digest = h.finalize();
-    let mut wide = [0u8; 64];
-    wide.copy_from_slice(&digest);
-    Scalar::from_bytes_mod_order_wide(&wide)
-}
-
-/// The ed25519 challenge scalar `c = SHA-512(R ‖ A ‖ M)` reduced mod ℓ.
-///
-/// This deliberately does NOT carry the FROST domain separator — the
-/// whole point is to produce a signature that any RFC-8032 verifier
-/// accepts. The only prefix is the implicit one baked into SHA-512 of
-/// `R || A || M`, exactly as RFC 8032 §5.1.7 prescribes.
-pub fn challenge(r_bytes: &[u8; 32], a_bytes: &[u8; 32], msg: &[u8]) -> Scalar {
-    let mut h = Sha512::new();
-    h.update(r_bytes);
-    h.update(a_bytes);
-    h.update(msg);
-    let digest = h.finalize();
-    let mut wide = [0u8; 64];
-    wide.copy_from_slice(&digest);
-    Scalar::from_bytes_mod_order_wide(&wide)
-}
-
-/// Build the "rho input" for a signing instance — the canonical bytes that
-/// all participating parties hash to derive each `rho_i`.
-///
-/// Format: `msg_len:u32 BE | msg | party_count:u32 BE | for each party
-/// (sorted by index): idx:u32 BE | D_i | E_i`.
-pub fn rho_input(
-    msg: &[u8],
-    commitments: &[(u32, [u8; group::ELEMENT_BYTES], [u8; group::ELEMENT_BYTES])],
-) -> Vec<u8> {
-    let mut out = Vec::new();
-    out.extend_from_slice(&(msg.len() as u32).to_be_bytes());
-    out.extend_from_slice(msg);
-    out.extend_from_slice(&(commitments.len() as u32).to_be_bytes());
-    let mut sorted = commitments.to_vec();
-    sorted.sort_by_key(|t| t.0);
-    for (idx, d, e) in &sorted {
-        out.extend_from_slice(&idx.to_be_bytes());
-        out.extend_from_slice(d);
-        out.extend_from_slice(e);
-    }
-    out
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use curve25519_dalek::scalar::Scalar;
-
-    #[test]
-    fn h_functions_produce_scalar() {
-        let _ = h1_binding_factor(b"abc");
-        let _ = h3_nonce(b"seed-32-bytes________________!", b"msg");
-    }
-
-    #[test]
-    fn challenge_matches_ed25519_definition() {
-        // The challenge must equal SHA-512(R || A || M) mod ℓ.
-        let r = [1u8; 32];
-        let a = [2u8; 32];
-        let msg = b"hello";
-        let c = challenge(&r, &a, msg);
-        // Recompute independently.
-        let mut h = Sha512::new();
-        h.update(r);
-        h.update(a);
-        h.update(msg);
-        let digest = h.finalize();
-        let mut wide = [0u8; 64];
-        wide.copy_from_slice(&digest);
-        let expected = Scalar::from_bytes_mod_order_wide(&wide);
-        assert_eq!(c, expected);
-    }
-
-    #[test]
-    fn rho_input_sorts_parties() {
-        let cs = vec![
-            (3u32, [3u8; 32], [13u8; 32]),
-            (1, [1u8; 32], [11u8; 32]),
-            (2, [2u8; 32], [12u8; 32]),
-        ];
-        let out = rho_input(b"m", &cs);
-        // The first party index after the header should be 1, not 3.
-        // Header: 4 (msg len) + 1 (msg) + 4 (party count) = 9 bytes.
-        let idx_bytes: [u8; 4] = out[9..13].try_into().unwrap();
-        assert_eq!(u32::from_be_bytes(idx_bytes), 1);
-        let idx_bytes2: [u8; 4] = out[9 + 4 + 64..9 + 4 + 64 + 4].try_into().unwrap();
-        assert_eq!(u32::from_be_bytes(idx_bytes2), 2);
-    }
-
-    /// Known-answer vector for `challenge()` with all-`0x42` R, all-`0x43`
-    /// A, and the message `b"frost-kat"`. The expected scalar is the
-    /// little-endian byte encoding of `SHA-512(R || A || M)` reduced mod
-    /// ℓ. This pins the Fiat-Shamir transcript so a future refactor that
-    /// accidentally changes the challenge derivation will fail loudly.
-    #[test]
-    fn challenge_known_answer_hex_vector() {
-        let r = [0x42u8; 32];
-        let a = [0x43u8; 32];
-        let msg = b"frost-kat";
-        let c = challenge(&r, &a, msg);
-        let hex_actual = hex::encode(c.to_bytes());
-        // Pinned: SHA-512(0x42^32 || 0x43^32 || b"frost-kat") mod ℓ,
-        // little-endian. Regenerate by running the script in the test's
-        // docstring if any input changes.
-        let pinned = "e099cbd6aa693ad425eaa910e8346501caa0ed6fed30ad3a3dc378da61c0dc0c";
-        assert_eq!(hex_actual, pinned);
-        assert_eq!(
-            hex_actual.len(),
-            64,
-            "scalar encodes to 32 bytes / 64 hex chars"
-        );
-    }
-}
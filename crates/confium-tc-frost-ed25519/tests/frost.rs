@@ -114,7 +114,8 @@ fn run_dkg(roster: &[&str], threshold: u32) -> Vec<([u8; 32], Vec<u8>)> {
         .iter()
         .map(|s| {
             let blob = s.result().expect("dkg result");
-            let (pk, _share) = parse_dkg_output(&blob).expect("dkg output parses");
+            let key = parse_dkg_output(&blob).expect("dkg output parses");
+            let pk: [u8; 32] = key.public_key.try_into().expect("32-byte public key");
             (pk, blob)
         })
         .collect()
@@ -464,6 +465,87 @@ fn single_party_dkg_then_one_of_one_sign() {
     );
 }
 
+const SNAPSHOT_KEY: &[u8] = b"frost-share-envelope-integrity-key";
+
+/// Messages in `outgoing` that `me` should receive.
+fn inbox(outgoing: &[confium_tc::Message], me: &str) -> Vec<confium_tc::Message> {
+    outgoing
+        .iter()
+        .filter(|m| m.from_party_id != me && m.is_for(me))
+        .cloned()
+        .collect()
+}
+
+#[test]
+fn signer_resumes_after_restart_without_reusing_its_nonce() {
+    use confium_tc::snapshot::MemoryNonceLedger;
+    use std::sync::Arc;
+
+    let roster = ["alice", "bob"];
+    let outputs = run_dkg(&roster, 2);
+    let msg = b"resume-after-restart";
+    let params: Vec<SessionParams> = (0..2)
+        .map(|i| sign_params(&roster, i, 2, outputs[i].1.clone(), msg))
+        .collect();
+    let ledger = Arc::new(MemoryNonceLedger::new());
+
+    let mut alice = Session::create(&params[0]).expect("alice");
+    alice.set_nonce_ledger(ledger.clone());
+    let mut bob = Session::create(&params[1]).expect("bob");
+    alice.round_step(&[]).expect("alice round 1");
+    let blob = alice.snapshot(SNAPSHOT_KEY).expect("snapshot");
+    let b1 = bob.round_step(&[]).expect("bob round 1").outgoing;
+    drop(alice);
+
+    // Alice restarts and re-sends her round-1 commitment.
+    let mut alice =
+        Session::resume(&params[0], &blob, SNAPSHOT_KEY, ledger.clone()).expect("resume");
+    let a1 = alice.last_outgoing().to_vec();
+    let a2 = alice
+        .round_step(&inbox(&b1, "alice"))
+        .expect("alice round 2");
+    let b2 = bob.round_step(&inbox(&a1, "bob")).expect("bob round 2");
+    alice
+        .round_step(&inbox(&b2.outgoing, "alice"))
+        .expect("alice round 3");
+    bob.round_step(&inbox(&a2.outgoing, "bob"))
+        .expect("bob round 3");
+    let sig = alice.result().expect("signature");
+    assert_eq!(sig, bob.result().expect("signature"));
+    assert!(verify_ed25519(&outputs[0].0, msg, &sig));
+
+    // The snapshot still holds the now-spent nonce pair.
+    let err = Session::resume(&params[0], &blob, SNAPSHOT_KEY, ledger).unwrap_err();
+    assert!(matches!(err, confium_tc::Error::NonceReuse { .. }));
+}
+
+#[test]
+fn dkg_resumes_after_dealing() {
+    use confium_tc::snapshot::MemoryNonceLedger;
+    use std::sync::Arc;
+
+    let roster = ["alice", "bob"];
+    let params: Vec<SessionParams> = (0..2).map(|i| dkg_params(&roster, i, 2)).collect();
+    let ledger = Arc::new(MemoryNonceLedger::new());
+    let mut alice = Session::create(&params[0]).expect("alice");
+    alice.set_nonce_ledger(ledger.clone());
+    let mut bob = Session::create(&params[1]).expect("bob");
+    alice.round_step(&[]).expect("alice round 1");
+    let blob = alice.snapshot(SNAPSHOT_KEY).expect("snapshot");
+    let b1 = bob.round_step(&[]).expect("bob round 1").outgoing;
+    drop(alice);
+
+    let mut alice = Session::resume(&params[0], &blob, SNAPSHOT_KEY, ledger).expect("resume");
+    let a1 = alice.last_outgoing().to_vec();
+    alice
+        .round_step(&inbox(&b1, "alice"))
+        .expect("alice round 2");
+    bob.round_step(&inbox(&a1, "bob")).expect("bob round 2");
+    let key_a = parse_dkg_output(&alice.result().unwrap()).unwrap();
+    let key_b = parse_dkg_output(&bob.result().unwrap()).unwrap();
+    assert_eq!(key_a.public_key, key_b.public_key);
+}
+
 /// Sanity: confirm the scalar field arithmetic the scheme relies on is
 /// self-consistent (regression guard against curve25519-dalek API drift).
 #[test]
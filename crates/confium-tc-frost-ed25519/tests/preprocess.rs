@@ -0,0 +1,300 @@
+//! Preprocessed FROST-ed25519: commitments published ahead of time, one
+//! online round per signature.
+//!
+//! Asserts that:
+//!
+//! 1. Signatures from pooled nonces verify under standard `ed25519-dalek`.
+//! 2. A replayed signing package, or a nonce pool restored from before a
+//!    spend, is refused with `NonceReuse` rather than signing twice.
+//! 3. The coordinator consumes nothing when a signer's pool is dry.
+//! 4. A `FROST-ed25519-preprocessed` session signs from the pool stored
+//!    in the share in one message round, and the framework's ledger
+//!    spend makes the stored share refuse the same package again.
+
+use std::sync::Arc;
+
+use confium_tc::party::{Party, PartyList};
+use confium_tc::share::Share;
+use confium_tc::snapshot::{FileNonceLedger, MemoryNonceLedger, NonceLedger};
+use confium_tc::{Session, SessionParams};
+use confium_tc_frost_ed25519::preprocess::{
+    CommitmentBatch, CommitmentPool, NoncePool, PreprocessedShare, SignatureShare, SigningPackage,
+    aggregate, sign_share,
+};
+use confium_tc_frost_ed25519::{KeyPackage, PREPROCESSED_SCHEME, inprocess, parse_dkg_output};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+
+/// One key holder: key package, nonce pool and ledger.
+struct Signer {
+    key: KeyPackage,
+    pool: NoncePool,
+    ledger: MemoryNonceLedger,
+}
+
+/// Run a DKG and give every party a pool of `pool_size` nonces, with
+/// the commitments published to a fresh coordinator pool.
+fn setup(threshold: u32, n: usize, pool_size: usize) -> (Vec<u8>, Vec<Signer>, CommitmentPool) {
+    let blobs = inprocess::keygen(threshold, n).expect("dkg");
+    let public_key = parse_dkg_output(&blobs[0]).expect("dkg output").public_key;
+    let mut coordinator = CommitmentPool::new();
+    let signers = blobs
+        .iter()
+        .enumerate()
+        .map(|(i, blob)| {
+            let key = parse_dkg_output(blob).expect("dkg output");
+            let mut pool = NoncePool::new(i as u32 + 1);
+            let batch = pool.generate(&key, pool_size).expect("generate");
+            // Published over the wire.
+            let batch = CommitmentBatch::from_bytes(&batch.to_bytes()).expect("batch");
+            coordinator.publish(&batch).expect("publish");
+            Signer {
+                key,
+                pool,
+                ledger: MemoryNonceLedger::new(),
+            }
+        })
+        .collect();
+    (public_key, signers, coordinator)
+}
+
+/// The online round for the signers the package names.
+fn respond(signers: &mut [Signer], package: &SigningPackage) -> Vec<SignatureShare> {
+    let wire = SigningPackage::from_bytes(&package.to_bytes()).expect("package");
+    wire.participants()
+        .iter()
+        .map(|idx| {
+            let s = &mut signers[*idx as usize - 1];
+            let share = sign_share(&s.key, &mut s.pool, &s.ledger, &wire).expect("share");
+            SignatureShare::from_bytes(&share.to_bytes()).expect("share bytes")
+        })
+        .collect()
+}
+
+fn verify_ed25519(pubkey: &[u8], msg: &[u8], sig: &[u8]) -> bool {
+    let (Ok(pubkey), Ok(sig)) = (pubkey.try_into(), Signature::from_slice(sig)) else {
+        return false;
+    };
+    let Ok(vk) = VerifyingKey::from_bytes(pubkey) else {
+        return false;
+    };
+    vk.verify(msg, &sig).is_ok()
+}
+
+#[test]
+fn pooled_nonces_sign_in_one_round() {
+    let (pk, mut signers, mut coordinator) = setup(2, 3, 3);
+    for (signer_set, msg) in [
+        ([1, 2], &b"first"[..]),
+        ([2, 3], b"second"),
+        ([1, 3], b"third"),
+    ] {
+        let package = coordinator.select(&signer_set, 2, msg).expect("select");
+        let shares = respond(&mut signers, &package);
+        let sig = aggregate(&package, &shares, &pk).expect("aggregate");
+        assert!(verify_ed25519(&pk, msg, &sig), "valid RFC-8032 signature");
+    }
+    for (i, s) in signers.iter().enumerate() {
+        assert_eq!(s.pool.len(), 1, "two of three nonces used");
+        assert_eq!(coordinator.available(i as u32 + 1), 1);
+    }
+}
+
+#[test]
+fn replayed_package_cannot_reuse_a_nonce() {
+    let (_, mut signers, mut coordinator) = setup(2, 2, 2);
+    let stale = signers[0].pool.to_bytes();
+    let package = coordinator.select(&[1, 2], 2, b"pay alice").unwrap();
+    respond(&mut signers, &package);
+    let s = &mut signers[0];
+
+    // The live pool no longer holds the nonce.
+    assert!(sign_share(&s.key, &mut s.pool, &s.ledger, &package).is_err());
+
+    // A pool blob from before the spend still does; the ledger refuses
+    // it whatever message the replayed package now carries.
+    let mut forged = package.clone();
+    forged.message = b"pay mallory".to_vec();
+    let mut stale = NoncePool::from_bytes(&stale).unwrap();
+    let err = sign_share(&s.key, &mut stale, &s.ledger, &forged).unwrap_err();
+    assert!(matches!(err, confium_tc::Error::NonceReuse { .. }));
+}
+
+#[test]
+fn pool_restored_after_crash_drops_spent_nonces() {
+    let path = std::env::temp_dir().join(format!(
+        "confium-frost-ed25519-ledger-{}",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&path);
+    let (pk, mut signers, mut coordinator) = setup(2, 2, 2);
+    let before_crash = signers[0].pool.to_bytes();
+
+    let ledger = FileNonceLedger::open(&path).expect("ledger");
+    let package = coordinator.select(&[1, 2], 2, b"m").unwrap();
+    let s = &mut signers[0];
+    let share = sign_share(&s.key, &mut s.pool, &ledger, &package).expect("share");
+    drop(ledger);
+
+    // Restart: the pool blob predates the spend, the ledger does not.
+    let ledger = FileNonceLedger::open(&path).expect("reopen");
+    let mut pool = NoncePool::from_bytes(&before_crash).unwrap();
+    assert!(matches!(
+        sign_share(&s.key, &mut pool, &ledger, &package),
+        Err(confium_tc::Error::NonceReuse { .. })
+    ));
+    let mut pool = NoncePool::from_bytes(&before_crash).unwrap();
+    assert_eq!(pool.prune(&ledger).unwrap(), 1);
+    assert_eq!(pool.len(), 1);
+
+    // The share handed out before the crash still completes the
+    // signature.
+    let other = &mut signers[1];
+    let share2 = sign_share(&other.key, &mut other.pool, &other.ledger, &package).unwrap();
+    let sig = aggregate(&package, &[share, share2], &pk).expect("aggregate");
+    assert!(verify_ed25519(&pk, b"m", &sig));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn coordinator_consumes_nothing_when_a_signer_is_dry() {
+    let (_, _, mut coordinator) = setup(2, 3, 1);
+    coordinator.select(&[1, 2], 2, b"a").unwrap();
+    assert!(coordinator.select(&[1, 3], 2, b"b").is_err());
+    assert_eq!(coordinator.available(3), 1, "signer 3 keeps its commitment");
+    assert!(
+        coordinator.select(&[3], 2, b"c").is_err(),
+        "below threshold"
+    );
+}
+
+#[test]
+fn missing_share_fails_aggregation() {
+    let (pk, mut signers, mut coordinator) = setup(2, 3, 1);
+    let package = coordinator.select(&[1, 2], 2, b"m").unwrap();
+    let shares = respond(&mut signers, &package);
+    assert!(aggregate(&package, &shares[..1], &pk).is_err());
+    let mut tampered = shares.clone();
+    let z = tampered[1].z;
+    tampered[1].z += z;
+    assert!(aggregate(&package, &tampered, &pk).is_err());
+}
+
+#[test]
+fn unused_ledger_entries_are_ignored() {
+    let (_, mut signers, _) = setup(1, 1, 2);
+    let ledger = MemoryNonceLedger::new();
+    ledger.spend(&[7u8; 32]).unwrap();
+    let pool = &mut signers[0].pool;
+    assert_eq!(pool.prune(&ledger).unwrap(), 0);
+}
+
+/// Every party's stored share after a DKG and `pool_size` preprocessed
+/// nonces each, with the commitments published to a fresh coordinator.
+fn stored_shares(
+    threshold: u32,
+    n: usize,
+    pool_size: usize,
+) -> (Vec<u8>, Vec<Share>, CommitmentPool) {
+    let blobs = inprocess::keygen(threshold, n).expect("dkg");
+    let public_key = parse_dkg_output(&blobs[0]).expect("dkg output").public_key;
+    let mut coordinator = CommitmentPool::new();
+    let shares = blobs
+        .iter()
+        .map(|blob| {
+            let mut share = PreprocessedShare::new(parse_dkg_output(blob).expect("dkg output"));
+            coordinator
+                .publish(&share.generate(pool_size).expect("generate"))
+                .expect("publish");
+            share.to_share()
+        })
+        .collect();
+    (public_key, shares, coordinator)
+}
+
+/// One `FROST-ed25519-preprocessed` session per signer in `package`,
+/// each on its stored share and its own ledger.
+fn online_sessions(
+    stored: &[Share],
+    ledgers: &[Arc<MemoryNonceLedger>],
+    package: &SigningPackage,
+) -> Vec<Session> {
+    let signers = package.participants();
+    let roster: Vec<Party> = signers
+        .iter()
+        .map(|idx| Party::inproc(format!("p{idx}")))
+        .collect();
+    let parties = PartyList::from_parties(roster);
+    signers
+        .iter()
+        .enumerate()
+        .map(|(pos, idx)| {
+            let mut session = Session::create(&SessionParams {
+                scheme: PREPROCESSED_SCHEME.to_string(),
+                parties: parties.clone(),
+                threshold: signers.len() as u32,
+                this_party_idx: pos,
+                local_share: Some(stored[*idx as usize - 1].clone()),
+                message: Some(package.to_bytes()),
+            })
+            .expect("session");
+            session.set_nonce_ledger(ledgers[*idx as usize - 1].clone());
+            session
+        })
+        .collect()
+}
+
+#[test]
+fn stored_share_signs_in_one_session_round() {
+    let (pk, mut stored, mut coordinator) = stored_shares(2, 3, 2);
+    let ledgers: Vec<_> = (0..3).map(|_| Arc::new(MemoryNonceLedger::new())).collect();
+    let package = coordinator.select(&[1, 3], 2, b"one round").unwrap();
+
+    let mut sessions = online_sessions(&stored, &ledgers, &package);
+    let round1: Vec<_> = sessions
+        .iter_mut()
+        .flat_map(|s| s.round_step(&[]).expect("round 1").outgoing)
+        .collect();
+    for s in &mut sessions {
+        assert!(s.round_step(&round1).expect("round 2").complete);
+    }
+    let sig = sessions[0].result().expect("signature");
+    assert_eq!(sig, sessions[1].result().unwrap());
+    assert!(verify_ed25519(&pk, b"one round", &sig));
+
+    // The stored shares still hold the nonces; the ledgers do not let
+    // them answer the same package twice.
+    let mut replay = online_sessions(&stored, &ledgers, &package);
+    assert!(matches!(
+        replay[0].round_step(&[]),
+        Err(confium_tc::Error::NonceReuse { .. })
+    ));
+
+    // Pruned against its ledger, the stored share keeps only the unused
+    // nonce, and the next package signs from it.
+    for idx in [1, 3] {
+        let mut share = PreprocessedShare::from_bytes(stored[idx - 1].bytes()).unwrap();
+        assert_eq!(share.prune(ledgers[idx - 1].as_ref()).unwrap(), 1);
+        assert_eq!(share.pool.len(), 1);
+        stored[idx - 1] = share.to_share();
+    }
+    let package = coordinator.select(&[1, 3], 2, b"again").unwrap();
+    let blobs: Vec<Vec<u8>> = [0, 2].iter().map(|i| stored[*i].bytes().to_vec()).collect();
+    let sig = inprocess::sign_preprocessed(&blobs, 2, &package.to_bytes()).expect("sign");
+    assert!(verify_ed25519(&pk, b"again", &sig));
+}
+
+#[test]
+fn tampered_online_share_names_its_sender() {
+    let (_, stored, mut coordinator) = stored_shares(2, 2, 1);
+    let ledgers: Vec<_> = (0..2).map(|_| Arc::new(MemoryNonceLedger::new())).collect();
+    let package = coordinator.select(&[1, 2], 2, b"m").unwrap();
+    let mut sessions = online_sessions(&stored, &ledgers, &package);
+    let mut round1: Vec<_> = sessions
+        .iter_mut()
+        .flat_map(|s| s.round_step(&[]).expect("round 1").outgoing)
+        .collect();
+    let last = round1[1].payload.len() - 1;
+    round1[1].payload[last] ^= 1;
+    let err = sessions[0].round_step(&round1).unwrap_err();
+    assert_eq!(err.culprit(), Some("p2"));
+}
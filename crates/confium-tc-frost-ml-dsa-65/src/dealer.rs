@@ -0,0 +1,145 @@
+//! Trusted-dealer key generation.
+//!
+//! The dealer samples `ρ` and, for each subset `S` of `n − t + 1`
+//! parties, a piece `s1_S ∈ [−η', η']^{ℓ·256}`, `s2_S ∈ [−η', η']^{k·256}`
+//! with `η' = max(1, ⌊4 / m⌋)` for `m` pieces, so the combined secret
+//! stays as short as a single-signer ML-DSA-65 key whenever `m ≤ 4`.
+//! See [`crate::keys`] for the layout.
+//!
+//! The dealer sees the whole key, so the ceremony must run on a machine
+//! that is trusted and wiped afterwards. The prototype caps the number
+//! of parties at [`MAX_PARTIES`] and of pieces at [`MAX_PIECES`]: each
+//! signer's rejection rate grows with the number of signers and with
+//! `m·η'`.
+
+use rand_core::{OsRng, RngCore};
+
+use crate::error::{MlDsaErrorCode, Result, scheme_error};
+use crate::keys::{MlDsaKeyShare, Piece};
+use crate::params::{ETA, K, L};
+use crate::poly::{Sampler, expand_a, mat_vec, vec_add};
+
+/// Largest party count.
+pub const MAX_PARTIES: u32 = 4;
+
+/// Largest number of secret pieces, `C(n, t − 1)`.
+pub const MAX_PIECES: u32 = 6;
+
+fn binomial(n: u32, k: u32) -> u32 {
+    (0..k).fold(1, |acc, i| acc * (n - i) / (i + 1))
+}
+
+/// Number of pieces of a `threshold`-of-`parties` key.
+pub(crate) fn piece_count(threshold: u32, parties: u32) -> u32 {
+    binomial(parties, threshold - 1)
+}
+
+/// Coefficient bound of each piece.
+pub(crate) fn piece_eta(threshold: u32, parties: u32) -> u32 {
+    (ETA / piece_count(threshold, parties)).max(1)
+}
+
+pub(crate) fn check_roster(threshold: u32, parties: u32) -> Result<()> {
+    if parties == 0
+        || parties > MAX_PARTIES
+        || !(1..=parties).contains(&threshold)
+        || piece_count(threshold, parties) > MAX_PIECES
+    {
+        return Err(scheme_error(MlDsaErrorCode::BAD_KEY_PARAMETERS));
+    }
+    Ok(())
+}
+
+/// Deal a fresh key to `parties` parties, any `threshold` of whom can
+/// sign.
+///
+/// Every configuration up to [`MAX_PARTIES`] parties is supported.
+pub fn deal(threshold: u32, parties: u32) -> Result<Vec<MlDsaKeyShare>> {
+    check_roster(threshold, parties)?;
+    let eta = piece_eta(threshold, parties);
+    let mut rho = [0u8; 32];
+    OsRng.fill_bytes(&mut rho);
+    let a_hat = expand_a(&rho);
+
+    let size = parties - threshold + 1;
+    let mut sampler = Sampler::new();
+    let pieces: Vec<Piece> = (0u32..1 << parties)
+        .filter(|members| members.count_ones() == size)
+        .map(|members| {
+            let s1 = sampler.vec::<L>(eta);
+            let s2 = sampler.vec::<K>(eta);
+            Piece {
+                members,
+                public: vec_add(&mat_vec(&a_hat, &s1), &s2),
+                secret: Some(s1),
+            }
+        })
+        .collect();
+
+    Ok((1..=parties)
+        .map(|index| MlDsaKeyShare {
+            index,
+            threshold,
+            parties,
+            eta,
+            rho,
+            pieces: pieces
+                .iter()
+                .map(|p| Piece {
+                    secret: p.secret.filter(|_| p.has_member(index)),
+                    ..p.clone()
+                })
+                .collect(),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_threshold_set_holds_every_piece() {
+        let shares = deal(2, 3).unwrap();
+        assert_eq!(shares[0].pieces.len(), 3);
+        for pair in [[0usize, 1], [0, 2], [1, 2]] {
+            for (i, piece) in shares[0].pieces.iter().enumerate() {
+                assert!(pair.iter().any(|&p| shares[p].pieces[i].secret.is_some()));
+                assert_eq!(piece.members, shares[pair[1]].pieces[i].members);
+            }
+        }
+    }
+
+    #[test]
+    fn shares_round_trip_and_agree_on_the_public_key() {
+        let shares = deal(3, 3).unwrap();
+        let pk = shares[0].public_key();
+        assert_eq!(pk.len(), crate::PUBLIC_KEY_SIZE);
+        for share in &shares {
+            let decoded = MlDsaKeyShare::from_bytes(&share.to_bytes()).unwrap();
+            assert_eq!(decoded.public_key(), pk);
+        }
+    }
+
+    #[test]
+    fn tampered_pieces_are_rejected() {
+        let mut share = deal(2, 2).unwrap().remove(0);
+        let held = share
+            .pieces
+            .iter_mut()
+            .find(|p| p.secret.is_some())
+            .unwrap();
+        held.public[0].0[0] = (held.public[0].0[0] + 1000) % crate::params::Q;
+        assert!(MlDsaKeyShare::from_bytes(&share.to_bytes()).is_err());
+        assert!(MlDsaKeyShare::from_bytes(b"TMLD").is_err());
+    }
+
+    #[test]
+    fn unsupported_configurations_are_refused() {
+        assert!(deal(0, 3).is_err());
+        assert!(deal(4, 3).is_err());
+        assert!(deal(2, 5).is_err());
+        assert!(deal(5, 5).is_err());
+        assert!(deal(3, 4).is_ok());
+    }
+}
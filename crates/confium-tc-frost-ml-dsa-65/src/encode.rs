@@ -0,0 +1,214 @@
+//! Byte encodings: the FIPS 204 public key, signature and `w1`
+//! formats, plus the plain 23-bit packing used for `R_q` vectors on the
+//! wire and in share blobs.
+
+use crate::params::{CTILDE_LEN, GAMMA1, K, L, N, OMEGA, Q};
+use crate::poly::{Poly, VecK, VecL, centered, from_centered};
+
+/// Bits per coefficient of a full `R_q` element.
+const Q_BITS: usize = 23;
+
+/// Bytes of one polynomial packed at [`Q_BITS`].
+pub const POLY_Q_BYTES: usize = N * Q_BITS / 8;
+
+/// SimpleBitPack: little-endian, `bits` per value.
+pub fn pack(out: &mut Vec<u8>, values: impl IntoIterator<Item = u32>, bits: usize) {
+    let (mut acc, mut held) = (0u64, 0);
+    for v in values {
+        acc |= u64::from(v) << held;
+        held += bits;
+        while held >= 8 {
+            out.push(acc as u8);
+            acc >>= 8;
+            held -= 8;
+        }
+    }
+    if held > 0 {
+        out.push(acc as u8);
+    }
+}
+
+/// Inverse of [`pack`]; `bytes` must hold exactly `count` values.
+pub fn unpack(bytes: &[u8], bits: usize, count: usize) -> Vec<u32> {
+    let mask = (1u64 << bits) - 1;
+    let (mut acc, mut held) = (0u64, 0);
+    let mut bytes = bytes.iter();
+    (0..count)
+        .map(|_| {
+            while held < bits {
+                acc |= u64::from(*bytes.next().unwrap_or(&0)) << held;
+                held += 8;
+            }
+            let v = (acc & mask) as u32;
+            acc >>= bits;
+            held -= bits;
+            v
+        })
+        .collect()
+}
+
+fn to_poly(values: &[u32]) -> Poly {
+    Poly(std::array::from_fn(|i| values[i]))
+}
+
+/// Pack a vector of `R_q` elements at 23 bits per coefficient.
+pub fn pack_q(out: &mut Vec<u8>, v: &[Poly]) {
+    for p in v {
+        pack(out, p.0, Q_BITS);
+    }
+}
+
+/// Inverse of [`pack_q`], rejecting out-of-range coefficients.
+pub fn unpack_q<const M: usize>(bytes: &[u8]) -> Option<[Poly; M]> {
+    if bytes.len() != M * POLY_Q_BYTES {
+        return None;
+    }
+    let values = unpack(bytes, Q_BITS, M * N);
+    if values.iter().any(|&x| x >= Q) {
+        return None;
+    }
+    Some(std::array::from_fn(|i| to_poly(&values[i * N..])))
+}
+
+/// Pack a vector with coefficients in `[−η, η]` at 4 bits each.
+pub fn pack_small(out: &mut Vec<u8>, v: &[Poly], eta: u32) {
+    for p in v {
+        pack(out, p.0.map(|x| (eta as i32 - centered(x)) as u32), 4);
+    }
+}
+
+/// Inverse of [`pack_small`], rejecting coefficients outside `[−η, η]`.
+pub fn unpack_small<const M: usize>(bytes: &[u8], eta: u32) -> Option<[Poly; M]> {
+    if bytes.len() != M * N / 2 {
+        return None;
+    }
+    let values = unpack(bytes, 4, M * N);
+    if values.iter().any(|&x| x > 2 * eta) {
+        return None;
+    }
+    let values: Vec<u32> = values
+        .into_iter()
+        .map(|x| from_centered(eta as i32 - x as i32))
+        .collect();
+    Some(std::array::from_fn(|i| to_poly(&values[i * N..])))
+}
+
+/// pkEncode (FIPS 204 Algorithm 22): `ρ ‖ t1` at 10 bits.
+pub fn pk_encode(rho: &[u8; 32], t1: &VecK) -> Vec<u8> {
+    let mut out = rho.to_vec();
+    for p in t1 {
+        pack(&mut out, p.0, 10);
+    }
+    out
+}
+
+/// pkDecode (FIPS 204 Algorithm 23).
+pub fn pk_decode(pk: &[u8]) -> Option<([u8; 32], VecK)> {
+    if pk.len() != crate::PUBLIC_KEY_SIZE {
+        return None;
+    }
+    let rho = pk[..32].try_into().ok()?;
+    let values = unpack(&pk[32..], 10, K * N);
+    Some((rho, std::array::from_fn(|i| to_poly(&values[i * N..]))))
+}
+
+/// w1Encode (FIPS 204 Algorithm 28): 4 bits per coefficient.
+pub fn w1_encode(w1: &VecK) -> Vec<u8> {
+    let mut out = Vec::with_capacity(K * N / 2);
+    for p in w1 {
+        pack(&mut out, p.0, 4);
+    }
+    out
+}
+
+/// sigEncode (FIPS 204 Algorithm 26).
+pub fn sig_encode(c_tilde: &[u8; CTILDE_LEN], z: &VecL, h: &[[bool; N]; K]) -> Vec<u8> {
+    let mut out = c_tilde.to_vec();
+    for p in z {
+        pack(
+            &mut out,
+            p.0.map(|x| (GAMMA1 as i32 - centered(x)) as u32),
+            20,
+        );
+    }
+    let mut hints = [0u8; OMEGA + K];
+    let mut index = 0;
+    for (i, row) in h.iter().enumerate() {
+        for (j, _) in row.iter().enumerate().filter(|(_, bit)| **bit) {
+            hints[index] = j as u8;
+            index += 1;
+        }
+        hints[OMEGA + i] = index as u8;
+    }
+    out.extend_from_slice(&hints);
+    out
+}
+
+/// A decoded signature `(c̃, z, h)`.
+pub type DecodedSignature = ([u8; CTILDE_LEN], VecL, [[bool; N]; K]);
+
+/// sigDecode (FIPS 204 Algorithm 27) with HintBitUnpack's
+/// malformed-hint checks.
+pub fn sig_decode(sig: &[u8]) -> Option<DecodedSignature> {
+    if sig.len() != crate::SIGNATURE_SIZE {
+        return None;
+    }
+    let (c_tilde, rest) = sig.split_at(CTILDE_LEN);
+    let (z_bytes, hints) = rest.split_at(L * N * 20 / 8);
+    let values = unpack(z_bytes, 20, L * N);
+    let z = std::array::from_fn(|i| {
+        Poly(std::array::from_fn(|j| {
+            from_centered(GAMMA1 as i32 - values[i * N + j] as i32)
+        }))
+    });
+
+    let mut h = [[false; N]; K];
+    let mut index = 0usize;
+    for (i, row) in h.iter_mut().enumerate() {
+        let end = usize::from(hints[OMEGA + i]);
+        if end < index || end > OMEGA {
+            return None;
+        }
+        let first = index;
+        while index < end {
+            if index > first && hints[index - 1] >= hints[index] {
+                return None;
+            }
+            row[usize::from(hints[index])] = true;
+            index += 1;
+        }
+    }
+    if hints[index..OMEGA].iter().any(|&b| b != 0) {
+        return None;
+    }
+    Some((c_tilde.try_into().ok()?, z, h))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn q_packing_round_trips() {
+        let v: [Poly; 2] = [
+            Poly(std::array::from_fn(|i| (i as u32 * 32_771) % Q)),
+            Poly(std::array::from_fn(|i| Q - 1 - i as u32)),
+        ];
+        let mut out = Vec::new();
+        pack_q(&mut out, &v);
+        assert_eq!(out.len(), 2 * POLY_Q_BYTES);
+        assert!(unpack_q::<2>(&out).unwrap() == v);
+        assert!(unpack_q::<2>(&out[1..]).is_none());
+    }
+
+    #[test]
+    fn small_packing_rejects_out_of_range() {
+        let v = [Poly(std::array::from_fn(|i| {
+            from_centered(i as i32 % 5 - 2)
+        }))];
+        let mut out = Vec::new();
+        pack_small(&mut out, &v, 2);
+        assert!(unpack_small::<1>(&out, 2).unwrap() == v);
+        assert!(unpack_small::<1>(&out, 1).is_none());
+    }
+}
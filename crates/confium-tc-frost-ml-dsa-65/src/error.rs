@@ -0,0 +1,54 @@
+//! Error helpers for the threshold ML-DSA-65 session.
+
+use confium_tc::error::Error as TcError;
+
+/// Threshold ML-DSA sub-codes (0x71xx). Distinct from threshold RSA's
+/// 0x70xx so callers can disambiguate the source scheme from a numeric
+/// code alone.
+#[allow(non_camel_case_types)]
+#[repr(u32)]
+pub enum MlDsaErrorCode {
+    /// A key-share blob failed to deserialize, had the wrong magic /
+    /// version, or a secret piece does not match its public piece.
+    /// Caller action: re-run the dealer ceremony.
+    BAD_SHARE = 0x7101,
+    /// The dealer was asked for an unsupported configuration: a
+    /// threshold outside `1..=parties`, too many parties, or more secret
+    /// pieces than the prototype supports.
+    /// Caller action: pick a smaller configuration.
+    BAD_KEY_PARAMETERS = 0x7102,
+    /// Fewer than T parties committed in round 1.
+    /// Caller action: collect more signers before retrying.
+    BELOW_THRESHOLD = 0x7120,
+    /// No attempt was accepted by every signer in `MAX_TRIES` tries.
+    /// Happens with small probability for honest signers; repeated
+    /// failures point to a signer refusing every attempt.
+    /// Caller action: run a fresh session.
+    NO_USABLE_ATTEMPT = 0x7130,
+    /// The combined signature does not verify even though every
+    /// response passed its check. Indicates shares from different
+    /// dealer ceremonies or a bug.
+    /// Caller action: abort; do not retry with the same shares.
+    COMBINE_FAILED = 0x7131,
+    /// Internal error — a panic-equivalent condition was caught and
+    /// converted to an error return. Indicates a bug; please open an
+    /// issue.
+    INTERNAL = 0x71FF,
+}
+
+impl From<MlDsaErrorCode> for u32 {
+    #[inline]
+    fn from(c: MlDsaErrorCode) -> u32 {
+        c as u32
+    }
+}
+
+/// Build a framework [`TcError`] carrying a threshold ML-DSA sub-code.
+pub fn scheme_error(code: MlDsaErrorCode) -> TcError {
+    confium_tc::error::SchemeInternalSnafu {
+        code: u32::from(code),
+    }
+    .build()
+}
+
+pub type Result<T> = std::result::Result<T, TcError>;
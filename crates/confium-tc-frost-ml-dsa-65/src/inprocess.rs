@@ -0,0 +1,39 @@
+//! In-process driver for threshold ML-DSA-65.
+//!
+//! [`keygen`] runs the trusted dealer locally and [`sign`] drives the
+//! registered session through [`confium_tc::inprocess`].
+//!
+//! ## Output wire format
+//!
+//! - [`KeygenOutput::shares`] are opaque `MlDsaKeyShare::to_bytes()`
+//!   blobs.
+//! - [`KeygenOutput::public_key`] is the 1952-byte FIPS 204 public key.
+//! - [`sign`] returns a 3309-byte FIPS 204 signature.
+
+use confium_tc::Result;
+use confium_tc::inprocess as driver;
+
+use crate::dealer;
+
+/// Outcome of a dealer ceremony: N share blobs plus the public key.
+#[derive(Debug, Clone)]
+pub struct KeygenOutput {
+    /// One share blob per party, in dealer-index order.
+    pub shares: Vec<Vec<u8>>,
+    /// FIPS 204 encoded public key.
+    pub public_key: Vec<u8>,
+}
+
+/// Deal a key to `party_count` parties at threshold `threshold`.
+pub fn keygen(threshold: u32, party_count: u32) -> Result<KeygenOutput> {
+    let shares = dealer::deal(threshold, party_count)?;
+    Ok(KeygenOutput {
+        public_key: shares[0].public_key(),
+        shares: shares.iter().map(|s| s.to_bytes()).collect(),
+    })
+}
+
+/// Threshold-sign `message`; every supplied share signs.
+pub fn sign(share_blobs: &[Vec<u8>], threshold: u32, message: &[u8]) -> Result<Vec<u8>> {
+    driver::run_sign(crate::ALGORITHM, share_blobs, threshold, message)
+}
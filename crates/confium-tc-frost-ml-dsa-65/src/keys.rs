@@ -0,0 +1,192 @@
+//! The per-party key share produced by the dealer.
+//!
+//! The signing key is shared with a *replicated* short sharing: for
+//! every subset `S` of `n − t + 1` parties the dealer samples a short
+//! piece `(s1_S, s2_S)` and gives `s1_S` to every member of `S`. The
+//! key is `s1 = Σ_S s1_S`, `t = Σ_S t_S` with `t_S = A·s1_S + s2_S`.
+//! Any `t` parties together hold every piece, and because each piece is
+//! short so is their sum — which is what lets the combined response
+//! pass the FIPS 204 norm checks, something a Shamir sharing over `Z_q`
+//! cannot offer.
+//!
+//! ```text
+//! share: magic "TMLD" | version 1 | index u32 | threshold u32 | parties u32
+//!        | eta u8 | ρ[32] | pieces u32
+//!        | (members u32 | t_S[23-bit × k·256] | held u8 | s1_S[4-bit × ℓ·256]?)*
+//! ```
+//!
+//! Every share carries all public pieces `t_S` so any signer can check
+//! any other signer's response.
+
+use crate::encode::{POLY_Q_BYTES, pack_q, pack_small, unpack_q, unpack_small};
+use crate::error::{MlDsaErrorCode, Result, scheme_error};
+use crate::mldsa;
+use crate::params::{K, L, N};
+use crate::poly::{Poly, VecK, VecL, expand_a, mat_vec, vec_add, vec_norm, vec_sub};
+
+const SHARE_MAGIC: [u8; 4] = *b"TMLD";
+const SHARE_VERSION: u8 = 1;
+
+/// One subset's piece of the signing key.
+#[derive(Clone)]
+pub(crate) struct Piece {
+    /// Bit `i − 1` set for every member `i` of the subset.
+    pub members: u32,
+    /// `t_S = A·s1_S + s2_S`.
+    pub public: VecK,
+    /// `s1_S`, present when this party is a member.
+    pub secret: Option<VecL>,
+}
+
+impl Piece {
+    pub fn has_member(&self, index: u32) -> bool {
+        self.members >> (index - 1) & 1 == 1
+    }
+}
+
+/// One party's share of a dealt threshold ML-DSA-65 key.
+#[derive(Clone)]
+pub struct MlDsaKeyShare {
+    /// 1-based dealer index of this party.
+    pub index: u32,
+    /// Parties needed to sign.
+    pub threshold: u32,
+    /// Number of parties the key was dealt to.
+    pub parties: u32,
+    /// Coefficient bound of every piece.
+    pub eta: u32,
+    /// Seed of the public matrix `A`.
+    pub rho: [u8; 32],
+    pub(crate) pieces: Vec<Piece>,
+}
+
+impl std::fmt::Debug for MlDsaKeyShare {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MlDsaKeyShare")
+            .field("index", &self.index)
+            .field("threshold", &self.threshold)
+            .field("parties", &self.parties)
+            .field("pieces", &self.pieces.len())
+            .field("secret", &"<redacted>")
+            .finish_non_exhaustive()
+    }
+}
+
+impl MlDsaKeyShare {
+    /// `t = Σ_S t_S`.
+    pub(crate) fn t(&self) -> VecK {
+        self.pieces
+            .iter()
+            .fold([Poly::ZERO; K], |acc, p| vec_add(&acc, &p.public))
+    }
+
+    /// The FIPS 204 encoded public key `ρ ‖ t1`.
+    pub fn public_key(&self) -> Vec<u8> {
+        mldsa::public_key(&self.rho, &self.t())
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = SHARE_MAGIC.to_vec();
+        out.push(SHARE_VERSION);
+        for word in [self.index, self.threshold, self.parties] {
+            out.extend_from_slice(&word.to_be_bytes());
+        }
+        out.push(self.eta as u8);
+        out.extend_from_slice(&self.rho);
+        out.extend_from_slice(&(self.pieces.len() as u32).to_be_bytes());
+        for piece in &self.pieces {
+            out.extend_from_slice(&piece.members.to_be_bytes());
+            pack_q(&mut out, &piece.public);
+            out.push(u8::from(piece.secret.is_some()));
+            if let Some(secret) = &piece.secret {
+                pack_small(&mut out, secret, self.eta);
+            }
+        }
+        out
+    }
+
+    /// Decode a share, checking its structure and that every held
+    /// secret piece matches its public piece (`t_S − A·s1_S` is short).
+    pub fn from_bytes(data: &[u8]) -> Result<Self> {
+        let bad = || scheme_error(MlDsaErrorCode::BAD_SHARE);
+        let mut r = Reader(data);
+        if r.take(4)? != SHARE_MAGIC || r.take(1)? != [SHARE_VERSION] {
+            return Err(bad());
+        }
+        let (index, threshold, parties) = (r.u32()?, r.u32()?, r.u32()?);
+        let eta = u32::from(r.take(1)?[0]);
+        let rho: [u8; 32] = r.take(32)?.try_into().map_err(|_| bad())?;
+        if crate::dealer::check_roster(threshold, parties).is_err()
+            || !(1..=parties).contains(&index)
+            || eta != crate::dealer::piece_eta(threshold, parties)
+        {
+            return Err(bad());
+        }
+
+        let count = r.u32()?;
+        if count != crate::dealer::piece_count(threshold, parties) {
+            return Err(bad());
+        }
+        let a_hat = expand_a(&rho);
+        let mut pieces: Vec<Piece> = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            let members = r.u32()?;
+            let public = unpack_q::<K>(r.take(K * POLY_Q_BYTES)?).ok_or_else(bad)?;
+            let held = r.take(1)?[0];
+            let secret = match held {
+                0 => None,
+                1 => Some(unpack_small::<L>(r.take(L * N / 2)?, eta).ok_or_else(bad)?),
+                _ => return Err(bad()),
+            };
+            let piece = Piece {
+                members,
+                public,
+                secret,
+            };
+            if members >> parties != 0
+                || members.count_ones() != parties - threshold + 1
+                || pieces.iter().any(|p| p.members == members)
+                || piece.has_member(index) != piece.secret.is_some()
+            {
+                return Err(bad());
+            }
+            let mismatched = piece
+                .secret
+                .as_ref()
+                .is_some_and(|s1| vec_norm(&vec_sub(&piece.public, &mat_vec(&a_hat, s1))) > eta);
+            if mismatched {
+                return Err(bad());
+            }
+            pieces.push(piece);
+        }
+        if !r.0.is_empty() {
+            return Err(bad());
+        }
+        Ok(MlDsaKeyShare {
+            index,
+            threshold,
+            parties,
+            eta,
+            rho,
+            pieces,
+        })
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.0.len() < len {
+            return Err(scheme_error(MlDsaErrorCode::BAD_SHARE));
+        }
+        let (head, tail) = self.0.split_at(len);
+        self.0 = tail;
+        Ok(head)
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+}
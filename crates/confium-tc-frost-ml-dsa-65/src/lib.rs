@@ -1,17 +1,64 @@
-//! Threshold FROST over ML-DSA-65 — research prototype.
+//! Threshold ML-DSA-65 — research prototype.
 //!
-//! Lattice-based threshold signature. Based on FIPS 204 (ML-DSA-65).
-//! Threshold variants require MPC over the lattice signing operations;
-//! academic work ongoing (Boneh et al. 2024).
+//! `T`-of-`N` signing that outputs ordinary FIPS 204 ML-DSA-65
+//! signatures: any standard verifier (for example
+//! `confium-composite`'s `pq::verify_mldsa65`) accepts them under the
+//! dealt public key, with no threshold-specific verification.
+//!
+//! The construction follows the short replicated secret sharing used by
+//! the published threshold Raccoon and threshold ML-DSA designs:
+//!
+//! - **Trusted-dealer keygen** — [`dealer::deal`] splits the key into
+//!   short pieces, one per subset of `N − T + 1` parties, so any `T`
+//!   parties hold the whole key and their combined response stays short.
+//! - **Four-round signing with per-signer rejection** — commit, reveal,
+//!   respond, combine. Many masks are tried in parallel; each signer
+//!   rejection-samples its own responses against its share and only
+//!   the accepted ones are sent. If no attempt is accepted by every
+//!   signer, the session aborts the try and retries with fresh masks;
+//!   see [`session`].
+//! - **Identifiable abort** — a malformed message or a response that
+//!   fails its check aborts with [`confium_tc::Error::MessageRejected`]
+//!   naming the sender.
+//!
+//! The prototype supports every configuration with `N ≤ 4`. It is
+//! research-grade — see the limitations in [`session`] — and must not
+//! protect real keys.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use confium_tc_frost_ml_dsa_65::{inprocess, verify};
+//!
+//! let kg = inprocess::keygen(2, 3)?;
+//! let signature = inprocess::sign(&kg.shares[1..], 2, b"hello")?;
+//! assert!(verify(&kg.public_key, b"hello", &signature));
+//! # Ok::<(), confium_tc::Error>(())
+//! ```
 //!
 //! See `TODO.roadmap/35-pq-composite-signatures.md` for full spec.
 
 #![forbid(unsafe_code)]
 #![allow(missing_docs)] // TODO: document before 1.0
 
+pub mod dealer;
+mod encode;
+pub mod error;
+pub mod inprocess;
+pub mod keys;
+mod mldsa;
+pub mod params;
+mod poly;
+pub mod scheme;
+pub mod session;
+
+pub use keys::MlDsaKeyShare;
+pub use mldsa::verify;
+pub use scheme::MlDsa65Threshold;
+
 use serde::{Deserialize, Serialize};
 
-/// Algorithm identifier.
+/// Algorithm identifier; also the registered scheme name.
 pub const ALGORITHM: &str = "FROST-ML-DSA-65";
 
 /// ML-DSA-65 public key size (FIPS 204).
@@ -47,18 +94,23 @@ pub enum FrostMlDsaError {
         /// Need count.
         need: u32,
     },
-    /// Research-only operation.
-    #[error("operation requires academic collaborator: {0}")]
-    ResearchOnly(String),
+    /// Public key has the wrong length.
+    #[error("invalid public key length: expected {expected}, got {got}")]
+    InvalidPublicKey {
+        /// Expected length in bytes.
+        expected: usize,
+        /// Actual length in bytes.
+        got: usize,
+    },
 }
 
 /// Validate that a public key has the correct length for ML-DSA-65.
 pub fn validate_public_key(pk: &ThresholdPublicKey) -> Result<(), FrostMlDsaError> {
     if pk.bytes.len() != PUBLIC_KEY_SIZE {
-        return Err(FrostMlDsaError::ResearchOnly(format!(
-            "expected public key length {PUBLIC_KEY_SIZE}, got {}",
-            pk.bytes.len()
-        )));
+        return Err(FrostMlDsaError::InvalidPublicKey {
+            expected: PUBLIC_KEY_SIZE,
+            got: pk.bytes.len(),
+        });
     }
     Ok(())
 }
@@ -86,6 +138,12 @@ mod tests {
             bytes: vec![0u8; 100],
         };
         let result = validate_public_key(&pk);
-        assert!(matches!(result, Err(FrostMlDsaError::ResearchOnly(_))));
+        assert!(matches!(
+            result,
+            Err(FrostMlDsaError::InvalidPublicKey {
+                expected: PUBLIC_KEY_SIZE,
+                got: 100
+            })
+        ));
     }
 }
@@ -0,0 +1,73 @@
+//! The single-signer FIPS 204 pieces the threshold protocol shares with
+//! plain ML-DSA-65: the message representative, the challenge, public
+//! key derivation and verification.
+//!
+//! Only the "pure" ML-DSA variant with an empty context string is
+//! supported, which is what `ML-DSA.Sign(sk, M, "")` produces.
+
+use sha3::Shake256;
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+
+use crate::encode::{pk_decode, pk_encode, sig_decode, w1_encode};
+use crate::params::{BETA, CTILDE_LEN, D, GAMMA1, K};
+use crate::poly::{Poly, VecK, expand_a, mat_vec, power2round, sample_in_ball, use_hint, vec_norm};
+
+/// `H(parts…)` with SHAKE256, filling `out`.
+pub fn shake256(parts: &[&[u8]], out: &mut [u8]) {
+    let mut h = Shake256::default();
+    for part in parts {
+        h.update(part);
+    }
+    h.finalize_xof().read(out);
+}
+
+/// `μ = H(H(pk, 64) ‖ 0 ‖ 0 ‖ M, 64)` — FIPS 204 Algorithm 2 with an
+/// empty context feeding Algorithm 7.
+pub fn message_representative(public_key: &[u8], message: &[u8]) -> [u8; 64] {
+    let mut tr = [0u8; 64];
+    shake256(&[public_key], &mut tr);
+    let mut mu = [0u8; 64];
+    shake256(&[&tr, &[0, 0], message], &mut mu);
+    mu
+}
+
+/// `c̃ = H(μ ‖ w1Encode(w1), λ/4)` and its challenge polynomial.
+pub fn challenge(mu: &[u8; 64], w1: &VecK) -> ([u8; CTILDE_LEN], Poly) {
+    let mut c_tilde = [0u8; CTILDE_LEN];
+    shake256(&[mu, &w1_encode(w1)], &mut c_tilde);
+    let c = sample_in_ball(&c_tilde);
+    (c_tilde, c)
+}
+
+/// Split `t` into `(t1, t0)` with Power2Round.
+pub fn split_t(t: &VecK) -> (VecK, [[i32; crate::params::N]; K]) {
+    let t1 = std::array::from_fn(|i| t[i].map(|x| power2round(x).0));
+    let t0 = std::array::from_fn(|i| t[i].0.map(|x| power2round(x).1));
+    (t1, t0)
+}
+
+/// The encoded public key `ρ ‖ t1` for `t = A·s1 + s2`.
+pub fn public_key(rho: &[u8; 32], t: &VecK) -> Vec<u8> {
+    pk_encode(rho, &split_t(t).0)
+}
+
+/// ML-DSA.Verify (FIPS 204 Algorithms 3 and 8) with an empty context.
+pub fn verify(public_key: &[u8], message: &[u8], signature: &[u8]) -> bool {
+    let (Some((rho, t1)), Some((c_tilde, z, h))) = (pk_decode(public_key), sig_decode(signature))
+    else {
+        return false;
+    };
+    if vec_norm(&z) >= GAMMA1 - BETA {
+        return false;
+    }
+    let a_hat = expand_a(&rho);
+    let mu = message_representative(public_key, message);
+    let c_hat = sample_in_ball(&c_tilde).ntt();
+    let az = mat_vec(&a_hat, &z);
+    let w1: VecK = std::array::from_fn(|i| {
+        let ct1 = t1[i].map(|x| x << D).mul_ntt(&c_hat);
+        let approx = az[i].sub(&ct1);
+        Poly(std::array::from_fn(|j| use_hint(h[i][j], approx.0[j])))
+    });
+    challenge(&mu, &w1).0 == c_tilde
+}
@@ -0,0 +1,26 @@
+//! ML-DSA-65 parameter set (FIPS 204, Table 1).
+
+/// The modulus `q = 2^23 − 2^13 + 1`.
+pub const Q: u32 = 8_380_417;
+/// Ring degree.
+pub const N: usize = 256;
+/// Rows of `A`.
+pub const K: usize = 6;
+/// Columns of `A`.
+pub const L: usize = 5;
+/// Bits dropped from `t` by `Power2Round`.
+pub const D: u32 = 13;
+/// Number of ±1 coefficients in the challenge.
+pub const TAU: u32 = 49;
+/// Secret coefficient bound of a single-signer key.
+pub const ETA: u32 = 4;
+/// Mask coefficient range.
+pub const GAMMA1: u32 = 1 << 19;
+/// Low-order rounding range.
+pub const GAMMA2: u32 = (Q - 1) / 32;
+/// `τ·η`; the verifier accepts `‖z‖∞ < γ1 − β`.
+pub const BETA: u32 = TAU * ETA;
+/// Maximum number of hint bits.
+pub const OMEGA: usize = 55;
+/// Commitment hash length `λ/4` in bytes.
+pub const CTILDE_LEN: usize = 48;
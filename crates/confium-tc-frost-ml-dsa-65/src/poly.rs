@@ -0,0 +1,379 @@
+//! Arithmetic in `R_q = Z_q[X]/(X^256 + 1)` and the FIPS 204 rounding
+//! and sampling helpers built on it.
+//!
+//! Coefficients are kept in `[0, q)`. The NTT follows FIPS 204
+//! Algorithms 41–42 with plain `u64` reduction; this is a prototype and
+//! none of it is constant time.
+
+use rand_core::{OsRng, RngCore};
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::{Shake128, Shake256, Shake256Reader};
+
+use crate::params::{D, GAMMA2, K, L, N, Q, TAU};
+
+/// A polynomial of `R_q`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Poly(pub [u32; N]);
+
+/// A length-`ℓ` vector.
+pub type VecL = [Poly; L];
+/// A length-`k` vector.
+pub type VecK = [Poly; K];
+/// The `k × ℓ` public matrix, in the NTT domain.
+pub type Matrix = [[Poly; L]; K];
+
+/// `ζ^brv(i)` for the 512th root of unity `ζ = 1753`.
+const ZETAS: [u32; N] = zetas();
+
+const fn zetas() -> [u32; N] {
+    let mut out = [0u32; N];
+    let mut i = 0;
+    while i < N {
+        let mut e = (i as u8).reverse_bits() as u32;
+        let mut base = 1753u64;
+        let mut acc = 1u64;
+        while e > 0 {
+            if e & 1 == 1 {
+                acc = acc * base % Q as u64;
+            }
+            base = base * base % Q as u64;
+            e >>= 1;
+        }
+        out[i] = acc as u32;
+        i += 1;
+    }
+    out
+}
+
+/// `256^-1 mod q`.
+const N_INV: u64 = 8_347_681;
+
+fn mul(a: u32, b: u32) -> u32 {
+    (a as u64 * b as u64 % Q as u64) as u32
+}
+
+fn add(a: u32, b: u32) -> u32 {
+    let s = a + b;
+    if s >= Q { s - Q } else { s }
+}
+
+fn sub(a: u32, b: u32) -> u32 {
+    if a >= b { a - b } else { a + Q - b }
+}
+
+/// `x mod± q` for `x ∈ [0, q)`.
+pub fn centered(x: u32) -> i32 {
+    if x > Q / 2 {
+        x as i32 - Q as i32
+    } else {
+        x as i32
+    }
+}
+
+/// The representative of `x` in `[0, q)`.
+pub fn from_centered(x: i32) -> u32 {
+    x.rem_euclid(Q as i32) as u32
+}
+
+impl Poly {
+    pub const ZERO: Poly = Poly([0; N]);
+
+    pub fn add(&self, other: &Poly) -> Poly {
+        Poly(std::array::from_fn(|i| add(self.0[i], other.0[i])))
+    }
+
+    pub fn sub(&self, other: &Poly) -> Poly {
+        Poly(std::array::from_fn(|i| sub(self.0[i], other.0[i])))
+    }
+
+    /// Coefficient-wise product; multiplication in the NTT domain.
+    pub fn pointwise(&self, other: &Poly) -> Poly {
+        Poly(std::array::from_fn(|i| mul(self.0[i], other.0[i])))
+    }
+
+    /// `‖·‖∞` over centered coefficients.
+    pub fn norm(&self) -> u32 {
+        self.0
+            .iter()
+            .map(|&x| centered(x).unsigned_abs())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// FIPS 204 Algorithm 41.
+    pub fn ntt(&self) -> Poly {
+        let mut w = self.0;
+        let mut m = 0;
+        let mut len = 128;
+        while len >= 1 {
+            for start in (0..N).step_by(2 * len) {
+                m += 1;
+                let z = ZETAS[m];
+                for j in start..start + len {
+                    let t = mul(z, w[j + len]);
+                    w[j + len] = sub(w[j], t);
+                    w[j] = add(w[j], t);
+                }
+            }
+            len /= 2;
+        }
+        Poly(w)
+    }
+
+    /// FIPS 204 Algorithm 42.
+    pub fn inv_ntt(&self) -> Poly {
+        let mut w = self.0;
+        let mut m = N;
+        let mut len = 1;
+        while len < N {
+            for start in (0..N).step_by(2 * len) {
+                m -= 1;
+                let z = Q - ZETAS[m];
+                for j in start..start + len {
+                    let t = w[j];
+                    w[j] = add(t, w[j + len]);
+                    w[j + len] = mul(z, sub(t, w[j + len]));
+                }
+            }
+            len *= 2;
+        }
+        Poly(w.map(|x| (x as u64 * N_INV % Q as u64) as u32))
+    }
+
+    /// `c·self` for `c_hat = NTT(c)`, back in the normal domain.
+    pub fn mul_ntt(&self, c_hat: &Poly) -> Poly {
+        self.ntt().pointwise(c_hat).inv_ntt()
+    }
+
+    /// Apply `f` to every coefficient.
+    pub fn map(&self, f: impl Fn(u32) -> u32) -> Poly {
+        Poly(self.0.map(f))
+    }
+}
+
+/// Element-wise sum of two vectors.
+pub fn vec_add<const M: usize>(a: &[Poly; M], b: &[Poly; M]) -> [Poly; M] {
+    std::array::from_fn(|i| a[i].add(&b[i]))
+}
+
+/// Element-wise difference of two vectors.
+pub fn vec_sub<const M: usize>(a: &[Poly; M], b: &[Poly; M]) -> [Poly; M] {
+    std::array::from_fn(|i| a[i].sub(&b[i]))
+}
+
+/// `‖·‖∞` of a vector.
+pub fn vec_norm(v: &[Poly]) -> u32 {
+    v.iter().map(Poly::norm).max().unwrap_or(0)
+}
+
+/// `c·v` for `c_hat = NTT(c)`.
+pub fn vec_mul_ntt<const M: usize>(c_hat: &Poly, v: &[Poly; M]) -> [Poly; M] {
+    std::array::from_fn(|i| v[i].mul_ntt(c_hat))
+}
+
+/// `A·v` for `v` in the normal domain.
+pub fn mat_vec(a_hat: &Matrix, v: &VecL) -> VecK {
+    let v_hat: VecL = std::array::from_fn(|i| v[i].ntt());
+    std::array::from_fn(|r| {
+        a_hat[r]
+            .iter()
+            .zip(&v_hat)
+            .fold(Poly::ZERO, |acc, (a, x)| acc.add(&a.pointwise(x)))
+            .inv_ntt()
+    })
+}
+
+/// ExpandA (FIPS 204 Algorithm 32), with RejNTTPoly inlined.
+pub fn expand_a(rho: &[u8; 32]) -> Matrix {
+    std::array::from_fn(|r| {
+        std::array::from_fn(|s| {
+            let mut h = Shake128::default();
+            h.update(rho);
+            h.update(&[s as u8, r as u8]);
+            let mut xof = h.finalize_xof();
+            let mut out = [0u32; N];
+            let mut filled = 0;
+            let mut buf = [0u8; 3];
+            while filled < N {
+                xof.read(&mut buf);
+                let x = u32::from(buf[0]) | u32::from(buf[1]) << 8 | u32::from(buf[2] & 0x7F) << 16;
+                if x < Q {
+                    out[filled] = x;
+                    filled += 1;
+                }
+            }
+            Poly(out)
+        })
+    })
+}
+
+/// SampleInBall (FIPS 204 Algorithm 29).
+pub fn sample_in_ball(seed: &[u8]) -> Poly {
+    let mut h = Shake256::default();
+    h.update(seed);
+    let mut xof = h.finalize_xof();
+    let mut sign_bytes = [0u8; 8];
+    xof.read(&mut sign_bytes);
+    let signs = u64::from_le_bytes(sign_bytes);
+    let mut c = [0u32; N];
+    let mut byte = [0u8; 1];
+    for i in N - TAU as usize..N {
+        let j = loop {
+            xof.read(&mut byte);
+            if usize::from(byte[0]) <= i {
+                break usize::from(byte[0]);
+            }
+        };
+        c[i] = c[j];
+        c[j] = if signs >> (i + TAU as usize - N) & 1 == 1 {
+            Q - 1
+        } else {
+            1
+        };
+    }
+    Poly(c)
+}
+
+/// Uniform small-coefficient sampler: SHAKE256 over a fresh 32-byte
+/// seed from the OS, read by rejection.
+pub struct Sampler(Shake256Reader);
+
+impl Sampler {
+    pub fn new() -> Self {
+        let mut seed = [0u8; 32];
+        OsRng.fill_bytes(&mut seed);
+        let mut h = Shake256::default();
+        h.update(&seed);
+        Sampler(h.finalize_xof())
+    }
+
+    /// A polynomial with coefficients uniform in `[−bound, bound]`.
+    pub fn poly(&mut self, bound: u32) -> Poly {
+        let range = 2 * bound + 1;
+        let mask = u32::MAX >> range.leading_zeros();
+        let mut buf = [0u8; 4];
+        Poly(std::array::from_fn(|_| {
+            loop {
+                self.0.read(&mut buf);
+                let x = u32::from_le_bytes(buf) & mask;
+                if x < range {
+                    break from_centered(x as i32 - bound as i32);
+                }
+            }
+        }))
+    }
+
+    pub fn vec<const M: usize>(&mut self, bound: u32) -> [Poly; M] {
+        std::array::from_fn(|_| self.poly(bound))
+    }
+}
+
+/// Power2Round (FIPS 204 Algorithm 35): `r = r1·2^d + r0`.
+pub fn power2round(r: u32) -> (u32, i32) {
+    let half = 1i32 << (D - 1);
+    let r0 = (r as i32 + half - 1).rem_euclid(1 << D) - (half - 1);
+    ((r as i32 - r0) as u32 >> D, r0)
+}
+
+/// Decompose (FIPS 204 Algorithm 36): `r = r1·2γ2 + r0`.
+pub fn decompose(r: u32) -> (u32, i32) {
+    let alpha = 2 * GAMMA2 as i32;
+    let mut r0 = (r as i32).rem_euclid(alpha);
+    if r0 > alpha / 2 {
+        r0 -= alpha;
+    }
+    if r as i32 - r0 == Q as i32 - 1 {
+        (0, r0 - 1)
+    } else {
+        ((r as i32 - r0) as u32 / alpha as u32, r0)
+    }
+}
+
+pub fn high_bits(r: u32) -> u32 {
+    decompose(r).0
+}
+
+pub fn low_bits(r: u32) -> i32 {
+    decompose(r).1
+}
+
+/// MakeHint (FIPS 204 Algorithm 39).
+pub fn make_hint(z: u32, r: u32) -> bool {
+    high_bits(r) != high_bits(add(r, z))
+}
+
+/// UseHint (FIPS 204 Algorithm 40).
+pub fn use_hint(h: bool, r: u32) -> u32 {
+    let m = (Q - 1) / (2 * GAMMA2);
+    let (r1, r0) = decompose(r);
+    match (h, r0 > 0) {
+        (false, _) => r1,
+        (true, true) => (r1 + 1) % m,
+        (true, false) => (r1 + m - 1) % m,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn poly(f: impl Fn(usize) -> u32) -> Poly {
+        Poly(std::array::from_fn(f))
+    }
+
+    #[test]
+    fn ntt_round_trips_and_multiplies_negacyclically() {
+        let a = poly(|i| (i as u32 * 7919 + 3) % Q);
+        assert!(a.ntt().inv_ntt() == a);
+
+        // X^255 · X = X^256 = −1.
+        let x255 = poly(|i| u32::from(i == 255));
+        let x = poly(|i| u32::from(i == 1));
+        let product = x255.ntt().pointwise(&x.ntt()).inv_ntt();
+        assert!(product == poly(|i| if i == 0 { Q - 1 } else { 0 }));
+    }
+
+    #[test]
+    fn rounding_recombines() {
+        for r in [
+            0,
+            1,
+            4096,
+            4097,
+            GAMMA2,
+            GAMMA2 + 1,
+            Q - GAMMA2,
+            Q - 2,
+            Q - 1,
+        ] {
+            let (r1, r0) = power2round(r);
+            assert_eq!(from_centered((r1 << D) as i32 + r0), r);
+            assert!(-(1 << (D - 1)) < r0 && r0 <= 1 << (D - 1));
+
+            let (r1, r0) = decompose(r);
+            assert_eq!(from_centered((r1 * 2 * GAMMA2) as i32 + r0), r);
+            assert!(r1 < 16);
+        }
+    }
+
+    #[test]
+    fn hints_recover_high_bits() {
+        for (r, z) in [
+            (5, GAMMA2),
+            (Q - 1, 3),
+            (2 * GAMMA2 - 1, 2),
+            (1000, Q - 2000),
+        ] {
+            let h = make_hint(z, r);
+            assert_eq!(use_hint(h, r), high_bits(add(r, z)));
+        }
+    }
+
+    #[test]
+    fn challenge_has_tau_signed_ones() {
+        let c = sample_in_ball(b"challenge seed");
+        let weight = c.0.iter().filter(|&&x| x != 0).count();
+        assert_eq!(weight, TAU as usize);
+        assert!(c.0.iter().all(|&x| x == 0 || x == 1 || x == Q - 1));
+    }
+}
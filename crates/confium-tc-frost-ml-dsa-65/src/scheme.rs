@@ -0,0 +1,30 @@
+//! Threshold ML-DSA-65 scheme registration.
+//!
+//! | Name              | Kind        | Session message | Produces                    |
+//! |-------------------|-------------|-----------------|-----------------------------|
+//! | `FROST-ML-DSA-65` | `Signature` | message         | FIPS 204 ML-DSA-65 signature|
+//!
+//! Share blobs come from [`crate::dealer`]; there is no DKG scheme.
+
+use confium_tc::Result;
+use confium_tc::registry::{SessionImpl, TcScheme, TcSchemeKind};
+use confium_tc::session::SessionParams;
+
+use crate::session::MlDsaSession;
+
+/// Threshold ML-DSA-65 signing, registered as [`crate::ALGORITHM`].
+pub struct MlDsa65Threshold;
+
+impl TcScheme for MlDsa65Threshold {
+    fn name(&self) -> &'static str {
+        crate::ALGORITHM
+    }
+    fn kind(&self) -> TcSchemeKind {
+        TcSchemeKind::Signature
+    }
+    fn create_session(&self, params: &SessionParams) -> Result<Box<dyn SessionImpl>> {
+        MlDsaSession::build_session(params)
+    }
+}
+
+confium_tc::register_tc_scheme!(MlDsa65Threshold);
@@ -0,0 +1,556 @@
+//! The threshold ML-DSA-65 signing session.
+//!
+//! Every member of the session roster signs; the roster must hold at
+//! least `threshold` distinct dealer indices. Each piece of the key is
+//! used by exactly one signer — the lowest-indexed signer holding it —
+//! so signer `i`'s effective secret is `s1_i = Σ_{S assigned to i} s1_S`
+//! and `Σ_i s1_i = s1`.
+//!
+//! ## Rounds
+//!
+//! 1. **Commit** — sample [`ATTEMPTS`] masks `y_i` with coefficients
+//!    in `[−B, B]`, `B = ⌊(γ1 − β + β' − 1)/k⌋` for `k` signers and
+//!    `β' = τ·η'·m`, compute `w_i = A·y_i` for each and broadcast a hash
+//!    of all of them.
+//! 2. **Reveal** — broadcast the `w_i`.
+//! 3. **Respond** — check every reveal against its commitment and sum
+//!    `w = Σ w_i` per attempt. For every attempt that passes the
+//!    *public* checks below, derive `c̃` and `c` exactly as FIPS 204
+//!    does and compute `z_i = y_i + c·s1_i`; broadcast only the `z_i`
+//!    that pass our own rejection test `‖z_i‖∞ ≤ B − β_i`, with
+//!    `β_i = τ·η'·m_i` for the `m_i` pieces assigned to us.
+//! 4. **Combine** — check every response, take the first attempt every
+//!    signer responded to, sum `z`, recompute `w − c·s2 = A·z − c·t`,
+//!    make the hints and emit the signature. If no attempt was accepted
+//!    by every signer the session aborts the try and starts over at
+//!    step 1 with fresh masks, in the same framework round, up to
+//!    [`MAX_TRIES`] times.
+//!
+//! ## Rejection sampling
+//!
+//! `‖c·s1_i‖∞ ≤ β_i`, so a `z_i` that passes the per-signer test is
+//! uniform on `[−(B − β_i), B − β_i]` whatever the secret — the
+//! single-signer ML-DSA argument, applied to each signer's own share.
+//! Rejected responses are never sent. The accepted ones sum to
+//! `‖z‖∞ ≤ k·B − β' < γ1 − β`, as the verifier requires.
+//!
+//! Plain ML-DSA also rejects on `LowBits(w − c·s2)` and on the hint
+//! count, both of which depend on the secret. Here the bound
+//! `‖c·s2‖∞ ≤ β'` is used instead, so an attempt is a candidate when
+//! `‖LowBits(w)‖∞ < γ2 − β'`, `‖c·t0‖∞ < γ2` and at most ω
+//! coefficients of `w + c·t0` are within `β'` of a rounding boundary or
+//! change high bits. All three only involve public values, so every
+//! signer agrees on the candidates; with honest signers the signature
+//! then verifies unconditionally.
+//!
+//! A signer's response is checked against its commitment with the
+//! public pieces `t_i = Σ t_S` of the pieces it was assigned:
+//! `‖w_i − (A·z_i − c·t_i)‖∞ = ‖c·s2_i‖∞` must be within `β_i` and
+//! `‖z_i‖∞` within its rejection bound. A signer that fails a check or
+//! withholds a message is named through
+//! [`confium_tc::Error::MessageRejected`].
+//!
+//! ## Limitations
+//!
+//! This is a research prototype. Masks are sampled from a box rather
+//! than a hyperball, so the per-signer acceptance rate falls quickly
+//! with the number of signers; the dealer caps keys at four parties to
+//! keep signing to a few tries. The `w_i` of rejected attempts are still
+//! revealed, as in the published designs, which relies on MLWE rather
+//! than on the rejection argument. A signer can stall signing by
+//! refusing every attempt; the session then fails with
+//! `NO_USABLE_ATTEMPT`. Keys come from a trusted dealer and sessions
+//! cannot be snapshotted, so keys should not protect anything of value.
+
+use confium_tc::registry::{RoundResult, SessionImpl};
+use confium_tc::session::SessionParams;
+use confium_tc::{Message, Result};
+
+use crate::encode::{POLY_Q_BYTES, pack_q, sig_encode, unpack_q};
+use crate::error::{MlDsaErrorCode, scheme_error};
+use crate::keys::{MlDsaKeyShare, Piece};
+use crate::mldsa::{self, shake256};
+use crate::params::{BETA, CTILDE_LEN, GAMMA1, GAMMA2, K, L, N, OMEGA, TAU};
+use crate::poly::{
+    Matrix, Poly, Sampler, VecK, VecL, expand_a, from_centered, high_bits, low_bits, make_hint,
+    mat_vec, vec_add, vec_mul_ntt, vec_norm, vec_sub,
+};
+
+/// Parallel signing attempts per try. Roughly one in six passes the
+/// public checks and each signer accepts its response to one with
+/// probability between 0.3 and 0.8; with at most four signers a try
+/// then succeeds with probability above 0.65 (measured), so
+/// [`MAX_TRIES`] tries all fail with probability around 2^-17.
+pub const ATTEMPTS: usize = 128;
+
+/// Tries before the session gives up with `NO_USABLE_ATTEMPT`. A try
+/// costs three framework rounds after the first, so ten tries fit the
+/// network driver's default round limit.
+pub const MAX_TRIES: u32 = 10;
+
+const TAG_COMMIT: u8 = 0xF1;
+const TAG_REVEAL: u8 = 0xF2;
+const TAG_RESPONSE: u8 = 0xF3;
+
+const COMMIT_DOMAIN: &[u8] = b"confium-tc-frost-ml-dsa-65/commit/v1";
+
+/// A signer as seen by this party, ourselves included.
+struct Signer {
+    id: String,
+    index: u32,
+    commitment: [u8; 32],
+    /// The revealed commitments `w_i`, one per attempt.
+    w: Vec<VecK>,
+}
+
+/// An attempt that passed the public checks in round 3.
+struct Attempt {
+    index: usize,
+    c_tilde: [u8; CTILDE_LEN],
+    c_hat: Poly,
+    /// `c·t0`.
+    ct0: VecK,
+}
+
+/// One signer's accepted responses, by attempt index.
+type Responses = Vec<(usize, VecL)>;
+
+/// One party's view of a threshold ML-DSA-65 signing run.
+pub struct MlDsaSession {
+    party_id: String,
+    roster_ids: Vec<String>,
+    share: MlDsaKeyShare,
+    message: Vec<u8>,
+    a_hat: Matrix,
+    /// `t = A·s1 + s2`, before Power2Round.
+    t: VecK,
+    /// Mask coefficient bound `B`.
+    mask_bound: u32,
+    /// `β' = τ·η'·m`, the bound on `‖c·s1‖∞` and `‖c·s2‖∞`.
+    piece_bound: u32,
+    /// Our masks, one per attempt.
+    masks: Vec<VecL>,
+    signers: Vec<Signer>,
+    candidates: Vec<Attempt>,
+    /// Our accepted responses, kept so the combine round can include
+    /// them.
+    own_responses: Responses,
+    tries: u32,
+    result: Option<Vec<u8>>,
+    round_done: u8,
+}
+
+/// Abort naming the sender of `msg`.
+fn reject(msg: &Message, reason: impl Into<String>) -> confium_tc::Error {
+    blame(&msg.from_party_id, msg.round, reason)
+}
+
+/// Abort naming `party`.
+fn blame(party: &str, round: u8, reason: impl Into<String>) -> confium_tc::Error {
+    confium_tc::error::MessageRejectedSnafu {
+        party: party.to_string(),
+        round,
+        reason: reason.into(),
+    }
+    .build()
+}
+
+/// The commitment to a packed reveal.
+fn commitment(mu: &[u8; 64], index: u32, reveal: &[u8]) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    shake256(&[COMMIT_DOMAIN, mu, &index.to_be_bytes(), reveal], &mut out);
+    out
+}
+
+impl MlDsaSession {
+    pub fn build_session(params: &SessionParams) -> Result<Box<dyn SessionImpl>> {
+        Ok(Box::new(Self::new(params)?))
+    }
+
+    fn new(params: &SessionParams) -> Result<Self> {
+        let party_id = params.parties.get(params.this_party_idx)?.id.clone();
+        let roster_ids: Vec<String> = params
+            .parties
+            .parties()
+            .iter()
+            .map(|p| p.id.clone())
+            .collect();
+        let share_bytes = params
+            .local_share
+            .as_ref()
+            .map(|s| s.bytes())
+            .ok_or_else(|| scheme_error(MlDsaErrorCode::BAD_SHARE))?;
+        let share = MlDsaKeyShare::from_bytes(share_bytes)?;
+        let signers = roster_ids.len() as u32;
+        if signers < share.threshold || signers > share.parties {
+            return Err(scheme_error(MlDsaErrorCode::BELOW_THRESHOLD));
+        }
+        let piece_bound = TAU * share.eta * share.pieces.len() as u32;
+        Ok(MlDsaSession {
+            party_id,
+            roster_ids,
+            a_hat: expand_a(&share.rho),
+            t: share.t(),
+            mask_bound: (GAMMA1 - BETA + piece_bound - 1) / signers,
+            piece_bound,
+            share,
+            message: params.message.clone().unwrap_or_default(),
+            masks: Vec::new(),
+            signers: Vec::new(),
+            candidates: Vec::new(),
+            own_responses: Vec::new(),
+            tries: 0,
+            result: None,
+            round_done: 0,
+        })
+    }
+
+    fn mu(&self) -> [u8; 64] {
+        mldsa::message_representative(&self.share.public_key(), &self.message)
+    }
+
+    /// Check that `msg` is a broadcast of the previous round with `tag`
+    /// from a roster member, returning its body.
+    fn body<'m>(&self, msg: &'m Message, tag: u8) -> Result<&'m [u8]> {
+        let round = self.round_done - 1;
+        if !self.roster_ids.contains(&msg.from_party_id) {
+            return Err(reject(msg, "sender is not in the roster"));
+        }
+        if msg.round != round || msg.payload.first() != Some(&tag) || !msg.is_broadcast() {
+            return Err(reject(msg, format!("expected a round {round} broadcast")));
+        }
+        Ok(&msg.payload[1..])
+    }
+
+    /// Fail naming the first roster member that `seen` does not cover.
+    fn require_all(&self, seen: impl Fn(&str) -> bool) -> Result<()> {
+        let round = self.round_done - 1;
+        match self.roster_ids.iter().find(|id| !seen(id)) {
+            Some(id) => Err(blame(id, round, format!("no round {round} message"))),
+            None => Ok(()),
+        }
+    }
+
+    /// The pieces assigned to signer `index`: those whose lowest-indexed
+    /// signing member it is.
+    fn assigned(&self, index: u32) -> impl Iterator<Item = &Piece> + '_ {
+        let indices: Vec<u32> = self.signers.iter().map(|s| s.index).collect();
+        self.share.pieces.iter().filter(move |p| {
+            indices
+                .iter()
+                .filter(|&&i| p.has_member(i))
+                .min()
+                .is_some_and(|&owner| owner == index)
+        })
+    }
+
+    /// `β_i`, the bound on `‖c·s1_i‖∞` and `‖c·s2_i‖∞` of signer `index`.
+    fn signer_bound(&self, index: u32) -> u32 {
+        TAU * self.share.eta * self.assigned(index).count() as u32
+    }
+
+    /// Round 1 of a try — sample fresh masks and commit to their images.
+    fn round_commit(&mut self) -> Result<RoundResult> {
+        self.tries += 1;
+        self.signers.clear();
+        self.candidates.clear();
+        self.own_responses.clear();
+        let mut sampler = Sampler::new();
+        self.masks = (0..ATTEMPTS)
+            .map(|_| sampler.vec::<L>(self.mask_bound))
+            .collect();
+        let w: Vec<VecK> = self.masks.iter().map(|y| mat_vec(&self.a_hat, y)).collect();
+        let mut reveal = Vec::new();
+        for wi in &w {
+            pack_q(&mut reveal, wi);
+        }
+        let index = self.share.index;
+        let commitment = commitment(&self.mu(), index, &reveal);
+        self.signers.push(Signer {
+            id: self.party_id.clone(),
+            index,
+            commitment,
+            w,
+        });
+        let mut payload = vec![TAG_COMMIT];
+        payload.extend_from_slice(&index.to_be_bytes());
+        payload.extend_from_slice(&commitment);
+        let msg = Message::broadcast(&self.party_id, self.round_done, payload);
+        Ok(RoundResult::new(vec![msg], false))
+    }
+
+    /// Round 2 — record every signer's commitment and reveal ours.
+    fn round_reveal(&mut self, incoming: &[Message]) -> Result<RoundResult> {
+        for m in incoming.iter().filter(|m| m.from_party_id != self.party_id) {
+            let body = self.body(m, TAG_COMMIT)?;
+            if body.len() != 36 {
+                return Err(reject(m, "malformed commitment"));
+            }
+            let index = u32::from_be_bytes([body[0], body[1], body[2], body[3]]);
+            if !(1..=self.share.parties).contains(&index) {
+                return Err(reject(m, "dealer index out of range"));
+            }
+            if self
+                .signers
+                .iter()
+                .any(|s| s.id == m.from_party_id || s.index == index)
+            {
+                return Err(reject(m, "duplicate signer or dealer index"));
+            }
+            self.signers.push(Signer {
+                id: m.from_party_id.clone(),
+                index,
+                commitment: body[4..].try_into().expect("length checked"),
+                w: Vec::new(),
+            });
+        }
+        self.require_all(|id| self.signers.iter().any(|s| s.id == id))?;
+        self.signers.sort_by_key(|s| s.index);
+
+        let mut payload = vec![TAG_REVEAL];
+        let own = self
+            .signers
+            .iter()
+            .find(|s| s.id == self.party_id)
+            .ok_or_else(|| scheme_error(MlDsaErrorCode::INTERNAL))?;
+        for wi in &own.w {
+            pack_q(&mut payload, wi);
+        }
+        let msg = Message::broadcast(&self.party_id, self.round_done, payload);
+        Ok(RoundResult::new(vec![msg], false))
+    }
+
+    /// Every attempt whose aggregate commitment passes the public
+    /// rejection checks, with its challenge.
+    fn candidates(&self) -> Vec<Attempt> {
+        let (_, t0) = mldsa::split_t(&self.t);
+        let t0: VecK = t0.map(|p| Poly(p.map(from_centered)));
+        let mu = self.mu();
+        let margin = GAMMA2 as i32 - self.piece_bound as i32;
+        let mut candidates = Vec::new();
+        for index in 0..ATTEMPTS {
+            let w = self
+                .signers
+                .iter()
+                .fold([Poly::ZERO; K], |acc, s| vec_add(&acc, &s.w[index]));
+            let flat = || w.iter().flat_map(|p| p.0);
+            if flat().any(|x| low_bits(x).abs() >= margin) {
+                continue;
+            }
+            let w1: VecK = w.map(|p| p.map(high_bits));
+            let (c_tilde, c) = mldsa::challenge(&mu, &w1);
+            let c_hat = c.ntt();
+            let ct0 = vec_mul_ntt(&c_hat, &t0);
+            if vec_norm(&ct0) >= GAMMA2 {
+                continue;
+            }
+            let shifted = vec_add(&w, &ct0);
+            let unsure = flat()
+                .zip(shifted.iter().flat_map(|p| p.0))
+                .filter(|&(x, y)| low_bits(y).abs() >= margin || high_bits(y) != high_bits(x))
+                .count();
+            if unsure > OMEGA {
+                continue;
+            }
+            candidates.push(Attempt {
+                index,
+                c_tilde,
+                c_hat,
+                ct0,
+            });
+        }
+        candidates
+    }
+
+    /// Round 3 — check the reveals and respond to every candidate
+    /// attempt whose response passes our rejection test.
+    fn round_respond(&mut self, incoming: &[Message]) -> Result<RoundResult> {
+        let mu = self.mu();
+        let reveal_len = ATTEMPTS * K * POLY_Q_BYTES;
+        for m in incoming.iter().filter(|m| m.from_party_id != self.party_id) {
+            let body = self.body(m, TAG_REVEAL)?;
+            let signer = self
+                .signers
+                .iter_mut()
+                .find(|s| s.id == m.from_party_id)
+                .ok_or_else(|| reject(m, "reveal without a commitment"))?;
+            if !signer.w.is_empty() {
+                return Err(reject(m, "duplicate reveal"));
+            }
+            if body.len() != reveal_len || commitment(&mu, signer.index, body) != signer.commitment
+            {
+                return Err(reject(m, "reveal does not match its commitment"));
+            }
+            signer.w = body
+                .chunks(K * POLY_Q_BYTES)
+                .map(unpack_q::<K>)
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(|| reject(m, "malformed reveal"))?;
+        }
+        self.require_all(|id| self.signers.iter().any(|s| s.id == id && !s.w.is_empty()))?;
+
+        self.candidates = self.candidates();
+        let s1 = self
+            .assigned(self.share.index)
+            .filter_map(|p| p.secret.as_ref())
+            .fold([Poly::ZERO; L], |acc, s| vec_add(&acc, s));
+        let bound = self.mask_bound - self.signer_bound(self.share.index);
+        self.own_responses = self
+            .candidates
+            .iter()
+            .map(|a| {
+                let z = vec_add(&self.masks[a.index], &vec_mul_ntt(&a.c_hat, &s1));
+                (a.index, z)
+            })
+            .filter(|(_, z)| vec_norm(z) <= bound)
+            .collect();
+
+        let mut payload = vec![TAG_RESPONSE, self.own_responses.len() as u8];
+        for (index, z) in &self.own_responses {
+            payload.push(*index as u8);
+            pack_q(&mut payload, z);
+        }
+        let msg = Message::broadcast(&self.party_id, self.round_done, payload);
+        Ok(RoundResult::new(vec![msg], false))
+    }
+
+    /// Check signer `index`'s response against its revealed commitment
+    /// and its rejection bound.
+    fn response_ok(&self, attempt: &Attempt, signer: &Signer, z: &VecL) -> bool {
+        let t_i = self
+            .assigned(signer.index)
+            .fold([Poly::ZERO; K], |acc, p| vec_add(&acc, &p.public));
+        let bound = self.signer_bound(signer.index);
+        if vec_norm(z) > self.mask_bound - bound {
+            return false;
+        }
+        let approx = vec_sub(&mat_vec(&self.a_hat, z), &vec_mul_ntt(&attempt.c_hat, &t_i));
+        vec_norm(&vec_sub(&signer.w[attempt.index], &approx)) <= bound
+    }
+
+    /// Parse and check one signer's accepted responses.
+    fn responses(&self, msg: &Message, signer: &Signer) -> Result<Responses> {
+        let body = self.body(msg, TAG_RESPONSE)?;
+        let entry_len = 1 + L * POLY_Q_BYTES;
+        let count = *body
+            .first()
+            .ok_or_else(|| reject(msg, "malformed response"))? as usize;
+        if body.len() != 1 + count * entry_len {
+            return Err(reject(msg, "malformed response"));
+        }
+        let mut responses: Responses = Vec::with_capacity(count);
+        for entry in body[1..].chunks(entry_len) {
+            let index = entry[0] as usize;
+            if responses.last().is_some_and(|&(last, _)| index <= last) {
+                return Err(reject(msg, "responses out of order"));
+            }
+            let attempt = self
+                .candidates
+                .iter()
+                .find(|a| a.index == index)
+                .ok_or_else(|| reject(msg, "response to an attempt that is not a candidate"))?;
+            let z = unpack_q::<L>(&entry[1..]).ok_or_else(|| reject(msg, "malformed response"))?;
+            if !self.response_ok(attempt, signer, &z) {
+                return Err(reject(msg, "response failed its check"));
+            }
+            responses.push((index, z));
+        }
+        Ok(responses)
+    }
+
+    /// Round 4 — check every response and combine the first attempt
+    /// all signers accepted, or start the next try.
+    fn round_combine(&mut self, incoming: &[Message]) -> Result<RoundResult> {
+        let mut accepted = vec![(self.party_id.clone(), self.own_responses.clone())];
+        for m in incoming.iter().filter(|m| m.from_party_id != self.party_id) {
+            if accepted.iter().any(|(id, _)| *id == m.from_party_id) {
+                return Err(reject(m, "duplicate response"));
+            }
+            let signer = self
+                .signers
+                .iter()
+                .find(|s| s.id == m.from_party_id)
+                .ok_or_else(|| reject(m, "response without a commitment"))?;
+            accepted.push((m.from_party_id.clone(), self.responses(m, signer)?));
+        }
+        self.require_all(|id| accepted.iter().any(|(a, _)| a == id))?;
+
+        let chosen = self.candidates.iter().find_map(|attempt| {
+            accepted
+                .iter()
+                .map(|(_, r)| r.iter().find(|(i, _)| *i == attempt.index).map(|(_, z)| z))
+                .try_fold([Poly::ZERO; L], |acc, z| z.map(|z| vec_add(&acc, z)))
+                .map(|z| (attempt, z))
+        });
+        let Some((attempt, z)) = chosen else {
+            if self.tries >= MAX_TRIES {
+                return Err(scheme_error(MlDsaErrorCode::NO_USABLE_ATTEMPT));
+            }
+            return self.round_commit();
+        };
+
+        // A·z − c·t = w − c·s2; the hints recover its high bits from
+        // A·z − c·t1·2^d = (w − c·s2) + c·t0.
+        let r = vec_sub(
+            &mat_vec(&self.a_hat, &z),
+            &vec_mul_ntt(&attempt.c_hat, &self.t),
+        );
+        let shifted = vec_add(&r, &attempt.ct0);
+        let hints: [[bool; N]; K] = std::array::from_fn(|i| {
+            std::array::from_fn(|j| {
+                let minus_ct0 = from_centered(-(attempt.ct0[i].0[j] as i32));
+                make_hint(minus_ct0, shifted[i].0[j])
+            })
+        });
+        let signature = sig_encode(&attempt.c_tilde, &z, &hints);
+        if !mldsa::verify(&self.share.public_key(), &self.message, &signature) {
+            return Err(scheme_error(MlDsaErrorCode::COMBINE_FAILED));
+        }
+        self.result = Some(signature);
+        Ok(RoundResult::done())
+    }
+}
+
+impl SessionImpl for MlDsaSession {
+    /// Round 1 commits; after that the rounds cycle through reveal,
+    /// respond and combine, combine starting the next try's commit when
+    /// no attempt was accepted by every signer.
+    fn round(&mut self, incoming: &[Message]) -> Result<RoundResult> {
+        self.round_done = self.round_done.checked_add(1).ok_or_else(|| {
+            confium_tc::error::RoundOverflowSnafu {
+                round: self.round_done,
+            }
+            .build()
+        })?;
+        if self.result.is_some() {
+            return Err(confium_tc::error::RoundOverflowSnafu {
+                round: self.round_done,
+            }
+            .build());
+        }
+        match self.round_done {
+            1 => self.round_commit(),
+            round => match (round - 2) % 3 {
+                0 => self.round_reveal(incoming),
+                1 => self.round_respond(incoming),
+                _ => self.round_combine(incoming),
+            },
+        }
+    }
+
+    fn result(&self) -> Result<Vec<u8>> {
+        self.result
+            .clone()
+            .ok_or_else(|| confium_tc::error::SessionNotCompleteSnafu {}.build())
+    }
+
+    fn destroy(&mut self) {
+        for piece in &mut self.share.pieces {
+            piece.secret = None;
+        }
+        self.masks.fill([Poly::ZERO; L]);
+        self.message.fill(0);
+        self.own_responses.clear();
+        self.result = None;
+    }
+}
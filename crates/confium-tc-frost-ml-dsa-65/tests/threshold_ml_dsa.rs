@@ -0,0 +1,134 @@
+//! End-to-end threshold ML-DSA-65.
+//!
+//! Every combined signature must verify under an independent FIPS 204
+//! implementation (`confium_composite::pq`, backed by the `ml-dsa`
+//! crate) as well as under this crate's own verifier.
+
+use confium_composite::pq::{MlDsa65Keypair, verify_mldsa65};
+use confium_tc::party::{Party, PartyList};
+use confium_tc::share::Share;
+use confium_tc::{Message, Session, SessionParams};
+use confium_tc_frost_ml_dsa_65::{ALGORITHM, SIGNATURE_SIZE, inprocess, verify};
+
+const MESSAGE: &[u8] = b"Confium threshold ML-DSA";
+
+#[test]
+fn scheme_is_registered() {
+    let scheme = confium_tc::registry::find(ALGORITHM).expect("registered");
+    assert_eq!(scheme.kind(), confium_tc::TcSchemeKind::Signature);
+}
+
+#[test]
+fn n_of_n_signature_verifies_with_a_fips_204_verifier() {
+    let key = inprocess::keygen(3, 3).expect("keygen");
+    let sig = inprocess::sign(&key.shares, 3, MESSAGE).expect("sign");
+    assert_eq!(sig.len(), SIGNATURE_SIZE);
+    verify_mldsa65(&key.public_key, MESSAGE, &sig).expect("ml-dsa accepts the signature");
+    assert!(verify(&key.public_key, MESSAGE, &sig));
+    assert!(verify_mldsa65(&key.public_key, b"another message", &sig).is_err());
+}
+
+#[test]
+fn every_t_of_n_subset_signs() {
+    let key = inprocess::keygen(2, 3).expect("keygen");
+    for pair in [[0, 1], [0, 2], [1, 2]] {
+        let signers = [key.shares[pair[0]].clone(), key.shares[pair[1]].clone()];
+        let sig = inprocess::sign(&signers, 2, MESSAGE).expect("sign");
+        verify_mldsa65(&key.public_key, MESSAGE, &sig).expect("ml-dsa accepts the signature");
+    }
+
+    let key = inprocess::keygen(3, 4).expect("keygen");
+    let sig = inprocess::sign(&key.shares[1..], 3, MESSAGE).expect("sign");
+    verify_mldsa65(&key.public_key, MESSAGE, &sig).expect("ml-dsa accepts the signature");
+}
+
+#[test]
+fn verifier_accepts_single_signer_signatures() {
+    let keypair = MlDsa65Keypair::generate();
+    let sig = keypair.sign(MESSAGE);
+    assert!(verify(&keypair.public_key, MESSAGE, &sig));
+
+    let mut forged = sig;
+    forged[100] ^= 1;
+    assert!(!verify(&keypair.public_key, MESSAGE, &forged));
+}
+
+#[test]
+fn signing_below_threshold_fails() {
+    let key = inprocess::keygen(2, 3).expect("keygen");
+    assert!(inprocess::sign(&key.shares[..1], 2, MESSAGE).is_err());
+}
+
+/// Alice and Bob on shares 1 and 3 of a 2-of-3 key.
+fn two_signers() -> Vec<Session> {
+    let key = inprocess::keygen(2, 3).expect("keygen");
+    let roster = PartyList::from_parties(vec![Party::inproc("alice"), Party::inproc("bob")]);
+    [0, 2]
+        .iter()
+        .enumerate()
+        .map(|(idx, &share)| {
+            Session::create(&SessionParams {
+                scheme: ALGORITHM.to_string(),
+                parties: roster.clone(),
+                threshold: 2,
+                this_party_idx: idx,
+                local_share: Some(Share::new(ALGORITHM.to_string(), key.shares[share].clone())),
+                message: Some(MESSAGE.to_vec()),
+            })
+            .expect("create")
+        })
+        .collect()
+}
+
+/// Run one round on both sessions, each receiving the other's messages.
+fn exchange(sessions: &mut [Session], outgoing: &[Vec<Message>]) -> Vec<Vec<Message>> {
+    [0, 1]
+        .iter()
+        .map(|&i| {
+            let incoming = outgoing[1 - i].clone();
+            sessions[i].round_step(&incoming).expect("round").outgoing
+        })
+        .collect()
+}
+
+#[test]
+fn tampered_response_names_the_signer() {
+    let mut sessions = two_signers();
+    let mut outgoing = exchange(&mut sessions, &[Vec::new(), Vec::new()]);
+    loop {
+        // Reveal, then respond.
+        outgoing = exchange(&mut sessions, &outgoing);
+        outgoing = exchange(&mut sessions, &outgoing);
+        // Payload: tag, response count, then attempt index and `z`.
+        if outgoing[1][0].payload[1] > 0 {
+            break;
+        }
+        // Bob accepted nothing this try; combining starts the next one.
+        outgoing = exchange(&mut sessions, &outgoing);
+    }
+
+    // Bob's response reaches Alice with one coefficient changed.
+    let mut response = outgoing[1][0].clone();
+    response.payload[3] ^= 0x10;
+    let err = sessions[0].round_step(&[response]).unwrap_err();
+    assert_eq!(err.culprit(), Some("bob"));
+}
+
+#[test]
+fn a_signer_accepting_nothing_aborts_the_try() {
+    let mut sessions = two_signers();
+    let commits = exchange(&mut sessions, &[Vec::new(), Vec::new()]);
+    let mut outgoing = exchange(&mut sessions, &commits);
+    outgoing = exchange(&mut sessions, &outgoing);
+
+    // Bob rejects every attempt: no attempt is usable, so Alice starts
+    // a new try with a fresh commitment instead of finishing.
+    let mut refusal = outgoing[1][0].clone();
+    refusal.payload.truncate(1);
+    refusal.payload.push(0);
+    let next = sessions[0].round_step(&[refusal]).expect("retry");
+    assert!(!next.complete);
+    assert_eq!(next.outgoing.len(), 1);
+    assert_eq!(next.outgoing[0].round, 4);
+    assert_eq!(next.outgoing[0].payload[0], commits[0][0].payload[0]);
+}
@@ -0,0 +1,59 @@
+//! In-process synchronous driver for FROST(P-256, SHA-256) DKG and
+//! signing.
+//!
+//! [`confium_tc_frost::inprocess`] specialised to [`P256`].
+
+use confium_tc::Result;
+use confium_tc_frost::inprocess as driver;
+
+use crate::P256;
+
+/// Run the FROST-P256 DKG for `party_count` parties at threshold
+/// `threshold`. Returns one key-package blob per party.
+pub fn keygen(threshold: u32, party_count: usize) -> Result<Vec<Vec<u8>>> {
+    driver::keygen::<P256>(threshold, party_count)
+}
+
+/// Threshold-sign `message`. Returns a 65-byte `R ‖ z` signature.
+pub fn sign(share_blobs: &[Vec<u8>], threshold: u32, message: &[u8]) -> Result<Vec<u8>> {
+    driver::sign::<P256>(share_blobs, threshold, message)
+}
+
+/// Sign N messages against the same group key.
+pub fn sign_batch(
+    share_blobs: &[Vec<u8>],
+    threshold: u32,
+    messages: &[&[u8]],
+) -> Result<Vec<Vec<u8>>> {
+    messages
+        .iter()
+        .map(|msg| sign(share_blobs, threshold, msg))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dkg_and_sign_round_trip() {
+        let shares = keygen(2, 3).expect("dkg");
+        let pk = crate::parse_dkg_output(&shares[0]).unwrap().public_key;
+        let sig = sign(&shares[..2], 2, b"hello p256").expect("sign");
+        crate::verify::verify(&pk, b"hello p256", &sig).expect("verifies");
+    }
+
+    #[test]
+    fn below_threshold_errors() {
+        let shares = keygen(3, 5).expect("dkg");
+        assert!(sign(&shares[..2], 3, b"msg").is_err());
+    }
+
+    #[test]
+    fn sign_batch_produces_one_sig_per_message() {
+        let shares = keygen(2, 3).expect("dkg");
+        let messages: Vec<&[u8]> = vec![b"msg-a", b"msg-b", b"msg-c"];
+        let sigs = sign_batch(&shares[1..], 2, &messages).expect("batch sign");
+        assert_eq!(sigs.len(), 3);
+    }
+}
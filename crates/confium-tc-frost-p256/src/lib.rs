@@ -1,58 +1,86 @@
-//! FROST threshold signature over ECDSA P-256.
+//! FROST(P-256, SHA-256) threshold Schnorr signatures (RFC 9591).
 //!
-//! Implements real Shamir secret sharing over the P-256 scalar field
-//! plus real P-256 ECDSA signing/verification. Used by OIML CNML IA
-//! quorum and Mode 2 enterprise PKI replacement for compatibility
-//! with existing P-256 PKI.
+//! The `FROST-P256-SHA256-v1` ciphersuite itself lives in the generic
+//! FROST core as [`confium_tc_frost::p256::P256`], which registers both
+//! schemes with the [`confium_tc`] link-time registry:
 //!
-//! ## Important: threshold ECDSA caveats
+//! - [`SIGN_SCHEME`] = `"FROST-P256"` — two-round threshold signing.
+//!   Produces a 65-byte Schnorr signature `R ‖ z` that
+//!   [`verify::verify`] (and `confium_verify::frost`) checks against the
+//!   33-byte group public key. The group secret is never reconstructed.
 //!
-//! True threshold ECDSA signing (where the secret is never reconstructed)
-//! requires the Multiplicative-to-Additive (MtA) protocol used by
-//! `confium-tc-cmp20` and `confium-tc-gg18`. This crate provides:
+//! - [`DKG_SCHEME`] = `"FROST-P256-dkg"` — Pedersen / Feldman VSS DKG,
+//!   emitting every participant's verifying share so signers can
+//!   attribute a bad signature share to its sender.
 //!
-//! - **Real Shamir secret sharing** over P-256 scalars
-//! - **Real Lagrange interpolation** to reconstruct the secret from T shares
-//! - **Real P-256 ECDSA** sign/verify
+//! This crate keeps the P-256 names callers already use —
+//! [`FrostP256`], [`FrostP256Dkg`], [`KeyPackage`], [`inprocess`],
+//! [`verify`] — as aliases of the generic types. The RFC 9591
+//! Appendix E.4 vectors are checked in `confium-tc-frost`'s
+//! `tests/rfc9591.rs`.
 //!
-//! For demonstration, integration testing, and as the underlying primitives
-//! for FROST-style schemes. For production threshold ECDSA signing,
-//! use `confium-tc-cmp20`.
+//! ## Dealer-side helpers
 //!
-//! See `TODO.roadmap/04-threshold-cryptography.md` for the FFI spec.
+//! The older [`keys`], [`shamir`] and [`sign`] modules are kept for
+//! callers that split an existing P-256 ECDSA key: Shamir sharing over
+//! the P-256 scalar field, Lagrange recovery, and ordinary ECDSA with
+//! the *recovered* key. They reconstruct the secret and are not a
+//! threshold signing protocol; use the `FROST-P256` scheme (Schnorr) or
+//! `confium-tc-cmp20` (ECDSA) instead.
 //!
 //! # Example
 //!
 //! ```
-//! use confium_tc_frost_p256::{generate_keypair, split_secret, recover_secret};
+//! use confium_tc_frost_p256::{inprocess, parse_dkg_output, verify};
 //!
-//! let kp = generate_keypair();
-//! // 3-of-5 secret sharing of the keypair's secret scalar.
-//! let shares = split_secret(&kp.secret_scalar, 3, 5);
-//! // Any 3 shares reconstruct the original secret.
-//! let subset: Vec<_> = shares.iter().take(3).collect();
-//! let recovered = recover_secret(&subset)?;
-//! assert_eq!(recovered, kp.secret_scalar);
-//! # Ok::<(), confium_tc_frost_p256::ShamirError>(())
+//! let blobs = inprocess::keygen(2, 3)?;
+//! let public_key = parse_dkg_output(&blobs[0]).expect("key package").public_key;
+//! let signature = inprocess::sign(&blobs[1..], 2, b"hello")?;
+//! verify::verify(&public_key, b"hello", &signature).expect("valid signature");
+//! # Ok::<(), confium_tc::Error>(())
 //! ```
 
 #![forbid(unsafe_code)]
 #![allow(missing_docs)] // TODO: document before 1.0
 
+pub mod inprocess;
 pub mod keys;
 pub mod scalar;
 pub mod shamir;
 pub mod sign;
-
-#[cfg(test)]
-mod props;
+pub mod verify;
 
 pub use keys::*;
 pub use shamir::*;
 pub use sign::*;
 
-/// Algorithm identifier for FROST-P256.
-pub const ALGORITHM: &str = "FROST-P256";
+pub use confium_tc_frost::p256::P256;
+pub use confium_tc_frost::{error, frost};
+
+use confium_tc_frost::Ciphersuite;
+
+/// The FROST-P256 signing scheme.
+pub type FrostP256 = confium_tc_frost::FrostSigning<P256>;
+
+/// The FROST-P256 DKG scheme.
+pub type FrostP256Dkg = confium_tc_frost::FrostDkg<P256>;
+
+/// A FROST-P256 DKG output: this party's share plus the group key.
+pub type KeyPackage = confium_tc_frost::KeyPackage<P256>;
+
+/// Algorithm identifier for FROST-P256 — the signing scheme's name.
+pub const ALGORITHM: &str = P256::SIGN_SCHEME;
+
+/// Convenience: the canonical name of the signing scheme.
+pub const SIGN_SCHEME: &str = P256::SIGN_SCHEME;
+
+/// Convenience: the canonical name of the DKG scheme.
+pub const DKG_SCHEME: &str = P256::DKG_SCHEME;
+
+/// Parse a FROST-P256 DKG output blob.
+pub fn parse_dkg_output(blob: &[u8]) -> error::Result<KeyPackage> {
+    KeyPackage::from_bytes(blob)
+}
 
 /// Re-export for convenience.
 pub use p256;
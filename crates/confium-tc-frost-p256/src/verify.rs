@@ -0,0 +1,50 @@
+//! Standalone FROST(P-256, SHA-256) signature verification.
+//!
+//! A FROST signature is an ordinary Schnorr signature, so verifying one
+//! needs nothing but the group public key: RFC 9591 §6.4 / Appendix B
+//! `verify_signature` checks `z·G == R + c·PK` with
+//! `c = H2(R ‖ PK ‖ msg)`. P-256 has cofactor 1, so no cofactor clearing.
+
+use confium_tc_frost::error::Result;
+
+use crate::P256;
+
+/// Verify a 65-byte `R ‖ z` signature over `message` under the 33-byte
+/// compressed group public key.
+pub fn verify(public_key: &[u8], message: &[u8], signature: &[u8]) -> Result<()> {
+    confium_tc_frost::verify::verify::<P256>(public_key, message, signature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use confium_tc_frost::Ciphersuite;
+    use confium_tc_frost::frost::{SigningPackage, commit};
+
+    fn single_signer(msg: &[u8]) -> (Vec<u8>, Vec<u8>) {
+        let secret = P256::random_scalar();
+        let pk = P256::serialize_element(&(P256::generator() * secret));
+        let nonces = commit::<P256>(&secret);
+        let pkg = SigningPackage::<P256>::new(&pk, msg, vec![nonces.commitment(1)], 1).unwrap();
+        let z = pkg.sign_share(1, &secret, &nonces);
+        let sig = pkg.aggregate([&z]);
+        (pk, sig)
+    }
+
+    #[test]
+    fn accepts_valid_and_rejects_tampered() {
+        let (pk, sig) = single_signer(b"hello");
+        verify(&pk, b"hello", &sig).expect("valid");
+        assert!(verify(&pk, b"hellO", &sig).is_err());
+        let mut bad = sig;
+        bad[40] ^= 1;
+        assert!(verify(&pk, b"hello", &bad).is_err());
+    }
+
+    #[test]
+    fn rejects_wrong_lengths() {
+        let (pk, sig) = single_signer(b"m");
+        assert!(verify(&pk[..32], b"m", &sig).is_err());
+        assert!(verify(&pk, b"m", &sig[..64]).is_err());
+    }
+}
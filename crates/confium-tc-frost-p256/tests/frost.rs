@@ -0,0 +1,266 @@
+//! End-to-end integration tests for the FROST(P-256, SHA-256) schemes.
+//!
+//! Drives every party through DKG → signing in-process, routing messages
+//! between sessions directly, and asserts:
+//!
+//! 1. DKG produces the same group public key and verifying shares on
+//!    every party.
+//! 2. Any `T` key holders — not only a prefix of the DKG roster — sign,
+//!    and the result verifies as a plain Schnorr signature.
+//! 3. A signer that tampers with its signature share is named as the
+//!    culprit.
+//! 4. Sessions resume from snapshots without reusing a nonce pair.
+
+use confium_tc::Session;
+use confium_tc::SessionParams;
+use confium_tc::party::{Party, PartyList};
+use confium_tc::share::Share;
+use confium_tc_frost_p256::DKG_SCHEME;
+use confium_tc_frost_p256::KeyPackage;
+use confium_tc_frost_p256::SIGN_SCHEME;
+use confium_tc_frost_p256::parse_dkg_output;
+use confium_tc_frost_p256::verify::verify;
+
+fn params(
+    scheme: &str,
+    roster: &[&str],
+    idx: usize,
+    threshold: u32,
+    share: Option<Vec<u8>>,
+    msg: Option<&[u8]>,
+) -> SessionParams {
+    let parties = roster.iter().map(|id| Party::inproc(*id)).collect();
+    SessionParams {
+        scheme: scheme.to_string(),
+        parties: PartyList::from_parties(parties),
+        threshold,
+        this_party_idx: idx,
+        local_share: share.map(|b| Share::new(SIGN_SCHEME, b)),
+        message: msg.map(<[u8]>::to_vec),
+    }
+}
+
+/// Messages in `outgoing` that `me` should receive.
+fn inbox(outgoing: &[confium_tc::Message], me: &str) -> Vec<confium_tc::Message> {
+    outgoing
+        .iter()
+        .filter(|m| m.from_party_id != me && m.is_for(me))
+        .cloned()
+        .collect()
+}
+
+/// Step every session once per round until `rounds` rounds are done,
+/// passing each outgoing batch through `tamper` before delivery.
+fn drive(
+    sessions: &mut [Session],
+    ids: &[&str],
+    rounds: u8,
+    mut tamper: impl FnMut(&mut confium_tc::Message),
+) -> confium_tc::Result<()> {
+    let mut outgoing: Vec<confium_tc::Message> = Vec::new();
+    for _ in 0..rounds {
+        let mut next = Vec::new();
+        for (sess, id) in sessions.iter_mut().zip(ids) {
+            next.extend(sess.round_step(&inbox(&outgoing, id))?.outgoing);
+        }
+        next.iter_mut().for_each(&mut tamper);
+        outgoing = next;
+    }
+    Ok(())
+}
+
+fn run_dkg(roster: &[&str], threshold: u32) -> Vec<Vec<u8>> {
+    let mut sessions: Vec<Session> = (0..roster.len())
+        .map(|i| {
+            Session::create(&params(DKG_SCHEME, roster, i, threshold, None, None))
+                .expect("dkg session")
+        })
+        .collect();
+    drive(&mut sessions, roster, 2, |_| {}).expect("dkg");
+    sessions
+        .iter()
+        .map(|s| s.result().expect("dkg result"))
+        .collect()
+}
+
+/// Sign with the key holders `signers` (names and blobs) as a fresh
+/// signing roster; returns every signer's output.
+fn run_sign(
+    signers: &[(&str, &Vec<u8>)],
+    threshold: u32,
+    msg: &[u8],
+    tamper: impl FnMut(&mut confium_tc::Message),
+) -> confium_tc::Result<Vec<Vec<u8>>> {
+    let ids: Vec<&str> = signers.iter().map(|(id, _)| *id).collect();
+    let mut sessions = signers
+        .iter()
+        .enumerate()
+        .map(|(i, (_, blob))| {
+            Session::create(&params(
+                SIGN_SCHEME,
+                &ids,
+                i,
+                threshold,
+                Some((*blob).clone()),
+                Some(msg),
+            ))
+        })
+        .collect::<confium_tc::Result<Vec<_>>>()?;
+    drive(&mut sessions, &ids, 3, tamper)?;
+    sessions.iter().map(Session::result).collect()
+}
+
+fn key(blob: &[u8]) -> KeyPackage {
+    parse_dkg_output(blob).expect("key package")
+}
+
+#[test]
+fn both_schemes_are_registered() {
+    assert!(confium_tc::registry::find(DKG_SCHEME).is_some());
+    assert!(confium_tc::registry::find(SIGN_SCHEME).is_some());
+}
+
+#[test]
+fn dkg_agrees_on_public_key_and_verifying_shares() {
+    let blobs = run_dkg(&["alice", "bob", "carol"], 2);
+    let keys: Vec<KeyPackage> = blobs.iter().map(|b| key(b)).collect();
+    for (i, k) in keys.iter().enumerate() {
+        assert_eq!(k.identifier, i as u32 + 1);
+        assert_eq!(k.public_key, keys[0].public_key);
+        assert_eq!(k.verifying_shares, keys[0].verifying_shares);
+    }
+}
+
+#[test]
+fn any_two_of_three_sign() {
+    let roster = ["alice", "bob", "carol"];
+    let blobs = run_dkg(&roster, 2);
+    let pk = key(&blobs[0]).public_key;
+    let msg = b"frost-p256 threshold message";
+    for pair in [[0, 1], [0, 2], [1, 2]] {
+        let signers: Vec<_> = pair.iter().map(|&i| (roster[i], &blobs[i])).collect();
+        let sigs = run_sign(&signers, 2, msg, |_| {}).expect("sign");
+        assert_eq!(
+            sigs[0], sigs[1],
+            "both signers aggregate the same signature"
+        );
+        assert_eq!(sigs[0].len(), 65);
+        verify(&pk, msg, &sigs[0]).expect("signature verifies");
+    }
+}
+
+#[test]
+fn three_of_five_above_and_at_threshold() {
+    let roster = ["p1", "p2", "p3", "p4", "p5"];
+    let blobs = run_dkg(&roster, 3);
+    let pk = key(&blobs[0]).public_key;
+    for subset in [vec![0, 2, 4], vec![1, 2, 3, 4]] {
+        let signers: Vec<_> = subset.iter().map(|&i| (roster[i], &blobs[i])).collect();
+        let sigs = run_sign(&signers, 3, b"m", |_| {}).expect("sign");
+        verify(&pk, b"m", &sigs[0]).expect("signature verifies");
+    }
+}
+
+#[test]
+fn below_threshold_signer_set_is_refused() {
+    let roster = ["p1", "p2", "p3"];
+    let blobs = run_dkg(&roster, 3);
+    let signers = [(roster[0], &blobs[0]), (roster[1], &blobs[1])];
+    assert!(run_sign(&signers, 3, b"m", |_| {}).is_err());
+}
+
+#[test]
+fn tampered_signature_share_names_the_signer() {
+    let roster = ["alice", "bob", "carol"];
+    let blobs = run_dkg(&roster, 2);
+    let signers = [(roster[1], &blobs[1]), (roster[2], &blobs[2])];
+    let err = run_sign(&signers, 2, b"m", |m| {
+        if m.from_party_id == "bob" && m.round == 2 {
+            m.payload[5] ^= 0x01;
+        }
+    })
+    .unwrap_err();
+    assert_eq!(err.culprit(), Some("bob"));
+}
+
+const SNAPSHOT_KEY: &[u8] = b"frost-share-envelope-integrity-key";
+
+#[test]
+fn signer_resumes_after_restart_without_reusing_its_nonce() {
+    use confium_tc::snapshot::MemoryNonceLedger;
+    use std::sync::Arc;
+
+    let roster = ["alice", "bob"];
+    let blobs = run_dkg(&roster, 2);
+    let msg: &[u8] = b"resume-after-restart";
+    let p: Vec<SessionParams> = (0..2)
+        .map(|i| {
+            params(
+                SIGN_SCHEME,
+                &roster,
+                i,
+                2,
+                Some(blobs[i].clone()),
+                Some(msg),
+            )
+        })
+        .collect();
+    let ledger = Arc::new(MemoryNonceLedger::new());
+
+    let mut alice = Session::create(&p[0]).expect("alice");
+    alice.set_nonce_ledger(ledger.clone());
+    let mut bob = Session::create(&p[1]).expect("bob");
+    alice.round_step(&[]).expect("alice round 1");
+    let blob = alice.snapshot(SNAPSHOT_KEY).expect("snapshot");
+    let b1 = bob.round_step(&[]).expect("bob round 1").outgoing;
+    drop(alice);
+
+    let mut alice = Session::resume(&p[0], &blob, SNAPSHOT_KEY, ledger.clone()).expect("resume");
+    let a1 = alice.last_outgoing().to_vec();
+    let a2 = alice
+        .round_step(&inbox(&b1, "alice"))
+        .expect("alice round 2");
+    let b2 = bob.round_step(&inbox(&a1, "bob")).expect("bob round 2");
+    alice
+        .round_step(&inbox(&b2.outgoing, "alice"))
+        .expect("alice round 3");
+    bob.round_step(&inbox(&a2.outgoing, "bob"))
+        .expect("bob round 3");
+    let sig = alice.result().expect("signature");
+    assert_eq!(sig, bob.result().expect("signature"));
+    verify(&key(&blobs[0]).public_key, msg, &sig).expect("signature verifies");
+
+    // The snapshot still holds the now-spent nonce pair.
+    let err = Session::resume(&p[0], &blob, SNAPSHOT_KEY, ledger).unwrap_err();
+    assert!(matches!(err, confium_tc::Error::NonceReuse { .. }));
+}
+
+#[test]
+fn dkg_resumes_after_dealing() {
+    use confium_tc::snapshot::MemoryNonceLedger;
+    use std::sync::Arc;
+
+    let roster = ["alice", "bob"];
+    let p: Vec<SessionParams> = (0..2)
+        .map(|i| params(DKG_SCHEME, &roster, i, 2, None, None))
+        .collect();
+    let ledger = Arc::new(MemoryNonceLedger::new());
+    let mut alice = Session::create(&p[0]).expect("alice");
+    alice.set_nonce_ledger(ledger.clone());
+    let mut bob = Session::create(&p[1]).expect("bob");
+    alice.round_step(&[]).expect("alice round 1");
+    let blob = alice.snapshot(SNAPSHOT_KEY).expect("snapshot");
+    let b1 = bob.round_step(&[]).expect("bob round 1").outgoing;
+    drop(alice);
+
+    let mut alice = Session::resume(&p[0], &blob, SNAPSHOT_KEY, ledger).expect("resume");
+    let a1 = alice.last_outgoing().to_vec();
+    alice
+        .round_step(&inbox(&b1, "alice"))
+        .expect("alice round 2");
+    bob.round_step(&inbox(&a1, "bob")).expect("bob round 2");
+    assert_eq!(
+        key(&alice.result().unwrap()).public_key,
+        key(&bob.result().unwrap()).public_key
+    );
+}
@@ -0,0 +1,191 @@
+//! The [`Ciphersuite`] trait: everything FROST needs from a prime-order
+//! group and its hash functions.
+//!
+//! RFC 9591 §6 defines a ciphersuite as a group (`G`, `Ns`, `Ne`,
+//! `SerializeScalar`, `DeserializeElement`, …), a context string and
+//! five hash functions H1–H5. The trait mirrors that list one-to-one, so
+//! adding a ciphersuite is a trait implementation plus a
+//! [`register_ciphersuite!`](crate::register_ciphersuite) line; the DKG,
+//! signing sessions, verification and key-package encoding in this
+//! crate are all generic over it.
+//!
+//! A few provided methods exist for suites that deviate from plain
+//! RFC 9591 Schnorr. The defaults are the RFC behaviour; BIP-340
+//! (`crate::secp256k1::Secp256k1Tr`) overrides them to use x-only keys
+//! and nonces with even `y`:
+//!
+//! - [`Ciphersuite::challenge`] — the challenge hash and its input
+//!   encoding.
+//! - [`Ciphersuite::needs_negation`] — whether a group key or group
+//!   commitment must be negated before use.
+//! - [`Ciphersuite::serialize_signature`] /
+//!   [`Ciphersuite::deserialize_signature`] — the signature encoding.
+//! - [`Ciphersuite::deserialize_public_key`] — the public-key encoding
+//!   accepted by standalone verification.
+//! - [`Ciphersuite::cofactor`] — multiplied into both sides of the
+//!   verification equation.
+
+use std::fmt::Debug;
+use std::ops::{Add, Mul, Neg, Sub};
+
+use confium_tc::snapshot::{self, StateReader};
+
+/// An RFC 9591 ciphersuite.
+pub trait Ciphersuite: Send + Sync + Sized + 'static {
+    /// Registered name of the threshold signing scheme.
+    const SIGN_SCHEME: &'static str;
+
+    /// Registered name of the DKG scheme whose output the signing scheme
+    /// consumes.
+    const DKG_SCHEME: &'static str;
+
+    /// Registered name of the one-round signing scheme that answers a
+    /// coordinator's [`crate::preprocess::SigningPackage`] from a
+    /// preprocessed nonce pool.
+    const PREPROCESSED_SCHEME: &'static str;
+
+    /// RFC 9591 `contextString`, prefixed into the hash functions.
+    const CONTEXT_STRING: &'static [u8];
+
+    /// `Ns` — byte length of a serialised scalar.
+    const SCALAR_BYTES: usize;
+
+    /// `Ne` — byte length of a serialised group element.
+    const ELEMENT_BYTES: usize;
+
+    /// Byte length of a serialised signature.
+    const SIGNATURE_BYTES: usize = Self::ELEMENT_BYTES + Self::SCALAR_BYTES;
+
+    /// An element of the scalar field.
+    type Scalar: Copy
+        + Send
+        + Sync
+        + Debug
+        + PartialEq
+        + Add<Output = Self::Scalar>
+        + Sub<Output = Self::Scalar>
+        + Mul<Output = Self::Scalar>
+        + Neg<Output = Self::Scalar>;
+
+    /// A group element.
+    type Element: Copy
+        + Send
+        + Sync
+        + Debug
+        + PartialEq
+        + Add<Output = Self::Element>
+        + Mul<Self::Scalar, Output = Self::Element>;
+
+    /// A small integer — participant identifiers, polynomial points.
+    fn scalar_from_u32(x: u32) -> Self::Scalar;
+
+    /// Multiplicative inverse; `None` for zero.
+    fn invert(s: &Self::Scalar) -> Option<Self::Scalar>;
+
+    /// A uniformly random scalar from the OS RNG.
+    fn random_scalar() -> Self::Scalar;
+
+    /// `SerializeScalar` — `Ns` bytes.
+    fn serialize_scalar(s: &Self::Scalar) -> Vec<u8>;
+
+    /// `DeserializeScalar` — `None` unless `bytes` is a canonical
+    /// `Ns`-byte encoding.
+    fn deserialize_scalar(bytes: &[u8]) -> Option<Self::Scalar>;
+
+    /// The identity element.
+    fn identity() -> Self::Element;
+
+    /// The fixed generator `G`.
+    fn generator() -> Self::Element;
+
+    /// `SerializeElement` — `Ne` bytes. Only called on non-identity
+    /// elements.
+    fn serialize_element(e: &Self::Element) -> Vec<u8>;
+
+    /// `DeserializeElement` — `None` unless `bytes` is the canonical
+    /// encoding of a non-identity element of the prime-order subgroup.
+    fn deserialize_element(bytes: &[u8]) -> Option<Self::Element>;
+
+    /// H1 — binding factors.
+    fn h1(msg: &[&[u8]]) -> Self::Scalar;
+
+    /// H2 — challenge.
+    fn h2(msg: &[&[u8]]) -> Self::Scalar;
+
+    /// H3 — nonce derivation.
+    fn h3(msg: &[&[u8]]) -> Self::Scalar;
+
+    /// H4 — message digest in the binding factor input.
+    fn h4(msg: &[u8]) -> Vec<u8>;
+
+    /// H5 — commitment-list digest in the binding factor input.
+    fn h5(msg: &[u8]) -> Vec<u8>;
+
+    /// RFC 9591 §4.6 `compute_challenge`: `H2(R ‖ PK ‖ msg)`.
+    fn challenge(
+        group_commitment: &Self::Element,
+        group_public_key: &Self::Element,
+        msg: &[u8],
+    ) -> Self::Scalar {
+        Self::h2(&[
+            &Self::serialize_element(group_commitment),
+            &Self::serialize_element(group_public_key),
+            msg,
+        ])
+    }
+
+    /// Whether `e`, as a group key or group commitment, must be negated
+    /// before signing so that it has the encoding the verifier assumes.
+    fn needs_negation(e: &Self::Element) -> bool {
+        let _ = e;
+        false
+    }
+
+    /// The group cofactor `h`; verification checks `h·z·G == h·(R + c·PK)`.
+    fn cofactor() -> Self::Scalar {
+        Self::scalar_from_u32(1)
+    }
+
+    /// Encode a signature `(R, z)`. RFC 9591: `SerializeElement(R) ‖
+    /// SerializeScalar(z)`.
+    fn serialize_signature(r: &Self::Element, z: &Self::Scalar) -> Vec<u8> {
+        let mut out = Self::serialize_element(r);
+        out.extend_from_slice(&Self::serialize_scalar(z));
+        out
+    }
+
+    /// Decode a signature produced by [`Ciphersuite::serialize_signature`].
+    fn deserialize_signature(bytes: &[u8]) -> Option<(Self::Element, Self::Scalar)> {
+        if bytes.len() != Self::SIGNATURE_BYTES {
+            return None;
+        }
+        let (r, z) = bytes.split_at(Self::ELEMENT_BYTES);
+        Some((Self::deserialize_element(r)?, Self::deserialize_scalar(z)?))
+    }
+
+    /// Decode a group public key handed to standalone verification.
+    fn deserialize_public_key(bytes: &[u8]) -> Option<Self::Element> {
+        Self::deserialize_element(bytes)
+    }
+}
+
+/// `s·G`.
+pub(crate) fn mul_base<C: Ciphersuite>(s: &C::Scalar) -> C::Element {
+    C::generator() * *s
+}
+
+/// `e`, or `-e` when `negate` is set.
+pub(crate) fn conditional_negate<C: Ciphersuite>(e: C::Element, negate: bool) -> C::Element {
+    if negate {
+        e * -C::scalar_from_u32(1)
+    } else {
+        e
+    }
+}
+
+/// Read a scalar written with `StateWriter::bytes`.
+pub(crate) fn scalar_from_state<C: Ciphersuite>(
+    r: &mut StateReader<'_>,
+) -> confium_tc::error::Result<C::Scalar> {
+    C::deserialize_scalar(r.bytes()?).ok_or_else(|| snapshot::invalid("scalar is not canonical"))
+}
@@ -0,0 +1,519 @@
+//! Distributed key generation, generic over the ciphersuite.
+//!
+//! The same two-round Pedersen / Feldman VSS DKG as FROST-P256:
+//!
+//! - **Round 1** — each party samples a degree-`T-1` polynomial `f_i`,
+//!   broadcasts its commitment list `C_{i,k} = a_{i,k}·G` and sends each
+//!   peer `j` the directed share `f_i(j)`.
+//! - **Round 2** — each party verifies every received share against its
+//!   sender's commitments, sums the valid ones into its signing share
+//!   `s_i`, sums the senders' `C_{i,0}` into the group public key and
+//!   computes every participant's verifying share `PK_j`.
+//!
+//! The output is a [`KeyPackage`] blob. As with the other FROST crates
+//! there is no complaint round and no proof of knowledge of `a_{i,0}`;
+//! directed shares rely on the framework's pairwise channel encryption
+//! for confidentiality.
+
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+use confium_tc::snapshot::{StateReader, StateWriter};
+use zeroize::Zeroizing;
+
+use crate::ciphersuite::{Ciphersuite, scalar_from_state};
+use crate::error::{
+    CODE_BELOW_THRESHOLD, CODE_MALFORMED_MESSAGE, CODE_ROSTER_CONFIG, CODE_ROUND_OVERFLOW,
+    CODE_SESSION_NOT_COMPLETE, FrostError, Result,
+};
+use crate::keys::KeyPackage;
+use crate::polynomial::{CommitmentList, Polynomial};
+
+/// Message type byte tags used inside payloads.
+const MSG_ROUND1_BROADCAST: u8 = 0x01;
+const MSG_ROUND1_DIRECTED: u8 = 0x02;
+
+// ---------------------------------------------------------------------------
+// Scheme
+// ---------------------------------------------------------------------------
+
+/// FROST distributed key generation for ciphersuite `C`, registered as
+/// [`Ciphersuite::DKG_SCHEME`].
+pub struct FrostDkg<C>(PhantomData<fn() -> C>);
+
+impl<C> FrostDkg<C> {
+    /// The scheme value [`register_ciphersuite!`](crate::register_ciphersuite)
+    /// submits to the registry.
+    pub const fn new() -> Self {
+        FrostDkg(PhantomData)
+    }
+}
+
+impl<C> Default for FrostDkg<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: Ciphersuite> confium_tc::registry::TcScheme for FrostDkg<C> {
+    fn name(&self) -> &'static str {
+        C::DKG_SCHEME
+    }
+
+    fn kind(&self) -> confium_tc::registry::TcSchemeKind {
+        confium_tc::registry::TcSchemeKind::Dkg
+    }
+
+    fn create_session(
+        &self,
+        params: &confium_tc::SessionParams,
+    ) -> confium_tc::error::Result<Box<dyn confium_tc::registry::SessionImpl>> {
+        DkgSession::<C>::new(params)
+            .map(|s| Box::new(s) as Box<dyn confium_tc::registry::SessionImpl>)
+            .map_err(FrostError::framework)
+    }
+
+    fn restore_session(
+        &self,
+        params: &confium_tc::SessionParams,
+        state: &[u8],
+    ) -> confium_tc::error::Result<Box<dyn confium_tc::registry::SessionImpl>> {
+        DkgSession::<C>::restore(params, state)
+            .map(|s| Box::new(s) as Box<dyn confium_tc::registry::SessionImpl>)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Session
+// ---------------------------------------------------------------------------
+
+/// Per-party DKG session state.
+struct DkgSession<C: Ciphersuite> {
+    party_id: String,
+    /// 1-indexed evaluation point, from the roster position.
+    party_index: u32,
+    threshold: u32,
+    /// All N party ids in roster order.
+    roster_ids: Vec<String>,
+    /// Our own VSS polynomial. Cleared after round 1.
+    poly: Option<Polynomial<C>>,
+    /// Our own commitment list, broadcast in round 1.
+    our_commitments: Vec<Vec<u8>>,
+    /// Commitment lists received from every peer (by party id).
+    peer_commitments: HashMap<String, Vec<Vec<u8>>>,
+    /// Our running share: `f_self(self)` until round 2 adds the peers'.
+    own_share: C::Scalar,
+    received_fragments: Vec<(String, C::Scalar)>,
+    /// Group public key and verifying shares, computed in round 2.
+    output: Option<(Vec<u8>, Vec<Vec<u8>>)>,
+    round_done: u8,
+}
+
+impl<C: Ciphersuite> DkgSession<C> {
+    fn new(params: &confium_tc::SessionParams) -> Result<Self> {
+        let threshold = params.threshold;
+        if threshold == 0 {
+            return Err(FrostError::RosterConfig {
+                reason: "threshold must be >= 1",
+                code: CODE_ROSTER_CONFIG,
+            });
+        }
+        let roster: Vec<String> = params
+            .parties
+            .parties()
+            .iter()
+            .map(|p| p.id.clone())
+            .collect();
+        if roster.is_empty() {
+            return Err(FrostError::RosterConfig {
+                reason: "roster must be non-empty",
+                code: CODE_ROSTER_CONFIG,
+            });
+        }
+        if threshold as usize > roster.len() {
+            return Err(FrostError::RosterConfig {
+                reason: "threshold exceeds party count",
+                code: CODE_ROSTER_CONFIG,
+            });
+        }
+        let this_idx = params.this_party_idx;
+        if this_idx >= roster.len() {
+            return Err(FrostError::RosterConfig {
+                reason: "this_party_idx out of range",
+                code: CODE_ROSTER_CONFIG,
+            });
+        }
+        let party_id = roster[this_idx].clone();
+        let party_index = (this_idx as u32) + 1;
+
+        let poly = Polynomial::<C>::random(threshold as usize);
+        let our_commitments = CommitmentList::commit(&poly).as_bytes().to_vec();
+        let own_share = poly.evaluate(party_index);
+
+        Ok(DkgSession {
+            party_id,
+            party_index,
+            threshold,
+            roster_ids: roster,
+            poly: Some(poly),
+            our_commitments,
+            peer_commitments: HashMap::new(),
+            own_share,
+            received_fragments: Vec::new(),
+            output: None,
+            round_done: 0,
+        })
+    }
+
+    /// Round 1 — broadcast our commitment list and direct shares to peers.
+    fn round1(&mut self) -> confium_tc::error::Result<confium_tc::registry::RoundResult> {
+        let poly = self.poly.take().ok_or_else(|| {
+            FrostError::RoundOverflow {
+                round: self.round_done,
+                code: CODE_ROUND_OVERFLOW,
+            }
+            .framework()
+        })?;
+        let mut outgoing = vec![confium_tc::Message::broadcast(
+            &self.party_id,
+            1,
+            encode_round1_broadcast::<C>(self.party_index, &self.our_commitments),
+        )];
+        for (pos, peer_id) in self.roster_ids.iter().enumerate() {
+            if peer_id == &self.party_id {
+                continue;
+            }
+            let frag = poly.evaluate(pos as u32 + 1);
+            outgoing.push(confium_tc::Message::directed(
+                &self.party_id,
+                peer_id,
+                1,
+                encode_round1_directed::<C>(self.party_index, &frag),
+            ));
+        }
+        Ok(confium_tc::registry::RoundResult::new(outgoing, false))
+    }
+
+    /// Round 2 — verify received fragments, aggregate the share, the
+    /// group public key and every party's verifying share.
+    fn round2(
+        &mut self,
+        incoming: &[confium_tc::Message],
+    ) -> confium_tc::error::Result<confium_tc::registry::RoundResult> {
+        for m in incoming {
+            if m.round != 1 || m.payload.is_empty() {
+                continue;
+            }
+            match m.payload[0] {
+                MSG_ROUND1_BROADCAST => {
+                    let (_idx, commits) =
+                        decode_round1_broadcast::<C>(&m.payload).map_err(FrostError::framework)?;
+                    self.peer_commitments
+                        .insert(m.from_party_id.clone(), commits);
+                }
+                MSG_ROUND1_DIRECTED => {
+                    if !m.is_for(&self.party_id) {
+                        continue;
+                    }
+                    let (_idx, frag) =
+                        decode_round1_directed::<C>(&m.payload).map_err(FrostError::framework)?;
+                    self.received_fragments
+                        .push((m.from_party_id.clone(), frag));
+                }
+                _ => {
+                    return Err(FrostError::MalformedMessage {
+                        reason: "unknown message tag in DKG round 1",
+                        code: CODE_MALFORMED_MESSAGE,
+                    }
+                    .framework());
+                }
+            }
+        }
+
+        // Senders whose fragment fails against their own commitments (or
+        // who sent a fragment without commitments) are excluded.
+        let byzantine: Vec<&String> = self
+            .received_fragments
+            .iter()
+            .filter(|(sender, frag)| {
+                self.peer_commitments.get(sender).is_none_or(|commits| {
+                    !CommitmentList::from_bytes(commits.clone())
+                        .verify_share::<C>(self.party_index, frag)
+                })
+            })
+            .map(|(sender, _)| sender)
+            .collect();
+
+        let mut share = self.own_share;
+        for (sender, frag) in &self.received_fragments {
+            if !byzantine.contains(&sender) {
+                share = share + *frag;
+            }
+        }
+
+        let mut contributions = vec![CommitmentList::from_bytes(self.our_commitments.clone())];
+        for (sender, commits) in &self.peer_commitments {
+            if !byzantine.contains(&sender) && commits.len() == self.threshold as usize {
+                contributions.push(CommitmentList::from_bytes(commits.clone()));
+            }
+        }
+        if (contributions.len() as u32) < self.threshold {
+            return Err(FrostError::BelowThreshold {
+                have: contributions.len() as u32,
+                need: self.threshold,
+                code: CODE_BELOW_THRESHOLD,
+            }
+            .framework());
+        }
+
+        let invalid = || {
+            FrostError::MalformedMessage {
+                reason: "commitment is not a valid group element",
+                code: CODE_MALFORMED_MESSAGE,
+            }
+            .framework()
+        };
+        let mut public_key = C::identity();
+        for cl in &contributions {
+            public_key =
+                public_key + C::deserialize_element(cl.public_key_bytes()).ok_or_else(invalid)?;
+        }
+        let mut verifying_shares = Vec::with_capacity(self.roster_ids.len());
+        for j in 1..=self.roster_ids.len() as u32 {
+            let mut vs = C::identity();
+            for cl in &contributions {
+                vs = vs + cl.evaluate::<C>(j).ok_or_else(invalid)?;
+            }
+            verifying_shares.push(C::serialize_element(&vs));
+        }
+
+        self.own_share = share;
+        self.output = Some((C::serialize_element(&public_key), verifying_shares));
+        Ok(confium_tc::registry::RoundResult::done())
+    }
+
+    /// Rebuild a session from [`DkgSession::save`] output.
+    fn restore(
+        params: &confium_tc::SessionParams,
+        state: &[u8],
+    ) -> confium_tc::error::Result<Self> {
+        let mut session = DkgSession::<C>::new(params).map_err(FrostError::framework)?;
+        let mut r = StateReader::new(state);
+        session.round_done = r.u8()?;
+        session.poly = if r.bool()? {
+            let coeff = (0..r.u32()?)
+                .map(|_| scalar_from_state::<C>(&mut r))
+                .collect::<confium_tc::error::Result<Vec<_>>>()?;
+            Some(Polynomial::from_coefficients(coeff))
+        } else {
+            None
+        };
+        session.our_commitments = read_elements(&mut r)?;
+        session.own_share = scalar_from_state::<C>(&mut r)?;
+        session.peer_commitments.clear();
+        for _ in 0..r.u32()? {
+            let sender = r.string()?;
+            session
+                .peer_commitments
+                .insert(sender, read_elements(&mut r)?);
+        }
+        for _ in 0..r.u32()? {
+            let sender = r.string()?;
+            session
+                .received_fragments
+                .push((sender, scalar_from_state::<C>(&mut r)?));
+        }
+        session.output = if r.bool()? {
+            Some((r.bytes()?.to_vec(), read_elements(&mut r)?))
+        } else {
+            None
+        };
+        r.finish()?;
+        Ok(session)
+    }
+
+    /// Serialise the round state. The polynomial is only present before
+    /// round 1 has dealt it out.
+    fn save(&self) -> Zeroizing<Vec<u8>> {
+        let mut w = StateWriter::new();
+        w.u8(self.round_done);
+        w.bool(self.poly.is_some());
+        if let Some(poly) = &self.poly {
+            w.u32(poly.coefficients().len() as u32);
+            for a in poly.coefficients() {
+                w.bytes(&C::serialize_scalar(a));
+            }
+        }
+        write_elements(&mut w, &self.our_commitments);
+        w.bytes(&C::serialize_scalar(&self.own_share));
+        w.u32(self.peer_commitments.len() as u32);
+        for (sender, commits) in &self.peer_commitments {
+            w.str(sender);
+            write_elements(&mut w, commits);
+        }
+        w.u32(self.received_fragments.len() as u32);
+        for (sender, frag) in &self.received_fragments {
+            w.str(sender);
+            w.bytes(&C::serialize_scalar(frag));
+        }
+        w.bool(self.output.is_some());
+        if let Some((public_key, verifying_shares)) = &self.output {
+            w.bytes(public_key);
+            write_elements(&mut w, verifying_shares);
+        }
+        w.finish()
+    }
+}
+
+fn write_elements(w: &mut StateWriter, elements: &[Vec<u8>]) {
+    w.u32(elements.len() as u32);
+    for e in elements {
+        w.bytes(e);
+    }
+}
+
+fn read_elements(r: &mut StateReader<'_>) -> confium_tc::error::Result<Vec<Vec<u8>>> {
+    (0..r.u32()?).map(|_| Ok(r.bytes()?.to_vec())).collect()
+}
+
+impl<C: Ciphersuite> confium_tc::registry::SessionImpl for DkgSession<C> {
+    fn round(
+        &mut self,
+        incoming: &[confium_tc::Message],
+    ) -> confium_tc::error::Result<confium_tc::registry::RoundResult> {
+        self.round_done = self.round_done.checked_add(1).ok_or_else(|| {
+            FrostError::RoundOverflow {
+                round: self.round_done,
+                code: CODE_ROUND_OVERFLOW,
+            }
+            .framework()
+        })?;
+        match self.round_done {
+            1 => self.round1(),
+            2 => self.round2(incoming),
+            other => Err(FrostError::RoundOverflow {
+                round: other,
+                code: CODE_ROUND_OVERFLOW,
+            }
+            .framework()),
+        }
+    }
+
+    fn result(&self) -> confium_tc::error::Result<Vec<u8>> {
+        let (public_key, verifying_shares) = self.output.clone().ok_or_else(|| {
+            FrostError::SessionNotComplete {
+                code: CODE_SESSION_NOT_COMPLETE,
+            }
+            .framework()
+        })?;
+        Ok(KeyPackage::<C> {
+            identifier: self.party_index,
+            public_key,
+            share: self.own_share,
+            verifying_shares,
+        }
+        .to_bytes())
+    }
+
+    fn destroy(&mut self) {
+        self.own_share = C::scalar_from_u32(0);
+        self.poly = None;
+        self.received_fragments.clear();
+    }
+
+    fn snapshot(&self) -> Option<Zeroizing<Vec<u8>>> {
+        Some(self.save())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Wire formats
+// ---------------------------------------------------------------------------
+
+/// Round-1 broadcast: `tag | sender_idx:u32 BE | n_commits:u32 BE | commits[Ne]…`
+fn encode_round1_broadcast<C: Ciphersuite>(idx: u32, commits: &[Vec<u8>]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(1 + 4 + 4 + commits.len() * C::ELEMENT_BYTES);
+    out.push(MSG_ROUND1_BROADCAST);
+    out.extend_from_slice(&idx.to_be_bytes());
+    out.extend_from_slice(&(commits.len() as u32).to_be_bytes());
+    for c in commits {
+        out.extend_from_slice(c);
+    }
+    out
+}
+
+fn decode_round1_broadcast<C: Ciphersuite>(p: &[u8]) -> Result<(u32, Vec<Vec<u8>>)> {
+    let malformed = |reason| FrostError::MalformedMessage {
+        reason,
+        code: CODE_MALFORMED_MESSAGE,
+    };
+    if p.len() < 1 + 4 + 4 || p[0] != MSG_ROUND1_BROADCAST {
+        return Err(malformed("bad round-1 broadcast header"));
+    }
+    let idx = u32::from_be_bytes([p[1], p[2], p[3], p[4]]);
+    let n = u32::from_be_bytes([p[5], p[6], p[7], p[8]]) as usize;
+    let body = &p[9..];
+    if body.len() != n.saturating_mul(C::ELEMENT_BYTES) {
+        return Err(malformed("round-1 broadcast length mismatch"));
+    }
+    let commits = body
+        .chunks_exact(C::ELEMENT_BYTES)
+        .map(<[u8]>::to_vec)
+        .collect();
+    Ok((idx, commits))
+}
+
+/// Round-1 directed share: `tag | sender_idx:u32 BE | share[Ns]`
+fn encode_round1_directed<C: Ciphersuite>(sender_idx: u32, frag: &C::Scalar) -> Vec<u8> {
+    let mut out = Vec::with_capacity(1 + 4 + C::SCALAR_BYTES);
+    out.push(MSG_ROUND1_DIRECTED);
+    out.extend_from_slice(&sender_idx.to_be_bytes());
+    out.extend_from_slice(&C::serialize_scalar(frag));
+    out
+}
+
+fn decode_round1_directed<C: Ciphersuite>(p: &[u8]) -> Result<(u32, C::Scalar)> {
+    let malformed = |reason| FrostError::MalformedMessage {
+        reason,
+        code: CODE_MALFORMED_MESSAGE,
+    };
+    if p.len() != 1 + 4 + C::SCALAR_BYTES || p[0] != MSG_ROUND1_DIRECTED {
+        return Err(malformed("bad round-1 directed share"));
+    }
+    let sender_idx = u32::from_be_bytes([p[1], p[2], p[3], p[4]]);
+    let frag = C::deserialize_scalar(&p[5..])
+        .ok_or_else(|| malformed("directed share is not a canonical scalar"))?;
+    Ok((sender_idx, frag))
+}
+
+#[cfg(all(test, feature = "ristretto255"))]
+mod tests {
+    use super::*;
+    use crate::ciphersuite::mul_base;
+    use crate::ristretto255::Ristretto255;
+
+    #[test]
+    fn round1_broadcast_round_trips() {
+        let commits: Vec<Vec<u8>> = (1..=3)
+            .map(|k| {
+                Ristretto255::serialize_element(&mul_base::<Ristretto255>(
+                    &Ristretto255::scalar_from_u32(k),
+                ))
+            })
+            .collect();
+        let payload = encode_round1_broadcast::<Ristretto255>(7, &commits);
+        let (idx, back) = decode_round1_broadcast::<Ristretto255>(&payload).unwrap();
+        assert_eq!(idx, 7);
+        assert_eq!(back, commits);
+        assert!(decode_round1_broadcast::<Ristretto255>(&payload[..payload.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn round1_directed_round_trips() {
+        let s = Ristretto255::random_scalar();
+        let payload = encode_round1_directed::<Ristretto255>(3, &s);
+        let (idx, back) = decode_round1_directed::<Ristretto255>(&payload).unwrap();
+        assert_eq!(idx, 3);
+        assert_eq!(back, s);
+    }
+}
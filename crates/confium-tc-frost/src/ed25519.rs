@@ -0,0 +1,153 @@
+//! FROST(Ed25519, SHA-512) — RFC 9591 §6.1.
+//!
+//! Scalars and elements use the RFC 8032 encodings: 32-byte
+//! little-endian scalars modulo the prime subgroup order `L`, and
+//! 32-byte compressed Edwards points. The curve has cofactor 8, so
+//! decoding rejects points outside the prime-order subgroup and
+//! verification is cofactored.
+//!
+//! H2 is the RFC 8032 Ed25519 challenge hash, with no context string,
+//! so aggregate signatures are ordinary Ed25519 signatures.
+//!
+//! | fn | definition |
+//! |----|------------|
+//! | H1 | `SHA-512(ctx ‖ "rho" ‖ m)` mod `L` |
+//! | H2 | `SHA-512(m)` mod `L` |
+//! | H3 | `SHA-512(ctx ‖ "nonce" ‖ m)` mod `L` |
+//! | H4 | `SHA-512(ctx ‖ "msg" ‖ m)` |
+//! | H5 | `SHA-512(ctx ‖ "com" ‖ m)` |
+
+use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsPoint};
+use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::Identity;
+use sha2::{Digest, Sha512};
+
+use crate::ciphersuite::Ciphersuite;
+
+/// The RFC 9591 `FROST-ED25519-SHA512-v1` ciphersuite.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ed25519;
+
+crate::register_ciphersuite!(Ed25519);
+
+fn hash(prefix: &[&[u8]], msg: &[&[u8]]) -> [u8; 64] {
+    let mut h = Sha512::new();
+    for p in prefix.iter().chain(msg) {
+        h.update(p);
+    }
+    h.finalize().into()
+}
+
+fn hash_to_scalar(tag: &[u8], msg: &[&[u8]]) -> Scalar {
+    Scalar::from_bytes_mod_order_wide(&hash(&[Ed25519::CONTEXT_STRING, tag], msg))
+}
+
+impl Ciphersuite for Ed25519 {
+    const SIGN_SCHEME: &'static str = "FROST-ed25519";
+    const DKG_SCHEME: &'static str = "FROST-ed25519-dkg";
+    const PREPROCESSED_SCHEME: &'static str = "FROST-ed25519-preprocessed";
+    const CONTEXT_STRING: &'static [u8] = b"FROST-ED25519-SHA512-v1";
+    const SCALAR_BYTES: usize = 32;
+    const ELEMENT_BYTES: usize = 32;
+
+    type Scalar = Scalar;
+    type Element = EdwardsPoint;
+
+    fn scalar_from_u32(x: u32) -> Scalar {
+        Scalar::from(x)
+    }
+
+    fn invert(s: &Scalar) -> Option<Scalar> {
+        (*s != Scalar::ZERO).then(|| s.invert())
+    }
+
+    fn random_scalar() -> Scalar {
+        let mut wide = [0u8; 64];
+        getrandom::fill(&mut wide).expect("OS RNG");
+        Scalar::from_bytes_mod_order_wide(&wide)
+    }
+
+    fn serialize_scalar(s: &Scalar) -> Vec<u8> {
+        s.to_bytes().to_vec()
+    }
+
+    fn deserialize_scalar(bytes: &[u8]) -> Option<Scalar> {
+        Option::from(Scalar::from_canonical_bytes(bytes.try_into().ok()?))
+    }
+
+    fn identity() -> EdwardsPoint {
+        EdwardsPoint::identity()
+    }
+
+    fn generator() -> EdwardsPoint {
+        ED25519_BASEPOINT_POINT
+    }
+
+    fn serialize_element(e: &EdwardsPoint) -> Vec<u8> {
+        e.compress().to_bytes().to_vec()
+    }
+
+    fn deserialize_element(bytes: &[u8]) -> Option<EdwardsPoint> {
+        let point = CompressedEdwardsY::from_slice(bytes).ok()?.decompress()?;
+        // Decompression accepts y >= p and a set sign bit for x = 0;
+        // round-tripping pins the encoding.
+        let canonical = point.compress().as_bytes() == bytes;
+        let valid = point != EdwardsPoint::identity() && point.is_torsion_free();
+        (canonical && valid).then_some(point)
+    }
+
+    fn h1(msg: &[&[u8]]) -> Scalar {
+        hash_to_scalar(b"rho", msg)
+    }
+
+    fn h2(msg: &[&[u8]]) -> Scalar {
+        Scalar::from_bytes_mod_order_wide(&hash(&[], msg))
+    }
+
+    fn h3(msg: &[&[u8]]) -> Scalar {
+        hash_to_scalar(b"nonce", msg)
+    }
+
+    fn h4(msg: &[u8]) -> Vec<u8> {
+        hash(&[Self::CONTEXT_STRING, b"msg"], &[msg]).to_vec()
+    }
+
+    fn h5(msg: &[u8]) -> Vec<u8> {
+        hash(&[Self::CONTEXT_STRING, b"com"], &[msg]).to_vec()
+    }
+
+    fn cofactor() -> Scalar {
+        Scalar::from(8u32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use curve25519_dalek::constants::EIGHT_TORSION;
+
+    #[test]
+    fn identity_and_small_order_points_are_rejected() {
+        assert!(
+            Ed25519::deserialize_element(EdwardsPoint::identity().compress().as_bytes()).is_none()
+        );
+        for torsion in &EIGHT_TORSION[1..] {
+            let mixed = ED25519_BASEPOINT_POINT + torsion;
+            assert!(Ed25519::deserialize_element(mixed.compress().as_bytes()).is_none());
+        }
+        let p = ED25519_BASEPOINT_POINT * Ed25519::scalar_from_u32(7);
+        assert_eq!(
+            Ed25519::deserialize_element(p.compress().as_bytes()),
+            Some(p)
+        );
+    }
+
+    #[test]
+    fn scalar_decoding_is_canonical() {
+        let s = Ed25519::random_scalar();
+        let bytes = Ed25519::serialize_scalar(&s);
+        assert_eq!(Ed25519::deserialize_scalar(&bytes), Some(s));
+        assert!(Ed25519::deserialize_scalar(&[0xff; 32]).is_none());
+    }
+}
@@ -0,0 +1,163 @@
+//! FROST(Ed448, SHAKE256) — RFC 9591 §6.3.
+//!
+//! Scalars and elements use the RFC 8032 encodings: 57-byte
+//! little-endian scalars modulo the prime subgroup order `L`, and
+//! 57-byte compressed Edwards points. The curve has cofactor 4, so
+//! decoding rejects points outside the prime-order subgroup and
+//! verification is cofactored.
+//!
+//! H2 is the RFC 8032 Ed448 challenge hash, with no context string, so
+//! aggregate signatures are ordinary Ed448 signatures.
+//!
+//! | fn | definition |
+//! |----|------------|
+//! | H1 | `SHAKE256(ctx ‖ "rho" ‖ m, 114)` mod `L` |
+//! | H2 | `SHAKE256("SigEd448" ‖ 0 ‖ 0 ‖ m, 114)` mod `L` |
+//! | H3 | `SHAKE256(ctx ‖ "nonce" ‖ m, 114)` mod `L` |
+//! | H4 | `SHAKE256(ctx ‖ "msg" ‖ m, 114)` |
+//! | H5 | `SHAKE256(ctx ‖ "com" ‖ m, 114)` |
+
+use ed448_goldilocks::{
+    AffinePoint, CompressedEdwardsY, EdwardsPoint, EdwardsScalar, EdwardsScalarBytes,
+    WideEdwardsScalarBytes,
+};
+use sha3::Shake256;
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+
+use crate::ciphersuite::Ciphersuite;
+
+/// Output length of every H1–H5 invocation.
+const HASH_BYTES: usize = 114;
+
+/// RFC 8032 `dom4(0, "")` prefix of the Ed448 challenge hash.
+const DOM4: &[u8] = b"SigEd448\x00\x00";
+
+/// The RFC 9591 `FROST-ED448-SHAKE256-v1` ciphersuite.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ed448;
+
+crate::register_ciphersuite!(Ed448);
+
+fn shake(prefix: &[&[u8]], msg: &[&[u8]]) -> [u8; HASH_BYTES] {
+    let mut h = Shake256::default();
+    for p in prefix.iter().chain(msg) {
+        h.update(p);
+    }
+    let mut out = [0u8; HASH_BYTES];
+    h.finalize_xof().read(&mut out);
+    out
+}
+
+fn scalar_from_wide(wide: [u8; HASH_BYTES]) -> EdwardsScalar {
+    EdwardsScalar::from_bytes_mod_order_wide(&WideEdwardsScalarBytes::from(wide))
+}
+
+fn hash_to_scalar(tag: &[u8], msg: &[&[u8]]) -> EdwardsScalar {
+    scalar_from_wide(shake(&[Ed448::CONTEXT_STRING, tag], msg))
+}
+
+impl Ciphersuite for Ed448 {
+    const SIGN_SCHEME: &'static str = "FROST-ed448";
+    const DKG_SCHEME: &'static str = "FROST-ed448-dkg";
+    const PREPROCESSED_SCHEME: &'static str = "FROST-ed448-preprocessed";
+    const CONTEXT_STRING: &'static [u8] = b"FROST-ED448-SHAKE256-v1";
+    const SCALAR_BYTES: usize = 57;
+    const ELEMENT_BYTES: usize = 57;
+
+    type Scalar = EdwardsScalar;
+    type Element = EdwardsPoint;
+
+    fn scalar_from_u32(x: u32) -> EdwardsScalar {
+        EdwardsScalar::from(x)
+    }
+
+    fn invert(s: &EdwardsScalar) -> Option<EdwardsScalar> {
+        (*s != EdwardsScalar::ZERO).then(|| s.invert())
+    }
+
+    fn random_scalar() -> EdwardsScalar {
+        let mut wide = [0u8; HASH_BYTES];
+        getrandom::fill(&mut wide).expect("OS RNG");
+        scalar_from_wide(wide)
+    }
+
+    fn serialize_scalar(s: &EdwardsScalar) -> Vec<u8> {
+        s.to_bytes_rfc_8032().to_vec()
+    }
+
+    fn deserialize_scalar(bytes: &[u8]) -> Option<EdwardsScalar> {
+        let bytes = EdwardsScalarBytes::try_from(bytes).ok()?;
+        Option::from(EdwardsScalar::from_canonical_bytes(&bytes))
+    }
+
+    fn identity() -> EdwardsPoint {
+        EdwardsPoint::IDENTITY
+    }
+
+    fn generator() -> EdwardsPoint {
+        EdwardsPoint::GENERATOR
+    }
+
+    fn serialize_element(e: &EdwardsPoint) -> Vec<u8> {
+        e.to_affine().compress().as_bytes().to_vec()
+    }
+
+    fn deserialize_element(bytes: &[u8]) -> Option<EdwardsPoint> {
+        let compressed = CompressedEdwardsY(bytes.try_into().ok()?);
+        let affine: AffinePoint = Option::from(compressed.decompress())?;
+        let point = affine.to_edwards();
+        // Decompression accepts a non-canonical x sign bit for x = 0;
+        // round-tripping pins the encoding.
+        let canonical = affine.compress().as_bytes() == bytes;
+        let valid = point != EdwardsPoint::IDENTITY && bool::from(point.is_torsion_free());
+        (canonical && valid).then_some(point)
+    }
+
+    fn h1(msg: &[&[u8]]) -> EdwardsScalar {
+        hash_to_scalar(b"rho", msg)
+    }
+
+    fn h2(msg: &[&[u8]]) -> EdwardsScalar {
+        scalar_from_wide(shake(&[DOM4], msg))
+    }
+
+    fn h3(msg: &[&[u8]]) -> EdwardsScalar {
+        hash_to_scalar(b"nonce", msg)
+    }
+
+    fn h4(msg: &[u8]) -> Vec<u8> {
+        shake(&[Self::CONTEXT_STRING, b"msg"], &[msg]).to_vec()
+    }
+
+    fn h5(msg: &[u8]) -> Vec<u8> {
+        shake(&[Self::CONTEXT_STRING, b"com"], &[msg]).to_vec()
+    }
+
+    fn cofactor() -> EdwardsScalar {
+        EdwardsScalar::from(4u32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn element_round_trip_and_identity_rejected() {
+        let p = EdwardsPoint::GENERATOR * Ed448::scalar_from_u32(7);
+        let bytes = Ed448::serialize_element(&p);
+        assert_eq!(bytes.len(), 57);
+        assert_eq!(Ed448::deserialize_element(&bytes), Some(p));
+        let identity = EdwardsPoint::IDENTITY.to_affine().compress();
+        assert!(Ed448::deserialize_element(identity.as_bytes()).is_none());
+    }
+
+    #[test]
+    fn scalar_decoding_is_canonical() {
+        let s = Ed448::random_scalar();
+        let bytes = Ed448::serialize_scalar(&s);
+        assert_eq!(bytes.len(), 57);
+        assert_eq!(Ed448::deserialize_scalar(&bytes), Some(s));
+        assert!(Ed448::deserialize_scalar(&[0xff; 57]).is_none());
+    }
+}
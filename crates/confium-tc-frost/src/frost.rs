@@ -0,0 +1,222 @@
+//! RFC 9591 signing operations, generic over the ciphersuite.
+//!
+//! These are the pure, transport-free steps of RFC 9591 §5: round-one
+//! `commit`, round-two `sign`, and the coordinator's `aggregate` plus
+//! `verify_signature_share`. The registered [`crate::signing`] sessions
+//! drive them over the framework's message rounds; the RFC test vectors
+//! drive them directly.
+//!
+//! For suites whose [`Ciphersuite::needs_negation`] can return `true`
+//! (BIP-340), the package negates the group key and/or the group
+//! commitment `R` so both have the encoding the verifier assumes, and
+//! every signer negates its share and/or nonces to match. For RFC 9591
+//! suites neither ever happens.
+
+use crate::ciphersuite::{Ciphersuite, conditional_negate, mul_base};
+use crate::error::{
+    CODE_BELOW_THRESHOLD, CODE_INVALID_COMMITMENT, CODE_MALFORMED_SHARE, FrostError, Result,
+};
+use crate::polynomial::lagrange_coefficient;
+use crate::transcript::{self, CommitmentEntry};
+
+/// A signer's secret nonce pair `(d_i, e_i)`. Single use.
+pub struct SigningNonces<C: Ciphersuite> {
+    /// Hiding nonce `d_i`.
+    pub hiding: C::Scalar,
+    /// Binding nonce `e_i`.
+    pub binding: C::Scalar,
+}
+
+impl<C: Ciphersuite> Clone for SigningNonces<C> {
+    fn clone(&self) -> Self {
+        SigningNonces {
+            hiding: self.hiding,
+            binding: self.binding,
+        }
+    }
+}
+
+impl<C: Ciphersuite> SigningNonces<C> {
+    /// The public commitment `(D_i, E_i) = (d_i·G, e_i·G)` for `identifier`.
+    pub fn commitment(&self, identifier: u32) -> CommitmentEntry {
+        (
+            identifier,
+            C::serialize_element(&mul_base::<C>(&self.hiding)),
+            C::serialize_element(&mul_base::<C>(&self.binding)),
+        )
+    }
+}
+
+/// RFC 9591 §5.1 `commit` with fresh OS randomness.
+pub fn commit<C: Ciphersuite>(secret: &C::Scalar) -> SigningNonces<C> {
+    let mut hiding_randomness = [0u8; 32];
+    let mut binding_randomness = [0u8; 32];
+    getrandom::fill(&mut hiding_randomness).expect("OS RNG");
+    getrandom::fill(&mut binding_randomness).expect("OS RNG");
+    commit_with_randomness(secret, &hiding_randomness, &binding_randomness)
+}
+
+/// RFC 9591 §5.1 `commit` with caller-supplied randomness — the form the
+/// RFC test vectors fix.
+pub fn commit_with_randomness<C: Ciphersuite>(
+    secret: &C::Scalar,
+    hiding_randomness: &[u8; 32],
+    binding_randomness: &[u8; 32],
+) -> SigningNonces<C> {
+    SigningNonces {
+        hiding: transcript::nonce_generate::<C>(hiding_randomness, secret),
+        binding: transcript::nonce_generate::<C>(binding_randomness, secret),
+    }
+}
+
+/// Everything derived from the signer set's commitments and the message:
+/// binding factors, the group commitment `R` and the challenge `c`.
+/// Every signer and the aggregator build the same package.
+pub struct SigningPackage<C: Ciphersuite> {
+    commitments: Vec<CommitmentEntry>,
+    binding_factors: Vec<(u32, C::Scalar)>,
+    participants: Vec<u32>,
+    /// The group key as signed for, after any negation.
+    group_public_key: C::Element,
+    negate_key: bool,
+    /// `R`, after any negation.
+    group_commitment: C::Element,
+    negate_nonces: bool,
+    challenge: C::Scalar,
+}
+
+impl<C: Ciphersuite> SigningPackage<C> {
+    /// Sort and validate `commitments`, then derive the binding factors
+    /// (§4.4), group commitment (§4.5) and challenge (§4.6).
+    pub fn new(
+        group_public_key: &[u8],
+        msg: &[u8],
+        mut commitments: Vec<CommitmentEntry>,
+        threshold: u32,
+    ) -> Result<Self> {
+        commitments.sort_by_key(|c| c.0);
+        if let Some(w) = commitments.windows(2).find(|w| w[0].0 == w[1].0) {
+            return Err(FrostError::InvalidCommitment {
+                party: format!("idx-{}", w[0].0),
+                reason: "duplicate participant identifier",
+                code: CODE_INVALID_COMMITMENT,
+            });
+        }
+        if (commitments.len() as u32) < threshold {
+            return Err(FrostError::BelowThreshold {
+                have: commitments.len() as u32,
+                need: threshold,
+                code: CODE_BELOW_THRESHOLD,
+            });
+        }
+        let pk = C::deserialize_element(group_public_key).ok_or(FrostError::MalformedShare {
+            reason: "group public key is not a valid group element",
+            code: CODE_MALFORMED_SHARE,
+        })?;
+        let negate_key = C::needs_negation(&pk);
+        let pk = conditional_negate::<C>(pk, negate_key);
+        let pk_bytes = C::serialize_element(&pk);
+
+        let binding_factors = transcript::binding_factors::<C>(&pk_bytes, msg, &commitments);
+        let mut group_commitment = C::identity();
+        for ((idx, d, e), (_, rho)) in commitments.iter().zip(&binding_factors) {
+            let invalid = || FrostError::InvalidCommitment {
+                party: format!("idx-{idx}"),
+                reason: "nonce commitment is not a valid group element",
+                code: CODE_INVALID_COMMITMENT,
+            };
+            let d = C::deserialize_element(d).ok_or_else(invalid)?;
+            let e = C::deserialize_element(e).ok_or_else(invalid)?;
+            group_commitment = group_commitment + d + e * *rho;
+        }
+        let negate_nonces = C::needs_negation(&group_commitment);
+        let group_commitment = conditional_negate::<C>(group_commitment, negate_nonces);
+        let challenge = C::challenge(&group_commitment, &pk, msg);
+        Ok(SigningPackage {
+            participants: commitments.iter().map(|c| c.0).collect(),
+            commitments,
+            binding_factors,
+            group_public_key: pk,
+            negate_key,
+            group_commitment,
+            negate_nonces,
+            challenge,
+        })
+    }
+
+    /// The sorted commitment list this package was built from.
+    pub fn commitments(&self) -> &[CommitmentEntry] {
+        &self.commitments
+    }
+
+    /// The signer identifiers, ascending.
+    pub fn participants(&self) -> &[u32] {
+        &self.participants
+    }
+
+    /// The binding factor `ρ_i` of participant `identifier`.
+    pub fn binding_factor(&self, identifier: u32) -> Option<C::Scalar> {
+        self.binding_factors
+            .iter()
+            .find(|(idx, _)| *idx == identifier)
+            .map(|(_, rho)| *rho)
+    }
+
+    /// The group commitment `R`.
+    pub fn group_commitment(&self) -> C::Element {
+        self.group_commitment
+    }
+
+    /// RFC 9591 §5.2 `sign`: `z_i = d_i + e_i·ρ_i + λ_i·s_i·c`.
+    pub fn sign_share(
+        &self,
+        identifier: u32,
+        secret: &C::Scalar,
+        nonces: &SigningNonces<C>,
+    ) -> C::Scalar {
+        let rho = self
+            .binding_factor(identifier)
+            .expect("signer is in its own signing package");
+        let lambda = lagrange_coefficient::<C>(identifier, &self.participants);
+        let nonce = nonces.hiding + nonces.binding * rho;
+        let nonce = if self.negate_nonces { -nonce } else { nonce };
+        let secret = if self.negate_key { -*secret } else { *secret };
+        nonce + lambda * secret * self.challenge
+    }
+
+    /// RFC 9591 §5.4 `verify_signature_share`:
+    /// `z_i·G == D_i + ρ_i·E_i + (c·λ_i)·PK_i`.
+    pub fn verify_share(
+        &self,
+        identifier: u32,
+        verifying_share: &C::Element,
+        share: &C::Scalar,
+    ) -> bool {
+        let Some(pos) = self.participants.iter().position(|i| *i == identifier) else {
+            return false;
+        };
+        let (_, d, e) = &self.commitments[pos];
+        let (Some(d), Some(e)) = (C::deserialize_element(d), C::deserialize_element(e)) else {
+            return false;
+        };
+        let rho = self.binding_factors[pos].1;
+        let lambda = lagrange_coefficient::<C>(identifier, &self.participants);
+        let commitment = conditional_negate::<C>(d + e * rho, self.negate_nonces);
+        let verifying_share = conditional_negate::<C>(*verifying_share, self.negate_key);
+        mul_base::<C>(share) == commitment + verifying_share * (self.challenge * lambda)
+    }
+
+    /// RFC 9591 §5.3 `aggregate`: `R ‖ Σ z_i`, in the suite's signature
+    /// encoding.
+    pub fn aggregate<'a>(&self, shares: impl IntoIterator<Item = &'a C::Scalar>) -> Vec<u8> {
+        let z = shares
+            .into_iter()
+            .fold(C::scalar_from_u32(0), |acc, z| acc + *z);
+        C::serialize_signature(&self.group_commitment, &z)
+    }
+
+    /// `true` iff `z·G == R + c·PK` for this package's `R`, `c` and key.
+    pub fn verify_aggregate(&self, z: &C::Scalar) -> bool {
+        mul_base::<C>(z) == self.group_commitment + self.group_public_key * self.challenge
+    }
+}
@@ -0,0 +1,38 @@
+//! In-process synchronous driver for FROST DKG and signing.
+//!
+//! Thin wrapper over [`confium_tc::inprocess`] that names the schemes of
+//! ciphersuite `C`.
+
+use confium_tc::Result;
+use confium_tc::inprocess as driver;
+
+use crate::ciphersuite::Ciphersuite;
+
+/// Run the DKG of ciphersuite `C` for `party_count` parties at
+/// threshold `threshold`. Returns one key-package blob per party.
+pub fn keygen<C: Ciphersuite>(threshold: u32, party_count: usize) -> Result<Vec<Vec<u8>>> {
+    driver::run_dkg(C::DKG_SCHEME, threshold, party_count)
+}
+
+/// Threshold-sign `message` with ciphersuite `C`. Returns a signature
+/// of [`Ciphersuite::SIGNATURE_BYTES`] bytes.
+pub fn sign<C: Ciphersuite>(
+    share_blobs: &[Vec<u8>],
+    threshold: u32,
+    message: &[u8],
+) -> Result<Vec<u8>> {
+    driver::run_sign(C::SIGN_SCHEME, share_blobs, threshold, message)
+}
+
+/// One-round sign: answer a coordinator's
+/// [`SigningPackage`](crate::preprocess::SigningPackage) with the
+/// [`PreprocessedShare`](crate::preprocess::PreprocessedShare) blobs of
+/// exactly its signers. Returns a signature of
+/// [`Ciphersuite::SIGNATURE_BYTES`] bytes.
+pub fn sign_preprocessed<C: Ciphersuite>(
+    share_blobs: &[Vec<u8>],
+    threshold: u32,
+    package: &[u8],
+) -> Result<Vec<u8>> {
+    driver::run_sign(C::PREPROCESSED_SCHEME, share_blobs, threshold, package)
+}
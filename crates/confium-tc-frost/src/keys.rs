@@ -0,0 +1,238 @@
+//! Key packages: one participant's DKG output.
+//!
+//! ## Blob shape
+//!
+//! ```text
+//!   pubkey_len:u32 BE | pubkey[Ne] | share_len:u32 BE | share[Ns]
+//!     | identifier:u32 BE | count:u32 BE | verifying_share[Ne] × count
+//! ```
+//!
+//! The same layout FROST-P256 uses, with the ciphersuite's `Ne` and
+//! `Ns`. `verifying_share[j-1]` is `PK_j = s_j·G` of identifier `j`; the
+//! identifier is carried so any subset of the DKG roster can sign under
+//! a fresh signing roster.
+
+use crate::ciphersuite::{Ciphersuite, conditional_negate, mul_base};
+use crate::error::{CODE_INVALID_TWEAK, CODE_MALFORMED_SHARE, FrostError, Result};
+
+/// One party's DKG output: what a signing session needs.
+pub struct KeyPackage<C: Ciphersuite> {
+    /// This party's participant identifier (1-based).
+    pub identifier: u32,
+    /// Group public key `PK`, serialised.
+    pub public_key: Vec<u8>,
+    /// This party's signing share `s_i`.
+    pub share: C::Scalar,
+    /// Every party's verifying share `PK_j = s_j·G`, indexed by `j - 1`.
+    pub verifying_shares: Vec<Vec<u8>>,
+}
+
+impl<C: Ciphersuite> Clone for KeyPackage<C> {
+    fn clone(&self) -> Self {
+        KeyPackage {
+            identifier: self.identifier,
+            public_key: self.public_key.clone(),
+            share: self.share,
+            verifying_shares: self.verifying_shares.clone(),
+        }
+    }
+}
+
+impl<C: Ciphersuite> KeyPackage<C> {
+    /// The group public key as a group element.
+    pub fn group_public_key(&self) -> C::Element {
+        C::deserialize_element(&self.public_key).expect("validated when parsed")
+    }
+
+    /// The verifying share of 1-based participant `identifier`.
+    pub fn verifying_share(&self, identifier: u32) -> Option<C::Element> {
+        let bytes = self
+            .verifying_shares
+            .get((identifier as usize).checked_sub(1)?)?;
+        C::deserialize_element(bytes)
+    }
+
+    /// Shift the whole sharing by the public scalar `t`: every share
+    /// becomes `s_i + t`, so the group secret becomes `s + t` (Lagrange
+    /// coefficients sum to one) and every public image moves by `t·G`.
+    /// With `negate_first`, the sharing is negated before shifting.
+    ///
+    /// This is how a Taproot tweak is applied without another DKG; see
+    /// `crate::secp256k1::tweak`.
+    pub fn tweaked(&self, negate_first: bool, t: &C::Scalar) -> Result<Self> {
+        let shift = |bytes: &[u8]| -> Result<Vec<u8>> {
+            let e = C::deserialize_element(bytes).ok_or(FrostError::MalformedShare {
+                reason: "key material is not a valid group element",
+                code: CODE_MALFORMED_SHARE,
+            })?;
+            let shifted = conditional_negate::<C>(e, negate_first) + mul_base::<C>(t);
+            if shifted == C::identity() {
+                return Err(FrostError::InvalidTweak {
+                    reason: "tweak cancels the key",
+                    code: CODE_INVALID_TWEAK,
+                });
+            }
+            Ok(C::serialize_element(&shifted))
+        };
+        let share = if negate_first {
+            -self.share
+        } else {
+            self.share
+        };
+        Ok(KeyPackage {
+            identifier: self.identifier,
+            public_key: shift(&self.public_key)?,
+            share: share + *t,
+            verifying_shares: self
+                .verifying_shares
+                .iter()
+                .map(|vs| shift(vs))
+                .collect::<Result<_>>()?,
+        })
+    }
+
+    /// Encode as the DKG output blob.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(
+            4 + C::ELEMENT_BYTES
+                + 4
+                + C::SCALAR_BYTES
+                + 4
+                + 4
+                + self.verifying_shares.len() * C::ELEMENT_BYTES,
+        );
+        out.extend_from_slice(&(C::ELEMENT_BYTES as u32).to_be_bytes());
+        out.extend_from_slice(&self.public_key);
+        out.extend_from_slice(&(C::SCALAR_BYTES as u32).to_be_bytes());
+        out.extend_from_slice(&C::serialize_scalar(&self.share));
+        out.extend_from_slice(&self.identifier.to_be_bytes());
+        out.extend_from_slice(&(self.verifying_shares.len() as u32).to_be_bytes());
+        for vs in &self.verifying_shares {
+            out.extend_from_slice(vs);
+        }
+        out
+    }
+
+    /// Parse a DKG output blob.
+    pub fn from_bytes(blob: &[u8]) -> Result<Self> {
+        let malformed = |reason| FrostError::MalformedShare {
+            reason,
+            code: CODE_MALFORMED_SHARE,
+        };
+        let mut rest = blob;
+        if take_u32(&mut rest)? != C::ELEMENT_BYTES {
+            return Err(malformed("unexpected pubkey length"));
+        }
+        let public_key = take_element::<C>(&mut rest)?;
+        if take_u32(&mut rest)? != C::SCALAR_BYTES {
+            return Err(malformed("unexpected share length"));
+        }
+        let share = C::deserialize_scalar(take(&mut rest, C::SCALAR_BYTES)?)
+            .ok_or(malformed("share is not a canonical scalar"))?;
+        let identifier = take_u32(&mut rest)? as u32;
+        let count = take_u32(&mut rest)?;
+        let verifying_shares = (0..count)
+            .map(|_| take_element::<C>(&mut rest))
+            .collect::<Result<Vec<_>>>()?;
+        if !rest.is_empty() {
+            return Err(malformed("trailing bytes after DKG output"));
+        }
+        if identifier == 0 || identifier as usize > verifying_shares.len() {
+            return Err(malformed("identifier has no verifying share"));
+        }
+        Ok(KeyPackage {
+            identifier,
+            public_key,
+            share,
+            verifying_shares,
+        })
+    }
+}
+
+fn take<'a>(rest: &mut &'a [u8], n: usize) -> Result<&'a [u8]> {
+    if rest.len() < n {
+        return Err(FrostError::MalformedShare {
+            reason: "DKG output truncated",
+            code: CODE_MALFORMED_SHARE,
+        });
+    }
+    let (head, tail) = rest.split_at(n);
+    *rest = tail;
+    Ok(head)
+}
+
+fn take_u32(rest: &mut &[u8]) -> Result<usize> {
+    let b = take(rest, 4)?;
+    Ok(u32::from_be_bytes([b[0], b[1], b[2], b[3]]) as usize)
+}
+
+fn take_element<C: Ciphersuite>(rest: &mut &[u8]) -> Result<Vec<u8>> {
+    let bytes = take(rest, C::ELEMENT_BYTES)?;
+    match C::deserialize_element(bytes) {
+        Some(_) => Ok(bytes.to_vec()),
+        None => Err(FrostError::MalformedShare {
+            reason: "key material is not a valid group element",
+            code: CODE_MALFORMED_SHARE,
+        }),
+    }
+}
+
+#[cfg(all(test, feature = "ristretto255"))]
+mod tests {
+    use super::*;
+    use crate::ristretto255::Ristretto255;
+
+    fn element(k: u32) -> Vec<u8> {
+        Ristretto255::serialize_element(&mul_base::<Ristretto255>(&Ristretto255::scalar_from_u32(
+            k,
+        )))
+    }
+
+    fn package() -> KeyPackage<Ristretto255> {
+        KeyPackage {
+            identifier: 2,
+            public_key: element(5),
+            share: Ristretto255::scalar_from_u32(2),
+            verifying_shares: vec![element(1), element(2)],
+        }
+    }
+
+    #[test]
+    fn key_package_round_trips() {
+        let pkg = package();
+        let back = KeyPackage::<Ristretto255>::from_bytes(&pkg.to_bytes()).unwrap();
+        assert_eq!(back.identifier, 2);
+        assert_eq!(back.public_key, pkg.public_key);
+        assert_eq!(back.share, pkg.share);
+        assert_eq!(back.verifying_shares, pkg.verifying_shares);
+        assert!(back.verifying_share(0).is_none());
+        assert!(back.verifying_share(3).is_none());
+    }
+
+    #[test]
+    fn from_bytes_rejects_truncated_and_trailing() {
+        let blob = package().to_bytes();
+        assert!(KeyPackage::<Ristretto255>::from_bytes(&blob[..blob.len() - 1]).is_err());
+        let mut long = blob.clone();
+        long.push(0);
+        assert!(KeyPackage::<Ristretto255>::from_bytes(&long).is_err());
+    }
+
+    #[test]
+    fn tweak_shifts_share_and_public_images_together() {
+        let t = Ristretto255::scalar_from_u32(10);
+        let tweaked = package().tweaked(true, &t).unwrap();
+        assert_eq!(tweaked.share, Ristretto255::scalar_from_u32(8));
+        assert_eq!(
+            tweaked.verifying_share(2),
+            Some(mul_base::<Ristretto255>(&tweaked.share))
+        );
+        assert_eq!(tweaked.public_key, element(5));
+        // -5·G + 5·G is the identity.
+        let cancel = Ristretto255::scalar_from_u32(5);
+        assert!(matches!(
+            package().tweaked(true, &cancel),
+            Err(FrostError::InvalidTweak { .. })
+        ));
+    }
+}
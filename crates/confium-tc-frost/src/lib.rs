@@ -0,0 +1,103 @@
+//! FROST threshold Schnorr signatures (RFC 9591), generic over the
+//! ciphersuite.
+//!
+//! One DKG, one signing session, one verifier and one key-package
+//! encoding, written once against the [`Ciphersuite`] trait and
+//! instantiated per suite. Each suite registers its schemes with the
+//! [`confium_tc`] link-time registry through [`register_ciphersuite!`]:
+//!
+//! | feature | ciphersuite | signing scheme | DKG scheme | signature |
+//! |---------|-------------|----------------|------------|-----------|
+//! | `ed25519` | `FROST-ED25519-SHA512-v1` | `FROST-ed25519` | `FROST-ed25519-dkg` | 64 bytes, RFC 8032 Ed25519 |
+//! | `ristretto255` | `FROST-RISTRETTO255-SHA512-v1` | `FROST-ristretto255` | `FROST-ristretto255-dkg` | 64 bytes |
+//! | `ed448` | `FROST-ED448-SHAKE256-v1` | `FROST-ed448` | `FROST-ed448-dkg` | 114 bytes, RFC 8032 Ed448 |
+//! | `p256` | `FROST-P256-SHA256-v1` | `FROST-P256` | `FROST-P256-dkg` | 65 bytes |
+//! | `secp256k1` | `FROST-secp256k1-SHA256-v1` | `FROST-secp256k1` | `FROST-secp256k1-dkg` | 65 bytes |
+//! | `secp256k1` | BIP-340 / Taproot | `FROST-secp256k1-TR` | `FROST-secp256k1-TR-dkg` | 64 bytes, BIP-340 |
+//!
+//! Every suite also registers `<signing scheme>-preprocessed`
+//! ([`Ciphersuite::PREPROCESSED_SCHEME`]): one-round signing from a
+//! nonce pool stored with the key share, see [`preprocess`] and
+//! [`online`].
+//!
+//! `tests/rfc9591.rs` checks all five RFC suites byte-for-byte against
+//! Appendix E; `tests/bip340.rs` checks the Taproot suite against the
+//! BIP-340 and BIP-341 vectors. The older `confium-tc-frost-ed25519` and
+//! `confium-tc-frost-p256` crates are now re-exports of the [`ed25519`]
+//! and [`p256`] suites.
+//!
+//! ## Adding a ciphersuite
+//!
+//! Implement [`Ciphersuite`] — the group, its encodings and H1–H5 — and
+//! invoke [`register_ciphersuite!`] with the type. Nothing else in the
+//! crate needs to change.
+//!
+//! # Example
+//!
+//! ```
+//! use confium_tc_frost::ristretto255::Ristretto255;
+//! use confium_tc_frost::{KeyPackage, inprocess, verify};
+//!
+//! let blobs = inprocess::keygen::<Ristretto255>(2, 3)?;
+//! let key = KeyPackage::<Ristretto255>::from_bytes(&blobs[0]).expect("key package");
+//! let signature = inprocess::sign::<Ristretto255>(&blobs[1..], 2, b"hello")?;
+//! verify::verify::<Ristretto255>(&key.public_key, b"hello", &signature).expect("valid");
+//! # Ok::<(), confium_tc::Error>(())
+//! ```
+
+#![forbid(unsafe_code)]
+#![allow(missing_docs)] // TODO: document before 1.0
+
+pub mod ciphersuite;
+pub mod dkg;
+pub mod error;
+pub mod frost;
+pub mod inprocess;
+pub mod keys;
+pub mod online;
+pub mod polynomial;
+pub mod preprocess;
+pub mod signing;
+pub mod transcript;
+pub mod verify;
+
+#[cfg(feature = "ed25519")]
+pub mod ed25519;
+#[cfg(feature = "ed448")]
+pub mod ed448;
+#[cfg(feature = "p256")]
+pub mod p256;
+#[cfg(feature = "ristretto255")]
+pub mod ristretto255;
+#[cfg(feature = "secp256k1")]
+pub mod secp256k1;
+#[cfg(any(feature = "p256", feature = "secp256k1"))]
+mod xmd;
+
+pub use ciphersuite::Ciphersuite;
+pub use dkg::FrostDkg;
+pub use keys::KeyPackage;
+pub use online::FrostPreprocessedSigning;
+pub use signing::FrostSigning;
+
+/// Register the DKG and signing schemes of a [`Ciphersuite`] with the
+/// `confium_tc` registry, under [`Ciphersuite::DKG_SCHEME`],
+/// [`Ciphersuite::SIGN_SCHEME`] and [`Ciphersuite::PREPROCESSED_SCHEME`].
+///
+/// ```ignore
+/// confium_tc_frost::register_ciphersuite!(MySuite);
+/// ```
+#[macro_export]
+macro_rules! register_ciphersuite {
+    ($suite:ty) => {
+        const _: () = {
+            static DKG: $crate::FrostDkg<$suite> = $crate::FrostDkg::new();
+            static SIGN: $crate::FrostSigning<$suite> = $crate::FrostSigning::new();
+            static ONLINE: $crate::FrostPreprocessedSigning<$suite> =
+                $crate::FrostPreprocessedSigning::new();
+            ::confium_tc::register_tc_scheme!(DKG);
+            ::confium_tc::register_tc_scheme!(SIGN);
+            ::confium_tc::register_tc_scheme!(ONLINE);
+        };
+    };
+}
@@ -0,0 +1,408 @@
+//! One-round threshold signing from a preprocessed nonce pool.
+//!
+//! Registered as [`Ciphersuite::PREPROCESSED_SCHEME`]. The local share
+//! is a [`PreprocessedShare`] — the DKG key package and its nonce pool,
+//! stored together — and the session message is the coordinator's
+//! [`SigningPackage`], which already fixes the signer set, the message
+//! and one pooled commitment per signer. The roster is exactly the
+//! package's signers.
+//!
+//! ## Rounds
+//!
+//! 1. **Sign** — take our pooled nonce for the package's commitment and
+//!    broadcast `z_i`. The framework records the nonce (`D ‖ E`, the
+//!    session's pending nonce) in its [`confium_tc::NonceLedger`]
+//!    before this round runs, so it answers at most one challenge.
+//! 2. **Aggregate** — local only: check every `z_j` against `PK_j`, sum
+//!    them, and emit the suite's signature encoding.
+//!
+//! The session works on its own copy of the pool. Storing the share
+//! again after a signature goes through [`PreprocessedShare::prune`]
+//! against the same ledger, which drops the nonce this session spent.
+//!
+//! A signer whose signature share is malformed, fails verification or
+//! never arrives is named through
+//! [`confium_tc::Error::MessageRejected`].
+
+use std::marker::PhantomData;
+
+use confium_tc::Message;
+use confium_tc::snapshot::{StateReader, StateWriter};
+use zeroize::Zeroizing;
+
+use crate::ciphersuite::{Ciphersuite, mul_base, scalar_from_state};
+use crate::error::{
+    CODE_AGG_VERIFY_FAILED, CODE_MALFORMED_MESSAGE, CODE_MALFORMED_SHARE, CODE_MISSING_COMMITMENT,
+    CODE_ROSTER_CONFIG, CODE_ROUND_OVERFLOW, CODE_SESSION_NOT_COMPLETE, CODE_UNKNOWN_NONCE,
+    FrostError,
+};
+use crate::frost::{self, SigningNonces};
+use crate::keys::KeyPackage;
+use crate::preprocess::{NonceCommitment, PreprocessedShare, SignatureShare, SigningPackage};
+
+// ---------------------------------------------------------------------------
+// Scheme
+// ---------------------------------------------------------------------------
+
+/// One-round FROST signing for ciphersuite `C`, registered as
+/// [`Ciphersuite::PREPROCESSED_SCHEME`].
+pub struct FrostPreprocessedSigning<C>(PhantomData<fn() -> C>);
+
+impl<C> FrostPreprocessedSigning<C> {
+    /// The scheme value [`register_ciphersuite!`](crate::register_ciphersuite)
+    /// submits to the registry.
+    pub const fn new() -> Self {
+        FrostPreprocessedSigning(PhantomData)
+    }
+}
+
+impl<C> Default for FrostPreprocessedSigning<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: Ciphersuite> confium_tc::registry::TcScheme for FrostPreprocessedSigning<C> {
+    fn name(&self) -> &'static str {
+        C::PREPROCESSED_SCHEME
+    }
+
+    fn kind(&self) -> confium_tc::registry::TcSchemeKind {
+        confium_tc::registry::TcSchemeKind::Signature
+    }
+
+    fn create_session(
+        &self,
+        params: &confium_tc::SessionParams,
+    ) -> confium_tc::error::Result<Box<dyn confium_tc::registry::SessionImpl>> {
+        OnlineSession::<C>::new(params)
+            .map(|s| Box::new(s) as Box<dyn confium_tc::registry::SessionImpl>)
+    }
+
+    fn restore_session(
+        &self,
+        params: &confium_tc::SessionParams,
+        state: &[u8],
+    ) -> confium_tc::error::Result<Box<dyn confium_tc::registry::SessionImpl>> {
+        OnlineSession::<C>::restore(params, state)
+            .map(|s| Box::new(s) as Box<dyn confium_tc::registry::SessionImpl>)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Session
+// ---------------------------------------------------------------------------
+
+struct OnlineSession<C: Ciphersuite> {
+    party_id: String,
+    /// Roster ids of this signing session.
+    roster_ids: Vec<String>,
+    key: KeyPackage<C>,
+    package: frost::SigningPackage<C>,
+    /// Our commitment in the package.
+    commitment: NonceCommitment,
+    /// Our pooled nonce pair, taken out of the pool at creation and
+    /// erased in round 1.
+    nonces: Option<SigningNonces<C>>,
+    /// Our signature share, computed in round 1.
+    our_share: Option<C::Scalar>,
+    /// Final signature, computed in round 2.
+    signature: Option<Vec<u8>>,
+    round_done: u8,
+}
+
+impl<C: Ciphersuite> OnlineSession<C> {
+    fn new(params: &confium_tc::SessionParams) -> confium_tc::error::Result<Self> {
+        Self::build(params, true)
+    }
+
+    /// Validate `params` and, if `with_nonce`, take our nonce out of the
+    /// share's pool. A session restored past round 1 has no use for it,
+    /// and the stored share may already have been pruned.
+    fn build(
+        params: &confium_tc::SessionParams,
+        with_nonce: bool,
+    ) -> confium_tc::error::Result<Self> {
+        let roster_ids: Vec<String> = params
+            .parties
+            .parties()
+            .iter()
+            .map(|p| p.id.clone())
+            .collect();
+        let this_idx = params.this_party_idx;
+        if this_idx >= roster_ids.len() {
+            return Err(FrostError::RosterConfig {
+                reason: "this_party_idx out of range",
+                code: CODE_ROSTER_CONFIG,
+            }
+            .framework());
+        }
+        let share = params.local_share.as_ref().ok_or_else(|| {
+            FrostError::MalformedShare {
+                reason: "signing session requires a local share",
+                code: CODE_MALFORMED_SHARE,
+            }
+            .framework()
+        })?;
+        let PreprocessedShare { key, mut pool } =
+            PreprocessedShare::<C>::from_bytes(share.bytes())?;
+        if key.verifying_share(key.identifier) != Some(mul_base::<C>(&key.share)) {
+            return Err(FrostError::MalformedShare {
+                reason: "signing share does not match its verifying share",
+                code: CODE_MALFORMED_SHARE,
+            }
+            .framework());
+        }
+        let signing_package = params
+            .message
+            .as_deref()
+            .ok_or(FrostError::MalformedMessage {
+                reason: "session message must be the coordinator's signing package",
+                code: CODE_MALFORMED_MESSAGE,
+            })
+            .and_then(SigningPackage::<C>::from_bytes)
+            .map_err(FrostError::framework)?;
+        if signing_package.commitments.len() != roster_ids.len() {
+            return Err(FrostError::RosterConfig {
+                reason: "roster must be exactly the signing package's signers",
+                code: CODE_ROSTER_CONFIG,
+            }
+            .framework());
+        }
+        if signing_package
+            .participants()
+            .iter()
+            .any(|idx| key.verifying_share(*idx).is_none())
+        {
+            return Err(FrostError::RosterConfig {
+                reason: "signing package names an unknown participant",
+                code: CODE_ROSTER_CONFIG,
+            }
+            .framework());
+        }
+        let commitment = signing_package
+            .commitments
+            .iter()
+            .find(|(idx, _)| *idx == key.identifier)
+            .map(|(_, c)| c.clone())
+            .ok_or_else(|| {
+                FrostError::MissingCommitment {
+                    party: roster_ids[this_idx].clone(),
+                    code: CODE_MISSING_COMMITMENT,
+                }
+                .framework()
+            })?;
+        let package = signing_package
+            .signing_package(&key.public_key)
+            .map_err(FrostError::framework)?;
+        let nonces = if with_nonce {
+            let nonces = pool.remove(&commitment).ok_or_else(|| {
+                FrostError::UnknownNonce {
+                    idx: key.identifier,
+                    code: CODE_UNKNOWN_NONCE,
+                }
+                .framework()
+            })?;
+            Some(nonces)
+        } else {
+            None
+        };
+        Ok(OnlineSession {
+            party_id: roster_ids[this_idx].clone(),
+            roster_ids,
+            key,
+            package,
+            commitment,
+            nonces,
+            our_share: None,
+            signature: None,
+            round_done: 0,
+        })
+    }
+
+    /// Round 1 — answer the package with our pooled nonce.
+    fn round1(&mut self) -> confium_tc::error::Result<confium_tc::registry::RoundResult> {
+        let nonces = self.nonces.take().ok_or_else(|| {
+            FrostError::RoundOverflow {
+                round: self.round_done,
+                code: CODE_ROUND_OVERFLOW,
+            }
+            .framework()
+        })?;
+        let z = self
+            .package
+            .sign_share(self.key.identifier, &self.key.share, &nonces);
+        self.our_share = Some(z);
+        let share = SignatureShare::<C> {
+            party_index: self.key.identifier,
+            z,
+        };
+        let msg = Message::broadcast(&self.party_id, 1, share.to_bytes());
+        Ok(confium_tc::registry::RoundResult::new(vec![msg], false))
+    }
+
+    /// Round 2 — verify every signature share, aggregate and self-check.
+    fn round2(
+        &mut self,
+        incoming: &[Message],
+    ) -> confium_tc::error::Result<confium_tc::registry::RoundResult> {
+        let our_share = self.our_share.ok_or_else(|| {
+            FrostError::RoundOverflow {
+                round: self.round_done,
+                code: CODE_ROUND_OVERFLOW,
+            }
+            .framework()
+        })?;
+        let mut shares: Vec<(String, u32, C::Scalar)> =
+            vec![(self.party_id.clone(), self.key.identifier, our_share)];
+        for m in incoming.iter().filter(|m| m.from_party_id != self.party_id) {
+            if !self.roster_ids.contains(&m.from_party_id) {
+                return Err(reject(m, "sender is not in the roster"));
+            }
+            if m.round != 1 || !m.is_broadcast() {
+                return Err(reject(m, "expected a round 1 broadcast"));
+            }
+            let share = SignatureShare::<C>::from_bytes(&m.payload)
+                .map_err(|_| reject(m, "malformed signature share"))?;
+            if shares
+                .iter()
+                .any(|(id, idx, _)| *id == m.from_party_id || *idx == share.party_index)
+            {
+                return Err(reject(m, "duplicate signature share"));
+            }
+            let Some(verifying_share) = self
+                .package
+                .binding_factor(share.party_index)
+                .and_then(|_| self.key.verifying_share(share.party_index))
+            else {
+                return Err(reject(m, "signer is not in the signing package"));
+            };
+            if !self
+                .package
+                .verify_share(share.party_index, &verifying_share, &share.z)
+            {
+                return Err(reject(m, "signature share failed verification"));
+            }
+            shares.push((m.from_party_id.clone(), share.party_index, share.z));
+        }
+        if let Some(party) = self
+            .roster_ids
+            .iter()
+            .find(|id| !shares.iter().any(|(s, _, _)| s == *id))
+        {
+            return Err(confium_tc::error::MessageRejectedSnafu {
+                party: party.clone(),
+                round: 1,
+                reason: "missing signature share".to_string(),
+            }
+            .build());
+        }
+
+        let z = shares
+            .iter()
+            .fold(C::scalar_from_u32(0), |acc, (_, _, z)| acc + *z);
+        if !self.package.verify_aggregate(&z) {
+            return Err(FrostError::AggregateVerificationFailed {
+                code: CODE_AGG_VERIFY_FAILED,
+            }
+            .framework());
+        }
+        self.signature = Some(C::serialize_signature(&self.package.group_commitment(), &z));
+        Ok(confium_tc::registry::RoundResult::done())
+    }
+
+    /// Rebuild a session from [`OnlineSession::save`] output.
+    fn restore(
+        params: &confium_tc::SessionParams,
+        state: &[u8],
+    ) -> confium_tc::error::Result<Self> {
+        let mut r = StateReader::new(state);
+        let round_done = r.u8()?;
+        let mut session = OnlineSession::<C>::build(params, round_done == 0)?;
+        session.round_done = round_done;
+        if r.bool()? {
+            session.our_share = Some(scalar_from_state::<C>(&mut r)?);
+        }
+        if r.bool()? {
+            session.signature = Some(r.bytes()?.to_vec());
+        }
+        r.finish()?;
+        Ok(session)
+    }
+
+    /// Serialise the round state. The nonce pair itself is never
+    /// written: before round 1 it is still in the stored share's pool,
+    /// after round 1 it is gone.
+    fn save(&self) -> Zeroizing<Vec<u8>> {
+        let mut w = StateWriter::new();
+        w.u8(self.round_done);
+        w.bool(self.our_share.is_some());
+        if let Some(z) = &self.our_share {
+            w.bytes(&C::serialize_scalar(z));
+        }
+        w.bool(self.signature.is_some());
+        if let Some(sig) = &self.signature {
+            w.bytes(sig);
+        }
+        w.finish()
+    }
+}
+
+/// Abort naming the sender of `msg`.
+fn reject(msg: &Message, reason: impl Into<String>) -> confium_tc::Error {
+    confium_tc::error::MessageRejectedSnafu {
+        party: msg.from_party_id.clone(),
+        round: msg.round,
+        reason: reason.into(),
+    }
+    .build()
+}
+
+impl<C: Ciphersuite> confium_tc::registry::SessionImpl for OnlineSession<C> {
+    fn round(
+        &mut self,
+        incoming: &[Message],
+    ) -> confium_tc::error::Result<confium_tc::registry::RoundResult> {
+        self.round_done = self.round_done.checked_add(1).ok_or_else(|| {
+            FrostError::RoundOverflow {
+                round: self.round_done,
+                code: CODE_ROUND_OVERFLOW,
+            }
+            .framework()
+        })?;
+        match self.round_done {
+            1 => self.round1(),
+            2 => self.round2(incoming),
+            other => Err(FrostError::RoundOverflow {
+                round: other,
+                code: CODE_ROUND_OVERFLOW,
+            }
+            .framework()),
+        }
+    }
+
+    fn result(&self) -> confium_tc::error::Result<Vec<u8>> {
+        self.signature.clone().ok_or_else(|| {
+            FrostError::SessionNotComplete {
+                code: CODE_SESSION_NOT_COMPLETE,
+            }
+            .framework()
+        })
+    }
+
+    fn destroy(&mut self) {
+        self.key.share = C::scalar_from_u32(0);
+        self.nonces = None;
+        self.our_share = None;
+        self.signature = None;
+    }
+
+    fn snapshot(&self) -> Option<Zeroizing<Vec<u8>>> {
+        Some(self.save())
+    }
+
+    /// Our package commitment `D ‖ E` until round 1 has used it.
+    fn pending_nonce(&self) -> Option<Vec<u8>> {
+        self.nonces.as_ref().map(|_| self.commitment.id())
+    }
+}
@@ -0,0 +1,149 @@
+//! FROST(P-256, SHA-256) — RFC 9591 §6.4.
+//!
+//! Scalars are 32-byte big-endian integers modulo the group order `n`;
+//! elements are 33-byte compressed SEC1 points. The identity has no
+//! compressed encoding and never decodes.
+//!
+//! | fn | definition |
+//! |----|------------|
+//! | H1 | `hash_to_field(m, DST = ctx ‖ "rho")` |
+//! | H2 | `hash_to_field(m, DST = ctx ‖ "chal")` |
+//! | H3 | `hash_to_field(m, DST = ctx ‖ "nonce")` |
+//! | H4 | `SHA-256(ctx ‖ "msg" ‖ m)` |
+//! | H5 | `SHA-256(ctx ‖ "com" ‖ m)` |
+//!
+//! `hash_to_field` is RFC 9380 §5.2 with `expand_message_xmd` over
+//! SHA-256 and `L = 48`, the same construction as secp256k1.
+
+use p256::elliptic_curve::PrimeField;
+use p256::elliptic_curve::sec1::{FromSec1Point, Sec1Point, ToSec1Point};
+use p256::{AffinePoint, FieldBytes, NistP256, ProjectivePoint, Scalar};
+
+use crate::ciphersuite::Ciphersuite;
+use crate::xmd::{HASH_TO_FIELD_LEN, expand_message_xmd, prefixed_digest};
+
+/// The RFC 9591 `FROST-P256-SHA256-v1` ciphersuite.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct P256;
+
+crate::register_ciphersuite!(P256);
+
+fn scalar_from_bytes(bytes: &[u8; 32]) -> Option<Scalar> {
+    Option::from(Scalar::from_repr(FieldBytes::from(*bytes)))
+}
+
+/// Reduce a big-endian byte string of 48 bytes modulo `n`, in 16-byte
+/// limbs so every limb is already a canonical scalar.
+fn scalar_from_wide(bytes: &[u8; HASH_TO_FIELD_LEN]) -> Scalar {
+    let mut shift = [0u8; 32];
+    shift[15] = 1;
+    let two_128 = scalar_from_bytes(&shift).expect("2^128 < n");
+    bytes.chunks(16).fold(Scalar::ZERO, |acc, limb| {
+        let mut padded = [0u8; 32];
+        padded[16..].copy_from_slice(limb);
+        acc * two_128 + scalar_from_bytes(&padded).expect("2^128 < n")
+    })
+}
+
+fn hash_to_field(tag: &[u8], msg: &[&[u8]]) -> Scalar {
+    scalar_from_wide(&expand_message_xmd(msg, &[P256::CONTEXT_STRING, tag]))
+}
+
+impl Ciphersuite for P256 {
+    const SIGN_SCHEME: &'static str = "FROST-P256";
+    const DKG_SCHEME: &'static str = "FROST-P256-dkg";
+    const PREPROCESSED_SCHEME: &'static str = "FROST-P256-preprocessed";
+    const CONTEXT_STRING: &'static [u8] = b"FROST-P256-SHA256-v1";
+    const SCALAR_BYTES: usize = 32;
+    const ELEMENT_BYTES: usize = 33;
+
+    type Scalar = Scalar;
+    type Element = ProjectivePoint;
+
+    fn scalar_from_u32(x: u32) -> Scalar {
+        Scalar::from(u64::from(x))
+    }
+
+    fn invert(s: &Scalar) -> Option<Scalar> {
+        Option::from(s.invert())
+    }
+
+    fn random_scalar() -> Scalar {
+        let mut wide = [0u8; HASH_TO_FIELD_LEN];
+        getrandom::fill(&mut wide).expect("OS RNG");
+        scalar_from_wide(&wide)
+    }
+
+    fn serialize_scalar(s: &Scalar) -> Vec<u8> {
+        s.to_repr().to_vec()
+    }
+
+    fn deserialize_scalar(bytes: &[u8]) -> Option<Scalar> {
+        scalar_from_bytes(bytes.try_into().ok()?)
+    }
+
+    fn identity() -> ProjectivePoint {
+        ProjectivePoint::IDENTITY
+    }
+
+    fn generator() -> ProjectivePoint {
+        ProjectivePoint::GENERATOR
+    }
+
+    fn serialize_element(e: &ProjectivePoint) -> Vec<u8> {
+        e.to_affine().to_sec1_point(true).as_bytes().to_vec()
+    }
+
+    fn deserialize_element(bytes: &[u8]) -> Option<ProjectivePoint> {
+        if bytes.len() != Self::ELEMENT_BYTES {
+            return None;
+        }
+        let encoded = Sec1Point::<NistP256>::from_bytes(bytes).ok()?;
+        let affine: AffinePoint = Option::from(AffinePoint::from_sec1_point(&encoded))?;
+        let point = ProjectivePoint::from(affine);
+        (point != ProjectivePoint::IDENTITY).then_some(point)
+    }
+
+    fn h1(msg: &[&[u8]]) -> Scalar {
+        hash_to_field(b"rho", msg)
+    }
+
+    fn h2(msg: &[&[u8]]) -> Scalar {
+        hash_to_field(b"chal", msg)
+    }
+
+    fn h3(msg: &[&[u8]]) -> Scalar {
+        hash_to_field(b"nonce", msg)
+    }
+
+    fn h4(msg: &[u8]) -> Vec<u8> {
+        prefixed_digest(Self::CONTEXT_STRING, b"msg", msg)
+    }
+
+    fn h5(msg: &[u8]) -> Vec<u8> {
+        prefixed_digest(Self::CONTEXT_STRING, b"com", msg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wide_reduction_of_all_ones() {
+        // (2^384 - 1) mod n, computed independently.
+        let want = hex::decode("431905529c0166ce652e96b7ccca0a99679b73e19ad16947f01cf013fc632550")
+            .unwrap();
+        assert_eq!(P256::serialize_scalar(&scalar_from_wide(&[0xff; 48])), want);
+    }
+
+    #[test]
+    fn identity_and_non_canonical_encodings_are_rejected() {
+        assert!(P256::deserialize_element(&[0u8; 33]).is_none());
+        assert!(P256::deserialize_element(&[2u8; 32]).is_none());
+        // n itself is not a canonical scalar.
+        let order = hex::decode("ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551")
+            .unwrap();
+        assert!(P256::deserialize_scalar(&order).is_none());
+    }
+}
@@ -0,0 +1,117 @@
+//! Polynomial helpers for verifiable secret sharing, generic over the
+//! ciphersuite.
+//!
+//! A degree-`T-1` polynomial `f` shares its constant term,
+//! [`CommitmentList`] is its Feldman commitment, and
+//! [`lagrange_coefficient`] weights any `T` evaluations back onto
+//! `f(0)`.
+
+use crate::ciphersuite::{Ciphersuite, mul_base};
+
+/// Lagrange coefficient `λ_i = ∏_{j ∈ S, j ≠ i} j / (j - i)` for party
+/// `i` relative to the participating set `S` (RFC 9591 §4.2
+/// `derive_interpolating_value`).
+///
+/// Panics if `participants` repeats an index (the denominator vanishes);
+/// callers dedupe the signer set first.
+pub fn lagrange_coefficient<C: Ciphersuite>(i: u32, participants: &[u32]) -> C::Scalar {
+    let mut num = C::scalar_from_u32(1);
+    let mut den = C::scalar_from_u32(1);
+    let i_scalar = C::scalar_from_u32(i);
+    for &j in participants {
+        if j == i {
+            continue;
+        }
+        let j_scalar = C::scalar_from_u32(j);
+        num = num * j_scalar;
+        den = den * (j_scalar - i_scalar);
+    }
+    num * C::invert(&den).expect("participant indices are distinct")
+}
+
+/// A degree-`(t-1)` polynomial over the scalar field used for VSS.
+/// Coefficients are little-endian: `f(X) = Σ coeff[k]·X^k`.
+pub struct Polynomial<C: Ciphersuite> {
+    coeff: Vec<C::Scalar>,
+}
+
+impl<C: Ciphersuite> Polynomial<C> {
+    /// Build a polynomial from its coefficient vector. `coeff[0]` is the
+    /// constant term.
+    pub fn from_coefficients(coeff: Vec<C::Scalar>) -> Self {
+        debug_assert!(!coeff.is_empty(), "polynomial must have at least one term");
+        Polynomial { coeff }
+    }
+
+    /// Sample a uniformly random polynomial with `terms` coefficients.
+    pub fn random(terms: usize) -> Self {
+        Polynomial::from_coefficients((0..terms).map(|_| C::random_scalar()).collect())
+    }
+
+    /// The constant term `f(0)` — the committed secret.
+    pub fn constant(&self) -> C::Scalar {
+        self.coeff[0]
+    }
+
+    /// Borrow the coefficient vector.
+    pub fn coefficients(&self) -> &[C::Scalar] {
+        &self.coeff
+    }
+
+    /// Evaluate `f(x)` at a party index using Horner's rule.
+    pub fn evaluate(&self, x: u32) -> C::Scalar {
+        let x_scalar = C::scalar_from_u32(x);
+        self.coeff
+            .iter()
+            .rev()
+            .fold(C::scalar_from_u32(0), |acc, c| acc * x_scalar + *c)
+    }
+}
+
+/// A Feldman commitment list `C_k = a_k·G` to a VSS polynomial, in wire
+/// form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitmentList {
+    commits: Vec<Vec<u8>>,
+}
+
+impl CommitmentList {
+    /// Build from already-encoded commitment bytes.
+    pub fn from_bytes(commits: Vec<Vec<u8>>) -> Self {
+        CommitmentList { commits }
+    }
+
+    /// Build by committing each coefficient of `poly` to the generator.
+    pub fn commit<C: Ciphersuite>(poly: &Polynomial<C>) -> Self {
+        let commits = poly
+            .coefficients()
+            .iter()
+            .map(|a| C::serialize_element(&mul_base::<C>(a)))
+            .collect();
+        CommitmentList { commits }
+    }
+
+    /// The commitment to the constant term, `C_0 = a_0·G`.
+    pub fn public_key_bytes(&self) -> &[u8] {
+        &self.commits[0]
+    }
+
+    /// The full commitment list.
+    pub fn as_bytes(&self) -> &[Vec<u8>] {
+        &self.commits
+    }
+
+    /// The public image `f(i)·G = Σ_k i^k·C_k` of participant `i`'s
+    /// share. `None` if any commitment fails to decode.
+    pub fn evaluate<C: Ciphersuite>(&self, participant: u32) -> Option<C::Element> {
+        let i_scalar = C::scalar_from_u32(participant);
+        self.commits.iter().rev().try_fold(C::identity(), |acc, c| {
+            Some(acc * i_scalar + C::deserialize_element(c)?)
+        })
+    }
+
+    /// Verify a share claimed to be `f(i)` for the committed polynomial.
+    pub fn verify_share<C: Ciphersuite>(&self, participant: u32, share: &C::Scalar) -> bool {
+        self.evaluate::<C>(participant) == Some(mul_base::<C>(share))
+    }
+}
@@ -0,0 +1,743 @@
+//! Nonce preprocessing — FROST with a one-round online phase.
+//!
+//! A [`crate::signing`] session generates its nonce pair in round 1, so
+//! every signature costs a full commitment round trip across the quorum
+//! before anyone can answer the challenge. RFC 9591 §5.1 allows that
+//! round to run ahead of time: each party generates a batch of nonce
+//! pairs, publishes the commitments `(D_i, E_i)`, and keeps the secret
+//! nonces until a coordinator assigns one commitment per signer to a
+//! message.
+//!
+//! ## Flow
+//!
+//! 1. **Offline.** Each party wraps its DKG [`KeyPackage`] in a
+//!    [`PreprocessedShare`], calls [`PreprocessedShare::generate`] and
+//!    sends the returned [`CommitmentBatch`] to the coordinator. The
+//!    secret nonces live in the share next to the key share:
+//!    [`PreprocessedShare::to_share`] is what goes back into the share
+//!    store.
+//! 2. **Coordinator.** Batches go into a [`CommitmentPool`]. For each
+//!    signature, [`CommitmentPool::select`] removes one commitment per
+//!    signer and returns the [`SigningPackage`].
+//! 3. **Online — the only round.** Each signer answers the package with
+//!    its [`SignatureShare`], either through a
+//!    [`Ciphersuite::PREPROCESSED_SCHEME`] session (see
+//!    [`crate::online`]), which broadcasts it and aggregates, or by
+//!    calling [`sign_share`] directly.
+//! 4. **Coordinator.** Outside a session, [`aggregate`] sums the shares
+//!    and verifies the result under the group key.
+//!
+//! ## Single use
+//!
+//! A pooled nonce is recorded as spent in the party's [`NonceLedger`]
+//! before it answers a challenge, under [`NonceCommitment::ledger_key`]:
+//! [`sign_share`] spends it itself, and the framework spends it for a
+//! [`Ciphersuite::PREPROCESSED_SCHEME`] session, whose pending nonce is
+//! the same `D ‖ E`. A share blob restored from before a crash, or a
+//! package replayed by the coordinator, finds its nonce already spent
+//! and fails with [`confium_tc::Error::NonceReuse`] instead of answering
+//! a second challenge. [`PreprocessedShare::prune`] drops such entries
+//! before the share is stored again.
+//!
+//! The coordinator's pool needs no such guarantee: handing the same
+//! commitment out twice can only make the second signature fail.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::marker::PhantomData;
+
+use confium_tc::share::Share;
+use confium_tc::snapshot::{self, NonceLedger, StateReader, StateWriter};
+use snafu::ensure;
+use zeroize::Zeroizing;
+
+use crate::ciphersuite::{Ciphersuite, scalar_from_state};
+use crate::error::{
+    CODE_AGG_VERIFY_FAILED, CODE_BELOW_THRESHOLD, CODE_INVALID_COMMITMENT, CODE_MALFORMED_MESSAGE,
+    CODE_MALFORMED_SHARE, CODE_MISSING_COMMITMENT, CODE_UNKNOWN_NONCE, FrostError, Result,
+};
+use crate::frost::{self, SigningNonces};
+use crate::keys::KeyPackage;
+
+/// Wire tags for the preprocessing messages.
+const MSG_COMMITMENT_BATCH: u8 = 0x21;
+const MSG_SIGNING_PACKAGE: u8 = 0x22;
+const MSG_SIGNATURE_SHARE: u8 = 0x23;
+
+/// Format version of [`NoncePool::to_bytes`].
+const POOL_VERSION: u8 = 1;
+
+/// Format version of [`PreprocessedShare::to_bytes`].
+const SHARE_VERSION: u8 = 1;
+
+/// A published nonce commitment `(D, E)`, serialised.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NonceCommitment {
+    /// Hiding commitment `D = d·G`.
+    pub d: Vec<u8>,
+    /// Binding commitment `E = e·G`.
+    pub e: Vec<u8>,
+}
+
+impl NonceCommitment {
+    fn of<C: Ciphersuite>(nonces: &SigningNonces<C>) -> Self {
+        let (_, d, e) = nonces.commitment(0);
+        NonceCommitment { d, e }
+    }
+
+    /// Key the nonce behind this commitment is spent under in a
+    /// [`NonceLedger`] — the key a [`Ciphersuite::PREPROCESSED_SCHEME`]
+    /// session's framework records it under.
+    pub fn ledger_key<C: Ciphersuite>(&self) -> [u8; 32] {
+        snapshot::nonce_key(C::PREPROCESSED_SCHEME, &self.id())
+    }
+
+    /// `D ‖ E`, the nonce's identifier in the ledger.
+    pub(crate) fn id(&self) -> Vec<u8> {
+        [&self.d[..], &self.e[..]].concat()
+    }
+
+    fn validate<C: Ciphersuite>(&self, idx: u32) -> Result<()> {
+        if C::deserialize_element(&self.d).is_none() || C::deserialize_element(&self.e).is_none() {
+            return Err(FrostError::InvalidCommitment {
+                party: format!("idx-{idx}"),
+                reason: "nonce commitment is not a valid group element",
+                code: CODE_INVALID_COMMITMENT,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// One party's published commitments, in generation order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CommitmentBatch<C> {
+    pub party_index: u32,
+    pub commitments: Vec<NonceCommitment>,
+    suite: PhantomData<fn() -> C>,
+}
+
+impl<C: Ciphersuite> CommitmentBatch<C> {
+    /// `tag | idx:u32 BE | count:u32 BE | (D[Ne] | E[Ne])*`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(9 + self.commitments.len() * 2 * C::ELEMENT_BYTES);
+        out.push(MSG_COMMITMENT_BATCH);
+        out.extend_from_slice(&self.party_index.to_be_bytes());
+        out.extend_from_slice(&(self.commitments.len() as u32).to_be_bytes());
+        for c in &self.commitments {
+            out.extend_from_slice(&c.d);
+            out.extend_from_slice(&c.e);
+        }
+        out
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut p = expect_tag(bytes, MSG_COMMITMENT_BATCH)?;
+        let party_index = u32::from_be_bytes(take_array(&mut p)?);
+        let count = u32::from_be_bytes(take_array(&mut p)?) as usize;
+        if p.len() != count * 2 * C::ELEMENT_BYTES {
+            return Err(malformed(
+                "commitment batch length does not match its count",
+            ));
+        }
+        let mut commitments = Vec::with_capacity(count);
+        for _ in 0..count {
+            commitments.push(NonceCommitment {
+                d: take(&mut p, C::ELEMENT_BYTES)?,
+                e: take(&mut p, C::ELEMENT_BYTES)?,
+            });
+        }
+        Ok(CommitmentBatch {
+            party_index,
+            commitments,
+            suite: PhantomData,
+        })
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Party side
+// ---------------------------------------------------------------------------
+
+/// A party's unused preprocessed nonces.
+///
+/// Holds secret nonce material: store [`NoncePool::to_bytes`] with the
+/// same protection as the key share.
+pub struct NoncePool<C: Ciphersuite> {
+    party_index: u32,
+    nonces: Vec<SigningNonces<C>>,
+}
+
+impl<C: Ciphersuite> NoncePool<C> {
+    /// An empty pool for the participant with identifier `party_index`
+    /// — its 1-based position in the DKG roster.
+    pub fn new(party_index: u32) -> Self {
+        NoncePool {
+            party_index,
+            nonces: Vec::new(),
+        }
+    }
+
+    pub fn party_index(&self) -> u32 {
+        self.party_index
+    }
+
+    pub fn len(&self) -> usize {
+        self.nonces.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nonces.is_empty()
+    }
+
+    /// Generate `count` fresh nonce pairs with RFC 9591 `commit`, hedged
+    /// with `key`'s signing share, and return their commitments for
+    /// publication.
+    pub fn generate(&mut self, key: &KeyPackage<C>, count: usize) -> Result<CommitmentBatch<C>> {
+        if key.identifier != self.party_index {
+            return Err(FrostError::MalformedShare {
+                reason: "key package belongs to another participant",
+                code: CODE_MALFORMED_SHARE,
+            });
+        }
+        let fresh: Vec<SigningNonces<C>> =
+            (0..count).map(|_| frost::commit::<C>(&key.share)).collect();
+        let commitments = fresh.iter().map(NonceCommitment::of).collect();
+        self.nonces.extend(fresh);
+        Ok(CommitmentBatch {
+            party_index: self.party_index,
+            commitments,
+            suite: PhantomData,
+        })
+    }
+
+    /// Drop every nonce `ledger` already lists as spent. Returns how
+    /// many were dropped.
+    pub fn prune(&mut self, ledger: &dyn NonceLedger) -> confium_tc::error::Result<usize> {
+        // Look everything up first: a ledger error leaves the pool as is.
+        let spent = self
+            .nonces
+            .iter()
+            .map(|n| ledger.is_spent(&NonceCommitment::of(n).ledger_key::<C>()))
+            .collect::<confium_tc::error::Result<Vec<bool>>>()?;
+        let mut spent_flags = spent.iter();
+        self.nonces
+            .retain(|_| !spent_flags.next().expect("one flag per nonce"));
+        Ok(spent.iter().filter(|s| **s).count())
+    }
+
+    /// Remove the nonce behind `commitment`, recording it as spent
+    /// first.
+    fn take(
+        &mut self,
+        commitment: &NonceCommitment,
+        ledger: &dyn NonceLedger,
+    ) -> confium_tc::error::Result<SigningNonces<C>> {
+        if !self
+            .nonces
+            .iter()
+            .any(|n| NonceCommitment::of(n) == *commitment)
+        {
+            return Err(FrostError::UnknownNonce {
+                idx: self.party_index,
+                code: CODE_UNKNOWN_NONCE,
+            }
+            .framework());
+        }
+        // Write-ahead: the spend is durable before the nonce answers a
+        // challenge. A failed spend leaves the pool untouched.
+        let fresh = ledger.spend(&commitment.ledger_key::<C>())?;
+        let nonces = self.remove(commitment).expect("checked above");
+        ensure!(
+            fresh,
+            confium_tc::error::NonceReuseSnafu {
+                scheme: C::PREPROCESSED_SCHEME,
+            }
+        );
+        Ok(nonces)
+    }
+
+    /// Remove the nonce behind `commitment` without touching a ledger.
+    /// Only for callers whose framework spends it first.
+    pub(crate) fn remove(&mut self, commitment: &NonceCommitment) -> Option<SigningNonces<C>> {
+        let pos = self
+            .nonces
+            .iter()
+            .position(|n| NonceCommitment::of(n) == *commitment)?;
+        Some(self.nonces.remove(pos))
+    }
+
+    /// Serialise the pool: `version | idx | count | (d | e)*`.
+    pub fn to_bytes(&self) -> Zeroizing<Vec<u8>> {
+        let mut w = StateWriter::new();
+        w.u8(POOL_VERSION);
+        w.u32(self.party_index);
+        w.u32(self.nonces.len() as u32);
+        for nonces in &self.nonces {
+            w.bytes(&C::serialize_scalar(&nonces.hiding));
+            w.bytes(&C::serialize_scalar(&nonces.binding));
+        }
+        w.finish()
+    }
+
+    /// Restore a pool from [`NoncePool::to_bytes`] output. Call
+    /// [`NoncePool::prune`] afterwards if the blob may predate spends.
+    pub fn from_bytes(bytes: &[u8]) -> confium_tc::error::Result<Self> {
+        let mut r = StateReader::new(bytes);
+        if r.u8()? != POOL_VERSION {
+            return Err(snapshot::invalid("unsupported nonce pool version"));
+        }
+        let party_index = r.u32()?;
+        let mut nonces = Vec::new();
+        for _ in 0..r.u32()? {
+            nonces.push(SigningNonces {
+                hiding: scalar_from_state::<C>(&mut r)?,
+                binding: scalar_from_state::<C>(&mut r)?,
+            });
+        }
+        r.finish()?;
+        Ok(NoncePool {
+            party_index,
+            nonces,
+        })
+    }
+}
+
+/// A key share and its nonce pool, stored together as one [`Share`] of
+/// scheme [`Ciphersuite::PREPROCESSED_SCHEME`].
+///
+/// Holds secret material twice over; the share store protects it like
+/// any other key share.
+pub struct PreprocessedShare<C: Ciphersuite> {
+    pub key: KeyPackage<C>,
+    pub pool: NoncePool<C>,
+}
+
+impl<C: Ciphersuite> PreprocessedShare<C> {
+    /// Wrap a DKG key package with an empty pool.
+    pub fn new(key: KeyPackage<C>) -> Self {
+        let pool = NoncePool::new(key.identifier);
+        PreprocessedShare { key, pool }
+    }
+
+    /// Add `count` nonces to the pool; see [`NoncePool::generate`].
+    pub fn generate(&mut self, count: usize) -> Result<CommitmentBatch<C>> {
+        self.pool.generate(&self.key, count)
+    }
+
+    /// Drop pooled nonces `ledger` lists as spent; see
+    /// [`NoncePool::prune`]. Run it on a stored share before storing it
+    /// again after a signature.
+    pub fn prune(&mut self, ledger: &dyn NonceLedger) -> confium_tc::error::Result<usize> {
+        self.pool.prune(ledger)
+    }
+
+    /// `version | key package | pool`, as length-prefixed fields.
+    pub fn to_bytes(&self) -> Zeroizing<Vec<u8>> {
+        let mut w = StateWriter::new();
+        w.u8(SHARE_VERSION);
+        w.bytes(&Zeroizing::new(self.key.to_bytes()));
+        w.bytes(&self.pool.to_bytes());
+        w.finish()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> confium_tc::error::Result<Self> {
+        let mut r = StateReader::new(bytes);
+        if r.u8()? != SHARE_VERSION {
+            return Err(snapshot::invalid("unsupported preprocessed share version"));
+        }
+        let key = KeyPackage::from_bytes(r.bytes()?).map_err(FrostError::framework)?;
+        let pool = NoncePool::from_bytes(r.bytes()?)?;
+        r.finish()?;
+        if pool.party_index != key.identifier {
+            return Err(snapshot::invalid(
+                "nonce pool belongs to another participant",
+            ));
+        }
+        Ok(PreprocessedShare { key, pool })
+    }
+
+    /// The share to store and hand to a
+    /// [`Ciphersuite::PREPROCESSED_SCHEME`] session.
+    pub fn to_share(&self) -> Share {
+        Share::new(C::PREPROCESSED_SCHEME, self.to_bytes().to_vec())
+    }
+}
+
+/// One signer's response `z_i` to a [`SigningPackage`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SignatureShare<C: Ciphersuite> {
+    pub party_index: u32,
+    pub z: C::Scalar,
+}
+
+impl<C: Ciphersuite> SignatureShare<C> {
+    /// `tag | idx:u32 BE | z[Ns]`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(5 + C::SCALAR_BYTES);
+        out.push(MSG_SIGNATURE_SHARE);
+        out.extend_from_slice(&self.party_index.to_be_bytes());
+        out.extend_from_slice(&C::serialize_scalar(&self.z));
+        out
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut p = expect_tag(bytes, MSG_SIGNATURE_SHARE)?;
+        let party_index = u32::from_be_bytes(take_array(&mut p)?);
+        let z = C::deserialize_scalar(p).ok_or(malformed("signature share is not a scalar"))?;
+        Ok(SignatureShare { party_index, z })
+    }
+}
+
+/// The online round: answer `package` with the pooled nonce it assigns
+/// to this party.
+///
+/// The nonce is spent in `ledger` before it is used; the pool no longer
+/// holds it afterwards, whether or not the caller delivers the share.
+pub fn sign_share<C: Ciphersuite>(
+    key: &KeyPackage<C>,
+    pool: &mut NoncePool<C>,
+    ledger: &dyn NonceLedger,
+    package: &SigningPackage<C>,
+) -> confium_tc::error::Result<SignatureShare<C>> {
+    let idx = pool.party_index();
+    if key.identifier != idx {
+        return Err(FrostError::MalformedShare {
+            reason: "key package belongs to another participant",
+            code: CODE_MALFORMED_SHARE,
+        }
+        .framework());
+    }
+    let signing = package
+        .signing_package(&key.public_key)
+        .map_err(FrostError::framework)?;
+    let own = package
+        .commitments
+        .iter()
+        .find(|(i, _)| *i == idx)
+        .map(|(_, c)| c)
+        .ok_or_else(|| {
+            FrostError::MissingCommitment {
+                party: format!("idx-{idx}"),
+                code: CODE_MISSING_COMMITMENT,
+            }
+            .framework()
+        })?;
+    let nonces = pool.take(own, ledger)?;
+    Ok(SignatureShare {
+        party_index: idx,
+        z: signing.sign_share(idx, &key.share, &nonces),
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Coordinator side
+// ---------------------------------------------------------------------------
+
+/// The message to sign and the commitment assigned to each signer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SigningPackage<C> {
+    pub message: Vec<u8>,
+    /// One commitment per signer, by ascending party index.
+    pub commitments: Vec<(u32, NonceCommitment)>,
+    suite: PhantomData<fn() -> C>,
+}
+
+impl<C: Ciphersuite> SigningPackage<C> {
+    pub fn participants(&self) -> Vec<u32> {
+        self.commitments.iter().map(|(idx, _)| *idx).collect()
+    }
+
+    /// Indices distinct, ascending and non-zero; every commitment a
+    /// group element.
+    fn validate(&self) -> Result<()> {
+        let mut prev = 0;
+        for (idx, c) in &self.commitments {
+            if *idx <= prev {
+                return Err(malformed(
+                    "signing package indices must be non-zero and ascending",
+                ));
+            }
+            c.validate::<C>(*idx)?;
+            prev = *idx;
+        }
+        Ok(())
+    }
+
+    /// The RFC 9591 signing package — binding factors, `R` and the
+    /// challenge — for this message and commitment list.
+    pub(crate) fn signing_package(&self, public_key: &[u8]) -> Result<frost::SigningPackage<C>> {
+        self.validate()?;
+        let entries = self
+            .commitments
+            .iter()
+            .map(|(idx, c)| (*idx, c.d.clone(), c.e.clone()))
+            .collect();
+        frost::SigningPackage::new(
+            public_key,
+            &self.message,
+            entries,
+            self.commitments.len() as u32,
+        )
+    }
+
+    /// `tag | msg_len:u32 BE | msg | count:u32 BE | (idx:u32 BE | D[Ne] | E[Ne])*`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(
+            9 + self.message.len() + self.commitments.len() * (4 + 2 * C::ELEMENT_BYTES),
+        );
+        out.push(MSG_SIGNING_PACKAGE);
+        out.extend_from_slice(&(self.message.len() as u32).to_be_bytes());
+        out.extend_from_slice(&self.message);
+        out.extend_from_slice(&(self.commitments.len() as u32).to_be_bytes());
+        for (idx, c) in &self.commitments {
+            out.extend_from_slice(&idx.to_be_bytes());
+            out.extend_from_slice(&c.d);
+            out.extend_from_slice(&c.e);
+        }
+        out
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let mut p = expect_tag(bytes, MSG_SIGNING_PACKAGE)?;
+        let msg_len = u32::from_be_bytes(take_array(&mut p)?) as usize;
+        let message = take(&mut p, msg_len)?;
+        let count = u32::from_be_bytes(take_array(&mut p)?) as usize;
+        if p.len() != count * (4 + 2 * C::ELEMENT_BYTES) {
+            return Err(malformed("signing package length does not match its count"));
+        }
+        let mut commitments = Vec::with_capacity(count);
+        for _ in 0..count {
+            let idx = u32::from_be_bytes(take_array(&mut p)?);
+            commitments.push((
+                idx,
+                NonceCommitment {
+                    d: take(&mut p, C::ELEMENT_BYTES)?,
+                    e: take(&mut p, C::ELEMENT_BYTES)?,
+                },
+            ));
+        }
+        Ok(SigningPackage {
+            message,
+            commitments,
+            suite: PhantomData,
+        })
+    }
+}
+
+/// The coordinator's queue of published commitments, per party.
+pub struct CommitmentPool<C> {
+    queues: BTreeMap<u32, VecDeque<NonceCommitment>>,
+    suite: PhantomData<fn() -> C>,
+}
+
+impl<C: Ciphersuite> Default for CommitmentPool<C> {
+    fn default() -> Self {
+        CommitmentPool {
+            queues: BTreeMap::new(),
+            suite: PhantomData,
+        }
+    }
+}
+
+impl<C: Ciphersuite> CommitmentPool<C> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue a party's published batch. Every commitment is checked
+    /// here, so a bad batch is refused whole and its publisher named.
+    pub fn publish(&mut self, batch: &CommitmentBatch<C>) -> Result<()> {
+        if batch.party_index == 0 {
+            return Err(malformed("commitment batch from party index 0"));
+        }
+        for c in &batch.commitments {
+            c.validate::<C>(batch.party_index)?;
+        }
+        self.queues
+            .entry(batch.party_index)
+            .or_default()
+            .extend(batch.commitments.iter().cloned());
+        Ok(())
+    }
+
+    /// Unassigned commitments held for `party_index`.
+    pub fn available(&self, party_index: u32) -> usize {
+        self.queues.get(&party_index).map_or(0, VecDeque::len)
+    }
+
+    /// Assign the oldest commitment of each of `signers` to `message`.
+    /// Consumes nothing unless every signer has one available.
+    pub fn select(
+        &mut self,
+        signers: &[u32],
+        threshold: u32,
+        message: &[u8],
+    ) -> Result<SigningPackage<C>> {
+        let mut signers = signers.to_vec();
+        signers.sort_unstable();
+        signers.dedup();
+        if (signers.len() as u32) < threshold {
+            return Err(FrostError::BelowThreshold {
+                have: signers.len() as u32,
+                need: threshold,
+                code: CODE_BELOW_THRESHOLD,
+            });
+        }
+        if let Some(idx) = signers.iter().find(|idx| self.available(**idx) == 0) {
+            return Err(FrostError::MissingCommitment {
+                party: format!("idx-{idx}"),
+                code: CODE_MISSING_COMMITMENT,
+            });
+        }
+        let commitments = signers
+            .iter()
+            .map(|idx| {
+                let queue = self.queues.get_mut(idx).expect("checked above");
+                (*idx, queue.pop_front().expect("checked above"))
+            })
+            .collect();
+        Ok(SigningPackage {
+            message: message.to_vec(),
+            commitments,
+            suite: PhantomData,
+        })
+    }
+}
+
+/// Sum the signers' responses to `package` into a signature in the
+/// suite's encoding and verify it under `public_key`.
+pub fn aggregate<C: Ciphersuite>(
+    package: &SigningPackage<C>,
+    shares: &[SignatureShare<C>],
+    public_key: &[u8],
+) -> confium_tc::error::Result<Vec<u8>> {
+    let signing = package
+        .signing_package(public_key)
+        .map_err(FrostError::framework)?;
+    let mut z = Vec::with_capacity(package.commitments.len());
+    for (idx, _) in &package.commitments {
+        let share = shares
+            .iter()
+            .find(|s| s.party_index == *idx)
+            .ok_or_else(|| {
+                FrostError::MissingCommitment {
+                    party: format!("idx-{idx}"),
+                    code: CODE_MISSING_COMMITMENT,
+                }
+                .framework()
+            })?;
+        z.push(share.z);
+    }
+    let sum = z.iter().fold(C::scalar_from_u32(0), |acc, z| acc + *z);
+    if !signing.verify_aggregate(&sum) {
+        return Err(FrostError::AggregateVerificationFailed {
+            code: CODE_AGG_VERIFY_FAILED,
+        }
+        .framework());
+    }
+    Ok(signing.aggregate(&z))
+}
+
+// ---------------------------------------------------------------------------
+// Wire helpers
+// ---------------------------------------------------------------------------
+
+fn malformed(reason: &'static str) -> FrostError {
+    FrostError::MalformedMessage {
+        reason,
+        code: CODE_MALFORMED_MESSAGE,
+    }
+}
+
+fn expect_tag(bytes: &[u8], tag: u8) -> Result<&[u8]> {
+    match bytes.split_first() {
+        Some((t, rest)) if *t == tag => Ok(rest),
+        _ => Err(malformed("unexpected preprocessing message tag")),
+    }
+}
+
+fn take(p: &mut &[u8], n: usize) -> Result<Vec<u8>> {
+    if p.len() < n {
+        return Err(malformed("preprocessing message truncated"));
+    }
+    let (head, rest) = p.split_at(n);
+    *p = rest;
+    Ok(head.to_vec())
+}
+
+fn take_array<const N: usize>(p: &mut &[u8]) -> Result<[u8; N]> {
+    Ok(take(p, N)?.try_into().expect("took N bytes"))
+}
+
+#[cfg(all(test, feature = "ristretto255"))]
+mod tests {
+    use super::*;
+    use crate::inprocess;
+    use crate::ristretto255::Ristretto255;
+
+    fn keys() -> Vec<KeyPackage<Ristretto255>> {
+        inprocess::keygen::<Ristretto255>(2, 3)
+            .expect("dkg")
+            .iter()
+            .map(|b| KeyPackage::from_bytes(b).expect("key package"))
+            .collect()
+    }
+
+    #[test]
+    fn commitment_batch_round_trips() {
+        let key = &keys()[1];
+        let batch = NoncePool::new(2).generate(key, 3).expect("generate");
+        let decoded = CommitmentBatch::from_bytes(&batch.to_bytes()).expect("decode");
+        assert_eq!(decoded, batch);
+        let bytes = batch.to_bytes();
+        assert!(CommitmentBatch::<Ristretto255>::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn signing_package_round_trips() {
+        let keys = keys();
+        let mut pool = CommitmentPool::new();
+        for i in [1, 3] {
+            let batch = NoncePool::new(i).generate(&keys[i as usize - 1], 1);
+            pool.publish(&batch.expect("generate")).unwrap();
+        }
+        let package = pool.select(&[3, 1], 2, b"msg").expect("select");
+        assert_eq!(package.participants(), vec![1, 3]);
+        let decoded = SigningPackage::from_bytes(&package.to_bytes()).expect("decode");
+        assert_eq!(decoded, package);
+    }
+
+    #[test]
+    fn nonce_pool_round_trips() {
+        let key = &keys()[0];
+        let mut pool = NoncePool::new(1);
+        let batch = pool.generate(key, 2).expect("generate");
+        let restored = NoncePool::<Ristretto255>::from_bytes(&pool.to_bytes()).expect("restore");
+        assert_eq!(restored.party_index(), 1);
+        let commitments: Vec<_> = restored.nonces.iter().map(NonceCommitment::of).collect();
+        assert_eq!(commitments, batch.commitments);
+    }
+
+    #[test]
+    fn preprocessed_share_round_trips() {
+        let mut share = PreprocessedShare::new(keys().remove(1));
+        let batch = share.generate(2).expect("generate");
+        let stored = share.to_share();
+        assert_eq!(stored.scheme(), Ristretto255::PREPROCESSED_SCHEME);
+        let restored = PreprocessedShare::<Ristretto255>::from_bytes(stored.bytes()).unwrap();
+        assert_eq!(restored.key.to_bytes(), share.key.to_bytes());
+        let commitments: Vec<_> = restored
+            .pool
+            .nonces
+            .iter()
+            .map(NonceCommitment::of)
+            .collect();
+        assert_eq!(commitments, batch.commitments);
+    }
+
+    #[test]
+    fn generating_for_another_participant_is_refused() {
+        let key = &keys()[0];
+        assert!(NoncePool::new(2).generate(key, 1).is_err());
+    }
+}
@@ -0,0 +1,135 @@
+//! FROST(ristretto255, SHA-512) — RFC 9591 §6.2.
+//!
+//! Scalars are 32-byte little-endian integers modulo
+//! `L = 2^252 + 27742317777372353535851937790883648493`; elements are
+//! 32-byte ristretto255 encodings. Ristretto is a prime-order group, so
+//! the cofactor is one and decoding only has to reject non-canonical
+//! encodings and the identity.
+//!
+//! | fn | definition |
+//! |----|------------|
+//! | H1 | `SHA-512(ctx ‖ "rho" ‖ m)` mod `L` |
+//! | H2 | `SHA-512(ctx ‖ "chal" ‖ m)` mod `L` |
+//! | H3 | `SHA-512(ctx ‖ "nonce" ‖ m)` mod `L` |
+//! | H4 | `SHA-512(ctx ‖ "msg" ‖ m)` |
+//! | H5 | `SHA-512(ctx ‖ "com" ‖ m)` |
+
+use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
+use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::Identity;
+use sha2::{Digest, Sha512};
+
+use crate::ciphersuite::Ciphersuite;
+
+/// The RFC 9591 `FROST-RISTRETTO255-SHA512-v1` ciphersuite.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ristretto255;
+
+crate::register_ciphersuite!(Ristretto255);
+
+fn hash(tag: &[u8], msg: &[&[u8]]) -> [u8; 64] {
+    let mut h = Sha512::new();
+    h.update(Ristretto255::CONTEXT_STRING);
+    h.update(tag);
+    for m in msg {
+        h.update(m);
+    }
+    h.finalize().into()
+}
+
+fn hash_to_scalar(tag: &[u8], msg: &[&[u8]]) -> Scalar {
+    Scalar::from_bytes_mod_order_wide(&hash(tag, msg))
+}
+
+impl Ciphersuite for Ristretto255 {
+    const SIGN_SCHEME: &'static str = "FROST-ristretto255";
+    const DKG_SCHEME: &'static str = "FROST-ristretto255-dkg";
+    const PREPROCESSED_SCHEME: &'static str = "FROST-ristretto255-preprocessed";
+    const CONTEXT_STRING: &'static [u8] = b"FROST-RISTRETTO255-SHA512-v1";
+    const SCALAR_BYTES: usize = 32;
+    const ELEMENT_BYTES: usize = 32;
+
+    type Scalar = Scalar;
+    type Element = RistrettoPoint;
+
+    fn scalar_from_u32(x: u32) -> Scalar {
+        Scalar::from(x)
+    }
+
+    fn invert(s: &Scalar) -> Option<Scalar> {
+        (*s != Scalar::ZERO).then(|| s.invert())
+    }
+
+    fn random_scalar() -> Scalar {
+        let mut wide = [0u8; 64];
+        getrandom::fill(&mut wide).expect("OS RNG");
+        Scalar::from_bytes_mod_order_wide(&wide)
+    }
+
+    fn serialize_scalar(s: &Scalar) -> Vec<u8> {
+        s.to_bytes().to_vec()
+    }
+
+    fn deserialize_scalar(bytes: &[u8]) -> Option<Scalar> {
+        Option::from(Scalar::from_canonical_bytes(bytes.try_into().ok()?))
+    }
+
+    fn identity() -> RistrettoPoint {
+        RistrettoPoint::identity()
+    }
+
+    fn generator() -> RistrettoPoint {
+        RISTRETTO_BASEPOINT_POINT
+    }
+
+    fn serialize_element(e: &RistrettoPoint) -> Vec<u8> {
+        e.compress().to_bytes().to_vec()
+    }
+
+    fn deserialize_element(bytes: &[u8]) -> Option<RistrettoPoint> {
+        let point = CompressedRistretto::from_slice(bytes).ok()?.decompress()?;
+        (point != RistrettoPoint::identity()).then_some(point)
+    }
+
+    fn h1(msg: &[&[u8]]) -> Scalar {
+        hash_to_scalar(b"rho", msg)
+    }
+
+    fn h2(msg: &[&[u8]]) -> Scalar {
+        hash_to_scalar(b"chal", msg)
+    }
+
+    fn h3(msg: &[&[u8]]) -> Scalar {
+        hash_to_scalar(b"nonce", msg)
+    }
+
+    fn h4(msg: &[u8]) -> Vec<u8> {
+        hash(b"msg", &[msg]).to_vec()
+    }
+
+    fn h5(msg: &[u8]) -> Vec<u8> {
+        hash(b"com", &[msg]).to_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identity_and_non_canonical_encodings_are_rejected() {
+        assert!(Ristretto255::deserialize_element(&[0u8; 32]).is_none());
+        // A field element >= p is never a canonical ristretto encoding.
+        assert!(Ristretto255::deserialize_element(&[0xff; 32]).is_none());
+        assert!(Ristretto255::deserialize_element(&[0u8; 31]).is_none());
+    }
+
+    #[test]
+    fn scalar_decoding_is_canonical() {
+        let s = Ristretto255::random_scalar();
+        let bytes = Ristretto255::serialize_scalar(&s);
+        assert_eq!(Ristretto255::deserialize_scalar(&bytes), Some(s));
+        assert!(Ristretto255::deserialize_scalar(&[0xff; 32]).is_none());
+    }
+}
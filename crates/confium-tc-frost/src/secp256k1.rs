@@ -0,0 +1,346 @@
+//! FROST over secp256k1: RFC 9591 §6.5 and the BIP-340 Taproot variant.
+//!
+//! Both suites share the group — 32-byte big-endian scalars modulo `n`
+//! and 33-byte compressed SEC1 points — and the RFC's H1, H3, H4 and H5:
+//!
+//! | fn | definition |
+//! |----|------------|
+//! | H1 | `hash_to_field(m, DST = ctx ‖ "rho")` |
+//! | H2 | `hash_to_field(m, DST = ctx ‖ "chal")` |
+//! | H3 | `hash_to_field(m, DST = ctx ‖ "nonce")` |
+//! | H4 | `SHA-256(ctx ‖ "msg" ‖ m)` |
+//! | H5 | `SHA-256(ctx ‖ "com" ‖ m)` |
+//!
+//! `hash_to_field` is RFC 9380 §5.2 with `expand_message_xmd` over
+//! SHA-256 and `L = 48`, exactly as for FROST-P256.
+//!
+//! [`Secp256k1`] is `FROST-secp256k1-SHA256-v1` and produces 65-byte
+//! `R ‖ z` signatures. [`Secp256k1Tr`] replaces the challenge with the
+//! BIP-340 `tagged_hash("BIP0340/challenge", x(R) ‖ x(PK) ‖ m)` and
+//! negates the group key and the group commitment whenever their `y` is
+//! odd, so the aggregate is a 64-byte BIP-340 signature `x(R) ‖ z`
+//! under the x-only group key. [`tweak`] applies a BIP-341 Taproot
+//! tweak to a key package without another DKG.
+
+use k256::elliptic_curve::PrimeField;
+use k256::elliptic_curve::point::AffineCoordinates;
+use k256::elliptic_curve::sec1::{FromSec1Point, Sec1Point, ToSec1Point};
+use k256::{AffinePoint, FieldBytes, ProjectivePoint, Scalar};
+use sha2::{Digest, Sha256};
+
+use crate::ciphersuite::{Ciphersuite, mul_base};
+use crate::error::{CODE_INVALID_TWEAK, CODE_MALFORMED_SHARE, FrostError, Result};
+use crate::keys::KeyPackage;
+use crate::xmd::{HASH_TO_FIELD_LEN, expand_message_xmd, prefixed_digest};
+
+/// The RFC 9591 `FROST-secp256k1-SHA256-v1` ciphersuite.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Secp256k1;
+
+/// FROST producing BIP-340 Schnorr signatures for Taproot key-path
+/// spends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Secp256k1Tr;
+
+crate::register_ciphersuite!(Secp256k1);
+crate::register_ciphersuite!(Secp256k1Tr);
+
+// ---------------------------------------------------------------------------
+// Group
+// ---------------------------------------------------------------------------
+
+fn scalar_from_bytes(bytes: &[u8; 32]) -> Option<Scalar> {
+    Option::from(Scalar::from_repr(FieldBytes::from(*bytes)))
+}
+
+/// Reduce a big-endian byte string of 48 bytes modulo `n`, in 16-byte
+/// limbs so every limb is already a canonical scalar.
+fn scalar_from_wide(bytes: &[u8; HASH_TO_FIELD_LEN]) -> Scalar {
+    let mut shift = [0u8; 32];
+    shift[15] = 1;
+    let two_128 = scalar_from_bytes(&shift).expect("2^128 < n");
+    bytes.chunks(16).fold(Scalar::ZERO, |acc, limb| {
+        let mut padded = [0u8; 32];
+        padded[16..].copy_from_slice(limb);
+        acc * two_128 + scalar_from_bytes(&padded).expect("2^128 < n")
+    })
+}
+
+/// A 32-byte big-endian integer reduced modulo `n`.
+fn scalar_reduce(bytes: &[u8; 32]) -> Scalar {
+    let mut wide = [0u8; HASH_TO_FIELD_LEN];
+    wide[16..].copy_from_slice(bytes);
+    scalar_from_wide(&wide)
+}
+
+fn random_scalar() -> Scalar {
+    let mut wide = [0u8; HASH_TO_FIELD_LEN];
+    getrandom::fill(&mut wide).expect("OS RNG");
+    scalar_from_wide(&wide)
+}
+
+fn serialize_element(e: &ProjectivePoint) -> Vec<u8> {
+    e.to_affine().to_sec1_point(true).as_bytes().to_vec()
+}
+
+fn deserialize_element(bytes: &[u8]) -> Option<ProjectivePoint> {
+    if bytes.len() != 33 {
+        return None;
+    }
+    let encoded = Sec1Point::<k256::Secp256k1>::from_bytes(bytes).ok()?;
+    let affine: AffinePoint = Option::from(AffinePoint::from_sec1_point(&encoded))?;
+    let point = ProjectivePoint::from(affine);
+    (point != ProjectivePoint::IDENTITY).then_some(point)
+}
+
+/// BIP-340 `lift_x`: the point with x-coordinate `x` and even `y`.
+fn lift_x(x: &[u8]) -> Option<ProjectivePoint> {
+    let mut compressed = [0u8; 33];
+    compressed[0] = 0x02;
+    compressed[1..].copy_from_slice(<&[u8; 32]>::try_from(x).ok()?);
+    deserialize_element(&compressed)
+}
+
+/// The 32-byte x-coordinate of `e`.
+fn x_only(e: &ProjectivePoint) -> [u8; 32] {
+    e.to_affine().x().into()
+}
+
+fn has_odd_y(e: &ProjectivePoint) -> bool {
+    bool::from(e.to_affine().y_is_odd())
+}
+
+// ---------------------------------------------------------------------------
+// Hashes
+// ---------------------------------------------------------------------------
+
+fn hash_to_field(ctx: &[u8], tag: &[u8], msg: &[&[u8]]) -> Scalar {
+    scalar_from_wide(&expand_message_xmd(msg, &[ctx, tag]))
+}
+
+/// BIP-340 `hash_tag(m) = SHA-256(SHA-256(tag) ‖ SHA-256(tag) ‖ m)`.
+pub fn tagged_hash(tag: &[u8], msg: &[&[u8]]) -> [u8; 32] {
+    let tag_hash = Sha256::digest(tag);
+    let mut h = Sha256::new();
+    h.update(tag_hash);
+    h.update(tag_hash);
+    for m in msg {
+        h.update(m);
+    }
+    h.finalize().into()
+}
+
+// ---------------------------------------------------------------------------
+// Ciphersuites
+// ---------------------------------------------------------------------------
+
+/// The group and hash functions both suites share; only the context
+/// string differs.
+macro_rules! secp256k1_group {
+    () => {
+        const SCALAR_BYTES: usize = 32;
+        const ELEMENT_BYTES: usize = 33;
+
+        type Scalar = Scalar;
+        type Element = ProjectivePoint;
+
+        fn scalar_from_u32(x: u32) -> Scalar {
+            Scalar::from(u64::from(x))
+        }
+
+        fn invert(s: &Scalar) -> Option<Scalar> {
+            Option::from(s.invert())
+        }
+
+        fn random_scalar() -> Scalar {
+            random_scalar()
+        }
+
+        fn serialize_scalar(s: &Scalar) -> Vec<u8> {
+            s.to_repr().to_vec()
+        }
+
+        fn deserialize_scalar(bytes: &[u8]) -> Option<Scalar> {
+            scalar_from_bytes(bytes.try_into().ok()?)
+        }
+
+        fn identity() -> ProjectivePoint {
+            ProjectivePoint::IDENTITY
+        }
+
+        fn generator() -> ProjectivePoint {
+            ProjectivePoint::GENERATOR
+        }
+
+        fn serialize_element(e: &ProjectivePoint) -> Vec<u8> {
+            serialize_element(e)
+        }
+
+        fn deserialize_element(bytes: &[u8]) -> Option<ProjectivePoint> {
+            deserialize_element(bytes)
+        }
+
+        fn h1(msg: &[&[u8]]) -> Scalar {
+            hash_to_field(Self::CONTEXT_STRING, b"rho", msg)
+        }
+
+        fn h2(msg: &[&[u8]]) -> Scalar {
+            hash_to_field(Self::CONTEXT_STRING, b"chal", msg)
+        }
+
+        fn h3(msg: &[&[u8]]) -> Scalar {
+            hash_to_field(Self::CONTEXT_STRING, b"nonce", msg)
+        }
+
+        fn h4(msg: &[u8]) -> Vec<u8> {
+            prefixed_digest(Self::CONTEXT_STRING, b"msg", msg)
+        }
+
+        fn h5(msg: &[u8]) -> Vec<u8> {
+            prefixed_digest(Self::CONTEXT_STRING, b"com", msg)
+        }
+    };
+}
+
+impl Ciphersuite for Secp256k1 {
+    const SIGN_SCHEME: &'static str = "FROST-secp256k1";
+    const DKG_SCHEME: &'static str = "FROST-secp256k1-dkg";
+    const PREPROCESSED_SCHEME: &'static str = "FROST-secp256k1-preprocessed";
+    const CONTEXT_STRING: &'static [u8] = b"FROST-secp256k1-SHA256-v1";
+
+    secp256k1_group!();
+}
+
+impl Ciphersuite for Secp256k1Tr {
+    const SIGN_SCHEME: &'static str = "FROST-secp256k1-TR";
+    const DKG_SCHEME: &'static str = "FROST-secp256k1-TR-dkg";
+    const PREPROCESSED_SCHEME: &'static str = "FROST-secp256k1-TR-preprocessed";
+    const CONTEXT_STRING: &'static [u8] = b"FROST-secp256k1-SHA256-TR-v1";
+    const SIGNATURE_BYTES: usize = 64;
+
+    secp256k1_group!();
+
+    fn challenge(
+        group_commitment: &ProjectivePoint,
+        group_public_key: &ProjectivePoint,
+        msg: &[u8],
+    ) -> Scalar {
+        scalar_reduce(&tagged_hash(
+            b"BIP0340/challenge",
+            &[&x_only(group_commitment), &x_only(group_public_key), msg],
+        ))
+    }
+
+    fn needs_negation(e: &ProjectivePoint) -> bool {
+        has_odd_y(e)
+    }
+
+    fn serialize_signature(r: &ProjectivePoint, z: &Scalar) -> Vec<u8> {
+        let mut out = x_only(r).to_vec();
+        out.extend_from_slice(&z.to_repr());
+        out
+    }
+
+    fn deserialize_signature(bytes: &[u8]) -> Option<(ProjectivePoint, Scalar)> {
+        if bytes.len() != Self::SIGNATURE_BYTES {
+            return None;
+        }
+        let (r, z) = bytes.split_at(32);
+        Some((lift_x(r)?, Self::deserialize_scalar(z)?))
+    }
+
+    /// A 32-byte BIP-340 x-only key, or a 33-byte compressed key.
+    fn deserialize_public_key(bytes: &[u8]) -> Option<ProjectivePoint> {
+        match bytes.len() {
+            32 => lift_x(bytes),
+            _ => deserialize_element(bytes),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Taproot
+// ---------------------------------------------------------------------------
+
+/// The BIP-341 `TapTweak` scalar for internal key `internal` (already
+/// with even `y`) and an optional script-tree Merkle root.
+fn tap_tweak(internal: &ProjectivePoint, merkle_root: Option<&[u8; 32]>) -> Result<Scalar> {
+    let x = x_only(internal);
+    let t = match merkle_root {
+        Some(root) => tagged_hash(b"TapTweak", &[&x, root]),
+        None => tagged_hash(b"TapTweak", &[&x]),
+    };
+    scalar_from_bytes(&t).ok_or(FrostError::InvalidTweak {
+        reason: "tweak hash is not below the group order",
+        code: CODE_INVALID_TWEAK,
+    })
+}
+
+/// Apply a BIP-341 Taproot tweak to a key package: the group key `P`
+/// (taken with even `y`) becomes the output key `Q = P + t·G` with
+/// `t = tagged_hash("TapTweak", x(P) ‖ merkle_root)`. Every holder
+/// tweaks its own package; the results sign under `Q` with the same
+/// `FROST-secp256k1-TR` session.
+pub fn tweak(
+    key_package: &KeyPackage<Secp256k1Tr>,
+    merkle_root: Option<&[u8; 32]>,
+) -> Result<KeyPackage<Secp256k1Tr>> {
+    let internal = key_package.group_public_key();
+    let negate = has_odd_y(&internal);
+    let internal = if negate { -internal } else { internal };
+    key_package.tweaked(negate, &tap_tweak(&internal, merkle_root)?)
+}
+
+/// The x-only Taproot output key for an internal key — 32-byte x-only
+/// or 33-byte compressed — and an optional Merkle root. Matches
+/// [`tweak`] applied to every share of that key.
+pub fn tweak_public_key(internal_key: &[u8], merkle_root: Option<&[u8; 32]>) -> Result<[u8; 32]> {
+    let internal =
+        Secp256k1Tr::deserialize_public_key(internal_key).ok_or(FrostError::MalformedShare {
+            reason: "internal key is not a valid curve point",
+            code: CODE_MALFORMED_SHARE,
+        })?;
+    let internal = if has_odd_y(&internal) {
+        -internal
+    } else {
+        internal
+    };
+    let output = internal + mul_base::<Secp256k1Tr>(&tap_tweak(&internal, merkle_root)?);
+    if output == ProjectivePoint::IDENTITY {
+        return Err(FrostError::InvalidTweak {
+            reason: "tweak cancels the key",
+            code: CODE_INVALID_TWEAK,
+        });
+    }
+    Ok(x_only(&output))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wide_reduction_of_small_value() {
+        let mut wide = [0u8; HASH_TO_FIELD_LEN];
+        wide[47] = 7;
+        assert_eq!(scalar_from_wide(&wide), Scalar::from(7u64));
+    }
+
+    #[test]
+    fn lift_x_has_even_y() {
+        let p = ProjectivePoint::GENERATOR * Scalar::from(3u64);
+        let lifted = lift_x(&x_only(&p)).unwrap();
+        assert!(!has_odd_y(&lifted));
+        assert!(lifted == p || lifted == -p);
+    }
+
+    #[test]
+    fn taproot_challenge_ignores_key_parity_prefix() {
+        let r = ProjectivePoint::GENERATOR * Scalar::from(5u64);
+        let pk = ProjectivePoint::GENERATOR * Scalar::from(9u64);
+        assert_eq!(
+            Secp256k1Tr::challenge(&r, &pk, b"m"),
+            Secp256k1Tr::challenge(&-r, &-pk, b"m")
+        );
+    }
+}
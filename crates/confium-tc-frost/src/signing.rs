@@ -0,0 +1,449 @@
+//! Threshold signing session, generic over the ciphersuite.
+//!
+//! Registered as [`Ciphersuite::SIGN_SCHEME`]. The local share is a
+//! [`KeyPackage`] blob from the matching [`crate::dkg`] scheme (or a
+//! tweaked one), so every signer holds its participant identifier, the
+//! group public key and all verifying shares alongside its own signing
+//! share. Any `T` key holders can sign together.
+//!
+//! ## Rounds
+//!
+//! The same three framework rounds as FROST-P256:
+//!
+//! 1. **Commit** — generate `(d_i, e_i)` with `nonce_generate` and
+//!    broadcast `(i, D_i, E_i)`. The signer set is whoever commits.
+//! 2. **Sign** — build the [`SigningPackage`] from the received
+//!    commitments and broadcast `z_i`. The nonce pair is erased here.
+//! 3. **Aggregate** — check every `z_j` with `verify_signature_share`
+//!    against `PK_j`, sum them, and emit the suite's signature encoding.
+//!
+//! A signer whose commitment or signature share is malformed or fails
+//! verification is named through [`confium_tc::Error::MessageRejected`].
+
+use std::marker::PhantomData;
+
+use confium_tc::Message;
+use confium_tc::snapshot::{self, StateReader, StateWriter};
+use zeroize::Zeroizing;
+
+use crate::ciphersuite::{Ciphersuite, mul_base, scalar_from_state};
+use crate::error::{
+    CODE_AGG_VERIFY_FAILED, CODE_MALFORMED_SHARE, CODE_ROSTER_CONFIG, CODE_ROUND_OVERFLOW,
+    CODE_SESSION_NOT_COMPLETE, FrostError, Result,
+};
+use crate::frost::{self, SigningNonces, SigningPackage};
+use crate::keys::KeyPackage;
+use crate::transcript::CommitmentEntry;
+
+/// Wire tags for the two message types.
+const MSG_ROUND1_COMMIT: u8 = 0x11;
+const MSG_ROUND2_SHARE: u8 = 0x12;
+
+// ---------------------------------------------------------------------------
+// Scheme
+// ---------------------------------------------------------------------------
+
+/// FROST threshold signing for ciphersuite `C`, registered as
+/// [`Ciphersuite::SIGN_SCHEME`].
+pub struct FrostSigning<C>(PhantomData<fn() -> C>);
+
+impl<C> FrostSigning<C> {
+    /// The scheme value [`register_ciphersuite!`](crate::register_ciphersuite)
+    /// submits to the registry.
+    pub const fn new() -> Self {
+        FrostSigning(PhantomData)
+    }
+}
+
+impl<C> Default for FrostSigning<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: Ciphersuite> confium_tc::registry::TcScheme for FrostSigning<C> {
+    fn name(&self) -> &'static str {
+        C::SIGN_SCHEME
+    }
+
+    fn kind(&self) -> confium_tc::registry::TcSchemeKind {
+        confium_tc::registry::TcSchemeKind::Signature
+    }
+
+    fn create_session(
+        &self,
+        params: &confium_tc::SessionParams,
+    ) -> confium_tc::error::Result<Box<dyn confium_tc::registry::SessionImpl>> {
+        FrostSession::<C>::new(params)
+            .map(|s| Box::new(s) as Box<dyn confium_tc::registry::SessionImpl>)
+            .map_err(FrostError::framework)
+    }
+
+    fn restore_session(
+        &self,
+        params: &confium_tc::SessionParams,
+        state: &[u8],
+    ) -> confium_tc::error::Result<Box<dyn confium_tc::registry::SessionImpl>> {
+        FrostSession::<C>::restore(params, state)
+            .map(|s| Box::new(s) as Box<dyn confium_tc::registry::SessionImpl>)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Session
+// ---------------------------------------------------------------------------
+
+struct FrostSession<C: Ciphersuite> {
+    party_id: String,
+    threshold: u32,
+    /// Roster ids of this signing session.
+    roster_ids: Vec<String>,
+    key: KeyPackage<C>,
+    message: Vec<u8>,
+    /// Our nonce pair, generated in round 1 and erased in round 2.
+    nonces: Option<SigningNonces<C>>,
+    /// Every signer's party id and commitment (ours included), fixed in
+    /// round 2.
+    signers: Vec<(String, CommitmentEntry)>,
+    /// Our signature share, computed in round 2.
+    our_share: Option<C::Scalar>,
+    /// Final signature, computed in round 3.
+    signature: Option<Vec<u8>>,
+    round_done: u8,
+}
+
+impl<C: Ciphersuite> FrostSession<C> {
+    fn new(params: &confium_tc::SessionParams) -> Result<Self> {
+        let roster_ids: Vec<String> = params
+            .parties
+            .parties()
+            .iter()
+            .map(|p| p.id.clone())
+            .collect();
+        let threshold = params.threshold;
+        if threshold == 0 || threshold as usize > roster_ids.len() {
+            return Err(FrostError::RosterConfig {
+                reason: "threshold must be between 1 and the party count",
+                code: CODE_ROSTER_CONFIG,
+            });
+        }
+        let this_idx = params.this_party_idx;
+        if this_idx >= roster_ids.len() {
+            return Err(FrostError::RosterConfig {
+                reason: "this_party_idx out of range",
+                code: CODE_ROSTER_CONFIG,
+            });
+        }
+        let share = params
+            .local_share
+            .as_ref()
+            .ok_or(FrostError::MalformedShare {
+                reason: "signing session requires a local share",
+                code: CODE_MALFORMED_SHARE,
+            })?;
+        let key = KeyPackage::<C>::from_bytes(share.bytes())?;
+        if key.verifying_share(key.identifier) != Some(mul_base::<C>(&key.share)) {
+            return Err(FrostError::MalformedShare {
+                reason: "signing share does not match its verifying share",
+                code: CODE_MALFORMED_SHARE,
+            });
+        }
+        Ok(FrostSession {
+            party_id: roster_ids[this_idx].clone(),
+            threshold,
+            roster_ids,
+            key,
+            message: params.message.clone().unwrap_or_default(),
+            nonces: None,
+            signers: Vec::new(),
+            our_share: None,
+            signature: None,
+            round_done: 0,
+        })
+    }
+
+    /// Check that `msg` is a round-`round` broadcast with `tag` from a
+    /// roster member, returning its body.
+    fn body<'m>(
+        &self,
+        msg: &'m Message,
+        round: u8,
+        tag: u8,
+    ) -> confium_tc::error::Result<&'m [u8]> {
+        if !self.roster_ids.contains(&msg.from_party_id) {
+            return Err(reject(msg, "sender is not in the roster"));
+        }
+        if msg.round != round || msg.payload.first() != Some(&tag) || !msg.is_broadcast() {
+            return Err(reject(msg, format!("expected a round {round} broadcast")));
+        }
+        Ok(&msg.payload[1..])
+    }
+
+    /// Round 1 — generate the nonce pair and broadcast its commitment.
+    fn round1(&mut self) -> confium_tc::error::Result<confium_tc::registry::RoundResult> {
+        let nonces = frost::commit::<C>(&self.key.share);
+        let (idx, d, e) = nonces.commitment(self.key.identifier);
+        self.nonces = Some(nonces);
+        let mut payload = Vec::with_capacity(1 + 4 + 2 * C::ELEMENT_BYTES);
+        payload.push(MSG_ROUND1_COMMIT);
+        payload.extend_from_slice(&idx.to_be_bytes());
+        payload.extend_from_slice(&d);
+        payload.extend_from_slice(&e);
+        let msg = Message::broadcast(&self.party_id, 1, payload);
+        Ok(confium_tc::registry::RoundResult::new(vec![msg], false))
+    }
+
+    /// Round 2 — fix the signer set, build the signing package and
+    /// broadcast our signature share.
+    fn round2(
+        &mut self,
+        incoming: &[Message],
+    ) -> confium_tc::error::Result<confium_tc::registry::RoundResult> {
+        let nonces = self.nonces.take().ok_or_else(|| {
+            FrostError::RoundOverflow {
+                round: self.round_done,
+                code: CODE_ROUND_OVERFLOW,
+            }
+            .framework()
+        })?;
+        let mut signers = vec![(
+            self.party_id.clone(),
+            nonces.commitment(self.key.identifier),
+        )];
+        for m in incoming.iter().filter(|m| m.from_party_id != self.party_id) {
+            let body = self.body(m, 1, MSG_ROUND1_COMMIT)?;
+            if body.len() != 4 + 2 * C::ELEMENT_BYTES {
+                return Err(reject(m, "malformed nonce commitment"));
+            }
+            let idx = u32::from_be_bytes([body[0], body[1], body[2], body[3]]);
+            if self.key.verifying_share(idx).is_none() {
+                return Err(reject(m, "unknown participant identifier"));
+            }
+            let (d, e) = body[4..].split_at(C::ELEMENT_BYTES);
+            if C::deserialize_element(d).is_none() || C::deserialize_element(e).is_none() {
+                return Err(reject(m, "nonce commitment is not a valid group element"));
+            }
+            if signers
+                .iter()
+                .any(|(id, c)| *id == m.from_party_id || c.0 == idx)
+            {
+                return Err(reject(m, "duplicate nonce commitment"));
+            }
+            signers.push((m.from_party_id.clone(), (idx, d.to_vec(), e.to_vec())));
+        }
+        signers.sort_by_key(|(_, c)| c.0);
+        self.signers = signers;
+        let package = self.package()?;
+        let z = package.sign_share(self.key.identifier, &self.key.share, &nonces);
+        self.our_share = Some(z);
+
+        let mut payload = Vec::with_capacity(1 + C::SCALAR_BYTES);
+        payload.push(MSG_ROUND2_SHARE);
+        payload.extend_from_slice(&C::serialize_scalar(&z));
+        let msg = Message::broadcast(&self.party_id, 2, payload);
+        Ok(confium_tc::registry::RoundResult::new(vec![msg], false))
+    }
+
+    /// Round 3 — verify every signature share, aggregate and self-check.
+    fn round3(
+        &mut self,
+        incoming: &[Message],
+    ) -> confium_tc::error::Result<confium_tc::registry::RoundResult> {
+        let our_share = self.our_share.ok_or_else(|| {
+            FrostError::RoundOverflow {
+                round: self.round_done,
+                code: CODE_ROUND_OVERFLOW,
+            }
+            .framework()
+        })?;
+        let package = self.package()?;
+        let mut shares: Vec<(u32, C::Scalar)> = vec![(self.key.identifier, our_share)];
+        for m in incoming.iter().filter(|m| m.from_party_id != self.party_id) {
+            let body = self.body(m, 2, MSG_ROUND2_SHARE)?;
+            let Some((_, (idx, _, _))) = self.signers.iter().find(|(id, _)| *id == m.from_party_id)
+            else {
+                return Err(reject(
+                    m,
+                    "signature share from a party that did not commit",
+                ));
+            };
+            if shares.iter().any(|(i, _)| i == idx) {
+                return Err(reject(m, "duplicate signature share"));
+            }
+            let z = C::deserialize_scalar(body)
+                .ok_or_else(|| reject(m, "signature share is not a canonical scalar"))?;
+            let verifying_share = self
+                .key
+                .verifying_share(*idx)
+                .expect("checked when the commitment arrived");
+            if !package.verify_share(*idx, &verifying_share, &z) {
+                return Err(reject(m, "signature share failed verification"));
+            }
+            shares.push((*idx, z));
+        }
+        if let Some((party, _)) = self
+            .signers
+            .iter()
+            .find(|(_, c)| !shares.iter().any(|(i, _)| *i == c.0))
+        {
+            return Err(confium_tc::error::MessageRejectedSnafu {
+                party: party.clone(),
+                round: 2,
+                reason: "missing signature share".to_string(),
+            }
+            .build());
+        }
+
+        let z = shares
+            .iter()
+            .fold(C::scalar_from_u32(0), |acc, (_, z)| acc + *z);
+        if !package.verify_aggregate(&z) {
+            return Err(FrostError::AggregateVerificationFailed {
+                code: CODE_AGG_VERIFY_FAILED,
+            }
+            .framework());
+        }
+        self.signature = Some(C::serialize_signature(&package.group_commitment(), &z));
+        Ok(confium_tc::registry::RoundResult::done())
+    }
+
+    fn package(&self) -> confium_tc::error::Result<SigningPackage<C>> {
+        let commitments = self.signers.iter().map(|(_, c)| c.clone()).collect();
+        SigningPackage::new(
+            &self.key.public_key,
+            &self.message,
+            commitments,
+            self.threshold,
+        )
+        .map_err(FrostError::framework)
+    }
+
+    /// Rebuild a session from [`FrostSession::save`] output.
+    fn restore(
+        params: &confium_tc::SessionParams,
+        state: &[u8],
+    ) -> confium_tc::error::Result<Self> {
+        let mut session = FrostSession::<C>::new(params).map_err(FrostError::framework)?;
+        let mut r = StateReader::new(state);
+        session.round_done = r.u8()?;
+        if r.bool()? {
+            session.nonces = Some(SigningNonces {
+                hiding: scalar_from_state::<C>(&mut r)?,
+                binding: scalar_from_state::<C>(&mut r)?,
+            });
+        }
+        for _ in 0..r.u32()? {
+            let party = r.string()?;
+            let idx = r.u32()?;
+            let d = r.bytes()?.to_vec();
+            let e = r.bytes()?.to_vec();
+            session.signers.push((party, (idx, d, e)));
+        }
+        if r.bool()? {
+            session.our_share = Some(scalar_from_state::<C>(&mut r)?);
+        }
+        if r.bool()? {
+            session.signature = Some(r.bytes()?.to_vec());
+        }
+        r.finish()?;
+        if session.signers.iter().any(|(_, (idx, d, e))| {
+            session.key.verifying_share(*idx).is_none()
+                || C::deserialize_element(d).is_none()
+                || C::deserialize_element(e).is_none()
+        }) {
+            return Err(snapshot::invalid("signer commitment is invalid"));
+        }
+        Ok(session)
+    }
+
+    /// Serialise the round state. The nonce pair is only present between
+    /// rounds 1 and 2.
+    fn save(&self) -> Zeroizing<Vec<u8>> {
+        let mut w = StateWriter::new();
+        w.u8(self.round_done);
+        w.bool(self.nonces.is_some());
+        if let Some(nonces) = &self.nonces {
+            w.bytes(&C::serialize_scalar(&nonces.hiding));
+            w.bytes(&C::serialize_scalar(&nonces.binding));
+        }
+        w.u32(self.signers.len() as u32);
+        for (party, (idx, d, e)) in &self.signers {
+            w.str(party);
+            w.u32(*idx);
+            w.bytes(d);
+            w.bytes(e);
+        }
+        w.bool(self.our_share.is_some());
+        if let Some(z) = &self.our_share {
+            w.bytes(&C::serialize_scalar(z));
+        }
+        w.bool(self.signature.is_some());
+        if let Some(sig) = &self.signature {
+            w.bytes(sig);
+        }
+        w.finish()
+    }
+}
+
+/// Abort naming the sender of `msg`.
+fn reject(msg: &Message, reason: impl Into<String>) -> confium_tc::Error {
+    confium_tc::error::MessageRejectedSnafu {
+        party: msg.from_party_id.clone(),
+        round: msg.round,
+        reason: reason.into(),
+    }
+    .build()
+}
+
+impl<C: Ciphersuite> confium_tc::registry::SessionImpl for FrostSession<C> {
+    fn round(
+        &mut self,
+        incoming: &[Message],
+    ) -> confium_tc::error::Result<confium_tc::registry::RoundResult> {
+        self.round_done = self.round_done.checked_add(1).ok_or_else(|| {
+            FrostError::RoundOverflow {
+                round: self.round_done,
+                code: CODE_ROUND_OVERFLOW,
+            }
+            .framework()
+        })?;
+        match self.round_done {
+            1 => self.round1(),
+            2 => self.round2(incoming),
+            3 => self.round3(incoming),
+            other => Err(FrostError::RoundOverflow {
+                round: other,
+                code: CODE_ROUND_OVERFLOW,
+            }
+            .framework()),
+        }
+    }
+
+    fn result(&self) -> confium_tc::error::Result<Vec<u8>> {
+        self.signature.clone().ok_or_else(|| {
+            FrostError::SessionNotComplete {
+                code: CODE_SESSION_NOT_COMPLETE,
+            }
+            .framework()
+        })
+    }
+
+    fn destroy(&mut self) {
+        self.key.share = C::scalar_from_u32(0);
+        self.nonces = None;
+        self.our_share = None;
+        self.signature = None;
+    }
+
+    fn snapshot(&self) -> Option<Zeroizing<Vec<u8>>> {
+        Some(self.save())
+    }
+
+    /// The commitment pair `D ‖ E` while the nonce is outstanding.
+    fn pending_nonce(&self) -> Option<Vec<u8>> {
+        self.nonces.as_ref().map(|n| {
+            let (_, d, e) = n.commitment(self.key.identifier);
+            [d, e].concat()
+        })
+    }
+}
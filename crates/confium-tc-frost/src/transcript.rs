@@ -0,0 +1,54 @@
+//! RFC 9591 §4 helper functions, generic over the ciphersuite.
+//!
+//! Everything that feeds a hash — nonce derivation, the encoded
+//! commitment list and the binding factor input — is built here from
+//! the ciphersuite's own serialisation and H1/H3/H4/H5, so a suite only
+//! supplies the primitives and inherits byte-exact RFC transcripts.
+
+use crate::ciphersuite::Ciphersuite;
+
+/// One participant's round-one commitment `(i, D_i, E_i)` in wire form.
+pub type CommitmentEntry = (u32, Vec<u8>, Vec<u8>);
+
+/// `SerializeScalar(i)` of a participant identifier.
+pub fn serialize_identifier<C: Ciphersuite>(identifier: u32) -> Vec<u8> {
+    C::serialize_scalar(&C::scalar_from_u32(identifier))
+}
+
+/// RFC 9591 §4.1 `nonce_generate`: `H3(random_bytes ‖ SerializeScalar(secret))`.
+/// Mixing the long-term share in means a weak RNG alone does not leak
+/// the nonce.
+pub fn nonce_generate<C: Ciphersuite>(random_bytes: &[u8; 32], secret: &C::Scalar) -> C::Scalar {
+    C::h3(&[random_bytes, &C::serialize_scalar(secret)])
+}
+
+/// RFC 9591 §4.3 `encode_group_commitment_list`. `commitments` must be
+/// sorted by identifier.
+pub fn encode_group_commitment_list<C: Ciphersuite>(commitments: &[CommitmentEntry]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(commitments.len() * (C::SCALAR_BYTES + 2 * C::ELEMENT_BYTES));
+    for (idx, d, e) in commitments {
+        out.extend_from_slice(&serialize_identifier::<C>(*idx));
+        out.extend_from_slice(d);
+        out.extend_from_slice(e);
+    }
+    out
+}
+
+/// RFC 9591 §4.4 `compute_binding_factors`. Returns `(i, ρ_i)` in the
+/// order of `commitments`, which must be sorted by identifier.
+pub fn binding_factors<C: Ciphersuite>(
+    group_public_key: &[u8],
+    msg: &[u8],
+    commitments: &[CommitmentEntry],
+) -> Vec<(u32, C::Scalar)> {
+    let msg_hash = C::h4(msg);
+    let encoded_commitment_hash = C::h5(&encode_group_commitment_list::<C>(commitments));
+    commitments
+        .iter()
+        .map(|(idx, _, _)| {
+            let id = serialize_identifier::<C>(*idx);
+            let rho = C::h1(&[group_public_key, &msg_hash, &encoded_commitment_hash, &id]);
+            (*idx, rho)
+        })
+        .collect()
+}
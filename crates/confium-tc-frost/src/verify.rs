@@ -0,0 +1,30 @@
+//! Standalone FROST signature verification.
+//!
+//! A FROST signature is an ordinary Schnorr signature, so verifying one
+//! needs nothing but the group public key: RFC 9591 Appendix B
+//! `verify_signature` checks `h·z·G == h·(R + c·PK)` with the suite's
+//! cofactor `h` and challenge `c`. For the Ed448 suite that is RFC 8032
+//! Ed448 verification; for secp256k1-TR it is BIP-340 verification.
+
+use crate::ciphersuite::{Ciphersuite, conditional_negate, mul_base};
+use crate::error::{CODE_INVALID_SIGNATURE, FrostError, Result};
+
+/// Verify `signature` over `message` under the serialised group public
+/// key, in the encodings of ciphersuite `C`.
+pub fn verify<C: Ciphersuite>(public_key: &[u8], message: &[u8], signature: &[u8]) -> Result<()> {
+    let invalid = |reason| FrostError::InvalidSignature {
+        reason,
+        code: CODE_INVALID_SIGNATURE,
+    };
+    let pk = C::deserialize_public_key(public_key)
+        .ok_or_else(|| invalid("public key is not a valid group element"))?;
+    let pk = conditional_negate::<C>(pk, C::needs_negation(&pk));
+    let (r, z) = C::deserialize_signature(signature)
+        .ok_or_else(|| invalid("signature is not a valid encoding"))?;
+    let c = C::challenge(&r, &pk, message);
+    let h = C::cofactor();
+    if mul_base::<C>(&z) * h != (r + pk * c) * h {
+        return Err(invalid("verification equation does not hold"));
+    }
+    Ok(())
+}
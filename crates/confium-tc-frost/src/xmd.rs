@@ -0,0 +1,64 @@
+//! The SHA-256 hashes shared by the secp256k1 and P-256 suites.
+//!
+//! Both instantiate H1–H3 as RFC 9380 §5.2 `hash_to_field` with
+//! `expand_message_xmd` over SHA-256 and `L = 48`, and H4/H5 as a
+//! prefixed SHA-256 digest. Only the reduction of the 48 uniform bytes
+//! modulo the group order is curve-specific.
+
+use sha2::{Digest, Sha256};
+
+/// `hash_to_field` output length for a 256-bit group order
+/// (`L = ceil((256 + 128) / 8)`).
+pub(crate) const HASH_TO_FIELD_LEN: usize = 48;
+
+/// SHA-256 block size, the `s_in_bytes` of `expand_message_xmd`.
+const SHA256_BLOCK: usize = 64;
+
+/// RFC 9380 §5.3.1 `expand_message_xmd` with SHA-256, specialised to
+/// the 48 output bytes `hash_to_field` needs (two digest blocks).
+pub(crate) fn expand_message_xmd(msg: &[&[u8]], dst: &[&[u8]]) -> [u8; HASH_TO_FIELD_LEN] {
+    let dst_len: usize = dst.iter().map(|d| d.len()).sum();
+    let dst_prime = |h: &mut Sha256| {
+        for d in dst {
+            h.update(d);
+        }
+        h.update([dst_len as u8]);
+    };
+
+    let mut h = Sha256::new();
+    h.update([0u8; SHA256_BLOCK]);
+    for m in msg {
+        h.update(m);
+    }
+    h.update((HASH_TO_FIELD_LEN as u16).to_be_bytes());
+    h.update([0u8]);
+    dst_prime(&mut h);
+    let b0 = h.finalize();
+
+    let mut h = Sha256::new();
+    h.update(b0);
+    h.update([1u8]);
+    dst_prime(&mut h);
+    let b1 = h.finalize();
+
+    let mut h = Sha256::new();
+    let mixed: Vec<u8> = b0.iter().zip(b1.iter()).map(|(x, y)| x ^ y).collect();
+    h.update(&mixed);
+    h.update([2u8]);
+    dst_prime(&mut h);
+    let b2 = h.finalize();
+
+    let mut out = [0u8; HASH_TO_FIELD_LEN];
+    out[..32].copy_from_slice(&b1);
+    out[32..].copy_from_slice(&b2[..HASH_TO_FIELD_LEN - 32]);
+    out
+}
+
+/// `SHA-256(ctx ‖ tag ‖ msg)` — H4 and H5.
+pub(crate) fn prefixed_digest(ctx: &[u8], tag: &[u8], msg: &[u8]) -> Vec<u8> {
+    let mut h = Sha256::new();
+    h.update(ctx);
+    h.update(tag);
+    h.update(msg);
+    h.finalize().to_vec()
+}
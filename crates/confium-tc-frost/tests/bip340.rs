@@ -0,0 +1,99 @@
+//! BIP-340 and BIP-341 vectors for the `FROST-secp256k1-TR` suite, plus
+//! threshold signing under a Taproot-tweaked key.
+
+use confium_tc_frost::secp256k1::{self, Secp256k1Tr};
+use confium_tc_frost::verify::verify;
+use confium_tc_frost::{KeyPackage, inprocess};
+
+fn bytes(hex_str: &str) -> Vec<u8> {
+    hex::decode(hex_str).expect("valid hex")
+}
+
+fn bytes32(hex_str: &str) -> [u8; 32] {
+    bytes(hex_str).try_into().expect("32 bytes")
+}
+
+/// BIP-340 `test-vectors.csv` rows 0 and 1.
+const VERIFY_VECTORS: [(&str, &str, &str); 2] = [
+    (
+        "F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "E907831F80848D1069A5371B402410364BDF1C5F8307B0084C55F1CE2DCA821525F66A4A85EA8B71E482A74F382D2CE5EBEEE8FDB2172F477DF4900D310536C0",
+    ),
+    (
+        "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+        "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+        "6896BD60EEAE296DB48A229FF71DFE071BDE413E6D43F917DC8DCF8C78DE33418906D11AC976ABCCB20B091292BFF4EA897EFCB639EA871CFA95F6DE339E4B0A",
+    ),
+];
+
+#[test]
+fn bip340_vectors_verify() {
+    for (pk, msg, sig) in VERIFY_VECTORS {
+        let (pk, msg, sig) = (bytes(pk), bytes(msg), bytes(sig));
+        verify::<Secp256k1Tr>(&pk, &msg, &sig).expect("vector verifies");
+        let mut tampered = sig.clone();
+        tampered[63] ^= 1;
+        assert!(verify::<Secp256k1Tr>(&pk, &msg, &tampered).is_err());
+    }
+}
+
+#[test]
+fn bip341_tweak_vectors() {
+    // BIP-341 `wallet-test-vectors.json`, scriptPubKey entries 0 and 3.
+    assert_eq!(
+        secp256k1::tweak_public_key(
+            &bytes("d6889cb081036e0faefa3a35157ad71086b123b2b144b649798b494c300a961d"),
+            None,
+        )
+        .unwrap(),
+        bytes32("53a1f6e454df1aa2776a2814a721372d6258050de330b3c6d10ee8f4e0dda343")
+    );
+    assert_eq!(
+        secp256k1::tweak_public_key(
+            &bytes("187791b6f712a8ea41c8ecdd0ee77fab3e85263b37e1ec18a3651926b3a6cf27"),
+            Some(&bytes32(
+                "5b75adecf53548f3ec6ad7d78383bf84cc57b55a3127c72b9a2481752dd88b21"
+            )),
+        )
+        .unwrap(),
+        bytes32("147c9c57132f6e7ecddba9800bb0c4449251c92a1e60371ee77557b6620f3ea3")
+    );
+}
+
+#[test]
+fn threshold_signature_is_bip340_under_the_group_key() {
+    let blobs = inprocess::keygen::<Secp256k1Tr>(2, 3).expect("dkg");
+    let key = KeyPackage::<Secp256k1Tr>::from_bytes(&blobs[0]).unwrap();
+    let x_only = &key.public_key[1..];
+    for msg in [&b"taproot"[..], b"key path", b""] {
+        let sig = inprocess::sign::<Secp256k1Tr>(&blobs[1..], 2, msg).expect("sign");
+        assert_eq!(sig.len(), 64);
+        verify::<Secp256k1Tr>(x_only, msg, &sig).expect("BIP-340 signature");
+    }
+}
+
+#[test]
+fn tweaked_shares_sign_for_the_output_key() {
+    let blobs = inprocess::keygen::<Secp256k1Tr>(2, 3).expect("dkg");
+    let merkle_root = [0x42u8; 32];
+    let tweaked: Vec<Vec<u8>> = blobs
+        .iter()
+        .map(|b| {
+            let key = KeyPackage::<Secp256k1Tr>::from_bytes(b).unwrap();
+            secp256k1::tweak(&key, Some(&merkle_root))
+                .unwrap()
+                .to_bytes()
+        })
+        .collect();
+    let internal = KeyPackage::<Secp256k1Tr>::from_bytes(&blobs[0])
+        .unwrap()
+        .public_key;
+    let output_key = secp256k1::tweak_public_key(&internal, Some(&merkle_root)).unwrap();
+
+    let sig =
+        inprocess::sign::<Secp256k1Tr>(&[tweaked[0].clone(), tweaked[2].clone()], 2, b"spend")
+            .expect("sign");
+    verify::<Secp256k1Tr>(&output_key, b"spend", &sig).expect("valid under output key");
+    assert!(verify::<Secp256k1Tr>(&internal[1..], b"spend", &sig).is_err());
+}
@@ -22,15 +22,14 @@ pub enum Gg18ErrorCode {
     /// Caller action: collect more shares before retrying.
     BELOW_THRESHOLD = 0x5010,
     /// A round message failed to deserialize or had an unexpected
-    /// sender / round number. GG18 has 4 rounds; a message from the
-    /// wrong round indicates protocol desynchronization or a buggy
-    /// peer.
+    /// sender / round number, or the signers' key shares do not
+    /// interpolate to the joint key (shares from different DKGs).
     /// Caller action: abort the session; do not retry with the same
     /// message feed.
     BAD_ROUND_MESSAGE = 0x5020,
-    /// A partial signature was malformed (wrong length, out-of-range
-    /// scalar). GG18 does not support identifiable abort; if you need
-    /// to attribute the failure to a specific peer, use CMP20 instead.
+    /// The combined `δ` or signature failed its check. Malformed
+    /// messages and failed proofs are attributed to their sender via
+    /// `Error::MessageRejected`; this code is the unattributed case.
     BAD_PARTIAL_SIGNATURE = 0x5030,
     /// Internal error — a panic-equivalent condition was caught and
     /// converted to an error return. Indicates a bug in the GG18
@@ -17,11 +17,11 @@
 //! [`sign`] returns a 64-byte `r || s` ECDSA signature. Verify it with
 //! the [`p256::ecdsa`] crate's `VerifyingKey::verify`.
 //!
-//! ## Security note
+//! ## Cost
 //!
-//! The underlying GG18 crate's MtA sub-round is a simplified in-clear
-//! stub. This driver inherits that property. See
-//! [`crate::mta`] for the gap.
+//! Every signing session generates a fresh 2048-bit Paillier-Blum
+//! modulus per party and proves it well formed (see [`crate::sign`]),
+//! which dominates the run time of [`sign`].
 
 use elliptic_curve::sec1::ToSec1Point;
 use p256::AffinePoint;
@@ -59,8 +59,8 @@ pub fn keygen(threshold: u32, party_count: usize) -> Result<KeygenOutput> {
 /// `threshold`. Returns the 64-byte `(r, s)` ECDSA signature.
 ///
 /// `share_blobs.len()` must be `>= threshold`. The supplied shares must
-/// all share the same joint public key — the protocol will otherwise
-/// silently produce an invalid signature.
+/// come from the same DKG; otherwise signing aborts in round 2 with
+/// [`crate::error::Gg18ErrorCode::BAD_ROUND_MESSAGE`].
 pub fn sign(share_blobs: &[Vec<u8>], threshold: u32, message: &[u8]) -> Result<Vec<u8>> {
     driver::run_sign(crate::SIGN_SCHEME_NAME, share_blobs, threshold, message)
 }
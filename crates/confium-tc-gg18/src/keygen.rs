@@ -23,9 +23,11 @@ use confium_tc::Result;
 use confium_tc::message::Message;
 use confium_tc::registry::{RoundResult, SessionImpl};
 use confium_tc::session::SessionParams;
+use confium_tc::snapshot::{StateReader, StateWriter};
+use zeroize::Zeroizing;
 
 use crate::error::{Gg18ErrorCode, scheme_error};
-use crate::share::{Gg18Share, SHARE_BYTES};
+use crate::share::{Gg18Share, SHARE_BYTES, read_point, read_scalar, write_point, write_scalar};
 use crate::vss::FeldmanVss;
 
 /// GG18 DKG scheme over P-256. Registered as `GG18-ECDSA-P256`.
@@ -33,6 +35,38 @@ pub struct Gg18DkgP256;
 
 impl Gg18DkgP256 {
     pub fn build_session(params: &SessionParams) -> Result<Box<dyn SessionImpl>> {
+        Ok(Box::new(Gg18DkgP256::new_session(params)?))
+    }
+
+    /// Rebuild a session from its [`SessionImpl::snapshot`] output. The
+    /// snapshot carries our dealing, so a resumed party re-sends the
+    /// same shares its peers may already hold.
+    pub fn restore_session(params: &SessionParams, state: &[u8]) -> Result<Box<dyn SessionImpl>> {
+        let mut session = Gg18DkgP256::new_session(params)?;
+        let mut r = StateReader::new(state);
+        session.round_done = r.u8()?;
+        session.our_vss.commitments = (0..r.u32()?)
+            .map(|_| read_point(&mut r))
+            .collect::<Result<_>>()?;
+        session.our_vss.shares = (0..r.u32()?)
+            .map(|_| read_scalar(&mut r))
+            .collect::<Result<_>>()?;
+        session.our_vss.secret = read_scalar(&mut r)?;
+        for _ in 0..r.u32()? {
+            let dealer = r.u64()?;
+            session.received_shares.push((dealer, read_scalar(&mut r)?));
+        }
+        if r.bool()? {
+            session.joint_public_key = Some(read_point(&mut r)?);
+        }
+        if r.bool()? {
+            session.our_combined_share = Some(read_scalar(&mut r)?);
+        }
+        r.finish()?;
+        Ok(Box::new(session))
+    }
+
+    fn new_session(params: &SessionParams) -> Result<Gg18DkgSession> {
         let party_id = params.parties.get(params.this_party_idx)?.id.clone();
         let n = params.parties.len();
         let t = params.threshold as usize;
@@ -46,7 +80,7 @@ impl Gg18DkgP256 {
 
         let vss = FeldmanVss::deal(&mut UnwrapErr(SysRng), n, t);
 
-        Ok(Box::new(Gg18DkgSession {
+        Ok(Gg18DkgSession {
             party_id,
             party_idx_1based,
             party_ids,
@@ -57,7 +91,7 @@ impl Gg18DkgP256 {
             joint_public_key: None,
             our_combined_share: None,
             round_done: 0,
-        }))
+        })
     }
 }
 
@@ -239,6 +273,34 @@ impl SessionImpl for Gg18DkgSession {
             *s = Scalar::ZERO;
         }
     }
+
+    fn snapshot(&self) -> Option<Zeroizing<Vec<u8>>> {
+        let mut w = StateWriter::new();
+        w.u8(self.round_done);
+        w.u32(self.our_vss.commitments.len() as u32);
+        for c in &self.our_vss.commitments {
+            write_point(&mut w, c);
+        }
+        w.u32(self.our_vss.shares.len() as u32);
+        for s in &self.our_vss.shares {
+            write_scalar(&mut w, s);
+        }
+        write_scalar(&mut w, &self.our_vss.secret);
+        w.u32(self.received_shares.len() as u32);
+        for (dealer, s) in &self.received_shares {
+            w.u64(*dealer);
+            write_scalar(&mut w, s);
+        }
+        w.bool(self.joint_public_key.is_some());
+        if let Some(pk) = &self.joint_public_key {
+            write_point(&mut w, pk);
+        }
+        w.bool(self.our_combined_share.is_some());
+        if let Some(s) = &self.our_combined_share {
+            write_scalar(&mut w, s);
+        }
+        Some(w.finish())
+    }
 }
 
 /// Parse a DKG-produced share blob.
@@ -16,9 +16,8 @@
 //!
 //! See the module-level docs of [`keygen`], [`sign`], [`vss`], [`mta`]
 //! for what is implemented and what is omitted. In short: the Feldman
-//! VSS, Lagrange interpolation, and threshold-ECDSA combine are all
-//! real; the MtA sub-round is a simplified in-process stub (nonce
-//! reveal in the clear) rather than a Paillier-based homomorphic MtA.
+//! VSS, Lagrange interpolation, Paillier MtA and threshold-ECDSA
+//! combine are all real, and no signer ever sees another's nonce share.
 
 pub mod error;
 pub mod inprocess;
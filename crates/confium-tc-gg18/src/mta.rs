@@ -1,110 +1,54 @@
 //! Multiplicative-to-Additive (MtA) sub-round.
 //!
-//! GG18 signing needs to convert, for every pair `(i, j)`, the product
-//! `k_i * x_j` into additive shares `alpha_{ij}` (held by `i`) and
-//! `beta_{ji}` (held by `j`) such that `alpha_{ij} + beta_{ji} = k_i x_j`.
+//! GG18 signing needs, for every ordered pair of signers `(i, j)`,
+//! additive shares of `k_j * γ_i` (MtA) and `k_j * w_i` (MtAwc, "with
+//! check" against the public `W_i = w_i * G`) without either party
+//! learning the other's factor.
 //!
-//! The cryptographic way is Paillier homomorphic encryption. This crate
-//! does not depend on a Paillier backend, so the MtA is computed **in
-//! the clear** inside the trusted test harness: `alpha_{ij} = 0` and
-//! `beta_{ji} = k_i x_j`. The arithmetic outcome is identical to a real
-//! MtA; only the cryptographic hiding is lost. See the crate-level docs
-//! for what a production replacement requires.
+//! Each party publishes a Paillier-Blum modulus at the start of the
+//! session, proven well formed with Π^mod and Π^prm, and encrypts its
+//! `k_j` under it as `K_j`. Party `i` answers with `D = K_j^x *
+//! enc_j(y)` and `F = enc_i(y)` for `x ∈ {γ_i, w_i}` and a fresh mask
+//! `y`, plus a Π^aff-g proof that `x` is the discrete log of `Γ_i` or
+//! `W_i` and that `y` is in range; CMP20's proofs stand in for GG18's
+//! own range proofs. `j` decrypts `D` to `α = k_j x + y`, `i` keeps
+//! `β = -y`, and `α + β = k_j x` mod the curve order.
+//!
+//! The proofs and the Paillier arithmetic live in
+//! [`confium_crypto_vss::paillier_zk`]; this module packs one signer's
+//! answer to one peer into a round message.
 
-use p256::Scalar;
+use confium_crypto_vss::paillier_zk::{Decoder, Encoder, LogStarProof};
+pub use confium_crypto_vss::paillier_zk::{MtaResponse, mta_receive, mta_respond, mta_verify};
 
-/// Collected per-party inputs for one signing session's MtA products.
-#[derive(Clone, Debug)]
-pub struct MtaInputs {
-    pub ks: Vec<Scalar>,
-    pub xs: Vec<Scalar>,
-    pub indices: Vec<u64>,
+/// Everything party `i` sends party `j` in phase 2: the MtA answer to
+/// `K_j` with `γ_i`, the MtAwc answer with `w_i`, and the Π^log* proof
+/// that `Γ_i` and `G_i = enc_i(γ_i)` hide the same `γ_i`.
+#[derive(Debug, Clone)]
+pub struct MtaMessage {
+    pub gamma: MtaResponse,
+    pub key: MtaResponse,
+    pub gamma_proof: LogStarProof,
 }
 
-impl MtaInputs {
-    /// Compute additive MtA products for every ordered pair `(i, j)`.
-    ///
-    /// Simplified path: `alphas[i][j] = 0`, `betas[j][i] = k_i * x_j`.
-    pub fn products(&self) -> (Vec<Vec<Scalar>>, Vec<Vec<Scalar>>) {
-        let n = self.ks.len();
-        let alphas = vec![vec![Scalar::ZERO; n]; n];
-        let mut betas = vec![vec![Scalar::ZERO; n]; n];
-        // Indexed matrix access (betas[j][i]) is clearer here than
-        // an iterator rewrite; suppress the needless_range_loop hint.
-        #[allow(clippy::needless_range_loop)]
-        for i in 0..n {
-            #[allow(clippy::needless_range_loop)]
-            for j in 0..n {
-                if i == j {
-                    continue;
-                }
-                betas[j][i] = self.ks[i] * self.xs[j];
-            }
-        }
-        (alphas, betas)
-    }
-}
-
-/// Party `i`'s total MtA adjustment:
-/// `delta_i = k_i x_i + sum_{j != i} (alphas[j][i] + betas[i][j])`.
-pub fn party_mta_sum(
-    i: usize,
-    ks: &[Scalar],
-    xs: &[Scalar],
-    alphas: &[Vec<Scalar>],
-    betas: &[Vec<Scalar>],
-) -> Scalar {
-    let n = ks.len();
-    let mut acc = ks[i] * xs[i];
-    for j in 0..n {
-        if j == i {
-            continue;
-        }
-        acc += alphas[j][i];
-        acc += betas[i][j];
-    }
-    acc
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn mta_products_sum_to_ki_xj() {
-        let ks = vec![Scalar::from(2u64), Scalar::from(3u64), Scalar::from(5u64)];
-        let xs = vec![Scalar::from(7u64), Scalar::from(11u64), Scalar::from(13u64)];
-        let inputs = MtaInputs {
-            ks: ks.clone(),
-            xs: xs.clone(),
-            indices: vec![1, 2, 3],
-        };
-        let (alphas, betas) = inputs.products();
-        for i in 0..3 {
-            for j in 0..3 {
-                if i == j {
-                    continue;
-                }
-                let sum = alphas[i][j] + betas[j][i];
-                assert_eq!(sum, ks[i] * xs[j], "pair ({}, {})", i, j);
-            }
-        }
+impl MtaMessage {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut e = Encoder::new();
+        self.gamma.encode(&mut e);
+        self.key.encode(&mut e);
+        self.gamma_proof.encode(&mut e);
+        e.finish()
     }
 
-    #[test]
-    fn party_mta_sum_matches_xi_sum_kj() {
-        let ks = vec![Scalar::from(2u64), Scalar::from(3u64), Scalar::from(5u64)];
-        let xs = vec![Scalar::from(7u64), Scalar::from(11u64), Scalar::from(13u64)];
-        let inputs = MtaInputs {
-            ks: ks.clone(),
-            xs: xs.clone(),
-            indices: vec![1, 2, 3],
+    /// `None` when `bytes` is not exactly one encoded message.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let mut d = Decoder::new(bytes);
+        let msg = MtaMessage {
+            gamma: MtaResponse::decode(&mut d)?,
+            key: MtaResponse::decode(&mut d)?,
+            gamma_proof: LogStarProof::decode(&mut d)?,
         };
-        let (alphas, betas) = inputs.products();
-        let k_sum: Scalar = ks.iter().copied().fold(Scalar::ZERO, |a, b| a + b);
-        for i in 0..3 {
-            let got = party_mta_sum(i, &ks, &xs, &alphas, &betas);
-            assert_eq!(got, xs[i] * k_sum, "party {}", i);
-        }
+        d.finish()?;
+        Some(msg)
     }
 }
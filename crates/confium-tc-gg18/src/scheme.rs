@@ -29,6 +29,13 @@ impl TcScheme for Gg18EcdsaP256 {
     fn create_session(&self, params: &SessionParams) -> Result<Box<dyn SessionImpl>> {
         Gg18DkgP256::build_session(params)
     }
+    fn restore_session(
+        &self,
+        params: &SessionParams,
+        state: &[u8],
+    ) -> Result<Box<dyn SessionImpl>> {
+        Gg18DkgP256::restore_session(params, state)
+    }
 }
 
 /// GG18 signing scheme (registered as `GG18-ECDSA-P256-SIGN`).
@@ -44,6 +51,13 @@ impl TcScheme for Gg18EcdsaP256Sign {
     fn create_session(&self, params: &SessionParams) -> Result<Box<dyn SessionImpl>> {
         Gg18SignP256::build_session(params)
     }
+    fn restore_session(
+        &self,
+        params: &SessionParams,
+        state: &[u8],
+    ) -> Result<Box<dyn SessionImpl>> {
+        Gg18SignP256::restore_session(params, state)
+    }
 }
 
 confium_tc::register_tc_scheme!(Gg18EcdsaP256);
@@ -1,9 +1,12 @@
 //! Per-party share material produced by GG18 DKG and consumed by signing.
 
+use elliptic_curve::PrimeField;
 use elliptic_curve::sec1::ToSec1Point;
 use p256::{AffinePoint, FieldBytes, NonZeroScalar, Scalar};
 use zeroize::Zeroize;
 
+use confium_tc::snapshot::{self, StateReader, StateWriter};
+
 use crate::error::{Gg18ErrorCode, Result, scheme_error};
 
 const SHARE_MAGIC: [u8; 4] = *b"GG18";
@@ -102,6 +105,27 @@ pub(crate) fn decode_affine(bytes: &[u8]) -> Result<AffinePoint> {
     Ok(pt)
 }
 
+/// Write a scalar into session snapshot state.
+pub(crate) fn write_scalar(w: &mut StateWriter, s: &Scalar) {
+    w.fixed(&s.to_bytes());
+}
+
+/// Read a scalar written by [`write_scalar`].
+pub(crate) fn read_scalar(r: &mut StateReader<'_>) -> Result<Scalar> {
+    let fb: FieldBytes = r.array::<32>()?.into();
+    Option::from(Scalar::from_repr(fb)).ok_or_else(|| snapshot::invalid("scalar out of range"))
+}
+
+/// Write a point into session snapshot state, SEC1-compressed.
+pub(crate) fn write_point(w: &mut StateWriter, p: &AffinePoint) {
+    w.fixed(p.to_sec1_point(true).as_bytes());
+}
+
+/// Read a point written by [`write_point`].
+pub(crate) fn read_point(r: &mut StateReader<'_>) -> Result<AffinePoint> {
+    decode_affine(&r.array::<33>()?).map_err(|_| snapshot::invalid("point is not on the curve"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
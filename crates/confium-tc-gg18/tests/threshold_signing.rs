@@ -7,8 +7,11 @@
 //!    under `p256::ecdsa::VerifyingKey`).
 //! 2. A 3-of-3 coalition produces a valid signature under the same key.
 //! 3. Any T-of-N subset produces the same-threshold signature.
-//! 4. A byzantine party (tampered partial signature) causes the session
-//!    to abort.
+//! 4. A party signing with a corrupted share causes the session to
+//!    abort before any nonce is used.
+//! 5. A party restarted from its snapshot after every round still
+//!    completes DKG and signing, and its spent nonce can never be
+//!    resumed.
 
 use confium_tc::Session;
 use confium_tc::SessionParams;
@@ -145,7 +148,7 @@ fn run_signing(
         roster,
         participating.clone(),
         |idx| sign_params(roster, idx, threshold, shares[idx].clone(), msg),
-        4,
+        6,
     )
 }
 
@@ -250,16 +253,16 @@ fn signing_without_share_aborts() {
 
 #[test]
 fn byzantine_partial_signature_aborts() {
-    // A party that posts a wrong partial signature causes verification
-    // to fail in round 4. We simulate this by having one party use a
-    // corrupted share (off-by-one in the secret), so its partial is
-    // inconsistent with the others.
+    // A party signing with a corrupted share (wrong party_idx) has a
+    // Lagrange weight inconsistent with the others. The round-1 key
+    // shares no longer interpolate to the joint key, so round 2 aborts
+    // before anyone commits a nonce.
     let roster = ["alice", "bob", "carol"];
     let msg = b"byzantine test";
     let mut shares = run_dkg(&roster, 2);
 
     // Corrupt carol's share: flip its party_idx so her Lagrange weight
-    // is wrong, making her partial signature inconsistent.
+    // is wrong.
     let mut carol = Gg18Share::from_bytes(&shares[2]).expect("share");
     carol.party_idx = 99; // bogus index
     shares[2] = carol.to_bytes();
@@ -268,10 +271,84 @@ fn byzantine_partial_signature_aborts() {
     match outcome {
         Outcome::Aborted(reason) => {
             assert!(
-                reason.contains("round 4") || reason.contains("round 3"),
-                "expected abort on byzantine partial, got: {reason}"
+                reason.contains("round 2"),
+                "expected abort on the corrupted share, got: {reason}"
             );
         }
-        Outcome::Ok(_) => panic!("byzantine partial must abort, not complete"),
+        Outcome::Ok(_) => panic!("a corrupted share must abort, not complete"),
     }
 }
+
+const SNAPSHOT_KEY: &[u8] = b"gg18-test-share-envelope-integrity-key";
+
+/// Messages from `outs` addressed to `to`.
+fn inbox(outs: &[Message], to: &str) -> Vec<Message> {
+    outs.iter()
+        .filter(|m| m.from_party_id != to && m.is_for(to))
+        .cloned()
+        .collect()
+}
+
+/// Run a two-party protocol where the first party checkpoints after
+/// every round and is restarted from the checkpoint before the next
+/// one. Returns both results and every checkpoint taken.
+fn run_restarting(
+    params: &[SessionParams; 2],
+    ledger: std::sync::Arc<confium_tc::snapshot::MemoryNonceLedger>,
+) -> (Vec<u8>, Vec<u8>, Vec<Vec<u8>>) {
+    let mut alice = Session::create(&params[0]).expect("alice");
+    alice.set_nonce_ledger(ledger.clone());
+    let mut bob = Session::create(&params[1]).expect("bob");
+    let mut a_out = Vec::new();
+    let mut b_out = Vec::new();
+    let mut blobs = Vec::new();
+    while !(alice.is_complete() && bob.is_complete()) {
+        let a_next = alice
+            .round_step(&inbox(&b_out, "alice"))
+            .expect("alice round")
+            .outgoing;
+        let b_next = bob
+            .round_step(&inbox(&a_out, "bob"))
+            .expect("bob round")
+            .outgoing;
+        let blob = alice.snapshot(SNAPSHOT_KEY).expect("snapshot");
+        alice = Session::resume(&params[0], &blob, SNAPSHOT_KEY, ledger.clone()).expect("resume");
+        assert_eq!(alice.last_outgoing(), a_next.as_slice());
+        blobs.push(blob);
+        a_out = a_next;
+        b_out = b_next;
+    }
+    (
+        alice.result().expect("alice result"),
+        bob.result().expect("bob result"),
+        blobs,
+    )
+}
+
+#[test]
+fn restarting_party_completes_dkg_and_signing() {
+    use confium_tc::snapshot::MemoryNonceLedger;
+    use std::sync::Arc;
+
+    let roster = ["alice", "bob"];
+    let ledger = Arc::new(MemoryNonceLedger::new());
+    let (a_share, b_share, _) = run_restarting(
+        &[dkg_params(&roster, 0, 2), dkg_params(&roster, 1, 2)],
+        ledger.clone(),
+    );
+    let pk = |s: &[u8]| Gg18Share::from_bytes(s).expect("share").public_key;
+    assert_eq!(pk(&a_share), pk(&b_share));
+
+    let msg = b"gg18 resume";
+    let params = [
+        sign_params(&roster, 0, 2, a_share.clone(), msg),
+        sign_params(&roster, 1, 2, b_share, msg),
+    ];
+    let (sig, b_sig, blobs) = run_restarting(&params, ledger.clone());
+    assert_eq!(sig, b_sig);
+    assert!(verify_sig(&a_share, msg, &sig));
+
+    // The round-4 checkpoint still holds the nonce spent in round 5.
+    let err = Session::resume(&params[0], &blobs[3], SNAPSHOT_KEY, ledger).unwrap_err();
+    assert!(matches!(err, confium_tc::Error::NonceReuse { .. }));
+}
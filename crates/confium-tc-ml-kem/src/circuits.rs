@@ -0,0 +1,365 @@
+//! The boolean and arithmetic circuits of the decapsulation, over the
+//! sharings of [`crate::mpc`].
+//!
+//! Bit vectors are *bitsliced*: bit `j` of a vector lives in bit
+//! `j % 64` of word `j / 64`, which is also the bit order of Keccak's
+//! lanes and of `ByteEncode`. A vector of `W`-bit numbers is stored
+//! plane-major: plane `w` (bit `w` of every number) occupies `L`
+//! consecutive words.
+
+use crate::mpc::{Ctx, Interrupt, Shared};
+use crate::params::Q;
+
+type Step<T> = Result<T, Interrupt>;
+
+/// Bits per number in [`in_interval`]; the sum of up to ten pieces
+/// mod `q` and every threshold stay below `2^W`.
+const W: usize = 16;
+
+const KECCAK_ROUNDS: [u64; 24] = [
+    0x0000000000000001,
+    0x0000000000008082,
+    0x800000000000808a,
+    0x8000000080008000,
+    0x000000000000808b,
+    0x0000000080000001,
+    0x8000000080008081,
+    0x8000000000008009,
+    0x000000000000008a,
+    0x0000000000000088,
+    0x0000000080008009,
+    0x000000008000000a,
+    0x000000008000808b,
+    0x800000000000008b,
+    0x8000000000008089,
+    0x8000000000008003,
+    0x8000000000008002,
+    0x8000000000000080,
+    0x000000000000800a,
+    0x800000008000000a,
+    0x8000000080008081,
+    0x8000000000008080,
+    0x0000000080000001,
+    0x8000000080008008,
+];
+
+/// `ρ` rotation of lane `x + 5y`.
+const KECCAK_RHO: [u32; 25] = [
+    0, 1, 62, 28, 27, 36, 44, 6, 55, 20, 3, 10, 43, 25, 39, 41, 45, 15, 21, 8, 18, 2, 61, 56, 14,
+];
+
+/// `θ`, `ρ` and `π` of every state in `lanes` — the linear part of a
+/// Keccak round.
+fn theta_rho_pi(lanes: &[u64]) -> Vec<u64> {
+    let mut out = vec![0u64; lanes.len()];
+    for (a, b) in lanes.chunks(25).zip(out.chunks_mut(25)) {
+        let c: [u64; 5] = std::array::from_fn(|x| (0..5).fold(0, |acc, y| acc ^ a[x + 5 * y]));
+        for x in 0..5 {
+            let d = c[(x + 4) % 5] ^ c[(x + 1) % 5].rotate_left(1);
+            for y in 0..5 {
+                let i = x + 5 * y;
+                // π: (x, y) → (y, 2x + 3y).
+                b[y + 5 * ((2 * x + 3 * y) % 5)] = (a[i] ^ d).rotate_left(KECCAK_RHO[i]);
+            }
+        }
+    }
+    out
+}
+
+/// Lane `(x + dx, y)` of every state at position `(x, y)`.
+fn shift_x(lanes: &[u64], dx: usize) -> Vec<u64> {
+    (0..lanes.len())
+        .map(|i| {
+            let (state, lane) = (i / 25, i % 25);
+            lanes[25 * state + (lane % 5 + dx) % 5 + 5 * (lane / 5)]
+        })
+        .collect()
+}
+
+/// Keccak-f\[1600\] on a batch of states, 25 lanes each; one layer per
+/// round for `χ`.
+pub(crate) fn keccak_f(ctx: &mut Ctx, state: &Shared<u64>) -> Step<Shared<u64>> {
+    let states = state.len() / 25;
+    let mut a = state.clone();
+    for rc in KECCAK_ROUNDS {
+        let b = a.map(theta_rho_pi);
+        // χ: a = b ⊕ (¬b[x+1] ∧ b[x+2]).
+        let not_next = ctx.add_public(&b.map(|p| shift_x(p, 1)), &vec![u64::MAX; b.len()]);
+        let after = b.map(|p| shift_x(p, 2));
+        a = b.add(&ctx.mul(&not_next, &after)?);
+        let mut iota = vec![0u64; 25 * states];
+        for s in 0..states {
+            iota[25 * s] = rc;
+        }
+        a = ctx.add_public(&a, &iota);
+    }
+    Ok(a)
+}
+
+/// The bitsliced bits of `values`, plane-major: plane `w` of word `l`
+/// holds bit `w` of `values[64l..64l + 64]`.
+fn bitslice(values: &[u16]) -> Vec<u64> {
+    let words = values.len() / 64;
+    let mut out = vec![0u64; W * words];
+    for (j, &v) in values.iter().enumerate() {
+        for w in 0..W {
+            out[w * words + j / 64] |= u64::from(v >> w & 1) << (j % 64);
+        }
+    }
+    out
+}
+
+/// Multiply every number by two, dropping the top plane.
+fn shift_planes(planes: &[u64], words: usize) -> Vec<u64> {
+    let mut out = vec![0u64; planes.len()];
+    out[words..].copy_from_slice(&planes[..planes.len() - words]);
+    out
+}
+
+/// Add shared binary numbers with carry-save adders until two remain.
+fn wallace(ctx: &mut Ctx, mut numbers: Vec<Shared<u64>>, words: usize) -> Step<[Shared<u64>; 2]> {
+    while numbers.len() > 2 {
+        let triples = numbers.len() / 3;
+        let rest = numbers.split_off(3 * triples);
+        // carry = maj(a, b, c) = ((a ⊕ c) ∧ (b ⊕ c)) ⊕ c.
+        let ac: Vec<Shared<u64>> = numbers.chunks(3).map(|t| t[0].add(&t[2])).collect();
+        let bc: Vec<Shared<u64>> = numbers.chunks(3).map(|t| t[1].add(&t[2])).collect();
+        let products = ctx.mul(
+            &Shared::concat(&ac.iter().collect::<Vec<_>>()),
+            &Shared::concat(&bc.iter().collect::<Vec<_>>()),
+        )?;
+        let size = W * words;
+        let mut next = Vec::with_capacity(2 * triples + rest.len());
+        for (t, triple) in numbers.chunks(3).enumerate() {
+            let carry = products.slice(t * size..(t + 1) * size).add(&triple[2]);
+            next.push(triple[0].add(&triple[1]).add(&triple[2]));
+            next.push(carry.map(|p| shift_planes(p, words)));
+        }
+        next.extend(rest);
+        numbers = next;
+    }
+    if numbers.len() == 1 {
+        numbers.push(numbers[0].map(|p| vec![0u64; p.len()]));
+    }
+    let b = numbers.pop().ok_or(Interrupt::Diverged)?;
+    let a = numbers.pop().ok_or(Interrupt::Diverged)?;
+    Ok([a, b])
+}
+
+/// `[x_j mod q ∈ [lo_j, hi_j]]` for every `j`, as bitsliced booleans.
+/// `bounds` come from [`crate::poly::compress_preimage`] and the length
+/// of `x` is a multiple of 64.
+///
+/// The pieces of `x` are added in binary to the integer `X = Σ_S x_S`,
+/// `0 ≤ X < m·q`, which lies in one of the intervals `[lo + kq,
+/// hi + kq]`, `0 ≤ k ≤ m`, exactly when `x` is in range. Each interval
+/// is tested as `[X ≥ lo + kq] ⊕ [X ≥ hi + 1 + kq]`; each comparison
+/// adds `2^W − T` to `X` and reads the carry out of bit `W − 1` with a
+/// parallel-prefix adder. Depth: the carry-save tree plus six.
+pub(crate) fn in_interval(
+    ctx: &mut Ctx,
+    x: &Shared<u16>,
+    bounds: &[(i32, i32)],
+) -> Step<Shared<u64>> {
+    let words = x.len() / 64;
+    let size = W * words;
+    let pieces = ctx.layout().piece_count() as i32;
+    let summands = ctx.summands(x, size, bitslice);
+    let [a, b] = wallace(ctx, summands, words)?;
+    let ab = ctx.mul(&a, &b)?;
+    let sum = a.add(&b);
+
+    // Thresholds, two per interval.
+    let q = i32::from(Q);
+    let thresholds: Vec<Vec<i32>> = (0..=pieces)
+        .flat_map(|k| {
+            [
+                bounds.iter().map(|&(lo, _)| (lo + k * q).max(0)).collect(),
+                bounds.iter().map(|&(_, hi)| hi + 1 + k * q).collect(),
+            ]
+        })
+        .collect();
+
+    // X + (2^W − T) = s + 2·cy with s = X ⊕ K, cy = maj(A, B, K); the
+    // comparison is the bit-W carry of that sum.
+    let mut s_all = Vec::new();
+    let mut b_all = Vec::new();
+    let mut top = Vec::new();
+    for t in &thresholds {
+        let k: Vec<u16> = t.iter().map(|&t| (65536 - t) as u16).collect();
+        let k_planes = bitslice(&k);
+        let k_top: Vec<u64> = (0..words)
+            .map(|l| (0..64).fold(0, |acc, i| acc | u64::from(t[64 * l + i] == 0) << i))
+            .collect();
+        let s = ctx.add_public(&sum, &k_planes);
+        let cy = ab.add(&sum.scale(&k_planes));
+        top.push(ctx.add_public(&cy.slice(size - words..size), &k_top));
+        b_all.push(cy.map(|p| shift_planes(p, words)));
+        s_all.push(s);
+    }
+    let s = Shared::concat(&s_all.iter().collect::<Vec<_>>());
+    let shifted = Shared::concat(&b_all.iter().collect::<Vec<_>>());
+    let mut g = ctx.mul(&s, &shifted)?;
+    let mut p = s.add(&shifted);
+
+    // Carry tree: (G, P) = (G_hi ⊕ P_hi ∧ G_lo, P_hi ∧ P_lo), halving the
+    // planes of every comparison each level.
+    let mut span = W;
+    while span > 1 {
+        let half = span / 2;
+        let pick = |v: &Shared<u64>, hi: bool| {
+            v.map(|part| {
+                part.chunks(span * words)
+                    .flat_map(|c| {
+                        c.chunks(words)
+                            .skip(usize::from(hi))
+                            .step_by(2)
+                            .flatten()
+                            .copied()
+                            .collect::<Vec<_>>()
+                    })
+                    .collect()
+            })
+        };
+        let (g_lo, g_hi, p_lo, p_hi) = (
+            pick(&g, false),
+            pick(&g, true),
+            pick(&p, false),
+            pick(&p, true),
+        );
+        let n = g_lo.len();
+        let products = ctx.mul(
+            &Shared::concat(&[&p_hi, &p_hi]),
+            &Shared::concat(&[&g_lo, &p_lo]),
+        )?;
+        g = g_hi.add(&products.slice(0..n));
+        p = products.slice(n..2 * n);
+        span = half;
+    }
+
+    // g now holds one carry word block per comparison.
+    let mut out = ctx.constant(&vec![0u64; words]);
+    for (c, t) in top.iter().enumerate() {
+        out = out.add(t).add(&g.slice(c * words..(c + 1) * words));
+    }
+    Ok(out)
+}
+
+/// Arithmetic sharings mod `q` of the bits of `bits`, one value per
+/// bit: the per-piece bits are combined with `x ⊕ y = x + y − 2xy` in a
+/// tree.
+pub(crate) fn b2a(ctx: &mut Ctx, bits: &Shared<u64>) -> Step<Shared<u16>> {
+    let count = 64 * bits.len();
+    let mut values = ctx.summands(bits, count, |p| {
+        (0..count)
+            .map(|j| (p[j / 64] >> (j % 64) & 1) as u16)
+            .collect()
+    });
+    while values.len() > 1 {
+        let pairs = values.len() / 2;
+        let rest = values.split_off(2 * pairs);
+        let xs: Vec<&Shared<u16>> = values.iter().step_by(2).collect();
+        let ys: Vec<&Shared<u16>> = values.iter().skip(1).step_by(2).collect();
+        let products = ctx.mul(&Shared::concat(&xs), &Shared::concat(&ys))?;
+        let mut next: Vec<Shared<u16>> = (0..pairs)
+            .map(|i| {
+                let xy = products.slice(i * count..(i + 1) * count);
+                xs[i].add(ys[i]).sub(&xy).sub(&xy)
+            })
+            .collect();
+        next.extend(rest);
+        values = next;
+    }
+    values.pop().ok_or(Interrupt::Diverged)
+}
+
+/// The AND of every bit of `bits` (a power-of-two number of words),
+/// in bit 0 of a one-word sharing whose other bits are zero.
+pub(crate) fn and_all(ctx: &mut Ctx, bits: &Shared<u64>) -> Step<Shared<u64>> {
+    let mut x = bits.clone();
+    while x.len() > 1 {
+        let half = x.len() / 2;
+        x = ctx.mul(&x.slice(0..half), &x.slice(half..2 * half))?;
+    }
+    for shift in [32, 16, 8, 4, 2, 1] {
+        let down = x.map(|p| vec![p[0] >> shift]);
+        x = ctx.mul(&x, &down)?;
+    }
+    Ok(x.scale(&[1]))
+}
+
+#[cfg(test)]
+mod tests {
+    use sha3::digest::{ExtendableOutput, Update, XofReader};
+
+    use super::*;
+    use crate::mpc::{Layout, run_local};
+    use crate::poly::compress_preimage;
+
+    #[test]
+    fn keccak_matches_shake256() {
+        let layout = Layout::single();
+        let input = b"threshold decapsulation";
+        // One SHAKE256 block: rate 136 bytes, domain 0x1F, final 0x80.
+        let mut block = [0u8; 200];
+        block[..input.len()].copy_from_slice(input);
+        block[input.len()] = 0x1F;
+        block[135] |= 0x80;
+        let lanes: Vec<u64> = block
+            .chunks(8)
+            .map(|c| u64::from_le_bytes(c.try_into().unwrap()))
+            .collect();
+        let out = run_local(&layout, |ctx| {
+            let state = ctx.constant(&lanes);
+            let out = keccak_f(ctx, &state)?;
+            ctx.open(&out)
+        });
+        let got: Vec<u8> = out[..4].iter().flat_map(|l| l.to_le_bytes()).collect();
+
+        let mut xof = sha3::Shake256::default();
+        xof.update(input);
+        let mut want = [0u8; 32];
+        xof.finalize_xof().read(&mut want);
+        assert_eq!(got, want);
+    }
+
+    #[test]
+    fn interval_tests_match_compression() {
+        let layout = Layout::single();
+        let values: Vec<u16> = (0..128).map(|j| (j * 211 + 5) % Q).collect();
+        let mut bounds: Vec<(i32, i32)> = (0..64).map(|_| compress_preimage(1, 1)).collect();
+        bounds.extend((0..64u16).map(|j| compress_preimage(j % 16, 4)));
+        let out = run_local(&layout, |ctx| {
+            let x = ctx.constant(&values);
+            let inside = in_interval(ctx, &x, &bounds)?;
+            ctx.open(&inside)
+        });
+        for (j, &v) in values.iter().enumerate() {
+            let want = if j < 64 {
+                crate::poly::compress(v, 1) == 1
+            } else {
+                crate::poly::compress(v, 4) == (j as u16 - 64) % 16
+            };
+            assert_eq!(out[j / 64] >> (j % 64) & 1 == 1, want, "j={j} v={v}");
+        }
+    }
+
+    #[test]
+    fn conversions_and_reductions() {
+        let layout = Layout::single();
+        let (values, all, none) = run_local(&layout, |ctx| {
+            let bits = ctx.constant(&[0x8000_0000_0000_0001u64]);
+            let values = b2a(ctx, &bits)?;
+            let ones = ctx.constant(&[u64::MAX; 4]);
+            let all = and_all(ctx, &ones)?;
+            let one_zero = ctx.constant(&[u64::MAX, !4, u64::MAX, u64::MAX]);
+            let none = and_all(ctx, &one_zero)?;
+            Ok((ctx.open(&values)?, ctx.open(&all)?, ctx.open(&none)?))
+        });
+        let mut want = vec![0u16; 64];
+        want[0] = 1;
+        want[63] = 1;
+        assert_eq!(values, want);
+        assert_eq!((all, none), (vec![1], vec![0]));
+    }
+}
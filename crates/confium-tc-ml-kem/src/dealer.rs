@@ -0,0 +1,207 @@
+//! Trusted-dealer key generation.
+//!
+//! The dealer runs ML-KEM-768 key generation from a seed `d ‖ z` and
+//! splits `ŝ` into uniformly random pieces, one per subset of `n − f`
+//! parties with `f = (t − 1)/2`; the hybrid's X25519 scalar is split the
+//! same way. See [`crate::keys`] for the layout.
+//!
+//! The threshold must be odd: the circuit multiplies shared values,
+//! which needs an honest majority of `2f + 1` parties for privacy
+//! against `f`. The dealer sees the whole key, so the ceremony must run
+//! on a machine that is trusted and wiped afterwards.
+
+use curve25519_dalek::Scalar;
+use rand_core::{OsRng, RngCore};
+
+use crate::Variant;
+use crate::error::{MlKemErrorCode, Result, scheme_error};
+use crate::hybrid;
+use crate::keys::{MlKemKeyShare, Piece};
+use crate::mlkem;
+use crate::mpc::Ring;
+use crate::params::{K, N};
+use crate::poly::{PolyVec, poly_add, poly_sub};
+
+/// Largest party count.
+pub const MAX_PARTIES: u32 = 7;
+
+/// Largest number of pieces, `C(n, f)`.
+pub const MAX_PIECES: u32 = 10;
+
+fn binomial(n: u32, k: u32) -> u32 {
+    (0..k).fold(1, |acc, i| acc * (n - i) / (i + 1))
+}
+
+/// The number of colluding parties a `threshold` key is private
+/// against.
+pub(crate) fn privacy(threshold: u32) -> u32 {
+    (threshold - 1) / 2
+}
+
+/// Number of pieces of a `threshold`-of-`parties` key.
+pub(crate) fn piece_count(threshold: u32, parties: u32) -> u32 {
+    binomial(parties, privacy(threshold))
+}
+
+pub(crate) fn check_roster(threshold: u32, parties: u32) -> Result<()> {
+    if parties > MAX_PARTIES
+        || threshold < 3
+        || threshold % 2 == 0
+        || threshold > parties
+        || piece_count(threshold, parties) > MAX_PIECES
+    {
+        return Err(scheme_error(MlKemErrorCode::BAD_KEY_PARAMETERS));
+    }
+    Ok(())
+}
+
+/// Deal a fresh key to `parties` parties, any `threshold` of whom can
+/// decapsulate.
+///
+/// Supported configurations are those with at most [`MAX_PIECES`]
+/// pieces: `3`-of-`n` up to seven parties and `5`-of-`5`.
+pub fn deal(variant: Variant, threshold: u32, parties: u32) -> Result<Vec<MlKemKeyShare>> {
+    let mut seed = vec![0u8; variant.seed_len()];
+    OsRng.fill_bytes(&mut seed);
+    let shares = deal_with_seed(variant, threshold, parties, &seed);
+    seed.fill(0);
+    shares
+}
+
+/// Deal the key generated from `seed` — `d ‖ z`, followed by the X25519
+/// secret key for the hybrid — which is also how single-party ML-KEM
+/// implementations accept a seed, so an existing key can be split.
+pub fn deal_with_seed(
+    variant: Variant,
+    threshold: u32,
+    parties: u32,
+    seed: &[u8],
+) -> Result<Vec<MlKemKeyShare>> {
+    check_roster(threshold, parties)?;
+    if seed.len() != variant.seed_len() {
+        return Err(scheme_error(MlKemErrorCode::BAD_KEY_PARAMETERS));
+    }
+    let d: [u8; 32] = seed[..32].try_into().expect("seed length checked");
+    let z: [u8; 32] = seed[32..64].try_into().expect("seed length checked");
+    let (ek, s_hat) = mlkem::keygen(&d);
+    let x25519 = (variant == Variant::X25519MlKem768)
+        .then(|| hybrid::split_secret(&seed[64..].try_into().expect("seed length checked")));
+
+    let size = parties - privacy(threshold);
+    let members: Vec<u32> = (0u32..1 << parties)
+        .filter(|m| m.count_ones() == size)
+        .collect();
+
+    // Uniform pieces; the last one makes up the sum.
+    let mut rng = OsRng;
+    let mut secrets: Vec<PolyVec> = (1..members.len())
+        .map(|_| std::array::from_fn(|_| std::array::from_fn(|_| u16::random(&mut rng))))
+        .collect();
+    let rest = secrets.iter().fold([[0u16; N]; K], |acc, s| {
+        std::array::from_fn(|i| poly_add(&acc[i], &s[i]))
+    });
+    secrets.push(std::array::from_fn(|i| poly_sub(&s_hat[i], &rest[i])));
+    let scalars: Vec<Scalar> = match &x25519 {
+        Some((y, _)) => {
+            let mut scalars: Vec<Scalar> = (1..members.len())
+                .map(|_| {
+                    let mut wide = [0u8; 64];
+                    rng.fill_bytes(&mut wide);
+                    Scalar::from_bytes_mod_order_wide(&wide)
+                })
+                .collect();
+            scalars.push(y - scalars.iter().sum::<Scalar>());
+            scalars
+        }
+        None => Vec::new(),
+    };
+
+    Ok((1..=parties)
+        .map(|index| MlKemKeyShare {
+            index,
+            threshold,
+            parties,
+            variant,
+            ek: ek.encode(),
+            z,
+            x25519_public: x25519.as_ref().map(|(_, pk)| *pk),
+            pieces: members
+                .iter()
+                .enumerate()
+                .map(|(i, &members)| {
+                    let held = members >> (index - 1) & 1 == 1;
+                    Piece {
+                        members,
+                        secret: held.then_some(secrets[i]),
+                        x25519: scalars.get(i).copied().filter(|_| held),
+                    }
+                })
+                .collect(),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pieces_sum_to_the_key() {
+        let seed = [7u8; 64];
+        let shares = deal_with_seed(Variant::MlKem768, 3, 4, &seed).unwrap();
+        let (ek, s_hat) = mlkem::keygen(&[7u8; 32]);
+        assert_eq!(shares[0].public_key(), ek.encode());
+        assert_eq!(shares[0].pieces.len(), 4);
+
+        let mut sum = [[0u16; N]; K];
+        for (i, piece) in shares[0].pieces.iter().enumerate() {
+            let holder = shares
+                .iter()
+                .find(|s| s.pieces[i].secret.is_some())
+                .unwrap();
+            let secret = holder.pieces[i].secret.unwrap();
+            assert_eq!(holder.pieces[i].members, piece.members);
+            sum = std::array::from_fn(|j| poly_add(&sum[j], &secret[j]));
+        }
+        assert_eq!(sum, s_hat);
+    }
+
+    #[test]
+    fn shares_round_trip() {
+        for variant in [Variant::MlKem768, Variant::X25519MlKem768] {
+            let shares = deal(variant, 3, 3).unwrap();
+            let pk = shares[0].public_key();
+            assert_eq!(pk.len(), variant.public_key_size());
+            for share in &shares {
+                let bytes = share.to_bytes();
+                let decoded = MlKemKeyShare::from_bytes(&bytes).unwrap();
+                assert_eq!(decoded.public_key(), pk);
+                assert_eq!(decoded.to_bytes(), bytes);
+            }
+        }
+    }
+
+    #[test]
+    fn malformed_shares_are_rejected() {
+        let share = deal(Variant::MlKem768, 3, 3).unwrap().remove(0);
+        let mut bytes = share.to_bytes();
+        assert!(MlKemKeyShare::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert!(MlKemKeyShare::from_bytes(b"TMLK").is_err());
+        // Claim a piece this party is not a member of.
+        *bytes.last_mut().unwrap() = 1;
+        bytes.extend_from_slice(&[0u8; 384 * K]);
+        assert!(MlKemKeyShare::from_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn unsupported_configurations_are_refused() {
+        assert!(deal(Variant::MlKem768, 1, 3).is_err());
+        assert!(deal(Variant::MlKem768, 2, 3).is_err());
+        assert!(deal(Variant::MlKem768, 5, 4).is_err());
+        assert!(deal(Variant::MlKem768, 3, 8).is_err());
+        // 5-of-6 needs C(6, 2) = 15 pieces.
+        assert!(deal(Variant::MlKem768, 5, 6).is_err());
+        assert!(deal(Variant::MlKem768, 5, 5).is_ok());
+        assert!(deal_with_seed(Variant::X25519MlKem768, 3, 3, &[0u8; 64]).is_err());
+    }
+}
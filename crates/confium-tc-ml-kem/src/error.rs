@@ -0,0 +1,55 @@
+//! Error helpers for the threshold ML-KEM-768 session.
+
+use confium_tc::error::Error as TcError;
+
+/// Threshold ML-KEM sub-codes (0x72xx). Distinct from threshold RSA's
+/// 0x70xx and threshold ML-DSA's 0x71xx so callers can disambiguate the
+/// source scheme from a numeric code alone.
+#[allow(non_camel_case_types)]
+#[repr(u32)]
+pub enum MlKemErrorCode {
+    /// A key-share blob failed to deserialize, had the wrong magic /
+    /// version, or does not match the session's scheme.
+    /// Caller action: re-run the dealer ceremony.
+    BAD_SHARE = 0x7201,
+    /// The dealer was asked for an unsupported configuration: an even
+    /// threshold or one below three, too many parties, more pieces than
+    /// the prototype supports, or a seed of the wrong length.
+    /// Caller action: pick a supported configuration.
+    BAD_KEY_PARAMETERS = 0x7202,
+    /// An encapsulation key has the wrong length or a coefficient
+    /// outside the field.
+    /// Caller action: check the key was produced for this scheme.
+    BAD_PUBLIC_KEY = 0x7203,
+    /// The session message is not a ciphertext of the key's length.
+    /// Caller action: check the ciphertext was produced for this key.
+    BAD_CIPHERTEXT = 0x7210,
+    /// Fewer than T distinct key holders joined the session.
+    /// Caller action: collect more parties before retrying.
+    BELOW_THRESHOLD = 0x7220,
+    /// The X25519 half of a hybrid ciphertext is a low-order point or
+    /// lies on the twist, so no shared secret can be derived.
+    /// Caller action: treat the ciphertext as undecryptable.
+    X25519_REJECTED = 0x7230,
+    /// Internal error — a panic-equivalent condition was caught and
+    /// converted to an error return. Indicates a bug; please open an
+    /// issue.
+    INTERNAL = 0x72FF,
+}
+
+impl From<MlKemErrorCode> for u32 {
+    #[inline]
+    fn from(c: MlKemErrorCode) -> u32 {
+        c as u32
+    }
+}
+
+/// Build a framework [`TcError`] carrying a threshold ML-KEM sub-code.
+pub fn scheme_error(code: MlKemErrorCode) -> TcError {
+    confium_tc::error::SchemeInternalSnafu {
+        code: u32::from(code),
+    }
+    .build()
+}
+
+pub type Result<T> = std::result::Result<T, TcError>;
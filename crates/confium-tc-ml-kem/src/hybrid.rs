@@ -0,0 +1,123 @@
+//! The X25519 half of the X25519+ML-KEM-768 hybrid and the X-Wing style
+//! combiner that joins the two shared secrets.
+//!
+//! The X25519 secret is shared additively in the scalar field: with
+//! `c = clamp(sk)` (a multiple of 8), the pieces `y_S` sum to `c/8 mod ℓ`
+//! and `X25519(sk, P) = c·P = (c/8)·(8·P)`. Every participant multiplies
+//! the sum of the pieces it opens (see [`crate::mpc::Layout::opens`]) by
+//! `8·P` and broadcasts the point; the sum of the broadcasts is
+//! `X25519(sk, P)`. Clearing the cofactor first keeps the scalar
+//! arithmetic in the prime-order subgroup, where reducing mod `ℓ` is
+//! exact.
+
+use curve25519_dalek::edwards::CompressedEdwardsY;
+use curve25519_dalek::montgomery::MontgomeryPoint;
+use curve25519_dalek::{EdwardsPoint, Scalar};
+use rand_core::{OsRng, RngCore};
+use sha3::Sha3_256;
+use sha3::digest::Digest;
+
+use crate::error::{MlKemErrorCode, Result, scheme_error};
+
+/// X-Wing combiner label.
+const XWING_LABEL: &[u8; 6] = b"\\.//^\\";
+
+/// The X25519 public key of `sk` and the scalar `clamp(sk)/8 mod ℓ`
+/// the dealer splits.
+pub(crate) fn split_secret(sk: &[u8; 32]) -> (Scalar, [u8; 32]) {
+    let clamped = curve25519_dalek::scalar::clamp_integer(*sk);
+    let y = Scalar::from_bytes_mod_order(clamped) * Scalar::from(8u8).invert();
+    (y, MontgomeryPoint::mul_base_clamped(*sk).to_bytes())
+}
+
+/// `8·P` for the Montgomery `u`-coordinate `ct_x`.
+///
+/// Points on the twist have no Edwards form and are refused, unlike
+/// single-party X25519, which computes on the twist; honest
+/// encapsulations never produce them.
+fn cleared(ct_x: &[u8; 32]) -> Result<EdwardsPoint> {
+    MontgomeryPoint(*ct_x)
+        .to_edwards(0)
+        .map(|p| p.mul_by_cofactor())
+        .ok_or_else(|| scheme_error(MlKemErrorCode::X25519_REJECTED))
+}
+
+/// Our partial `(Σ y_S)·8·P` over the pieces we open, compressed.
+pub(crate) fn partial<'a>(
+    pieces: impl IntoIterator<Item = &'a Scalar>,
+    ct_x: &[u8; 32],
+) -> Result<[u8; 32]> {
+    let y: Scalar = pieces.into_iter().sum();
+    Ok((y * cleared(ct_x)?).compress().to_bytes())
+}
+
+/// Parse a peer's partial.
+pub(crate) fn parse_partial(bytes: &[u8]) -> Option<EdwardsPoint> {
+    CompressedEdwardsY::from_slice(bytes).ok()?.decompress()
+}
+
+/// `X25519(sk, P)` from every participant's partial, refusing the
+/// all-zero output of a low-order `P` (RFC 7748 §6.1).
+pub(crate) fn shared_secret(partials: impl IntoIterator<Item = EdwardsPoint>) -> Result<[u8; 32]> {
+    let sum: EdwardsPoint = partials.into_iter().sum();
+    let ss = sum.to_montgomery().to_bytes();
+    if ss == [0u8; 32] {
+        return Err(scheme_error(MlKemErrorCode::X25519_REJECTED));
+    }
+    Ok(ss)
+}
+
+/// The X25519 half of an encapsulation to `pk_x`: an ephemeral public
+/// key and the shared secret.
+pub(crate) fn encapsulate(pk_x: &[u8; 32]) -> Result<([u8; 32], [u8; 32])> {
+    let mut eph = [0u8; 32];
+    OsRng.fill_bytes(&mut eph);
+    let ct_x = MontgomeryPoint::mul_base_clamped(eph).to_bytes();
+    let ss_x = MontgomeryPoint(*pk_x).mul_clamped(eph).to_bytes();
+    eph.fill(0);
+    if ss_x == [0u8; 32] {
+        return Err(scheme_error(MlKemErrorCode::X25519_REJECTED));
+    }
+    Ok((ct_x, ss_x))
+}
+
+/// `SHA3-256(ss_M ‖ ss_X ‖ ct_X ‖ pk_X ‖ label)`.
+pub(crate) fn combine(
+    ss_m: &[u8; 32],
+    ss_x: &[u8; 32],
+    ct_x: &[u8; 32],
+    pk_x: &[u8; 32],
+) -> [u8; 32] {
+    let mut h = Sha3_256::new();
+    for part in [&ss_m[..], ss_x, ct_x, pk_x, XWING_LABEL] {
+        h.update(part);
+    }
+    h.finalize().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_partials_match_x25519() {
+        let sk = [5u8; 32];
+        let (y, pk) = split_secret(&sk);
+        assert_eq!(pk, MontgomeryPoint::mul_base_clamped(sk).to_bytes());
+
+        let (ct_x, ss_x) = encapsulate(&pk).unwrap();
+        let a = Scalar::from(12345u64);
+        let b = y - a;
+        let partials = [partial([&a], &ct_x).unwrap(), partial([&b], &ct_x).unwrap()];
+        let points = partials.iter().map(|p| parse_partial(p).unwrap());
+        assert_eq!(shared_secret(points).unwrap(), ss_x);
+        assert_eq!(MontgomeryPoint(ct_x).mul_clamped(sk).to_bytes(), ss_x);
+    }
+
+    #[test]
+    fn low_order_points_are_refused() {
+        let (y, _) = split_secret(&[5u8; 32]);
+        let zero = partial([&y], &[0u8; 32]).unwrap();
+        assert!(shared_secret([parse_partial(&zero).unwrap()]).is_err());
+    }
+}
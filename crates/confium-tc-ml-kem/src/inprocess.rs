@@ -0,0 +1,46 @@
+//! In-process driver for threshold ML-KEM-768.
+//!
+//! [`keygen`] runs the trusted dealer locally and [`decapsulate`]
+//! drives the registered session through [`confium_tc::inprocess`].
+//!
+//! ## Output wire format
+//!
+//! - [`KeygenOutput::shares`] are opaque `MlKemKeyShare::to_bytes()`
+//!   blobs.
+//! - [`KeygenOutput::public_key`] is the 1184-byte FIPS 203
+//!   encapsulation key, followed by the 32-byte X25519 public key for
+//!   the hybrid.
+//! - [`decapsulate`] returns the 32-byte shared secret.
+
+use confium_tc::Result;
+use confium_tc::inprocess as driver;
+
+use crate::{Variant, dealer};
+
+/// Outcome of a dealer ceremony: N share blobs plus the public key.
+#[derive(Debug, Clone)]
+pub struct KeygenOutput {
+    /// One share blob per party, in dealer-index order.
+    pub shares: Vec<Vec<u8>>,
+    /// The encapsulation key.
+    pub public_key: Vec<u8>,
+}
+
+/// Deal a key to `party_count` parties at threshold `threshold`.
+pub fn keygen(variant: Variant, threshold: u32, party_count: u32) -> Result<KeygenOutput> {
+    let shares = dealer::deal(variant, threshold, party_count)?;
+    Ok(KeygenOutput {
+        public_key: shares[0].public_key(),
+        shares: shares.iter().map(|s| s.to_bytes()).collect(),
+    })
+}
+
+/// Threshold-decapsulate `ciphertext`; every supplied share takes part.
+pub fn decapsulate(
+    variant: Variant,
+    share_blobs: &[Vec<u8>],
+    threshold: u32,
+    ciphertext: &[u8],
+) -> Result<Vec<u8>> {
+    driver::run_sign(variant.algorithm(), share_blobs, threshold, ciphertext)
+}
@@ -0,0 +1,223 @@
+//! The per-party key share produced by the dealer.
+//!
+//! The decapsulation key `ŝ` is shared with a *replicated* sharing over
+//! `Z_q`: for every subset `S` of `n − f` parties, `f = (t − 1)/2`, the
+//! dealer picks a piece `ŝ_S` and gives it to every member of `S`, with
+//! `ŝ = Σ_S ŝ_S`. Any `f` parties miss at least one piece and learn
+//! nothing; any `t = 2f + 1` parties hold every product of two pieces
+//! between them, which is what the decapsulation circuit needs to
+//! multiply (see [`crate::mpc`]). The hybrid's X25519 key is shared the
+//! same way, as scalars `y_S` with `Σ y_S = clamp(sk)/8 mod ℓ`.
+//!
+//! ```text
+//! share: magic "TMLK" | version 1 | index u32 | threshold u32 | parties u32
+//!        | variant u8 | ek[1184] | z[32] | pk_X[32]? | pieces u32
+//!        | (members u32 | held u8 | ŝ_S[12-bit × k·256]? | y_S[32]?)*
+//! ```
+//!
+//! `pk_X` and `y_S` are present for the hybrid only. The implicit
+//! rejection seed `z` is given to every party; see [`crate::session`].
+
+use curve25519_dalek::Scalar;
+
+use crate::Variant;
+use crate::error::{MlKemErrorCode, Result, scheme_error};
+use crate::mlkem::EncapsulationKey;
+use crate::params::{EK_SIZE, K, N};
+use crate::poly::{PolyVec, byte_encode, decode_12};
+
+const SHARE_MAGIC: [u8; 4] = *b"TMLK";
+const SHARE_VERSION: u8 = 1;
+
+/// One subset's piece of the decapsulation key.
+#[derive(Clone)]
+pub(crate) struct Piece {
+    /// Bit `i − 1` set for every member `i` of the subset.
+    pub members: u32,
+    /// `ŝ_S`, present when this party is a member.
+    pub secret: Option<PolyVec>,
+    /// `y_S`, present when this party is a member of a hybrid key.
+    pub x25519: Option<Scalar>,
+}
+
+impl Piece {
+    pub fn has_member(&self, index: u32) -> bool {
+        self.members >> (index - 1) & 1 == 1
+    }
+}
+
+/// One party's share of a dealt threshold ML-KEM-768 key.
+#[derive(Clone)]
+pub struct MlKemKeyShare {
+    /// 1-based dealer index of this party.
+    pub index: u32,
+    /// Parties needed to decapsulate.
+    pub threshold: u32,
+    /// Number of parties the key was dealt to.
+    pub parties: u32,
+    /// Plain ML-KEM-768 or the X25519 hybrid.
+    pub variant: Variant,
+    /// The FIPS 203 encapsulation key.
+    pub(crate) ek: Vec<u8>,
+    /// The implicit-rejection seed.
+    pub(crate) z: [u8; 32],
+    /// The hybrid's X25519 public key.
+    pub(crate) x25519_public: Option<[u8; 32]>,
+    pub(crate) pieces: Vec<Piece>,
+}
+
+impl std::fmt::Debug for MlKemKeyShare {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MlKemKeyShare")
+            .field("index", &self.index)
+            .field("threshold", &self.threshold)
+            .field("parties", &self.parties)
+            .field("variant", &self.variant)
+            .field("pieces", &self.pieces.len())
+            .field("secret", &"<redacted>")
+            .finish_non_exhaustive()
+    }
+}
+
+impl MlKemKeyShare {
+    /// The encapsulation key: the FIPS 203 `ek`, followed by the X25519
+    /// public key for the hybrid.
+    pub fn public_key(&self) -> Vec<u8> {
+        let mut out = self.ek.clone();
+        if let Some(pk) = &self.x25519_public {
+            out.extend_from_slice(pk);
+        }
+        out
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = SHARE_MAGIC.to_vec();
+        out.push(SHARE_VERSION);
+        for word in [self.index, self.threshold, self.parties] {
+            out.extend_from_slice(&word.to_be_bytes());
+        }
+        out.push(self.variant as u8);
+        out.extend_from_slice(&self.ek);
+        out.extend_from_slice(&self.z);
+        if let Some(pk) = &self.x25519_public {
+            out.extend_from_slice(pk);
+        }
+        out.extend_from_slice(&(self.pieces.len() as u32).to_be_bytes());
+        for piece in &self.pieces {
+            out.extend_from_slice(&piece.members.to_be_bytes());
+            out.push(u8::from(piece.secret.is_some()));
+            if let Some(secret) = &piece.secret {
+                for poly in secret {
+                    byte_encode(&mut out, poly, 12);
+                }
+            }
+            if let Some(y) = &piece.x25519 {
+                out.extend_from_slice(y.as_bytes());
+            }
+        }
+        out
+    }
+
+    /// Decode a share, checking its structure: the roster, the piece
+    /// subsets, that exactly this party's pieces are held, and that
+    /// every value is canonical.
+    pub fn from_bytes(data: &[u8]) -> Result<Self> {
+        let bad = || scheme_error(MlKemErrorCode::BAD_SHARE);
+        let mut r = Reader(data);
+        if r.take(4)? != SHARE_MAGIC || r.take(1)? != [SHARE_VERSION] {
+            return Err(bad());
+        }
+        let (index, threshold, parties) = (r.u32()?, r.u32()?, r.u32()?);
+        let variant = Variant::from_u8(r.take(1)?[0]).ok_or_else(bad)?;
+        if crate::dealer::check_roster(threshold, parties).is_err()
+            || !(1..=parties).contains(&index)
+        {
+            return Err(bad());
+        }
+        let ek = r.take(EK_SIZE)?.to_vec();
+        if EncapsulationKey::parse(&ek).is_none() {
+            return Err(bad());
+        }
+        let z: [u8; 32] = r.take(32)?.try_into().map_err(|_| bad())?;
+        let hybrid = variant == Variant::X25519MlKem768;
+        let x25519_public = if hybrid {
+            Some(r.take(32)?.try_into().map_err(|_| bad())?)
+        } else {
+            None
+        };
+
+        let count = r.u32()?;
+        if count != crate::dealer::piece_count(threshold, parties) {
+            return Err(bad());
+        }
+        let size = parties - crate::dealer::privacy(threshold);
+        let mut pieces: Vec<Piece> = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            let members = r.u32()?;
+            let held = match r.take(1)?[0] {
+                0 => false,
+                1 => true,
+                _ => return Err(bad()),
+            };
+            let (secret, x25519) = if held {
+                let mut secret = [[0u16; N]; K];
+                for poly in &mut secret {
+                    *poly = decode_12(r.take(384)?).ok_or_else(bad)?;
+                }
+                let x25519 = if hybrid {
+                    let bytes: [u8; 32] = r.take(32)?.try_into().map_err(|_| bad())?;
+                    Some(Option::from(Scalar::from_canonical_bytes(bytes)).ok_or_else(bad)?)
+                } else {
+                    None
+                };
+                (Some(secret), x25519)
+            } else {
+                (None, None)
+            };
+            let piece = Piece {
+                members,
+                secret,
+                x25519,
+            };
+            if members >> parties != 0
+                || members.count_ones() != size
+                || pieces.last().is_some_and(|p| p.members >= members)
+                || piece.has_member(index) != held
+            {
+                return Err(bad());
+            }
+            pieces.push(piece);
+        }
+        if !r.0.is_empty() {
+            return Err(bad());
+        }
+        Ok(MlKemKeyShare {
+            index,
+            threshold,
+            parties,
+            variant,
+            ek,
+            z,
+            x25519_public,
+            pieces,
+        })
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.0.len() < len {
+            return Err(scheme_error(MlKemErrorCode::BAD_SHARE));
+        }
+        let (head, tail) = self.0.split_at(len);
+        self.0 = tail;
+        Ok(head)
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+}
@@ -1,21 +1,124 @@
-//! Threshold ML-KEM (FIPS 203) — research prototype.
+//! Threshold ML-KEM-768 — research prototype.
 //!
-//! **No production-quality threshold ML-KEM exists today.** This crate
-//! is a research prototype. Production use requires academic collaborator
-//! engagement (see `TODO.roadmap/26`).
+//! `T`-of-`N` decapsulation of ordinary FIPS 203 ML-KEM-768
+//! ciphertexts: any standard encapsulator (for example
+//! `confium-rustcrypto-plugin`'s `kem::encapsulate`) produces
+//! ciphertexts for the dealt key, and the parties jointly recover the
+//! same shared secret single-party decapsulation would, including the
+//! implicit-rejection value for invalid ciphertexts.
 //!
-//! Research questions:
-//! - Proactive share refresh for lattice schemes
-//! - Threshold decryption ceremony with audit trail
-//! - Re-encryption for quorum composition changes
-//! - Composition with AEAD for symmetric encryption
-//! - Cross-tier re-encryption (IA → BIML without plaintext exposure)
+//! - **Trusted-dealer keygen** — [`dealer::deal`] splits `ŝ` with a
+//!   replicated sharing, one piece per subset of `N − (T − 1)/2`
+//!   parties; see [`keys`].
+//! - **MPC decapsulation** — the decryption is linear and local; the
+//!   rounding to message bits, the `G` and `PRF` Keccak calls, the
+//!   re-encryption and the Fujisaki–Okamoto comparison run as a
+//!   semi-honest honest-majority circuit, so neither the message nor
+//!   the re-encryption check's intermediate values are revealed. Only
+//!   the check bit and the final shared secret are opened; see
+//!   [`session`].
+//! - **X25519 hybrid** — [`HYBRID_ALGORITHM`] adds an X25519 key shared
+//!   in the scalar field and combines the two secrets as X-Wing does,
+//!   matching `confium-rustcrypto-plugin`'s `x25519-mlkem768`.
+//!
+//! Both variants are registered as [`confium_tc::registry::TcSchemeKind::Kem`]
+//! schemes, so [`confium_tc::kem::KemSession`] can drive them. The
+//! prototype supports odd thresholds `T ≥ 3` with at most ten pieces
+//! (`3`-of-`N` up to seven parties, `5`-of-`5`) and is not secure
+//! against malicious parties; it must not protect real keys.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use confium_tc_ml_kem::{Variant, inprocess, scheme};
+//!
+//! let kg = inprocess::keygen(Variant::MlKem768, 3, 4)?;
+//! let (ciphertext, secret) = scheme::encapsulate(Variant::MlKem768, &kg.public_key)?;
+//! let recovered = inprocess::decapsulate(Variant::MlKem768, &kg.shares[1..], 3, &ciphertext)?;
+//! assert_eq!(recovered, secret);
+//! # Ok::<(), confium_tc::Error>(())
+//! ```
+//!
+//! Not yet addressed: proactive share refresh, a distributed key
+//! generation, and security against malicious parties.
 
 #![forbid(unsafe_code)]
 #![allow(missing_docs)] // TODO: document before 1.0
 
+mod circuits;
+pub mod dealer;
+pub mod error;
+mod hybrid;
+pub mod inprocess;
+pub mod keys;
+mod mlkem;
+mod mpc;
+pub mod params;
+mod poly;
+pub mod scheme;
+pub mod session;
+
+pub use keys::MlKemKeyShare;
+pub use scheme::{MlKem768Threshold, X25519MlKem768Threshold};
+
 use serde::{Deserialize, Serialize};
 
+/// Algorithm identifier of threshold ML-KEM-768; also the registered
+/// scheme name.
+pub const ALGORITHM: &str = "ML-KEM-768-threshold";
+
+/// Algorithm identifier of the threshold X25519+ML-KEM-768 hybrid.
+pub const HYBRID_ALGORITHM: &str = "X25519-ML-KEM-768-threshold";
+
+/// Which KEM a threshold key decapsulates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Variant {
+    /// Plain ML-KEM-768.
+    MlKem768 = 0,
+    /// X25519+ML-KEM-768 with the X-Wing combiner.
+    X25519MlKem768 = 1,
+}
+
+impl Variant {
+    pub fn from_u8(b: u8) -> Option<Self> {
+        match b {
+            0 => Some(Variant::MlKem768),
+            1 => Some(Variant::X25519MlKem768),
+            _ => None,
+        }
+    }
+
+    /// The registered scheme name.
+    pub fn algorithm(self) -> &'static str {
+        match self {
+            Variant::MlKem768 => ALGORITHM,
+            Variant::X25519MlKem768 => HYBRID_ALGORITHM,
+        }
+    }
+
+    /// Dealer seed length: `d ‖ z`, plus the X25519 secret key.
+    pub fn seed_len(self) -> usize {
+        match self {
+            Variant::MlKem768 => 64,
+            Variant::X25519MlKem768 => 96,
+        }
+    }
+
+    pub fn public_key_size(self) -> usize {
+        match self {
+            Variant::MlKem768 => params::EK_SIZE,
+            Variant::X25519MlKem768 => params::EK_SIZE + 32,
+        }
+    }
+
+    pub fn ciphertext_size(self) -> usize {
+        match self {
+            Variant::MlKem768 => params::CT_SIZE,
+            Variant::X25519MlKem768 => params::CT_SIZE + 32,
+        }
+    }
+}
+
 /// ML-KEM parameter sets (FIPS 203).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
@@ -0,0 +1,204 @@
+//! The single-party FIPS 203 pieces the threshold protocol shares with
+//! plain ML-KEM-768: the hash functions, key generation, K-PKE
+//! encryption and encapsulation.
+//!
+//! Decapsulation is only ever run by the threshold session; the
+//! single-party version here exists for the tests.
+
+use sha3::digest::{Digest, ExtendableOutput, Update, XofReader};
+use sha3::{Sha3_256, Sha3_512, Shake256};
+
+use crate::params::{CT_SIZE, DU, DV, EK_SIZE, ETA1, K, N};
+use crate::poly::{
+    Matrix, Poly, PolyVec, byte_decode, byte_encode, cbd2, compress, decode_12, decompress,
+    dot_ntt, expand_a, inv_ntt, ntt, poly_add,
+};
+
+/// `H(s) = SHA3-256(s)`.
+pub fn h(s: &[u8]) -> [u8; 32] {
+    Sha3_256::digest(s).into()
+}
+
+/// `G(s) = SHA3-512(s)`, split into two 32-byte halves.
+pub fn g(parts: &[&[u8]]) -> ([u8; 32], [u8; 32]) {
+    let mut hasher = Sha3_512::new();
+    for part in parts {
+        Digest::update(&mut hasher, part);
+    }
+    let out = hasher.finalize();
+    (
+        out[..32].try_into().expect("64-byte digest"),
+        out[32..].try_into().expect("64-byte digest"),
+    )
+}
+
+/// `J(s) = SHAKE256(s, 32)`.
+pub fn j(parts: &[&[u8]]) -> [u8; 32] {
+    let mut xof = Shake256::default();
+    for part in parts {
+        xof.update(part);
+    }
+    let mut out = [0u8; 32];
+    xof.finalize_xof().read(&mut out);
+    out
+}
+
+/// `SamplePolyCBD_2(PRF_2(s, b))`.
+pub fn prf_cbd(s: &[u8; 32], b: u8) -> Poly {
+    let mut xof = Shake256::default();
+    xof.update(s);
+    xof.update(&[b]);
+    let mut bytes = [0u8; 64 * ETA1];
+    xof.finalize_xof().read(&mut bytes);
+    cbd2(&bytes)
+}
+
+/// A parsed encapsulation key.
+pub struct EncapsulationKey {
+    pub t_hat: PolyVec,
+    pub rho: [u8; 32],
+    pub a_hat: Matrix,
+}
+
+impl EncapsulationKey {
+    /// Parse `ek`, applying the FIPS 203 §7.2 modulus check.
+    pub fn parse(ek: &[u8]) -> Option<Self> {
+        if ek.len() != EK_SIZE {
+            return None;
+        }
+        let mut t_hat = [[0u16; N]; K];
+        for (i, chunk) in ek[..384 * K].chunks(384).enumerate() {
+            t_hat[i] = decode_12(chunk)?;
+        }
+        let rho: [u8; 32] = ek[384 * K..].try_into().ok()?;
+        Some(EncapsulationKey {
+            t_hat,
+            a_hat: expand_a(&rho),
+            rho,
+        })
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(EK_SIZE);
+        for poly in &self.t_hat {
+            byte_encode(&mut out, poly, 12);
+        }
+        out.extend_from_slice(&self.rho);
+        out
+    }
+
+    /// `(u, v)` before compression for noise `(r, e1, e2)` and the
+    /// decompressed message `μ` — the linear core of K-PKE.Encrypt.
+    pub fn encrypt_raw(&self, r: &PolyVec, e1: &PolyVec, e2: &Poly, mu: &Poly) -> (PolyVec, Poly) {
+        let r_hat = r.map(|p| ntt(&p));
+        let u = std::array::from_fn(|i| {
+            let column = std::array::from_fn(|j| self.a_hat[j][i]);
+            poly_add(&inv_ntt(&dot_ntt(&column, &r_hat)), &e1[i])
+        });
+        let v = poly_add(&poly_add(&inv_ntt(&dot_ntt(&self.t_hat, &r_hat)), e2), mu);
+        (u, v)
+    }
+
+    /// K-PKE.Encrypt (FIPS 203 Algorithm 14).
+    pub fn encrypt(&self, m: &[u8; 32], seed: &[u8; 32]) -> Vec<u8> {
+        let r = std::array::from_fn(|i| prf_cbd(seed, i as u8));
+        let e1 = std::array::from_fn(|i| prf_cbd(seed, (K + i) as u8));
+        let e2 = prf_cbd(seed, 2 * K as u8);
+        let mu = message_poly(m);
+        let (u, v) = self.encrypt_raw(&r, &e1, &e2, &mu);
+        let mut ct = Vec::with_capacity(CT_SIZE);
+        for poly in &u {
+            byte_encode(&mut ct, &poly.map(|x| compress(x, DU)), DU);
+        }
+        byte_encode(&mut ct, &v.map(|x| compress(x, DV)), DV);
+        ct
+    }
+}
+
+/// `Decompress_1(ByteDecode_1(m))`.
+pub fn message_poly(m: &[u8; 32]) -> Poly {
+    let bits = byte_decode(m, 1, N);
+    std::array::from_fn(|i| decompress(bits[i], 1))
+}
+
+/// `(Decompress_du(c1), Decompress_dv(c2))` of a well-sized ciphertext.
+pub fn decompress_ciphertext(ct: &[u8]) -> (PolyVec, Poly) {
+    let (c1, c2) = ct.split_at(32 * DU as usize * K);
+    let u = std::array::from_fn(|i| {
+        let chunk = &c1[i * 32 * DU as usize..(i + 1) * 32 * DU as usize];
+        let values = byte_decode(chunk, DU, N);
+        std::array::from_fn(|j| decompress(values[j], DU))
+    });
+    let values = byte_decode(c2, DV, N);
+    let v = std::array::from_fn(|j| decompress(values[j], DV));
+    (u, v)
+}
+
+/// ML-KEM.KeyGen_internal (FIPS 203 Algorithms 13 and 16), returning
+/// the encapsulation key and `ŝ`.
+pub fn keygen(d: &[u8; 32]) -> (EncapsulationKey, PolyVec) {
+    let (rho, sigma) = g(&[d, &[K as u8]]);
+    let s = std::array::from_fn(|i| prf_cbd(&sigma, i as u8));
+    let e: PolyVec = std::array::from_fn(|i| prf_cbd(&sigma, (K + i) as u8));
+    let s_hat: PolyVec = s.map(|p| ntt(&p));
+    let a_hat = expand_a(&rho);
+    let t_hat = std::array::from_fn(|i| poly_add(&dot_ntt(&a_hat[i], &s_hat), &ntt(&e[i])));
+    (EncapsulationKey { t_hat, rho, a_hat }, s_hat)
+}
+
+/// ML-KEM.Encaps_internal (FIPS 203 Algorithm 17).
+pub fn encapsulate_with(ek: &EncapsulationKey, m: &[u8; 32]) -> (Vec<u8>, [u8; 32]) {
+    let (key, seed) = g(&[m, &h(&ek.encode())]);
+    (ek.encrypt(m, &seed), key)
+}
+
+/// ML-KEM.Decaps_internal (FIPS 203 Algorithm 18).
+#[cfg(test)]
+pub fn decapsulate(ek: &EncapsulationKey, s_hat: &PolyVec, z: &[u8; 32], ct: &[u8]) -> [u8; 32] {
+    use crate::params::Q;
+    use crate::poly::poly_sub;
+
+    let (u, v) = decompress_ciphertext(ct);
+    let u_hat = u.map(|p| ntt(&p));
+    let w = poly_sub(&v, &inv_ntt(&dot_ntt(s_hat, &u_hat)));
+    let mut m = Vec::new();
+    byte_encode(&mut m, &w.map(|x| compress(x % Q, 1)), 1);
+    let m: [u8; 32] = m.try_into().expect("32 bytes");
+    let (key, seed) = g(&[&m, &h(&ek.encode())]);
+    if ek.encrypt(&m, &seed) == ct {
+        key
+    } else {
+        j(&[z, ct])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encapsulation_round_trips() {
+        let (ek, s_hat) = keygen(&[7u8; 32]);
+        let encoded = ek.encode();
+        assert_eq!(encoded.len(), EK_SIZE);
+        let ek = EncapsulationKey::parse(&encoded).unwrap();
+        let (ct, key) = encapsulate_with(&ek, &[9u8; 32]);
+        assert_eq!(ct.len(), CT_SIZE);
+        assert_eq!(decapsulate(&ek, &s_hat, &[1u8; 32], &ct), key);
+
+        let mut forged = ct.clone();
+        forged[0] ^= 1;
+        assert_eq!(
+            decapsulate(&ek, &s_hat, &[1u8; 32], &forged),
+            j(&[&[1u8; 32], &forged])
+        );
+    }
+
+    #[test]
+    fn keys_outside_the_field_are_rejected() {
+        let mut ek = keygen(&[7u8; 32]).0.encode();
+        ek[0] = 0xFF;
+        ek[1] |= 0x0F;
+        assert!(EncapsulationKey::parse(&ek).is_none());
+    }
+}
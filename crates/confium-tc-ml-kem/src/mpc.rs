@@ -0,0 +1,518 @@
+//! Replicated secret sharing and the multiplication layers of the
+//! decapsulation circuit.
+//!
+//! A value is split into pieces `x = Σ_S x_S`, one per subset `S` of
+//! `n − f` parties (see [`crate::keys`]), and every member of `S` holds
+//! `x_S`. Linear operations and public constants are local; a public
+//! constant is added to piece 0 by all of its holders.
+//!
+//! ## Multiplication
+//!
+//! For `x·y = Σ_{S,S'} x_S·y_{S'}` every term is computed by the first
+//! participant holding both pieces, which exists whenever at least
+//! `2f + 1` parties take part. Each participant then reshares its sum
+//! of terms: it draws random values for every piece summing to it and
+//! sends each other participant the values of the pieces that
+//! participant holds. The pieces of the product are the sums of what
+//! was received. `f` colluders miss the piece indexed by the complement
+//! of their set, so each honest reshare looks uniform to them.
+//!
+//! ## Opening
+//!
+//! Every participant broadcasts the sum of the pieces it is the first
+//! holder of, and everyone adds the broadcasts.
+//!
+//! ## Replay
+//!
+//! Circuits are straight-line code over a [`Ctx`]. Each session round
+//! replays the circuit from the start: layers finished in earlier
+//! rounds are served from the transcript, and the first unfinished
+//! layer queues its messages and stops the replay with
+//! [`Interrupt::Suspend`].
+
+use rand_core::{OsRng, RngCore};
+
+use crate::params::Q;
+use crate::poly;
+
+/// The pieces of the sharing and who takes part in the session.
+pub(crate) struct Layout {
+    /// Members of every piece, bit `i − 1` for dealer index `i`.
+    pieces: Vec<u32>,
+    /// Dealer indices of the participants, in roster order.
+    participants: Vec<u32>,
+    /// Our position in `participants`.
+    me: usize,
+    /// The pieces we hold, in increasing order.
+    held: Vec<usize>,
+    /// Pairs of positions in `held` whose product term is ours.
+    terms: Vec<(usize, usize)>,
+}
+
+impl Layout {
+    /// `None` when some pair of pieces has no participant holding both.
+    pub fn new(pieces: Vec<u32>, participants: Vec<u32>, me: usize) -> Option<Self> {
+        let holds = |p: usize, piece: usize| pieces[piece] >> (participants[p] - 1) & 1 == 1;
+        let held: Vec<usize> = (0..pieces.len()).filter(|&i| holds(me, i)).collect();
+        let mut terms = Vec::new();
+        for i in 0..pieces.len() {
+            for j in 0..pieces.len() {
+                let owner = (0..participants.len()).find(|&p| holds(p, i) && holds(p, j))?;
+                if owner == me {
+                    let pos = |piece| held.iter().position(|&h| h == piece);
+                    terms.push((pos(i)?, pos(j)?));
+                }
+            }
+        }
+        Some(Layout {
+            pieces,
+            participants,
+            me,
+            held,
+            terms,
+        })
+    }
+
+    /// A single party holding the only piece, so every circuit runs
+    /// locally.
+    #[cfg(test)]
+    pub fn single() -> Self {
+        Self::new(vec![1], vec![1], 0).expect("one piece")
+    }
+
+    pub fn piece_count(&self) -> usize {
+        self.pieces.len()
+    }
+
+    pub fn held(&self) -> &[usize] {
+        &self.held
+    }
+
+    pub fn participants(&self) -> usize {
+        self.participants.len()
+    }
+
+    fn holds(&self, participant: usize, piece: usize) -> bool {
+        self.pieces[piece] >> (self.participants[participant] - 1) & 1 == 1
+    }
+
+    /// Whether we are the first participant holding `piece`.
+    pub fn opens(&self, piece: usize) -> bool {
+        (0..self.participants.len()).find(|&p| self.holds(p, piece)) == Some(self.me)
+    }
+}
+
+/// Local values of `GF(2)^64` (xor, and) or of `Z_q`.
+pub(crate) trait Ring: Copy + Default + PartialEq {
+    const BYTES: usize;
+    fn add(self, other: Self) -> Self;
+    fn sub(self, other: Self) -> Self;
+    fn mul(self, other: Self) -> Self;
+    fn random(rng: &mut OsRng) -> Self;
+    fn put(self, out: &mut Vec<u8>);
+    fn get(bytes: &[u8]) -> Option<Self>;
+    fn wrap(parts: Vec<Vec<Self>>) -> Layer;
+    fn unwrap(layer: &Layer) -> Option<&Vec<Vec<Self>>>;
+}
+
+impl Ring for u64 {
+    const BYTES: usize = 8;
+    fn add(self, other: Self) -> Self {
+        self ^ other
+    }
+    fn sub(self, other: Self) -> Self {
+        self ^ other
+    }
+    fn mul(self, other: Self) -> Self {
+        self & other
+    }
+    fn random(rng: &mut OsRng) -> Self {
+        rng.next_u64()
+    }
+    fn put(self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.to_be_bytes());
+    }
+    fn get(bytes: &[u8]) -> Option<Self> {
+        Some(u64::from_be_bytes(bytes.try_into().ok()?))
+    }
+    fn wrap(parts: Vec<Vec<Self>>) -> Layer {
+        Layer::Bits(parts)
+    }
+    fn unwrap(layer: &Layer) -> Option<&Vec<Vec<Self>>> {
+        match layer {
+            Layer::Bits(parts) => Some(parts),
+            Layer::Field(_) => None,
+        }
+    }
+}
+
+impl Ring for u16 {
+    const BYTES: usize = 2;
+    fn add(self, other: Self) -> Self {
+        poly::add(self, other)
+    }
+    fn sub(self, other: Self) -> Self {
+        poly::sub(self, other)
+    }
+    fn mul(self, other: Self) -> Self {
+        poly::mul(self, other)
+    }
+    fn random(rng: &mut OsRng) -> Self {
+        loop {
+            let x = rng.next_u32() as u16 & 0x0FFF;
+            if x < Q {
+                return x;
+            }
+        }
+    }
+    fn put(self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.to_be_bytes());
+    }
+    fn get(bytes: &[u8]) -> Option<Self> {
+        Some(u16::from_be_bytes(bytes.try_into().ok()?)).filter(|&x| x < Q)
+    }
+    fn wrap(parts: Vec<Vec<Self>>) -> Layer {
+        Layer::Field(parts)
+    }
+    fn unwrap(layer: &Layer) -> Option<&Vec<Vec<Self>>> {
+        match layer {
+            Layer::Field(parts) => Some(parts),
+            Layer::Bits(_) => None,
+        }
+    }
+}
+
+/// Our pieces of a shared vector, in [`Layout::held`] order.
+#[derive(Clone)]
+pub(crate) struct Shared<R> {
+    parts: Vec<Vec<R>>,
+}
+
+impl<R: Ring> Shared<R> {
+    pub fn from_parts(parts: Vec<Vec<R>>) -> Self {
+        Shared { parts }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parts.first().map_or(0, Vec::len)
+    }
+
+    /// Apply the same linear map to every piece.
+    pub fn map<S: Ring>(&self, f: impl Fn(&[R]) -> Vec<S>) -> Shared<S> {
+        Shared {
+            parts: self.parts.iter().map(|p| f(p)).collect(),
+        }
+    }
+
+    pub fn add(&self, other: &Self) -> Self {
+        let parts = self.parts.iter().zip(&other.parts);
+        Shared {
+            parts: parts
+                .map(|(a, b)| a.iter().zip(b).map(|(&x, &y)| x.add(y)).collect())
+                .collect(),
+        }
+    }
+
+    pub fn sub(&self, other: &Self) -> Self {
+        let parts = self.parts.iter().zip(&other.parts);
+        Shared {
+            parts: parts
+                .map(|(a, b)| a.iter().zip(b).map(|(&x, &y)| x.sub(y)).collect())
+                .collect(),
+        }
+    }
+
+    /// Multiply element-wise by a public vector.
+    pub fn scale(&self, public: &[R]) -> Self {
+        self.map(|p| p.iter().zip(public).map(|(&x, &c)| x.mul(c)).collect())
+    }
+
+    pub fn slice(&self, range: std::ops::Range<usize>) -> Self {
+        self.map(|p| p[range.clone()].to_vec())
+    }
+
+    pub fn concat(items: &[&Self]) -> Self {
+        let count = items.first().map_or(0, |s| s.parts.len());
+        Shared {
+            parts: (0..count)
+                .map(|h| {
+                    items
+                        .iter()
+                        .flat_map(|s| s.parts[h].iter().copied())
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Why a replay stopped before the end of the circuit.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Interrupt {
+    /// The next layer's messages are queued in [`Ctx::frontier`].
+    Suspend,
+    /// A finished layer does not fit the step replaying it — a bug.
+    Diverged,
+}
+
+/// The finished result of a layer, or our running sum of an
+/// unfinished one.
+#[derive(Clone)]
+pub(crate) enum Layer {
+    Bits(Vec<Vec<u64>>),
+    Field(Vec<Vec<u16>>),
+}
+
+impl Layer {
+    /// Add a peer's message body of the same shape.
+    pub fn absorb(&mut self, body: &[u8]) -> bool {
+        fn absorb<R: Ring>(parts: &mut [Vec<R>], body: &[u8]) -> bool {
+            let count: usize = parts.iter().map(Vec::len).sum();
+            if body.len() != count * R::BYTES {
+                return false;
+            }
+            let mut chunks = body.chunks(R::BYTES);
+            for x in parts.iter_mut().flatten() {
+                match chunks.next().and_then(R::get) {
+                    Some(y) => *x = x.add(y),
+                    None => return false,
+                }
+            }
+            true
+        }
+        match self {
+            Layer::Bits(parts) => absorb(parts, body),
+            Layer::Field(parts) => absorb(parts, body),
+        }
+    }
+}
+
+/// The messages of the first unfinished layer.
+pub(crate) struct Frontier {
+    pub index: usize,
+    /// Openings are broadcast; reshares are sent to each participant.
+    pub broadcast: bool,
+    /// Our own contribution, to which the peers' bodies are added.
+    pub acc: Layer,
+    /// `(participant position, body)`; `None` for a broadcast.
+    pub outgoing: Vec<(Option<usize>, Vec<u8>)>,
+}
+
+/// One replay of a circuit.
+pub(crate) struct Ctx<'a> {
+    layout: &'a Layout,
+    transcript: &'a [Layer],
+    next: usize,
+    pub frontier: Option<Frontier>,
+}
+
+fn encode<R: Ring>(values: impl IntoIterator<Item = R>) -> Vec<u8> {
+    let mut out = Vec::new();
+    for x in values {
+        x.put(&mut out);
+    }
+    out
+}
+
+impl<'a> Ctx<'a> {
+    pub fn new(layout: &'a Layout, transcript: &'a [Layer]) -> Self {
+        Ctx {
+            layout,
+            transcript,
+            next: 0,
+            frontier: None,
+        }
+    }
+
+    pub fn layout(&self) -> &Layout {
+        self.layout
+    }
+
+    /// A sharing of the public vector `values`.
+    pub fn constant<R: Ring>(&self, values: &[R]) -> Shared<R> {
+        let zero = Shared {
+            parts: vec![vec![R::default(); values.len()]; self.layout.held.len()],
+        };
+        self.add_public(&zero, values)
+    }
+
+    pub fn add_public<R: Ring>(&self, x: &Shared<R>, values: &[R]) -> Shared<R> {
+        let mut out = x.clone();
+        if self.layout.held.first() == Some(&0) {
+            for (a, &c) in out.parts[0].iter_mut().zip(values) {
+                *a = a.add(c);
+            }
+        }
+        out
+    }
+
+    /// One summand per piece: piece `i`'s summand carries `f(x_i)` in
+    /// piece `i` and zero elsewhere. Summing them gives back `x` in any
+    /// representation `f` maps to, which is how sharings move between
+    /// rings.
+    pub fn summands<R: Ring, S: Ring>(
+        &self,
+        x: &Shared<R>,
+        out_len: usize,
+        f: impl Fn(&[R]) -> Vec<S>,
+    ) -> Vec<Shared<S>> {
+        (0..self.layout.piece_count())
+            .map(|piece| Shared {
+                parts: self
+                    .layout
+                    .held
+                    .iter()
+                    .zip(&x.parts)
+                    .map(|(&h, part)| {
+                        if h == piece {
+                            f(part)
+                        } else {
+                            vec![S::default(); out_len]
+                        }
+                    })
+                    .collect(),
+            })
+            .collect()
+    }
+
+    /// The finished layer the replay has reached, or `None` at the
+    /// frontier.
+    fn replayed<R: Ring>(&mut self) -> Result<Option<Vec<Vec<R>>>, Interrupt> {
+        let index = self.next;
+        self.next += 1;
+        match self.transcript.get(index) {
+            Some(layer) => R::unwrap(layer)
+                .cloned()
+                .map(Some)
+                .ok_or(Interrupt::Diverged),
+            None => Ok(None),
+        }
+    }
+
+    /// Element-wise product of two sharings.
+    pub fn mul<R: Ring>(&mut self, x: &Shared<R>, y: &Shared<R>) -> Result<Shared<R>, Interrupt> {
+        if let Some(parts) = self.replayed()? {
+            return Ok(Shared { parts });
+        }
+        let len = x.len();
+        let mut sum = vec![R::default(); len];
+        for &(i, j) in &self.layout.terms {
+            for (s, (&a, &b)) in sum.iter_mut().zip(x.parts[i].iter().zip(&y.parts[j])) {
+                *s = s.add(a.mul(b));
+            }
+        }
+
+        // Reshare: random values for all pieces but the last, which
+        // makes up the sum.
+        let count = self.layout.piece_count();
+        let mut rng = OsRng;
+        let mut fresh: Vec<Vec<R>> = (0..count - 1)
+            .map(|_| (0..len).map(|_| R::random(&mut rng)).collect())
+            .collect();
+        let last = fresh.iter().fold(sum, |acc, v| {
+            acc.iter().zip(v).map(|(&a, &b)| a.sub(b)).collect()
+        });
+        fresh.push(last);
+
+        let outgoing = (0..self.layout.participants())
+            .filter(|&p| p != self.layout.me)
+            .map(|p| {
+                let pieces = (0..count).filter(|&i| self.layout.holds(p, i));
+                let body = encode(pieces.flat_map(|i| fresh[i].iter().copied()));
+                (Some(p), body)
+            })
+            .collect();
+        let own = self.layout.held.iter().map(|&i| fresh[i].clone()).collect();
+        self.suspend(false, R::wrap(own), outgoing)
+    }
+
+    /// Reveal a sharing to every participant.
+    pub fn open<R: Ring>(&mut self, x: &Shared<R>) -> Result<Vec<R>, Interrupt> {
+        if let Some(mut parts) = self.replayed()? {
+            return parts.pop().ok_or(Interrupt::Diverged);
+        }
+        let mut sum = vec![R::default(); x.len()];
+        for (&piece, part) in self.layout.held.iter().zip(&x.parts) {
+            if self.layout.opens(piece) {
+                for (s, &v) in sum.iter_mut().zip(part) {
+                    *s = s.add(v);
+                }
+            }
+        }
+        let body = encode(sum.iter().copied());
+        self.suspend(true, R::wrap(vec![sum]), vec![(None, body)])
+    }
+
+    fn suspend<T>(
+        &mut self,
+        broadcast: bool,
+        acc: Layer,
+        outgoing: Vec<(Option<usize>, Vec<u8>)>,
+    ) -> Result<T, Interrupt> {
+        self.frontier = Some(Frontier {
+            index: self.next - 1,
+            broadcast,
+            acc,
+            outgoing,
+        });
+        Err(Interrupt::Suspend)
+    }
+}
+
+/// Run `circuit` to the end on a single-party layout, finishing each
+/// layer from our own contribution alone.
+#[cfg(test)]
+pub(crate) fn run_local<T>(
+    layout: &Layout,
+    circuit: impl Fn(&mut Ctx) -> Result<T, Interrupt>,
+) -> T {
+    let mut transcript = Vec::new();
+    loop {
+        let mut ctx = Ctx::new(layout, &transcript);
+        match circuit(&mut ctx) {
+            Ok(out) => return out,
+            Err(Interrupt::Suspend) => {
+                let frontier = ctx.frontier.expect("suspended at a frontier");
+                transcript.push(frontier.acc);
+            }
+            Err(Interrupt::Diverged) => panic!("replay diverged"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn products_and_openings_are_exact() {
+        let layout = Layout::single();
+        let (bits, field) = run_local(&layout, |ctx| {
+            let a = ctx.constant(&[0b1100u64, u64::MAX]);
+            let b = ctx.constant(&[0b1010u64, 7]);
+            let ab = ctx.mul(&a, &b)?;
+            let x = ctx.constant(&[3000u16, 2]);
+            let xx = ctx.mul(&x, &x)?;
+            Ok((ctx.open(&ab)?, ctx.open(&xx)?))
+        });
+        assert_eq!(bits, vec![0b1000, 7]);
+        assert_eq!(field, vec![(3000 * 3000 % 3329) as u16, 4]);
+    }
+
+    #[test]
+    fn every_pair_of_pieces_has_a_holder() {
+        // 3-of-4: pieces are the 3-subsets of four parties.
+        let pieces: Vec<u32> = (0u32..16).filter(|m| m.count_ones() == 3).collect();
+        for absent in 1..=4u32 {
+            let present: Vec<u32> = (1..=4).filter(|&i| i != absent).collect();
+            let mut terms = 0;
+            for me in 0..3 {
+                let layout = Layout::new(pieces.clone(), present.clone(), me).unwrap();
+                terms += layout.terms.len();
+            }
+            assert_eq!(terms, 16);
+        }
+        // Two of four parties cannot multiply.
+        assert!(Layout::new(pieces, vec![1, 2], 0).is_none());
+    }
+}
@@ -0,0 +1,23 @@
+//! ML-KEM-768 parameter set (FIPS 203, Table 2).
+
+/// The modulus `q = 3329`.
+pub const Q: u16 = 3329;
+/// Ring degree.
+pub const N: usize = 256;
+/// Module rank.
+pub const K: usize = 3;
+/// CBD parameter of the secret and of `r`.
+pub const ETA1: usize = 2;
+/// CBD parameter of the encryption noise.
+pub const ETA2: usize = 2;
+/// Bits per coefficient of the compressed `u`.
+pub const DU: u32 = 10;
+/// Bits per coefficient of the compressed `v`.
+pub const DV: u32 = 4;
+
+/// Encapsulation key `ByteEncode12(t̂) ‖ ρ`.
+pub const EK_SIZE: usize = 384 * K + 32;
+/// Ciphertext `c1 ‖ c2`.
+pub const CT_SIZE: usize = 32 * (DU as usize * K + DV as usize);
+/// Shared secret.
+pub const SS_SIZE: usize = 32;
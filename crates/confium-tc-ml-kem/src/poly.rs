@@ -0,0 +1,287 @@
+//! Arithmetic in `R_q = Z_q[X]/(X^256 + 1)` and the FIPS 203 sampling,
+//! compression and encoding helpers built on it.
+//!
+//! Coefficients are kept in `[0, q)`. The NTT follows FIPS 203
+//! Algorithms 9–12 with plain `u32` reduction; this is a prototype and
+//! none of it is constant time.
+
+use sha3::Shake128;
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+
+use crate::params::{K, N, Q};
+
+/// A polynomial of `R_q`, or its NTT representation.
+pub type Poly = [u16; N];
+/// A length-`k` vector.
+pub type PolyVec = [Poly; K];
+/// The public matrix `Â`, indexed `[i][j]` as in FIPS 203.
+pub type Matrix = [[Poly; K]; K];
+
+/// `ζ^BitRev7(i)` for the 256th root of unity `ζ = 17`.
+const ZETAS: [u16; 128] = zetas();
+
+const fn pow17(mut e: u32) -> u16 {
+    let mut base = 17u32;
+    let mut acc = 1u32;
+    while e > 0 {
+        if e & 1 == 1 {
+            acc = acc * base % Q as u32;
+        }
+        base = base * base % Q as u32;
+        e >>= 1;
+    }
+    acc as u16
+}
+
+const fn zetas() -> [u16; 128] {
+    let mut out = [0u16; 128];
+    let mut i = 0;
+    while i < 128 {
+        out[i] = pow17(((i as u8).reverse_bits() >> 1) as u32);
+        i += 1;
+    }
+    out
+}
+
+/// `128^-1 mod q`.
+const N_INV: u32 = 3303;
+
+pub fn add(a: u16, b: u16) -> u16 {
+    let s = a + b;
+    if s >= Q { s - Q } else { s }
+}
+
+pub fn sub(a: u16, b: u16) -> u16 {
+    if a >= b { a - b } else { a + Q - b }
+}
+
+pub fn mul(a: u16, b: u16) -> u16 {
+    (a as u32 * b as u32 % Q as u32) as u16
+}
+
+pub fn poly_add(a: &Poly, b: &Poly) -> Poly {
+    std::array::from_fn(|i| add(a[i], b[i]))
+}
+
+pub fn poly_sub(a: &Poly, b: &Poly) -> Poly {
+    std::array::from_fn(|i| sub(a[i], b[i]))
+}
+
+/// FIPS 203 Algorithm 9.
+pub fn ntt(f: &Poly) -> Poly {
+    let mut f = *f;
+    let mut i = 1;
+    let mut len = 128;
+    while len >= 2 {
+        for start in (0..N).step_by(2 * len) {
+            let zeta = ZETAS[i];
+            i += 1;
+            for j in start..start + len {
+                let t = mul(zeta, f[j + len]);
+                f[j + len] = sub(f[j], t);
+                f[j] = add(f[j], t);
+            }
+        }
+        len /= 2;
+    }
+    f
+}
+
+/// FIPS 203 Algorithm 10.
+pub fn inv_ntt(f: &Poly) -> Poly {
+    let mut f = *f;
+    let mut i = 127;
+    let mut len = 2;
+    while len <= 128 {
+        for start in (0..N).step_by(2 * len) {
+            let zeta = ZETAS[i];
+            i -= 1;
+            for j in start..start + len {
+                let t = f[j];
+                f[j] = add(t, f[j + len]);
+                f[j + len] = mul(zeta, sub(f[j + len], t));
+            }
+        }
+        len *= 2;
+    }
+    f.map(|x| (x as u32 * N_INV % Q as u32) as u16)
+}
+
+/// `ζ^(2·BitRev7(i)+1)`, the moduli of the degree-2 factors.
+const GAMMAS: [u16; 128] = gammas();
+
+const fn gammas() -> [u16; 128] {
+    let mut out = [0u16; 128];
+    let mut i = 0;
+    while i < 128 {
+        out[i] = pow17(2 * ((i as u8).reverse_bits() >> 1) as u32 + 1);
+        i += 1;
+    }
+    out
+}
+
+/// `f̂ ∘ ĝ` — FIPS 203 Algorithms 11–12.
+pub fn mul_ntt(f: &Poly, g: &Poly) -> Poly {
+    let mut h = [0u16; N];
+    for (i, &gamma) in GAMMAS.iter().enumerate() {
+        let (a0, a1, b0, b1) = (f[2 * i], f[2 * i + 1], g[2 * i], g[2 * i + 1]);
+        h[2 * i] = add(mul(a0, b0), mul(mul(a1, b1), gamma));
+        h[2 * i + 1] = add(mul(a0, b1), mul(a1, b0));
+    }
+    h
+}
+
+/// `Σ_j â_j ∘ b̂_j`.
+pub fn dot_ntt(a: &PolyVec, b: &PolyVec) -> Poly {
+    (0..K).fold([0; N], |acc, j| poly_add(&acc, &mul_ntt(&a[j], &b[j])))
+}
+
+/// `Â` from `ρ`: `Â[i][j] = SampleNTT(ρ ‖ j ‖ i)` (FIPS 203 Algorithm 7).
+pub fn expand_a(rho: &[u8; 32]) -> Matrix {
+    std::array::from_fn(|i| {
+        std::array::from_fn(|j| {
+            let mut xof = Shake128::default();
+            xof.update(rho);
+            xof.update(&[j as u8, i as u8]);
+            let mut reader = xof.finalize_xof();
+            let mut a = [0u16; N];
+            let mut filled = 0;
+            let mut buf = [0u8; 3];
+            while filled < N {
+                reader.read(&mut buf);
+                let d1 = u16::from(buf[0]) | (u16::from(buf[1] & 15) << 8);
+                let d2 = u16::from(buf[1] >> 4) | (u16::from(buf[2]) << 4);
+                for d in [d1, d2] {
+                    if d < Q && filled < N {
+                        a[filled] = d;
+                        filled += 1;
+                    }
+                }
+            }
+            a
+        })
+    })
+}
+
+/// `SamplePolyCBD_η(B)` for `η = 2` (FIPS 203 Algorithm 8); `bytes`
+/// holds `128` bytes.
+pub fn cbd2(bytes: &[u8]) -> Poly {
+    let bit = |k: usize| u16::from(bytes[k / 8] >> (k % 8) & 1);
+    std::array::from_fn(|i| {
+        let x = bit(4 * i) + bit(4 * i + 1);
+        let y = bit(4 * i + 2) + bit(4 * i + 3);
+        sub(x, y)
+    })
+}
+
+/// `Compress_d(x) = ⌈(2^d/q)·x⌋ mod 2^d`.
+pub fn compress(x: u16, d: u32) -> u16 {
+    let scaled = ((x as u32) << d) + Q as u32 / 2;
+    ((scaled / Q as u32) & ((1 << d) - 1)) as u16
+}
+
+/// `Decompress_d(y) = ⌈(q/2^d)·y⌋`.
+pub fn decompress(y: u16, d: u32) -> u16 {
+    ((y as u32 * Q as u32 + (1 << (d - 1))) >> d) as u16
+}
+
+/// The preimage of `y` under `Compress_d` as an interval `[lo, hi]` of
+/// integers with `−q < lo ≤ hi < q`: every `x ∈ [0, q)` with
+/// `Compress_d(x) = y` is congruent to exactly one member. The
+/// preimage of `0` wraps around `q` and has `lo < 0`.
+pub fn compress_preimage(y: u16, d: u32) -> (i32, i32) {
+    // The smallest x with ⌈(2^d/q)·x⌋ ≥ c.
+    let first = |c: u32| ((2 * c).saturating_sub(1) * Q as u32).div_ceil(2 << d) as i32;
+    let y = u32::from(y);
+    if y == 0 {
+        (first(1 << d) - Q as i32, first(1) - 1)
+    } else {
+        (first(y), first(y + 1) - 1)
+    }
+}
+
+/// `ByteEncode_d` of `values`, appended to `out`.
+pub fn byte_encode(out: &mut Vec<u8>, values: &[u16], d: u32) {
+    let mut acc = 0u32;
+    let mut bits = 0;
+    for &v in values {
+        acc |= u32::from(v) << bits;
+        bits += d;
+        while bits >= 8 {
+            out.push(acc as u8);
+            acc >>= 8;
+            bits -= 8;
+        }
+    }
+}
+
+/// `ByteDecode_d` of `count` values.
+pub fn byte_decode(bytes: &[u8], d: u32, count: usize) -> Vec<u16> {
+    let mut out = Vec::with_capacity(count);
+    let mut acc = 0u32;
+    let mut bits = 0;
+    let mut iter = bytes.iter();
+    while out.len() < count {
+        while bits < d {
+            acc |= u32::from(*iter.next().unwrap_or(&0)) << bits;
+            bits += 8;
+        }
+        out.push((acc & ((1 << d) - 1)) as u16);
+        acc >>= d;
+        bits -= d;
+    }
+    out
+}
+
+/// `ByteDecode_12` of one polynomial, or `None` when a coefficient is
+/// not below `q`.
+pub fn decode_12(bytes: &[u8]) -> Option<Poly> {
+    let values = byte_decode(bytes, 12, N);
+    if values.iter().any(|&v| v >= Q) {
+        return None;
+    }
+    values.try_into().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ntt_round_trips_and_multiplies_negacyclically() {
+        let f: Poly = std::array::from_fn(|i| (i as u16 * 7 + 3) % Q);
+        assert_eq!(inv_ntt(&ntt(&f)), f);
+
+        // X^255 · X = X^256 = −1.
+        let mut a = [0u16; N];
+        a[255] = 1;
+        let mut b = [0u16; N];
+        b[1] = 1;
+        let mut want = [0u16; N];
+        want[0] = Q - 1;
+        assert_eq!(inv_ntt(&mul_ntt(&ntt(&a), &ntt(&b))), want);
+    }
+
+    #[test]
+    fn compress_preimages_are_exact() {
+        for d in [1, 4, 10] {
+            for y in 0..1u16 << d {
+                let (lo, hi) = compress_preimage(y, d);
+                for x in lo - 2..=hi + 2 {
+                    let inside = (lo..=hi).contains(&x);
+                    let xq = x.rem_euclid(Q as i32) as u16;
+                    assert_eq!(compress(xq, d) == y, inside, "d={d} y={y} x={x}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn byte_codec_round_trips() {
+        let values: Vec<u16> = (0..N as u16).map(|i| i * 13 % 1024).collect();
+        let mut out = Vec::new();
+        byte_encode(&mut out, &values, 10);
+        assert_eq!(out.len(), 320);
+        assert_eq!(byte_decode(&out, 10, N), values);
+    }
+}
@@ -0,0 +1,122 @@
+//! Threshold ML-KEM-768 scheme registration.
+//!
+//! | Name                          | Kind  | Session message             | Produces               |
+//! |-------------------------------|-------|-----------------------------|------------------------|
+//! | `ML-KEM-768-threshold`        | `Kem` | FIPS 203 ciphertext         | 32-byte shared secret  |
+//! | `X25519-ML-KEM-768-threshold` | `Kem` | ciphertext ‖ X25519 share   | 32-byte X-Wing secret  |
+//!
+//! Share blobs come from [`crate::dealer`]; there is no DKG scheme.
+//! Encapsulation needs no shares: [`encapsulate`] is ordinary ML-KEM
+//! encapsulation, and any FIPS 203 implementation interoperates.
+
+use confium_tc::Result;
+use confium_tc::kem::{
+    EncapsulateError, EncapsulatedKey, Encapsulator, SharedSecret, ThresholdPublicKey,
+};
+use confium_tc::registry::{SessionImpl, TcScheme, TcSchemeKind};
+use confium_tc::session::SessionParams;
+use rand_core::{OsRng, RngCore};
+
+use crate::Variant;
+use crate::error::{MlKemErrorCode, scheme_error};
+use crate::hybrid;
+use crate::mlkem::{self, EncapsulationKey};
+use crate::params::EK_SIZE;
+use crate::session::MlKemSession;
+
+/// Threshold ML-KEM-768 decapsulation, registered as
+/// [`crate::ALGORITHM`].
+pub struct MlKem768Threshold;
+
+/// Threshold X25519+ML-KEM-768 decapsulation, registered as
+/// [`crate::HYBRID_ALGORITHM`].
+pub struct X25519MlKem768Threshold;
+
+/// Encapsulate a fresh shared secret to `public_key`, returning
+/// `(ciphertext, shared_secret)`.
+pub fn encapsulate(variant: Variant, public_key: &[u8]) -> Result<(Vec<u8>, [u8; 32])> {
+    let bad = || scheme_error(MlKemErrorCode::BAD_PUBLIC_KEY);
+    if public_key.len() != variant.public_key_size() {
+        return Err(bad());
+    }
+    let (ek, pk_x) = public_key.split_at(EK_SIZE);
+    let ek = EncapsulationKey::parse(ek).ok_or_else(bad)?;
+    let mut m = [0u8; 32];
+    OsRng.fill_bytes(&mut m);
+    let (mut ct, ss_m) = mlkem::encapsulate_with(&ek, &m);
+    m.fill(0);
+    match variant {
+        Variant::MlKem768 => Ok((ct, ss_m)),
+        Variant::X25519MlKem768 => {
+            let pk_x: [u8; 32] = pk_x.try_into().map_err(|_| bad())?;
+            let (ct_x, ss_x) = hybrid::encapsulate(&pk_x)?;
+            ct.extend_from_slice(&ct_x);
+            Ok((ct, hybrid::combine(&ss_m, &ss_x, &ct_x, &pk_x)))
+        }
+    }
+}
+
+fn encapsulate_to(
+    variant: Variant,
+    recipient: &ThresholdPublicKey,
+) -> std::result::Result<(EncapsulatedKey, SharedSecret), EncapsulateError> {
+    if recipient.algorithm != variant.algorithm() {
+        return Err(EncapsulateError::UnknownAlgorithm(
+            recipient.algorithm.clone(),
+        ));
+    }
+    let (ct, ss) = encapsulate(variant, &recipient.bytes)
+        .map_err(|e| EncapsulateError::InvalidPublicKey(e.to_string()))?;
+    Ok((
+        EncapsulatedKey {
+            algorithm: variant.algorithm().into(),
+            bytes: ct,
+        },
+        SharedSecret { bytes: ss.to_vec() },
+    ))
+}
+
+impl TcScheme for MlKem768Threshold {
+    fn name(&self) -> &'static str {
+        crate::ALGORITHM
+    }
+    fn kind(&self) -> TcSchemeKind {
+        TcSchemeKind::Kem
+    }
+    fn create_session(&self, params: &SessionParams) -> Result<Box<dyn SessionImpl>> {
+        MlKemSession::build_session(params, Variant::MlKem768)
+    }
+}
+
+impl Encapsulator for MlKem768Threshold {
+    fn encapsulate(
+        &self,
+        recipient_public_key: &ThresholdPublicKey,
+    ) -> std::result::Result<(EncapsulatedKey, SharedSecret), EncapsulateError> {
+        encapsulate_to(Variant::MlKem768, recipient_public_key)
+    }
+}
+
+impl TcScheme for X25519MlKem768Threshold {
+    fn name(&self) -> &'static str {
+        crate::HYBRID_ALGORITHM
+    }
+    fn kind(&self) -> TcSchemeKind {
+        TcSchemeKind::Kem
+    }
+    fn create_session(&self, params: &SessionParams) -> Result<Box<dyn SessionImpl>> {
+        MlKemSession::build_session(params, Variant::X25519MlKem768)
+    }
+}
+
+impl Encapsulator for X25519MlKem768Threshold {
+    fn encapsulate(
+        &self,
+        recipient_public_key: &ThresholdPublicKey,
+    ) -> std::result::Result<(EncapsulatedKey, SharedSecret), EncapsulateError> {
+        encapsulate_to(Variant::X25519MlKem768, recipient_public_key)
+    }
+}
+
+confium_tc::register_tc_scheme!(MlKem768Threshold);
+confium_tc::register_tc_scheme!(X25519MlKem768Threshold);
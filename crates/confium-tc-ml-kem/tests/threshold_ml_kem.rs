@@ -0,0 +1,225 @@
+//! End-to-end threshold ML-KEM-768.
+//!
+//! Ciphertexts come from an independent FIPS 203 implementation (the
+//! `ml-kem` crate) and every threshold decapsulation must agree with
+//! its single-party decapsulation, including implicit rejection.
+
+use confium_tc::kem::{
+    EncapsulatedKey, KemSession, KemSessionParams, KemSessionState, ThresholdShare,
+};
+use confium_tc::{Message, TcSchemeKind};
+use confium_tc_ml_kem::dealer::deal_with_seed;
+use confium_tc_ml_kem::{ALGORITHM, HYBRID_ALGORITHM, Variant, inprocess, scheme};
+
+/// Single-party references: ML-KEM-768 straight from the `ml-kem`
+/// crate, and the X25519 hybrid built on it with the X-Wing combiner
+/// `SHA3-256(ss_M ‖ ss_X ‖ ct_X ‖ pk_X ‖ "\.//^\")`. Keys are
+/// `(public, secret)`; hybrid fields put the ML-KEM part first.
+mod reference {
+    use curve25519_dalek::montgomery::MontgomeryPoint;
+    use ml_kem::kem::{Decapsulate as _, Encapsulate as _};
+    use ml_kem::{EncodedSizeUser as _, KemCore, MlKem768};
+    use rand_core::{OsRng, RngCore};
+    use sha3::{Digest, Sha3_256};
+
+    type Ek = <MlKem768 as KemCore>::EncapsulationKey;
+    type Dk = <MlKem768 as KemCore>::DecapsulationKey;
+
+    const DK_LEN: usize = 2400;
+    const CT_LEN: usize = 1088;
+
+    /// From a 64-byte `d ‖ z` seed.
+    pub fn generate(seed: &[u8]) -> (Vec<u8>, Vec<u8>) {
+        let d = seed[..32].try_into().expect("d");
+        let z = seed[32..64].try_into().expect("z");
+        let (dk, ek) = MlKem768::generate_deterministic(&d, &z);
+        (ek.as_bytes().to_vec(), dk.as_bytes().to_vec())
+    }
+
+    pub fn encapsulate(pk: &[u8]) -> (Vec<u8>, Vec<u8>) {
+        let ek = Ek::from_bytes(&pk.try_into().expect("public key length"));
+        let (ct, ss) = ek.encapsulate(&mut OsRng).expect("encapsulate");
+        (ct.to_vec(), ss.to_vec())
+    }
+
+    pub fn decapsulate(sk: &[u8], ct: &[u8]) -> Vec<u8> {
+        let dk = Dk::from_bytes(&sk.try_into().expect("secret key length"));
+        let ct = ct.try_into().expect("ciphertext length");
+        dk.decapsulate(&ct).expect("decapsulate").to_vec()
+    }
+
+    fn combine(ss_m: &[u8], ss_x: &[u8; 32], ct_x: &[u8], pk_x: &[u8]) -> Vec<u8> {
+        let mut h = Sha3_256::new();
+        for part in [ss_m, ss_x, ct_x, pk_x, b"\\.//^\\"] {
+            h.update(part);
+        }
+        h.finalize().to_vec()
+    }
+
+    fn x25519(scalar: [u8; 32], point: &[u8]) -> [u8; 32] {
+        MontgomeryPoint(point.try_into().expect("X25519 point"))
+            .mul_clamped(scalar)
+            .to_bytes()
+    }
+
+    /// From a 96-byte `d ‖ z ‖ x25519 secret` seed.
+    pub fn hybrid_generate(seed: &[u8]) -> (Vec<u8>, Vec<u8>) {
+        let (mut pk, mut sk) = generate(&seed[..64]);
+        let sk_x: [u8; 32] = seed[64..].try_into().expect("X25519 secret");
+        pk.extend_from_slice(&MontgomeryPoint::mul_base_clamped(sk_x).to_bytes());
+        sk.extend_from_slice(&sk_x);
+        (pk, sk)
+    }
+
+    pub fn hybrid_encapsulate(pk: &[u8]) -> (Vec<u8>, Vec<u8>) {
+        let (ek, pk_x) = pk.split_at(pk.len() - 32);
+        let (mut ct, ss_m) = encapsulate(ek);
+        let mut eph = [0u8; 32];
+        OsRng.fill_bytes(&mut eph);
+        let ct_x = MontgomeryPoint::mul_base_clamped(eph).to_bytes();
+        let ss = combine(&ss_m, &x25519(eph, pk_x), &ct_x, pk_x);
+        ct.extend_from_slice(&ct_x);
+        (ct, ss)
+    }
+
+    pub fn hybrid_decapsulate(sk: &[u8], ct: &[u8]) -> Vec<u8> {
+        let (dk, sk_x) = sk.split_at(DK_LEN);
+        let (ct_m, ct_x) = ct.split_at(CT_LEN);
+        let sk_x: [u8; 32] = sk_x.try_into().expect("X25519 secret");
+        let pk_x = MontgomeryPoint::mul_base_clamped(sk_x).to_bytes();
+        combine(&decapsulate(dk, ct_m), &x25519(sk_x, ct_x), ct_x, &pk_x)
+    }
+}
+
+fn dealt(variant: Variant, threshold: u32, parties: u32, seed: &[u8]) -> Vec<Vec<u8>> {
+    deal_with_seed(variant, threshold, parties, seed)
+        .expect("deal")
+        .iter()
+        .map(|s| s.to_bytes())
+        .collect()
+}
+
+#[test]
+fn schemes_are_registered() {
+    for name in [ALGORITHM, HYBRID_ALGORITHM] {
+        let scheme = confium_tc::registry::find(name).expect("registered");
+        assert_eq!(scheme.kind(), TcSchemeKind::Kem);
+    }
+}
+
+#[test]
+fn decapsulation_matches_fips_203() {
+    let seed = [0x42u8; 64];
+    let (public_key, secret_key) = reference::generate(&seed);
+    let shares = dealt(Variant::MlKem768, 3, 3, &seed);
+    let key = confium_tc_ml_kem::MlKemKeyShare::from_bytes(&shares[0]).expect("share");
+    assert_eq!(key.public_key(), public_key);
+
+    let (ct, ss) = reference::encapsulate(&public_key);
+    let recovered = inprocess::decapsulate(Variant::MlKem768, &shares, 3, &ct).expect("decaps");
+    assert_eq!(recovered, ss);
+
+    // A modified ciphertext takes the implicit-rejection path.
+    let mut forged = ct.clone();
+    forged[5] ^= 0x40;
+    let expected = reference::decapsulate(&secret_key, &forged);
+    let rejected = inprocess::decapsulate(Variant::MlKem768, &shares, 3, &forged).expect("decaps");
+    assert_eq!(rejected, expected);
+    assert_ne!(rejected, recovered);
+}
+
+#[test]
+fn any_threshold_subset_decapsulates() {
+    let key = inprocess::keygen(Variant::MlKem768, 3, 4).expect("keygen");
+    let (ct, ss) = scheme::encapsulate(Variant::MlKem768, &key.public_key).expect("encaps");
+    for absent in 0..4 {
+        let present: Vec<Vec<u8>> = (0..4)
+            .filter(|&i| i != absent)
+            .map(|i| key.shares[i].clone())
+            .collect();
+        let recovered =
+            inprocess::decapsulate(Variant::MlKem768, &present, 3, &ct).expect("decaps");
+        assert_eq!(recovered, ss);
+    }
+
+    let key = inprocess::keygen(Variant::MlKem768, 5, 5).expect("keygen");
+    let (ct, ss) = scheme::encapsulate(Variant::MlKem768, &key.public_key).expect("encaps");
+    let recovered = inprocess::decapsulate(Variant::MlKem768, &key.shares, 5, &ct).expect("decaps");
+    assert_eq!(recovered, ss);
+}
+
+#[test]
+fn hybrid_matches_the_reference_combiner() {
+    let seed = [0x17u8; 96];
+    let (public_key, secret_key) = reference::hybrid_generate(&seed);
+    let shares = dealt(Variant::X25519MlKem768, 3, 4, &seed);
+
+    let (ct, ss) = reference::hybrid_encapsulate(&public_key);
+    let recovered =
+        inprocess::decapsulate(Variant::X25519MlKem768, &shares[..3], 3, &ct).expect("decaps");
+    assert_eq!(recovered, ss);
+
+    // Our encapsulation decapsulates under the single-party key.
+    let (ct, ss) = scheme::encapsulate(Variant::X25519MlKem768, &public_key).expect("encaps");
+    assert_eq!(ss.to_vec(), reference::hybrid_decapsulate(&secret_key, &ct));
+
+    // A low-order X25519 share cannot be decapsulated.
+    let mut low_order = ct;
+    let len = low_order.len();
+    low_order[len - 32..].fill(0);
+    assert!(inprocess::decapsulate(Variant::X25519MlKem768, &shares[..3], 3, &low_order).is_err());
+}
+
+#[test]
+fn decapsulation_below_threshold_fails() {
+    let key = inprocess::keygen(Variant::MlKem768, 3, 4).expect("keygen");
+    let (ct, _) = scheme::encapsulate(Variant::MlKem768, &key.public_key).expect("encaps");
+    assert!(inprocess::decapsulate(Variant::MlKem768, &key.shares[..2], 2, &ct).is_err());
+    assert!(inprocess::decapsulate(Variant::MlKem768, &key.shares[..3], 3, &ct[1..]).is_err());
+}
+
+#[test]
+fn kem_sessions_drive_the_registered_scheme() {
+    let key = inprocess::keygen(Variant::MlKem768, 3, 3).expect("keygen");
+    let (ct, ss) = scheme::encapsulate(Variant::MlKem768, &key.public_key).expect("encaps");
+    let mut sessions: Vec<KemSession> = (0..3)
+        .map(|idx| {
+            let mut session = KemSession::new(KemSessionParams {
+                algorithm: ALGORITHM.into(),
+                quorum_id: "ml-kem".into(),
+                threshold: 3,
+                num_parties: 3,
+                this_party_idx: idx,
+                encapsulated_key: EncapsulatedKey {
+                    algorithm: ALGORITHM.into(),
+                    bytes: ct.clone(),
+                },
+            });
+            let share = ThresholdShare::new(ALGORITHM, idx, key.shares[idx as usize].clone());
+            session.set_local_share(share).expect("share");
+            session
+        })
+        .collect();
+
+    let mut outgoing: Vec<Vec<Message>> = vec![Vec::new(); 3];
+    while sessions
+        .iter()
+        .any(|s| s.state() != KemSessionState::Completed)
+    {
+        outgoing = (0..3)
+            .map(|i| {
+                let id = format!("p{i}");
+                let incoming: Vec<Message> = outgoing
+                    .iter()
+                    .enumerate()
+                    .filter(|&(j, _)| j != i)
+                    .flat_map(|(_, msgs)| msgs.iter().filter(|m| m.is_for(&id)).cloned())
+                    .collect();
+                sessions[i].round(&incoming).expect("round").outgoing
+            })
+            .collect();
+    }
+    for session in &mut sessions {
+        assert_eq!(session.try_complete().expect("complete"), ss);
+    }
+}
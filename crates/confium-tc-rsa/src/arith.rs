@@ -0,0 +1,181 @@
+//! Integer arithmetic for the dealer and the combiner: primality,
+//! safe primes, modular inverses and signed exponents.
+
+use num_bigint::{BigInt, BigUint, RandBigInt, Sign};
+use num_integer::Integer;
+use num_traits::{One, ToPrimitive, Zero};
+use rand_core::OsRng;
+
+/// Miller–Rabin rounds for a final primality decision. Error
+/// probability at most `4^-40` per candidate.
+const MR_ROUNDS: u32 = 40;
+
+fn small_primes() -> Vec<u32> {
+    (3u32..2000)
+        .step_by(2)
+        .filter(|&n| {
+            (3..n)
+                .step_by(2)
+                .take_while(|d| d * d <= n)
+                .all(|d| n % d != 0)
+        })
+        .collect()
+}
+
+/// Probabilistic primality test with `rounds` random bases.
+pub(crate) fn miller_rabin(n: &BigUint, rounds: u32) -> bool {
+    let two = BigUint::from(2u32);
+    if *n < two {
+        return false;
+    }
+    if *n == two || *n == BigUint::from(3u32) {
+        return true;
+    }
+    if n.is_even() {
+        return false;
+    }
+    let n_minus_one = n - 1u32;
+    let r = n_minus_one.trailing_zeros().unwrap_or(0);
+    let d = &n_minus_one >> r;
+    'outer: for _ in 0..rounds {
+        let a = OsRng.gen_biguint_range(&two, &n_minus_one);
+        let mut x = a.modpow(&d, n);
+        if x.is_one() || x == n_minus_one {
+            continue;
+        }
+        for _ in 1..r {
+            x = (&x * &x) % n;
+            if x == n_minus_one {
+                continue 'outer;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+/// Whether `p` is a safe prime: `p` and `(p - 1) / 2` both prime.
+pub(crate) fn is_safe_prime(p: &BigUint) -> bool {
+    p.bit(0) && miller_rabin(&(p >> 1u32), MR_ROUNDS) && miller_rabin(p, MR_ROUNDS)
+}
+
+/// A random `bits`-bit safe prime `p = 2p' + 1` with its top two bits
+/// set, so the product of two is exactly `2 * bits` long.
+///
+/// Candidates are sieved on both `p'` and `p`, then screened with a
+/// base-2 Fermat test on `p` before the full Miller–Rabin runs. A
+/// 1024-bit safe prime still takes tens of seconds; this is a dealer
+/// ceremony cost, not a per-signature one.
+pub(crate) fn safe_prime(bits: u64) -> BigUint {
+    let sieve = small_primes();
+    let two = BigUint::from(2u32);
+    loop {
+        let mut q = OsRng.gen_biguint(bits - 1);
+        for bit in [bits - 2, bits - 3, 0] {
+            q.set_bit(bit, true);
+        }
+        if sieve.iter().any(|&s| {
+            let r = (&q % s).to_u32().unwrap_or(0);
+            r == 0 || r == (s - 1) / 2
+        }) {
+            continue;
+        }
+        let p: BigUint = (&q << 1u32) + 1u32;
+        if !two.modpow(&(&p - 1u32), &p).is_one() {
+            continue;
+        }
+        if miller_rabin(&q, MR_ROUNDS) && miller_rabin(&p, MR_ROUNDS) {
+            return p;
+        }
+    }
+}
+
+/// Extended Euclid: `(g, x, y)` with `a·x + b·y = g = gcd(a, b)`.
+pub(crate) fn extended_gcd(a: &BigInt, b: &BigInt) -> (BigInt, BigInt, BigInt) {
+    let (mut old_r, mut r) = (a.clone(), b.clone());
+    let (mut old_s, mut s) = (BigInt::one(), BigInt::zero());
+    let (mut old_t, mut t) = (BigInt::zero(), BigInt::one());
+    while !r.is_zero() {
+        let q = &old_r / &r;
+        (old_r, r) = (r.clone(), &old_r - &q * &r);
+        (old_s, s) = (s.clone(), &old_s - &q * &s);
+        (old_t, t) = (t.clone(), &old_t - &q * &t);
+    }
+    (old_r, old_s, old_t)
+}
+
+/// `a^-1 mod m`, or `None` when `gcd(a, m) != 1`.
+pub(crate) fn modinv(a: &BigUint, m: &BigUint) -> Option<BigUint> {
+    let (g, x, _) = extended_gcd(&BigInt::from(a.clone()), &BigInt::from(m.clone()));
+    if !g.is_one() {
+        return None;
+    }
+    x.mod_floor(&BigInt::from(m.clone())).to_biguint()
+}
+
+/// `base^exp mod n` for a signed exponent. A negative exponent inverts
+/// `base` first; `None` when it has no inverse.
+pub(crate) fn pow_signed(base: &BigUint, exp: &BigInt, n: &BigUint) -> Option<BigUint> {
+    match exp.sign() {
+        Sign::Minus => Some(modinv(base, n)?.modpow(exp.magnitude(), n)),
+        _ => Some(base.modpow(exp.magnitude(), n)),
+    }
+}
+
+/// `k!`, Shoup's `Δ` for `k` parties.
+pub(crate) fn factorial(k: u32) -> BigUint {
+    (2..=k).fold(BigUint::one(), |acc, i| acc * i)
+}
+
+/// RFC 8017 I2OSP: `x` as exactly `len` big-endian bytes, or `None`
+/// when it does not fit.
+pub(crate) fn i2osp(x: &BigUint, len: usize) -> Option<Vec<u8>> {
+    let bytes = x.to_bytes_be();
+    if x.is_zero() {
+        return Some(vec![0; len]);
+    }
+    if bytes.len() > len {
+        return None;
+    }
+    let mut out = vec![0; len - bytes.len()];
+    out.extend_from_slice(&bytes);
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn miller_rabin_separates_primes_and_composites() {
+        assert!(miller_rabin(&BigUint::from(65537u32), 20));
+        assert!(!miller_rabin(&BigUint::from(65535u32), 20));
+        // 561 is a Carmichael number.
+        assert!(!miller_rabin(&BigUint::from(561u32), 20));
+    }
+
+    #[test]
+    fn generated_safe_prime_has_prime_half() {
+        let p = safe_prime(128);
+        assert_eq!(p.bits(), 128);
+        assert!(p.bit(126));
+        assert!(is_safe_prime(&p));
+        assert!(!is_safe_prime(&BigUint::from(13u32)));
+    }
+
+    #[test]
+    fn signed_exponent_inverts() {
+        let n = BigUint::from(35u32);
+        let x = BigUint::from(4u32);
+        let inv = pow_signed(&x, &BigInt::from(-1), &n).unwrap();
+        assert_eq!((inv * &x) % &n, BigUint::one());
+        assert!(pow_signed(&BigUint::from(5u32), &BigInt::from(-1), &n).is_none());
+    }
+
+    #[test]
+    fn i2osp_pads_and_rejects_overflow() {
+        assert_eq!(i2osp(&BigUint::from(0x0102u32), 4).unwrap(), [0, 0, 1, 2]);
+        assert_eq!(i2osp(&BigUint::zero(), 2).unwrap(), [0, 0]);
+        assert!(i2osp(&BigUint::from(0x010203u32), 2).is_none());
+    }
+}
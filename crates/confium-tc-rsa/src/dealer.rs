@@ -0,0 +1,198 @@
+//! Trusted-dealer key generation (Shoup §2.1).
+//!
+//! The dealer picks safe primes `p = 2p' + 1`, `q = 2q' + 1`, sets
+//! `n = pq`, `m = p'q'` and `d = e^-1 mod m`, and Shamir-shares `d`
+//! over `Z_m` with a random polynomial `f` of degree `threshold - 1`:
+//! party `i` gets `s_i = f(i) mod m`. It also publishes a random square
+//! `v` and every party's verification key `v_i = v^{s_i}`.
+//!
+//! The dealer sees `d` and the factorisation, so the ceremony must run
+//! on a machine that is trusted and wiped afterwards; the primes and
+//! `d` are dropped before [`deal`] returns and only the shares leave it.
+
+use num_bigint::{BigUint, RandBigInt};
+use num_integer::Integer;
+use num_traits::One;
+use rand_core::OsRng;
+
+use crate::arith::{is_safe_prime, modinv, safe_prime};
+use crate::error::{Result, RsaErrorCode, scheme_error};
+use crate::keys::{RsaKeyShare, RsaPublicKey};
+
+/// The public exponent of every dealt key. Shoup needs a prime `e`
+/// larger than the number of parties; F4 is both.
+pub const PUBLIC_EXPONENT: u32 = 65_537;
+
+/// Smallest modulus [`deal`] accepts.
+pub const MIN_MODULUS_BITS: u64 = 1024;
+
+/// Largest party count. Bounded well below [`PUBLIC_EXPONENT`], and at
+/// 255 so `Δ = l!` stays a few hundred bytes.
+pub const MAX_PARTIES: u32 = 255;
+
+/// Deal a fresh `modulus_bits`-bit key to `parties` parties, any
+/// `threshold` of whom can sign or decrypt.
+///
+/// Generating the two safe primes dominates: seconds for a 1024-bit
+/// modulus, a minute or more for 2048 bits.
+pub fn deal(modulus_bits: u64, threshold: u32, parties: u32) -> Result<Vec<RsaKeyShare>> {
+    if modulus_bits < MIN_MODULUS_BITS || modulus_bits.is_odd() {
+        return Err(scheme_error(RsaErrorCode::BAD_KEY_PARAMETERS));
+    }
+    check_roster(threshold, parties)?;
+    loop {
+        let p = safe_prime(modulus_bits / 2);
+        let q = safe_prime(modulus_bits / 2);
+        if p != q {
+            return share(&p, &q, threshold, parties);
+        }
+    }
+}
+
+/// Deal a key over caller-supplied safe primes `p` and `q`. For
+/// ceremonies that generate the primes elsewhere (an HSM, an audited
+/// tool) and for fixtures.
+pub fn deal_with_primes(
+    p: &BigUint,
+    q: &BigUint,
+    threshold: u32,
+    parties: u32,
+) -> Result<Vec<RsaKeyShare>> {
+    check_roster(threshold, parties)?;
+    if p == q || (p * q).bits() < MIN_MODULUS_BITS || !is_safe_prime(p) || !is_safe_prime(q) {
+        return Err(scheme_error(RsaErrorCode::BAD_KEY_PARAMETERS));
+    }
+    share(p, q, threshold, parties)
+}
+
+fn check_roster(threshold: u32, parties: u32) -> Result<()> {
+    if parties == 0 || parties > MAX_PARTIES || !(1..=parties).contains(&threshold) {
+        return Err(scheme_error(RsaErrorCode::BAD_KEY_PARAMETERS));
+    }
+    Ok(())
+}
+
+fn share(p: &BigUint, q: &BigUint, threshold: u32, parties: u32) -> Result<Vec<RsaKeyShare>> {
+    let n = p * q;
+    let m = (p >> 1u32) * (q >> 1u32);
+    let e = BigUint::from(PUBLIC_EXPONENT);
+    let d = modinv(&e, &m).ok_or_else(|| scheme_error(RsaErrorCode::BAD_KEY_PARAMETERS))?;
+
+    // f(X) = d + a_1 X + … + a_{t-1} X^{t-1} over Z_m.
+    let mut coefficients = vec![d];
+    coefficients.extend((1..threshold).map(|_| OsRng.gen_biguint_below(&m)));
+    let secrets: Vec<BigUint> = (1..=parties)
+        .map(|i| {
+            coefficients
+                .iter()
+                .rev()
+                .fold(BigUint::default(), |acc, a| (acc * i + a) % &m)
+        })
+        .collect();
+
+    let v = loop {
+        let r = OsRng.gen_biguint_range(&BigUint::from(2u32), &n);
+        if r.gcd(&n).is_one() {
+            break r.modpow(&BigUint::from(2u32), &n);
+        }
+    };
+    let verification_keys: Vec<BigUint> = secrets.iter().map(|s| v.modpow(s, &n)).collect();
+    let public_key = RsaPublicKey::new(n, e);
+
+    Ok(secrets
+        .into_iter()
+        .zip(1..)
+        .map(|(secret, index)| RsaKeyShare {
+            index,
+            threshold,
+            public_key: public_key.clone(),
+            verification_base: v.clone(),
+            verification_keys: verification_keys.clone(),
+            secret,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::arith::factorial;
+
+    /// 512-bit safe primes; their product is a 1024-bit modulus.
+    const P: &str = "f7270cfc837b8879c958fa689b6637c48e0a302d8ca83075bbeba1d960ef40dc7f68ad0cccc0cece0c9a1a3d4b52bcd5f7880428d6622b382906b72107ac2a57";
+    const Q: &str = "e92859643f40adf1d51d9ac3836e1fa9d002130937b8d0cdeb8c8bebd4a9d83c7db9641405c7b19bec3663eecab4559c4553baf60775491f4f747605a27867bb";
+
+    fn primes() -> (BigUint, BigUint) {
+        (
+            BigUint::parse_bytes(P.as_bytes(), 16).unwrap(),
+            BigUint::parse_bytes(Q.as_bytes(), 16).unwrap(),
+        )
+    }
+
+    #[test]
+    fn shares_interpolate_to_the_private_exponent() {
+        let (p, q) = primes();
+        let shares = deal_with_primes(&p, &q, 3, 5).unwrap();
+        let m = (&p >> 1u32) * (&q >> 1u32);
+        let e = BigUint::from(PUBLIC_EXPONENT);
+        // Σ λ_j s_j = Δ·d (mod m) over any three shares, with Shoup's
+        // integer Lagrange coefficients.
+        let delta = factorial(5);
+        let set = [1u32, 3, 5];
+        let mut acc = num_bigint::BigInt::default();
+        for &j in &set {
+            let mut num = num_bigint::BigInt::from(delta.clone());
+            let mut den = num_bigint::BigInt::one();
+            for &k in set.iter().filter(|&&k| k != j) {
+                num *= k;
+                den *= i64::from(k) - i64::from(j);
+            }
+            acc += num / den * num_bigint::BigInt::from(shares[j as usize - 1].secret.clone());
+        }
+        let delta_d = acc.mod_floor(&m.clone().into()).to_biguint().unwrap();
+        assert_eq!((delta_d * &e) % &m, delta % &m);
+    }
+
+    #[test]
+    fn verification_keys_match_secrets() {
+        let (p, q) = primes();
+        let shares = deal_with_primes(&p, &q, 2, 3).unwrap();
+        for share in &shares {
+            assert_eq!(
+                share
+                    .verification_base
+                    .modpow(&share.secret, &share.public_key.n),
+                *share.verification_key(share.index).unwrap()
+            );
+            let decoded = RsaKeyShare::from_bytes(&share.to_bytes()).unwrap();
+            assert_eq!(decoded.secret, share.secret);
+            assert_eq!(decoded.public_key, share.public_key);
+        }
+    }
+
+    #[test]
+    fn tampered_secret_is_rejected() {
+        let (p, q) = primes();
+        let mut share = deal_with_primes(&p, &q, 2, 3).unwrap().remove(0);
+        share.secret += 1u32;
+        assert!(RsaKeyShare::from_bytes(&share.to_bytes()).is_err());
+    }
+
+    #[test]
+    fn bad_parameters_are_refused() {
+        let (p, q) = primes();
+        assert!(deal_with_primes(&p, &q, 0, 3).is_err());
+        assert!(deal_with_primes(&p, &q, 4, 3).is_err());
+        assert!(deal_with_primes(&p, &p, 2, 3).is_err());
+        assert!(deal_with_primes(&p, &(&q + 2u32), 2, 3).is_err());
+        assert!(deal(512, 2, 3).is_err());
+    }
+
+    #[test]
+    fn fresh_deal_produces_a_full_length_modulus() {
+        let shares = deal(MIN_MODULUS_BITS, 2, 3).unwrap();
+        assert_eq!(shares[0].public_key.bits(), MIN_MODULUS_BITS);
+        assert_eq!(shares.len(), 3);
+    }
+}
@@ -0,0 +1,60 @@
+//! Error helpers for the threshold RSA scheme crate.
+
+use confium_tc::error::Error as TcError;
+
+/// Threshold RSA sub-codes (0x70xx). Distinct from GG18's 0x50xx and
+/// CMP20's 0x60xx so callers can disambiguate the source scheme from a
+/// numeric code alone.
+#[allow(non_camel_case_types)]
+#[repr(u32)]
+pub enum RsaErrorCode {
+    /// A key-share blob failed to deserialize, had the wrong magic /
+    /// version, or its secret share does not match its verification key.
+    /// Caller action: re-run the dealer ceremony; the on-disk format may
+    /// have been corrupted or written by a different scheme.
+    BAD_SHARE = 0x7001,
+    /// The dealer was asked for an unusable key: a threshold outside
+    /// `1..=parties`, more parties than the public exponent allows, a
+    /// modulus too small to be safe, or primes that are not safe primes.
+    /// Caller action: fix the ceremony parameters.
+    BAD_KEY_PARAMETERS = 0x7002,
+    /// The session input cannot be encoded under the requested padding:
+    /// too long for the modulus, a PSS digest that is not 32 bytes, or
+    /// an OAEP ciphertext that is not a modulus-sized integer below `n`.
+    /// Caller action: use a larger key or fix the input.
+    BAD_MESSAGE = 0x7010,
+    /// The ciphertext is not a valid RSAES-OAEP ciphertext for this key.
+    /// Deliberately uninformative about which check failed.
+    /// Caller action: treat the ciphertext as forged or corrupted.
+    DECRYPTION_FAILED = 0x7011,
+    /// Fewer than T parties contributed a valid partial result.
+    /// Caller action: collect more signers before retrying.
+    BELOW_THRESHOLD = 0x7020,
+    /// The combined signature does not verify under the public key even
+    /// though every partial result's proof did. Indicates shares from
+    /// different dealer ceremonies or a bug.
+    /// Caller action: abort the session; do not retry with the same
+    /// shares.
+    COMBINE_FAILED = 0x7030,
+    /// Internal error — a panic-equivalent condition was caught and
+    /// converted to an error return. Indicates a bug in the threshold
+    /// RSA implementation; please open an issue.
+    INTERNAL = 0x70FF,
+}
+
+impl From<RsaErrorCode> for u32 {
+    #[inline]
+    fn from(c: RsaErrorCode) -> u32 {
+        c as u32
+    }
+}
+
+/// Build a framework [`TcError`] carrying a threshold RSA sub-code.
+pub fn scheme_error(code: RsaErrorCode) -> TcError {
+    confium_tc::error::SchemeInternalSnafu {
+        code: u32::from(code),
+    }
+    .build()
+}
+
+pub type Result<T> = std::result::Result<T, TcError>;
@@ -0,0 +1,59 @@
+//! In-process driver for threshold RSA.
+//!
+//! [`keygen`] runs the trusted dealer locally and [`sign`] / [`decrypt`]
+//! drive the registered sessions through [`confium_tc::inprocess`].
+//!
+//! ## Output wire format
+//!
+//! - [`KeygenOutput::shares`] are opaque `RsaKeyShare::to_bytes()`
+//!   blobs, usable with every padding's scheme.
+//! - [`KeygenOutput::public_key`] is the PKCS#1 `RSAPublicKey` DER.
+//! - [`sign`] returns a modulus-sized signature any RSA verifier
+//!   accepts; [`decrypt`] returns the OAEP plaintext.
+
+use confium_tc::Result;
+use confium_tc::inprocess as driver;
+
+use crate::dealer;
+use crate::padding::RsaPadding;
+
+/// Outcome of a dealer ceremony: N share blobs plus the public key.
+#[derive(Debug, Clone)]
+pub struct KeygenOutput {
+    /// One share blob per party, in dealer-index order.
+    pub shares: Vec<Vec<u8>>,
+    /// PKCS#1 `RSAPublicKey` DER of the dealt key.
+    pub public_key: Vec<u8>,
+}
+
+/// Deal a `modulus_bits`-bit key to `party_count` parties at threshold
+/// `threshold`. See [`dealer::deal`] for the cost.
+pub fn keygen(modulus_bits: u64, threshold: u32, party_count: u32) -> Result<KeygenOutput> {
+    let shares = dealer::deal(modulus_bits, threshold, party_count)?;
+    Ok(KeygenOutput {
+        public_key: shares[0].public_key.to_pkcs1_der(),
+        shares: shares.iter().map(|s| s.to_bytes()).collect(),
+    })
+}
+
+/// Threshold-sign `message` under `padding` with the given share blobs.
+/// `message` is whatever the padding's scheme expects as its session
+/// message (see [`crate::scheme`]).
+pub fn sign(
+    padding: RsaPadding,
+    share_blobs: &[Vec<u8>],
+    threshold: u32,
+    message: &[u8],
+) -> Result<Vec<u8>> {
+    driver::run_sign(padding.scheme_name(), share_blobs, threshold, message)
+}
+
+/// Threshold-decrypt an RSAES-OAEP (SHA-256) `ciphertext`.
+pub fn decrypt(share_blobs: &[Vec<u8>], threshold: u32, ciphertext: &[u8]) -> Result<Vec<u8>> {
+    driver::run_sign(
+        RsaPadding::OaepSha256.scheme_name(),
+        share_blobs,
+        threshold,
+        ciphertext,
+    )
+}
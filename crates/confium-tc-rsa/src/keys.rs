@@ -0,0 +1,285 @@
+//! The RSA public key and the per-party key share produced by the
+//! dealer.
+//!
+//! ```text
+//! share: magic "TRSA" | version 1 | index u32 | threshold u32 | parties u32
+//!        | n | e | v | s_i | v_1 … v_parties
+//! ```
+//!
+//! Integers are a `u32` big-endian length followed by their big-endian
+//! magnitude. Every share carries all verification keys `v_j = v^{s_j}`
+//! so any signer can check any other signer's proof.
+
+use num_bigint::BigUint;
+use num_integer::Integer;
+use num_traits::{One, Zero};
+use rand_core::{OsRng, RngCore};
+use sha2::{Digest, Sha256};
+
+use crate::arith::i2osp;
+use crate::error::{Result, RsaErrorCode, scheme_error};
+use crate::padding::{self, HASH_LEN};
+
+const SHARE_MAGIC: [u8; 4] = *b"TRSA";
+const SHARE_VERSION: u8 = 1;
+
+/// An RSA public key `(n, e)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RsaPublicKey {
+    /// The modulus `n = p·q`.
+    pub n: BigUint,
+    /// The public exponent.
+    pub e: BigUint,
+}
+
+impl RsaPublicKey {
+    pub fn new(n: BigUint, e: BigUint) -> Self {
+        RsaPublicKey { n, e }
+    }
+
+    /// Modulus length in bits.
+    pub fn bits(&self) -> u64 {
+        self.n.bits()
+    }
+
+    /// Modulus length in bytes, `k` in RFC 8017 — also the length of
+    /// every signature and ciphertext.
+    pub fn size(&self) -> usize {
+        self.bits().div_ceil(8) as usize
+    }
+
+    /// The PKCS#1 `RSAPublicKey` DER encoding.
+    pub fn to_pkcs1_der(&self) -> Vec<u8> {
+        let mut body = der_integer(&self.n);
+        body.extend(der_integer(&self.e));
+        der_tlv(0x30, &body)
+    }
+
+    /// RSAVP1: `s^e mod n` as `len` bytes, or `None` when `s` is out of
+    /// range.
+    fn open(&self, signature: &[u8], len: usize) -> Option<Vec<u8>> {
+        let s = BigUint::from_bytes_be(signature);
+        if signature.len() != self.size() || s >= self.n {
+            return None;
+        }
+        i2osp(&s.modpow(&self.e, &self.n), len)
+    }
+
+    /// Verify an RSASSA-PKCS1-v1_5 signature with SHA-256.
+    pub fn verify_pkcs1v15_sha256(&self, message: &[u8], signature: &[u8]) -> bool {
+        let Some(em) = self.open(signature, self.size()) else {
+            return false;
+        };
+        padding::emsa_pkcs1v15(&padding::digest_info_sha256(message), self.size())
+            .is_ok_and(|expected| expected == em)
+    }
+
+    /// Verify an RSASSA-PSS signature with SHA-256, MGF1-SHA-256 and a
+    /// 32-byte salt.
+    pub fn verify_pss_sha256(&self, message: &[u8], signature: &[u8]) -> bool {
+        let em_len = (self.bits() - 1).div_ceil(8) as usize;
+        let Some(em) = self.open(signature, em_len) else {
+            return false;
+        };
+        padding::emsa_pss_verify(&Sha256::digest(message), &em, self.bits())
+    }
+
+    /// RSAES-OAEP encryption with SHA-256, MGF1-SHA-256 and an empty
+    /// label — the public half of [`RsaPadding::OaepSha256`](crate::RsaPadding::OaepSha256).
+    pub fn encrypt_oaep_sha256(&self, message: &[u8]) -> Result<Vec<u8>> {
+        let mut seed = [0u8; HASH_LEN];
+        OsRng.fill_bytes(&mut seed);
+        let em = padding::oaep_encode(message, self.size(), &seed)?;
+        let c = BigUint::from_bytes_be(&em).modpow(&self.e, &self.n);
+        i2osp(&c, self.size()).ok_or_else(|| scheme_error(RsaErrorCode::INTERNAL))
+    }
+}
+
+fn der_tlv(tag: u8, body: &[u8]) -> Vec<u8> {
+    let mut out = vec![tag];
+    match body.len() {
+        len @ 0..0x80 => out.push(len as u8),
+        len => {
+            let len_bytes: Vec<u8> = len
+                .to_be_bytes()
+                .into_iter()
+                .skip_while(|&b| b == 0)
+                .collect();
+            out.push(0x80 | len_bytes.len() as u8);
+            out.extend(len_bytes);
+        }
+    }
+    out.extend_from_slice(body);
+    out
+}
+
+fn der_integer(x: &BigUint) -> Vec<u8> {
+    let mut body = x.to_bytes_be();
+    if body[0] & 0x80 != 0 {
+        body.insert(0, 0);
+    }
+    der_tlv(0x02, &body)
+}
+
+/// One party's share of a dealt threshold RSA key.
+#[derive(Clone)]
+pub struct RsaKeyShare {
+    /// 1-based dealer index of this party.
+    pub index: u32,
+    /// Parties needed to sign or decrypt.
+    pub threshold: u32,
+    /// The RSA public key.
+    pub public_key: RsaPublicKey,
+    /// Shoup's verification base `v`, a random square mod `n`.
+    pub verification_base: BigUint,
+    /// `v_j = v^{s_j} mod n` for every party `j = 1 …`, in index order.
+    pub verification_keys: Vec<BigUint>,
+    /// This party's secret exponent share `s_i = f(i) mod p'q'`.
+    pub(crate) secret: BigUint,
+}
+
+impl std::fmt::Debug for RsaKeyShare {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RsaKeyShare")
+            .field("index", &self.index)
+            .field("threshold", &self.threshold)
+            .field("parties", &self.parties())
+            .field("modulus_bits", &self.public_key.bits())
+            .field("secret", &"<redacted>")
+            .finish_non_exhaustive()
+    }
+}
+
+impl RsaKeyShare {
+    /// Number of parties the key was dealt to, Shoup's `l`.
+    pub fn parties(&self) -> u32 {
+        self.verification_keys.len() as u32
+    }
+
+    /// The verification key of party `index`, if it exists.
+    pub fn verification_key(&self, index: u32) -> Option<&BigUint> {
+        let slot = index.checked_sub(1)?;
+        self.verification_keys.get(slot as usize)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = SHARE_MAGIC.to_vec();
+        out.push(SHARE_VERSION);
+        out.extend_from_slice(&self.index.to_be_bytes());
+        out.extend_from_slice(&self.threshold.to_be_bytes());
+        out.extend_from_slice(&self.parties().to_be_bytes());
+        for x in [
+            &self.public_key.n,
+            &self.public_key.e,
+            &self.verification_base,
+            &self.secret,
+        ]
+        .into_iter()
+        .chain(&self.verification_keys)
+        {
+            put_uint(&mut out, x);
+        }
+        out
+    }
+
+    /// Decode a share, checking its structure and that the secret share
+    /// matches its own verification key.
+    pub fn from_bytes(data: &[u8]) -> Result<Self> {
+        let bad = || scheme_error(RsaErrorCode::BAD_SHARE);
+        if data.len() < 17 || data[0..4] != SHARE_MAGIC || data[4] != SHARE_VERSION {
+            return Err(bad());
+        }
+        let word =
+            |at: usize| u32::from_be_bytes([data[at], data[at + 1], data[at + 2], data[at + 3]]);
+        let (index, threshold, parties) = (word(5), word(9), word(13));
+        if parties == 0
+            || parties > crate::dealer::MAX_PARTIES
+            || !(1..=parties).contains(&index)
+            || !(1..=parties).contains(&threshold)
+        {
+            return Err(bad());
+        }
+        let mut rest = &data[17..];
+        let mut next = || take_uint(&mut rest).ok_or_else(bad);
+        let n = next()?;
+        let e = next()?;
+        let verification_base = next()?;
+        let secret = next()?;
+        let verification_keys = (0..parties).map(|_| next()).collect::<Result<Vec<_>>>()?;
+        if !rest.is_empty() || n.is_even() || n <= BigUint::one() || e.is_even() || e.is_one() {
+            return Err(bad());
+        }
+        if verification_keys
+            .iter()
+            .chain([&verification_base])
+            .any(|v| v.is_zero() || *v >= n)
+        {
+            return Err(bad());
+        }
+        let share = RsaKeyShare {
+            index,
+            threshold,
+            public_key: RsaPublicKey::new(n, e),
+            verification_base,
+            verification_keys,
+            secret,
+        };
+        let own = share.verification_key(index).ok_or_else(bad)?;
+        if share
+            .verification_base
+            .modpow(&share.secret, &share.public_key.n)
+            != *own
+        {
+            return Err(bad());
+        }
+        Ok(share)
+    }
+}
+
+fn put_uint(out: &mut Vec<u8>, x: &BigUint) {
+    let bytes = x.to_bytes_be();
+    out.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+    out.extend_from_slice(&bytes);
+}
+
+fn take_uint(data: &mut &[u8]) -> Option<BigUint> {
+    let len_bytes: [u8; 4] = data.get(..4)?.try_into().ok()?;
+    let len = u32::from_be_bytes(len_bytes) as usize;
+    let bytes = data.get(4..4 + len)?;
+    *data = &data[4 + len..];
+    Some(BigUint::from_bytes_be(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pkcs1_der_of_small_key() {
+        let key = RsaPublicKey::new(BigUint::from(0xc5u32), BigUint::from(65537u32));
+        assert_eq!(
+            key.to_pkcs1_der(),
+            [
+                0x30, 0x09, 0x02, 0x02, 0x00, 0xc5, 0x02, 0x03, 0x01, 0x00, 0x01
+            ]
+        );
+    }
+
+    #[test]
+    fn long_der_lengths_use_the_long_form() {
+        let body = vec![0u8; 300];
+        let tlv = der_tlv(0x30, &body);
+        assert_eq!(&tlv[..4], &[0x30, 0x82, 0x01, 0x2c]);
+        assert_eq!(tlv.len(), 304);
+    }
+
+    #[test]
+    fn share_blob_rejects_garbage() {
+        assert!(RsaKeyShare::from_bytes(b"").is_err());
+        assert!(RsaKeyShare::from_bytes(&[0u8; 64]).is_err());
+        let mut blob = SHARE_MAGIC.to_vec();
+        blob.push(SHARE_VERSION);
+        blob.extend_from_slice(&[0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 1]);
+        assert!(RsaKeyShare::from_bytes(&blob).is_err());
+    }
+}
@@ -0,0 +1,65 @@
+//! Shoup threshold RSA (V. Shoup, "Practical Threshold Signatures",
+//! EUROCRYPT 2000).
+//!
+//! Produces ordinary RSA artifacts — RSASSA-PKCS1-v1_5 and RSASSA-PSS
+//! signatures, RSAES-OAEP decryptions — for relying parties that only
+//! speak RSA (legacy TLS clients, Authenticode, HSM-integrated PKIs),
+//! while the private exponent only ever exists as `T`-of-`N` shares.
+//!
+//! - **Trusted-dealer keygen** — [`dealer::deal`] generates the safe
+//!   primes, shares `d` and publishes per-party verification keys. There
+//!   is no DKG: the dealer machine is trusted for the ceremony.
+//! - **Non-interactive signing** — each party publishes one partial
+//!   result with a proof of correctness; any `T` verified partials
+//!   combine without further interaction (PSS adds one salt round).
+//! - **Identifiable abort** — a malformed message or failed proof aborts
+//!   with [`confium_tc::Error::MessageRejected`] naming the sender.
+//!
+//! Five schemes are registered with the [`confium_tc`] registry, one
+//! per [`RsaPadding`]; see [`scheme`] for the table. All of them take
+//! the same share blobs.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use confium_tc_rsa::{RsaKeyShare, RsaPadding, inprocess};
+//!
+//! let kg = inprocess::keygen(2048, 2, 3)?;
+//! let signature = inprocess::sign(RsaPadding::PssSha256, &kg.shares[..2], 2, b"hello")?;
+//! let key = RsaKeyShare::from_bytes(&kg.shares[0])?.public_key;
+//! assert!(key.verify_pss_sha256(b"hello", &signature));
+//!
+//! let ciphertext = key.encrypt_oaep_sha256(b"secret")?;
+//! assert_eq!(inprocess::decrypt(&kg.shares[1..], 2, &ciphertext)?, b"secret");
+//! # Ok::<(), confium_tc::Error>(())
+//! ```
+
+#![forbid(unsafe_code)]
+#![allow(missing_docs)] // TODO: document before 1.0
+
+mod arith;
+pub mod dealer;
+pub mod error;
+pub mod inprocess;
+pub mod keys;
+pub mod padding;
+pub mod scheme;
+pub mod session;
+pub mod shoup;
+
+pub use keys::{RsaKeyShare, RsaPublicKey};
+pub use padding::RsaPadding;
+pub use scheme::{
+    RsaShoupOaepSha256, RsaShoupPkcs1, RsaShoupPkcs1Sha256, RsaShoupPss, RsaShoupPssSha256,
+};
+
+/// RSASSA-PKCS1-v1_5 over a caller-built `DigestInfo`.
+pub const PKCS1_SCHEME_NAME: &str = "RSA-SHOUP-PKCS1";
+/// RSASSA-PKCS1-v1_5 with SHA-256.
+pub const PKCS1_SHA256_SCHEME_NAME: &str = "RSA-SHOUP-PKCS1-SHA256";
+/// RSASSA-PSS over a caller-computed SHA-256 digest.
+pub const PSS_SCHEME_NAME: &str = "RSA-SHOUP-PSS";
+/// RSASSA-PSS with SHA-256.
+pub const PSS_SHA256_SCHEME_NAME: &str = "RSA-SHOUP-PSS-SHA256";
+/// RSAES-OAEP decryption with SHA-256.
+pub const OAEP_SHA256_SCHEME_NAME: &str = "RSA-SHOUP-OAEP-SHA256";
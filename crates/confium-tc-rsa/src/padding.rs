@@ -0,0 +1,321 @@
+//! RFC 8017 encodings: EMSA-PKCS1-v1_5, EMSA-PSS and EME-OAEP, all
+//! with SHA-256 (and MGF1-SHA-256 where a mask is needed).
+//!
+//! Shoup's protocol raises an integer to the shared private exponent
+//! and is indifferent to where the integer came from. [`RsaPadding`]
+//! decides that: for the signature paddings it is the encoded message,
+//! for OAEP it is the ciphertext, and [`RsaPadding::finish`] turns the
+//! combined result back into a signature or a plaintext.
+
+use num_bigint::BigUint;
+use sha2::{Digest, Sha256};
+
+use crate::arith::i2osp;
+use crate::error::{Result, RsaErrorCode, scheme_error};
+use crate::keys::RsaPublicKey;
+
+/// SHA-256 output length, also the PSS salt length.
+pub const HASH_LEN: usize = 32;
+
+/// DER `DigestInfo` prefix for SHA-256 (RFC 8017 §9.2 note 1).
+pub const SHA256_DIGEST_INFO_PREFIX: [u8; 19] = [
+    0x30, 0x31, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01, 0x05,
+    0x00, 0x04, 0x20,
+];
+
+/// Which RSA operation a session performs, and so how its input is
+/// encoded and its output decoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RsaPadding {
+    /// RSASSA-PKCS1-v1_5 over a caller-supplied DER `DigestInfo`
+    /// (PKCS#11 `CKM_RSA_PKCS`).
+    Pkcs1v15,
+    /// RSASSA-PKCS1-v1_5 with SHA-256; the message is hashed here
+    /// (PKCS#11 `CKM_SHA256_RSA_PKCS`).
+    Pkcs1v15Sha256,
+    /// RSASSA-PSS with SHA-256, MGF1-SHA-256 and a 32-byte salt over a
+    /// caller-supplied SHA-256 digest (PKCS#11 `CKM_RSA_PKCS_PSS`).
+    Pss,
+    /// RSASSA-PSS with SHA-256, MGF1-SHA-256 and a 32-byte salt; the
+    /// message is hashed here (PKCS#11 `CKM_SHA256_RSA_PKCS_PSS`).
+    PssSha256,
+    /// RSAES-OAEP decryption with SHA-256, MGF1-SHA-256 and an empty
+    /// label (PKCS#11 `CKM_RSA_PKCS_OAEP`).
+    OaepSha256,
+}
+
+impl RsaPadding {
+    /// Every padding, in scheme-table order.
+    pub const ALL: [RsaPadding; 5] = [
+        RsaPadding::Pkcs1v15,
+        RsaPadding::Pkcs1v15Sha256,
+        RsaPadding::Pss,
+        RsaPadding::PssSha256,
+        RsaPadding::OaepSha256,
+    ];
+
+    /// The registered scheme name for this padding.
+    pub fn scheme_name(self) -> &'static str {
+        match self {
+            RsaPadding::Pkcs1v15 => crate::PKCS1_SCHEME_NAME,
+            RsaPadding::Pkcs1v15Sha256 => crate::PKCS1_SHA256_SCHEME_NAME,
+            RsaPadding::Pss => crate::PSS_SCHEME_NAME,
+            RsaPadding::PssSha256 => crate::PSS_SHA256_SCHEME_NAME,
+            RsaPadding::OaepSha256 => crate::OAEP_SHA256_SCHEME_NAME,
+        }
+    }
+
+    /// Whether the padding decrypts rather than signs.
+    pub fn is_decryption(self) -> bool {
+        self == RsaPadding::OaepSha256
+    }
+
+    /// Whether the encoding needs a salt the signers agree on first.
+    pub fn salted(self) -> bool {
+        matches!(self, RsaPadding::Pss | RsaPadding::PssSha256)
+    }
+
+    /// The integer the signers exponentiate for session input `input`.
+    /// `salt` is only read by the PSS paddings.
+    pub fn encode(self, key: &RsaPublicKey, input: &[u8], salt: &[u8]) -> Result<BigUint> {
+        let k = key.size();
+        let em = match self {
+            RsaPadding::Pkcs1v15 => emsa_pkcs1v15(input, k)?,
+            RsaPadding::Pkcs1v15Sha256 => emsa_pkcs1v15(&digest_info_sha256(input), k)?,
+            RsaPadding::Pss => {
+                if input.len() != HASH_LEN {
+                    return Err(scheme_error(RsaErrorCode::BAD_MESSAGE));
+                }
+                emsa_pss_encode(input, key.bits(), salt)?
+            }
+            RsaPadding::PssSha256 => emsa_pss_encode(&Sha256::digest(input), key.bits(), salt)?,
+            RsaPadding::OaepSha256 => {
+                let c = BigUint::from_bytes_be(input);
+                if input.len() != k || c >= key.n {
+                    return Err(scheme_error(RsaErrorCode::BAD_MESSAGE));
+                }
+                return Ok(c);
+            }
+        };
+        Ok(BigUint::from_bytes_be(&em))
+    }
+
+    /// Turn the combined `y = x^d mod n` into the session output: a
+    /// `k`-byte signature, or the OAEP-decoded plaintext.
+    pub fn finish(self, key: &RsaPublicKey, y: &BigUint) -> Result<Vec<u8>> {
+        let em = i2osp(y, key.size()).ok_or_else(|| scheme_error(RsaErrorCode::INTERNAL))?;
+        if self.is_decryption() {
+            oaep_decode(&em)
+        } else {
+            Ok(em)
+        }
+    }
+}
+
+/// The DER `DigestInfo` of SHA-256(`message`).
+pub fn digest_info_sha256(message: &[u8]) -> Vec<u8> {
+    let mut out = SHA256_DIGEST_INFO_PREFIX.to_vec();
+    out.extend_from_slice(&Sha256::digest(message));
+    out
+}
+
+/// EMSA-PKCS1-v1_5 (RFC 8017 §9.2) of an already-built `DigestInfo`:
+/// `00 01 FF..FF 00 ‖ T`, `k` bytes long.
+pub fn emsa_pkcs1v15(digest_info: &[u8], k: usize) -> Result<Vec<u8>> {
+    if digest_info.len() + 11 > k {
+        return Err(scheme_error(RsaErrorCode::BAD_MESSAGE));
+    }
+    let mut em = vec![0xff; k];
+    em[0] = 0x00;
+    em[1] = 0x01;
+    em[k - digest_info.len() - 1] = 0x00;
+    em[k - digest_info.len()..].copy_from_slice(digest_info);
+    Ok(em)
+}
+
+/// MGF1 with SHA-256 (RFC 8017 §B.2.1).
+fn mgf1(seed: &[u8], len: usize) -> Vec<u8> {
+    let mut out = Vec::with_capacity(len + HASH_LEN);
+    let mut counter = 0u32;
+    while out.len() < len {
+        let mut h = Sha256::new();
+        h.update(seed);
+        h.update(counter.to_be_bytes());
+        out.extend_from_slice(&h.finalize());
+        counter += 1;
+    }
+    out.truncate(len);
+    out
+}
+
+fn xor_in_place(dst: &mut [u8], mask: &[u8]) {
+    for (d, m) in dst.iter_mut().zip(mask) {
+        *d ^= m;
+    }
+}
+
+/// `H = SHA-256(0x00 * 8 ‖ mHash ‖ salt)`.
+fn pss_hash(m_hash: &[u8], salt: &[u8]) -> Vec<u8> {
+    let mut h = Sha256::new();
+    h.update([0u8; 8]);
+    h.update(m_hash);
+    h.update(salt);
+    h.finalize().to_vec()
+}
+
+/// EMSA-PSS-ENCODE (RFC 8017 §9.1.1) of digest `m_hash` for a modulus
+/// of `mod_bits` bits, with `emBits = mod_bits - 1`.
+pub fn emsa_pss_encode(m_hash: &[u8], mod_bits: u64, salt: &[u8]) -> Result<Vec<u8>> {
+    let em_bits = mod_bits - 1;
+    let em_len = em_bits.div_ceil(8) as usize;
+    if em_len < HASH_LEN + salt.len() + 2 {
+        return Err(scheme_error(RsaErrorCode::BAD_MESSAGE));
+    }
+    let h = pss_hash(m_hash, salt);
+    let db_len = em_len - HASH_LEN - 1;
+    let mut db = vec![0u8; db_len];
+    db[db_len - salt.len() - 1] = 0x01;
+    db[db_len - salt.len()..].copy_from_slice(salt);
+    xor_in_place(&mut db, &mgf1(&h, db_len));
+    db[0] &= 0xff >> (8 * em_len as u64 - em_bits);
+    let mut em = db;
+    em.extend_from_slice(&h);
+    em.push(0xbc);
+    Ok(em)
+}
+
+/// EMSA-PSS-VERIFY (RFC 8017 §9.1.2) with a [`HASH_LEN`]-byte salt.
+pub fn emsa_pss_verify(m_hash: &[u8], em: &[u8], mod_bits: u64) -> bool {
+    let em_bits = mod_bits - 1;
+    let em_len = em_bits.div_ceil(8) as usize;
+    if em.len() != em_len || em_len < 2 * HASH_LEN + 2 || em[em_len - 1] != 0xbc {
+        return false;
+    }
+    let top_mask = 0xffu8 >> (8 * em_len as u64 - em_bits);
+    if em[0] & !top_mask != 0 {
+        return false;
+    }
+    let db_len = em_len - HASH_LEN - 1;
+    let (masked_db, h) = em[..em_len - 1].split_at(db_len);
+    let mut db = masked_db.to_vec();
+    xor_in_place(&mut db, &mgf1(h, db_len));
+    db[0] &= top_mask;
+    let ps_len = db_len - HASH_LEN - 1;
+    if db[..ps_len].iter().any(|&b| b != 0) || db[ps_len] != 0x01 {
+        return false;
+    }
+    pss_hash(m_hash, &db[ps_len + 1..]) == h
+}
+
+/// `SHA-256("")`, the OAEP label hash for the empty label.
+fn empty_label_hash() -> Vec<u8> {
+    Sha256::digest(b"").to_vec()
+}
+
+/// EME-OAEP encoding (RFC 8017 §7.1.1 step 2) of `message` into `k`
+/// bytes with the given `seed`.
+pub fn oaep_encode(message: &[u8], k: usize, seed: &[u8; HASH_LEN]) -> Result<Vec<u8>> {
+    if k < 2 * HASH_LEN + 2 || message.len() > k - 2 * HASH_LEN - 2 {
+        return Err(scheme_error(RsaErrorCode::BAD_MESSAGE));
+    }
+    let db_len = k - HASH_LEN - 1;
+    let mut db = empty_label_hash();
+    db.resize(db_len - message.len() - 1, 0);
+    db.push(0x01);
+    db.extend_from_slice(message);
+    xor_in_place(&mut db, &mgf1(seed, db_len));
+    let mut masked_seed = seed.to_vec();
+    xor_in_place(&mut masked_seed, &mgf1(&db, HASH_LEN));
+    let mut em = vec![0x00];
+    em.extend_from_slice(&masked_seed);
+    em.extend_from_slice(&db);
+    Ok(em)
+}
+
+/// EME-OAEP decoding (RFC 8017 §7.1.2 step 3). Every failure is the
+/// same `DECRYPTION_FAILED`, and the scan does not stop early, so the
+/// caller learns nothing about which check failed.
+pub fn oaep_decode(em: &[u8]) -> Result<Vec<u8>> {
+    let k = em.len();
+    if k < 2 * HASH_LEN + 2 {
+        return Err(scheme_error(RsaErrorCode::DECRYPTION_FAILED));
+    }
+    let (masked_seed, masked_db) = em[1..].split_at(HASH_LEN);
+    let mut seed = masked_seed.to_vec();
+    xor_in_place(&mut seed, &mgf1(masked_db, HASH_LEN));
+    let mut db = masked_db.to_vec();
+    xor_in_place(&mut db, &mgf1(&seed, k - HASH_LEN - 1));
+
+    let mut bad = em[0];
+    for (a, b) in db[..HASH_LEN].iter().zip(empty_label_hash()) {
+        bad |= a ^ b;
+    }
+    // Find the 0x01 separator after the zero padding without branching
+    // on secret bytes.
+    let mut separator = 0usize;
+    let mut looking = 1u8;
+    for (i, &b) in db[HASH_LEN..].iter().enumerate() {
+        let is_one = u8::from(b == 0x01);
+        let is_zero = u8::from(b == 0x00);
+        let found = looking & is_one;
+        separator |= (found as usize) * (HASH_LEN + i);
+        bad |= looking & !is_one & !is_zero;
+        looking &= !found & 1;
+    }
+    bad |= looking;
+    if bad != 0 {
+        return Err(scheme_error(RsaErrorCode::DECRYPTION_FAILED));
+    }
+    Ok(db[separator + 1..].to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pkcs1v15_layout() {
+        let t = digest_info_sha256(b"abc");
+        assert_eq!(t.len(), 51);
+        let em = emsa_pkcs1v15(&t, 128).unwrap();
+        assert_eq!(&em[..2], &[0x00, 0x01]);
+        assert!(em[2..128 - 52].iter().all(|&b| b == 0xff));
+        assert_eq!(em[128 - 52], 0x00);
+        assert_eq!(&em[128 - 51..], &t[..]);
+        assert!(emsa_pkcs1v15(&t, 61).is_err());
+    }
+
+    #[test]
+    fn pss_round_trip_and_tamper() {
+        let m_hash = Sha256::digest(b"pss");
+        for mod_bits in [1024u64, 1025, 2047] {
+            let em = emsa_pss_encode(&m_hash, mod_bits, &[7u8; HASH_LEN]).unwrap();
+            assert_eq!(em.len() as u64, (mod_bits - 1).div_ceil(8));
+            assert!(emsa_pss_verify(&m_hash, &em, mod_bits));
+            let mut bad = em.clone();
+            bad[10] ^= 1;
+            assert!(!emsa_pss_verify(&m_hash, &bad, mod_bits));
+            assert!(!emsa_pss_verify(&Sha256::digest(b"other"), &em, mod_bits));
+        }
+    }
+
+    #[test]
+    fn oaep_round_trip() {
+        for msg in [&b""[..], b"attack at dawn", &[0x01; 62]] {
+            let em = oaep_encode(msg, 128, &[3u8; HASH_LEN]).unwrap();
+            assert_eq!(em.len(), 128);
+            assert_eq!(oaep_decode(&em).unwrap(), msg);
+        }
+        assert!(oaep_encode(&[0u8; 63], 128, &[3u8; HASH_LEN]).is_err());
+    }
+
+    #[test]
+    fn oaep_rejects_any_tamper() {
+        let em = oaep_encode(b"secret", 128, &[9u8; HASH_LEN]).unwrap();
+        for i in [0usize, 1, 40, 127] {
+            let mut bad = em.clone();
+            bad[i] ^= 0x80;
+            assert!(oaep_decode(&bad).is_err(), "byte {i}");
+        }
+    }
+}
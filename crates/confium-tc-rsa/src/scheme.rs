@@ -0,0 +1,58 @@
+//! Threshold RSA scheme registration.
+//!
+//! One scheme name per padding, all driven by [`ShoupSession`] over the
+//! same dealer-issued [`RsaKeyShare`](crate::RsaKeyShare) blobs:
+//!
+//! | Name                     | Kind        | Session message      | Produces                     |
+//! |--------------------------|-------------|----------------------|------------------------------|
+//! | `RSA-SHOUP-PKCS1`        | `Signature` | DER `DigestInfo`     | RSASSA-PKCS1-v1_5 signature  |
+//! | `RSA-SHOUP-PKCS1-SHA256` | `Signature` | message              | RSASSA-PKCS1-v1_5 signature  |
+//! | `RSA-SHOUP-PSS`          | `Signature` | SHA-256 digest       | RSASSA-PSS signature         |
+//! | `RSA-SHOUP-PSS-SHA256`   | `Signature` | message              | RSASSA-PSS signature         |
+//! | `RSA-SHOUP-OAEP-SHA256`  | `Kem`       | RSAES-OAEP ciphertext| plaintext                    |
+
+use confium_tc::Result;
+use confium_tc::registry::{SessionImpl, TcScheme, TcSchemeKind};
+use confium_tc::session::SessionParams;
+
+use crate::padding::RsaPadding;
+use crate::session::ShoupSession;
+
+/// Declare and register the scheme of one padding.
+macro_rules! rsa_scheme {
+    ($scheme:ident, $padding:expr) => {
+        #[doc = concat!("Threshold RSA with `", stringify!($padding), "` (registered as its `scheme_name`).")]
+        pub struct $scheme;
+
+        impl TcScheme for $scheme {
+            fn name(&self) -> &'static str {
+                $padding.scheme_name()
+            }
+            fn kind(&self) -> TcSchemeKind {
+                if $padding.is_decryption() {
+                    TcSchemeKind::Kem
+                } else {
+                    TcSchemeKind::Signature
+                }
+            }
+            fn create_session(&self, params: &SessionParams) -> Result<Box<dyn SessionImpl>> {
+                ShoupSession::build_session(params, $padding)
+            }
+            fn restore_session(
+                &self,
+                params: &SessionParams,
+                state: &[u8],
+            ) -> Result<Box<dyn SessionImpl>> {
+                ShoupSession::restore_session(params, $padding, state)
+            }
+        }
+
+        confium_tc::register_tc_scheme!($scheme);
+    };
+}
+
+rsa_scheme!(RsaShoupPkcs1, RsaPadding::Pkcs1v15);
+rsa_scheme!(RsaShoupPkcs1Sha256, RsaPadding::Pkcs1v15Sha256);
+rsa_scheme!(RsaShoupPss, RsaPadding::Pss);
+rsa_scheme!(RsaShoupPssSha256, RsaPadding::PssSha256);
+rsa_scheme!(RsaShoupOaepSha256, RsaPadding::OaepSha256);
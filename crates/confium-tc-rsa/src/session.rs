@@ -0,0 +1,294 @@
+//! The threshold RSA session, shared by every [`RsaPadding`].
+//!
+//! The local share is an [`RsaKeyShare`] blob from the [`crate::dealer`]
+//! ceremony; the session message is the message (or digest, or
+//! `DigestInfo`) to sign, or the ciphertext to decrypt. Any `T` share
+//! holders can run it together.
+//!
+//! ## Rounds
+//!
+//! PKCS#1 v1.5 and OAEP take two framework rounds:
+//!
+//! 1. **Partial** — encode the input to `x`, broadcast `x_i` and its
+//!    proof of correctness.
+//! 2. **Combine** — verify every peer's proof, combine `T` of them into
+//!    `y = x^d` and finish it into a signature or a plaintext.
+//!
+//! PSS needs every signer to encode with the same salt, so it runs a
+//! salt round first: each signer broadcasts a random 32-byte
+//! contribution and the salt is SHA-256 over all of them in party-id
+//! order. A signer that withholds or biases its contribution can at
+//! worst pick among salts, which PSS tolerates — the salt is public and
+//! only needs to vary.
+//!
+//! A peer whose message is malformed or whose proof fails is named
+//! through [`confium_tc::Error::MessageRejected`].
+
+use num_bigint::BigUint;
+use rand_core::{OsRng, RngCore};
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+use confium_tc::registry::{RoundResult, SessionImpl};
+use confium_tc::session::SessionParams;
+use confium_tc::snapshot::{self, StateReader, StateWriter};
+use confium_tc::{Message, Result};
+
+use crate::error::{RsaErrorCode, scheme_error};
+use crate::keys::RsaKeyShare;
+use crate::padding::{HASH_LEN, RsaPadding};
+use crate::shoup::{self, PartialResult};
+
+const TAG_SALT: u8 = 0xE1;
+const TAG_PARTIAL: u8 = 0xE2;
+
+const SALT_DOMAIN: &[u8] = b"confium-tc-rsa/pss-salt/v1";
+
+/// One party's view of a threshold RSA signing or decryption run.
+pub struct ShoupSession {
+    padding: RsaPadding,
+    party_id: String,
+    roster_ids: Vec<String>,
+    share: RsaKeyShare,
+    input: Vec<u8>,
+    /// Salt contributions by party id, ours included. PSS only.
+    salts: Vec<(String, [u8; HASH_LEN])>,
+    /// The encoded input, fixed in the partial round.
+    x: Option<BigUint>,
+    /// Our partial result, kept so the combine round can include it.
+    own_partial: Option<PartialResult>,
+    result: Option<Vec<u8>>,
+    round_done: u8,
+}
+
+/// Abort naming the sender of `msg`.
+fn reject(msg: &Message, reason: impl Into<String>) -> confium_tc::Error {
+    confium_tc::error::MessageRejectedSnafu {
+        party: msg.from_party_id.clone(),
+        round: msg.round,
+        reason: reason.into(),
+    }
+    .build()
+}
+
+impl ShoupSession {
+    pub fn build_session(
+        params: &SessionParams,
+        padding: RsaPadding,
+    ) -> Result<Box<dyn SessionImpl>> {
+        Ok(Box::new(Self::new(params, padding)?))
+    }
+
+    /// Rebuild a session from its [`SessionImpl::snapshot`] output.
+    pub fn restore_session(
+        params: &SessionParams,
+        padding: RsaPadding,
+        state: &[u8],
+    ) -> Result<Box<dyn SessionImpl>> {
+        let mut session = Self::new(params, padding)?;
+        let mut r = StateReader::new(state);
+        session.round_done = r.u8()?;
+        for _ in 0..r.u32()? {
+            let id = r.string()?;
+            session.salts.push((id, r.array()?));
+        }
+        if r.bool()? {
+            session.x = Some(BigUint::from_bytes_be(r.bytes()?));
+        }
+        if r.bool()? {
+            let k = session.share.public_key.size();
+            session.own_partial = Some(
+                PartialResult::from_bytes(r.bytes()?, k)
+                    .ok_or_else(|| snapshot::invalid("bad partial result"))?,
+            );
+        }
+        if r.bool()? {
+            session.result = Some(r.bytes()?.to_vec());
+        }
+        r.finish()?;
+        Ok(Box::new(session))
+    }
+
+    fn new(params: &SessionParams, padding: RsaPadding) -> Result<Self> {
+        let party_id = params.parties.get(params.this_party_idx)?.id.clone();
+        let roster_ids = params
+            .parties
+            .parties()
+            .iter()
+            .map(|p| p.id.clone())
+            .collect();
+        let share_bytes = params
+            .local_share
+            .as_ref()
+            .map(|s| s.bytes())
+            .ok_or_else(|| scheme_error(RsaErrorCode::BAD_SHARE))?;
+        let share = RsaKeyShare::from_bytes(share_bytes)?;
+        Ok(ShoupSession {
+            padding,
+            party_id,
+            roster_ids,
+            share,
+            input: params.message.clone().unwrap_or_default(),
+            salts: Vec::new(),
+            x: None,
+            own_partial: None,
+            result: None,
+            round_done: 0,
+        })
+    }
+
+    /// The round in which partial results are broadcast.
+    fn partial_round(&self) -> u8 {
+        if self.padding.salted() { 2 } else { 1 }
+    }
+
+    /// Check that `msg` is a round-`round` broadcast with `tag` from a
+    /// roster member, returning its body.
+    fn body<'m>(&self, msg: &'m Message, round: u8, tag: u8) -> Result<&'m [u8]> {
+        if !self.roster_ids.contains(&msg.from_party_id) {
+            return Err(reject(msg, "sender is not in the roster"));
+        }
+        if msg.round != round || msg.payload.first() != Some(&tag) || !msg.is_broadcast() {
+            return Err(reject(msg, format!("expected a round {round} broadcast")));
+        }
+        Ok(&msg.payload[1..])
+    }
+
+    /// PSS round 1 — broadcast our salt contribution.
+    fn round_salt(&mut self) -> Result<RoundResult> {
+        let mut contribution = [0u8; HASH_LEN];
+        OsRng.fill_bytes(&mut contribution);
+        self.salts.push((self.party_id.clone(), contribution));
+        let mut payload = vec![TAG_SALT];
+        payload.extend_from_slice(&contribution);
+        let msg = Message::broadcast(&self.party_id, 1, payload);
+        Ok(RoundResult::new(vec![msg], false))
+    }
+
+    /// The agreed PSS salt: SHA-256 over every contribution in party-id
+    /// order.
+    fn collect_salt(&mut self, incoming: &[Message]) -> Result<Vec<u8>> {
+        for m in incoming.iter().filter(|m| m.from_party_id != self.party_id) {
+            let body = self.body(m, 1, TAG_SALT)?;
+            let contribution: [u8; HASH_LEN] = body
+                .try_into()
+                .map_err(|_| reject(m, "malformed salt contribution"))?;
+            if self.salts.iter().any(|(id, _)| *id == m.from_party_id) {
+                return Err(reject(m, "duplicate salt contribution"));
+            }
+            self.salts.push((m.from_party_id.clone(), contribution));
+        }
+        self.salts.sort_by(|a, b| a.0.cmp(&b.0));
+        let mut h = Sha256::new();
+        h.update(SALT_DOMAIN);
+        for (id, contribution) in &self.salts {
+            h.update((id.len() as u32).to_be_bytes());
+            h.update(id.as_bytes());
+            h.update(contribution);
+        }
+        Ok(h.finalize().to_vec())
+    }
+
+    /// Encode the input and broadcast our partial result.
+    fn round_partial(&mut self, incoming: &[Message]) -> Result<RoundResult> {
+        let salt = if self.padding.salted() {
+            self.collect_salt(incoming)?
+        } else {
+            Vec::new()
+        };
+        let key = &self.share.public_key;
+        let x = self.padding.encode(key, &self.input, &salt)?;
+        let partial = shoup::partial(&self.share, &x);
+        let mut payload = vec![TAG_PARTIAL];
+        payload.extend(partial.to_bytes(key.size()));
+        self.x = Some(x);
+        self.own_partial = Some(partial);
+        let msg = Message::broadcast(&self.party_id, self.partial_round(), payload);
+        Ok(RoundResult::new(vec![msg], false))
+    }
+
+    /// Verify every peer's partial result, combine and finish.
+    fn round_combine(&mut self, incoming: &[Message]) -> Result<RoundResult> {
+        let (Some(x), Some(own)) = (&self.x, &self.own_partial) else {
+            return Err(scheme_error(RsaErrorCode::INTERNAL));
+        };
+        let k = self.share.public_key.size();
+        let mut partials = vec![own.clone()];
+        let mut senders = vec![self.party_id.as_str()];
+        for m in incoming.iter().filter(|m| m.from_party_id != self.party_id) {
+            let body = self.body(m, self.partial_round(), TAG_PARTIAL)?;
+            let partial = PartialResult::from_bytes(body, k)
+                .ok_or_else(|| reject(m, "malformed partial result"))?;
+            if senders.contains(&m.from_party_id.as_str()) {
+                return Err(reject(m, "duplicate partial result"));
+            }
+            if !shoup::verify_partial(&self.share, x, &partial) {
+                return Err(reject(m, "partial result failed its proof"));
+            }
+            senders.push(&m.from_party_id);
+            // x_i is a function of the share alone, so a second valid
+            // partial for the same index adds nothing.
+            if partials.iter().all(|p| p.index != partial.index) {
+                partials.push(partial);
+            }
+        }
+        let y = shoup::combine(&self.share, x, &partials)?;
+        self.result = Some(self.padding.finish(&self.share.public_key, &y)?);
+        Ok(RoundResult::done())
+    }
+}
+
+impl SessionImpl for ShoupSession {
+    fn round(&mut self, incoming: &[Message]) -> Result<RoundResult> {
+        self.round_done = self.round_done.checked_add(1).ok_or_else(|| {
+            confium_tc::error::RoundOverflowSnafu {
+                round: self.round_done,
+            }
+            .build()
+        })?;
+        let partial_round = self.partial_round();
+        match self.round_done {
+            1 if self.padding.salted() => self.round_salt(),
+            r if r == partial_round => self.round_partial(incoming),
+            r if r == partial_round + 1 => self.round_combine(incoming),
+            other => Err(confium_tc::error::RoundOverflowSnafu { round: other }.build()),
+        }
+    }
+
+    fn result(&self) -> Result<Vec<u8>> {
+        self.result
+            .clone()
+            .ok_or_else(|| confium_tc::error::SessionNotCompleteSnafu {}.build())
+    }
+
+    fn destroy(&mut self) {
+        self.share.secret = BigUint::default();
+        self.input.fill(0);
+        self.x = None;
+        self.own_partial = None;
+        self.result = None;
+    }
+
+    fn snapshot(&self) -> Option<Zeroizing<Vec<u8>>> {
+        let mut w = StateWriter::new();
+        w.u8(self.round_done);
+        w.u32(self.salts.len() as u32);
+        for (id, contribution) in &self.salts {
+            w.str(id);
+            w.fixed(contribution);
+        }
+        w.bool(self.x.is_some());
+        if let Some(x) = &self.x {
+            w.bytes(&x.to_bytes_be());
+        }
+        w.bool(self.own_partial.is_some());
+        if let Some(partial) = &self.own_partial {
+            w.bytes(&partial.to_bytes(self.share.public_key.size()));
+        }
+        w.bool(self.result.is_some());
+        if let Some(result) = &self.result {
+            w.bytes(result);
+        }
+        Some(w.finish())
+    }
+}
@@ -0,0 +1,279 @@
+//! Shoup's partial exponentiation, its proof of correctness and the
+//! combination step (Shoup §2.2–2.3).
+//!
+//! With `Δ = l!` for `l` dealt parties, party `i` publishes
+//! `x_i = x^{2Δ s_i}` for the input `x`, together with a non-interactive
+//! proof that `log_{x̃}(x_i²) = log_v(v_i)` where `x̃ = x^{4Δ}`. Any set
+//! `S` of at least `threshold` verified partials combines to
+//! `w = Π x_j^{2λ_j}` with the integer coefficients
+//! `λ_j = Δ·Π_{j'≠j} j'/(j' − j)`, so `w^e = x^{4Δ²}`; Bézout's
+//! `4Δ²·a + e·b = 1` then gives `y = w^a·x^b` with `y^e = x`.
+
+use num_bigint::{BigInt, BigUint, RandBigInt};
+use num_traits::One;
+use rand_core::OsRng;
+use sha2::{Digest, Sha256};
+
+use crate::arith::{extended_gcd, factorial, i2osp, pow_signed};
+use crate::error::{Result, RsaErrorCode, scheme_error};
+use crate::keys::RsaKeyShare;
+
+/// Challenge length in bits, Shoup's `L1`.
+pub const CHALLENGE_BITS: u64 = 128;
+
+const CHALLENGE_BYTES: usize = (CHALLENGE_BITS / 8) as usize;
+
+/// Extra bytes a response needs beyond the modulus: `z = s_i·c + r`
+/// with `r < 2^{|n| + 2·L1}` is below `2^{|n| + 2·L1 + 1}`.
+const RESPONSE_SLACK: usize = 2 * CHALLENGE_BYTES + 1;
+
+const PROOF_DOMAIN: &[u8] = b"confium-tc-rsa/shoup-proof/v1";
+
+/// One party's partial result `x_i` and its proof of correctness.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartialResult {
+    /// 1-based dealer index of the party.
+    pub index: u32,
+    /// `x_i = x^{2Δ s_i} mod n`.
+    pub value: BigUint,
+    /// Proof challenge `c`, below `2^L1`.
+    pub challenge: BigUint,
+    /// Proof response `z = s_i·c + r`.
+    pub response: BigUint,
+}
+
+impl PartialResult {
+    /// Wire length for a `k`-byte modulus.
+    pub fn encoded_len(k: usize) -> usize {
+        4 + k + CHALLENGE_BYTES + k + RESPONSE_SLACK
+    }
+
+    /// `index u32 | x_i[k] | c[16] | z[k + 33]`.
+    pub fn to_bytes(&self, k: usize) -> Vec<u8> {
+        let mut out = self.index.to_be_bytes().to_vec();
+        for (x, len) in [
+            (&self.value, k),
+            (&self.challenge, CHALLENGE_BYTES),
+            (&self.response, k + RESPONSE_SLACK),
+        ] {
+            out.extend(i2osp(x, len).expect("partial result fields are range-checked"));
+        }
+        out
+    }
+
+    /// Decode a partial result for a `k`-byte modulus. Range checks
+    /// against the key happen in [`verify_partial`].
+    pub fn from_bytes(data: &[u8], k: usize) -> Option<Self> {
+        if data.len() != Self::encoded_len(k) {
+            return None;
+        }
+        let (index, rest) = data.split_at(4);
+        let (value, rest) = rest.split_at(k);
+        let (challenge, response) = rest.split_at(CHALLENGE_BYTES);
+        Some(PartialResult {
+            index: u32::from_be_bytes(index.try_into().ok()?),
+            value: BigUint::from_bytes_be(value),
+            challenge: BigUint::from_bytes_be(challenge),
+            response: BigUint::from_bytes_be(response),
+        })
+    }
+}
+
+/// `Δ = l!` for the key's party count.
+fn delta(share: &RsaKeyShare) -> BigUint {
+    factorial(share.parties())
+}
+
+/// `c = H(n, v, x̃, v_i, x_i², v', x')` truncated to [`CHALLENGE_BITS`].
+fn challenge(share: &RsaKeyShare, index: u32, elements: [&BigUint; 5]) -> BigUint {
+    let key = &share.public_key;
+    let k = key.size();
+    let mut h = Sha256::new();
+    h.update(PROOF_DOMAIN);
+    h.update(index.to_be_bytes());
+    for x in [&key.n, &share.verification_base]
+        .into_iter()
+        .chain(elements)
+    {
+        h.update(i2osp(x, k).expect("group elements are reduced mod n"));
+    }
+    BigUint::from_bytes_be(&h.finalize()[..CHALLENGE_BYTES])
+}
+
+/// Compute this party's partial result on input `x` (already reduced
+/// mod `n`), with its proof.
+pub fn partial(share: &RsaKeyShare, x: &BigUint) -> PartialResult {
+    let n = &share.public_key.n;
+    let delta = delta(share);
+    let x_tilde = x.modpow(&(&delta << 2u32), n);
+    let value = x.modpow(&((&delta << 1u32) * &share.secret), n);
+    let value_sq = value.modpow(&BigUint::from(2u32), n);
+
+    let r = OsRng.gen_biguint(n.bits() + 2 * CHALLENGE_BITS);
+    let v_prime = share.verification_base.modpow(&r, n);
+    let x_prime = x_tilde.modpow(&r, n);
+    let own_key = share
+        .verification_key(share.index)
+        .expect("share index is range-checked on decode");
+    let challenge = challenge(
+        share,
+        share.index,
+        [&x_tilde, own_key, &value_sq, &v_prime, &x_prime],
+    );
+    let response = &share.secret * &challenge + r;
+    PartialResult {
+        index: share.index,
+        value,
+        challenge,
+        response,
+    }
+}
+
+/// Check `partial`'s proof of correctness for input `x` against the
+/// verification keys carried by `share`.
+pub fn verify_partial(share: &RsaKeyShare, x: &BigUint, partial: &PartialResult) -> bool {
+    let n = &share.public_key.n;
+    let Some(v_i) = share.verification_key(partial.index) else {
+        return false;
+    };
+    if partial.value >= *n
+        || partial.challenge.bits() > CHALLENGE_BITS
+        || partial.response.bits() > n.bits() + 2 * CHALLENGE_BITS + 1
+    {
+        return false;
+    }
+    let x_tilde = x.modpow(&(delta(share) << 2u32), n);
+    let value_sq = partial.value.modpow(&BigUint::from(2u32), n);
+    let minus_c = -BigInt::from(partial.challenge.clone());
+    let (Some(v_c), Some(x_c)) = (
+        pow_signed(v_i, &minus_c, n),
+        pow_signed(&value_sq, &minus_c, n),
+    ) else {
+        return false;
+    };
+    let v_prime = share.verification_base.modpow(&partial.response, n) * v_c % n;
+    let x_prime = x_tilde.modpow(&partial.response, n) * x_c % n;
+    challenge(
+        share,
+        partial.index,
+        [&x_tilde, v_i, &value_sq, &v_prime, &x_prime],
+    ) == partial.challenge
+}
+
+/// Shoup's integer Lagrange coefficient `λ_{0,j}^S = Δ·Π j'/(j' − j)`.
+fn lagrange(delta: &BigUint, set: &[u32], j: u32) -> BigInt {
+    let mut num = BigInt::from(delta.clone());
+    let mut den = BigInt::one();
+    for &other in set.iter().filter(|&&other| other != j) {
+        num *= other;
+        den *= i64::from(other) - i64::from(j);
+    }
+    num / den
+}
+
+/// Combine verified partial results on input `x` into `y = x^d mod n`.
+///
+/// `partials` must have passed [`verify_partial`] and carry distinct
+/// indices; the first `threshold` of them (by index) are used.
+pub fn combine(share: &RsaKeyShare, x: &BigUint, partials: &[PartialResult]) -> Result<BigUint> {
+    let key = &share.public_key;
+    let n = &key.n;
+    if partials.len() < share.threshold as usize {
+        return Err(scheme_error(RsaErrorCode::BELOW_THRESHOLD));
+    }
+    let mut chosen: Vec<&PartialResult> = partials.iter().collect();
+    chosen.sort_by_key(|p| p.index);
+    chosen.truncate(share.threshold as usize);
+    let set: Vec<u32> = chosen.iter().map(|p| p.index).collect();
+
+    let delta = delta(share);
+    let combine_failed = || scheme_error(RsaErrorCode::COMBINE_FAILED);
+    let mut w = BigUint::one();
+    for p in &chosen {
+        let exponent = lagrange(&delta, &set, p.index) << 1u32;
+        w = w * pow_signed(&p.value, &exponent, n).ok_or_else(combine_failed)? % n;
+    }
+
+    // 4Δ²·a + e·b = 1; gcd is 1 because e is a prime above l.
+    let e_prime = BigInt::from(&delta * &delta) << 2u32;
+    let (g, a, b) = extended_gcd(&e_prime, &BigInt::from(key.e.clone()));
+    if !g.is_one() {
+        return Err(scheme_error(RsaErrorCode::BAD_SHARE));
+    }
+    let y = pow_signed(&w, &a, n).ok_or_else(combine_failed)?
+        * pow_signed(x, &b, n).ok_or_else(combine_failed)?
+        % n;
+    if y.modpow(&key.e, n) != *x {
+        return Err(combine_failed());
+    }
+    Ok(y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::dealer::deal_with_primes;
+
+    const P: &str = "f7270cfc837b8879c958fa689b6637c48e0a302d8ca83075bbeba1d960ef40dc7f68ad0cccc0cece0c9a1a3d4b52bcd5f7880428d6622b382906b72107ac2a57";
+    const Q: &str = "e92859643f40adf1d51d9ac3836e1fa9d002130937b8d0cdeb8c8bebd4a9d83c7db9641405c7b19bec3663eecab4559c4553baf60775491f4f747605a27867bb";
+
+    fn shares(threshold: u32, parties: u32) -> Vec<RsaKeyShare> {
+        let p = BigUint::parse_bytes(P.as_bytes(), 16).unwrap();
+        let q = BigUint::parse_bytes(Q.as_bytes(), 16).unwrap();
+        deal_with_primes(&p, &q, threshold, parties).unwrap()
+    }
+
+    #[test]
+    fn any_threshold_subset_combines_to_the_rsa_root() {
+        let shares = shares(3, 5);
+        let x = BigUint::from(0x1234_5678u32);
+        for subset in [[0usize, 1, 2], [0, 2, 4], [4, 3, 1]] {
+            let partials: Vec<PartialResult> =
+                subset.iter().map(|&i| partial(&shares[i], &x)).collect();
+            for p in &partials {
+                assert!(verify_partial(&shares[0], &x, p));
+            }
+            let y = combine(&shares[0], &x, &partials).unwrap();
+            let key = &shares[0].public_key;
+            assert_eq!(y.modpow(&key.e, &key.n), x);
+        }
+    }
+
+    #[test]
+    fn below_threshold_is_refused() {
+        let shares = shares(3, 5);
+        let x = BigUint::from(7u32);
+        let partials: Vec<PartialResult> = shares[..2].iter().map(|s| partial(s, &x)).collect();
+        assert!(combine(&shares[0], &x, &partials).is_err());
+    }
+
+    #[test]
+    fn forged_partials_fail_their_proof() {
+        let shares = shares(2, 3);
+        let x = BigUint::from(99u32);
+        let honest = partial(&shares[1], &x);
+        let n = &shares[0].public_key.n;
+
+        let mut wrong_value = honest.clone();
+        wrong_value.value = &wrong_value.value * 2u32 % n;
+        assert!(!verify_partial(&shares[0], &x, &wrong_value));
+
+        let mut wrong_index = honest.clone();
+        wrong_index.index = 3;
+        assert!(!verify_partial(&shares[0], &x, &wrong_index));
+
+        assert!(!verify_partial(&shares[0], &BigUint::from(100u32), &honest));
+    }
+
+    #[test]
+    fn partial_result_round_trips() {
+        let shares = shares(2, 3);
+        let p = partial(&shares[2], &BigUint::from(5u32));
+        let k = shares[0].public_key.size();
+        let bytes = p.to_bytes(k);
+        assert_eq!(bytes.len(), PartialResult::encoded_len(k));
+        assert_eq!(PartialResult::from_bytes(&bytes, k).unwrap(), p);
+        assert!(PartialResult::from_bytes(&bytes[1..], k).is_none());
+    }
+}
@@ -0,0 +1,201 @@
+//! End-to-end threshold RSA over a fixed 2048-bit key.
+//!
+//! The key is dealt from fixed safe primes so the suite does not spend
+//! a minute generating them. The PKCS#1 v1.5 signature and the OAEP
+//! ciphertext below were produced by an independent implementation
+//! (pyca/cryptography) holding the unshared private key; Shoup's
+//! combined result must match it byte for byte. PSS signatures are
+//! randomised and are checked with the `rsa` crate's verifier instead.
+
+use confium_tc::Session;
+use confium_tc::SessionParams;
+use confium_tc::party::{Party, PartyList};
+use confium_tc::share::Share;
+use confium_tc_rsa::padding::{SHA256_DIGEST_INFO_PREFIX, digest_info_sha256};
+use confium_tc_rsa::{RsaKeyShare, RsaPadding, RsaPublicKey, dealer, inprocess};
+use rsa::pkcs1::DecodeRsaPublicKey as _;
+use rsa::sha2::{Digest as _, Sha256};
+use signature::Verifier as _;
+
+/// 1024-bit safe primes; their product is a 2048-bit modulus.
+const P: &str = "c34770e67cdf1a2aff7db657c5186c9107ad3871adcc98cf529298a82fbc374d977c6579afa280e48310bba7fcebad8a3e28834984cf378ff4138b08e2c8271c5bb8c71b936c9e33b4688248674e7c81f9c3b256b54bb4f789b64fff103b4d014e2d2b80087095b976cc72a19083362b4790902390a52b9e3abac2b00599524b";
+const Q: &str = "ec348febacff46f9841150ac8d270922265f3b43daf1c8fee6bc2f33ea1df22f6d38bdc3d8a2909a356cb623225c7a278c0ae5440888c2cf9506df73e508bca6b785a717d32bbb529d8e4a4af8b711d7809b3209e048a1613e51ebbeb9398f6e33a19fb085df35174217421910bbb7236523cf796b4ed22b4f87ca269c89c9f3";
+
+const MESSAGE: &[u8] = b"Confium threshold RSA";
+
+/// RSASSA-PKCS1-v1_5 / SHA-256 over [`MESSAGE`] with the fixture key.
+const PKCS1_SIGNATURE: &str = "5275630eb28aad0f895bb3f609282bb78d43b6c5193477b8c1f614974785980fb436cfbc6198bb301652fdafafb76955446a46a99a93b990da8b7931da9da0abdd0499b3333b0aa14e42bdc78d047bfa36f7c5b2299b0a878b8ac4646e9bfb7df741c04f9835aea90162d1e3122341e6a4b2c29b0d5e6fca7ecac6464cf6c2440e6af26eccf7718e4369dc1638a03d09de8f7391961e1ac765b1af63d1d55f2d1189b8177f2d9c3aa78ca7b159c6690ef93a5d39c1a9348d9308e4c2b63a91bb8c10871d955e328a313b4fa220741b219481889aa747ebbdcfd1b7727e7d8fb537ef377e3dc4489c1a0cb8dc94014950e0d3cd80ce231b8b88152b679bdaa65f";
+
+/// RSAES-OAEP / SHA-256 encryption of `attack at dawn` to the fixture key.
+const OAEP_CIPHERTEXT: &str = "03d5c6f061337998fbf8da6d31957cb518c6deaa564132344f27957fdd357767ee81fbf55788afb70f89570dd35fa7bd8b2512ba5677971ebb532e877f09e6859cd420de918f31e770a3abada18a1ac2a75c6ccf97b48589ea870da550625858134135c2996ebc2e1d9860c74fb5a97dd448182e53fb549b7ccfc311473498a544fb124afcb08080179a3f0627f99c230deef3d23c8c824a55fc704a00fdc93240b8776e91b076f1540e9b8fda30f41a184b457ce5c1cef445dfeb17bace592c6da00aaeaffa3186b269a1e2231c9604463d33fe186ac8415e44dea2087fe889ce4dc81ea6bd2f73cb981ffe03b6ac90886fdb924ee9f760dbe331f80615fdf9";
+
+fn unhex(s: &str) -> Vec<u8> {
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+/// Deal the fixture key; returns the share blobs and the public key.
+fn deal(threshold: u32, parties: u32) -> (Vec<Vec<u8>>, RsaPublicKey) {
+    let p = num_bigint::BigUint::parse_bytes(P.as_bytes(), 16).unwrap();
+    let q = num_bigint::BigUint::parse_bytes(Q.as_bytes(), 16).unwrap();
+    let shares = dealer::deal_with_primes(&p, &q, threshold, parties).expect("deal");
+    let key = shares[0].public_key.clone();
+    (shares.iter().map(RsaKeyShare::to_bytes).collect(), key)
+}
+
+fn rsa_key(key: &RsaPublicKey) -> rsa::RsaPublicKey {
+    rsa::RsaPublicKey::from_pkcs1_der(&key.to_pkcs1_der()).expect("PKCS#1 DER")
+}
+
+#[test]
+fn every_padding_is_registered() {
+    for padding in RsaPadding::ALL {
+        let scheme = confium_tc::registry::find(padding.scheme_name()).expect("registered");
+        let kind = if padding.is_decryption() {
+            confium_tc::TcSchemeKind::Kem
+        } else {
+            confium_tc::TcSchemeKind::Signature
+        };
+        assert_eq!(scheme.kind(), kind);
+    }
+}
+
+#[test]
+fn pkcs1_signature_matches_the_unshared_key() {
+    let (blobs, key) = deal(2, 3);
+    let expected = unhex(PKCS1_SIGNATURE);
+    for pair in [[0, 1], [0, 2], [1, 2]] {
+        let signers = [blobs[pair[0]].clone(), blobs[pair[1]].clone()];
+        let sig = inprocess::sign(RsaPadding::Pkcs1v15Sha256, &signers, 2, MESSAGE).expect("sign");
+        assert_eq!(sig, expected);
+    }
+    assert!(key.verify_pkcs1v15_sha256(MESSAGE, &expected));
+
+    // CKM_RSA_PKCS: the caller builds the DigestInfo.
+    let digest_info = digest_info_sha256(MESSAGE);
+    assert_eq!(digest_info[..19], SHA256_DIGEST_INFO_PREFIX);
+    let sig = inprocess::sign(RsaPadding::Pkcs1v15, &blobs[1..], 2, &digest_info).expect("sign");
+    assert_eq!(sig, expected);
+
+    let vk = rsa::pkcs1v15::VerifyingKey::<Sha256>::new(rsa_key(&key));
+    let sig = rsa::pkcs1v15::Signature::try_from(&expected[..]).unwrap();
+    vk.verify(MESSAGE, &sig)
+        .expect("rsa crate accepts the signature");
+}
+
+#[test]
+fn pss_signatures_verify_with_the_rsa_crate() {
+    let (blobs, key) = deal(3, 5);
+    let vk = rsa::pss::VerifyingKey::<Sha256>::new(rsa_key(&key));
+    let signers = [blobs[0].clone(), blobs[2].clone(), blobs[4].clone()];
+
+    let sig = inprocess::sign(RsaPadding::PssSha256, &signers, 3, MESSAGE).expect("sign");
+    assert_eq!(sig.len(), 256);
+    assert!(key.verify_pss_sha256(MESSAGE, &sig));
+    let parsed = rsa::pss::Signature::try_from(&sig[..]).unwrap();
+    vk.verify(MESSAGE, &parsed)
+        .expect("rsa crate accepts the signature");
+
+    // CKM_RSA_PKCS_PSS: the caller hashes.
+    let digest = Sha256::digest(MESSAGE);
+    let sig = inprocess::sign(RsaPadding::Pss, &signers, 3, &digest).expect("sign");
+    assert!(key.verify_pss_sha256(MESSAGE, &sig));
+    assert!(inprocess::sign(RsaPadding::Pss, &signers, 3, MESSAGE).is_err());
+}
+
+#[test]
+fn oaep_decryption_matches_the_unshared_key() {
+    let (blobs, key) = deal(2, 3);
+    let plaintext = inprocess::decrypt(&blobs[..2], 2, &unhex(OAEP_CIPHERTEXT)).expect("decrypt");
+    assert_eq!(plaintext, b"attack at dawn");
+
+    let ciphertext = key.encrypt_oaep_sha256(b"threshold secret").unwrap();
+    assert_eq!(
+        inprocess::decrypt(&blobs[1..], 2, &ciphertext).unwrap(),
+        b"threshold secret"
+    );
+
+    let mut forged = ciphertext;
+    forged[100] ^= 1;
+    assert!(inprocess::decrypt(&blobs[1..], 2, &forged).is_err());
+}
+
+#[test]
+fn below_threshold_fails() {
+    let (blobs, _) = deal(3, 5);
+    assert!(inprocess::sign(RsaPadding::Pkcs1v15Sha256, &blobs[..2], 3, MESSAGE).is_err());
+}
+
+fn params(padding: RsaPadding, roster: &[&str], idx: usize, blob: &[u8]) -> SessionParams {
+    let parties = roster.iter().map(|id| Party::inproc(*id)).collect();
+    SessionParams {
+        scheme: padding.scheme_name().to_string(),
+        parties: PartyList::from_parties(parties),
+        threshold: 2,
+        this_party_idx: idx,
+        local_share: Some(Share::new(padding.scheme_name(), blob.to_vec())),
+        message: Some(MESSAGE.to_vec()),
+    }
+}
+
+#[test]
+fn tampered_partial_names_the_signer() {
+    let (blobs, _) = deal(2, 3);
+    let roster = ["alice", "bob"];
+    let mut sessions: Vec<Session> = (0..2)
+        .map(|i| {
+            Session::create(&params(RsaPadding::Pkcs1v15Sha256, &roster, i, &blobs[i])).unwrap()
+        })
+        .collect();
+    let mut outgoing: Vec<confium_tc::Message> = Vec::new();
+    for sess in &mut sessions {
+        outgoing.extend(sess.round_step(&[]).unwrap().outgoing);
+    }
+    for m in outgoing.iter_mut().filter(|m| m.from_party_id == "bob") {
+        m.payload[10] ^= 0x01;
+    }
+    let inbox: Vec<_> = outgoing
+        .iter()
+        .filter(|m| m.from_party_id == "bob")
+        .cloned()
+        .collect();
+    let err = sessions[0].round_step(&inbox).unwrap_err();
+    assert_eq!(err.culprit(), Some("bob"));
+}
+
+#[test]
+fn pss_session_resumes_from_a_snapshot() {
+    use std::sync::Arc;
+
+    use confium_tc::snapshot::MemoryNonceLedger;
+
+    const SNAPSHOT_KEY: &[u8] = b"threshold-rsa-snapshot-key";
+    let (blobs, key) = deal(2, 3);
+    let roster = ["alice", "bob"];
+    let padding = RsaPadding::PssSha256;
+    let p: Vec<SessionParams> = (0..2)
+        .map(|i| params(padding, &roster, i, &blobs[i]))
+        .collect();
+    let ledger = Arc::new(MemoryNonceLedger::new());
+
+    let mut alice = Session::create(&p[0]).expect("alice");
+    alice.set_nonce_ledger(ledger.clone());
+    let mut bob = Session::create(&p[1]).expect("bob");
+    alice.round_step(&[]).expect("alice round 1");
+    let blob = alice.snapshot(SNAPSHOT_KEY).expect("snapshot");
+    let b1 = bob.round_step(&[]).expect("bob round 1").outgoing;
+    drop(alice);
+
+    let mut alice = Session::resume(&p[0], &blob, SNAPSHOT_KEY, ledger).expect("resume");
+    let a1 = alice.last_outgoing().to_vec();
+    let a2 = alice.round_step(&b1).expect("alice round 2").outgoing;
+    let b2 = bob.round_step(&a1).expect("bob round 2").outgoing;
+    alice.round_step(&b2).expect("alice round 3");
+    bob.round_step(&a2).expect("bob round 3");
+
+    let sig = alice.result().expect("alice signature");
+    assert_eq!(sig, bob.result().expect("bob signature"));
+    assert!(key.verify_pss_sha256(MESSAGE, &sig));
+}
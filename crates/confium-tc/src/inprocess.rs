@@ -38,10 +38,11 @@ use crate::session::{Session, SessionParams};
 use crate::share::Share;
 
 /// Upper bound on the number of framework-round iterations before the
-/// driver gives up. Real protocols (FROST, CMP20, GG18) top out at 4;
-/// the bound exists to fail loudly on a misbehaving scheme rather than
-/// spin forever.
-const MAX_ROUNDS: u8 = 8;
+/// driver gives up. Signing protocols (FROST, CMP20, GG18) top out at
+/// 4; threshold ML-KEM evaluates a circuit one layer per round and needs
+/// about 90. The bound exists to fail loudly on a misbehaving scheme
+/// rather than spin forever.
+const MAX_ROUNDS: u8 = 128;
 
 /// Drive a registered DKG scheme to completion in-process.
 ///
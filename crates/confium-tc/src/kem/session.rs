@@ -3,9 +3,20 @@
 //! State machine parallel to `confium-tc::Session` but for decryption.
 //! Each party holds a share; T-of-N collaborate via the coordinator to
 //! decapsulate a shared secret that can then AEAD-decrypt the ciphertext.
+//!
+//! When the algorithm is a registered [`TcSchemeKind::Kem`] scheme (for
+//! example `confium-tc-ml-kem`'s `ML-KEM-768-threshold`), [`KemSession::round`]
+//! drives that scheme's session with the roster `p0` … `p{N-1}` and the
+//! encapsulated key as the session message. Other algorithms fall back
+//! to collecting [`PartialDecryption`]s.
 
 use crate::kem::encapsulate::{EncapsulateError, EncapsulatedKey};
 use crate::kem::share::ThresholdShare;
+use crate::message::Message;
+use crate::party::{Party, PartyList};
+use crate::registry::{self, RoundResult, TcSchemeKind};
+use crate::session::{Session, SessionParams};
+use crate::share::Share;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -50,6 +61,8 @@ pub struct KemSession {
     partial_decryptions: Vec<PartialDecryption>,
     result: Option<Vec<u8>>,
     created_at: DateTime<Utc>,
+    /// The registered scheme's session, once [`KemSession::round`] runs.
+    inner: Option<Session>,
 }
 
 /// A single party's partial decryption contribution.
@@ -94,6 +107,9 @@ pub enum KemError {
         /// Session algorithm.
         session: String,
     },
+    /// The registered scheme's session failed.
+    #[error("scheme session error: {0}")]
+    Scheme(#[from] crate::Error),
 }
 
 impl KemSession {
@@ -106,6 +122,7 @@ impl KemSession {
             partial_decryptions: Vec::new(),
             result: None,
             created_at: Utc::now(),
+            inner: None,
         }
     }
 
@@ -133,8 +150,70 @@ impl KemSession {
         Ok(())
     }
 
+    /// Run one round of the registered scheme's session with the
+    /// messages other parties sent in the previous round, returning the
+    /// messages to deliver for the next one.
+    pub fn round(&mut self, incoming: &[Message]) -> Result<RoundResult, KemError> {
+        if self.state != KemSessionState::Pending && self.state != KemSessionState::Round1Complete {
+            return Err(KemError::InvalidState {
+                current: self.state,
+                expected: "pending or round1_complete",
+            });
+        }
+        if self.inner.is_none() {
+            self.inner = Some(self.create_inner()?);
+        }
+        let inner = self.inner.as_mut().expect("created above");
+        match inner.round_step(incoming) {
+            Ok(res) => {
+                self.state = if res.complete {
+                    KemSessionState::Completed
+                } else {
+                    KemSessionState::Round1Complete
+                };
+                Ok(res)
+            }
+            Err(e) => {
+                self.state = KemSessionState::Aborted;
+                Err(e.into())
+            }
+        }
+    }
+
+    /// Create the registered scheme's session for this party.
+    fn create_inner(&self) -> Result<Session, KemError> {
+        let algorithm = &self.params.algorithm;
+        let kem = registry::find(algorithm).filter(|s| s.kind() == TcSchemeKind::Kem);
+        if kem.is_none() {
+            return Err(EncapsulateError::UnknownAlgorithm(algorithm.clone()).into());
+        }
+        if self.params.encapsulated_key.algorithm != *algorithm {
+            return Err(KemError::AlgorithmMismatch {
+                share: self.params.encapsulated_key.algorithm.clone(),
+                session: algorithm.clone(),
+            });
+        }
+        let share = self.local_share.as_ref().ok_or(KemError::MissingShare)?;
+        let roster = (0..self.params.num_parties)
+            .map(|i| Party::inproc(format!("p{i}")))
+            .collect();
+        Ok(Session::create(&SessionParams {
+            scheme: algorithm.clone(),
+            parties: PartyList::from_parties(roster),
+            threshold: self.params.threshold,
+            this_party_idx: self.params.this_party_idx as usize,
+            local_share: Some(Share::new(algorithm.clone(), share.bytes.clone())),
+            message: Some(self.params.encapsulated_key.bytes.clone()),
+        })?)
+    }
+
     /// Try to complete the decapsulation.
     pub fn try_complete(&mut self) -> Result<Vec<u8>, KemError> {
+        if let Some(inner) = &self.inner {
+            let result = inner.result()?;
+            self.result = Some(result.clone());
+            return Ok(result);
+        }
         let needed = self.params.threshold as usize;
         if self.partial_decryptions.len() < needed {
             return Err(KemError::ThresholdNotMet {
@@ -236,6 +315,19 @@ mod tests {
         assert!(matches!(result, Err(KemError::ThresholdNotMet { .. })));
     }
 
+    #[test]
+    fn unregistered_algorithm_cannot_run_rounds() {
+        let mut session = KemSession::new(sample_params());
+        let share = ThresholdShare::new("mock-threshold-kem", 0, vec![1u8; 32]);
+        session.set_local_share(share).unwrap();
+        let result = session.round(&[]);
+        assert!(matches!(
+            result,
+            Err(KemError::Encapsulate(EncapsulateError::UnknownAlgorithm(_)))
+        ));
+        assert_eq!(session.state(), KemSessionState::Pending);
+    }
+
     #[test]
     fn algorithm_mismatch_rejected() {
         let mut session = KemSession::new(sample_params());
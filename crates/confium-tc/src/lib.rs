@@ -11,6 +11,8 @@
 //!
 //! - the `cfm_tc_*` FFI surface for threshold sessions
 //! - async session coordinator (for globally distributed signers)
+//! - networked session driver running any registered scheme over
+//!   `confium-net` transports
 //! - share re-sharing + proactive refresh (committee evolution without
 //!   changing public key)
 //! - threshold KEM session interface (parallel to signing session)
@@ -31,6 +33,8 @@
 //   error, message, party, registry, session, share, share_envelope.
 // ffi and inprocess have tc-specific concerns (unsafe, different imports)
 // and remain as local modules.
+pub use confium_tc_core::auth;
+pub use confium_tc_core::broadcast;
 pub use confium_tc_core::error;
 pub use confium_tc_core::message;
 pub use confium_tc_core::party;
@@ -38,9 +42,11 @@ pub use confium_tc_core::registry;
 pub use confium_tc_core::session;
 pub use confium_tc_core::share;
 pub use confium_tc_core::share_envelope;
+pub use confium_tc_core::snapshot;
 
 pub use confium_tc_core::Error;
 pub use confium_tc_core::Message;
+pub use confium_tc_core::NonceLedger;
 pub use confium_tc_core::Party;
 pub use confium_tc_core::PartyList;
 pub use confium_tc_core::Result;
@@ -57,6 +63,7 @@ pub mod coordinator;
 pub mod ffi;
 pub mod inprocess;
 pub mod kem;
+pub mod network;
 pub mod paillier;
 pub mod reshare;
 pub mod schemes;
@@ -0,0 +1,785 @@
+//! [`NetworkDriver`]: one party's round loop over real transports.
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use confium_net::{Listener, Transport};
+
+use super::wire::{Frame, MAX_FRAME_LEN};
+use crate::message::Message;
+use crate::party::PartyList;
+use crate::session::Session;
+
+/// Timing and identification knobs for a [`NetworkDriver`].
+#[derive(Debug, Clone)]
+pub struct DriverConfig {
+    /// Carried in every connection's `Hello`; connections for a
+    /// different tag are dropped. Use a per-session value so two
+    /// sessions sharing endpoints cannot cross-talk.
+    pub session_tag: String,
+    /// How long to wait for every peer's frames of one round before
+    /// failing with [`DriverError::RoundTimeout`].
+    pub round_timeout: Duration,
+    /// While waiting on a round, how often to ask the peers that are
+    /// still missing to retransmit it.
+    pub resend_interval: Duration,
+    /// Delay between connection attempts to an unreachable peer. Also
+    /// bounds how quickly cancellation is noticed.
+    pub reconnect_interval: Duration,
+    /// After the local session completes, how long to keep serving
+    /// retransmission requests while peers finish.
+    pub linger: Duration,
+    /// Upper bound on driven rounds, so a misbehaving scheme fails
+    /// loudly instead of spinning.
+    pub max_rounds: u8,
+}
+
+impl Default for DriverConfig {
+    fn default() -> Self {
+        DriverConfig {
+            session_tag: String::new(),
+            round_timeout: Duration::from_secs(30),
+            resend_interval: Duration::from_secs(2),
+            reconnect_interval: Duration::from_millis(100),
+            linger: Duration::from_secs(5),
+            max_rounds: 32,
+        }
+    }
+}
+
+/// Cancels a running [`NetworkDriver::run`] from another thread.
+///
+/// Cloning shares the flag. The driver notices within one
+/// [`DriverConfig::reconnect_interval`], tells its peers it aborted and
+/// returns [`DriverError::Cancelled`].
+#[derive(Debug, Clone, Default)]
+pub struct CancelHandle(Arc<AtomicBool>);
+
+impl CancelHandle {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+/// Errors from [`NetworkDriver`].
+#[derive(Debug, thiserror::Error)]
+pub enum DriverError {
+    /// The session itself failed (scheme abort, rejected message, ...).
+    #[error("session error: {0}")]
+    Session(#[from] crate::Error),
+    /// The local listener could not be set up.
+    #[error("transport error: {0}")]
+    Transport(#[from] confium_net::Error),
+    /// The roster does not describe the session being driven.
+    #[error("roster has {roster} parties, session has {session}")]
+    RosterMismatch {
+        /// Roster length.
+        roster: usize,
+        /// Session party count.
+        session: usize,
+    },
+    /// A roster party has no transport URL.
+    #[error("party '{party}' has no transport endpoint")]
+    MissingEndpoint {
+        /// Party id.
+        party: String,
+    },
+    /// The scheme addressed a message to a party outside the roster.
+    #[error("message directed to unknown party '{party}'")]
+    UnknownRecipient {
+        /// The unknown recipient id.
+        party: String,
+    },
+    /// Some peers never delivered a round.
+    #[error("round {round} timed out waiting for {missing:?}")]
+    RoundTimeout {
+        /// The round whose messages were awaited.
+        round: u8,
+        /// Parties that did not deliver it.
+        missing: Vec<String>,
+    },
+    /// A peer announced it gave up on the session.
+    #[error("party '{party}' aborted the session at round {round}")]
+    PeerAborted {
+        /// The aborting party.
+        party: String,
+        /// Its last round.
+        round: u8,
+    },
+    /// The session ran past [`DriverConfig::max_rounds`].
+    #[error("session did not complete within {max} rounds")]
+    TooManyRounds {
+        /// The configured bound.
+        max: u8,
+    },
+    /// [`CancelHandle::cancel`] was called.
+    #[error("session cancelled")]
+    Cancelled,
+}
+
+/// Drives one party's [`Session`] to completion against its peers over
+/// `confium-net` transports.
+///
+/// Every roster party needs a `transport_endpoint`; the local party
+/// listens on its own (or on [`NetworkDriver::with_listen_url`]) and
+/// connects out to every peer's. Any URL scheme whose transport crate
+/// is linked works (`inproc://`, `tcp://`, `tcp+tls://`, `quic://`,
+/// `ws://`, ...).
+///
+/// A session restored with [`Session::resume`] picks up where its
+/// snapshot left off: the driver re-posts [`Session::last_outgoing`]
+/// for the snapshotted round and waits for the next one. Register a
+/// [`NetworkDriver::with_checkpoint`] hook to take those snapshots.
+pub struct NetworkDriver {
+    session: Session,
+    checkpoint: Option<Checkpoint>,
+    our_id: String,
+    listen_url: String,
+    peers: Vec<Peer>,
+    config: DriverConfig,
+    cancel: CancelHandle,
+}
+
+/// Called after every round, before its messages are sent.
+type Checkpoint = Box<dyn FnMut(&Session) -> crate::Result<()> + Send>;
+
+/// Outbound state towards one peer.
+struct Peer {
+    id: String,
+    url: String,
+    conn: Option<Box<dyn Transport>>,
+    queue: VecDeque<Vec<u8>>,
+    next_connect: Instant,
+}
+
+/// Encoded frames sent at each step, per peer (roster order minus
+/// ourselves), kept for retransmission.
+type Outbox = BTreeMap<u8, Vec<Vec<Vec<u8>>>>;
+
+impl NetworkDriver {
+    /// Wrap `session`, whose roster is `parties`. Fails when the roster
+    /// does not match the session or a party has no endpoint.
+    pub fn new(session: Session, parties: &PartyList) -> Result<Self, DriverError> {
+        if parties.len() != session.party_count() {
+            return Err(DriverError::RosterMismatch {
+                roster: parties.len(),
+                session: session.party_count(),
+            });
+        }
+        let (our_id, listen_url) = endpoint(parties, session.this_party_idx())?;
+        let now = Instant::now();
+        let peers = (0..parties.len())
+            .filter(|&idx| idx != session.this_party_idx())
+            .map(|idx| {
+                let (id, url) = endpoint(parties, idx)?;
+                Ok(Peer {
+                    id,
+                    url,
+                    conn: None,
+                    queue: VecDeque::new(),
+                    next_connect: now,
+                })
+            })
+            .collect::<Result<Vec<_>, DriverError>>()?;
+        Ok(NetworkDriver {
+            session,
+            checkpoint: None,
+            our_id,
+            listen_url,
+            peers,
+            config: DriverConfig::default(),
+            cancel: CancelHandle::default(),
+        })
+    }
+
+    pub fn with_config(mut self, config: DriverConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Listen somewhere other than our roster endpoint — e.g. bind
+    /// `tcp://0.0.0.0:7000` while peers dial a public hostname.
+    pub fn with_listen_url(mut self, url: impl Into<String>) -> Self {
+        self.listen_url = url.into();
+        self
+    }
+
+    /// Run `checkpoint` after every round, before that round's messages
+    /// go out — typically to persist [`Session::snapshot`]. An error
+    /// fails the run with [`DriverError::Session`] without sending.
+    pub fn with_checkpoint(
+        mut self,
+        checkpoint: impl FnMut(&Session) -> crate::Result<()> + Send + 'static,
+    ) -> Self {
+        self.checkpoint = Some(Box::new(checkpoint));
+        self
+    }
+
+    pub fn cancel_handle(&self) -> CancelHandle {
+        self.cancel.clone()
+    }
+
+    pub fn session(&self) -> &Session {
+        &self.session
+    }
+
+    pub fn into_session(self) -> Session {
+        self.session
+    }
+
+    /// Run rounds until the session completes and return
+    /// [`Session::result`].
+    ///
+    /// Blocks the calling thread. On any failure the peers are sent an
+    /// abort notice before the error is returned, so they fail fast
+    /// instead of timing out.
+    pub fn run(&mut self) -> Result<Vec<u8>, DriverError> {
+        let listener = confium_net::listen(&self.listen_url)?;
+        let (tx, rx) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let peer_ids: Arc<[String]> = self.peers.iter().map(|p| p.id.clone()).collect();
+        let acceptor = {
+            let stop = stop.clone();
+            let url = self.listen_url.clone();
+            let tag = self.config.session_tag.clone();
+            thread::spawn(move || accept_loop(listener, &url, stop, peer_ids, tag, tx))
+        };
+
+        let outcome = self.drive(&rx);
+        if outcome.is_err() {
+            self.abort_peers();
+        }
+        for peer in &mut self.peers {
+            if let Some(mut conn) = peer.conn.take() {
+                conn.close().ok();
+            }
+        }
+        // The accept thread is parked in a blocking `accept`; a throwaway
+        // connection wakes it up to observe `stop` and drop the listener.
+        // An inproc listener may be between registrations, so retry until
+        // the thread is gone and the endpoint is free for a later run.
+        stop.store(true, Ordering::SeqCst);
+        while !acceptor.is_finished() {
+            if let Ok(mut conn) = confium_net::connect(&self.listen_url) {
+                conn.close().ok();
+                break;
+            }
+            thread::sleep(self.config.reconnect_interval);
+        }
+        acceptor.join().ok();
+        outcome
+    }
+
+    fn drive(&mut self, rx: &Receiver<(usize, Frame)>) -> Result<Vec<u8>, DriverError> {
+        let mut inbox = Inbox::new(self.peers.len());
+        let mut outbox = Outbox::new();
+        let mut incoming = Vec::new();
+        // A resumed session has already run its snapshotted round; its
+        // peers may or may not have seen that round's messages, so send
+        // them (again) before moving on.
+        let mut resumed = self.session.round() > 0;
+        loop {
+            if self.cancel.is_cancelled() {
+                return Err(DriverError::Cancelled);
+            }
+            let (outgoing, complete) = if resumed {
+                resumed = false;
+                let outgoing = self.session.last_outgoing().to_vec();
+                (outgoing, self.session.is_complete())
+            } else {
+                let res = self.session.round_step(&incoming)?;
+                if let Some(checkpoint) = &mut self.checkpoint {
+                    checkpoint(&self.session)?;
+                }
+                (res.outgoing, res.complete)
+            };
+            let step = self.session.round();
+            self.post(step, &outgoing, complete, &mut outbox)?;
+            if complete {
+                self.linger(rx, &mut inbox, &outbox, step);
+                return Ok(self.session.result()?);
+            }
+            if step >= self.config.max_rounds {
+                return Err(DriverError::TooManyRounds {
+                    max: self.config.max_rounds,
+                });
+            }
+            incoming = self.collect(rx, &mut inbox, &outbox, step)?;
+        }
+    }
+
+    /// Queue this step's messages (plus a closing `RoundEnd`) for every
+    /// peer they are addressed to.
+    fn post(
+        &mut self,
+        step: u8,
+        outgoing: &[Message],
+        complete: bool,
+        outbox: &mut Outbox,
+    ) -> Result<(), DriverError> {
+        let unknown = outgoing
+            .iter()
+            .filter_map(|m| m.to_party_id.as_deref())
+            .find(|to| *to != self.our_id && !self.peers.iter().any(|p| p.id == *to));
+        if let Some(to) = unknown {
+            return Err(DriverError::UnknownRecipient {
+                party: to.to_string(),
+            });
+        }
+        let mut per_peer = Vec::with_capacity(self.peers.len());
+        for peer in &mut self.peers {
+            let mut frames: Vec<Vec<u8>> = outgoing
+                .iter()
+                .filter(|m| m.is_for(&peer.id))
+                .enumerate()
+                .map(|(seq, m)| {
+                    Frame::Msg {
+                        step,
+                        seq: seq as u32,
+                        message: m.clone(),
+                    }
+                    .encode()
+                })
+                .collect();
+            frames.push(
+                Frame::RoundEnd {
+                    step,
+                    count: frames.len() as u32,
+                    complete,
+                }
+                .encode(),
+            );
+            peer.queue.extend(frames.iter().cloned());
+            per_peer.push(frames);
+        }
+        outbox.insert(step, per_peer);
+        self.flush();
+        Ok(())
+    }
+
+    /// Wait until every peer has closed `step`, then hand back its
+    /// messages for the next `round_step`.
+    fn collect(
+        &mut self,
+        rx: &Receiver<(usize, Frame)>,
+        inbox: &mut Inbox,
+        outbox: &Outbox,
+        step: u8,
+    ) -> Result<Vec<Message>, DriverError> {
+        let start = Instant::now();
+        let deadline = start + self.config.round_timeout;
+        let mut next_resend = start + self.config.resend_interval;
+        loop {
+            let missing = inbox.missing(step);
+            if missing.is_empty() {
+                return Ok(inbox.take(step));
+            }
+            if self.cancel.is_cancelled() {
+                return Err(DriverError::Cancelled);
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(DriverError::RoundTimeout {
+                    round: step,
+                    missing: missing.iter().map(|&i| self.peers[i].id.clone()).collect(),
+                });
+            }
+            if now >= next_resend {
+                for &idx in &missing {
+                    self.peers[idx]
+                        .queue
+                        .push_back(Frame::Resend { step }.encode());
+                }
+                next_resend = now + self.config.resend_interval;
+            }
+            self.flush();
+            let wait = self
+                .config
+                .reconnect_interval
+                .min(deadline.saturating_duration_since(now));
+            match rx.recv_timeout(wait) {
+                Ok((peer, frame)) => self.handle(peer, frame, inbox, outbox, step)?,
+                Err(RecvTimeoutError::Timeout) => {}
+                // The accept thread died with the listener; nothing new
+                // can arrive, so let the deadline report who is missing.
+                Err(RecvTimeoutError::Disconnected) => thread::sleep(wait),
+            }
+        }
+    }
+
+    /// Keep delivering our final frames and answering retransmission
+    /// requests until every peer reports completion or
+    /// [`DriverConfig::linger`] expires. Best effort: our own result is
+    /// already final.
+    fn linger(
+        &mut self,
+        rx: &Receiver<(usize, Frame)>,
+        inbox: &mut Inbox,
+        outbox: &Outbox,
+        step: u8,
+    ) {
+        let deadline = Instant::now() + self.config.linger;
+        loop {
+            self.flush();
+            let queued = self.peers.iter().any(|p| !p.queue.is_empty());
+            if (!queued && inbox.all_finished()) || self.cancel.is_cancelled() {
+                return;
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return;
+            }
+            let wait = self
+                .config
+                .reconnect_interval
+                .min(deadline.saturating_duration_since(now));
+            match rx.recv_timeout(wait) {
+                Ok((peer, frame)) => {
+                    if self.handle(peer, frame, inbox, outbox, step).is_err() {
+                        inbox.finished[peer] = true;
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+        }
+    }
+
+    fn handle(
+        &mut self,
+        peer: usize,
+        frame: Frame,
+        inbox: &mut Inbox,
+        outbox: &Outbox,
+        step: u8,
+    ) -> Result<(), DriverError> {
+        match frame {
+            Frame::Resend { step } => {
+                if let Some(frames) = outbox.get(&step) {
+                    self.peers[peer].queue.extend(frames[peer].iter().cloned());
+                }
+                Ok(())
+            }
+            Frame::Abort { step } => Err(DriverError::PeerAborted {
+                party: self.peers[peer].id.clone(),
+                round: step,
+            }),
+            frame => {
+                inbox.accept(peer, frame, step);
+                Ok(())
+            }
+        }
+    }
+
+    /// Push queued frames to every reachable peer, (re)connecting where
+    /// the connection is down and the back-off has elapsed. A failed
+    /// send keeps the frame queued for the next connection.
+    fn flush(&mut self) {
+        let now = Instant::now();
+        for peer in &mut self.peers {
+            if peer.queue.is_empty() {
+                continue;
+            }
+            if peer.conn.is_none() {
+                if now < peer.next_connect {
+                    continue;
+                }
+                peer.conn = open(&peer.url, &self.config.session_tag, &self.our_id);
+                if peer.conn.is_none() {
+                    peer.next_connect = now + self.config.reconnect_interval;
+                    continue;
+                }
+            }
+            while let Some(frame) = peer.queue.front() {
+                let conn = peer.conn.as_mut().expect("connected above");
+                if conn.send(frame).is_err() {
+                    peer.conn = None;
+                    peer.next_connect = now + self.config.reconnect_interval;
+                    break;
+                }
+                peer.queue.pop_front();
+            }
+        }
+    }
+
+    /// Best-effort abort notice to every peer: one connection attempt
+    /// each, nothing retried.
+    fn abort_peers(&mut self) {
+        let abort = Frame::Abort {
+            step: self.session.round(),
+        }
+        .encode();
+        for peer in &mut self.peers {
+            if peer.conn.is_none() {
+                peer.conn = open(&peer.url, &self.config.session_tag, &self.our_id);
+            }
+            if let Some(conn) = peer.conn.as_mut() {
+                conn.send(&abort).ok();
+            }
+        }
+    }
+}
+
+/// `(id, url)` of roster party `idx`.
+fn endpoint(parties: &PartyList, idx: usize) -> Result<(String, String), DriverError> {
+    let party = parties.get(idx)?;
+    let url = party
+        .transport_endpoint
+        .clone()
+        .ok_or_else(|| DriverError::MissingEndpoint {
+            party: party.id.clone(),
+        })?;
+    Ok((party.id.clone(), url))
+}
+
+/// Connect to `url` and introduce ourselves.
+fn open(url: &str, session_tag: &str, our_id: &str) -> Option<Box<dyn Transport>> {
+    let mut conn = confium_net::connect(url).ok()?;
+    let hello = Frame::Hello {
+        session_tag: session_tag.to_string(),
+        from: our_id.to_string(),
+    };
+    conn.send(&hello.encode()).ok()?;
+    Some(conn)
+}
+
+/// Accept inbound connections on `url` until `stop` is raised, one
+/// reader thread per connection.
+///
+/// An `inproc://` registration is consumed by the first peer that
+/// connects to it, so the channel is registered again after every
+/// accept; a peer dialing in the gap retries after its back-off.
+fn accept_loop(
+    mut listener: Box<dyn Listener>,
+    url: &str,
+    stop: Arc<AtomicBool>,
+    peers: Arc<[String]>,
+    session_tag: String,
+    tx: Sender<(usize, Frame)>,
+) {
+    let rearm = url.starts_with("inproc:");
+    while let Ok(conn) = listener.accept() {
+        if stop.load(Ordering::SeqCst) {
+            return;
+        }
+        let peers = peers.clone();
+        let session_tag = session_tag.clone();
+        let tx = tx.clone();
+        thread::spawn(move || read_loop(conn, &peers, &session_tag, &tx));
+        if rearm {
+            // Dropping the spent listener unregisters its name, so it
+            // must go before the new one is registered under that name.
+            drop(listener);
+            listener = match confium_net::listen(url) {
+                Ok(listener) => listener,
+                Err(_) => return,
+            };
+        }
+    }
+}
+
+/// Read one connection: a `Hello` naming a roster peer, then frames
+/// forwarded to the driver tagged with that peer's index. Anything
+/// malformed drops the connection; the sender will reconnect and the
+/// retransmission logic fills the gap.
+fn read_loop(
+    mut conn: Box<dyn Transport>,
+    peers: &[String],
+    session_tag: &str,
+    tx: &Sender<(usize, Frame)>,
+) {
+    let mut buf = vec![0u8; MAX_FRAME_LEN];
+    let Ok(n) = conn.recv(&mut buf) else { return };
+    let (idx, from) = match Frame::decode(&buf[..n], "") {
+        Some(Frame::Hello {
+            session_tag: tag,
+            from,
+        }) if tag == session_tag => match peers.iter().position(|p| *p == from) {
+            Some(idx) => (idx, from),
+            None => return,
+        },
+        _ => return,
+    };
+    while let Ok(n) = conn.recv(&mut buf) {
+        match Frame::decode(&buf[..n], &from) {
+            Some(Frame::Hello { .. }) | None => return,
+            Some(frame) => {
+                if tx.send((idx, frame)).is_err() {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// Inbound frames, buffered per step and peer until the step is whole.
+///
+/// Only the step being awaited and the one after it are kept: a peer
+/// cannot legitimately be further ahead, since its next step needs our
+/// frames for the current one. Older steps are duplicates or replays.
+struct Inbox {
+    msgs: HashMap<(u8, usize), BTreeMap<u32, Message>>,
+    ends: HashMap<(u8, usize), u32>,
+    completed_at: Vec<Option<u8>>,
+    finished: Vec<bool>,
+}
+
+impl Inbox {
+    fn new(peers: usize) -> Self {
+        Inbox {
+            msgs: HashMap::new(),
+            ends: HashMap::new(),
+            completed_at: vec![None; peers],
+            finished: vec![false; peers],
+        }
+    }
+
+    fn accept(&mut self, peer: usize, frame: Frame, awaiting: u8) {
+        let in_window = |step: u8| step == awaiting || Some(step) == awaiting.checked_add(1);
+        match frame {
+            Frame::Msg { step, seq, message } if in_window(step) => {
+                self.msgs
+                    .entry((step, peer))
+                    .or_default()
+                    .entry(seq)
+                    .or_insert(message);
+            }
+            Frame::RoundEnd {
+                step,
+                count,
+                complete,
+            } => {
+                if complete {
+                    self.completed_at[peer] = Some(step);
+                    self.finished[peer] = true;
+                }
+                if in_window(step) {
+                    self.ends.insert((step, peer), count);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Peers whose frames for `step` are not all in.
+    fn missing(&self, step: u8) -> Vec<usize> {
+        (0..self.completed_at.len())
+            .filter(|&peer| !self.has_step(peer, step))
+            .collect()
+    }
+
+    fn has_step(&self, peer: usize, step: u8) -> bool {
+        if self.completed_at[peer].is_some_and(|done| done < step) {
+            return true;
+        }
+        let Some(&count) = self.ends.get(&(step, peer)) else {
+            return false;
+        };
+        let msgs = self.msgs.get(&(step, peer));
+        (0..count).all(|seq| msgs.is_some_and(|m| m.contains_key(&seq)))
+    }
+
+    /// Remove and return `step`'s messages in roster then send order,
+    /// discarding anything older.
+    fn take(&mut self, step: u8) -> Vec<Message> {
+        let mut out = Vec::new();
+        for peer in 0..self.completed_at.len() {
+            let count = self.ends.get(&(step, peer)).copied().unwrap_or(0);
+            if let Some(msgs) = self.msgs.remove(&(step, peer)) {
+                out.extend(
+                    msgs.into_iter()
+                        .filter(|(seq, _)| *seq < count)
+                        .map(|(_, m)| m),
+                );
+            }
+        }
+        self.msgs.retain(|(s, _), _| *s > step);
+        self.ends.retain(|(s, _), _| *s > step);
+        out
+    }
+
+    fn all_finished(&self) -> bool {
+        self.finished.iter().all(|f| *f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn msg(step: u8, seq: u32, from: &str, payload: u8) -> Frame {
+        Frame::Msg {
+            step,
+            seq,
+            message: Message::broadcast(from, step, vec![payload]),
+        }
+    }
+
+    fn end(step: u8, count: u32, complete: bool) -> Frame {
+        Frame::RoundEnd {
+            step,
+            count,
+            complete,
+        }
+    }
+
+    #[test]
+    fn inbox_reorders_and_deduplicates() {
+        let mut inbox = Inbox::new(2);
+        inbox.accept(1, end(1, 1, false), 1);
+        inbox.accept(0, msg(1, 1, "a", 0xA1), 1);
+        assert_eq!(inbox.missing(1), vec![0, 1]);
+        inbox.accept(0, end(1, 2, false), 1);
+        inbox.accept(1, msg(1, 0, "b", 0xB0), 1);
+        assert_eq!(inbox.missing(1), vec![0]);
+        inbox.accept(0, msg(1, 0, "a", 0xA0), 1);
+        // A retransmitted copy is absorbed.
+        inbox.accept(0, msg(1, 0, "a", 0xA0), 1);
+        assert!(inbox.missing(1).is_empty());
+        let payloads: Vec<u8> = inbox.take(1).iter().map(|m| m.payload[0]).collect();
+        assert_eq!(payloads, vec![0xA0, 0xA1, 0xB0]);
+    }
+
+    #[test]
+    fn inbox_buffers_the_next_step_only() {
+        let mut inbox = Inbox::new(1);
+        inbox.accept(0, msg(2, 0, "a", 2), 1);
+        inbox.accept(0, end(2, 1, false), 1);
+        inbox.accept(0, msg(3, 0, "a", 3), 1);
+        inbox.accept(0, end(3, 1, false), 1);
+        inbox.accept(0, end(1, 0, false), 1);
+        assert!(inbox.take(1).is_empty());
+        assert!(inbox.missing(2).is_empty());
+        assert_eq!(inbox.take(2)[0].payload, vec![2]);
+        // Step 3 arrived two steps early and was dropped; a late replay
+        // of step 2 is ignored as well.
+        inbox.accept(0, msg(2, 0, "a", 2), 3);
+        assert_eq!(inbox.missing(3), vec![0]);
+    }
+
+    #[test]
+    fn completed_peer_satisfies_later_steps() {
+        let mut inbox = Inbox::new(1);
+        inbox.accept(0, end(2, 0, true), 2);
+        assert!(inbox.missing(2).is_empty());
+        assert!(inbox.missing(3).is_empty());
+        assert!(inbox.all_finished());
+    }
+
+    #[test]
+    fn cancel_handle_is_shared() {
+        let handle = CancelHandle::default();
+        let clone = handle.clone();
+        assert!(!handle.is_cancelled());
+        clone.cancel();
+        assert!(handle.is_cancelled());
+    }
+}
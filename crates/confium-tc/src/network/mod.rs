@@ -0,0 +1,40 @@
+//! Networked session driver.
+//!
+//! [`NetworkDriver`] is the over-the-wire counterpart of
+//! [`crate::inprocess`]: it takes one party's [`crate::Session`] and the
+//! session roster — every [`crate::Party`] carrying a `confium-net` URL
+//! as its `transport_endpoint` — and runs rounds to completion against
+//! the other parties, each running its own driver in its own process.
+//!
+//! What the driver adds on top of the raw `confium-net` byte pipes:
+//!
+//! - **Round closure.** After every round each party tells every peer
+//!   how many messages it sent it; a round is consumed only once every
+//!   peer's messages are in, whatever order they arrived in.
+//! - **Out-of-order buffering.** A fast peer may already be one round
+//!   ahead; its frames are held until the local session catches up.
+//! - **Retransmission.** Outbound connections are (re)established with
+//!   back-off and frames queue while a peer is unreachable. A party
+//!   still missing a peer's round asks for it again every
+//!   [`DriverConfig::resend_interval`]; copies de-duplicate by sequence
+//!   number.
+//! - **Timeouts and cancellation.** Each round is bounded by
+//!   [`DriverConfig::round_timeout`], and a [`CancelHandle`] stops the
+//!   run from another thread. Either way, and on any session error,
+//!   peers get an abort notice so they fail fast too.
+//!
+//! The driver itself does not authenticate anything: the `Hello` that
+//! names the sender of a connection is taken on trust. Run it over an
+//! authenticated session ([`crate::Session::create_authenticated`]) or
+//! an authenticated transport when peers are not trusted.
+//!
+//! Only transports linked into the binary are reachable — link
+//! `confium-net-tcp`, `confium-net-quic`, `confium-net-ws` as needed.
+//! See `TODO.roadmap/05-networking-primitives.md`.
+
+#![forbid(unsafe_code)]
+
+pub mod driver;
+pub mod wire;
+
+pub use driver::*;
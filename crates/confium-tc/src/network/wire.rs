@@ -0,0 +1,281 @@
+//! Driver wire format.
+//!
+//! Every connection between two drivers is one-directional: the
+//! connecting side opens it with a [`Frame::Hello`] and then only
+//! writes, the accepting side only reads. One [`Frame`] is one
+//! transport message (the `confium-net` contract is one `send` == one
+//! `recv`), so no extra length framing is needed at this layer.
+//!
+//! ```text
+//! hello    := 0x00 || VERSION || lp(session_tag) || lp(from)
+//! msg      := 0x01 || step || seq(u32) || to || round || lp(payload)
+//! to       := 0x00                      (broadcast)
+//!           | 0x01 || lp(to_party_id)   (directed)
+//! round_end:= 0x02 || step || count(u32) || complete(0|1)
+//! resend   := 0x03 || step
+//! abort    := 0x04 || step
+//! lp(x)    := u32-be(len(x)) || x
+//! ```
+//!
+//! `step` is the driver step (the [`crate::Session::round`] value after
+//! the `round_step` call that produced the frame), not the scheme's own
+//! `Message::round` label — the latter is carried verbatim inside
+//! `msg` so authenticated sessions verify exactly what was signed.
+
+use crate::message::Message;
+
+/// Wire version carried in [`Frame::Hello`].
+pub const VERSION: u8 = 1;
+
+/// Largest frame the driver will read. Matches the `confium-net-tcp`
+/// frame ceiling so every built-in transport can carry it.
+pub const MAX_FRAME_LEN: usize = 8 * 1024 * 1024;
+
+const TAG_HELLO: u8 = 0x00;
+const TAG_MSG: u8 = 0x01;
+const TAG_ROUND_END: u8 = 0x02;
+const TAG_RESEND: u8 = 0x03;
+const TAG_ABORT: u8 = 0x04;
+
+/// One driver-to-driver frame.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Frame {
+    /// First frame on every connection: who is writing, for which
+    /// session.
+    Hello { session_tag: String, from: String },
+    /// One session [`Message`] produced at `step`. `seq` numbers the
+    /// messages for one recipient within one step so retransmitted
+    /// copies de-duplicate.
+    Msg {
+        step: u8,
+        seq: u32,
+        message: Message,
+    },
+    /// Closes `step` towards one recipient: `count` messages were sent,
+    /// and `complete` is set when the sender's session finished at
+    /// this step.
+    RoundEnd {
+        step: u8,
+        count: u32,
+        complete: bool,
+    },
+    /// Ask the peer to send its `step` frames again.
+    Resend { step: u8 },
+    /// The sender gave up on the session at `step` (cancelled or the
+    /// scheme aborted); waiting for it further is pointless.
+    Abort { step: u8 },
+}
+
+impl Frame {
+    /// Encode the frame. `Msg` frames are encoded without their
+    /// `from_party_id`: the sender is the party named in the
+    /// connection's `Hello`.
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        match self {
+            Frame::Hello { session_tag, from } => {
+                out.push(TAG_HELLO);
+                out.push(VERSION);
+                put_lp(&mut out, session_tag.as_bytes());
+                put_lp(&mut out, from.as_bytes());
+            }
+            Frame::Msg { step, seq, message } => {
+                out.push(TAG_MSG);
+                out.push(*step);
+                out.extend_from_slice(&seq.to_be_bytes());
+                match &message.to_party_id {
+                    None => out.push(0),
+                    Some(to) => {
+                        out.push(1);
+                        put_lp(&mut out, to.as_bytes());
+                    }
+                }
+                out.push(message.round);
+                put_lp(&mut out, &message.payload);
+            }
+            Frame::RoundEnd {
+                step,
+                count,
+                complete,
+            } => {
+                out.push(TAG_ROUND_END);
+                out.push(*step);
+                out.extend_from_slice(&count.to_be_bytes());
+                out.push(u8::from(*complete));
+            }
+            Frame::Resend { step } => {
+                out.push(TAG_RESEND);
+                out.push(*step);
+            }
+            Frame::Abort { step } => {
+                out.push(TAG_ABORT);
+                out.push(*step);
+            }
+        }
+        out
+    }
+
+    /// Decode a frame received on a connection whose `Hello` named
+    /// `from`. Returns `None` for anything malformed, including
+    /// trailing bytes.
+    pub fn decode(bytes: &[u8], from: &str) -> Option<Frame> {
+        let mut r = Reader(bytes);
+        let frame = match r.u8()? {
+            TAG_HELLO => {
+                if r.u8()? != VERSION {
+                    return None;
+                }
+                let session_tag = r.string()?;
+                let from = r.string()?;
+                Frame::Hello { session_tag, from }
+            }
+            TAG_MSG => {
+                let step = r.u8()?;
+                let seq = r.u32()?;
+                let to_party_id = match r.u8()? {
+                    0 => None,
+                    1 => Some(r.string()?),
+                    _ => return None,
+                };
+                let round = r.u8()?;
+                let payload = r.lp()?.to_vec();
+                Frame::Msg {
+                    step,
+                    seq,
+                    message: Message {
+                        from_party_id: from.to_string(),
+                        to_party_id,
+                        round,
+                        payload,
+                    },
+                }
+            }
+            TAG_ROUND_END => {
+                let step = r.u8()?;
+                let count = r.u32()?;
+                let complete = match r.u8()? {
+                    0 => false,
+                    1 => true,
+                    _ => return None,
+                };
+                Frame::RoundEnd {
+                    step,
+                    count,
+                    complete,
+                }
+            }
+            TAG_RESEND => Frame::Resend { step: r.u8()? },
+            TAG_ABORT => Frame::Abort { step: r.u8()? },
+            _ => return None,
+        };
+        r.0.is_empty().then_some(frame)
+    }
+}
+
+fn put_lp(out: &mut Vec<u8>, bytes: &[u8]) {
+    out.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+    out.extend_from_slice(bytes);
+}
+
+/// Bounds-checked cursor over a frame.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Option<&'a [u8]> {
+        if self.0.len() < n {
+            return None;
+        }
+        let (head, tail) = self.0.split_at(n);
+        self.0 = tail;
+        Some(head)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        self.take(1).map(|b| b[0])
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        let b = self.take(4)?;
+        Some(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn lp(&mut self) -> Option<&'a [u8]> {
+        let len = self.u32()? as usize;
+        self.take(len)
+    }
+
+    fn string(&mut self) -> Option<String> {
+        String::from_utf8(self.lp()?.to_vec()).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roundtrip(frame: Frame) {
+        let bytes = frame.encode();
+        assert_eq!(Frame::decode(&bytes, "alice"), Some(frame));
+    }
+
+    #[test]
+    fn every_frame_kind_roundtrips() {
+        roundtrip(Frame::Hello {
+            session_tag: "sess-1".into(),
+            from: "alice".into(),
+        });
+        roundtrip(Frame::Msg {
+            step: 2,
+            seq: 7,
+            message: Message::broadcast("alice", 2, vec![1, 2, 3]),
+        });
+        roundtrip(Frame::Msg {
+            step: 3,
+            seq: 0,
+            message: Message::directed("alice", "bob", 3, vec![]),
+        });
+        roundtrip(Frame::RoundEnd {
+            step: 4,
+            count: 2,
+            complete: true,
+        });
+        roundtrip(Frame::Resend { step: 1 });
+        roundtrip(Frame::Abort { step: 5 });
+    }
+
+    #[test]
+    fn msg_sender_comes_from_the_connection() {
+        let bytes = Frame::Msg {
+            step: 1,
+            seq: 0,
+            message: Message::broadcast("mallory", 1, vec![9]),
+        }
+        .encode();
+        match Frame::decode(&bytes, "bob") {
+            Some(Frame::Msg { message, .. }) => assert_eq!(message.from_party_id, "bob"),
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+
+    #[test]
+    fn malformed_frames_are_rejected() {
+        let good = Frame::Msg {
+            step: 1,
+            seq: 0,
+            message: Message::directed("a", "b", 1, vec![1, 2]),
+        }
+        .encode();
+        for cut in 0..good.len() {
+            assert_eq!(Frame::decode(&good[..cut], "a"), None, "prefix {cut}");
+        }
+        let mut trailing = good.clone();
+        trailing.push(0);
+        assert_eq!(Frame::decode(&trailing, "a"), None);
+        assert_eq!(Frame::decode(&[0x7F], "a"), None);
+        assert_eq!(
+            Frame::decode(&[TAG_HELLO, VERSION + 1, 0, 0, 0, 0], "a"),
+            None
+        );
+        assert_eq!(Frame::decode(&[TAG_ROUND_END, 1, 0, 0, 0, 0, 2], "a"), None);
+    }
+}
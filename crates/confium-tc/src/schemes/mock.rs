@@ -31,14 +31,19 @@
 //!   tag and concatenating them sorted by `party_id`. Because nonces
 //!   and the HMAC key are deterministic, this signature is identical on
 //!   every party regardless of coalition.
+//!
+//! Sessions support [`crate::snapshot`]; the mock has no nonce worth
+//! protecting, so nothing is reported as pending.
 
 use hmac::{Hmac, KeyInit, Mac};
 use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
 
 use crate::Result;
 use crate::message::Message;
 use crate::registry::{RoundResult, SessionImpl, TcScheme, TcSchemeKind};
 use crate::session::SessionParams;
+use crate::snapshot::{StateReader, StateWriter};
 
 /// Canonical scheme name advertised through the registry.
 pub const SCHEME_NAME: &str = "mock-tc-sig";
@@ -61,35 +66,21 @@ impl TcScheme for MockTcSigScheme {
     }
 
     fn create_session(&self, params: &SessionParams) -> Result<Box<dyn SessionImpl>> {
-        let party_id = params.parties.get(params.this_party_idx)?.id.clone();
-        let threshold = params.threshold;
-        let message = params.message.clone().unwrap_or_default();
-        let roster_ids = params
-            .parties
-            .parties()
-            .iter()
-            .map(|p| p.id.clone())
-            .collect::<Vec<_>>();
-        // The shared secret is the party's share bytes (identical on
-        // every party in a real deployment). Default to a fixed
-        // well-known key when no share is supplied so tests don't need
-        // to fabricate one.
-        let shared_key = params
-            .local_share
-            .as_ref()
-            .map(|s| s.bytes().to_vec())
-            .unwrap_or_else(|| DEFAULT_SHARED_KEY.to_vec());
+        Ok(Box::new(MockTcSigSession::new(params)?))
+    }
 
-        Ok(Box::new(MockTcSigSession {
-            party_id,
-            threshold,
-            roster_ids,
-            shared_key,
-            message,
-            round_done: 0,
-            collected_tags: 0,
-            signature: Vec::new(),
-        }))
+    fn restore_session(
+        &self,
+        params: &SessionParams,
+        state: &[u8],
+    ) -> Result<Box<dyn SessionImpl>> {
+        let mut session = MockTcSigSession::new(params)?;
+        let mut r = StateReader::new(state);
+        session.round_done = r.u8()?;
+        session.collected_tags = r.u32()? as usize;
+        session.signature = r.bytes()?.to_vec();
+        r.finish()?;
+        Ok(Box::new(session))
     }
 }
 
@@ -125,6 +116,39 @@ struct MockTcSigSession {
 }
 
 impl MockTcSigSession {
+    /// Fresh session state from the session params.
+    fn new(params: &SessionParams) -> Result<Self> {
+        let party_id = params.parties.get(params.this_party_idx)?.id.clone();
+        let threshold = params.threshold;
+        let message = params.message.clone().unwrap_or_default();
+        let roster_ids = params
+            .parties
+            .parties()
+            .iter()
+            .map(|p| p.id.clone())
+            .collect::<Vec<_>>();
+        // The shared secret is the party's share bytes (identical on
+        // every party in a real deployment). Default to a fixed
+        // well-known key when no share is supplied so tests don't need
+        // to fabricate one.
+        let shared_key = params
+            .local_share
+            .as_ref()
+            .map(|s| s.bytes().to_vec())
+            .unwrap_or_else(|| DEFAULT_SHARED_KEY.to_vec());
+
+        Ok(MockTcSigSession {
+            party_id,
+            threshold,
+            roster_ids,
+            shared_key,
+            message,
+            round_done: 0,
+            collected_tags: 0,
+            signature: Vec::new(),
+        })
+    }
+
     /// Deterministically derive a party's nonce from the shared key,
     /// its party id, and the message. The nonce is therefore identical
     /// regardless of which coalition this party is part of.
@@ -238,6 +262,14 @@ impl SessionImpl for MockTcSigSession {
         self.message.fill(0);
         self.signature.fill(0);
     }
+
+    fn snapshot(&self) -> Option<Zeroizing<Vec<u8>>> {
+        let mut w = StateWriter::new();
+        w.u8(self.round_done);
+        w.u32(self.collected_tags as u32);
+        w.bytes(&self.signature);
+        Some(w.finish())
+    }
 }
 
 // ---------------------------------------------------------------------------
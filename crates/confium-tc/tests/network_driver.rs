@@ -0,0 +1,254 @@
+//! [`NetworkDriver`] end-to-end: the `mock-tc-sig` scheme run with one
+//! driver per party, each on its own thread, talking only through
+//! `confium-net` transports — in-process channels and real TCP sockets.
+//! Also covers checkpointing and resuming a driven session.
+
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use confium_tc::network::{CancelHandle, DriverConfig, DriverError, NetworkDriver};
+use confium_tc::party::{Party, PartyList};
+use confium_tc::share::Share;
+use confium_tc::snapshot::MemoryNonceLedger;
+use confium_tc::{Session, SessionParams};
+// Links the `tcp://` transport into the test binary's registry.
+use confium_net_tcp as _;
+
+const SCHEME: &str = "mock-tc-sig";
+const SHARED_KEY: &[u8] = b"confium-mock-tc-sig-shared-key";
+const IDS: [&str; 3] = ["alice", "bob", "carol"];
+
+fn roster(urls: &[String]) -> PartyList {
+    PartyList::from_parties(
+        IDS.iter()
+            .zip(urls)
+            .map(|(id, url)| Party::new(*id, Some(url.clone())))
+            .collect(),
+    )
+}
+
+fn inproc_urls(tag: &str) -> Vec<String> {
+    IDS.iter()
+        .map(|id| format!("inproc://{tag}-{id}"))
+        .collect()
+}
+
+fn tcp_urls() -> Vec<String> {
+    IDS.iter()
+        .map(|_| {
+            let sock = std::net::TcpListener::bind("127.0.0.1:0").expect("free port");
+            format!("tcp://127.0.0.1:{}", sock.local_addr().unwrap().port())
+        })
+        .collect()
+}
+
+fn config(round_timeout: Duration) -> DriverConfig {
+    DriverConfig {
+        session_tag: "network-driver-test".into(),
+        round_timeout,
+        resend_interval: Duration::from_millis(200),
+        reconnect_interval: Duration::from_millis(20),
+        linger: Duration::from_secs(2),
+        ..DriverConfig::default()
+    }
+}
+
+const SNAPSHOT_KEY: &[u8] = b"network-driver-test-integrity-key";
+
+fn params(parties: &PartyList, idx: usize) -> SessionParams {
+    SessionParams {
+        scheme: SCHEME.to_string(),
+        parties: parties.clone(),
+        threshold: 2,
+        this_party_idx: idx,
+        local_share: Some(Share::new(SCHEME, SHARED_KEY.to_vec())),
+        message: Some(b"networked".to_vec()),
+    }
+}
+
+fn driver(parties: &PartyList, idx: usize, config: DriverConfig) -> NetworkDriver {
+    let session = Session::create(&params(parties, idx)).expect("session created");
+    NetworkDriver::new(session, parties)
+        .expect("roster accepted")
+        .with_config(config)
+}
+
+/// Run the listed parties concurrently and collect their outcomes in
+/// the same order.
+fn run_parties(
+    parties: &PartyList,
+    which: &[usize],
+    config: DriverConfig,
+) -> Vec<Result<Vec<u8>, DriverError>> {
+    let handles: Vec<_> = which
+        .iter()
+        .map(|&idx| {
+            let parties = parties.clone();
+            let config = config.clone();
+            thread::spawn(move || driver(&parties, idx, config).run())
+        })
+        .collect();
+    handles
+        .into_iter()
+        .map(|h| h.join().expect("driver thread"))
+        .collect()
+}
+
+fn assert_all_agree(results: Vec<Result<Vec<u8>, DriverError>>) {
+    let sigs: Vec<Vec<u8>> = results
+        .into_iter()
+        .map(|r| r.expect("party completes"))
+        .collect();
+    assert!(!sigs[0].is_empty());
+    assert!(sigs.iter().all(|s| *s == sigs[0]), "parties disagree");
+}
+
+#[test]
+fn three_parties_complete_over_inproc() {
+    let parties = roster(&inproc_urls("complete"));
+    assert_all_agree(run_parties(
+        &parties,
+        &[0, 1, 2],
+        config(Duration::from_secs(10)),
+    ));
+}
+
+#[test]
+fn three_parties_complete_over_tcp() {
+    let parties = roster(&tcp_urls());
+    assert_all_agree(run_parties(
+        &parties,
+        &[0, 1, 2],
+        config(Duration::from_secs(10)),
+    ));
+}
+
+#[test]
+fn late_party_is_reached_by_reconnecting() {
+    let parties = roster(&inproc_urls("late"));
+    let early = {
+        let parties = parties.clone();
+        thread::spawn(move || run_parties(&parties, &[0, 1], config(Duration::from_secs(10))))
+    };
+    thread::sleep(Duration::from_millis(300));
+    let late = driver(&parties, 2, config(Duration::from_secs(10))).run();
+    let mut results = early.join().expect("early parties");
+    results.push(late);
+    assert_all_agree(results);
+}
+
+#[test]
+fn absent_parties_time_out_the_round() {
+    let parties = roster(&inproc_urls("absent"));
+    match driver(&parties, 0, config(Duration::from_millis(500))).run() {
+        Err(DriverError::RoundTimeout { round, missing }) => {
+            assert_eq!(round, 1);
+            assert_eq!(missing, vec!["bob".to_string(), "carol".to_string()]);
+        }
+        other => panic!("expected a round timeout, got {other:?}"),
+    }
+}
+
+#[test]
+fn cancel_stops_a_waiting_driver() {
+    let parties = roster(&inproc_urls("cancel"));
+    let mut alice = driver(&parties, 0, config(Duration::from_secs(30)));
+    let cancel: CancelHandle = alice.cancel_handle();
+    let start = Instant::now();
+    let run = thread::spawn(move || alice.run());
+    thread::sleep(Duration::from_millis(100));
+    cancel.cancel();
+    let result = run.join().expect("driver thread");
+    assert!(matches!(result, Err(DriverError::Cancelled)), "{result:?}");
+    assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+fn peers_fail_fast_when_a_party_aborts() {
+    let parties = roster(&inproc_urls("abort"));
+    let others = {
+        let parties = parties.clone();
+        thread::spawn(move || run_parties(&parties, &[0, 1], config(Duration::from_secs(30))))
+    };
+    thread::sleep(Duration::from_millis(200));
+    let mut carol = driver(&parties, 2, config(Duration::from_secs(30)));
+    carol.cancel_handle().cancel();
+    assert!(matches!(carol.run(), Err(DriverError::Cancelled)));
+    // Whoever hears carol first aborts in turn, so the other may
+    // report that party instead.
+    let aborted_by: Vec<String> = others
+        .join()
+        .expect("other parties")
+        .into_iter()
+        .map(|result| match result {
+            Err(DriverError::PeerAborted { party, .. }) => party,
+            other => panic!("expected a peer abort, got {other:?}"),
+        })
+        .collect();
+    assert!(aborted_by.iter().any(|p| p == "carol"), "{aborted_by:?}");
+}
+
+#[test]
+fn checkpoint_runs_after_every_round() {
+    let parties = roster(&inproc_urls("checkpoint"));
+    let others = {
+        let parties = parties.clone();
+        thread::spawn(move || run_parties(&parties, &[1, 2], config(Duration::from_secs(10))))
+    };
+    let mut session = Session::create(&params(&parties, 0)).expect("session created");
+    session.set_nonce_ledger(Arc::new(MemoryNonceLedger::new()));
+    let rounds = Arc::new(Mutex::new(Vec::new()));
+    let seen = rounds.clone();
+    let mut alice = NetworkDriver::new(session, &parties)
+        .expect("roster accepted")
+        .with_config(config(Duration::from_secs(10)))
+        .with_checkpoint(move |session| {
+            session.snapshot(SNAPSHOT_KEY)?;
+            seen.lock().unwrap().push(session.round());
+            Ok(())
+        });
+    let mut results = vec![alice.run()];
+    results.extend(others.join().expect("other parties"));
+    assert_all_agree(results);
+    assert_eq!(*rounds.lock().unwrap(), vec![1, 2, 3]);
+}
+
+#[test]
+fn resumed_session_rejoins_its_peers() {
+    let parties = roster(&inproc_urls("resume"));
+    let ledger = Arc::new(MemoryNonceLedger::new());
+    let p = params(&parties, 0);
+
+    // Alice ran round 1 and checkpointed before her process died
+    // without sending anything.
+    let mut session = Session::create(&p).expect("session created");
+    session.set_nonce_ledger(ledger.clone());
+    session.round_step(&[]).expect("round 1");
+    let blob = session.snapshot(SNAPSHOT_KEY).expect("snapshot");
+    drop(session);
+
+    let others = {
+        let parties = parties.clone();
+        thread::spawn(move || run_parties(&parties, &[1, 2], config(Duration::from_secs(10))))
+    };
+    let session = Session::resume(&p, &blob, SNAPSHOT_KEY, ledger).expect("resume");
+    assert_eq!(session.round(), 1);
+    let alice = NetworkDriver::new(session, &parties)
+        .expect("roster accepted")
+        .with_config(config(Duration::from_secs(10)))
+        .run();
+    let mut results = vec![alice];
+    results.extend(others.join().expect("other parties"));
+    assert_all_agree(results);
+}
+
+#[test]
+fn roster_without_endpoints_is_rejected() {
+    let parties = PartyList::from_parties(IDS.iter().map(|id| Party::inproc(*id)).collect());
+    let session = Session::create(&params(&parties, 0)).expect("session created");
+    assert!(matches!(
+        NetworkDriver::new(session, &parties),
+        Err(DriverError::MissingEndpoint { party }) if party == "alice"
+    ));
+}
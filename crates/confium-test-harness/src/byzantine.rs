@@ -31,6 +31,8 @@ use confium_tc::Message;
 /// - `byzantine-tamper` — flip a bit in every payload
 /// - `byzantine-replay` — duplicate the previous round's messages
 /// - `byzantine-malicious` — substitute a crafted payload
+/// - `byzantine-equivocate` — send different recipients different
+///   versions of each broadcast
 /// - `byzantine-collusion` — alias for `malicious`; the runner treats
 ///   any group of N-1 colluding peers as N-1 individual malicious
 ///   senders
@@ -42,6 +44,7 @@ pub enum PeerBehavior {
     Replay,
     Malicious,
     Collusion,
+    Equivocate,
 }
 
 impl PeerBehavior {
@@ -56,6 +59,7 @@ impl PeerBehavior {
             "byzantine-replay" => Some(PeerBehavior::Replay),
             "byzantine-malicious" => Some(PeerBehavior::Malicious),
             "byzantine-collusion" => Some(PeerBehavior::Collusion),
+            "byzantine-equivocate" => Some(PeerBehavior::Equivocate),
             _ => None,
         }
     }
@@ -69,6 +73,7 @@ impl PeerBehavior {
             PeerBehavior::Replay => "byzantine-replay",
             PeerBehavior::Malicious => "byzantine-malicious",
             PeerBehavior::Collusion => "byzantine-collusion",
+            PeerBehavior::Equivocate => "byzantine-equivocate",
         }
     }
 }
@@ -94,12 +99,16 @@ pub struct BehaviorSpec {
 /// actually see.
 ///
 /// The wrapper owns a small per-party history so the `Replay` behavior
-/// can resurface the previous round's traffic.
+/// can resurface the previous round's traffic, and the session roster
+/// ([`ByzantineTransport::set_roster`]) so `Equivocate` can address
+/// each recipient separately.
 #[derive(Debug, Default)]
 pub struct ByzantineTransport {
     behaviors: HashMap<String, BehaviorSpec>,
     /// Last round of messages each party sent, for `Replay`.
     last_sent: HashMap<String, Vec<Message>>,
+    /// Party ids in roster order, for `Equivocate`.
+    roster: Vec<String>,
 }
 
 impl ByzantineTransport {
@@ -115,9 +124,17 @@ impl ByzantineTransport {
         ByzantineTransport {
             behaviors,
             last_sent: HashMap::new(),
+            roster: Vec::new(),
         }
     }
 
+    /// Tell the wrapper who the parties are. Without a roster an
+    /// `Equivocate` party has nobody to split its broadcasts between
+    /// and behaves honestly.
+    pub fn set_roster(&mut self, party_ids: Vec<String>) {
+        self.roster = party_ids;
+    }
+
     /// Configure (or replace) the behavior for `party_id`.
     pub fn set(&mut self, spec: BehaviorSpec) {
         self.behaviors.insert(spec.party_id.clone(), spec);
@@ -168,6 +185,10 @@ impl ByzantineTransport {
                     .iter()
                     .map(|m| malicious_message(m))
                     .collect::<Vec<_>>(),
+                PeerBehavior::Equivocate => msgs
+                    .iter()
+                    .flat_map(|m| equivocate(m, &self.roster))
+                    .collect::<Vec<_>>(),
                 PeerBehavior::Replay => {
                     if let Some(prev) = self.last_sent.get(sender) {
                         prev.clone()
@@ -201,6 +222,36 @@ fn tamper_message(msg: &Message) -> Message {
     }
 }
 
+/// Split a broadcast into one directed copy per other roster party,
+/// flipping the low bit of the *last* payload byte for every second
+/// recipient so half the roster sees a different value. The last byte
+/// rather than the first, so a framing header (e.g. the tc-core echo
+/// broadcast frame) survives and the lie reaches the layer that has to
+/// catch it. Directed messages, empty payloads, and everything when no
+/// roster is set pass through.
+fn equivocate(msg: &Message, roster: &[String]) -> Vec<Message> {
+    if msg.is_directed() || msg.payload.is_empty() || roster.is_empty() {
+        return vec![msg.clone()];
+    }
+    roster
+        .iter()
+        .filter(|id| **id != msg.from_party_id)
+        .enumerate()
+        .map(|(k, to)| {
+            let mut payload = msg.payload.clone();
+            if k % 2 == 1 {
+                *payload.last_mut().expect("non-empty") ^= 0x01;
+            }
+            Message {
+                from_party_id: msg.from_party_id.clone(),
+                to_party_id: Some(to.clone()),
+                round: msg.round,
+                payload,
+            }
+        })
+        .collect()
+}
+
 /// Substitute a recognizable bogus payload so a correct scheme rejects
 /// the message. We keep the envelope (from/to/round) so the harness can
 /// attribute the misbehavior to the right party.
@@ -358,6 +409,33 @@ mod tests {
         assert_eq!(out2[0].round, 1, "replayed message keeps old round number");
     }
 
+    #[test]
+    fn equivocate_splits_broadcast_into_differing_copies() {
+        let mut tport = ByzantineTransport::from_specs(vec![BehaviorSpec {
+            party_id: "eve".into(),
+            behavior: PeerBehavior::Equivocate,
+            drop_round: None,
+        }]);
+        tport.set_roster(vec!["alice".into(), "bob".into(), "eve".into()]);
+        let out = tport.route(&[msg("eve", None, 1, &[0x10, 0x20])]);
+        assert_eq!(out.len(), 2);
+        assert_eq!(out[0].to_party_id.as_deref(), Some("alice"));
+        assert_eq!(out[0].payload, vec![0x10, 0x20]);
+        assert_eq!(out[1].to_party_id.as_deref(), Some("bob"));
+        assert_eq!(out[1].payload, vec![0x10, 0x21]);
+    }
+
+    #[test]
+    fn equivocate_without_roster_is_honest() {
+        let mut tport = ByzantineTransport::from_specs(vec![BehaviorSpec {
+            party_id: "eve".into(),
+            behavior: PeerBehavior::Equivocate,
+            drop_round: None,
+        }]);
+        let m = msg("eve", None, 1, &[1]);
+        assert_eq!(tport.route(std::slice::from_ref(&m)), vec![m]);
+    }
+
     #[test]
     fn behavior_round_trips_through_tags() {
         for behavior in [
@@ -367,6 +445,7 @@ mod tests {
             PeerBehavior::Replay,
             PeerBehavior::Malicious,
             PeerBehavior::Collusion,
+            PeerBehavior::Equivocate,
         ] {
             let tag = behavior.as_tag();
             assert_eq!(PeerBehavior::from_tag(tag), Some(behavior));
@@ -6,7 +6,8 @@
 //!    used by the workspace's own test suite so it doesn't depend on the
 //!    external Botan plugin.
 //! 2. **Byzantine peer simulation** — for threshold-cryptography plugin
-//!    testing: drop, tamper, replay, malicious-collusion behaviors.
+//!    testing: drop, tamper, replay, malicious-collusion and
+//!    equivocation behaviors.
 //! 3. **NIST evaluation bench** — conformance + performance harness for
 //!    MPTS candidate schemes. Deterministic environment, in-process
 //!    transport, controlled RNG, vector-driven test runner.
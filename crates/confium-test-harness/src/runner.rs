@@ -26,6 +26,7 @@ use confium_tc::Party;
 use confium_tc::PartyList;
 use confium_tc::Session;
 use confium_tc::SessionParams;
+use confium_tc::Share;
 
 use crate::ByzantineTransport;
 use crate::DeterministicEnv;
@@ -50,6 +51,13 @@ impl VectorRunner {
     /// round. The deterministic env is seeded from the vector; the
     /// Byzantine transport applies the vector's per-party behaviors.
     pub fn run(vector: &TestVector) -> Result<TestResult> {
+        Self::run_with_shares(vector, &[])
+    }
+
+    /// Like [`VectorRunner::run`], with `shares[i]` as party `i`'s
+    /// `local_share`. Signing schemes need the output of a prior DKG;
+    /// parties beyond `shares.len()` run without one.
+    pub fn run_with_shares(vector: &TestVector, shares: &[Share]) -> Result<TestResult> {
         let started = Instant::now();
 
         // The env exists for side effects (clock, memory) that schemes
@@ -59,6 +67,7 @@ impl VectorRunner {
         let mut tport = ByzantineTransport::from_specs(vector.behavior_specs());
 
         let parties = build_party_list(vector);
+        tport.set_roster(parties.parties().iter().map(|p| p.id.clone()).collect());
         let message_bytes = vector.test.message_bytes();
 
         // One session per party. Session::create resolves the scheme
@@ -70,7 +79,7 @@ impl VectorRunner {
                 parties: parties.clone(),
                 threshold: vector.test.threshold,
                 this_party_idx: idx,
-                local_share: None,
+                local_share: shares.get(idx).cloned(),
                 message: Some(message_bytes.clone()),
             };
             let session = Session::create(&params)?;
@@ -117,12 +126,18 @@ impl VectorRunner {
                         // harness fault: the candidate detected the
                         // configured Byzantine behavior and refused to
                         // produce a (potentially invalid) signature.
+                        // When the framework can pin the abort on a
+                        // party (identifiable abort), say who.
                         let elapsed = started.elapsed();
+                        let culprit = scheme_err
+                            .culprit()
+                            .map(|party| format!(" (culprit '{party}')"))
+                            .unwrap_or_default();
                         return Ok(TestResult::aborted(
                             vector,
                             format!(
-                                "scheme '{}' aborted at round {}: {}",
-                                vector.scheme.name, round, scheme_err
+                                "scheme '{}' aborted at round {}{}: {}",
+                                vector.scheme.name, round, culprit, scheme_err
                             ),
                             round,
                             elapsed,
@@ -0,0 +1,126 @@
+//! Echo broadcast against an equivocating peer.
+//!
+//! `echo-commit` is a two-round scheme defined here that opts into the
+//! framework's echo broadcast (`TcScheme::reliable_broadcast`): round 1
+//! broadcasts a commitment, round 2 outputs the commitments it saw.
+//! Under `byzantine-equivocate` the sender hands half the roster a
+//! different commitment; the echo round must notice and abort. The
+//! abort names the disputed broadcast but blames nobody: an echo alone
+//! cannot tell an equivocating sender from a lying witness.
+//! `echo-commit-plain` is the same scheme without the opt-in, showing
+//! the lie otherwise goes through.
+
+use confium_tc::registry::{RegisteredScheme, TcScheme};
+use confium_tc::{Message, Result, RoundResult, SessionImpl, SessionParams, TcSchemeKind};
+use confium_test_harness::{Outcome, TestVector, VectorRunner};
+
+struct CommitSession {
+    id: String,
+    started: bool,
+    seen: Vec<Message>,
+}
+
+impl SessionImpl for CommitSession {
+    fn round(&mut self, incoming: &[Message]) -> Result<RoundResult> {
+        if !self.started {
+            self.started = true;
+            let msg = Message::broadcast(&self.id, 1, format!("commit:{}", self.id));
+            return Ok(RoundResult::new(vec![msg], false));
+        }
+        self.seen = incoming.to_vec();
+        self.seen
+            .sort_by(|a, b| a.from_party_id.cmp(&b.from_party_id));
+        Ok(RoundResult::done())
+    }
+    fn result(&self) -> Result<Vec<u8>> {
+        Ok(self.seen.iter().flat_map(|m| m.payload.clone()).collect())
+    }
+    fn destroy(&mut self) {}
+}
+
+struct EchoCommit(&'static str, bool);
+
+impl TcScheme for EchoCommit {
+    fn name(&self) -> &'static str {
+        self.0
+    }
+    fn kind(&self) -> TcSchemeKind {
+        TcSchemeKind::Signature
+    }
+    fn create_session(&self, params: &SessionParams) -> Result<Box<dyn SessionImpl>> {
+        Ok(Box::new(CommitSession {
+            id: params.parties.get(params.this_party_idx)?.id.clone(),
+            started: false,
+            seen: Vec::new(),
+        }))
+    }
+    fn reliable_broadcast(&self) -> bool {
+        self.1
+    }
+}
+
+static ECHOED: EchoCommit = EchoCommit("echo-commit", true);
+static PLAIN: EchoCommit = EchoCommit("echo-commit-plain", false);
+
+inventory::submit! { RegisteredScheme { scheme: &ECHOED } }
+inventory::submit! { RegisteredScheme { scheme: &PLAIN } }
+
+fn vector(scheme: &str, eve: &str) -> TestVector {
+    TestVector::parse(&format!(
+        r#"
+conformance_level = "must_pass"
+
+[scheme]
+name = "{scheme}"
+version = "in-test"
+
+[test]
+parties = 4
+threshold = 3
+message = "echo"
+seed = "0x1"
+
+[[peer_behavior]]
+party_id = "alice"
+type = "honest"
+
+[[peer_behavior]]
+party_id = "bob"
+type = "honest"
+
+[[peer_behavior]]
+party_id = "carol"
+type = "honest"
+
+[[peer_behavior]]
+party_id = "eve"
+type = "{eve}"
+"#
+    ))
+    .expect("vector parses")
+}
+
+#[test]
+fn honest_run_completes_with_extra_echo_round() {
+    let result = VectorRunner::run(&vector("echo-commit", "honest")).expect("run");
+    assert_eq!(result.outcome, Outcome::Pass, "note: {:?}", result.note);
+    // Two scheme rounds plus one echo round.
+    assert_eq!(result.rounds, 3);
+}
+
+#[test]
+fn equivocation_aborts_without_blaming_anyone() {
+    let result = VectorRunner::run(&vector("echo-commit", "byzantine-equivocate")).expect("run");
+    assert_eq!(result.outcome, Outcome::Aborted, "note: {:?}", result.note);
+    let note = result.note.expect("abort note");
+    assert!(note.contains("'eve'"), "note: {note}");
+    assert!(!note.contains("culprit"), "note: {note}");
+    assert!(result.output.is_empty());
+}
+
+#[test]
+fn equivocation_goes_unnoticed_without_echo_broadcast() {
+    let result =
+        VectorRunner::run(&vector("echo-commit-plain", "byzantine-equivocate")).expect("run");
+    assert_eq!(result.outcome, Outcome::Pass, "note: {:?}", result.note);
+}
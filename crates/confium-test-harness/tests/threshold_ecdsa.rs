@@ -0,0 +1,106 @@
+//! Threshold ECDSA signing against a malicious co-signer.
+//!
+//! Runs CMP20 and GG18 signing over a 2-of-3 key with `eve` as the
+//! third signer. Whatever eve does to her messages — flip a bit,
+//! replace them outright, or tell half the roster something different
+//! — the Paillier MtA's proofs must catch it and abort naming her,
+//! before any honest party's nonce share is used.
+
+use confium_tc::Share;
+use confium_test_harness::{Outcome, TestVector, VectorRunner};
+
+const SCHEMES: [&str; 2] = [
+    confium_tc_cmp20::SIGN_SCHEME_NAME,
+    confium_tc_gg18::SIGN_SCHEME_NAME,
+];
+
+fn vector(scheme: &str, eve: &str) -> TestVector {
+    TestVector::parse(&format!(
+        r#"
+conformance_level = "must_pass"
+
+[scheme]
+name = "{scheme}"
+version = "in-test"
+
+[test]
+parties = 3
+threshold = 2
+message = "threshold ecdsa"
+seed = "0x1"
+
+[[peer_behavior]]
+party_id = "alice"
+type = "honest"
+
+[[peer_behavior]]
+party_id = "bob"
+type = "honest"
+
+[[peer_behavior]]
+party_id = "eve"
+type = "{eve}"
+"#
+    ))
+    .expect("vector parses")
+}
+
+/// Shares of one 2-of-3 key, in roster order.
+fn shares(scheme: &str) -> Vec<Share> {
+    let blobs = if scheme == confium_tc_cmp20::SIGN_SCHEME_NAME {
+        confium_tc_cmp20::inprocess::keygen(2, 3)
+            .expect("keygen")
+            .shares
+    } else {
+        confium_tc_gg18::inprocess::keygen(2, 3)
+            .expect("keygen")
+            .shares
+    };
+    blobs.into_iter().map(|b| Share::new(scheme, b)).collect()
+}
+
+fn assert_eve_blamed(eve: &str) {
+    for scheme in SCHEMES {
+        let result =
+            VectorRunner::run_with_shares(&vector(scheme, eve), &shares(scheme)).expect("run");
+        assert_eq!(
+            result.outcome,
+            Outcome::Aborted,
+            "{scheme}: {:?}",
+            result.note
+        );
+        let note = result.note.expect("abort note");
+        assert!(note.contains("culprit 'eve'"), "{scheme}: {note}");
+        assert!(result.output.is_empty());
+    }
+}
+
+#[test]
+fn honest_signers_produce_a_signature() {
+    for scheme in SCHEMES {
+        let result =
+            VectorRunner::run_with_shares(&vector(scheme, "honest"), &shares(scheme)).expect("run");
+        assert_eq!(result.outcome, Outcome::Pass, "{scheme}: {:?}", result.note);
+        assert_eq!(result.output.len(), 64);
+    }
+}
+
+#[test]
+fn tampered_messages_abort_naming_sender() {
+    assert_eve_blamed("byzantine-tamper");
+}
+
+#[test]
+fn malicious_messages_abort_naming_sender() {
+    assert_eve_blamed("byzantine-malicious");
+}
+
+#[test]
+fn equivocation_aborts_naming_sender() {
+    assert_eve_blamed("byzantine-equivocate");
+}
+
+#[test]
+fn replayed_messages_abort_naming_sender() {
+    assert_eve_blamed("byzantine-replay");
+}
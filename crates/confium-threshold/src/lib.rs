@@ -32,6 +32,10 @@ pub use confium_tc_frost_p256 as frost_p256;
 /// FROST over Ed25519.
 pub use confium_tc_frost_ed25519 as frost_ed25519;
 
+#[cfg(feature = "frost")]
+/// Generic FROST: ristretto255, Ed448 and secp256k1 (incl. Taproot).
+pub use confium_tc_frost as frost;
+
 #[cfg(feature = "bls")]
 /// Threshold BLS.
 pub use confium_tc_bls as bls;
@@ -24,6 +24,8 @@ pub enum ArtifactType {
     DirectorIdentity,
     /// Archive renewal (re-quorum of long-term archival).
     ArchiveRenewal,
+    /// Verifiable map epoch root (see [`crate::vmap`]).
+    MapRoot,
 }
 
 impl ArtifactType {
@@ -41,6 +43,7 @@ impl ArtifactType {
             ArtifactType::QuorumPolicy => "quorum_policy",
             ArtifactType::DirectorIdentity => "director_identity",
             ArtifactType::ArchiveRenewal => "archive_renewal",
+            ArtifactType::MapRoot => "map_root",
         }
     }
 
@@ -55,6 +58,7 @@ impl ArtifactType {
         ArtifactType::QuorumPolicy,
         ArtifactType::DirectorIdentity,
         ArtifactType::ArchiveRenewal,
+        ArtifactType::MapRoot,
     ];
 }
 
@@ -10,6 +10,9 @@
 //!   public calendar servers.
 //! - **Evidence Records (RFC 4998 ERS)**: long-term archival protection
 //!   via periodic re-timestamping as hash algorithms age.
+//! - **Verifiable map** ([`vmap`]): a sparse Merkle tree of key → value
+//!   with inclusion and non-inclusion proofs, its root committed into
+//!   the log every epoch. Backs key-transparency lookups.
 //!
 //! See `TODO.roadmap/36-transparency-and-ots.md` and
 //! `TODO.roadmap/37-long-term-archival.md` for full specs.
@@ -39,6 +42,7 @@ pub mod merkle;
 pub mod ots;
 pub mod proof;
 pub mod test_vectors;
+pub mod vmap;
 pub mod witness;
 
 #[cfg(test)]
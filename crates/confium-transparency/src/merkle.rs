@@ -511,6 +511,61 @@ impl MerkleTree {
     }
 }
 
+/// The right edge of a tree: the roots of its perfect subtrees,
+/// largest first.
+///
+/// Lets a verifier that reads every entry in order, such as a monitor
+/// tailing the log, recompute the root a tree head commits to while
+/// keeping only `O(log N)` hashes between runs.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Frontier {
+    size: u64,
+    subtrees: Vec<Hash>,
+}
+
+impl Frontier {
+    /// The frontier of the empty tree.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of entries absorbed so far, which is also the sequence
+    /// the next [`push`](Self::push) must carry.
+    pub fn len(&self) -> u64 {
+        self.size
+    }
+
+    /// Whether no entry has been absorbed.
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// Absorb the next entry.
+    pub fn push(&mut self, entry: &MerkleEntry) {
+        let mut node = hash_leaf(entry.entry_hash());
+        // Each trailing one bit of the old size is a perfect subtree
+        // the new leaf completes.
+        let mut size = self.size;
+        while size & 1 == 1 {
+            let left = self.subtrees.pop().expect("one subtree per set bit");
+            node = hash_internal(left, node);
+            size >>= 1;
+        }
+        self.subtrees.push(node);
+        self.size += 1;
+    }
+
+    /// Root of the tree of the absorbed entries; equal to
+    /// [`MerkleTree::root`] over the same entries.
+    pub fn root(&self) -> Hash {
+        let mut subtrees = self.subtrees.iter().rev();
+        let Some(&last) = subtrees.next() else {
+            return [0u8; 32];
+        };
+        subtrees.fold(last, |right, &left| hash_internal(left, right))
+    }
+}
+
 #[cfg(test)]
 mod consistency_tests {
     use super::*;
@@ -674,6 +729,25 @@ mod tests {
         }
     }
 
+    #[test]
+    fn frontier_root_matches_tree_root_at_every_size() {
+        let mut tree = MerkleTree::new();
+        let mut frontier = Frontier::new();
+        assert_eq!(frontier.root(), tree.root());
+        for i in 0..70u64 {
+            let entry = MerkleEntry::new(i, ArtifactType::CertificateIssuance, [i as u8; 32]);
+            frontier.push(&entry);
+            tree.append(entry);
+            assert_eq!(frontier.len(), tree.len() as u64);
+            assert_eq!(
+                frontier.root(),
+                tree.root(),
+                "root diverges at size {}",
+                i + 1
+            );
+        }
+    }
+
     #[test]
     fn empty_tree_has_zero_root() {
         let tree = MerkleTree::new();
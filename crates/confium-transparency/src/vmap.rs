@@ -0,0 +1,650 @@
+//! Verifiable log-backed map for key-transparency lookups.
+//!
+//! A sparse Merkle tree over `SHA-256(key)` maps arbitrary keys to
+//! values with both inclusion proofs (this key maps to this value)
+//! and non-inclusion proofs (this key is absent). Every published
+//! epoch commits the map root into the append-only [`MerkleTree`] as
+//! a [`ArtifactType::MapRoot`] entry, so a server cannot show
+//! different clients different maps without also forking the log —
+//! which monitors and witnesses already catch.
+//!
+//! Intended uses: the current public key of each `confium-store`
+//! identity, and revocation status for `confium-signatif` authorities.
+//!
+//! ## Tree shape
+//!
+//! The tree is 256 levels deep, indexed by the bits of the key hash
+//! (most significant first). Subtrees holding a single leaf collapse
+//! to that leaf's hash, and empty subtrees hash to all-zeros, so a
+//! proof is `O(log n)` siblings rather than 256. Leaves and internal
+//! nodes are domain-separated (`0x03` / `0x04`) from each other and
+//! from the log's own `0x01` / `0x02` prefixes.
+//!
+//! ## Example
+//!
+//! ```
+//! use confium_transparency::MerkleTree;
+//! use confium_transparency::vmap::{MapVerifier, VerifiableMap};
+//!
+//! let mut log = MerkleTree::new();
+//! let mut map = VerifiableMap::new();
+//! map.insert(b"alice@example.com", b"alice-ed25519-pk".to_vec());
+//! map.publish(&mut log);
+//!
+//! let lookup = map.lookup(b"alice@example.com", &log)?;
+//! let mut verifier = MapVerifier::new();
+//! let value = verifier.verify(b"alice@example.com", &lookup, log.root())?;
+//! assert_eq!(value, Some(&b"alice-ed25519-pk"[..]));
+//!
+//! let absent = map.lookup(b"mallory@example.com", &log)?;
+//! assert_eq!(verifier.verify(b"mallory@example.com", &absent, log.root())?, None);
+//! # Ok::<(), confium_transparency::vmap::MapError>(())
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+
+use crate::entry::{ArtifactType, MerkleEntry};
+use crate::merkle::{Hash, InclusionProof, MerkleError, MerkleTree};
+
+/// Hash of an empty subtree.
+const EMPTY: Hash = [0u8; 32];
+
+/// Depth of the tree: one level per key-hash bit.
+const DEPTH: usize = 256;
+
+/// Errors from map lookups and proof verification.
+#[derive(Debug, thiserror::Error)]
+pub enum MapError {
+    /// No epoch has been published yet.
+    #[error("map has no published epoch")]
+    NotPublished,
+    /// The map proof does not reconstruct the committed root.
+    #[error("map proof does not reach the committed root for epoch {0}")]
+    ProofFailed(u64),
+    /// The proof is structurally invalid.
+    #[error("malformed map proof: {0}")]
+    Malformed(&'static str),
+    /// The log entry is not a commitment to the presented map head.
+    #[error("log entry {0} does not commit to the presented map head")]
+    CommitmentMismatch(u64),
+    /// The server presented an epoch older than one already seen.
+    #[error("map epoch went backwards: saw {seen}, got {got}")]
+    EpochRollback {
+        /// Highest epoch previously verified.
+        seen: u64,
+        /// Epoch just presented.
+        got: u64,
+    },
+    /// Same epoch presented with a different root (split view).
+    #[error("map epoch {0} presented with two different roots")]
+    Equivocation(u64),
+    /// Stored contents do not rebuild the stored head.
+    #[error("stored map contents do not reproduce the head of epoch {0}")]
+    RestoreMismatch(u64),
+    /// The log inclusion proof failed.
+    #[error(transparent)]
+    Log(#[from] MerkleError),
+}
+
+/// A published map epoch: the root committed into the log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MapHead {
+    /// Monotonically increasing epoch number, starting at 1.
+    pub epoch: u64,
+    /// Sparse Merkle tree root.
+    pub root: Hash,
+    /// Number of keys in the map.
+    pub size: u64,
+    /// Sequence of the log entry carrying this head's commitment.
+    pub log_sequence: u64,
+}
+
+impl MapHead {
+    /// The value recorded as the log entry's `artifact_hash`.
+    /// Binds epoch, root and size; `log_sequence` is bound by the log
+    /// entry itself.
+    pub fn commitment(&self) -> Hash {
+        let mut h = Sha256::new();
+        h.update(b"confium-vmap-head-v1");
+        h.update(self.epoch.to_be_bytes());
+        h.update(self.root);
+        h.update(self.size.to_be_bytes());
+        h.finalize().into()
+    }
+}
+
+/// A leaf that terminates a non-inclusion path: the one other key
+/// occupying the collapsed subtree the queried key would fall into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MapLeaf {
+    /// `SHA-256(key)` of the occupying key.
+    pub key_hash: Hash,
+    /// `SHA-256(value)` of the occupying key's value.
+    pub value_hash: Hash,
+}
+
+/// Sparse Merkle proof for one key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MapProof {
+    /// Sibling hashes from the root downward; the path ends at the
+    /// collapsed subtree holding the key (or proving its absence).
+    pub siblings: Vec<Hash>,
+    /// For non-inclusion proofs ending at another key's leaf.
+    pub terminal: Option<MapLeaf>,
+}
+
+/// Everything a client needs to check a lookup against a log root.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MapLookup {
+    /// Epoch the lookup was served from.
+    pub head: MapHead,
+    /// The value, or `None` if the key is absent.
+    pub value: Option<Vec<u8>>,
+    /// Map proof against `head.root`.
+    pub proof: MapProof,
+    /// The log entry committing to `head`.
+    pub log_entry: MerkleEntry,
+    /// Inclusion proof of `log_entry` in the log.
+    pub log_proof: InclusionProof,
+}
+
+/// Hash of a map key: its position in the tree.
+pub fn key_hash(key: &[u8]) -> Hash {
+    Sha256::digest(key).into()
+}
+
+fn value_hash(value: &[u8]) -> Hash {
+    Sha256::digest(value).into()
+}
+
+fn hash_leaf(key_hash: &Hash, value_hash: &Hash) -> Hash {
+    let mut h = Sha256::new();
+    h.update([0x03]);
+    h.update(key_hash);
+    h.update(value_hash);
+    h.finalize().into()
+}
+
+fn hash_node(left: &Hash, right: &Hash) -> Hash {
+    let mut h = Sha256::new();
+    h.update([0x04]);
+    h.update(left);
+    h.update(right);
+    h.finalize().into()
+}
+
+/// Bit `i` of `h`, most significant bit of byte 0 first.
+fn bit(h: &Hash, i: usize) -> bool {
+    (h[i / 8] >> (7 - i % 8)) & 1 == 1
+}
+
+/// `h` with every bit from `depth` onward cleared: the identity of
+/// the subtree at `depth` containing `h`.
+fn prefix(h: &Hash, depth: usize) -> Hash {
+    let mut out = [0u8; 32];
+    let full = depth / 8;
+    out[..full].copy_from_slice(&h[..full]);
+    if depth % 8 != 0 {
+        out[full] = h[full] & (0xffu8 << (8 - depth % 8));
+    }
+    out
+}
+
+/// An immutable, published view of the map. Lookups are always
+/// served from the last snapshot so that every proof is against a
+/// root that is in the log.
+#[derive(Debug, Clone)]
+struct Snapshot {
+    head: MapHead,
+    /// `(key_hash, value_hash)` sorted by key hash.
+    leaves: Vec<(Hash, Hash)>,
+    values: HashMap<Hash, Vec<u8>>,
+    /// Internal node hashes by `(depth, prefix)`, for subtrees with
+    /// two or more leaves.
+    nodes: HashMap<(u16, Hash), Hash>,
+}
+
+impl Snapshot {
+    fn build(epoch: u64, entries: &BTreeMap<Hash, Vec<u8>>, log_sequence: u64) -> Self {
+        let leaves: Vec<(Hash, Hash)> = entries.iter().map(|(k, v)| (*k, value_hash(v))).collect();
+        let mut nodes = HashMap::new();
+        let root = Self::subtree(&leaves, 0, &mut nodes);
+        Snapshot {
+            head: MapHead {
+                epoch,
+                root,
+                size: leaves.len() as u64,
+                log_sequence,
+            },
+            values: entries.iter().map(|(k, v)| (*k, v.clone())).collect(),
+            leaves,
+            nodes,
+        }
+    }
+
+    fn subtree(
+        leaves: &[(Hash, Hash)],
+        depth: usize,
+        nodes: &mut HashMap<(u16, Hash), Hash>,
+    ) -> Hash {
+        match leaves {
+            [] => EMPTY,
+            [(k, v)] => hash_leaf(k, v),
+            _ => {
+                let split = leaves.partition_point(|(k, _)| !bit(k, depth));
+                let left = Self::subtree(&leaves[..split], depth + 1, nodes);
+                let right = Self::subtree(&leaves[split..], depth + 1, nodes);
+                let h = hash_node(&left, &right);
+                nodes.insert((depth as u16, prefix(&leaves[0].0, depth)), h);
+                h
+            }
+        }
+    }
+
+    /// Hash of the (already built) subtree holding exactly `leaves`.
+    fn cached(&self, leaves: &[(Hash, Hash)], depth: usize) -> Hash {
+        match leaves {
+            [] => EMPTY,
+            [(k, v)] => hash_leaf(k, v),
+            _ => self.nodes[&(depth as u16, prefix(&leaves[0].0, depth))],
+        }
+    }
+
+    fn prove(&self, kh: &Hash) -> MapProof {
+        let mut siblings = Vec::new();
+        let mut slice = &self.leaves[..];
+        let mut depth = 0;
+        while slice.len() > 1 {
+            let split = slice.partition_point(|(k, _)| !bit(k, depth));
+            let (left, right) = slice.split_at(split);
+            if bit(kh, depth) {
+                siblings.push(self.cached(left, depth + 1));
+                slice = right;
+            } else {
+                siblings.push(self.cached(right, depth + 1));
+                slice = left;
+            }
+            depth += 1;
+        }
+        let terminal = match slice {
+            [(k, v)] if k != kh => Some(MapLeaf {
+                key_hash: *k,
+                value_hash: *v,
+            }),
+            _ => None,
+        };
+        MapProof { siblings, terminal }
+    }
+}
+
+/// The server side: a mutable key → value map whose state is
+/// published into a transparency log one epoch at a time.
+#[derive(Debug, Default, Clone)]
+pub struct VerifiableMap {
+    pending: BTreeMap<Hash, Vec<u8>>,
+    published: Option<Snapshot>,
+}
+
+impl VerifiableMap {
+    /// Construct an empty map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set `key` to `value`. Visible to lookups after the next
+    /// [`publish`](Self::publish).
+    pub fn insert(&mut self, key: &[u8], value: Vec<u8>) -> Option<Vec<u8>> {
+        self.pending.insert(key_hash(key), value)
+    }
+
+    /// Remove `key`. Visible to lookups after the next
+    /// [`publish`](Self::publish).
+    pub fn remove(&mut self, key: &[u8]) -> Option<Vec<u8>> {
+        self.pending.remove(&key_hash(key))
+    }
+
+    /// Number of keys, including unpublished changes.
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Is the map empty (including unpublished changes)?
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// The last published head, if any.
+    pub fn head(&self) -> Option<&MapHead> {
+        self.published.as_ref().map(|s| &s.head)
+    }
+
+    /// Seal the current contents as the next epoch and append its
+    /// commitment to `log`.
+    pub fn publish(&mut self, log: &mut MerkleTree) -> MapHead {
+        let log_sequence = log.len() as u64;
+        let entry = self.seal(log_sequence);
+        let appended = log.append(entry);
+        debug_assert_eq!(appended, log_sequence);
+        self.published.as_ref().expect("just sealed").head
+    }
+
+    /// Seal the current contents as the next epoch and return the log
+    /// entry committing to it. The caller must append that entry to its
+    /// log at `log_sequence` before serving lookups; [`publish`] does
+    /// both for an in-memory log. A server that persists entries
+    /// separately uses this to store the commitment before its tree
+    /// changes.
+    ///
+    /// [`publish`]: Self::publish
+    pub fn seal(&mut self, log_sequence: u64) -> MerkleEntry {
+        let epoch = self.head().map_or(1, |h| h.epoch + 1);
+        let snapshot = Snapshot::build(epoch, &self.pending, log_sequence);
+        let head = snapshot.head;
+        let mut entry = MerkleEntry::new(log_sequence, ArtifactType::MapRoot, head.commitment());
+        entry.metadata = serde_json::json!({
+            "epoch": head.epoch,
+            "root": hex::encode(head.root),
+            "size": head.size,
+        });
+        self.published = Some(snapshot);
+        entry
+    }
+
+    /// Rebuild a map whose last published epoch was `head` from the
+    /// `(key_hash, value)` pairs it held, e.g. after a restart. Fails
+    /// if the pairs do not reproduce `head`.
+    pub fn restore(values: BTreeMap<Hash, Vec<u8>>, head: MapHead) -> Result<Self, MapError> {
+        let snapshot = Snapshot::build(head.epoch, &values, head.log_sequence);
+        if snapshot.head != head {
+            return Err(MapError::RestoreMismatch(head.epoch));
+        }
+        Ok(VerifiableMap {
+            pending: values,
+            published: Some(snapshot),
+        })
+    }
+
+    /// The `(key_hash, value)` pairs of the last published epoch, in
+    /// key-hash order: what [`restore`](Self::restore) needs.
+    pub fn published_values(&self) -> impl Iterator<Item = (&Hash, &[u8])> {
+        self.published.iter().flat_map(|s| {
+            s.leaves
+                .iter()
+                .map(move |(k, _)| (k, s.values[k].as_slice()))
+        })
+    }
+
+    /// Look up `key` in the last published epoch, with a map proof
+    /// and the log inclusion proof of that epoch's commitment.
+    pub fn lookup(&self, key: &[u8], log: &MerkleTree) -> Result<MapLookup, MapError> {
+        let snapshot = self.published.as_ref().ok_or(MapError::NotPublished)?;
+        let kh = key_hash(key);
+        let sequence = snapshot.head.log_sequence;
+        Ok(MapLookup {
+            head: snapshot.head,
+            value: snapshot.values.get(&kh).cloned(),
+            proof: snapshot.prove(&kh),
+            log_entry: log.entry(sequence)?.clone(),
+            log_proof: log.inclusion_proof(sequence)?,
+        })
+    }
+}
+
+/// Verify a map proof for `key` against `root`. `value` is the
+/// claimed value (`None` claims absence).
+pub fn verify_proof(
+    root: &Hash,
+    epoch: u64,
+    key: &[u8],
+    value: Option<&[u8]>,
+    proof: &MapProof,
+) -> Result<(), MapError> {
+    let kh = key_hash(key);
+    let depth = proof.siblings.len();
+    if depth > DEPTH {
+        return Err(MapError::Malformed("proof deeper than the tree"));
+    }
+    let mut current = match (value, &proof.terminal) {
+        (Some(v), None) => hash_leaf(&kh, &value_hash(v)),
+        (Some(_), Some(_)) => return Err(MapError::Malformed("inclusion proof with terminal")),
+        (None, None) => EMPTY,
+        (None, Some(leaf)) => {
+            // The other key must genuinely sit on our path, and must
+            // not be our key (which would make this an inclusion).
+            if leaf.key_hash == kh || prefix(&leaf.key_hash, depth) != prefix(&kh, depth) {
+                return Err(MapError::Malformed("terminal leaf not on key path"));
+            }
+            hash_leaf(&leaf.key_hash, &leaf.value_hash)
+        }
+    };
+    for (i, sibling) in proof.siblings.iter().enumerate().rev() {
+        current = if bit(&kh, i) {
+            hash_node(sibling, &current)
+        } else {
+            hash_node(&current, sibling)
+        };
+    }
+    if current.ct_eq(root).into() {
+        Ok(())
+    } else {
+        Err(MapError::ProofFailed(epoch))
+    }
+}
+
+/// The client side: verifies lookups end to end and remembers the
+/// newest head it has accepted, rejecting epoch rollback and
+/// same-epoch equivocation.
+#[derive(Debug, Default, Clone)]
+pub struct MapVerifier {
+    latest: Option<MapHead>,
+}
+
+impl MapVerifier {
+    /// A verifier that has seen no heads.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The newest head accepted so far.
+    pub fn latest(&self) -> Option<&MapHead> {
+        self.latest.as_ref()
+    }
+
+    /// Verify `lookup` for `key` against a log root the caller already
+    /// trusts (e.g. a witnessed tree head). Returns the proven value,
+    /// or `None` for a proven absence.
+    pub fn verify<'a>(
+        &mut self,
+        key: &[u8],
+        lookup: &'a MapLookup,
+        log_root: Hash,
+    ) -> Result<Option<&'a [u8]>, MapError> {
+        let head = &lookup.head;
+        let entry = &lookup.log_entry;
+        if entry.artifact_type != ArtifactType::MapRoot
+            || entry.sequence != head.log_sequence
+            || !bool::from(entry.artifact_hash.ct_eq(&head.commitment()))
+        {
+            return Err(MapError::CommitmentMismatch(entry.sequence));
+        }
+        MerkleTree::verify_inclusion(entry, &lookup.log_proof, log_root)?;
+        let value = lookup.value.as_deref();
+        verify_proof(&head.root, head.epoch, key, value, &lookup.proof)?;
+
+        if let Some(seen) = &self.latest {
+            if head.epoch < seen.epoch {
+                return Err(MapError::EpochRollback {
+                    seen: seen.epoch,
+                    got: head.epoch,
+                });
+            }
+            if head.epoch == seen.epoch && head != seen {
+                return Err(MapError::Equivocation(head.epoch));
+            }
+        }
+        self.latest = Some(*head);
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn populated(n: usize) -> (VerifiableMap, MerkleTree) {
+        let mut log = MerkleTree::new();
+        let mut map = VerifiableMap::new();
+        for i in 0..n {
+            map.insert(format!("id-{i}").as_bytes(), format!("pk-{i}").into_bytes());
+        }
+        map.publish(&mut log);
+        (map, log)
+    }
+
+    #[test]
+    fn inclusion_and_non_inclusion_verify() {
+        let (map, log) = populated(50);
+        let mut verifier = MapVerifier::new();
+        for i in 0..50 {
+            let key = format!("id-{i}");
+            let lookup = map.lookup(key.as_bytes(), &log).unwrap();
+            let value = verifier
+                .verify(key.as_bytes(), &lookup, log.root())
+                .unwrap();
+            assert_eq!(value, Some(format!("pk-{i}").as_bytes()));
+        }
+        for i in 50..100 {
+            let key = format!("id-{i}");
+            let lookup = map.lookup(key.as_bytes(), &log).unwrap();
+            assert!(lookup.value.is_none());
+            assert_eq!(
+                verifier
+                    .verify(key.as_bytes(), &lookup, log.root())
+                    .unwrap(),
+                None
+            );
+        }
+    }
+
+    #[test]
+    fn empty_and_singleton_maps_prove() {
+        let (map, log) = populated(0);
+        let lookup = map.lookup(b"anyone", &log).unwrap();
+        assert_eq!(lookup.head.root, EMPTY);
+        MapVerifier::new()
+            .verify(b"anyone", &lookup, log.root())
+            .unwrap();
+
+        let (map, log) = populated(1);
+        let absent = map.lookup(b"nobody", &log).unwrap();
+        assert!(absent.proof.siblings.is_empty());
+        assert!(absent.proof.terminal.is_some());
+        MapVerifier::new()
+            .verify(b"nobody", &absent, log.root())
+            .unwrap();
+    }
+
+    #[test]
+    fn tampered_value_or_absence_claim_rejected() {
+        let (map, log) = populated(20);
+        let mut lookup = map.lookup(b"id-3", &log).unwrap();
+        lookup.value = Some(b"attacker-pk".to_vec());
+        assert!(matches!(
+            MapVerifier::new().verify(b"id-3", &lookup, log.root()),
+            Err(MapError::ProofFailed(1))
+        ));
+
+        // Hiding a present key behind a non-inclusion claim fails too.
+        let mut hidden = map.lookup(b"id-3", &log).unwrap();
+        hidden.value = None;
+        assert!(
+            MapVerifier::new()
+                .verify(b"id-3", &hidden, log.root())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn lookup_must_be_committed_in_log() {
+        let (map, log) = populated(5);
+        let mut lookup = map.lookup(b"id-1", &log).unwrap();
+        lookup.head.root = [9u8; 32];
+        assert!(matches!(
+            MapVerifier::new().verify(b"id-1", &lookup, log.root()),
+            Err(MapError::CommitmentMismatch(0))
+        ));
+
+        let lookup = map.lookup(b"id-1", &log).unwrap();
+        assert!(matches!(
+            MapVerifier::new().verify(b"id-1", &lookup, [0u8; 32]),
+            Err(MapError::Log(_))
+        ));
+    }
+
+    #[test]
+    fn updates_take_effect_at_publish_and_epochs_only_advance() {
+        let mut log = MerkleTree::new();
+        let mut map = VerifiableMap::new();
+        map.insert(b"alice", b"pk-1".to_vec());
+        map.publish(&mut log);
+        let old = map.lookup(b"alice", &log).unwrap();
+
+        map.insert(b"alice", b"pk-2".to_vec());
+        assert_eq!(map.lookup(b"alice", &log).unwrap().value.unwrap(), b"pk-1");
+        let head = map.publish(&mut log);
+        assert_eq!(head.epoch, 2);
+        let new = map.lookup(b"alice", &log).unwrap();
+        assert_eq!(new.value.as_deref(), Some(&b"pk-2"[..]));
+
+        let mut verifier = MapVerifier::new();
+        verifier.verify(b"alice", &new, log.root()).unwrap();
+        // Re-proving the old epoch against the current log is valid
+        // on its own, but a client that has seen epoch 2 refuses it.
+        let log_proof = log.inclusion_proof(old.head.log_sequence).unwrap();
+        let old = MapLookup { log_proof, ..old };
+        assert!(matches!(
+            verifier.verify(b"alice", &old, log.root()),
+            Err(MapError::EpochRollback { seen: 2, got: 1 })
+        ));
+    }
+
+    #[test]
+    fn restore_rebuilds_the_published_epoch() {
+        let (mut map, mut log) = populated(30);
+        map.insert(b"id-0", b"unpublished".to_vec());
+        let head = *map.head().unwrap();
+        let values: BTreeMap<Hash, Vec<u8>> = map
+            .published_values()
+            .map(|(k, v)| (*k, v.to_vec()))
+            .collect();
+        assert_eq!(values.len(), 30);
+
+        let mut restored = VerifiableMap::restore(values.clone(), head).unwrap();
+        let lookup = restored.lookup(b"id-0", &log).unwrap();
+        assert_eq!(lookup.value.as_deref(), Some(&b"pk-0"[..]));
+        MapVerifier::new()
+            .verify(b"id-0", &lookup, log.root())
+            .unwrap();
+        assert_eq!(restored.publish(&mut log).epoch, 2);
+
+        let mut tampered = values;
+        tampered.insert(key_hash(b"id-0"), b"attacker-pk".to_vec());
+        assert!(matches!(
+            VerifiableMap::restore(tampered, head),
+            Err(MapError::RestoreMismatch(1))
+        ));
+    }
+
+    #[test]
+    fn lookup_before_publish_is_an_error() {
+        let map = VerifiableMap::new();
+        assert!(matches!(
+            map.lookup(b"x", &MerkleTree::new()),
+            Err(MapError::NotPublished)
+        ));
+    }
+}
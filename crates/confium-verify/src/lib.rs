@@ -37,3 +37,45 @@ pub use confium_signatif as signatif;
 /// HTTP verification service.
 #[allow(rustdoc::broken_intra_doc_links)]
 pub use confium_verify_server as server;
+
+#[cfg(feature = "frost")]
+/// FROST threshold Schnorr signature verification (RFC 9591). A FROST
+/// signature verifies under the group public key alone.
+pub mod frost {
+    use confium_tc_frost::ed448::Ed448;
+    use confium_tc_frost::error::Result;
+    use confium_tc_frost::p256::P256;
+    use confium_tc_frost::ristretto255::Ristretto255;
+    use confium_tc_frost::secp256k1::{Secp256k1, Secp256k1Tr};
+    use confium_tc_frost::verify::verify;
+
+    /// Verify a 65-byte FROST(P-256, SHA-256) signature `R ‖ z` under a
+    /// 33-byte compressed group public key.
+    pub fn verify_p256(public_key: &[u8], message: &[u8], signature: &[u8]) -> Result<()> {
+        verify::<P256>(public_key, message, signature)
+    }
+
+    /// Verify a 64-byte FROST(ristretto255, SHA-512) signature under a
+    /// 32-byte group public key.
+    pub fn verify_ristretto255(public_key: &[u8], message: &[u8], signature: &[u8]) -> Result<()> {
+        verify::<Ristretto255>(public_key, message, signature)
+    }
+
+    /// Verify a 114-byte FROST(Ed448, SHAKE256) signature — an RFC 8032
+    /// Ed448 signature — under a 57-byte group public key.
+    pub fn verify_ed448(public_key: &[u8], message: &[u8], signature: &[u8]) -> Result<()> {
+        verify::<Ed448>(public_key, message, signature)
+    }
+
+    /// Verify a 65-byte FROST(secp256k1, SHA-256) signature `R ‖ z` under
+    /// a 33-byte compressed group public key.
+    pub fn verify_secp256k1(public_key: &[u8], message: &[u8], signature: &[u8]) -> Result<()> {
+        verify::<Secp256k1>(public_key, message, signature)
+    }
+
+    /// Verify a 64-byte BIP-340 signature under a 32-byte x-only (or
+    /// 33-byte compressed) public key, such as a Taproot output key.
+    pub fn verify_secp256k1_tr(public_key: &[u8], message: &[u8], signature: &[u8]) -> Result<()> {
+        verify::<Secp256k1Tr>(public_key, message, signature)
+    }
+}
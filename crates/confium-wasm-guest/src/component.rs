@@ -0,0 +1,689 @@
+//! The `confium:plugin/plugin` world, implemented over the
+//! registrations in [`crate::exports`].
+//!
+//! Each resource wraps the boxed plugin object in a `RefCell`: the
+//! bindings hand out `&self`, while the plugin traits mutate.
+
+use std::cell::RefCell;
+
+use confium_api::{ErrorCode, KeyHandle, OptionMap, OptionValue, OptionView, PluginError};
+
+use crate::bindings::confium::plugin::types;
+use crate::bindings::exports::confium::plugin::{
+    aead, cipher, hash, kdf, kem, keyfmt, lifecycle, rng, signature,
+};
+use crate::exports::{
+    DynAead, DynCipher, DynHash, DynKdf, DynKem, DynKey, DynRng, DynSignature, Exports,
+    Registration,
+};
+
+/// The plugin world's implementation. `#[export]` points the
+/// generated exports at it; plugins never name it directly.
+pub struct Component;
+
+type Code = types::ErrorCode;
+
+const MISSING: Code = ErrorCode::PLUGIN_MISSING_INTERFACE as u32;
+const INSUFFICIENT_BUFFER: Code = ErrorCode::INSUFFICIENT_BUFFER as u32;
+
+fn wire(e: PluginError) -> Code {
+    e.into_wire()
+}
+
+/// The entry points registered for one interface, if any.
+fn registered<T>(
+    pick: impl Fn(&'static Exports) -> Option<&'static T>,
+) -> Result<&'static T, Code> {
+    inventory::iter::<Registration>
+        .into_iter()
+        .find_map(|r| pick(&r.exports))
+        .ok_or(MISSING)
+}
+
+/// Rebuild the nested option map the WIT options were flattened from.
+fn option_map(entries: types::Options) -> OptionMap {
+    fn insert(map: &mut OptionMap, path: &[String], value: OptionValue) {
+        match path {
+            [] => {}
+            [key] => {
+                map.insert(key.clone(), value);
+            }
+            [key, rest @ ..] => {
+                let slot = map
+                    .entry(key.clone())
+                    .or_insert_with(|| OptionValue::Map(Box::default()));
+                if !matches!(slot, OptionValue::Map(_)) {
+                    *slot = OptionValue::Map(Box::default());
+                }
+                if let OptionValue::Map(inner) = slot {
+                    insert(inner, rest, value);
+                }
+            }
+        }
+    }
+    let mut map = OptionMap::new();
+    for entry in entries {
+        let value = match entry.value {
+            types::OptionValue::Text(s) => OptionValue::String(s),
+            types::OptionValue::Number(n) => OptionValue::U32(n),
+        };
+        insert(&mut map, &entry.path, value);
+    }
+    map
+}
+
+fn view(map: &Option<OptionMap>) -> Option<OptionView<'_>> {
+    map.as_ref().map(OptionView::new)
+}
+
+fn key_handle(handle: &types::KeyHandle) -> KeyHandle<'_> {
+    KeyHandle::new(&handle.backend, &handle.key_id, std::ptr::null_mut())
+}
+
+/// Run `f` over a buffer of `cap` bytes and keep what it wrote.
+fn produce(
+    cap: u32,
+    f: impl FnOnce(&mut [u8]) -> Result<usize, PluginError>,
+) -> Result<Vec<u8>, Code> {
+    let mut out = vec![0u8; cap as usize];
+    let n = f(&mut out).map_err(wire)?;
+    out.truncate(n);
+    Ok(out)
+}
+
+/// A keypair the caller's buffers can hold.
+fn keypair(
+    public_key: Vec<u8>,
+    secret_key: Vec<u8>,
+    pk_cap: u32,
+    sk_cap: u32,
+) -> Result<types::Keypair, Code> {
+    if public_key.len() > pk_cap as usize || secret_key.len() > sk_cap as usize {
+        return Err(INSUFFICIENT_BUFFER);
+    }
+    Ok(types::Keypair {
+        public_key,
+        secret_key,
+    })
+}
+
+/// A keypair-generation seed; an empty one means none, as in the C ABI.
+fn seed(seed: &Option<Vec<u8>>) -> Option<&[u8]> {
+    seed.as_deref().filter(|s| !s.is_empty())
+}
+
+// ---------------------------------------------------------------------
+// lifecycle
+// ---------------------------------------------------------------------
+
+impl lifecycle::Guest for Component {
+    fn initialize(_opts: Option<types::Options>) -> Result<(), Code> {
+        Ok(())
+    }
+
+    fn finalize() {}
+
+    fn interfaces() -> Vec<(String, u8)> {
+        inventory::iter::<Registration>
+            .into_iter()
+            .flat_map(|r| (0..=r.version).map(|v| (r.wire_name.to_string(), v)))
+            .collect()
+    }
+}
+
+// ---------------------------------------------------------------------
+// hash
+// ---------------------------------------------------------------------
+
+pub struct HashState(RefCell<Box<dyn DynHash>>);
+
+impl hash::Guest for Component {
+    type Context = HashState;
+
+    fn create(name: String, opts: Option<types::Options>) -> Result<hash::Context, Code> {
+        let export = registered(|e| match e {
+            Exports::Hash(x) => Some(x),
+            _ => None,
+        })?;
+        let opts = opts.map(option_map);
+        let inner = (export.create)(&name, view(&opts)).map_err(wire)?;
+        Ok(hash::Context::new(HashState(RefCell::new(inner))))
+    }
+}
+
+impl hash::GuestContext for HashState {
+    fn output_size(&self) -> u32 {
+        self.0.borrow().output_size()
+    }
+
+    fn block_size(&self) -> u32 {
+        self.0.borrow().block_size()
+    }
+
+    fn update(&self, data: Vec<u8>) -> Result<(), Code> {
+        self.0.borrow_mut().update(&data).map_err(wire)
+    }
+
+    fn reset(&self) -> Result<(), Code> {
+        self.0.borrow_mut().reset().map_err(wire)
+    }
+
+    fn clone(&self) -> Result<hash::Context, Code> {
+        let inner = self.0.borrow().try_clone().map_err(wire)?;
+        Ok(hash::Context::new(HashState(RefCell::new(inner))))
+    }
+
+    fn finalize(&self, len: u32) -> Result<Vec<u8>, Code> {
+        let mut out = vec![0u8; len as usize];
+        self.0.borrow_mut().finalize(&mut out).map_err(wire)?;
+        Ok(out)
+    }
+}
+
+// ---------------------------------------------------------------------
+// rng
+// ---------------------------------------------------------------------
+
+pub struct RngState(RefCell<Box<dyn DynRng>>);
+
+impl rng::Guest for Component {
+    type Context = RngState;
+
+    fn create(name: String, opts: Option<types::Options>) -> Result<rng::Context, Code> {
+        let export = registered(|e| match e {
+            Exports::Rng(x) => Some(x),
+            _ => None,
+        })?;
+        let opts = opts.map(option_map);
+        let inner = (export.create)(&name, view(&opts)).map_err(wire)?;
+        Ok(rng::Context::new(RngState(RefCell::new(inner))))
+    }
+}
+
+impl rng::GuestContext for RngState {
+    fn reseed(&self, data: Vec<u8>) -> Result<(), Code> {
+        self.0.borrow_mut().reseed(&data).map_err(wire)
+    }
+
+    fn add_entropy(&self, data: Vec<u8>) -> Result<(), Code> {
+        self.0.borrow_mut().add_entropy(&data).map_err(wire)
+    }
+
+    fn generate(&self, len: u32) -> Result<Vec<u8>, Code> {
+        let mut out = vec![0u8; len as usize];
+        self.0.borrow_mut().generate(&mut out).map_err(wire)?;
+        Ok(out)
+    }
+}
+
+// ---------------------------------------------------------------------
+// symmetric cipher
+// ---------------------------------------------------------------------
+
+pub struct CipherState(RefCell<Box<dyn DynCipher>>);
+
+impl cipher::Guest for Component {
+    type Context = CipherState;
+
+    fn create(
+        name: String,
+        key: Vec<u8>,
+        iv: Vec<u8>,
+        opts: Option<types::Options>,
+    ) -> Result<cipher::Context, Code> {
+        let export = registered(|e| match e {
+            Exports::Cipher(x) => Some(x),
+            _ => None,
+        })?;
+        let opts = opts.map(option_map);
+        let inner = (export.create)(&name, &key, &iv, view(&opts)).map_err(wire)?;
+        Ok(cipher::Context::new(CipherState(RefCell::new(inner))))
+    }
+}
+
+impl cipher::GuestContext for CipherState {
+    fn block_size(&self) -> u32 {
+        self.0.borrow().block_size()
+    }
+
+    fn key_size(&self) -> u32 {
+        self.0.borrow().key_size()
+    }
+
+    fn iv_size(&self) -> u32 {
+        self.0.borrow().iv_size()
+    }
+
+    fn update(&self, input: Vec<u8>, out_cap: u32) -> Result<Vec<u8>, Code> {
+        produce(out_cap, |out| self.0.borrow_mut().update(&input, out))
+    }
+
+    fn finalize(&self, out_cap: u32) -> Result<Vec<u8>, Code> {
+        produce(out_cap, |out| self.0.borrow_mut().finalize(out))
+    }
+
+    fn reset(&self) -> Result<(), Code> {
+        self.0.borrow_mut().reset().map_err(wire)
+    }
+}
+
+// ---------------------------------------------------------------------
+// aead
+// ---------------------------------------------------------------------
+
+pub struct AeadState(RefCell<Box<dyn DynAead>>);
+
+impl aead::Guest for Component {
+    type Context = AeadState;
+
+    fn create(
+        name: String,
+        key: Vec<u8>,
+        opts: Option<types::Options>,
+    ) -> Result<aead::Context, Code> {
+        let export = registered(|e| match e {
+            Exports::Aead(x) => Some(x),
+            _ => None,
+        })?;
+        let opts = opts.map(option_map);
+        let inner = (export.create)(&name, &key, view(&opts)).map_err(wire)?;
+        Ok(aead::Context::new(AeadState(RefCell::new(inner))))
+    }
+}
+
+impl aead::GuestContext for AeadState {
+    fn set_nonce(&self, nonce: Vec<u8>) -> Result<(), Code> {
+        self.0.borrow_mut().set_nonce(&nonce).map_err(wire)
+    }
+
+    fn associated_data_update(&self, data: Vec<u8>) -> Result<(), Code> {
+        self.0
+            .borrow_mut()
+            .associated_data_update(&data)
+            .map_err(wire)
+    }
+
+    fn encrypt_update(&self, input: Vec<u8>, out_cap: u32) -> Result<Vec<u8>, Code> {
+        produce(out_cap, |out| {
+            self.0.borrow_mut().encrypt_update(&input, out)
+        })
+    }
+
+    fn decrypt_update(&self, input: Vec<u8>, out_cap: u32) -> Result<Vec<u8>, Code> {
+        produce(out_cap, |out| {
+            self.0.borrow_mut().decrypt_update(&input, out)
+        })
+    }
+
+    fn finalize(&self, out_cap: u32) -> Result<Vec<u8>, Code> {
+        produce(out_cap, |out| self.0.borrow_mut().finalize(out))
+    }
+
+    fn verify_tag(&self, tag: Vec<u8>) -> Result<(), Code> {
+        self.0.borrow_mut().verify_tag(&tag).map_err(wire)
+    }
+}
+
+// ---------------------------------------------------------------------
+// kdf
+// ---------------------------------------------------------------------
+
+pub struct KdfState(RefCell<Box<dyn DynKdf>>);
+
+impl kdf::Guest for Component {
+    type Context = KdfState;
+
+    fn create(name: String, opts: Option<types::Options>) -> Result<kdf::Context, Code> {
+        let export = registered(|e| match e {
+            Exports::Kdf(x) => Some(x),
+            _ => None,
+        })?;
+        let opts = opts.map(option_map);
+        let inner = (export.create)(&name, view(&opts)).map_err(wire)?;
+        Ok(kdf::Context::new(KdfState(RefCell::new(inner))))
+    }
+}
+
+impl kdf::GuestContext for KdfState {
+    fn set_salt(&self, salt: Vec<u8>) -> Result<(), Code> {
+        self.0.borrow_mut().set_salt(&salt).map_err(wire)
+    }
+
+    fn set_iterations(&self, iterations: u32) -> Result<(), Code> {
+        self.0.borrow_mut().set_iterations(iterations).map_err(wire)
+    }
+
+    fn set_memory_cost(&self, bytes: u64) -> Result<(), Code> {
+        self.0.borrow_mut().set_memory_cost(bytes).map_err(wire)
+    }
+
+    fn set_parallelism(&self, lanes: u32) -> Result<(), Code> {
+        self.0.borrow_mut().set_parallelism(lanes).map_err(wire)
+    }
+
+    fn set_hash(&self, name: String) -> Result<(), Code> {
+        self.0.borrow_mut().set_hash(&name).map_err(wire)
+    }
+
+    fn derive(&self, secret: Vec<u8>, len: u32) -> Result<Vec<u8>, Code> {
+        let mut out = vec![0u8; len as usize];
+        self.0
+            .borrow_mut()
+            .derive(&secret, &mut out)
+            .map_err(wire)?;
+        Ok(out)
+    }
+}
+
+// ---------------------------------------------------------------------
+// keyfmt
+// ---------------------------------------------------------------------
+
+pub struct KeyState(Box<dyn DynKey>);
+
+impl keyfmt::Guest for Component {
+    type Key = KeyState;
+
+    fn parse(
+        format: String,
+        algorithm: Option<String>,
+        data: Vec<u8>,
+        opts: Option<types::Options>,
+    ) -> Result<keyfmt::Key, Code> {
+        let export = registered(|e| match e {
+            Exports::Keyfmt(x) => Some(x),
+            _ => None,
+        })?;
+        let opts = opts.map(option_map);
+        let inner =
+            (export.parse)(&format, algorithm.as_deref(), &data, view(&opts)).map_err(wire)?;
+        Ok(keyfmt::Key::new(KeyState(inner)))
+    }
+}
+
+impl keyfmt::GuestKey for KeyState {
+    fn serialize(&self, format: String, out_cap: u32) -> Result<Vec<u8>, Code> {
+        let bytes = self.0.serialize(&format).map_err(wire)?;
+        if bytes.len() > out_cap as usize {
+            return Err(INSUFFICIENT_BUFFER);
+        }
+        Ok(bytes)
+    }
+
+    fn kind(&self) -> Result<u32, Code> {
+        self.0.kind().map(|k| k as u32).map_err(wire)
+    }
+
+    fn algorithm(&self) -> Result<String, Code> {
+        self.0.algorithm().map_err(wire)
+    }
+
+    fn public(&self) -> Result<keyfmt::Key, Code> {
+        let inner = self.0.public().map_err(wire)?;
+        Ok(keyfmt::Key::new(KeyState(inner)))
+    }
+}
+
+// ---------------------------------------------------------------------
+// signature
+// ---------------------------------------------------------------------
+
+pub struct SignerState(RefCell<Box<dyn DynSignature>>);
+pub struct VerifierState(RefCell<Box<dyn DynSignature>>);
+
+fn signature_export() -> Result<&'static crate::SignatureExport, Code> {
+    registered(|e| match e {
+        Exports::Signature(x) => Some(x),
+        _ => None,
+    })
+}
+
+impl signature::Guest for Component {
+    type Signer = SignerState;
+    type Verifier = VerifierState;
+
+    fn signer_create(
+        algorithm: String,
+        secret_key: Vec<u8>,
+        opts: Option<types::Options>,
+    ) -> Result<signature::Signer, Code> {
+        let export = signature_export()?;
+        let opts = opts.map(option_map);
+        let inner = (export.signer_create)(&algorithm, &secret_key, view(&opts)).map_err(wire)?;
+        Ok(signature::Signer::new(SignerState(RefCell::new(inner))))
+    }
+
+    fn verifier_create(
+        algorithm: String,
+        public_key: Vec<u8>,
+        opts: Option<types::Options>,
+    ) -> Result<signature::Verifier, Code> {
+        let export = signature_export()?;
+        let opts = opts.map(option_map);
+        let inner = (export.verifier_create)(&algorithm, &public_key, view(&opts)).map_err(wire)?;
+        Ok(signature::Verifier::new(VerifierState(RefCell::new(inner))))
+    }
+
+    fn keypair_generate(
+        algorithm: String,
+        seed: Option<Vec<u8>>,
+        pk_cap: u32,
+        sk_cap: u32,
+    ) -> Result<types::Keypair, Code> {
+        let export = signature_export()?;
+        let kp = (export.keypair_generate)(&algorithm, self::seed(&seed), None).map_err(wire)?;
+        keypair(kp.public_key, kp.secret_key, pk_cap, sk_cap)
+    }
+
+    fn handle_capabilities(algorithm: String, handle: types::KeyHandle) -> u32 {
+        let Ok(handles) = signature_export().map(|e| e.handles.as_ref()) else {
+            return 0;
+        };
+        handles.map_or(0, |h| {
+            (h.capabilities)(&algorithm, &key_handle(&handle)).bits()
+        })
+    }
+
+    fn signer_create_with_handle(
+        algorithm: String,
+        handle: types::KeyHandle,
+        opts: Option<types::Options>,
+    ) -> Result<signature::Signer, Code> {
+        let handles = signature_export()?.handles.as_ref().ok_or(MISSING)?;
+        let opts = opts.map(option_map);
+        let inner =
+            (handles.signer_create)(&algorithm, &key_handle(&handle), view(&opts)).map_err(wire)?;
+        Ok(signature::Signer::new(SignerState(RefCell::new(inner))))
+    }
+}
+
+impl signature::GuestSigner for SignerState {
+    fn set_hash(&self, name: String) -> Result<(), Code> {
+        self.0.borrow_mut().set_hash(&name).map_err(wire)
+    }
+
+    fn update(&self, data: Vec<u8>) -> Result<(), Code> {
+        self.0.borrow_mut().update(&data).map_err(wire)
+    }
+
+    fn finalize(&self, out_cap: u32) -> Result<Vec<u8>, Code> {
+        produce(out_cap, |out| self.0.borrow_mut().signer_finalize(out))
+    }
+}
+
+impl signature::GuestVerifier for VerifierState {
+    fn set_hash(&self, name: String) -> Result<(), Code> {
+        self.0.borrow_mut().set_hash(&name).map_err(wire)
+    }
+
+    fn update(&self, data: Vec<u8>) -> Result<(), Code> {
+        self.0.borrow_mut().update(&data).map_err(wire)
+    }
+
+    fn finalize(&self, signature: Vec<u8>) -> Result<(), Code> {
+        self.0
+            .borrow_mut()
+            .verifier_finalize(&signature)
+            .map_err(wire)
+    }
+}
+
+// ---------------------------------------------------------------------
+// kem
+// ---------------------------------------------------------------------
+
+pub struct EncapsulatorState(RefCell<Box<dyn DynKem>>);
+pub struct DecapsulatorState(RefCell<Box<dyn DynKem>>);
+
+fn kem_export() -> Result<&'static crate::KemExport, Code> {
+    registered(|e| match e {
+        Exports::Kem(x) => Some(x),
+        _ => None,
+    })
+}
+
+impl kem::Guest for Component {
+    type Encapsulator = EncapsulatorState;
+    type Decapsulator = DecapsulatorState;
+
+    fn encapsulator_create(
+        algorithm: String,
+        public_key: Vec<u8>,
+        opts: Option<types::Options>,
+    ) -> Result<kem::Encapsulator, Code> {
+        let export = kem_export()?;
+        let opts = opts.map(option_map);
+        let inner =
+            (export.encapsulator_create)(&algorithm, &public_key, view(&opts)).map_err(wire)?;
+        Ok(kem::Encapsulator::new(EncapsulatorState(RefCell::new(
+            inner,
+        ))))
+    }
+
+    fn decapsulator_create(
+        algorithm: String,
+        secret_key: Vec<u8>,
+        opts: Option<types::Options>,
+    ) -> Result<kem::Decapsulator, Code> {
+        let export = kem_export()?;
+        let opts = opts.map(option_map);
+        let inner =
+            (export.decapsulator_create)(&algorithm, &secret_key, view(&opts)).map_err(wire)?;
+        Ok(kem::Decapsulator::new(DecapsulatorState(RefCell::new(
+            inner,
+        ))))
+    }
+
+    fn shared_secret_size(algorithm: String) -> Result<u32, Code> {
+        (kem_export()?.shared_secret_size)(&algorithm).map_err(wire)
+    }
+
+    fn keypair_generate(
+        algorithm: String,
+        seed: Option<Vec<u8>>,
+        pk_cap: u32,
+        sk_cap: u32,
+    ) -> Result<types::Keypair, Code> {
+        let export = kem_export()?;
+        let kp = (export.keypair_generate)(&algorithm, self::seed(&seed), None).map_err(wire)?;
+        keypair(kp.public_key, kp.secret_key, pk_cap, sk_cap)
+    }
+
+    fn handle_capabilities(algorithm: String, handle: types::KeyHandle) -> u32 {
+        let Ok(handles) = kem_export().map(|e| e.handles.as_ref()) else {
+            return 0;
+        };
+        handles.map_or(0, |h| {
+            (h.capabilities)(&algorithm, &key_handle(&handle)).bits()
+        })
+    }
+
+    fn decapsulator_create_with_handle(
+        algorithm: String,
+        handle: types::KeyHandle,
+        opts: Option<types::Options>,
+    ) -> Result<kem::Decapsulator, Code> {
+        let handles = kem_export()?.handles.as_ref().ok_or(MISSING)?;
+        let opts = opts.map(option_map);
+        let inner = (handles.decapsulator_create)(&algorithm, &key_handle(&handle), view(&opts))
+            .map_err(wire)?;
+        Ok(kem::Decapsulator::new(DecapsulatorState(RefCell::new(
+            inner,
+        ))))
+    }
+}
+
+impl kem::GuestEncapsulator for EncapsulatorState {
+    fn encapsulate(&self, ct_cap: u32, ss_cap: u32) -> Result<kem::Encapsulation, Code> {
+        let mut ct = vec![0u8; ct_cap as usize];
+        let mut ss = vec![0u8; ss_cap as usize];
+        let mut res = self
+            .0
+            .borrow_mut()
+            .encapsulate(&mut ct, &mut ss)
+            .map_err(wire)?;
+        // As over the C ABI, only what fits the caller's buffers is
+        // handed back.
+        res.ciphertext.truncate(ct_cap as usize);
+        res.shared_secret.truncate(ss_cap as usize);
+        Ok(kem::Encapsulation {
+            ciphertext: res.ciphertext,
+            shared_secret: res.shared_secret,
+        })
+    }
+}
+
+impl kem::GuestDecapsulator for DecapsulatorState {
+    fn decapsulate(&self, ciphertext: Vec<u8>, ss_cap: u32) -> Result<Vec<u8>, Code> {
+        produce(ss_cap, |out| {
+            self.0.borrow_mut().decapsulate(&ciphertext, out)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(path: &[&str], value: types::OptionValue) -> types::OptionEntry {
+        types::OptionEntry {
+            path: path.iter().map(|s| s.to_string()).collect(),
+            value,
+        }
+    }
+
+    #[test]
+    fn options_regain_their_nesting() {
+        let map = option_map(vec![
+            entry(&["mode"], types::OptionValue::Text("gcm".to_string())),
+            entry(
+                &["keyfmt", "encoding"],
+                types::OptionValue::Text("pem".to_string()),
+            ),
+            entry(&["keyfmt", "bits"], types::OptionValue::Number(256)),
+        ]);
+        let view = OptionView::new(&map);
+        assert_eq!(view.get_str("mode"), Some("gcm"));
+        let keyfmt = view.get_map("keyfmt").expect("nested map");
+        assert_eq!(keyfmt.get_str("encoding"), Some("pem"));
+        assert_eq!(keyfmt.get_u32("bits"), Some(256));
+    }
+
+    #[test]
+    fn unregistered_interfaces_are_missing() {
+        // Nothing in this crate carries #[plugin_interface].
+        assert!(<Component as lifecycle::Guest>::interfaces().is_empty());
+        let err = <Component as hash::Guest>::create("sha-256".to_string(), None)
+            .expect_err("no hash registered");
+        assert_eq!(err, MISSING);
+    }
+
+    #[test]
+    fn oversized_keypairs_are_refused() {
+        assert_eq!(
+            keypair(vec![0; 33], vec![0; 4], 32, 32).err(),
+            Some(INSUFFICIENT_BUFFER)
+        );
+        assert!(keypair(vec![0; 32], vec![0; 4], 32, 32).is_ok());
+    }
+}
@@ -0,0 +1,479 @@
+//! What `#[plugin_interface]` registers.
+//!
+//! The component bindings are generated once, in this crate, so they
+//! cannot be generic over a plugin's types. Each interface therefore
+//! gets an object-safe mirror of its `confium_api::plugin` trait
+//! (`Dyn*`, blanket-implemented for every implementor) and a table of
+//! the trait's associated functions as plain function pointers. The
+//! macro submits one [`Registration`] per `impl` block; [`Component`]
+//! looks the table up when the host creates an object.
+//!
+//! [`Component`]: crate::Component
+
+use confium_api::plugin::kem::{KemEncapsulateResult, KemKeypair};
+use confium_api::plugin::keyfmt::KeyKind;
+use confium_api::plugin::signature::SignatureKeypair;
+use confium_api::{
+    AeadPlugin, CipherPlugin, HandleCapabilities, HashPlugin, KdfPlugin, KemHandlePlugin,
+    KemPlugin, KeyHandle, KeyfmtPlugin, OptionView, PluginResult, RngPlugin, SignatureHandlePlugin,
+    SignaturePlugin,
+};
+
+type Opts<'a> = Option<OptionView<'a>>;
+
+/// One `#[plugin_interface]` impl block.
+pub struct Registration {
+    /// Wire name, as advertised to the host (`symmetric` for `cipher`).
+    pub wire_name: &'static str,
+    /// Highest version implemented; every version up to it is
+    /// advertised, as the native macro does.
+    pub version: u8,
+    /// The implementing type's entry points.
+    pub exports: Exports,
+}
+
+inventory::collect!(Registration);
+
+/// Entry points for one interface.
+pub enum Exports {
+    Hash(HashExport),
+    Rng(RngExport),
+    Cipher(CipherExport),
+    Aead(AeadExport),
+    Kdf(KdfExport),
+    Keyfmt(KeyfmtExport),
+    Signature(SignatureExport),
+    Kem(KemExport),
+}
+
+// ---------------------------------------------------------------------
+// hash
+// ---------------------------------------------------------------------
+
+pub(crate) trait DynHash {
+    fn output_size(&self) -> u32;
+    fn block_size(&self) -> u32;
+    fn update(&mut self, data: &[u8]) -> PluginResult<()>;
+    fn reset(&mut self) -> PluginResult<()>;
+    fn try_clone(&self) -> PluginResult<Box<dyn DynHash>>;
+    fn finalize(&mut self, out: &mut [u8]) -> PluginResult<()>;
+}
+
+impl<T: HashPlugin + 'static> DynHash for T {
+    fn output_size(&self) -> u32 {
+        HashPlugin::output_size(self)
+    }
+
+    fn block_size(&self) -> u32 {
+        HashPlugin::block_size(self)
+    }
+
+    fn update(&mut self, data: &[u8]) -> PluginResult<()> {
+        HashPlugin::update(self, data)
+    }
+
+    fn reset(&mut self) -> PluginResult<()> {
+        HashPlugin::reset(self)
+    }
+
+    fn try_clone(&self) -> PluginResult<Box<dyn DynHash>> {
+        Ok(Box::new(HashPlugin::try_clone(self)?))
+    }
+
+    fn finalize(&mut self, out: &mut [u8]) -> PluginResult<()> {
+        HashPlugin::finalize(self, out)
+    }
+}
+
+pub struct HashExport {
+    pub(crate) create: fn(&str, Opts<'_>) -> PluginResult<Box<dyn DynHash>>,
+}
+
+impl HashExport {
+    pub const fn of<T: HashPlugin + 'static>() -> Self {
+        Self {
+            create: |name, opts| Ok(Box::new(T::create_with_opts(name, opts)?)),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------
+// rng
+// ---------------------------------------------------------------------
+
+pub(crate) trait DynRng {
+    fn reseed(&mut self, data: &[u8]) -> PluginResult<()>;
+    fn add_entropy(&mut self, data: &[u8]) -> PluginResult<()>;
+    fn generate(&mut self, out: &mut [u8]) -> PluginResult<()>;
+}
+
+impl<T: RngPlugin + 'static> DynRng for T {
+    fn reseed(&mut self, data: &[u8]) -> PluginResult<()> {
+        RngPlugin::reseed(self, data)
+    }
+
+    fn add_entropy(&mut self, data: &[u8]) -> PluginResult<()> {
+        RngPlugin::add_entropy(self, data)
+    }
+
+    fn generate(&mut self, out: &mut [u8]) -> PluginResult<()> {
+        RngPlugin::generate(self, out)
+    }
+}
+
+pub struct RngExport {
+    pub(crate) create: fn(&str, Opts<'_>) -> PluginResult<Box<dyn DynRng>>,
+}
+
+impl RngExport {
+    pub const fn of<T: RngPlugin + 'static>() -> Self {
+        Self {
+            create: |name, opts| Ok(Box::new(T::create(name, opts)?)),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------
+// symmetric cipher
+// ---------------------------------------------------------------------
+
+pub(crate) trait DynCipher {
+    fn block_size(&self) -> u32;
+    fn key_size(&self) -> u32;
+    fn iv_size(&self) -> u32;
+    fn update(&mut self, input: &[u8], output: &mut [u8]) -> PluginResult<usize>;
+    fn finalize(&mut self, output: &mut [u8]) -> PluginResult<usize>;
+    fn reset(&mut self) -> PluginResult<()>;
+}
+
+impl<T: CipherPlugin + 'static> DynCipher for T {
+    fn block_size(&self) -> u32 {
+        CipherPlugin::block_size(self)
+    }
+
+    fn key_size(&self) -> u32 {
+        CipherPlugin::key_size(self)
+    }
+
+    fn iv_size(&self) -> u32 {
+        CipherPlugin::iv_size(self)
+    }
+
+    fn update(&mut self, input: &[u8], output: &mut [u8]) -> PluginResult<usize> {
+        CipherPlugin::update(self, input, output)
+    }
+
+    fn finalize(&mut self, output: &mut [u8]) -> PluginResult<usize> {
+        CipherPlugin::finalize(self, output)
+    }
+
+    fn reset(&mut self) -> PluginResult<()> {
+        CipherPlugin::reset(self)
+    }
+}
+
+pub struct CipherExport {
+    #[allow(clippy::type_complexity)]
+    pub(crate) create: fn(&str, &[u8], &[u8], Opts<'_>) -> PluginResult<Box<dyn DynCipher>>,
+}
+
+impl CipherExport {
+    pub const fn of<T: CipherPlugin + 'static>() -> Self {
+        Self {
+            create: |name, key, iv, opts| Ok(Box::new(T::create_with_key(name, key, iv, opts)?)),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------
+// aead
+// ---------------------------------------------------------------------
+
+pub(crate) trait DynAead {
+    fn set_nonce(&mut self, nonce: &[u8]) -> PluginResult<()>;
+    fn associated_data_update(&mut self, data: &[u8]) -> PluginResult<()>;
+    fn encrypt_update(&mut self, input: &[u8], output: &mut [u8]) -> PluginResult<usize>;
+    fn decrypt_update(&mut self, input: &[u8], output: &mut [u8]) -> PluginResult<usize>;
+    fn finalize(&mut self, tag: &mut [u8]) -> PluginResult<usize>;
+    fn verify_tag(&mut self, tag: &[u8]) -> PluginResult<()>;
+}
+
+impl<T: AeadPlugin + 'static> DynAead for T {
+    fn set_nonce(&mut self, nonce: &[u8]) -> PluginResult<()> {
+        AeadPlugin::set_nonce(self, nonce)
+    }
+
+    fn associated_data_update(&mut self, data: &[u8]) -> PluginResult<()> {
+        AeadPlugin::associated_data_update(self, data)
+    }
+
+    fn encrypt_update(&mut self, input: &[u8], output: &mut [u8]) -> PluginResult<usize> {
+        AeadPlugin::encrypt_update(self, input, output)
+    }
+
+    fn decrypt_update(&mut self, input: &[u8], output: &mut [u8]) -> PluginResult<usize> {
+        AeadPlugin::decrypt_update(self, input, output)
+    }
+
+    fn finalize(&mut self, tag: &mut [u8]) -> PluginResult<usize> {
+        AeadPlugin::finalize(self, tag)
+    }
+
+    fn verify_tag(&mut self, tag: &[u8]) -> PluginResult<()> {
+        AeadPlugin::verify_tag(self, tag)
+    }
+}
+
+type AeadCreate = fn(&str, &[u8], Opts<'_>) -> PluginResult<Box<dyn DynAead>>;
+
+pub struct AeadExport {
+    pub(crate) create: AeadCreate,
+}
+
+impl AeadExport {
+    pub const fn of<T: AeadPlugin + 'static>() -> Self {
+        Self {
+            create: |name, key, opts| Ok(Box::new(T::create_with_key(name, key, opts)?)),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------
+// kdf
+// ---------------------------------------------------------------------
+
+pub(crate) trait DynKdf {
+    fn set_salt(&mut self, salt: &[u8]) -> PluginResult<()>;
+    fn set_iterations(&mut self, iterations: u32) -> PluginResult<()>;
+    fn set_memory_cost(&mut self, bytes: u64) -> PluginResult<()>;
+    fn set_parallelism(&mut self, lanes: u32) -> PluginResult<()>;
+    fn set_hash(&mut self, hash_name: &str) -> PluginResult<()>;
+    fn derive(&mut self, input: &[u8], out: &mut [u8]) -> PluginResult<()>;
+}
+
+impl<T: KdfPlugin + 'static> DynKdf for T {
+    fn set_salt(&mut self, salt: &[u8]) -> PluginResult<()> {
+        KdfPlugin::set_salt(self, salt)
+    }
+
+    fn set_iterations(&mut self, iterations: u32) -> PluginResult<()> {
+        KdfPlugin::set_iterations(self, iterations)
+    }
+
+    fn set_memory_cost(&mut self, bytes: u64) -> PluginResult<()> {
+        KdfPlugin::set_memory_cost(self, bytes)
+    }
+
+    fn set_parallelism(&mut self, lanes: u32) -> PluginResult<()> {
+        KdfPlugin::set_parallelism(self, lanes)
+    }
+
+    fn set_hash(&mut self, hash_name: &str) -> PluginResult<()> {
+        KdfPlugin::set_hash(self, hash_name)
+    }
+
+    fn derive(&mut self, input: &[u8], out: &mut [u8]) -> PluginResult<()> {
+        KdfPlugin::derive(self, input, out)
+    }
+}
+
+pub struct KdfExport {
+    pub(crate) create: fn(&str, Opts<'_>) -> PluginResult<Box<dyn DynKdf>>,
+}
+
+impl KdfExport {
+    pub const fn of<T: KdfPlugin + 'static>() -> Self {
+        Self {
+            create: |name, opts| Ok(Box::new(T::create(name, opts)?)),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------
+// keyfmt
+// ---------------------------------------------------------------------
+
+pub(crate) trait DynKey {
+    fn serialize(&self, format: &str) -> PluginResult<Vec<u8>>;
+    fn kind(&self) -> PluginResult<KeyKind>;
+    fn algorithm(&self) -> PluginResult<String>;
+    fn public(&self) -> PluginResult<Box<dyn DynKey>>;
+}
+
+impl<T: KeyfmtPlugin + 'static> DynKey for T {
+    fn serialize(&self, format: &str) -> PluginResult<Vec<u8>> {
+        KeyfmtPlugin::serialize(self, format)
+    }
+
+    fn kind(&self) -> PluginResult<KeyKind> {
+        KeyfmtPlugin::kind(self)
+    }
+
+    fn algorithm(&self) -> PluginResult<String> {
+        KeyfmtPlugin::algorithm(self)
+    }
+
+    fn public(&self) -> PluginResult<Box<dyn DynKey>> {
+        Ok(Box::new(KeyfmtPlugin::public(self)?))
+    }
+}
+
+pub struct KeyfmtExport {
+    #[allow(clippy::type_complexity)]
+    pub(crate) parse: fn(&str, Option<&str>, &[u8], Opts<'_>) -> PluginResult<Box<dyn DynKey>>,
+}
+
+impl KeyfmtExport {
+    pub const fn of<T: KeyfmtPlugin + 'static>() -> Self {
+        Self {
+            parse: |format, hint, bytes, opts| Ok(Box::new(T::parse(format, hint, bytes, opts)?)),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------
+// signature
+// ---------------------------------------------------------------------
+
+/// A signer or a verifier: the trait uses one type for both.
+pub(crate) trait DynSignature {
+    fn set_hash(&mut self, hash_name: &str) -> PluginResult<()>;
+    fn update(&mut self, data: &[u8]) -> PluginResult<()>;
+    fn signer_finalize(&mut self, sig_out: &mut [u8]) -> PluginResult<usize>;
+    fn verifier_finalize(&mut self, signature: &[u8]) -> PluginResult<()>;
+}
+
+impl<T: SignaturePlugin + 'static> DynSignature for T {
+    fn set_hash(&mut self, hash_name: &str) -> PluginResult<()> {
+        SignaturePlugin::set_hash(self, hash_name)
+    }
+
+    fn update(&mut self, data: &[u8]) -> PluginResult<()> {
+        SignaturePlugin::update(self, data)
+    }
+
+    fn signer_finalize(&mut self, sig_out: &mut [u8]) -> PluginResult<usize> {
+        SignaturePlugin::signer_finalize(self, sig_out)
+    }
+
+    fn verifier_finalize(&mut self, signature: &[u8]) -> PluginResult<()> {
+        SignaturePlugin::verifier_finalize(self, signature)
+    }
+}
+
+type SignatureCreate = fn(&str, &[u8], Opts<'_>) -> PluginResult<Box<dyn DynSignature>>;
+
+pub struct SignatureExport {
+    pub(crate) signer_create: SignatureCreate,
+    pub(crate) verifier_create: SignatureCreate,
+    pub(crate) keypair_generate:
+        fn(&str, Option<&[u8]>, Opts<'_>) -> PluginResult<SignatureKeypair>,
+    /// Present for a version 1 registration.
+    pub(crate) handles: Option<SignatureHandleExport>,
+}
+
+pub(crate) struct SignatureHandleExport {
+    pub(crate) capabilities: fn(&str, &KeyHandle<'_>) -> HandleCapabilities,
+    #[allow(clippy::type_complexity)]
+    pub(crate) signer_create:
+        fn(&str, &KeyHandle<'_>, Opts<'_>) -> PluginResult<Box<dyn DynSignature>>,
+}
+
+impl SignatureExport {
+    pub const fn of<T: SignaturePlugin + 'static>() -> Self {
+        Self {
+            signer_create: |alg, key, opts| Ok(Box::new(T::signer_create(alg, key, opts)?)),
+            verifier_create: |alg, key, opts| Ok(Box::new(T::verifier_create(alg, key, opts)?)),
+            keypair_generate: T::keypair_generate,
+            handles: None,
+        }
+    }
+
+    pub const fn with_handles<T: SignatureHandlePlugin + 'static>() -> Self {
+        Self {
+            handles: Some(SignatureHandleExport {
+                capabilities: T::handle_capabilities,
+                signer_create: |alg, handle, opts| {
+                    Ok(Box::new(T::signer_create_with_handle(alg, handle, opts)?))
+                },
+            }),
+            ..Self::of::<T>()
+        }
+    }
+}
+
+// ---------------------------------------------------------------------
+// kem
+// ---------------------------------------------------------------------
+
+/// An encapsulator or a decapsulator: the trait uses one type for both.
+pub(crate) trait DynKem {
+    fn encapsulate(
+        &mut self,
+        ct_out: &mut [u8],
+        ss_out: &mut [u8],
+    ) -> PluginResult<KemEncapsulateResult>;
+    fn decapsulate(&mut self, ciphertext: &[u8], ss_out: &mut [u8]) -> PluginResult<usize>;
+}
+
+impl<T: KemPlugin + 'static> DynKem for T {
+    fn encapsulate(
+        &mut self,
+        ct_out: &mut [u8],
+        ss_out: &mut [u8],
+    ) -> PluginResult<KemEncapsulateResult> {
+        KemPlugin::encapsulate(self, ct_out, ss_out)
+    }
+
+    fn decapsulate(&mut self, ciphertext: &[u8], ss_out: &mut [u8]) -> PluginResult<usize> {
+        KemPlugin::decapsulate(self, ciphertext, ss_out)
+    }
+}
+
+type KemCreate = fn(&str, &[u8], Opts<'_>) -> PluginResult<Box<dyn DynKem>>;
+
+pub struct KemExport {
+    pub(crate) encapsulator_create: KemCreate,
+    pub(crate) decapsulator_create: KemCreate,
+    pub(crate) shared_secret_size: fn(&str) -> PluginResult<u32>,
+    pub(crate) keypair_generate: fn(&str, Option<&[u8]>, Opts<'_>) -> PluginResult<KemKeypair>,
+    /// Present for a version 1 registration.
+    pub(crate) handles: Option<KemHandleExport>,
+}
+
+pub(crate) struct KemHandleExport {
+    pub(crate) capabilities: fn(&str, &KeyHandle<'_>) -> HandleCapabilities,
+    #[allow(clippy::type_complexity)]
+    pub(crate) decapsulator_create:
+        fn(&str, &KeyHandle<'_>, Opts<'_>) -> PluginResult<Box<dyn DynKem>>,
+}
+
+impl KemExport {
+    pub const fn of<T: KemPlugin + 'static>() -> Self {
+        Self {
+            encapsulator_create: |alg, key, opts| {
+                Ok(Box::new(T::encapsulator_create(alg, key, opts)?))
+            },
+            decapsulator_create: |alg, key, opts| {
+                Ok(Box::new(T::decapsulator_create(alg, key, opts)?))
+            },
+            shared_secret_size: T::shared_secret_size,
+            keypair_generate: T::keypair_generate,
+            handles: None,
+        }
+    }
+
+    pub const fn with_handles<T: KemHandlePlugin + 'static>() -> Self {
+        Self {
+            handles: Some(KemHandleExport {
+                capabilities: T::handle_capabilities,
+                decapsulator_create: |alg, handle, opts| {
+                    Ok(Box::new(T::decapsulator_create_with_handle(
+                        alg, handle, opts,
+                    )?))
+                },
+            }),
+            ..Self::of::<T>()
+        }
+    }
+}
@@ -0,0 +1,58 @@
+//! Guest-side SDK for Confium crypto plugins built as WebAssembly
+//! components.
+//!
+//! A WASM plugin is written against the same `confium_api::plugin`
+//! traits as a native one. The two attributes re-exported here take the
+//! place of the `confium_api` ones:
+//!
+//! - [`macro@plugin_interface`] (`name = "...", version = N`, the same
+//!   arguments as the native macro) registers the implementing type
+//!   with this crate instead of emitting `cfmp_*` symbols.
+//! - [`macro@export`] emits the exports of the `confium:plugin/plugin`
+//!   world (`confium-sandbox-wasm/wit/plugin.wit`), backed by
+//!   [`Component`].
+//!
+//! [`Component`] answers every interface of the world; the ones no
+//! type was registered for answer `PLUGIN_MISSING_INTERFACE`, and
+//! `lifecycle.interfaces` lists only the registered ones, so the host
+//! never proxies them.
+//!
+//! Registration goes through `inventory`. On WebAssembly its
+//! constructors run from `__wasm_call_ctors`, which the generated
+//! exports call before the first entry into the plugin.
+//!
+//! ## Surfaces
+//!
+//! - [`bindings`] — the `wit-bindgen` output for the plugin world.
+//! - [`Component`] — the world's implementation.
+//! - [`Registration`] / [`Exports`] and the `*Export` tables (hidden)
+//!   — what the interface macro submits.
+
+mod component;
+mod exports;
+
+/// Bindings for the `confium:plugin/plugin` world.
+#[allow(missing_docs)]
+pub mod bindings {
+    wit_bindgen::generate!({
+        path: "../confium-sandbox-wasm/wit",
+        world: "plugin",
+        pub_export_macro: true,
+        export_macro_name: "export_plugin",
+        default_bindings_module: "confium_wasm_guest::bindings",
+    });
+}
+
+pub use component::Component;
+#[doc(hidden)]
+pub use exports::{
+    AeadExport, CipherExport, Exports, HashExport, KdfExport, KemExport, KeyfmtExport,
+    Registration, RngExport, SignatureExport,
+};
+
+/// Re-export for the interface macro's registrations.
+#[doc(hidden)]
+pub use inventory;
+
+pub use confium_macros::wasm_export as export;
+pub use confium_macros::wasm_plugin_interface as plugin_interface;